@@ -0,0 +1,60 @@
+//! Exposure aggregation: in-memory matrix vs streaming accumulation.
+//!
+//! `in_memory` materialises every scenario and computes EE, ENE, EPE and
+//! 95% PFE with `ExposureCalculator`; `streaming` generates the same
+//! scenarios block by block into t-digest sketches, trading exact
+//! quantiles for bounded memory.
+
+use benchmarks::fixtures::{exposure_scenarios, scenario, time_grid};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use pricer_risk::exposure::ExposureCalculator;
+
+const N_TIMES: usize = 52;
+
+fn bench_exposure_aggregation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("exposure_aggregation");
+    group.sample_size(20);
+
+    let grid = time_grid(N_TIMES, 5.0);
+    for n_scenarios in [1_000, 10_000, 50_000] {
+        group.throughput(Throughput::Elements((n_scenarios * N_TIMES) as u64));
+
+        group.bench_with_input(
+            BenchmarkId::new("in_memory", n_scenarios),
+            &n_scenarios,
+            |b, &n| {
+                b.iter(|| {
+                    let values = exposure_scenarios(n, N_TIMES);
+                    let ee = ExposureCalculator::expected_exposure(&values);
+                    let ene = ExposureCalculator::expected_negative_exposure(&values);
+                    let pfe = ExposureCalculator::potential_future_exposure(&values, 0.95);
+                    let epe = ExposureCalculator::expected_positive_exposure(&ee, &grid);
+                    black_box((ene, pfe, epe))
+                });
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("streaming", n_scenarios),
+            &n_scenarios,
+            |b, &n| {
+                b.iter(|| {
+                    let mut stream =
+                        ExposureCalculator::stream_exposure(N_TIMES, n, 1_000, 100.0, |range| {
+                            range.map(|s| scenario(s, N_TIMES)).collect()
+                        });
+                    let ee = stream.expected_exposure();
+                    let ene = stream.expected_negative_exposure();
+                    let pfe = stream.potential_future_exposure(0.95);
+                    let epe = ExposureCalculator::expected_positive_exposure(&ee, &grid);
+                    black_box((ene, pfe, epe))
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_exposure_aggregation);
+criterion_main!(benches);
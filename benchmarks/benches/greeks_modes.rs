@@ -0,0 +1,65 @@
+//! Monte Carlo Greeks: bump-and-revalue vs adjoint.
+//!
+//! All modes price the same seeded European call and return Delta, Gamma,
+//! Vega, Rho and Theta:
+//! - `bump`: `MonteCarloPricer::price_with_greeks`, one repricing per bump
+//! - `finite_difference`: the AD entry point forced to finite differences
+//! - `adjoint`: reverse mode over the simulated paths in one sweep
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use pricer_pricing::enzyme::greeks::{GreeksEnzyme, GreeksMode};
+use pricer_pricing::mc::{GbmParams, Greek, MonteCarloConfig, MonteCarloPricer, PayoffParams};
+
+const GREEKS: [Greek; 5] = [
+    Greek::Delta,
+    Greek::Gamma,
+    Greek::Vega,
+    Greek::Rho,
+    Greek::Theta,
+];
+
+fn pricer(n_paths: usize) -> MonteCarloPricer {
+    let config = MonteCarloConfig::builder()
+        .n_paths(n_paths)
+        .n_steps(52)
+        .seed(42)
+        .build()
+        .expect("valid benchmark configuration");
+    MonteCarloPricer::new(config).expect("valid pricer")
+}
+
+fn bench_greeks_modes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("greeks_modes");
+    group.sample_size(10);
+
+    let gbm = GbmParams::default();
+    let payoff = PayoffParams::call(100.0);
+    let df = (-gbm.rate * gbm.maturity).exp();
+
+    for n_paths in [1_000, 10_000] {
+        group.bench_with_input(BenchmarkId::new("bump", n_paths), &n_paths, |b, &n| {
+            let mut pricer = pricer(n);
+            b.iter(|| {
+                pricer.reset();
+                pricer.price_with_greeks(black_box(gbm), black_box(payoff), df, &GREEKS)
+            });
+        });
+        for (name, mode) in [
+            ("finite_difference", GreeksMode::FiniteDifference),
+            ("adjoint", GreeksMode::ReverseMode),
+        ] {
+            group.bench_with_input(BenchmarkId::new(name, n_paths), &n_paths, |b, &n| {
+                let mut pricer = pricer(n);
+                b.iter(|| {
+                    pricer.reset();
+                    pricer.price_with_enzyme_greeks(black_box(gbm), black_box(payoff), df, mode)
+                });
+            });
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_greeks_modes);
+criterion_main!(benches);
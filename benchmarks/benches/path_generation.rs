@@ -0,0 +1,56 @@
+//! GBM path generation throughput: scalar vs dispatched kernel.
+//!
+//! Without `--features simd` both entries run the scalar kernel, so the
+//! pair doubles as a dispatch-overhead check; with it, `dispatched` uses
+//! the vector kernel for the detected CPU. Throughput is reported in path
+//! steps per second.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use pricer_pricing::mc::{generate_gbm_paths, generate_gbm_paths_scalar, GbmParams, PathWorkspace};
+use pricer_pricing::rng::PricerRng;
+
+fn bench_path_generation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("path_generation");
+    group.sample_size(20);
+
+    let gbm = GbmParams::default();
+    for (n_paths, n_steps) in [(1_000, 52), (10_000, 52), (10_000, 252)] {
+        let label = format!("{}x{}", n_paths, n_steps);
+        let dt = gbm.maturity / n_steps as f64;
+        let drift_dt = (gbm.rate - 0.5 * gbm.volatility * gbm.volatility) * dt;
+        let vol_sqrt_dt = gbm.volatility * dt.sqrt();
+
+        let mut workspace = PathWorkspace::new(n_paths, n_steps);
+        workspace.ensure_capacity(n_paths, n_steps);
+        PricerRng::from_seed(42).fill_normal(workspace.randoms_mut());
+        let randoms = workspace.randoms().to_vec();
+        let mut paths = vec![0.0; n_paths * (n_steps + 1)];
+
+        group.throughput(Throughput::Elements((n_paths * n_steps) as u64));
+        group.bench_function(BenchmarkId::new("scalar", &label), |b| {
+            b.iter(|| {
+                generate_gbm_paths_scalar(
+                    &mut paths,
+                    &randoms,
+                    gbm.spot,
+                    drift_dt,
+                    vol_sqrt_dt,
+                    n_paths,
+                    n_steps,
+                );
+                black_box(&paths);
+            })
+        });
+        group.bench_function(BenchmarkId::new("dispatched", &label), |b| {
+            b.iter(|| {
+                generate_gbm_paths(&mut workspace, gbm, n_paths, n_steps);
+                black_box(workspace.paths());
+            })
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_path_generation);
+criterion_main!(benches);
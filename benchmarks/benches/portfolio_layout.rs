@@ -0,0 +1,66 @@
+//! Portfolio layout: structure-of-arrays vs array-of-structs payoffs.
+//!
+//! `aos` walks `Vec<Trade>` and evaluates each instrument's payoff through
+//! the `Instrument` enum; `soa` evaluates the same European vanillas from
+//! the contiguous strike/notional/sign columns of `TradeSoA`, sequentially
+//! and with Rayon. `black_scholes` and `black_scholes_parallel` price the
+//! same columns with the SoA Black-Scholes kernel, including all Greeks.
+
+use benchmarks::fixtures::{spots, vanilla_trades};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use pricer_risk::soa::TradeSoA;
+
+fn bench_portfolio_layout(c: &mut Criterion) {
+    let mut group = c.benchmark_group("portfolio_layout");
+
+    for n_trades in [1_000, 10_000, 100_000] {
+        let trades = vanilla_trades(n_trades, 64);
+        let refs: Vec<_> = trades.iter().collect();
+        let soa = TradeSoA::from_trades(&refs);
+        let priced = soa.clone().with_flat_volatility(0.2);
+        let spots = spots(n_trades);
+        let mut payoffs = vec![0.0; n_trades];
+
+        group.throughput(Throughput::Elements(n_trades as u64));
+        group.bench_with_input(BenchmarkId::new("aos", n_trades), &n_trades, |b, _| {
+            b.iter(|| {
+                for ((payoff, trade), &spot) in payoffs.iter_mut().zip(&trades).zip(&spots) {
+                    *payoff = trade.payoff(spot);
+                }
+                black_box(&payoffs);
+            });
+        });
+        group.bench_with_input(BenchmarkId::new("soa", n_trades), &n_trades, |b, _| {
+            b.iter(|| {
+                soa.compute_payoffs(black_box(&spots), &mut payoffs);
+                black_box(&payoffs);
+            });
+        });
+        group.bench_with_input(
+            BenchmarkId::new("soa_parallel", n_trades),
+            &n_trades,
+            |b, _| {
+                b.iter(|| black_box(soa.compute_payoffs_par(black_box(&spots))));
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("black_scholes", n_trades),
+            &n_trades,
+            |b, _| {
+                b.iter(|| black_box(priced.price_black_scholes(black_box(&spots), 0.03)));
+            },
+        );
+        group.bench_with_input(
+            BenchmarkId::new("black_scholes_parallel", n_trades),
+            &n_trades,
+            |b, _| {
+                b.iter(|| black_box(priced.price_black_scholes_par(black_box(&spots), 0.03)));
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_portfolio_layout);
+criterion_main!(benches);
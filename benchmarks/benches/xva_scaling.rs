@@ -0,0 +1,70 @@
+//! Portfolio XVA parallel scaling over 1–64 Rayon threads.
+//!
+//! Each run computes CVA, DVA and FVA for 2,048 netting sets on a weekly
+//! five-year grid inside a dedicated thread pool. Thread counts above the
+//! machine's core count are still measured, so oversubscription overhead
+//! shows up in the report rather than being hidden.
+
+use std::collections::HashMap;
+
+use benchmarks::fixtures::{scenario, time_grid, vanilla_portfolio};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use pricer_risk::xva::{
+    generate_flat_discount_factors, FundingParams, OwnCreditParams, XvaCalculator,
+};
+
+const N_NETTING_SETS: usize = 2_048;
+const N_TIMES: usize = 261;
+
+fn bench_xva_scaling(c: &mut Criterion) {
+    let mut group = c.benchmark_group("xva_scaling");
+    group.sample_size(20);
+
+    let portfolio = vanilla_portfolio(N_NETTING_SETS * 4, N_NETTING_SETS);
+    let grid = time_grid(N_TIMES, 5.0);
+    let dfs = generate_flat_discount_factors(0.03, &grid);
+    let mut ee_profiles = HashMap::new();
+    let mut ene_profiles = HashMap::new();
+    for (i, ns) in portfolio.netting_sets().enumerate() {
+        let values = scenario(i, N_TIMES);
+        ee_profiles.insert(ns.id().clone(), values.iter().map(|v| v.max(0.0)).collect());
+        ene_profiles.insert(
+            ns.id().clone(),
+            values.iter().map(|v| (-v).max(0.0)).collect(),
+        );
+    }
+    let calculator = XvaCalculator::new()
+        .with_own_credit(OwnCreditParams::new(0.01, 0.6).expect("valid own credit"))
+        .with_funding(FundingParams::from_bps(50.0, 30.0));
+
+    for n_threads in [1, 2, 4, 8, 16, 32, 64] {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(n_threads)
+            .build()
+            .expect("thread pool");
+        group.bench_with_input(
+            BenchmarkId::new("threads", n_threads),
+            &n_threads,
+            |b, _| {
+                b.iter(|| {
+                    pool.install(|| {
+                        calculator
+                            .compute_portfolio_xva(
+                                black_box(&portfolio),
+                                &ee_profiles,
+                                &ene_profiles,
+                                &grid,
+                                &dfs,
+                            )
+                            .expect("consistent XVA inputs")
+                    })
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_xva_scaling);
+criterion_main!(benches);
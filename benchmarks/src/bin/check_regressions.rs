@@ -0,0 +1,42 @@
+//! Fails when Criterion benchmarks slowed down beyond their thresholds.
+//!
+//! Usage: `check_regressions [CRITERION_DIR] [THRESHOLDS]`, defaulting to
+//! `target/criterion` and `benchmarks/thresholds.toml` relative to the
+//! workspace root.
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use benchmarks::Thresholds;
+
+fn main() -> ExitCode {
+    let workspace = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..");
+    let mut args = std::env::args().skip(1);
+    let criterion_dir = args
+        .next()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| workspace.join("target").join("criterion"));
+    let thresholds_path = args
+        .next()
+        .map(PathBuf::from)
+        .unwrap_or_else(|| workspace.join("benchmarks").join("thresholds.toml"));
+
+    let result = Thresholds::load(&thresholds_path).and_then(|t| t.check(&criterion_dir));
+    match result {
+        Ok(regressions) if regressions.is_empty() => {
+            println!("No benchmark regressions beyond thresholds");
+            ExitCode::SUCCESS
+        }
+        Ok(regressions) => {
+            eprintln!("{} benchmark regression(s):", regressions.len());
+            for regression in &regressions {
+                eprintln!("  {}", regression);
+            }
+            ExitCode::FAILURE
+        }
+        Err(e) => {
+            eprintln!("check_regressions: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! Deterministic inputs shared by the benchmarks.
+//!
+//! Fixtures avoid random number generators so every run, on every machine,
+//! measures the same work.
+
+use pricer_core::types::Currency;
+use pricer_models::instruments::{
+    ExerciseStyle, Instrument, InstrumentParams, PayoffType, VanillaOption,
+};
+use pricer_risk::portfolio::{
+    Counterparty, CounterpartyId, CreditParams, NettingSet, NettingSetId, Portfolio,
+    PortfolioBuilder, Trade, TradeId,
+};
+
+/// Returns `n_times` equally spaced times from 0 to `maturity`.
+pub fn time_grid(n_times: usize, maturity: f64) -> Vec<f64> {
+    let last = n_times.saturating_sub(1).max(1) as f64;
+    (0..n_times).map(|i| maturity * i as f64 / last).collect()
+}
+
+/// Returns synthetic netted values `[scenario][time]`: a hump-shaped
+/// profile with deterministic scenario noise, positive and negative.
+pub fn exposure_scenarios(n_scenarios: usize, n_times: usize) -> Vec<Vec<f64>> {
+    (0..n_scenarios).map(|s| scenario(s, n_times)).collect()
+}
+
+/// Returns the values of one synthetic scenario.
+pub fn scenario(s: usize, n_times: usize) -> Vec<f64> {
+    let mid = (n_times as f64 / 2.0).max(1.0);
+    (0..n_times)
+        .map(|t| {
+            let base = 100.0 * (1.0 - (t as f64 - mid).abs() / mid);
+            base + (((s * 17 + t * 13) % 100) as f64 - 50.0) * 3.0
+        })
+        .collect()
+}
+
+/// Builds a portfolio of European vanillas spread round-robin over
+/// `n_netting_sets` netting sets, each with its own counterparty.
+///
+/// Strikes cycle through 80–120, expiries through 0.5–5 years, and calls
+/// alternate with puts.
+pub fn vanilla_portfolio(n_trades: usize, n_netting_sets: usize) -> Portfolio {
+    let n_netting_sets = n_netting_sets.max(1);
+    let trades = vanilla_trades(n_trades, n_netting_sets);
+
+    let mut builder = PortfolioBuilder::new();
+    for ns in 0..n_netting_sets {
+        let hazard = 0.005 + 0.001 * (ns % 20) as f64;
+        let credit = CreditParams::new(hazard, 0.6).expect("valid credit parameters");
+        let mut netting_set = NettingSet::new(netting_set_id(ns), counterparty_id(ns));
+        netting_set.add_trades(
+            trades
+                .iter()
+                .skip(ns)
+                .step_by(n_netting_sets)
+                .map(|t| t.id().clone()),
+        );
+        builder = builder
+            .add_counterparty(Counterparty::new(counterparty_id(ns), credit))
+            .add_netting_set(netting_set);
+    }
+    builder
+        .add_trades(trades)
+        .build()
+        .expect("fixture portfolio is consistent")
+}
+
+/// Builds the trades of [`vanilla_portfolio`] without the portfolio.
+pub fn vanilla_trades(n_trades: usize, n_netting_sets: usize) -> Vec<Trade> {
+    let n_netting_sets = n_netting_sets.max(1);
+    (0..n_trades)
+        .map(|i| {
+            let strike = 80.0 + (i % 41) as f64;
+            let expiry = 0.5 + 0.5 * (i % 10) as f64;
+            let payoff = if i % 2 == 0 {
+                PayoffType::Call
+            } else {
+                PayoffType::Put
+            };
+            let params = InstrumentParams::new(strike, expiry, 1.0).expect("valid fixture trade");
+            let option = VanillaOption::new(params, payoff, ExerciseStyle::European, 1e-6);
+            let ns = i % n_netting_sets;
+            Trade::new(
+                TradeId::new(format!("T{:06}", i)),
+                Instrument::Vanilla(option),
+                Currency::USD,
+                counterparty_id(ns),
+                netting_set_id(ns),
+                1_000.0 + (i % 7) as f64 * 250.0,
+            )
+        })
+        .collect()
+}
+
+/// Returns one terminal spot per trade, cycling through 70–130.
+pub fn spots(n: usize) -> Vec<f64> {
+    (0..n).map(|i| 70.0 + (i * 7 % 61) as f64).collect()
+}
+
+fn netting_set_id(index: usize) -> NettingSetId {
+    NettingSetId::new(format!("NS{:04}", index))
+}
+
+fn counterparty_id(index: usize) -> CounterpartyId {
+    CounterpartyId::new(format!("CP{:04}", index))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_grid() {
+        let grid = time_grid(5, 2.0);
+        assert_eq!(grid, vec![0.0, 0.5, 1.0, 1.5, 2.0]);
+        assert_eq!(time_grid(1, 2.0), vec![0.0]);
+    }
+
+    #[test]
+    fn test_scenarios_have_both_signs() {
+        let values = exposure_scenarios(50, 20);
+        assert_eq!(values.len(), 50);
+        assert!(values.iter().flatten().any(|&v| v > 0.0));
+        assert!(values.iter().flatten().any(|&v| v < 0.0));
+        assert_eq!(values, exposure_scenarios(50, 20));
+    }
+
+    #[test]
+    fn test_vanilla_portfolio_links_netting_sets() {
+        let portfolio = vanilla_portfolio(100, 8);
+        assert_eq!(portfolio.trade_count(), 100);
+        assert_eq!(portfolio.netting_set_count(), 8);
+        assert_eq!(portfolio.counterparty_count(), 8);
+        let linked: usize = portfolio.netting_sets().map(|ns| ns.trade_count()).sum();
+        assert_eq!(linked, 100);
+    }
+}
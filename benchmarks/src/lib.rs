@@ -0,0 +1,32 @@
+//! # Benchmarks
+//!
+//! Cross-layer Criterion benchmarks for neutryx-rust and the thresholds
+//! that gate performance regressions in CI.
+//!
+//! ## Benchmark Groups
+//!
+//! | Bench | Compares |
+//! |-------|----------|
+//! | `path_generation` | Scalar vs dispatched (SIMD with `--features simd`) GBM kernels |
+//! | `greeks_modes` | Bump-and-revalue vs adjoint Monte Carlo Greeks |
+//! | `exposure_aggregation` | In-memory vs streaming EE/EPE/PFE |
+//! | `xva_scaling` | Portfolio XVA on 1–64 Rayon threads |
+//! | `portfolio_layout` | Structure-of-arrays vs array-of-structs payoffs |
+//!
+//! ## Usage
+//!
+//! ```bash
+//! cargo bench -p benchmarks -- --noplot
+//! cargo run -p benchmarks --bin check_regressions
+//! ```
+//!
+//! Criterion compares each run with the previous one in `target/criterion`;
+//! `check_regressions` reads those comparisons and fails when a mean slows
+//! down by more than the limit in `benchmarks/thresholds.toml`.
+
+#![deny(missing_docs)]
+
+pub mod fixtures;
+pub mod thresholds;
+
+pub use thresholds::{Regression, ThresholdError, Thresholds};
@@ -0,0 +1,325 @@
+//! Performance regression thresholds over Criterion output.
+//!
+//! After a `cargo bench` run that follows an earlier one, Criterion writes
+//! for each benchmark `new/benchmark.json` (holding the full benchmark id)
+//! and `change/estimates.json` (holding the relative change of the mean).
+//! [`Thresholds::check`] walks that tree and reports every benchmark whose
+//! mean slowed down by more than its limit.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use thiserror::Error;
+
+/// Errors from loading thresholds or Criterion output.
+#[derive(Debug, Error)]
+pub enum ThresholdError {
+    /// File could not be read.
+    #[error("Cannot read {path}: {source}")]
+    Io {
+        /// Offending path
+        path: PathBuf,
+        /// Underlying error
+        source: std::io::Error,
+    },
+
+    /// Thresholds file is not valid TOML or has the wrong shape.
+    #[error("Invalid thresholds: {0}")]
+    Toml(#[from] toml::de::Error),
+
+    /// Criterion JSON file has an unexpected shape.
+    #[error("Invalid Criterion output {path}: {source}")]
+    Json {
+        /// Offending path
+        path: PathBuf,
+        /// Underlying error
+        source: serde_json::Error,
+    },
+
+    /// A limit is negative or not finite.
+    #[error("Invalid limit {value} for '{name}'")]
+    InvalidLimit {
+        /// Benchmark prefix, or `default`
+        name: String,
+        /// Offending value
+        value: f64,
+    },
+}
+
+/// Maximum allowed slowdown per benchmark-id prefix.
+///
+/// # Examples
+///
+/// ```
+/// use benchmarks::Thresholds;
+///
+/// let thresholds = Thresholds::from_toml_str(r#"
+///     default = 0.10
+///     [benchmarks]
+///     "xva_scaling" = 0.20
+///     "xva_scaling/threads/64" = 0.50
+/// "#).unwrap();
+///
+/// assert_eq!(thresholds.limit_for("greeks_modes/adjoint"), 0.10);
+/// assert_eq!(thresholds.limit_for("xva_scaling/threads/4"), 0.20);
+/// assert_eq!(thresholds.limit_for("xva_scaling/threads/64"), 0.50);
+/// ```
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct Thresholds {
+    default: f64,
+    #[serde(default)]
+    benchmarks: BTreeMap<String, f64>,
+}
+
+/// A benchmark that slowed down beyond its threshold.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Regression {
+    /// Criterion benchmark id, e.g. `xva_scaling/threads/8`
+    pub id: String,
+    /// Relative change of the mean time (0.12 = 12% slower)
+    pub change: f64,
+    /// Allowed relative change
+    pub limit: f64,
+}
+
+impl fmt::Display for Regression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: mean {:+.1}% (limit +{:.1}%)",
+            self.id,
+            self.change * 100.0,
+            self.limit * 100.0
+        )
+    }
+}
+
+#[derive(Deserialize)]
+struct BenchmarkId {
+    full_id: String,
+}
+
+#[derive(Deserialize)]
+struct ChangeEstimates {
+    mean: PointEstimate,
+}
+
+#[derive(Deserialize)]
+struct PointEstimate {
+    point_estimate: f64,
+}
+
+impl Thresholds {
+    /// Parses thresholds from TOML with a top-level `default` and an
+    /// optional `[benchmarks]` table of prefix limits.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ThresholdError::Toml` for malformed input and
+    /// `ThresholdError::InvalidLimit` for negative or non-finite limits.
+    pub fn from_toml_str(text: &str) -> Result<Self, ThresholdError> {
+        let thresholds: Self = toml::from_str(text)?;
+        let limits = std::iter::once(("default", thresholds.default)).chain(
+            thresholds
+                .benchmarks
+                .iter()
+                .map(|(name, &value)| (name.as_str(), value)),
+        );
+        for (name, value) in limits {
+            if !(value >= 0.0 && value.is_finite()) {
+                return Err(ThresholdError::InvalidLimit {
+                    name: name.to_string(),
+                    value,
+                });
+            }
+        }
+        Ok(thresholds)
+    }
+
+    /// Loads thresholds from a TOML file.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ThresholdError` if the file cannot be read or parsed.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ThresholdError> {
+        Self::from_toml_str(&read(path.as_ref())?)
+    }
+
+    /// Returns the limit for `id`: the longest matching prefix, or the
+    /// default.
+    pub fn limit_for(&self, id: &str) -> f64 {
+        self.benchmarks
+            .iter()
+            .filter(|(prefix, _)| id.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map_or(self.default, |(_, &limit)| limit)
+    }
+
+    /// Scans a Criterion output directory and returns the benchmarks whose
+    /// mean slowed down beyond their limit, sorted by id.
+    ///
+    /// Benchmarks without a comparison (first run) are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ThresholdError` if a Criterion file cannot be read or
+    /// parsed.
+    pub fn check(
+        &self,
+        criterion_dir: impl AsRef<Path>,
+    ) -> Result<Vec<Regression>, ThresholdError> {
+        let mut regressions: Vec<Regression> = changes(criterion_dir.as_ref())?
+            .into_iter()
+            .filter_map(|(id, change)| {
+                let limit = self.limit_for(&id);
+                (change > limit || change.is_nan()).then_some(Regression { id, change, limit })
+            })
+            .collect();
+        regressions.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(regressions)
+    }
+}
+
+/// Collects `(full_id, mean change)` for every benchmark directory below
+/// `dir` that holds both `new/benchmark.json` and `change/estimates.json`.
+fn changes(dir: &Path) -> Result<Vec<(String, f64)>, ThresholdError> {
+    let mut found = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let entries = fs::read_dir(&current).map_err(|source| ThresholdError::Io {
+            path: current.clone(),
+            source,
+        })?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !path.is_dir() {
+                continue;
+            }
+            let benchmark = path.join("new").join("benchmark.json");
+            let change = path.join("change").join("estimates.json");
+            if benchmark.is_file() && change.is_file() {
+                let id: BenchmarkId = parse_json(&benchmark)?;
+                let estimates: ChangeEstimates = parse_json(&change)?;
+                found.push((id.full_id, estimates.mean.point_estimate));
+            } else {
+                pending.push(path);
+            }
+        }
+    }
+    Ok(found)
+}
+
+fn read(path: &Path) -> Result<String, ThresholdError> {
+    fs::read_to_string(path).map_err(|source| ThresholdError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+fn parse_json<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T, ThresholdError> {
+    serde_json::from_str(&read(path)?).map_err(|source| ThresholdError::Json {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_benchmark(root: &Path, dir: &str, id: &str, change: Option<f64>) {
+        let path = root.join(dir);
+        fs::create_dir_all(path.join("new")).unwrap();
+        fs::write(
+            path.join("new").join("benchmark.json"),
+            format!(
+                r#"{{"group_id":"g","full_id":"{}","directory_name":"{}"}}"#,
+                id, dir
+            ),
+        )
+        .unwrap();
+        if let Some(change) = change {
+            fs::create_dir_all(path.join("change")).unwrap();
+            fs::write(
+                path.join("change").join("estimates.json"),
+                format!(
+                    r#"{{"mean":{{"confidence_interval":{{"confidence_level":0.95,"lower_bound":0.0,"upper_bound":0.0}},"point_estimate":{},"standard_error":0.01}}}}"#,
+                    change
+                ),
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_check_reports_regressions() {
+        let root = std::env::temp_dir().join(format!("neutryx-thresholds-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        write_benchmark(
+            &root,
+            "xva_scaling/threads/8",
+            "xva_scaling/threads/8",
+            Some(0.15),
+        );
+        write_benchmark(
+            &root,
+            "xva_scaling/threads/64",
+            "xva_scaling/threads/64",
+            Some(0.3),
+        );
+        write_benchmark(&root, "greeks_modes/bump", "greeks_modes/bump", Some(0.12));
+        write_benchmark(
+            &root,
+            "greeks_modes/adjoint",
+            "greeks_modes/adjoint",
+            Some(-0.4),
+        );
+        write_benchmark(&root, "first_run/x", "first_run/x", None);
+
+        let thresholds =
+            Thresholds::from_toml_str("default = 0.10\n[benchmarks]\n\"xva_scaling\" = 0.20\n")
+                .unwrap();
+        let regressions = thresholds.check(&root).unwrap();
+        fs::remove_dir_all(&root).unwrap();
+
+        let ids: Vec<&str> = regressions.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["greeks_modes/bump", "xva_scaling/threads/64"]);
+        assert_eq!(regressions[1].limit, 0.20);
+        assert_eq!(
+            regressions[0].to_string(),
+            "greeks_modes/bump: mean +12.0% (limit +10.0%)"
+        );
+    }
+
+    #[test]
+    fn test_invalid_thresholds() {
+        assert!(matches!(
+            Thresholds::from_toml_str("default = -0.1"),
+            Err(ThresholdError::InvalidLimit { .. })
+        ));
+        assert!(matches!(
+            Thresholds::from_toml_str("default = 0.1\n[benchmarks]\n\"a\" = nan\n"),
+            Err(ThresholdError::InvalidLimit { .. })
+        ));
+        assert!(matches!(
+            Thresholds::from_toml_str("[benchmarks]\n"),
+            Err(ThresholdError::Toml(_))
+        ));
+        assert!(Thresholds::check(
+            &Thresholds::from_toml_str("default = 0.1").unwrap(),
+            "/nonexistent/criterion"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_repository_thresholds_parse() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/thresholds.toml");
+        let thresholds = Thresholds::load(path).unwrap();
+        assert_eq!(thresholds.limit_for("xva_scaling/threads/4"), 0.20);
+        assert_eq!(thresholds.limit_for("unknown/bench"), 0.10);
+    }
+}
@@ -0,0 +1,635 @@
+//! Tick capture files.
+//!
+//! Records a market data stream to tick files and reads it back for replay
+//! or post-mortem analysis. Each line after the header holds one quote:
+//!
+//! ```text
+//! timestamp_ms,identifier,bid,ask,last,currency
+//! 1700000000000,USD.CURVE,,,0.051,USD
+//! 1700000000250,AAPL,150.25,150.30,,USD
+//! ```
+//!
+//! Empty price fields are absent prices. Quotes are written in arrival
+//! order; readers reject files whose timestamps go backwards. Files ending
+//! in `.gz` are gzip-compressed, and readers detect compression from the
+//! file contents.
+//!
+//! [`TickCapture`] records everything a feed delivers into a directory of
+//! rotating, timestamped files; [`read_capture`] reads such a directory (or
+//! a single file) back in order.
+
+use crate::quote::MarketQuote;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use pricer_core::types::Currency;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+
+/// Header line of a tick file
+pub const TICK_FILE_HEADER: &str = "timestamp_ms,identifier,bid,ask,last,currency";
+
+/// Extension of uncompressed tick files
+pub const TICK_FILE_EXTENSION: &str = "ticks";
+
+/// Extension of compressed tick files
+pub const COMPRESSED_TICK_FILE_EXTENSION: &str = "ticks.gz";
+
+/// Leading bytes of a gzip stream
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Errors reading or writing tick files.
+#[derive(Error, Debug)]
+pub enum CaptureError {
+    /// IO error
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Header line missing or not a tick file header
+    #[error("Invalid tick file header: {0}")]
+    InvalidHeader(String),
+
+    /// Malformed tick line
+    #[error("Invalid tick on line {line}: {message}")]
+    InvalidTick { line: usize, message: String },
+
+    /// Quote cannot be written without corrupting the file
+    #[error("Cannot record quote '{0}': identifier contains a separator")]
+    UnrecordableQuote(String),
+
+    /// Capture directory holds no tick files
+    #[error("No tick files found in {0}")]
+    EmptyCapture(String),
+}
+
+/// Writes quotes to a tick file as they arrive.
+///
+/// # Example
+///
+/// ```rust
+/// use adapter_feeds::capture::{read_ticks, TickRecorder};
+/// use adapter_feeds::MarketQuote;
+///
+/// let mut recorder = TickRecorder::new(Vec::new()).unwrap();
+/// recorder
+///     .record(&MarketQuote::with_last("USD.CURVE", 0.051).with_timestamp(1_000))
+///     .unwrap();
+/// let buffer = recorder.into_inner().unwrap();
+///
+/// let ticks = read_ticks(buffer.as_slice()).unwrap();
+/// assert_eq!(ticks[0].last, Some(0.051));
+/// ```
+pub struct TickRecorder<W: Write> {
+    writer: W,
+    count: usize,
+}
+
+/// Tick file on disk, optionally gzip-compressed
+pub enum TickFileWriter {
+    /// Uncompressed file
+    Plain(BufWriter<File>),
+    /// Gzip-compressed file
+    Gzip(GzEncoder<BufWriter<File>>),
+}
+
+impl Write for TickFileWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Self::Plain(w) => w.write(buf),
+            Self::Gzip(w) => w.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Self::Plain(w) => w.flush(),
+            Self::Gzip(w) => w.flush(),
+        }
+    }
+}
+
+impl TickRecorder<TickFileWriter> {
+    /// Create (or truncate) a tick file at `path`.
+    ///
+    /// The file is gzip-compressed if `path` ends in `.gz`.
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<Self, CaptureError> {
+        let path = path.as_ref();
+        let file = BufWriter::new(File::create(path)?);
+        let writer = if path.extension().is_some_and(|ext| ext == "gz") {
+            TickFileWriter::Gzip(GzEncoder::new(file, Compression::default()))
+        } else {
+            TickFileWriter::Plain(file)
+        };
+        Self::new(writer)
+    }
+
+    /// Complete the file, writing the gzip trailer if compressed.
+    pub fn finish(self) -> Result<(), CaptureError> {
+        match self.into_inner()? {
+            TickFileWriter::Plain(mut w) => w.flush()?,
+            TickFileWriter::Gzip(w) => w.finish()?.flush()?,
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> TickRecorder<W> {
+    /// Start a tick file on `writer`, writing the header line.
+    pub fn new(mut writer: W) -> Result<Self, CaptureError> {
+        writeln!(writer, "{}", TICK_FILE_HEADER)?;
+        Ok(Self { writer, count: 0 })
+    }
+
+    /// Append a quote.
+    pub fn record(&mut self, quote: &MarketQuote) -> Result<(), CaptureError> {
+        if quote.identifier.contains([',', '\n', '\r']) {
+            return Err(CaptureError::UnrecordableQuote(quote.identifier.clone()));
+        }
+        writeln!(
+            self.writer,
+            "{},{},{},{},{},{}",
+            quote.timestamp_ms,
+            quote.identifier,
+            format_price(quote.bid),
+            format_price(quote.ask),
+            format_price(quote.last),
+            quote.currency.code()
+        )?;
+        self.count += 1;
+        Ok(())
+    }
+
+    /// Number of quotes recorded.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Flush buffered quotes to the underlying writer.
+    pub fn flush(&mut self) -> Result<(), CaptureError> {
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    /// Flush and return the underlying writer.
+    pub fn into_inner(mut self) -> Result<W, CaptureError> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+fn format_price(price: Option<f64>) -> String {
+    price.map(|p| p.to_string()).unwrap_or_default()
+}
+
+/// Read every quote from a tick file on disk, compressed or not.
+pub fn read_tick_file<P: AsRef<Path>>(path: P) -> Result<Vec<MarketQuote>, CaptureError> {
+    let mut reader = BufReader::new(File::open(path)?);
+    if reader.fill_buf()?.starts_with(&GZIP_MAGIC) {
+        read_ticks(GzDecoder::new(reader))
+    } else {
+        read_ticks(reader)
+    }
+}
+
+/// Read every quote from a tick file.
+///
+/// Blank lines are skipped. Fails on the first malformed line or on a
+/// timestamp earlier than the previous quote's.
+pub fn read_ticks<R: Read>(reader: R) -> Result<Vec<MarketQuote>, CaptureError> {
+    let mut lines = BufReader::new(reader).lines();
+    let header = lines.next().transpose()?.unwrap_or_default();
+    if header.trim() != TICK_FILE_HEADER {
+        return Err(CaptureError::InvalidHeader(header));
+    }
+
+    let mut quotes: Vec<MarketQuote> = Vec::new();
+    for (idx, line) in lines.enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let line_no = idx + 2;
+        let quote = parse_tick(&line).map_err(|message| CaptureError::InvalidTick {
+            line: line_no,
+            message,
+        })?;
+        if let Some(prev) = quotes.last() {
+            if quote.timestamp_ms < prev.timestamp_ms {
+                return Err(CaptureError::InvalidTick {
+                    line: line_no,
+                    message: format!(
+                        "timestamp {} precedes previous tick at {}",
+                        quote.timestamp_ms, prev.timestamp_ms
+                    ),
+                });
+            }
+        }
+        quotes.push(quote);
+    }
+    Ok(quotes)
+}
+
+fn parse_tick(line: &str) -> Result<MarketQuote, String> {
+    let fields: Vec<&str> = line.trim_end().split(',').collect();
+    if fields.len() != 6 {
+        return Err(format!("expected 6 fields, found {}", fields.len()));
+    }
+    let timestamp_ms = fields[0]
+        .parse::<i64>()
+        .map_err(|_| format!("invalid timestamp '{}'", fields[0]))?;
+    if fields[1].is_empty() {
+        return Err("missing identifier".to_string());
+    }
+    let currency = fields[5]
+        .parse::<Currency>()
+        .map_err(|_| format!("invalid currency '{}'", fields[5]))?;
+    let bid = parse_price(fields[2], "bid")?;
+    let ask = parse_price(fields[3], "ask")?;
+    let last = parse_price(fields[4], "last")?;
+    if bid.is_none() && ask.is_none() && last.is_none() {
+        return Err("tick has no prices".to_string());
+    }
+
+    Ok(MarketQuote {
+        identifier: fields[1].to_string(),
+        bid,
+        ask,
+        last,
+        currency,
+        timestamp_ms,
+    })
+}
+
+fn parse_price(field: &str, name: &str) -> Result<Option<f64>, String> {
+    if field.is_empty() {
+        return Ok(None);
+    }
+    field
+        .parse::<f64>()
+        .ok()
+        .filter(|p| p.is_finite())
+        .map(Some)
+        .ok_or_else(|| format!("invalid {} '{}'", name, field))
+}
+
+/// Records a feed into a directory of rotating tick files.
+///
+/// Files are named `ticks-<first timestamp ms>-<sequence>` so that name
+/// order is recording order. Quotes without a timestamp are stamped with
+/// the time they are recorded, and a quote stamped earlier than its
+/// predecessor is recorded at the predecessor's time, so that files stay
+/// in arrival order.
+///
+/// # Example
+///
+/// ```rust
+/// use adapter_feeds::capture::{read_capture, TickCapture};
+/// use adapter_feeds::MarketQuote;
+///
+/// let dir = std::env::temp_dir().join("adapter_feeds_capture_doc");
+/// # let _ = std::fs::remove_dir_all(&dir);
+/// let mut capture = TickCapture::new(&dir).unwrap().with_max_ticks_per_file(2);
+/// for (i, level) in [0.050, 0.051, 0.052].into_iter().enumerate() {
+///     let quote = MarketQuote::with_last("USD.CURVE", level).with_timestamp(1_000 + i as i64);
+///     capture.record(&quote).unwrap();
+/// }
+/// let files = capture.finish().unwrap();
+/// assert_eq!(files.len(), 2);
+///
+/// let ticks = read_capture(&dir).unwrap();
+/// assert_eq!(ticks.len(), 3);
+/// # std::fs::remove_dir_all(&dir).unwrap();
+/// ```
+pub struct TickCapture {
+    dir: PathBuf,
+    compress: bool,
+    max_ticks_per_file: usize,
+    current: Option<TickRecorder<TickFileWriter>>,
+    files: Vec<PathBuf>,
+    last_timestamp_ms: i64,
+    total: usize,
+}
+
+impl TickCapture {
+    /// Capture into `dir`, creating it if needed.
+    ///
+    /// Files are compressed and rotated every 100,000 ticks by default.
+    pub fn new<P: AsRef<Path>>(dir: P) -> Result<Self, CaptureError> {
+        let dir = dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            compress: true,
+            max_ticks_per_file: 100_000,
+            current: None,
+            files: Vec::new(),
+            last_timestamp_ms: i64::MIN,
+            total: 0,
+        })
+    }
+
+    /// Enable or disable gzip compression of new files
+    pub fn with_compression(mut self, compress: bool) -> Self {
+        self.compress = compress;
+        self
+    }
+
+    /// Start a new file after this many ticks (at least one)
+    pub fn with_max_ticks_per_file(mut self, max_ticks: usize) -> Self {
+        self.max_ticks_per_file = max_ticks.max(1);
+        self
+    }
+
+    /// Record a quote, rotating to a new file when the current one is full.
+    pub fn record(&mut self, quote: &MarketQuote) -> Result<(), CaptureError> {
+        let mut stamped = quote.clone();
+        if stamped.timestamp_ms <= 0 {
+            stamped.timestamp_ms = now_ms();
+        }
+        stamped.timestamp_ms = stamped.timestamp_ms.max(self.last_timestamp_ms);
+
+        let full = self
+            .current
+            .as_ref()
+            .is_some_and(|r| r.count() >= self.max_ticks_per_file);
+        if full {
+            if let Some(recorder) = self.current.take() {
+                recorder.finish()?;
+            }
+        }
+        if self.current.is_none() {
+            let extension = if self.compress {
+                COMPRESSED_TICK_FILE_EXTENSION
+            } else {
+                TICK_FILE_EXTENSION
+            };
+            let path = self.dir.join(format!(
+                "ticks-{:013}-{:04}.{}",
+                stamped.timestamp_ms,
+                self.files.len() + 1,
+                extension
+            ));
+            self.current = Some(TickRecorder::create(&path)?);
+            self.files.push(path);
+        }
+
+        if let Some(recorder) = self.current.as_mut() {
+            recorder.record(&stamped)?;
+        }
+        self.last_timestamp_ms = stamped.timestamp_ms;
+        self.total += 1;
+        Ok(())
+    }
+
+    /// Flush the current file (compressed data may stay buffered)
+    pub fn flush(&mut self) -> Result<(), CaptureError> {
+        match self.current.as_mut() {
+            Some(recorder) => recorder.flush(),
+            None => Ok(()),
+        }
+    }
+
+    /// Close the current file and return every file written.
+    pub fn finish(mut self) -> Result<Vec<PathBuf>, CaptureError> {
+        if let Some(recorder) = self.current.take() {
+            recorder.finish()?;
+        }
+        Ok(std::mem::take(&mut self.files))
+    }
+
+    /// Files written so far, in recording order
+    pub fn files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    /// Number of quotes recorded
+    pub fn ticks_recorded(&self) -> usize {
+        self.total
+    }
+
+    /// Capture directory
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or_default()
+}
+
+/// Read a capture back in recording order.
+///
+/// `path` may be a single tick file or a directory written by
+/// [`TickCapture`], in which case every tick file in it is read in name
+/// order.
+pub fn read_capture<P: AsRef<Path>>(path: P) -> Result<Vec<MarketQuote>, CaptureError> {
+    let path = path.as_ref();
+    if !path.is_dir() {
+        return read_tick_file(path);
+    }
+
+    let mut files: Vec<PathBuf> = std::fs::read_dir(path)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.file_name().and_then(|n| n.to_str()).is_some_and(|name| {
+                name.ends_with(&format!(".{}", TICK_FILE_EXTENSION))
+                    || name.ends_with(&format!(".{}", COMPRESSED_TICK_FILE_EXTENSION))
+            })
+        })
+        .collect();
+    if files.is_empty() {
+        return Err(CaptureError::EmptyCapture(path.display().to_string()));
+    }
+    files.sort();
+
+    let mut quotes = Vec::new();
+    for file in files {
+        quotes.extend(read_tick_file(&file)?);
+    }
+    Ok(quotes)
+}
+
+/// Quotes with `from_ms <= timestamp_ms < to_ms` from a capture in
+/// recording order, e.g. the ticks around a pricing spike.
+pub fn ticks_between(quotes: &[MarketQuote], from_ms: i64, to_ms: i64) -> &[MarketQuote] {
+    let start = quotes.partition_point(|q| q.timestamp_ms < from_ms);
+    let end = quotes
+        .partition_point(|q| q.timestamp_ms < to_ms)
+        .max(start);
+    &quotes[start..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record_all(quotes: &[MarketQuote]) -> Vec<u8> {
+        let mut recorder = TickRecorder::new(Vec::new()).unwrap();
+        for quote in quotes {
+            recorder.record(quote).unwrap();
+        }
+        assert_eq!(recorder.count(), quotes.len());
+        recorder.into_inner().unwrap()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let quotes = vec![
+            MarketQuote::with_last("USD.CURVE", 0.051).with_timestamp(1_000),
+            MarketQuote::new("EURUSD", 1.0841, 1.0843)
+                .with_currency(Currency::EUR)
+                .with_timestamp(1_250),
+        ];
+        let ticks = read_ticks(record_all(&quotes).as_slice()).unwrap();
+
+        assert_eq!(ticks.len(), 2);
+        assert_eq!(ticks[0].identifier, "USD.CURVE");
+        assert_eq!(ticks[0].last, Some(0.051));
+        assert_eq!(ticks[0].bid, None);
+        assert_eq!(ticks[1].mid(), quotes[1].mid());
+        assert_eq!(ticks[1].currency, Currency::EUR);
+        assert_eq!(ticks[1].timestamp_ms, 1_250);
+    }
+
+    #[test]
+    fn test_rejects_bad_header() {
+        let result = read_ticks("time,id\n".as_bytes());
+        assert!(matches!(result, Err(CaptureError::InvalidHeader(_))));
+    }
+
+    #[test]
+    fn test_rejects_malformed_tick() {
+        let file = format!("{}\n1000,USD.CURVE,,,abc,USD\n", TICK_FILE_HEADER);
+        match read_ticks(file.as_bytes()) {
+            Err(CaptureError::InvalidTick { line, message }) => {
+                assert_eq!(line, 2);
+                assert!(message.contains("last"));
+            }
+            other => panic!("expected invalid tick, got {:?}", other.map(|q| q.len())),
+        }
+    }
+
+    #[test]
+    fn test_rejects_out_of_order_timestamps() {
+        let file = format!(
+            "{}\n2000,USD.CURVE,,,0.05,USD\n\n1000,USD.CURVE,,,0.051,USD\n",
+            TICK_FILE_HEADER
+        );
+        match read_ticks(file.as_bytes()) {
+            Err(CaptureError::InvalidTick { line, .. }) => assert_eq!(line, 4),
+            other => panic!("expected invalid tick, got {:?}", other.map(|q| q.len())),
+        }
+    }
+
+    #[test]
+    fn test_refuses_identifier_with_separator() {
+        let mut recorder = TickRecorder::new(Vec::new()).unwrap();
+        let result = recorder.record(&MarketQuote::with_last("A,B", 1.0));
+        assert!(matches!(result, Err(CaptureError::UnrecordableQuote(_))));
+    }
+
+    #[test]
+    fn test_compressed_file_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let plain = dir.path().join("session.ticks");
+        let gzip = dir.path().join("session.ticks.gz");
+        for path in [&plain, &gzip] {
+            let mut recorder = TickRecorder::create(path).unwrap();
+            for i in 0..500 {
+                let quote = MarketQuote::with_last("USD.CURVE", 0.05 + i as f64 * 1e-5)
+                    .with_timestamp(1_000 + i);
+                recorder.record(&quote).unwrap();
+            }
+            recorder.finish().unwrap();
+        }
+
+        let plain_size = std::fs::metadata(&plain).unwrap().len();
+        let gzip_size = std::fs::metadata(&gzip).unwrap().len();
+        assert!(
+            gzip_size < plain_size / 2,
+            "{} vs {}",
+            gzip_size,
+            plain_size
+        );
+
+        let from_plain = read_tick_file(&plain).unwrap();
+        let from_gzip = read_tick_file(&gzip).unwrap();
+        assert_eq!(from_gzip.len(), 500);
+        assert_eq!(from_gzip[499].last, from_plain[499].last);
+        assert_eq!(from_gzip[499].timestamp_ms, 1_499);
+    }
+
+    #[test]
+    fn test_capture_rotates_and_reads_back_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut capture = TickCapture::new(dir.path())
+            .unwrap()
+            .with_max_ticks_per_file(4);
+        for i in 0..10 {
+            let quote = MarketQuote::new("EURUSD", 1.08 + i as f64 * 1e-4, 1.0802)
+                .with_timestamp(5_000 + i * 10);
+            capture.record(&quote).unwrap();
+        }
+        assert_eq!(capture.ticks_recorded(), 10);
+        let files = capture.finish().unwrap();
+        assert_eq!(files.len(), 3);
+        assert!(files[0]
+            .to_string_lossy()
+            .ends_with("ticks-0000000005000-0001.ticks.gz"));
+
+        let ticks = read_capture(dir.path()).unwrap();
+        assert_eq!(ticks.len(), 10);
+        assert!(ticks
+            .windows(2)
+            .all(|w| w[0].timestamp_ms < w[1].timestamp_ms));
+        assert_eq!(read_capture(&files[2]).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_capture_stamps_and_orders_quotes() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut capture = TickCapture::new(dir.path())
+            .unwrap()
+            .with_compression(false);
+        let before = now_ms();
+        capture
+            .record(&MarketQuote::with_last("USD.CURVE", 0.05))
+            .unwrap();
+        // Stamped earlier than its predecessor
+        capture
+            .record(&MarketQuote::with_last("USD.CURVE", 0.051).with_timestamp(1))
+            .unwrap();
+        let files = capture.finish().unwrap();
+        assert!(files[0].to_string_lossy().ends_with(".ticks"));
+
+        let ticks = read_capture(dir.path()).unwrap();
+        assert!(ticks[0].timestamp_ms >= before);
+        assert_eq!(ticks[1].timestamp_ms, ticks[0].timestamp_ms);
+    }
+
+    #[test]
+    fn test_empty_capture_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(matches!(
+            read_capture(dir.path()),
+            Err(CaptureError::EmptyCapture(_))
+        ));
+    }
+
+    #[test]
+    fn test_ticks_between() {
+        let quotes: Vec<MarketQuote> = (0..10)
+            .map(|i| MarketQuote::with_last("AAPL", 185.0).with_timestamp(i * 100))
+            .collect();
+        let window = ticks_between(&quotes, 250, 600);
+        assert_eq!(window.len(), 3);
+        assert_eq!(window[0].timestamp_ms, 300);
+        assert!(ticks_between(&quotes, 600, 250).is_empty());
+    }
+}
@@ -4,6 +4,7 @@
 //!
 //! This crate handles connectivity to market data providers (Reuters, Bloomberg, internal lakes)
 //! and normalises raw quotes (Bid/Ask, Last) into standardised `MarketQuote` structs.
+//! Streams can be captured to tick files and read back for replay (see [`capture`]).
 //!
 //! ## Architecture Position
 //!
@@ -18,6 +19,7 @@
 //! let quote = MarketQuote::new("AAPL", 150.25, 150.30);
 //! ```
 
+pub mod capture;
 mod quote;
 
 pub use quote::{MarketQuote, QuoteType};
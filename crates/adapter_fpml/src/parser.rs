@@ -1,6 +1,10 @@
 //! FpML parser implementation.
 
 use crate::error::FpmlError;
+use pricer_models::instruments::{
+    ISDA_EQUITY_OPTION_SINGLE_NAME, ISDA_FX_FORWARD, ISDA_FX_VANILLA_OPTION,
+    ISDA_IR_SWAP_FIXED_FLOAT,
+};
 
 /// FpML parser for trade definitions.
 ///
@@ -61,6 +65,24 @@ pub enum ProductType {
     Unknown,
 }
 
+impl ProductType {
+    /// ISDA taxonomy code of the product type.
+    ///
+    /// Used as the key into `pricer_models::instruments::ProductRegistry`,
+    /// which constructs the instrument from the parsed trade terms.
+    /// Returns `None` for `Unknown`.
+    pub fn taxonomy_code(&self) -> Option<&'static str> {
+        match self {
+            ProductType::InterestRateSwap => Some(ISDA_IR_SWAP_FIXED_FLOAT),
+            ProductType::FxForward => Some(ISDA_FX_FORWARD),
+            ProductType::FxOption => Some(ISDA_FX_VANILLA_OPTION),
+            ProductType::CreditDefaultSwap => Some("Credit:SingleName:Corporate"),
+            ProductType::EquityOption => Some(ISDA_EQUITY_OPTION_SINGLE_NAME),
+            ProductType::Unknown => None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -70,4 +92,20 @@ mod tests {
         let result = FpmlParser::parse("<trade></trade>");
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_taxonomy_codes_resolve_in_registry() {
+        use pricer_models::instruments::ProductRegistry;
+
+        let registry = ProductRegistry::<f64>::with_defaults();
+        for product in [
+            ProductType::InterestRateSwap,
+            ProductType::FxForward,
+            ProductType::FxOption,
+            ProductType::EquityOption,
+        ] {
+            assert!(registry.contains(product.taxonomy_code().unwrap()));
+        }
+        assert_eq!(ProductType::Unknown.taxonomy_code(), None);
+    }
 }
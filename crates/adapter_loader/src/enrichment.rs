@@ -0,0 +1,790 @@
+//! Trade enrichment from reference data.
+//!
+//! Booking systems identify counterparties and netting sets by internal
+//! codes. Before a portfolio reaches the XVA or regulatory engines it is
+//! enriched from the firm's reference data: counterparty Legal Entity
+//! Identifiers (LEIs), the ISDA master agreement behind each netting set
+//! and whether close-out netting is enforceable under it, the CSA that
+//! collateralises it, and the legal entity that booked the trades.
+//!
+//! Reference data comes from any [`ReferenceDataSource`]; [`ReferenceData`]
+//! is an in-memory source loaded from CSV files, and database-backed
+//! sources implement the trait directly. [`TradeEnricher`] resolves every
+//! reference in a portfolio and fails with
+//! [`LoaderError::UnresolvedReferences`] listing *all* missing or
+//! inconsistent entries, each naming the reference data file to fix.
+//!
+//! ## CSV Files
+//!
+//! | File                     | Columns                                                                  |
+//! |--------------------------|--------------------------------------------------------------------------|
+//! | `counterparties.csv`     | `counterparty_id`, `lei`, `legal_name`?, `jurisdiction`?                 |
+//! | `booking_entities.csv`   | `entity_id`, `lei`, `name`?                                              |
+//! | `netting_agreements.csv` | `netting_set_id`, `counterparty_id`, `agreement_id`, `booking_entity`, `enforceable`, `csa_id`? |
+//! | `csas.csv`               | `csa_id`, `threshold`, `minimum_transfer_amount`, `independent_amount`, `currency`, `margin_period_of_risk` |
+//!
+//! Columns marked `?` may be left empty. The margin period of risk is in
+//! business days.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+use pricer_core::types::Currency;
+use pricer_risk::portfolio::{
+    CollateralAgreement, Counterparty, NettingSet, Portfolio, PortfolioBuilder,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::csa::CsaTerms;
+use crate::error::LoaderError;
+
+/// File name of counterparty reference data in a reference data directory.
+pub const COUNTERPARTIES_FILE: &str = "counterparties.csv";
+/// File name of booking entity reference data.
+pub const BOOKING_ENTITIES_FILE: &str = "booking_entities.csv";
+/// File name of netting agreement reference data.
+pub const NETTING_AGREEMENTS_FILE: &str = "netting_agreements.csv";
+/// File name of CSA reference data.
+pub const CSAS_FILE: &str = "csas.csv";
+
+/// Business days per year, converting CSA margin periods of risk.
+const BUSINESS_DAYS_PER_YEAR: f64 = 252.0;
+
+/// Counterparty legal entity data.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CounterpartyReference {
+    /// Internal counterparty identifier
+    pub counterparty_id: String,
+    /// Legal Entity Identifier (ISO 17442)
+    pub lei: String,
+    /// Registered legal name
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub legal_name: Option<String>,
+    /// Jurisdiction of incorporation
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub jurisdiction: Option<String>,
+}
+
+/// Legal entity of the firm that books trades.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BookingEntityReference {
+    /// Internal entity identifier
+    pub entity_id: String,
+    /// Legal Entity Identifier (ISO 17442)
+    pub lei: String,
+    /// Entity name
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub name: Option<String>,
+}
+
+/// Master agreement governing a netting set.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NettingAgreementReference {
+    /// Netting set the agreement governs
+    pub netting_set_id: String,
+    /// Counterparty to the agreement
+    pub counterparty_id: String,
+    /// Master agreement identifier
+    pub agreement_id: String,
+    /// Entity of the firm party to the agreement, booking its trades
+    pub booking_entity: String,
+    /// Whether close-out netting is legally enforceable
+    pub enforceable: bool,
+    /// Credit Support Annex collateralising the netting set
+    #[serde(default, deserialize_with = "empty_as_none")]
+    pub csa_id: Option<String>,
+}
+
+/// CSA row as stored in `csas.csv`.
+#[derive(Debug, Deserialize)]
+struct CsaRow {
+    csa_id: String,
+    threshold: f64,
+    minimum_transfer_amount: f64,
+    independent_amount: f64,
+    currency: String,
+    margin_period_of_risk: u32,
+}
+
+/// Kind of reference data entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ReferenceKind {
+    /// Counterparty legal entity
+    Counterparty,
+    /// Booking entity
+    BookingEntity,
+    /// Netting agreement
+    NettingAgreement,
+    /// Credit Support Annex
+    Csa,
+}
+
+impl ReferenceKind {
+    /// Reference data file holding entries of this kind.
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            ReferenceKind::Counterparty => COUNTERPARTIES_FILE,
+            ReferenceKind::BookingEntity => BOOKING_ENTITIES_FILE,
+            ReferenceKind::NettingAgreement => NETTING_AGREEMENTS_FILE,
+            ReferenceKind::Csa => CSAS_FILE,
+        }
+    }
+}
+
+impl fmt::Display for ReferenceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ReferenceKind::Counterparty => "counterparty",
+            ReferenceKind::BookingEntity => "booking entity",
+            ReferenceKind::NettingAgreement => "netting agreement",
+            ReferenceKind::Csa => "CSA",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A reference that could not be resolved.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnresolvedReference {
+    /// Portfolio object holding the reference (e.g. `netting set 'NS001'`)
+    pub subject: String,
+    /// Kind of reference data looked up
+    pub kind: ReferenceKind,
+    /// Key looked up
+    pub key: String,
+    /// What is wrong with the entry
+    pub reason: String,
+}
+
+impl fmt::Display for UnresolvedReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} '{}' {} (check {})",
+            self.subject,
+            self.kind,
+            self.key,
+            self.reason,
+            self.kind.file_name()
+        )
+    }
+}
+
+/// Source of reference data.
+///
+/// Lookups return `Ok(None)` for absent entries; errors are reserved for
+/// failures of the source itself, such as a lost database connection.
+pub trait ReferenceDataSource {
+    /// Looks up a counterparty by internal identifier.
+    fn counterparty(&self, id: &str) -> Result<Option<CounterpartyReference>, LoaderError>;
+
+    /// Looks up a booking entity by internal identifier.
+    fn booking_entity(&self, id: &str) -> Result<Option<BookingEntityReference>, LoaderError>;
+
+    /// Looks up the netting agreement of a netting set.
+    fn netting_agreement(
+        &self,
+        netting_set_id: &str,
+    ) -> Result<Option<NettingAgreementReference>, LoaderError>;
+
+    /// Looks up a CSA by identifier.
+    fn csa(&self, id: &str) -> Result<Option<CsaTerms>, LoaderError>;
+}
+
+/// In-memory reference data.
+#[derive(Debug, Clone, Default)]
+pub struct ReferenceData {
+    counterparties: HashMap<String, CounterpartyReference>,
+    booking_entities: HashMap<String, BookingEntityReference>,
+    netting_agreements: HashMap<String, NettingAgreementReference>,
+    csas: HashMap<String, CsaTerms>,
+}
+
+impl ReferenceData {
+    /// Create empty reference data.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load the standard reference data files from a directory.
+    ///
+    /// Files that do not exist are skipped, so that, for example, an
+    /// uncollateralised book needs no `csas.csv`.
+    pub fn from_directory<P: AsRef<Path>>(dir: P) -> Result<Self, LoaderError> {
+        let dir = dir.as_ref();
+        let mut data = Self::new();
+        let path = |name: &str| Some(dir.join(name)).filter(|p| p.exists());
+        if let Some(p) = path(COUNTERPARTIES_FILE) {
+            data.load_counterparties_csv(p)?;
+        }
+        if let Some(p) = path(BOOKING_ENTITIES_FILE) {
+            data.load_booking_entities_csv(p)?;
+        }
+        if let Some(p) = path(NETTING_AGREEMENTS_FILE) {
+            data.load_netting_agreements_csv(p)?;
+        }
+        if let Some(p) = path(CSAS_FILE) {
+            data.load_csas_csv(p)?;
+        }
+        Ok(data)
+    }
+
+    /// Load counterparties from CSV, rejecting malformed LEIs.
+    pub fn load_counterparties_csv<P: AsRef<Path>>(&mut self, path: P) -> Result<(), LoaderError> {
+        for (row, record) in read_rows::<CounterpartyReference>(path.as_ref())? {
+            check_lei(row, &record.lei)?;
+            self.add_counterparty(record);
+        }
+        Ok(())
+    }
+
+    /// Load booking entities from CSV, rejecting malformed LEIs.
+    pub fn load_booking_entities_csv<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<(), LoaderError> {
+        for (row, record) in read_rows::<BookingEntityReference>(path.as_ref())? {
+            check_lei(row, &record.lei)?;
+            self.add_booking_entity(record);
+        }
+        Ok(())
+    }
+
+    /// Load netting agreements from CSV.
+    pub fn load_netting_agreements_csv<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> Result<(), LoaderError> {
+        for (_, record) in read_rows::<NettingAgreementReference>(path.as_ref())? {
+            self.add_netting_agreement(record);
+        }
+        Ok(())
+    }
+
+    /// Load CSAs from CSV.
+    pub fn load_csas_csv<P: AsRef<Path>>(&mut self, path: P) -> Result<(), LoaderError> {
+        for (row, record) in read_rows::<CsaRow>(path.as_ref())? {
+            let currency =
+                record
+                    .currency
+                    .parse::<Currency>()
+                    .map_err(|_| LoaderError::InvalidFormat {
+                        row,
+                        message: format!("unknown currency '{}'", record.currency),
+                    })?;
+            self.add_csa(CsaTerms {
+                csa_id: record.csa_id,
+                threshold: record.threshold,
+                minimum_transfer_amount: record.minimum_transfer_amount,
+                independent_amount: record.independent_amount,
+                currency,
+                margin_period_of_risk: record.margin_period_of_risk,
+            });
+        }
+        Ok(())
+    }
+
+    /// Add or replace a counterparty.
+    pub fn add_counterparty(&mut self, record: CounterpartyReference) {
+        self.counterparties
+            .insert(record.counterparty_id.clone(), record);
+    }
+
+    /// Add or replace a booking entity.
+    pub fn add_booking_entity(&mut self, record: BookingEntityReference) {
+        self.booking_entities
+            .insert(record.entity_id.clone(), record);
+    }
+
+    /// Add or replace a netting agreement.
+    pub fn add_netting_agreement(&mut self, record: NettingAgreementReference) {
+        self.netting_agreements
+            .insert(record.netting_set_id.clone(), record);
+    }
+
+    /// Add or replace a CSA.
+    pub fn add_csa(&mut self, csa: CsaTerms) {
+        self.csas.insert(csa.csa_id.clone(), csa);
+    }
+}
+
+impl ReferenceDataSource for ReferenceData {
+    fn counterparty(&self, id: &str) -> Result<Option<CounterpartyReference>, LoaderError> {
+        Ok(self.counterparties.get(id).cloned())
+    }
+
+    fn booking_entity(&self, id: &str) -> Result<Option<BookingEntityReference>, LoaderError> {
+        Ok(self.booking_entities.get(id).cloned())
+    }
+
+    fn netting_agreement(
+        &self,
+        netting_set_id: &str,
+    ) -> Result<Option<NettingAgreementReference>, LoaderError> {
+        Ok(self.netting_agreements.get(netting_set_id).cloned())
+    }
+
+    fn csa(&self, id: &str) -> Result<Option<CsaTerms>, LoaderError> {
+        Ok(self.csas.get(id).cloned())
+    }
+}
+
+/// Enriches portfolios from a reference data source.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use adapter_loader::{ReferenceData, TradeDocument, TradeEnricher};
+///
+/// let reference = ReferenceData::from_directory("refdata")?;
+/// let portfolio = TradeDocument::load("trades.json")?.portfolio()?;
+/// let enriched = TradeEnricher::new(&reference).enrich(&portfolio)?;
+/// ```
+pub struct TradeEnricher<'a, S: ReferenceDataSource + ?Sized> {
+    source: &'a S,
+}
+
+impl<'a, S: ReferenceDataSource + ?Sized> TradeEnricher<'a, S> {
+    /// Create an enricher over a reference data source.
+    pub fn new(source: &'a S) -> Self {
+        Self { source }
+    }
+
+    /// Return a copy of the portfolio with reference data attached.
+    ///
+    /// - Counterparties gain their LEI, and their legal name and
+    ///   jurisdiction where the portfolio has none
+    /// - Netting sets take the enforceability of their netting agreement
+    ///   and the collateral terms of its CSA
+    /// - Trades gain the LEI of the booking entity of their netting
+    ///   agreement
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LoaderError::UnresolvedReferences`] listing every missing
+    /// counterparty, netting agreement, booking entity or CSA, netting
+    /// agreements whose counterparty disagrees with the portfolio, and
+    /// CSAs with invalid terms. Errors of the source itself are returned
+    /// as they occur.
+    pub fn enrich(&self, portfolio: &Portfolio) -> Result<Portfolio, LoaderError> {
+        let mut unresolved = Vec::new();
+
+        let mut counterparty_ids: Vec<_> = portfolio.counterparty_ids().collect();
+        counterparty_ids.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        let mut counterparties = Vec::with_capacity(counterparty_ids.len());
+        for id in counterparty_ids {
+            let counterparty = portfolio.counterparty(id).expect("id from portfolio");
+            match self.source.counterparty(id.as_str())? {
+                Some(reference) => {
+                    counterparties.push(enrich_counterparty(counterparty, reference))
+                }
+                None => unresolved.push(not_found(
+                    format!("counterparty '{}'", id),
+                    ReferenceKind::Counterparty,
+                    id.as_str(),
+                )),
+            }
+        }
+
+        let mut netting_set_ids: Vec<_> = portfolio.netting_set_ids().collect();
+        netting_set_ids.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        let mut netting_sets = Vec::with_capacity(netting_set_ids.len());
+        let mut booking_entities = HashMap::new();
+        for id in netting_set_ids {
+            let netting_set = portfolio.netting_set(id).expect("id from portfolio");
+            let subject = format!("netting set '{}'", id);
+            match self.source.netting_agreement(id.as_str())? {
+                Some(agreement) => {
+                    if let Some(enriched) =
+                        self.enrich_netting_set(netting_set, &agreement, &subject, &mut unresolved)?
+                    {
+                        netting_sets.push(enriched);
+                    }
+                    match self.source.booking_entity(&agreement.booking_entity)? {
+                        Some(entity) => {
+                            booking_entities.insert(id.clone(), entity.lei);
+                        }
+                        None => unresolved.push(not_found(
+                            subject,
+                            ReferenceKind::BookingEntity,
+                            &agreement.booking_entity,
+                        )),
+                    }
+                }
+                None => unresolved.push(not_found(
+                    subject,
+                    ReferenceKind::NettingAgreement,
+                    id.as_str(),
+                )),
+            }
+        }
+
+        if !unresolved.is_empty() {
+            return Err(LoaderError::UnresolvedReferences(unresolved));
+        }
+
+        let trades = portfolio.trades().map(|trade| {
+            let lei = &booking_entities[trade.netting_set_id()];
+            trade.clone().with_booking_entity(lei)
+        });
+
+        Ok(PortfolioBuilder::new()
+            .add_counterparties(counterparties)
+            .add_netting_sets(netting_sets)
+            .add_trades(trades)
+            .build()?)
+    }
+
+    /// Apply a netting agreement and its CSA to a netting set.
+    ///
+    /// Returns `None` after recording the problem if the agreement or CSA
+    /// is inconsistent.
+    fn enrich_netting_set(
+        &self,
+        netting_set: &NettingSet,
+        agreement: &NettingAgreementReference,
+        subject: &str,
+        unresolved: &mut Vec<UnresolvedReference>,
+    ) -> Result<Option<NettingSet>, LoaderError> {
+        if agreement.counterparty_id != netting_set.counterparty_id().as_str() {
+            unresolved.push(UnresolvedReference {
+                subject: subject.to_string(),
+                kind: ReferenceKind::NettingAgreement,
+                key: agreement.agreement_id.clone(),
+                reason: format!(
+                    "is with counterparty '{}', but the netting set belongs to '{}'",
+                    agreement.counterparty_id,
+                    netting_set.counterparty_id()
+                ),
+            });
+            return Ok(None);
+        }
+
+        let mut enriched = netting_set.clone();
+        enriched.set_enforceable(agreement.enforceable);
+        let Some(csa_id) = &agreement.csa_id else {
+            return Ok(Some(enriched));
+        };
+        let Some(csa) = self.source.csa(csa_id)? else {
+            unresolved.push(not_found(subject.to_string(), ReferenceKind::Csa, csa_id));
+            return Ok(None);
+        };
+        match CollateralAgreement::new(
+            csa.threshold,
+            csa.minimum_transfer_amount,
+            csa.independent_amount,
+            csa.currency,
+            f64::from(csa.margin_period_of_risk) / BUSINESS_DAYS_PER_YEAR,
+        ) {
+            Ok(collateral) => {
+                enriched.set_collateral(collateral);
+                Ok(Some(enriched))
+            }
+            Err(err) => {
+                unresolved.push(UnresolvedReference {
+                    subject: subject.to_string(),
+                    kind: ReferenceKind::Csa,
+                    key: csa_id.clone(),
+                    reason: format!("has invalid terms: {}", err),
+                });
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// Attach reference data to a counterparty, keeping existing name and
+/// jurisdiction.
+fn enrich_counterparty(
+    counterparty: &Counterparty,
+    reference: CounterpartyReference,
+) -> Counterparty {
+    let mut enriched = counterparty.clone().with_lei(reference.lei);
+    if let (None, Some(name)) = (counterparty.name(), reference.legal_name) {
+        enriched = enriched.with_name(name);
+    }
+    if let (None, Some(jurisdiction)) = (counterparty.jurisdiction(), reference.jurisdiction) {
+        enriched = enriched.with_jurisdiction(jurisdiction);
+    }
+    enriched
+}
+
+/// Unresolved reference for an absent entry.
+fn not_found(subject: String, kind: ReferenceKind, key: &str) -> UnresolvedReference {
+    UnresolvedReference {
+        subject,
+        kind,
+        key: key.to_string(),
+        reason: "not found in reference data".to_string(),
+    }
+}
+
+/// Read all rows of a CSV file with a header row, numbered from 1.
+fn read_rows<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<Vec<(usize, T)>, LoaderError> {
+    if !path.exists() {
+        return Err(LoaderError::FileNotFound(path.display().to_string()));
+    }
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_path(path)?;
+    reader
+        .deserialize()
+        .enumerate()
+        .map(|(idx, row)| Ok((idx + 1, row?)))
+        .collect()
+}
+
+/// Reject a malformed LEI.
+fn check_lei(row: usize, lei: &str) -> Result<(), LoaderError> {
+    if is_valid_lei(lei) {
+        Ok(())
+    } else {
+        Err(LoaderError::InvalidFormat {
+            row,
+            message: format!("'{}' is not a valid ISO 17442 LEI", lei),
+        })
+    }
+}
+
+/// Whether a string is a well-formed LEI: 20 upper-case alphanumerics
+/// with valid ISO 7064 MOD 97-10 check digits.
+pub fn is_valid_lei(lei: &str) -> bool {
+    if lei.len() != 20
+        || !lei
+            .bytes()
+            .all(|b| b.is_ascii_digit() || b.is_ascii_uppercase())
+    {
+        return false;
+    }
+    // Letters map to 10..=35; the whole number must be 1 mod 97
+    let remainder = lei.chars().fold(0u32, |acc, c| {
+        let value = c.to_digit(36).expect("alphanumeric");
+        if value < 10 {
+            (acc * 10 + value) % 97
+        } else {
+            (acc * 100 + value) % 97
+        }
+    });
+    remainder == 1
+}
+
+/// Deserialise an empty CSV field as `None`.
+fn empty_as_none<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let value = Option::<String>::deserialize(deserializer)?;
+    Ok(value.filter(|s| !s.is_empty()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trade_json::TradeDocument;
+
+    const ACME_LEI: &str = "5493001KJTIIGC8Y1R12";
+    const GLOBEX_LEI: &str = "529900T8BM49AURSDO55";
+    const BANK_LEI: &str = "7LTWFZYICNSX8D621K86";
+
+    fn portfolio() -> Portfolio {
+        let json = r#"{
+            "schema_version": 1,
+            "as_of": "2026-01-15",
+            "counterparties": [
+                { "id": "CP001", "hazard_rate": 0.02, "lgd": 0.6 },
+                { "id": "CP002", "name": "Globex", "hazard_rate": 0.01, "lgd": 0.6 }
+            ],
+            "netting_sets": [
+                { "id": "NS001", "counterparty_id": "CP001" },
+                { "id": "NS002", "counterparty_id": "CP002" }
+            ],
+            "trades": [
+                {
+                    "trade_id": "T001", "counterparty_id": "CP001", "netting_set_id": "NS001",
+                    "currency": "USD", "notional": 1000000.0,
+                    "trade_date": "2026-01-15", "maturity_date": "2027-01-15",
+                    "product": { "type": "forward", "strike": 100.0, "direction": "long" }
+                },
+                {
+                    "trade_id": "T002", "counterparty_id": "CP002", "netting_set_id": "NS002",
+                    "currency": "EUR", "notional": 500000.0,
+                    "trade_date": "2026-01-15", "maturity_date": "2028-01-15",
+                    "product": { "type": "swap", "fixed_rate": 0.02, "frequency": "annual" }
+                }
+            ]
+        }"#;
+        TradeDocument::from_json(json).unwrap().portfolio().unwrap()
+    }
+
+    fn reference_data() -> ReferenceData {
+        let mut data = ReferenceData::new();
+        data.add_counterparty(CounterpartyReference {
+            counterparty_id: "CP001".to_string(),
+            lei: ACME_LEI.to_string(),
+            legal_name: Some("Acme Corporation".to_string()),
+            jurisdiction: Some("US".to_string()),
+        });
+        data.add_counterparty(CounterpartyReference {
+            counterparty_id: "CP002".to_string(),
+            lei: GLOBEX_LEI.to_string(),
+            legal_name: Some("Globex Holdings SE".to_string()),
+            jurisdiction: None,
+        });
+        data.add_booking_entity(BookingEntityReference {
+            entity_id: "BANK-LDN".to_string(),
+            lei: BANK_LEI.to_string(),
+            name: None,
+        });
+        for (netting_set, counterparty, csa) in
+            [("NS001", "CP001", Some("CSA-1")), ("NS002", "CP002", None)]
+        {
+            data.add_netting_agreement(NettingAgreementReference {
+                netting_set_id: netting_set.to_string(),
+                counterparty_id: counterparty.to_string(),
+                agreement_id: format!("ISDA-{}", counterparty),
+                booking_entity: "BANK-LDN".to_string(),
+                enforceable: counterparty == "CP001",
+                csa_id: csa.map(str::to_string),
+            });
+        }
+        data.add_csa(CsaTerms {
+            csa_id: "CSA-1".to_string(),
+            threshold: 250_000.0,
+            minimum_transfer_amount: 50_000.0,
+            ..CsaTerms::default()
+        });
+        data
+    }
+
+    #[test]
+    fn test_lei_check_digits() {
+        assert!(is_valid_lei(ACME_LEI));
+        assert!(is_valid_lei(BANK_LEI));
+        assert!(!is_valid_lei("5493001KJTIIGC8Y1R13"));
+        assert!(!is_valid_lei("5493001kjtiigc8y1r12"));
+        assert!(!is_valid_lei("5493001KJTIIGC8Y1R1"));
+    }
+
+    #[test]
+    fn test_enrich_attaches_reference_data() {
+        let enriched = TradeEnricher::new(&reference_data())
+            .enrich(&portfolio())
+            .unwrap();
+
+        let acme = enriched.counterparty(&"CP001".into()).unwrap();
+        assert_eq!(acme.lei(), Some(ACME_LEI));
+        assert_eq!(acme.name(), Some("Acme Corporation"));
+        assert_eq!(acme.jurisdiction(), Some("US"));
+        // Names already in the portfolio are kept
+        let globex = enriched.counterparty(&"CP002".into()).unwrap();
+        assert_eq!(globex.name(), Some("Globex"));
+
+        let ns1 = enriched.netting_set(&"NS001".into()).unwrap();
+        assert!(ns1.is_enforceable());
+        let collateral = ns1.collateral().unwrap();
+        assert_eq!(collateral.threshold(), 250_000.0);
+        assert!((collateral.mpor_days() - 10.0).abs() < 1e-12);
+        let ns2 = enriched.netting_set(&"NS002".into()).unwrap();
+        assert!(!ns2.is_enforceable());
+        assert!(!ns2.is_collateralised());
+
+        assert!(enriched
+            .trades()
+            .all(|trade| trade.booking_entity() == Some(BANK_LEI)));
+    }
+
+    #[test]
+    fn test_unresolved_references_reported_together() {
+        let mut data = reference_data();
+        data.counterparties.remove("CP002");
+        data.csas.clear();
+        data.add_netting_agreement(NettingAgreementReference {
+            netting_set_id: "NS002".to_string(),
+            counterparty_id: "CP001".to_string(),
+            agreement_id: "ISDA-X".to_string(),
+            booking_entity: "BANK-NY".to_string(),
+            enforceable: true,
+            csa_id: None,
+        });
+
+        let Err(LoaderError::UnresolvedReferences(unresolved)) =
+            TradeEnricher::new(&data).enrich(&portfolio())
+        else {
+            panic!("expected unresolved references");
+        };
+        let kinds: Vec<_> = unresolved
+            .iter()
+            .map(|u| (u.kind, u.key.as_str()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (ReferenceKind::Counterparty, "CP002"),
+                (ReferenceKind::Csa, "CSA-1"),
+                (ReferenceKind::NettingAgreement, "ISDA-X"),
+                (ReferenceKind::BookingEntity, "BANK-NY"),
+            ]
+        );
+        assert_eq!(
+            unresolved[0].to_string(),
+            "counterparty 'CP002': counterparty 'CP002' not found in reference data \
+             (check counterparties.csv)"
+        );
+    }
+
+    #[test]
+    fn test_load_from_directory() {
+        let dir = std::env::temp_dir().join(format!("neutryx_refdata_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join(COUNTERPARTIES_FILE),
+            format!(
+                "counterparty_id,lei,legal_name,jurisdiction\n\
+                 CP001,{},Acme Corporation,US\n\
+                 CP002,{},,\n",
+                ACME_LEI, GLOBEX_LEI
+            ),
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join(BOOKING_ENTITIES_FILE),
+            format!("entity_id,lei,name\nBANK-LDN,{},\n", BANK_LEI),
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join(NETTING_AGREEMENTS_FILE),
+            "netting_set_id,counterparty_id,agreement_id,booking_entity,enforceable,csa_id\n\
+             NS001,CP001,ISDA-1,BANK-LDN,true,CSA-1\n\
+             NS002,CP002,ISDA-2,BANK-LDN,false,\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join(CSAS_FILE),
+            "csa_id,threshold,minimum_transfer_amount,independent_amount,currency,margin_period_of_risk\n\
+             CSA-1,0,10000,0,EUR,5\n",
+        )
+        .unwrap();
+
+        let data = ReferenceData::from_directory(&dir).unwrap();
+        let enriched = TradeEnricher::new(&data).enrich(&portfolio()).unwrap();
+        let ns1 = enriched.netting_set(&"NS001".into()).unwrap();
+        assert_eq!(ns1.collateral().unwrap().currency(), Currency::EUR);
+        assert!(enriched
+            .counterparty(&"CP002".into())
+            .unwrap()
+            .jurisdiction()
+            .is_none());
+
+        std::fs::write(
+            dir.join(COUNTERPARTIES_FILE),
+            "counterparty_id,lei\nCP001,NOT-AN-LEI\n",
+        )
+        .unwrap();
+        assert!(matches!(
+            ReferenceData::from_directory(&dir),
+            Err(LoaderError::InvalidFormat { row: 1, .. })
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
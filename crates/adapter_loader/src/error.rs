@@ -24,4 +24,33 @@ pub enum LoaderError {
     /// File not found
     #[error("File not found: {0}")]
     FileNotFound(String),
+
+    /// File format not supported by this build
+    #[error("Unsupported file format: {0}")]
+    UnsupportedFormat(String),
+
+    /// JSON parsing error
+    #[cfg(feature = "json")]
+    #[error("JSON parsing error: {0}")]
+    JsonError(#[from] serde_json::Error),
+
+    /// Field-level validation failures
+    #[cfg(feature = "json")]
+    #[error("Validation failed with {} error(s): {}", .0.len(), .0.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+    Validation(Vec<crate::trade_json::FieldError>),
+
+    /// References that could not be resolved against reference data
+    #[cfg(feature = "json")]
+    #[error("{} unresolved reference(s): {}", .0.len(), .0.iter().map(ToString::to_string).collect::<Vec<_>>().join("; "))]
+    UnresolvedReferences(Vec<crate::enrichment::UnresolvedReference>),
+
+    /// Portfolio construction error
+    #[cfg(feature = "json")]
+    #[error("Portfolio error: {0}")]
+    PortfolioError(#[from] pricer_risk::portfolio::PortfolioError),
+
+    /// Parquet reading error
+    #[cfg(feature = "parquet")]
+    #[error("Parquet error: {0}")]
+    ParquetError(#[from] parquet::errors::ParquetError),
 }
@@ -3,13 +3,20 @@
 //! Flat file loaders (CSV/Parquet) and CSA details for Neutryx.
 //!
 //! This crate handles bulk loading of CSV, JSON, or Parquet files,
-//! and manages CSA (Credit Support Annex) terms, counterparty details,
-//! and netting set configurations.
+//! reads portfolio snapshots for reconciliation, and manages CSA
+//! (Credit Support Annex) terms, counterparty details, and netting set
+//! configurations.
 //!
 //! ## Architecture Position
 //!
 //! Part of the **A**dapter layer in the A-I-P-S architecture.
-//! Depends only on `pricer_core` (for types) and `infra_master` (for identifiers).
+//! Depends on `pricer_core` (for types) and `infra_master` (for identifiers);
+//! the `json` feature adds `pricer_models` and `pricer_risk` so the canonical
+//! trade schema can deserialise straight into portfolio trades, and a
+//! [`MarketDataCatalog`] can be checked against a portfolio's market data
+//! requirements before a run.
+//! A [`TradeEnricher`] attaches counterparty LEIs, netting agreements, CSAs
+//! and booking entities from a [`ReferenceDataSource`] to a loaded portfolio.
 //!
 //! ## Example
 //!
@@ -21,13 +28,42 @@
 
 mod csa;
 mod csv_loader;
+#[cfg(feature = "json")]
+mod enrichment;
 mod error;
+#[cfg(feature = "json")]
+mod market_data;
+mod snapshot;
+#[cfg(feature = "json")]
+mod trade_json;
 
 pub use csa::{CsaTerms, NettingSetConfig};
-pub use csv_loader::CsvLoader;
+pub use csv_loader::{CsvLoader, CsvRecord};
+#[cfg(feature = "json")]
+pub use enrichment::{
+    is_valid_lei, BookingEntityReference, CounterpartyReference, NettingAgreementReference,
+    ReferenceData, ReferenceDataSource, ReferenceKind, TradeEnricher, UnresolvedReference,
+    BOOKING_ENTITIES_FILE, COUNTERPARTIES_FILE, CSAS_FILE, NETTING_AGREEMENTS_FILE,
+};
 pub use error::LoaderError;
+#[cfg(feature = "json")]
+pub use market_data::MarketDataCatalog;
+pub use snapshot::{PortfolioSnapshot, SnapshotTrade, REQUIRED_COLUMNS};
+#[cfg(feature = "json")]
+pub use trade_json::{
+    CounterpartyRecord, FieldError, ForwardDirection, NettingSetRecord, OptionExercise,
+    OptionPayoff, ProductRecord, SwapFrequency, TradeDocument, TradeRecord, TRADE_SCHEMA_VERSION,
+};
 
 /// Prelude module for convenient imports
 pub mod prelude {
-    pub use crate::{CsaTerms, CsvLoader, LoaderError, NettingSetConfig};
+    pub use crate::{
+        CsaTerms, CsvLoader, CsvRecord, LoaderError, NettingSetConfig, PortfolioSnapshot,
+        SnapshotTrade,
+    };
+    #[cfg(feature = "json")]
+    pub use crate::{
+        FieldError, MarketDataCatalog, ReferenceData, ReferenceDataSource, TradeDocument,
+        TradeEnricher,
+    };
 }
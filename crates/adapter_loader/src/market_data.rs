@@ -0,0 +1,187 @@
+//! Catalogue of available market data, checked against portfolio needs.
+//!
+//! A [`MarketDataCatalog`] lists the market data a source can supply, by
+//! the canonical keys of [`MarketDataRequirement`] (`curve/USD/discount`,
+//! `vol/EUR`, `fx/EUR/USD`, `fixing/USD`, `credit/CP001`) and the years of
+//! coverage each entry has. Before a run, the catalogue is checked against
+//! [`Portfolio::market_data_requirements`](pricer_risk::portfolio::Portfolio::market_data_requirements)
+//! so that only the required entries are fetched and any gap fails the run
+//! up front rather than mid-valuation. The catalogue is a
+//! [`MarketSnapshot`], so a
+//! [`MarketDataPolicy`](pricer_risk::portfolio::MarketDataPolicy) can fill
+//! gaps instead of failing.
+//!
+//! The CSV form has a `key` column and an optional `horizon` column; an
+//! empty horizon means unlimited coverage.
+//!
+//! ```text
+//! key,horizon
+//! curve/USD/discount,50
+//! vol/USD,10
+//! fx/EUR/USD,
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use pricer_risk::portfolio::{MarketDataRequirement, MarketDataRequirements, MarketSnapshot};
+
+use crate::error::LoaderError;
+
+/// Available market data keyed by requirement key.
+#[derive(Debug, Clone, Default)]
+pub struct MarketDataCatalog {
+    entries: BTreeMap<String, Option<f64>>,
+}
+
+impl MarketDataCatalog {
+    /// Create an empty catalogue.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an entry with `horizon` years of coverage (`None` for unlimited).
+    pub fn with_entry(mut self, key: impl Into<String>, horizon: Option<f64>) -> Self {
+        self.entries.insert(key.into(), horizon);
+        self
+    }
+
+    /// Load a catalogue from a CSV file with a header row.
+    pub fn load_csv<P: AsRef<Path>>(path: P) -> Result<Self, LoaderError> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Err(LoaderError::FileNotFound(path.display().to_string()));
+        }
+
+        let mut reader = csv::Reader::from_path(path)?;
+        let headers: Vec<String> = reader
+            .headers()?
+            .iter()
+            .map(|h| h.trim().to_string())
+            .collect();
+        let column = |name: &str| headers.iter().position(|h| h == name);
+        let key_column =
+            column("key").ok_or_else(|| LoaderError::MissingColumn("key".to_string()))?;
+        let horizon_column = column("horizon");
+
+        let mut catalog = Self::new();
+        for (idx, result) in reader.records().enumerate() {
+            let record = result?;
+            let key = record.get(key_column).unwrap_or("").trim();
+            if key.is_empty() {
+                return Err(LoaderError::InvalidFormat {
+                    row: idx + 1,
+                    message: "empty key".to_string(),
+                });
+            }
+            let horizon = match horizon_column.and_then(|c| record.get(c)).map(str::trim) {
+                None | Some("") => None,
+                Some(value) => {
+                    Some(
+                        value
+                            .parse::<f64>()
+                            .map_err(|_| LoaderError::InvalidFormat {
+                                row: idx + 1,
+                                message: format!("invalid horizon '{}'", value),
+                            })?,
+                    )
+                }
+            };
+            catalog.entries.insert(key.to_string(), horizon);
+        }
+
+        Ok(catalog)
+    }
+
+    /// Number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if the catalogue has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Keys to fetch for a run: the required entries, ordered by key.
+    ///
+    /// Fails fast, naming every gap, if anything required is not covered.
+    pub fn select(
+        &self,
+        requirements: &MarketDataRequirements,
+    ) -> Result<Vec<String>, LoaderError> {
+        requirements.ensure(|r| self.covers(r))?;
+        Ok(requirements
+            .iter()
+            .map(MarketDataRequirement::key)
+            .collect())
+    }
+}
+
+impl MarketSnapshot for MarketDataCatalog {
+    fn coverage(&self, key: &str) -> Option<f64> {
+        self.entries
+            .get(key)
+            .map(|horizon| horizon.unwrap_or(f64::INFINITY))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pricer_core::types::Currency;
+    use pricer_risk::portfolio::CurveRole;
+
+    fn requirements() -> MarketDataRequirements {
+        let mut requirements = MarketDataRequirements::new(Currency::USD);
+        requirements.add(MarketDataRequirement::Curve {
+            currency: Currency::EUR,
+            role: CurveRole::Discount,
+            max_tenor: 10.0,
+        });
+        requirements.add(MarketDataRequirement::FxPair {
+            base: Currency::EUR,
+            quote: Currency::USD,
+        });
+        requirements
+    }
+
+    #[test]
+    fn test_select_required_keys() {
+        let catalog = MarketDataCatalog::new()
+            .with_entry("curve/EUR/discount", Some(30.0))
+            .with_entry("curve/GBP/discount", Some(30.0))
+            .with_entry("fx/EUR/USD", None);
+
+        let keys = catalog.select(&requirements()).unwrap();
+        assert_eq!(keys, vec!["curve/EUR/discount", "fx/EUR/USD"]);
+    }
+
+    #[test]
+    fn test_short_horizon_is_a_gap() {
+        let catalog = MarketDataCatalog::new()
+            .with_entry("curve/EUR/discount", Some(5.0))
+            .with_entry("fx/EUR/USD", None);
+
+        let err = catalog.select(&requirements()).unwrap_err();
+        assert!(err.to_string().contains("curve/EUR/discount (10Y)"));
+    }
+
+    #[test]
+    fn test_load_csv() {
+        let dir = std::env::temp_dir().join("adapter_loader_market_data_catalog");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("catalog.csv");
+        std::fs::write(&path, "key,horizon\ncurve/EUR/discount,30\nfx/EUR/USD,\n").unwrap();
+
+        let catalog = MarketDataCatalog::load_csv(&path).unwrap();
+        assert_eq!(catalog.len(), 2);
+        assert!(catalog.select(&requirements()).is_ok());
+
+        std::fs::write(&path, "key,horizon\ncurve/EUR/discount,long\n").unwrap();
+        assert!(matches!(
+            MarketDataCatalog::load_csv(&path),
+            Err(LoaderError::InvalidFormat { row: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,295 @@
+//! Portfolio snapshot loader.
+//!
+//! Loads a flat portfolio snapshot (one row per trade) from CSV or, with the
+//! `parquet` feature, from Parquet. The common trade columns are mapped to
+//! typed fields; any product-specific columns are kept as string attributes
+//! so snapshots of mixed books can be compared field by field.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::error::LoaderError;
+
+/// Columns every snapshot must provide.
+pub const REQUIRED_COLUMNS: [&str; 8] = [
+    "trade_id",
+    "instrument_type",
+    "counterparty_id",
+    "netting_set_id",
+    "notional",
+    "currency",
+    "trade_date",
+    "maturity_date",
+];
+
+/// A single trade row in a portfolio snapshot.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotTrade {
+    /// Trade identifier
+    pub trade_id: String,
+    /// Instrument type (e.g. "InterestRateSwap")
+    pub instrument_type: String,
+    /// Counterparty identifier
+    pub counterparty_id: String,
+    /// Netting set identifier
+    pub netting_set_id: String,
+    /// Notional amount
+    pub notional: f64,
+    /// Trade currency (ISO code)
+    pub currency: String,
+    /// Trade date (as booked)
+    pub trade_date: String,
+    /// Maturity date (as booked)
+    pub maturity_date: String,
+    /// Product-specific columns (empty values omitted)
+    pub attributes: BTreeMap<String, String>,
+}
+
+impl SnapshotTrade {
+    /// Get a product-specific attribute.
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes.get(name).map(|s| s.as_str())
+    }
+
+    /// Build a trade from column name/value pairs.
+    fn from_columns(
+        row: usize,
+        mut columns: BTreeMap<String, String>,
+    ) -> Result<Self, LoaderError> {
+        let mut take = |name: &str| -> Result<String, LoaderError> {
+            columns
+                .remove(name)
+                .ok_or_else(|| LoaderError::MissingColumn(name.to_string()))
+        };
+
+        let trade_id = take("trade_id")?;
+        let instrument_type = take("instrument_type")?;
+        let counterparty_id = take("counterparty_id")?;
+        let netting_set_id = take("netting_set_id")?;
+        let notional_raw = take("notional")?;
+        let currency = take("currency")?;
+        let trade_date = take("trade_date")?;
+        let maturity_date = take("maturity_date")?;
+
+        if trade_id.is_empty() {
+            return Err(LoaderError::InvalidFormat {
+                row,
+                message: "empty trade_id".to_string(),
+            });
+        }
+
+        let notional = notional_raw
+            .parse::<f64>()
+            .map_err(|_| LoaderError::InvalidFormat {
+                row,
+                message: format!("invalid notional '{}'", notional_raw),
+            })?;
+
+        columns.retain(|_, v| !v.is_empty());
+
+        Ok(Self {
+            trade_id,
+            instrument_type,
+            counterparty_id,
+            netting_set_id,
+            notional,
+            currency,
+            trade_date,
+            maturity_date,
+            attributes: columns,
+        })
+    }
+}
+
+/// A portfolio snapshot: the full trade population at a point in time.
+#[derive(Debug, Clone, Default)]
+pub struct PortfolioSnapshot {
+    trades: Vec<SnapshotTrade>,
+}
+
+impl PortfolioSnapshot {
+    /// Create a snapshot from trades.
+    pub fn new(trades: Vec<SnapshotTrade>) -> Self {
+        Self { trades }
+    }
+
+    /// Load a snapshot, choosing the reader from the file extension.
+    ///
+    /// `.parquet` files require the `parquet` feature; everything else is
+    /// read as CSV with a header row.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, LoaderError> {
+        let path = path.as_ref();
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("parquet") => Self::load_parquet(path),
+            _ => Self::load_csv(path),
+        }
+    }
+
+    /// Load a snapshot from a CSV file with a header row.
+    pub fn load_csv<P: AsRef<Path>>(path: P) -> Result<Self, LoaderError> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Err(LoaderError::FileNotFound(path.display().to_string()));
+        }
+
+        let mut reader = csv::Reader::from_path(path)?;
+        let headers: Vec<String> = reader
+            .headers()?
+            .iter()
+            .map(|h| h.trim().to_string())
+            .collect();
+        if let Some(missing) = REQUIRED_COLUMNS
+            .iter()
+            .find(|c| !headers.iter().any(|h| h == *c))
+        {
+            return Err(LoaderError::MissingColumn(missing.to_string()));
+        }
+
+        let mut trades = Vec::new();
+        for (idx, result) in reader.records().enumerate() {
+            let record = result?;
+            let columns = headers
+                .iter()
+                .cloned()
+                .zip(record.iter().map(|v| v.trim().to_string()))
+                .collect();
+            trades.push(SnapshotTrade::from_columns(idx + 1, columns)?);
+        }
+
+        Ok(Self { trades })
+    }
+
+    /// Load a snapshot from a Parquet file.
+    #[cfg(feature = "parquet")]
+    pub fn load_parquet<P: AsRef<Path>>(path: P) -> Result<Self, LoaderError> {
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+        use parquet::record::Field;
+
+        let path = path.as_ref();
+        if !path.exists() {
+            return Err(LoaderError::FileNotFound(path.display().to_string()));
+        }
+
+        let reader = SerializedFileReader::new(std::fs::File::open(path)?)?;
+        let mut trades = Vec::new();
+        for (idx, row) in reader.get_row_iter(None)?.enumerate() {
+            let row = row?;
+            let columns = row
+                .get_column_iter()
+                .map(|(name, field)| {
+                    let value = match field {
+                        Field::Null => String::new(),
+                        Field::Str(s) => s.clone(),
+                        other => other.to_string(),
+                    };
+                    (name.clone(), value)
+                })
+                .collect();
+            trades.push(SnapshotTrade::from_columns(idx + 1, columns)?);
+        }
+
+        Ok(Self { trades })
+    }
+
+    /// Load a snapshot from a Parquet file.
+    ///
+    /// Always fails: this build does not include the `parquet` feature.
+    #[cfg(not(feature = "parquet"))]
+    pub fn load_parquet<P: AsRef<Path>>(path: P) -> Result<Self, LoaderError> {
+        Err(LoaderError::UnsupportedFormat(format!(
+            "{} (rebuild with the `parquet` feature)",
+            path.as_ref().display()
+        )))
+    }
+
+    /// All trades in the snapshot.
+    pub fn trades(&self) -> &[SnapshotTrade] {
+        &self.trades
+    }
+
+    /// Number of trades.
+    pub fn len(&self) -> usize {
+        self.trades.len()
+    }
+
+    /// Whether the snapshot has no trades.
+    pub fn is_empty(&self) -> bool {
+        self.trades.is_empty()
+    }
+
+    /// Index trades by trade ID.
+    ///
+    /// Returns an error if the snapshot contains duplicate trade IDs.
+    pub fn by_trade_id(&self) -> Result<BTreeMap<&str, &SnapshotTrade>, LoaderError> {
+        let mut index = BTreeMap::new();
+        for (idx, trade) in self.trades.iter().enumerate() {
+            if index.insert(trade.trade_id.as_str(), trade).is_some() {
+                return Err(LoaderError::InvalidFormat {
+                    row: idx + 1,
+                    message: format!("duplicate trade_id '{}'", trade.trade_id),
+                });
+            }
+        }
+        Ok(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn write_csv(dir: &Path, name: &str, content: &str) -> std::path::PathBuf {
+        let path = dir.join(name);
+        let mut file = std::fs::File::create(&path).unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_load_csv_snapshot() {
+        let dir = std::env::temp_dir().join("adapter_loader_snapshot_csv");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = write_csv(
+            &dir,
+            "book.csv",
+            "trade_id,instrument_type,counterparty_id,netting_set_id,notional,currency,trade_date,maturity_date,fixed_rate,float_index\n\
+             IRS-1,InterestRateSwap,CP001,NS001,1000000,USD,2026-01-10,2031-01-10,0.0425,\n",
+        );
+
+        let snapshot = PortfolioSnapshot::load(&path).unwrap();
+        assert_eq!(snapshot.len(), 1);
+        let trade = &snapshot.trades()[0];
+        assert_eq!(trade.trade_id, "IRS-1");
+        assert_eq!(trade.notional, 1_000_000.0);
+        assert_eq!(trade.attribute("fixed_rate"), Some("0.0425"));
+        assert_eq!(trade.attribute("float_index"), None);
+    }
+
+    #[test]
+    fn test_load_csv_missing_column() {
+        let dir = std::env::temp_dir().join("adapter_loader_snapshot_missing");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = write_csv(&dir, "book.csv", "trade_id,notional\nT1,100\n");
+
+        let result = PortfolioSnapshot::load(&path);
+        assert!(matches!(result, Err(LoaderError::MissingColumn(_))));
+    }
+
+    #[test]
+    fn test_duplicate_trade_ids_rejected() {
+        let trade = SnapshotTrade {
+            trade_id: "T1".to_string(),
+            instrument_type: "InterestRateSwap".to_string(),
+            counterparty_id: "CP001".to_string(),
+            netting_set_id: "NS001".to_string(),
+            notional: 1.0,
+            currency: "USD".to_string(),
+            trade_date: String::new(),
+            maturity_date: String::new(),
+            attributes: BTreeMap::new(),
+        };
+        let snapshot = PortfolioSnapshot::new(vec![trade.clone(), trade]);
+        assert!(snapshot.by_trade_id().is_err());
+    }
+}
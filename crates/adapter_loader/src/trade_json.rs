@@ -0,0 +1,819 @@
+//! Canonical JSON trade schema.
+//!
+//! Defines the versioned JSON document used to exchange portfolios between
+//! Neutryx services and deserialises it into [`Trade`]s and a [`Portfolio`].
+//! Parsing happens in two stages: serde enforces the document shape (unknown
+//! fields, product types and enum values are rejected with a line/column),
+//! then every record is validated field by field so that *all* problems in a
+//! file are reported together rather than one per run.
+//!
+//! ## Schema (version 1)
+//!
+//! ```json
+//! {
+//!   "schema_version": 1,
+//!   "as_of": "2026-01-15",
+//!   "counterparties": [
+//!     { "id": "CP001", "name": "Acme Corp", "hazard_rate": 0.02, "lgd": 0.6 }
+//!   ],
+//!   "netting_sets": [
+//!     { "id": "NS001", "counterparty_id": "CP001" }
+//!   ],
+//!   "trades": [
+//!     {
+//!       "trade_id": "T001",
+//!       "counterparty_id": "CP001",
+//!       "netting_set_id": "NS001",
+//!       "currency": "USD",
+//!       "notional": 1000000.0,
+//!       "trade_date": "2026-01-15",
+//!       "maturity_date": "2027-01-15",
+//!       "product": { "type": "vanilla_option", "strike": 100.0, "payoff": "call" }
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! Dates are ISO 8601 (`YYYY-MM-DD`). Instrument times are Act/365 year
+//! fractions measured from `as_of`, or from each trade's `trade_date` when
+//! `as_of` is omitted; swap payment dates roll forward from that anchor with
+//! any short stub at the end. Supported products are `vanilla_option`
+//! (`payoff`: `call`/`put`, `exercise`: `european`/`american`), `forward`
+//! (`direction`: `long`/`short`) and `swap` (`fixed_rate`, `frequency`:
+//! `annual`/`semi_annual`/`quarterly`/`monthly`).
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::path::Path;
+
+use pricer_core::types::time::{Date, DayCountConvention};
+use pricer_core::types::Currency;
+use pricer_models::instruments::{
+    Direction, ExerciseStyle, Forward, Instrument, InstrumentParams, PaymentFrequency, PayoffType,
+    Swap, VanillaOption,
+};
+use pricer_models::schedules::{Frequency, ScheduleBuilder};
+use pricer_risk::portfolio::{
+    Counterparty, CounterpartyId, CreditParams, NettingSet, NettingSetId, Portfolio,
+    PortfolioBuilder, Trade, TradeId,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::error::LoaderError;
+
+/// Current version of the trade schema.
+pub const TRADE_SCHEMA_VERSION: u32 = 1;
+
+/// Smoothing epsilon used for option payoffs built from the schema.
+const PAYOFF_SMOOTHING: f64 = 1e-6;
+
+/// A single validation failure, located by a JSON path.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldError {
+    /// Path to the offending field (e.g. `trades[2].notional`)
+    pub path: String,
+    /// Human-readable description of the problem
+    pub message: String,
+}
+
+impl FieldError {
+    fn new(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// Top-level trade document.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TradeDocument {
+    /// Schema version; must equal [`TRADE_SCHEMA_VERSION`]
+    pub schema_version: u32,
+    /// Valuation date from which instrument times are measured
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub as_of: Option<String>,
+    /// Counterparty records
+    #[serde(default)]
+    pub counterparties: Vec<CounterpartyRecord>,
+    /// Netting set records
+    #[serde(default)]
+    pub netting_sets: Vec<NettingSetRecord>,
+    /// Trade records
+    pub trades: Vec<TradeRecord>,
+}
+
+/// Counterparty record.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CounterpartyRecord {
+    /// Counterparty identifier
+    pub id: String,
+    /// Legal name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Constant hazard rate
+    pub hazard_rate: f64,
+    /// Loss given default in [0, 1]
+    pub lgd: f64,
+}
+
+/// Netting set record.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NettingSetRecord {
+    /// Netting set identifier
+    pub id: String,
+    /// Owning counterparty
+    pub counterparty_id: String,
+}
+
+/// Trade record.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TradeRecord {
+    /// Trade identifier
+    pub trade_id: String,
+    /// Counterparty identifier
+    pub counterparty_id: String,
+    /// Netting set identifier
+    pub netting_set_id: String,
+    /// Trade currency (ISO 4217)
+    pub currency: String,
+    /// Notional amount
+    pub notional: f64,
+    /// Trade date (`YYYY-MM-DD`)
+    pub trade_date: String,
+    /// Maturity date (`YYYY-MM-DD`)
+    pub maturity_date: String,
+    /// Product terms
+    pub product: ProductRecord,
+}
+
+/// Product terms, tagged by `type`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum ProductRecord {
+    /// Vanilla option on a single underlying
+    VanillaOption {
+        /// Strike price
+        strike: f64,
+        /// Call or put
+        payoff: OptionPayoff,
+        /// Exercise style
+        #[serde(default)]
+        exercise: OptionExercise,
+    },
+    /// Outright forward
+    Forward {
+        /// Forward price
+        strike: f64,
+        /// Long or short
+        direction: ForwardDirection,
+    },
+    /// Fixed-for-floating swap
+    Swap {
+        /// Fixed leg rate (decimal)
+        fixed_rate: f64,
+        /// Fixed leg payment frequency
+        frequency: SwapFrequency,
+    },
+}
+
+/// Option payoff in the schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OptionPayoff {
+    /// Call option
+    Call,
+    /// Put option
+    Put,
+}
+
+/// Option exercise style in the schema.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OptionExercise {
+    /// Exercise at expiry only
+    #[default]
+    European,
+    /// Exercise at any time up to expiry
+    American,
+}
+
+/// Forward direction in the schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ForwardDirection {
+    /// Buy the underlying
+    Long,
+    /// Sell the underlying
+    Short,
+}
+
+/// Swap payment frequency in the schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SwapFrequency {
+    /// Once per year
+    Annual,
+    /// Twice per year
+    SemiAnnual,
+    /// Four times per year
+    Quarterly,
+    /// Twelve times per year
+    Monthly,
+}
+
+impl From<SwapFrequency> for Frequency {
+    fn from(frequency: SwapFrequency) -> Self {
+        match frequency {
+            SwapFrequency::Annual => Frequency::Annual,
+            SwapFrequency::SemiAnnual => Frequency::SemiAnnual,
+            SwapFrequency::Quarterly => Frequency::Quarterly,
+            SwapFrequency::Monthly => Frequency::Monthly,
+        }
+    }
+}
+
+impl From<SwapFrequency> for PaymentFrequency {
+    fn from(frequency: SwapFrequency) -> Self {
+        match frequency {
+            SwapFrequency::Annual => PaymentFrequency::Annual,
+            SwapFrequency::SemiAnnual => PaymentFrequency::SemiAnnual,
+            SwapFrequency::Quarterly => PaymentFrequency::Quarterly,
+            SwapFrequency::Monthly => PaymentFrequency::Monthly,
+        }
+    }
+}
+
+impl TradeDocument {
+    /// Parse a trade document from a JSON string.
+    ///
+    /// Only the document shape is checked here; use [`validate`](Self::validate),
+    /// [`trades`](Self::trades) or [`portfolio`](Self::portfolio) for the
+    /// field-level checks.
+    pub fn from_json(json: &str) -> Result<Self, LoaderError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Load a trade document from a JSON file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, LoaderError> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Err(LoaderError::FileNotFound(path.display().to_string()));
+        }
+        Self::from_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// Serialise the document as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String, LoaderError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Validate every field and reference in the document.
+    ///
+    /// Returns all problems found, in document order; an empty vector means
+    /// [`portfolio`](Self::portfolio) will succeed.
+    pub fn validate(&self) -> Vec<FieldError> {
+        let (_, mut errors) = self.convert_trades();
+        errors.extend(self.check_references());
+        errors
+    }
+
+    /// Convert the trade records into typed trades.
+    ///
+    /// Counterparty and netting set references are not checked, so trades-only
+    /// documents are accepted.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LoaderError::Validation`] listing every invalid field.
+    pub fn trades(&self) -> Result<Vec<Trade>, LoaderError> {
+        let (trades, errors) = self.convert_trades();
+        if errors.is_empty() {
+            Ok(trades)
+        } else {
+            Err(LoaderError::Validation(errors))
+        }
+    }
+
+    /// Build a validated portfolio from the document.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`LoaderError::Validation`] listing every invalid field or
+    /// dangling reference.
+    pub fn portfolio(&self) -> Result<Portfolio, LoaderError> {
+        let (trades, mut errors) = self.convert_trades();
+        errors.extend(self.check_references());
+        if !errors.is_empty() {
+            return Err(LoaderError::Validation(errors));
+        }
+
+        let counterparties = self.counterparties.iter().map(|record| {
+            // Credit parameters were checked by `check_references`
+            let params = CreditParams::new(record.hazard_rate, record.lgd)
+                .expect("credit parameters validated");
+            let counterparty = Counterparty::new(CounterpartyId::new(&record.id), params);
+            match &record.name {
+                Some(name) => counterparty.with_name(name),
+                None => counterparty,
+            }
+        });
+        let netting_sets = self.netting_sets.iter().map(|record| {
+            let mut netting_set = NettingSet::new(
+                NettingSetId::new(&record.id),
+                CounterpartyId::new(&record.counterparty_id),
+            );
+            netting_set.add_trades(
+                trades
+                    .iter()
+                    .filter(|trade| trade.netting_set_id().as_str() == record.id)
+                    .map(|trade| trade.id().clone()),
+            );
+            netting_set
+        });
+
+        Ok(PortfolioBuilder::new()
+            .add_counterparties(counterparties)
+            .add_netting_sets(netting_sets)
+            .add_trades(trades)
+            .build()?)
+    }
+
+    /// Convert trade records, collecting field errors.
+    fn convert_trades(&self) -> (Vec<Trade>, Vec<FieldError>) {
+        let mut errors = Vec::new();
+        if self.schema_version != TRADE_SCHEMA_VERSION {
+            errors.push(FieldError::new(
+                "schema_version",
+                format!(
+                    "unsupported schema version {} (expected {})",
+                    self.schema_version, TRADE_SCHEMA_VERSION
+                ),
+            ));
+        }
+
+        let as_of = match &self.as_of {
+            Some(value) => match parse_date("as_of", value) {
+                Ok(date) => Some(date),
+                Err(err) => {
+                    errors.push(err);
+                    // Trade-level times cannot be computed without a valid anchor
+                    return (Vec::new(), errors);
+                }
+            },
+            None => None,
+        };
+
+        let mut seen = HashSet::new();
+        let mut trades = Vec::with_capacity(self.trades.len());
+        for (idx, record) in self.trades.iter().enumerate() {
+            let path = format!("trades[{}]", idx);
+            if !record.trade_id.is_empty() && !seen.insert(record.trade_id.as_str()) {
+                errors.push(FieldError::new(
+                    format!("{}.trade_id", path),
+                    format!("duplicate trade id '{}'", record.trade_id),
+                ));
+            }
+            match record.to_trade(&path, as_of) {
+                Ok(trade) => trades.push(trade),
+                Err(mut trade_errors) => errors.append(&mut trade_errors),
+            }
+        }
+        (trades, errors)
+    }
+
+    /// Check counterparty/netting set records and the references to them.
+    fn check_references(&self) -> Vec<FieldError> {
+        let mut errors = Vec::new();
+
+        let mut counterparties = HashSet::new();
+        for (idx, record) in self.counterparties.iter().enumerate() {
+            let path = format!("counterparties[{}]", idx);
+            if record.id.is_empty() {
+                errors.push(FieldError::new(format!("{}.id", path), "must not be empty"));
+            } else if !counterparties.insert(record.id.as_str()) {
+                errors.push(FieldError::new(
+                    format!("{}.id", path),
+                    format!("duplicate counterparty id '{}'", record.id),
+                ));
+            }
+            if !record.hazard_rate.is_finite() || record.hazard_rate < 0.0 {
+                errors.push(FieldError::new(
+                    format!("{}.hazard_rate", path),
+                    format!("must be non-negative, got {}", record.hazard_rate),
+                ));
+            }
+            if !(0.0..=1.0).contains(&record.lgd) {
+                errors.push(FieldError::new(
+                    format!("{}.lgd", path),
+                    format!("must be in [0, 1], got {}", record.lgd),
+                ));
+            }
+        }
+
+        let mut netting_sets: HashMap<&str, &str> = HashMap::new();
+        for (idx, record) in self.netting_sets.iter().enumerate() {
+            let path = format!("netting_sets[{}]", idx);
+            if record.id.is_empty() {
+                errors.push(FieldError::new(format!("{}.id", path), "must not be empty"));
+            } else if netting_sets
+                .insert(&record.id, &record.counterparty_id)
+                .is_some()
+            {
+                errors.push(FieldError::new(
+                    format!("{}.id", path),
+                    format!("duplicate netting set id '{}'", record.id),
+                ));
+            }
+            if !counterparties.contains(record.counterparty_id.as_str()) {
+                errors.push(FieldError::new(
+                    format!("{}.counterparty_id", path),
+                    format!("unknown counterparty '{}'", record.counterparty_id),
+                ));
+            }
+        }
+
+        for (idx, record) in self.trades.iter().enumerate() {
+            let path = format!("trades[{}]", idx);
+            if !counterparties.contains(record.counterparty_id.as_str()) {
+                errors.push(FieldError::new(
+                    format!("{}.counterparty_id", path),
+                    format!("unknown counterparty '{}'", record.counterparty_id),
+                ));
+            }
+            match netting_sets.get(record.netting_set_id.as_str()) {
+                None => errors.push(FieldError::new(
+                    format!("{}.netting_set_id", path),
+                    format!("unknown netting set '{}'", record.netting_set_id),
+                )),
+                Some(&owner) if owner != record.counterparty_id => errors.push(FieldError::new(
+                    format!("{}.netting_set_id", path),
+                    format!(
+                        "netting set '{}' belongs to counterparty '{}', not '{}'",
+                        record.netting_set_id, owner, record.counterparty_id
+                    ),
+                )),
+                Some(_) => {}
+            }
+        }
+
+        errors
+    }
+}
+
+impl TradeRecord {
+    /// Convert the record into a typed trade, collecting every field error.
+    fn to_trade(&self, path: &str, as_of: Option<Date>) -> Result<Trade, Vec<FieldError>> {
+        let mut errors = Vec::new();
+        let field = |name: &str| format!("{}.{}", path, name);
+
+        for (name, value) in [
+            ("trade_id", &self.trade_id),
+            ("counterparty_id", &self.counterparty_id),
+            ("netting_set_id", &self.netting_set_id),
+        ] {
+            if value.is_empty() {
+                errors.push(FieldError::new(field(name), "must not be empty"));
+            }
+        }
+
+        let currency = self
+            .currency
+            .parse::<Currency>()
+            .map_err(|_| {
+                errors.push(FieldError::new(
+                    field("currency"),
+                    format!("unknown currency '{}'", self.currency),
+                ))
+            })
+            .ok();
+
+        if !(self.notional.is_finite() && self.notional > 0.0) {
+            errors.push(FieldError::new(
+                field("notional"),
+                format!("must be positive, got {}", self.notional),
+            ));
+        }
+
+        let trade_date = parse_date(&field("trade_date"), &self.trade_date)
+            .map_err(|err| errors.push(err))
+            .ok();
+        let maturity_date = parse_date(&field("maturity_date"), &self.maturity_date)
+            .map_err(|err| errors.push(err))
+            .ok();
+
+        // Valuation anchor and maturity, once both are known to be usable
+        let dates = match (as_of.or(trade_date), maturity_date) {
+            (Some(start), Some(end)) if end <= start => {
+                errors.push(FieldError::new(
+                    field("maturity_date"),
+                    format!("must be after {}", start),
+                ));
+                None
+            }
+            (Some(start), Some(end)) => Some((start, end)),
+            _ => None,
+        };
+
+        match &self.product {
+            ProductRecord::VanillaOption { strike, .. } | ProductRecord::Forward { strike, .. } => {
+                if !(strike.is_finite() && *strike > 0.0) {
+                    errors.push(FieldError::new(
+                        field("product.strike"),
+                        format!("must be positive, got {}", strike),
+                    ));
+                }
+            }
+            ProductRecord::Swap { fixed_rate, .. } => {
+                if !fixed_rate.is_finite() {
+                    errors.push(FieldError::new(
+                        field("product.fixed_rate"),
+                        format!("must be finite, got {}", fixed_rate),
+                    ));
+                }
+            }
+        }
+
+        let (Some(currency), Some((start, end)), true) = (currency, dates, errors.is_empty())
+        else {
+            return Err(errors);
+        };
+
+        let instrument = self
+            .product
+            .to_instrument(start, end, self.notional, currency)
+            .map_err(|message| vec![FieldError::new(field("product"), message)])?;
+
+        Ok(Trade::new(
+            TradeId::new(&self.trade_id),
+            instrument,
+            currency,
+            CounterpartyId::new(&self.counterparty_id),
+            NettingSetId::new(&self.netting_set_id),
+            self.notional,
+        ))
+    }
+}
+
+impl ProductRecord {
+    /// Build the pricing instrument for a product running from `start` to `end`.
+    fn to_instrument(
+        &self,
+        start: Date,
+        end: Date,
+        notional: f64,
+        currency: Currency,
+    ) -> Result<Instrument<f64>, String> {
+        let day_count = DayCountConvention::ActualActual365;
+        let expiry = day_count.year_fraction_dates(start, end);
+        let instrument = match *self {
+            ProductRecord::VanillaOption {
+                strike,
+                payoff,
+                exercise,
+            } => {
+                let params =
+                    InstrumentParams::new(strike, expiry, notional).map_err(|e| e.to_string())?;
+                let payoff = match payoff {
+                    OptionPayoff::Call => PayoffType::Call,
+                    OptionPayoff::Put => PayoffType::Put,
+                };
+                let exercise = match exercise {
+                    OptionExercise::European => ExerciseStyle::European,
+                    OptionExercise::American => ExerciseStyle::American,
+                };
+                Instrument::Vanilla(VanillaOption::new(
+                    params,
+                    payoff,
+                    exercise,
+                    PAYOFF_SMOOTHING,
+                ))
+            }
+            ProductRecord::Forward { strike, direction } => {
+                let direction = match direction {
+                    ForwardDirection::Long => Direction::Long,
+                    ForwardDirection::Short => Direction::Short,
+                };
+                Instrument::Forward(
+                    Forward::new(strike, expiry, notional, direction).map_err(|e| e.to_string())?,
+                )
+            }
+            ProductRecord::Swap {
+                fixed_rate,
+                frequency,
+            } => {
+                let schedule = ScheduleBuilder::new()
+                    .start(start)
+                    .end(end)
+                    .frequency(frequency.into())
+                    .day_count(day_count)
+                    .build()
+                    .map_err(|e| e.to_string())?;
+                let payment_dates = schedule
+                    .payment_dates()
+                    .iter()
+                    .map(|&date| day_count.year_fraction_dates(start, date))
+                    .collect();
+                let frequency = PaymentFrequency::from(frequency);
+                Instrument::Swap(
+                    Swap::new(notional, fixed_rate, payment_dates, frequency, currency)
+                        .map_err(|e| e.to_string())?,
+                )
+            }
+        };
+        Ok(instrument)
+    }
+}
+
+fn parse_date(path: &str, value: &str) -> Result<Date, FieldError> {
+    Date::parse(value).map_err(|_| {
+        FieldError::new(
+            path,
+            format!("invalid date '{}' (expected YYYY-MM-DD)", value),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALID: &str = r#"{
+        "schema_version": 1,
+        "as_of": "2026-01-15",
+        "counterparties": [
+            { "id": "CP001", "name": "Acme Corp", "hazard_rate": 0.02, "lgd": 0.6 }
+        ],
+        "netting_sets": [
+            { "id": "NS001", "counterparty_id": "CP001" }
+        ],
+        "trades": [
+            {
+                "trade_id": "T001",
+                "counterparty_id": "CP001",
+                "netting_set_id": "NS001",
+                "currency": "USD",
+                "notional": 1000000.0,
+                "trade_date": "2026-01-15",
+                "maturity_date": "2027-01-15",
+                "product": { "type": "vanilla_option", "strike": 100.0, "payoff": "call" }
+            },
+            {
+                "trade_id": "T002",
+                "counterparty_id": "CP001",
+                "netting_set_id": "NS001",
+                "currency": "EUR",
+                "notional": 5000000.0,
+                "trade_date": "2026-01-15",
+                "maturity_date": "2031-01-15",
+                "product": { "type": "swap", "fixed_rate": 0.025, "frequency": "semi_annual" }
+            }
+        ]
+    }"#;
+
+    fn document() -> TradeDocument {
+        TradeDocument::from_json(VALID).unwrap()
+    }
+
+    fn paths(errors: &[FieldError]) -> Vec<&str> {
+        errors.iter().map(|e| e.path.as_str()).collect()
+    }
+
+    #[test]
+    fn test_valid_document_builds_portfolio() {
+        let doc = document();
+        assert!(doc.validate().is_empty());
+
+        let portfolio = doc.portfolio().unwrap();
+        assert_eq!(portfolio.trade_count(), 2);
+        assert_eq!(portfolio.counterparty_count(), 1);
+
+        let netting_set = portfolio.netting_set(&NettingSetId::new("NS001")).unwrap();
+        assert_eq!(netting_set.trade_count(), 2);
+
+        let option = portfolio.trade(&TradeId::new("T001")).unwrap();
+        assert_eq!(option.currency(), Currency::USD);
+        match option.instrument() {
+            Instrument::Vanilla(v) => {
+                assert_eq!(v.params().strike(), 100.0);
+                assert!((v.params().expiry() - 1.0).abs() < 1e-12);
+            }
+            other => panic!("expected vanilla option, got {:?}", other),
+        }
+
+        match portfolio.trade(&TradeId::new("T002")).unwrap().instrument() {
+            Instrument::Swap(s) => {
+                assert_eq!(s.num_periods(), 10);
+                assert_eq!(s.currency(), Currency::EUR);
+            }
+            other => panic!("expected swap, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_per_field_errors_are_collected() {
+        let mut doc = document();
+        doc.trades[0].notional = -1.0;
+        doc.trades[0].currency = "XYZ".to_string();
+        doc.trades[1].maturity_date = "2031-13-40".to_string();
+
+        let errors = doc.validate();
+        assert_eq!(
+            paths(&errors),
+            [
+                "trades[0].currency",
+                "trades[0].notional",
+                "trades[1].maturity_date"
+            ]
+        );
+        assert_eq!(errors[0].message, "unknown currency 'XYZ'");
+        assert_eq!(errors[1].message, "must be positive, got -1");
+        assert!(errors[2].message.contains("invalid date '2031-13-40'"));
+
+        match doc.trades() {
+            Err(LoaderError::Validation(errs)) => assert_eq!(errs, errors),
+            other => panic!("expected validation error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_maturity_must_follow_as_of() {
+        let mut doc = document();
+        doc.trades[0].maturity_date = "2025-06-30".to_string();
+        let errors = doc.validate();
+        assert_eq!(paths(&errors), ["trades[0].maturity_date"]);
+        assert_eq!(errors[0].message, "must be after 2026-01-15");
+    }
+
+    #[test]
+    fn test_reference_errors() {
+        let mut doc = document();
+        doc.trades[1].counterparty_id = "CP999".to_string();
+        doc.trades[0].trade_id = "T002".to_string();
+        doc.counterparties[0].lgd = 1.5;
+
+        let errors = doc.validate();
+        assert_eq!(
+            paths(&errors),
+            [
+                "trades[1].trade_id",
+                "counterparties[0].lgd",
+                "trades[1].counterparty_id",
+                "trades[1].netting_set_id"
+            ]
+        );
+        assert!(errors[3]
+            .message
+            .contains("belongs to counterparty 'CP001'"));
+        assert!(matches!(doc.portfolio(), Err(LoaderError::Validation(_))));
+    }
+
+    #[test]
+    fn test_trades_only_document() {
+        let mut doc = document();
+        doc.counterparties.clear();
+        doc.netting_sets.clear();
+        assert_eq!(doc.trades().unwrap().len(), 2);
+        assert!(!doc.validate().is_empty());
+    }
+
+    #[test]
+    fn test_unsupported_schema_version() {
+        let mut doc = document();
+        doc.schema_version = 2;
+        let errors = doc.validate();
+        assert_eq!(paths(&errors), ["schema_version"]);
+    }
+
+    #[test]
+    fn test_shape_errors_are_rejected_by_serde() {
+        let unknown_product = VALID.replace("\"vanilla_option\"", "\"barrier\"");
+        assert!(matches!(
+            TradeDocument::from_json(&unknown_product),
+            Err(LoaderError::JsonError(_))
+        ));
+
+        let unknown_field = VALID.replace("\"notional\": 1000000.0", "\"notionl\": 1000000.0");
+        assert!(matches!(
+            TradeDocument::from_json(&unknown_field),
+            Err(LoaderError::JsonError(_))
+        ));
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let doc = document();
+        let back = TradeDocument::from_json(&doc.to_json().unwrap()).unwrap();
+        assert_eq!(back, doc);
+    }
+}
@@ -0,0 +1,470 @@
+//! Calibration result store.
+//!
+//! Keeps one [`CalibrationRecord`] per model and date: the calibrated
+//! parameters and the quality of the fit. Records can be queried as a
+//! parameter history, and each new record is checked against the previous
+//! one for the same model so that parameters jumping beyond a
+//! [`DriftThreshold`] raise a [`DriftAlert`].
+//!
+//! A file-backed store appends each saved record as a JSON line; on open,
+//! a later line for the same model and date replaces an earlier one, so
+//! recalibrating a date overwrites it.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::error::StoreError;
+use crate::traits::{Load, Save};
+
+/// Quality of a calibration fit.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct FitQuality {
+    /// Root mean square pricing error
+    pub rmse: f64,
+    /// Largest absolute pricing error
+    pub max_error: f64,
+    /// Whether the optimiser converged
+    pub converged: bool,
+}
+
+/// Calibrated parameters of one model on one date.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CalibrationRecord {
+    /// Model name, e.g. `hull-white`
+    pub model: String,
+    /// Calibration date
+    pub date: NaiveDate,
+    /// Parameter values by name
+    pub parameters: BTreeMap<String, f64>,
+    /// Fit quality
+    pub fit: FitQuality,
+}
+
+impl CalibrationRecord {
+    /// Create a record with no parameters.
+    pub fn new(model: impl Into<String>, date: NaiveDate, fit: FitQuality) -> Self {
+        Self {
+            model: model.into(),
+            date,
+            parameters: BTreeMap::new(),
+            fit,
+        }
+    }
+
+    /// Add a parameter.
+    pub fn with_parameter(mut self, name: impl Into<String>, value: f64) -> Self {
+        self.parameters.insert(name.into(), value);
+        self
+    }
+
+    fn key(&self) -> (String, NaiveDate) {
+        (self.model.clone(), self.date)
+    }
+}
+
+/// Largest acceptable move of a parameter between calibrations.
+///
+/// A move breaches the threshold if it exceeds either bound that is set.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct DriftThreshold {
+    /// Largest absolute change
+    pub absolute: Option<f64>,
+    /// Largest change relative to the previous value
+    pub relative: Option<f64>,
+}
+
+impl DriftThreshold {
+    /// Threshold on the absolute change only.
+    pub fn absolute(limit: f64) -> Self {
+        Self {
+            absolute: Some(limit),
+            relative: None,
+        }
+    }
+
+    /// Threshold on the relative change only.
+    pub fn relative(limit: f64) -> Self {
+        Self {
+            absolute: None,
+            relative: Some(limit),
+        }
+    }
+
+    /// Whether a move from `previous` to `current` breaches the threshold.
+    pub fn is_breached(&self, previous: f64, current: f64) -> bool {
+        let change = (current - previous).abs();
+        let absolute = self.absolute.is_some_and(|limit| change > limit);
+        let relative = self
+            .relative
+            .is_some_and(|limit| previous != 0.0 && change / previous.abs() > limit);
+        absolute || relative
+    }
+}
+
+/// Drift thresholds, with overrides per parameter.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DriftConfig {
+    /// Threshold for parameters without an override
+    pub default: DriftThreshold,
+    /// Thresholds by parameter name
+    pub parameters: BTreeMap<String, DriftThreshold>,
+}
+
+impl Default for DriftConfig {
+    /// Alerts on moves of more than 25% of the previous value.
+    fn default() -> Self {
+        Self::new(DriftThreshold::relative(0.25))
+    }
+}
+
+impl DriftConfig {
+    /// Create a configuration with one threshold for every parameter.
+    pub fn new(default: DriftThreshold) -> Self {
+        Self {
+            default,
+            parameters: BTreeMap::new(),
+        }
+    }
+
+    /// Override the threshold of one parameter.
+    pub fn with_parameter(mut self, name: impl Into<String>, threshold: DriftThreshold) -> Self {
+        self.parameters.insert(name.into(), threshold);
+        self
+    }
+
+    /// Threshold applying to a parameter.
+    pub fn threshold(&self, name: &str) -> &DriftThreshold {
+        self.parameters.get(name).unwrap_or(&self.default)
+    }
+}
+
+/// A parameter that moved beyond its threshold.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DriftAlert {
+    /// Model name
+    pub model: String,
+    /// Parameter name
+    pub parameter: String,
+    /// Date of the previous calibration
+    pub previous_date: NaiveDate,
+    /// Date of the new calibration
+    pub date: NaiveDate,
+    /// Previous value
+    pub previous: f64,
+    /// New value
+    pub current: f64,
+}
+
+impl DriftAlert {
+    /// Change in the parameter.
+    pub fn change(&self) -> f64 {
+        self.current - self.previous
+    }
+
+    /// Change relative to the previous value.
+    pub fn relative_change(&self) -> f64 {
+        self.change() / self.previous.abs()
+    }
+}
+
+impl fmt::Display for DriftAlert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} moved from {} ({}) to {} ({}), {:+.1}%",
+            self.model,
+            self.parameter,
+            self.previous,
+            self.previous_date,
+            self.current,
+            self.date,
+            self.relative_change() * 100.0
+        )
+    }
+}
+
+/// Store of calibration records, in memory or backed by a JSON lines file.
+///
+/// # Example
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use infra_store::{CalibrationRecord, CalibrationStore, DriftConfig, FitQuality, Save};
+///
+/// let fit = FitQuality { rmse: 1e-4, max_error: 3e-4, converged: true };
+/// let day = |d| NaiveDate::from_ymd_opt(2024, 3, d).unwrap();
+/// let store = CalibrationStore::in_memory();
+///
+/// store
+///     .save(&CalibrationRecord::new("hull-white", day(1), fit).with_parameter("sigma", 0.010))
+///     .unwrap();
+/// let alerts = store
+///     .save_checked(
+///         &CalibrationRecord::new("hull-white", day(4), fit).with_parameter("sigma", 0.015),
+///         &DriftConfig::default(),
+///     )
+///     .unwrap();
+///
+/// assert_eq!(alerts.len(), 1); // sigma up 50%
+/// assert_eq!(store.history("hull-white", "sigma").len(), 2);
+/// ```
+#[derive(Debug, Default)]
+pub struct CalibrationStore {
+    path: Option<PathBuf>,
+    records: RwLock<BTreeMap<(String, NaiveDate), CalibrationRecord>>,
+}
+
+impl CalibrationStore {
+    /// Create an empty store held in memory.
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// Open a file-backed store, loading any records already in the file.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, StoreError> {
+        let path = path.as_ref().to_path_buf();
+        let mut records = BTreeMap::new();
+        if path.exists() {
+            let file = File::open(&path).map_err(|e| StoreError::QueryError(e.to_string()))?;
+            for (idx, line) in BufReader::new(file).lines().enumerate() {
+                let line = line.map_err(|e| StoreError::QueryError(e.to_string()))?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let record: CalibrationRecord = serde_json::from_str(&line).map_err(|e| {
+                    StoreError::SerialisationError(format!("line {}: {}", idx + 1, e))
+                })?;
+                records.insert(record.key(), record);
+            }
+        }
+        Ok(Self {
+            path: Some(path),
+            records: RwLock::new(records),
+        })
+    }
+
+    /// Number of records.
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    /// Returns true if the store has no records.
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    /// Names of the models with records.
+    pub fn models(&self) -> Vec<String> {
+        let mut models: Vec<String> = self.read().keys().map(|(m, _)| m.clone()).collect();
+        models.dedup();
+        models
+    }
+
+    /// Records of a model, oldest first.
+    pub fn records(&self, model: &str) -> Vec<CalibrationRecord> {
+        self.read()
+            .values()
+            .filter(|r| r.model == model)
+            .cloned()
+            .collect()
+    }
+
+    /// Most recent record of a model.
+    pub fn latest(&self, model: &str) -> Option<CalibrationRecord> {
+        self.read()
+            .values()
+            .rev()
+            .find(|r| r.model == model)
+            .cloned()
+    }
+
+    /// Most recent record of a model strictly before `date`.
+    pub fn previous(&self, model: &str, date: NaiveDate) -> Option<CalibrationRecord> {
+        self.read()
+            .values()
+            .rev()
+            .find(|r| r.model == model && r.date < date)
+            .cloned()
+    }
+
+    /// Values of one parameter of a model by date, oldest first.
+    pub fn history(&self, model: &str, parameter: &str) -> Vec<(NaiveDate, f64)> {
+        self.read()
+            .values()
+            .filter(|r| r.model == model)
+            .filter_map(|r| r.parameters.get(parameter).map(|&v| (r.date, v)))
+            .collect()
+    }
+
+    /// Parameters of `record` that moved beyond their threshold since the
+    /// previous calibration of the model.
+    ///
+    /// Parameters missing from either calibration are not compared.
+    pub fn drift(&self, record: &CalibrationRecord, config: &DriftConfig) -> Vec<DriftAlert> {
+        let Some(previous) = self.previous(&record.model, record.date) else {
+            return Vec::new();
+        };
+        record
+            .parameters
+            .iter()
+            .filter_map(|(name, &current)| {
+                let &before = previous.parameters.get(name)?;
+                config
+                    .threshold(name)
+                    .is_breached(before, current)
+                    .then(|| DriftAlert {
+                        model: record.model.clone(),
+                        parameter: name.clone(),
+                        previous_date: previous.date,
+                        date: record.date,
+                        previous: before,
+                        current,
+                    })
+            })
+            .collect()
+    }
+
+    /// Check a record for drift, then save it.
+    pub fn save_checked(
+        &self,
+        record: &CalibrationRecord,
+        config: &DriftConfig,
+    ) -> Result<Vec<DriftAlert>, StoreError> {
+        let alerts = self.drift(record, config);
+        self.save(record)?;
+        Ok(alerts)
+    }
+
+    fn read(
+        &self,
+    ) -> std::sync::RwLockReadGuard<'_, BTreeMap<(String, NaiveDate), CalibrationRecord>> {
+        self.records
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Save<CalibrationRecord> for CalibrationStore {
+    fn save(&self, record: &CalibrationRecord) -> Result<(), StoreError> {
+        if let Some(path) = &self.path {
+            let line = serde_json::to_string(record)
+                .map_err(|e| StoreError::SerialisationError(e.to_string()))?;
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(|e| StoreError::QueryError(e.to_string()))?;
+            writeln!(file, "{}", line).map_err(|e| StoreError::QueryError(e.to_string()))?;
+        }
+        self.records
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(record.key(), record.clone());
+        Ok(())
+    }
+}
+
+impl Load<CalibrationRecord, (String, NaiveDate)> for CalibrationStore {
+    fn load(&self, key: &(String, NaiveDate)) -> Result<Option<CalibrationRecord>, StoreError> {
+        Ok(self.read().get(key).cloned())
+    }
+
+    fn load_all(&self) -> Result<Vec<CalibrationRecord>, StoreError> {
+        Ok(self.read().values().cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIT: FitQuality = FitQuality {
+        rmse: 1e-4,
+        max_error: 2e-4,
+        converged: true,
+    };
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, d).unwrap()
+    }
+
+    fn record(d: u32, alpha: f64, sigma: f64) -> CalibrationRecord {
+        CalibrationRecord::new("hull-white", day(d), FIT)
+            .with_parameter("alpha", alpha)
+            .with_parameter("sigma", sigma)
+    }
+
+    #[test]
+    fn test_drift_against_previous_date() {
+        let store = CalibrationStore::in_memory();
+        store.save(&record(1, 0.05, 0.010)).unwrap();
+        store.save(&record(5, 0.05, 0.010)).unwrap();
+
+        // Backfilling 3 March compares against 1 March
+        let config = DriftConfig::new(DriftThreshold::relative(0.5))
+            .with_parameter("alpha", DriftThreshold::absolute(0.001));
+        let alerts = store.drift(&record(3, 0.052, 0.014), &config);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].parameter, "alpha");
+        assert_eq!(alerts[0].previous_date, day(1));
+
+        assert!(store
+            .drift(&CalibrationRecord::new("cir", day(3), FIT), &config)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_history_and_queries() {
+        let store = CalibrationStore::in_memory();
+        store.save(&record(4, 0.06, 0.011)).unwrap();
+        store.save(&record(1, 0.05, 0.010)).unwrap();
+        store
+            .save(&CalibrationRecord::new("cir", day(2), FIT).with_parameter("kappa", 0.1))
+            .unwrap();
+
+        assert_eq!(
+            store.history("hull-white", "alpha"),
+            vec![(day(1), 0.05), (day(4), 0.06)]
+        );
+        assert_eq!(store.models(), vec!["cir", "hull-white"]);
+        assert_eq!(store.latest("hull-white").unwrap().date, day(4));
+        assert_eq!(
+            store
+                .load(&("cir".to_string(), day(2)))
+                .unwrap()
+                .map(|r| r.parameters["kappa"]),
+            Some(0.1)
+        );
+    }
+
+    #[test]
+    fn test_file_store_round_trip() {
+        let dir = std::env::temp_dir().join("infra_store_calibration");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("calibrations.jsonl");
+        let _ = std::fs::remove_file(&path);
+
+        let store = CalibrationStore::open(&path).unwrap();
+        store.save(&record(1, 0.05, 0.010)).unwrap();
+        store.save(&record(1, 0.07, 0.010)).unwrap();
+        store.save(&record(2, 0.05, 0.012)).unwrap();
+
+        // The recalibration of 1 March replaces the first line
+        let reopened = CalibrationStore::open(&path).unwrap();
+        assert_eq!(reopened.len(), 2);
+        assert_eq!(reopened.history("hull-white", "alpha")[0], (day(1), 0.07));
+
+        std::fs::write(&path, "not json\n").unwrap();
+        assert!(matches!(
+            CalibrationStore::open(&path),
+            Err(StoreError::SerialisationError(_))
+        ));
+    }
+}
@@ -21,6 +21,10 @@ pub enum StoreError {
     #[error("Record not found: {0}")]
     NotFound(String),
 
+    /// Invalid result key
+    #[error("Invalid key: {0}")]
+    InvalidKey(String),
+
     /// Duplicate record
     #[error("Duplicate record: {0}")]
     Duplicate(String),
@@ -6,6 +6,17 @@
 //! traits for Trades and Risk Reports using `sqlx` (Postgres) or other backends.
 //! It isolates I/O dependencies from the kernel.
 //!
+//! [`CalibrationStore`] keeps calibrated model parameters by date, with
+//! parameter history queries and drift alerts between calibrations.
+//!
+//! [`MarketSnapshotStore`] keeps versioned market data snapshots by
+//! valuation date and recording time, so runs can use the market as seen
+//! at a given time or with later corrections applied.
+//!
+//! The [`storage`] module persists pricing runs, exposure profiles, XVA
+//! results and reports behind a [`ResultStore`] trait, with local
+//! Parquet, S3/GCS (`object-store` feature) and PostgreSQL backends.
+//!
 //! ## Architecture Position
 //!
 //! Part of the **I**nfra layer in the A-I-P-S architecture.
@@ -20,10 +31,18 @@
 //! store.save(&trade).await?;
 //! ```
 
+mod calibration;
 mod error;
+mod market;
+pub mod storage;
 mod traits;
 
+pub use calibration::{
+    CalibrationRecord, CalibrationStore, DriftAlert, DriftConfig, DriftThreshold, FitQuality,
+};
 pub use error::StoreError;
+pub use market::{MarketSnapshotRecord, MarketSnapshotStore, QuoteChange};
+pub use storage::{ResultFrame, ResultKey, ResultKind, ResultQuery, ResultStore};
 pub use traits::{Load, Save};
 
 #[cfg(feature = "postgres")]
@@ -34,7 +53,10 @@ pub use postgres::PostgresStore;
 
 /// Prelude module for convenient imports
 pub mod prelude {
-    pub use crate::{Load, Save, StoreError};
+    pub use crate::{
+        CalibrationStore, Load, MarketSnapshotStore, ResultFrame, ResultKey, ResultKind,
+        ResultQuery, ResultStore, Save, StoreError,
+    };
 
     #[cfg(feature = "postgres")]
     pub use crate::PostgresStore;
@@ -0,0 +1,426 @@
+//! Versioned market data snapshots.
+//!
+//! A [`MarketSnapshotRecord`] holds the market quotes of one valuation date
+//! (`as_of`), keyed by the canonical market data requirement keys
+//! (`curve/USD/discount`, `fx/EUR/USD`, ...). Snapshots are bitemporal:
+//! besides the date the market belongs to, each carries the time it was
+//! `recorded_at`, and a correction of the date is saved as a new version
+//! rather than replacing the old one. A run can then be repeated against
+//! the market as seen at a given time ([`MarketSnapshotStore::as_seen_at`],
+//! e.g. the 16:30 EOD cut) or against the corrected market
+//! ([`MarketSnapshotStore::latest`]), and P&L explain can attribute the
+//! difference to the [`QuoteChange`]s between two versions.
+//!
+//! A file-backed store appends each saved version as a JSON line; versions
+//! are never rewritten.
+
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::StoreError;
+use crate::traits::{Load, Save};
+
+/// Market quotes of one valuation date, as recorded at one time.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarketSnapshotRecord {
+    /// Valuation date the quotes belong to
+    pub as_of: NaiveDate,
+    /// Version of the date's snapshot, from 1
+    pub version: u32,
+    /// Time the version was recorded
+    pub recorded_at: DateTime<Utc>,
+    /// Quote values by market data key
+    pub quotes: BTreeMap<String, f64>,
+}
+
+impl MarketSnapshotRecord {
+    /// Create a first version with no quotes.
+    pub fn new(as_of: NaiveDate, recorded_at: DateTime<Utc>) -> Self {
+        Self {
+            as_of,
+            version: 1,
+            recorded_at,
+            quotes: BTreeMap::new(),
+        }
+    }
+
+    /// Add a quote.
+    pub fn with_quote(mut self, key: impl Into<String>, value: f64) -> Self {
+        self.quotes.insert(key.into(), value);
+        self
+    }
+
+    /// Value of a quote.
+    pub fn quote(&self, key: &str) -> Option<f64> {
+        self.quotes.get(key).copied()
+    }
+
+    /// Quotes that differ from an `earlier` snapshot, by key.
+    pub fn changes(&self, earlier: &MarketSnapshotRecord) -> Vec<QuoteChange> {
+        let mut keys: Vec<&String> = earlier.quotes.keys().chain(self.quotes.keys()).collect();
+        keys.sort();
+        keys.dedup();
+        keys.into_iter()
+            .filter_map(|key| {
+                let before = earlier.quote(key);
+                let after = self.quote(key);
+                (before != after).then(|| QuoteChange {
+                    key: key.clone(),
+                    before,
+                    after,
+                })
+            })
+            .collect()
+    }
+
+    fn key(&self) -> (NaiveDate, u32) {
+        (self.as_of, self.version)
+    }
+}
+
+/// A quote that differs between two snapshot versions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuoteChange {
+    /// Market data key
+    pub key: String,
+    /// Value in the earlier version, if quoted
+    pub before: Option<f64>,
+    /// Value in the later version, if quoted
+    pub after: Option<f64>,
+}
+
+impl QuoteChange {
+    /// Change in the value, if quoted in both versions.
+    pub fn change(&self) -> Option<f64> {
+        Some(self.after? - self.before?)
+    }
+}
+
+/// Store of market snapshot versions, in memory or backed by a JSON lines
+/// file.
+///
+/// # Example
+///
+/// ```
+/// use chrono::{NaiveDate, TimeZone, Utc};
+/// use infra_store::{MarketSnapshotRecord, MarketSnapshotStore};
+///
+/// let as_of = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+/// let at = |h, m| Utc.with_ymd_and_hms(2024, 3, 1, h, m, 0).unwrap();
+/// let store = MarketSnapshotStore::in_memory();
+///
+/// store
+///     .record(MarketSnapshotRecord::new(as_of, at(16, 30)).with_quote("fx/EUR/USD", 1.0850))
+///     .unwrap();
+/// store
+///     .record(MarketSnapshotRecord::new(as_of, at(18, 5)).with_quote("fx/EUR/USD", 1.0852))
+///     .unwrap();
+///
+/// let eod = store.as_seen_at(as_of, at(17, 0)).unwrap();
+/// let corrected = store.latest(as_of).unwrap();
+/// assert_eq!((eod.version, corrected.version), (1, 2));
+/// assert_eq!(corrected.changes(&eod).len(), 1);
+/// ```
+#[derive(Debug, Default)]
+pub struct MarketSnapshotStore {
+    path: Option<PathBuf>,
+    records: RwLock<BTreeMap<(NaiveDate, u32), MarketSnapshotRecord>>,
+}
+
+impl MarketSnapshotStore {
+    /// Create an empty store held in memory.
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// Open a file-backed store, loading any versions already in the file.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, StoreError> {
+        let path = path.as_ref().to_path_buf();
+        let mut records = BTreeMap::new();
+        if path.exists() {
+            let file = File::open(&path).map_err(|e| StoreError::QueryError(e.to_string()))?;
+            for (idx, line) in BufReader::new(file).lines().enumerate() {
+                let line = line.map_err(|e| StoreError::QueryError(e.to_string()))?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let record: MarketSnapshotRecord = serde_json::from_str(&line).map_err(|e| {
+                    StoreError::SerialisationError(format!("line {}: {}", idx + 1, e))
+                })?;
+                records.insert(record.key(), record);
+            }
+        }
+        Ok(Self {
+            path: Some(path),
+            records: RwLock::new(records),
+        })
+    }
+
+    /// Number of versions across all dates.
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    /// Returns true if the store has no versions.
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    /// Valuation dates with a snapshot, oldest first.
+    pub fn dates(&self) -> Vec<NaiveDate> {
+        let mut dates: Vec<NaiveDate> = self.read().keys().map(|&(d, _)| d).collect();
+        dates.dedup();
+        dates
+    }
+
+    /// Save a snapshot as the next version of its date, returning the
+    /// version saved.
+    ///
+    /// The record's own version is ignored. It must not be recorded before
+    /// the date's latest version.
+    pub fn record(&self, mut record: MarketSnapshotRecord) -> Result<u32, StoreError> {
+        let mut records = self.write();
+        let latest = records
+            .range((record.as_of, 0)..=(record.as_of, u32::MAX))
+            .next_back()
+            .map(|(_, r)| r);
+        record.version = match latest {
+            Some(latest) if record.recorded_at < latest.recorded_at => {
+                return Err(StoreError::InvalidKey(format!(
+                    "{} snapshot recorded at {} precedes version {} recorded at {}",
+                    record.as_of, record.recorded_at, latest.version, latest.recorded_at
+                )));
+            }
+            Some(latest) => latest.version + 1,
+            None => 1,
+        };
+        self.append(&mut records, record)
+    }
+
+    /// Versions of a date, oldest first.
+    pub fn versions(&self, as_of: NaiveDate) -> Vec<MarketSnapshotRecord> {
+        self.read()
+            .range((as_of, 0)..=(as_of, u32::MAX))
+            .map(|(_, r)| r.clone())
+            .collect()
+    }
+
+    /// Latest version of a date: the market with all corrections applied.
+    pub fn latest(&self, as_of: NaiveDate) -> Option<MarketSnapshotRecord> {
+        self.read()
+            .range((as_of, 0)..=(as_of, u32::MAX))
+            .next_back()
+            .map(|(_, r)| r.clone())
+    }
+
+    /// Version of a date that was current at `recorded_at`: the market as
+    /// seen at that time, before any later corrections.
+    pub fn as_seen_at(
+        &self,
+        as_of: NaiveDate,
+        recorded_at: DateTime<Utc>,
+    ) -> Option<MarketSnapshotRecord> {
+        self.read()
+            .range((as_of, 0)..=(as_of, u32::MAX))
+            .rev()
+            .find(|(_, r)| r.recorded_at <= recorded_at)
+            .map(|(_, r)| r.clone())
+    }
+
+    /// Quotes corrected after `recorded_at`: the latest version of the date
+    /// against the version seen at that time.
+    ///
+    /// Every quote counts as changed if no version had been recorded by
+    /// then.
+    pub fn corrections(&self, as_of: NaiveDate, recorded_at: DateTime<Utc>) -> Vec<QuoteChange> {
+        let Some(latest) = self.latest(as_of) else {
+            return Vec::new();
+        };
+        let seen = self
+            .as_seen_at(as_of, recorded_at)
+            .unwrap_or_else(|| MarketSnapshotRecord::new(as_of, recorded_at));
+        latest.changes(&seen)
+    }
+
+    fn read(
+        &self,
+    ) -> std::sync::RwLockReadGuard<'_, BTreeMap<(NaiveDate, u32), MarketSnapshotRecord>> {
+        self.records
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn write(
+        &self,
+    ) -> std::sync::RwLockWriteGuard<'_, BTreeMap<(NaiveDate, u32), MarketSnapshotRecord>> {
+        self.records
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Append a version to the file, if any, then to the records.
+    fn append(
+        &self,
+        records: &mut BTreeMap<(NaiveDate, u32), MarketSnapshotRecord>,
+        record: MarketSnapshotRecord,
+    ) -> Result<u32, StoreError> {
+        if let Some(path) = &self.path {
+            let line = serde_json::to_string(&record)
+                .map_err(|e| StoreError::SerialisationError(e.to_string()))?;
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(|e| StoreError::QueryError(e.to_string()))?;
+            writeln!(file, "{}", line).map_err(|e| StoreError::QueryError(e.to_string()))?;
+        }
+        let version = record.version;
+        records.insert(record.key(), record);
+        Ok(version)
+    }
+}
+
+impl Save<MarketSnapshotRecord> for MarketSnapshotStore {
+    /// Save a snapshot under its own version; versions cannot be
+    /// overwritten.
+    fn save(&self, record: &MarketSnapshotRecord) -> Result<(), StoreError> {
+        let mut records = self.write();
+        if records.contains_key(&record.key()) {
+            return Err(StoreError::Duplicate(format!(
+                "{} snapshot version {}",
+                record.as_of, record.version
+            )));
+        }
+        self.append(&mut records, record.clone())?;
+        Ok(())
+    }
+}
+
+impl Load<MarketSnapshotRecord, (NaiveDate, u32)> for MarketSnapshotStore {
+    fn load(&self, key: &(NaiveDate, u32)) -> Result<Option<MarketSnapshotRecord>, StoreError> {
+        Ok(self.read().get(key).cloned())
+    }
+
+    fn load_all(&self) -> Result<Vec<MarketSnapshotRecord>, StoreError> {
+        Ok(self.read().values().cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn day(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, d).unwrap()
+    }
+
+    fn at(d: u32, h: u32, m: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, d, h, m, 0).unwrap()
+    }
+
+    fn snapshot(recorded_at: DateTime<Utc>, usd: f64, fx: f64) -> MarketSnapshotRecord {
+        MarketSnapshotRecord::new(day(1), recorded_at)
+            .with_quote("curve/USD/discount", usd)
+            .with_quote("fx/EUR/USD", fx)
+    }
+
+    #[test]
+    fn test_bitemporal_queries() {
+        let store = MarketSnapshotStore::in_memory();
+        assert_eq!(
+            store.record(snapshot(at(1, 16, 30), 0.050, 1.085)).unwrap(),
+            1
+        );
+        assert_eq!(
+            store.record(snapshot(at(2, 9, 0), 0.051, 1.085)).unwrap(),
+            2
+        );
+        store
+            .record(MarketSnapshotRecord::new(day(2), at(2, 16, 30)).with_quote("fx/EUR/USD", 1.09))
+            .unwrap();
+
+        assert_eq!(store.len(), 3);
+        assert_eq!(store.dates(), vec![day(1), day(2)]);
+        assert_eq!(store.versions(day(1)).len(), 2);
+
+        let eod = store.as_seen_at(day(1), at(1, 17, 0)).unwrap();
+        assert_eq!(eod.version, 1);
+        assert_eq!(eod.quote("curve/USD/discount"), Some(0.050));
+        assert!(store.as_seen_at(day(1), at(1, 12, 0)).is_none());
+        assert_eq!(store.latest(day(1)).unwrap().version, 2);
+        assert_eq!(store.latest(day(2)).unwrap().version, 1);
+        assert!(store.latest(day(3)).is_none());
+
+        let corrections = store.corrections(day(1), at(1, 17, 0));
+        assert_eq!(corrections.len(), 1);
+        assert_eq!(corrections[0].key, "curve/USD/discount");
+        assert!((corrections[0].change().unwrap() - 0.001).abs() < 1e-12);
+        assert_eq!(store.corrections(day(1), at(2, 10, 0)), Vec::new());
+        assert_eq!(store.corrections(day(1), at(1, 12, 0)).len(), 2);
+    }
+
+    #[test]
+    fn test_versions_are_append_only() {
+        let store = MarketSnapshotStore::in_memory();
+        store.record(snapshot(at(1, 16, 30), 0.05, 1.085)).unwrap();
+
+        assert!(matches!(
+            store.record(snapshot(at(1, 16, 0), 0.05, 1.085)),
+            Err(StoreError::InvalidKey(_))
+        ));
+        assert!(matches!(
+            store.save(&snapshot(at(1, 18, 0), 0.05, 1.085)),
+            Err(StoreError::Duplicate(_))
+        ));
+        assert_eq!(store.len(), 1);
+    }
+
+    #[test]
+    fn test_changes_include_added_and_removed_quotes() {
+        let before = snapshot(at(1, 16, 30), 0.05, 1.085);
+        let after = MarketSnapshotRecord::new(day(1), at(1, 18, 0))
+            .with_quote("curve/USD/discount", 0.05)
+            .with_quote("vol/USD", 0.2);
+        let changes = after.changes(&before);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].key, "fx/EUR/USD");
+        assert_eq!((changes[0].before, changes[0].after), (Some(1.085), None));
+        assert_eq!(changes[1].change(), None);
+    }
+
+    #[test]
+    fn test_file_store_reloads_versions() {
+        let dir = std::env::temp_dir().join("infra_store_market");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("market.jsonl");
+        let _ = std::fs::remove_file(&path);
+
+        let store = MarketSnapshotStore::open(&path).unwrap();
+        store.record(snapshot(at(1, 16, 30), 0.050, 1.085)).unwrap();
+        store.record(snapshot(at(1, 18, 0), 0.051, 1.085)).unwrap();
+
+        let reopened = MarketSnapshotStore::open(&path).unwrap();
+        assert_eq!(reopened.len(), 2);
+        assert_eq!(
+            reopened
+                .load(&(day(1), 1))
+                .unwrap()
+                .unwrap()
+                .quote("curve/USD/discount"),
+            Some(0.050)
+        );
+        assert_eq!(
+            reopened
+                .record(snapshot(at(2, 9, 0), 0.052, 1.085))
+                .unwrap(),
+            3
+        );
+    }
+}
@@ -25,7 +25,7 @@ impl PostgresStore {
     pub async fn connect(url: &str) -> Result<Self, StoreError> {
         let pool = PgPool::connect(url)
             .await
-            .map_err(|e| StoreError::ConnectionFailed(e.to_string()))?;
+            .map_err(|e| StoreError::ConnectionError(e.to_string()))?;
 
         Ok(Self { pool })
     }
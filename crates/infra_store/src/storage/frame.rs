@@ -0,0 +1,224 @@
+//! Columnar result frames.
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::StoreError;
+
+/// Values of one column; `None` marks a missing value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "values", rename_all = "snake_case")]
+pub enum ColumnData {
+    /// Floating-point values
+    Float(Vec<Option<f64>>),
+    /// Integer values
+    Integer(Vec<Option<i64>>),
+    /// Text values
+    Text(Vec<Option<String>>),
+}
+
+impl ColumnData {
+    /// Number of values.
+    pub fn len(&self) -> usize {
+        match self {
+            ColumnData::Float(values) => values.len(),
+            ColumnData::Integer(values) => values.len(),
+            ColumnData::Text(values) => values.len(),
+        }
+    }
+
+    /// Whether the column has no values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Values in `range`, clamped to the column.
+    pub fn slice(&self, range: Range<usize>) -> Self {
+        let end = range.end.min(self.len());
+        let range = range.start.min(end)..end;
+        match self {
+            ColumnData::Float(values) => ColumnData::Float(values[range].to_vec()),
+            ColumnData::Integer(values) => ColumnData::Integer(values[range].to_vec()),
+            ColumnData::Text(values) => ColumnData::Text(values[range].to_vec()),
+        }
+    }
+}
+
+/// Named column of a [`ResultFrame`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResultColumn {
+    /// Column name
+    pub name: String,
+    /// Column values
+    pub data: ColumnData,
+}
+
+/// Table of results: equal-length named columns plus string metadata
+/// (model, currency, source run parameters, ...).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResultFrame {
+    /// Columns in order
+    pub columns: Vec<ResultColumn>,
+    /// Free-form metadata
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+}
+
+impl ResultFrame {
+    /// Create an empty frame.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a column.
+    ///
+    /// Fails if the name is already taken or the length differs from the
+    /// existing columns.
+    pub fn with_column(
+        mut self,
+        name: impl Into<String>,
+        data: ColumnData,
+    ) -> Result<Self, StoreError> {
+        let name = name.into();
+        if self.column(&name).is_some() {
+            return Err(StoreError::Duplicate(format!("column '{}'", name)));
+        }
+        if let Some(first) = self.columns.first() {
+            if first.data.len() != data.len() {
+                return Err(StoreError::SerialisationError(format!(
+                    "column '{}' has {} rows, frame has {}",
+                    name,
+                    data.len(),
+                    first.data.len()
+                )));
+            }
+        }
+        self.columns.push(ResultColumn { name, data });
+        Ok(self)
+    }
+
+    /// Add a metadata entry.
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// Values of the named column.
+    pub fn column(&self, name: &str) -> Option<&ColumnData> {
+        self.columns
+            .iter()
+            .find(|column| column.name == name)
+            .map(|column| &column.data)
+    }
+
+    /// Number of rows.
+    pub fn num_rows(&self) -> usize {
+        self.columns.first().map_or(0, |column| column.data.len())
+    }
+
+    /// Up to `limit` rows from `offset`, with the frame's metadata.
+    pub fn rows(&self, offset: usize, limit: usize) -> Self {
+        let range = offset..offset.saturating_add(limit);
+        Self {
+            columns: self
+                .columns
+                .iter()
+                .map(|column| ResultColumn {
+                    name: column.name.clone(),
+                    data: column.data.slice(range.clone()),
+                })
+                .collect(),
+            metadata: self.metadata.clone(),
+        }
+    }
+
+    /// The named columns, in the order given, with the frame's metadata.
+    ///
+    /// Fails with [`StoreError::NotFound`] naming the first missing column.
+    pub fn select<S: AsRef<str>>(&self, names: &[S]) -> Result<Self, StoreError> {
+        let columns = names
+            .iter()
+            .map(|name| {
+                let name = name.as_ref();
+                self.column(name)
+                    .map(|data| ResultColumn {
+                        name: name.to_string(),
+                        data: data.clone(),
+                    })
+                    .ok_or_else(|| StoreError::NotFound(format!("column '{}'", name)))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            columns,
+            metadata: self.metadata.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_rejects_ragged_and_duplicate_columns() {
+        let frame = ResultFrame::new()
+            .with_column("t", ColumnData::Float(vec![Some(0.0), Some(1.0)]))
+            .unwrap();
+        assert_eq!(frame.num_rows(), 2);
+
+        let ragged = frame
+            .clone()
+            .with_column("ee", ColumnData::Float(vec![Some(1.0)]));
+        assert!(matches!(ragged, Err(StoreError::SerialisationError(_))));
+
+        let duplicate = frame.with_column("t", ColumnData::Integer(vec![None, None]));
+        assert!(matches!(duplicate, Err(StoreError::Duplicate(_))));
+    }
+
+    #[test]
+    fn test_rows_and_select() {
+        let frame = ResultFrame::new()
+            .with_column(
+                "id",
+                ColumnData::Text(vec![Some("T1".into()), Some("T2".into()), None]),
+            )
+            .unwrap()
+            .with_column(
+                "pv",
+                ColumnData::Float(vec![Some(1.0), Some(2.0), Some(3.0)]),
+            )
+            .unwrap()
+            .with_metadata("currency", "EUR");
+
+        let page = frame.rows(1, 5);
+        assert_eq!(page.num_rows(), 2);
+        assert_eq!(
+            page.column("pv"),
+            Some(&ColumnData::Float(vec![Some(2.0), Some(3.0)]))
+        );
+        assert_eq!(page.metadata["currency"], "EUR");
+        assert_eq!(frame.rows(7, 2).num_rows(), 0);
+
+        let selected = frame.select(&["pv", "id"]).unwrap();
+        assert_eq!(selected.columns[0].name, "pv");
+        assert_eq!(selected.columns.len(), 2);
+        assert!(matches!(
+            frame.select(&["cva"]),
+            Err(StoreError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_frame_json_round_trip() {
+        let frame = ResultFrame::new()
+            .with_column("id", ColumnData::Text(vec![Some("NS1".into()), None]))
+            .unwrap()
+            .with_column("n", ColumnData::Integer(vec![Some(3), None]))
+            .unwrap()
+            .with_metadata("currency", "EUR");
+        let json = serde_json::to_string(&frame).unwrap();
+        assert_eq!(serde_json::from_str::<ResultFrame>(&json).unwrap(), frame);
+    }
+}
@@ -0,0 +1,351 @@
+//! Result storage with pluggable backends.
+//!
+//! Pricing runs, exposure profiles, XVA results and reports are stored as
+//! columnar [`ResultFrame`]s under a [`ResultKey`]: the kind of result, the
+//! valuation date, the run that produced it and a name within the run. EOD
+//! batches write frames through a [`ResultStore`]; the API and GUI list
+//! and read them back with a [`ResultQuery`].
+//!
+//! Backends:
+//!
+//! - [`MemoryResultStore`]: in process, for tests and single-shot runs
+//! - `ParquetResultStore` (feature `parquet`): one Parquet file per frame
+//!   on any [`ObjectStore`] — a local directory ([`LocalObjectStore`]) or,
+//!   with the `object-store` feature, an S3 or GCS bucket
+//!   (`S3ObjectStore`)
+//! - `PostgresResultStore` (feature `postgres`): one row per frame, with
+//!   the frame as JSONB
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::RwLock;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::error::StoreError;
+
+mod frame;
+mod object;
+#[cfg(feature = "parquet")]
+mod parquet_store;
+#[cfg(feature = "postgres")]
+mod postgres;
+#[cfg(feature = "object-store")]
+mod s3;
+
+pub use frame::{ColumnData, ResultColumn, ResultFrame};
+pub use object::{LocalObjectStore, ObjectStore};
+#[cfg(feature = "parquet")]
+pub use parquet_store::ParquetResultStore;
+#[cfg(feature = "postgres")]
+pub use postgres::PostgresResultStore;
+#[cfg(feature = "object-store")]
+pub use s3::{S3Credentials, S3ObjectStore};
+
+/// Kind of stored result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResultKind {
+    /// Trade values and Greeks of a pricing run
+    PricingRun,
+    /// Exposure profiles (EE, PFE, ...) over a time grid
+    ExposureProfile,
+    /// CVA, DVA, FVA and other valuation adjustments
+    Xva,
+    /// Rendered report tables
+    Report,
+}
+
+impl ResultKind {
+    /// All kinds.
+    pub const ALL: [ResultKind; 4] = [
+        ResultKind::PricingRun,
+        ResultKind::ExposureProfile,
+        ResultKind::Xva,
+        ResultKind::Report,
+    ];
+
+    /// Stable identifier used in storage paths and queries.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ResultKind::PricingRun => "pricing_run",
+            ResultKind::ExposureProfile => "exposure_profile",
+            ResultKind::Xva => "xva",
+            ResultKind::Report => "report",
+        }
+    }
+}
+
+impl fmt::Display for ResultKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ResultKind {
+    type Err = StoreError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.as_str() == s)
+            .ok_or_else(|| StoreError::InvalidKey(format!("unknown result kind '{}'", s)))
+    }
+}
+
+/// Location of a stored result.
+///
+/// Keys order by valuation date, then kind, run and name.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct ResultKey {
+    /// Valuation date
+    pub as_of: NaiveDate,
+    /// Kind of result
+    pub kind: ResultKind,
+    /// Run that produced the result, e.g. `eod-20240301`
+    pub run_id: String,
+    /// Name of the result within the run, e.g. `netting_set_xva`
+    pub name: String,
+}
+
+impl ResultKey {
+    /// Create a key.
+    ///
+    /// Run IDs and names may contain only ASCII letters, digits, `-`, `_`
+    /// and `.`, so that every backend can use them in paths.
+    pub fn new(
+        kind: ResultKind,
+        as_of: NaiveDate,
+        run_id: impl Into<String>,
+        name: impl Into<String>,
+    ) -> Result<Self, StoreError> {
+        let key = Self {
+            as_of,
+            kind,
+            run_id: run_id.into(),
+            name: name.into(),
+        };
+        validate_segment("run ID", &key.run_id)?;
+        validate_segment("name", &key.name)?;
+        Ok(key)
+    }
+}
+
+impl fmt::Display for ResultKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{}/{}/{}",
+            self.kind, self.as_of, self.run_id, self.name
+        )
+    }
+}
+
+fn validate_segment(what: &str, value: &str) -> Result<(), StoreError> {
+    let valid = !value.is_empty()
+        && !value.starts_with('.')
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(StoreError::InvalidKey(format!(
+            "invalid {} '{}'",
+            what, value
+        )))
+    }
+}
+
+/// Filter over stored results; an empty query matches everything.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResultQuery {
+    /// Only this kind
+    pub kind: Option<ResultKind>,
+    /// Only this run
+    pub run_id: Option<String>,
+    /// Valuation dates on or after
+    pub from: Option<NaiveDate>,
+    /// Valuation dates on or before
+    pub to: Option<NaiveDate>,
+}
+
+impl ResultQuery {
+    /// Query matching every result.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restrict to one kind.
+    pub fn with_kind(mut self, kind: ResultKind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    /// Restrict to one run.
+    pub fn with_run_id(mut self, run_id: impl Into<String>) -> Self {
+        self.run_id = Some(run_id.into());
+        self
+    }
+
+    /// Restrict to valuation dates in `[from, to]`.
+    pub fn with_dates(mut self, from: NaiveDate, to: NaiveDate) -> Self {
+        self.from = Some(from);
+        self.to = Some(to);
+        self
+    }
+
+    /// Whether the query selects `key`.
+    pub fn matches(&self, key: &ResultKey) -> bool {
+        self.kind.is_none_or(|kind| kind == key.kind)
+            && self.run_id.as_ref().is_none_or(|run| *run == key.run_id)
+            && self.from.is_none_or(|from| key.as_of >= from)
+            && self.to.is_none_or(|to| key.as_of <= to)
+    }
+}
+
+/// Storage of result frames.
+///
+/// Writing a key that already exists replaces its frame, so re-running a
+/// batch overwrites its earlier results.
+pub trait ResultStore: Send + Sync {
+    /// Store a frame under `key`.
+    fn put(&self, key: &ResultKey, frame: &ResultFrame) -> Result<(), StoreError>;
+
+    /// Load the frame stored under `key`.
+    fn get(&self, key: &ResultKey) -> Result<Option<ResultFrame>, StoreError>;
+
+    /// Keys selected by `query`, in key order.
+    fn list(&self, query: &ResultQuery) -> Result<Vec<ResultKey>, StoreError>;
+
+    /// Remove the frame stored under `key`, returning whether it existed.
+    fn delete(&self, key: &ResultKey) -> Result<bool, StoreError>;
+}
+
+/// Async version of [`ResultStore`].
+#[cfg(feature = "postgres")]
+#[async_trait::async_trait]
+pub trait ResultStoreAsync: Send + Sync {
+    /// Store a frame under `key`.
+    async fn put(&self, key: &ResultKey, frame: &ResultFrame) -> Result<(), StoreError>;
+
+    /// Load the frame stored under `key`.
+    async fn get(&self, key: &ResultKey) -> Result<Option<ResultFrame>, StoreError>;
+
+    /// Keys selected by `query`, in key order.
+    async fn list(&self, query: &ResultQuery) -> Result<Vec<ResultKey>, StoreError>;
+
+    /// Remove the frame stored under `key`, returning whether it existed.
+    async fn delete(&self, key: &ResultKey) -> Result<bool, StoreError>;
+}
+
+/// In-memory result store.
+#[derive(Debug, Default)]
+pub struct MemoryResultStore {
+    frames: RwLock<BTreeMap<ResultKey, ResultFrame>>,
+}
+
+impl MemoryResultStore {
+    /// Create an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ResultStore for MemoryResultStore {
+    fn put(&self, key: &ResultKey, frame: &ResultFrame) -> Result<(), StoreError> {
+        self.frames
+            .write()
+            .map_err(|e| StoreError::QueryError(e.to_string()))?
+            .insert(key.clone(), frame.clone());
+        Ok(())
+    }
+
+    fn get(&self, key: &ResultKey) -> Result<Option<ResultFrame>, StoreError> {
+        Ok(self
+            .frames
+            .read()
+            .map_err(|e| StoreError::QueryError(e.to_string()))?
+            .get(key)
+            .cloned())
+    }
+
+    fn list(&self, query: &ResultQuery) -> Result<Vec<ResultKey>, StoreError> {
+        Ok(self
+            .frames
+            .read()
+            .map_err(|e| StoreError::QueryError(e.to_string()))?
+            .keys()
+            .filter(|key| query.matches(key))
+            .cloned()
+            .collect())
+    }
+
+    fn delete(&self, key: &ResultKey) -> Result<bool, StoreError> {
+        Ok(self
+            .frames
+            .write()
+            .map_err(|e| StoreError::QueryError(e.to_string()))?
+            .remove(key)
+            .is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 3, d).unwrap()
+    }
+
+    #[test]
+    fn test_key_validation() {
+        assert!(ResultKey::new(ResultKind::Xva, date(1), "eod-1", "cva.v2").is_ok());
+        for bad in ["", "../x", "a/b", ".hidden", "a b"] {
+            assert!(matches!(
+                ResultKey::new(ResultKind::Xva, date(1), bad, "cva"),
+                Err(StoreError::InvalidKey(_))
+            ));
+        }
+        assert_eq!(
+            "exposure_profile".parse::<ResultKind>().unwrap(),
+            ResultKind::ExposureProfile
+        );
+        assert!("pnl".parse::<ResultKind>().is_err());
+    }
+
+    #[test]
+    fn test_memory_store_queries() {
+        let store = MemoryResultStore::new();
+        let frame = ResultFrame::new()
+            .with_column("cva", ColumnData::Float(vec![Some(1.5)]))
+            .unwrap();
+        let keys = [
+            ResultKey::new(ResultKind::Xva, date(2), "eod", "cva").unwrap(),
+            ResultKey::new(ResultKind::Xva, date(1), "eod", "cva").unwrap(),
+            ResultKey::new(ResultKind::ExposureProfile, date(1), "eod", "ee").unwrap(),
+            ResultKey::new(ResultKind::Xva, date(1), "intraday", "cva").unwrap(),
+        ];
+        for key in &keys {
+            store.put(key, &frame).unwrap();
+        }
+
+        let xva = store
+            .list(&ResultQuery::new().with_kind(ResultKind::Xva))
+            .unwrap();
+        assert_eq!(xva, vec![keys[1].clone(), keys[3].clone(), keys[0].clone()]);
+
+        let eod_first = ResultQuery::new()
+            .with_run_id("eod")
+            .with_dates(date(1), date(1));
+        assert_eq!(store.list(&eod_first).unwrap().len(), 2);
+
+        assert_eq!(store.get(&keys[0]).unwrap(), Some(frame));
+        assert!(store.delete(&keys[0]).unwrap());
+        assert!(!store.delete(&keys[0]).unwrap());
+        assert_eq!(store.get(&keys[0]).unwrap(), None);
+    }
+}
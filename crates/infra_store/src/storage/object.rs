@@ -0,0 +1,166 @@
+//! Object storage for file-based result backends.
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use crate::error::StoreError;
+
+/// Flat key-value object storage with `/`-separated paths.
+///
+/// Implemented by [`LocalObjectStore`] and, with the `object-store`
+/// feature, by the S3/GCS backend.
+pub trait ObjectStore: Send + Sync {
+    /// Write an object, replacing any existing one.
+    fn put(&self, path: &str, bytes: Vec<u8>) -> Result<(), StoreError>;
+
+    /// Read an object.
+    fn get(&self, path: &str) -> Result<Option<Vec<u8>>, StoreError>;
+
+    /// Paths of all objects starting with `prefix`.
+    fn list(&self, prefix: &str) -> Result<Vec<String>, StoreError>;
+
+    /// Remove an object, returning whether it existed.
+    fn delete(&self, path: &str) -> Result<bool, StoreError>;
+}
+
+/// Object storage in a local directory.
+///
+/// Writes go to a temporary file that is renamed into place, so readers
+/// never see a partially written object.
+#[derive(Debug, Clone)]
+pub struct LocalObjectStore {
+    root: PathBuf,
+}
+
+impl LocalObjectStore {
+    /// Store objects under `root`, which is created on first write.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Root directory.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    fn resolve(&self, path: &str) -> Result<PathBuf, StoreError> {
+        let mut resolved = self.root.clone();
+        for segment in path.split('/').filter(|s| !s.is_empty()) {
+            if segment == "." || segment == ".." {
+                return Err(StoreError::InvalidKey(format!("invalid path '{}'", path)));
+            }
+            resolved.push(segment);
+        }
+        Ok(resolved)
+    }
+}
+
+fn io_error(path: &Path, error: std::io::Error) -> StoreError {
+    StoreError::QueryError(format!("{}: {}", path.display(), error))
+}
+
+fn collect_files(dir: &Path, relative: &str, out: &mut Vec<String>) -> Result<(), StoreError> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(io_error(dir, e)),
+    };
+    for entry in entries {
+        let entry = entry.map_err(|e| io_error(dir, e))?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.starts_with('.') {
+            continue;
+        }
+        let path = if relative.is_empty() {
+            name
+        } else {
+            format!("{}/{}", relative, name)
+        };
+        let file_type = entry.file_type().map_err(|e| io_error(dir, e))?;
+        if file_type.is_dir() {
+            collect_files(&entry.path(), &path, out)?;
+        } else {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+impl ObjectStore for LocalObjectStore {
+    fn put(&self, path: &str, bytes: Vec<u8>) -> Result<(), StoreError> {
+        let target = self.resolve(path)?;
+        let parent = target.parent().unwrap_or(&self.root);
+        fs::create_dir_all(parent).map_err(|e| io_error(parent, e))?;
+
+        let file_name = target
+            .file_name()
+            .ok_or_else(|| StoreError::InvalidKey(format!("invalid path '{}'", path)))?;
+        let temp = parent.join(format!(".{}.tmp", file_name.to_string_lossy()));
+        fs::write(&temp, bytes).map_err(|e| io_error(&temp, e))?;
+        fs::rename(&temp, &target).map_err(|e| io_error(&target, e))
+    }
+
+    fn get(&self, path: &str) -> Result<Option<Vec<u8>>, StoreError> {
+        let target = self.resolve(path)?;
+        match fs::read(&target) {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(io_error(&target, e)),
+        }
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>, StoreError> {
+        // Walk from the deepest directory fully named by the prefix
+        let dir = prefix.rsplit_once('/').map_or("", |(dir, _)| dir);
+        let mut paths = Vec::new();
+        collect_files(&self.resolve(dir)?, dir.trim_matches('/'), &mut paths)?;
+        paths.retain(|path| path.starts_with(prefix));
+        paths.sort();
+        Ok(paths)
+    }
+
+    fn delete(&self, path: &str) -> Result<bool, StoreError> {
+        let target = self.resolve(path)?;
+        match fs::remove_file(&target) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(io_error(&target, e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_object_store() {
+        let root = std::env::temp_dir().join(format!("neutryx_objects_{}", std::process::id()));
+        let store = LocalObjectStore::new(&root);
+
+        store.put("xva/a.bin", vec![1, 2]).unwrap();
+        store.put("xva/b/c.bin", vec![3]).unwrap();
+        store.put("report/d.bin", vec![4]).unwrap();
+        store.put("xva/a.bin", vec![5]).unwrap();
+
+        assert_eq!(store.get("xva/a.bin").unwrap(), Some(vec![5]));
+        assert_eq!(store.get("xva/missing.bin").unwrap(), None);
+        assert_eq!(
+            store.list("xva/").unwrap(),
+            vec!["xva/a.bin".to_string(), "xva/b/c.bin".to_string()]
+        );
+        assert_eq!(store.list("").unwrap().len(), 3);
+        assert_eq!(
+            store.list("xva/b").unwrap(),
+            vec!["xva/b/c.bin".to_string()]
+        );
+        assert!(store.list("pnl/").unwrap().is_empty());
+        assert!(store.get("../etc/passwd").is_err());
+
+        assert!(store.delete("xva/a.bin").unwrap());
+        assert!(!store.delete("xva/a.bin").unwrap());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,315 @@
+//! Parquet result store on object storage.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, Float64Array, Int64Array, RecordBatch, StringArray};
+use arrow::datatypes::{DataType, Field, Schema};
+use bytes::Bytes;
+use chrono::NaiveDate;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+
+use super::{
+    ColumnData, LocalObjectStore, ObjectStore, ResultFrame, ResultKey, ResultQuery, ResultStore,
+};
+use crate::error::StoreError;
+
+/// Result store writing one Parquet file per frame.
+///
+/// Files are laid out in Hive style,
+/// `{kind}/as_of={YYYY-MM-DD}/run={run_id}/{name}.parquet`, so that query
+/// engines reading the bucket or directory directly see `as_of` and `run`
+/// as partition columns. Frame metadata is kept in the file's schema
+/// metadata.
+#[derive(Debug, Clone)]
+pub struct ParquetResultStore<S> {
+    objects: S,
+    prefix: String,
+}
+
+impl ParquetResultStore<LocalObjectStore> {
+    /// Store results under a local directory.
+    pub fn local(root: impl Into<PathBuf>) -> Self {
+        Self::new(LocalObjectStore::new(root))
+    }
+}
+
+impl<S: ObjectStore> ParquetResultStore<S> {
+    /// Store results on `objects`.
+    pub fn new(objects: S) -> Self {
+        Self {
+            objects,
+            prefix: String::new(),
+        }
+    }
+
+    /// Place all files under a path prefix, e.g. `neutryx/results`.
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        let prefix = prefix.into();
+        let prefix = prefix.trim_matches('/');
+        self.prefix = if prefix.is_empty() {
+            String::new()
+        } else {
+            format!("{}/", prefix)
+        };
+        self
+    }
+
+    /// Underlying object storage.
+    pub fn objects(&self) -> &S {
+        &self.objects
+    }
+
+    fn path(&self, key: &ResultKey) -> String {
+        format!(
+            "{}{}/as_of={}/run={}/{}.parquet",
+            self.prefix, key.kind, key.as_of, key.run_id, key.name
+        )
+    }
+
+    fn parse_path(&self, path: &str) -> Option<ResultKey> {
+        let mut parts = path.strip_prefix(&self.prefix)?.split('/');
+        let kind = parts.next()?.parse().ok()?;
+        let as_of = parts.next()?.strip_prefix("as_of=")?;
+        let as_of = NaiveDate::parse_from_str(as_of, "%Y-%m-%d").ok()?;
+        let run_id = parts.next()?.strip_prefix("run=")?;
+        let name = parts.next()?.strip_suffix(".parquet")?;
+        if parts.next().is_some() {
+            return None;
+        }
+        ResultKey::new(kind, as_of, run_id, name).ok()
+    }
+}
+
+impl<S: ObjectStore> ResultStore for ParquetResultStore<S> {
+    fn put(&self, key: &ResultKey, frame: &ResultFrame) -> Result<(), StoreError> {
+        self.objects.put(&self.path(key), encode(frame)?)
+    }
+
+    fn get(&self, key: &ResultKey) -> Result<Option<ResultFrame>, StoreError> {
+        self.objects
+            .get(&self.path(key))?
+            .map(|bytes| decode(bytes.into()))
+            .transpose()
+    }
+
+    fn list(&self, query: &ResultQuery) -> Result<Vec<ResultKey>, StoreError> {
+        let prefix = match query.kind {
+            Some(kind) => format!("{}{}/", self.prefix, kind),
+            None => self.prefix.clone(),
+        };
+        let mut keys: Vec<_> = self
+            .objects
+            .list(&prefix)?
+            .iter()
+            .filter_map(|path| self.parse_path(path))
+            .filter(|key| query.matches(key))
+            .collect();
+        keys.sort();
+        Ok(keys)
+    }
+
+    fn delete(&self, key: &ResultKey) -> Result<bool, StoreError> {
+        self.objects.delete(&self.path(key))
+    }
+}
+
+fn parquet_error(error: impl std::fmt::Display) -> StoreError {
+    StoreError::SerialisationError(format!("parquet: {}", error))
+}
+
+/// Encode a frame as a Snappy-compressed Parquet file.
+fn encode(frame: &ResultFrame) -> Result<Vec<u8>, StoreError> {
+    if frame.columns.is_empty() {
+        return Err(StoreError::SerialisationError(
+            "cannot store a frame without columns".to_string(),
+        ));
+    }
+    let fields: Vec<Field> = frame
+        .columns
+        .iter()
+        .map(|column| {
+            let data_type = match column.data {
+                ColumnData::Float(_) => DataType::Float64,
+                ColumnData::Integer(_) => DataType::Int64,
+                ColumnData::Text(_) => DataType::Utf8,
+            };
+            Field::new(&column.name, data_type, true)
+        })
+        .collect();
+    let metadata: HashMap<_, _> = frame
+        .metadata
+        .iter()
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    let schema = Arc::new(Schema::new(fields).with_metadata(metadata));
+
+    let arrays: Vec<ArrayRef> = frame
+        .columns
+        .iter()
+        .map(|column| -> ArrayRef {
+            match &column.data {
+                ColumnData::Float(values) => Arc::new(Float64Array::from(values.clone())),
+                ColumnData::Integer(values) => Arc::new(Int64Array::from(values.clone())),
+                ColumnData::Text(values) => Arc::new(StringArray::from(values.clone())),
+            }
+        })
+        .collect();
+    let batch = RecordBatch::try_new(schema.clone(), arrays).map_err(parquet_error)?;
+
+    let props = WriterProperties::builder()
+        .set_compression(Compression::SNAPPY)
+        .build();
+    let mut buffer = Vec::new();
+    let mut writer =
+        ArrowWriter::try_new(&mut buffer, schema, Some(props)).map_err(parquet_error)?;
+    writer.write(&batch).map_err(parquet_error)?;
+    writer.close().map_err(parquet_error)?;
+    Ok(buffer)
+}
+
+/// Decode a Parquet file written by [`encode`].
+fn decode(bytes: Bytes) -> Result<ResultFrame, StoreError> {
+    let builder = ParquetRecordBatchReaderBuilder::try_new(bytes).map_err(parquet_error)?;
+    let schema = builder.schema().clone();
+    let mut columns: Vec<ColumnData> = schema
+        .fields()
+        .iter()
+        .map(|field| match field.data_type() {
+            DataType::Float64 => Ok(ColumnData::Float(Vec::new())),
+            DataType::Int64 => Ok(ColumnData::Integer(Vec::new())),
+            DataType::Utf8 => Ok(ColumnData::Text(Vec::new())),
+            other => Err(parquet_error(format!(
+                "unsupported type {} of column '{}'",
+                other,
+                field.name()
+            ))),
+        })
+        .collect::<Result<_, _>>()?;
+
+    for batch in builder.build().map_err(parquet_error)? {
+        let batch = batch.map_err(parquet_error)?;
+        for (column, array) in columns.iter_mut().zip(batch.columns()) {
+            append(column, array.as_ref())?;
+        }
+    }
+
+    Ok(ResultFrame {
+        columns: schema
+            .fields()
+            .iter()
+            .zip(columns)
+            .map(|(field, data)| super::ResultColumn {
+                name: field.name().clone(),
+                data,
+            })
+            .collect(),
+        metadata: schema
+            .metadata()
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect(),
+    })
+}
+
+fn append(column: &mut ColumnData, array: &dyn Array) -> Result<(), StoreError> {
+    let mismatch = || parquet_error(format!("unexpected array type {}", array.data_type()));
+    match column {
+        ColumnData::Float(values) => values.extend(
+            array
+                .as_any()
+                .downcast_ref::<Float64Array>()
+                .ok_or_else(mismatch)?
+                .iter(),
+        ),
+        ColumnData::Integer(values) => values.extend(
+            array
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .ok_or_else(mismatch)?
+                .iter(),
+        ),
+        ColumnData::Text(values) => values.extend(
+            array
+                .as_any()
+                .downcast_ref::<StringArray>()
+                .ok_or_else(mismatch)?
+                .iter()
+                .map(|value| value.map(str::to_string)),
+        ),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::ResultKind;
+
+    fn exposure_frame() -> ResultFrame {
+        ResultFrame::new()
+            .with_column(
+                "time",
+                ColumnData::Float(vec![Some(0.0), Some(0.5), Some(1.0)]),
+            )
+            .unwrap()
+            .with_column("ee", ColumnData::Float(vec![Some(0.0), Some(4.2), None]))
+            .unwrap()
+            .with_column(
+                "paths",
+                ColumnData::Integer(vec![Some(1000), Some(1000), Some(998)]),
+            )
+            .unwrap()
+            .with_column(
+                "netting_set",
+                ColumnData::Text(vec![Some("NS1".into()), Some("NS1".into()), None]),
+            )
+            .unwrap()
+            .with_metadata("currency", "EUR")
+    }
+
+    #[test]
+    fn test_parquet_round_trip_and_layout() {
+        let root = std::env::temp_dir().join(format!("neutryx_results_{}", std::process::id()));
+        let store = ParquetResultStore::local(&root).with_prefix("/results/");
+        let as_of = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let key = ResultKey::new(ResultKind::ExposureProfile, as_of, "eod", "NS1").unwrap();
+        let frame = exposure_frame();
+
+        store.put(&key, &frame).unwrap();
+        assert!(root
+            .join("results/exposure_profile/as_of=2024-03-01/run=eod/NS1.parquet")
+            .exists());
+        assert_eq!(store.get(&key).unwrap(), Some(frame));
+
+        let xva = ResultKey::new(ResultKind::Xva, as_of, "eod", "cva").unwrap();
+        store
+            .put(
+                &xva,
+                &ResultFrame::new()
+                    .with_column("cva", ColumnData::Float(vec![Some(12.5)]))
+                    .unwrap(),
+            )
+            .unwrap();
+        assert_eq!(
+            store.list(&ResultQuery::new()).unwrap(),
+            vec![key.clone(), xva.clone()]
+        );
+        assert_eq!(
+            store
+                .list(&ResultQuery::new().with_kind(ResultKind::Xva))
+                .unwrap(),
+            vec![xva]
+        );
+
+        assert!(store.delete(&key).unwrap());
+        assert_eq!(store.get(&key).unwrap(), None);
+        assert!(store.put(&key, &ResultFrame::new()).is_err());
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
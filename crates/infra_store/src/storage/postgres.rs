@@ -0,0 +1,134 @@
+//! PostgreSQL result store.
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use sqlx::postgres::PgPool;
+use sqlx::Row;
+
+use super::{ResultFrame, ResultKey, ResultKind, ResultQuery, ResultStoreAsync};
+use crate::error::StoreError;
+
+const CREATE_TABLE: &str = "CREATE TABLE IF NOT EXISTS result_frames (
+    kind TEXT NOT NULL,
+    as_of DATE NOT NULL,
+    run_id TEXT NOT NULL,
+    name TEXT NOT NULL,
+    frame JSONB NOT NULL,
+    stored_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (as_of, kind, run_id, name)
+)";
+
+/// Result store keeping one row per frame in `result_frames`, with the
+/// frame as JSONB so that the API can also query inside it.
+pub struct PostgresResultStore {
+    pool: PgPool,
+}
+
+impl PostgresResultStore {
+    /// Connect and create the `result_frames` table if needed.
+    pub async fn connect(url: &str) -> Result<Self, StoreError> {
+        let pool = PgPool::connect(url)
+            .await
+            .map_err(|e| StoreError::ConnectionError(e.to_string()))?;
+        Self::with_pool(pool).await
+    }
+
+    /// Use an existing pool, creating the `result_frames` table if needed.
+    pub async fn with_pool(pool: PgPool) -> Result<Self, StoreError> {
+        sqlx::query(CREATE_TABLE).execute(&pool).await?;
+        Ok(Self { pool })
+    }
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate, StoreError> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|e| StoreError::SerialisationError(format!("as_of '{}': {}", value, e)))
+}
+
+#[async_trait]
+impl ResultStoreAsync for PostgresResultStore {
+    async fn put(&self, key: &ResultKey, frame: &ResultFrame) -> Result<(), StoreError> {
+        let json = serde_json::to_string(frame)
+            .map_err(|e| StoreError::SerialisationError(e.to_string()))?;
+        sqlx::query(
+            "INSERT INTO result_frames (kind, as_of, run_id, name, frame)
+             VALUES ($1, $2::date, $3, $4, $5::jsonb)
+             ON CONFLICT (as_of, kind, run_id, name)
+             DO UPDATE SET frame = EXCLUDED.frame, stored_at = now()",
+        )
+        .bind(key.kind.as_str())
+        .bind(key.as_of.to_string())
+        .bind(&key.run_id)
+        .bind(&key.name)
+        .bind(json)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &ResultKey) -> Result<Option<ResultFrame>, StoreError> {
+        let row = sqlx::query(
+            "SELECT frame::text AS frame FROM result_frames
+             WHERE kind = $1 AND as_of = $2::date AND run_id = $3 AND name = $4",
+        )
+        .bind(key.kind.as_str())
+        .bind(key.as_of.to_string())
+        .bind(&key.run_id)
+        .bind(&key.name)
+        .fetch_optional(&self.pool)
+        .await?;
+        row.map(|row| {
+            let json: String = row.try_get("frame")?;
+            serde_json::from_str(&json).map_err(|e| StoreError::SerialisationError(e.to_string()))
+        })
+        .transpose()
+    }
+
+    async fn list(&self, query: &ResultQuery) -> Result<Vec<ResultKey>, StoreError> {
+        let rows = sqlx::query(
+            "SELECT kind, as_of::text AS as_of, run_id, name FROM result_frames
+             WHERE ($1::text IS NULL OR kind = $1)
+               AND ($2::text IS NULL OR run_id = $2)
+               AND ($3::date IS NULL OR as_of >= $3::date)
+               AND ($4::date IS NULL OR as_of <= $4::date)
+             ORDER BY as_of, kind, run_id, name",
+        )
+        .bind(query.kind.map(|kind| kind.as_str()))
+        .bind(query.run_id.as_deref())
+        .bind(query.from.map(|date| date.to_string()))
+        .bind(query.to.map(|date| date.to_string()))
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut keys = rows
+            .iter()
+            .map(|row| {
+                let kind: String = row.try_get("kind")?;
+                let as_of: String = row.try_get("as_of")?;
+                Ok(ResultKey {
+                    as_of: parse_date(&as_of)?,
+                    kind: kind.parse::<ResultKind>()?,
+                    run_id: row.try_get("run_id")?,
+                    name: row.try_get("name")?,
+                })
+            })
+            .collect::<Result<Vec<_>, StoreError>>()?;
+        // Kinds sort by declaration order, not by name
+        keys.sort();
+        Ok(keys)
+    }
+
+    async fn delete(&self, key: &ResultKey) -> Result<bool, StoreError> {
+        let result = sqlx::query(
+            "DELETE FROM result_frames
+             WHERE kind = $1 AND as_of = $2::date AND run_id = $3 AND name = $4",
+        )
+        .bind(key.kind.as_str())
+        .bind(key.as_of.to_string())
+        .bind(&key.run_id)
+        .bind(&key.name)
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+}
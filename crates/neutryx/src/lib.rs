@@ -0,0 +1,203 @@
+//! # Neutryx
+//!
+//! Single public import path for the Neutryx XVA Pricing Library.
+//!
+//! The Pricer layer grew in stages, and each rename left a deprecated alias
+//! behind (`pricer_kernel` inside `pricer_pricing`, the self-named module
+//! inside `pricer_risk` that stood in for `pricer_xva`). This crate fixes one
+//! blessed surface over those crates so downstream code depends on a single
+//! name, and the aliases can be retired without touching callers.
+//!
+//! | Module | Source |
+//! |--------|--------|
+//! | [`types`] | `pricer_core::types` |
+//! | [`market`] | `pricer_core::market_data` |
+//! | [`instruments`] | `pricer_models::instruments` |
+//! | [`pricing`] | `pricer_models::analytical`, `pricer_pricing` |
+//! | [`portfolio`] | `pricer_risk::portfolio` |
+//! | [`exposure`] | `pricer_risk::exposure` |
+//! | [`xva`] | `pricer_risk::xva` |
+//!
+//! The generic numeric types are also exposed as `f64` aliases at the crate
+//! root ([`Instrument`], [`Curve`], [`CurveSet`], [`BlackScholes`], ...),
+//! which is what every service crate instantiates them with.
+//!
+//! ## Architecture Position
+//!
+//! Sits on top of the **P**ricer layer of the A-I-P-S architecture and adds
+//! no behaviour of its own: every item is a re-export or a type alias.
+//!
+//! ## Example
+//!
+//! ```
+//! use std::collections::HashMap;
+//!
+//! use neutryx::prelude::*;
+//!
+//! let params = InstrumentParams::new(100.0, 1.0, 1.0).unwrap();
+//! let call = VanillaOption::new(params, PayoffType::Call, ExerciseStyle::European, 1e-6);
+//!
+//! let model = BlackScholes::new(100.0, 0.05, 0.2).unwrap();
+//! let pv = model.price_option(&call).unwrap();
+//! assert!(pv > 0.0);
+//!
+//! let portfolio = PortfolioBuilder::new()
+//!     .add_counterparty(Counterparty::new(
+//!         CounterpartyId::new("CP001"),
+//!         CreditParams::new(0.02, 0.6).unwrap(),
+//!     ))
+//!     .add_netting_set(NettingSet::new(
+//!         NettingSetId::new("NS001"),
+//!         CounterpartyId::new("CP001"),
+//!     ))
+//!     .add_trade(Trade::new(
+//!         TradeId::new("T001"),
+//!         Instrument::Vanilla(call),
+//!         Currency::USD,
+//!         CounterpartyId::new("CP001"),
+//!         NettingSetId::new("NS001"),
+//!         1_000_000.0,
+//!     ))
+//!     .build()
+//!     .unwrap();
+//!
+//! let time_grid = [0.25_f64, 0.5, 0.75, 1.0];
+//! let ee = HashMap::from([(NettingSetId::new("NS001"), vec![pv; 4])]);
+//! let ene = HashMap::from([(NettingSetId::new("NS001"), vec![0.0; 4])]);
+//! let discount_factors: Vec<f64> = time_grid.iter().map(|t| (-0.05 * t).exp()).collect();
+//!
+//! let xva = XvaCalculator::new()
+//!     .compute_portfolio_xva(&portfolio, &ee, &ene, &time_grid, &discount_factors)
+//!     .unwrap();
+//! assert!(xva.cva > 0.0);
+//! ```
+
+#![deny(missing_docs)]
+#![deny(rustdoc::broken_intra_doc_links)]
+
+/// Core value types: currencies, dates, day counts and identifiers.
+pub mod types {
+    pub use pricer_core::types::*;
+}
+
+/// Market data: yield and credit curves, curve sets and volatility surfaces.
+pub mod market {
+    pub use pricer_core::market_data::*;
+}
+
+/// Instrument definitions across asset classes.
+pub mod instruments {
+    pub use pricer_models::instruments::*;
+}
+
+/// Pricing: closed-form models, Monte Carlo, Greeks, engine routing and
+/// payoff smoothing widths.
+pub mod pricing {
+    pub use pricer_models::analytical::{
+        Bachelier, BarrierKind, Black76, BlackScholes, BlackScholesBarrier,
+    };
+    pub use pricer_pricing::engine::{Engine, ProductKind};
+    pub use pricer_pricing::greeks::{GreeksConfig, GreeksMode, GreeksResult};
+    pub use pricer_pricing::mc::{
+        Fallback, GbmParams, GbmTermStructure, Greek, MonteCarloConfig, MonteCarloPricer,
+        PayoffParams, PiecewiseConstant, PricingDiagnostics, PricingResult,
+    };
+    pub use pricer_pricing::router::{EngineRouter, MarketInputs, RoutedPrice, RouterError};
+    pub use pricer_pricing::smoothing::{
+        SmoothingCandidate, SmoothingChoice, SmoothingError, SmoothingTuner,
+    };
+}
+
+/// Portfolio structure: trades, counterparties and netting sets.
+pub mod portfolio {
+    pub use pricer_risk::portfolio::*;
+}
+
+/// Exposure metrics (EE, EPE, ENE, PFE) and collateral simulation.
+pub mod exposure {
+    pub use pricer_risk::exposure::*;
+}
+
+/// Valuation adjustments: CVA, DVA, FVA and ColVA.
+pub mod xva {
+    pub use pricer_risk::xva::*;
+}
+
+/// Instrument with `f64` terms.
+pub type Instrument = instruments::Instrument<f64>;
+
+/// Yield curve with `f64` values.
+pub type Curve = market::CurveEnum<f64>;
+
+/// Named set of `f64` yield curves.
+pub type CurveSet = market::CurveSet<f64>;
+
+/// Interpolated volatility surface with `f64` values.
+pub type VolSurface = market::InterpolatedVolSurface<f64>;
+
+/// Black-Scholes model with `f64` parameters.
+pub type BlackScholes = pricing::BlackScholes<f64>;
+
+/// Vanilla option with `f64` terms.
+pub type VanillaOption = instruments::VanillaOption<f64>;
+
+/// Instrument parameters with `f64` values.
+pub type InstrumentParams = instruments::InstrumentParams<f64>;
+
+/// Exercise style with `f64` dates.
+pub type ExerciseStyle = instruments::ExerciseStyle<f64>;
+
+/// Prelude module for convenient imports
+pub mod prelude {
+    pub use crate::exposure::ExposureCalculator;
+    pub use crate::instruments::{PayoffType, Swap};
+    pub use crate::portfolio::{
+        Counterparty, CounterpartyId, CreditParams, NettingSet, NettingSetId, Portfolio,
+        PortfolioBuilder, PortfolioError, Trade, TradeId,
+    };
+    pub use crate::pricing::{GreeksConfig, GreeksResult, MonteCarloConfig, MonteCarloPricer};
+    pub use crate::types::Currency;
+    pub use crate::xva::{PortfolioXva, XvaCalculator, XvaConfig, XvaError};
+    pub use crate::{
+        BlackScholes, Curve, CurveSet, ExerciseStyle, Instrument, InstrumentParams, VanillaOption,
+        VolSurface,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Each alias names the same type as its source crate.
+    #[test]
+    fn test_aliases_are_source_types() {
+        fn same<T>(_: std::marker::PhantomData<T>, _: std::marker::PhantomData<T>) {}
+        use std::marker::PhantomData as P;
+
+        same(
+            P::<Instrument>,
+            P::<pricer_models::instruments::Instrument<f64>>,
+        );
+        same(P::<Curve>, P::<pricer_core::market_data::CurveEnum<f64>>);
+        same(
+            P::<portfolio::Portfolio>,
+            P::<pricer_risk::portfolio::Portfolio>,
+        );
+        same(P::<xva::XvaCalculator>, P::<pricer_risk::XvaCalculator>);
+        same(
+            P::<pricing::MonteCarloPricer>,
+            P::<pricer_pricing::MonteCarloPricer>,
+        );
+    }
+
+    #[test]
+    fn test_prelude_prices_vanilla() {
+        use crate::prelude::*;
+
+        let params = InstrumentParams::new(100.0, 1.0, 1.0).unwrap();
+        let call = VanillaOption::new(params, PayoffType::Call, ExerciseStyle::European, 1e-6);
+        let model = BlackScholes::new(100.0, 0.05, 0.2).unwrap();
+        let pv = model.price_option(&call).unwrap();
+        assert!((pv - model.price_call(100.0, 1.0)).abs() < 1e-10);
+    }
+}
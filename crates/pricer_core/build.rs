@@ -0,0 +1,50 @@
+//! Build script for pricer_core.
+//!
+//! Records the git commit of the source tree as `NEUTRYX_GIT_SHA`, read by
+//! `types::provenance::RunMetadata` at compile time.
+//!
+//! # Environment Variables
+//!
+//! - `NEUTRYX_GIT_SHA`: Commit to record (optional, overrides `git rev-parse`,
+//!   for builds from a source archive)
+
+use std::path::PathBuf;
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=NEUTRYX_GIT_SHA");
+
+    if std::env::var_os("NEUTRYX_GIT_SHA").is_some() {
+        // Passed through to the compiler unchanged
+        return;
+    }
+
+    if let Some(sha) = git(&["rev-parse", "HEAD"]) {
+        println!("cargo:rustc-env=NEUTRYX_GIT_SHA={sha}");
+    }
+
+    // Rebuild when HEAD moves, without rerunning on every build outside git
+    if let Some(git_dir) = git(&["rev-parse", "--absolute-git-dir"]).map(PathBuf::from) {
+        let head = git_dir.join("HEAD");
+        if head.exists() {
+            println!("cargo:rerun-if-changed={}", head.display());
+        }
+        if let Some(reference) = git(&["symbolic-ref", "-q", "HEAD"]) {
+            let reference = git_dir.join(reference);
+            if reference.exists() {
+                println!("cargo:rerun-if-changed={}", reference.display());
+            }
+        }
+    }
+}
+
+/// Runs git in the package directory, returning trimmed stdout on success.
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8(output.stdout).ok()?;
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
@@ -177,6 +177,7 @@ pub trait CreditCurve<T: Float> {
 /// let def_5y = curve.default_probability(5.0).unwrap();
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HazardRateCurve<T: Float> {
     /// Sorted tenor points (years)
     tenors: Vec<T>,
@@ -459,6 +460,7 @@ impl<T: Float> CreditCurve<T> for HazardRateCurve<T> {
 /// assert!((surv - expected).abs() < 1e-10);
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FlatHazardRateCurve<T: Float> {
     /// The constant hazard rate
     hazard_rate: T,
@@ -863,4 +865,46 @@ mod tests {
         let h2 = cloned.hazard_rate(2.0).unwrap();
         assert!((h1 - h2).abs() < 1e-10);
     }
+
+    #[test]
+    #[cfg(feature = "num-dual-mode")]
+    fn test_hazard_curve_pillar_deltas_match_bumped() {
+        use crate::types::dual::DualFloat;
+
+        let tenors = [1.0_f64, 3.0, 5.0, 10.0];
+        let hazard_rates = [0.010, 0.014, 0.017, 0.020];
+        let dual_tenors: Vec<DualFloat> = tenors.iter().map(|&x| DualFloat::constant(x)).collect();
+        let h = 1e-6;
+
+        for t in [0.5, 2.0, 4.0, 7.0, 12.0] {
+            for k in 0..hazard_rates.len() {
+                let curve =
+                    HazardRateCurve::new(&dual_tenors, &DualFloat::seeded(&hazard_rates, k), true)
+                        .unwrap();
+                let ad = curve
+                    .survival_probability(DualFloat::constant(t))
+                    .unwrap()
+                    .derivative();
+
+                let survival = |bump: f64| {
+                    let mut bumped = hazard_rates;
+                    bumped[k] += bump;
+                    HazardRateCurve::new(&tenors, &bumped, true)
+                        .unwrap()
+                        .survival_probability(t)
+                        .unwrap()
+                };
+                let fd = (survival(h) - survival(-h)) / (2.0 * h);
+
+                assert!(
+                    (ad - fd).abs() < 1e-8,
+                    "t={} pillar {}: AD {} vs bumped {}",
+                    t,
+                    k,
+                    ad,
+                    fd
+                );
+            }
+        }
+    }
 }
@@ -19,6 +19,7 @@ use num_traits::Float;
 /// - `Sofr`: Secured Overnight Financing Rate curve
 /// - `Tonar`: Tokyo Overnight Average Rate curve
 /// - `Euribor`: Euro Interbank Offered Rate curve
+/// - `Repo`: General collateral repo curve for financing trades
 /// - `Forward`: Generic forward curve
 /// - `Discount`: Discount curve for present value calculations
 /// - `Custom`: User-defined curve with custom name
@@ -44,6 +45,8 @@ pub enum CurveName {
     Tonar,
     /// Euro Interbank Offered Rate curve
     Euribor,
+    /// General collateral repo curve for financing trades
+    Repo,
     /// Generic forward curve
     Forward,
     /// Discount curve
@@ -70,6 +73,7 @@ impl CurveName {
             CurveName::Sofr => "SOFR",
             CurveName::Tonar => "TONAR",
             CurveName::Euribor => "EURIBOR",
+            CurveName::Repo => "REPO",
             CurveName::Forward => "FORWARD",
             CurveName::Discount => "DISCOUNT",
             CurveName::Custom(name) => name,
@@ -83,6 +87,84 @@ impl std::fmt::Display for CurveName {
     }
 }
 
+/// Curve names serialise as their string form. Deserialising an unknown
+/// name yields `Custom`. Custom names are interned for the life of the
+/// process, so at most `MAX_CUSTOM_CURVE_NAMES` distinct names of up to
+/// `MAX_CUSTOM_CURVE_NAME_LEN` bytes are accepted; anything beyond that
+/// is rejected rather than growing the interned set without bound.
+#[cfg(feature = "serde")]
+impl serde::Serialize for CurveName {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Maximum number of distinct custom curve names accepted by deserialisation.
+#[cfg(feature = "serde")]
+const MAX_CUSTOM_CURVE_NAMES: usize = 256;
+
+/// Maximum length in bytes of a deserialised custom curve name.
+#[cfg(feature = "serde")]
+const MAX_CUSTOM_CURVE_NAME_LEN: usize = 64;
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for CurveName {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+        use std::collections::HashSet;
+        use std::sync::{Mutex, OnceLock};
+
+        static CUSTOM_NAMES: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+
+        let name = String::deserialize(deserializer)?;
+        Ok(match name.as_str() {
+            "OIS" => CurveName::Ois,
+            "SOFR" => CurveName::Sofr,
+            "TONAR" => CurveName::Tonar,
+            "EURIBOR" => CurveName::Euribor,
+            "REPO" => CurveName::Repo,
+            "FORWARD" => CurveName::Forward,
+            "DISCOUNT" => CurveName::Discount,
+            _ => {
+                let mut names = CUSTOM_NAMES
+                    .get_or_init(Default::default)
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner());
+                let interned = intern_custom_name(&mut names, name, MAX_CUSTOM_CURVE_NAMES)
+                    .map_err(D::Error::custom)?;
+                CurveName::Custom(interned)
+            }
+        })
+    }
+}
+
+/// Look up `name` in `names`, adding it if there is room below `capacity`.
+#[cfg(feature = "serde")]
+fn intern_custom_name(
+    names: &mut std::collections::HashSet<&'static str>,
+    name: String,
+    capacity: usize,
+) -> Result<&'static str, String> {
+    if let Some(interned) = names.get(name.as_str()) {
+        return Ok(interned);
+    }
+    if name.len() > MAX_CUSTOM_CURVE_NAME_LEN {
+        return Err(format!(
+            "custom curve name exceeds {} bytes",
+            MAX_CUSTOM_CURVE_NAME_LEN
+        ));
+    }
+    if names.len() >= capacity {
+        return Err(format!(
+            "too many custom curve names (limit {}), rejecting '{}'",
+            capacity, name
+        ));
+    }
+    let leaked: &'static str = Box::leak(name.into_boxed_str());
+    names.insert(leaked);
+    Ok(leaked)
+}
+
 /// Static dispatch enum wrapping concrete yield curve implementations.
 ///
 /// This enum provides efficient static dispatch for yield curve operations,
@@ -112,6 +194,7 @@ impl std::fmt::Display for CurveName {
 /// assert!((df - 0.951229).abs() < 1e-5);
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CurveEnum<T: Float> {
     /// Flat (constant rate) yield curve
     Flat(FlatCurve<T>),
@@ -199,6 +282,7 @@ mod tests {
         assert_eq!(CurveName::Sofr.as_str(), "SOFR");
         assert_eq!(CurveName::Tonar.as_str(), "TONAR");
         assert_eq!(CurveName::Euribor.as_str(), "EURIBOR");
+        assert_eq!(CurveName::Repo.as_str(), "REPO");
         assert_eq!(CurveName::Forward.as_str(), "FORWARD");
         assert_eq!(CurveName::Discount.as_str(), "DISCOUNT");
         assert_eq!(CurveName::Custom("MY_CURVE").as_str(), "MY_CURVE");
@@ -378,4 +462,78 @@ mod tests {
         let debug_str = format!("{:?}", curve);
         assert!(debug_str.contains("Flat"));
     }
+
+    #[cfg(feature = "serde")]
+    mod serde_tests {
+        use super::*;
+
+        #[test]
+        fn test_curve_name_schema() {
+            assert_eq!(
+                serde_json::to_string(&CurveName::Sofr).unwrap(),
+                r#""SOFR""#
+            );
+            assert_eq!(
+                serde_json::to_string(&CurveName::Custom("SONIA")).unwrap(),
+                r#""SONIA""#
+            );
+
+            let parsed: CurveName = serde_json::from_str(r#""EURIBOR""#).unwrap();
+            assert_eq!(parsed, CurveName::Euribor);
+            let parsed: CurveName = serde_json::from_str(r#""SONIA""#).unwrap();
+            assert_eq!(parsed, CurveName::Custom("SONIA"));
+        }
+
+        #[test]
+        fn test_custom_curve_names_are_interned() {
+            let first: CurveName = serde_json::from_str(r#""SARON""#).unwrap();
+            let second: CurveName = serde_json::from_str(r#""SARON""#).unwrap();
+            match (first, second) {
+                (CurveName::Custom(a), CurveName::Custom(b)) => assert!(std::ptr::eq(a, b)),
+                _ => panic!("expected custom curve names"),
+            }
+        }
+
+        #[test]
+        fn test_custom_curve_names_are_bounded() {
+            let mut names = std::collections::HashSet::new();
+            let a = intern_custom_name(&mut names, "CAP_A".to_string(), 1).unwrap();
+            assert_eq!(a, "CAP_A");
+            // Known names are still resolved once the set is full.
+            assert!(intern_custom_name(&mut names, "CAP_A".to_string(), 1).is_ok());
+            assert!(intern_custom_name(&mut names, "CAP_B".to_string(), 1).is_err());
+            assert_eq!(names.len(), 1);
+
+            let long = "X".repeat(MAX_CUSTOM_CURVE_NAME_LEN + 1);
+            assert!(serde_json::from_str::<CurveName>(&format!("\"{}\"", long)).is_err());
+        }
+
+        #[test]
+        fn test_curve_enum_schema() {
+            let curve = CurveEnum::flat(0.05_f64);
+            let json = serde_json::to_string(&curve).unwrap();
+            assert_eq!(json, r#"{"Flat":{"rate":0.05}}"#);
+
+            let curve = CurveEnum::Interpolated(
+                InterpolatedCurve::new(
+                    &[0.5, 1.0, 2.0],
+                    &[0.02, 0.025, 0.03],
+                    CurveInterpolation::Linear,
+                    true,
+                )
+                .unwrap(),
+            );
+            let json = serde_json::to_string(&curve).unwrap();
+            assert_eq!(
+                json,
+                r#"{"Interpolated":{"tenors":[0.5,1.0,2.0],"rates":[0.02,0.025,0.03],"method":"Linear","allow_extrapolation":true}}"#
+            );
+
+            let parsed: CurveEnum<f64> = serde_json::from_str(&json).unwrap();
+            assert_eq!(
+                parsed.discount_factor(1.5).unwrap(),
+                curve.discount_factor(1.5).unwrap()
+            );
+        }
+    }
 }
@@ -46,6 +46,7 @@ use std::collections::HashMap;
 /// let df = discount.discount_factor(1.0).unwrap();
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CurveSet<T: Float> {
     /// Named curves stored in a HashMap
     curves: HashMap<CurveName, CurveEnum<T>>,
@@ -703,4 +704,35 @@ mod tests {
         assert!((df - (-0.03_f64).exp()).abs() < 1e-10);
         assert!((fwd_rate - 0.035).abs() < 1e-10);
     }
+
+    #[cfg(feature = "serde")]
+    mod serde_tests {
+        use super::*;
+
+        #[test]
+        fn test_curve_set_roundtrip() {
+            let mut curves = CurveSet::new();
+            curves.insert(CurveName::Ois, CurveEnum::flat(0.03_f64));
+            curves.insert(CurveName::Custom("SONIA"), CurveEnum::flat(0.04));
+            curves.set_discount_curve(CurveName::Ois);
+
+            let json = serde_json::to_string(&curves).unwrap();
+            let parsed: CurveSet<f64> = serde_json::from_str(&json).unwrap();
+
+            assert_eq!(parsed.len(), 2);
+            assert!(parsed.get(&CurveName::Custom("SONIA")).is_some());
+            assert_eq!(
+                parsed
+                    .discount_curve()
+                    .unwrap()
+                    .discount_factor(1.0)
+                    .unwrap(),
+                curves
+                    .discount_curve()
+                    .unwrap()
+                    .discount_factor(1.0)
+                    .unwrap()
+            );
+        }
+    }
 }
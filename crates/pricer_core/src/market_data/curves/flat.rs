@@ -29,6 +29,7 @@ use num_traits::Float;
 /// assert_eq!(curve.zero_rate(5.0).unwrap(), 0.05);
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FlatCurve<T: Float> {
     /// The constant interest rate
     rate: T,
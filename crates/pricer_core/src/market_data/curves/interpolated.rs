@@ -10,6 +10,7 @@ use num_traits::Float;
 /// Determines how rates or discount factors are interpolated between
 /// pillar points.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CurveInterpolation {
     /// Linear interpolation on zero rates.
     ///
@@ -57,6 +58,7 @@ pub enum CurveInterpolation {
 /// let df = curve.discount_factor(0.75).unwrap();
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InterpolatedCurve<T: Float> {
     /// Sorted tenor points (years)
     tenors: Vec<T>,
@@ -522,4 +524,65 @@ mod tests {
         let df = curve.discount_factor(1.0_f32).unwrap();
         assert!(df > 0.0 && df < 1.0);
     }
+
+    // ========================================
+    // Pillar Sensitivity Tests
+    // ========================================
+
+    /// Discount factor deltas to each pillar rate by AD and by central bumps.
+    #[cfg(feature = "num-dual-mode")]
+    fn assert_pillar_deltas_match_bumped(method: CurveInterpolation, t: f64) {
+        use crate::types::dual::DualFloat;
+
+        let tenors = [0.5_f64, 1.0, 2.0, 5.0, 10.0];
+        let rates = [0.020, 0.024, 0.027, 0.031, 0.033];
+        let dual_tenors: Vec<DualFloat> = tenors.iter().map(|&x| DualFloat::constant(x)).collect();
+        let h = 1e-6;
+
+        for k in 0..rates.len() {
+            let curve =
+                InterpolatedCurve::new(&dual_tenors, &DualFloat::seeded(&rates, k), method, true)
+                    .unwrap();
+            let ad = curve
+                .discount_factor(DualFloat::constant(t))
+                .unwrap()
+                .derivative();
+
+            let df = |bump: f64| {
+                let mut bumped = rates;
+                bumped[k] += bump;
+                InterpolatedCurve::new(&tenors, &bumped, method, true)
+                    .unwrap()
+                    .discount_factor(t)
+                    .unwrap()
+            };
+            let fd = (df(h) - df(-h)) / (2.0 * h);
+
+            assert!(
+                (ad - fd).abs() < 1e-8,
+                "{:?} t={} pillar {}: AD {} vs bumped {}",
+                method,
+                t,
+                k,
+                ad,
+                fd
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "num-dual-mode")]
+    fn test_linear_pillar_deltas_match_bumped() {
+        for t in [0.25, 0.75, 3.0, 7.5, 12.0] {
+            assert_pillar_deltas_match_bumped(CurveInterpolation::Linear, t);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "num-dual-mode")]
+    fn test_log_linear_pillar_deltas_match_bumped() {
+        for t in [0.25, 0.75, 3.0, 7.5, 12.0] {
+            assert_pillar_deltas_match_bumped(CurveInterpolation::LogLinear, t);
+        }
+    }
 }
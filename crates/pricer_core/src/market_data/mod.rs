@@ -6,13 +6,16 @@
 //! # Architecture
 //!
 //! All structures are generic over `T: Float` to support both standard
-//! floating-point types (f64, f32) and automatic differentiation types (Dual64).
-//! This design ensures compatibility with Enzyme AD at LLVM level.
+//! floating-point types (f64, f32) and automatic differentiation types
+//! (`DualFloat`). This design ensures compatibility with Enzyme AD at LLVM
+//! level: seeding a pillar rate or grid volatility with
+//! `DualFloat::seeded` gives its delta or vega in one pass.
 //!
 //! # Components
 //!
 //! - [`curves`]: Yield curve trait and implementations (FlatCurve, InterpolatedCurve)
 //! - [`surfaces`]: Volatility surface trait and implementations (FlatVol, InterpolatedVolSurface)
+//! - [`snapshot`]: Market of one currency on one date (MarketSnapshot)
 //! - [`error`]: Market data error types (MarketDataError)
 //!
 //! # Example
@@ -34,6 +37,7 @@
 
 pub mod curves;
 pub mod error;
+pub mod snapshot;
 pub mod surfaces;
 
 // Re-export commonly used types
@@ -42,6 +46,8 @@ pub use curves::{
     FlatHazardRateCurve, HazardRateCurve, InterpolatedCurve, YieldCurve,
 };
 pub use error::MarketDataError;
+pub use snapshot::MarketSnapshot;
 pub use surfaces::{
-    FlatVol, FxDeltaPoint, FxVolatilitySurface, InterpolatedVolSurface, VolatilitySurface,
+    FlatVol, FxAtmConvention, FxDeltaConvention, FxDeltaPoint, FxSmileQuote, FxSmileSurface,
+    FxVolatilitySurface, InterpolatedVolSurface, SwaptionVolCube, VolatilitySurface,
 };
@@ -0,0 +1,139 @@
+//! Market snapshot for valuing a trade's cashflows.
+//!
+//! A [`MarketSnapshot`] holds the market of one currency on one date: the
+//! discount curve, and for equity underlyings the spot and a flat
+//! volatility. Times are in years from the snapshot date on Act/365.
+
+use num_traits::Float;
+
+use super::curves::{CurveEnum, YieldCurve};
+use super::error::MarketDataError;
+use crate::types::time::Date;
+use crate::types::Currency;
+
+/// Days per year converting snapshot times to dates (Act/365).
+const DAYS_PER_YEAR: f64 = 365.0;
+
+/// Market of one currency on one date.
+///
+/// # Examples
+///
+/// ```
+/// use pricer_core::market_data::curves::CurveEnum;
+/// use pricer_core::market_data::MarketSnapshot;
+/// use pricer_core::types::time::Date;
+/// use pricer_core::types::Currency;
+///
+/// let as_of = Date::from_ymd(2026, 1, 15).unwrap();
+/// let market = MarketSnapshot::new(as_of, Currency::USD, CurveEnum::flat(0.03_f64))
+///     .with_spot(100.0)
+///     .with_volatility(0.2);
+///
+/// assert!((market.discount_factor(1.0).unwrap() - (-0.03_f64).exp()).abs() < 1e-12);
+/// assert_eq!(market.date_after(0.5), Date::from_ymd(2026, 7, 17).unwrap());
+/// assert_eq!(market.spot().unwrap(), 100.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MarketSnapshot<T: Float> {
+    as_of: Date,
+    currency: Currency,
+    discount_curve: CurveEnum<T>,
+    spot: Option<T>,
+    volatility: T,
+}
+
+impl<T: Float> MarketSnapshot<T> {
+    /// Creates a snapshot on `as_of` discounting on `discount_curve`, with
+    /// no spot and zero volatility.
+    pub fn new(as_of: Date, currency: Currency, discount_curve: CurveEnum<T>) -> Self {
+        Self {
+            as_of,
+            currency,
+            discount_curve,
+            spot: None,
+            volatility: T::zero(),
+        }
+    }
+
+    /// Sets the spot of the underlying.
+    pub fn with_spot(mut self, spot: T) -> Self {
+        self.spot = Some(spot);
+        self
+    }
+
+    /// Sets the flat volatility of the underlying.
+    pub fn with_volatility(mut self, volatility: T) -> Self {
+        self.volatility = volatility;
+        self
+    }
+
+    /// Returns the snapshot date.
+    #[inline]
+    pub fn as_of(&self) -> Date {
+        self.as_of
+    }
+
+    /// Returns the currency.
+    #[inline]
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// Returns the discount curve.
+    #[inline]
+    pub fn discount_curve(&self) -> &CurveEnum<T> {
+        &self.discount_curve
+    }
+
+    /// Returns the spot of the underlying.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MarketDataError::MissingData` if no spot was set.
+    pub fn spot(&self) -> Result<T, MarketDataError> {
+        self.spot.ok_or_else(|| MarketDataError::MissingData {
+            description: format!("{} spot", self.currency),
+        })
+    }
+
+    /// Returns the flat volatility of the underlying.
+    #[inline]
+    pub fn volatility(&self) -> T {
+        self.volatility
+    }
+
+    /// Discount factor to `t` years from the snapshot date.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the curve cannot discount to `t`.
+    pub fn discount_factor(&self, t: T) -> Result<T, MarketDataError> {
+        self.discount_curve.discount_factor(t)
+    }
+
+    /// Date `t` years after the snapshot date, to the nearest day.
+    pub fn date_after(&self, t: T) -> Date {
+        let days = t.to_f64().unwrap_or(0.0) * DAYS_PER_YEAR;
+        self.as_of + days.round() as i64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_spot() {
+        let market = MarketSnapshot::new(
+            Date::from_ymd(2026, 1, 15).unwrap(),
+            Currency::EUR,
+            CurveEnum::flat(0.02_f64),
+        );
+        assert!(matches!(
+            market.spot(),
+            Err(MarketDataError::MissingData { .. })
+        ));
+        assert_eq!(market.volatility(), 0.0);
+        assert_eq!(market.date_after(0.0), market.as_of());
+    }
+}
@@ -27,6 +27,7 @@ use num_traits::Float;
 /// assert_eq!(surface.volatility(120.0, 2.0).unwrap(), 0.20);
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FlatVol<T: Float> {
     /// The constant implied volatility
     sigma: T,
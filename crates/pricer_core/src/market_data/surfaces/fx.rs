@@ -28,6 +28,7 @@ use num_traits::Float;
 /// assert!((atm.as_delta() - 0.5).abs() < 1e-10);
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FxDeltaPoint {
     /// 10 delta put
     Put10D,
@@ -114,6 +115,7 @@ impl FxDeltaPoint {
 /// assert!((atm_vol - 0.11).abs() < 1e-10);
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FxVolatilitySurface<T: Float> {
     /// Delta points (X-axis)
     deltas: Vec<T>,
@@ -0,0 +1,675 @@
+//! FX volatility smile built from market quotes.
+//!
+//! FX volatilities are quoted per expiry as an ATM volatility with 25-delta
+//! (and optionally 10-delta) risk reversals and butterflies. Under the
+//! smile convention the wing volatilities are
+//!
+//! ```text
+//! σ(ΔC) = σ_ATM + BF_Δ + RR_Δ / 2
+//! σ(ΔP) = σ_ATM + BF_Δ − RR_Δ / 2
+//! ```
+//!
+//! [`FxSmileSurface`] converts each quote to strike space: the ATM strike
+//! follows the [`FxAtmConvention`], and each wing strike is the one whose
+//! delta under the pair's [`FxDeltaConvention`] (spot or forward,
+//! premium-adjusted or not) equals the quoted delta at the wing
+//! volatility. Within an expiry the smile is a natural cubic spline in
+//! log-moneyness `ln(K/F)`, flat beyond the outermost pillars; between
+//! expiries total variance `σ²T` is interpolated linearly at constant
+//! log-moneyness, so the smile moves with the forward.
+
+use std::f64::consts::{FRAC_1_SQRT_2, PI};
+
+use super::VolatilitySurface;
+use crate::market_data::error::MarketDataError;
+use crate::math::interpolators::{CubicSplineInterpolator, Interpolator};
+use crate::math::solvers::{BrentSolver, SolverConfig};
+
+/// Delta convention of an FX option quote.
+///
+/// With `d1,2 = (ln(F/K) ± σ²T/2) / (σ√T)`, `ω = ±1` for calls and puts
+/// and `DF_f` the foreign discount factor:
+///
+/// | Convention                 | Delta                      |
+/// |----------------------------|----------------------------|
+/// | `Spot`                     | `ω DF_f N(ω d1)`           |
+/// | `Forward`                  | `ω N(ω d1)`                |
+/// | `SpotPremiumAdjusted`      | `ω DF_f (K/F) N(ω d2)`     |
+/// | `ForwardPremiumAdjusted`   | `ω (K/F) N(ω d2)`          |
+///
+/// Premium-adjusted deltas apply when the premium is paid in the foreign
+/// (base) currency, e.g. USD/JPY.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FxDeltaConvention {
+    /// Spot delta
+    Spot,
+    /// Forward delta
+    Forward,
+    /// Premium-adjusted spot delta
+    SpotPremiumAdjusted,
+    /// Premium-adjusted forward delta
+    ForwardPremiumAdjusted,
+}
+
+impl FxDeltaConvention {
+    /// Whether the delta is premium-adjusted.
+    #[inline]
+    pub fn is_premium_adjusted(&self) -> bool {
+        matches!(
+            self,
+            FxDeltaConvention::SpotPremiumAdjusted | FxDeltaConvention::ForwardPremiumAdjusted
+        )
+    }
+
+    /// Whether the delta is a spot delta.
+    #[inline]
+    pub fn is_spot(&self) -> bool {
+        matches!(
+            self,
+            FxDeltaConvention::Spot | FxDeltaConvention::SpotPremiumAdjusted
+        )
+    }
+
+    /// Delta of an option under this convention.
+    ///
+    /// # Arguments
+    ///
+    /// * `is_call` - Call (positive delta) or put (negative delta)
+    /// * `forward` - Outright forward to expiry
+    /// * `strike` - Strike
+    /// * `vol` - Volatility of the option
+    /// * `expiry` - Time to expiry in years
+    /// * `foreign_df` - Foreign currency discount factor to expiry
+    pub fn delta(
+        &self,
+        is_call: bool,
+        forward: f64,
+        strike: f64,
+        vol: f64,
+        expiry: f64,
+        foreign_df: f64,
+    ) -> f64 {
+        let omega = if is_call { 1.0 } else { -1.0 };
+        let s = vol * expiry.sqrt();
+        let d1 = ((forward / strike).ln() + 0.5 * s * s) / s;
+        let forward_delta = if self.is_premium_adjusted() {
+            omega * strike / forward * norm_cdf(omega * (d1 - s))
+        } else {
+            omega * norm_cdf(omega * d1)
+        };
+        if self.is_spot() {
+            foreign_df * forward_delta
+        } else {
+            forward_delta
+        }
+    }
+}
+
+/// Strike of the ATM volatility quote.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FxAtmConvention {
+    /// ATM forward: `K = F`
+    Forward,
+    /// Delta-neutral straddle: the call and put deltas sum to zero,
+    /// `K = F exp(σ²T/2)`, or `F exp(−σ²T/2)` for premium-adjusted deltas
+    DeltaNeutral,
+}
+
+/// Market volatility quotes of one FX option expiry.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FxSmileQuote {
+    /// Time to expiry in years
+    pub expiry: f64,
+    /// ATM volatility
+    pub atm: f64,
+    /// 25-delta risk reversal, `σ(25C) − σ(25P)`
+    pub risk_reversal_25d: f64,
+    /// 25-delta smile butterfly, `(σ(25C) + σ(25P)) / 2 − σ_ATM`
+    pub butterfly_25d: f64,
+    /// 10-delta risk reversal
+    pub risk_reversal_10d: Option<f64>,
+    /// 10-delta smile butterfly
+    pub butterfly_10d: Option<f64>,
+}
+
+impl FxSmileQuote {
+    /// Create a quote with ATM and 25-delta wings.
+    pub fn new(expiry: f64, atm: f64, risk_reversal_25d: f64, butterfly_25d: f64) -> Self {
+        Self {
+            expiry,
+            atm,
+            risk_reversal_25d,
+            butterfly_25d,
+            risk_reversal_10d: None,
+            butterfly_10d: None,
+        }
+    }
+
+    /// Add 10-delta wings.
+    pub fn with_10d(mut self, risk_reversal: f64, butterfly: f64) -> Self {
+        self.risk_reversal_10d = Some(risk_reversal);
+        self.butterfly_10d = Some(butterfly);
+        self
+    }
+
+    /// Call and put wing volatilities at 25 and, if quoted, 10 delta, as
+    /// `(delta, call vol, put vol)`.
+    fn wings(&self) -> Vec<(f64, f64, f64)> {
+        let wing = |delta: f64, rr: f64, bf: f64| {
+            (delta, self.atm + bf + 0.5 * rr, self.atm + bf - 0.5 * rr)
+        };
+        let mut wings = vec![wing(0.25, self.risk_reversal_25d, self.butterfly_25d)];
+        if let (Some(rr), Some(bf)) = (self.risk_reversal_10d, self.butterfly_10d) {
+            wings.push(wing(0.10, rr, bf));
+        }
+        wings
+    }
+}
+
+/// Smile of one expiry in strike space.
+#[derive(Debug, Clone)]
+pub struct FxSmileSlice {
+    expiry: f64,
+    forward: f64,
+    atm_strike: f64,
+    strikes: Vec<f64>,
+    volatilities: Vec<f64>,
+    spline: CubicSplineInterpolator<f64>,
+}
+
+impl FxSmileSlice {
+    /// Time to expiry in years.
+    #[inline]
+    pub fn expiry(&self) -> f64 {
+        self.expiry
+    }
+
+    /// Outright forward to expiry.
+    #[inline]
+    pub fn forward(&self) -> f64 {
+        self.forward
+    }
+
+    /// Strike of the ATM quote.
+    #[inline]
+    pub fn atm_strike(&self) -> f64 {
+        self.atm_strike
+    }
+
+    /// Pillar strikes, ascending: 10P, 25P, ATM, 25C, 10C.
+    #[inline]
+    pub fn strikes(&self) -> &[f64] {
+        &self.strikes
+    }
+
+    /// Pillar volatilities, by strike.
+    #[inline]
+    pub fn volatilities(&self) -> &[f64] {
+        &self.volatilities
+    }
+
+    /// Smile volatility at a log-moneyness `ln(K/F)`.
+    fn volatility_at(&self, moneyness: f64) -> Result<f64, MarketDataError> {
+        let (lo, hi) = self.spline.domain();
+        self.spline
+            .interpolate(moneyness.clamp(lo, hi))
+            .map_err(MarketDataError::from)
+    }
+}
+
+/// FX volatility surface in strike space, built from ATM, risk reversal
+/// and butterfly quotes.
+///
+/// # Example
+///
+/// ```
+/// use pricer_core::market_data::surfaces::{
+///     FxAtmConvention, FxDeltaConvention, FxSmileQuote, FxSmileSurface, VolatilitySurface,
+/// };
+///
+/// let quotes = [
+///     FxSmileQuote::new(0.25, 0.075, -0.004, 0.0020),
+///     FxSmileQuote::new(1.0, 0.080, -0.005, 0.0025).with_10d(-0.010, 0.0080),
+/// ];
+/// let surface = FxSmileSurface::new(
+///     1.10,
+///     0.05,
+///     0.03,
+///     FxDeltaConvention::Spot,
+///     FxAtmConvention::DeltaNeutral,
+///     &quotes,
+/// )
+/// .unwrap();
+///
+/// // The ATM quote is recovered at its strike
+/// let slice = &surface.slices()[1];
+/// let atm = surface.volatility(slice.atm_strike(), 1.0).unwrap();
+/// assert!((atm - 0.080).abs() < 1e-12);
+///
+/// // Negative risk reversal: puts trade over calls
+/// let (put, call) = (slice.strikes()[1], slice.strikes()[3]);
+/// assert!(surface.volatility(put, 1.0).unwrap() > surface.volatility(call, 1.0).unwrap());
+/// ```
+#[derive(Debug, Clone)]
+pub struct FxSmileSurface {
+    spot: f64,
+    domestic_rate: f64,
+    foreign_rate: f64,
+    delta_convention: FxDeltaConvention,
+    atm_convention: FxAtmConvention,
+    slices: Vec<FxSmileSlice>,
+}
+
+impl FxSmileSurface {
+    /// Build the surface from quotes.
+    ///
+    /// # Arguments
+    ///
+    /// * `spot` - FX spot rate (quote currency per unit of base currency)
+    /// * `domestic_rate` - Continuously compounded quote currency rate
+    /// * `foreign_rate` - Continuously compounded base currency rate
+    /// * `delta_convention` - Delta convention of the wing quotes
+    /// * `atm_convention` - Strike convention of the ATM quote
+    /// * `quotes` - Quotes by expiry, in any order
+    ///
+    /// # Errors
+    ///
+    /// Fails if spot, an expiry or a pillar volatility is not positive, if
+    /// two quotes share an expiry, if a quoted delta cannot be reached (a
+    /// premium-adjusted call delta is bounded) or if the pillar strikes of
+    /// an expiry are not increasing.
+    pub fn new(
+        spot: f64,
+        domestic_rate: f64,
+        foreign_rate: f64,
+        delta_convention: FxDeltaConvention,
+        atm_convention: FxAtmConvention,
+        quotes: &[FxSmileQuote],
+    ) -> Result<Self, MarketDataError> {
+        if quotes.is_empty() {
+            return Err(MarketDataError::InsufficientData { got: 0, need: 1 });
+        }
+        if spot <= 0.0 {
+            return Err(MarketDataError::InvalidStrike { strike: spot });
+        }
+
+        let mut quotes = quotes.to_vec();
+        quotes.sort_by(|a, b| a.expiry.total_cmp(&b.expiry));
+        let mut surface = Self {
+            spot,
+            domestic_rate,
+            foreign_rate,
+            delta_convention,
+            atm_convention,
+            slices: Vec::with_capacity(quotes.len()),
+        };
+        for (i, quote) in quotes.iter().enumerate() {
+            if quote.expiry <= 0.0 || (i > 0 && quote.expiry <= quotes[i - 1].expiry) {
+                return Err(MarketDataError::InvalidExpiry {
+                    expiry: quote.expiry,
+                });
+            }
+            let slice = surface.slice(quote)?;
+            surface.slices.push(slice);
+        }
+        Ok(surface)
+    }
+
+    /// FX spot rate.
+    #[inline]
+    pub fn spot(&self) -> f64 {
+        self.spot
+    }
+
+    /// Delta convention of the quotes.
+    #[inline]
+    pub fn delta_convention(&self) -> FxDeltaConvention {
+        self.delta_convention
+    }
+
+    /// ATM convention of the quotes.
+    #[inline]
+    pub fn atm_convention(&self) -> FxAtmConvention {
+        self.atm_convention
+    }
+
+    /// Smiles by expiry, ascending.
+    #[inline]
+    pub fn slices(&self) -> &[FxSmileSlice] {
+        &self.slices
+    }
+
+    /// Outright forward, `S exp((r_d − r_f) T)`.
+    #[inline]
+    pub fn forward(&self, expiry: f64) -> f64 {
+        self.spot * ((self.domestic_rate - self.foreign_rate) * expiry).exp()
+    }
+
+    /// Delta of an option at its smile volatility, under the quote
+    /// convention.
+    pub fn delta(&self, is_call: bool, strike: f64, expiry: f64) -> Result<f64, MarketDataError> {
+        let vol = self.volatility(strike, expiry)?;
+        Ok(self.delta_convention.delta(
+            is_call,
+            self.forward(expiry),
+            strike,
+            vol,
+            expiry,
+            (-self.foreign_rate * expiry).exp(),
+        ))
+    }
+
+    /// Convert one expiry's quotes to strike space.
+    fn slice(&self, quote: &FxSmileQuote) -> Result<FxSmileSlice, MarketDataError> {
+        let expiry = quote.expiry;
+        let forward = self.forward(expiry);
+        let foreign_df = (-self.foreign_rate * expiry).exp();
+
+        let atm_variance = quote.atm * quote.atm * expiry;
+        let atm_strike = match self.atm_convention {
+            FxAtmConvention::Forward => forward,
+            FxAtmConvention::DeltaNeutral if self.delta_convention.is_premium_adjusted() => {
+                forward * (-0.5 * atm_variance).exp()
+            }
+            FxAtmConvention::DeltaNeutral => forward * (0.5 * atm_variance).exp(),
+        };
+
+        let mut pillars = vec![(atm_strike, quote.atm)];
+        for (delta, call_vol, put_vol) in quote.wings() {
+            for (is_call, vol) in [(true, call_vol), (false, put_vol)] {
+                let target = if is_call { delta } else { -delta };
+                let strike = self.strike_for_delta(target, vol, expiry, forward, foreign_df)?;
+                pillars.push((strike, vol));
+            }
+        }
+        pillars.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        for (i, &(strike, vol)) in pillars.iter().enumerate() {
+            if vol <= 0.0 {
+                return Err(MarketDataError::InterpolationFailed {
+                    reason: format!(
+                        "Volatility must be positive, got {} at expiry {}",
+                        vol, expiry
+                    ),
+                });
+            }
+            if i > 0 && strike <= pillars[i - 1].0 {
+                return Err(MarketDataError::InterpolationFailed {
+                    reason: format!("Smile strikes overlap at expiry {}", expiry),
+                });
+            }
+        }
+
+        let strikes: Vec<f64> = pillars.iter().map(|p| p.0).collect();
+        let volatilities: Vec<f64> = pillars.iter().map(|p| p.1).collect();
+        let moneyness: Vec<f64> = strikes.iter().map(|k| (k / forward).ln()).collect();
+        let spline = CubicSplineInterpolator::new(&moneyness, &volatilities)?;
+        Ok(FxSmileSlice {
+            expiry,
+            forward,
+            atm_strike,
+            strikes,
+            volatilities,
+            spline,
+        })
+    }
+
+    /// Strike whose delta at `vol` is `target` (negative for puts).
+    fn strike_for_delta(
+        &self,
+        target: f64,
+        vol: f64,
+        expiry: f64,
+        forward: f64,
+        foreign_df: f64,
+    ) -> Result<f64, MarketDataError> {
+        if vol <= 0.0 {
+            return Err(MarketDataError::InterpolationFailed {
+                reason: format!("Volatility must be positive, got {}", vol),
+            });
+        }
+        let is_call = target > 0.0;
+        let convention = self.delta_convention;
+        let delta = |x: f64| {
+            convention.delta(is_call, forward, forward * x.exp(), vol, expiry, foreign_df) - target
+        };
+
+        // Deltas fall with log-moneyness x = ln(K/F), except the
+        // premium-adjusted call delta, which rises to a maximum first; its
+        // quoted strike lies beyond the maximum.
+        let s = vol * expiry.sqrt();
+        let mut lo = -10.0 * s - s * s;
+        let hi = 10.0 * s + s * s;
+        if is_call && convention.is_premium_adjusted() {
+            lo = Self::premium_adjusted_call_peak(s);
+            if delta(lo) < 0.0 {
+                return Err(MarketDataError::InterpolationFailed {
+                    reason: format!(
+                        "Premium-adjusted call delta {} is not attainable at volatility {}",
+                        target, vol
+                    ),
+                });
+            }
+        }
+
+        let solver = BrentSolver::new(SolverConfig::new(1e-14, 200));
+        solver
+            .find_root(delta, lo, hi)
+            .map(|x| forward * x.exp())
+            .map_err(|e| MarketDataError::InterpolationFailed {
+                reason: format!("Strike for delta {}: {}", target, e),
+            })
+    }
+
+    /// Log-moneyness of the largest premium-adjusted call delta,
+    /// `(K/F) N(d2)`, at total volatility `s`: where `s N(d2) = n(d2)`.
+    fn premium_adjusted_call_peak(s: f64) -> f64 {
+        let solver = BrentSolver::new(SolverConfig::default());
+        let d2 = solver
+            .find_root(|d: f64| s - norm_pdf(d) / norm_cdf(d), -10.0, 10.0)
+            .unwrap_or(0.0);
+        -d2 * s - 0.5 * s * s
+    }
+}
+
+impl VolatilitySurface<f64> for FxSmileSurface {
+    /// Smile volatility of a strike.
+    ///
+    /// Before the first expiry the first smile applies, after the last the
+    /// last; in between total variance is linear in expiry at constant
+    /// log-moneyness.
+    fn volatility(&self, strike: f64, expiry: f64) -> Result<f64, MarketDataError> {
+        if strike <= 0.0 {
+            return Err(MarketDataError::InvalidStrike { strike });
+        }
+        if expiry <= 0.0 {
+            return Err(MarketDataError::InvalidExpiry { expiry });
+        }
+        let moneyness = (strike / self.forward(expiry)).ln();
+
+        let after = self.slices.partition_point(|s| s.expiry < expiry);
+        let vol = if after == 0 {
+            self.slices[0].volatility_at(moneyness)?
+        } else if after == self.slices.len() {
+            self.slices[after - 1].volatility_at(moneyness)?
+        } else {
+            let (near, far) = (&self.slices[after - 1], &self.slices[after]);
+            let near_variance = near.volatility_at(moneyness)?.powi(2) * near.expiry;
+            let far_variance = far.volatility_at(moneyness)?.powi(2) * far.expiry;
+            let weight = (expiry - near.expiry) / (far.expiry - near.expiry);
+            let variance = near_variance + weight * (far_variance - near_variance);
+            (variance / expiry).sqrt()
+        };
+
+        if vol > 0.0 {
+            Ok(vol)
+        } else {
+            Err(MarketDataError::InterpolationFailed {
+                reason: format!(
+                    "Smile volatility {} at strike {} and expiry {} is not positive",
+                    vol, strike, expiry
+                ),
+            })
+        }
+    }
+
+    /// Pillar strikes spanned by all expiries.
+    fn strike_domain(&self) -> (f64, f64) {
+        self.slices
+            .iter()
+            .fold((f64::MAX, f64::MIN), |(lo, hi), s| {
+                (lo.min(s.strikes[0]), hi.max(s.strikes[s.strikes.len() - 1]))
+            })
+    }
+
+    fn expiry_domain(&self) -> (f64, f64) {
+        (
+            self.slices[0].expiry,
+            self.slices[self.slices.len() - 1].expiry,
+        )
+    }
+}
+
+/// Standard normal cumulative distribution function.
+#[inline]
+fn norm_cdf(x: f64) -> f64 {
+    0.5 * libm::erfc(-x * FRAC_1_SQRT_2)
+}
+
+/// Standard normal density.
+#[inline]
+fn norm_pdf(x: f64) -> f64 {
+    (-0.5 * x * x).exp() / (2.0 * PI).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPOT: f64 = 1.10;
+    const RD: f64 = 0.05;
+    const RF: f64 = 0.03;
+
+    fn quotes() -> [FxSmileQuote; 2] {
+        [
+            FxSmileQuote::new(0.25, 0.075, -0.004, 0.0020).with_10d(-0.008, 0.0070),
+            FxSmileQuote::new(1.0, 0.080, -0.005, 0.0025).with_10d(-0.010, 0.0080),
+        ]
+    }
+
+    fn surface(delta: FxDeltaConvention, atm: FxAtmConvention) -> FxSmileSurface {
+        FxSmileSurface::new(SPOT, RD, RF, delta, atm, &quotes()).unwrap()
+    }
+
+    const CONVENTIONS: [FxDeltaConvention; 4] = [
+        FxDeltaConvention::Spot,
+        FxDeltaConvention::Forward,
+        FxDeltaConvention::SpotPremiumAdjusted,
+        FxDeltaConvention::ForwardPremiumAdjusted,
+    ];
+
+    #[test]
+    fn test_pillars_recover_quoted_deltas_and_vols() {
+        for convention in CONVENTIONS {
+            let surface = surface(convention, FxAtmConvention::DeltaNeutral);
+            let slice = &surface.slices()[1];
+            assert_eq!(slice.strikes().len(), 5);
+
+            // 10P, 25P, ATM, 25C, 10C
+            let expected = [
+                (false, -0.10, 0.080 + 0.0080 + 0.005),
+                (false, -0.25, 0.080 + 0.0025 + 0.0025),
+                (true, f64::NAN, 0.080),
+                (true, 0.25, 0.080 + 0.0025 - 0.0025),
+                (true, 0.10, 0.080 + 0.0080 - 0.005),
+            ];
+            for (&strike, (is_call, delta, vol)) in slice.strikes().iter().zip(expected) {
+                let smile = surface.volatility(strike, 1.0).unwrap();
+                assert!((smile - vol).abs() < 1e-12, "{:?}", convention);
+                if !delta.is_nan() {
+                    let quoted = surface.delta(is_call, strike, 1.0).unwrap();
+                    assert!((quoted - delta).abs() < 1e-10, "{:?}", convention);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_atm_conventions() {
+        let forward = SPOT * ((RD - RF) * 1.0_f64).exp();
+
+        let atmf = surface(FxDeltaConvention::Spot, FxAtmConvention::Forward);
+        assert!((atmf.slices()[1].atm_strike() - forward).abs() < 1e-12);
+
+        // Delta-neutral straddle: call and put deltas cancel
+        for convention in CONVENTIONS {
+            let surface = surface(convention, FxAtmConvention::DeltaNeutral);
+            let strike = surface.slices()[1].atm_strike();
+            let call = surface.delta(true, strike, 1.0).unwrap();
+            let put = surface.delta(false, strike, 1.0).unwrap();
+            assert!((call + put).abs() < 1e-12, "{:?}", convention);
+        }
+    }
+
+    #[test]
+    fn test_premium_adjustment_lowers_strikes() {
+        let plain = surface(FxDeltaConvention::Forward, FxAtmConvention::DeltaNeutral);
+        let adjusted = surface(
+            FxDeltaConvention::ForwardPremiumAdjusted,
+            FxAtmConvention::DeltaNeutral,
+        );
+        for (k_plain, k_adjusted) in plain.slices()[1]
+            .strikes()
+            .iter()
+            .zip(adjusted.slices()[1].strikes())
+        {
+            assert!(k_adjusted < k_plain);
+        }
+    }
+
+    #[test]
+    fn test_term_interpolation_in_total_variance() {
+        let surface = surface(FxDeltaConvention::Spot, FxAtmConvention::Forward);
+        let atm = |t: f64| surface.volatility(surface.forward(t), t).unwrap();
+
+        let variance = 0.075_f64.powi(2) * 0.25
+            + (0.080_f64.powi(2) - 0.075_f64.powi(2) * 0.25) * (0.5 - 0.25) / 0.75;
+        assert!((atm(0.5) - (variance / 0.5).sqrt()).abs() < 1e-12);
+
+        // Flat beyond the quoted expiries
+        assert!((atm(0.1) - 0.075).abs() < 1e-12);
+        assert!((atm(3.0) - 0.080).abs() < 1e-12);
+
+        // Flat beyond the wing strikes
+        let wing = surface.volatility(0.5 * SPOT, 1.0).unwrap();
+        assert!((wing - (0.080 + 0.0080 + 0.005)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_invalid_quotes() {
+        let build = |spot: f64, quotes: &[FxSmileQuote]| {
+            FxSmileSurface::new(
+                spot,
+                RD,
+                RF,
+                FxDeltaConvention::Spot,
+                FxAtmConvention::DeltaNeutral,
+                quotes,
+            )
+        };
+        assert!(build(SPOT, &[]).is_err());
+        assert!(build(0.0, &quotes()).is_err());
+        assert!(build(SPOT, &[quotes()[0], quotes()[0]]).is_err());
+        assert!(build(SPOT, &[FxSmileQuote::new(-1.0, 0.08, 0.0, 0.0)]).is_err());
+        // Butterfly driving a wing volatility negative
+        assert!(build(SPOT, &[FxSmileQuote::new(1.0, 0.08, 0.0, -0.09)]).is_err());
+
+        let surface = build(SPOT, &quotes()).unwrap();
+        assert!(surface.volatility(-1.0, 1.0).is_err());
+        assert!(surface.volatility(SPOT, 0.0).is_err());
+        assert_eq!(surface.expiry_domain(), (0.25, 1.0));
+    }
+}
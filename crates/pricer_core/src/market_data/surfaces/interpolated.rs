@@ -37,6 +37,7 @@ use num_traits::Float;
 /// let vol = surface.volatility(95.0, 0.375).unwrap();
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InterpolatedVolSurface<T: Float> {
     /// Sorted strike prices
     strikes: Vec<T>,
@@ -476,4 +477,90 @@ mod tests {
         let vol = surface.volatility(100.0_f32, 0.75_f32).unwrap();
         assert!(vol > 0.0 && vol < 1.0);
     }
+
+    #[cfg(feature = "serde")]
+    mod serde_tests {
+        use super::*;
+
+        #[test]
+        fn test_surface_schema() {
+            let surface = InterpolatedVolSurface::new(
+                &[90.0, 110.0],
+                &[0.5, 1.0],
+                &[&[0.22, 0.20][..], &[0.23, 0.21][..]],
+                false,
+            )
+            .unwrap();
+            let json = serde_json::to_string(&surface).unwrap();
+            assert_eq!(
+                json,
+                r#"{"strikes":[90.0,110.0],"expiries":[0.5,1.0],"vols":[[0.22,0.2],[0.23,0.21]],"allow_extrapolation":false}"#
+            );
+        }
+
+        #[test]
+        fn test_surface_roundtrip() {
+            let surface = create_test_surface();
+            let json = serde_json::to_string(&surface).unwrap();
+            let parsed: InterpolatedVolSurface<f64> = serde_json::from_str(&json).unwrap();
+            assert_eq!(
+                parsed.volatility(95.0, 0.75).unwrap(),
+                surface.volatility(95.0, 0.75).unwrap()
+            );
+        }
+    }
+
+    // ========================================
+    // Pillar Sensitivity Tests
+    // ========================================
+
+    #[test]
+    #[cfg(feature = "num-dual-mode")]
+    fn test_node_vegas_match_bumped() {
+        use crate::types::dual::DualFloat;
+
+        let strikes = [80.0_f64, 100.0, 120.0];
+        let expiries = [0.25_f64, 1.0, 2.0];
+        let vols = [0.25, 0.21, 0.23, 0.24, 0.20, 0.22, 0.23, 0.20, 0.21];
+        let constants =
+            |xs: &[f64]| -> Vec<DualFloat> { xs.iter().map(|&x| DualFloat::constant(x)).collect() };
+        let (dual_strikes, dual_expiries) = (constants(&strikes), constants(&expiries));
+        let h = 1e-6;
+
+        // Inside the grid and in the flat-extrapolated region
+        for (strike, expiry) in [(95.0, 0.6), (110.0, 1.5), (130.0, 0.75), (90.0, 3.0)] {
+            for k in 0..vols.len() {
+                let seeded = DualFloat::seeded(&vols, k);
+                let rows: Vec<&[DualFloat]> = seeded.chunks(3).collect();
+                let surface =
+                    InterpolatedVolSurface::new(&dual_strikes, &dual_expiries, &rows, true)
+                        .unwrap();
+                let ad = surface
+                    .volatility(DualFloat::constant(strike), DualFloat::constant(expiry))
+                    .unwrap()
+                    .derivative();
+
+                let vol = |bump: f64| {
+                    let mut bumped = vols;
+                    bumped[k] += bump;
+                    let rows: Vec<&[f64]> = bumped.chunks(3).collect();
+                    InterpolatedVolSurface::new(&strikes, &expiries, &rows, true)
+                        .unwrap()
+                        .volatility(strike, expiry)
+                        .unwrap()
+                };
+                let fd = (vol(h) - vol(-h)) / (2.0 * h);
+
+                assert!(
+                    (ad - fd).abs() < 1e-8,
+                    "({}, {}) node {}: AD {} vs bumped {}",
+                    strike,
+                    expiry,
+                    k,
+                    ad,
+                    fd
+                );
+            }
+        }
+    }
 }
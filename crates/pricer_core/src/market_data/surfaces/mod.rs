@@ -6,13 +6,23 @@
 //! - [`InterpolatedVolSurface`]: Grid-based interpolated volatility surface
 //! - [`FxVolatilitySurface`]: Delta-expiry based volatility surface for FX options
 //! - [`FxDeltaPoint`]: Standard delta points used in FX markets
+//! - [`FxSmileSurface`]: Strike-space FX smile built from ATM, risk reversal
+//!   and butterfly quotes
+//! - [`SwaptionVolCube`]: Swaption normal volatilities by expiry, tenor and
+//!   strike offset
 
 mod flat;
 mod fx;
+mod fx_smile;
 mod interpolated;
+mod swaption_cube;
 mod traits;
 
 pub use flat::FlatVol;
 pub use fx::{FxDeltaPoint, FxVolatilitySurface};
+pub use fx_smile::{
+    FxAtmConvention, FxDeltaConvention, FxSmileQuote, FxSmileSlice, FxSmileSurface,
+};
 pub use interpolated::InterpolatedVolSurface;
+pub use swaption_cube::SwaptionVolCube;
 pub use traits::VolatilitySurface;
@@ -0,0 +1,274 @@
+//! Swaption volatility cube.
+//!
+//! Normal (Bachelier) volatilities of European swaptions indexed by option
+//! expiry, underlying swap tenor and strike offset from the ATM forward swap
+//! rate. This is the market input for replication-based CMS convexity
+//! adjustments, which need the whole smile of each expiry/tenor pair.
+
+use crate::market_data::error::MarketDataError;
+use num_traits::Float;
+
+/// Swaption normal volatility cube.
+///
+/// Volatilities are stored as `vols[expiry_idx][tenor_idx][offset_idx]`,
+/// where offsets are absolute strike distances from the ATM forward swap
+/// rate (e.g. `-0.01` for ATM − 100bp). Lookups interpolate linearly along
+/// each axis and extrapolate flat beyond the grid; an axis may hold a single
+/// point, in which case the cube is constant along it.
+///
+/// # Example
+///
+/// ```
+/// use pricer_core::market_data::surfaces::SwaptionVolCube;
+///
+/// let cube: SwaptionVolCube<f64> = SwaptionVolCube::new(
+///     &[1.0, 5.0],
+///     &[10.0],
+///     &[-0.01, 0.0, 0.01],
+///     vec![
+///         vec![vec![0.0110, 0.0100, 0.0105]],
+///         vec![vec![0.0100, 0.0090, 0.0095]],
+///     ],
+/// )
+/// .unwrap();
+///
+/// assert!((cube.atm_volatility(1.0, 10.0) - 0.0100).abs() < 1e-12);
+/// assert!((cube.volatility(3.0, 10.0, -0.01) - 0.0105).abs() < 1e-12);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SwaptionVolCube<T: Float> {
+    /// Sorted option expiries in years
+    expiries: Vec<T>,
+    /// Sorted underlying swap tenors in years
+    tenors: Vec<T>,
+    /// Sorted strike offsets from the ATM forward swap rate
+    strike_offsets: Vec<T>,
+    /// Normal volatility cube: `vols[expiry_idx][tenor_idx][offset_idx]`
+    vols: Vec<Vec<Vec<T>>>,
+}
+
+impl<T: Float> SwaptionVolCube<T> {
+    /// Construct a cube from grid data.
+    ///
+    /// # Arguments
+    ///
+    /// * `expiries` - Sorted option expiries in years (at least 1 point)
+    /// * `tenors` - Sorted swap tenors in years (at least 1 point)
+    /// * `strike_offsets` - Sorted strike offsets from ATM (at least 1 point)
+    /// * `vols` - Normal volatilities: `vols[expiry_idx][tenor_idx][offset_idx]`
+    ///
+    /// # Returns
+    ///
+    /// * `Err(MarketDataError::InsufficientData)` - An empty axis, or a grid
+    ///   whose dimensions do not match the axes
+    /// * `Err(MarketDataError::InvalidExpiry)` - Non-positive or unsorted expiries
+    /// * `Err(MarketDataError::InvalidMaturity)` - Non-positive or unsorted tenors
+    /// * `Err(MarketDataError::InvalidStrike)` - Unsorted strike offsets
+    /// * `Err(MarketDataError::InterpolationFailed)` - A non-positive volatility
+    pub fn new(
+        expiries: &[T],
+        tenors: &[T],
+        strike_offsets: &[T],
+        vols: Vec<Vec<Vec<T>>>,
+    ) -> Result<Self, MarketDataError> {
+        for axis in [expiries, tenors, strike_offsets] {
+            if axis.is_empty() {
+                return Err(MarketDataError::InsufficientData { got: 0, need: 1 });
+            }
+        }
+        for (i, &expiry) in expiries.iter().enumerate() {
+            if expiry <= T::zero() || (i > 0 && expiry <= expiries[i - 1]) {
+                return Err(MarketDataError::InvalidExpiry {
+                    expiry: expiry.to_f64().unwrap_or(0.0),
+                });
+            }
+        }
+        for (i, &tenor) in tenors.iter().enumerate() {
+            if tenor <= T::zero() || (i > 0 && tenor <= tenors[i - 1]) {
+                return Err(MarketDataError::InvalidMaturity {
+                    t: tenor.to_f64().unwrap_or(0.0),
+                });
+            }
+        }
+        for i in 1..strike_offsets.len() {
+            if strike_offsets[i] <= strike_offsets[i - 1] {
+                return Err(MarketDataError::InvalidStrike {
+                    strike: strike_offsets[i].to_f64().unwrap_or(0.0),
+                });
+            }
+        }
+
+        if vols.len() != expiries.len() {
+            return Err(MarketDataError::InsufficientData {
+                got: vols.len(),
+                need: expiries.len(),
+            });
+        }
+        for matrix in &vols {
+            if matrix.len() != tenors.len() {
+                return Err(MarketDataError::InsufficientData {
+                    got: matrix.len(),
+                    need: tenors.len(),
+                });
+            }
+            for smile in matrix {
+                if smile.len() != strike_offsets.len() {
+                    return Err(MarketDataError::InsufficientData {
+                        got: smile.len(),
+                        need: strike_offsets.len(),
+                    });
+                }
+                if let Some(&vol) = smile.iter().find(|&&v| v <= T::zero()) {
+                    return Err(MarketDataError::InterpolationFailed {
+                        reason: format!(
+                            "non-positive swaption volatility {}",
+                            vol.to_f64().unwrap_or(0.0)
+                        ),
+                    });
+                }
+            }
+        }
+
+        Ok(Self {
+            expiries: expiries.to_vec(),
+            tenors: tenors.to_vec(),
+            strike_offsets: strike_offsets.to_vec(),
+            vols,
+        })
+    }
+
+    /// Cube with the same normal volatility everywhere.
+    pub fn flat(vol: T) -> Result<Self, MarketDataError> {
+        Self::new(
+            &[T::one()],
+            &[T::one()],
+            &[T::zero()],
+            vec![vec![vec![vol]]],
+        )
+    }
+
+    /// Option expiries in years.
+    #[inline]
+    pub fn expiries(&self) -> &[T] {
+        &self.expiries
+    }
+
+    /// Underlying swap tenors in years.
+    #[inline]
+    pub fn tenors(&self) -> &[T] {
+        &self.tenors
+    }
+
+    /// Strike offsets from the ATM forward swap rate.
+    #[inline]
+    pub fn strike_offsets(&self) -> &[T] {
+        &self.strike_offsets
+    }
+
+    /// Normal volatility of a swaption struck `strike_offset` away from the
+    /// ATM forward swap rate.
+    pub fn volatility(&self, expiry: T, tenor: T, strike_offset: T) -> T {
+        let (e0, e1, we) = bracket(&self.expiries, expiry);
+        let (t0, t1, wt) = bracket(&self.tenors, tenor);
+        let (k0, k1, wk) = bracket(&self.strike_offsets, strike_offset);
+
+        let smile = |e: usize, t: usize| {
+            let row = &self.vols[e][t];
+            row[k0] + (row[k1] - row[k0]) * wk
+        };
+        let matrix = |e: usize| smile(e, t0) + (smile(e, t1) - smile(e, t0)) * wt;
+        matrix(e0) + (matrix(e1) - matrix(e0)) * we
+    }
+
+    /// Normal volatility of the ATM swaption.
+    #[inline]
+    pub fn atm_volatility(&self, expiry: T, tenor: T) -> T {
+        self.volatility(expiry, tenor, T::zero())
+    }
+}
+
+/// Indices enclosing `x` on a sorted axis and the weight of the upper one,
+/// clamped to the ends for flat extrapolation.
+fn bracket<T: Float>(axis: &[T], x: T) -> (usize, usize, T) {
+    let last = axis.len() - 1;
+    if x <= axis[0] {
+        return (0, 0, T::zero());
+    }
+    if x >= axis[last] {
+        return (last, last, T::zero());
+    }
+    let hi = axis.partition_point(|&a| a <= x);
+    let lo = hi - 1;
+    (lo, hi, (x - axis[lo]) / (axis[hi] - axis[lo]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn cube() -> SwaptionVolCube<f64> {
+        SwaptionVolCube::new(
+            &[1.0, 5.0],
+            &[2.0, 10.0],
+            &[-0.01, 0.0, 0.01],
+            vec![
+                vec![vec![0.012, 0.010, 0.011], vec![0.011, 0.009, 0.010]],
+                vec![vec![0.010, 0.008, 0.009], vec![0.009, 0.007, 0.008]],
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_grid_points_are_returned() {
+        let cube = cube();
+        assert_relative_eq!(cube.atm_volatility(1.0, 2.0), 0.010, epsilon = 1e-15);
+        assert_relative_eq!(cube.volatility(5.0, 10.0, 0.01), 0.008, epsilon = 1e-15);
+        assert_relative_eq!(cube.volatility(1.0, 10.0, -0.01), 0.011, epsilon = 1e-15);
+    }
+
+    #[test]
+    fn test_interpolation_and_flat_extrapolation() {
+        let cube = cube();
+        // Midway along every axis
+        let mid = cube.volatility(3.0, 6.0, -0.005);
+        let corners = [0.012, 0.010, 0.011, 0.009, 0.010, 0.008, 0.009, 0.007];
+        assert_relative_eq!(mid, corners.iter().sum::<f64>() / 8.0, epsilon = 1e-15);
+
+        assert_relative_eq!(
+            cube.volatility(0.1, 1.0, -0.05),
+            cube.volatility(1.0, 2.0, -0.01),
+            epsilon = 1e-15
+        );
+        assert_relative_eq!(
+            cube.volatility(30.0, 30.0, 0.05),
+            cube.volatility(5.0, 10.0, 0.01),
+            epsilon = 1e-15
+        );
+
+        let flat = SwaptionVolCube::flat(0.008).unwrap();
+        assert_relative_eq!(flat.volatility(7.0, 3.0, 0.02), 0.008);
+    }
+
+    #[test]
+    fn test_invalid_grids_are_rejected() {
+        assert!(matches!(
+            SwaptionVolCube::<f64>::new(&[], &[1.0], &[0.0], vec![]),
+            Err(MarketDataError::InsufficientData { .. })
+        ));
+        assert!(matches!(
+            SwaptionVolCube::new(&[2.0, 1.0], &[1.0], &[0.0], vec![vec![vec![0.01]]; 2]),
+            Err(MarketDataError::InvalidExpiry { .. })
+        ));
+        assert!(matches!(
+            SwaptionVolCube::new(&[1.0], &[1.0], &[0.0, 0.01], vec![vec![vec![0.01]]]),
+            Err(MarketDataError::InsufficientData { got: 1, need: 2 })
+        ));
+        assert!(matches!(
+            SwaptionVolCube::flat(0.0),
+            Err(MarketDataError::InterpolationFailed { .. })
+        ));
+    }
+}
@@ -0,0 +1,562 @@
+//! Implied volatility inversion for Black-Scholes, Black-76 and Bachelier.
+//!
+//! Prices are inverted in normalised form, following Jäckel ("Let's Be
+//! Rational", 2015, and "Implied Normal Volatility", 2017):
+//!
+//! 1. The quote is reduced to the out-of-the-money option by put-call
+//!    parity, so that only the time value is inverted.
+//! 2. The time value is normalised by `√(F K)` (Black) or used as is
+//!    (Bachelier), leaving a function of total volatility `s = σ √T` alone.
+//! 3. Third-order Householder iterations solve for `s`, on the logarithm of
+//!    the normalised price below the inflection point of the Black price
+//!    and on the price itself above it. Each step is safeguarded by a
+//!    bracket and falls back to bisection, so iterations always converge.
+//!
+//! The Black iteration starts from the inflection point `s = √(2 |x|)`,
+//! `x = ln(F / K)`, from which Newton steps are monotone; the Bachelier
+//! iteration starts from the lower bound `s ≥ √(2π) v`, or from the
+//! deep out-of-the-money asymptote where that is larger. Both converge to
+//! machine precision in a handful of iterations.
+//!
+//! The `*_implied_vols` functions invert arrays of [`OptionQuote`]s, for
+//! surface construction and calibration to quoted prices.
+//!
+//! # Examples
+//!
+//! ```
+//! use pricer_core::math::implied_vol::{black_implied_vol, black_price};
+//!
+//! let price = black_price(100.0, 110.0, 0.5, 0.25, 0.98, true);
+//! let vol = black_implied_vol(price, 100.0, 110.0, 0.5, 0.98, true).unwrap();
+//! assert!((vol - 0.25).abs() < 1e-12);
+//! ```
+
+use crate::types::ImpliedVolError;
+use std::f64::consts::{FRAC_1_SQRT_2, PI};
+
+/// Maximum Householder iterations before giving up.
+const MAX_ITERATIONS: usize = 100;
+
+/// Relative step size at which iterations stop.
+const TOLERANCE: f64 = 4.0 * f64::EPSILON;
+
+/// A quoted option price to invert.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OptionQuote {
+    /// Discounted option price
+    pub price: f64,
+    /// Forward of the underlying to expiry
+    pub forward: f64,
+    /// Strike
+    pub strike: f64,
+    /// Time to expiry in years
+    pub expiry: f64,
+    /// Discount factor to the payment date
+    pub discount: f64,
+    /// Call (true) or put (false)
+    pub is_call: bool,
+}
+
+impl OptionQuote {
+    /// Create a quote on a forward.
+    pub fn new(
+        price: f64,
+        forward: f64,
+        strike: f64,
+        expiry: f64,
+        discount: f64,
+        is_call: bool,
+    ) -> Self {
+        Self {
+            price,
+            forward,
+            strike,
+            expiry,
+            discount,
+            is_call,
+        }
+    }
+
+    /// Create a Black-Scholes quote on a spot with continuous rate `rate`.
+    pub fn from_spot(
+        price: f64,
+        spot: f64,
+        strike: f64,
+        expiry: f64,
+        rate: f64,
+        is_call: bool,
+    ) -> Self {
+        let discount = (-rate * expiry).exp();
+        Self::new(price, spot / discount, strike, expiry, discount, is_call)
+    }
+}
+
+/// Discounted Black-76 price of a call or put on a forward.
+pub fn black_price(
+    forward: f64,
+    strike: f64,
+    expiry: f64,
+    vol: f64,
+    discount: f64,
+    is_call: bool,
+) -> f64 {
+    let x = (forward / strike).ln();
+    let s = vol * expiry.sqrt();
+    let theta = if is_call { 1.0 } else { -1.0 };
+    let intrinsic = (theta * (forward - strike)).max(0.0);
+    if s <= 0.0 {
+        return discount * intrinsic;
+    }
+    // Out-of-the-money value plus intrinsic is accurate for deep strikes
+    let otm = normalised_black(-x.abs(), s) * (forward * strike).sqrt();
+    discount * (otm + intrinsic)
+}
+
+/// Discounted Bachelier (normal model) price of a call or put.
+pub fn bachelier_price(
+    forward: f64,
+    strike: f64,
+    expiry: f64,
+    vol: f64,
+    discount: f64,
+    is_call: bool,
+) -> f64 {
+    let s = vol * expiry.sqrt();
+    let theta = if is_call { 1.0 } else { -1.0 };
+    let intrinsic = (theta * (forward - strike)).max(0.0);
+    if s <= 0.0 {
+        return discount * intrinsic;
+    }
+    discount * (normalised_bachelier(-(forward - strike).abs(), s) + intrinsic)
+}
+
+/// Black-76 (lognormal) implied volatility of a discounted option price.
+///
+/// # Errors
+///
+/// - `ImpliedVolError::InvalidInput` if the forward, strike, expiry or
+///   discount factor is not positive and finite, or the price is not finite
+/// - `ImpliedVolError::PriceBelowIntrinsic` if the price is below the
+///   discounted intrinsic value
+/// - `ImpliedVolError::PriceAboveMaximum` if the price reaches the
+///   discounted forward (calls) or strike (puts)
+/// - `ImpliedVolError::NotConverged` if the iterations fail to converge
+pub fn black_implied_vol(
+    price: f64,
+    forward: f64,
+    strike: f64,
+    expiry: f64,
+    discount: f64,
+    is_call: bool,
+) -> Result<f64, ImpliedVolError> {
+    check_positive("forward", forward)?;
+    check_positive("strike", strike)?;
+    check_positive("expiry", expiry)?;
+    check_positive("discount factor", discount)?;
+    let time_value = time_value(price, forward, strike, discount, is_call)?;
+    if time_value == 0.0 {
+        return Ok(0.0);
+    }
+    let x = -(forward / strike).ln().abs();
+    let beta = time_value / (forward * strike).sqrt();
+    let maximum = (0.5 * x).exp();
+    if beta >= maximum {
+        return Err(ImpliedVolError::PriceAboveMaximum {
+            price,
+            maximum: discount * if is_call { forward } else { strike },
+        });
+    }
+
+    let s = if x == 0.0 {
+        // At the money b(s) = 2Φ(s/2) - 1 is concave: Newton from below
+        let start = beta * (2.0 * PI).sqrt();
+        householder(|s| black_derivatives(x, s, beta), start, 0.0, f64::INFINITY)?
+    } else {
+        let inflection = (-2.0 * x).sqrt();
+        if beta < normalised_black(x, inflection) {
+            householder(
+                |s| log_derivatives(black_derivatives(x, s, 0.0), beta),
+                inflection,
+                0.0,
+                inflection,
+            )?
+        } else {
+            householder(
+                |s| black_derivatives(x, s, beta),
+                inflection,
+                inflection,
+                f64::INFINITY,
+            )?
+        }
+    };
+    Ok(s / expiry.sqrt())
+}
+
+/// Black-Scholes implied volatility of an option on a spot.
+///
+/// The forward is `spot · e^{rate · expiry}`; see [`black_implied_vol`]
+/// for the errors.
+pub fn black_scholes_implied_vol(
+    price: f64,
+    spot: f64,
+    strike: f64,
+    expiry: f64,
+    rate: f64,
+    is_call: bool,
+) -> Result<f64, ImpliedVolError> {
+    let quote = OptionQuote::from_spot(price, spot, strike, expiry, rate, is_call);
+    black_implied_vol(
+        quote.price,
+        quote.forward,
+        quote.strike,
+        quote.expiry,
+        quote.discount,
+        quote.is_call,
+    )
+}
+
+/// Bachelier (normal) implied volatility of a discounted option price.
+///
+/// # Errors
+///
+/// - `ImpliedVolError::InvalidInput` if the expiry or discount factor is
+///   not positive and finite, or the price, forward or strike not finite
+/// - `ImpliedVolError::PriceBelowIntrinsic` if the price is below the
+///   discounted intrinsic value
+/// - `ImpliedVolError::NotConverged` if the iterations fail to converge
+pub fn bachelier_implied_vol(
+    price: f64,
+    forward: f64,
+    strike: f64,
+    expiry: f64,
+    discount: f64,
+    is_call: bool,
+) -> Result<f64, ImpliedVolError> {
+    // The normal model admits negative forwards and strikes
+    if !(forward.is_finite() && strike.is_finite()) {
+        return Err(ImpliedVolError::InvalidInput(format!(
+            "forward {} and strike {} must be finite",
+            forward, strike
+        )));
+    }
+    check_positive("expiry", expiry)?;
+    check_positive("discount factor", discount)?;
+    let time_value = time_value(price, forward, strike, discount, is_call)?;
+    if time_value == 0.0 {
+        return Ok(0.0);
+    }
+    let x = -(forward - strike).abs();
+    let lower = time_value * (2.0 * PI).sqrt();
+    let s = if x == 0.0 {
+        lower
+    } else {
+        // Deep out of the money v ~ s³ φ(x/s) / x², so s is close to
+        // |x| / √(2 ln(|x| / v)) rather than the lower bound
+        let start = if time_value < -x {
+            lower.max(-x / (2.0 * (-x / time_value).ln()).sqrt())
+        } else {
+            lower
+        };
+        householder(
+            |s| log_derivatives(bachelier_derivatives(x, s), time_value),
+            start,
+            lower,
+            f64::INFINITY,
+        )?
+    };
+    Ok(s / expiry.sqrt())
+}
+
+/// Black-76 implied volatilities of `quotes`, one result per quote.
+pub fn black_implied_vols(quotes: &[OptionQuote]) -> Vec<Result<f64, ImpliedVolError>> {
+    quotes
+        .iter()
+        .map(|q| {
+            black_implied_vol(
+                q.price, q.forward, q.strike, q.expiry, q.discount, q.is_call,
+            )
+        })
+        .collect()
+}
+
+/// Bachelier implied volatilities of `quotes`, one result per quote.
+pub fn bachelier_implied_vols(quotes: &[OptionQuote]) -> Vec<Result<f64, ImpliedVolError>> {
+    quotes
+        .iter()
+        .map(|q| {
+            bachelier_implied_vol(
+                q.price, q.forward, q.strike, q.expiry, q.discount, q.is_call,
+            )
+        })
+        .collect()
+}
+
+/// Error unless `value` is positive and finite.
+fn check_positive(name: &str, value: f64) -> Result<(), ImpliedVolError> {
+    if value > 0.0 && value.is_finite() {
+        Ok(())
+    } else {
+        Err(ImpliedVolError::InvalidInput(format!(
+            "{} {} must be positive and finite",
+            name, value
+        )))
+    }
+}
+
+/// Undiscounted price less intrinsic value, zero within rounding.
+fn time_value(
+    price: f64,
+    forward: f64,
+    strike: f64,
+    discount: f64,
+    is_call: bool,
+) -> Result<f64, ImpliedVolError> {
+    if !price.is_finite() {
+        return Err(ImpliedVolError::InvalidInput(format!(
+            "price {} must be finite",
+            price
+        )));
+    }
+    let theta = if is_call { 1.0 } else { -1.0 };
+    let intrinsic = (theta * (forward - strike)).max(0.0);
+    let time_value = price / discount - intrinsic;
+    let rounding = 8.0 * f64::EPSILON * forward.abs().max(strike.abs());
+    if time_value < -rounding {
+        Err(ImpliedVolError::PriceBelowIntrinsic {
+            price,
+            intrinsic: discount * intrinsic,
+        })
+    } else {
+        Ok(time_value.max(0.0))
+    }
+}
+
+/// Standard normal cumulative distribution function.
+#[inline]
+fn norm_cdf(x: f64) -> f64 {
+    0.5 * libm::erfc(-x * FRAC_1_SQRT_2)
+}
+
+/// Standard normal density.
+#[inline]
+fn norm_pdf(x: f64) -> f64 {
+    (-0.5 * x * x).exp() / (2.0 * PI).sqrt()
+}
+
+/// Normalised out-of-the-money Black call value, `x = ln(F/K) ≤ 0`.
+fn normalised_black(x: f64, s: f64) -> f64 {
+    let h = x / s;
+    let t = 0.5 * s;
+    ((0.5 * x).exp() * norm_cdf(h + t) - (-0.5 * x).exp() * norm_cdf(h - t)).max(0.0)
+}
+
+/// Normalised out-of-the-money Bachelier value, `x = -|F - K|`.
+fn normalised_bachelier(x: f64, s: f64) -> f64 {
+    let u = x / s;
+    (x * norm_cdf(u) + s * norm_pdf(u)).max(0.0)
+}
+
+/// Black value less `target` and its first three derivatives in `s`.
+fn black_derivatives(x: f64, s: f64, target: f64) -> [f64; 4] {
+    let b = normalised_black(x, s);
+    let vega = (-0.5 * (x * x / (s * s) + 0.25 * s * s)).exp() / (2.0 * PI).sqrt();
+    let a = x * x / (s * s * s) - 0.25 * s;
+    let volga = vega * a;
+    let third = vega * (a * a - 3.0 * x * x / (s * s * s * s) - 0.25);
+    [b - target, vega, volga, third]
+}
+
+/// Bachelier value and its first three derivatives in `s`.
+fn bachelier_derivatives(x: f64, s: f64) -> [f64; 4] {
+    let u = x / s;
+    let density = norm_pdf(u);
+    let x2 = x * x;
+    let s2 = s * s;
+    [
+        normalised_bachelier(x, s),
+        density,
+        density * x2 / (s2 * s),
+        density * (x2 * x2 / (s2 * s2 * s2) - 3.0 * x2 / (s2 * s2)),
+    ]
+}
+
+/// `ln f - ln target` and its derivatives from those of `f`.
+fn log_derivatives([f, d1, d2, d3]: [f64; 4], target: f64) -> [f64; 4] {
+    let g1 = d1 / f;
+    let g2 = d2 / f - g1 * g1;
+    let g3 = d3 / f - 3.0 * (d2 / f) * g1 + 2.0 * g1 * g1 * g1;
+    [(f / target).ln(), g1, g2, g3]
+}
+
+/// Root of an increasing function by safeguarded Householder iterations.
+///
+/// `f` returns the value and first three derivatives; the root lies in
+/// `[lo, hi]`. Steps leaving the bracket, or taken where the derivatives
+/// are not finite, are replaced by bisection (or doubling while `hi` is
+/// unbounded).
+fn householder<F>(f: F, start: f64, mut lo: f64, mut hi: f64) -> Result<f64, ImpliedVolError>
+where
+    F: Fn(f64) -> [f64; 4],
+{
+    let mut s = start;
+    for _ in 0..MAX_ITERATIONS {
+        let [g, d1, d2, d3] = f(s);
+        if g == 0.0 {
+            return Ok(s);
+        }
+        // ln 0 = -inf still orders the bracket correctly
+        if g < 0.0 {
+            lo = lo.max(s);
+        } else {
+            hi = hi.min(s);
+        }
+
+        let nu = -g / d1;
+        let h2 = d2 / d1;
+        let h3 = d3 / d1;
+        let step = nu * (1.0 + 0.5 * h2 * nu) / (1.0 + nu * (h2 + h3 * nu / 6.0));
+        let mut next = s + step;
+        if !(next.is_finite() && next > lo && next < hi) {
+            next = if hi.is_finite() {
+                0.5 * (lo + hi)
+            } else {
+                2.0 * lo.max(s).max(f64::MIN_POSITIVE)
+            };
+        }
+        if (next - s).abs() <= TOLERANCE * next || hi - lo <= TOLERANCE * lo {
+            return Ok(next);
+        }
+        s = next;
+    }
+    Err(ImpliedVolError::NotConverged {
+        iterations: MAX_ITERATIONS,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_black_round_trip() {
+        for &strike in &[20.0, 60.0, 90.0, 100.0, 110.0, 150.0, 400.0] {
+            for &vol in &[0.01, 0.05, 0.2, 0.5, 1.0, 3.0] {
+                for &is_call in &[true, false] {
+                    let price = black_price(100.0, strike, 2.0, vol, 0.95, is_call);
+                    match black_implied_vol(price, 100.0, strike, 2.0, 0.95, is_call) {
+                        Ok(implied) => {
+                            let repriced = black_price(100.0, strike, 2.0, implied, 0.95, is_call);
+                            assert_relative_eq!(repriced, price, max_relative = 1e-10);
+                        }
+                        Err(e) => panic!("K={} vol={} call={}: {}", strike, vol, is_call, e),
+                    }
+                }
+            }
+        }
+        // Well-conditioned quotes recover the volatility itself
+        let price = black_price(100.0, 120.0, 1.0, 0.3, 1.0, true);
+        assert_relative_eq!(
+            black_implied_vol(price, 100.0, 120.0, 1.0, 1.0, true).unwrap(),
+            0.3,
+            epsilon = 1e-13
+        );
+        let price = black_price(100.0, 100.0, 1.0, 0.3, 1.0, false);
+        assert_relative_eq!(
+            black_implied_vol(price, 100.0, 100.0, 1.0, 1.0, false).unwrap(),
+            0.3,
+            epsilon = 1e-13
+        );
+    }
+
+    #[test]
+    fn test_black_scholes_matches_forward_form() {
+        let rate: f64 = 0.04;
+        let forward = 100.0 * (rate * 0.75).exp();
+        let price = black_price(forward, 95.0, 0.75, 0.22, (-rate * 0.75).exp(), false);
+        let vol = black_scholes_implied_vol(price, 100.0, 95.0, 0.75, rate, false).unwrap();
+        assert_relative_eq!(vol, 0.22, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_bachelier_round_trip() {
+        // Normal vols of a rates market, in absolute terms
+        for &strike in &[-0.01, 0.0, 0.02, 0.03, 0.05, 0.1] {
+            for &vol in &[0.0005, 0.005, 0.01, 0.03] {
+                for &is_call in &[true, false] {
+                    let price = bachelier_price(0.03, strike, 5.0, vol, 0.9, is_call);
+                    let implied =
+                        bachelier_implied_vol(price, 0.03, strike, 5.0, 0.9, is_call).unwrap();
+                    let repriced = bachelier_price(0.03, strike, 5.0, implied, 0.9, is_call);
+                    assert_relative_eq!(repriced, price, max_relative = 1e-10);
+                    if (strike - 0.03_f64).abs() / (vol * 5.0_f64.sqrt()) < 5.0 {
+                        assert_relative_eq!(implied, vol, max_relative = 1e-10);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_price_bounds() {
+        assert_eq!(
+            black_implied_vol(10.0, 100.0, 90.0, 1.0, 1.0, true).unwrap(),
+            0.0
+        );
+        assert!(matches!(
+            black_implied_vol(9.0, 100.0, 90.0, 1.0, 1.0, true),
+            Err(ImpliedVolError::PriceBelowIntrinsic { .. })
+        ));
+        assert!(matches!(
+            black_implied_vol(100.0, 100.0, 90.0, 1.0, 1.0, true),
+            Err(ImpliedVolError::PriceAboveMaximum { .. })
+        ));
+        assert!(matches!(
+            black_implied_vol(5.0, -100.0, 90.0, 1.0, 1.0, true),
+            Err(ImpliedVolError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            bachelier_implied_vol(0.001, 0.03, 0.0, 1.0, 1.0, false),
+            Ok(v) if v > 0.0
+        ));
+        assert!(matches!(
+            bachelier_implied_vol(0.01, 0.03, 0.0, 1.0, 1.0, true),
+            Err(ImpliedVolError::PriceBelowIntrinsic { .. })
+        ));
+    }
+
+    #[test]
+    fn test_vectorised() {
+        let quotes: Vec<OptionQuote> = [80.0, 100.0, 120.0]
+            .iter()
+            .map(|&k| {
+                let price = black_price(100.0, k, 1.0, 0.2, 0.97, k >= 100.0);
+                OptionQuote::new(price, 100.0, k, 1.0, 0.97, k >= 100.0)
+            })
+            .chain(std::iter::once(OptionQuote::new(
+                -1.0, 100.0, 100.0, 1.0, 0.97, true,
+            )))
+            .collect();
+        let vols = black_implied_vols(&quotes);
+        assert_eq!(vols.len(), 4);
+        for vol in &vols[..3] {
+            assert_relative_eq!(*vol.as_ref().unwrap(), 0.2, epsilon = 1e-12);
+        }
+        assert!(vols[3].is_err());
+
+        let quote = OptionQuote::new(
+            bachelier_price(0.02, 0.025, 2.0, 0.008, 1.0, true),
+            0.02,
+            0.025,
+            2.0,
+            1.0,
+            true,
+        );
+        assert_relative_eq!(
+            *bachelier_implied_vols(&[quote])[0].as_ref().unwrap(),
+            0.008,
+            epsilon = 1e-12
+        );
+    }
+}
@@ -597,4 +597,47 @@ mod tests {
         let y = interp.interpolate(1.5_f32).unwrap();
         assert!(y.is_finite());
     }
+
+    /// The spline is linear in the ordinates, so AD pillar deltas are the
+    /// spline's basis functions and must match central bumps.
+    #[test]
+    #[cfg(feature = "num-dual-mode")]
+    fn test_pillar_deltas_match_bumped() {
+        use crate::types::dual::DualFloat;
+
+        let xs = [0.0_f64, 0.5, 1.0, 2.0, 5.0, 10.0];
+        let ys = [1.0, 1.3, 0.8, 1.9, 2.4, 2.2];
+        let dual_xs: Vec<DualFloat> = xs.iter().map(|&x| DualFloat::constant(x)).collect();
+        let h = 1e-6;
+
+        for x in [0.2, 0.9, 1.7, 3.3, 9.0] {
+            for k in 0..ys.len() {
+                let interp =
+                    CubicSplineInterpolator::new(&dual_xs, &DualFloat::seeded(&ys, k)).unwrap();
+                let ad = interp
+                    .interpolate(DualFloat::constant(x))
+                    .unwrap()
+                    .derivative();
+
+                let value = |bump: f64| {
+                    let mut bumped = ys;
+                    bumped[k] += bump;
+                    CubicSplineInterpolator::new(&xs, &bumped)
+                        .unwrap()
+                        .interpolate(x)
+                        .unwrap()
+                };
+                let fd = (value(h) - value(-h)) / (2.0 * h);
+
+                assert!(
+                    (ad - fd).abs() < 1e-7,
+                    "x={} pillar {}: AD {} vs bumped {}",
+                    x,
+                    k,
+                    ad,
+                    fd
+                );
+            }
+        }
+    }
 }
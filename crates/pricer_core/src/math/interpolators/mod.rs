@@ -22,7 +22,12 @@
 //!
 //! All interpolators are generic over `T: num_traits::Float`, enabling use with:
 //! - `f64`: Standard floating-point computation
-//! - `Dual64`: Automatic differentiation via num-dual
+//! - `DualFloat`: Automatic differentiation via num-dual (`types::dual`)
+//!
+//! Linear, cubic spline and bilinear interpolation are linear in the
+//! ordinates, and the monotonic slopes are continuous in them away from
+//! sign changes of the secants, so derivatives with respect to pillar
+//! values agree with bump-and-revalue sensitivities.
 //!
 //! ## Example
 //!
@@ -575,4 +575,47 @@ mod tests {
         let y = interp.interpolate(0.5_f32).unwrap();
         assert!(y.is_finite());
     }
+
+    /// Includes steep segments where the Fritsch-Carlson limiter rescales the
+    /// slopes, so the deltas also flow through the slope correction.
+    #[test]
+    #[cfg(feature = "num-dual-mode")]
+    fn test_pillar_deltas_match_bumped() {
+        use crate::types::dual::DualFloat;
+
+        let xs = [0.0_f64, 0.5, 1.0, 2.0, 5.0, 10.0];
+        let ys = [0.0, 0.05, 0.6, 0.7, 0.72, 1.5];
+        let dual_xs: Vec<DualFloat> = xs.iter().map(|&x| DualFloat::constant(x)).collect();
+        let h = 1e-6;
+
+        for x in [0.2, 0.9, 1.7, 3.3, 9.0] {
+            for k in 0..ys.len() {
+                let interp =
+                    MonotonicInterpolator::new(&dual_xs, &DualFloat::seeded(&ys, k)).unwrap();
+                let ad = interp
+                    .interpolate(DualFloat::constant(x))
+                    .unwrap()
+                    .derivative();
+
+                let value = |bump: f64| {
+                    let mut bumped = ys;
+                    bumped[k] += bump;
+                    MonotonicInterpolator::new(&xs, &bumped)
+                        .unwrap()
+                        .interpolate(x)
+                        .unwrap()
+                };
+                let fd = (value(h) - value(-h)) / (2.0 * h);
+
+                assert!(
+                    (ad - fd).abs() < 1e-7,
+                    "x={} pillar {}: AD {} vs bumped {}",
+                    x,
+                    k,
+                    ad,
+                    fd
+                );
+            }
+        }
+    }
 }
@@ -0,0 +1,305 @@
+//! Cholesky factorisation with and without pivoting.
+
+use super::{check_square, check_symmetric};
+use crate::types::LinalgError;
+use num_traits::Float;
+
+/// Lower triangular Cholesky factor `L` of a positive definite matrix,
+/// `A = L Lᵀ`.
+///
+/// Only the lower triangle of `a` is read. The factor is returned
+/// row-major with zeros above the diagonal.
+///
+/// # Errors
+///
+/// - `LinalgError::InvalidDimensions` if `a` does not hold `n × n` elements
+/// - `LinalgError::NotPositiveDefinite` if a pivot is not positive
+///
+/// # Example
+///
+/// ```
+/// use pricer_core::math::linalg::cholesky;
+///
+/// let lower = cholesky(&[4.0, 2.0, 2.0, 5.0], 2).unwrap();
+/// assert_eq!(lower, vec![2.0, 0.0, 1.0, 2.0]);
+/// ```
+pub fn cholesky<T: Float>(a: &[T], n: usize) -> Result<Vec<T>, LinalgError> {
+    check_square(a, n)?;
+    let mut lower = vec![T::zero(); n * n];
+    for j in 0..n {
+        let diag = (0..j).fold(a[j * n + j], |d, k| d - lower[j * n + k] * lower[j * n + k]);
+        if diag.is_nan() || diag <= T::zero() {
+            return Err(LinalgError::NotPositiveDefinite { index: j });
+        }
+        let ljj = diag.sqrt();
+        lower[j * n + j] = ljj;
+        for i in (j + 1)..n {
+            let sum = (0..j).fold(a[i * n + j], |s, k| s - lower[i * n + k] * lower[j * n + k]);
+            lower[i * n + j] = sum / ljj;
+        }
+    }
+    Ok(lower)
+}
+
+/// Cholesky factor of a positive semi-definite matrix with diagonal
+/// pivoting.
+///
+/// At each step the largest remaining diagonal element is chosen as the
+/// pivot, and the factorisation stops once it falls below a tolerance
+/// relative to the largest diagonal of the input. The number of steps
+/// taken is the numerical rank.
+///
+/// The factor is returned in the original row order, so `A ≈ F Fᵀ` with
+/// `F` an `n × n` matrix whose columns beyond the rank are zero. `F` is
+/// lower triangular after permuting its rows by [`permutation`]; mapping
+/// independent normals through `F` gives correlated normals even when `A`
+/// is singular, e.g. for perfectly correlated or redundant factors.
+///
+/// [`permutation`]: PivotedCholesky::permutation
+#[derive(Debug, Clone, PartialEq)]
+pub struct PivotedCholesky<T: Float> {
+    /// Factor in original row order, row-major
+    factor: Vec<T>,
+    /// Pivot order: `permutation[k]` is the row eliminated at step `k`
+    permutation: Vec<usize>,
+    /// Numerical rank
+    rank: usize,
+    /// Matrix dimension
+    dim: usize,
+}
+
+impl<T: Float> PivotedCholesky<T> {
+    /// Matrix dimension.
+    #[inline]
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    /// Numerical rank of the matrix.
+    #[inline]
+    pub fn rank(&self) -> usize {
+        self.rank
+    }
+
+    /// Factor `F` with `A ≈ F Fᵀ`, row-major in the original row order.
+    #[inline]
+    pub fn factor(&self) -> &[T] {
+        &self.factor
+    }
+
+    /// Rows in pivot order.
+    #[inline]
+    pub fn permutation(&self) -> &[usize] {
+        &self.permutation
+    }
+
+    /// Map independent standard normals `z` to `F z`.
+    ///
+    /// Only the first [`rank`](Self::rank) normals are used.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `z.len() < self.rank()`.
+    pub fn transform(&self, z: &[T]) -> Vec<T> {
+        assert!(
+            z.len() >= self.rank,
+            "Input vector length {} is less than rank {}",
+            z.len(),
+            self.rank
+        );
+        let n = self.dim;
+        (0..n)
+            .map(|i| (0..self.rank).fold(T::zero(), |sum, k| sum + self.factor[i * n + k] * z[k]))
+            .collect()
+    }
+}
+
+/// Pivoted Cholesky factorisation of a positive semi-definite matrix.
+///
+/// Pivots below `tolerance` times the largest diagonal element are
+/// treated as zero and end the factorisation.
+///
+/// # Errors
+///
+/// - `LinalgError::InvalidDimensions` if `a` does not hold `n × n` elements
+/// - `LinalgError::NotSymmetric` if `a` is not symmetric
+/// - `LinalgError::NotPositiveDefinite` if a remaining pivot is negative
+///   beyond the tolerance, so that `a` is indefinite
+///
+/// # Example
+///
+/// ```
+/// use pricer_core::math::linalg::pivoted_cholesky;
+///
+/// // Two perfectly correlated factors and an independent one
+/// let a = [
+///     1.0, 1.0, 0.0,
+///     1.0, 1.0, 0.0,
+///     0.0, 0.0, 1.0,
+/// ];
+/// let factor = pivoted_cholesky(&a, 3, 1e-12).unwrap();
+/// assert_eq!(factor.rank(), 2);
+///
+/// let w = factor.transform(&[0.3, -1.2]);
+/// assert_eq!(w[0], w[1]);
+/// ```
+pub fn pivoted_cholesky<T: Float>(
+    a: &[T],
+    n: usize,
+    tolerance: T,
+) -> Result<PivotedCholesky<T>, LinalgError> {
+    check_symmetric(a, n)?;
+    let mut work = a.to_vec();
+    let mut lower = vec![T::zero(); n * n];
+    let mut permutation: Vec<usize> = (0..n).collect();
+    let max_diag = (0..n).fold(T::zero(), |m, i| m.max(a[i * n + i].abs()));
+    let threshold = tolerance * max_diag;
+
+    let mut rank = n;
+    for k in 0..n {
+        // Remaining diagonal of the Schur complement
+        let remaining = |j: usize, lower: &[T], work: &[T]| {
+            (0..k).fold(work[j * n + j], |d, m| {
+                d - lower[j * n + m] * lower[j * n + m]
+            })
+        };
+        let (pivot, diag) = (k..n).map(|j| (j, remaining(j, &lower, &work))).fold(
+            (k, T::neg_infinity()),
+            |best, (j, d)| {
+                if d > best.1 {
+                    (j, d)
+                } else {
+                    best
+                }
+            },
+        );
+        if diag <= threshold {
+            if let Some(j) = (k..n).find(|&j| remaining(j, &lower, &work) < -threshold) {
+                return Err(LinalgError::NotPositiveDefinite {
+                    index: permutation[j],
+                });
+            }
+            rank = k;
+            break;
+        }
+
+        if pivot != k {
+            permutation.swap(k, pivot);
+            for c in 0..n {
+                work.swap(k * n + c, pivot * n + c);
+            }
+            for r in 0..n {
+                work.swap(r * n + k, r * n + pivot);
+            }
+            for c in 0..k {
+                lower.swap(k * n + c, pivot * n + c);
+            }
+        }
+
+        let lkk = diag.sqrt();
+        lower[k * n + k] = lkk;
+        for i in (k + 1)..n {
+            let sum = (0..k).fold(work[i * n + k], |s, m| {
+                s - lower[i * n + m] * lower[k * n + m]
+            });
+            lower[i * n + k] = sum / lkk;
+        }
+    }
+
+    // Undo the row permutation: row k of the pivoted factor is row
+    // permutation[k] of the original matrix
+    let mut factor = vec![T::zero(); n * n];
+    for (k, &row) in permutation.iter().enumerate() {
+        factor[row * n..(row + 1) * n].copy_from_slice(&lower[k * n..(k + 1) * n]);
+    }
+
+    Ok(PivotedCholesky {
+        factor,
+        permutation,
+        rank,
+        dim: n,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn product(f: &[f64], n: usize) -> Vec<f64> {
+        let mut a = vec![0.0; n * n];
+        for i in 0..n {
+            for j in 0..n {
+                a[i * n + j] = (0..n).map(|k| f[i * n + k] * f[j * n + k]).sum();
+            }
+        }
+        a
+    }
+
+    #[test]
+    fn test_cholesky() {
+        let a = [
+            4.0, 12.0, -16.0, //
+            12.0, 37.0, -43.0, //
+            -16.0, -43.0, 98.0,
+        ];
+        let lower = cholesky(&a, 3).unwrap();
+        assert_eq!(lower, vec![2.0, 0.0, 0.0, 6.0, 1.0, 0.0, -8.0, 5.0, 3.0]);
+
+        assert_eq!(
+            cholesky(&[1.0, 2.0, 2.0, 1.0], 2),
+            Err(LinalgError::NotPositiveDefinite { index: 1 })
+        );
+        assert_eq!(
+            cholesky(&[1.0, 0.0, 0.0], 2),
+            Err(LinalgError::InvalidDimensions {
+                expected: 4,
+                got: 3
+            })
+        );
+        assert!(cholesky(&[f64::NAN], 1).is_err());
+    }
+
+    #[test]
+    fn test_pivoted_cholesky_full_rank() {
+        let a = [
+            1.0, 0.3, 0.6, //
+            0.3, 2.0, 0.1, //
+            0.6, 0.1, 1.5,
+        ];
+        let factor = pivoted_cholesky(&a, 3, 1e-12).unwrap();
+        assert_eq!(factor.rank(), 3);
+        // Largest diagonal first
+        assert_eq!(factor.permutation()[0], 1);
+        for (x, y) in product(factor.factor(), 3).iter().zip(&a) {
+            assert!((x - y).abs() < 1e-14);
+        }
+    }
+
+    #[test]
+    fn test_pivoted_cholesky_singular() {
+        // Third factor is the average of the first two
+        let rho = 0.4;
+        let c = ((1.0 + rho) / 2.0_f64).sqrt();
+        let a = [
+            1.0, rho, c, //
+            rho, 1.0, c, //
+            c, c, 1.0,
+        ];
+        assert!(cholesky(&a, 3).is_err());
+        let factor = pivoted_cholesky(&a, 3, 1e-10).unwrap();
+        assert_eq!(factor.rank(), 2);
+        for (x, y) in product(factor.factor(), 3).iter().zip(&a) {
+            assert!((x - y).abs() < 1e-12);
+        }
+
+        let indefinite = [1.0, 2.0, 2.0, 1.0];
+        assert!(matches!(
+            pivoted_cholesky(&indefinite, 2, 1e-12),
+            Err(LinalgError::NotPositiveDefinite { .. })
+        ));
+        assert_eq!(
+            pivoted_cholesky(&[1.0, 0.5, 0.4, 1.0], 2, 1e-12),
+            Err(LinalgError::NotSymmetric { i: 0, j: 1 })
+        );
+    }
+}
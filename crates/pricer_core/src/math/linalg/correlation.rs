@@ -0,0 +1,163 @@
+//! Nearest correlation matrix by alternating projections.
+
+use super::{check_symmetric, symmetric_eigen};
+use crate::types::LinalgError;
+use num_traits::Float;
+
+/// Maximum alternating projection iterations.
+const MAX_ITERATIONS: usize = 1000;
+
+/// Relative change in the iterate at which projections stop.
+const TOLERANCE: f64 = 1e-12;
+
+/// Nearest correlation matrix to a symmetric matrix, in Frobenius norm.
+///
+/// Correlation matrices estimated pairwise, from asynchronous data or
+/// after stressing individual entries are often not positive
+/// semi-definite, so they have no Cholesky factor. Higham's alternating
+/// projections ("Computing the nearest correlation matrix", 2002) project
+/// in turn onto the positive semi-definite cone, by clipping negative
+/// eigenvalues, and onto unit-diagonal matrices, with Dykstra's correction
+/// so that the iterates converge to the nearest point of the intersection.
+///
+/// The result is then lifted to have eigenvalues of at least
+/// `min_eigenvalue` and rescaled to unit diagonal, so that a positive
+/// floor makes it positive definite for [`cholesky`](super::cholesky).
+/// Valid correlation matrices with eigenvalues above the floor are
+/// returned unchanged up to rounding.
+///
+/// # Errors
+///
+/// - `LinalgError::InvalidDimensions` if `a` does not hold `n × n` elements
+/// - `LinalgError::NotSymmetric` if `a` is not symmetric
+/// - `LinalgError::NotConverged` if the projections or an
+///   eigendecomposition fail to converge
+///
+/// # Example
+///
+/// ```
+/// use pricer_core::math::linalg::{nearest_correlation, symmetric_eigen};
+///
+/// let a = [
+///     1.0_f64, 1.0, 0.0,
+///     1.0, 1.0, 1.0,
+///     0.0, 1.0, 1.0,
+/// ];
+/// let repaired = nearest_correlation(&a, 3, 0.0).unwrap();
+/// assert!(symmetric_eigen(&repaired, 3).unwrap().values()[0] > -1e-12);
+/// assert!((repaired[1] - 0.7607).abs() < 1e-4);
+/// ```
+pub fn nearest_correlation<T: Float>(
+    a: &[T],
+    n: usize,
+    min_eigenvalue: T,
+) -> Result<Vec<T>, LinalgError> {
+    check_symmetric(a, n)?;
+    let tolerance = T::from(TOLERANCE).unwrap();
+
+    let mut y = a.to_vec();
+    let mut correction = vec![T::zero(); n * n];
+    let mut converged = false;
+    for _ in 0..MAX_ITERATIONS {
+        let r: Vec<T> = y.iter().zip(&correction).map(|(&y, &d)| y - d).collect();
+        let x = symmetric_eigen(&r, n)?.reconstruct(|v| v.max(T::zero()));
+        for ((d, &x), &r) in correction.iter_mut().zip(&x).zip(&r) {
+            *d = x - r;
+        }
+        let mut next = x.clone();
+        for i in 0..n {
+            next[i * n + i] = T::one();
+        }
+
+        let change = frobenius(next.iter().zip(&y).map(|(&a, &b)| a - b));
+        let size = frobenius(next.iter().copied());
+        y = next;
+        if change <= tolerance * size.max(T::one()) {
+            converged = true;
+            break;
+        }
+    }
+    if !converged {
+        return Err(LinalgError::NotConverged {
+            iterations: MAX_ITERATIONS,
+        });
+    }
+
+    // Enforce the eigenvalue floor and restore the unit diagonal
+    let floor = min_eigenvalue.max(T::zero());
+    let lifted = symmetric_eigen(&y, n)?.reconstruct(|v| v.max(floor));
+    let scale: Vec<T> = (0..n)
+        .map(|i| T::one() / lifted[i * n + i].sqrt())
+        .collect();
+    let mut result = lifted;
+    for i in 0..n {
+        for j in 0..n {
+            result[i * n + j] = if i == j {
+                T::one()
+            } else {
+                result[i * n + j] * scale[i] * scale[j]
+            };
+        }
+    }
+    Ok(result)
+}
+
+/// Frobenius norm of a matrix given by its elements.
+fn frobenius<T: Float>(elements: impl Iterator<Item = T>) -> T {
+    elements.fold(T::zero(), |sum, x| sum + x * x).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::cholesky;
+    use super::*;
+
+    #[test]
+    fn test_higham_example() {
+        // Higham (2002), section 4: a matrix of unit correlations between
+        // neighbours only
+        let a = [
+            1.0, 1.0, 0.0, //
+            1.0, 1.0, 1.0, //
+            0.0, 1.0, 1.0,
+        ];
+        let x = nearest_correlation(&a, 3, 0.0).unwrap();
+        let expected = [
+            1.0, 0.7607, 0.1573, //
+            0.7607, 1.0, 0.7607, //
+            0.1573, 0.7607, 1.0,
+        ];
+        for (x, y) in x.iter().zip(&expected) {
+            assert!((x - y).abs() < 1e-4, "{} vs {}", x, y);
+        }
+    }
+
+    #[test]
+    fn test_floor_makes_positive_definite() {
+        let a = [
+            1.0, 0.9, 0.7, //
+            0.9, 1.0, -0.4, //
+            0.7, -0.4, 1.0,
+        ];
+        let x = nearest_correlation(&a, 3, 1e-6).unwrap();
+        assert!(cholesky(&x, 3).is_ok());
+        for i in 0..3 {
+            assert_eq!(x[i * 3 + i], 1.0);
+        }
+        let eigen = symmetric_eigen(&x, 3).unwrap();
+        assert!(eigen.values()[0] > 0.0);
+    }
+
+    #[test]
+    fn test_valid_matrix_unchanged() {
+        let a = [
+            1.0, 0.3, -0.2, //
+            0.3, 1.0, 0.5, //
+            -0.2, 0.5, 1.0,
+        ];
+        let x = nearest_correlation(&a, 3, 1e-8).unwrap();
+        for (x, y) in x.iter().zip(&a) {
+            assert!((x - y).abs() < 1e-12);
+        }
+    }
+}
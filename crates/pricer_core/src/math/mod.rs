@@ -8,7 +8,15 @@
 //! - `smoothing`: Smooth approximations using LogSumExp and sigmoid functions
 //! - `interpolators`: Interpolation methods for curve and surface fitting
 //! - `solvers`: Root-finding algorithms for numerical solving
+//! - `implied_vol`: Black-Scholes, Black-76 and Bachelier implied volatility
+//! - `linalg`: Cholesky, symmetric eigendecomposition and nearest-correlation
+//!   repair
+//! - `quadrature`: Gauss-Legendre, Gauss-Hermite, adaptive Simpson and
+//!   trapezoidal integration
 
+pub mod implied_vol;
 pub mod interpolators;
+pub mod linalg;
+pub mod quadrature;
 pub mod smoothing;
 pub mod solvers;
@@ -0,0 +1,183 @@
+//! Gauss-Hermite quadrature for Gaussian expectations.
+
+use num_traits::Float;
+use std::f64::consts::PI;
+
+/// Gauss-Hermite quadrature rule for standard normal expectations.
+///
+/// Approximates `E[f(Z)]`, `Z ~ N(0, 1)`, by `Σ wᵢ f(zᵢ)`, exact when `f`
+/// is a polynomial of degree up to `2n - 1`. Nodes and weights are the
+/// physicists' Hermite rule rescaled to the standard normal density, so
+/// the weights sum to one. Used for integrating over Gaussian factors,
+/// e.g. conditional default in one-factor copulas or convexity
+/// adjustments.
+///
+/// # Type Parameters
+///
+/// * `T` - Floating-point type (e.g., `f64`, `Dual64`)
+///
+/// # Example
+///
+/// ```
+/// use pricer_core::math::quadrature::GaussHermite;
+///
+/// let rule = GaussHermite::new(20);
+///
+/// // E[exp(σZ)] = exp(σ²/2)
+/// let sigma = 0.3_f64;
+/// let expectation = rule.expectation(|z: f64| (sigma * z).exp());
+/// assert!((expectation - (0.5 * sigma * sigma).exp()).abs() < 1e-14);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct GaussHermite<T: Float> {
+    /// Nodes for the standard normal, ascending
+    nodes: Vec<T>,
+    /// Probability weights, summing to 1
+    weights: Vec<T>,
+}
+
+impl<T: Float> GaussHermite<T> {
+    /// Create an `n`-point rule.
+    ///
+    /// Weights of the outermost nodes underflow beyond a few hundred
+    /// points, where they no longer contribute to any expectation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n == 0`.
+    pub fn new(n: usize) -> Self {
+        assert!(n > 0, "quadrature needs at least one node");
+        let mut nodes = vec![0.0; n];
+        let mut weights = vec![0.0; n];
+        let nf = n as f64;
+
+        // Roots of the physicists' H_n, largest first, by Newton iteration
+        // on the orthonormal recurrence with the usual asymptotic guesses
+        let mut roots: Vec<f64> = Vec::with_capacity(n.div_ceil(2));
+        for i in 0..n.div_ceil(2) {
+            let mut z = match i {
+                0 => (2.0 * nf + 1.0).sqrt() - 1.85575 * (2.0 * nf + 1.0).powf(-1.0 / 6.0),
+                1 => roots[0] - 1.14 * nf.powf(0.426) / roots[0],
+                2 => 1.86 * roots[1] - 0.86 * roots[0],
+                3 => 1.91 * roots[2] - 0.91 * roots[1],
+                _ => 2.0 * roots[i - 1] - roots[i - 2],
+            };
+            for _ in 0..100 {
+                let (p, dp) = hermite(n, z);
+                let step = p / dp;
+                z -= step;
+                if step.abs() <= 4.0 * f64::EPSILON * z.abs().max(1.0) {
+                    break;
+                }
+            }
+            roots.push(z);
+
+            // Rescale to the standard normal: z → √2 z, w → w / √π
+            let (_, dp) = hermite(n, z);
+            let weight = 2.0 / (dp * dp) / PI.sqrt();
+            nodes[i] = -std::f64::consts::SQRT_2 * z;
+            nodes[n - 1 - i] = std::f64::consts::SQRT_2 * z;
+            weights[i] = weight;
+            weights[n - 1 - i] = weight;
+        }
+
+        Self {
+            nodes: nodes.into_iter().map(|x| T::from(x).unwrap()).collect(),
+            weights: weights.into_iter().map(|w| T::from(w).unwrap()).collect(),
+        }
+    }
+
+    /// Number of nodes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Always false: a rule has at least one node.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Nodes for the standard normal, ascending.
+    #[inline]
+    pub fn nodes(&self) -> &[T] {
+        &self.nodes
+    }
+
+    /// Probability weights of the nodes.
+    #[inline]
+    pub fn weights(&self) -> &[T] {
+        &self.weights
+    }
+
+    /// Expectation `E[f(Z)]` of a standard normal `Z`.
+    pub fn expectation<F>(&self, f: F) -> T
+    where
+        F: Fn(T) -> T,
+    {
+        self.nodes
+            .iter()
+            .zip(&self.weights)
+            .fold(T::zero(), |sum, (&z, &w)| sum + w * f(z))
+    }
+}
+
+/// Orthonormal Hermite function `π^{-1/4} H_n(x) / √(2ⁿ n!)` and its
+/// derivative, which stay finite where `H_n` itself would overflow.
+fn hermite(n: usize, x: f64) -> (f64, f64) {
+    let mut p1 = PI.powf(-0.25);
+    let mut p2 = 0.0;
+    for j in 0..n {
+        let p3 = p2;
+        p2 = p1;
+        let j = j as f64;
+        p1 = x * (2.0 / (j + 1.0)).sqrt() * p2 - (j / (j + 1.0)).sqrt() * p3;
+    }
+    (p1, (2.0 * n as f64).sqrt() * p2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normal_moments() {
+        for n in 1..=30 {
+            let rule: GaussHermite<f64> = GaussHermite::new(n);
+            assert!((rule.expectation(|_| 1.0) - 1.0).abs() < 1e-13, "n = {}", n);
+            assert!(rule.expectation(|z| z).abs() < 1e-13, "n = {}", n);
+            // E[Z^(2k)] = (2k - 1)!! for 2k ≤ 2n - 1
+            let mut double_factorial = 1.0;
+            for k in 1..n {
+                double_factorial *= (2 * k - 1) as f64;
+                let moment = rule.expectation(|z| z.powi(2 * k as i32));
+                assert!(
+                    (moment / double_factorial - 1.0).abs() < 1e-10,
+                    "n = {}, k = {}",
+                    n,
+                    k
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_known_nodes() {
+        let rule: GaussHermite<f64> = GaussHermite::new(3);
+        let root = 3.0_f64.sqrt();
+        assert!((rule.nodes()[0] + root).abs() < 1e-14);
+        assert!(rule.nodes()[1].abs() < 1e-14);
+        assert!((rule.weights()[1] - 2.0 / 3.0).abs() < 1e-14);
+        assert!((rule.weights()[2] - 1.0 / 6.0).abs() < 1e-14);
+    }
+
+    #[test]
+    fn test_large_rule() {
+        let rule: GaussHermite<f64> = GaussHermite::new(100);
+        assert!(rule.nodes().windows(2).all(|w| w[0] < w[1]));
+        // E[cos(4Z)] = exp(-8)
+        let expectation = rule.expectation(|z| (4.0 * z).cos());
+        assert!((expectation - (-8.0_f64).exp()).abs() < 1e-14);
+    }
+}
@@ -0,0 +1,181 @@
+//! Gauss-Legendre quadrature on finite intervals.
+
+use num_traits::Float;
+use std::f64::consts::PI;
+
+/// Gauss-Legendre quadrature rule.
+///
+/// The n-point rule integrates polynomials of degree up to `2n - 1`
+/// exactly on `[a, b]`. Nodes are the roots of the Legendre polynomial
+/// `P_n`, found by Newton iteration from Tricomi's approximation, so the
+/// rule is built once and reused across integrals.
+///
+/// # Type Parameters
+///
+/// * `T` - Floating-point type (e.g., `f64`, `Dual64`)
+///
+/// # Example
+///
+/// ```
+/// use pricer_core::math::quadrature::GaussLegendre;
+///
+/// let rule = GaussLegendre::new(5);
+///
+/// // Degree 9 polynomials are integrated exactly
+/// let integral = rule.integrate(|x: f64| x.powi(9) + 1.0, 0.0, 1.0);
+/// assert!((integral - 1.1).abs() < 1e-14);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct GaussLegendre<T: Float> {
+    /// Nodes on `[-1, 1]`, ascending
+    nodes: Vec<T>,
+    /// Weights, summing to 2
+    weights: Vec<T>,
+}
+
+impl<T: Float> GaussLegendre<T> {
+    /// Create an `n`-point rule.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n == 0`.
+    pub fn new(n: usize) -> Self {
+        assert!(n > 0, "quadrature needs at least one node");
+        let mut nodes = vec![0.0; n];
+        let mut weights = vec![0.0; n];
+        let nf = n as f64;
+
+        // Nodes are symmetric: solve for the positive half
+        for i in 0..n.div_ceil(2) {
+            let mut x = (PI * (i as f64 + 0.75) / (nf + 0.5)).cos();
+            for _ in 0..100 {
+                let (p, dp) = legendre(n, x);
+                let step = p / dp;
+                x -= step;
+                if step.abs() <= 4.0 * f64::EPSILON {
+                    break;
+                }
+            }
+            let (_, dp) = legendre(n, x);
+            let weight = 2.0 / ((1.0 - x * x) * dp * dp);
+            nodes[i] = -x;
+            nodes[n - 1 - i] = x;
+            weights[i] = weight;
+            weights[n - 1 - i] = weight;
+        }
+
+        Self {
+            nodes: nodes.into_iter().map(|x| T::from(x).unwrap()).collect(),
+            weights: weights.into_iter().map(|w| T::from(w).unwrap()).collect(),
+        }
+    }
+
+    /// Number of nodes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Always false: a rule has at least one node.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Nodes on `[-1, 1]`, ascending.
+    #[inline]
+    pub fn nodes(&self) -> &[T] {
+        &self.nodes
+    }
+
+    /// Weights of the nodes on `[-1, 1]`.
+    #[inline]
+    pub fn weights(&self) -> &[T] {
+        &self.weights
+    }
+
+    /// Integral of `f` over `[a, b]`.
+    pub fn integrate<F>(&self, f: F, a: T, b: T) -> T
+    where
+        F: Fn(T) -> T,
+    {
+        let two = T::from(2.0).unwrap();
+        let half_width = (b - a) / two;
+        let centre = (a + b) / two;
+        let sum = self
+            .nodes
+            .iter()
+            .zip(&self.weights)
+            .fold(T::zero(), |sum, (&x, &w)| {
+                sum + w * f(centre + half_width * x)
+            });
+        sum * half_width
+    }
+}
+
+/// Legendre polynomial `P_n(x)` and its derivative by the three-term
+/// recurrence.
+fn legendre(n: usize, x: f64) -> (f64, f64) {
+    if n == 0 {
+        return (1.0, 0.0);
+    }
+    let mut p0 = 1.0;
+    let mut p1 = x;
+    for k in 2..=n {
+        let k = k as f64;
+        let p2 = ((2.0 * k - 1.0) * x * p1 - (k - 1.0) * p0) / k;
+        p0 = p1;
+        p1 = p2;
+    }
+    let nf = n as f64;
+    (p1, nf * (x * p1 - p0) / (x * x - 1.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_nodes() {
+        let rule: GaussLegendre<f64> = GaussLegendre::new(2);
+        let node = 1.0 / 3.0_f64.sqrt();
+        assert!((rule.nodes()[0] + node).abs() < 1e-15);
+        assert!((rule.nodes()[1] - node).abs() < 1e-15);
+        assert!((rule.weights()[0] - 1.0).abs() < 1e-15);
+
+        let rule: GaussLegendre<f64> = GaussLegendre::new(3);
+        assert!(rule.nodes()[1].abs() < 1e-15);
+        assert!((rule.weights()[1] - 8.0 / 9.0).abs() < 1e-15);
+        assert!((rule.nodes()[2] - 0.6_f64.sqrt()).abs() < 1e-15);
+    }
+
+    #[test]
+    fn test_polynomial_exactness() {
+        for n in 1..=20 {
+            let rule: GaussLegendre<f64> = GaussLegendre::new(n);
+            let weights: f64 = rule.weights().iter().sum();
+            assert!((weights - 2.0).abs() < 1e-13, "n = {}", n);
+
+            // ∫₀² x^(2n-1) dx = 2^(2n) / 2n
+            let degree = 2 * n as i32 - 1;
+            let exact = 2.0_f64.powi(degree + 1) / (degree + 1) as f64;
+            let integral = rule.integrate(|x| x.powi(degree), 0.0, 2.0);
+            assert!((integral / exact - 1.0).abs() < 1e-12, "n = {}", n);
+        }
+    }
+
+    #[test]
+    fn test_large_rule() {
+        let rule: GaussLegendre<f64> = GaussLegendre::new(200);
+        assert_eq!(rule.len(), 200);
+        assert!(rule.nodes().windows(2).all(|w| w[0] < w[1]));
+        let integral = rule.integrate(|x| (-x).exp(), 0.0, 50.0);
+        assert!((integral - (1.0 - (-50.0_f64).exp())).abs() < 1e-13);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one node")]
+    fn test_empty_rule_panics() {
+        let _: GaussLegendre<f64> = GaussLegendre::new(0);
+    }
+}
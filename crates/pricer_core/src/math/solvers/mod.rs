@@ -9,7 +9,8 @@
 //!
 //! ### Root-Finding
 //!
-//! - [`NewtonRaphsonSolver`]: Fast quadratic convergence using derivatives
+//! - [`NewtonRaphsonSolver`]: Fast quadratic convergence using derivatives,
+//!   with a bisection-safeguarded variant for bracketed roots
 //! - [`BrentSolver`]: Robust bracketing method without derivative requirement
 //!
 //! ### Optimization
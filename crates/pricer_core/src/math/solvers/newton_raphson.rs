@@ -141,6 +141,96 @@ impl<T: Float> NewtonRaphsonSolver<T> {
         })
     }
 
+    /// Find a root of `f` in the bracket [a, b] by safeguarded Newton.
+    ///
+    /// Newton steps that would leave the current bracket, or that shrink
+    /// it too slowly, are replaced by bisection, so the iteration keeps
+    /// Newton's quadratic convergence near the root while converging for
+    /// any continuous `f` with a valid bracket.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(x)` - Root where `|f(x)| < tolerance` or the bracket is
+    ///   narrower than the tolerance
+    /// * `Err(SolverError::NoBracket)` - `f(a)` and `f(b)` have same sign
+    /// * `Err(SolverError::MaxIterationsExceeded)` - Failed to converge
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use pricer_core::math::solvers::{NewtonRaphsonSolver, SolverConfig};
+    ///
+    /// let solver = NewtonRaphsonSolver::new(SolverConfig::default());
+    ///
+    /// // Plain Newton from 0 diverges on atan; the bracket keeps it safe
+    /// let f = |x: f64| (x - 1.0).atan();
+    /// let f_prime = |x: f64| 1.0 / (1.0 + (x - 1.0) * (x - 1.0));
+    ///
+    /// let root = solver.find_root_bracketed(f, f_prime, -10.0, 10.0).unwrap();
+    /// assert!((root - 1.0).abs() < 1e-10);
+    /// ```
+    pub fn find_root_bracketed<F, G>(&self, f: F, f_prime: G, a: T, b: T) -> Result<T, SolverError>
+    where
+        F: Fn(T) -> T,
+        G: Fn(T) -> T,
+    {
+        let fa = f(a);
+        let fb = f(b);
+        if fa.abs() < self.config.tolerance {
+            return Ok(a);
+        }
+        if fb.abs() < self.config.tolerance {
+            return Ok(b);
+        }
+        if fa * fb > T::zero() {
+            return Err(SolverError::NoBracket {
+                a: a.to_f64().unwrap_or(f64::NAN),
+                b: b.to_f64().unwrap_or(f64::NAN),
+            });
+        }
+
+        // Orient the bracket so that f(lo) < 0 < f(hi)
+        let (mut lo, mut hi) = if fa < T::zero() { (a, b) } else { (b, a) };
+        let two = T::from(2.0).unwrap();
+        let mut x = (lo + hi) / two;
+        let mut step_before_last = (hi - lo).abs();
+        let mut last_step = step_before_last;
+
+        for _iteration in 0..self.config.max_iterations {
+            let f_val = f(x);
+            if f_val.abs() < self.config.tolerance {
+                return Ok(x);
+            }
+            if f_val < T::zero() {
+                lo = x;
+            } else {
+                hi = x;
+            }
+
+            let f_prime_val = f_prime(x);
+            let newton = x - f_val / f_prime_val;
+            let inside = newton.is_finite() && (newton - lo) * (newton - hi) < T::zero();
+            let fast_enough = (two * f_val).abs() <= (step_before_last * f_prime_val).abs();
+
+            step_before_last = last_step;
+            let next = if inside && fast_enough {
+                newton
+            } else {
+                (lo + hi) / two
+            };
+            last_step = (next - x).abs();
+            x = next;
+
+            if last_step < self.config.tolerance || (hi - lo).abs() < self.config.tolerance {
+                return Ok(x);
+            }
+        }
+
+        Err(SolverError::MaxIterationsExceeded {
+            iterations: self.config.max_iterations,
+        })
+    }
+
     /// Returns a reference to the solver configuration.
     pub fn config(&self) -> &SolverConfig<T> {
         &self.config
@@ -347,6 +437,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_find_root_bracketed() {
+        let solver = NewtonRaphsonSolver::new(SolverConfig::default());
+
+        // Plain Newton overshoots on atan from far away
+        let f = |x: f64| (x - 1.0).atan();
+        let f_prime = |x: f64| 1.0 / (1.0 + (x - 1.0) * (x - 1.0));
+        assert!(solver.find_root(f, f_prime, 5.0).is_err());
+        let root = solver.find_root_bracketed(f, f_prime, 5.0, -5.0).unwrap();
+        assert!((root - 1.0).abs() < 1e-10);
+
+        // A zero derivative at the midpoint falls back to bisection
+        let f = |x: f64| x * x * x - 8.0;
+        let f_prime = |x: f64| 3.0 * x * x;
+        let root = solver.find_root_bracketed(f, f_prime, -4.0, 4.0).unwrap();
+        assert!((root - 2.0).abs() < 1e-10);
+
+        let result = solver.find_root_bracketed(f, f_prime, 3.0, 4.0);
+        assert!(matches!(result, Err(SolverError::NoBracket { .. })));
+    }
+
     #[test]
     fn test_with_defaults() {
         let solver: NewtonRaphsonSolver<f64> = NewtonRaphsonSolver::with_defaults();
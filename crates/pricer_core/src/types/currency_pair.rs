@@ -46,6 +46,7 @@ use super::error::CurrencyError;
 /// assert_eq!(eurusd.quote(), Currency::USD);
 /// ```
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CurrencyPair<T: Float> {
     /// Base currency (the numerator in the exchange rate)
     base: Currency,
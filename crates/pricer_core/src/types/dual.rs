@@ -22,6 +22,12 @@
 //! let gradient = result.eps;    // ∂smooth_max/∂a
 //! ```
 
+use num_dual::DualNum;
+use num_traits::{Float, Num, NumCast, One, Signed, ToPrimitive, Zero};
+use std::fmt;
+use std::num::FpCategory;
+use std::ops;
+
 /// Type alias for num-dual's Dual64 (f64-based dual numbers).
 ///
 /// This type supports first-order automatic differentiation with:
@@ -31,26 +37,426 @@
 /// # Integration with Smoothing Functions
 ///
 /// NOTE: `DualNumber` (`Dual64`) does NOT implement `num_traits::Float`.
-/// To use smoothing functions with AD, they need to be refactored to use
-/// a more permissive trait bound (e.g., `DualNum<f64>` or a custom `Scalar` trait).
+/// Wrap it in [`DualFloat`] to pass it to code generic over `T: Float`.
 ///
-/// Example of intended usage (requires trait bound refactoring):
+/// Example:
 ///
-/// ```ignore
-/// # #[cfg(feature = "num-dual-mode")]
-/// # {
-/// use pricer_core::types::dual::DualNumber;
+/// ```
+/// use pricer_core::types::dual::DualFloat;
 /// use pricer_core::math::smoothing::{smooth_max, smooth_min, smooth_abs, smooth_indicator};
 ///
-/// let x = DualNumber::from(2.0).derivative();
-/// let y = DualNumber::from(3.0);
+/// let x = DualFloat::variable(2.0);
+/// let y = DualFloat::constant(3.0);
+/// let eps = DualFloat::constant(1e-6);
 ///
 /// // All smoothing functions propagate gradients
-/// let max_result = smooth_max(x, y, 1e-6);
-/// let min_result = smooth_min(x, y, 1e-6);
-/// let abs_result = smooth_abs(x, 1e-6);
-/// let ind_result = smooth_indicator(x, 1e-6);
-/// # }
+/// let max_result = smooth_max(x, y, eps);
+/// let min_result = smooth_min(x, y, eps);
+/// let abs_result = smooth_abs(x, eps);
+/// let ind_result = smooth_indicator(x, eps);
+/// assert!((min_result.derivative() - 1.0).abs() < 1e-9);
 /// ```
 #[cfg(feature = "num-dual-mode")]
 pub type DualNumber = num_dual::Dual64;
+
+/// Forward-mode dual number implementing `num_traits::Float`.
+///
+/// Wraps [`DualNumber`] so that code generic over `T: Float` (interpolators,
+/// curves, surfaces, analytical models) can be evaluated with a tangent
+/// attached to one input. Seed the input of interest with
+/// [`variable`](Self::variable) and read the sensitivity of the result with
+/// [`derivative`](Self::derivative).
+///
+/// Comparisons, `floor`/`ceil`/`round`/`trunc` and `max`/`min` act on the
+/// real part; rounding functions have zero derivative, and `max`/`min`
+/// carry the derivative of the argument they select.
+///
+/// # Example
+///
+/// ```
+/// use pricer_core::math::interpolators::{Interpolator, LinearInterpolator};
+/// use pricer_core::types::dual::DualFloat;
+///
+/// // Sensitivity of the interpolated value at x = 0.25 to the pillar y₀
+/// let xs = [DualFloat::constant(0.0), DualFloat::constant(1.0)];
+/// let ys = [DualFloat::variable(1.0), DualFloat::constant(3.0)];
+/// let interp = LinearInterpolator::new(&xs, &ys).unwrap();
+/// let y = interp.interpolate(DualFloat::constant(0.25)).unwrap();
+///
+/// assert!((y.value() - 1.5).abs() < 1e-12);
+/// assert!((y.derivative() - 0.75).abs() < 1e-12);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct DualFloat(pub DualNumber);
+
+impl DualFloat {
+    /// Create a dual number with the given value and derivative.
+    #[inline]
+    pub fn new(value: f64, derivative: f64) -> Self {
+        Self(DualNumber::new(value, derivative))
+    }
+
+    /// Create a constant (zero derivative).
+    #[inline]
+    pub fn constant(value: f64) -> Self {
+        Self::new(value, 0.0)
+    }
+
+    /// Create the variable of differentiation (unit derivative).
+    #[inline]
+    pub fn variable(value: f64) -> Self {
+        Self::new(value, 1.0)
+    }
+
+    /// Lift `values` to dual numbers, seeding `values[index]` as the variable.
+    ///
+    /// Used to differentiate with respect to one pillar of a curve or grid.
+    pub fn seeded(values: &[f64], index: usize) -> Vec<Self> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, &v)| Self::new(v, if i == index { 1.0 } else { 0.0 }))
+            .collect()
+    }
+
+    /// Return the real part.
+    #[inline]
+    pub fn value(&self) -> f64 {
+        self.0.re
+    }
+
+    /// Return the derivative part.
+    #[inline]
+    pub fn derivative(&self) -> f64 {
+        self.0.eps
+    }
+}
+
+impl fmt::Display for DualFloat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} + {}ε", self.value(), self.derivative())
+    }
+}
+
+macro_rules! forward_binop {
+    ($($trt:ident, $mth:ident, $assign_trt:ident, $assign_mth:ident);* $(;)?) => {
+        $(
+            impl ops::$trt for DualFloat {
+                type Output = Self;
+                #[inline]
+                fn $mth(self, rhs: Self) -> Self {
+                    Self(ops::$trt::$mth(self.0, rhs.0))
+                }
+            }
+
+            impl ops::$assign_trt for DualFloat {
+                #[inline]
+                fn $assign_mth(&mut self, rhs: Self) {
+                    *self = ops::$trt::$mth(*self, rhs);
+                }
+            }
+        )*
+    };
+}
+
+forward_binop! {
+    Add, add, AddAssign, add_assign;
+    Sub, sub, SubAssign, sub_assign;
+    Mul, mul, MulAssign, mul_assign;
+    Div, div, DivAssign, div_assign;
+}
+
+impl ops::Rem for DualFloat {
+    type Output = Self;
+
+    /// `a − trunc(a / b)·b`, with the quotient treated as constant.
+    #[inline]
+    fn rem(self, rhs: Self) -> Self {
+        let n = (self.value() / rhs.value()).trunc();
+        Self::new(
+            self.value() % rhs.value(),
+            self.derivative() - n * rhs.derivative(),
+        )
+    }
+}
+
+impl ops::RemAssign for DualFloat {
+    #[inline]
+    fn rem_assign(&mut self, rhs: Self) {
+        *self = *self % rhs;
+    }
+}
+
+impl ops::Neg for DualFloat {
+    type Output = Self;
+    #[inline]
+    fn neg(self) -> Self {
+        Self(-self.0)
+    }
+}
+
+impl Zero for DualFloat {
+    #[inline]
+    fn zero() -> Self {
+        Self::constant(0.0)
+    }
+
+    #[inline]
+    fn is_zero(&self) -> bool {
+        self.value() == 0.0 && self.derivative() == 0.0
+    }
+}
+
+impl One for DualFloat {
+    #[inline]
+    fn one() -> Self {
+        Self::constant(1.0)
+    }
+}
+
+impl Num for DualFloat {
+    type FromStrRadixErr = <f64 as Num>::FromStrRadixErr;
+
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        f64::from_str_radix(s, radix).map(Self::constant)
+    }
+}
+
+impl ToPrimitive for DualFloat {
+    #[inline]
+    fn to_i64(&self) -> Option<i64> {
+        self.value().to_i64()
+    }
+
+    #[inline]
+    fn to_u64(&self) -> Option<u64> {
+        self.value().to_u64()
+    }
+
+    #[inline]
+    fn to_f64(&self) -> Option<f64> {
+        Some(self.value())
+    }
+}
+
+impl NumCast for DualFloat {
+    #[inline]
+    fn from<N: ToPrimitive>(n: N) -> Option<Self> {
+        n.to_f64().map(Self::constant)
+    }
+}
+
+/// Float methods evaluated by num-dual's chain rule.
+macro_rules! forward_unary {
+    ($($mth:ident),* $(,)?) => {
+        $(
+            #[inline]
+            fn $mth(self) -> Self {
+                Self(DualNum::$mth(&self.0))
+            }
+        )*
+    };
+}
+
+/// Float methods that only inspect the real part.
+macro_rules! forward_predicate {
+    ($($mth:ident),* $(,)?) => {
+        $(
+            #[inline]
+            fn $mth(self) -> bool {
+                self.value().$mth()
+            }
+        )*
+    };
+}
+
+/// Piecewise-constant Float methods: zero derivative.
+macro_rules! forward_step {
+    ($($mth:ident),* $(,)?) => {
+        $(
+            #[inline]
+            fn $mth(self) -> Self {
+                Self::constant(self.value().$mth())
+            }
+        )*
+    };
+}
+
+impl Float for DualFloat {
+    forward_unary!(
+        sqrt, cbrt, exp, exp2, exp_m1, ln, ln_1p, log2, log10, recip, sin, cos, tan, asin, acos,
+        atan, sinh, cosh, tanh, asinh, acosh, atanh,
+    );
+    forward_predicate!(
+        is_nan,
+        is_infinite,
+        is_finite,
+        is_normal,
+        is_sign_positive,
+        is_sign_negative
+    );
+    forward_step!(floor, ceil, round, trunc);
+
+    fn nan() -> Self {
+        Self::constant(f64::NAN)
+    }
+
+    fn infinity() -> Self {
+        Self::constant(f64::INFINITY)
+    }
+
+    fn neg_infinity() -> Self {
+        Self::constant(f64::NEG_INFINITY)
+    }
+
+    fn neg_zero() -> Self {
+        Self::constant(-0.0)
+    }
+
+    fn min_value() -> Self {
+        Self::constant(f64::MIN)
+    }
+
+    fn min_positive_value() -> Self {
+        Self::constant(f64::MIN_POSITIVE)
+    }
+
+    fn max_value() -> Self {
+        Self::constant(f64::MAX)
+    }
+
+    fn epsilon() -> Self {
+        Self::constant(f64::EPSILON)
+    }
+
+    fn classify(self) -> FpCategory {
+        self.value().classify()
+    }
+
+    fn fract(self) -> Self {
+        Self::new(self.value().fract(), self.derivative())
+    }
+
+    fn abs(self) -> Self {
+        Self(Signed::abs(&self.0))
+    }
+
+    fn signum(self) -> Self {
+        Self::constant(self.value().signum())
+    }
+
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        self * a + b
+    }
+
+    fn powi(self, n: i32) -> Self {
+        Self(DualNum::powi(&self.0, n))
+    }
+
+    fn powf(self, n: Self) -> Self {
+        if n.derivative() == 0.0 {
+            Self(DualNum::powf(&self.0, n.value()))
+        } else {
+            (self.ln() * n).exp()
+        }
+    }
+
+    fn log(self, base: Self) -> Self {
+        self.ln() / base.ln()
+    }
+
+    fn max(self, other: Self) -> Self {
+        if other > self {
+            other
+        } else {
+            self
+        }
+    }
+
+    fn min(self, other: Self) -> Self {
+        if other < self {
+            other
+        } else {
+            self
+        }
+    }
+
+    fn abs_sub(self, other: Self) -> Self {
+        (self - other).max(Self::zero())
+    }
+
+    fn hypot(self, other: Self) -> Self {
+        (self * self + other * other).sqrt()
+    }
+
+    fn atan2(self, other: Self) -> Self {
+        let r2 = self * self + other * other;
+        Self::new(
+            self.value().atan2(other.value()),
+            (other.value() * self.derivative() - self.value() * other.derivative()) / r2.value(),
+        )
+    }
+
+    fn sin_cos(self) -> (Self, Self) {
+        (self.sin(), self.cos())
+    }
+
+    fn integer_decode(self) -> (u64, i16, i8) {
+        self.value().integer_decode()
+    }
+
+    fn to_degrees(self) -> Self {
+        Self(self.0 * (180.0 / std::f64::consts::PI))
+    }
+
+    fn to_radians(self) -> Self {
+        Self(self.0 * (std::f64::consts::PI / 180.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn d(f: impl Fn(DualFloat) -> DualFloat, x: f64) -> f64 {
+        f(DualFloat::variable(x)).derivative()
+    }
+
+    #[test]
+    fn test_elementary_derivatives() {
+        let x = 0.7;
+        assert_relative_eq!(d(|x| x * x, x), 2.0 * x, epsilon = 1e-14);
+        assert_relative_eq!(d(Float::exp, x), x.exp(), epsilon = 1e-14);
+        assert_relative_eq!(d(Float::ln, x), 1.0 / x, epsilon = 1e-14);
+        assert_relative_eq!(d(Float::sqrt, x), 0.5 / x.sqrt(), epsilon = 1e-14);
+        assert_relative_eq!(d(|x| x.powi(3), x), 3.0 * x * x, epsilon = 1e-14);
+        assert_relative_eq!(
+            d(|x| x.powf(x), x),
+            x.powf(x) * (x.ln() + 1.0),
+            epsilon = 1e-14
+        );
+        assert_relative_eq!(d(Float::recip, x), -1.0 / (x * x), epsilon = 1e-14);
+        assert_relative_eq!(d(|x| -x.abs(), -x), 1.0, epsilon = 1e-14);
+        assert_relative_eq!(
+            d(|x| x.atan2(DualFloat::constant(2.0)), x),
+            2.0 / (x * x + 4.0),
+            epsilon = 1e-14
+        );
+    }
+
+    #[test]
+    fn test_comparisons_and_casts_use_real_part() {
+        let a = DualFloat::new(1.0, 5.0);
+        let b = DualFloat::new(2.0, -1.0);
+        assert!(a < b);
+        assert_eq!(a.max(b), b);
+        assert_eq!(a.min(b), a);
+        assert_eq!(a.floor().derivative(), 0.0);
+        assert_eq!(a.to_f64(), Some(1.0));
+        assert_eq!(
+            <DualFloat as NumCast>::from(3_i32),
+            Some(DualFloat::constant(3.0))
+        );
+    }
+}
@@ -6,6 +6,8 @@
 //! - `CurrencyError`: Errors from currency parsing
 //! - `InterpolationError`: Errors from interpolation operations
 //! - `SolverError`: Errors from root-finding solvers
+//! - `ImpliedVolError`: Errors from implied volatility inversion
+//! - `LinalgError`: Errors from matrix decompositions
 //! - `CalibrationError`: Errors from model calibration
 
 use std::fmt;
@@ -256,6 +258,105 @@ pub enum SolverError {
     NumericalInstability(String),
 }
 
+/// Implied volatility inversion errors.
+///
+/// # Variants
+/// - `InvalidInput`: Non-positive forward, strike, expiry or discount factor
+/// - `PriceBelowIntrinsic`: Price below the discounted intrinsic value
+/// - `PriceAboveMaximum`: Price at or above the no-arbitrage upper bound
+/// - `NotConverged`: Iterations failed to converge
+///
+/// # Examples
+/// ```
+/// use pricer_core::types::ImpliedVolError;
+///
+/// let err = ImpliedVolError::PriceBelowIntrinsic { price: 4.0, intrinsic: 5.0 };
+/// assert!(format!("{}", err).contains("below intrinsic"));
+/// ```
+#[derive(Error, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ImpliedVolError {
+    /// Invalid quote inputs.
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+
+    /// Price below the discounted intrinsic value.
+    #[error("Price {price} below intrinsic value {intrinsic}")]
+    PriceBelowIntrinsic {
+        /// Quoted price
+        price: f64,
+        /// Discounted intrinsic value
+        intrinsic: f64,
+    },
+
+    /// Price at or above the no-arbitrage upper bound.
+    #[error("Price {price} at or above maximum {maximum}")]
+    PriceAboveMaximum {
+        /// Quoted price
+        price: f64,
+        /// Upper bound: discounted forward for calls, strike for puts
+        maximum: f64,
+    },
+
+    /// Iterations failed to converge.
+    #[error("Implied volatility failed to converge after {iterations} iterations")]
+    NotConverged {
+        /// Number of iterations attempted
+        iterations: usize,
+    },
+}
+
+/// Dense linear algebra errors.
+///
+/// # Variants
+/// - `InvalidDimensions`: Element count does not match the matrix dimension
+/// - `NotSymmetric`: Matrix differs from its transpose
+/// - `NotPositiveDefinite`: Cholesky pivot is not positive
+/// - `NotConverged`: Iterative decomposition failed to converge
+///
+/// # Examples
+/// ```
+/// use pricer_core::types::LinalgError;
+///
+/// let err = LinalgError::NotPositiveDefinite { index: 2 };
+/// assert!(format!("{}", err).contains("not positive definite"));
+/// ```
+#[derive(Error, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LinalgError {
+    /// Element count does not match the matrix dimension.
+    #[error("Invalid matrix dimensions: expected {expected} elements, got {got}")]
+    InvalidDimensions {
+        /// Expected number of elements
+        expected: usize,
+        /// Actual number of elements
+        got: usize,
+    },
+
+    /// Matrix is not symmetric.
+    #[error("Matrix is not symmetric at ({i}, {j})")]
+    NotSymmetric {
+        /// Row index
+        i: usize,
+        /// Column index
+        j: usize,
+    },
+
+    /// Matrix is not positive definite.
+    #[error("Matrix is not positive definite: non-positive pivot at {index}")]
+    NotPositiveDefinite {
+        /// Row of the failing pivot
+        index: usize,
+    },
+
+    /// Iterations failed to converge.
+    #[error("Decomposition failed to converge after {iterations} iterations")]
+    NotConverged {
+        /// Number of iterations attempted
+        iterations: usize,
+    },
+}
+
 /// Calibration error kind.
 ///
 /// Categorises the type of calibration failure.
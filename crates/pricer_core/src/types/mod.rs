@@ -5,6 +5,7 @@
 //! - `time`: Time types (Date, DayCountConvention, BusinessDayConvention) for financial calculations
 //! - `currency`: ISO 4217 currency codes with metadata
 //! - `currency_pair`: Currency pair types for FX calculations
+//! - `provenance`: Run metadata (seed, versions, input hashes, git commit) for reproducing results
 //! - `error`: Structured error types for pricing, date, currency, interpolation, solver, and calibration operations
 //!
 //! # Re-exports
@@ -13,22 +14,25 @@
 //! - [`Date`], [`DayCountConvention`], [`BusinessDayConvention`], [`time_to_maturity`], [`time_to_maturity_dates`] from `time`
 //! - [`Currency`] from `currency`
 //! - [`CurrencyPair`] from `currency_pair`
-//! - [`PricingError`], [`DateError`], [`CurrencyError`], [`InterpolationError`], [`SolverError`], [`CalibrationError`], [`CalibrationErrorKind`] from `error`
+//! - [`RunMetadata`], [`fingerprint`] from `provenance`
+//! - [`PricingError`], [`DateError`], [`CurrencyError`], [`InterpolationError`], [`SolverError`], [`ImpliedVolError`], [`LinalgError`], [`CalibrationError`], [`CalibrationErrorKind`] from `error`
 
 pub mod currency;
 pub mod currency_pair;
 #[cfg(feature = "num-dual-mode")]
 pub mod dual;
 pub mod error;
+pub mod provenance;
 pub mod time;
 
 // Re-export commonly used types at module level
 pub use currency::Currency;
 pub use currency_pair::CurrencyPair;
 pub use error::{
-    CalibrationError, CalibrationErrorKind, CurrencyError, DateError, InterpolationError,
-    PricingError, SolverError,
+    CalibrationError, CalibrationErrorKind, CurrencyError, DateError, ImpliedVolError,
+    InterpolationError, LinalgError, PricingError, SolverError,
 };
+pub use provenance::{fingerprint, RunMetadata};
 pub use time::{
     time_to_maturity, time_to_maturity_dates, BusinessDayConvention, Date, DayCountConvention,
 };
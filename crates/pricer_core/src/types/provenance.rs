@@ -0,0 +1,235 @@
+//! Run provenance for reproducible results.
+//!
+//! [`RunMetadata`] records what is needed to reproduce a published number
+//! exactly: the random seed, the versions of the engine crates involved, a
+//! hash of the calculation configuration, a hash of the market snapshot and
+//! the git commit the library was built from. Substitutions made for
+//! missing market data are recorded alongside, so an audit can see which
+//! inputs were proxied, extrapolated or zeroed.
+//!
+//! Hashes are computed by [`fingerprint`], a 64-bit FNV-1a over a value's
+//! `Debug` form. Unlike `std`'s `DefaultHasher` it is stable across Rust
+//! releases and platforms, so hashes recorded today can be compared with a
+//! rerun next year. `f64` values format with their shortest round-trip
+//! representation, so two configurations hash equal exactly when they are
+//! bit-for-bit equal (up to the sign of zero and NaN payloads).
+//!
+//! # Examples
+//!
+//! ```
+//! use pricer_core::types::provenance::{fingerprint, RunMetadata};
+//!
+//! let meta = RunMetadata::current()
+//!     .with_seed(42)
+//!     .with_config_hash(fingerprint(&(10_000, 252, 0.05)))
+//!     .with_market_snapshot_hash(fingerprint(&[0.99, 0.97, 0.95]));
+//!
+//! assert_eq!(meta.seed, Some(42));
+//! assert!(meta.engine_versions.contains_key("pricer_core"));
+//! assert!(meta.same_inputs(&meta.clone()));
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Write};
+
+/// Git commit recorded by the build script, if available.
+const GIT_SHA: Option<&str> = option_env!("NEUTRYX_GIT_SHA");
+
+/// Provenance of a pricing, XVA or report run.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RunMetadata {
+    /// Random seed, for simulation runs.
+    pub seed: Option<u64>,
+    /// Engine crate versions by crate name.
+    pub engine_versions: BTreeMap<String, String>,
+    /// [`fingerprint`] of the calculation configuration.
+    pub config_hash: Option<u64>,
+    /// [`fingerprint`] of the market data snapshot.
+    pub market_snapshot_hash: Option<u64>,
+    /// Git commit the library was built from.
+    pub git_sha: Option<String>,
+    /// Decisions taken for missing market data, by requirement key.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub market_data_decisions: BTreeMap<String, String>,
+}
+
+impl RunMetadata {
+    /// Creates metadata for this build: the `pricer_core` version and the
+    /// git commit, when known.
+    pub fn current() -> Self {
+        Self {
+            git_sha: GIT_SHA.map(str::to_string),
+            ..Self::default()
+        }
+        .with_engine_version("pricer_core", env!("CARGO_PKG_VERSION"))
+    }
+
+    /// Sets the random seed.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Records the version of an engine crate.
+    pub fn with_engine_version(
+        mut self,
+        name: impl Into<String>,
+        version: impl Into<String>,
+    ) -> Self {
+        self.engine_versions.insert(name.into(), version.into());
+        self
+    }
+
+    /// Sets the configuration hash.
+    pub fn with_config_hash(mut self, hash: u64) -> Self {
+        self.config_hash = Some(hash);
+        self
+    }
+
+    /// Sets the market snapshot hash.
+    pub fn with_market_snapshot_hash(mut self, hash: u64) -> Self {
+        self.market_snapshot_hash = Some(hash);
+        self
+    }
+
+    /// Records the decision taken for a missing market data requirement.
+    pub fn with_market_data_decision(
+        mut self,
+        key: impl Into<String>,
+        decision: impl Into<String>,
+    ) -> Self {
+        self.market_data_decisions
+            .insert(key.into(), decision.into());
+        self
+    }
+
+    /// Returns whether two runs used the same seed, configuration and
+    /// market snapshot, regardless of the build that produced them.
+    pub fn same_inputs(&self, other: &Self) -> bool {
+        self.seed == other.seed
+            && self.config_hash == other.config_hash
+            && self.market_snapshot_hash == other.market_snapshot_hash
+    }
+
+    /// Returns the metadata as ordered key/value pairs, for report headers.
+    ///
+    /// Hashes are rendered as 16 hex digits; unset fields are omitted.
+    pub fn entries(&self) -> Vec<(String, String)> {
+        let mut entries = Vec::new();
+        if let Some(seed) = self.seed {
+            entries.push(("seed".to_string(), seed.to_string()));
+        }
+        if let Some(hash) = self.config_hash {
+            entries.push(("config_hash".to_string(), format!("{hash:016x}")));
+        }
+        if let Some(hash) = self.market_snapshot_hash {
+            entries.push(("market_snapshot_hash".to_string(), format!("{hash:016x}")));
+        }
+        if let Some(sha) = &self.git_sha {
+            entries.push(("git_sha".to_string(), sha.clone()));
+        }
+        for (name, version) in &self.engine_versions {
+            entries.push((format!("version.{name}"), version.clone()));
+        }
+        for (key, decision) in &self.market_data_decisions {
+            entries.push((format!("market_data.{key}"), decision.clone()));
+        }
+        entries
+    }
+}
+
+impl fmt::Display for RunMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (key, value)) in self.entries().iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{key}={value}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Stable 64-bit FNV-1a hash of a value's `Debug` representation.
+pub fn fingerprint<T: fmt::Debug + ?Sized>(value: &T) -> u64 {
+    let mut hasher = Fnv1a::default();
+    // Writing to the hasher cannot fail
+    let _ = write!(hasher, "{value:?}");
+    hasher.0
+}
+
+/// FNV-1a hasher fed through `fmt::Write`, so `Debug` output is hashed
+/// without allocating.
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Write for Fnv1a {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for &byte in s.as_bytes() {
+            self.0 ^= u64::from(byte);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint_is_stable() {
+        // Reference FNV-1a 64 values
+        let mut empty = Fnv1a::default();
+        empty.write_str("").unwrap();
+        assert_eq!(empty.0, 0xcbf2_9ce4_8422_2325);
+        let mut a = Fnv1a::default();
+        a.write_str("a").unwrap();
+        assert_eq!(a.0, 0xaf63_dc4c_8601_ec8c);
+    }
+
+    #[test]
+    fn test_fingerprint_distinguishes_values() {
+        assert_eq!(fingerprint(&[0.05, 0.2]), fingerprint(&[0.05, 0.2]));
+        assert_ne!(fingerprint(&[0.05, 0.2]), fingerprint(&[0.05, 0.2000001]));
+        assert_ne!(fingerprint(&Some(1u64)), fingerprint(&None::<u64>));
+    }
+
+    #[test]
+    fn test_current_records_build() {
+        let meta = RunMetadata::current();
+        assert_eq!(
+            meta.engine_versions.get("pricer_core").map(String::as_str),
+            Some(env!("CARGO_PKG_VERSION"))
+        );
+        assert_eq!(meta.git_sha.as_deref(), GIT_SHA);
+        assert_eq!(meta.seed, None);
+    }
+
+    #[test]
+    fn test_same_inputs_ignores_build() {
+        let a = RunMetadata::current().with_seed(7).with_config_hash(1);
+        let mut b = a.clone().with_engine_version("pricer_core", "0.0.0");
+        b.git_sha = Some("deadbeef".into());
+        assert!(a.same_inputs(&b));
+        assert!(!a.same_inputs(&b.with_seed(8)));
+    }
+
+    #[test]
+    fn test_entries_and_display() {
+        let meta = RunMetadata::default()
+            .with_seed(42)
+            .with_config_hash(0xab)
+            .with_engine_version("pricer_pricing", "1.2.3");
+        assert_eq!(
+            meta.to_string(),
+            "seed=42 config_hash=00000000000000ab version.pricer_pricing=1.2.3"
+        );
+    }
+}
@@ -0,0 +1,308 @@
+//! Closed-form pricing for single-barrier European options.
+//!
+//! Implements the Rubinstein-Reiner (1991) formulas under Black-Scholes
+//! dynamics with a continuous dividend yield and continuously monitored
+//! barrier, without rebate.
+//!
+//! ## Mathematical Formulas
+//!
+//! With φ = +1 for calls and -1 for puts, η = +1 for down and -1 for up
+//! barriers, and λ = (r - q + σ²/2) / σ², each price is a combination of
+//!
+//! - A = φS·e^(-qT)·N(φx₁) - φK·e^(-rT)·N(φx₁ - φσ√T)
+//! - B = φS·e^(-qT)·N(φx₂) - φK·e^(-rT)·N(φx₂ - φσ√T)
+//! - C = φS·e^(-qT)·(H/S)^(2λ)·N(ηy₁) - φK·e^(-rT)·(H/S)^(2λ-2)·N(ηy₁ - ησ√T)
+//! - D = φS·e^(-qT)·(H/S)^(2λ)·N(ηy₂) - φK·e^(-rT)·(H/S)^(2λ-2)·N(ηy₂ - ησ√T)
+//!
+//! Where:
+//! - x₁ = ln(S/K)/(σ√T) + λσ√T, x₂ = ln(S/H)/(σ√T) + λσ√T
+//! - y₁ = ln(H²/(SK))/(σ√T) + λσ√T, y₂ = ln(H/S)/(σ√T) + λσ√T
+
+use num_traits::Float;
+
+use super::distributions::norm_cdf;
+use super::error::AnalyticalError;
+
+/// Barrier direction and knock type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BarrierKind {
+    /// Activated when the spot falls to the barrier
+    DownIn,
+    /// Extinguished when the spot falls to the barrier
+    DownOut,
+    /// Activated when the spot rises to the barrier
+    UpIn,
+    /// Extinguished when the spot rises to the barrier
+    UpOut,
+}
+
+impl BarrierKind {
+    /// Whether the barrier sits below the spot.
+    #[inline]
+    pub fn is_down(self) -> bool {
+        matches!(self, Self::DownIn | Self::DownOut)
+    }
+
+    /// Whether hitting the barrier activates the option.
+    #[inline]
+    pub fn is_knock_in(self) -> bool {
+        matches!(self, Self::DownIn | Self::UpIn)
+    }
+}
+
+/// Black-Scholes model for single-barrier European options.
+///
+/// # Type Parameters
+/// * `T` - Floating-point type implementing `Float` (e.g., `f64`, `Dual64`)
+///
+/// # Examples
+/// ```
+/// use pricer_models::analytical::{BarrierKind, BlackScholes, BlackScholesBarrier};
+///
+/// let model = BlackScholesBarrier::new(100.0_f64, 0.05, 0.0, 0.2).unwrap();
+/// let knock_in = model.price(BarrierKind::DownIn, true, 100.0, 90.0, 1.0);
+/// let knock_out = model.price(BarrierKind::DownOut, true, 100.0, 90.0, 1.0);
+///
+/// // In-out parity: knock-in + knock-out = vanilla
+/// let vanilla = BlackScholes::new(100.0_f64, 0.05, 0.2).unwrap().price_call(100.0, 1.0);
+/// assert!((knock_in + knock_out - vanilla).abs() < 1e-10);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BlackScholesBarrier<T: Float> {
+    /// Spot price (S)
+    spot: T,
+    /// Risk-free interest rate (r)
+    rate: T,
+    /// Continuous dividend yield (q)
+    dividend: T,
+    /// Volatility (σ)
+    volatility: T,
+}
+
+impl<T: Float> BlackScholesBarrier<T> {
+    /// Creates a new barrier pricing model.
+    ///
+    /// # Arguments
+    /// * `spot` - Current spot price (must be positive)
+    /// * `rate` - Risk-free interest rate (annualised)
+    /// * `dividend` - Continuous dividend yield (annualised)
+    /// * `volatility` - Volatility (must be positive)
+    ///
+    /// # Errors
+    /// - `AnalyticalError::InvalidSpot` if spot <= 0
+    /// - `AnalyticalError::InvalidVolatility` if volatility <= 0
+    pub fn new(spot: T, rate: T, dividend: T, volatility: T) -> Result<Self, AnalyticalError> {
+        let zero = T::zero();
+
+        if spot <= zero {
+            return Err(AnalyticalError::InvalidSpot {
+                spot: spot.to_f64().unwrap_or(0.0),
+            });
+        }
+
+        if volatility <= zero {
+            return Err(AnalyticalError::InvalidVolatility {
+                volatility: volatility.to_f64().unwrap_or(0.0),
+            });
+        }
+
+        Ok(Self {
+            spot,
+            rate,
+            dividend,
+            volatility,
+        })
+    }
+
+    /// Returns the spot price.
+    #[inline]
+    pub fn spot(&self) -> T {
+        self.spot
+    }
+
+    /// Computes the price of a single-barrier option.
+    ///
+    /// # Arguments
+    /// * `kind` - Barrier direction and knock type
+    /// * `is_call` - True for a call, false for a put
+    /// * `strike` - Strike price (K)
+    /// * `barrier` - Barrier level (H)
+    /// * `expiry` - Time to expiration in years (T)
+    ///
+    /// # Returns
+    /// The option price. Once the spot is through the barrier a knock-in
+    /// is worth the vanilla and a knock-out nothing.
+    pub fn price(&self, kind: BarrierKind, is_call: bool, strike: T, barrier: T, expiry: T) -> T {
+        let zero = T::zero();
+        let one = T::one();
+        let epsilon = T::from(1e-10).unwrap();
+
+        let phi = if is_call { one } else { -one };
+        let eta = if kind.is_down() { one } else { -one };
+        let breached = if kind.is_down() {
+            self.spot <= barrier
+        } else {
+            self.spot >= barrier
+        };
+
+        if expiry <= epsilon || strike <= zero || barrier <= zero {
+            let intrinsic = (phi * (self.spot - strike)).max(zero);
+            return if breached == kind.is_knock_in() {
+                intrinsic
+            } else {
+                zero
+            };
+        }
+
+        let t = self.terms(strike, barrier, expiry, phi, eta);
+        if breached {
+            return if kind.is_knock_in() { t.a } else { zero };
+        }
+
+        // Knock-in value by case; the knock-out follows from in-out parity
+        // with the vanilla, A
+        let knock_in = match (kind.is_down(), is_call, strike > barrier) {
+            (true, true, true) => t.c,
+            (true, true, false) => t.a - t.b + t.d,
+            (true, false, true) => t.b - t.c + t.d,
+            (true, false, false) => t.a,
+            (false, true, true) => t.a,
+            (false, true, false) => t.b - t.c + t.d,
+            (false, false, true) => t.a - t.b + t.d,
+            (false, false, false) => t.c,
+        };
+
+        let price = if kind.is_knock_in() {
+            knock_in
+        } else {
+            t.a - knock_in
+        };
+        price.max(zero)
+    }
+
+    /// Computes the A, B, C and D building blocks.
+    fn terms(&self, strike: T, barrier: T, expiry: T, phi: T, eta: T) -> Terms<T> {
+        let two = T::from(2.0).unwrap();
+        let (s, k, h) = (self.spot, strike, barrier);
+        let vol_sqrt_t = self.volatility * expiry.sqrt();
+        let vol_sq = self.volatility * self.volatility;
+
+        let lambda = (self.rate - self.dividend + vol_sq / two) / vol_sq;
+        let x1 = (s / k).ln() / vol_sqrt_t + lambda * vol_sqrt_t;
+        let x2 = (s / h).ln() / vol_sqrt_t + lambda * vol_sqrt_t;
+        let y1 = (h * h / (s * k)).ln() / vol_sqrt_t + lambda * vol_sqrt_t;
+        let y2 = (h / s).ln() / vol_sqrt_t + lambda * vol_sqrt_t;
+
+        let spot_df = s * (-self.dividend * expiry).exp();
+        let strike_df = k * (-self.rate * expiry).exp();
+        let h_s_2l = (h / s).powf(two * lambda);
+        let h_s_2l_m2 = (h / s).powf(two * lambda - two);
+
+        let lognormal = |x: T| {
+            phi * spot_df * norm_cdf(phi * x) - phi * strike_df * norm_cdf(phi * (x - vol_sqrt_t))
+        };
+        let reflected = |y: T| {
+            phi * spot_df * h_s_2l * norm_cdf(eta * y)
+                - phi * strike_df * h_s_2l_m2 * norm_cdf(eta * (y - vol_sqrt_t))
+        };
+
+        Terms {
+            a: lognormal(x1),
+            b: lognormal(x2),
+            c: reflected(y1),
+            d: reflected(y2),
+        }
+    }
+}
+
+/// Rubinstein-Reiner building blocks.
+struct Terms<T> {
+    a: T,
+    b: T,
+    c: T,
+    d: T,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytical::BlackScholes;
+    use approx::assert_relative_eq;
+
+    const KINDS: [(BarrierKind, BarrierKind); 2] = [
+        (BarrierKind::DownIn, BarrierKind::DownOut),
+        (BarrierKind::UpIn, BarrierKind::UpOut),
+    ];
+
+    #[test]
+    fn test_new_rejects_invalid_inputs() {
+        assert!(matches!(
+            BlackScholesBarrier::new(0.0_f64, 0.05, 0.0, 0.2),
+            Err(AnalyticalError::InvalidSpot { .. })
+        ));
+        assert!(matches!(
+            BlackScholesBarrier::new(100.0_f64, 0.05, 0.0, 0.0),
+            Err(AnalyticalError::InvalidVolatility { .. })
+        ));
+    }
+
+    #[test]
+    fn test_in_out_parity() {
+        let model = BlackScholesBarrier::new(100.0_f64, 0.05, 0.0, 0.25).unwrap();
+        let bs = BlackScholes::new(100.0_f64, 0.05, 0.25).unwrap();
+
+        for (barrier, (knock_in, knock_out)) in [(90.0, KINDS[0]), (115.0, KINDS[1])] {
+            for strike in [85.0, 100.0, 120.0] {
+                for is_call in [true, false] {
+                    let vanilla = if is_call {
+                        bs.price_call(strike, 1.0)
+                    } else {
+                        bs.price_put(strike, 1.0)
+                    };
+                    let total = model.price(knock_in, is_call, strike, barrier, 1.0)
+                        + model.price(knock_out, is_call, strike, barrier, 1.0);
+                    assert_relative_eq!(total, vanilla, epsilon = 1e-10);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_matches_reference_values() {
+        // Values from the Rubinstein-Reiner implementation used to verify
+        // Monte Carlo barrier pricing: S = 100, K = 100, r = 8%, q = 4%,
+        // σ = 25%, T = 0.5
+        let model = BlackScholesBarrier::new(100.0_f64, 0.08, 0.04, 0.25).unwrap();
+        let cases = [
+            (BarrierKind::DownOut, true, 95.0, 4.512_583_744_5),
+            (BarrierKind::UpOut, false, 105.0, 3.147_868_056_8),
+            (BarrierKind::DownIn, false, 95.0, 5.893_617_965_6),
+            (BarrierKind::UpIn, true, 105.0, 7.836_742_855_9),
+        ];
+        for (kind, is_call, barrier, expected) in cases {
+            assert_relative_eq!(
+                model.price(kind, is_call, 100.0, barrier, 0.5),
+                expected,
+                epsilon = 1e-5
+            );
+        }
+    }
+
+    #[test]
+    fn test_breached_barrier() {
+        let model = BlackScholesBarrier::new(100.0_f64, 0.05, 0.0, 0.2).unwrap();
+        let vanilla = BlackScholes::new(100.0_f64, 0.05, 0.2)
+            .unwrap()
+            .price_call(100.0, 1.0);
+
+        assert_eq!(
+            model.price(BarrierKind::DownOut, true, 100.0, 100.0, 1.0),
+            0.0
+        );
+        assert_relative_eq!(
+            model.price(BarrierKind::DownIn, true, 100.0, 100.0, 1.0),
+            vanilla,
+            epsilon = 1e-12
+        );
+    }
+}
@@ -0,0 +1,211 @@
+//! Black-76 (lognormal forward) pricing model for European options.
+//!
+//! This module provides the Black-76 model for options on forwards and
+//! futures, where the forward is lognormal under its own measure. Prices
+//! are undiscounted, matching [`Bachelier`](super::Bachelier); multiply by
+//! the discount factor to the payment date.
+//!
+//! ## Mathematical Formulas
+//!
+//! **Call Price**: C = F·N(d₁) - K·N(d₂)
+//! **Put Price**: P = K·N(-d₂) - F·N(-d₁)
+//!
+//! Where:
+//! - d₁ = (ln(F/K) + σ²T/2) / (σ√T)
+//! - d₂ = d₁ - σ√T
+
+use num_traits::Float;
+
+use super::distributions::norm_cdf;
+use super::error::AnalyticalError;
+
+/// Black-76 model for European options on forwards.
+///
+/// # Type Parameters
+/// * `T` - Floating-point type implementing `Float` (e.g., `f64`, `Dual64`)
+///
+/// # Examples
+/// ```
+/// use pricer_models::analytical::Black76;
+///
+/// let model = Black76::new(100.0_f64, 0.2).unwrap();
+/// let call_price = model.price_call(105.0, 1.0);
+/// let put_price = model.price_put(105.0, 1.0);
+///
+/// // Put-call parity: C - P = F - K
+/// let parity = call_price - put_price - (100.0 - 105.0);
+/// assert!(parity.abs() < 1e-10);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Black76<T: Float> {
+    /// Forward price (F) - must be positive
+    forward: T,
+    /// Lognormal volatility (σ) - must be positive
+    volatility: T,
+}
+
+impl<T: Float> Black76<T> {
+    /// Creates a new Black-76 model.
+    ///
+    /// # Arguments
+    /// * `forward` - Forward price (must be positive)
+    /// * `volatility` - Lognormal volatility (must be positive)
+    ///
+    /// # Errors
+    /// - `AnalyticalError::InvalidSpot` if forward <= 0
+    /// - `AnalyticalError::InvalidVolatility` if volatility <= 0
+    pub fn new(forward: T, volatility: T) -> Result<Self, AnalyticalError> {
+        let zero = T::zero();
+
+        if forward <= zero {
+            return Err(AnalyticalError::InvalidSpot {
+                spot: forward.to_f64().unwrap_or(0.0),
+            });
+        }
+
+        if volatility <= zero {
+            return Err(AnalyticalError::InvalidVolatility {
+                volatility: volatility.to_f64().unwrap_or(0.0),
+            });
+        }
+
+        Ok(Self {
+            forward,
+            volatility,
+        })
+    }
+
+    /// Returns the forward price.
+    #[inline]
+    pub fn forward(&self) -> T {
+        self.forward
+    }
+
+    /// Returns the volatility.
+    #[inline]
+    pub fn volatility(&self) -> T {
+        self.volatility
+    }
+
+    /// Computes the d1 term of the Black-76 formula.
+    ///
+    /// d₁ = (ln(F/K) + σ²T/2) / (σ√T)
+    #[inline]
+    fn d1(&self, strike: T, expiry: T) -> T {
+        let half = T::from(0.5).unwrap();
+        let vol_sqrt_t = self.volatility * expiry.sqrt();
+
+        ((self.forward / strike).ln() + half * self.volatility * self.volatility * expiry)
+            / vol_sqrt_t
+    }
+
+    /// Computes the undiscounted European call price.
+    ///
+    /// C = F·N(d₁) - K·N(d₂)
+    ///
+    /// # Arguments
+    /// * `strike` - Strike price (K)
+    /// * `expiry` - Time to expiration in years (T)
+    ///
+    /// # Returns
+    /// The undiscounted call price; intrinsic value at expiry.
+    #[inline]
+    pub fn price_call(&self, strike: T, expiry: T) -> T {
+        let zero = T::zero();
+        let epsilon = T::from(1e-10).unwrap();
+
+        if expiry <= epsilon || strike <= zero {
+            let intrinsic = self.forward - strike;
+            return if intrinsic > zero { intrinsic } else { zero };
+        }
+
+        let d1 = self.d1(strike, expiry);
+        let d2 = d1 - self.volatility * expiry.sqrt();
+
+        self.forward * norm_cdf(d1) - strike * norm_cdf(d2)
+    }
+
+    /// Computes the undiscounted European put price.
+    ///
+    /// P = K·N(-d₂) - F·N(-d₁)
+    ///
+    /// # Arguments
+    /// * `strike` - Strike price (K)
+    /// * `expiry` - Time to expiration in years (T)
+    ///
+    /// # Returns
+    /// The undiscounted put price; intrinsic value at expiry.
+    #[inline]
+    pub fn price_put(&self, strike: T, expiry: T) -> T {
+        let zero = T::zero();
+        let epsilon = T::from(1e-10).unwrap();
+
+        if expiry <= epsilon || strike <= zero {
+            let intrinsic = strike - self.forward;
+            return if intrinsic > zero { intrinsic } else { zero };
+        }
+
+        let d1 = self.d1(strike, expiry);
+        let d2 = d1 - self.volatility * expiry.sqrt();
+
+        strike * norm_cdf(-d2) - self.forward * norm_cdf(-d1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytical::BlackScholes;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_new_rejects_invalid_inputs() {
+        assert!(Black76::new(100.0_f64, 0.2).is_ok());
+        assert!(matches!(
+            Black76::new(0.0_f64, 0.2),
+            Err(AnalyticalError::InvalidSpot { .. })
+        ));
+        assert!(matches!(
+            Black76::new(100.0_f64, 0.0),
+            Err(AnalyticalError::InvalidVolatility { .. })
+        ));
+    }
+
+    #[test]
+    fn test_put_call_parity() {
+        let model = Black76::new(0.03_f64, 0.25).unwrap();
+        for strike in [0.01, 0.03, 0.05] {
+            let parity = model.price_call(strike, 2.0) - model.price_put(strike, 2.0);
+            assert_relative_eq!(parity, 0.03 - strike, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_matches_discounted_black_scholes() {
+        // Black-Scholes with forward F = S·e^(rT) equals discounted Black-76
+        let (spot, rate, vol, strike, expiry) = (100.0_f64, 0.05, 0.2, 110.0, 1.5);
+        let forward = spot * (rate * expiry).exp();
+        let df = (-rate * expiry).exp();
+
+        let black = Black76::new(forward, vol).unwrap();
+        let bs = BlackScholes::new(spot, rate, vol).unwrap();
+
+        assert_relative_eq!(
+            df * black.price_call(strike, expiry),
+            bs.price_call(strike, expiry),
+            epsilon = 1e-10
+        );
+        assert_relative_eq!(
+            df * black.price_put(strike, expiry),
+            bs.price_put(strike, expiry),
+            epsilon = 1e-10
+        );
+    }
+
+    #[test]
+    fn test_expiry_zero_returns_intrinsic() {
+        let model = Black76::new(100.0_f64, 0.2).unwrap();
+        assert_eq!(model.price_call(90.0, 0.0), 10.0);
+        assert_eq!(model.price_put(90.0, 0.0), 0.0);
+    }
+}
@@ -14,6 +14,7 @@ use thiserror::Error;
 /// # Variants
 /// - `InvalidVolatility`: Non-positive volatility
 /// - `InvalidSpot`: Non-positive spot price (for Black-Scholes)
+/// - `InvalidCorrelation`: Correlation outside [-1, 1]
 /// - `UnsupportedExerciseStyle`: Exercise style not supported by model
 /// - `NumericalInstability`: Computation encountered numerical issues
 ///
@@ -40,6 +41,13 @@ pub enum AnalyticalError {
         spot: f64,
     },
 
+    /// Invalid correlation (outside [-1, 1]).
+    #[error("Invalid correlation: ρ = {correlation}")]
+    InvalidCorrelation {
+        /// The invalid correlation value
+        correlation: f64,
+    },
+
     /// Unsupported exercise style.
     #[error("Unsupported exercise style: {style}")]
     UnsupportedExerciseStyle {
@@ -58,7 +66,9 @@ pub enum AnalyticalError {
 impl From<AnalyticalError> for PricingError {
     fn from(err: AnalyticalError) -> Self {
         match err {
-            AnalyticalError::InvalidVolatility { .. } | AnalyticalError::InvalidSpot { .. } => {
+            AnalyticalError::InvalidVolatility { .. }
+            | AnalyticalError::InvalidSpot { .. }
+            | AnalyticalError::InvalidCorrelation { .. } => {
                 PricingError::InvalidInput(err.to_string())
             }
             AnalyticalError::UnsupportedExerciseStyle { .. } => {
@@ -91,6 +101,16 @@ mod tests {
         assert_eq!(format!("{}", err), "Invalid spot price: S = -100");
     }
 
+    #[test]
+    fn test_invalid_correlation_display() {
+        let err = AnalyticalError::InvalidCorrelation { correlation: 1.5 };
+        assert_eq!(format!("{}", err), "Invalid correlation: ρ = 1.5");
+        assert!(matches!(
+            PricingError::from(err),
+            PricingError::InvalidInput(_)
+        ));
+    }
+
     #[test]
     fn test_unsupported_exercise_style_display() {
         let err = AnalyticalError::UnsupportedExerciseStyle {
@@ -2,7 +2,9 @@
 //!
 //! This module provides closed-form solutions for option pricing:
 //! - Black-Scholes model for lognormal dynamics
+//! - Black-76 model for options on forwards
 //! - Bachelier model for normal dynamics
+//! - Rubinstein-Reiner closed forms for single-barrier options
 //! - Garman-Kohlhagen model for FX options
 //! - Analytical Greeks (Delta, Gamma, Vega, Theta, Rho)
 //!
@@ -16,6 +18,8 @@ pub mod distributions;
 pub mod error;
 
 mod bachelier;
+mod barrier;
+mod black76;
 mod black_scholes;
 
 #[cfg(feature = "fx")]
@@ -23,6 +27,8 @@ pub mod garman_kohlhagen;
 
 // Re-export main types at module level
 pub use bachelier::Bachelier;
+pub use barrier::{BarrierKind, BlackScholesBarrier};
+pub use black76::Black76;
 pub use black_scholes::BlackScholes;
 pub use distributions::{norm_cdf, norm_pdf};
 pub use error::AnalyticalError;
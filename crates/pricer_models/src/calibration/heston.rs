@@ -25,9 +25,12 @@
 //! where C(u) and D(u) are complex-valued functions of the model
 //! parameters.
 
+use pricer_core::math::implied_vol::black_scholes_implied_vol;
+use pricer_core::math::quadrature::GaussLegendre;
 use pricer_core::traits::calibration::{
     CalibrationConfig, CalibrationResult, Calibrator, Constraint, ParameterBounds,
 };
+use pricer_core::types::ImpliedVolError;
 use std::f64::consts::PI;
 
 use super::{ModelCalibrator, ModelCalibratorConfig};
@@ -341,35 +344,28 @@ impl HestonCalibrator {
         let forward = spot * ((rate - dividend) * expiry).exp();
         let discount = (-rate * expiry).exp();
 
-        // Integration via trapezoidal rule
-        let n = self.integration_points;
+        // Integration via Gauss-Legendre quadrature
+        let rule = GaussLegendre::new(self.integration_points);
         let u_max = 100.0; // Upper limit for integration
-        let du = u_max / n as f64;
 
-        let mut p1 = 0.0;
-        let mut p2 = 0.0;
-
-        for i in 1..n {
-            let u = i as f64 * du;
-
-            // P1: probability with stock price as numeraire (u - i)
+        // P1: probability with stock price as numeraire (u - i)
+        let integrand1 = |u: f64| {
             let u1 = Complex64::new(u, -1.0);
             let phi1 = self.characteristic_function(u1, expiry, v0, theta, kappa, xi, rho);
             let exp_term1 = Complex64::new(0.0, -u * strike.ln()).exp();
-            let integrand1 = (phi1 * exp_term1).re / u;
+            (phi1 * exp_term1).re / u
+        };
 
-            // P2: probability with money market as numeraire (u + 0i)
+        // P2: probability with money market as numeraire (u + 0i)
+        let integrand2 = |u: f64| {
             let u2 = Complex64::new(u, 0.0);
             let phi2 = self.characteristic_function(u2, expiry, v0, theta, kappa, xi, rho);
             let exp_term2 = Complex64::new(0.0, -u * strike.ln()).exp();
-            let integrand2 = (phi2 * exp_term2).re / u;
-
-            p1 += integrand1;
-            p2 += integrand2;
-        }
+            (phi2 * exp_term2).re / u
+        };
 
-        p1 = 0.5 + p1 * du / PI;
-        p2 = 0.5 + p2 * du / PI;
+        let mut p1 = 0.5 + rule.integrate(integrand1, 0.0, u_max) / PI;
+        let mut p2 = 0.5 + rule.integrate(integrand2, 0.0, u_max) / PI;
 
         // Clamp probabilities to [0, 1]
         p1 = p1.clamp(0.0, 1.0);
@@ -437,7 +433,8 @@ impl HestonCalibrator {
         (c + d_fn * v0).exp()
     }
 
-    /// Compute implied volatility from price using Newton-Raphson.
+    /// Black-Scholes implied volatility of a model price, clamped to
+    /// `[0.001, 5]` so that unattainable prices still give a finite residual.
     fn implied_vol(
         spot: f64,
         strike: f64,
@@ -446,32 +443,11 @@ impl HestonCalibrator {
         price: f64,
         is_call: bool,
     ) -> f64 {
-        let _forward = spot * (rate * expiry).exp();
-        let _discount = (-rate * expiry).exp();
-
-        // Initial guess from Brenner-Subrahmanyam approximation
-        let mut sigma = (2.0 * PI / expiry).sqrt() * price / spot;
-        sigma = sigma.clamp(0.01, 3.0);
-
-        // Newton-Raphson iteration
-        for _ in 0..50 {
-            let (bs_price, vega) =
-                black_scholes_with_vega(spot, strike, expiry, rate, sigma, is_call);
-
-            if vega.abs() < 1e-15 {
-                break;
-            }
-
-            let error = bs_price - price;
-            if error.abs() < 1e-10 {
-                break;
-            }
-
-            sigma -= error / vega;
-            sigma = sigma.clamp(0.001, 5.0);
+        match black_scholes_implied_vol(price, spot, strike, expiry, rate, is_call) {
+            Ok(sigma) => sigma.clamp(0.001, 5.0),
+            Err(ImpliedVolError::PriceAboveMaximum { .. }) => 5.0,
+            Err(_) => 0.001,
         }
-
-        sigma
     }
 }
 
@@ -808,34 +784,28 @@ fn heston_price_numerical(
     let discount = (-rate * expiry).exp();
     let log_strike = strike.ln();
 
-    // Integration via trapezoidal rule
+    // Integration via Gauss-Legendre quadrature
+    let rule = GaussLegendre::new(n_points);
     let u_max = 100.0;
-    let du = u_max / n_points as f64;
-
-    let mut p1 = 0.0;
-    let mut p2 = 0.0;
 
-    for i in 1..n_points {
-        let u = i as f64 * du;
-
-        // P1 integral (stock price numeraire)
+    // P1 integral (stock price numeraire)
+    let integrand1 = |u: f64| {
         let u_shifted = Complex64::new(u, -1.0);
         let phi1 = heston_cf(u_shifted, expiry, v0, theta, kappa, xi, rho, forward);
         let exp_term1 = Complex64::new(0.0, -u * log_strike).exp();
-        let integrand1 = (phi1 * exp_term1).re / u;
+        (phi1 * exp_term1).re / u
+    };
 
-        // P2 integral (money market numeraire)
+    // P2 integral (money market numeraire)
+    let integrand2 = |u: f64| {
         let u_real = Complex64::new(u, 0.0);
         let phi2 = heston_cf(u_real, expiry, v0, theta, kappa, xi, rho, forward);
         let exp_term2 = Complex64::new(0.0, -u * log_strike).exp();
-        let integrand2 = (phi2 * exp_term2).re / u;
-
-        p1 += integrand1;
-        p2 += integrand2;
-    }
+        (phi2 * exp_term2).re / u
+    };
 
-    p1 = 0.5 + p1 * du / PI;
-    p2 = 0.5 + p2 * du / PI;
+    let mut p1 = 0.5 + rule.integrate(integrand1, 0.0, u_max) / PI;
+    let mut p2 = 0.5 + rule.integrate(integrand2, 0.0, u_max) / PI;
 
     // Clamp to valid range
     p1 = p1.clamp(0.0, 1.0);
@@ -898,59 +868,6 @@ fn heston_cf(
     (log_fwd_term + c + d_fn * Complex64::new(v0, 0.0)).exp()
 }
 
-/// Black-Scholes price and vega for implied vol calculation.
-fn black_scholes_with_vega(
-    spot: f64,
-    strike: f64,
-    expiry: f64,
-    rate: f64,
-    sigma: f64,
-    is_call: bool,
-) -> (f64, f64) {
-    let sqrt_t = expiry.sqrt();
-    let d1 = ((spot / strike).ln() + (rate + 0.5 * sigma * sigma) * expiry) / (sigma * sqrt_t);
-    let d2 = d1 - sigma * sqrt_t;
-
-    let discount = (-rate * expiry).exp();
-
-    // Normal CDF approximation
-    let n_d1 = norm_cdf(d1);
-    let n_d2 = norm_cdf(d2);
-    let n_neg_d1 = norm_cdf(-d1);
-    let n_neg_d2 = norm_cdf(-d2);
-
-    let price = if is_call {
-        spot * n_d1 - strike * discount * n_d2
-    } else {
-        strike * discount * n_neg_d2 - spot * n_neg_d1
-    };
-
-    // Vega (same for calls and puts)
-    let pdf_d1 = (-0.5 * d1 * d1).exp() / (2.0 * PI).sqrt();
-    let vega = spot * sqrt_t * pdf_d1;
-
-    (price, vega)
-}
-
-/// Standard normal CDF approximation (Abramowitz and Stegun).
-fn norm_cdf(x: f64) -> f64 {
-    let a1 = 0.254829592;
-    let a2 = -0.284496736;
-    let a3 = 1.421413741;
-    let a4 = -1.453152027;
-    let a5 = 1.061405429;
-    let p = 0.3275911;
-
-    let sign = if x < 0.0 { -1.0 } else { 1.0 };
-    let x_abs = x.abs();
-
-    let t = 1.0 / (1.0 + p * x_abs);
-    let y =
-        1.0 - (((((a5 * t + a4) * t) + a3) * t + a2) * t + a1) * t * (-x_abs * x_abs / 2.0).exp();
-
-    0.5 * (1.0 + sign * y)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1056,23 +973,6 @@ mod tests {
         assert!(exp_z.im.abs() < 1e-10);
     }
 
-    #[test]
-    fn test_norm_cdf() {
-        assert!((norm_cdf(0.0) - 0.5).abs() < 1e-6);
-        assert!(norm_cdf(-5.0) < 0.001);
-        assert!(norm_cdf(5.0) > 0.999);
-    }
-
-    #[test]
-    fn test_black_scholes_with_vega() {
-        let (price, vega) = black_scholes_with_vega(100.0, 100.0, 1.0, 0.05, 0.2, true);
-
-        // ATM call should have reasonable price
-        assert!(price > 5.0 && price < 15.0);
-        // Vega should be positive
-        assert!(vega > 0.0);
-    }
-
     #[test]
     fn test_heston_price_option_smoke() {
         let calibrator = HestonCalibrator::new();
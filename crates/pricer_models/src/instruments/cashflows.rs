@@ -0,0 +1,329 @@
+//! Trade-level cashflow decomposition.
+//!
+//! [`Instrument::cashflows`] breaks an instrument into its expected future
+//! cashflows on a [`MarketSnapshot`], each dated, currency-tagged and
+//! discounted:
+//!
+//! - Swaps: fixed coupons paid and floating coupons received on each
+//!   payment date, floating coupons at the simple forward rate of the
+//!   discount curve over the remaining accrual period
+//! - Forwards: the cash settlement at expiry, on the forward of the spot
+//! - Options: the expected payoff at expiry under the forward measure
+//!   (Black on the forward of the spot at the snapshot volatility; the
+//!   intrinsic value on the forward at zero volatility)
+//!
+//! Amounts are signed from the holder's side: positive received, negative
+//! paid. A swap is the payer side. Forward prices carry the spot at the
+//! curve rate without dividends, so the present values of an instrument's
+//! cashflows sum to its value on the snapshot.
+
+use num_traits::Float;
+use pricer_core::market_data::MarketSnapshot;
+use pricer_core::types::time::Date;
+use pricer_core::types::Currency;
+
+use super::{Direction, Instrument, InstrumentError, PayoffType, Swap, VanillaOption};
+use crate::analytical::distributions::norm_cdf;
+
+/// Origin of an instrument cashflow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CashflowType {
+    /// Fixed leg coupon of a swap
+    FixedCoupon,
+    /// Floating leg coupon of a swap
+    FloatingCoupon,
+    /// Cash settlement of a forward at expiry
+    ForwardSettlement,
+    /// Expected payoff of an option at expiry
+    OptionPayoff,
+}
+
+impl CashflowType {
+    /// Snake-case name used in reports.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CashflowType::FixedCoupon => "fixed_coupon",
+            CashflowType::FloatingCoupon => "floating_coupon",
+            CashflowType::ForwardSettlement => "forward_settlement",
+            CashflowType::OptionPayoff => "option_payoff",
+        }
+    }
+}
+
+/// An expected cashflow with its discount factor and present value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValuedCashflow<T: Float> {
+    /// Origin of the cashflow
+    pub kind: CashflowType,
+    /// Payment time in years from the snapshot date
+    pub payment_time: T,
+    /// Payment date
+    pub payment_date: Date,
+    /// Payment currency
+    pub currency: Currency,
+    /// Expected amount: positive received, negative paid
+    pub amount: T,
+    /// Discount factor to the payment date
+    pub discount_factor: T,
+    /// Discounted amount
+    pub present_value: T,
+}
+
+impl<T: Float> Instrument<T> {
+    /// Expected future cashflows on `market`, ordered by payment time.
+    ///
+    /// Cashflows paid on or before the snapshot date are left out. Options
+    /// and forwards are tagged with the snapshot currency, swaps with their
+    /// own.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InstrumentError::PayoffError` if an option or forward is
+    /// valued on a snapshot without a spot, or the curve cannot discount to
+    /// a payment time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pricer_core::market_data::curves::CurveEnum;
+    /// use pricer_core::market_data::MarketSnapshot;
+    /// use pricer_core::types::time::Date;
+    /// use pricer_core::types::Currency;
+    /// use pricer_models::instruments::{Direction, Forward, Instrument};
+    ///
+    /// let market = MarketSnapshot::new(
+    ///     Date::from_ymd(2026, 1, 15).unwrap(),
+    ///     Currency::USD,
+    ///     CurveEnum::flat(0.03_f64),
+    /// )
+    /// .with_spot(100.0);
+    /// let forward = Instrument::Forward(Forward::new(100.0, 1.0, 10.0, Direction::Long).unwrap());
+    ///
+    /// let cashflows = forward.cashflows(&market).unwrap();
+    /// assert_eq!(cashflows.len(), 1);
+    /// // The settlement on the forward discounts back to S - K·DF
+    /// let df = (-0.03_f64).exp();
+    /// assert!((cashflows[0].present_value - 10.0 * (100.0 - 100.0 * df)).abs() < 1e-9);
+    /// ```
+    pub fn cashflows(
+        &self,
+        market: &MarketSnapshot<T>,
+    ) -> Result<Vec<ValuedCashflow<T>>, InstrumentError> {
+        let mut flows = Vec::new();
+        let mut push = |kind: CashflowType, time: T, currency: Currency, amount: T| {
+            if time <= T::zero() {
+                return Ok(());
+            }
+            let discount_factor = discount_factor(market, time)?;
+            flows.push(ValuedCashflow {
+                kind,
+                payment_time: time,
+                payment_date: market.date_after(time),
+                currency,
+                amount,
+                discount_factor,
+                present_value: amount * discount_factor,
+            });
+            Ok(())
+        };
+        match self {
+            Instrument::Swap(swap) => {
+                for (start, end) in accrual_periods(swap) {
+                    if end <= T::zero() {
+                        continue;
+                    }
+                    let year_fraction = end - start;
+                    let fixed = -swap.fixed_leg_cashflow(year_fraction);
+                    push(CashflowType::FixedCoupon, end, swap.currency(), fixed)?;
+
+                    // Simple forward over the part of the period still to run
+                    let from = start.max(T::zero());
+                    let rate = (discount_factor(market, from)? / discount_factor(market, end)?
+                        - T::one())
+                        / (end - from);
+                    let floating = swap.notional() * rate * year_fraction;
+                    push(CashflowType::FloatingCoupon, end, swap.currency(), floating)?;
+                }
+            }
+            Instrument::Forward(forward) => {
+                let expiry = forward.expiry();
+                if expiry > T::zero() {
+                    let price = forward_price(market, expiry)?;
+                    let long = forward.notional() * (price - forward.strike());
+                    let amount = match forward.direction() {
+                        Direction::Long => long,
+                        Direction::Short => -long,
+                    };
+                    push(
+                        CashflowType::ForwardSettlement,
+                        expiry,
+                        market.currency(),
+                        amount,
+                    )?;
+                }
+            }
+            Instrument::Vanilla(option) => {
+                let expiry = option.expiry();
+                if expiry > T::zero() {
+                    let price = forward_price(market, expiry)?;
+                    let stdev = market.volatility() * expiry.sqrt();
+                    let amount = option.notional() * expected_payoff(option, price, stdev);
+                    push(
+                        CashflowType::OptionPayoff,
+                        expiry,
+                        market.currency(),
+                        amount,
+                    )?;
+                }
+            }
+        }
+        Ok(flows)
+    }
+}
+
+/// Accrual start and end times of each swap period.
+///
+/// The first period accrues one period of the payment frequency; later
+/// periods run between consecutive payment dates.
+fn accrual_periods<T: Float>(swap: &Swap<T>) -> Vec<(T, T)> {
+    let mut start = swap.payment_dates()[0] - swap.frequency().period_fraction::<T>();
+    swap.payment_dates()
+        .iter()
+        .map(|&end| {
+            let period = (start, end);
+            start = end;
+            period
+        })
+        .collect()
+}
+
+fn discount_factor<T: Float>(market: &MarketSnapshot<T>, time: T) -> Result<T, InstrumentError> {
+    market
+        .discount_factor(time)
+        .map_err(|e| InstrumentError::PayoffError {
+            message: e.to_string(),
+        })
+}
+
+/// Forward of the spot to `time`, carried at the curve rate.
+fn forward_price<T: Float>(market: &MarketSnapshot<T>, time: T) -> Result<T, InstrumentError> {
+    let spot = market.spot().map_err(|e| InstrumentError::PayoffError {
+        message: e.to_string(),
+    })?;
+    Ok(spot / discount_factor(market, time)?)
+}
+
+/// Expected unit payoff under the forward measure with total volatility
+/// `stdev`; the unsmoothed intrinsic value when `stdev` is zero.
+fn expected_payoff<T: Float>(option: &VanillaOption<T>, forward: T, stdev: T) -> T {
+    let strike = option.strike();
+    let zero = T::zero();
+    let one = T::one();
+    if stdev <= zero || strike <= zero {
+        let indicator = |b: bool| if b { one } else { zero };
+        return match option.payoff_type() {
+            PayoffType::Call => (forward - strike).max(zero),
+            PayoffType::Put => (strike - forward).max(zero),
+            PayoffType::DigitalCall => indicator(forward > strike),
+            PayoffType::DigitalPut => indicator(forward < strike),
+        };
+    }
+    let half = T::from(0.5).unwrap_or_else(T::zero);
+    let d1 = ((forward / strike).ln() + half * stdev * stdev) / stdev;
+    let d2 = d1 - stdev;
+    match option.payoff_type() {
+        PayoffType::Call => forward * norm_cdf(d1) - strike * norm_cdf(d2),
+        PayoffType::Put => strike * norm_cdf(-d2) - forward * norm_cdf(-d1),
+        PayoffType::DigitalCall => norm_cdf(d2),
+        PayoffType::DigitalPut => norm_cdf(-d2),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytical::BlackScholes;
+    use crate::instruments::{ExerciseStyle, Forward, InstrumentParams, PaymentFrequency};
+    use approx::assert_relative_eq;
+    use pricer_core::market_data::curves::CurveEnum;
+
+    fn market() -> MarketSnapshot<f64> {
+        MarketSnapshot::new(
+            Date::from_ymd(2026, 1, 15).unwrap(),
+            Currency::EUR,
+            CurveEnum::flat(0.03),
+        )
+        .with_spot(100.0)
+        .with_volatility(0.2)
+    }
+
+    #[test]
+    fn test_swap_cashflows_price_floating_at_par() {
+        let swap = Swap::new(
+            1_000_000.0,
+            0.03,
+            vec![0.5, 1.0, 1.5, 2.0],
+            PaymentFrequency::SemiAnnual,
+            Currency::USD,
+        )
+        .unwrap();
+        let cashflows = Instrument::Swap(swap).cashflows(&market()).unwrap();
+
+        assert_eq!(cashflows.len(), 8);
+        assert_eq!(cashflows[0].kind, CashflowType::FixedCoupon);
+        assert_relative_eq!(cashflows[0].amount, -15_000.0);
+        assert_eq!(cashflows[0].currency, Currency::USD);
+        assert_eq!(
+            cashflows[7].payment_date,
+            Date::from_ymd(2028, 1, 15).unwrap()
+        );
+
+        // Floating coupons plus the final notional discount to par
+        let floating: f64 = cashflows
+            .iter()
+            .filter(|cf| cf.kind == CashflowType::FloatingCoupon)
+            .map(|cf| cf.present_value)
+            .sum();
+        let df_end = cashflows[7].discount_factor;
+        assert_relative_eq!(floating, 1_000_000.0 * (1.0 - df_end), epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_option_cashflow_discounts_to_black_scholes() {
+        let params = InstrumentParams::new(105.0, 1.0, 50.0).unwrap();
+        let call = VanillaOption::new(params, PayoffType::Call, ExerciseStyle::European, 1e-6);
+        let cashflows = Instrument::Vanilla(call).cashflows(&market()).unwrap();
+
+        assert_eq!(cashflows.len(), 1);
+        let bs = BlackScholes::new(100.0, 0.03, 0.2).unwrap();
+        assert_relative_eq!(
+            cashflows[0].present_value,
+            50.0 * bs.price_call(105.0, 1.0),
+            epsilon = 1e-8
+        );
+        assert_eq!(cashflows[0].currency, Currency::EUR);
+    }
+
+    #[test]
+    fn test_forward_needs_spot() {
+        let no_spot = MarketSnapshot::new(
+            Date::from_ymd(2026, 1, 15).unwrap(),
+            Currency::EUR,
+            CurveEnum::flat(0.03),
+        );
+        let forward = Instrument::Forward(Forward::new(100.0, 1.0, 1.0, Direction::Short).unwrap());
+        assert!(matches!(
+            forward.cashflows(&no_spot),
+            Err(InstrumentError::PayoffError { .. })
+        ));
+
+        // Short forward at the money of the carried spot settles nothing
+        let at_forward = 100.0 / (-0.03_f64).exp();
+        let forward =
+            Instrument::Forward(Forward::new(at_forward, 1.0, 1.0, Direction::Short).unwrap());
+        let cashflows = forward.cashflows(&market()).unwrap();
+        assert_relative_eq!(cashflows[0].amount, 0.0, epsilon = 1e-9);
+    }
+}
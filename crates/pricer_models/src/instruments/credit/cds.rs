@@ -55,6 +55,7 @@ use crate::schedules::Schedule;
 /// - **BuyProtection**: Buy protection (pay premium, receive payment on default)
 /// - **SellProtection**: Sell protection (receive premium, pay on default)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CdsDirection {
     /// Buy protection: pay premium, receive payment on default.
     BuyProtection,
@@ -148,6 +149,7 @@ impl fmt::Display for CdsDirection {
 /// assert_eq!(cds.currency(), Currency::USD);
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CreditDefaultSwap<T: Float> {
     /// Reference entity name (issuer/obligor).
     reference_entity: String,
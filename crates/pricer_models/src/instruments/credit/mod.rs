@@ -108,6 +108,7 @@ use crate::instruments::traits::InstrumentTrait;
 /// assert!(instrument.is_cds());
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CreditInstrument<T: Float> {
     /// Credit Default Swap.
     Cds(CreditDefaultSwap<T>),
@@ -295,4 +296,19 @@ mod tests {
 
         assert!(debug_str.contains("Cds"));
     }
+
+    #[cfg(feature = "serde")]
+    mod serde_tests {
+        use super::*;
+
+        #[test]
+        fn test_credit_instrument_roundtrip() {
+            let instrument = CreditInstrument::Cds(create_test_cds());
+            let json = serde_json::to_string(&instrument).unwrap();
+            assert!(json.starts_with(r#"{"Cds":{"#));
+
+            let parsed: CreditInstrument<f64> = serde_json::from_str(&json).unwrap();
+            assert_eq!(format!("{:?}", parsed), format!("{:?}", instrument));
+        }
+    }
 }
@@ -70,6 +70,7 @@ use super::cds::{CdsDirection, CreditDefaultSwap};
 
 /// CDS pricing result containing leg values.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CdsPriceResult<T: Float> {
     /// Present value of the protection leg.
     pub protection_leg_pv: T,
@@ -203,6 +203,7 @@ impl<'a, T: Float, C: CreditCurve<T>> DefaultTimeSimulator<'a, T, C> {
 
 /// Indicator for whether default has occurred.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DefaultStatus {
     /// Entity has not defaulted.
     Survived,
@@ -212,6 +213,7 @@ pub enum DefaultStatus {
 
 /// Result of a credit simulation for a single path.
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CreditPathResult<T: Float> {
     /// Default time (None if no default within horizon).
     pub default_time: Option<T>,
@@ -0,0 +1,434 @@
+//! Listed equity index and dividend futures.
+//!
+//! Futures are margined daily: each settlement price change is paid as
+//! variation margin, so a position is worth only the move since the last
+//! settlement and carries no credit exposure to the clearing house.
+//!
+//! - [`EquityIndexFuture`]: Future on an equity index level
+//! - [`DividendFuture`]: Future on the index dividends paid over a period
+//!
+//! # Example
+//!
+//! ```
+//! use pricer_models::instruments::equity::EquityIndexFuture;
+//! use pricer_models::instruments::Direction;
+//! use pricer_core::types::Currency;
+//!
+//! // 10 long contracts at 4500 with a $50 point value
+//! let future =
+//!     EquityIndexFuture::new(4500.0_f64, 0.25, 10.0, 50.0, Direction::Long, Currency::USD)
+//!         .unwrap();
+//!
+//! // Daily variation margin as the settlement price moves
+//! let flows = future.margin_flows(&[4510.0, 4490.0]);
+//! assert_eq!(flows, vec![5_000.0, -10_000.0]);
+//!
+//! // After settling at 4490, the position is worth only the next move
+//! assert_eq!(future.value(4490.0, 4490.0), 0.0);
+//! ```
+
+use num_traits::Float;
+use pricer_core::types::Currency;
+
+use crate::instruments::error::InstrumentError;
+use crate::instruments::Direction;
+
+/// Listed equity index future.
+///
+/// Pays `contracts × multiplier × (F_t − F_{t−1})` at each daily settlement,
+/// which sums to `contracts × multiplier × (S_T − trade_price)` for a long
+/// position held to final settlement against the index level `S_T`.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EquityIndexFuture<T: Float> {
+    trade_price: T,
+    expiry: T,
+    contracts: T,
+    multiplier: T,
+    direction: Direction,
+    currency: Currency,
+}
+
+impl<T: Float> EquityIndexFuture<T> {
+    /// Creates a new equity index future position.
+    ///
+    /// # Arguments
+    /// * `trade_price` - Price the position was opened at (must be positive)
+    /// * `expiry` - Time to final settlement in years (must be positive)
+    /// * `contracts` - Number of contracts (must be positive)
+    /// * `multiplier` - Currency amount per index point (must be positive)
+    /// * `direction` - Long or Short
+    /// * `currency` - Settlement currency
+    pub fn new(
+        trade_price: T,
+        expiry: T,
+        contracts: T,
+        multiplier: T,
+        direction: Direction,
+        currency: Currency,
+    ) -> Result<Self, InstrumentError> {
+        if trade_price <= T::zero() {
+            return Err(InstrumentError::InvalidStrike {
+                strike: trade_price.to_f64().unwrap_or(f64::NAN),
+            });
+        }
+        validate_terms(expiry, contracts, multiplier)?;
+
+        Ok(Self {
+            trade_price,
+            expiry,
+            contracts,
+            multiplier,
+            direction,
+            currency,
+        })
+    }
+
+    /// Returns the price the position was opened at.
+    #[inline]
+    pub fn trade_price(&self) -> T {
+        self.trade_price
+    }
+
+    /// Returns the time to final settlement in years.
+    #[inline]
+    pub fn expiry(&self) -> T {
+        self.expiry
+    }
+
+    /// Returns the number of contracts.
+    #[inline]
+    pub fn contracts(&self) -> T {
+        self.contracts
+    }
+
+    /// Returns the currency amount per index point.
+    #[inline]
+    pub fn multiplier(&self) -> T {
+        self.multiplier
+    }
+
+    /// Returns the position direction.
+    #[inline]
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// Returns the settlement currency.
+    #[inline]
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// Fair futures price at time `t` with deterministic carry,
+    /// `S × exp((r − q)(T − t))`.
+    pub fn fair_price(&self, spot: T, rate: T, dividend_yield: T, t: T) -> T {
+        let remaining = (self.expiry - t).max(T::zero());
+        spot * ((rate - dividend_yield) * remaining).exp()
+    }
+
+    /// Variation margin received when the settlement price moves from
+    /// `previous` to `settlement` (negative when paid).
+    #[inline]
+    pub fn variation_margin(&self, previous: T, settlement: T) -> T {
+        signed_point_value(self.direction, self.contracts, self.multiplier)
+            * (settlement - previous)
+    }
+
+    /// Value of the position at `price` when last settled at
+    /// `last_settlement`: only the unsettled variation margin.
+    #[inline]
+    pub fn value(&self, price: T, last_settlement: T) -> T {
+        self.variation_margin(last_settlement, price)
+    }
+
+    /// Variation margin at each of successive settlement prices, starting
+    /// from the trade price.
+    pub fn margin_flows(&self, settlement_prices: &[T]) -> Vec<T> {
+        margin_flows(
+            signed_point_value(self.direction, self.contracts, self.multiplier),
+            self.trade_price,
+            settlement_prices,
+        )
+    }
+
+    /// Total margin received to final settlement at the index level `spot`.
+    #[inline]
+    pub fn payoff(&self, spot: T) -> T {
+        self.variation_margin(self.trade_price, spot)
+    }
+}
+
+/// Dividend future.
+///
+/// Settles on the index dividends, in index points, with ex-dates in the
+/// accrual period `(accrual_start, expiry]`; margined daily like an index
+/// future.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DividendFuture<T: Float> {
+    trade_price: T,
+    accrual_start: T,
+    expiry: T,
+    contracts: T,
+    multiplier: T,
+    direction: Direction,
+    currency: Currency,
+}
+
+impl<T: Float> DividendFuture<T> {
+    /// Creates a new dividend future position.
+    ///
+    /// # Arguments
+    /// * `trade_price` - Price the position was opened at (must be non-negative)
+    /// * `accrual_start` - Start of the dividend period in years
+    /// * `expiry` - End of the dividend period and final settlement in years
+    ///   (must be positive and after `accrual_start`)
+    /// * `contracts` - Number of contracts (must be positive)
+    /// * `multiplier` - Currency amount per dividend point (must be positive)
+    /// * `direction` - Long or Short
+    /// * `currency` - Settlement currency
+    pub fn new(
+        trade_price: T,
+        accrual_start: T,
+        expiry: T,
+        contracts: T,
+        multiplier: T,
+        direction: Direction,
+        currency: Currency,
+    ) -> Result<Self, InstrumentError> {
+        if trade_price < T::zero() {
+            return Err(InstrumentError::InvalidStrike {
+                strike: trade_price.to_f64().unwrap_or(f64::NAN),
+            });
+        }
+        validate_terms(expiry, contracts, multiplier)?;
+        if accrual_start >= expiry {
+            return Err(InstrumentError::InvalidParameter {
+                message: "dividend accrual period must end after it starts".to_string(),
+            });
+        }
+
+        Ok(Self {
+            trade_price,
+            accrual_start,
+            expiry,
+            contracts,
+            multiplier,
+            direction,
+            currency,
+        })
+    }
+
+    /// Returns the price the position was opened at.
+    #[inline]
+    pub fn trade_price(&self) -> T {
+        self.trade_price
+    }
+
+    /// Returns the start of the dividend period in years.
+    #[inline]
+    pub fn accrual_start(&self) -> T {
+        self.accrual_start
+    }
+
+    /// Returns the end of the dividend period and final settlement in years.
+    #[inline]
+    pub fn expiry(&self) -> T {
+        self.expiry
+    }
+
+    /// Returns the number of contracts.
+    #[inline]
+    pub fn contracts(&self) -> T {
+        self.contracts
+    }
+
+    /// Returns the currency amount per dividend point.
+    #[inline]
+    pub fn multiplier(&self) -> T {
+        self.multiplier
+    }
+
+    /// Returns the position direction.
+    #[inline]
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// Returns the settlement currency.
+    #[inline]
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// Fair price from projected `(ex_date, amount)` dividends in index
+    /// points: the sum of those going ex in the accrual period.
+    pub fn fair_price(&self, dividends: &[(T, T)]) -> T {
+        dividends
+            .iter()
+            .filter(|(t, _)| *t > self.accrual_start && *t <= self.expiry)
+            .fold(T::zero(), |sum, &(_, amount)| sum + amount)
+    }
+
+    /// Fair price from a continuous dividend yield with deterministic carry,
+    /// `∫ q S exp((r − q)u) du` over the accrual period, measured from today.
+    pub fn fair_price_from_yield(&self, spot: T, rate: T, dividend_yield: T) -> T {
+        let start = self.accrual_start.max(T::zero());
+        let end = self.expiry;
+        let carry = rate - dividend_yield;
+        let growth = if carry.abs() < T::from(1e-12).unwrap_or_else(T::zero) {
+            end - start
+        } else {
+            ((carry * end).exp() - (carry * start).exp()) / carry
+        };
+        dividend_yield * spot * growth
+    }
+
+    /// Variation margin received when the settlement price moves from
+    /// `previous` to `settlement` (negative when paid).
+    #[inline]
+    pub fn variation_margin(&self, previous: T, settlement: T) -> T {
+        signed_point_value(self.direction, self.contracts, self.multiplier)
+            * (settlement - previous)
+    }
+
+    /// Value of the position at `price` when last settled at
+    /// `last_settlement`: only the unsettled variation margin.
+    #[inline]
+    pub fn value(&self, price: T, last_settlement: T) -> T {
+        self.variation_margin(last_settlement, price)
+    }
+
+    /// Variation margin at each of successive settlement prices, starting
+    /// from the trade price.
+    pub fn margin_flows(&self, settlement_prices: &[T]) -> Vec<T> {
+        margin_flows(
+            signed_point_value(self.direction, self.contracts, self.multiplier),
+            self.trade_price,
+            settlement_prices,
+        )
+    }
+
+    /// Total margin received to final settlement on `realised` dividend points.
+    #[inline]
+    pub fn payoff(&self, realised: T) -> T {
+        self.variation_margin(self.trade_price, realised)
+    }
+}
+
+fn validate_terms<T: Float>(expiry: T, contracts: T, multiplier: T) -> Result<(), InstrumentError> {
+    if expiry <= T::zero() {
+        return Err(InstrumentError::InvalidExpiry {
+            expiry: expiry.to_f64().unwrap_or(f64::NAN),
+        });
+    }
+    if contracts <= T::zero() {
+        return Err(InstrumentError::InvalidNotional {
+            notional: contracts.to_f64().unwrap_or(f64::NAN),
+        });
+    }
+    if multiplier <= T::zero() {
+        return Err(InstrumentError::InvalidParameter {
+            message: format!(
+                "contract multiplier must be positive, got {}",
+                multiplier.to_f64().unwrap_or(f64::NAN)
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// Currency amount per point, negative for short positions.
+fn signed_point_value<T: Float>(direction: Direction, contracts: T, multiplier: T) -> T {
+    let point_value = contracts * multiplier;
+    if direction.is_long() {
+        point_value
+    } else {
+        -point_value
+    }
+}
+
+fn margin_flows<T: Float>(point_value: T, trade_price: T, settlement_prices: &[T]) -> Vec<T> {
+    let mut previous = trade_price;
+    settlement_prices
+        .iter()
+        .map(|&price| {
+            let flow = point_value * (price - previous);
+            previous = price;
+            flow
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_index_future_margin_sums_to_payoff() {
+        let future =
+            EquityIndexFuture::new(4500.0, 0.5, 2.0, 50.0, Direction::Short, Currency::USD)
+                .unwrap();
+        let prices = [4520.0, 4480.0, 4400.0, 4450.0];
+        let flows = future.margin_flows(&prices);
+
+        assert_eq!(flows[0], -2_000.0);
+        assert_relative_eq!(flows.iter().sum::<f64>(), future.payoff(4450.0));
+        assert_eq!(future.payoff(4450.0), 5_000.0);
+        assert_eq!(future.value(4460.0, 4450.0), -1_000.0);
+    }
+
+    #[test]
+    fn test_index_future_fair_price() {
+        let future =
+            EquityIndexFuture::new(100.0, 1.0, 1.0, 1.0, Direction::Long, Currency::EUR).unwrap();
+        assert_relative_eq!(
+            future.fair_price(100.0, 0.05, 0.02, 0.0),
+            100.0 * 0.03_f64.exp(),
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(future.fair_price(100.0, 0.05, 0.02, 2.0), 100.0);
+    }
+
+    #[test]
+    fn test_dividend_future_fair_price() {
+        let future =
+            DividendFuture::new(120.0, 1.0, 2.0, 10.0, 10.0, Direction::Long, Currency::EUR)
+                .unwrap();
+        let dividends = [(0.9, 40.0), (1.2, 50.0), (1.8, 70.0), (2.1, 60.0)];
+        assert_eq!(future.fair_price(&dividends), 120.0);
+
+        // Equal rate and yield: q S over the one-year period
+        assert_relative_eq!(
+            future.fair_price_from_yield(4000.0, 0.03, 0.03),
+            120.0,
+            epsilon = 1e-9
+        );
+        let carry = future.fair_price_from_yield(4000.0, 0.05, 0.03);
+        assert!(carry > 120.0);
+
+        assert_eq!(future.payoff(125.0), 500.0);
+    }
+
+    #[test]
+    fn test_invalid_terms() {
+        assert!(matches!(
+            EquityIndexFuture::new(0.0, 1.0, 1.0, 1.0, Direction::Long, Currency::USD),
+            Err(InstrumentError::InvalidStrike { .. })
+        ));
+        assert!(matches!(
+            EquityIndexFuture::new(100.0, 1.0, 1.0, 0.0, Direction::Long, Currency::USD),
+            Err(InstrumentError::InvalidParameter { .. })
+        ));
+        assert!(matches!(
+            DividendFuture::new(100.0, 2.0, 1.0, 1.0, 1.0, Direction::Long, Currency::USD),
+            Err(InstrumentError::InvalidParameter { .. })
+        ));
+        assert!(matches!(
+            DividendFuture::new(100.0, 0.0, 1.0, -1.0, 1.0, Direction::Long, Currency::USD),
+            Err(InstrumentError::InvalidNotional { .. })
+        ));
+    }
+}
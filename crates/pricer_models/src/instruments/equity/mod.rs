@@ -3,6 +3,7 @@
 //! This module provides equity-linked derivative instruments including:
 //! - Vanilla options (European, American, Bermudan)
 //! - Forward contracts
+//! - Listed index and dividend futures, margined daily
 //!
 //! # Feature Flag
 //!
@@ -29,9 +30,12 @@
 //! let forward_inst = EquityInstrument::Forward(forward);
 //! ```
 
+mod futures;
+
 use num_traits::Float;
 use pricer_core::types::Currency;
 
+pub use futures::{DividendFuture, EquityIndexFuture};
 // Re-export equity instruments from parent module for organized access
 pub use super::forward::{Direction, Forward};
 pub use super::vanilla::VanillaOption;
@@ -51,6 +55,8 @@ use super::InstrumentTrait;
 ///
 /// - `Vanilla`: Vanilla options (Call, Put, Digital)
 /// - `Forward`: Forward contracts
+/// - `IndexFuture`: Listed equity index futures
+/// - `DividendFuture`: Dividend futures
 ///
 /// # Examples
 ///
@@ -67,11 +73,16 @@ use super::InstrumentTrait;
 /// let payoff = equity.payoff(110.0);
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum EquityInstrument<T: Float> {
     /// Vanilla option (Call, Put, Digital).
     Vanilla(VanillaOption<T>),
     /// Forward contract.
     Forward(Forward<T>),
+    /// Listed equity index future.
+    IndexFuture(EquityIndexFuture<T>),
+    /// Dividend future.
+    DividendFuture(DividendFuture<T>),
 }
 
 impl<T: Float> EquityInstrument<T> {
@@ -83,6 +94,8 @@ impl<T: Float> EquityInstrument<T> {
         match self {
             EquityInstrument::Vanilla(option) => option.payoff(spot),
             EquityInstrument::Forward(forward) => forward.payoff(spot),
+            EquityInstrument::IndexFuture(future) => future.payoff(spot),
+            EquityInstrument::DividendFuture(future) => future.payoff(spot),
         }
     }
 
@@ -92,15 +105,21 @@ impl<T: Float> EquityInstrument<T> {
         match self {
             EquityInstrument::Vanilla(option) => option.expiry(),
             EquityInstrument::Forward(forward) => forward.expiry(),
+            EquityInstrument::IndexFuture(future) => future.expiry(),
+            EquityInstrument::DividendFuture(future) => future.expiry(),
         }
     }
 
     /// Return the underlying instrument's currency.
     #[inline]
     pub fn currency(&self) -> Currency {
-        // Both vanilla options and forwards default to USD
-        // In a real implementation, this would be configurable
-        Currency::USD
+        match self {
+            EquityInstrument::IndexFuture(future) => future.currency(),
+            EquityInstrument::DividendFuture(future) => future.currency(),
+            // Vanilla options and forwards default to USD
+            // In a real implementation, this would be configurable
+            _ => Currency::USD,
+        }
     }
 
     /// Return whether this is a vanilla option.
@@ -115,6 +134,15 @@ impl<T: Float> EquityInstrument<T> {
         matches!(self, EquityInstrument::Forward(_))
     }
 
+    /// Return whether this is an index or dividend future.
+    #[inline]
+    pub fn is_future(&self) -> bool {
+        matches!(
+            self,
+            EquityInstrument::IndexFuture(_) | EquityInstrument::DividendFuture(_)
+        )
+    }
+
     /// Return a reference to the vanilla option if this is a Vanilla variant.
     pub fn as_vanilla(&self) -> Option<&VanillaOption<T>> {
         match self {
@@ -130,6 +158,22 @@ impl<T: Float> EquityInstrument<T> {
             _ => None,
         }
     }
+
+    /// Return a reference to the index future if this is an IndexFuture variant.
+    pub fn as_index_future(&self) -> Option<&EquityIndexFuture<T>> {
+        match self {
+            EquityInstrument::IndexFuture(future) => Some(future),
+            _ => None,
+        }
+    }
+
+    /// Return a reference to the dividend future if this is a DividendFuture variant.
+    pub fn as_dividend_future(&self) -> Option<&DividendFuture<T>> {
+        match self {
+            EquityInstrument::DividendFuture(future) => Some(future),
+            _ => None,
+        }
+    }
 }
 
 impl<T: Float> InstrumentTrait<T> for EquityInstrument<T> {
@@ -152,6 +196,8 @@ impl<T: Float> InstrumentTrait<T> for EquityInstrument<T> {
         match self {
             EquityInstrument::Vanilla(_) => "EquityVanilla",
             EquityInstrument::Forward(_) => "EquityForward",
+            EquityInstrument::IndexFuture(_) => "EquityIndexFuture",
+            EquityInstrument::DividendFuture(_) => "EquityDividendFuture",
         }
     }
 }
@@ -169,6 +215,18 @@ impl<T: Float> From<Forward<T>> for EquityInstrument<T> {
     }
 }
 
+impl<T: Float> From<EquityIndexFuture<T>> for EquityInstrument<T> {
+    fn from(future: EquityIndexFuture<T>) -> Self {
+        EquityInstrument::IndexFuture(future)
+    }
+}
+
+impl<T: Float> From<DividendFuture<T>> for EquityInstrument<T> {
+    fn from(future: DividendFuture<T>) -> Self {
+        EquityInstrument::DividendFuture(future)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -265,6 +323,29 @@ mod tests {
         assert!(equity.is_forward());
     }
 
+    #[test]
+    fn test_futures() {
+        let future: EquityInstrument<f64> =
+            EquityIndexFuture::new(4500.0, 0.25, 1.0, 50.0, Direction::Long, Currency::EUR)
+                .unwrap()
+                .into();
+        assert!(future.is_future());
+        assert!(!future.is_forward());
+        assert_eq!(future.currency(), Currency::EUR);
+        assert_eq!(future.type_name(), "EquityIndexFuture");
+        assert_eq!(future.payoff(4510.0), 500.0);
+        assert!(future.as_index_future().is_some());
+
+        let dividend: EquityInstrument<f64> =
+            DividendFuture::new(100.0, 1.0, 2.0, 1.0, 10.0, Direction::Long, Currency::EUR)
+                .unwrap()
+                .into();
+        assert_eq!(dividend.type_name(), "EquityDividendFuture");
+        assert_eq!(dividend.expiry(), 2.0);
+        assert!(dividend.as_dividend_future().is_some());
+        assert!(dividend.as_index_future().is_none());
+    }
+
     #[test]
     fn test_instrument_trait_payoff() {
         let call = create_test_call();
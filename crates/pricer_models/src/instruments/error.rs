@@ -17,6 +17,8 @@ use thiserror::Error;
 /// - `InvalidNotional`: Notional amount is invalid
 /// - `PayoffError`: Payoff computation failed
 /// - `InvalidParameter`: General parameter validation failure
+/// - `UnknownProduct`: Product code not in the registry
+/// - `MissingTerm`: Economic term required by a product is absent
 ///
 /// # Examples
 /// ```
@@ -61,6 +63,22 @@ pub enum InstrumentError {
         /// Description of the parameter error
         message: String,
     },
+
+    /// Product code not registered in the product registry.
+    #[error("Unknown product code: {code}")]
+    UnknownProduct {
+        /// The unrecognised product code
+        code: String,
+    },
+
+    /// Economic term required to construct a product is absent.
+    #[error("Missing term '{term}' for product {code}")]
+    MissingTerm {
+        /// The product code being constructed
+        code: String,
+        /// Name of the missing term
+        term: &'static str,
+    },
 }
 
 impl From<InstrumentError> for PricingError {
@@ -77,6 +95,12 @@ impl From<InstrumentError> for PricingError {
             }
             InstrumentError::PayoffError { message } => PricingError::ModelFailure(message),
             InstrumentError::InvalidParameter { message } => PricingError::InvalidInput(message),
+            InstrumentError::UnknownProduct { code } => {
+                PricingError::UnsupportedInstrument(format!("Unknown product code: {}", code))
+            }
+            InstrumentError::MissingTerm { code, term } => {
+                PricingError::InvalidInput(format!("Missing term '{}' for product {}", term, code))
+            }
         }
     }
 }
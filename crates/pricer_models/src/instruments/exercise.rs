@@ -29,6 +29,7 @@ use num_traits::Float;
 /// };
 /// ```
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ExerciseStyle<T: Float> {
     /// European style: exercise only at expiry.
     European,
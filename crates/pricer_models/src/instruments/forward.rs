@@ -13,6 +13,7 @@ use super::error::InstrumentError;
 /// - `Long`: Buyer of the underlying (profits when price rises)
 /// - `Short`: Seller of the underlying (profits when price falls)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Direction {
     /// Long position (buy underlying)
     Long,
@@ -55,6 +56,7 @@ impl Direction {
 /// assert!((payoff - 10_000_000.0).abs() < 1.0); // 1M * (110 - 100)
 /// ```
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Forward<T: Float> {
     strike: T,
     expiry: T,
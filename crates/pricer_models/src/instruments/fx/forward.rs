@@ -32,6 +32,7 @@ use crate::instruments::traits::InstrumentTrait;
 /// - Buy: Buy base currency, sell quote currency (long position)
 /// - Sell: Sell base currency, buy quote currency (short position)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FxForwardDirection {
     /// Buy base currency at forward rate (long position).
     Buy,
@@ -139,6 +140,7 @@ impl From<CurrencyError> for FxForwardError {
 /// assert!((payoff - 30_000.0_f64).abs() < 1e-6);
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FxForward<T: Float> {
     /// Currency pair (BASE/QUOTE).
     currency_pair: CurrencyPair<T>,
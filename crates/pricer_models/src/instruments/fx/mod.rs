@@ -75,6 +75,7 @@ use crate::instruments::traits::InstrumentTrait;
 /// assert!(payoff > 0.0);
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FxInstrument<T: Float> {
     /// FX vanilla option (call or put).
     Option(FxOption<T>),
@@ -329,4 +330,24 @@ mod tests {
         let payoff = <FxInstrument<f64> as InstrumentTrait<f64>>::payoff(&instrument, 1.15);
         assert!(payoff > 0.0);
     }
+
+    #[cfg(feature = "serde")]
+    mod serde_tests {
+        use super::*;
+
+        #[test]
+        fn test_fx_instrument_roundtrip() {
+            let pair = create_test_pair();
+            let option =
+                FxOption::new(pair, 1.10, 1.0, 1_000_000.0, FxOptionType::Call, 1e-6).unwrap();
+            let forward =
+                FxForward::new(pair, 1.12, 1.0, 1_000_000.0, FxForwardDirection::Buy).unwrap();
+
+            for instrument in [FxInstrument::Option(option), FxInstrument::Forward(forward)] {
+                let json = serde_json::to_string(&instrument).unwrap();
+                let parsed: FxInstrument<f64> = serde_json::from_str(&json).unwrap();
+                assert_eq!(format!("{:?}", parsed), format!("{:?}", instrument));
+            }
+        }
+    }
 }
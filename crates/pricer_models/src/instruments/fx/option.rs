@@ -29,6 +29,7 @@ use crate::instruments::traits::InstrumentTrait;
 /// - Call: Right to buy base currency, sell quote currency
 /// - Put: Right to sell base currency, buy quote currency
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FxOptionType {
     /// Right to buy base currency at strike price in quote currency.
     Call,
@@ -129,6 +130,7 @@ impl From<CurrencyError> for FxOptionError {
 /// assert!((payoff - 30_000.0_f64).abs() < 100.0);
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FxOption<T: Float> {
     /// Currency pair (BASE/QUOTE).
     currency_pair: CurrencyPair<T>,
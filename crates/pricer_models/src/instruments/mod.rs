@@ -26,6 +26,18 @@
 //! - [`Forward`]: Forward contracts with linear payoffs
 //! - [`Swap`]: Interest rate swaps with payment schedules
 //!
+//! # Cashflows
+//!
+//! [`Instrument::cashflows`] decomposes an instrument into dated,
+//! currency-tagged expected cashflows, each with its discount factor and
+//! present value on a [`MarketSnapshot`](pricer_core::market_data::MarketSnapshot).
+//!
+//! # Product Registry
+//!
+//! [`ProductRegistry`] maps external product codes (ISDA taxonomy, internal
+//! codes) to constructors of [`Instrument`] variants, applying per-market
+//! [`MarketConventions`] to terms a trade leaves unstated.
+//!
 //! # Examples
 //!
 //! ```
@@ -46,10 +58,12 @@
 //! ```
 
 // Core types (always available)
+mod cashflows;
 mod error;
 mod exercise;
 mod params;
 mod payoff;
+mod registry;
 mod traits;
 
 // Instrument implementations (always available for backward compatibility)
@@ -77,11 +91,18 @@ pub mod commodity;
 pub mod exotic;
 
 // Re-export all public types
+pub use cashflows::{CashflowType, ValuedCashflow};
 pub use error::InstrumentError;
 pub use exercise::ExerciseStyle;
 pub use forward::{Direction, Forward};
 pub use params::InstrumentParams;
 pub use payoff::PayoffType;
+pub use registry::{
+    MarketConventions, ProductConstructor, ProductDefinition, ProductRegistry, ProductTerms,
+    ISDA_EQUITY_FORWARD_SINGLE_NAME, ISDA_EQUITY_OPTION_SINGLE_INDEX,
+    ISDA_EQUITY_OPTION_SINGLE_NAME, ISDA_FX_FORWARD, ISDA_FX_VANILLA_OPTION,
+    ISDA_IR_SWAP_FIXED_FLOAT, ISDA_IR_SWAP_OIS,
+};
 pub use swap::{PaymentFrequency, Swap};
 pub use traits::{Cashflow, CashflowInstrument, InstrumentTrait};
 pub use vanilla::VanillaOption;
@@ -135,6 +156,7 @@ use pricer_core::types::Currency;
 /// let payoff2 = forward_inst.payoff(110.0);
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Instrument<T: Float> {
     /// Vanilla option (Call, Put, Digital)
     Vanilla(VanillaOption<T>),
@@ -304,6 +326,7 @@ impl<T: Float> Instrument<T> {
 /// assert!((payoff - 10.0).abs() < 0.01);
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[non_exhaustive]
 pub enum InstrumentEnum<T: Float> {
     /// Equity derivatives (vanilla options, forwards).
@@ -526,6 +549,7 @@ impl<T: Float> From<FxInstrument<T>> for InstrumentEnum<T> {
 /// Used to categorize instruments at the top level for risk management
 /// and reporting purposes.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AssetClass {
     /// Equity derivatives (options, forwards on stocks/indices).
     Equity,
@@ -866,4 +890,63 @@ mod tests {
 
         assert_eq!(set.len(), 2);
     }
+
+    #[cfg(feature = "serde")]
+    mod serde_tests {
+        use super::*;
+
+        // The JSON layouts below are part of the snapshot and REST schema;
+        // changing them breaks stored payloads.
+
+        #[test]
+        fn test_vanilla_schema() {
+            let json = serde_json::to_string(&Instrument::Vanilla(create_test_call())).unwrap();
+            assert_eq!(
+                json,
+                r#"{"Vanilla":{"params":{"strike":100.0,"expiry":1.0,"notional":1.0},"payoff_type":"Call","exercise_style":"European","epsilon":1e-6}}"#
+            );
+        }
+
+        #[test]
+        fn test_forward_schema() {
+            let json = serde_json::to_string(&Instrument::Forward(create_test_forward())).unwrap();
+            assert_eq!(
+                json,
+                r#"{"Forward":{"strike":100.0,"expiry":1.0,"notional":1.0,"direction":"Long"}}"#
+            );
+        }
+
+        #[test]
+        fn test_swap_schema() {
+            let json = serde_json::to_string(&Instrument::Swap(create_test_swap())).unwrap();
+            assert_eq!(
+                json,
+                r#"{"Swap":{"notional":1000000.0,"fixed_rate":0.03,"payment_dates":[0.5,1.0,1.5,2.0],"frequency":"SemiAnnual","currency":"USD"}}"#
+            );
+        }
+
+        #[test]
+        fn test_instrument_roundtrip() {
+            for instrument in [
+                Instrument::Vanilla(create_test_call()),
+                Instrument::Forward(create_test_forward()),
+                Instrument::Swap(create_test_swap()),
+            ] {
+                let json = serde_json::to_string(&instrument).unwrap();
+                let parsed: Instrument<f64> = serde_json::from_str(&json).unwrap();
+                assert_eq!(parsed.payoff(110.0), instrument.payoff(110.0));
+                assert_eq!(parsed.expiry(), instrument.expiry());
+            }
+        }
+
+        #[test]
+        fn test_instrument_enum_schema() {
+            let instrument = InstrumentEnum::Equity(EquityInstrument::Vanilla(create_test_call()));
+            let json = serde_json::to_string(&instrument).unwrap();
+            assert!(json.starts_with(r#"{"Equity":{"Vanilla":{"#));
+
+            let parsed: InstrumentEnum<f64> = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed.payoff(110.0), instrument.payoff(110.0));
+        }
+    }
 }
@@ -10,7 +10,9 @@ use super::error::InstrumentError;
 /// Common parameters shared across instrument types.
 ///
 /// Contains strike price, expiry time, and notional amount with
-/// validation ensuring all values are positive.
+/// validation ensuring all values are positive, and an optional
+/// per-instrument payoff smoothing width that overrides the one the
+/// instrument was built with.
 ///
 /// # Type Parameters
 /// * `T` - Floating-point type implementing `Float` (e.g., `f64`, `Dual64`)
@@ -25,10 +27,13 @@ use super::error::InstrumentError;
 /// assert_eq!(params.notional(), 1_000_000.0);
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InstrumentParams<T: Float> {
     strike: T,
     expiry: T,
     notional: T,
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    smoothing_epsilon: Option<T>,
 }
 
 impl<T: Float> InstrumentParams<T> {
@@ -80,9 +85,39 @@ impl<T: Float> InstrumentParams<T> {
             strike,
             expiry,
             notional,
+            smoothing_epsilon: None,
         })
     }
 
+    /// Overrides the payoff smoothing width for this instrument.
+    ///
+    /// # Errors
+    /// Returns `InstrumentError::InvalidParameter` if `epsilon` is not
+    /// positive and finite.
+    ///
+    /// # Examples
+    /// ```
+    /// use pricer_models::instruments::InstrumentParams;
+    ///
+    /// let params = InstrumentParams::new(100.0_f64, 1.0, 1.0)
+    ///     .unwrap()
+    ///     .with_smoothing_epsilon(0.05)
+    ///     .unwrap();
+    /// assert_eq!(params.smoothing_epsilon(), Some(0.05));
+    /// ```
+    pub fn with_smoothing_epsilon(mut self, epsilon: T) -> Result<Self, InstrumentError> {
+        if !(epsilon > T::zero() && epsilon.is_finite()) {
+            return Err(InstrumentError::InvalidParameter {
+                message: format!(
+                    "Smoothing epsilon must be positive and finite: {}",
+                    epsilon.to_f64().unwrap_or(f64::NAN)
+                ),
+            });
+        }
+        self.smoothing_epsilon = Some(epsilon);
+        Ok(self)
+    }
+
     /// Returns the strike price.
     #[inline]
     pub fn strike(&self) -> T {
@@ -100,6 +135,12 @@ impl<T: Float> InstrumentParams<T> {
     pub fn notional(&self) -> T {
         self.notional
     }
+
+    /// Returns the smoothing width override, if set.
+    #[inline]
+    pub fn smoothing_epsilon(&self) -> Option<T> {
+        self.smoothing_epsilon
+    }
 }
 
 #[cfg(test)]
@@ -168,6 +209,25 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_smoothing_epsilon_override() {
+        let params = InstrumentParams::new(100.0_f64, 1.0, 1.0).unwrap();
+        assert_eq!(params.smoothing_epsilon(), None);
+        assert_eq!(
+            params
+                .with_smoothing_epsilon(1e-3)
+                .unwrap()
+                .smoothing_epsilon(),
+            Some(1e-3)
+        );
+        for bad in [0.0, -1e-3, f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                params.with_smoothing_epsilon(bad),
+                Err(InstrumentError::InvalidParameter { .. })
+            ));
+        }
+    }
+
     #[test]
     fn test_f32_compatibility() {
         let params = InstrumentParams::new(100.0_f32, 1.0_f32, 1_000_000.0_f32).unwrap();
@@ -27,6 +27,7 @@ use pricer_core::math::smoothing::{smooth_indicator, smooth_max};
 /// assert!((payoff - 10.0).abs() < 0.01);
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PayoffType {
     /// Call option: max(S - K, 0)
     Call,
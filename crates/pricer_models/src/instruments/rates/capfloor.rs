@@ -61,6 +61,7 @@ use crate::schedules::Schedule;
 ///
 /// Caps are used to hedge against rising interest rates.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Cap<T: Float> {
     /// Notional principal amount.
     notional: T,
@@ -187,6 +188,7 @@ impl<T: Float> Cap<T> {
 ///
 /// Floors are used to hedge against falling interest rates.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Floor<T: Float> {
     /// Notional principal amount.
     notional: T,
@@ -315,6 +317,7 @@ impl<T: Float> Floor<T> {
 /// Often structured as a zero-cost collar where cap and floor
 /// premiums offset each other.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Collar<T: Float> {
     /// The cap component.
     cap: Cap<T>,
@@ -0,0 +1,438 @@
+//! Constant maturity swap (CMS) instruments.
+//!
+//! This module provides:
+//! - [`CmsIndex`]: Swap rate of a fixed tenor, fixed at each period start
+//! - [`CmsCapFloor`]: Series of caplets or floorlets on a CMS rate
+//! - [`CmsSpreadOption`]: Series of options on the spread of two CMS rates
+//!
+//! CMS coupons themselves are paid by a [`FloatingLeg`](super::FloatingLeg)
+//! referencing a [`CmsIndex`] through `FloatingLeg::with_cms_index`.
+//!
+//! # Payoff
+//!
+//! For each period i, with S_i the CMS rate fixed at the period start:
+//! - **CMS caplet**: max(S_i - Strike, 0) × YearFraction × Notional
+//! - **CMS floorlet**: max(Strike - S_i, 0) × YearFraction × Notional
+//! - **CMS spread caplet**: max(S1_i - S2_i - Strike, 0) × YearFraction × Notional
+//!
+//! A CMS rate paid once rather than over the swap's life is worth more than
+//! its forward swap rate; the pricing layer adds this convexity adjustment by
+//! replication off a [`SwaptionVolCube`](pricer_core::market_data::surfaces::SwaptionVolCube).
+//!
+//! # Example
+//!
+//! ```
+//! use pricer_models::instruments::rates::{CmsCapFloor, CmsIndex, CmsOptionType, RateIndex};
+//! use pricer_models::schedules::{ScheduleBuilder, Frequency};
+//! use pricer_core::types::{Currency, time::{Date, DayCountConvention}};
+//!
+//! let schedule = ScheduleBuilder::new()
+//!     .start(Date::from_ymd(2024, 1, 15).unwrap())
+//!     .end(Date::from_ymd(2029, 1, 15).unwrap())
+//!     .frequency(Frequency::Annual)
+//!     .day_count(DayCountConvention::ActualActual360)
+//!     .build()
+//!     .unwrap();
+//!
+//! let cms10y = CmsIndex::new(RateIndex::Sofr, 10);
+//! let cap = CmsCapFloor::new(
+//!     1_000_000.0,
+//!     schedule,
+//!     0.045,
+//!     cms10y,
+//!     CmsOptionType::Cap,
+//!     Currency::USD,
+//! );
+//!
+//! assert_eq!(cap.cms_index().name(), "SOFR-CMS10Y");
+//! assert_eq!(cap.num_periods(), 5);
+//! ```
+
+use num_traits::Float;
+use pricer_core::types::time::DayCountConvention;
+use pricer_core::types::Currency;
+
+use super::RateIndex;
+use crate::schedules::{Frequency, Schedule};
+
+/// Constant maturity swap rate index.
+///
+/// The par rate of a spot-starting swap of `tenor_years` against the
+/// underlying floating index, fixed at the start of each coupon period.
+/// The swap's fixed leg pays annually unless set otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CmsIndex {
+    /// Floating index of the underlying swap.
+    index: RateIndex,
+    /// Underlying swap tenor in years.
+    tenor_years: u32,
+    /// Fixed leg payment frequency of the underlying swap.
+    fixed_frequency: Frequency,
+}
+
+impl CmsIndex {
+    /// Create a CMS index on a swap of `tenor_years` (at least one year).
+    pub fn new(index: RateIndex, tenor_years: u32) -> Self {
+        Self {
+            index,
+            tenor_years: tenor_years.max(1),
+            fixed_frequency: Frequency::Annual,
+        }
+    }
+
+    /// Set the fixed leg frequency of the underlying swap.
+    pub fn with_fixed_frequency(mut self, frequency: Frequency) -> Self {
+        self.fixed_frequency = frequency;
+        self
+    }
+
+    /// Returns the floating index of the underlying swap.
+    #[inline]
+    pub fn index(&self) -> RateIndex {
+        self.index
+    }
+
+    /// Returns the underlying swap tenor in years.
+    #[inline]
+    pub fn tenor_years(&self) -> u32 {
+        self.tenor_years
+    }
+
+    /// Returns the fixed leg frequency of the underlying swap.
+    #[inline]
+    pub fn fixed_frequency(&self) -> Frequency {
+        self.fixed_frequency
+    }
+
+    /// Returns the currency of the underlying swap.
+    #[inline]
+    pub fn currency(&self) -> Currency {
+        self.index.currency()
+    }
+
+    /// Returns the index name, e.g. `"EURIBOR6M-CMS10Y"`.
+    pub fn name(&self) -> String {
+        format!("{}-CMS{}Y", self.index.name(), self.tenor_years)
+    }
+}
+
+/// Direction of a CMS option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CmsOptionType {
+    /// Pays when the rate (or spread) exceeds the strike.
+    Cap,
+    /// Pays when the rate (or spread) is below the strike.
+    Floor,
+}
+
+/// CMS cap or floor.
+///
+/// A series of caplets or floorlets on a CMS rate, one per schedule period,
+/// each fixed at the period start and paid at the period payment date.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CmsCapFloor<T: Float> {
+    /// Notional principal amount.
+    notional: T,
+    /// Payment schedule (defines caplet/floorlet periods).
+    schedule: Schedule,
+    /// Strike rate.
+    strike: T,
+    /// Reference CMS index.
+    cms_index: CmsIndex,
+    /// Cap or floor.
+    option_type: CmsOptionType,
+    /// Day count convention for accrual.
+    day_count: DayCountConvention,
+    /// Settlement currency.
+    currency: Currency,
+}
+
+impl<T: Float> CmsCapFloor<T> {
+    /// Create a new CMS cap or floor.
+    ///
+    /// # Arguments
+    ///
+    /// * `notional` - Notional principal amount
+    /// * `schedule` - Payment schedule (defines caplet/floorlet periods)
+    /// * `strike` - Strike rate
+    /// * `cms_index` - Reference CMS index
+    /// * `option_type` - Cap or floor
+    /// * `currency` - Settlement currency
+    pub fn new(
+        notional: T,
+        schedule: Schedule,
+        strike: T,
+        cms_index: CmsIndex,
+        option_type: CmsOptionType,
+        currency: Currency,
+    ) -> Self {
+        Self {
+            notional,
+            schedule,
+            strike,
+            cms_index,
+            option_type,
+            day_count: cms_index.index().default_day_count(),
+            currency,
+        }
+    }
+
+    /// Set the accrual day count convention.
+    pub fn with_day_count(mut self, day_count: DayCountConvention) -> Self {
+        self.day_count = day_count;
+        self
+    }
+
+    /// Returns the notional amount.
+    #[inline]
+    pub fn notional(&self) -> T {
+        self.notional
+    }
+
+    /// Returns the payment schedule.
+    #[inline]
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+
+    /// Returns the strike rate.
+    #[inline]
+    pub fn strike(&self) -> T {
+        self.strike
+    }
+
+    /// Returns the reference CMS index.
+    #[inline]
+    pub fn cms_index(&self) -> CmsIndex {
+        self.cms_index
+    }
+
+    /// Returns whether this is a cap or a floor.
+    #[inline]
+    pub fn option_type(&self) -> CmsOptionType {
+        self.option_type
+    }
+
+    /// Returns the day count convention.
+    #[inline]
+    pub fn day_count(&self) -> DayCountConvention {
+        self.day_count
+    }
+
+    /// Returns the settlement currency.
+    #[inline]
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// Returns the maturity time in years (from start to end of last period).
+    pub fn maturity(&self) -> T {
+        schedule_maturity(&self.schedule)
+    }
+
+    /// Returns the number of caplets or floorlets.
+    #[inline]
+    pub fn num_periods(&self) -> usize {
+        self.schedule.periods().len()
+    }
+}
+
+/// CMS spread option.
+///
+/// A series of options on the spread between two CMS rates, typically a
+/// long and a short tenor (e.g. 10Y − 2Y curve steepeners), one per
+/// schedule period, fixed at the period start.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CmsSpreadOption<T: Float> {
+    /// Notional principal amount.
+    notional: T,
+    /// Payment schedule (defines option periods).
+    schedule: Schedule,
+    /// Strike on the spread.
+    strike: T,
+    /// CMS index the spread is long.
+    long_index: CmsIndex,
+    /// CMS index the spread is short.
+    short_index: CmsIndex,
+    /// Cap (spread above strike) or floor (spread below strike).
+    option_type: CmsOptionType,
+    /// Day count convention for accrual.
+    day_count: DayCountConvention,
+    /// Settlement currency.
+    currency: Currency,
+}
+
+impl<T: Float> CmsSpreadOption<T> {
+    /// Create a new CMS spread option on `long_index − short_index`.
+    ///
+    /// # Arguments
+    ///
+    /// * `notional` - Notional principal amount
+    /// * `schedule` - Payment schedule (defines option periods)
+    /// * `strike` - Strike on the spread
+    /// * `long_index` - CMS index the spread is long
+    /// * `short_index` - CMS index the spread is short
+    /// * `option_type` - Cap or floor on the spread
+    /// * `currency` - Settlement currency
+    pub fn new(
+        notional: T,
+        schedule: Schedule,
+        strike: T,
+        long_index: CmsIndex,
+        short_index: CmsIndex,
+        option_type: CmsOptionType,
+        currency: Currency,
+    ) -> Self {
+        Self {
+            notional,
+            schedule,
+            strike,
+            long_index,
+            short_index,
+            option_type,
+            day_count: long_index.index().default_day_count(),
+            currency,
+        }
+    }
+
+    /// Set the accrual day count convention.
+    pub fn with_day_count(mut self, day_count: DayCountConvention) -> Self {
+        self.day_count = day_count;
+        self
+    }
+
+    /// Returns the notional amount.
+    #[inline]
+    pub fn notional(&self) -> T {
+        self.notional
+    }
+
+    /// Returns the payment schedule.
+    #[inline]
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+
+    /// Returns the strike on the spread.
+    #[inline]
+    pub fn strike(&self) -> T {
+        self.strike
+    }
+
+    /// Returns the CMS index the spread is long.
+    #[inline]
+    pub fn long_index(&self) -> CmsIndex {
+        self.long_index
+    }
+
+    /// Returns the CMS index the spread is short.
+    #[inline]
+    pub fn short_index(&self) -> CmsIndex {
+        self.short_index
+    }
+
+    /// Returns whether this is a cap or a floor on the spread.
+    #[inline]
+    pub fn option_type(&self) -> CmsOptionType {
+        self.option_type
+    }
+
+    /// Returns the day count convention.
+    #[inline]
+    pub fn day_count(&self) -> DayCountConvention {
+        self.day_count
+    }
+
+    /// Returns the settlement currency.
+    #[inline]
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// Returns the maturity time in years (from start to end of last period).
+    pub fn maturity(&self) -> T {
+        schedule_maturity(&self.schedule)
+    }
+
+    /// Returns the number of spread options.
+    #[inline]
+    pub fn num_periods(&self) -> usize {
+        self.schedule.periods().len()
+    }
+}
+
+/// Years from the first period start to the last period end.
+fn schedule_maturity<T: Float>(schedule: &Schedule) -> T {
+    let periods = schedule.periods();
+    match (periods.first(), periods.last()) {
+        (Some(first), Some(last)) => {
+            let year_frac =
+                DayCountConvention::ActualActual365.year_fraction_dates(first.start(), last.end());
+            T::from(year_frac).unwrap_or_else(T::zero)
+        }
+        _ => T::zero(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schedules::ScheduleBuilder;
+    use pricer_core::types::time::Date;
+
+    fn schedule() -> Schedule {
+        ScheduleBuilder::new()
+            .start(Date::from_ymd(2024, 1, 15).unwrap())
+            .end(Date::from_ymd(2027, 1, 15).unwrap())
+            .frequency(Frequency::SemiAnnual)
+            .day_count(DayCountConvention::ActualActual360)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_cms_index() {
+        let index = CmsIndex::new(RateIndex::Euribor6M, 10);
+        assert_eq!(index.name(), "EURIBOR6M-CMS10Y");
+        assert_eq!(index.currency(), Currency::EUR);
+        assert_eq!(index.fixed_frequency(), Frequency::Annual);
+
+        let index = index.with_fixed_frequency(Frequency::SemiAnnual);
+        assert_eq!(index.fixed_frequency(), Frequency::SemiAnnual);
+        assert_eq!(CmsIndex::new(RateIndex::Sofr, 0).tenor_years(), 1);
+    }
+
+    #[test]
+    fn test_cms_cap_floor_and_spread_option() {
+        let cap = CmsCapFloor::new(
+            1_000_000.0,
+            schedule(),
+            0.04,
+            CmsIndex::new(RateIndex::Sofr, 10),
+            CmsOptionType::Cap,
+            Currency::USD,
+        )
+        .with_day_count(DayCountConvention::Thirty360);
+        assert_eq!(cap.num_periods(), 6);
+        assert_eq!(cap.day_count(), DayCountConvention::Thirty360);
+        assert!((cap.maturity() - 3.0).abs() < 0.01);
+
+        let spread = CmsSpreadOption::new(
+            1_000_000.0,
+            schedule(),
+            0.0,
+            CmsIndex::new(RateIndex::Sofr, 10),
+            CmsIndex::new(RateIndex::Sofr, 2),
+            CmsOptionType::Cap,
+            Currency::USD,
+        );
+        assert_eq!(spread.long_index().tenor_years(), 10);
+        assert_eq!(spread.short_index().tenor_years(), 2);
+        assert_eq!(spread.day_count(), RateIndex::Sofr.default_day_count());
+        assert_eq!(spread.option_type(), CmsOptionType::Cap);
+    }
+}
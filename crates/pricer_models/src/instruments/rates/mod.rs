@@ -5,6 +5,12 @@
 //! - [`Swaption`]: Option on interest rate swaps
 //! - [`Cap`] and [`Floor`]: Interest rate caps and floors
 //! - [`Collar`]: Combination of cap and floor
+//! - [`CmsCapFloor`] and [`CmsSpreadOption`]: Options on constant maturity
+//!   swap rates, and CMS coupons via [`FloatingLeg::with_cms_index`]
+//! - [`RangeAccrual`] and [`CallableRangeAccrual`]: Notes accruing while an
+//!   index fixes in a range
+//! - [`Repo`] and [`SecuritiesLoan`]: Collateralised financing trades valued
+//!   off the repo curve
 //!
 //! # Feature Flag
 //!
@@ -54,14 +60,24 @@
 //! ```
 
 mod capfloor;
+mod cms;
 pub mod pricing;
+mod range_accrual;
+mod repo;
 mod swap;
 mod swaption;
 
 pub use capfloor::{Cap, Collar, Floor};
+pub use cms::{CmsCapFloor, CmsIndex, CmsOptionType, CmsSpreadOption};
 pub use pricing::{
-    par_swap_rate, price_fixed_leg, price_floating_leg, price_irs, price_swaption_bachelier,
-    price_swaption_black76,
+    cms_convexity_adjustment, cms_forward_rate, par_repo_rate, par_swap_rate, price_cms_cap_floor,
+    price_cms_floating_leg, price_cms_spread_option, price_fixed_leg, price_floating_leg,
+    price_irs, price_repo, price_securities_loan, price_swaption_bachelier, price_swaption_black76,
+    swap_accrued_interest, swap_annuity, swap_carry, swap_pv01,
+};
+pub use range_accrual::{CallableRangeAccrual, RangeAccrual, RateScenarios};
+pub use repo::{
+    CollateralPosition, CollateralSchedule, LendingDirection, Repo, RepoDirection, SecuritiesLoan,
 };
 pub use swap::{FixedLeg, FloatingLeg, InterestRateSwap, RateIndex, SwapDirection};
 pub use swaption::{Swaption, SwaptionStyle, SwaptionType};
@@ -86,6 +102,12 @@ use super::InstrumentTrait;
 /// - `Swaption`: Option on interest rate swap
 /// - `Cap`: Interest rate cap (series of caplets)
 /// - `Floor`: Interest rate floor (series of floorlets)
+/// - `RangeAccrual`: Range accrual note
+/// - `CallableRangeAccrual`: Range accrual note callable by the issuer
+/// - `CmsCapFloor`: Cap or floor on a constant maturity swap rate
+/// - `CmsSpreadOption`: Option on the spread of two CMS rates
+/// - `Repo`: Repo or reverse repo
+/// - `SecuritiesLoan`: Securities lent or borrowed against collateral
 ///
 /// # Examples
 ///
@@ -127,6 +149,7 @@ use super::InstrumentTrait;
 /// assert_eq!(instrument.type_name(), "RatesSwap");
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RatesInstrument<T: Float> {
     /// Plain vanilla interest rate swap.
     Swap(InterestRateSwap<T>),
@@ -136,6 +159,18 @@ pub enum RatesInstrument<T: Float> {
     Cap(Cap<T>),
     /// Interest rate floor.
     Floor(Floor<T>),
+    /// Range accrual note.
+    RangeAccrual(RangeAccrual<T>),
+    /// Callable range accrual note.
+    CallableRangeAccrual(CallableRangeAccrual<T>),
+    /// CMS cap or floor.
+    CmsCapFloor(CmsCapFloor<T>),
+    /// CMS spread option.
+    CmsSpreadOption(CmsSpreadOption<T>),
+    /// Repo or reverse repo.
+    Repo(Repo<T>),
+    /// Securities loan.
+    SecuritiesLoan(SecuritiesLoan<T>),
 }
 
 impl<T: Float> RatesInstrument<T> {
@@ -158,6 +193,12 @@ impl<T: Float> RatesInstrument<T> {
             RatesInstrument::Swaption(swaption) => swaption.expiry(),
             RatesInstrument::Cap(cap) => cap.maturity(),
             RatesInstrument::Floor(floor) => floor.maturity(),
+            RatesInstrument::RangeAccrual(note) => note.maturity(),
+            RatesInstrument::CallableRangeAccrual(note) => note.maturity(),
+            RatesInstrument::CmsCapFloor(cap_floor) => cap_floor.maturity(),
+            RatesInstrument::CmsSpreadOption(option) => option.maturity(),
+            RatesInstrument::Repo(repo) => repo.maturity(),
+            RatesInstrument::SecuritiesLoan(loan) => loan.maturity(),
         }
     }
 
@@ -169,6 +210,12 @@ impl<T: Float> RatesInstrument<T> {
             RatesInstrument::Swaption(swaption) => swaption.currency(),
             RatesInstrument::Cap(cap) => cap.currency(),
             RatesInstrument::Floor(floor) => floor.currency(),
+            RatesInstrument::RangeAccrual(note) => note.currency(),
+            RatesInstrument::CallableRangeAccrual(note) => note.currency(),
+            RatesInstrument::CmsCapFloor(cap_floor) => cap_floor.currency(),
+            RatesInstrument::CmsSpreadOption(option) => option.currency(),
+            RatesInstrument::Repo(repo) => repo.currency(),
+            RatesInstrument::SecuritiesLoan(loan) => loan.currency(),
         }
     }
 
@@ -179,6 +226,12 @@ impl<T: Float> RatesInstrument<T> {
             RatesInstrument::Swaption(_) => "RatesSwaption",
             RatesInstrument::Cap(_) => "RatesCap",
             RatesInstrument::Floor(_) => "RatesFloor",
+            RatesInstrument::RangeAccrual(_) => "RatesRangeAccrual",
+            RatesInstrument::CallableRangeAccrual(_) => "RatesCallableRangeAccrual",
+            RatesInstrument::CmsCapFloor(_) => "RatesCmsCapFloor",
+            RatesInstrument::CmsSpreadOption(_) => "RatesCmsSpreadOption",
+            RatesInstrument::Repo(_) => "RatesRepo",
+            RatesInstrument::SecuritiesLoan(_) => "RatesSecuritiesLoan",
         }
     }
 
@@ -206,6 +259,33 @@ impl<T: Float> RatesInstrument<T> {
         matches!(self, RatesInstrument::Floor(_))
     }
 
+    /// Check if this is a range accrual, callable or not.
+    #[inline]
+    pub fn is_range_accrual(&self) -> bool {
+        matches!(
+            self,
+            RatesInstrument::RangeAccrual(_) | RatesInstrument::CallableRangeAccrual(_)
+        )
+    }
+
+    /// Check if this is a CMS cap, floor or spread option.
+    #[inline]
+    pub fn is_cms(&self) -> bool {
+        matches!(
+            self,
+            RatesInstrument::CmsCapFloor(_) | RatesInstrument::CmsSpreadOption(_)
+        )
+    }
+
+    /// Check if this is a repo or securities loan.
+    #[inline]
+    pub fn is_financing(&self) -> bool {
+        matches!(
+            self,
+            RatesInstrument::Repo(_) | RatesInstrument::SecuritiesLoan(_)
+        )
+    }
+
     /// Get reference to swap if this is a Swap variant.
     pub fn as_swap(&self) -> Option<&InterestRateSwap<T>> {
         match self {
@@ -237,6 +317,47 @@ impl<T: Float> RatesInstrument<T> {
             _ => None,
         }
     }
+
+    /// Get reference to the range accrual note, callable or not.
+    pub fn as_range_accrual(&self) -> Option<&RangeAccrual<T>> {
+        match self {
+            RatesInstrument::RangeAccrual(note) => Some(note),
+            RatesInstrument::CallableRangeAccrual(callable) => Some(callable.note()),
+            _ => None,
+        }
+    }
+
+    /// Get reference to the CMS cap or floor if this is a CmsCapFloor variant.
+    pub fn as_cms_cap_floor(&self) -> Option<&CmsCapFloor<T>> {
+        match self {
+            RatesInstrument::CmsCapFloor(cap_floor) => Some(cap_floor),
+            _ => None,
+        }
+    }
+
+    /// Get reference to the CMS spread option if this is a CmsSpreadOption variant.
+    pub fn as_cms_spread_option(&self) -> Option<&CmsSpreadOption<T>> {
+        match self {
+            RatesInstrument::CmsSpreadOption(option) => Some(option),
+            _ => None,
+        }
+    }
+
+    /// Get reference to the repo if this is a Repo variant.
+    pub fn as_repo(&self) -> Option<&Repo<T>> {
+        match self {
+            RatesInstrument::Repo(repo) => Some(repo),
+            _ => None,
+        }
+    }
+
+    /// Get reference to the securities loan if this is a SecuritiesLoan variant.
+    pub fn as_securities_loan(&self) -> Option<&SecuritiesLoan<T>> {
+        match self {
+            RatesInstrument::SecuritiesLoan(loan) => Some(loan),
+            _ => None,
+        }
+    }
 }
 
 impl<T: Float> InstrumentTrait<T> for RatesInstrument<T> {
@@ -285,6 +406,42 @@ impl<T: Float> From<Floor<T>> for RatesInstrument<T> {
     }
 }
 
+impl<T: Float> From<RangeAccrual<T>> for RatesInstrument<T> {
+    fn from(note: RangeAccrual<T>) -> Self {
+        RatesInstrument::RangeAccrual(note)
+    }
+}
+
+impl<T: Float> From<CallableRangeAccrual<T>> for RatesInstrument<T> {
+    fn from(note: CallableRangeAccrual<T>) -> Self {
+        RatesInstrument::CallableRangeAccrual(note)
+    }
+}
+
+impl<T: Float> From<CmsCapFloor<T>> for RatesInstrument<T> {
+    fn from(cap_floor: CmsCapFloor<T>) -> Self {
+        RatesInstrument::CmsCapFloor(cap_floor)
+    }
+}
+
+impl<T: Float> From<CmsSpreadOption<T>> for RatesInstrument<T> {
+    fn from(option: CmsSpreadOption<T>) -> Self {
+        RatesInstrument::CmsSpreadOption(option)
+    }
+}
+
+impl<T: Float> From<Repo<T>> for RatesInstrument<T> {
+    fn from(repo: Repo<T>) -> Self {
+        RatesInstrument::Repo(repo)
+    }
+}
+
+impl<T: Float> From<SecuritiesLoan<T>> for RatesInstrument<T> {
+    fn from(loan: SecuritiesLoan<T>) -> Self {
+        RatesInstrument::SecuritiesLoan(loan)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -350,6 +507,30 @@ mod tests {
         Floor::new(1_000_000.0, schedule, 0.02, RateIndex::Sofr, Currency::USD)
     }
 
+    fn create_test_range_accrual() -> RangeAccrual<f64> {
+        let start = Date::from_ymd(2024, 1, 15).unwrap();
+        let end = Date::from_ymd(2027, 1, 15).unwrap();
+
+        let schedule = ScheduleBuilder::new()
+            .start(start)
+            .end(end)
+            .frequency(Frequency::Quarterly)
+            .day_count(DayCountConvention::ActualActual360)
+            .build()
+            .unwrap();
+
+        RangeAccrual::new(
+            1_000_000.0,
+            schedule,
+            0.05,
+            0.01,
+            0.04,
+            RateIndex::Sofr,
+            Currency::USD,
+        )
+        .unwrap()
+    }
+
     fn create_test_swaption() -> Swaption<f64> {
         let swap = create_test_swap();
         Swaption::new(
@@ -464,6 +645,120 @@ mod tests {
         assert!(instrument.as_floor().is_some());
     }
 
+    // ========================================
+    // RatesInstrument Range Accrual Tests
+    // ========================================
+
+    #[test]
+    fn test_rates_instrument_range_accrual() {
+        let note = create_test_range_accrual();
+        let instrument: RatesInstrument<f64> = note.clone().into();
+
+        assert_eq!(instrument.currency(), Currency::USD);
+        assert_eq!(instrument.type_name(), "RatesRangeAccrual");
+        assert!((instrument.expiry() - note.maturity()).abs() < 1e-12);
+        assert!(instrument.is_range_accrual());
+
+        let callable: RatesInstrument<f64> = CallableRangeAccrual::every_period(note).into();
+        assert_eq!(callable.type_name(), "RatesCallableRangeAccrual");
+        assert!(callable.is_range_accrual());
+        assert_eq!(callable.as_range_accrual().unwrap().num_periods(), 12);
+        assert!(!callable.is_cap());
+    }
+
+    // ========================================
+    // RatesInstrument CMS Tests
+    // ========================================
+
+    #[test]
+    fn test_rates_instrument_cms() {
+        let schedule = ScheduleBuilder::new()
+            .start(Date::from_ymd(2024, 1, 15).unwrap())
+            .end(Date::from_ymd(2029, 1, 15).unwrap())
+            .frequency(Frequency::Annual)
+            .day_count(DayCountConvention::ActualActual360)
+            .build()
+            .unwrap();
+
+        let cap: RatesInstrument<f64> = CmsCapFloor::new(
+            1_000_000.0,
+            schedule.clone(),
+            0.045,
+            CmsIndex::new(RateIndex::Sofr, 10),
+            CmsOptionType::Cap,
+            Currency::USD,
+        )
+        .into();
+        assert_eq!(cap.type_name(), "RatesCmsCapFloor");
+        assert!(cap.is_cms());
+        assert!(!cap.is_cap());
+        assert!(cap.as_cms_cap_floor().is_some());
+        assert!((cap.expiry() - 5.0).abs() < 0.01);
+
+        let spread: RatesInstrument<f64> = CmsSpreadOption::new(
+            1_000_000.0,
+            schedule,
+            0.0,
+            CmsIndex::new(RateIndex::Sofr, 10),
+            CmsIndex::new(RateIndex::Sofr, 2),
+            CmsOptionType::Cap,
+            Currency::USD,
+        )
+        .into();
+        assert_eq!(spread.type_name(), "RatesCmsSpreadOption");
+        assert_eq!(spread.currency(), Currency::USD);
+        assert!(spread.as_cms_spread_option().is_some());
+        assert!(spread.as_cms_cap_floor().is_none());
+    }
+
+    // ========================================
+    // RatesInstrument Financing Tests
+    // ========================================
+
+    #[test]
+    fn test_rates_instrument_financing() {
+        let start = Date::from_ymd(2024, 1, 15).unwrap();
+        let end = Date::from_ymd(2025, 1, 15).unwrap();
+        let collateral = CollateralSchedule::constant(
+            start,
+            CollateralPosition::new(1_020_000.0, 0.02).unwrap(),
+        );
+
+        let repo: RatesInstrument<f64> = Repo::new(
+            1_000_000.0,
+            0.05,
+            start,
+            end,
+            RepoDirection::Repo,
+            collateral.clone(),
+            Currency::EUR,
+        )
+        .unwrap()
+        .into();
+        assert_eq!(repo.type_name(), "RatesRepo");
+        assert_eq!(repo.currency(), Currency::EUR);
+        assert!(repo.is_financing());
+        assert!((repo.expiry() - 366.0 / 365.0).abs() < 1e-12);
+        assert!(repo.as_repo().is_some());
+
+        let loan: RatesInstrument<f64> = SecuritiesLoan::new(
+            1_000_000.0,
+            0.003,
+            start,
+            end,
+            LendingDirection::Lend,
+            collateral,
+            Currency::EUR,
+        )
+        .unwrap()
+        .into();
+        assert_eq!(loan.type_name(), "RatesSecuritiesLoan");
+        assert!(loan.is_financing());
+        assert!(loan.as_securities_loan().is_some());
+        assert!(loan.as_repo().is_none());
+        assert!(!RatesInstrument::Cap(create_test_cap()).is_financing());
+    }
+
     // ========================================
     // InstrumentTrait Implementation Tests
     // ========================================
@@ -506,4 +801,57 @@ mod tests {
 
         assert!(debug_str.contains("Swap"));
     }
+
+    #[cfg(feature = "serde")]
+    mod serde_tests {
+        use super::*;
+
+        #[test]
+        fn test_rates_instrument_roundtrip() {
+            for instrument in [
+                RatesInstrument::Swap(create_test_swap()),
+                RatesInstrument::Swaption(create_test_swaption()),
+                RatesInstrument::Cap(create_test_cap()),
+                RatesInstrument::Floor(create_test_floor()),
+                RatesInstrument::RangeAccrual(create_test_range_accrual()),
+                RatesInstrument::CallableRangeAccrual(
+                    CallableRangeAccrual::new(create_test_range_accrual(), &[3, 7]).unwrap(),
+                ),
+                RatesInstrument::CmsCapFloor(CmsCapFloor::new(
+                    1_000_000.0,
+                    create_test_cap().schedule().clone(),
+                    0.04,
+                    CmsIndex::new(RateIndex::Euribor6M, 10),
+                    CmsOptionType::Floor,
+                    Currency::EUR,
+                )),
+                RatesInstrument::Repo(
+                    Repo::new(
+                        1_000_000.0,
+                        0.04,
+                        Date::from_ymd(2024, 1, 15).unwrap(),
+                        Date::from_ymd(2024, 4, 15).unwrap(),
+                        RepoDirection::ReverseRepo,
+                        CollateralSchedule::new(vec![
+                            (
+                                Date::from_ymd(2024, 1, 15).unwrap(),
+                                CollateralPosition::new(1_050_000.0, 0.05).unwrap(),
+                            ),
+                            (
+                                Date::from_ymd(2024, 2, 15).unwrap(),
+                                CollateralPosition::new(1_060_000.0, 0.05).unwrap(),
+                            ),
+                        ])
+                        .unwrap(),
+                        Currency::USD,
+                    )
+                    .unwrap(),
+                ),
+            ] {
+                let json = serde_json::to_string(&instrument).unwrap();
+                let parsed: RatesInstrument<f64> = serde_json::from_str(&json).unwrap();
+                assert_eq!(format!("{:?}", parsed), format!("{:?}", instrument));
+            }
+        }
+    }
 }
@@ -2,7 +2,12 @@
 //!
 //! This module provides pricing logic for interest rate derivatives:
 //! - IRS (Interest Rate Swap) valuation
+//! - Swap analytics: par rate, annuity, PV01, accrued interest and carry
 //! - Swaption pricing using Black76 and Bachelier models
+//! - CMS coupons, caps/floors and spread options with replication-based
+//!   convexity adjustments off a swaption volatility cube
+//! - Repo and securities-lending financing trades discounted on the repo
+//!   curve
 //!
 //! # IRS Pricing
 //!
@@ -62,11 +67,16 @@
 //! ```
 
 use num_traits::Float;
-use pricer_core::market_data::curves::{CurveName, CurveSet, YieldCurve};
+use pricer_core::market_data::curves::{CurveEnum, CurveName, CurveSet, YieldCurve};
+use pricer_core::market_data::surfaces::SwaptionVolCube;
 use pricer_core::types::time::{Date, DayCountConvention};
 
-use super::{InterestRateSwap, RateIndex, SwapDirection};
+use super::{
+    CmsCapFloor, CmsIndex, CmsOptionType, CmsSpreadOption, FloatingLeg, InterestRateSwap, Repo,
+    SecuritiesLoan, SwapDirection,
+};
 use crate::analytical::error::AnalyticalError;
+use crate::schedules::Period;
 
 /// Price an Interest Rate Swap.
 ///
@@ -191,7 +201,7 @@ pub fn price_floating_leg<T: Float>(
         .expect("Discount curve not found in curve set");
 
     // Get forward curve based on the index
-    let forward_curve_name = get_curve_name_for_index(swap.floating_leg().index());
+    let forward_curve_name = swap.floating_leg().index().curve_name();
     let forward_curve = curves
         .get(&forward_curve_name)
         .or_else(|| curves.discount_curve())
@@ -226,17 +236,9 @@ pub fn price_floating_leg<T: Float>(
         let year_frac = day_count.year_fraction_dates(period.start(), period.end());
         let year_frac_t = T::from(year_frac).unwrap_or_else(T::zero);
 
-        // Get forward rate from the forward curve
-        let forward_rate = if t_start_t <= T::zero() {
-            // For periods starting on or before valuation date, use zero rate
-            forward_curve
-                .zero_rate(t_end_t)
-                .unwrap_or_else(|_| T::zero())
-        } else {
-            forward_curve
-                .forward_rate(t_start_t, t_end_t)
-                .unwrap_or_else(|_| T::zero())
-        };
+        // Get forward rate from the forward curve, or the CMS swap rate
+        let forward_rate =
+            floating_period_rate(floating_leg, curves, forward_curve, t_start_t, t_end_t);
 
         // Get discount factor
         let df = discount_curve
@@ -278,7 +280,7 @@ pub fn par_swap_rate<T: Float>(
         .discount_curve()
         .expect("Discount curve not found in curve set");
 
-    let forward_curve_name = get_curve_name_for_index(swap.floating_leg().index());
+    let forward_curve_name = swap.floating_leg().index().curve_name();
     let forward_curve = curves
         .get(&forward_curve_name)
         .or_else(|| curves.discount_curve())
@@ -332,15 +334,8 @@ pub fn par_swap_rate<T: Float>(
         let year_frac = floating_day_count.year_fraction_dates(period.start(), period.end());
         let year_frac_t = T::from(year_frac).unwrap_or_else(T::zero);
 
-        let forward_rate = if t_start_t <= T::zero() {
-            forward_curve
-                .zero_rate(t_end_t)
-                .unwrap_or_else(|_| T::zero())
-        } else {
-            forward_curve
-                .forward_rate(t_start_t, t_end_t)
-                .unwrap_or_else(|_| T::zero())
-        };
+        let forward_rate =
+            floating_period_rate(floating_leg, curves, forward_curve, t_start_t, t_end_t);
 
         let df = discount_curve
             .discount_factor(t_payment_t)
@@ -357,14 +352,147 @@ pub fn par_swap_rate<T: Float>(
     }
 }
 
-/// Map rate index to curve name.
-fn get_curve_name_for_index(index: RateIndex) -> CurveName {
-    match index {
-        RateIndex::Sofr => CurveName::Sofr,
-        RateIndex::Tonar => CurveName::Tonar,
-        RateIndex::Euribor3M | RateIndex::Euribor6M => CurveName::Euribor,
-        RateIndex::Sonia => CurveName::Custom("SONIA"),
-        RateIndex::Saron => CurveName::Custom("SARON"),
+/// Calculate the PV01 of a swap.
+///
+/// The change in value for a one basis point move in the fixed rate,
+/// Notional × Annuity × 0.0001, quoted as a positive amount.
+///
+/// # Panics
+///
+/// Panics if the curve set has no discount curve.
+pub fn swap_pv01<T: Float>(
+    swap: &InterestRateSwap<T>,
+    curves: &CurveSet<T>,
+    valuation_date: Date,
+) -> T {
+    let basis_point = T::from(1e-4).unwrap_or_else(T::zero);
+    swap.notional() * swap_annuity(swap, curves, valuation_date) * basis_point
+}
+
+/// Calculate the interest accrued on a swap since the last payment.
+///
+/// For each leg, the coupon of the period running over the valuation date
+/// accrued from the period start, signed by direction (positive when
+/// receivable). The floating coupon uses the period's projected rate, as
+/// the curve set holds no past fixings.
+///
+/// # Panics
+///
+/// Panics if required curves are not found in the curve set.
+pub fn swap_accrued_interest<T: Float>(
+    swap: &InterestRateSwap<T>,
+    curves: &CurveSet<T>,
+    valuation_date: Date,
+) -> T {
+    let start = |period: &Period| period.start();
+    let until = |period: &Period| period.end().min(valuation_date);
+    swap_coupon_accrual(swap, curves, valuation_date, start, until)
+}
+
+/// Calculate the carry of a swap to a horizon date.
+///
+/// The net coupon accruing from the valuation date to `horizon_date` on
+/// both legs, with each floating period at its projected rate and the
+/// curves unchanged: what the position earns (positive) or costs
+/// (negative) by holding it to the horizon, before roll-down.
+///
+/// # Panics
+///
+/// Panics if required curves are not found in the curve set.
+pub fn swap_carry<T: Float>(
+    swap: &InterestRateSwap<T>,
+    curves: &CurveSet<T>,
+    valuation_date: Date,
+    horizon_date: Date,
+) -> T {
+    let from = |period: &Period| period.start().max(valuation_date);
+    let until = |period: &Period| period.end().min(horizon_date);
+    swap_coupon_accrual(swap, curves, valuation_date, from, until)
+}
+
+/// Net coupon of both legs accrued between `from` and `until` of each
+/// period, signed by swap direction.
+fn swap_coupon_accrual<T: Float>(
+    swap: &InterestRateSwap<T>,
+    curves: &CurveSet<T>,
+    valuation_date: Date,
+    from: impl Fn(&Period) -> Date,
+    until: impl Fn(&Period) -> Date,
+) -> T {
+    let forward_curve = curves
+        .get(&swap.floating_leg().index().curve_name())
+        .or_else(|| curves.discount_curve())
+        .expect("Forward curve not found in curve set");
+    let accrual = |period: &Period, day_count: DayCountConvention| {
+        let (from, until) = (from(period), until(period));
+        if period.end() > valuation_date && until > from {
+            T::from(day_count.year_fraction_dates(from, until)).unwrap_or_else(T::zero)
+        } else {
+            T::zero()
+        }
+    };
+
+    let fixed_leg = swap.fixed_leg();
+    let fixed = fixed_leg
+        .schedule()
+        .periods()
+        .iter()
+        .fold(T::zero(), |sum, period| {
+            sum + fixed_leg.fixed_rate() * accrual(period, fixed_leg.day_count())
+        });
+
+    let floating_leg = swap.floating_leg();
+    let floating = floating_leg
+        .schedule()
+        .periods()
+        .iter()
+        .fold(T::zero(), |sum, period| {
+            let year_frac = accrual(period, floating_leg.day_count());
+            if year_frac == T::zero() {
+                return sum;
+            }
+            let t_start = DayCountConvention::ActualActual365
+                .year_fraction_dates(valuation_date, period.start());
+            let t_end = DayCountConvention::ActualActual365
+                .year_fraction_dates(valuation_date, period.end());
+            let rate = floating_period_rate(
+                floating_leg,
+                curves,
+                forward_curve,
+                T::from(t_start).unwrap_or_else(T::zero),
+                T::from(t_end).unwrap_or_else(T::zero),
+            );
+            sum + (rate + floating_leg.spread()) * year_frac
+        });
+
+    let direction = swap.direction();
+    swap.notional()
+        * (direction.fixed_multiplier::<T>() * fixed
+            + direction.floating_multiplier::<T>() * floating)
+}
+
+/// Rate of one floating leg period before spread.
+///
+/// This is the index forward rate, or for a CMS leg the forward swap rate
+/// of its CMS index fixed at the period start, without convexity
+/// adjustment (see [`price_cms_floating_leg`]).
+fn floating_period_rate<T: Float>(
+    floating_leg: &FloatingLeg<T>,
+    curves: &CurveSet<T>,
+    forward_curve: &CurveEnum<T>,
+    t_start: T,
+    t_end: T,
+) -> T {
+    if let Some(cms_index) = floating_leg.cms_index() {
+        return cms_forward_rate(&cms_index, curves, t_start);
+    }
+    if t_start <= T::zero() {
+        // For periods starting on or before valuation date, use zero rate
+        forward_curve.zero_rate(t_end).unwrap_or_else(|_| T::zero())
+    } else {
+        forward_curve
+            .forward_rate(t_start, t_end)
+            .unwrap_or_else(|_| T::zero())
     }
 }
 
@@ -411,7 +539,7 @@ pub fn price_swaption_black76<T: Float>(
     let forward = par_swap_rate(underlying, curves, valuation_date);
 
     // Calculate swap annuity
-    let annuity = calculate_annuity(underlying, curves, valuation_date);
+    let annuity = swap_annuity(underlying, curves, valuation_date);
 
     // Black76 formula
     let sqrt_t = expiry.sqrt();
@@ -486,7 +614,7 @@ pub fn price_swaption_bachelier<T: Float>(
     let forward = par_swap_rate(underlying, curves, valuation_date);
 
     // Calculate swap annuity
-    let annuity = calculate_annuity(underlying, curves, valuation_date);
+    let annuity = swap_annuity(underlying, curves, valuation_date);
 
     // Bachelier formula
     let sqrt_t = expiry.sqrt();
@@ -512,10 +640,17 @@ pub fn price_swaption_bachelier<T: Float>(
     Ok(price)
 }
 
-/// Calculate the annuity (PV01) of a swap.
+/// Calculate the annuity of a swap per unit notional.
 ///
 /// Annuity = Sum_i(DF_i × YearFrac_i)
-fn calculate_annuity<T: Float>(
+///
+/// over the fixed leg periods not yet ended: the value of receiving one
+/// unit of fixed rate.
+///
+/// # Panics
+///
+/// Panics if the curve set has no discount curve.
+pub fn swap_annuity<T: Float>(
     swap: &InterestRateSwap<T>,
     curves: &CurveSet<T>,
     valuation_date: Date,
@@ -551,10 +686,589 @@ fn calculate_annuity<T: Float>(
     annuity
 }
 
+// ========================================
+// CMS Pricing
+// ========================================
+
+/// Simpson intervals on each side of the forward in CMS replication.
+const CMS_REPLICATION_STEPS: usize = 200;
+
+/// Standard deviations of the swap rate covered by CMS replication.
+const CMS_REPLICATION_WIDTH: f64 = 10.0;
+
+/// Forward swap rate of a CMS index fixing `fixing` years from valuation.
+///
+/// The underlying swap starts at the fixing and pays its fixed leg at the
+/// index's fixed frequency; the floating leg is projected off the curve of
+/// the index (or the discount curve if absent) and all flows are discounted
+/// on the discount curve. Fixings in the past use a spot-starting swap.
+///
+/// # Panics
+///
+/// Panics if the discount curve is not found in the curve set.
+pub fn cms_forward_rate<T: Float>(cms_index: &CmsIndex, curves: &CurveSet<T>, fixing: T) -> T {
+    cms_swap(cms_index, curves, fixing).0
+}
+
+/// Convexity adjustment of a CMS rate fixed at `fixing` and paid at
+/// `payment` (years from valuation).
+///
+/// The adjusted CMS rate is `cms_forward_rate + cms_convexity_adjustment`.
+/// The adjustment is obtained by replicating the CMS payoff with swaptions
+/// of every strike priced off the cube's smile; see [`price_cms_cap_floor`].
+///
+/// # Panics
+///
+/// Panics if the discount curve is not found in the curve set.
+pub fn cms_convexity_adjustment<T: Float>(
+    cms_index: &CmsIndex,
+    curves: &CurveSet<T>,
+    cube: &SwaptionVolCube<T>,
+    fixing: T,
+    payment: T,
+) -> T {
+    let replication = CmsReplication::new(cms_index, curves, cube, fixing, payment);
+    replication.adjusted_rate() - replication.forward
+}
+
+/// Price a CMS floating leg with convexity-adjusted CMS rates.
+///
+/// PV = Sum_i(Notional × (AdjustedCmsRate_i + Spread) × YearFraction_i × DF_i)
+///
+/// Legs that do not reference a [`CmsIndex`] are priced by
+/// [`price_floating_leg`], which also prices CMS legs at their unadjusted
+/// forward swap rates.
+///
+/// # Arguments
+///
+/// * `swap` - Swap whose floating leg pays a CMS rate
+/// * `curves` - Curve set containing discount and forward curves
+/// * `cube` - Swaption normal volatility cube
+/// * `valuation_date` - The valuation date
+///
+/// # Returns
+///
+/// Present value of the floating leg.
+pub fn price_cms_floating_leg<T: Float>(
+    swap: &InterestRateSwap<T>,
+    curves: &CurveSet<T>,
+    cube: &SwaptionVolCube<T>,
+    valuation_date: Date,
+) -> T {
+    let floating_leg = swap.floating_leg();
+    let Some(cms_index) = floating_leg.cms_index() else {
+        return price_floating_leg(swap, curves, valuation_date);
+    };
+
+    let notional = swap.notional();
+    let spread = floating_leg.spread();
+    let day_count = floating_leg.day_count();
+
+    let mut pv = T::zero();
+    for period in floating_leg.schedule().periods() {
+        if period.end() <= valuation_date {
+            continue;
+        }
+
+        let (t_fixing, t_payment) = cms_period_times(period, valuation_date);
+        let year_frac = T::from(day_count.year_fraction_dates(period.start(), period.end()))
+            .unwrap_or_else(T::zero);
+
+        let replication = CmsReplication::new(&cms_index, curves, cube, t_fixing, t_payment);
+        let rate = replication.adjusted_rate();
+
+        pv = pv + notional * (rate + spread) * year_frac * replication.payment_df;
+    }
+
+    pv
+}
+
+/// Price a CMS cap or floor by static replication.
+///
+/// Under the annuity measure of the underlying swap, a payoff `f(S)` paid
+/// at `T_p` is worth `A(0) × E[f(S) × P(T_p)/A(S)]`. The linear terminal
+/// swap rate model maps
+///
+/// ```text
+/// P(T_p)/A(S) ≈ α(S) = P(0,T_p)/A(0) + a × (S - S0)
+/// ```
+///
+/// with slope `a` from a flat-curve annuity, so that each caplet is
+///
+/// ```text
+/// A(0) × [α(K) × C(K) + 2a × ∫_K^∞ C(x) dx]
+/// ```
+///
+/// where `C(x)` is the undiscounted payer swaption on the CMS tenor priced
+/// with the cube's normal volatility at strike `x` (floorlets are the
+/// receiver-side analogue). Periods already ended are skipped; periods
+/// already fixed pay intrinsic value on the current forward.
+///
+/// # Arguments
+///
+/// * `cap_floor` - The CMS cap or floor
+/// * `curves` - Curve set containing discount and forward curves
+/// * `cube` - Swaption normal volatility cube
+/// * `valuation_date` - The valuation date
+///
+/// # Returns
+///
+/// Present value of the cap or floor.
+pub fn price_cms_cap_floor<T: Float>(
+    cap_floor: &CmsCapFloor<T>,
+    curves: &CurveSet<T>,
+    cube: &SwaptionVolCube<T>,
+    valuation_date: Date,
+) -> T {
+    let cms_index = cap_floor.cms_index();
+    let strike = cap_floor.strike();
+    let day_count = cap_floor.day_count();
+
+    let mut pv = T::zero();
+    for period in cap_floor.schedule().periods() {
+        if period.end() <= valuation_date {
+            continue;
+        }
+
+        let (t_fixing, t_payment) = cms_period_times(period, valuation_date);
+        let year_frac = T::from(day_count.year_fraction_dates(period.start(), period.end()))
+            .unwrap_or_else(T::zero);
+
+        let replication = CmsReplication::new(&cms_index, curves, cube, t_fixing, t_payment);
+        let optionlet = match cap_floor.option_type() {
+            CmsOptionType::Cap => replication.caplet(strike),
+            CmsOptionType::Floor => replication.floorlet(strike),
+        };
+
+        pv = pv + optionlet * year_frac;
+    }
+
+    cap_floor.notional() * pv
+}
+
+/// Price a CMS spread option.
+///
+/// Each period is priced with the Bachelier formula on the spread of the
+/// two convexity-adjusted CMS rates, whose normal volatility combines the
+/// cube's ATM volatilities of both tenors:
+///
+/// ```text
+/// σ_spread² = σ_long² + σ_short² - 2ρ × σ_long × σ_short
+/// ```
+///
+/// # Arguments
+///
+/// * `option` - The CMS spread option
+/// * `curves` - Curve set containing discount and forward curves
+/// * `cube` - Swaption normal volatility cube
+/// * `correlation` - Correlation of the two CMS rates
+/// * `valuation_date` - The valuation date
+///
+/// # Returns
+///
+/// Present value of the spread option, or
+/// `AnalyticalError::InvalidCorrelation` if `correlation` is outside [-1, 1].
+pub fn price_cms_spread_option<T: Float>(
+    option: &CmsSpreadOption<T>,
+    curves: &CurveSet<T>,
+    cube: &SwaptionVolCube<T>,
+    correlation: T,
+    valuation_date: Date,
+) -> Result<T, AnalyticalError> {
+    if correlation.abs() > T::one() || correlation.is_nan() {
+        return Err(AnalyticalError::InvalidCorrelation {
+            correlation: correlation.to_f64().unwrap_or(f64::NAN),
+        });
+    }
+
+    let long_index = option.long_index();
+    let short_index = option.short_index();
+    let strike = option.strike();
+    let day_count = option.day_count();
+    let two = T::one() + T::one();
+
+    let mut pv = T::zero();
+    for period in option.schedule().periods() {
+        if period.end() <= valuation_date {
+            continue;
+        }
+
+        let (t_fixing, t_payment) = cms_period_times(period, valuation_date);
+        let year_frac = T::from(day_count.year_fraction_dates(period.start(), period.end()))
+            .unwrap_or_else(T::zero);
+
+        let long = CmsReplication::new(&long_index, curves, cube, t_fixing, t_payment);
+        let short = CmsReplication::new(&short_index, curves, cube, t_fixing, t_payment);
+
+        let expiry = t_fixing.max(T::zero());
+        let vol_long = long.atm_vol();
+        let vol_short = short.atm_vol();
+        let variance = (vol_long * vol_long + vol_short * vol_short
+            - two * correlation * vol_long * vol_short)
+            .max(T::zero());
+        let std_dev = (variance * expiry).sqrt();
+
+        let spread_forward = long.adjusted_rate() - short.adjusted_rate();
+        let optionlet = bachelier(
+            spread_forward,
+            strike,
+            std_dev,
+            option.option_type() == CmsOptionType::Cap,
+        );
+
+        pv = pv + optionlet * year_frac * long.payment_df;
+    }
+
+    Ok(option.notional() * pv)
+}
+
+/// Price a repo or reverse repo.
+///
+/// Both cash legs are discounted on the repo curve ([`CurveName::Repo`]),
+/// falling back to the discount curve when the set has none:
+///
+/// ```text
+/// PV = ±(C × (1 + r × τ) × DF(T_end) − C × DF(T_start))
+/// ```
+///
+/// signed positive for the cash lender. A repo starting on the valuation
+/// date still carries its opening leg; once the start date has passed only
+/// the repurchase leg remains.
+///
+/// # Arguments
+///
+/// * `repo` - The repo
+/// * `curves` - Curve set containing the repo or discount curve
+/// * `valuation_date` - The valuation date
+///
+/// # Returns
+///
+/// Present value of the repo, zero after its end date.
+///
+/// # Panics
+///
+/// Panics if the curve set has neither a repo nor a discount curve.
+pub fn price_repo<T: Float>(repo: &Repo<T>, curves: &CurveSet<T>, valuation_date: Date) -> T {
+    if repo.end_date() <= valuation_date {
+        return T::zero();
+    }
+    let curve = repo_curve(curves);
+
+    let mut pv = repo.repurchase_amount() * financing_df(curve, valuation_date, repo.end_date());
+    if repo.start_date() >= valuation_date {
+        pv = pv - repo.cash_amount() * financing_df(curve, valuation_date, repo.start_date());
+    }
+    repo.direction().sign::<T>() * pv
+}
+
+/// Repo rate at which a repo on these terms is worth zero.
+///
+/// The simple forward rate of the repo curve from the later of start and
+/// valuation date to the end date, over the repo's accrual fraction τ.
+///
+/// # Panics
+///
+/// Panics if the curve set has neither a repo nor a discount curve.
+pub fn par_repo_rate<T: Float>(repo: &Repo<T>, curves: &CurveSet<T>, valuation_date: Date) -> T {
+    let curve = repo_curve(curves);
+    let start = repo.start_date().max(valuation_date);
+    let growth = financing_df(curve, valuation_date, start)
+        / financing_df(curve, valuation_date, repo.end_date());
+    let tau = repo.year_fraction();
+    if tau > T::zero() {
+        (growth - T::one()) / tau
+    } else {
+        T::zero()
+    }
+}
+
+/// Price a securities loan.
+///
+/// The securities lent come back at the end date, so the loan is worth its
+/// fee discounted on the repo curve (or the discount curve when the set
+/// has none), signed positive for the lender.
+///
+/// # Arguments
+///
+/// * `loan` - The securities loan
+/// * `curves` - Curve set containing the repo or discount curve
+/// * `valuation_date` - The valuation date
+///
+/// # Returns
+///
+/// Present value of the outstanding fee, zero after the end date.
+///
+/// # Panics
+///
+/// Panics if the curve set has neither a repo nor a discount curve.
+pub fn price_securities_loan<T: Float>(
+    loan: &SecuritiesLoan<T>,
+    curves: &CurveSet<T>,
+    valuation_date: Date,
+) -> T {
+    if loan.end_date() <= valuation_date {
+        return T::zero();
+    }
+    let df = financing_df(repo_curve(curves), valuation_date, loan.end_date());
+    loan.direction().sign::<T>() * loan.fee() * df
+}
+
+/// Repo curve of the set, or its discount curve.
+fn repo_curve<T: Float>(curves: &CurveSet<T>) -> &CurveEnum<T> {
+    curves
+        .get(&CurveName::Repo)
+        .or_else(|| curves.discount_curve())
+        .expect("Repo or discount curve not found in curve set")
+}
+
+/// Discount factor to `date` on a financing curve.
+fn financing_df<T: Float>(curve: &CurveEnum<T>, valuation_date: Date, date: Date) -> T {
+    let t = DayCountConvention::ActualActual365.year_fraction_dates(valuation_date, date);
+    curve
+        .discount_factor(T::from(t).unwrap_or_else(T::zero))
+        .unwrap_or_else(|_| T::one())
+}
+
+/// Fixing and payment times of a CMS period in years from valuation.
+fn cms_period_times<T: Float>(period: &Period, valuation_date: Date) -> (T, T) {
+    let t_fixing =
+        DayCountConvention::ActualActual365.year_fraction_dates(valuation_date, period.start());
+    let t_payment =
+        DayCountConvention::ActualActual365.year_fraction_dates(valuation_date, period.payment());
+    (
+        T::from(t_fixing).unwrap_or_else(T::zero),
+        T::from(t_payment).unwrap_or_else(T::zero),
+    )
+}
+
+/// Forward swap rate and annuity of the swap underlying a CMS fixing.
+fn cms_swap<T: Float>(cms_index: &CmsIndex, curves: &CurveSet<T>, fixing: T) -> (T, T) {
+    let discount_curve = curves
+        .discount_curve()
+        .expect("Discount curve not found in curve set");
+    let forward_curve = curves
+        .get(&cms_index.index().curve_name())
+        .or_else(|| curves.discount_curve())
+        .expect("Forward curve not found in curve set");
+
+    let frequency = cms_index.fixed_frequency().periods_per_year();
+    let tau = T::one() / T::from(frequency).unwrap_or_else(T::one);
+    let start = fixing.max(T::zero());
+
+    let mut annuity = T::zero();
+    let mut floating_pv = T::zero();
+    let mut previous = start;
+    for i in 1..=cms_index.tenor_years() * frequency {
+        let t = start + tau * T::from(i).unwrap_or_else(T::zero);
+        let df = discount_curve
+            .discount_factor(t)
+            .unwrap_or_else(|_| T::one());
+        let growth = forward_curve
+            .discount_factor(previous)
+            .unwrap_or_else(|_| T::one())
+            / forward_curve
+                .discount_factor(t)
+                .unwrap_or_else(|_| T::one());
+
+        annuity = annuity + tau * df;
+        floating_pv = floating_pv + (growth - T::one()) * df;
+        previous = t;
+    }
+
+    if annuity > T::zero() {
+        (floating_pv / annuity, annuity)
+    } else {
+        (T::zero(), T::zero())
+    }
+}
+
+/// Undiscounted Bachelier option price.
+fn bachelier<T: Float>(forward: T, strike: T, std_dev: T, is_call: bool) -> T {
+    use crate::analytical::distributions::{norm_cdf, norm_pdf};
+
+    let moneyness = if is_call {
+        forward - strike
+    } else {
+        strike - forward
+    };
+    if std_dev <= T::zero() {
+        return moneyness.max(T::zero());
+    }
+    let d = moneyness / std_dev;
+    moneyness * norm_cdf(d) + std_dev * norm_pdf(d)
+}
+
+/// Static replication of a CMS fixing in the linear terminal swap rate model.
+struct CmsReplication<'a, T: Float> {
+    cube: &'a SwaptionVolCube<T>,
+    /// Forward swap rate S0
+    forward: T,
+    /// Annuity A(0) of the underlying swap
+    annuity: T,
+    /// Discount factor P(0, T_p) to the payment date
+    payment_df: T,
+    /// Slope `a` of the annuity mapping α(S)
+    slope: T,
+    /// Fixing time in years, floored at zero
+    expiry: T,
+    /// Underlying swap tenor in years
+    tenor: T,
+}
+
+impl<'a, T: Float> CmsReplication<'a, T> {
+    fn new(
+        cms_index: &CmsIndex,
+        curves: &CurveSet<T>,
+        cube: &'a SwaptionVolCube<T>,
+        fixing: T,
+        payment: T,
+    ) -> Self {
+        let (forward, annuity) = cms_swap(cms_index, curves, fixing);
+        let payment_df = curves
+            .discount_curve()
+            .expect("Discount curve not found in curve set")
+            .discount_factor(payment.max(T::zero()))
+            .unwrap_or_else(|_| T::one());
+
+        // With a flat curve at the swap rate, P(T_p)/A(S) ∝ (1 + S/q)^(-δq) / a(S)
+        // where a(S) = Sum_i (1/q)(1 + S/q)^(-i) and δ is the payment delay.
+        let frequency = cms_index.fixed_frequency().periods_per_year();
+        let q = T::from(frequency).unwrap_or_else(T::one);
+        let base = T::one() + forward / q;
+        let mut flat_annuity = T::zero();
+        let mut flat_annuity_slope = T::zero();
+        for i in 1..=cms_index.tenor_years() * frequency {
+            let i = T::from(i).unwrap_or_else(T::zero);
+            flat_annuity = flat_annuity + base.powf(-i) / q;
+            flat_annuity_slope = flat_annuity_slope - i * base.powf(-i - T::one()) / (q * q);
+        }
+        let delay = (payment - fixing.max(T::zero())).max(T::zero());
+        let log_slope = -delay / base - flat_annuity_slope / flat_annuity;
+        let alpha = if annuity > T::zero() {
+            payment_df / annuity
+        } else {
+            T::zero()
+        };
+
+        Self {
+            cube,
+            forward,
+            annuity,
+            payment_df,
+            slope: alpha * log_slope,
+            expiry: fixing.max(T::zero()),
+            tenor: T::from(cms_index.tenor_years()).unwrap_or_else(T::one),
+        }
+    }
+
+    /// α(S0) = P(0, T_p)/A(0)
+    fn alpha(&self) -> T {
+        if self.annuity > T::zero() {
+            self.payment_df / self.annuity
+        } else {
+            T::zero()
+        }
+    }
+
+    /// α(K) = α(S0) + a (K - S0)
+    fn alpha_at(&self, strike: T) -> T {
+        self.alpha() + self.slope * (strike - self.forward)
+    }
+
+    fn atm_vol(&self) -> T {
+        self.cube.atm_volatility(self.expiry, self.tenor)
+    }
+
+    /// Standard deviation of the swap rate for a swaption struck at `strike`
+    fn std_dev(&self, strike: T) -> T {
+        self.cube
+            .volatility(self.expiry, self.tenor, strike - self.forward)
+            * self.expiry.sqrt()
+    }
+
+    /// Undiscounted payer swaption under the annuity measure
+    fn payer(&self, strike: T) -> T {
+        bachelier(self.forward, strike, self.std_dev(strike), true)
+    }
+
+    /// Undiscounted receiver swaption under the annuity measure
+    fn receiver(&self, strike: T) -> T {
+        bachelier(self.forward, strike, self.std_dev(strike), false)
+    }
+
+    /// Half-width of the replicated strike range around the forward
+    fn width(&self) -> T {
+        T::from(CMS_REPLICATION_WIDTH).unwrap_or_else(T::one) * self.std_dev(self.forward)
+    }
+
+    /// Variance of the swap rate under the annuity measure, replicated as
+    /// 2 × (∫ receivers below S0 + ∫ payers above S0)
+    fn variance(&self) -> T {
+        let width = self.width();
+        let two = T::one() + T::one();
+        two * (simpson(|k| self.receiver(k), self.forward - width, self.forward)
+            + simpson(|k| self.payer(k), self.forward, self.forward + width))
+    }
+
+    /// Convexity-adjusted CMS rate S0 + (a/α(S0)) × Var(S)
+    fn adjusted_rate(&self) -> T {
+        let alpha = self.alpha();
+        if alpha <= T::zero() {
+            return self.forward;
+        }
+        self.forward + self.slope / alpha * self.variance()
+    }
+
+    /// Caplet value at valuation per unit of notional × accrual
+    fn caplet(&self, strike: T) -> T {
+        let upper = self.forward + self.width();
+        let two = T::one() + T::one();
+        let tail = if strike < upper {
+            simpson(|k| self.payer(k), strike, upper)
+        } else {
+            T::zero()
+        };
+        self.annuity * (self.alpha_at(strike) * self.payer(strike) + two * self.slope * tail)
+    }
+
+    /// Floorlet value at valuation per unit of notional × accrual
+    fn floorlet(&self, strike: T) -> T {
+        let lower = self.forward - self.width();
+        let two = T::one() + T::one();
+        let tail = if strike > lower {
+            simpson(|k| self.receiver(k), lower, strike)
+        } else {
+            T::zero()
+        };
+        self.annuity * (self.alpha_at(strike) * self.receiver(strike) - two * self.slope * tail)
+    }
+}
+
+/// Composite Simpson integral of `f` over [a, b].
+fn simpson<T: Float>(f: impl Fn(T) -> T, a: T, b: T) -> T {
+    if b <= a {
+        return T::zero();
+    }
+    let n = CMS_REPLICATION_STEPS;
+    let h = (b - a) / T::from(n).unwrap_or_else(T::one);
+    let two = T::one() + T::one();
+    let four = two + two;
+
+    let mut sum = f(a) + f(b);
+    for i in 1..n {
+        let x = a + h * T::from(i).unwrap_or_else(T::zero);
+        sum = sum + if i % 2 == 1 { four } else { two } * f(x);
+    }
+    sum * h / (two + T::one())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::instruments::rates::{FixedLeg, FloatingLeg, Swaption, SwaptionStyle, SwaptionType};
+    use crate::instruments::rates::{
+        CollateralPosition, CollateralSchedule, FixedLeg, FloatingLeg, LendingDirection, RateIndex,
+        RepoDirection, Swaption, SwaptionStyle, SwaptionType,
+    };
     use crate::schedules::{Frequency, ScheduleBuilder};
     use pricer_core::market_data::curves::CurveEnum;
     use pricer_core::types::Currency;
@@ -740,6 +1454,63 @@ mod tests {
         assert!((par_rate - 0.035).abs() < 0.001);
     }
 
+    #[test]
+    fn test_swap_annuity_and_pv01() {
+        let swap = create_test_swap();
+        let curves = create_test_curves();
+        let valuation_date = Date::from_ymd(2024, 1, 15).unwrap();
+
+        // Four semi-annual periods of 0.5 at ~3% discounting
+        let annuity = swap_annuity(&swap, &curves, valuation_date);
+        assert!(annuity > 1.85 && annuity < 2.0);
+        assert_eq!(swap.annuity(&curves, valuation_date), annuity);
+
+        let pv01 = swap_pv01(&swap, &curves, valuation_date);
+        assert!((pv01 - 1_000_000.0 * annuity * 1e-4).abs() < 1e-9);
+        assert_eq!(swap.pv01(&curves, valuation_date), pv01);
+
+        // Off par the PV is the rate difference times the annuity
+        let pv = price_irs(&swap, &curves, valuation_date);
+        let par_rate = swap.par_rate(&curves, valuation_date);
+        assert!((pv - (par_rate - swap.fixed_rate()) * 1e4 * pv01).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_swap_accrued_interest() {
+        let swap = create_test_swap();
+        let curves = create_test_curves();
+
+        // Two months into the first period: 60 days on both 30/360 and Act/360
+        let valuation_date = Date::from_ymd(2024, 3, 15).unwrap();
+        let accrued = swap_accrued_interest(&swap, &curves, valuation_date);
+        let expected = 1_000_000.0 * (0.035 - 0.03) * 60.0 / 360.0;
+        assert!((accrued - expected).abs() < 1e-6);
+        assert_eq!(swap.accrued_interest(&curves, valuation_date), accrued);
+
+        // Nothing accrued on a payment date
+        let payment_date = Date::from_ymd(2024, 7, 15).unwrap();
+        assert_eq!(swap_accrued_interest(&swap, &curves, payment_date), 0.0);
+    }
+
+    #[test]
+    fn test_swap_carry() {
+        let swap = create_test_swap();
+        let curves = create_test_curves();
+        let valuation_date = Date::from_ymd(2024, 1, 15).unwrap();
+        let horizon = Date::from_ymd(2024, 7, 15).unwrap();
+
+        // Payer earns floating over 182 Act/360 days, pays fixed over 180 30/360 days
+        let carry = swap_carry(&swap, &curves, valuation_date, horizon);
+        let expected = 1_000_000.0 * (0.035 * 182.0 / 360.0 - 0.03 * 180.0 / 360.0);
+        assert!((carry - expected).abs() < 1e-6);
+        assert_eq!(swap.carry(&curves, valuation_date, horizon), carry);
+
+        assert_eq!(
+            swap_carry(&swap, &curves, valuation_date, valuation_date),
+            0.0
+        );
+    }
+
     // ========================================
     // Swaption Pricing Tests
     // ========================================
@@ -817,7 +1588,7 @@ mod tests {
 
         // Calculate forward swap rate
         let forward = par_swap_rate(&swap, &curves, valuation_date);
-        let annuity = calculate_annuity(&swap, &curves, valuation_date);
+        let annuity = swap_annuity(&swap, &curves, valuation_date);
         let notional = swap.notional();
 
         // Payer - Receiver = Notional × Annuity × (Forward - Strike)
@@ -852,4 +1623,342 @@ mod tests {
         let result = price_swaption_black76(&swaption, &curves, -0.1, valuation_date);
         assert!(result.is_err());
     }
+
+    // ========================================
+    // CMS Tests
+    // ========================================
+
+    fn create_cms_schedule() -> crate::schedules::Schedule {
+        ScheduleBuilder::new()
+            .start(Date::from_ymd(2025, 1, 15).unwrap())
+            .end(Date::from_ymd(2030, 1, 15).unwrap())
+            .frequency(Frequency::Annual)
+            .day_count(DayCountConvention::Thirty360)
+            .build()
+            .unwrap()
+    }
+
+    fn create_cms_swap(spread: f64) -> InterestRateSwap<f64> {
+        let schedule = create_cms_schedule();
+        let fixed_leg = FixedLeg::new(schedule.clone(), 0.03, DayCountConvention::Thirty360);
+        let floating_leg = FloatingLeg::new(
+            schedule,
+            spread,
+            RateIndex::Sofr,
+            DayCountConvention::Thirty360,
+        )
+        .with_cms_index(CmsIndex::new(RateIndex::Sofr, 10));
+
+        InterestRateSwap::new(
+            1_000_000.0,
+            fixed_leg,
+            floating_leg,
+            Currency::USD,
+            SwapDirection::ReceiveFixed,
+        )
+    }
+
+    fn create_cms_cap_floor(strike: f64, option_type: CmsOptionType) -> CmsCapFloor<f64> {
+        CmsCapFloor::new(
+            1_000_000.0,
+            create_cms_schedule(),
+            strike,
+            CmsIndex::new(RateIndex::Sofr, 10),
+            option_type,
+            Currency::USD,
+        )
+        .with_day_count(DayCountConvention::Thirty360)
+    }
+
+    fn create_cms_spread_option(strike: f64) -> CmsSpreadOption<f64> {
+        CmsSpreadOption::new(
+            1_000_000.0,
+            create_cms_schedule(),
+            strike,
+            CmsIndex::new(RateIndex::Sofr, 10),
+            CmsIndex::new(RateIndex::Sofr, 2),
+            CmsOptionType::Cap,
+            Currency::USD,
+        )
+        .with_day_count(DayCountConvention::Thirty360)
+    }
+
+    fn create_flat_curves() -> CurveSet<f64> {
+        let mut curves = CurveSet::new();
+        curves.insert(CurveName::Discount, CurveEnum::flat(0.03));
+        curves.set_discount_curve(CurveName::Discount);
+        curves
+    }
+
+    #[test]
+    fn test_cms_forward_rate_on_flat_curve() {
+        let curves = create_flat_curves();
+        let cms10y = CmsIndex::new(RateIndex::Sofr, 10);
+
+        // Annual par rate of a flat continuously compounded curve
+        let expected = 0.03_f64.exp() - 1.0;
+        assert!((cms_forward_rate(&cms10y, &curves, 2.0) - expected).abs() < 1e-12);
+        assert!((cms_forward_rate(&cms10y, &curves, -1.0) - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_cms_convexity_adjustment() {
+        let curves = create_flat_curves();
+        let cms10y = CmsIndex::new(RateIndex::Sofr, 10);
+        let cms2y = CmsIndex::new(RateIndex::Sofr, 2);
+        let cube = SwaptionVolCube::flat(0.01).unwrap();
+
+        let adjustment = cms_convexity_adjustment(&cms10y, &curves, &cube, 5.0, 5.0);
+        assert!(adjustment > 0.0 && adjustment < 0.005, "{}", adjustment);
+
+        // Longer tenors are more convex
+        assert!(cms_convexity_adjustment(&cms2y, &curves, &cube, 5.0, 5.0) < adjustment);
+
+        // Paying later than the fixing offsets part of the convexity
+        assert!(cms_convexity_adjustment(&cms10y, &curves, &cube, 5.0, 6.0) < adjustment);
+
+        // The replicated variance is σ²T, so the adjustment scales with σ²
+        let doubled = SwaptionVolCube::flat(0.02).unwrap();
+        let ratio = cms_convexity_adjustment(&cms10y, &curves, &doubled, 5.0, 5.0) / adjustment;
+        assert!((ratio - 4.0).abs() < 1e-6, "{}", ratio);
+
+        // Fixed rates carry no adjustment
+        assert_eq!(
+            cms_convexity_adjustment(&cms10y, &curves, &cube, 0.0, 0.0),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_price_cms_floating_leg() {
+        let curves = create_flat_curves();
+        let cube = SwaptionVolCube::flat(0.01).unwrap();
+        let valuation_date = Date::from_ymd(2024, 1, 15).unwrap();
+        let swap = create_cms_swap(0.0);
+
+        let unadjusted = price_floating_leg(&swap, &curves, valuation_date);
+        let adjusted = price_cms_floating_leg(&swap, &curves, &cube, valuation_date);
+
+        // Five annual coupons on the ~3.05% 10Y swap rate
+        assert!(
+            unadjusted > 120_000.0 && unadjusted < 150_000.0,
+            "{}",
+            unadjusted
+        );
+        assert!(adjusted > unadjusted);
+
+        // Non-CMS legs are priced as ordinary floating legs
+        let swap = create_test_swap();
+        assert_eq!(
+            price_cms_floating_leg(&swap, &curves, &cube, valuation_date),
+            price_floating_leg(&swap, &curves, valuation_date)
+        );
+    }
+
+    #[test]
+    fn test_cms_cap_floor_parity() {
+        let curves = create_flat_curves();
+        let cube = SwaptionVolCube::new(
+            &[1.0, 10.0],
+            &[2.0, 10.0],
+            &[-0.02, 0.0, 0.02],
+            vec![
+                vec![vec![0.012, 0.009, 0.011], vec![0.011, 0.008, 0.010]],
+                vec![vec![0.011, 0.008, 0.010], vec![0.010, 0.007, 0.009]],
+            ],
+        )
+        .unwrap();
+        let valuation_date = Date::from_ymd(2024, 1, 15).unwrap();
+        let strike = 0.035;
+
+        let cap = price_cms_cap_floor(
+            &create_cms_cap_floor(strike, CmsOptionType::Cap),
+            &curves,
+            &cube,
+            valuation_date,
+        );
+        let floor = price_cms_cap_floor(
+            &create_cms_cap_floor(strike, CmsOptionType::Floor),
+            &curves,
+            &cube,
+            valuation_date,
+        );
+        assert!(cap > 0.0 && floor > 0.0);
+
+        // Cap - Floor = CMS leg - fixed leg at the strike
+        let schedule = create_cms_schedule();
+        let swap = InterestRateSwap::new(
+            1_000_000.0,
+            FixedLeg::new(schedule.clone(), strike, DayCountConvention::Thirty360),
+            create_cms_swap(0.0).floating_leg().clone(),
+            Currency::USD,
+            SwapDirection::PayFixed,
+        );
+        let forward_value = price_cms_floating_leg(&swap, &curves, &cube, valuation_date)
+            - price_fixed_leg(&swap, &curves, valuation_date);
+        assert!(
+            (cap - floor - forward_value).abs() < 1.0,
+            "cap {} floor {} forward {}",
+            cap,
+            floor,
+            forward_value
+        );
+
+        // Higher volatility raises both
+        let high = SwaptionVolCube::flat(0.015).unwrap();
+        let high_cap = price_cms_cap_floor(
+            &create_cms_cap_floor(strike, CmsOptionType::Cap),
+            &curves,
+            &high,
+            valuation_date,
+        );
+        assert!(high_cap > cap);
+    }
+
+    #[test]
+    fn test_price_cms_spread_option() {
+        let curves = create_flat_curves();
+        let cube = SwaptionVolCube::flat(0.01).unwrap();
+        let valuation_date = Date::from_ymd(2024, 1, 15).unwrap();
+        let option = create_cms_spread_option(0.0);
+
+        let low = price_cms_spread_option(&option, &curves, &cube, 0.2, valuation_date).unwrap();
+        let high = price_cms_spread_option(&option, &curves, &cube, 0.9, valuation_date).unwrap();
+        // More correlated rates leave less spread volatility
+        assert!(low > high && high > 0.0);
+
+        // Deep in the money the option is worth the adjusted spread forward
+        let deep = create_cms_spread_option(-0.2);
+        let pv = price_cms_spread_option(&deep, &curves, &cube, 0.5, valuation_date).unwrap();
+        let mut expected = 0.0;
+        for period in create_cms_schedule().periods() {
+            let (t_fixing, t_payment) = cms_period_times::<f64>(period, valuation_date);
+            let long = CmsIndex::new(RateIndex::Sofr, 10);
+            let short = CmsIndex::new(RateIndex::Sofr, 2);
+            let spread = cms_forward_rate(&long, &curves, t_fixing)
+                + cms_convexity_adjustment(&long, &curves, &cube, t_fixing, t_payment)
+                - cms_forward_rate(&short, &curves, t_fixing)
+                - cms_convexity_adjustment(&short, &curves, &cube, t_fixing, t_payment);
+            let year_frac =
+                DayCountConvention::Thirty360.year_fraction_dates(period.start(), period.end());
+            let df = (-0.03 * t_payment).exp();
+            expected += 1_000_000.0 * (spread + 0.2) * year_frac * df;
+        }
+        assert!(
+            (pv - expected).abs() < 1e-6 * expected,
+            "{} vs {}",
+            pv,
+            expected
+        );
+
+        assert!(matches!(
+            price_cms_spread_option(&option, &curves, &cube, 1.5, valuation_date),
+            Err(AnalyticalError::InvalidCorrelation { .. })
+        ));
+    }
+
+    // ========================================
+    // Repo and Securities Lending Tests
+    // ========================================
+
+    fn create_repo(direction: RepoDirection, rate: f64) -> Repo<f64> {
+        let start = Date::from_ymd(2024, 3, 15).unwrap();
+        Repo::new(
+            1_000_000.0,
+            rate,
+            start,
+            Date::from_ymd(2024, 9, 15).unwrap(),
+            direction,
+            CollateralSchedule::constant(
+                start,
+                CollateralPosition::new(1_050_000.0, 0.05).unwrap(),
+            ),
+            Currency::USD,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_price_repo_off_repo_curve() {
+        use RepoDirection;
+
+        let valuation_date = Date::from_ymd(2024, 1, 15).unwrap();
+        let mut curves = create_flat_curves();
+        curves.insert(CurveName::Repo, CurveEnum::flat(0.05));
+
+        let reverse = create_repo(RepoDirection::ReverseRepo, 0.05);
+        let t_start = DayCountConvention::ActualActual365
+            .year_fraction_dates(valuation_date, reverse.start_date());
+        let t_end = DayCountConvention::ActualActual365
+            .year_fraction_dates(valuation_date, reverse.end_date());
+        let expected = reverse.repurchase_amount() * (-0.05 * t_end).exp()
+            - 1_000_000.0 * (-0.05 * t_start).exp();
+        let pv = price_repo(&reverse, &curves, valuation_date);
+        assert!((pv - expected).abs() < 1e-6);
+
+        // Repo and reverse repo offset
+        let repo = create_repo(RepoDirection::Repo, 0.05);
+        assert!((price_repo(&repo, &curves, valuation_date) + pv).abs() < 1e-9);
+
+        // At the par rate the forward-starting repo is worth nothing
+        let par = par_repo_rate(&reverse, &curves, valuation_date);
+        let at_par = create_repo(RepoDirection::ReverseRepo, par);
+        assert!(price_repo(&at_par, &curves, valuation_date).abs() < 1e-6);
+
+        // Without a repo curve the discount curve is used
+        let discount_only = create_flat_curves();
+        let expected = reverse.repurchase_amount() * (-0.03 * t_end).exp()
+            - 1_000_000.0 * (-0.03 * t_start).exp();
+        assert!((price_repo(&reverse, &discount_only, valuation_date) - expected).abs() < 1e-6);
+
+        // Started: only the repurchase leg; matured: nothing
+        let started = Date::from_ymd(2024, 6, 15).unwrap();
+        let t_end =
+            DayCountConvention::ActualActual365.year_fraction_dates(started, reverse.end_date());
+        assert!(
+            (price_repo(&reverse, &curves, started)
+                - (reverse.repurchase_amount() * (-0.05 * t_end).exp()))
+            .abs()
+                < 1e-6
+        );
+        assert_eq!(price_repo(&reverse, &curves, reverse.end_date()), 0.0);
+    }
+
+    #[test]
+    fn test_price_securities_loan() {
+        let valuation_date = Date::from_ymd(2024, 1, 15).unwrap();
+        let end = Date::from_ymd(2025, 1, 15).unwrap();
+        let mut curves = create_flat_curves();
+        curves.insert(CurveName::Repo, CurveEnum::flat(0.04));
+
+        let collateral = CollateralSchedule::constant(
+            valuation_date,
+            CollateralPosition::new(1_020_000.0, 0.0).unwrap(),
+        );
+        let lend = SecuritiesLoan::new(
+            1_000_000.0,
+            0.0025,
+            valuation_date,
+            end,
+            LendingDirection::Lend,
+            collateral.clone(),
+            Currency::USD,
+        )
+        .unwrap();
+        let borrow = SecuritiesLoan::new(
+            1_000_000.0,
+            0.0025,
+            valuation_date,
+            end,
+            LendingDirection::Borrow,
+            collateral,
+            Currency::USD,
+        )
+        .unwrap();
+
+        let t_end = DayCountConvention::ActualActual365.year_fraction_dates(valuation_date, end);
+        let pv = price_securities_loan(&lend, &curves, valuation_date);
+        assert!((pv - (lend.fee() * (-0.04 * t_end).exp())).abs() < 1e-9);
+        assert!((price_securities_loan(&borrow, &curves, valuation_date) + pv).abs() < 1e-9);
+    }
 }
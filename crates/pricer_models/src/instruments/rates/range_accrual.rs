@@ -0,0 +1,728 @@
+//! Range accrual notes.
+//!
+//! This module provides:
+//! - [`RangeAccrual`]: Note whose coupon accrues for each day the index
+//!   fixes inside a range
+//! - [`CallableRangeAccrual`]: Range accrual the issuer may redeem at par
+//!   on coupon dates, priced by Longstaff-Schwartz regression
+//! - [`RateScenarios`]: Simulated index fixings and discount factors the
+//!   notes are priced on
+//!
+//! # Payoff
+//!
+//! For each period i with `n_i` daily observations:
+//!
+//! ```text
+//! Coupon_i = Notional × Rate × YearFraction_i × #{fixings in [L, U]} / n_i
+//! ```
+//!
+//! and the notional is repaid at maturity (or on the call date). The range
+//! indicator is smoothed as `σ((f − L)/ε) σ((U − f)/ε)` with the logistic
+//! `σ`, so prices are differentiable in the fixings; the width `ε` tends to
+//! the digital payoff as it goes to zero.
+//!
+//! # Example
+//!
+//! ```
+//! use pricer_models::instruments::rates::{RangeAccrual, RateIndex};
+//! use pricer_models::schedules::{ScheduleBuilder, Frequency};
+//! use pricer_core::types::{Currency, time::{Date, DayCountConvention}};
+//!
+//! let schedule = ScheduleBuilder::new()
+//!     .start(Date::from_ymd(2024, 1, 15).unwrap())
+//!     .end(Date::from_ymd(2026, 1, 15).unwrap())
+//!     .frequency(Frequency::Quarterly)
+//!     .day_count(DayCountConvention::ActualActual360)
+//!     .build()
+//!     .unwrap();
+//!
+//! // 5% coupon accruing while SOFR fixes between 1% and 4%
+//! let note = RangeAccrual::new(
+//!     1_000_000.0_f64, schedule, 0.05, 0.01, 0.04, RateIndex::Sofr, Currency::USD,
+//! )
+//! .unwrap();
+//!
+//! assert!((note.accrual_fraction(&[0.02, 0.03, 0.05, 0.035]) - 0.75).abs() < 1e-6);
+//! ```
+
+use num_traits::Float;
+use pricer_core::math::smoothing::smooth_indicator;
+use pricer_core::types::time::DayCountConvention;
+use pricer_core::types::Currency;
+
+use super::RateIndex;
+use crate::instruments::InstrumentError;
+use crate::schedules::Schedule;
+
+/// Default width of the smoothed range indicator (0.01bp).
+const DEFAULT_EPSILON: f64 = 1e-6;
+
+/// Range accrual note.
+///
+/// Pays a fixed coupon rate scaled by the fraction of daily index fixings
+/// in `[lower, upper]` over each period, and the notional at maturity.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RangeAccrual<T: Float> {
+    /// Notional principal amount.
+    notional: T,
+    /// Coupon schedule.
+    schedule: Schedule,
+    /// Coupon rate paid for a period fully in range.
+    coupon_rate: T,
+    /// Lower bound of the accrual range.
+    lower: T,
+    /// Upper bound of the accrual range.
+    upper: T,
+    /// Observed rate index.
+    index: RateIndex,
+    /// Coupon day count convention.
+    day_count: DayCountConvention,
+    /// Settlement currency.
+    currency: Currency,
+    /// Width of the smoothed range indicator.
+    epsilon: T,
+}
+
+impl<T: Float> RangeAccrual<T> {
+    /// Create a new range accrual note.
+    ///
+    /// # Arguments
+    ///
+    /// * `notional` - Notional principal amount
+    /// * `schedule` - Coupon schedule
+    /// * `coupon_rate` - Coupon rate paid for a period fully in range
+    /// * `lower` - Lower bound of the accrual range
+    /// * `upper` - Upper bound of the accrual range
+    /// * `index` - Observed rate index
+    /// * `currency` - Settlement currency
+    ///
+    /// # Errors
+    ///
+    /// Fails if the notional is not positive, the range is empty or the
+    /// schedule has no periods.
+    pub fn new(
+        notional: T,
+        schedule: Schedule,
+        coupon_rate: T,
+        lower: T,
+        upper: T,
+        index: RateIndex,
+        currency: Currency,
+    ) -> Result<Self, InstrumentError> {
+        if notional <= T::zero() {
+            return Err(InstrumentError::InvalidNotional {
+                notional: notional.to_f64().unwrap_or(0.0),
+            });
+        }
+        if lower >= upper {
+            return Err(InstrumentError::InvalidParameter {
+                message: format!(
+                    "Range lower bound {} must be below upper bound {}",
+                    lower.to_f64().unwrap_or(0.0),
+                    upper.to_f64().unwrap_or(0.0)
+                ),
+            });
+        }
+        if schedule.periods().is_empty() {
+            return Err(InstrumentError::InvalidParameter {
+                message: "Range accrual schedule has no periods".to_string(),
+            });
+        }
+        Ok(Self {
+            notional,
+            schedule,
+            coupon_rate,
+            lower,
+            upper,
+            index,
+            day_count: index.default_day_count(),
+            currency,
+            epsilon: T::from(DEFAULT_EPSILON).unwrap(),
+        })
+    }
+
+    /// Set the coupon day count convention.
+    pub fn with_day_count(mut self, day_count: DayCountConvention) -> Self {
+        self.day_count = day_count;
+        self
+    }
+
+    /// Set the width of the smoothed range indicator.
+    pub fn with_epsilon(mut self, epsilon: T) -> Self {
+        self.epsilon = epsilon;
+        self
+    }
+
+    /// Returns the notional amount.
+    #[inline]
+    pub fn notional(&self) -> T {
+        self.notional
+    }
+
+    /// Returns the coupon schedule.
+    #[inline]
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+
+    /// Returns the coupon rate.
+    #[inline]
+    pub fn coupon_rate(&self) -> T {
+        self.coupon_rate
+    }
+
+    /// Returns the accrual range as `(lower, upper)`.
+    #[inline]
+    pub fn range(&self) -> (T, T) {
+        (self.lower, self.upper)
+    }
+
+    /// Returns the observed rate index.
+    #[inline]
+    pub fn index(&self) -> RateIndex {
+        self.index
+    }
+
+    /// Returns the coupon day count convention.
+    #[inline]
+    pub fn day_count(&self) -> DayCountConvention {
+        self.day_count
+    }
+
+    /// Returns the settlement currency.
+    #[inline]
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// Returns the width of the smoothed range indicator.
+    #[inline]
+    pub fn epsilon(&self) -> T {
+        self.epsilon
+    }
+
+    /// Returns the number of coupon periods.
+    #[inline]
+    pub fn num_periods(&self) -> usize {
+        self.schedule.periods().len()
+    }
+
+    /// Returns the maturity time in years (from start to end of last period).
+    pub fn maturity(&self) -> T {
+        self.period_times()
+            .last()
+            .map_or_else(T::zero, |&(_, end)| end)
+    }
+
+    /// Start and end of each period in years from the start of the first.
+    pub fn period_times(&self) -> Vec<(T, T)> {
+        let periods = self.schedule.periods();
+        let Some(first) = periods.first() else {
+            return Vec::new();
+        };
+        let years = |date| {
+            T::from(DayCountConvention::ActualActual365.year_fraction_dates(first.start(), date))
+                .unwrap_or_else(T::zero)
+        };
+        periods
+            .iter()
+            .map(|p| (years(p.start()), years(p.end())))
+            .collect()
+    }
+
+    /// Smoothed indicator of a fixing lying in the range.
+    #[inline]
+    pub fn in_range(&self, fixing: T) -> T {
+        smooth_indicator(fixing - self.lower, self.epsilon)
+            * smooth_indicator(self.upper - fixing, self.epsilon)
+    }
+
+    /// Fraction of fixings in the range.
+    pub fn accrual_fraction(&self, fixings: &[T]) -> T {
+        if fixings.is_empty() {
+            return T::zero();
+        }
+        let in_range = fixings
+            .iter()
+            .fold(T::zero(), |acc, &f| acc + self.in_range(f));
+        in_range / T::from(fixings.len()).unwrap()
+    }
+
+    /// Coupon of a period given its daily fixings.
+    pub fn period_coupon(&self, period: usize, fixings: &[T]) -> T {
+        let Some(period) = self.schedule.periods().get(period) else {
+            return T::zero();
+        };
+        let year_fraction = T::from(
+            self.day_count
+                .year_fraction_dates(period.start(), period.end()),
+        )
+        .unwrap_or_else(T::zero);
+        self.notional * self.coupon_rate * year_fraction * self.accrual_fraction(fixings)
+    }
+
+    /// Monte Carlo value: the mean over scenarios of the discounted
+    /// coupons and the notional repaid at maturity.
+    pub fn price(&self, scenarios: &RateScenarios<T>) -> Result<T, InstrumentError> {
+        let flows = PathFlows::new(self, scenarios)?;
+        let n = self.num_periods();
+        let total = (0..scenarios.n_paths()).fold(T::zero(), |acc, p| {
+            let coupons = (0..n).fold(T::zero(), |acc, i| {
+                acc + flows.discount[i][p] * flows.coupons[i][p]
+            });
+            acc + coupons + flows.discount[n - 1][p] * self.notional
+        });
+        Ok(total / T::from(scenarios.n_paths()).unwrap())
+    }
+}
+
+/// Range accrual note callable by the issuer at par.
+///
+/// On each call date (the end of a call period, after its coupon) the
+/// issuer may redeem the notional; it does so when the note is worth more
+/// than par, so the holder receives `min(continuation, notional)`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CallableRangeAccrual<T: Float> {
+    /// Underlying range accrual note.
+    note: RangeAccrual<T>,
+    /// Periods after whose coupon the note is callable, ascending.
+    call_periods: Vec<usize>,
+}
+
+impl<T: Float> CallableRangeAccrual<T> {
+    /// Create a callable range accrual.
+    ///
+    /// # Arguments
+    ///
+    /// * `note` - Underlying range accrual note
+    /// * `call_periods` - Periods after whose coupon the issuer may call
+    ///
+    /// # Errors
+    ///
+    /// Fails if a call period is the last period or beyond.
+    pub fn new(note: RangeAccrual<T>, call_periods: &[usize]) -> Result<Self, InstrumentError> {
+        let mut call_periods = call_periods.to_vec();
+        call_periods.sort_unstable();
+        call_periods.dedup();
+        if let Some(&last) = call_periods.last() {
+            if last + 1 >= note.num_periods() {
+                return Err(InstrumentError::InvalidParameter {
+                    message: format!(
+                        "Call period {} must precede the last of {} periods",
+                        last,
+                        note.num_periods()
+                    ),
+                });
+            }
+        }
+        Ok(Self { note, call_periods })
+    }
+
+    /// Callable every period but the last.
+    pub fn every_period(note: RangeAccrual<T>) -> Self {
+        let call_periods = (0..note.num_periods().saturating_sub(1)).collect();
+        Self { note, call_periods }
+    }
+
+    /// Returns the underlying range accrual note.
+    #[inline]
+    pub fn note(&self) -> &RangeAccrual<T> {
+        &self.note
+    }
+
+    /// Returns the periods after whose coupon the note is callable.
+    #[inline]
+    pub fn call_periods(&self) -> &[usize] {
+        &self.call_periods
+    }
+
+    /// Returns the maturity time in years.
+    #[inline]
+    pub fn maturity(&self) -> T {
+        self.note.maturity()
+    }
+
+    /// Returns the settlement currency.
+    #[inline]
+    pub fn currency(&self) -> Currency {
+        self.note.currency()
+    }
+
+    /// Longstaff-Schwartz value.
+    ///
+    /// Works backwards from maturity: at each call date the value of the
+    /// remaining flows, in call-date money, is regressed on a quadratic in
+    /// the index fixing; where the fitted continuation exceeds par the
+    /// issuer calls, and the path's remaining flows become the notional.
+    /// Exercise decisions use the fit, values the realised flows.
+    pub fn price_lsm(&self, scenarios: &RateScenarios<T>) -> Result<T, InstrumentError> {
+        let note = &self.note;
+        let flows = PathFlows::new(note, scenarios)?;
+        let n = note.num_periods();
+        let paths = scenarios.n_paths();
+
+        // Discounted value of the flows from each period on
+        let mut values: Vec<T> = (0..paths)
+            .map(|p| flows.discount[n - 1][p] * (flows.coupons[n - 1][p] + note.notional))
+            .collect();
+        for i in (0..n - 1).rev() {
+            if self.call_periods.binary_search(&i).is_ok() {
+                let continuation: Vec<T> = (0..paths)
+                    .map(|p| values[p] / flows.discount[i][p])
+                    .collect();
+                let beta = regress(&flows.state[i], &continuation);
+                for (p, value) in values.iter_mut().enumerate() {
+                    let x = flows.state[i][p];
+                    if beta[0] + beta[1] * x + beta[2] * x * x > note.notional {
+                        *value = flows.discount[i][p] * note.notional;
+                    }
+                }
+            }
+            for (p, value) in values.iter_mut().enumerate() {
+                *value = *value + flows.discount[i][p] * flows.coupons[i][p];
+            }
+        }
+
+        let total = values.iter().fold(T::zero(), |acc, &v| acc + v);
+        Ok(total / T::from(paths).unwrap())
+    }
+}
+
+/// Simulated daily index fixings and discount factors.
+///
+/// Times are in years from the start of the note's first period; path `p`
+/// fixes at `fixings[p][j]` at `times[j]`, and `discounts[p][j]` is its
+/// discount factor from time zero to `times[j]`.
+#[derive(Debug, Clone)]
+pub struct RateScenarios<T: Float> {
+    times: Vec<T>,
+    fixings: Vec<Vec<T>>,
+    discounts: Vec<Vec<T>>,
+}
+
+impl<T: Float> RateScenarios<T> {
+    /// Create scenarios on an ascending time grid.
+    ///
+    /// # Errors
+    ///
+    /// Fails if there are no paths, the grid is not ascending, or a path
+    /// does not cover the grid.
+    pub fn new(
+        times: Vec<T>,
+        fixings: Vec<Vec<T>>,
+        discounts: Vec<Vec<T>>,
+    ) -> Result<Self, InstrumentError> {
+        let invalid = |message: String| InstrumentError::InvalidParameter { message };
+        if fixings.is_empty() || fixings.len() != discounts.len() {
+            return Err(invalid(format!(
+                "{} fixing and {} discount paths",
+                fixings.len(),
+                discounts.len()
+            )));
+        }
+        if times.windows(2).any(|w| w[1] <= w[0]) {
+            return Err(invalid("Scenario times must be ascending".to_string()));
+        }
+        if fixings
+            .iter()
+            .chain(&discounts)
+            .any(|path| path.len() != times.len())
+        {
+            return Err(invalid(format!(
+                "Scenario paths must have {} points",
+                times.len()
+            )));
+        }
+        Ok(Self {
+            times,
+            fixings,
+            discounts,
+        })
+    }
+
+    /// Returns the number of paths.
+    #[inline]
+    pub fn n_paths(&self) -> usize {
+        self.fixings.len()
+    }
+
+    /// Returns the time grid.
+    #[inline]
+    pub fn times(&self) -> &[T] {
+        &self.times
+    }
+}
+
+/// Per-period coupons, payment discount factors and call-date fixings of
+/// each scenario path, indexed `[period][path]`.
+struct PathFlows<T> {
+    coupons: Vec<Vec<T>>,
+    discount: Vec<Vec<T>>,
+    state: Vec<Vec<T>>,
+}
+
+impl<T: Float> PathFlows<T> {
+    /// Fixings observed in `[start, end)` accrue to a period; the payment
+    /// discount factor and state are read at the first grid time on or
+    /// after its end.
+    fn new(note: &RangeAccrual<T>, scenarios: &RateScenarios<T>) -> Result<Self, InstrumentError> {
+        let times = scenarios.times();
+        let mut flows = Self {
+            coupons: Vec::new(),
+            discount: Vec::new(),
+            state: Vec::new(),
+        };
+        for (i, (start, end)) in note.period_times().into_iter().enumerate() {
+            let first = times.partition_point(|&t| t < start);
+            let last = times.partition_point(|&t| t < end);
+            if first == last || last == times.len() {
+                return Err(InstrumentError::InvalidParameter {
+                    message: format!(
+                        "Scenario grid does not cover period {} ({} to {} years)",
+                        i,
+                        start.to_f64().unwrap_or(0.0),
+                        end.to_f64().unwrap_or(0.0)
+                    ),
+                });
+            }
+            flows.coupons.push(
+                scenarios
+                    .fixings
+                    .iter()
+                    .map(|path| note.period_coupon(i, &path[first..last]))
+                    .collect(),
+            );
+            flows
+                .discount
+                .push(scenarios.discounts.iter().map(|path| path[last]).collect());
+            flows
+                .state
+                .push(scenarios.fixings.iter().map(|path| path[last]).collect());
+        }
+        Ok(flows)
+    }
+}
+
+/// Least-squares fit of `y ≈ β0 + β1 x + β2 x²`.
+///
+/// Falls back to a line, then to the mean, when the fixings are too few
+/// or too concentrated to identify the higher terms.
+fn regress<T: Float>(xs: &[T], ys: &[T]) -> [T; 3] {
+    let n = T::from(xs.len()).unwrap();
+    let mean = ys.iter().fold(T::zero(), |acc, &y| acc + y) / n;
+
+    // Normal equations on centred fixings for conditioning
+    let centre = xs.iter().fold(T::zero(), |acc, &x| acc + x) / n;
+    let mut m = [[T::zero(); 3]; 3];
+    let mut v = [T::zero(); 3];
+    for (&x, &y) in xs.iter().zip(ys) {
+        let u = x - centre;
+        let basis = [T::one(), u, u * u];
+        for r in 0..3 {
+            v[r] = v[r] + basis[r] * y;
+            for c in 0..3 {
+                m[r][c] = m[r][c] + basis[r] * basis[c];
+            }
+        }
+    }
+
+    let tolerance = T::from(1e-12).unwrap();
+    let det2 = m[0][0] * m[1][1] - m[0][1] * m[1][0];
+    let det3 = m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+        - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+        + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+    let scale = m[0][0] * m[1][1].max(tolerance) * m[2][2].max(tolerance);
+
+    // Coefficients in the centred fixing u = x − centre
+    let centred = if det3.abs() > tolerance * scale.abs() {
+        let solve = |col: usize| {
+            let mut a = m;
+            for r in 0..3 {
+                a[r][col] = v[r];
+            }
+            (a[0][0] * (a[1][1] * a[2][2] - a[1][2] * a[2][1])
+                - a[0][1] * (a[1][0] * a[2][2] - a[1][2] * a[2][0])
+                + a[0][2] * (a[1][0] * a[2][1] - a[1][1] * a[2][0]))
+                / det3
+        };
+        [solve(0), solve(1), solve(2)]
+    } else if det2.abs() > tolerance * (m[0][0] * m[1][1].max(tolerance)).abs() {
+        let b1 = (m[0][0] * v[1] - m[1][0] * v[0]) / det2;
+        let b0 = (v[0] - m[0][1] * b1) / m[0][0];
+        [b0, b1, T::zero()]
+    } else {
+        [mean, T::zero(), T::zero()]
+    };
+
+    // Expand β0 + β1 u + β2 u² in x
+    let [a, b, c] = centred;
+    let two = T::one() + T::one();
+    [
+        a - b * centre + c * centre * centre,
+        b - two * c * centre,
+        c,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schedules::{Frequency, ScheduleBuilder};
+    use pricer_core::types::time::Date;
+
+    fn note(coupon: f64) -> RangeAccrual<f64> {
+        let schedule = ScheduleBuilder::new()
+            .start(Date::from_ymd(2024, 1, 15).unwrap())
+            .end(Date::from_ymd(2025, 1, 15).unwrap())
+            .frequency(Frequency::Quarterly)
+            .day_count(DayCountConvention::ActualActual360)
+            .build()
+            .unwrap();
+        RangeAccrual::new(
+            100.0,
+            schedule,
+            coupon,
+            0.01,
+            0.04,
+            RateIndex::Sofr,
+            Currency::USD,
+        )
+        .unwrap()
+    }
+
+    /// Daily grid with each path fixing flat at its rate and discounting
+    /// at that rate.
+    fn flat_scenarios(rates: &[f64]) -> RateScenarios<f64> {
+        let times: Vec<f64> = (0..=400).map(|d| d as f64 / 365.0).collect();
+        let fixings = rates.iter().map(|&r| vec![r; times.len()]).collect();
+        let discounts = rates
+            .iter()
+            .map(|&r| times.iter().map(|t| (-r * t).exp()).collect())
+            .collect();
+        RateScenarios::new(times, fixings, discounts).unwrap()
+    }
+
+    #[test]
+    fn test_smoothed_range_indicator() {
+        let note = note(0.05);
+        assert!(note.in_range(0.02) > 1.0 - 1e-9);
+        assert!(note.in_range(0.05) < 1e-9);
+        assert!(note.in_range(0.0) < 1e-9);
+        assert!((note.in_range(0.04) - 0.5).abs() < 1e-9);
+
+        let wide = note.clone().with_epsilon(0.01);
+        assert!(wide.in_range(0.045) > 0.1);
+        assert_eq!(note.accrual_fraction(&[]), 0.0);
+    }
+
+    #[test]
+    fn test_period_coupon() {
+        let note = note(0.05);
+        assert_eq!(note.num_periods(), 4);
+        let year_fraction = note.schedule().periods()[0].year_fraction();
+        let coupon = note.period_coupon(0, &[0.02, 0.03, 0.05, 0.00]);
+        assert!((coupon - 100.0 * 0.05 * year_fraction * 0.5).abs() < 1e-9);
+        assert!((note.maturity() - 366.0 / 365.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_price_matches_flat_scenario() {
+        let note = note(0.05);
+        let rate = 0.02;
+        let price = note.price(&flat_scenarios(&[rate])).unwrap();
+
+        let times = note.period_times();
+        let expected = note
+            .schedule()
+            .periods()
+            .iter()
+            .zip(&times)
+            .map(|(p, &(_, end))| {
+                let paid = (end * 365.0).round() / 365.0;
+                100.0 * 0.05 * p.year_fraction() * (-rate * paid).exp()
+            })
+            .sum::<f64>()
+            + 100.0 * (-rate * (times[3].1 * 365.0).round() / 365.0).exp();
+        assert!((price - expected).abs() < 1e-6);
+
+        // Out of range: only the notional
+        let out = note.price(&flat_scenarios(&[0.05])).unwrap();
+        assert!(out < 100.0 && out > 94.0);
+    }
+
+    #[test]
+    fn test_issuer_calls_when_note_is_above_par() {
+        // A 10% coupon in range at 2% rates is worth well above par
+        let note = note(0.10);
+        let scenarios = flat_scenarios(&[0.02]);
+        let straight = note.price(&scenarios).unwrap();
+        let callable = CallableRangeAccrual::every_period(note.clone());
+        let called = callable.price_lsm(&scenarios).unwrap();
+        assert!(called < straight);
+
+        // Called after the first coupon
+        let end = (note.period_times()[0].1 * 365.0).round() / 365.0;
+        let df = (-0.02 * end).exp();
+        let first = 100.0 * 0.10 * note.schedule().periods()[0].year_fraction();
+        assert!((called - df * (first + 100.0)).abs() < 1e-6);
+
+        // Out of range the note is below par and never called
+        let low = flat_scenarios(&[0.05]);
+        let straight = note.price(&low).unwrap();
+        assert!((callable.price_lsm(&low).unwrap() - straight).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_callable_is_bounded_by_straight_note() {
+        let note = note(0.06);
+        let scenarios = flat_scenarios(&[0.005, 0.01, 0.02, 0.03, 0.035, 0.045, 0.06]);
+        let straight = note.price(&scenarios).unwrap();
+        let callable = CallableRangeAccrual::new(note, &[1, 2])
+            .unwrap()
+            .price_lsm(&scenarios)
+            .unwrap();
+        assert!(callable <= straight + 1e-12);
+        assert!(callable > 0.9 * straight);
+    }
+
+    #[test]
+    fn test_invalid_terms() {
+        let schedule = note(0.05).schedule().clone();
+        let build = |notional: f64, lower: f64, upper: f64| {
+            RangeAccrual::new(
+                notional,
+                schedule.clone(),
+                0.05,
+                lower,
+                upper,
+                RateIndex::Sofr,
+                Currency::USD,
+            )
+        };
+        assert!(build(0.0, 0.01, 0.04).is_err());
+        assert!(build(100.0, 0.04, 0.01).is_err());
+        assert!(CallableRangeAccrual::new(note(0.05), &[3]).is_err());
+
+        let short =
+            RateScenarios::new(vec![0.0, 0.5], vec![vec![0.02; 2]], vec![vec![1.0; 2]]).unwrap();
+        assert!(note(0.05).price(&short).is_err());
+        assert!(
+            RateScenarios::new(vec![0.0, 0.5], vec![vec![0.02; 3]], vec![vec![1.0; 2]]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_regression_recovers_quadratic() {
+        let xs: Vec<f64> = (0..20).map(|i| 0.01 + i as f64 * 0.002).collect();
+        let ys: Vec<f64> = xs.iter().map(|x| 1.0 - 3.0 * x + 50.0 * x * x).collect();
+        let beta = regress(&xs, &ys);
+        assert!((beta[0] - 1.0).abs() < 1e-8);
+        assert!((beta[1] + 3.0).abs() < 1e-6);
+        assert!((beta[2] - 50.0).abs() < 1e-4);
+
+        let flat = regress(&[0.02, 0.02], &[1.0, 3.0]);
+        assert_eq!(flat, [2.0, 0.0, 0.0]);
+    }
+}
@@ -0,0 +1,697 @@
+//! Repo and securities-lending financing trades.
+//!
+//! This module provides:
+//! - [`Repo`]: Sale and repurchase of securities, i.e. a cash loan secured
+//!   on collateral, in either direction ([`RepoDirection`])
+//! - [`SecuritiesLoan`]: Loan of securities against collateral for a fee
+//! - [`CollateralSchedule`]: Collateral held over the life of a trade, each
+//!   [`CollateralPosition`] credited after its haircut
+//!
+//! # Cashflows
+//!
+//! With τ the accrual fraction from start to end date:
+//! - **Repo**: cash C received at start, C × (1 + r × τ) repaid at end
+//! - **Reverse repo**: cash C lent at start, C × (1 + r × τ) received at end
+//! - **Securities loan**: fee × LoanValue × τ received by the lender at end
+//!
+//! # Collateral
+//!
+//! Collateral counts for its market value less the haircut, MV × (1 − h),
+//! so a repo lending 98 against 100 of bonds at a 2% haircut starts fully
+//! secured. The net exposure of a trade is the cash (or securities) owed
+//! to us net of this haircut-adjusted collateral, in the comprehensive
+//! approach's E − C × (1 − h) form.
+//!
+//! # Example
+//!
+//! ```
+//! use pricer_models::instruments::rates::{
+//!     CollateralPosition, CollateralSchedule, Repo, RepoDirection,
+//! };
+//! use pricer_core::types::{Currency, time::Date};
+//!
+//! let start = Date::from_ymd(2024, 1, 15).unwrap();
+//! let end = Date::from_ymd(2024, 4, 15).unwrap();
+//! let collateral = CollateralSchedule::constant(
+//!     start,
+//!     CollateralPosition::new(10_204_081.63_f64, 0.02).unwrap(),
+//! );
+//!
+//! let repo = Repo::new(
+//!     10_000_000.0, 0.053, start, end, RepoDirection::ReverseRepo, collateral, Currency::USD,
+//! )
+//! .unwrap();
+//!
+//! // 91 days at 5.3% on Act/360
+//! assert!((repo.interest() - 133_972.22).abs() < 0.01);
+//! assert!(repo.net_exposure(start).abs() < 0.01);
+//! ```
+
+use num_traits::Float;
+use pricer_core::types::time::{Date, DayCountConvention};
+use pricer_core::types::Currency;
+
+use crate::instruments::InstrumentError;
+
+/// Side of a repo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RepoDirection {
+    /// Borrow cash against collateral delivered.
+    Repo,
+    /// Lend cash against collateral received.
+    ReverseRepo,
+}
+
+impl RepoDirection {
+    /// +1 for the cash lender, −1 for the cash borrower.
+    #[inline]
+    pub fn sign<T: Float>(&self) -> T {
+        match self {
+            RepoDirection::Repo => -T::one(),
+            RepoDirection::ReverseRepo => T::one(),
+        }
+    }
+}
+
+/// Side of a securities loan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LendingDirection {
+    /// Lend securities and receive the fee.
+    Lend,
+    /// Borrow securities and pay the fee.
+    Borrow,
+}
+
+impl LendingDirection {
+    /// +1 for the lender, −1 for the borrower.
+    #[inline]
+    pub fn sign<T: Float>(&self) -> T {
+        match self {
+            LendingDirection::Lend => T::one(),
+            LendingDirection::Borrow => -T::one(),
+        }
+    }
+}
+
+/// Collateral held against a financing trade.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CollateralPosition<T: Float> {
+    /// Market value of the collateral.
+    market_value: T,
+    /// Haircut as a fraction of market value.
+    haircut: T,
+}
+
+impl<T: Float> CollateralPosition<T> {
+    /// Create a collateral position.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the market value is negative or the haircut is outside [0, 1).
+    pub fn new(market_value: T, haircut: T) -> Result<Self, InstrumentError> {
+        if market_value < T::zero() {
+            return Err(InstrumentError::InvalidParameter {
+                message: format!(
+                    "Collateral market value {} must not be negative",
+                    market_value.to_f64().unwrap_or(0.0)
+                ),
+            });
+        }
+        if haircut < T::zero() || haircut >= T::one() {
+            return Err(InstrumentError::InvalidParameter {
+                message: format!(
+                    "Collateral haircut {} must be in [0, 1)",
+                    haircut.to_f64().unwrap_or(0.0)
+                ),
+            });
+        }
+        Ok(Self {
+            market_value,
+            haircut,
+        })
+    }
+
+    /// Returns the market value.
+    #[inline]
+    pub fn market_value(&self) -> T {
+        self.market_value
+    }
+
+    /// Returns the haircut.
+    #[inline]
+    pub fn haircut(&self) -> T {
+        self.haircut
+    }
+
+    /// Market value credited after the haircut: MV × (1 − h).
+    #[inline]
+    pub fn adjusted_value(&self) -> T {
+        self.market_value * (T::one() - self.haircut)
+    }
+}
+
+/// Collateral positions over the life of a trade.
+///
+/// Each position applies from its date until the next one, so substitutions
+/// and margin top-ups are entered as new dated positions.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CollateralSchedule<T: Float> {
+    /// Positions sorted by the date they take effect.
+    positions: Vec<(Date, CollateralPosition<T>)>,
+}
+
+impl<T: Float> CollateralSchedule<T> {
+    /// Create a schedule from dated positions.
+    ///
+    /// # Errors
+    ///
+    /// Fails if there are no positions or the dates are not strictly increasing.
+    pub fn new(positions: Vec<(Date, CollateralPosition<T>)>) -> Result<Self, InstrumentError> {
+        if positions.is_empty() {
+            return Err(InstrumentError::InvalidParameter {
+                message: "Collateral schedule has no positions".to_string(),
+            });
+        }
+        if positions.windows(2).any(|pair| pair[1].0 <= pair[0].0) {
+            return Err(InstrumentError::InvalidParameter {
+                message: "Collateral schedule dates must be strictly increasing".to_string(),
+            });
+        }
+        Ok(Self { positions })
+    }
+
+    /// Schedule holding the same position from `date` onwards.
+    pub fn constant(date: Date, position: CollateralPosition<T>) -> Self {
+        Self {
+            positions: vec![(date, position)],
+        }
+    }
+
+    /// Returns the dated positions.
+    #[inline]
+    pub fn positions(&self) -> &[(Date, CollateralPosition<T>)] {
+        &self.positions
+    }
+
+    /// Position in effect on `date`, if any has started.
+    pub fn position_at(&self, date: Date) -> Option<&CollateralPosition<T>> {
+        let n_started = self.positions.partition_point(|(from, _)| *from <= date);
+        n_started
+            .checked_sub(1)
+            .map(|index| &self.positions[index].1)
+    }
+
+    /// Haircut-adjusted collateral value on `date`, zero before the first position.
+    pub fn adjusted_value_at(&self, date: Date) -> T {
+        self.position_at(date)
+            .map_or_else(T::zero, CollateralPosition::adjusted_value)
+    }
+}
+
+/// Validates the common terms of a financing trade.
+fn validate_terms<T: Float>(
+    amount: T,
+    start_date: Date,
+    end_date: Date,
+) -> Result<(), InstrumentError> {
+    if amount <= T::zero() {
+        return Err(InstrumentError::InvalidNotional {
+            notional: amount.to_f64().unwrap_or(0.0),
+        });
+    }
+    if end_date <= start_date {
+        return Err(InstrumentError::InvalidParameter {
+            message: format!(
+                "End date {} must be after start date {}",
+                end_date, start_date
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// Years from `start` to `end` on Act/365.
+fn term_years<T: Float>(start: Date, end: Date) -> T {
+    T::from(DayCountConvention::ActualActual365.year_fraction_dates(start, end))
+        .unwrap_or_else(T::zero)
+}
+
+/// Fraction of `[start, end]` accrued by `date`, on `day_count`.
+fn accrued_fraction<T: Float>(
+    day_count: DayCountConvention,
+    start: Date,
+    end: Date,
+    date: Date,
+) -> T {
+    let date = date.clamp(start, end);
+    T::from(day_count.year_fraction_dates(start, date)).unwrap_or_else(T::zero)
+}
+
+/// Repurchase agreement.
+///
+/// A cash loan of `cash_amount` from start to end date at `repo_rate`,
+/// secured on the collateral schedule.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Repo<T: Float> {
+    /// Cash lent or borrowed.
+    cash_amount: T,
+    /// Simple interest rate on the cash.
+    repo_rate: T,
+    /// Purchase (start) date.
+    start_date: Date,
+    /// Repurchase (end) date.
+    end_date: Date,
+    /// Repo or reverse repo.
+    direction: RepoDirection,
+    /// Collateral delivered against the cash.
+    collateral: CollateralSchedule<T>,
+    /// Interest day count convention.
+    day_count: DayCountConvention,
+    /// Cash currency.
+    currency: Currency,
+}
+
+impl<T: Float> Repo<T> {
+    /// Create a new repo.
+    ///
+    /// # Arguments
+    ///
+    /// * `cash_amount` - Cash lent or borrowed
+    /// * `repo_rate` - Simple interest rate on the cash
+    /// * `start_date` - Purchase (start) date
+    /// * `end_date` - Repurchase (end) date
+    /// * `direction` - Repo or reverse repo
+    /// * `collateral` - Collateral delivered against the cash
+    /// * `currency` - Cash currency
+    ///
+    /// # Errors
+    ///
+    /// Fails if the cash amount is not positive or the end date is not
+    /// after the start date.
+    pub fn new(
+        cash_amount: T,
+        repo_rate: T,
+        start_date: Date,
+        end_date: Date,
+        direction: RepoDirection,
+        collateral: CollateralSchedule<T>,
+        currency: Currency,
+    ) -> Result<Self, InstrumentError> {
+        validate_terms(cash_amount, start_date, end_date)?;
+        Ok(Self {
+            cash_amount,
+            repo_rate,
+            start_date,
+            end_date,
+            direction,
+            collateral,
+            day_count: DayCountConvention::ActualActual360,
+            currency,
+        })
+    }
+
+    /// Set the interest day count convention (Act/360 by default).
+    pub fn with_day_count(mut self, day_count: DayCountConvention) -> Self {
+        self.day_count = day_count;
+        self
+    }
+
+    /// Returns the cash amount.
+    #[inline]
+    pub fn cash_amount(&self) -> T {
+        self.cash_amount
+    }
+
+    /// Returns the repo rate.
+    #[inline]
+    pub fn repo_rate(&self) -> T {
+        self.repo_rate
+    }
+
+    /// Returns the start date.
+    #[inline]
+    pub fn start_date(&self) -> Date {
+        self.start_date
+    }
+
+    /// Returns the end date.
+    #[inline]
+    pub fn end_date(&self) -> Date {
+        self.end_date
+    }
+
+    /// Returns the direction.
+    #[inline]
+    pub fn direction(&self) -> RepoDirection {
+        self.direction
+    }
+
+    /// Returns the collateral schedule.
+    #[inline]
+    pub fn collateral(&self) -> &CollateralSchedule<T> {
+        &self.collateral
+    }
+
+    /// Returns the day count convention.
+    #[inline]
+    pub fn day_count(&self) -> DayCountConvention {
+        self.day_count
+    }
+
+    /// Returns the currency.
+    #[inline]
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// Time from start to end date in years (Act/365).
+    pub fn maturity(&self) -> T {
+        term_years(self.start_date, self.end_date)
+    }
+
+    /// Accrual fraction from start to end date.
+    pub fn year_fraction(&self) -> T {
+        accrued_fraction(
+            self.day_count,
+            self.start_date,
+            self.end_date,
+            self.end_date,
+        )
+    }
+
+    /// Interest paid at the end date.
+    pub fn interest(&self) -> T {
+        self.cash_amount * self.repo_rate * self.year_fraction()
+    }
+
+    /// Cash repaid at the end date: C × (1 + r × τ).
+    pub fn repurchase_amount(&self) -> T {
+        self.cash_amount + self.interest()
+    }
+
+    /// Cash plus interest accrued up to `date`, zero outside the trade's life.
+    pub fn cash_owed(&self, date: Date) -> T {
+        if date < self.start_date || date >= self.end_date {
+            return T::zero();
+        }
+        let accrued: T = accrued_fraction(self.day_count, self.start_date, self.end_date, date);
+        self.cash_amount * (T::one() + self.repo_rate * accrued)
+    }
+
+    /// Exposure to the counterparty on `date`, from our side: the cash owed
+    /// net of haircut-adjusted collateral for a reverse repo, and the
+    /// reverse for a repo.
+    pub fn net_exposure(&self, date: Date) -> T {
+        if date < self.start_date || date >= self.end_date {
+            return T::zero();
+        }
+        self.direction.sign::<T>()
+            * (self.cash_owed(date) - self.collateral.adjusted_value_at(date))
+    }
+}
+
+/// Securities loan.
+///
+/// Securities worth `loan_value` are lent from start to end date against
+/// the collateral schedule, for a fee accruing on the loan value and paid
+/// at the end date.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SecuritiesLoan<T: Float> {
+    /// Market value of the securities lent.
+    loan_value: T,
+    /// Lending fee rate.
+    fee_rate: T,
+    /// Loan start date.
+    start_date: Date,
+    /// Loan end (return) date.
+    end_date: Date,
+    /// Lend or borrow.
+    direction: LendingDirection,
+    /// Collateral delivered against the securities.
+    collateral: CollateralSchedule<T>,
+    /// Fee day count convention.
+    day_count: DayCountConvention,
+    /// Fee and valuation currency.
+    currency: Currency,
+}
+
+impl<T: Float> SecuritiesLoan<T> {
+    /// Create a new securities loan.
+    ///
+    /// # Arguments
+    ///
+    /// * `loan_value` - Market value of the securities lent
+    /// * `fee_rate` - Lending fee rate
+    /// * `start_date` - Loan start date
+    /// * `end_date` - Loan end (return) date
+    /// * `direction` - Lend or borrow
+    /// * `collateral` - Collateral delivered against the securities
+    /// * `currency` - Fee and valuation currency
+    ///
+    /// # Errors
+    ///
+    /// Fails if the loan value is not positive, the fee is negative or the
+    /// end date is not after the start date.
+    pub fn new(
+        loan_value: T,
+        fee_rate: T,
+        start_date: Date,
+        end_date: Date,
+        direction: LendingDirection,
+        collateral: CollateralSchedule<T>,
+        currency: Currency,
+    ) -> Result<Self, InstrumentError> {
+        validate_terms(loan_value, start_date, end_date)?;
+        if fee_rate < T::zero() {
+            return Err(InstrumentError::InvalidParameter {
+                message: format!(
+                    "Lending fee {} must not be negative",
+                    fee_rate.to_f64().unwrap_or(0.0)
+                ),
+            });
+        }
+        Ok(Self {
+            loan_value,
+            fee_rate,
+            start_date,
+            end_date,
+            direction,
+            collateral,
+            day_count: DayCountConvention::ActualActual360,
+            currency,
+        })
+    }
+
+    /// Set the fee day count convention (Act/360 by default).
+    pub fn with_day_count(mut self, day_count: DayCountConvention) -> Self {
+        self.day_count = day_count;
+        self
+    }
+
+    /// Returns the loan value.
+    #[inline]
+    pub fn loan_value(&self) -> T {
+        self.loan_value
+    }
+
+    /// Returns the fee rate.
+    #[inline]
+    pub fn fee_rate(&self) -> T {
+        self.fee_rate
+    }
+
+    /// Returns the start date.
+    #[inline]
+    pub fn start_date(&self) -> Date {
+        self.start_date
+    }
+
+    /// Returns the end date.
+    #[inline]
+    pub fn end_date(&self) -> Date {
+        self.end_date
+    }
+
+    /// Returns the direction.
+    #[inline]
+    pub fn direction(&self) -> LendingDirection {
+        self.direction
+    }
+
+    /// Returns the collateral schedule.
+    #[inline]
+    pub fn collateral(&self) -> &CollateralSchedule<T> {
+        &self.collateral
+    }
+
+    /// Returns the day count convention.
+    #[inline]
+    pub fn day_count(&self) -> DayCountConvention {
+        self.day_count
+    }
+
+    /// Returns the currency.
+    #[inline]
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// Time from start to end date in years (Act/365).
+    pub fn maturity(&self) -> T {
+        term_years(self.start_date, self.end_date)
+    }
+
+    /// Fee paid at the end date: fee × LoanValue × τ.
+    pub fn fee(&self) -> T {
+        let tau: T = accrued_fraction(
+            self.day_count,
+            self.start_date,
+            self.end_date,
+            self.end_date,
+        );
+        self.loan_value * self.fee_rate * tau
+    }
+
+    /// Exposure to the counterparty on `date`, from our side: the
+    /// securities and accrued fee owed net of haircut-adjusted collateral
+    /// for the lender, and the reverse for the borrower.
+    pub fn net_exposure(&self, date: Date) -> T {
+        if date < self.start_date || date >= self.end_date {
+            return T::zero();
+        }
+        let accrued: T = accrued_fraction(self.day_count, self.start_date, self.end_date, date);
+        let owed = self.loan_value * (T::one() + self.fee_rate * accrued);
+        self.direction.sign::<T>() * (owed - self.collateral.adjusted_value_at(date))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn date(y: i32, m: u32, d: u32) -> Date {
+        Date::from_ymd(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_repo_cashflows_and_exposure() {
+        let start = date(2024, 1, 15);
+        let end = date(2024, 7, 15);
+        let collateral = CollateralSchedule::new(vec![
+            (start, CollateralPosition::new(1_020_408.16, 0.02).unwrap()),
+            (
+                date(2024, 4, 15),
+                CollateralPosition::new(1_000_000.0, 0.02).unwrap(),
+            ),
+        ])
+        .unwrap();
+        let reverse = Repo::new(
+            1_000_000.0,
+            0.05,
+            start,
+            end,
+            RepoDirection::ReverseRepo,
+            collateral.clone(),
+            Currency::USD,
+        )
+        .unwrap();
+
+        // 182 days on Act/360
+        assert_relative_eq!(reverse.year_fraction(), 182.0 / 360.0, epsilon = 1e-12);
+        assert_relative_eq!(
+            reverse.repurchase_amount(),
+            1_000_000.0 * (1.0 + 0.05 * 182.0 / 360.0),
+            epsilon = 1e-6
+        );
+
+        // Fully secured at start; after the collateral falls, 20k plus
+        // accrued interest is unsecured
+        assert_relative_eq!(reverse.net_exposure(start), 0.0, epsilon = 0.01);
+        let later = date(2024, 4, 15);
+        let expected = 1_000_000.0 * (1.0 + 0.05 * 91.0 / 360.0) - 980_000.0;
+        assert_relative_eq!(reverse.net_exposure(later), expected, epsilon = 1e-6);
+        assert_eq!(reverse.net_exposure(end), 0.0);
+        assert_eq!(reverse.net_exposure(date(2024, 1, 1)), 0.0);
+
+        let repo = Repo::new(
+            1_000_000.0,
+            0.05,
+            start,
+            end,
+            RepoDirection::Repo,
+            collateral,
+            Currency::USD,
+        )
+        .unwrap();
+        assert_relative_eq!(repo.net_exposure(later), -expected, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_securities_loan_fee_and_exposure() {
+        let start = date(2024, 1, 15);
+        let end = date(2025, 1, 15);
+        let collateral =
+            CollateralSchedule::constant(start, CollateralPosition::new(1_050_000.0, 0.0).unwrap());
+        let loan = SecuritiesLoan::new(
+            1_000_000.0,
+            0.004,
+            start,
+            end,
+            LendingDirection::Lend,
+            collateral,
+            Currency::EUR,
+        )
+        .unwrap();
+
+        assert_relative_eq!(
+            loan.fee(),
+            1_000_000.0 * 0.004 * 366.0 / 360.0,
+            epsilon = 1e-6
+        );
+        // Over-collateralised by 5%
+        assert_relative_eq!(loan.net_exposure(start), -50_000.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_invalid_terms_are_rejected() {
+        let start = date(2024, 1, 15);
+        let position = CollateralPosition::new(100.0, 0.1).unwrap();
+        let collateral = CollateralSchedule::constant(start, position);
+
+        assert!(CollateralPosition::new(100.0, 1.0).is_err());
+        assert!(CollateralPosition::new(-1.0, 0.0).is_err());
+        assert!(CollateralSchedule::<f64>::new(vec![]).is_err());
+        assert!(CollateralSchedule::new(vec![(start, position), (start, position)]).is_err());
+        assert!(Repo::new(
+            100.0,
+            0.05,
+            start,
+            start,
+            RepoDirection::Repo,
+            collateral.clone(),
+            Currency::USD
+        )
+        .is_err());
+        assert!(SecuritiesLoan::new(
+            100.0,
+            -0.01,
+            start,
+            date(2024, 2, 15),
+            LendingDirection::Borrow,
+            collateral,
+            Currency::USD
+        )
+        .is_err());
+
+        assert!(CollateralSchedule::constant(start, position)
+            .position_at(date(2024, 1, 14))
+            .is_none());
+    }
+}
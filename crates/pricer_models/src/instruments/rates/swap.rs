@@ -50,11 +50,13 @@
 //! ```
 
 use num_traits::Float;
-use pricer_core::types::time::DayCountConvention;
+use pricer_core::market_data::curves::{CurveName, CurveSet};
+use pricer_core::types::time::{Date, DayCountConvention};
 use pricer_core::types::Currency;
 use std::fmt;
 use std::str::FromStr;
 
+use super::CmsIndex;
 use crate::schedules::Schedule;
 
 /// Interest rate benchmark index.
@@ -73,6 +75,7 @@ use crate::schedules::Schedule;
 /// assert_eq!(index.tenor_months(), 0); // overnight
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum RateIndex {
     /// Secured Overnight Financing Rate (USD).
     /// US dollar overnight rate based on Treasury repo transactions.
@@ -150,6 +153,18 @@ impl RateIndex {
             RateIndex::Saron => DayCountConvention::ActualActual360,
         }
     }
+
+    /// Returns the name of the forward curve projecting this index.
+    #[inline]
+    pub fn curve_name(&self) -> CurveName {
+        match self {
+            RateIndex::Sofr => CurveName::Sofr,
+            RateIndex::Tonar => CurveName::Tonar,
+            RateIndex::Euribor3M | RateIndex::Euribor6M => CurveName::Euribor,
+            RateIndex::Sonia => CurveName::Custom("SONIA"),
+            RateIndex::Saron => CurveName::Custom("SARON"),
+        }
+    }
 }
 
 impl fmt::Display for RateIndex {
@@ -180,6 +195,7 @@ impl FromStr for RateIndex {
 /// - **PayFixed** (Payer swap): Pay fixed rate, receive floating rate
 /// - **ReceiveFixed** (Receiver swap): Receive fixed rate, pay floating rate
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SwapDirection {
     /// Pay fixed rate, receive floating rate.
     PayFixed,
@@ -234,6 +250,7 @@ impl fmt::Display for SwapDirection {
 /// CF_i = Notional × FixedRate × YearFraction_i
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FixedLeg<T: Float> {
     /// Payment schedule.
     schedule: Schedule,
@@ -295,7 +312,11 @@ impl<T: Float> FixedLeg<T> {
 /// ```text
 /// CF_i = Notional × (ForwardRate_i + Spread) × YearFraction_i
 /// ```
+///
+/// A leg referencing a [`CmsIndex`] pays the swap rate of the CMS tenor,
+/// fixed at each period start, in place of the index forward rate.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FloatingLeg<T: Float> {
     /// Payment schedule.
     schedule: Schedule,
@@ -305,6 +326,9 @@ pub struct FloatingLeg<T: Float> {
     index: RateIndex,
     /// Day count convention for accrual calculation.
     day_count: DayCountConvention,
+    /// CMS index paid in place of the index forward rate.
+    #[cfg_attr(feature = "serde", serde(default))]
+    cms_index: Option<CmsIndex>,
 }
 
 impl<T: Float> FloatingLeg<T> {
@@ -327,9 +351,16 @@ impl<T: Float> FloatingLeg<T> {
             spread,
             index,
             day_count,
+            cms_index: None,
         }
     }
 
+    /// Pay the CMS rate of `cms_index` instead of the index forward rate.
+    pub fn with_cms_index(mut self, cms_index: CmsIndex) -> Self {
+        self.cms_index = Some(cms_index);
+        self
+    }
+
     /// Returns the payment schedule.
     #[inline]
     pub fn schedule(&self) -> &Schedule {
@@ -354,6 +385,18 @@ impl<T: Float> FloatingLeg<T> {
         self.day_count
     }
 
+    /// Returns the CMS index, if the leg pays a CMS rate.
+    #[inline]
+    pub fn cms_index(&self) -> Option<CmsIndex> {
+        self.cms_index
+    }
+
+    /// Returns whether the leg pays a CMS rate.
+    #[inline]
+    pub fn is_cms(&self) -> bool {
+        self.cms_index.is_some()
+    }
+
     /// Returns the number of payment periods.
     #[inline]
     pub fn num_periods(&self) -> usize {
@@ -419,6 +462,7 @@ impl<T: Float> FloatingLeg<T> {
 /// assert_eq!(swap.currency(), Currency::USD);
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InterestRateSwap<T: Float> {
     /// Notional principal amount.
     notional: T,
@@ -561,6 +605,32 @@ impl<T: Float> InterestRateSwap<T> {
     pub fn is_receiver(&self) -> bool {
         self.direction == SwapDirection::ReceiveFixed
     }
+
+    /// Returns the par swap rate; see [`par_swap_rate`](super::par_swap_rate).
+    pub fn par_rate(&self, curves: &CurveSet<T>, valuation_date: Date) -> T {
+        super::par_swap_rate(self, curves, valuation_date)
+    }
+
+    /// Returns the annuity per unit notional; see [`swap_annuity`](super::swap_annuity).
+    pub fn annuity(&self, curves: &CurveSet<T>, valuation_date: Date) -> T {
+        super::swap_annuity(self, curves, valuation_date)
+    }
+
+    /// Returns the PV01; see [`swap_pv01`](super::swap_pv01).
+    pub fn pv01(&self, curves: &CurveSet<T>, valuation_date: Date) -> T {
+        super::swap_pv01(self, curves, valuation_date)
+    }
+
+    /// Returns the net accrued interest; see
+    /// [`swap_accrued_interest`](super::swap_accrued_interest).
+    pub fn accrued_interest(&self, curves: &CurveSet<T>, valuation_date: Date) -> T {
+        super::swap_accrued_interest(self, curves, valuation_date)
+    }
+
+    /// Returns the carry to `horizon_date`; see [`swap_carry`](super::swap_carry).
+    pub fn carry(&self, curves: &CurveSet<T>, valuation_date: Date, horizon_date: Date) -> T {
+        super::swap_carry(self, curves, valuation_date, horizon_date)
+    }
 }
 
 #[cfg(test)]
@@ -593,6 +663,14 @@ mod tests {
         assert_eq!(RateIndex::Saron.tenor_months(), 0);
     }
 
+    #[test]
+    fn test_rate_index_curve_name() {
+        assert_eq!(RateIndex::Sofr.curve_name(), CurveName::Sofr);
+        assert_eq!(RateIndex::Euribor3M.curve_name(), CurveName::Euribor);
+        assert_eq!(RateIndex::Euribor6M.curve_name(), CurveName::Euribor);
+        assert_eq!(RateIndex::Sonia.curve_name(), CurveName::Custom("SONIA"));
+    }
+
     #[test]
     fn test_rate_index_is_overnight() {
         assert!(RateIndex::Sofr.is_overnight());
@@ -746,6 +824,22 @@ mod tests {
         assert!((floating_leg.spread() - 0.005).abs() < 1e-10);
         assert_eq!(floating_leg.index(), RateIndex::Euribor6M);
         assert!(!floating_leg.schedule().periods().is_empty());
+        assert!(!floating_leg.is_cms());
+    }
+
+    #[test]
+    fn test_floating_leg_cms_index() {
+        let cms10y = CmsIndex::new(RateIndex::Euribor6M, 10);
+        let floating_leg = FloatingLeg::new(
+            create_test_schedule(),
+            0.0,
+            RateIndex::Euribor6M,
+            DayCountConvention::ActualActual360,
+        )
+        .with_cms_index(cms10y);
+
+        assert!(floating_leg.is_cms());
+        assert_eq!(floating_leg.cms_index(), Some(cms10y));
     }
 
     // ========================================
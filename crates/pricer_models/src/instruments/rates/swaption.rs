@@ -69,6 +69,7 @@ use super::InterestRateSwap;
 
 /// Swaption type (payer or receiver).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SwaptionType {
     /// Right to enter a payer swap (pay fixed, receive floating).
     Payer,
@@ -87,6 +88,7 @@ impl fmt::Display for SwaptionType {
 
 /// Swaption exercise style.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SwaptionStyle {
     /// European: exercise only at expiry.
     European,
@@ -109,6 +111,7 @@ impl fmt::Display for SwaptionStyle {
 /// to enter into an interest rate swap at a predetermined rate
 /// (the strike rate) on or before the expiry date.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Swaption<T: Float> {
     /// Underlying swap that will be entered upon exercise.
     underlying: InterestRateSwap<T>,
@@ -0,0 +1,587 @@
+//! Product taxonomy and registry.
+//!
+//! Trade adapters (FpML, CDM, internal booking feeds) identify products
+//! by external codes: ISDA OTC taxonomy paths such as
+//! `InterestRate:IRSwap:FixedFloat`, or a firm's internal product codes.
+//! [`ProductRegistry`] maps those codes to constructors of [`Instrument`]
+//! variants, so that an adapter only extracts the economic terms of a
+//! trade into [`ProductTerms`] and leaves construction, and the market
+//! conventions for terms the trade leaves unstated, to the registry.
+
+use std::collections::HashMap;
+
+use num_traits::Float;
+use pricer_core::types::Currency;
+
+use super::error::InstrumentError;
+use super::exercise::ExerciseStyle;
+use super::forward::{Direction, Forward};
+use super::params::InstrumentParams;
+use super::payoff::PayoffType;
+use super::swap::{PaymentFrequency, Swap};
+use super::vanilla::VanillaOption;
+use super::{AssetClass, Instrument};
+
+/// ISDA taxonomy code of fixed-for-floating interest rate swaps.
+pub const ISDA_IR_SWAP_FIXED_FLOAT: &str = "InterestRate:IRSwap:FixedFloat";
+/// ISDA taxonomy code of overnight indexed swaps.
+pub const ISDA_IR_SWAP_OIS: &str = "InterestRate:IRSwap:OIS";
+/// ISDA taxonomy code of single-name equity options.
+pub const ISDA_EQUITY_OPTION_SINGLE_NAME: &str =
+    "Equity:Option:PriceReturnBasicPerformance:SingleName";
+/// ISDA taxonomy code of equity index options.
+pub const ISDA_EQUITY_OPTION_SINGLE_INDEX: &str =
+    "Equity:Option:PriceReturnBasicPerformance:SingleIndex";
+/// ISDA taxonomy code of single-name equity forwards.
+pub const ISDA_EQUITY_FORWARD_SINGLE_NAME: &str =
+    "Equity:Forward:PriceReturnBasicPerformance:SingleName";
+/// ISDA taxonomy code of deliverable FX forwards.
+pub const ISDA_FX_FORWARD: &str = "ForeignExchange:Forward";
+/// ISDA taxonomy code of vanilla FX options.
+pub const ISDA_FX_VANILLA_OPTION: &str = "ForeignExchange:VanillaOption";
+
+/// Constructor of an instrument from trade terms and market conventions.
+///
+/// The `&str` argument is the product code being built, for error
+/// messages.
+pub type ProductConstructor<T> =
+    fn(&str, &ProductTerms<T>, &MarketConventions) -> Result<Instrument<T>, InstrumentError>;
+
+/// Economic terms of a trade as extracted by an adapter.
+///
+/// Terms a trade does not state are left as `None`; each constructor
+/// requires the terms its product needs and takes the rest from the
+/// [`MarketConventions`] of the trade currency.
+///
+/// # Examples
+/// ```
+/// use pricer_models::instruments::{PayoffType, ProductTerms};
+/// use pricer_core::types::Currency;
+///
+/// let terms = ProductTerms::new(Currency::USD)
+///     .with_notional(1_000_000.0_f64)
+///     .with_strike(100.0)
+///     .with_expiry(0.5)
+///     .with_payoff_type(PayoffType::Put);
+/// assert_eq!(terms.strike, Some(100.0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ProductTerms<T: Float> {
+    /// Trade currency, selecting the market conventions
+    pub currency: Currency,
+    /// Notional amount
+    pub notional: Option<T>,
+    /// Option or forward strike
+    pub strike: Option<T>,
+    /// Swap fixed rate
+    pub fixed_rate: Option<T>,
+    /// Option expiry, forward delivery or swap maturity in years
+    pub expiry: Option<T>,
+    /// Call or put
+    pub payoff_type: Option<PayoffType>,
+    /// Option exercise style
+    pub exercise_style: Option<ExerciseStyle<T>>,
+    /// Long or short
+    pub direction: Option<Direction>,
+    /// Swap fixed leg payment frequency
+    pub payment_frequency: Option<PaymentFrequency>,
+}
+
+impl<T: Float> ProductTerms<T> {
+    /// Creates empty terms in the given currency.
+    pub fn new(currency: Currency) -> Self {
+        Self {
+            currency,
+            notional: None,
+            strike: None,
+            fixed_rate: None,
+            expiry: None,
+            payoff_type: None,
+            exercise_style: None,
+            direction: None,
+            payment_frequency: None,
+        }
+    }
+
+    /// Sets the notional.
+    pub fn with_notional(mut self, notional: T) -> Self {
+        self.notional = Some(notional);
+        self
+    }
+
+    /// Sets the strike.
+    pub fn with_strike(mut self, strike: T) -> Self {
+        self.strike = Some(strike);
+        self
+    }
+
+    /// Sets the swap fixed rate.
+    pub fn with_fixed_rate(mut self, fixed_rate: T) -> Self {
+        self.fixed_rate = Some(fixed_rate);
+        self
+    }
+
+    /// Sets the expiry or maturity in years.
+    pub fn with_expiry(mut self, expiry: T) -> Self {
+        self.expiry = Some(expiry);
+        self
+    }
+
+    /// Sets the payoff type.
+    pub fn with_payoff_type(mut self, payoff_type: PayoffType) -> Self {
+        self.payoff_type = Some(payoff_type);
+        self
+    }
+
+    /// Sets the exercise style.
+    pub fn with_exercise_style(mut self, exercise_style: ExerciseStyle<T>) -> Self {
+        self.exercise_style = Some(exercise_style);
+        self
+    }
+
+    /// Sets the direction.
+    pub fn with_direction(mut self, direction: Direction) -> Self {
+        self.direction = Some(direction);
+        self
+    }
+
+    /// Sets the swap fixed leg payment frequency.
+    pub fn with_payment_frequency(mut self, frequency: PaymentFrequency) -> Self {
+        self.payment_frequency = Some(frequency);
+        self
+    }
+}
+
+/// Default conventions of a market, applied to terms a trade leaves
+/// unstated.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MarketConventions {
+    /// Fixed leg payment frequency of vanilla swaps
+    pub fixed_frequency: PaymentFrequency,
+    /// Overnight indexed swap payment frequency
+    pub ois_frequency: PaymentFrequency,
+    /// Payoff smoothing width for options
+    pub smoothing_epsilon: f64,
+}
+
+impl MarketConventions {
+    /// Standard conventions of a currency's swap market.
+    ///
+    /// USD and JPY swaps pay fixed semi-annually, EUR, GBP and CHF swaps
+    /// annually; overnight indexed swaps pay annually in all five.
+    ///
+    /// # Examples
+    /// ```
+    /// use pricer_models::instruments::{MarketConventions, PaymentFrequency};
+    /// use pricer_core::types::Currency;
+    ///
+    /// let usd = MarketConventions::for_currency(Currency::USD);
+    /// assert_eq!(usd.fixed_frequency, PaymentFrequency::SemiAnnual);
+    /// ```
+    pub fn for_currency(currency: Currency) -> Self {
+        let fixed_frequency = match currency {
+            Currency::USD | Currency::JPY => PaymentFrequency::SemiAnnual,
+            _ => PaymentFrequency::Annual,
+        };
+        Self {
+            fixed_frequency,
+            ois_frequency: PaymentFrequency::Annual,
+            smoothing_epsilon: 1e-6,
+        }
+    }
+}
+
+/// A registered product: its asset class and constructor.
+#[derive(Debug, Clone, Copy)]
+pub struct ProductDefinition<T: Float> {
+    /// Asset class for routing and reporting
+    pub asset_class: AssetClass,
+    /// Instrument constructor
+    pub constructor: ProductConstructor<T>,
+}
+
+/// Registry of product codes and their instrument constructors.
+///
+/// Codes are matched case-insensitively. [`with_defaults`] registers the
+/// ISDA taxonomy codes of the instruments this crate provides together
+/// with short internal aliases (`IRS`, `OIS`, `EQ_OPTION`,
+/// `EQ_INDEX_OPTION`, `EQ_FORWARD`, `FX_FORWARD`, `FX_OPTION`); firms add their own codes with
+/// [`register`] or [`alias`] and override market defaults with
+/// [`set_conventions`].
+///
+/// [`with_defaults`]: ProductRegistry::with_defaults
+/// [`register`]: ProductRegistry::register
+/// [`alias`]: ProductRegistry::alias
+/// [`set_conventions`]: ProductRegistry::set_conventions
+///
+/// # Examples
+/// ```
+/// use pricer_models::instruments::{ProductRegistry, ProductTerms, PaymentFrequency};
+/// use pricer_core::types::Currency;
+///
+/// let registry = ProductRegistry::<f64>::with_defaults();
+///
+/// // A 5-year EUR swap with the fixed frequency left to market convention
+/// let terms = ProductTerms::new(Currency::EUR)
+///     .with_notional(10_000_000.0)
+///     .with_fixed_rate(0.025)
+///     .with_expiry(5.0);
+/// let instrument = registry.build("InterestRate:IRSwap:FixedFloat", &terms).unwrap();
+///
+/// let swap = instrument.as_swap().unwrap();
+/// assert_eq!(swap.frequency(), PaymentFrequency::Annual);
+/// assert_eq!(swap.payment_dates().len(), 5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ProductRegistry<T: Float> {
+    products: HashMap<String, ProductDefinition<T>>,
+    conventions: HashMap<Currency, MarketConventions>,
+}
+
+impl<T: Float> Default for ProductRegistry<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float> ProductRegistry<T> {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self {
+            products: HashMap::new(),
+            conventions: HashMap::new(),
+        }
+    }
+
+    /// Creates a registry with the ISDA taxonomy codes and internal
+    /// aliases of the built-in instruments.
+    pub fn with_defaults() -> Self {
+        let mut registry = Self::new();
+        let defaults: [(&str, &str, AssetClass, ProductConstructor<T>); 7] = [
+            (
+                ISDA_IR_SWAP_FIXED_FLOAT,
+                "IRS",
+                AssetClass::Rates,
+                build_swap,
+            ),
+            (ISDA_IR_SWAP_OIS, "OIS", AssetClass::Rates, build_ois),
+            (
+                ISDA_EQUITY_OPTION_SINGLE_NAME,
+                "EQ_OPTION",
+                AssetClass::Equity,
+                build_option,
+            ),
+            (
+                ISDA_EQUITY_OPTION_SINGLE_INDEX,
+                "EQ_INDEX_OPTION",
+                AssetClass::Equity,
+                build_option,
+            ),
+            (
+                ISDA_EQUITY_FORWARD_SINGLE_NAME,
+                "EQ_FORWARD",
+                AssetClass::Equity,
+                build_forward,
+            ),
+            (ISDA_FX_FORWARD, "FX_FORWARD", AssetClass::Fx, build_forward),
+            (
+                ISDA_FX_VANILLA_OPTION,
+                "FX_OPTION",
+                AssetClass::Fx,
+                build_option,
+            ),
+        ];
+        for (code, alias, asset_class, constructor) in defaults {
+            registry.register(code, asset_class, constructor);
+            registry
+                .alias(alias, code)
+                .expect("taxonomy code registered above");
+        }
+        registry
+    }
+
+    /// Registers a product code, replacing any previous definition.
+    pub fn register(
+        &mut self,
+        code: &str,
+        asset_class: AssetClass,
+        constructor: ProductConstructor<T>,
+    ) -> &mut Self {
+        self.products.insert(
+            normalise(code),
+            ProductDefinition {
+                asset_class,
+                constructor,
+            },
+        );
+        self
+    }
+
+    /// Registers `alias` as another code for the product `code`.
+    ///
+    /// # Errors
+    /// - `UnknownProduct`: If `code` is not registered
+    pub fn alias(&mut self, alias: &str, code: &str) -> Result<&mut Self, InstrumentError> {
+        let definition = *self.get(code)?;
+        self.products.insert(normalise(alias), definition);
+        Ok(self)
+    }
+
+    /// Overrides the market conventions of a currency.
+    pub fn set_conventions(&mut self, currency: Currency, conventions: MarketConventions) {
+        self.conventions.insert(currency, conventions);
+    }
+
+    /// Returns the market conventions of a currency: the override if set,
+    /// otherwise [`MarketConventions::for_currency`].
+    pub fn conventions(&self, currency: Currency) -> MarketConventions {
+        self.conventions
+            .get(&currency)
+            .copied()
+            .unwrap_or_else(|| MarketConventions::for_currency(currency))
+    }
+
+    /// Returns whether a product code is registered.
+    pub fn contains(&self, code: &str) -> bool {
+        self.products.contains_key(&normalise(code))
+    }
+
+    /// Returns the definition of a product code.
+    ///
+    /// # Errors
+    /// - `UnknownProduct`: If `code` is not registered
+    pub fn get(&self, code: &str) -> Result<&ProductDefinition<T>, InstrumentError> {
+        self.products
+            .get(&normalise(code))
+            .ok_or_else(|| InstrumentError::UnknownProduct {
+                code: code.to_string(),
+            })
+    }
+
+    /// Returns the asset class of a product code.
+    ///
+    /// # Errors
+    /// - `UnknownProduct`: If `code` is not registered
+    pub fn asset_class(&self, code: &str) -> Result<AssetClass, InstrumentError> {
+        self.get(code).map(|definition| definition.asset_class)
+    }
+
+    /// Constructs the instrument for a product code from trade terms.
+    ///
+    /// # Errors
+    /// - `UnknownProduct`: If `code` is not registered
+    /// - `MissingTerm`: If a term the product requires is absent
+    /// - Any validation error of the instrument constructor
+    pub fn build(
+        &self,
+        code: &str,
+        terms: &ProductTerms<T>,
+    ) -> Result<Instrument<T>, InstrumentError> {
+        let definition = self.get(code)?;
+        (definition.constructor)(code, terms, &self.conventions(terms.currency))
+    }
+}
+
+/// Registry key of a product code.
+fn normalise(code: &str) -> String {
+    code.trim().to_ascii_uppercase()
+}
+
+/// Returns a required term or a `MissingTerm` error.
+fn require<V>(code: &str, term: &'static str, value: Option<V>) -> Result<V, InstrumentError> {
+    value.ok_or_else(|| InstrumentError::MissingTerm {
+        code: code.to_string(),
+        term,
+    })
+}
+
+/// Vanilla option; European unless an exercise style is given.
+fn build_option<T: Float>(
+    code: &str,
+    terms: &ProductTerms<T>,
+    conventions: &MarketConventions,
+) -> Result<Instrument<T>, InstrumentError> {
+    let params = InstrumentParams::new(
+        require(code, "strike", terms.strike)?,
+        require(code, "expiry", terms.expiry)?,
+        require(code, "notional", terms.notional)?,
+    )?;
+    Ok(Instrument::Vanilla(VanillaOption::new(
+        params,
+        require(code, "payoff_type", terms.payoff_type)?,
+        terms
+            .exercise_style
+            .clone()
+            .unwrap_or(ExerciseStyle::European),
+        T::from(conventions.smoothing_epsilon).unwrap(),
+    )))
+}
+
+/// Forward; long unless a direction is given.
+fn build_forward<T: Float>(
+    code: &str,
+    terms: &ProductTerms<T>,
+    _conventions: &MarketConventions,
+) -> Result<Instrument<T>, InstrumentError> {
+    Ok(Instrument::Forward(Forward::new(
+        require(code, "strike", terms.strike)?,
+        require(code, "expiry", terms.expiry)?,
+        require(code, "notional", terms.notional)?,
+        terms.direction.unwrap_or(Direction::Long),
+    )?))
+}
+
+/// Fixed-for-floating swap paying at the market fixed leg frequency.
+fn build_swap<T: Float>(
+    code: &str,
+    terms: &ProductTerms<T>,
+    conventions: &MarketConventions,
+) -> Result<Instrument<T>, InstrumentError> {
+    swap_with_frequency(code, terms, conventions.fixed_frequency)
+}
+
+/// Overnight indexed swap paying at the market OIS frequency.
+fn build_ois<T: Float>(
+    code: &str,
+    terms: &ProductTerms<T>,
+    conventions: &MarketConventions,
+) -> Result<Instrument<T>, InstrumentError> {
+    swap_with_frequency(code, terms, conventions.ois_frequency)
+}
+
+/// Swap with regular payments from the end of the first period to
+/// maturity, and a short final stub if the maturity is not a whole number
+/// of periods.
+fn swap_with_frequency<T: Float>(
+    code: &str,
+    terms: &ProductTerms<T>,
+    default_frequency: PaymentFrequency,
+) -> Result<Instrument<T>, InstrumentError> {
+    let maturity = require(code, "expiry", terms.expiry)?;
+    if maturity.is_nan() || maturity <= T::zero() {
+        return Err(InstrumentError::InvalidExpiry {
+            expiry: maturity.to_f64().unwrap_or(f64::NAN),
+        });
+    }
+    let frequency = terms.payment_frequency.unwrap_or(default_frequency);
+    let period = frequency.period_fraction::<T>();
+    let tolerance = T::from(1e-9).unwrap();
+
+    let mut payment_dates = Vec::new();
+    let mut date = period;
+    while date < maturity - tolerance {
+        payment_dates.push(date);
+        date = date + period;
+    }
+    payment_dates.push(maturity);
+
+    Ok(Instrument::Swap(Swap::new(
+        require(code, "notional", terms.notional)?,
+        require(code, "fixed_rate", terms.fixed_rate)?,
+        payment_dates,
+        frequency,
+        terms.currency,
+    )?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn option_terms() -> ProductTerms<f64> {
+        ProductTerms::new(Currency::USD)
+            .with_notional(1.0)
+            .with_strike(100.0)
+            .with_expiry(1.0)
+            .with_payoff_type(PayoffType::Call)
+    }
+
+    #[test]
+    fn test_taxonomy_and_alias_build_same_instrument() {
+        let registry = ProductRegistry::<f64>::with_defaults();
+        let by_taxonomy = registry
+            .build(ISDA_EQUITY_OPTION_SINGLE_NAME, &option_terms())
+            .unwrap();
+        let by_alias = registry.build("eq_option", &option_terms()).unwrap();
+
+        assert!(by_taxonomy.is_vanilla());
+        assert!((by_taxonomy.payoff(110.0) - by_alias.payoff(110.0)).abs() < 1e-12);
+        assert_eq!(registry.asset_class("FX_OPTION").unwrap(), AssetClass::Fx);
+    }
+
+    #[test]
+    fn test_swap_conventions_per_market() {
+        let registry = ProductRegistry::<f64>::with_defaults();
+        let terms = |currency| {
+            ProductTerms::new(currency)
+                .with_notional(1e6)
+                .with_fixed_rate(0.03)
+                .with_expiry(2.25)
+        };
+
+        let usd = registry.build("IRS", &terms(Currency::USD)).unwrap();
+        let usd = usd.as_swap().unwrap();
+        assert_eq!(usd.frequency(), PaymentFrequency::SemiAnnual);
+        assert_eq!(usd.payment_dates(), &[0.5, 1.0, 1.5, 2.0, 2.25]);
+
+        let gbp = registry.build("IRS", &terms(Currency::GBP)).unwrap();
+        assert_eq!(gbp.as_swap().unwrap().payment_dates(), &[1.0, 2.0, 2.25]);
+
+        // Explicit trade terms win over market defaults
+        let quarterly = terms(Currency::GBP).with_payment_frequency(PaymentFrequency::Quarterly);
+        let swap = registry.build("IRS", &quarterly).unwrap();
+        assert_eq!(swap.as_swap().unwrap().payment_dates().len(), 9);
+
+        // Registry overrides replace the built-in conventions
+        let mut registry = registry;
+        registry.set_conventions(
+            Currency::USD,
+            MarketConventions {
+                ois_frequency: PaymentFrequency::Quarterly,
+                ..MarketConventions::for_currency(Currency::USD)
+            },
+        );
+        let ois = registry.build("OIS", &terms(Currency::USD)).unwrap();
+        assert_eq!(
+            ois.as_swap().unwrap().frequency(),
+            PaymentFrequency::Quarterly
+        );
+    }
+
+    #[test]
+    fn test_custom_codes_and_errors() {
+        let mut registry = ProductRegistry::<f64>::with_defaults();
+        registry
+            .alias("BOOK-7/VANILLA", ISDA_FX_VANILLA_OPTION)
+            .unwrap();
+        assert!(registry.contains("book-7/vanilla"));
+
+        assert_eq!(
+            registry.alias("X", "NoSuchProduct").unwrap_err(),
+            InstrumentError::UnknownProduct {
+                code: "NoSuchProduct".to_string()
+            }
+        );
+        assert!(matches!(
+            registry.build("Credit:SingleName:Corporate", &option_terms()),
+            Err(InstrumentError::UnknownProduct { .. })
+        ));
+
+        let no_strike = ProductTerms::new(Currency::EUR)
+            .with_notional(1.0)
+            .with_expiry(1.0);
+        assert_eq!(
+            registry.build("FX_FORWARD", &no_strike).unwrap_err(),
+            InstrumentError::MissingTerm {
+                code: "FX_FORWARD".to_string(),
+                term: "strike"
+            }
+        );
+
+        let expired = option_terms().with_expiry(0.0);
+        assert!(matches!(
+            registry.build("EQ_OPTION", &expired),
+            Err(InstrumentError::InvalidExpiry { .. })
+        ));
+    }
+}
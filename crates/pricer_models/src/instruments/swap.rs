@@ -26,6 +26,7 @@ use super::error::InstrumentError;
 /// assert_eq!(freq.periods_per_year(), 4);
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PaymentFrequency {
     /// Annual payments (once per year)
     Annual,
@@ -104,6 +105,7 @@ impl PaymentFrequency {
 /// assert_eq!(swap.fixed_rate(), 0.02);
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Swap<T: Float> {
     notional: T,
     fixed_rate: T,
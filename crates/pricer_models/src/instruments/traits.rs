@@ -131,6 +131,7 @@ pub trait InstrumentTrait<T: Float> {
 ///
 /// * `T` - Floating-point type implementing `Float`
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Cashflow<T: Float> {
     /// Payment date as time in years from valuation date.
     pub payment_time: T,
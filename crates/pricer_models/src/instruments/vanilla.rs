@@ -36,6 +36,7 @@ use super::payoff::PayoffType;
 /// assert!((payoff - 10_000_000.0).abs() < 1000.0); // notional * (S - K)
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VanillaOption<T: Float> {
     params: InstrumentParams<T>,
     payoff_type: PayoffType,
@@ -50,7 +51,8 @@ impl<T: Float> VanillaOption<T> {
     /// * `params` - Instrument parameters (strike, expiry, notional)
     /// * `payoff_type` - Type of payoff (Call, Put, Digital)
     /// * `exercise_style` - Exercise style (European, American, etc.)
-    /// * `epsilon` - Smoothing parameter for AD-compatible payoff
+    /// * `epsilon` - Smoothing parameter for AD-compatible payoff, unless
+    ///   `params` carries an override
     ///
     /// # Examples
     /// ```
@@ -113,7 +115,7 @@ impl<T: Float> VanillaOption<T> {
     pub fn payoff(&self, spot: T) -> T {
         let unit_payoff = self
             .payoff_type
-            .evaluate(spot, self.params.strike(), self.epsilon);
+            .evaluate(spot, self.params.strike(), self.epsilon());
         self.params.notional() * unit_payoff
     }
 
@@ -135,10 +137,11 @@ impl<T: Float> VanillaOption<T> {
         &self.exercise_style
     }
 
-    /// Returns the smoothing epsilon.
+    /// Returns the smoothing epsilon: the override in the instrument
+    /// parameters if set, otherwise the one the option was built with.
     #[inline]
     pub fn epsilon(&self) -> T {
-        self.epsilon
+        self.params.smoothing_epsilon().unwrap_or(self.epsilon)
     }
 
     /// Returns the strike price.
@@ -248,6 +251,25 @@ mod tests {
         assert!(payoff_otm < 0.01);
     }
 
+    #[test]
+    fn test_params_smoothing_override() {
+        let params = create_test_params().with_smoothing_epsilon(1.0).unwrap();
+        let digital = VanillaOption::new(
+            params,
+            PayoffType::DigitalCall,
+            ExerciseStyle::European,
+            1e-6,
+        );
+
+        // One strike unit above the barrier is only σ(1) with ε = 1
+        assert_eq!(digital.epsilon(), 1.0);
+        assert_relative_eq!(
+            digital.payoff(101.0),
+            1.0 / (1.0 + (-1.0_f64).exp()),
+            epsilon = 1e-12
+        );
+    }
+
     #[test]
     fn test_american_option() {
         let params = create_test_params();
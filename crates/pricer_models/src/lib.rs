@@ -27,6 +27,9 @@ pub mod instruments;
 pub mod models;
 pub mod schedules;
 
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
 #[cfg(test)]
 mod tests {
     #[test]
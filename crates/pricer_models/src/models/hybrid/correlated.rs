@@ -37,7 +37,9 @@
 //! assert_eq!(w.len(), 2);
 //! ```
 
+use pricer_core::math::linalg::{cholesky, nearest_correlation, symmetric_eigen};
 use pricer_core::traits::Float;
+use pricer_core::types::LinalgError;
 
 /// Error types for correlation operations.
 #[derive(Debug, Clone, PartialEq)]
@@ -216,6 +218,46 @@ impl<T: Float> CorrelationMatrix<T> {
         self.data[i * self.dim + j]
     }
 
+    /// Nearest valid correlation matrix to `data`, for estimated or
+    /// stressed correlations that are not positive definite.
+    ///
+    /// Repairs the matrix by Higham's alternating projections (see
+    /// [`nearest_correlation`]) with eigenvalues floored at `1e-8`, so the
+    /// result always has a Cholesky factor.
+    ///
+    /// # Returns
+    ///
+    /// `Err(CorrelationError)` if `data` has the wrong number of elements
+    /// or is not symmetric.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pricer_models::models::hybrid::correlated::CorrelationMatrix;
+    ///
+    /// // Pairwise correlations that are not jointly consistent
+    /// let data = [
+    ///     1.0_f64, 0.9, 0.7,
+    ///     0.9, 1.0, -0.4,
+    ///     0.7, -0.4, 1.0,
+    /// ];
+    /// assert!(CorrelationMatrix::new(&data, 3).unwrap().cholesky().is_err());
+    /// assert!(CorrelationMatrix::nearest(&data, 3).unwrap().cholesky().is_ok());
+    /// ```
+    pub fn nearest(data: &[T], dim: usize) -> Result<Self, CorrelationError> {
+        let floor = T::from(1e-8).unwrap_or(T::zero());
+        let repaired = nearest_correlation(data, dim, floor).map_err(|e| match e {
+            LinalgError::InvalidDimensions { expected, got } => {
+                CorrelationError::InvalidDimensions { expected, got }
+            }
+            LinalgError::NotSymmetric { i, j } => CorrelationError::NotSymmetric { i, j },
+            LinalgError::NotPositiveDefinite { .. } | LinalgError::NotConverged { .. } => {
+                CorrelationError::NotPositiveDefinite
+            }
+        })?;
+        Self::new(&repaired, dim)
+    }
+
     /// Compute Cholesky decomposition (lower triangular L where C = L * L^T).
     ///
     /// # Returns
@@ -223,43 +265,23 @@ impl<T: Float> CorrelationMatrix<T> {
     /// `Ok(CholeskyFactor)` if decomposition succeeds (matrix is positive definite),
     /// `Err(CorrelationError::NotPositiveDefinite)` otherwise.
     pub fn cholesky(&self) -> Result<CholeskyFactor<T>, CorrelationError> {
-        let n = self.dim;
-        let mut lower = vec![T::zero(); n * n];
-
-        for i in 0..n {
-            for j in 0..=i {
-                let mut sum = T::zero();
-
-                if j == i {
-                    // Diagonal element
-                    for k in 0..j {
-                        let l_jk = lower[j * n + k];
-                        sum = sum + l_jk * l_jk;
-                    }
-                    let diag = self.get(j, j) - sum;
-                    if diag <= T::zero() {
-                        return Err(CorrelationError::NotPositiveDefinite);
-                    }
-                    lower[j * n + j] = diag.sqrt();
-                } else {
-                    // Off-diagonal element
-                    for k in 0..j {
-                        sum = sum + lower[i * n + k] * lower[j * n + k];
-                    }
-                    let l_jj = lower[j * n + j];
-                    if l_jj <= T::zero() {
-                        return Err(CorrelationError::NotPositiveDefinite);
-                    }
-                    lower[i * n + j] = (self.get(i, j) - sum) / l_jj;
-                }
-            }
-        }
-
+        let lower =
+            cholesky(&self.data, self.dim).map_err(|_| CorrelationError::NotPositiveDefinite)?;
         Ok(CholeskyFactor {
             data: lower,
-            dim: n,
+            dim: self.dim,
         })
     }
+
+    /// Eigenvalues in ascending order.
+    ///
+    /// The smallest eigenvalue measures how close the matrix is to
+    /// singular; it is negative for an invalid matrix.
+    pub fn eigenvalues(&self) -> Result<Vec<T>, CorrelationError> {
+        symmetric_eigen(&self.data, self.dim)
+            .map(|eigen| eigen.values().to_vec())
+            .map_err(|_| CorrelationError::NotPositiveDefinite)
+    }
 }
 
 /// Lower triangular Cholesky factor of a correlation matrix.
@@ -319,8 +341,8 @@ impl<T: Float> CholeskyFactor<T> {
 
         for i in 0..n {
             let mut sum = T::zero();
-            for j in 0..=i {
-                sum = sum + self.get(i, j) * z[j];
+            for (j, &zj) in z.iter().enumerate().take(i + 1) {
+                sum = sum + self.get(i, j) * zj;
             }
             w.push(sum);
         }
@@ -347,12 +369,12 @@ impl<T: Float> CholeskyFactor<T> {
         let mut temp = vec![T::zero(); n];
 
         // Compute W = L * Z
-        for i in 0..n {
+        for (i, out) in temp.iter_mut().enumerate() {
             let mut sum = T::zero();
-            for j in 0..=i {
-                sum = sum + self.get(i, j) * z[j];
+            for (j, &zj) in z.iter().enumerate().take(i + 1) {
+                sum = sum + self.get(i, j) * zj;
             }
-            temp[i] = sum;
+            *out = sum;
         }
 
         // Copy back
@@ -578,6 +600,29 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_nearest_repairs_inconsistent_correlations() {
+        let data = [
+            1.0_f64, 0.9, 0.7, //
+            0.9, 1.0, -0.4, //
+            0.7, -0.4, 1.0,
+        ];
+        let invalid = CorrelationMatrix::new(&data, 3).unwrap();
+        assert!(invalid.eigenvalues().unwrap()[0] < 0.0);
+        assert!(invalid.cholesky().is_err());
+
+        let repaired = CorrelationMatrix::nearest(&data, 3).unwrap();
+        assert!(repaired.eigenvalues().unwrap()[0] > 0.0);
+        assert!(repaired.cholesky().is_ok());
+        // Signs of the pairwise correlations survive the repair
+        assert!(repaired.get(0, 1) > 0.0 && repaired.get(1, 2) < 0.0);
+
+        assert!(matches!(
+            CorrelationMatrix::nearest(&[1.0_f64, 0.5, 0.2, 1.0], 2),
+            Err(CorrelationError::NotSymmetric { i: 0, j: 1 })
+        ));
+    }
+
     // Test: CholeskyFactor transform
     #[test]
     fn test_cholesky_transform_identity() {
@@ -0,0 +1,435 @@
+//! Fixture-driven conformance checks for day counts and schedules.
+//!
+//! Banks validate a pricing library against their own conventions library
+//! by running it over reference datasets. This module reads such datasets
+//! as CSV fixtures, recomputes every row with
+//! [`DayCountConvention::year_fraction_dates`] and [`ScheduleBuilder`],
+//! and reports each mismatch rather than stopping at the first.
+//!
+//! # Fixture Format
+//!
+//! Fixtures are comma-separated with a header row. Columns are matched by
+//! name, so their order is free and extra columns are ignored. Blank lines
+//! and lines starting with `#` are skipped. Fields are trimmed and may not
+//! contain commas or quotes.
+//!
+//! Day count fixtures have one row per date pair:
+//!
+//! | Column       | Content                                   |
+//! |--------------|-------------------------------------------|
+//! | `case`       | Optional case identifier                  |
+//! | `start`      | Start date, `YYYY-MM-DD`                  |
+//! | `end`        | End date, `YYYY-MM-DD`                    |
+//! | `convention` | Day count, e.g. `ACT/360`, `30/360`       |
+//! | `expected`   | Expected year fraction                    |
+//!
+//! Schedule fixtures have one row per expected period, with consecutive
+//! rows sharing a `case` forming one schedule:
+//!
+//! | Column          | Content                                    |
+//! |-----------------|--------------------------------------------|
+//! | `case`          | Case identifier                            |
+//! | `start`         | Schedule start date                        |
+//! | `end`           | Schedule end date                          |
+//! | `frequency`     | Frequency, e.g. `quarterly`, `6M`          |
+//! | `day_count`     | Day count convention                       |
+//! | `period_start`  | Expected accrual start                     |
+//! | `period_end`    | Expected accrual end                       |
+//! | `payment`       | Expected payment date                      |
+//! | `year_fraction` | Expected accrual year fraction             |
+//!
+//! The schedule parameters are read from the first row of each case.
+//!
+//! # Examples
+//!
+//! ```
+//! use pricer_models::schedules::conformance::check_day_counts;
+//!
+//! let fixture = "\
+//! start,end,convention,expected
+//! 2024-01-31,2024-03-31,30/360,0.1666666667
+//! 2024-01-01,2025-01-01,ACT/365,1.0027397260
+//! ";
+//! let report = check_day_counts(fixture, 1e-9).unwrap();
+//! assert_eq!(report.cases, 2);
+//! assert!(report.is_pass());
+//! ```
+
+use super::error::ConformanceError;
+use super::frequency::Frequency;
+use super::schedule::ScheduleBuilder;
+use pricer_core::types::time::{Date, DayCountConvention};
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+/// A single mismatch between a fixture and the library.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConformanceFailure {
+    /// One-based fixture line of the row that failed.
+    pub line: usize,
+    /// Case identifier, or the line number if the fixture has none.
+    pub case: String,
+    /// Compared quantity, e.g. `year_fraction` or `period_end`.
+    pub field: &'static str,
+    /// Value in the fixture.
+    pub expected: String,
+    /// Value computed by the library.
+    pub actual: String,
+}
+
+impl fmt::Display for ConformanceFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {} [{}] {}: expected {}, got {}",
+            self.line, self.case, self.field, self.expected, self.actual
+        )
+    }
+}
+
+/// Outcome of running a fixture.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ConformanceReport {
+    /// Number of cases checked: date pairs for day counts, schedules for
+    /// schedule fixtures.
+    pub cases: usize,
+    /// All mismatches found.
+    pub failures: Vec<ConformanceFailure>,
+}
+
+impl ConformanceReport {
+    /// Returns whether every case matched.
+    #[inline]
+    pub fn is_pass(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Returns the number of cases without a mismatch.
+    pub fn passed(&self) -> usize {
+        let mut failed: Vec<&str> = self.failures.iter().map(|f| f.case.as_str()).collect();
+        failed.sort_unstable();
+        failed.dedup();
+        self.cases - failed.len()
+    }
+}
+
+impl fmt::Display for ConformanceReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{} cases passed", self.passed(), self.cases)?;
+        for failure in &self.failures {
+            write!(f, "\n  {}", failure)?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks year fractions against a day count fixture.
+///
+/// Year fractions within `tolerance` of the expected value match.
+///
+/// # Errors
+///
+/// Returns an error if the fixture is empty, lacks a required column or
+/// has a field that cannot be parsed. Mismatches are not errors; they are
+/// collected in the report.
+pub fn check_day_counts(csv: &str, tolerance: f64) -> Result<ConformanceReport, ConformanceError> {
+    let table = Table::parse(csv)?;
+    let start = table.column("start")?;
+    let end = table.column("end")?;
+    let convention = table.column("convention")?;
+    let expected = table.column("expected")?;
+    let case = table.optional_column("case");
+
+    let mut report = ConformanceReport::default();
+    for row in &table.rows {
+        let start: Date = row.parse(start, "start")?;
+        let end: Date = row.parse(end, "end")?;
+        let convention: DayCountConvention = row.parse(convention, "convention")?;
+        let expected: f64 = row.parse(expected, "expected")?;
+
+        report.cases += 1;
+        let actual = convention.year_fraction_dates(start, end);
+        if (actual - expected).abs() > tolerance {
+            report.failures.push(ConformanceFailure {
+                line: row.line,
+                case: row.case(case),
+                field: "year_fraction",
+                expected: expected.to_string(),
+                actual: actual.to_string(),
+            });
+        }
+    }
+    Ok(report)
+}
+
+/// Checks generated schedules against a schedule fixture.
+///
+/// Each case is built with [`ScheduleBuilder`] and compared period by
+/// period; year fractions within `tolerance` match. A schedule that fails
+/// to build or has the wrong number of periods is reported as a single
+/// failure on its `periods` field.
+///
+/// # Errors
+///
+/// Returns an error if the fixture is empty, lacks a required column or
+/// has a field that cannot be parsed.
+pub fn check_schedules(csv: &str, tolerance: f64) -> Result<ConformanceReport, ConformanceError> {
+    let table = Table::parse(csv)?;
+    let case = table.column("case")?;
+    let start = table.column("start")?;
+    let end = table.column("end")?;
+    let frequency = table.column("frequency")?;
+    let day_count = table.column("day_count")?;
+    let period_start = table.column("period_start")?;
+    let period_end = table.column("period_end")?;
+    let payment = table.column("payment")?;
+    let year_fraction = table.column("year_fraction")?;
+
+    let mut report = ConformanceReport::default();
+    for rows in table.rows.chunk_by(|a, b| a.field(case) == b.field(case)) {
+        let first = &rows[0];
+        let id = first.field(case).to_string();
+        let frequency: Frequency = first.parse(frequency, "frequency")?;
+        let day_count: DayCountConvention = first.parse(day_count, "day_count")?;
+        let built = ScheduleBuilder::new()
+            .start(first.parse(start, "start")?)
+            .end(first.parse(end, "end")?)
+            .frequency(frequency)
+            .day_count(day_count)
+            .build();
+
+        report.cases += 1;
+        let schedule = match built {
+            Ok(schedule) if schedule.len() == rows.len() => schedule,
+            other => {
+                report.failures.push(ConformanceFailure {
+                    line: first.line,
+                    case: id,
+                    field: "periods",
+                    expected: rows.len().to_string(),
+                    actual: match other {
+                        Ok(schedule) => schedule.len().to_string(),
+                        Err(e) => e.to_string(),
+                    },
+                });
+                continue;
+            }
+        };
+
+        for (row, period) in rows.iter().zip(schedule.periods()) {
+            let dates = [
+                ("period_start", period_start, period.start()),
+                ("period_end", period_end, period.end()),
+                ("payment", payment, period.payment()),
+            ];
+            for (field, index, actual) in dates {
+                let expected: Date = row.parse(index, field)?;
+                if expected != actual {
+                    report.failures.push(ConformanceFailure {
+                        line: row.line,
+                        case: id.clone(),
+                        field,
+                        expected: expected.to_string(),
+                        actual: actual.to_string(),
+                    });
+                }
+            }
+
+            let expected: f64 = row.parse(year_fraction, "year_fraction")?;
+            let actual = period.year_fraction();
+            if (actual - expected).abs() > tolerance {
+                report.failures.push(ConformanceFailure {
+                    line: row.line,
+                    case: id.clone(),
+                    field: "year_fraction",
+                    expected: expected.to_string(),
+                    actual: actual.to_string(),
+                });
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Checks year fractions against a day count fixture file.
+///
+/// See [`check_day_counts`].
+pub fn check_day_count_file(
+    path: impl AsRef<Path>,
+    tolerance: f64,
+) -> Result<ConformanceReport, ConformanceError> {
+    check_day_counts(&read_fixture(path.as_ref())?, tolerance)
+}
+
+/// Checks generated schedules against a schedule fixture file.
+///
+/// See [`check_schedules`].
+pub fn check_schedule_file(
+    path: impl AsRef<Path>,
+    tolerance: f64,
+) -> Result<ConformanceReport, ConformanceError> {
+    check_schedules(&read_fixture(path.as_ref())?, tolerance)
+}
+
+/// Reads a fixture file into a string.
+fn read_fixture(path: &Path) -> Result<String, ConformanceError> {
+    std::fs::read_to_string(path).map_err(|e| ConformanceError::Io {
+        path: path.display().to_string(),
+        reason: e.to_string(),
+    })
+}
+
+/// Parsed CSV fixture: header names and data rows.
+struct Table<'a> {
+    header: Vec<&'a str>,
+    rows: Vec<Row<'a>>,
+}
+
+/// A data row with its one-based line number.
+struct Row<'a> {
+    line: usize,
+    fields: Vec<&'a str>,
+}
+
+impl<'a> Table<'a> {
+    /// Splits a fixture into header and rows, skipping blank and comment
+    /// lines.
+    fn parse(csv: &'a str) -> Result<Self, ConformanceError> {
+        let mut lines = csv
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+        let (_, header) = lines.next().ok_or(ConformanceError::Empty)?;
+        let split = |line: &'a str| line.split(',').map(str::trim).collect::<Vec<_>>();
+
+        Ok(Self {
+            header: split(header),
+            rows: lines
+                .map(|(line, text)| Row {
+                    line,
+                    fields: split(text),
+                })
+                .collect(),
+        })
+    }
+
+    /// Index of a required column.
+    fn column(&self, name: &'static str) -> Result<usize, ConformanceError> {
+        self.optional_column(name)
+            .ok_or(ConformanceError::MissingColumn { column: name })
+    }
+
+    /// Index of an optional column.
+    fn optional_column(&self, name: &str) -> Option<usize> {
+        self.header
+            .iter()
+            .position(|&h| h.eq_ignore_ascii_case(name))
+    }
+}
+
+impl Row<'_> {
+    /// Raw field, empty if the row is short.
+    fn field(&self, index: usize) -> &str {
+        self.fields.get(index).copied().unwrap_or("")
+    }
+
+    /// Parses a field.
+    fn parse<T>(&self, index: usize, column: &'static str) -> Result<T, ConformanceError>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        let value = self.field(index);
+        value
+            .parse()
+            .map_err(|e: T::Err| ConformanceError::InvalidField {
+                line: self.line,
+                column,
+                value: value.to_string(),
+                reason: e.to_string(),
+            })
+    }
+
+    /// Case identifier from an optional column, defaulting to the line.
+    fn case(&self, index: Option<usize>) -> String {
+        match index.map(|i| self.field(i)) {
+            Some(id) if !id.is_empty() => id.to_string(),
+            _ => format!("line {}", self.line),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day_count_mismatch_reported() {
+        let fixture = "\
+# ISDA examples
+case,convention,start,end,expected,comment
+a,ACT/360,2024-01-01,2024-07-01,0.5055555556,half year
+b,30/360,2024-02-28,2024-08-31,0.5,wrong: 30/360 US gives 183/360
+
+c,ACT/365,2024-01-01,2024-01-01,0.0,
+";
+        let report = check_day_counts(fixture, 1e-9).unwrap();
+        assert_eq!(report.cases, 3);
+        assert_eq!(report.passed(), 2);
+        assert_eq!(report.failures.len(), 1);
+
+        let failure = &report.failures[0];
+        assert_eq!(failure.line, 4);
+        assert_eq!(failure.case, "b");
+        assert_eq!(failure.field, "year_fraction");
+        assert!(report.to_string().starts_with("2/3 cases passed"));
+    }
+
+    #[test]
+    fn test_schedule_mismatch_reported() {
+        let fixture = "\
+case,start,end,frequency,day_count,period_start,period_end,payment,year_fraction
+ok,2024-01-15,2025-01-15,semiannual,ACT/360,2024-01-15,2024-07-15,2024-07-15,0.5055555556
+ok,2024-01-15,2025-01-15,semiannual,ACT/360,2024-07-15,2025-01-15,2025-01-15,0.5111111111
+short,2024-01-15,2025-01-15,annual,ACT/365,2024-01-15,2024-07-15,2024-07-15,0.5
+short,2024-01-15,2025-01-15,annual,ACT/365,2024-07-15,2025-01-15,2025-01-15,0.5
+";
+        let report = check_schedules(fixture, 1e-9).unwrap();
+        assert_eq!(report.cases, 2);
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].case, "short");
+        assert_eq!(report.failures[0].field, "periods");
+        assert_eq!(report.failures[0].actual, "1");
+    }
+
+    #[test]
+    fn test_malformed_fixtures() {
+        assert_eq!(
+            check_day_counts("# only a comment\n", 0.0),
+            Err(ConformanceError::Empty)
+        );
+        assert_eq!(
+            check_day_counts("start,end,expected\n", 0.0),
+            Err(ConformanceError::MissingColumn {
+                column: "convention"
+            })
+        );
+        assert!(matches!(
+            check_day_counts(
+                "start,end,convention,expected\n2024-01-01,2024-02-30,ACT/360,0.1\n",
+                0.0
+            ),
+            Err(ConformanceError::InvalidField {
+                line: 2,
+                column: "end",
+                ..
+            })
+        ));
+        assert!(matches!(
+            check_day_count_file("does/not/exist.csv", 0.0),
+            Err(ConformanceError::Io { .. })
+        ));
+    }
+}
@@ -49,3 +49,40 @@ pub enum ScheduleError {
         reason: String,
     },
 }
+
+/// Errors that can occur while reading conformance fixtures.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum ConformanceError {
+    /// Fixture file could not be read.
+    #[error("Cannot read fixture file {path}: {reason}")]
+    Io {
+        /// The fixture path.
+        path: String,
+        /// Reason for the failure.
+        reason: String,
+    },
+
+    /// Fixture has no header row.
+    #[error("Fixture is empty")]
+    Empty,
+
+    /// Required column is missing from the header row.
+    #[error("Missing fixture column: {column}")]
+    MissingColumn {
+        /// The name of the missing column.
+        column: &'static str,
+    },
+
+    /// Field could not be parsed.
+    #[error("Line {line}, column {column}: cannot parse {value:?}: {reason}")]
+    InvalidField {
+        /// One-based line number in the fixture.
+        line: usize,
+        /// The column name.
+        column: &'static str,
+        /// The raw field value.
+        value: String,
+        /// Reason for the failure.
+        reason: String,
+    },
+}
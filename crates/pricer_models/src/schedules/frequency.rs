@@ -18,6 +18,7 @@ use std::str::FromStr;
 /// assert_eq!(freq.months_between_payments(), 3);
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Frequency {
     /// Annual payments (once per year).
     Annual,
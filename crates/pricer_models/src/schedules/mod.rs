@@ -5,6 +5,8 @@
 //! - [`Period`]: A single accrual period with start, end, and payment dates
 //! - [`Frequency`]: Payment frequency enumeration (Annual, SemiAnnual, etc.)
 //! - [`ScheduleBuilder`]: Builder pattern for constructing schedules
+//! - [`conformance`]: Fixture-driven checks of day counts and schedules
+//!   against an external conventions library
 //!
 //! # Examples
 //!
@@ -24,12 +26,13 @@
 //! assert_eq!(schedule.periods().len(), 4); // 4 semi-annual periods over 2 years
 //! ```
 
+pub mod conformance;
 mod error;
 mod frequency;
 mod period;
 mod schedule;
 
-pub use error::ScheduleError;
+pub use error::{ConformanceError, ScheduleError};
 pub use frequency::Frequency;
 pub use period::Period;
 pub use schedule::{Schedule, ScheduleBuilder};
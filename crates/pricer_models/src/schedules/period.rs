@@ -26,6 +26,7 @@ use std::fmt;
 /// assert!((period.year_fraction() - 0.5056).abs() < 0.001); // ~182/360
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Period {
     /// Start date of the accrual period.
     start: Date,
@@ -29,6 +29,7 @@ use pricer_core::types::time::{Date, DayCountConvention};
 /// assert_eq!(schedule.payment_dates().len(), 4);
 /// ```
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Schedule {
     /// All periods in the schedule.
     periods: Vec<Period>,
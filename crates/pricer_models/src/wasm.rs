@@ -0,0 +1,237 @@
+//! WebAssembly bindings for the analytical pricers.
+//!
+//! Enabled with the `wasm` feature. Exposes Black-Scholes, Black-76,
+//! Bachelier and barrier closed forms to JavaScript with camelCase names,
+//! so browser dashboards can reprice what-if scenarios without a server
+//! round trip. Invalid inputs throw a JavaScript `Error`.
+//!
+//! Build with `wasm-pack`:
+//!
+//! ```text
+//! wasm-pack build crates/pricer_models --target web \
+//!     --out-dir ../../demo/gui/static/pkg --features wasm
+//! ```
+
+use wasm_bindgen::prelude::*;
+
+use crate::analytical::{
+    AnalyticalError, Bachelier, BarrierKind, Black76, BlackScholes, BlackScholesBarrier,
+};
+
+/// Option price with Black-Scholes Greeks.
+#[wasm_bindgen(js_name = Greeks)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WasmGreeks {
+    /// Option price
+    pub price: f64,
+    /// dV/dS
+    pub delta: f64,
+    /// d²V/dS²
+    pub gamma: f64,
+    /// dV/dσ
+    pub vega: f64,
+    /// dV/dt
+    pub theta: f64,
+    /// dV/dr
+    pub rho: f64,
+}
+
+fn to_js(err: AnalyticalError) -> JsError {
+    JsError::new(&err.to_string())
+}
+
+/// Black-Scholes price of a European option.
+#[wasm_bindgen(js_name = blackScholesPrice)]
+pub fn black_scholes_price(
+    spot: f64,
+    strike: f64,
+    expiry: f64,
+    rate: f64,
+    volatility: f64,
+    is_call: bool,
+) -> Result<f64, JsError> {
+    bs_price(spot, strike, expiry, rate, volatility, is_call).map_err(to_js)
+}
+
+/// Black-Scholes price and Greeks of a European option.
+#[wasm_bindgen(js_name = blackScholesGreeks)]
+pub fn black_scholes_greeks(
+    spot: f64,
+    strike: f64,
+    expiry: f64,
+    rate: f64,
+    volatility: f64,
+    is_call: bool,
+) -> Result<WasmGreeks, JsError> {
+    bs_greeks(spot, strike, expiry, rate, volatility, is_call).map_err(to_js)
+}
+
+/// Black-Scholes prices across a ladder of spots.
+///
+/// Takes and returns a `Float64Array`, one price per spot.
+#[wasm_bindgen(js_name = blackScholesPrices)]
+pub fn black_scholes_prices(
+    spots: &[f64],
+    strike: f64,
+    expiry: f64,
+    rate: f64,
+    volatility: f64,
+    is_call: bool,
+) -> Result<Vec<f64>, JsError> {
+    spots
+        .iter()
+        .map(|&spot| bs_price(spot, strike, expiry, rate, volatility, is_call))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(to_js)
+}
+
+/// Black-76 price of a European option on a forward.
+#[wasm_bindgen(js_name = black76Price)]
+pub fn black76_price(
+    forward: f64,
+    strike: f64,
+    expiry: f64,
+    volatility: f64,
+    discount_factor: f64,
+    is_call: bool,
+) -> Result<f64, JsError> {
+    let model = Black76::new(forward, volatility).map_err(to_js)?;
+    let undiscounted = if is_call {
+        model.price_call(strike, expiry)
+    } else {
+        model.price_put(strike, expiry)
+    };
+    Ok(discount_factor * undiscounted)
+}
+
+/// Bachelier (normal) price of a European option on a forward.
+#[wasm_bindgen(js_name = bachelierPrice)]
+pub fn bachelier_price(
+    forward: f64,
+    strike: f64,
+    expiry: f64,
+    volatility: f64,
+    discount_factor: f64,
+    is_call: bool,
+) -> Result<f64, JsError> {
+    let model = Bachelier::new(forward, volatility).map_err(to_js)?;
+    let undiscounted = if is_call {
+        model.price_call(strike, expiry)
+    } else {
+        model.price_put(strike, expiry)
+    };
+    Ok(discount_factor * undiscounted)
+}
+
+/// Rubinstein-Reiner price of a single-barrier European option.
+///
+/// `kind` is one of `down-in-call`, `down-out-call`, `down-in-put`,
+/// `down-out-put`, `up-in-call`, `up-out-call`, `up-in-put` or
+/// `up-out-put`.
+#[allow(clippy::too_many_arguments)]
+#[wasm_bindgen(js_name = barrierPrice)]
+pub fn barrier_price(
+    kind: &str,
+    spot: f64,
+    strike: f64,
+    barrier: f64,
+    expiry: f64,
+    rate: f64,
+    dividend: f64,
+    volatility: f64,
+) -> Result<f64, JsError> {
+    let (kind, is_call) = parse_barrier(kind)
+        .ok_or_else(|| JsError::new(&format!("Unknown barrier type: {}", kind)))?;
+    let model = BlackScholesBarrier::new(spot, rate, dividend, volatility).map_err(to_js)?;
+    Ok(model.price(kind, is_call, strike, barrier, expiry))
+}
+
+fn bs_price(
+    spot: f64,
+    strike: f64,
+    expiry: f64,
+    rate: f64,
+    volatility: f64,
+    is_call: bool,
+) -> Result<f64, AnalyticalError> {
+    let model = BlackScholes::new(spot, rate, volatility)?;
+    Ok(if is_call {
+        model.price_call(strike, expiry)
+    } else {
+        model.price_put(strike, expiry)
+    })
+}
+
+fn bs_greeks(
+    spot: f64,
+    strike: f64,
+    expiry: f64,
+    rate: f64,
+    volatility: f64,
+    is_call: bool,
+) -> Result<WasmGreeks, AnalyticalError> {
+    let model = BlackScholes::new(spot, rate, volatility)?;
+    Ok(WasmGreeks {
+        price: if is_call {
+            model.price_call(strike, expiry)
+        } else {
+            model.price_put(strike, expiry)
+        },
+        delta: model.delta(strike, expiry, is_call),
+        gamma: model.gamma(strike, expiry),
+        vega: model.vega(strike, expiry),
+        theta: model.theta(strike, expiry, is_call),
+        rho: model.rho(strike, expiry, is_call),
+    })
+}
+
+fn parse_barrier(kind: &str) -> Option<(BarrierKind, bool)> {
+    let kind = kind.to_ascii_lowercase();
+    let (barrier, option) = kind.rsplit_once('-')?;
+    let barrier = match barrier {
+        "down-in" => BarrierKind::DownIn,
+        "down-out" => BarrierKind::DownOut,
+        "up-in" => BarrierKind::UpIn,
+        "up-out" => BarrierKind::UpOut,
+        _ => return None,
+    };
+    match option {
+        "call" => Some((barrier, true)),
+        "put" => Some((barrier, false)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_bs_greeks_match_model() {
+        let greeks = bs_greeks(100.0, 100.0, 1.0, 0.05, 0.2, true).unwrap();
+        let model = BlackScholes::new(100.0, 0.05, 0.2).unwrap();
+
+        assert_relative_eq!(greeks.price, model.price_call(100.0, 1.0));
+        assert_relative_eq!(greeks.delta, model.delta(100.0, 1.0, true));
+        assert_relative_eq!(greeks.vega, model.vega(100.0, 1.0));
+        assert!(matches!(
+            bs_price(-1.0, 100.0, 1.0, 0.05, 0.2, true),
+            Err(AnalyticalError::InvalidSpot { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_barrier() {
+        assert_eq!(
+            parse_barrier("up-out-put"),
+            Some((BarrierKind::UpOut, false))
+        );
+        assert_eq!(
+            parse_barrier("Down-In-Call"),
+            Some((BarrierKind::DownIn, true))
+        );
+        assert_eq!(parse_barrier("double-knock-out"), None);
+        assert_eq!(parse_barrier("up-out-digital"), None);
+    }
+}
@@ -0,0 +1,30 @@
+//! Conformance of day counts and schedules against the bundled fixtures.
+//!
+//! The fixtures in `tests/fixtures` double as templates for users checking
+//! the library against their own conventions library through
+//! `pricer_models::schedules::conformance`.
+
+use pricer_models::schedules::conformance::{check_day_count_file, check_schedule_file};
+use std::path::PathBuf;
+
+/// Path of a bundled fixture.
+fn fixture(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(name)
+}
+
+#[test]
+fn test_day_count_fixture() {
+    let report = check_day_count_file(fixture("day_count.csv"), 1e-10).unwrap();
+    assert_eq!(report.cases, 12);
+    assert!(report.is_pass(), "{}", report);
+}
+
+#[test]
+fn test_schedule_fixture() {
+    let report = check_schedule_file(fixture("schedules.csv"), 1e-10).unwrap();
+    assert_eq!(report.cases, 6);
+    assert!(report.is_pass(), "{}", report);
+}
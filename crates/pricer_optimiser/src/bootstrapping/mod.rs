@@ -11,6 +11,7 @@
 //! - `CurveBootstrapper<T>`: Sequential bootstrapping engine
 //! - `BootstrappedCurve<T>`: Result curve implementing `YieldCurve<T>`
 //! - `MultiCurveBuilder<T>`: OIS discount + tenor curve construction
+//! - `CurveRiskTransform`: zero ↔ par ↔ forward sensitivity conversion
 //!
 //! ## AAD Support
 //!
@@ -28,6 +29,7 @@ mod engine;
 mod error;
 mod instrument;
 mod multi_curve;
+mod risk_transform;
 mod sensitivity;
 
 pub use adjoint_solver::{
@@ -45,6 +47,7 @@ pub use engine::{CachedBootstrapper, GenericBootstrapResult, SequentialBootstrap
 pub use error::BootstrapError;
 pub use instrument::{BootstrapInstrument, Frequency};
 pub use multi_curve::{CurveSet, MultiCurveBuilder, ParallelCurveSetBuilder, Tenor};
+pub use risk_transform::{CurveRepresentation, CurveRiskTransform};
 pub use sensitivity::{
     BootstrapResultWithSensitivities, SensitivityBootstrapper, SensitivityVerification,
 };
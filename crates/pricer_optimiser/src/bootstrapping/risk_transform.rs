@@ -0,0 +1,387 @@
+//! Curve risk transformation between zero, par and forward representations.
+//!
+//! Sensitivities computed against one curve parameterisation are mapped to
+//! another with the chain rule. For a value `V` and representations `x`
+//! and `y`, `∂V/∂y = (∂x/∂y)ᵀ ∂V/∂x`. All conversions go through the
+//! zero-rate representation:
+//!
+//! - **Zero rates** `zᵢ = −ln(DFᵢ) / tᵢ` at each pillar
+//! - **Par rates** `rⱼ`, the quotes of the bootstrap instruments, linked to
+//!   zero rates by the bootstrap Jacobian `∂z/∂r = diag(−1 / (tᵢ DFᵢ)) · ∂DF/∂r`
+//! - **Forward rates** `fᵢ = ln(DFᵢ₋₁ / DFᵢ) / (tᵢ − tᵢ₋₁)`, continuously
+//!   compounded between consecutive pillars, so that `zᵢ tᵢ = Σₖ≤ᵢ fₖ Δtₖ`
+//!
+//! Reporting par sensitivities expresses risk against the tradeable
+//! instruments the curve was built from, ready to hedge.
+
+use super::error::BootstrapError;
+use super::sensitivity::BootstrapResultWithSensitivities;
+use crate::solvers::solve_linear_system;
+
+/// Curve parameterisation for sensitivities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CurveRepresentation {
+    /// Continuously compounded zero rates at the pillars
+    ZeroRate,
+    /// Quoted rates of the bootstrap instruments
+    ParRate,
+    /// Continuously compounded forward rates between consecutive pillars
+    ForwardRate,
+}
+
+/// Jacobian-based converter of curve sensitivities.
+///
+/// # Examples
+///
+/// ```
+/// use pricer_optimiser::bootstrapping::{
+///     BootstrapInstrument, CurveRepresentation, CurveRiskTransform, SensitivityBootstrapper,
+/// };
+///
+/// let instruments = vec![
+///     BootstrapInstrument::ois(1.0, 0.030),
+///     BootstrapInstrument::ois(2.0, 0.032),
+///     BootstrapInstrument::ois(3.0, 0.034),
+/// ];
+/// let result = SensitivityBootstrapper::with_defaults()
+///     .bootstrap_with_bump_and_revalue(&instruments)
+///     .unwrap();
+/// let transform = CurveRiskTransform::from_bootstrap(&result).unwrap();
+///
+/// // Zero-rate delta of a position, re-expressed against the OIS quotes
+/// let zero_delta = [0.0, -150.0, 0.0];
+/// let par_delta = transform
+///     .transform(&zero_delta, CurveRepresentation::ZeroRate, CurveRepresentation::ParRate)
+///     .unwrap();
+/// assert_eq!(par_delta.len(), 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CurveRiskTransform {
+    pillars: Vec<f64>,
+    discount_factors: Vec<f64>,
+    /// `zero_par[i][j]` = ∂zᵢ/∂rⱼ
+    zero_par: Vec<Vec<f64>>,
+}
+
+impl CurveRiskTransform {
+    /// Create a transform from pillars, discount factors and the bootstrap
+    /// Jacobian `df_jacobian[i][j]` = ∂DFᵢ/∂rⱼ.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BootstrapError::InvalidInput` if the dimensions disagree,
+    /// pillars are not strictly increasing and positive, or a discount
+    /// factor is not positive.
+    pub fn new(
+        pillars: Vec<f64>,
+        discount_factors: Vec<f64>,
+        df_jacobian: Vec<Vec<f64>>,
+    ) -> Result<Self, BootstrapError> {
+        let n = pillars.len();
+        if n == 0 {
+            return Err(BootstrapError::insufficient_data(1, 0));
+        }
+        if discount_factors.len() != n || df_jacobian.len() != n {
+            return Err(BootstrapError::invalid_input(format!(
+                "{} pillars, {} discount factors and {} Jacobian rows",
+                n,
+                discount_factors.len(),
+                df_jacobian.len()
+            )));
+        }
+        let n_inputs = df_jacobian[0].len();
+        if df_jacobian.iter().any(|row| row.len() != n_inputs) {
+            return Err(BootstrapError::invalid_input(
+                "Jacobian rows have different lengths",
+            ));
+        }
+        let mut previous = 0.0;
+        for (&t, &df) in pillars.iter().zip(&discount_factors) {
+            if t <= previous || df.is_nan() || df <= 0.0 {
+                return Err(BootstrapError::invalid_input(format!(
+                    "pillar {} with discount factor {} (pillars must be positive and increasing)",
+                    t, df
+                )));
+            }
+            previous = t;
+        }
+
+        let zero_par = df_jacobian
+            .iter()
+            .zip(pillars.iter().zip(&discount_factors))
+            .map(|(row, (&t, &df))| row.iter().map(|d| -d / (t * df)).collect())
+            .collect();
+
+        Ok(Self {
+            pillars,
+            discount_factors,
+            zero_par,
+        })
+    }
+
+    /// Create a transform from a bootstrap with sensitivities.
+    pub fn from_bootstrap(
+        result: &BootstrapResultWithSensitivities,
+    ) -> Result<Self, BootstrapError> {
+        Self::new(
+            result.pillars.clone(),
+            result.discount_factors.clone(),
+            result.sensitivities.clone(),
+        )
+    }
+
+    /// Pillar maturities.
+    pub fn pillars(&self) -> &[f64] {
+        &self.pillars
+    }
+
+    /// Zero rates at the pillars.
+    pub fn zero_rates(&self) -> Vec<f64> {
+        self.pillars
+            .iter()
+            .zip(&self.discount_factors)
+            .map(|(t, df)| -df.ln() / t)
+            .collect()
+    }
+
+    /// Forward rates between consecutive pillars (the first from time 0).
+    pub fn forward_rates(&self) -> Vec<f64> {
+        let mut previous = (0.0, 1.0);
+        self.pillars
+            .iter()
+            .zip(&self.discount_factors)
+            .map(|(&t, &df)| {
+                let forward = (previous.1 / df).ln() / (t - previous.0);
+                previous = (t, df);
+                forward
+            })
+            .collect()
+    }
+
+    /// Jacobian `∂z/∂r` of zero rates with respect to par rates.
+    pub fn zero_par_jacobian(&self) -> &[Vec<f64>] {
+        &self.zero_par
+    }
+
+    /// Jacobian `∂z/∂f` of zero rates with respect to forward rates.
+    pub fn zero_forward_jacobian(&self) -> Vec<Vec<f64>> {
+        let n = self.pillars.len();
+        (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|k| {
+                        if k <= i {
+                            self.period(k) / self.pillars[i]
+                        } else {
+                            0.0
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Convert sensitivities `∂V/∂x` from one representation to another.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BootstrapError::InvalidInput` if the sensitivity vector has
+    /// the wrong length, or when converting from par rates with a
+    /// non-square or singular bootstrap Jacobian.
+    pub fn transform(
+        &self,
+        sensitivities: &[f64],
+        from: CurveRepresentation,
+        to: CurveRepresentation,
+    ) -> Result<Vec<f64>, BootstrapError> {
+        let expected = match from {
+            CurveRepresentation::ParRate => self.zero_par[0].len(),
+            _ => self.pillars.len(),
+        };
+        if sensitivities.len() != expected {
+            return Err(BootstrapError::invalid_input(format!(
+                "expected {} {:?} sensitivities, got {}",
+                expected,
+                from,
+                sensitivities.len()
+            )));
+        }
+        if from == to {
+            return Ok(sensitivities.to_vec());
+        }
+
+        let zero = match from {
+            CurveRepresentation::ZeroRate => sensitivities.to_vec(),
+            CurveRepresentation::ParRate => {
+                if self.zero_par[0].len() != self.pillars.len() {
+                    return Err(BootstrapError::invalid_input(
+                        "par Jacobian is not square; cannot convert from par rates",
+                    ));
+                }
+                // ∂V/∂r = (∂z/∂r)ᵀ ∂V/∂z, solved for ∂V/∂z
+                solve_linear_system(&transpose(&self.zero_par), sensitivities)
+                    .map_err(|_| BootstrapError::invalid_input("par Jacobian is singular"))?
+            }
+            CurveRepresentation::ForwardRate => self.zero_from_forward(sensitivities),
+        };
+
+        Ok(match to {
+            CurveRepresentation::ZeroRate => zero,
+            CurveRepresentation::ParRate => transposed_product(&self.zero_par, &zero),
+            CurveRepresentation::ForwardRate => {
+                transposed_product(&self.zero_forward_jacobian(), &zero)
+            }
+        })
+    }
+
+    /// Length of the forward period ending at pillar `k`.
+    fn period(&self, k: usize) -> f64 {
+        self.pillars[k] - if k == 0 { 0.0 } else { self.pillars[k - 1] }
+    }
+
+    /// `∂V/∂z = (∂f/∂z)ᵀ ∂V/∂f` with `∂fᵢ/∂zᵢ = tᵢ/Δtᵢ` and
+    /// `∂fᵢ/∂zᵢ₋₁ = −tᵢ₋₁/Δtᵢ`.
+    fn zero_from_forward(&self, forward: &[f64]) -> Vec<f64> {
+        let n = self.pillars.len();
+        (0..n)
+            .map(|i| {
+                let own = forward[i] * self.pillars[i] / self.period(i);
+                let next = if i + 1 < n {
+                    forward[i + 1] * self.pillars[i] / self.period(i + 1)
+                } else {
+                    0.0
+                };
+                own - next
+            })
+            .collect()
+    }
+}
+
+fn transpose(matrix: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let cols = matrix.first().map_or(0, Vec::len);
+    (0..cols)
+        .map(|j| matrix.iter().map(|row| row[j]).collect())
+        .collect()
+}
+
+/// `Aᵀ v`.
+fn transposed_product(matrix: &[Vec<f64>], v: &[f64]) -> Vec<f64> {
+    let cols = matrix.first().map_or(0, Vec::len);
+    (0..cols)
+        .map(|j| matrix.iter().zip(v).map(|(row, vi)| row[j] * vi).sum())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bootstrapping::{BootstrapInstrument, SensitivityBootstrapper};
+
+    fn transform() -> (CurveRiskTransform, BootstrapResultWithSensitivities) {
+        let instruments = vec![
+            BootstrapInstrument::ois(1.0, 0.030),
+            BootstrapInstrument::ois(2.0, 0.032),
+            BootstrapInstrument::irs(3.0, 0.035),
+            BootstrapInstrument::irs(5.0, 0.037),
+        ];
+        let result = SensitivityBootstrapper::with_defaults()
+            .with_bump_size(1e-6)
+            .bootstrap_with_bump_and_revalue(&instruments)
+            .unwrap();
+        (CurveRiskTransform::from_bootstrap(&result).unwrap(), result)
+    }
+
+    #[test]
+    fn test_zero_coupon_bond_par_risk() {
+        // V = DF(t₂): ∂V/∂z₂ = −t₂ DF₂, so par risk is the bootstrap Jacobian row
+        let (transform, result) = transform();
+        let mut zero = vec![0.0; 4];
+        zero[2] = -result.pillars[2] * result.discount_factors[2];
+
+        let par = transform
+            .transform(
+                &zero,
+                CurveRepresentation::ZeroRate,
+                CurveRepresentation::ParRate,
+            )
+            .unwrap();
+        for (p, expected) in par.iter().zip(&result.sensitivities[2]) {
+            assert!((p - expected).abs() < 1e-10);
+        }
+        // Later quotes do not move an earlier pillar
+        assert!(par[3].abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_zero_coupon_bond_forward_risk() {
+        // V = exp(−Σₖ≤₂ fₖ Δtₖ): ∂V/∂fₖ = −Δtₖ V for k ≤ 2
+        let (transform, result) = transform();
+        let value = result.discount_factors[2];
+        let mut zero = vec![0.0; 4];
+        zero[2] = -result.pillars[2] * value;
+
+        let forward = transform
+            .transform(
+                &zero,
+                CurveRepresentation::ZeroRate,
+                CurveRepresentation::ForwardRate,
+            )
+            .unwrap();
+        // Pillars at 1, 2, 3 and 5 years, so Δtₖ = 1 up to pillar 2
+        let expected = [-value, -value, -value, 0.0];
+        for (f, e) in forward.iter().zip(expected) {
+            assert!((f - e).abs() < 1e-12, "{:?}", forward);
+        }
+    }
+
+    #[test]
+    fn test_round_trips() {
+        use CurveRepresentation::*;
+        let (transform, _) = transform();
+        let zero = [120.0, -40.0, 15.0, -300.0];
+
+        for via in [ParRate, ForwardRate] {
+            let there = transform.transform(&zero, ZeroRate, via).unwrap();
+            let back = transform.transform(&there, via, ZeroRate).unwrap();
+            for (a, b) in back.iter().zip(zero) {
+                assert!((a - b).abs() < 1e-8, "{:?}: {:?}", via, back);
+            }
+        }
+
+        let par = transform.transform(&zero, ZeroRate, ParRate).unwrap();
+        let forward = transform.transform(&par, ParRate, ForwardRate).unwrap();
+        let direct = transform.transform(&zero, ZeroRate, ForwardRate).unwrap();
+        for (a, b) in forward.iter().zip(direct) {
+            assert!((a - b).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_rates_consistent() {
+        let (transform, _) = transform();
+        let zero = transform.zero_rates();
+        let forward = transform.forward_rates();
+        let pillars = transform.pillars();
+        let mut integrated = 0.0;
+        for i in 0..pillars.len() {
+            integrated += forward[i] * (pillars[i] - if i == 0 { 0.0 } else { pillars[i - 1] });
+            assert!((integrated - zero[i] * pillars[i]).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let (transform, _) = transform();
+        assert!(transform
+            .transform(
+                &[1.0],
+                CurveRepresentation::ZeroRate,
+                CurveRepresentation::ParRate
+            )
+            .is_err());
+        assert!(
+            CurveRiskTransform::new(vec![1.0, 0.5], vec![0.99, 0.98], vec![vec![0.0]; 2]).is_err()
+        );
+    }
+}
@@ -1,6 +1,14 @@
 //! Calibration engine implementation.
 
+use super::problem::{
+    fd_jacobian, standard_errors, CalibrationProblem, CalibrationReport, CalibrationSolver,
+    JacobianSource,
+};
 use crate::error::OptimiserError;
+use crate::solvers::{
+    Bounds, DifferentialEvolution, DifferentialEvolutionConfig, LbfgsB, LbfgsBConfig,
+    LevenbergMarquardt, LevenbergMarquardtConfig, NelderMead, NelderMeadConfig,
+};
 
 /// Configuration for model calibration.
 #[derive(Debug, Clone)]
@@ -148,6 +156,128 @@ impl CalibrationEngine {
             residual,
         })
     }
+
+    /// Calibrate a [`CalibrationProblem`] with the chosen solver.
+    ///
+    /// The problem's exact Jacobian is used when it supplies one and
+    /// `use_finite_diff` is off; otherwise finite differences with
+    /// `fd_step` are used. The report carries the Jacobian at the solution,
+    /// the objective history and asymptotic parameter standard errors.
+    ///
+    /// # Errors
+    ///
+    /// Returns `OptimiserError::InsufficientData` if the problem has no
+    /// residuals, `OptimiserError::InvalidBounds` if the bounds do not match
+    /// the parameters (or are infinite for differential evolution), and the
+    /// solver's error if it fails to converge.
+    pub fn solve_problem<P: CalibrationProblem + ?Sized>(
+        &self,
+        problem: &P,
+        solver: CalibrationSolver,
+    ) -> Result<CalibrationReport, OptimiserError> {
+        let n = problem.n_parameters();
+        let initial = problem.initial_guess();
+        let bounds = problem.bounds().unwrap_or_else(|| Bounds::unbounded(n));
+        bounds.check_dimension(n)?;
+        let m = problem.residuals(&bounds.project(&initial)).len();
+        if m == 0 {
+            return Err(OptimiserError::InsufficientData {
+                required: 1,
+                provided: 0,
+            });
+        }
+
+        let exact =
+            !self.config.use_finite_diff && problem.jacobian(&bounds.project(&initial)).is_some();
+        let residuals = |p: &[f64]| problem.residuals(&bounds.project(p));
+        let jacobian = |p: &[f64]| {
+            let p = bounds.project(p);
+            if exact {
+                problem.jacobian(&p).expect("problem supplied a Jacobian")
+            } else {
+                fd_jacobian(
+                    problem,
+                    &p,
+                    &problem.residuals(&p),
+                    &bounds,
+                    self.config.fd_step,
+                )
+            }
+        };
+        let objective = |p: &[f64]| residuals(p).iter().map(|r| r * r).sum::<f64>();
+
+        let result = match solver {
+            CalibrationSolver::LevenbergMarquardt => {
+                let lm = LevenbergMarquardt::with_config(LevenbergMarquardtConfig {
+                    max_iterations: self.config.max_iterations,
+                    residual_tolerance: self.config.tolerance,
+                    fd_step: self.config.fd_step,
+                    ..Default::default()
+                });
+                if exact {
+                    lm.solve_with_jacobian(&initial, residuals, jacobian)?
+                } else {
+                    lm.solve(&initial, residuals)?
+                }
+            }
+            CalibrationSolver::LbfgsB => {
+                let solver = LbfgsB::with_config(LbfgsBConfig {
+                    max_iterations: self.config.max_iterations,
+                    fd_step: self.config.fd_step,
+                    ..Default::default()
+                });
+                if exact {
+                    let gradient = |p: &[f64]| {
+                        let (r, j) = (residuals(p), jacobian(p));
+                        (0..n)
+                            .map(|k| {
+                                2.0 * j.iter().zip(&r).map(|(row, ri)| row[k] * ri).sum::<f64>()
+                            })
+                            .collect()
+                    };
+                    solver.minimise_with_gradient(&initial, &bounds, objective, gradient)?
+                } else {
+                    solver.minimise(&initial, &bounds, objective)?
+                }
+            }
+            CalibrationSolver::NelderMead => NelderMead::with_config(NelderMeadConfig {
+                max_iterations: self.config.max_iterations,
+                objective_tolerance: self.config.tolerance,
+                ..Default::default()
+            })
+            .minimise(&initial, &bounds, objective)?,
+            CalibrationSolver::DifferentialEvolution => {
+                DifferentialEvolution::with_config(DifferentialEvolutionConfig {
+                    max_generations: self.config.max_iterations,
+                    tolerance: self.config.tolerance,
+                    ..Default::default()
+                })
+                .minimise(&initial, &bounds, objective)?
+            }
+        };
+
+        let parameters = bounds.project(&result.parameters);
+        let final_residuals = problem.residuals(&parameters);
+        let ssr: f64 = final_residuals.iter().map(|r| r * r).sum();
+        let jacobian = jacobian(&parameters);
+
+        Ok(CalibrationReport {
+            standard_errors: standard_errors(&jacobian, ssr),
+            parameters,
+            residuals: final_residuals,
+            jacobian,
+            jacobian_source: if exact {
+                JacobianSource::Exact
+            } else {
+                JacobianSource::FiniteDifference
+            },
+            objective: ssr,
+            history: result.history,
+            iterations: result.iterations,
+            function_evaluations: result.function_evaluations,
+            converged: result.converged,
+        })
+    }
 }
 
 impl Default for CalibrationEngine {
@@ -179,4 +309,134 @@ mod tests {
         let result = result.unwrap();
         assert!((result.parameters[0] - 2.0).abs() < 0.1);
     }
+
+    /// Straight-line fit `y = a + b x` with deterministic noise.
+    struct LineFit {
+        x: Vec<f64>,
+        y: Vec<f64>,
+        exact_jacobian: bool,
+        bounds: Option<Bounds>,
+    }
+
+    impl LineFit {
+        fn new() -> Self {
+            let x: Vec<f64> = (0..10).map(|i| i as f64).collect();
+            let noise = [0.3, -0.2, 0.1, -0.4, 0.2, 0.0, -0.1, 0.3, -0.3, 0.1];
+            let y = x
+                .iter()
+                .zip(noise)
+                .map(|(x, e)| 1.0 + 0.5 * x + e)
+                .collect();
+            Self {
+                x,
+                y,
+                exact_jacobian: false,
+                bounds: None,
+            }
+        }
+
+        /// Ordinary least squares estimates and standard errors.
+        fn ols(&self) -> ([f64; 2], [f64; 2]) {
+            let m = self.x.len() as f64;
+            let x_mean = self.x.iter().sum::<f64>() / m;
+            let y_mean = self.y.iter().sum::<f64>() / m;
+            let sxx: f64 = self.x.iter().map(|x| (x - x_mean).powi(2)).sum();
+            let sxy: f64 = self
+                .x
+                .iter()
+                .zip(&self.y)
+                .map(|(x, y)| (x - x_mean) * (y - y_mean))
+                .sum();
+            let b = sxy / sxx;
+            let a = y_mean - b * x_mean;
+            let ssr: f64 = self.residuals(&[a, b]).iter().map(|r| r * r).sum();
+            let sigma2 = ssr / (m - 2.0);
+            let se_b = (sigma2 / sxx).sqrt();
+            let se_a = (sigma2 * (1.0 / m + x_mean * x_mean / sxx)).sqrt();
+            ([a, b], [se_a, se_b])
+        }
+    }
+
+    impl CalibrationProblem for LineFit {
+        fn initial_guess(&self) -> Vec<f64> {
+            vec![0.0, 0.0]
+        }
+
+        fn residuals(&self, p: &[f64]) -> Vec<f64> {
+            self.x
+                .iter()
+                .zip(&self.y)
+                .map(|(x, y)| p[0] + p[1] * x - y)
+                .collect()
+        }
+
+        fn jacobian(&self, _p: &[f64]) -> Option<Vec<Vec<f64>>> {
+            self.exact_jacobian
+                .then(|| self.x.iter().map(|x| vec![1.0, *x]).collect())
+        }
+
+        fn bounds(&self) -> Option<Bounds> {
+            self.bounds.clone()
+        }
+    }
+
+    #[test]
+    fn test_problem_standard_errors() {
+        let problem = LineFit {
+            exact_jacobian: true,
+            ..LineFit::new()
+        };
+        let ([a, b], [se_a, se_b]) = problem.ols();
+        let engine = CalibrationEngine::with_config(CalibrationConfig {
+            use_finite_diff: false,
+            ..Default::default()
+        });
+
+        let report = engine
+            .solve_problem(&problem, CalibrationSolver::LevenbergMarquardt)
+            .unwrap();
+        assert_eq!(report.jacobian_source, JacobianSource::Exact);
+        assert!((report.parameters[0] - a).abs() < 1e-6);
+        assert!((report.parameters[1] - b).abs() < 1e-6);
+        let errors = report.standard_errors.unwrap();
+        assert!((errors[0] - se_a).abs() < 1e-8);
+        assert!((errors[1] - se_b).abs() < 1e-8);
+        assert!(!report.history.is_empty());
+    }
+
+    #[test]
+    fn test_problem_solvers_agree() {
+        let problem = LineFit {
+            bounds: Some(Bounds::new(vec![-5.0, -5.0], vec![5.0, 5.0]).unwrap()),
+            ..LineFit::new()
+        };
+        let ([a, b], _) = problem.ols();
+        let engine = CalibrationEngine::new();
+
+        for solver in [
+            CalibrationSolver::LevenbergMarquardt,
+            CalibrationSolver::LbfgsB,
+            CalibrationSolver::NelderMead,
+            CalibrationSolver::DifferentialEvolution,
+        ] {
+            let report = engine.solve_problem(&problem, solver).unwrap();
+            assert_eq!(report.jacobian_source, JacobianSource::FiniteDifference);
+            assert!((report.parameters[0] - a).abs() < 1e-3, "{:?}", solver);
+            assert!((report.parameters[1] - b).abs() < 1e-3, "{:?}", solver);
+            assert!(report.standard_errors.is_some());
+        }
+    }
+
+    #[test]
+    fn test_problem_active_bound() {
+        // Slope capped below its unconstrained estimate
+        let problem = LineFit {
+            bounds: Some(Bounds::new(vec![-5.0, 0.0], vec![5.0, 0.4]).unwrap()),
+            ..LineFit::new()
+        };
+        let report = CalibrationEngine::new()
+            .solve_problem(&problem, CalibrationSolver::LbfgsB)
+            .unwrap();
+        assert!((report.parameters[1] - 0.4).abs() < 1e-8);
+    }
 }
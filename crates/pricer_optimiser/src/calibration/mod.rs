@@ -2,10 +2,17 @@
 //!
 //! This module implements calibration of stochastic models by minimising
 //! the error between theoretical prices and market prices.
+//!
+//! - [`CalibrationEngine`]: drives any [`CalibrationProblem`] with a chosen solver
+//! - [`HestonSurfaceCalibration`]: joint Heston fit to an implied volatility surface
 
 mod engine;
+mod heston;
+mod problem;
 
 pub use engine::{CalibrationConfig, CalibrationEngine, CalibrationResult};
+pub use heston::{ExpiryBucketFit, HestonSurfaceCalibration, HestonSurfaceFit, SurfaceQuote};
+pub use problem::{CalibrationProblem, CalibrationReport, CalibrationSolver, JacobianSource};
 
 /// Market data for calibration.
 #[derive(Debug, Clone)]
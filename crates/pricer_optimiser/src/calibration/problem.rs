@@ -0,0 +1,171 @@
+//! Unified calibration problem interface and diagnostics.
+
+use crate::solvers::{solve_linear_system, Bounds};
+
+/// A least squares calibration problem.
+///
+/// Implementors report residuals `rᵢ(θ) = model_i(θ) − market_i` (weighted
+/// as they see fit) and may supply an exact Jacobian, typically from
+/// automatic differentiation. Problems without one fall back to finite
+/// differences.
+///
+/// # Examples
+///
+/// ```
+/// use pricer_optimiser::calibration::{CalibrationEngine, CalibrationProblem, CalibrationSolver};
+///
+/// struct Line {
+///     x: Vec<f64>,
+///     y: Vec<f64>,
+/// }
+///
+/// impl CalibrationProblem for Line {
+///     fn initial_guess(&self) -> Vec<f64> {
+///         vec![0.0, 0.0]
+///     }
+///
+///     fn residuals(&self, p: &[f64]) -> Vec<f64> {
+///         self.x.iter().zip(&self.y).map(|(x, y)| p[0] + p[1] * x - y).collect()
+///     }
+/// }
+///
+/// let line = Line { x: vec![0.0, 1.0, 2.0], y: vec![1.0, 3.0, 5.0] };
+/// let report = CalibrationEngine::new()
+///     .solve_problem(&line, CalibrationSolver::LevenbergMarquardt)
+///     .unwrap();
+/// assert!((report.parameters[1] - 2.0).abs() < 1e-6);
+/// ```
+pub trait CalibrationProblem {
+    /// Number of model parameters.
+    fn n_parameters(&self) -> usize {
+        self.initial_guess().len()
+    }
+
+    /// Starting point for the solver.
+    fn initial_guess(&self) -> Vec<f64>;
+
+    /// Residual vector at `params`.
+    fn residuals(&self, params: &[f64]) -> Vec<f64>;
+
+    /// Exact `m × n` Jacobian of the residuals, if available.
+    ///
+    /// The default returns `None`, selecting finite differences.
+    fn jacobian(&self, _params: &[f64]) -> Option<Vec<Vec<f64>>> {
+        None
+    }
+
+    /// Parameter bounds, if any.
+    fn bounds(&self) -> Option<Bounds> {
+        None
+    }
+}
+
+/// Solver used to calibrate a [`CalibrationProblem`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CalibrationSolver {
+    /// Levenberg-Marquardt on the residuals (bounds enforced by projection)
+    #[default]
+    LevenbergMarquardt,
+    /// L-BFGS-B on the sum of squared residuals
+    LbfgsB,
+    /// Nelder-Mead simplex on the sum of squared residuals
+    NelderMead,
+    /// Differential evolution on the sum of squared residuals (finite bounds required)
+    DifferentialEvolution,
+}
+
+/// How the reported Jacobian was obtained.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JacobianSource {
+    /// Supplied by the problem (AD or closed form)
+    Exact,
+    /// Forward finite differences
+    FiniteDifference,
+}
+
+/// Calibration outcome with fit diagnostics.
+#[derive(Debug, Clone)]
+pub struct CalibrationReport {
+    /// Calibrated parameters
+    pub parameters: Vec<f64>,
+    /// Residuals at the calibrated parameters
+    pub residuals: Vec<f64>,
+    /// Jacobian of the residuals at the calibrated parameters
+    pub jacobian: Vec<Vec<f64>>,
+    /// How the Jacobian was obtained
+    pub jacobian_source: JacobianSource,
+    /// Final sum of squared residuals
+    pub objective: f64,
+    /// Objective value after each iteration
+    pub history: Vec<f64>,
+    /// Asymptotic parameter standard errors, `None` when the problem has no
+    /// spare degrees of freedom or `JᵀJ` is singular
+    pub standard_errors: Option<Vec<f64>>,
+    /// Number of solver iterations
+    pub iterations: usize,
+    /// Number of residual or objective evaluations by the solver
+    pub function_evaluations: usize,
+    /// Convergence status
+    pub converged: bool,
+}
+
+impl CalibrationReport {
+    /// Root mean square residual.
+    pub fn rmse(&self) -> f64 {
+        (self.objective / self.residuals.len().max(1) as f64).sqrt()
+    }
+}
+
+/// Forward-difference Jacobian, stepping backwards at an upper bound.
+pub(crate) fn fd_jacobian<P: CalibrationProblem + ?Sized>(
+    problem: &P,
+    params: &[f64],
+    residuals: &[f64],
+    bounds: &Bounds,
+    fd_step: f64,
+) -> Vec<Vec<f64>> {
+    let n = params.len();
+    let mut jacobian = vec![vec![0.0; n]; residuals.len()];
+    let mut bumped = params.to_vec();
+    for k in 0..n {
+        let h = fd_step * params[k].abs().max(1.0);
+        let step = if params[k] + h <= bounds.upper()[k] {
+            h
+        } else {
+            -h
+        };
+        bumped[k] = params[k] + step;
+        let r_bumped = problem.residuals(&bumped);
+        bumped[k] = params[k];
+        for (row, (rb, r)) in jacobian.iter_mut().zip(r_bumped.iter().zip(residuals)) {
+            row[k] = (rb - r) / step;
+        }
+    }
+    jacobian
+}
+
+/// Standard errors from `cov = σ² (JᵀJ)⁻¹` with `σ² = SSR / (m − n)`.
+pub(crate) fn standard_errors(jacobian: &[Vec<f64>], ssr: f64) -> Option<Vec<f64>> {
+    let m = jacobian.len();
+    let n = jacobian.first().map_or(0, Vec::len);
+    if m <= n || n == 0 {
+        return None;
+    }
+    let jtj: Vec<Vec<f64>> = (0..n)
+        .map(|i| {
+            (0..n)
+                .map(|k| jacobian.iter().map(|row| row[i] * row[k]).sum())
+                .collect()
+        })
+        .collect();
+    let sigma2 = ssr / (m - n) as f64;
+    (0..n)
+        .map(|i| {
+            let mut unit = vec![0.0; n];
+            unit[i] = 1.0;
+            let column = solve_linear_system(&jtj, &unit).ok()?;
+            let variance = sigma2 * column[i];
+            (variance >= 0.0).then(|| variance.sqrt())
+        })
+        .collect()
+}
@@ -15,16 +15,17 @@
 //!
 //! - `bootstrapping`: Yield curve stripping from OIS/Swap rates (multi-curve)
 //! - `calibration`: Stochastic model calibration (e.g., Hull-White α/σ from swaptions)
-//! - `solvers`: Levenberg-Marquardt, BFGS algorithms
+//! - `solvers`: Levenberg-Marquardt, BFGS, L-BFGS-B, Nelder-Mead, differential evolution
 //!
 //! ## Example
 //!
 //! ```rust,ignore
-//! use pricer_optimiser::{LevenbergMarquardt, CalibrationProblem};
+//! use pricer_optimiser::calibration::{CalibrationEngine, CalibrationSolver};
 //!
-//! let problem = CalibrationProblem::new(market_data);
-//! let solver = LevenbergMarquardt::default();
-//! let calibrated_params = solver.solve(&problem)?;
+//! // `problem` implements `CalibrationProblem`
+//! let report = CalibrationEngine::new()
+//!     .solve_problem(&problem, CalibrationSolver::LevenbergMarquardt)?;
+//! println!("{:?} ± {:?}", report.parameters, report.standard_errors);
 //! ```
 
 pub mod bootstrapping;
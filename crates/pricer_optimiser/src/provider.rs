@@ -166,6 +166,24 @@ impl MarketProvider {
         cache.insert(ccy, Arc::clone(&vol));
         vol
     }
+
+    /// Replaces the cached yield curve for the given currency.
+    ///
+    /// Used to apply intraday market data updates. `Arc`s handed out
+    /// earlier keep the previous curve; subsequent `get_curve()` calls
+    /// return the new one.
+    pub fn set_curve(&self, ccy: Currency, curve: CurveEnum) {
+        let mut cache = self.curve_cache.write().unwrap();
+        cache.insert(ccy, Arc::new(curve));
+    }
+
+    /// Replaces the cached volatility surface for the given currency.
+    ///
+    /// See `set_curve()` for the semantics towards existing `Arc`s.
+    pub fn set_vol(&self, ccy: Currency, vol: VolSurfaceEnum) {
+        let mut cache = self.vol_cache.write().unwrap();
+        cache.insert(ccy, Arc::new(vol));
+    }
 }
 
 impl Default for MarketProvider {
@@ -379,6 +397,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_set_curve_and_vol_replace_cache() {
+        let provider = MarketProvider::new();
+        let old_curve = provider.get_curve(Currency::USD);
+
+        provider.set_curve(Currency::USD, CurveEnum::Flat(FlatCurve { rate: 0.045 }));
+        provider.set_vol(
+            Currency::USD,
+            VolSurfaceEnum::Sabr(SabrVolSurface { alpha: 0.35 }),
+        );
+
+        let new_curve = provider.get_curve(Currency::USD);
+        assert!(!Arc::ptr_eq(&old_curve, &new_curve));
+        assert_eq!(*new_curve, CurveEnum::Flat(FlatCurve { rate: 0.045 }));
+        assert_eq!(
+            *provider.get_vol(Currency::USD),
+            VolSurfaceEnum::Sabr(SabrVolSurface { alpha: 0.35 })
+        );
+
+        // Previously resolved references are unaffected
+        assert_eq!(*old_curve, CurveEnum::Flat(FlatCurve { rate: 0.05 }));
+    }
+
     // -------------------------------------------------------------------------
     // Thread Safety Tests (Basic)
     // -------------------------------------------------------------------------
@@ -85,6 +85,7 @@ impl Bfgs {
         let mut f = objective(&x);
         func_evals += 1;
         let mut g = self.gradient(&x, &objective, &mut func_evals);
+        let mut history = vec![f];
 
         for iteration in 0..self.config.max_iterations {
             // Check gradient convergence
@@ -96,6 +97,7 @@ impl Bfgs {
                     iterations: iteration,
                     function_evaluations: func_evals,
                     converged: true,
+                    history,
                 });
             }
 
@@ -127,6 +129,8 @@ impl Bfgs {
                     let g_new = self.gradient(&x_new, &objective, &mut func_evals);
                     let y: Vec<f64> = g_new.iter().zip(g.iter()).map(|(gn, go)| gn - go).collect();
 
+                    history.push(f_new);
+
                     // Check objective convergence
                     if (f - f_new).abs() < self.config.objective_tolerance {
                         return Ok(OptimisationResult {
@@ -135,6 +139,7 @@ impl Bfgs {
                             iterations: iteration,
                             function_evaluations: func_evals,
                             converged: true,
+                            history,
                         });
                     }
 
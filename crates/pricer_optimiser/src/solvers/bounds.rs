@@ -0,0 +1,135 @@
+//! Box constraints on solver parameters.
+
+use crate::error::OptimiserError;
+
+/// Lower and upper bounds `lᵢ ≤ xᵢ ≤ uᵢ` for each parameter.
+///
+/// Infinite bounds leave a parameter unconstrained on that side.
+///
+/// # Examples
+///
+/// ```
+/// use pricer_optimiser::solvers::Bounds;
+///
+/// let bounds = Bounds::new(vec![0.0, -1.0], vec![1.0, f64::INFINITY]).unwrap();
+/// assert_eq!(bounds.project(&[2.0, -3.0]), vec![1.0, -1.0]);
+/// assert!(!bounds.is_finite());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bounds {
+    lower: Vec<f64>,
+    upper: Vec<f64>,
+}
+
+impl Bounds {
+    /// Create bounds from lower and upper vectors.
+    ///
+    /// # Errors
+    ///
+    /// Returns `OptimiserError::InvalidBounds` if the lengths differ, a
+    /// bound is NaN, or a lower bound exceeds its upper bound.
+    pub fn new(lower: Vec<f64>, upper: Vec<f64>) -> Result<Self, OptimiserError> {
+        if lower.len() != upper.len() {
+            return Err(OptimiserError::InvalidBounds(format!(
+                "{} lower bounds but {} upper bounds",
+                lower.len(),
+                upper.len()
+            )));
+        }
+        for (i, (&lo, &hi)) in lower.iter().zip(upper.iter()).enumerate() {
+            if lo.is_nan() || hi.is_nan() || lo > hi {
+                return Err(OptimiserError::InvalidBounds(format!(
+                    "parameter {}: [{}, {}]",
+                    i, lo, hi
+                )));
+            }
+        }
+        Ok(Self { lower, upper })
+    }
+
+    /// Create bounds that leave `n` parameters unconstrained.
+    pub fn unbounded(n: usize) -> Self {
+        Self {
+            lower: vec![f64::NEG_INFINITY; n],
+            upper: vec![f64::INFINITY; n],
+        }
+    }
+
+    /// Number of parameters.
+    pub fn len(&self) -> usize {
+        self.lower.len()
+    }
+
+    /// Whether there are no parameters.
+    pub fn is_empty(&self) -> bool {
+        self.lower.is_empty()
+    }
+
+    /// Lower bounds.
+    pub fn lower(&self) -> &[f64] {
+        &self.lower
+    }
+
+    /// Upper bounds.
+    pub fn upper(&self) -> &[f64] {
+        &self.upper
+    }
+
+    /// Whether every bound is finite (required for global search).
+    pub fn is_finite(&self) -> bool {
+        self.lower
+            .iter()
+            .chain(self.upper.iter())
+            .all(|b| b.is_finite())
+    }
+
+    /// Whether `x` satisfies the bounds.
+    pub fn contains(&self, x: &[f64]) -> bool {
+        x.iter()
+            .zip(self.lower.iter().zip(self.upper.iter()))
+            .all(|(&xi, (&lo, &hi))| xi >= lo && xi <= hi)
+    }
+
+    /// Project `x` onto the box.
+    pub fn project(&self, x: &[f64]) -> Vec<f64> {
+        x.iter()
+            .zip(self.lower.iter().zip(self.upper.iter()))
+            .map(|(&xi, (&lo, &hi))| xi.clamp(lo, hi))
+            .collect()
+    }
+
+    /// Check that the bounds match a parameter vector.
+    pub(crate) fn check_dimension(&self, n: usize) -> Result<(), OptimiserError> {
+        if self.len() == n {
+            Ok(())
+        } else {
+            Err(OptimiserError::InvalidBounds(format!(
+                "bounds cover {} parameters, expected {}",
+                self.len(),
+                n
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_bounds() {
+        assert!(Bounds::new(vec![1.0], vec![0.0]).is_err());
+        assert!(Bounds::new(vec![0.0, 0.0], vec![1.0]).is_err());
+        assert!(Bounds::new(vec![f64::NAN], vec![1.0]).is_err());
+    }
+
+    #[test]
+    fn test_projection() {
+        let bounds = Bounds::new(vec![0.0, 0.0], vec![1.0, 2.0]).unwrap();
+        assert!(bounds.is_finite());
+        assert!(bounds.contains(&[0.5, 2.0]));
+        assert!(!bounds.contains(&[0.5, 2.1]));
+        assert_eq!(bounds.project(&[-1.0, 1.5]), vec![0.0, 1.5]);
+        assert!(!Bounds::unbounded(2).is_finite());
+    }
+}
@@ -0,0 +1,242 @@
+//! Differential evolution for global optimisation within bounds.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::error::OptimiserError;
+use crate::solvers::{Bounds, OptimisationResult};
+
+/// Configuration for differential evolution solver.
+#[derive(Debug, Clone)]
+pub struct DifferentialEvolutionConfig {
+    /// Population size (0 selects `15 × n_parameters`)
+    pub population_size: usize,
+    /// Maximum generations
+    pub max_generations: usize,
+    /// Differential weight `F` in `[0, 2]`
+    pub mutation: f64,
+    /// Crossover probability `CR` in `[0, 1]`
+    pub crossover: f64,
+    /// Convergence tolerance for the spread of population objective values
+    pub tolerance: f64,
+    /// Random seed, for reproducible searches
+    pub seed: u64,
+}
+
+impl Default for DifferentialEvolutionConfig {
+    fn default() -> Self {
+        Self {
+            population_size: 0,
+            max_generations: 1000,
+            mutation: 0.7,
+            crossover: 0.9,
+            tolerance: 1e-10,
+            seed: 42,
+        }
+    }
+}
+
+/// Differential evolution (DE/rand/1/bin) global optimiser.
+///
+/// Searches the whole bounded box with a population of candidates, so it
+/// can escape the local minima that trap gradient methods. The result is
+/// typically polished with [`LbfgsB`](super::LbfgsB) or
+/// [`LevenbergMarquardt`](super::LevenbergMarquardt).
+///
+/// # Examples
+///
+/// ```
+/// use pricer_optimiser::solvers::{Bounds, DifferentialEvolution};
+///
+/// let bounds = Bounds::new(vec![-5.0, -5.0], vec![5.0, 5.0]).unwrap();
+/// let objective = |p: &[f64]| (p[0] - 1.0).powi(2) + (p[1] + 2.0).powi(2);
+///
+/// let result = DifferentialEvolution::new()
+///     .minimise(&[0.0, 0.0], &bounds, objective)
+///     .unwrap();
+/// assert!((result.parameters[0] - 1.0).abs() < 1e-3);
+/// assert!((result.parameters[1] + 2.0).abs() < 1e-3);
+/// ```
+pub struct DifferentialEvolution {
+    config: DifferentialEvolutionConfig,
+}
+
+impl DifferentialEvolution {
+    /// Create a new solver with default configuration.
+    pub fn new() -> Self {
+        Self {
+            config: DifferentialEvolutionConfig::default(),
+        }
+    }
+
+    /// Create a new solver with custom configuration.
+    pub fn with_config(config: DifferentialEvolutionConfig) -> Self {
+        Self { config }
+    }
+
+    /// Minimise an objective function over a bounded box.
+    ///
+    /// # Arguments
+    ///
+    /// * `initial` - Initial guess, seeded into the population (projected onto the bounds)
+    /// * `bounds` - Box constraints; every bound must be finite
+    /// * `objective` - Objective function to minimise
+    ///
+    /// # Returns
+    ///
+    /// An `OptimisationResult` containing the best member found.
+    ///
+    /// # Errors
+    ///
+    /// Returns `OptimiserError::InvalidBounds` if any bound is infinite.
+    pub fn minimise<F>(
+        &self,
+        initial: &[f64],
+        bounds: &Bounds,
+        objective: F,
+    ) -> Result<OptimisationResult, OptimiserError>
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        let n = initial.len();
+        bounds.check_dimension(n)?;
+        if !bounds.is_finite() {
+            return Err(OptimiserError::InvalidBounds(
+                "differential evolution requires finite bounds".to_string(),
+            ));
+        }
+
+        let size = match self.config.population_size {
+            0 => 15 * n,
+            size => size,
+        }
+        .max(4);
+        let (lower, upper) = (bounds.lower(), bounds.upper());
+        let mut rng = StdRng::seed_from_u64(self.config.seed);
+
+        let mut population: Vec<Vec<f64>> = std::iter::once(bounds.project(initial))
+            .chain((1..size).map(|_| {
+                (0..n)
+                    .map(|i| lower[i] + rng.gen::<f64>() * (upper[i] - lower[i]))
+                    .collect()
+            }))
+            .collect();
+        let mut fitness: Vec<f64> = population.iter().map(|x| objective(x)).collect();
+        let mut func_evals = size;
+        let mut history = Vec::new();
+
+        for generation in 0..self.config.max_generations {
+            let (best, &f_best) = fitness
+                .iter()
+                .enumerate()
+                .min_by(|a, b| a.1.total_cmp(b.1))
+                .expect("population is non-empty");
+            history.push(f_best);
+
+            let f_worst = fitness.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            if f_worst - f_best <= self.config.tolerance * (1.0 + f_best.abs()) {
+                return Ok(OptimisationResult {
+                    parameters: population.swap_remove(best),
+                    objective: f_best,
+                    iterations: generation,
+                    function_evaluations: func_evals,
+                    converged: true,
+                    history,
+                });
+            }
+
+            for target in 0..size {
+                // Three distinct members other than the target
+                let mut pick = |taken: &[usize]| loop {
+                    let k = rng.gen_range(0..size);
+                    if k != target && !taken.contains(&k) {
+                        break k;
+                    }
+                };
+                let a = pick(&[]);
+                let b = pick(&[a]);
+                let c = pick(&[a, b]);
+                let forced = rng.gen_range(0..n);
+
+                let trial: Vec<f64> = (0..n)
+                    .map(|i| {
+                        if i == forced || rng.gen::<f64>() < self.config.crossover {
+                            let mutant = population[a][i]
+                                + self.config.mutation * (population[b][i] - population[c][i]);
+                            // Reflect back into the box rather than clamping
+                            if mutant < lower[i] {
+                                lower[i] + rng.gen::<f64>() * (population[target][i] - lower[i])
+                            } else if mutant > upper[i] {
+                                upper[i] - rng.gen::<f64>() * (upper[i] - population[target][i])
+                            } else {
+                                mutant
+                            }
+                        } else {
+                            population[target][i]
+                        }
+                    })
+                    .collect();
+
+                let f_trial = objective(&trial);
+                func_evals += 1;
+                if f_trial <= fitness[target] {
+                    population[target] = trial;
+                    fitness[target] = f_trial;
+                }
+            }
+        }
+
+        Err(OptimiserError::ConvergenceFailure {
+            iterations: self.config.max_generations,
+            residual: fitness.iter().copied().fold(f64::INFINITY, f64::min),
+        })
+    }
+}
+
+impl Default for DifferentialEvolution {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    fn rastrigin(p: &[f64]) -> f64 {
+        10.0 * p.len() as f64
+            + p.iter()
+                .map(|x| x * x - 10.0 * (2.0 * PI * x).cos())
+                .sum::<f64>()
+    }
+
+    #[test]
+    fn test_rastrigin_global_minimum() {
+        let bounds = Bounds::new(vec![-5.12; 2], vec![5.12; 2]).unwrap();
+        // Start in a local minimum that traps gradient methods
+        let result = DifferentialEvolution::new()
+            .minimise(&[3.0, -3.0], &bounds, rastrigin)
+            .unwrap();
+        assert!(result.converged);
+        assert!(result.objective < 1e-8);
+        assert!(result.parameters.iter().all(|x| x.abs() < 1e-4));
+        assert!(bounds.contains(&result.parameters));
+    }
+
+    #[test]
+    fn test_reproducible() {
+        let bounds = Bounds::new(vec![-5.12; 3], vec![5.12; 3]).unwrap();
+        let solver = DifferentialEvolution::new();
+        let a = solver.minimise(&[1.0; 3], &bounds, rastrigin).unwrap();
+        let b = solver.minimise(&[1.0; 3], &bounds, rastrigin).unwrap();
+        assert_eq!(a.parameters, b.parameters);
+        assert_eq!(a.function_evaluations, b.function_evaluations);
+    }
+
+    #[test]
+    fn test_requires_finite_bounds() {
+        let result = DifferentialEvolution::new().minimise(&[0.0], &Bounds::unbounded(1), |p| p[0]);
+        assert!(matches!(result, Err(OptimiserError::InvalidBounds(_))));
+    }
+}
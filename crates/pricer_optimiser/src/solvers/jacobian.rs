@@ -0,0 +1,130 @@
+//! Automatic Jacobians for residual functions.
+//!
+//! Residual functions written over [`AdScalar`] are differentiated in
+//! forward mode with dual numbers when the `num-dual-mode` feature is
+//! enabled (one pass per parameter, exact to machine precision). Without
+//! the feature, [`AdScalar`] is plain `f64` and the same functions are
+//! differentiated by forward finite differences.
+//!
+//! Enzyme-generated Jacobians from `pricer_pricing` sit above this crate in
+//! the layer stack; supply them through
+//! [`LevenbergMarquardt::solve_with_jacobian`](super::LevenbergMarquardt::solve_with_jacobian)
+//! or [`CalibrationProblem::jacobian`](crate::calibration::CalibrationProblem::jacobian).
+
+/// Scalar type for residual functions differentiated by [`ad_jacobian`].
+#[cfg(feature = "num-dual-mode")]
+pub type AdScalar = num_dual::Dual64;
+
+/// Scalar type for residual functions differentiated by [`ad_jacobian`].
+#[cfg(not(feature = "num-dual-mode"))]
+pub type AdScalar = f64;
+
+/// Whether [`ad_jacobian`] uses dual numbers (`true`) or falls back to
+/// finite differences (`false`).
+pub const AD_ENABLED: bool = cfg!(feature = "num-dual-mode");
+
+/// Real part of an [`AdScalar`].
+#[inline]
+pub(crate) fn real(x: &AdScalar) -> f64 {
+    #[cfg(feature = "num-dual-mode")]
+    {
+        x.re
+    }
+    #[cfg(not(feature = "num-dual-mode"))]
+    {
+        *x
+    }
+}
+
+/// Evaluate residuals at `f64` parameters.
+pub(crate) fn primal<F>(residuals: &F, params: &[f64]) -> Vec<f64>
+where
+    F: Fn(&[AdScalar]) -> Vec<AdScalar>,
+{
+    let x: Vec<AdScalar> = params.iter().map(|&p| AdScalar::from(p)).collect();
+    residuals(&x).iter().map(real).collect()
+}
+
+/// Residuals and their `m × n` Jacobian at `params`.
+///
+/// `fd_step` is only used when the `num-dual-mode` feature is disabled.
+///
+/// # Examples
+///
+/// ```
+/// use pricer_optimiser::solvers::{ad_jacobian, AdScalar};
+///
+/// // r(a, b) = [a·b, a + 2]
+/// let residuals = |p: &[AdScalar]| vec![p[0] * p[1], p[0] + 2.0];
+/// let (values, jacobian) = ad_jacobian(residuals, &[3.0, 4.0], 1e-7);
+///
+/// assert_eq!(values, vec![12.0, 5.0]);
+/// assert!((jacobian[0][0] - 4.0).abs() < 1e-6);
+/// assert!((jacobian[0][1] - 3.0).abs() < 1e-6);
+/// assert!((jacobian[1][0] - 1.0).abs() < 1e-6);
+/// ```
+pub fn ad_jacobian<F>(residuals: F, params: &[f64], fd_step: f64) -> (Vec<f64>, Vec<Vec<f64>>)
+where
+    F: Fn(&[AdScalar]) -> Vec<AdScalar>,
+{
+    let n = params.len();
+    let values = primal(&residuals, params);
+    let mut jacobian = vec![vec![0.0; n]; values.len()];
+
+    #[cfg(feature = "num-dual-mode")]
+    {
+        let _ = fd_step;
+        let mut x: Vec<AdScalar> = params.iter().map(|&p| AdScalar::from(p)).collect();
+        for k in 0..n {
+            x[k].eps = 1.0;
+            for (row, r) in jacobian.iter_mut().zip(residuals(&x)) {
+                row[k] = r.eps;
+            }
+            x[k].eps = 0.0;
+        }
+    }
+
+    #[cfg(not(feature = "num-dual-mode"))]
+    {
+        let mut x = params.to_vec();
+        for k in 0..n {
+            let h = fd_step * params[k].abs().max(1.0);
+            x[k] = params[k] + h;
+            for (row, (r, r0)) in jacobian.iter_mut().zip(residuals(&x).iter().zip(&values)) {
+                row[k] = (r - r0) / h;
+            }
+            x[k] = params[k];
+        }
+    }
+
+    (values, jacobian)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "num-dual-mode")]
+    use num_dual::DualNum;
+
+    #[test]
+    fn test_exponential_jacobian() {
+        // r_i = a·exp(-b·t_i)
+        let times = [0.5, 1.0, 2.0];
+        let residuals = |p: &[AdScalar]| {
+            times
+                .iter()
+                .map(|&t| p[0] * (p[1] * -t).exp())
+                .collect::<Vec<_>>()
+        };
+        let (a, b) = (2.0_f64, 0.3_f64);
+        let (values, jacobian) = ad_jacobian(residuals, &[a, b], 1e-7);
+
+        let tolerance = if AD_ENABLED { 1e-14 } else { 1e-6 };
+        for (i, &t) in times.iter().enumerate() {
+            let decay = (-b * t).exp();
+            assert!((values[i] - a * decay).abs() < 1e-14);
+            assert!((jacobian[i][0] - decay).abs() < tolerance);
+            assert!((jacobian[i][1] + a * t * decay).abs() < tolerance);
+        }
+    }
+}
@@ -0,0 +1,393 @@
+//! L-BFGS-B algorithm for bound-constrained optimisation.
+
+use std::collections::VecDeque;
+
+use crate::error::OptimiserError;
+use crate::solvers::{Bounds, OptimisationResult};
+
+/// Configuration for L-BFGS-B solver.
+#[derive(Debug, Clone)]
+pub struct LbfgsBConfig {
+    /// Maximum iterations
+    pub max_iterations: usize,
+    /// Number of correction pairs kept for the inverse Hessian
+    pub memory: usize,
+    /// Convergence tolerance for the projected gradient (max norm)
+    pub gradient_tolerance: f64,
+    /// Convergence tolerance for relative objective change
+    pub objective_tolerance: f64,
+    /// Finite difference step size
+    pub fd_step: f64,
+    /// Line search parameters (Armijo condition)
+    pub c1: f64,
+    /// Maximum line search iterations
+    pub max_line_search: usize,
+}
+
+impl Default for LbfgsBConfig {
+    fn default() -> Self {
+        Self {
+            max_iterations: 1000,
+            memory: 10,
+            gradient_tolerance: 1e-6,
+            objective_tolerance: 1e-12,
+            fd_step: 1e-7,
+            c1: 1e-4,
+            max_line_search: 30,
+        }
+    }
+}
+
+/// Limited-memory BFGS solver with box constraints.
+///
+/// Iterates are kept feasible by projection. Each step computes the
+/// two-loop L-BFGS direction over the free variables (those not held at a
+/// bound by the gradient) and backtracks along the projected path
+/// `P(x + α d)` until the Armijo condition holds.
+///
+/// # Examples
+///
+/// ```
+/// use pricer_optimiser::solvers::{Bounds, LbfgsB};
+///
+/// // Minimise (x - 2)² + (y + 1)² on [0, 1] × [0, 1]: optimum at (1, 0)
+/// let bounds = Bounds::new(vec![0.0, 0.0], vec![1.0, 1.0]).unwrap();
+/// let objective = |p: &[f64]| (p[0] - 2.0).powi(2) + (p[1] + 1.0).powi(2);
+///
+/// let result = LbfgsB::new().minimise(&[0.5, 0.5], &bounds, objective).unwrap();
+/// assert!((result.parameters[0] - 1.0).abs() < 1e-8);
+/// assert!(result.parameters[1].abs() < 1e-8);
+/// ```
+pub struct LbfgsB {
+    config: LbfgsBConfig,
+}
+
+impl LbfgsB {
+    /// Create a new solver with default configuration.
+    pub fn new() -> Self {
+        Self {
+            config: LbfgsBConfig::default(),
+        }
+    }
+
+    /// Create a new solver with custom configuration.
+    pub fn with_config(config: LbfgsBConfig) -> Self {
+        Self { config }
+    }
+
+    /// Minimise an objective function within bounds, using finite
+    /// difference gradients.
+    ///
+    /// # Arguments
+    ///
+    /// * `initial` - Initial parameter guess (projected onto the bounds)
+    /// * `bounds` - Box constraints
+    /// * `objective` - Objective function to minimise
+    ///
+    /// # Returns
+    ///
+    /// An `OptimisationResult` containing the optimal parameters.
+    pub fn minimise<F>(
+        &self,
+        initial: &[f64],
+        bounds: &Bounds,
+        objective: F,
+    ) -> Result<OptimisationResult, OptimiserError>
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        self.run(initial, bounds, &objective, None::<&fn(&[f64]) -> Vec<f64>>)
+    }
+
+    /// Minimise an objective function within bounds, using a supplied
+    /// gradient (e.g. from AD).
+    ///
+    /// # Arguments
+    ///
+    /// * `initial` - Initial parameter guess (projected onto the bounds)
+    /// * `bounds` - Box constraints
+    /// * `objective` - Objective function to minimise
+    /// * `gradient` - Gradient of the objective
+    pub fn minimise_with_gradient<F, G>(
+        &self,
+        initial: &[f64],
+        bounds: &Bounds,
+        objective: F,
+        gradient: G,
+    ) -> Result<OptimisationResult, OptimiserError>
+    where
+        F: Fn(&[f64]) -> f64,
+        G: Fn(&[f64]) -> Vec<f64>,
+    {
+        self.run(initial, bounds, &objective, Some(&gradient))
+    }
+
+    fn run<F, G>(
+        &self,
+        initial: &[f64],
+        bounds: &Bounds,
+        objective: &F,
+        gradient: Option<&G>,
+    ) -> Result<OptimisationResult, OptimiserError>
+    where
+        F: Fn(&[f64]) -> f64,
+        G: Fn(&[f64]) -> Vec<f64>,
+    {
+        let n = initial.len();
+        bounds.check_dimension(n)?;
+        let mut func_evals = 0;
+        let grad = |x: &[f64], f: f64, evals: &mut usize| match gradient {
+            Some(g) => g(x),
+            None => self.fd_gradient(x, f, bounds, objective, evals),
+        };
+
+        let mut x = bounds.project(initial);
+        let mut f = objective(&x);
+        func_evals += 1;
+        let mut g = grad(&x, f, &mut func_evals);
+        let mut history = vec![f];
+        let mut pairs: VecDeque<(Vec<f64>, Vec<f64>, f64)> = VecDeque::new();
+
+        for iteration in 0..self.config.max_iterations {
+            let free = free_variables(&x, &g, bounds);
+            let pg_norm = g
+                .iter()
+                .zip(free.iter())
+                .filter(|(_, &is_free)| is_free)
+                .fold(0.0_f64, |acc, (gi, _)| acc.max(gi.abs()));
+            if pg_norm < self.config.gradient_tolerance {
+                return Ok(OptimisationResult {
+                    parameters: x,
+                    objective: f,
+                    iterations: iteration,
+                    function_evaluations: func_evals,
+                    converged: true,
+                    history,
+                });
+            }
+
+            let mut d = two_loop(&g, &free, &pairs);
+            let mut slope: f64 = d.iter().zip(g.iter()).map(|(di, gi)| di * gi).sum();
+            if slope >= 0.0 {
+                // Curvature information is stale; restart with steepest descent
+                pairs.clear();
+                d = g
+                    .iter()
+                    .zip(free.iter())
+                    .map(|(gi, &is_free)| if is_free { -gi } else { 0.0 })
+                    .collect();
+                slope = -d.iter().map(|di| di * di).sum::<f64>();
+            }
+
+            // Projected backtracking line search
+            let mut alpha = if pairs.is_empty() {
+                (1.0 / slope.abs().sqrt()).min(1.0)
+            } else {
+                1.0
+            };
+            let mut accepted = None;
+            for _ in 0..self.config.max_line_search {
+                let trial: Vec<f64> = x
+                    .iter()
+                    .zip(d.iter())
+                    .map(|(xi, di)| xi + alpha * di)
+                    .collect();
+                let x_new = bounds.project(&trial);
+                let f_new = objective(&x_new);
+                func_evals += 1;
+                let decrease: f64 = g
+                    .iter()
+                    .zip(x_new.iter().zip(x.iter()))
+                    .map(|(gi, (xn, xo))| gi * (xn - xo))
+                    .sum();
+                if f_new <= f + self.config.c1 * decrease {
+                    accepted = Some((x_new, f_new));
+                    break;
+                }
+                alpha *= 0.5;
+            }
+
+            let Some((x_new, f_new)) = accepted else {
+                // No progress possible along the projected path
+                return Ok(OptimisationResult {
+                    parameters: x,
+                    objective: f,
+                    iterations: iteration,
+                    function_evaluations: func_evals,
+                    converged: pg_norm < self.config.gradient_tolerance.sqrt(),
+                    history,
+                });
+            };
+
+            let g_new = grad(&x_new, f_new, &mut func_evals);
+            let s: Vec<f64> = x_new.iter().zip(x.iter()).map(|(a, b)| a - b).collect();
+            let y: Vec<f64> = g_new.iter().zip(g.iter()).map(|(a, b)| a - b).collect();
+            let sy: f64 = s.iter().zip(y.iter()).map(|(si, yi)| si * yi).sum();
+            let yy: f64 = y.iter().map(|yi| yi * yi).sum();
+            if sy > f64::EPSILON * yy {
+                if pairs.len() == self.config.memory {
+                    pairs.pop_front();
+                }
+                pairs.push_back((s, y, sy));
+            }
+
+            let f_change = (f - f_new).abs();
+            x = x_new;
+            g = g_new;
+            f = f_new;
+            history.push(f);
+
+            if f_change <= self.config.objective_tolerance * f.abs().max(1.0) {
+                return Ok(OptimisationResult {
+                    parameters: x,
+                    objective: f,
+                    iterations: iteration + 1,
+                    function_evaluations: func_evals,
+                    converged: true,
+                    history,
+                });
+            }
+        }
+
+        Err(OptimiserError::ConvergenceFailure {
+            iterations: self.config.max_iterations,
+            residual: f,
+        })
+    }
+
+    /// Compute gradient via one-sided finite differences that stay inside
+    /// the bounds.
+    fn fd_gradient<F>(
+        &self,
+        x: &[f64],
+        f0: f64,
+        bounds: &Bounds,
+        objective: &F,
+        func_evals: &mut usize,
+    ) -> Vec<f64>
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        let mut x_bumped = x.to_vec();
+        (0..x.len())
+            .map(|i| {
+                let h = self.config.fd_step * x[i].abs().max(1.0);
+                let step = if x[i] + h <= bounds.upper()[i] { h } else { -h };
+                x_bumped[i] = x[i] + step;
+                let f_bumped = objective(&x_bumped);
+                *func_evals += 1;
+                x_bumped[i] = x[i];
+                (f_bumped - f0) / step
+            })
+            .collect()
+    }
+}
+
+impl Default for LbfgsB {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Variables not held at a bound by the gradient.
+fn free_variables(x: &[f64], g: &[f64], bounds: &Bounds) -> Vec<bool> {
+    x.iter()
+        .zip(g.iter())
+        .zip(bounds.lower().iter().zip(bounds.upper().iter()))
+        .map(|((&xi, &gi), (&lo, &hi))| !((xi <= lo && gi > 0.0) || (xi >= hi && gi < 0.0)))
+        .collect()
+}
+
+/// L-BFGS two-loop recursion restricted to the free variables.
+fn two_loop(g: &[f64], free: &[bool], pairs: &VecDeque<(Vec<f64>, Vec<f64>, f64)>) -> Vec<f64> {
+    let mask = |v: &[f64]| -> Vec<f64> {
+        v.iter()
+            .zip(free.iter())
+            .map(|(vi, &is_free)| if is_free { *vi } else { 0.0 })
+            .collect()
+    };
+    let dot = |a: &[f64], b: &[f64]| -> f64 { a.iter().zip(b.iter()).map(|(x, y)| x * y).sum() };
+
+    let mut q = mask(g);
+    let mut alphas = Vec::with_capacity(pairs.len());
+    for (s, y, sy) in pairs.iter().rev() {
+        let a = dot(s, &q) / sy;
+        for (qi, yi) in q.iter_mut().zip(mask(y).iter()) {
+            *qi -= a * yi;
+        }
+        alphas.push(a);
+    }
+    if let Some((_, y, sy)) = pairs.back() {
+        let gamma = sy / dot(y, y).max(f64::MIN_POSITIVE);
+        q.iter_mut().for_each(|qi| *qi *= gamma);
+    }
+    for ((s, y, sy), a) in pairs.iter().zip(alphas.iter().rev()) {
+        let b = dot(&mask(y), &q) / sy;
+        for (qi, si) in q.iter_mut().zip(mask(s).iter()) {
+            *qi += (a - b) * si;
+        }
+    }
+    q.iter().map(|qi| -qi).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rosenbrock(p: &[f64]) -> f64 {
+        (1.0 - p[0]).powi(2) + 100.0 * (p[1] - p[0] * p[0]).powi(2)
+    }
+
+    #[test]
+    fn test_unconstrained_rosenbrock() {
+        let solver = LbfgsB::new();
+        let result = solver
+            .minimise(&[-1.2, 1.0], &Bounds::unbounded(2), rosenbrock)
+            .unwrap();
+        assert!(result.converged);
+        assert!((result.parameters[0] - 1.0).abs() < 1e-4);
+        assert!((result.parameters[1] - 1.0).abs() < 1e-4);
+        assert!(result.history.windows(2).all(|w| w[1] <= w[0]));
+    }
+
+    #[test]
+    fn test_active_bound() {
+        // Rosenbrock with x ≤ 0.5: optimum at (0.5, 0.25)
+        let bounds = Bounds::new(vec![-2.0, -2.0], vec![0.5, 2.0]).unwrap();
+        let result = LbfgsB::new()
+            .minimise(&[-1.0, 1.5], &bounds, rosenbrock)
+            .unwrap();
+        assert!((result.parameters[0] - 0.5).abs() < 1e-6);
+        assert!((result.parameters[1] - 0.25).abs() < 1e-4);
+        assert!(bounds.contains(&result.parameters));
+    }
+
+    #[test]
+    fn test_analytic_gradient() {
+        let objective = |p: &[f64]| {
+            p.iter()
+                .enumerate()
+                .map(|(i, x)| (i + 1) as f64 * x * x)
+                .sum()
+        };
+        let gradient = |p: &[f64]| {
+            p.iter()
+                .enumerate()
+                .map(|(i, x)| 2.0 * (i + 1) as f64 * x)
+                .collect()
+        };
+        let bounds = Bounds::new(vec![1.0, -5.0, -5.0], vec![5.0, 5.0, 5.0]).unwrap();
+        let result = LbfgsB::new()
+            .minimise_with_gradient(&[3.0, 2.0, -4.0], &bounds, objective, gradient)
+            .unwrap();
+        assert!((result.parameters[0] - 1.0).abs() < 1e-10);
+        assert!(result.parameters[1].abs() < 1e-6);
+        assert!(result.parameters[2].abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_dimension_mismatch() {
+        let result = LbfgsB::new().minimise(&[0.0], &Bounds::unbounded(2), |p| p[0]);
+        assert!(matches!(result, Err(OptimiserError::InvalidBounds(_))));
+    }
+}
@@ -1,6 +1,7 @@
 //! Levenberg-Marquardt algorithm for nonlinear least squares.
 
 use crate::error::OptimiserError;
+use crate::solvers::jacobian::{ad_jacobian, primal, AdScalar};
 use crate::solvers::OptimisationResult;
 
 /// Configuration for Levenberg-Marquardt solver.
@@ -69,7 +70,6 @@ impl LevenbergMarquardt {
     /// # Returns
     ///
     /// An `OptimisationResult` containing the optimal parameters.
-    #[allow(clippy::needless_range_loop)]
     pub fn solve<F>(
         &self,
         initial: &[f64],
@@ -77,11 +77,90 @@ impl LevenbergMarquardt {
     ) -> Result<OptimisationResult, OptimiserError>
     where
         F: Fn(&[f64]) -> Vec<f64>,
+    {
+        self.run(initial, &residuals, None::<&fn(&[f64]) -> Vec<Vec<f64>>>)
+    }
+
+    /// Solve a nonlinear least squares problem with a supplied Jacobian
+    /// (e.g. from AD) in place of finite differences.
+    ///
+    /// # Arguments
+    ///
+    /// * `initial` - Initial parameter guess
+    /// * `residuals` - Function that computes residual vector given parameters
+    /// * `jacobian` - Function that computes the `m × n` Jacobian of the residuals
+    pub fn solve_with_jacobian<F, J>(
+        &self,
+        initial: &[f64],
+        residuals: F,
+        jacobian: J,
+    ) -> Result<OptimisationResult, OptimiserError>
+    where
+        F: Fn(&[f64]) -> Vec<f64>,
+        J: Fn(&[f64]) -> Vec<Vec<f64>>,
+    {
+        self.run(initial, &residuals, Some(&jacobian))
+    }
+
+    /// Solve a nonlinear least squares problem with an automatic Jacobian.
+    ///
+    /// The residual function is written over [`AdScalar`]: dual numbers
+    /// under the `num-dual-mode` feature, giving exact Jacobians, or `f64`
+    /// without it, falling back to finite differences with `fd_step`.
+    ///
+    /// # Arguments
+    ///
+    /// * `initial` - Initial parameter guess
+    /// * `residuals` - Function that computes residual vector given parameters
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pricer_optimiser::solvers::{AdScalar, LevenbergMarquardt};
+    ///
+    /// // Fit y = a·x + b·x² to exact data with a = 2, b = -1
+    /// let data = [(1.0, 1.0), (2.0, 0.0), (3.0, -3.0)];
+    /// let residuals = |p: &[AdScalar]| {
+    ///     data.iter()
+    ///         .map(|&(x, y)| p[0] * x + p[1] * (x * x) - y)
+    ///         .collect::<Vec<_>>()
+    /// };
+    ///
+    /// let result = LevenbergMarquardt::new().solve_ad(&[0.0, 0.0], residuals).unwrap();
+    /// assert!((result.parameters[0] - 2.0).abs() < 1e-6);
+    /// assert!((result.parameters[1] + 1.0).abs() < 1e-6);
+    /// ```
+    pub fn solve_ad<F>(
+        &self,
+        initial: &[f64],
+        residuals: F,
+    ) -> Result<OptimisationResult, OptimiserError>
+    where
+        F: Fn(&[AdScalar]) -> Vec<AdScalar>,
+    {
+        self.run(
+            initial,
+            &|p: &[f64]| primal(&residuals, p),
+            Some(&|p: &[f64]| ad_jacobian(&residuals, p, self.config.fd_step).1),
+        )
+    }
+
+    #[allow(clippy::needless_range_loop)]
+    fn run<F, J>(
+        &self,
+        initial: &[f64],
+        residuals: &F,
+        jacobian: Option<&J>,
+    ) -> Result<OptimisationResult, OptimiserError>
+    where
+        F: Fn(&[f64]) -> Vec<f64>,
+        J: Fn(&[f64]) -> Vec<Vec<f64>>,
     {
         let n = initial.len();
         let mut x = initial.to_vec();
         let mut lambda = self.config.initial_lambda;
         let mut func_evals = 0;
+        let mut history = Vec::new();
 
         for iteration in 0..self.config.max_iterations {
             // Compute residuals and Jacobian
@@ -91,6 +170,7 @@ impl LevenbergMarquardt {
 
             // Compute sum of squared residuals
             let ssr: f64 = r.iter().map(|ri| ri * ri).sum();
+            history.push(ssr);
 
             // Check for convergence
             if ssr < self.config.residual_tolerance {
@@ -100,21 +180,28 @@ impl LevenbergMarquardt {
                     iterations: iteration,
                     function_evaluations: func_evals,
                     converged: true,
+                    history,
                 });
             }
 
-            // Compute Jacobian via finite differences
-            let mut j = vec![vec![0.0; n]; m];
-            for k in 0..n {
-                let mut x_plus = x.clone();
-                x_plus[k] += self.config.fd_step;
-                let r_plus = residuals(&x_plus);
-                func_evals += 1;
-
-                for i in 0..m {
-                    j[i][k] = (r_plus[i] - r[i]) / self.config.fd_step;
+            // Compute Jacobian, via finite differences unless supplied
+            let j = match jacobian {
+                Some(jac) => jac(&x),
+                None => {
+                    let mut j = vec![vec![0.0; n]; m];
+                    for k in 0..n {
+                        let mut x_plus = x.clone();
+                        x_plus[k] += self.config.fd_step;
+                        let r_plus = residuals(&x_plus);
+                        func_evals += 1;
+
+                        for i in 0..m {
+                            j[i][k] = (r_plus[i] - r[i]) / self.config.fd_step;
+                        }
+                    }
+                    j
                 }
-            }
+            };
 
             // Compute J^T * J and J^T * r
             let mut jtj = vec![vec![0.0; n]; n];
@@ -148,6 +235,7 @@ impl LevenbergMarquardt {
                     iterations: iteration,
                     function_evaluations: func_evals,
                     converged: true,
+                    history,
                 });
             }
 
@@ -180,7 +268,7 @@ impl Default for LevenbergMarquardt {
 }
 
 /// Simple linear system solver (Gaussian elimination with partial pivoting).
-fn solve_linear_system(a: &[Vec<f64>], b: &[f64]) -> Result<Vec<f64>, OptimiserError> {
+pub(crate) fn solve_linear_system(a: &[Vec<f64>], b: &[f64]) -> Result<Vec<f64>, OptimiserError> {
     let n = b.len();
     if a.len() != n || a.iter().any(|row| row.len() != n) {
         return Err(OptimiserError::InvalidBounds(
@@ -266,4 +354,42 @@ mod tests {
         assert!((result.parameters[0] - 1.0).abs() < 0.1);
         assert!((result.parameters[1] - 1.0).abs() < 0.1);
     }
+
+    #[test]
+    fn test_ad_jacobian() {
+        #[cfg(feature = "num-dual-mode")]
+        use num_dual::DualNum;
+
+        // Exponential decay y = a·exp(-b·t) with a = 3, b = 0.7
+        let data: Vec<(f64, f64)> = [0.0, 0.5, 1.0, 2.0, 4.0]
+            .iter()
+            .map(|&t| (t, 3.0 * (-0.7_f64 * t).exp()))
+            .collect();
+        let residuals = |p: &[AdScalar]| {
+            data.iter()
+                .map(|&(t, y)| p[0] * (p[1] * -t).exp() - y)
+                .collect::<Vec<_>>()
+        };
+
+        let result = LevenbergMarquardt::new()
+            .solve_ad(&[1.0, 0.1], residuals)
+            .unwrap();
+        assert!(result.converged);
+        assert!((result.parameters[0] - 3.0).abs() < 1e-5);
+        assert!((result.parameters[1] - 0.7).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_supplied_jacobian() {
+        let lm = LevenbergMarquardt::new();
+        let residuals = |p: &[f64]| vec![1.0 - p[0], 10.0 * (p[1] - p[0] * p[0])];
+        let jacobian = |p: &[f64]| vec![vec![-1.0, 0.0], vec![-20.0 * p[0], 10.0]];
+
+        let result = lm
+            .solve_with_jacobian(&[-1.0, 1.0], residuals, jacobian)
+            .unwrap();
+        assert!((result.parameters[0] - 1.0).abs() < 1e-3);
+        assert!((result.parameters[1] - 1.0).abs() < 1e-3);
+        assert_eq!(result.history.len(), result.iterations + 1);
+    }
 }
@@ -3,12 +3,31 @@
 //! This module implements optimisation algorithms including:
 //! - Levenberg-Marquardt for nonlinear least squares
 //! - BFGS for general unconstrained optimisation
+//! - L-BFGS-B for bound-constrained optimisation
+//! - Nelder-Mead for derivative-free optimisation
+//! - Differential evolution for global search within bounds
+//!
+//! Jacobians for residual functions written over [`AdScalar`] come from
+//! dual numbers under the `num-dual-mode` feature and from finite
+//! differences otherwise (see [`ad_jacobian`]).
 
 mod bfgs;
+mod bounds;
+mod differential_evolution;
+mod jacobian;
+mod lbfgsb;
 mod levenberg_marquardt;
+mod nelder_mead;
 
 pub use bfgs::{Bfgs, BfgsConfig};
+pub use bounds::Bounds;
+pub use differential_evolution::{DifferentialEvolution, DifferentialEvolutionConfig};
+pub use jacobian::{ad_jacobian, AdScalar, AD_ENABLED};
+pub use lbfgsb::{LbfgsB, LbfgsBConfig};
 pub use levenberg_marquardt::{LevenbergMarquardt, LevenbergMarquardtConfig};
+pub use nelder_mead::{NelderMead, NelderMeadConfig};
+
+pub(crate) use levenberg_marquardt::solve_linear_system;
 
 /// Optimisation result.
 #[derive(Debug, Clone)]
@@ -23,4 +42,7 @@ pub struct OptimisationResult {
     pub function_evaluations: usize,
     /// Convergence status
     pub converged: bool,
+    /// Objective value after each iteration (sum of squared residuals for
+    /// least squares solvers)
+    pub history: Vec<f64>,
 }
@@ -0,0 +1,255 @@
+//! Nelder-Mead simplex algorithm for derivative-free optimisation.
+
+use std::cell::Cell;
+
+use crate::error::OptimiserError;
+use crate::solvers::{Bounds, OptimisationResult};
+
+/// Configuration for Nelder-Mead solver.
+#[derive(Debug, Clone)]
+pub struct NelderMeadConfig {
+    /// Maximum iterations
+    pub max_iterations: usize,
+    /// Convergence tolerance for the spread of objective values over the simplex
+    pub objective_tolerance: f64,
+    /// Relative size of the initial simplex
+    pub initial_step: f64,
+    /// Reflection coefficient
+    pub reflection: f64,
+    /// Expansion coefficient
+    pub expansion: f64,
+    /// Contraction coefficient
+    pub contraction: f64,
+    /// Shrink coefficient
+    pub shrink: f64,
+}
+
+impl Default for NelderMeadConfig {
+    fn default() -> Self {
+        Self {
+            max_iterations: 5000,
+            objective_tolerance: 1e-10,
+            initial_step: 0.05,
+            reflection: 1.0,
+            expansion: 2.0,
+            contraction: 0.5,
+            shrink: 0.5,
+        }
+    }
+}
+
+/// Nelder-Mead downhill simplex solver.
+///
+/// Needs only objective values, which makes it robust for noisy or
+/// non-smooth calibration objectives. Trial vertices are projected onto the
+/// bounds, so the simplex never leaves the feasible box.
+///
+/// # Examples
+///
+/// ```
+/// use pricer_optimiser::solvers::{Bounds, NelderMead};
+///
+/// let objective = |p: &[f64]| (p[0] - 1.0).powi(2) + (p[1] - 2.0).powi(2);
+/// let result = NelderMead::new()
+///     .minimise(&[0.0, 0.0], &Bounds::unbounded(2), objective)
+///     .unwrap();
+/// assert!((result.parameters[0] - 1.0).abs() < 1e-4);
+/// assert!((result.parameters[1] - 2.0).abs() < 1e-4);
+/// ```
+pub struct NelderMead {
+    config: NelderMeadConfig,
+}
+
+impl NelderMead {
+    /// Create a new solver with default configuration.
+    pub fn new() -> Self {
+        Self {
+            config: NelderMeadConfig::default(),
+        }
+    }
+
+    /// Create a new solver with custom configuration.
+    pub fn with_config(config: NelderMeadConfig) -> Self {
+        Self { config }
+    }
+
+    /// Minimise an objective function within bounds.
+    ///
+    /// # Arguments
+    ///
+    /// * `initial` - Initial parameter guess (projected onto the bounds)
+    /// * `bounds` - Box constraints
+    /// * `objective` - Objective function to minimise
+    ///
+    /// # Returns
+    ///
+    /// An `OptimisationResult` containing the best vertex found.
+    pub fn minimise<F>(
+        &self,
+        initial: &[f64],
+        bounds: &Bounds,
+        objective: F,
+    ) -> Result<OptimisationResult, OptimiserError>
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        let n = initial.len();
+        bounds.check_dimension(n)?;
+        let func_evals = Cell::new(0);
+        let evaluate = |x: Vec<f64>| {
+            let x = bounds.project(&x);
+            func_evals.set(func_evals.get() + 1);
+            let f = objective(&x);
+            (x, f)
+        };
+
+        // Initial simplex: the guess plus one vertex per coordinate
+        let x0 = bounds.project(initial);
+        let mut simplex = vec![evaluate(x0.clone())];
+        for i in 0..n {
+            let mut vertex = x0.clone();
+            let step = if x0[i] != 0.0 {
+                self.config.initial_step * x0[i]
+            } else {
+                self.config.initial_step
+            };
+            // Step away from an upper bound rather than onto it
+            vertex[i] += if x0[i] + step <= bounds.upper()[i] {
+                step
+            } else {
+                -step
+            };
+            simplex.push(evaluate(vertex));
+        }
+
+        let mut history = Vec::new();
+        for iteration in 0..self.config.max_iterations {
+            simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+            history.push(simplex[0].1);
+
+            if simplex[n].1 - simplex[0].1 <= self.config.objective_tolerance {
+                let (parameters, objective) = simplex.swap_remove(0);
+                return Ok(OptimisationResult {
+                    parameters,
+                    objective,
+                    iterations: iteration,
+                    function_evaluations: func_evals.get(),
+                    converged: true,
+                    history,
+                });
+            }
+
+            // Centroid of all vertices except the worst
+            let centroid: Vec<f64> = (0..n)
+                .map(|i| simplex[..n].iter().map(|(x, _)| x[i]).sum::<f64>() / n as f64)
+                .collect();
+            let towards = |coefficient: f64, from: &[f64]| -> Vec<f64> {
+                centroid
+                    .iter()
+                    .zip(from.iter())
+                    .map(|(c, x)| c + coefficient * (x - c))
+                    .collect()
+            };
+
+            let worst = simplex[n].0.clone();
+            let reflected = evaluate(towards(-self.config.reflection, &worst));
+
+            if reflected.1 < simplex[0].1 {
+                let expanded = evaluate(towards(
+                    -self.config.reflection * self.config.expansion,
+                    &worst,
+                ));
+                simplex[n] = if expanded.1 < reflected.1 {
+                    expanded
+                } else {
+                    reflected
+                };
+            } else if reflected.1 < simplex[n - 1].1 {
+                simplex[n] = reflected;
+            } else {
+                // Contract towards the better of the worst and reflected points
+                let (target, f_target) = if reflected.1 < simplex[n].1 {
+                    (reflected.0.clone(), reflected.1)
+                } else {
+                    (worst, simplex[n].1)
+                };
+                let contracted = evaluate(towards(self.config.contraction, &target));
+                if contracted.1 < f_target {
+                    simplex[n] = contracted;
+                } else {
+                    let best = simplex[0].0.clone();
+                    for vertex in simplex.iter_mut().skip(1) {
+                        let shrunk = best
+                            .iter()
+                            .zip(vertex.0.iter())
+                            .map(|(b, x)| b + self.config.shrink * (x - b))
+                            .collect();
+                        *vertex = evaluate(shrunk);
+                    }
+                }
+            }
+        }
+
+        simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+        Err(OptimiserError::ConvergenceFailure {
+            iterations: self.config.max_iterations,
+            residual: simplex[0].1,
+        })
+    }
+}
+
+impl Default for NelderMead {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quadratic() {
+        let objective = |p: &[f64]| {
+            (p[0] - 3.0).powi(2) + 2.0 * (p[1] + 1.0).powi(2) + 0.5 * (p[2] - 0.5).powi(2)
+        };
+        let result = NelderMead::new()
+            .minimise(&[0.0, 0.0, 0.0], &Bounds::unbounded(3), objective)
+            .unwrap();
+        assert!(result.converged);
+        assert!((result.parameters[0] - 3.0).abs() < 1e-3);
+        assert!((result.parameters[1] + 1.0).abs() < 1e-3);
+        assert!((result.parameters[2] - 0.5).abs() < 1e-3);
+        assert!(result.history.windows(2).all(|w| w[1] <= w[0]));
+    }
+
+    #[test]
+    fn test_non_smooth_with_bounds() {
+        // |x - 2| + |y - 2| on [0, 1]²: optimum at the corner (1, 1)
+        let bounds = Bounds::new(vec![0.0, 0.0], vec![1.0, 1.0]).unwrap();
+        let objective = |p: &[f64]| (p[0] - 2.0).abs() + (p[1] - 2.0).abs();
+        let result = NelderMead::new()
+            .minimise(&[0.2, 0.3], &bounds, objective)
+            .unwrap();
+        assert!(bounds.contains(&result.parameters));
+        assert!((result.objective - 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_iteration_limit() {
+        let config = NelderMeadConfig {
+            max_iterations: 3,
+            ..Default::default()
+        };
+        let rosenbrock = |p: &[f64]| (1.0 - p[0]).powi(2) + 100.0 * (p[1] - p[0] * p[0]).powi(2);
+        let result = NelderMead::with_config(config).minimise(
+            &[-1.2, 1.0],
+            &Bounds::unbounded(2),
+            rosenbrock,
+        );
+        assert!(matches!(
+            result,
+            Err(OptimiserError::ConvergenceFailure { iterations: 3, .. })
+        ));
+    }
+}
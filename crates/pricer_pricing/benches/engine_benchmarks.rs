@@ -10,7 +10,10 @@ use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criteri
 use pricer_pricing::checkpoint::CheckpointStrategy;
 use pricer_pricing::mc::pricer_checkpoint::{CheckpointPricer, CheckpointPricingConfig};
 use pricer_pricing::mc::thread_local::{current_thread_index, ParallelWorkspaces};
-use pricer_pricing::mc::{GbmParams, MonteCarloConfig, MonteCarloPricer, PayoffParams};
+use pricer_pricing::mc::{
+    generate_gbm_paths, generate_gbm_paths_scalar, GbmParams, MonteCarloConfig, MonteCarloPricer,
+    PathWorkspace, PayoffParams,
+};
 use pricer_pricing::path_dependent::PathPayoffType;
 use pricer_pricing::rng::PricerRng;
 use rayon::prelude::*;
@@ -468,6 +471,58 @@ fn bench_checkpoint_payoff_types(c: &mut Criterion) {
     group.finish();
 }
 
+// ============================================================================
+// Path Generation Kernels
+// ============================================================================
+
+/// Benchmark the scalar path kernel against the dispatched one.
+///
+/// Without the `simd` feature both entries run the scalar kernel; with it,
+/// `dispatched` uses the vector kernel for the detected CPU.
+fn bench_path_generation_kernels(c: &mut Criterion) {
+    let mut group = c.benchmark_group("path_generation_kernels");
+    group.sample_size(20);
+
+    // Cache-resident size, so this measures kernel throughput rather than
+    // memory bandwidth
+    let n_paths = 2_000;
+    let n_steps = 100;
+    let gbm = GbmParams::default();
+    let dt = gbm.maturity / n_steps as f64;
+    let drift_dt = (gbm.rate - 0.5 * gbm.volatility * gbm.volatility) * dt;
+    let vol_sqrt_dt = gbm.volatility * dt.sqrt();
+
+    let mut workspace = PathWorkspace::new(n_paths, n_steps);
+    workspace.ensure_capacity(n_paths, n_steps);
+    PricerRng::from_seed(42).fill_normal(workspace.randoms_mut());
+    let randoms = workspace.randoms().to_vec();
+    let mut paths = vec![0.0; n_paths * (n_steps + 1)];
+
+    group.bench_function("scalar", |b| {
+        b.iter(|| {
+            generate_gbm_paths_scalar(
+                &mut paths,
+                &randoms,
+                gbm.spot,
+                drift_dt,
+                vol_sqrt_dt,
+                n_paths,
+                n_steps,
+            );
+            black_box(&paths);
+        })
+    });
+
+    group.bench_function("dispatched", |b| {
+        b.iter(|| {
+            generate_gbm_paths(&mut workspace, gbm, n_paths, n_steps);
+            black_box(workspace.paths());
+        })
+    });
+
+    group.finish();
+}
+
 // ============================================================================
 // Scaling Benchmarks (Task 13.2)
 // ============================================================================
@@ -1054,6 +1109,7 @@ criterion_group!(
     bench_checkpoint_time_overhead,
     bench_checkpoint_memory_usage,
     bench_checkpoint_payoff_types,
+    bench_path_generation_kernels,
     bench_path_scaling,
     bench_step_scaling,
     bench_payoff_type_comparison,
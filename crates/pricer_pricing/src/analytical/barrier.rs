@@ -36,6 +36,7 @@ pub enum KnockType {
 
 /// Option type (call or put).
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum OptionType {
     /// Call option
     Call,
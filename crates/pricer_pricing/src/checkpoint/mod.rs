@@ -19,6 +19,8 @@
 //! - [`SimulationState`]: Full captured state at a checkpoint
 //! - [`CheckpointStorage`]: Storage for checkpoint states
 //! - [`CheckpointManager`]: Orchestrates checkpoint operations
+//! - [`CheckpointSchedule`]: Explicit reverse-sweep schedules (Revolve,
+//!   segmented, store-all) executed against an [`AdjointStep`]
 //!
 //! # Example
 //!
@@ -44,10 +46,12 @@
 
 mod budget;
 mod manager;
+mod schedule;
 mod state;
 mod strategy;
 
 pub use budget::MemoryBudget;
 pub use manager::{CheckpointError, CheckpointManager, CheckpointResult};
+pub use schedule::{AdjointStep, CheckpointSchedule, ScheduleAction, ScheduleReport};
 pub use state::{CheckpointStorage, MinimalState, SimulationState};
 pub use strategy::CheckpointStrategy;
@@ -0,0 +1,596 @@
+//! Checkpoint schedules for reverse-mode sweeps over long simulations.
+//!
+//! A [`CheckpointSchedule`] is the explicit sequence of forward advances,
+//! snapshots, restores and adjoint steps needed to reverse an `n_steps`
+//! time loop. [`CheckpointSchedule::execute`] replays it against any
+//! [`AdjointStep`] implementation and reports the measured memory/time
+//! trade-off in a [`ScheduleReport`].
+//!
+//! # Schedules
+//!
+//! | Strategy | Schedule | Stored states | Forward steps |
+//! |----------|----------|---------------|---------------|
+//! | `None` | store every step | `n` | `n` |
+//! | `Uniform`, `Logarithmic`, `Adaptive` | checkpoints plus one stored segment | `n/k + k` | `2n` |
+//! | `Binomial { memory_slots: c }` | Revolve | `c` | `r·n - β(c+1, r-1)` |
+//!
+//! where `r` is the smallest repetition number with `β(c, r) = C(c+r, c) ≥ n`.
+//! With `c = 20` slots a 10-year daily simulation (2520 steps) has `r = 4`
+//! and is reversed with about 3.2 times the forward work, while storing 20
+//! states instead of 2520.
+//!
+//! # Reference
+//!
+//! Griewank, A., & Walther, A. (2000). Algorithm 799: revolve: an
+//! implementation of checkpointing for the reverse or adjoint mode of
+//! computational differentiation. ACM TOMS, 26(1), 19-45.
+
+use std::time::{Duration, Instant};
+
+use super::strategy::CheckpointStrategy;
+
+/// A single instruction in a checkpoint schedule.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScheduleAction {
+    /// Advance the current state from step `from` to step `to`.
+    Advance {
+        /// Step the current state is at
+        from: usize,
+        /// Step to advance to
+        to: usize,
+    },
+    /// Store a copy of the current state (at `step`).
+    Snapshot {
+        /// Step of the stored state
+        step: usize,
+    },
+    /// Replace the current state with the stored state at `step`.
+    Restore {
+        /// Step of the stored state
+        step: usize,
+    },
+    /// Discard the stored state at `step`.
+    Free {
+        /// Step of the stored state
+        step: usize,
+    },
+    /// Seed the adjoint from the final state.
+    ///
+    /// The current state is at `n_steps - 1`; the executor advances a
+    /// temporary copy to `n_steps` so no extra slot is needed.
+    Terminal,
+    /// Propagate the adjoint through step `step → step + 1`, with the
+    /// current state at `step`.
+    Reverse {
+        /// Input step of the reversed transition
+        step: usize,
+    },
+}
+
+/// Measured cost of executing a schedule.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ScheduleReport {
+    /// Number of time steps reversed.
+    pub n_steps: usize,
+    /// Total forward steps taken, including recomputation.
+    pub forward_steps: usize,
+    /// Forward steps beyond the single sweep an unlimited tape would need.
+    pub recomputed_steps: usize,
+    /// Maximum number of states stored at once (including the initial state).
+    pub peak_stored_states: usize,
+    /// Maximum bytes held by stored states at once.
+    pub peak_memory_bytes: usize,
+    /// Wall-clock time spent executing the schedule.
+    pub elapsed: Duration,
+}
+
+impl ScheduleReport {
+    /// Returns the forward work relative to a single forward sweep.
+    #[inline]
+    pub fn recomputation_ratio(&self) -> f64 {
+        if self.n_steps == 0 {
+            return 1.0;
+        }
+        self.forward_steps as f64 / self.n_steps as f64
+    }
+}
+
+/// One step of a reversible time loop.
+///
+/// Implementors define the forward transition, the terminal adjoint seed
+/// and the adjoint of a single transition; the schedule decides which
+/// states are kept and which are recomputed.
+pub trait AdjointStep {
+    /// Simulation state between steps.
+    type State: Clone;
+    /// Adjoint accumulator (state adjoints plus parameter sensitivities).
+    type Adjoint;
+
+    /// Advances `state` in place from `step` to `step + 1`.
+    fn advance(&mut self, step: usize, state: &mut Self::State);
+
+    /// Seeds `adjoint` from the final state.
+    fn terminal(&mut self, state: &Self::State, adjoint: &mut Self::Adjoint);
+
+    /// Propagates `adjoint` back through `step → step + 1`, given the state
+    /// at `step`.
+    fn reverse(&mut self, step: usize, state: &Self::State, adjoint: &mut Self::Adjoint);
+
+    /// Returns the memory footprint of a stored state in bytes.
+    fn state_bytes(&self, _state: &Self::State) -> usize {
+        std::mem::size_of::<Self::State>()
+    }
+}
+
+/// An explicit checkpointing schedule for reversing `n_steps` steps.
+///
+/// # Examples
+///
+/// ```rust
+/// use pricer_pricing::checkpoint::CheckpointSchedule;
+///
+/// let schedule = CheckpointSchedule::revolve(2520, 20);
+/// assert!(schedule.peak_stored_states() <= 20);
+/// assert!(schedule.forward_steps() <= 4 * 2520);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CheckpointSchedule {
+    n_steps: usize,
+    actions: Vec<ScheduleAction>,
+}
+
+impl CheckpointSchedule {
+    /// Stores every state in a single forward sweep (no recomputation).
+    pub fn store_all(n_steps: usize) -> Self {
+        let mut actions = Vec::with_capacity(4 * n_steps + 2);
+        for step in 0..n_steps {
+            actions.push(ScheduleAction::Snapshot { step });
+            if step + 1 < n_steps {
+                actions.push(ScheduleAction::Advance {
+                    from: step,
+                    to: step + 1,
+                });
+            }
+        }
+        actions.push(ScheduleAction::Terminal);
+        for step in (0..n_steps).rev() {
+            actions.push(ScheduleAction::Restore { step });
+            actions.push(ScheduleAction::Reverse { step });
+            actions.push(ScheduleAction::Free { step });
+        }
+        Self { n_steps, actions }
+    }
+
+    /// Two-level schedule: checkpoints at `checkpoints`, segments re-taped.
+    ///
+    /// The forward sweep stores a state at each checkpoint step (step 0 is
+    /// always stored). During the reverse sweep each segment is replayed
+    /// once from its checkpoint with every state stored, then reversed.
+    pub fn segmented(n_steps: usize, checkpoints: &[usize]) -> Self {
+        if n_steps == 0 {
+            return Self::store_all(0);
+        }
+        let mut starts: Vec<usize> = std::iter::once(0)
+            .chain(checkpoints.iter().copied().filter(|&s| s < n_steps))
+            .collect();
+        starts.sort_unstable();
+        starts.dedup();
+
+        let mut actions = Vec::new();
+        for (i, &start) in starts.iter().enumerate() {
+            let end = starts.get(i + 1).copied().unwrap_or(n_steps - 1);
+            actions.push(ScheduleAction::Snapshot { step: start });
+            if end > start {
+                actions.push(ScheduleAction::Advance {
+                    from: start,
+                    to: end,
+                });
+            }
+        }
+        actions.push(ScheduleAction::Terminal);
+
+        for (i, &start) in starts.iter().enumerate().rev() {
+            let end = starts.get(i + 1).copied().unwrap_or(n_steps);
+            actions.push(ScheduleAction::Restore { step: start });
+            for step in start + 1..end {
+                actions.push(ScheduleAction::Advance {
+                    from: step - 1,
+                    to: step,
+                });
+                actions.push(ScheduleAction::Snapshot { step });
+            }
+            for step in (start..end).rev() {
+                actions.push(ScheduleAction::Restore { step });
+                actions.push(ScheduleAction::Reverse { step });
+                actions.push(ScheduleAction::Free { step });
+            }
+        }
+        Self { n_steps, actions }
+    }
+
+    /// Binomial (Revolve) schedule with at most `memory_slots` stored states.
+    ///
+    /// The slot count includes the initial state, so `memory_slots = 1`
+    /// recomputes every step from the start (quadratic cost). For `n`
+    /// steps the number of forward steps is `r·n - β(c+1, r-1) + 1`, the
+    /// Griewank-Walther optimum plus the advance to the final state.
+    pub fn revolve(n_steps: usize, memory_slots: usize) -> Self {
+        let mut builder = RevolveBuilder {
+            n_steps,
+            actions: Vec::new(),
+            terminal_done: false,
+        };
+        if n_steps > 0 {
+            builder.actions.push(ScheduleAction::Snapshot { step: 0 });
+            builder.reverse(0, n_steps, memory_slots.max(1) - 1);
+        } else {
+            builder.actions.push(ScheduleAction::Terminal);
+        }
+        Self {
+            n_steps,
+            actions: builder.actions,
+        }
+    }
+
+    /// Builds the schedule corresponding to a [`CheckpointStrategy`].
+    ///
+    /// `Binomial` maps to [`revolve`](Self::revolve), `None` to
+    /// [`store_all`](Self::store_all), and the interval strategies to
+    /// [`segmented`](Self::segmented) at their checkpoint steps.
+    pub fn from_strategy(strategy: &CheckpointStrategy, n_steps: usize) -> Self {
+        match *strategy {
+            CheckpointStrategy::Binomial { memory_slots } => Self::revolve(n_steps, memory_slots),
+            CheckpointStrategy::None => Self::store_all(n_steps),
+            _ => {
+                let checkpoints: Vec<usize> = (0..n_steps)
+                    .filter(|&step| strategy.should_checkpoint(step, n_steps))
+                    .collect();
+                Self::segmented(n_steps, &checkpoints)
+            }
+        }
+    }
+
+    /// Returns the number of steps the schedule reverses.
+    #[inline]
+    pub fn n_steps(&self) -> usize {
+        self.n_steps
+    }
+
+    /// Returns the schedule's actions in execution order.
+    #[inline]
+    pub fn actions(&self) -> &[ScheduleAction] {
+        &self.actions
+    }
+
+    /// Returns the total number of forward steps the schedule takes.
+    pub fn forward_steps(&self) -> usize {
+        self.actions
+            .iter()
+            .map(|action| match *action {
+                ScheduleAction::Advance { from, to } => to - from,
+                ScheduleAction::Terminal => self.n_steps.min(1),
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// Returns the maximum number of states stored at once.
+    pub fn peak_stored_states(&self) -> usize {
+        let mut stored = 0usize;
+        let mut peak = 0;
+        for action in &self.actions {
+            match action {
+                ScheduleAction::Snapshot { .. } => {
+                    stored += 1;
+                    peak = peak.max(stored);
+                }
+                ScheduleAction::Free { .. } => stored -= 1,
+                _ => {}
+            }
+        }
+        peak
+    }
+
+    /// Executes the schedule, accumulating into `adjoint`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the schedule restores a step that is not stored, which
+    /// cannot happen for schedules built by this type's constructors.
+    pub fn execute<A: AdjointStep>(
+        &self,
+        stepper: &mut A,
+        initial: A::State,
+        adjoint: &mut A::Adjoint,
+    ) -> ScheduleReport {
+        let start_time = Instant::now();
+        let mut current = initial;
+        let mut stored: Vec<(usize, A::State)> = Vec::new();
+        let mut stored_bytes = 0;
+        let mut report = ScheduleReport {
+            n_steps: self.n_steps,
+            ..Default::default()
+        };
+
+        for action in &self.actions {
+            match *action {
+                ScheduleAction::Advance { from, to } => {
+                    for step in from..to {
+                        stepper.advance(step, &mut current);
+                    }
+                    report.forward_steps += to - from;
+                }
+                ScheduleAction::Snapshot { step } => {
+                    stored_bytes += stepper.state_bytes(&current);
+                    stored.push((step, current.clone()));
+                    report.peak_stored_states = report.peak_stored_states.max(stored.len());
+                    report.peak_memory_bytes = report.peak_memory_bytes.max(stored_bytes);
+                }
+                ScheduleAction::Restore { step } => {
+                    let (_, state) = stored
+                        .iter()
+                        .rev()
+                        .find(|(s, _)| *s == step)
+                        .expect("schedule restores a stored step");
+                    current.clone_from(state);
+                }
+                ScheduleAction::Free { step } => {
+                    if let Some(pos) = stored.iter().rposition(|(s, _)| *s == step) {
+                        let (_, state) = stored.remove(pos);
+                        stored_bytes -= stepper.state_bytes(&state);
+                    }
+                }
+                ScheduleAction::Terminal => {
+                    if self.n_steps == 0 {
+                        stepper.terminal(&current, adjoint);
+                    } else {
+                        let mut last = current.clone();
+                        stepper.advance(self.n_steps - 1, &mut last);
+                        stepper.terminal(&last, adjoint);
+                        report.forward_steps += 1;
+                    }
+                }
+                ScheduleAction::Reverse { step } => stepper.reverse(step, &current, adjoint),
+            }
+        }
+
+        report.recomputed_steps = report.forward_steps.saturating_sub(self.n_steps);
+        report.elapsed = start_time.elapsed();
+        report
+    }
+}
+
+/// Recursive construction of the binomial schedule.
+struct RevolveBuilder {
+    n_steps: usize,
+    actions: Vec<ScheduleAction>,
+    terminal_done: bool,
+}
+
+impl RevolveBuilder {
+    /// Reverses steps `start..end`, with the state at `start` stored and
+    /// `free` further slots available.
+    fn reverse(&mut self, start: usize, end: usize, free: usize) {
+        let len = end - start;
+        if len == 1 {
+            self.actions.push(ScheduleAction::Restore { step: start });
+            self.leaf(start);
+        } else if free == 0 {
+            for step in (start..end).rev() {
+                self.actions.push(ScheduleAction::Restore { step: start });
+                if step > start {
+                    self.actions.push(ScheduleAction::Advance {
+                        from: start,
+                        to: step,
+                    });
+                }
+                self.leaf(step);
+            }
+        } else {
+            let mid = start + revolve_split(len, free + 1);
+            self.actions.push(ScheduleAction::Restore { step: start });
+            self.actions.push(ScheduleAction::Advance {
+                from: start,
+                to: mid,
+            });
+            self.actions.push(ScheduleAction::Snapshot { step: mid });
+            self.reverse(mid, end, free - 1);
+            self.actions.push(ScheduleAction::Free { step: mid });
+            self.reverse(start, mid, free);
+        }
+    }
+
+    /// Reverses the single step `step → step + 1`, with the current state
+    /// at `step`.
+    fn leaf(&mut self, step: usize) {
+        if !self.terminal_done && step + 1 == self.n_steps {
+            self.actions.push(ScheduleAction::Terminal);
+            self.terminal_done = true;
+        }
+        self.actions.push(ScheduleAction::Reverse { step });
+    }
+}
+
+/// Binomial coefficient `β(c, r) = C(c + r, c)`, saturating at `usize::MAX`.
+fn beta(c: usize, r: usize) -> usize {
+    let mut value: u128 = 1;
+    for i in 1..=r as u128 {
+        value = value * (c as u128 + i) / i;
+        if value >= usize::MAX as u128 {
+            return usize::MAX;
+        }
+    }
+    value as usize
+}
+
+/// Offset of the first checkpoint when reversing `len` steps with `c`
+/// slots (including the one holding the start state).
+fn revolve_split(len: usize, c: usize) -> usize {
+    let mut r = 0;
+    while beta(c, r) < len {
+        r += 1;
+    }
+    let below = |r: usize, c: usize| if r == 0 { 0 } else { beta(c, r - 1) };
+    below(r, c).min(len - below(r, c - 1)).clamp(1, len - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // ========================================================================
+    // Test Stepper
+    // ========================================================================
+
+    /// Reverses `x_{k+1} = x_k² · a_k`, recording the order of reversals.
+    struct Square {
+        coefficients: Vec<f64>,
+        reversed: Vec<usize>,
+    }
+
+    impl AdjointStep for Square {
+        type State = (usize, f64);
+        type Adjoint = (f64, Vec<f64>);
+
+        fn advance(&mut self, step: usize, state: &mut Self::State) {
+            assert_eq!(state.0, step);
+            *state = (step + 1, state.1 * state.1 * self.coefficients[step]);
+        }
+
+        fn terminal(&mut self, state: &Self::State, adjoint: &mut Self::Adjoint) {
+            assert_eq!(state.0, self.coefficients.len());
+            adjoint.0 = 1.0;
+        }
+
+        fn reverse(&mut self, step: usize, state: &Self::State, adjoint: &mut Self::Adjoint) {
+            assert_eq!(state.0, step);
+            self.reversed.push(step);
+            let x = state.1;
+            adjoint.1[step] = adjoint.0 * x * x;
+            adjoint.0 *= 2.0 * x * self.coefficients[step];
+        }
+    }
+
+    fn run(schedule: &CheckpointSchedule) -> ((f64, Vec<f64>), ScheduleReport) {
+        let n = schedule.n_steps();
+        let mut stepper = Square {
+            coefficients: (0..n).map(|i| 1.0 + 0.01 * (i % 7) as f64).collect(),
+            reversed: Vec::new(),
+        };
+        let mut adjoint = (0.0, vec![0.0; n]);
+        let report = schedule.execute(&mut stepper, (0, 0.999), &mut adjoint);
+        assert_eq!(stepper.reversed, (0..n).rev().collect::<Vec<_>>());
+        (adjoint, report)
+    }
+
+    /// Optimal forward cost of reversing `len` steps with `free` spare slots.
+    fn optimal_cost(len: usize, free: usize, memo: &mut Vec<Vec<Option<usize>>>) -> usize {
+        if len == 1 {
+            return 0;
+        }
+        if free == 0 {
+            return len * (len - 1) / 2;
+        }
+        if let Some(cost) = memo[len][free] {
+            return cost;
+        }
+        let cost = (1..len)
+            .map(|m| m + optimal_cost(len - m, free - 1, memo) + optimal_cost(m, free, memo))
+            .min()
+            .unwrap();
+        memo[len][free] = Some(cost);
+        cost
+    }
+
+    // ========================================================================
+    // Schedule Tests
+    // ========================================================================
+
+    #[test]
+    fn test_all_schedules_give_identical_adjoints() {
+        let n = 57;
+        let (reference, tape) = run(&CheckpointSchedule::store_all(n));
+        assert_eq!(tape.forward_steps, n);
+        assert_eq!(tape.peak_stored_states, n);
+
+        for schedule in [
+            CheckpointSchedule::segmented(n, &[10, 20, 30, 40, 50]),
+            CheckpointSchedule::revolve(n, 1),
+            CheckpointSchedule::revolve(n, 3),
+            CheckpointSchedule::revolve(n, 8),
+            CheckpointSchedule::revolve(n, 100),
+        ] {
+            let (adjoint, _) = run(&schedule);
+            assert_eq!(adjoint, reference);
+        }
+    }
+
+    #[test]
+    fn test_revolve_is_optimal() {
+        let mut memo = vec![vec![None; 8]; 80];
+        for len in 1..80 {
+            for slots in 1..8 {
+                let schedule = CheckpointSchedule::revolve(len, slots);
+                assert!(schedule.peak_stored_states() <= slots);
+                assert_eq!(
+                    schedule.forward_steps(),
+                    optimal_cost(len, slots - 1, &mut memo) + 1,
+                    "len={} slots={}",
+                    len,
+                    slots
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_revolve_ten_year_daily() {
+        let schedule = CheckpointSchedule::revolve(2520, 20);
+        let (_, report) = run(&schedule);
+
+        assert!(report.peak_stored_states <= 20);
+        assert_eq!(report.forward_steps, schedule.forward_steps());
+        // β(20, 3) = 1771 < 2520 ≤ β(20, 4) = 10626, so r = 4:
+        // 4 × 2520 - β(21, 3) + 1 = 8057
+        assert_eq!(report.forward_steps, 8057);
+        assert_eq!(
+            report.peak_memory_bytes,
+            report.peak_stored_states * std::mem::size_of::<(usize, f64)>()
+        );
+    }
+
+    #[test]
+    fn test_segmented_memory() {
+        let n = 100;
+        let schedule =
+            CheckpointSchedule::from_strategy(&CheckpointStrategy::Uniform { interval: 10 }, n);
+        let (_, report) = run(&schedule);
+
+        // 10 checkpoints plus one re-taped segment of 10 (sharing its start)
+        assert_eq!(report.peak_stored_states, 19);
+        assert_eq!(report.forward_steps, 2 * n - 10);
+    }
+
+    #[test]
+    fn test_from_strategy() {
+        assert_eq!(
+            CheckpointSchedule::from_strategy(&CheckpointStrategy::None, 30),
+            CheckpointSchedule::store_all(30)
+        );
+        assert_eq!(
+            CheckpointSchedule::from_strategy(
+                &CheckpointStrategy::Binomial { memory_slots: 4 },
+                30
+            ),
+            CheckpointSchedule::revolve(30, 4)
+        );
+    }
+
+    #[test]
+    fn test_empty_schedule() {
+        let (adjoint, report) = run(&CheckpointSchedule::revolve(0, 4));
+        assert_eq!(adjoint.0, 1.0);
+        assert_eq!(report.forward_steps, 0);
+        assert_eq!(report.recomputation_ratio(), 1.0);
+    }
+}
@@ -0,0 +1,173 @@
+//! Pricing engine selection.
+//!
+//! The crate offers several numerical engines with overlapping coverage.
+//! [`Engine`] names them and records which [`ProductKind`]s each can price,
+//! so that a service layer can route an instrument to a capable engine or
+//! honour a caller's explicit choice.
+//!
+//! # Example
+//!
+//! ```rust
+//! use pricer_pricing::engine::{Engine, ProductKind};
+//!
+//! assert_eq!(Engine::preferred(ProductKind::AmericanVanilla), Engine::Tree);
+//! assert!(Engine::Pde.supports(ProductKind::Barrier));
+//! assert!(!Engine::MonteCarlo.supports(ProductKind::CallableBond));
+//!
+//! let engine: Engine = "pde".parse().unwrap();
+//! assert_eq!(engine, Engine::Pde);
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+/// Product families distinguished for engine routing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ProductKind {
+    /// European call or put
+    EuropeanVanilla,
+    /// European cash-or-nothing digital
+    Digital,
+    /// Forward contract
+    Forward,
+    /// American call or put
+    AmericanVanilla,
+    /// Bermudan call or put
+    BermudanVanilla,
+    /// Continuously monitored single-barrier option
+    Barrier,
+    /// Arithmetic or geometric Asian option
+    Asian,
+    /// Fixed-coupon bond with issuer calls
+    CallableBond,
+}
+
+/// Numerical pricing engine.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Engine {
+    /// Closed-form formulas ([`crate::analytical`])
+    Analytic,
+    /// Monte Carlo simulation ([`crate::mc`])
+    MonteCarlo,
+    /// Finite-difference PDE solvers ([`crate::pde`])
+    Pde,
+    /// Binomial, trinomial and short-rate trees ([`crate::tree`])
+    Tree,
+}
+
+impl Engine {
+    /// All engines, in order of preference for products several support.
+    pub const ALL: [Engine; 4] = [
+        Engine::Analytic,
+        Engine::Tree,
+        Engine::Pde,
+        Engine::MonteCarlo,
+    ];
+
+    /// Returns whether this engine can price `kind`.
+    pub fn supports(&self, kind: ProductKind) -> bool {
+        use ProductKind::*;
+        match self {
+            // Asians are averaged arithmetically, which has no closed form
+            Engine::Analytic => matches!(kind, EuropeanVanilla | Digital | Forward | Barrier),
+            Engine::MonteCarlo => matches!(kind, EuropeanVanilla | Barrier | Asian),
+            Engine::Pde => matches!(kind, EuropeanVanilla | AmericanVanilla | Barrier),
+            Engine::Tree => matches!(
+                kind,
+                EuropeanVanilla | AmericanVanilla | BermudanVanilla | CallableBond
+            ),
+        }
+    }
+
+    /// Returns the default engine for `kind`: the first of [`Engine::ALL`]
+    /// that supports it.
+    pub fn preferred(kind: ProductKind) -> Engine {
+        *Self::ALL
+            .iter()
+            .find(|engine| engine.supports(kind))
+            .expect("every product kind has an engine")
+    }
+
+    /// Returns the lower-case engine name.
+    #[inline]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Engine::Analytic => "analytic",
+            Engine::MonteCarlo => "monte-carlo",
+            Engine::Pde => "pde",
+            Engine::Tree => "tree",
+        }
+    }
+}
+
+impl fmt::Display for Engine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Engine {
+    type Err = String;
+
+    /// Parses an engine name (case-insensitive).
+    ///
+    /// Supported formats:
+    /// - Analytic: "analytic", "analytical", "closed-form"
+    /// - MonteCarlo: "monte-carlo", "montecarlo", "mc"
+    /// - Pde: "pde", "fd", "finite-difference"
+    /// - Tree: "tree", "lattice", "binomial", "trinomial"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace(['-', '_', ' '], "").as_str() {
+            "analytic" | "analytical" | "closedform" => Ok(Engine::Analytic),
+            "montecarlo" | "mc" => Ok(Engine::MonteCarlo),
+            "pde" | "fd" | "finitedifference" => Ok(Engine::Pde),
+            "tree" | "lattice" | "binomial" | "trinomial" => Ok(Engine::Tree),
+            _ => Err(format!("Unknown engine: {}", s)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KINDS: [ProductKind; 8] = [
+        ProductKind::EuropeanVanilla,
+        ProductKind::Digital,
+        ProductKind::Forward,
+        ProductKind::AmericanVanilla,
+        ProductKind::BermudanVanilla,
+        ProductKind::Barrier,
+        ProductKind::Asian,
+        ProductKind::CallableBond,
+    ];
+
+    #[test]
+    fn test_preferred_engine_supports_kind() {
+        for kind in KINDS {
+            assert!(Engine::preferred(kind).supports(kind), "{:?}", kind);
+        }
+        assert_eq!(
+            Engine::preferred(ProductKind::EuropeanVanilla),
+            Engine::Analytic
+        );
+        assert_eq!(
+            Engine::preferred(ProductKind::BermudanVanilla),
+            Engine::Tree
+        );
+        assert_eq!(Engine::preferred(ProductKind::CallableBond), Engine::Tree);
+        assert_eq!(Engine::preferred(ProductKind::Asian), Engine::MonteCarlo);
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        for engine in Engine::ALL {
+            assert_eq!(engine.to_string().parse::<Engine>().unwrap(), engine);
+        }
+        assert_eq!("MC".parse::<Engine>().unwrap(), Engine::MonteCarlo);
+        assert_eq!("Finite_Difference".parse::<Engine>().unwrap(), Engine::Pde);
+        assert!("quantum".parse::<Engine>().is_err());
+    }
+}
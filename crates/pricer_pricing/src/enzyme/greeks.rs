@@ -1,9 +1,10 @@
 //! Enzyme-based Greeks calculation for Monte Carlo pricing.
 //!
-//! This module provides the `GreeksEnzyme` trait for integrating Enzyme
-//! automatic differentiation with the Monte Carlo pricer. When the `enzyme-ad`
-//! feature is enabled, it uses LLVM-level AD; otherwise, it falls back to
-//! finite difference approximations.
+//! This module provides the `GreeksEnzyme` trait for integrating automatic
+//! differentiation with the Monte Carlo pricer. Reverse mode computes Delta,
+//! Vega, Rho, Theta and Gamma (forward-over-reverse) from a single sweep over
+//! the simulated paths; `GreeksMode::FiniteDifference` keeps bump-and-revalue
+//! available for verification.
 //!
 //! # Usage
 //!
@@ -28,7 +29,10 @@
 //! ```
 
 use crate::greeks::GreeksResult;
-use crate::mc::{GbmParams, MonteCarloPricer, PayoffParams, PricingResult};
+use crate::mc::{
+    soft_plus, soft_plus_derivative, GbmParams, MonteCarloPricer, PayoffParams, PayoffType,
+    PricingResult,
+};
 
 /// Mode for Greeks computation.
 ///
@@ -37,7 +41,7 @@ use crate::mc::{GbmParams, MonteCarloPricer, PayoffParams, PricingResult};
 pub enum GreeksMode {
     /// Automatically select the best available method.
     ///
-    /// Uses Enzyme AD when available, falls back to finite differences.
+    /// Resolves to reverse mode.
     #[default]
     Auto,
 
@@ -54,7 +58,8 @@ pub enum GreeksMode {
 
     /// Use reverse mode AD for all Greeks at once.
     ///
-    /// Most efficient when computing multiple Greeks.
+    /// Most efficient when computing multiple Greeks. Does not require
+    /// Enzyme: the adjoint of the GBM sweep is implemented directly.
     ReverseMode,
 }
 
@@ -62,10 +67,7 @@ impl GreeksMode {
     /// Returns whether this mode requires Enzyme AD.
     #[inline]
     pub fn requires_enzyme(&self) -> bool {
-        matches!(
-            self,
-            Self::EnzymeOnly | Self::ForwardMode | Self::ReverseMode
-        )
+        matches!(self, Self::EnzymeOnly | Self::ForwardMode)
     }
 
     /// Returns whether Enzyme AD is available.
@@ -78,13 +80,7 @@ impl GreeksMode {
     #[inline]
     pub fn resolve(&self) -> Self {
         match self {
-            Self::Auto => {
-                if Self::enzyme_available() {
-                    Self::ReverseMode
-                } else {
-                    Self::FiniteDifference
-                }
-            }
+            Self::Auto => Self::ReverseMode,
             other => *other,
         }
     }
@@ -187,6 +183,7 @@ impl EnzymeGreeksResult {
             } else {
                 None
             },
+            ..Default::default()
         }
     }
 
@@ -291,8 +288,9 @@ pub trait GreeksEnzyme {
 
 /// Implementation of GreeksEnzyme for MonteCarloPricer.
 ///
-/// When `enzyme-ad` feature is enabled, uses Enzyme LLVM-level AD.
-/// Otherwise, falls back to finite difference approximations.
+/// Reverse mode runs a hand-written adjoint of the GBM path and smoothed
+/// payoff, so all first-order Greeks and Gamma come from one Monte Carlo
+/// sweep. Finite differences remain available for verification.
 impl GreeksEnzyme for MonteCarloPricer {
     fn price_with_enzyme_greeks(
         &mut self,
@@ -305,7 +303,7 @@ impl GreeksEnzyme for MonteCarloPricer {
 
         match resolved_mode {
             GreeksMode::FiniteDifference | GreeksMode::Auto => {
-                // Fall back to finite differences
+                // Bump-and-revalue (verification path)
                 self.compute_greeks_fd(gbm, payoff, discount_factor)
             }
             GreeksMode::ForwardMode => {
@@ -336,17 +334,8 @@ impl GreeksEnzyme for MonteCarloPricer {
         payoff: PayoffParams,
         discount_factor: f64,
     ) -> f64 {
-        #[cfg(feature = "enzyme-ad")]
-        {
-            // Enzyme forward mode would go here
-            // For now, use the existing price_with_delta_ad method
-            let result = self.price_with_delta_ad(gbm, payoff, discount_factor);
-            result.delta.unwrap_or(0.0)
-        }
-        #[cfg(not(feature = "enzyme-ad"))]
-        {
-            self.compute_delta_fd(gbm, payoff, discount_factor)
-        }
+        self.compute_greeks_reverse(gbm, payoff, discount_factor)
+            .delta
     }
 
     fn compute_gamma_ad(
@@ -355,9 +344,9 @@ impl GreeksEnzyme for MonteCarloPricer {
         payoff: PayoffParams,
         discount_factor: f64,
     ) -> f64 {
-        // Gamma requires nested AD or finite differences on Delta
-        // For now, use finite differences
-        self.compute_gamma_fd(gbm, payoff, discount_factor)
+        // Forward-over-reverse: tangent of the spot adjoint
+        self.compute_greeks_reverse(gbm, payoff, discount_factor)
+            .gamma
     }
 
     fn compute_vega_ad(
@@ -366,15 +355,8 @@ impl GreeksEnzyme for MonteCarloPricer {
         payoff: PayoffParams,
         discount_factor: f64,
     ) -> f64 {
-        #[cfg(feature = "enzyme-ad")]
-        {
-            // Enzyme would compute this via reverse mode
-            self.compute_vega_fd(gbm, payoff, discount_factor)
-        }
-        #[cfg(not(feature = "enzyme-ad"))]
-        {
-            self.compute_vega_fd(gbm, payoff, discount_factor)
-        }
+        self.compute_greeks_reverse(gbm, payoff, discount_factor)
+            .vega
     }
 
     fn compute_theta_ad(
@@ -383,7 +365,8 @@ impl GreeksEnzyme for MonteCarloPricer {
         payoff: PayoffParams,
         discount_factor: f64,
     ) -> f64 {
-        self.compute_theta_fd(gbm, payoff, discount_factor)
+        self.compute_greeks_reverse(gbm, payoff, discount_factor)
+            .theta
     }
 
     fn compute_rho_ad(
@@ -392,10 +375,18 @@ impl GreeksEnzyme for MonteCarloPricer {
         payoff: PayoffParams,
         discount_factor: f64,
     ) -> f64 {
-        self.compute_rho_fd(gbm, payoff, discount_factor)
+        self.compute_greeks_reverse(gbm, payoff, discount_factor)
+            .rho
     }
 }
 
+/// Relative width of the payoff smoothing used for the Gamma tangent.
+///
+/// The pathwise second derivative of a kinked payoff is a spike of width
+/// `smoothing_epsilon`; widening it to 1% of spot (the finite-difference
+/// Gamma bump) keeps the estimator's variance finite.
+const GAMMA_SMOOTHING: f64 = 0.01;
+
 /// Internal implementation methods for MonteCarloPricer.
 impl MonteCarloPricer {
     /// Computes all Greeks using finite differences.
@@ -436,7 +427,7 @@ impl MonteCarloPricer {
         // Forward mode computes one Greek at a time
         let base_result = self.price_european(gbm, payoff, discount_factor);
 
-        let delta = self.compute_delta_ad(gbm, payoff, discount_factor);
+        let (_, delta) = self.price_with_delta_ad(gbm, payoff, discount_factor);
         let gamma = self.compute_gamma_fd(gbm, payoff, discount_factor);
         let vega = self.compute_vega_fd(gbm, payoff, discount_factor);
         let theta = self.compute_theta_fd(gbm, payoff, discount_factor);
@@ -453,27 +444,120 @@ impl MonteCarloPricer {
         )
     }
 
-    /// Computes Greeks using reverse mode AD.
+    /// Computes price and Greeks in a single reverse-mode sweep.
+    ///
+    /// Each path is simulated forward with its spot values recorded on a
+    /// tape, then the payoff adjoint is propagated back through the
+    /// log-space GBM steps. The adjoints of `drift_dt` and `vol_sqrt_dt`
+    /// are accumulated across paths and chained to rate, volatility and
+    /// maturity once at the end. Gamma is the tangent of the spot adjoint
+    /// with respect to spot (forward-over-reverse).
+    ///
+    /// Conventions match the finite-difference path: Theta is `-∂V/∂T`
+    /// with the discount factor held fixed, and Rho includes discounting
+    /// at `exp(-rT)` and is scaled to a 1% rate move.
     ///
-    /// In a full Enzyme implementation, this would compute all Greeks
-    /// in a single reverse pass. Currently falls back to finite differences.
+    /// The sweep assumes flat GBM coefficients, so with a term structure
+    /// set this falls back to finite differences.
     fn compute_greeks_reverse(
         &mut self,
         gbm: GbmParams,
         payoff: PayoffParams,
         discount_factor: f64,
     ) -> EnzymeGreeksResult {
-        #[cfg(feature = "enzyme-ad")]
-        {
-            // When Enzyme is enabled, this would use #[autodiff_reverse]
-            // to compute all Greeks in one reverse pass.
-            // For now, use finite differences as placeholder.
-            self.compute_greeks_fd(gbm, payoff, discount_factor)
+        if self.term_structure().is_some() {
+            return self.compute_greeks_fd(gbm, payoff, discount_factor);
         }
-        #[cfg(not(feature = "enzyme-ad"))]
-        {
-            self.compute_greeks_fd(gbm, payoff, discount_factor)
+
+        let n_paths = self.config().n_paths();
+        let n_steps = self.config().n_steps();
+
+        // Same random stream as the bump-and-revalue path
+        let seed = self.rng.seed();
+        self.reset_with_seed(seed);
+        let mut randoms = vec![0.0; n_paths * n_steps];
+        self.rng.fill_normal(&mut randoms);
+
+        let dt = gbm.maturity / n_steps as f64;
+        let sqrt_dt = dt.sqrt();
+        let drift = gbm.rate - 0.5 * gbm.volatility * gbm.volatility;
+        let drift_dt = drift * dt;
+        let vol_sqrt_dt = gbm.volatility * sqrt_dt;
+
+        let sign = match payoff.payoff_type {
+            PayoffType::Call => 1.0,
+            PayoffType::Put => -1.0,
+        };
+        let epsilon = payoff.smoothing_epsilon;
+        let gamma_epsilon = epsilon.max(GAMMA_SMOOTHING * gbm.spot);
+
+        // Tape of spot values along the current path
+        let mut tape = vec![0.0; n_steps + 1];
+
+        let mut payoff_sum = 0.0;
+        let mut payoff_sq_sum = 0.0;
+        let mut spot_adj_sum = 0.0;
+        let mut spot_adj_tangent_sum = 0.0;
+        let mut drift_dt_adj = 0.0;
+        let mut vol_sqrt_dt_adj = 0.0;
+
+        for z_path in randoms.chunks_exact(n_steps) {
+            // Forward pass
+            tape[0] = gbm.spot;
+            for (step, &z) in z_path.iter().enumerate() {
+                tape[step + 1] = tape[step] * (drift_dt + vol_sqrt_dt * z).exp();
+            }
+
+            let terminal = tape[n_steps];
+            let moneyness = sign * (terminal - payoff.strike);
+            let value = soft_plus(moneyness, epsilon);
+            payoff_sum += value;
+            payoff_sq_sum += value * value;
+
+            // Reverse pass, seeded with ∂payoff/∂S_T
+            let mut spot_adj = sign * soft_plus_derivative(moneyness, epsilon);
+
+            // Tangent of the adjoint w.r.t. spot: ∂S_T/∂S₀ = S_T/S₀
+            let indicator = soft_plus_derivative(moneyness, gamma_epsilon);
+            let mut spot_adj_tangent =
+                indicator * (1.0 - indicator) / gamma_epsilon * terminal / gbm.spot;
+
+            for step in (0..n_steps).rev() {
+                // S[t+1] = S[t] × exp(drift_dt + vol_sqrt_dt × Z)
+                let increment_adj = spot_adj * tape[step + 1];
+                drift_dt_adj += increment_adj;
+                vol_sqrt_dt_adj += increment_adj * z_path[step];
+
+                let growth = tape[step + 1] / tape[step];
+                spot_adj *= growth;
+                spot_adj_tangent *= growth;
+            }
+
+            spot_adj_sum += spot_adj;
+            spot_adj_tangent_sum += spot_adj_tangent;
         }
+
+        // Chain the step-constant adjoints back to the model parameters
+        let rate_adj = drift_dt_adj * dt;
+        let vol_adj = -gbm.volatility * dt * drift_dt_adj + sqrt_dt * vol_sqrt_dt_adj;
+        let dt_adj = drift * drift_dt_adj + 0.5 * gbm.volatility / sqrt_dt * vol_sqrt_dt_adj;
+        let maturity_adj = dt_adj / n_steps as f64;
+
+        let n = n_paths as f64;
+        let scale = discount_factor / n;
+        let mean = payoff_sum / n;
+        let variance = (payoff_sq_sum - n * mean * mean).max(0.0) / (n - 1.0);
+        let price = mean * discount_factor;
+
+        EnzymeGreeksResult::new(
+            price,
+            (variance / n).sqrt() * discount_factor,
+            spot_adj_sum * scale,
+            spot_adj_tangent_sum * scale,
+            vol_adj * scale,
+            -maturity_adj * scale,
+            (rate_adj * scale - gbm.maturity * price) * 0.01,
+        )
     }
 
     /// Computes Delta using finite differences (central difference).
@@ -554,7 +638,9 @@ impl MonteCarloPricer {
             volatility: gbm.volatility + bump,
             ..gbm
         };
-        let price_up = self.price_european(gbm_up, payoff, discount_factor).price;
+        let price_up = self.with_shifted_term_structure(bump, 0.0, |p| {
+            p.price_european(gbm_up, payoff, discount_factor).price
+        });
 
         // Price at vol - bump
         self.reset_with_seed(seed);
@@ -562,7 +648,9 @@ impl MonteCarloPricer {
             volatility: (gbm.volatility - bump).max(0.001),
             ..gbm
         };
-        let price_down = self.price_european(gbm_down, payoff, discount_factor).price;
+        let price_down = self.with_shifted_term_structure(-bump, 0.0, |p| {
+            p.price_european(gbm_down, payoff, discount_factor).price
+        });
 
         (price_up - price_down) / (2.0 * bump)
     }
@@ -613,7 +701,9 @@ impl MonteCarloPricer {
             ..gbm
         };
         let df_up = discount_factor * (-bump * gbm.maturity).exp();
-        let price_up = self.price_european(gbm_up, payoff, df_up).price;
+        let price_up = self.with_shifted_term_structure(0.0, bump, |p| {
+            p.price_european(gbm_up, payoff, df_up).price
+        });
 
         // Price at r - bump
         self.reset_with_seed(seed);
@@ -622,7 +712,9 @@ impl MonteCarloPricer {
             ..gbm
         };
         let df_down = discount_factor * (bump * gbm.maturity).exp();
-        let price_down = self.price_european(gbm_down, payoff, df_down).price;
+        let price_down = self.with_shifted_term_structure(0.0, -bump, |p| {
+            p.price_european(gbm_down, payoff, df_down).price
+        });
 
         // Scaled to 1% rate move
         (price_up - price_down) / (2.0 * bump) * 0.01
@@ -657,15 +749,13 @@ mod tests {
         assert!(GreeksMode::EnzymeOnly.requires_enzyme());
         assert!(!GreeksMode::FiniteDifference.requires_enzyme());
         assert!(GreeksMode::ForwardMode.requires_enzyme());
-        assert!(GreeksMode::ReverseMode.requires_enzyme());
+        assert!(!GreeksMode::ReverseMode.requires_enzyme());
     }
 
     #[test]
     fn test_greeks_mode_resolve() {
         let auto = GreeksMode::Auto.resolve();
-        // Without enzyme-ad feature, should resolve to FiniteDifference
-        #[cfg(not(feature = "enzyme-ad"))]
-        assert_eq!(auto, GreeksMode::FiniteDifference);
+        assert_eq!(auto, GreeksMode::ReverseMode);
 
         let fd = GreeksMode::FiniteDifference.resolve();
         assert_eq!(fd, GreeksMode::FiniteDifference);
@@ -823,4 +913,79 @@ mod tests {
         let pricing: PricingResult = enzyme_result2.into();
         assert!((pricing.price - 10.5).abs() < 1e-10);
     }
+
+    #[test]
+    fn test_reverse_matches_finite_difference() {
+        let (gbm, payoff, df) = standard_params();
+
+        let reverse =
+            create_pricer().price_with_enzyme_greeks(gbm, payoff, df, GreeksMode::ReverseMode);
+        let fd =
+            create_pricer().price_with_enzyme_greeks(gbm, payoff, df, GreeksMode::FiniteDifference);
+
+        // Same random stream, so the price is identical
+        assert!((reverse.price - fd.price).abs() < 1e-10);
+        assert!((reverse.delta - fd.delta).abs() < 1e-3);
+        assert!((reverse.vega - fd.vega).abs() < 0.1);
+        assert!((reverse.theta - fd.theta).abs() < 0.1);
+        assert!((reverse.rho - fd.rho).abs() < 1e-3);
+        assert!((reverse.gamma - fd.gamma).abs() < 2e-3);
+    }
+
+    #[test]
+    fn test_reverse_matches_black_scholes() {
+        let config = MonteCarloConfig::builder()
+            .n_paths(100_000)
+            .n_steps(1)
+            .seed(7)
+            .build()
+            .unwrap();
+        let mut pricer = MonteCarloPricer::new(config).unwrap();
+        let (gbm, payoff, df) = standard_params();
+
+        let result = pricer.price_with_enzyme_greeks(gbm, payoff, df, GreeksMode::ReverseMode);
+
+        // Black-Scholes: S=100, K=100, r=5%, σ=20%, T=1
+        assert!((result.price - 10.4506).abs() < 0.15);
+        assert!((result.delta - 0.6368).abs() < 0.01);
+        assert!((result.gamma - 0.018762).abs() < 0.001);
+        assert!((result.vega - 37.524).abs() < 0.6);
+    }
+
+    #[test]
+    fn test_reverse_put_greeks_signs() {
+        let mut pricer = create_pricer();
+        let (gbm, _, df) = standard_params();
+
+        let result =
+            pricer.price_with_enzyme_greeks(gbm, PayoffParams::put(100.0), df, GreeksMode::Auto);
+
+        assert!(result.delta < 0.0 && result.delta > -0.6);
+        assert!(result.gamma > 0.0);
+        assert!(result.vega > 0.0);
+        assert!(result.rho < 0.0);
+    }
+
+    #[test]
+    fn test_reverse_multi_step_matches_finite_difference() {
+        let config = MonteCarloConfig::builder()
+            .n_paths(5_000)
+            .n_steps(20)
+            .seed(11)
+            .build()
+            .unwrap();
+        let (gbm, payoff, df) = standard_params();
+
+        let reverse = MonteCarloPricer::new(config.clone())
+            .unwrap()
+            .price_with_enzyme_greeks(gbm, payoff, df, GreeksMode::ReverseMode);
+        let fd = MonteCarloPricer::new(config)
+            .unwrap()
+            .price_with_enzyme_greeks(gbm, payoff, df, GreeksMode::FiniteDifference);
+
+        assert!((reverse.price - fd.price).abs() < 1e-10);
+        assert!((reverse.delta - fd.delta).abs() < 1e-3);
+        assert!((reverse.vega - fd.vega).abs() < 0.1);
+        assert!((reverse.theta - fd.theta).abs() < 0.1);
+    }
 }
@@ -2,7 +2,7 @@
 //!
 //! This module provides comprehensive comparison between different AD methods:
 //!
-//! - Enzyme AD (or finite difference fallback)
+//! - Reverse-mode AD (single adjoint sweep)
 //! - Bump-and-revalue finite differences
 //! - Black-Scholes analytical Greeks (for European options)
 //!
@@ -12,7 +12,7 @@
 //!
 //! | Comparison | Tolerance |
 //! |------------|-----------|
-//! | Enzyme vs FD | 1e-4 |
+//! | Enzyme vs FD | 1e-2 |
 //! | Enzyme vs Analytical | 1e-3 |
 //! | FD vs Analytical | 1e-3 |
 //!
@@ -56,7 +56,7 @@ pub struct VerificationConfig {
 impl Default for VerificationConfig {
     fn default() -> Self {
         Self {
-            enzyme_fd_tolerance: 1e-4,
+            enzyme_fd_tolerance: 1e-2,  // pathwise vs central-difference truncation
             analytical_tolerance: 5e-2, // MC has inherent variance
             n_paths: 100_000,
             seed: 42,
@@ -543,7 +543,7 @@ mod tests {
         VerificationConfig::new()
             .with_n_paths(50_000)
             .with_seed(12345)
-            .with_enzyme_fd_tolerance(1e-4)
+            .with_enzyme_fd_tolerance(1e-2)
             .with_analytical_tolerance(0.1) // MC variance requires wider tolerance
     }
 
@@ -715,30 +715,31 @@ mod tests {
 
     #[test]
     fn test_enzyme_fd_consistency() {
-        // The key test: Enzyme and FD should produce same results
-        // (since without enzyme-ad feature, both use FD)
+        // The key test: the adjoint sweep and bump-and-revalue agree on the
+        // same random stream, up to the bumps' truncation error
         let config = verification_config();
         let result = verify_european_greeks(100.0, 100.0, 0.05, 0.2, 1.0, true, config);
 
-        #[cfg(not(feature = "enzyme-ad"))]
-        {
-            // Without Enzyme, both methods should be identical
-            assert_relative_eq!(
-                result.delta.enzyme_value,
-                result.delta.fd_value,
-                epsilon = 1e-10
-            );
-            assert_relative_eq!(
-                result.gamma.enzyme_value,
-                result.gamma.fd_value,
-                epsilon = 1e-10
-            );
-            assert_relative_eq!(
-                result.vega.enzyme_value,
-                result.vega.fd_value,
-                epsilon = 1e-10
-            );
-        }
+        assert_relative_eq!(
+            result.delta.enzyme_value,
+            result.delta.fd_value,
+            max_relative = 1e-3
+        );
+        assert_relative_eq!(
+            result.gamma.enzyme_value,
+            result.gamma.fd_value,
+            max_relative = 5e-2
+        );
+        assert_relative_eq!(
+            result.vega.enzyme_value,
+            result.vega.fd_value,
+            max_relative = 1e-3
+        );
+        assert_relative_eq!(
+            result.rho.enzyme_value,
+            result.rho.fd_value,
+            max_relative = 1e-3
+        );
     }
 
     #[test]
@@ -0,0 +1,373 @@
+//! COS method (Fang & Oosterlee, 2008).
+//!
+//! The density of `y = ln(S_T / K)` is expanded in a Fourier-cosine series
+//! on a truncated range `[a, b]`, whose coefficients follow directly from
+//! the characteristic function. The put payoff has closed-form cosine
+//! coefficients, so the price is a single sum of `N` terms:
+//!
+//! ```text
+//! P = K e^{-rT} Σ'ₖ Re[φ(uₖ) e^{-iuₖa}] Vₖ,   uₖ = kπ / (b - a)
+//! ```
+//!
+//! Calls follow by put-call parity, which avoids the cancellation error of
+//! the call coefficients for deep in-the-money strikes.
+//!
+//! The range is centred on `X = ln(S_T / F)` rather than on `y`, so
+//! `φ(uₖ) e^{-iuₖa}` depends only on the maturity. [`CosPricer::price_strikes`]
+//! evaluates it once per maturity and reprices every strike in `O(N)`.
+
+use num_complex::Complex64;
+
+use super::error::{FourierError, FourierResult};
+use super::models::CharacteristicFunction;
+use super::{FourierMarket, FourierOption};
+use crate::analytical::OptionType;
+use crate::validate::positive;
+
+/// COS method pricer for European options.
+///
+/// # Examples
+///
+/// ```rust
+/// use pricer_pricing::fourier::{CosPricer, FourierMarket, FourierOption, HestonCf};
+///
+/// let model = HestonCf::new(0.0175, 1.5768, 0.0398, 0.5751, -0.5711);
+/// let market = FourierMarket::new(100.0, 0.0, 0.0);
+/// let call = FourierOption::european_call(100.0, 1.0);
+///
+/// let price = CosPricer::default().price(&model, &market, &call).unwrap();
+/// assert!((price - 5.785155450).abs() < 1e-6);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CosPricer {
+    /// Number of cosine terms
+    pub n_terms: usize,
+    /// Truncation range in units of `√(c₂ + √c₄)`; the default of 20 covers
+    /// the fat left tail of Heston models that violate the Feller condition
+    pub truncation: f64,
+}
+
+impl Default for CosPricer {
+    fn default() -> Self {
+        Self {
+            n_terms: 256,
+            truncation: 20.0,
+        }
+    }
+}
+
+impl CosPricer {
+    /// Creates a pricer with `n_terms` cosine terms and the default
+    /// truncation range.
+    #[inline]
+    pub fn new(n_terms: usize) -> Self {
+        Self {
+            n_terms,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the truncation range multiplier `L`.
+    #[inline]
+    pub fn with_truncation(mut self, truncation: f64) -> Self {
+        self.truncation = truncation;
+        self
+    }
+
+    /// Prices a European option.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FourierError::InvalidParameter` if the model, market, option
+    /// or pricer settings are invalid.
+    pub fn price<M: CharacteristicFunction + ?Sized>(
+        &self,
+        model: &M,
+        market: &FourierMarket,
+        option: &FourierOption,
+    ) -> FourierResult<f64> {
+        let prices = self.price_strikes(
+            model,
+            market,
+            option.option_type,
+            option.maturity,
+            &[option.strike],
+        )?;
+        Ok(prices[0])
+    }
+
+    /// Prices European options of one type and maturity across strikes.
+    ///
+    /// The characteristic function is evaluated `n_terms` times in total,
+    /// not per strike.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FourierError::InvalidParameter` if the model, market, any
+    /// strike or the pricer settings are invalid.
+    pub fn price_strikes<M: CharacteristicFunction + ?Sized>(
+        &self,
+        model: &M,
+        market: &FourierMarket,
+        option_type: OptionType,
+        maturity: f64,
+        strikes: &[f64],
+    ) -> FourierResult<Vec<f64>> {
+        self.validate()?;
+        model.validate()?;
+        market.validate()?;
+        positive("maturity", maturity)?;
+        for &strike in strikes {
+            positive("strike", strike)?;
+        }
+
+        let df = market.discount_factor(maturity);
+        let forward = market.forward(maturity);
+        let (a, b) = self.range(model, maturity);
+        let width = b - a;
+
+        // Strike-independent part: φ(uₖ) e^{-iuₖa}, first term halved
+        let weights: Vec<(f64, Complex64)> = (0..self.n_terms)
+            .map(|k| {
+                let u = k as f64 * std::f64::consts::PI / width;
+                let phase = Complex64::new(0.0, -u * a).exp();
+                let w = model.cf(Complex64::new(u, 0.0), maturity) * phase;
+                (u, if k == 0 { 0.5 * w } else { w })
+            })
+            .collect();
+
+        Ok(strikes
+            .iter()
+            .map(|&strike| {
+                let x = (forward / strike).ln();
+                // y = x + X ranges over [x + a, x + b]; the put pays on y < 0
+                let (lo, hi) = (x + a, (x + b).min(0.0));
+                let put = if hi <= lo {
+                    0.0
+                } else {
+                    let sum: f64 = weights
+                        .iter()
+                        .map(|&(u, w)| w.re * (psi(u, lo, lo, hi) - chi(u, lo, lo, hi)))
+                        .sum();
+                    (df * strike * 2.0 / width * sum).max(0.0)
+                };
+                match option_type {
+                    OptionType::Put => put,
+                    OptionType::Call => (put + df * (forward - strike)).max(0.0),
+                }
+            })
+            .collect())
+    }
+
+    /// Prices a surface of European options, one row per maturity.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FourierError::InvalidParameter` as [`price_strikes`](Self::price_strikes).
+    pub fn price_surface<M: CharacteristicFunction + ?Sized>(
+        &self,
+        model: &M,
+        market: &FourierMarket,
+        option_type: OptionType,
+        maturities: &[f64],
+        strikes: &[f64],
+    ) -> FourierResult<Vec<Vec<f64>>> {
+        maturities
+            .iter()
+            .map(|&maturity| self.price_strikes(model, market, option_type, maturity, strikes))
+            .collect()
+    }
+
+    /// Truncation range for `X = ln(S_T / F)`.
+    fn range<M: CharacteristicFunction + ?Sized>(&self, model: &M, maturity: f64) -> (f64, f64) {
+        let c = model.cumulants(maturity);
+        let half_width = self.truncation * (c.c2.abs() + c.c4.abs().sqrt()).sqrt();
+        (c.c1 - half_width, c.c1 + half_width)
+    }
+
+    fn validate(&self) -> FourierResult<()> {
+        if self.n_terms < 2 {
+            return Err(FourierError::InvalidParameter {
+                name: "n_terms",
+                value: format!("{} must be at least 2", self.n_terms),
+            });
+        }
+        positive("truncation", self.truncation)?;
+        Ok(())
+    }
+}
+
+/// Cosine coefficient of `eʸ` on `[c, d]` within the range starting at `a`.
+#[inline]
+fn chi(u: f64, a: f64, c: f64, d: f64) -> f64 {
+    let (ec, ed) = (c.exp(), d.exp());
+    let (sc, cc) = (u * (c - a)).sin_cos();
+    let (sd, cd) = (u * (d - a)).sin_cos();
+    (cd * ed - cc * ec + u * (sd * ed - sc * ec)) / (1.0 + u * u)
+}
+
+/// Cosine coefficient of `1` on `[c, d]` within the range starting at `a`.
+#[inline]
+fn psi(u: f64, a: f64, c: f64, d: f64) -> f64 {
+    if u == 0.0 {
+        d - c
+    } else {
+        ((u * (d - a)).sin() - (u * (c - a)).sin()) / u
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fourier::{BatesCf, BlackScholesCf, HestonCf, MertonCf};
+    use crate::pde::{AdiConfig, HestonPde, PdeOption};
+    use approx::assert_relative_eq;
+
+    const BS_CALL: f64 = 10.450_583_572_185_565;
+
+    fn market() -> FourierMarket {
+        FourierMarket::new(100.0, 0.05, 0.0)
+    }
+
+    #[test]
+    fn test_black_scholes_matches_closed_form() {
+        let model = BlackScholesCf::new(0.2);
+        let pricer = CosPricer::new(128);
+        let call = pricer
+            .price(&model, &market(), &FourierOption::european_call(100.0, 1.0))
+            .unwrap();
+        assert_relative_eq!(call, BS_CALL, epsilon = 1e-10);
+
+        let put = pricer
+            .price(&model, &market(), &FourierOption::european_put(100.0, 1.0))
+            .unwrap();
+        assert_relative_eq!(
+            call - put,
+            100.0 * (1.0 - (-0.05_f64).exp()),
+            epsilon = 1e-10
+        );
+    }
+
+    #[test]
+    fn test_heston_reference_value() {
+        // Fang & Oosterlee (2008), Section 5.2
+        let model = HestonCf::new(0.0175, 1.5768, 0.0398, 0.5751, -0.5711);
+        let market = FourierMarket::new(100.0, 0.0, 0.0);
+        let call = CosPricer::default()
+            .price(&model, &market, &FourierOption::european_call(100.0, 1.0))
+            .unwrap();
+        assert_relative_eq!(call, 5.785_155_450, epsilon = 1e-7);
+    }
+
+    #[test]
+    fn test_heston_matches_adi() {
+        let (kappa, theta, xi, rho, v0) = (2.0, 0.04, 0.3, -0.7, 0.04);
+        let model = HestonCf::new(v0, kappa, theta, xi, rho);
+        let cos = CosPricer::default()
+            .price(&model, &market(), &FourierOption::european_call(100.0, 1.0))
+            .unwrap();
+
+        let pde = HestonPde::new(0.05, 0.0, kappa, theta, xi, rho)
+            .price(
+                &PdeOption::european_call(100.0, 1.0),
+                100.0,
+                v0,
+                &AdiConfig::default(),
+            )
+            .unwrap();
+        assert_relative_eq!(cos, pde.price, max_relative = 5e-3);
+    }
+
+    #[test]
+    fn test_merton_matches_series() {
+        let (sigma, lambda, m, v) = (0.15, 0.8, -0.1, 0.25);
+        let (s, k, r, t) = (100.0, 95.0, 0.03, 0.75);
+        let model = MertonCf::new(sigma, lambda, m, v);
+        let cos = CosPricer::default()
+            .price(
+                &model,
+                &FourierMarket::new(s, r, 0.0),
+                &FourierOption::european_call(k, t),
+            )
+            .unwrap();
+
+        // Merton (1976): Poisson mixture of Black-Scholes prices
+        let kbar = (m + 0.5 * v * v).exp() - 1.0;
+        let lambda_p = lambda * (1.0 + kbar);
+        let mut series = 0.0;
+        let mut weight = (-lambda_p * t).exp();
+        for n in 0..60 {
+            if n > 0 {
+                weight *= lambda_p * t / n as f64;
+            }
+            let vol = (sigma * sigma + n as f64 * v * v / t).sqrt();
+            let rate = r - lambda * kbar + n as f64 * (1.0 + kbar).ln() / t;
+            // Each term is a Black-Scholes price, checked against the
+            // closed form above
+            let term = CosPricer::default()
+                .price(
+                    &BlackScholesCf::new(vol),
+                    &FourierMarket::new(s, rate, 0.0),
+                    &FourierOption::european_call(k, t),
+                )
+                .unwrap();
+            series += weight * term;
+        }
+        assert_relative_eq!(cos, series, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_price_strikes_matches_single_prices() {
+        let model = BatesCf::new(HestonCf::new(0.04, 1.5, 0.05, 0.4, -0.6), 0.3, -0.05, 0.1);
+        let pricer = CosPricer::default();
+        let strikes = [60.0, 90.0, 100.0, 110.0, 160.0];
+        let batch = pricer
+            .price_strikes(&model, &market(), OptionType::Call, 0.5, &strikes)
+            .unwrap();
+        for (&strike, &price) in strikes.iter().zip(&batch) {
+            let single = pricer
+                .price(
+                    &model,
+                    &market(),
+                    &FourierOption::european_call(strike, 0.5),
+                )
+                .unwrap();
+            assert_eq!(price, single);
+        }
+        // Monotone decreasing in strike, bounded by intrinsic value
+        assert!(batch.windows(2).all(|w| w[0] > w[1]));
+        assert!(batch[0] >= 100.0 - 60.0 * (-0.025_f64).exp());
+    }
+
+    #[test]
+    fn test_price_surface_shape_and_errors() {
+        let model = BlackScholesCf::new(0.2);
+        let surface = CosPricer::default()
+            .price_surface(
+                &model,
+                &market(),
+                OptionType::Put,
+                &[0.25, 1.0],
+                &[90.0, 100.0, 110.0],
+            )
+            .unwrap();
+        assert_eq!(surface.len(), 2);
+        assert_eq!(surface[0].len(), 3);
+        // Longer maturity puts are worth more at the money here
+        assert!(surface[1][1] > surface[0][1]);
+
+        let err = CosPricer::new(1)
+            .price(&model, &market(), &FourierOption::european_put(100.0, 1.0))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            FourierError::InvalidParameter {
+                name: "n_terms",
+                ..
+            }
+        ));
+        assert!(CosPricer::default()
+            .price(&model, &market(), &FourierOption::european_put(-1.0, 1.0))
+            .is_err());
+    }
+}
@@ -0,0 +1,25 @@
+//! Error types for the Fourier pricers.
+
+use thiserror::Error;
+
+/// Errors from characteristic-function pricing.
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum FourierError {
+    /// Invalid model, market, instrument or quadrature parameter.
+    #[error("Invalid parameter '{name}': {value}")]
+    InvalidParameter {
+        /// Parameter name
+        name: &'static str,
+        /// Description of the invalid value
+        value: String,
+    },
+}
+
+/// Result type for Fourier pricing.
+pub type FourierResult<T> = Result<T, FourierError>;
+
+impl From<crate::validate::Invalid> for FourierError {
+    fn from((name, value): crate::validate::Invalid) -> Self {
+        FourierError::InvalidParameter { name, value }
+    }
+}
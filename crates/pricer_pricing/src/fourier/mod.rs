@@ -0,0 +1,140 @@
+//! Characteristic-function (Fourier) pricing engines.
+//!
+//! Models whose log-price has a closed-form characteristic function price
+//! European vanillas by a single quadrature, in microseconds, with no
+//! simulation noise. That makes them the engine of choice for repricing a
+//! vanilla surface inside a calibration loop, and an independent reference
+//! for Monte Carlo and PDE prices of the same model.
+//!
+//! # Models
+//!
+//! - [`BlackScholesCf`]: constant volatility (quadrature reference)
+//! - [`HestonCf`]: Heston stochastic volatility
+//! - [`MertonCf`]: Merton log-normal jump-diffusion
+//! - [`BatesCf`]: Heston with Merton jumps
+//! - [`BlackScholesHullWhiteCf`]: Black-Scholes equity with Hull-White
+//!   stochastic rates
+//!
+//! Further models plug in by implementing [`CharacteristicFunction`].
+//!
+//! # Pricers
+//!
+//! - [`CosPricer`]: Fang-Oosterlee COS expansion, exponentially convergent
+//!   in the number of terms; [`CosPricer::price_strikes`] reuses the
+//!   characteristic function across all strikes of a maturity
+//! - [`CarrMadanPricer`]: damped Fourier integral, used to cross-check COS
+//!
+//! # Example
+//!
+//! ```rust
+//! use pricer_pricing::analytical::OptionType;
+//! use pricer_pricing::fourier::{CosPricer, FourierMarket, HestonCf};
+//!
+//! let model = HestonCf::new(0.04, 2.0, 0.04, 0.3, -0.7);
+//! let market = FourierMarket::new(100.0, 0.05, 0.0);
+//!
+//! let strikes = [80.0, 90.0, 100.0, 110.0, 120.0];
+//! let calls = CosPricer::default()
+//!     .price_strikes(&model, &market, OptionType::Call, 1.0, &strikes)
+//!     .unwrap();
+//! assert!(calls.windows(2).all(|w| w[0] > w[1]));
+//! ```
+
+mod carr_madan;
+mod cos;
+mod error;
+mod models;
+
+pub use carr_madan::CarrMadanPricer;
+pub use cos::CosPricer;
+pub use error::{FourierError, FourierResult};
+pub use models::{
+    BatesCf, BlackScholesCf, BlackScholesHullWhiteCf, CharacteristicFunction, Cumulants, HestonCf,
+    MertonCf,
+};
+
+use crate::analytical::OptionType;
+use crate::validate::{finite, positive};
+
+/// Flat market for a single underlying.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FourierMarket {
+    /// Spot price
+    pub spot: f64,
+    /// Continuously compounded risk-free rate
+    pub rate: f64,
+    /// Continuous dividend yield
+    pub dividend: f64,
+}
+
+impl FourierMarket {
+    /// Creates market inputs.
+    #[inline]
+    pub fn new(spot: f64, rate: f64, dividend: f64) -> Self {
+        Self {
+            spot,
+            rate,
+            dividend,
+        }
+    }
+
+    /// Forward price to `maturity`.
+    #[inline]
+    pub fn forward(&self, maturity: f64) -> f64 {
+        self.spot * ((self.rate - self.dividend) * maturity).exp()
+    }
+
+    /// Discount factor to `maturity`.
+    #[inline]
+    pub fn discount_factor(&self, maturity: f64) -> f64 {
+        (-self.rate * maturity).exp()
+    }
+
+    fn validate(&self) -> FourierResult<()> {
+        positive("spot", self.spot)?;
+        finite("rate", self.rate)?;
+        finite("dividend", self.dividend)?;
+        Ok(())
+    }
+}
+
+/// European call or put.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FourierOption {
+    /// Strike price
+    pub strike: f64,
+    /// Time to maturity in years
+    pub maturity: f64,
+    /// Call or put
+    pub option_type: OptionType,
+}
+
+impl FourierOption {
+    /// Creates a European option.
+    #[inline]
+    pub fn new(strike: f64, maturity: f64, option_type: OptionType) -> Self {
+        Self {
+            strike,
+            maturity,
+            option_type,
+        }
+    }
+
+    /// European call.
+    #[inline]
+    pub fn european_call(strike: f64, maturity: f64) -> Self {
+        Self::new(strike, maturity, OptionType::Call)
+    }
+
+    /// European put.
+    #[inline]
+    pub fn european_put(strike: f64, maturity: f64) -> Self {
+        Self::new(strike, maturity, OptionType::Put)
+    }
+
+    fn validate(&self) -> FourierResult<()> {
+        positive("strike", self.strike)?;
+        positive("maturity", self.maturity)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,490 @@
+//! Characteristic functions of the supported models.
+//!
+//! Every model describes the forward-normalised log-price
+//! `X_t = ln(S_t / F_t)`, where `F_t` is the forward to `t` under flat
+//! rates. Because `F_t` carries the drift, the characteristic functions are
+//! free of rates and dividends and satisfy the martingale condition
+//! `φ(-i) = E[S_t / F_t] = 1`.
+
+use num_complex::Complex64;
+
+use super::error::FourierResult;
+use crate::validate::{correlation, finite, non_negative, positive};
+
+/// First, second and fourth cumulants of `X_t`, used to size the COS
+/// truncation range.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Cumulants {
+    /// Mean
+    pub c1: f64,
+    /// Variance
+    pub c2: f64,
+    /// Fourth cumulant (zero when not available in closed form)
+    pub c4: f64,
+}
+
+impl Cumulants {
+    fn add(self, other: Self) -> Self {
+        Self {
+            c1: self.c1 + other.c1,
+            c2: self.c2 + other.c2,
+            c4: self.c4 + other.c4,
+        }
+    }
+}
+
+/// A model with a closed-form characteristic function of the
+/// forward-normalised log-price.
+pub trait CharacteristicFunction {
+    /// Returns `ln E[exp(iuX_t)]` for complex `u`.
+    ///
+    /// Complex arguments are needed by the damped Carr-Madan integrand.
+    fn log_cf(&self, u: Complex64, t: f64) -> Complex64;
+
+    /// Returns the cumulants of `X_t`.
+    fn cumulants(&self, t: f64) -> Cumulants;
+
+    /// Checks the model parameters.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FourierError::InvalidParameter` for out-of-range parameters.
+    fn validate(&self) -> FourierResult<()>;
+
+    /// Returns `E[exp(iuX_t)]`.
+    #[inline]
+    fn cf(&self, u: Complex64, t: f64) -> Complex64 {
+        self.log_cf(u, t).exp()
+    }
+}
+
+/// Black-Scholes with constant volatility.
+///
+/// Mainly a reference for the quadrature: its Fourier prices must
+/// reproduce the closed form.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BlackScholesCf {
+    /// Volatility σ
+    pub sigma: f64,
+}
+
+impl BlackScholesCf {
+    /// Creates a Black-Scholes model.
+    #[inline]
+    pub fn new(sigma: f64) -> Self {
+        Self { sigma }
+    }
+}
+
+impl CharacteristicFunction for BlackScholesCf {
+    fn log_cf(&self, u: Complex64, t: f64) -> Complex64 {
+        gaussian_log_cf(u, self.sigma * self.sigma * t)
+    }
+
+    fn cumulants(&self, t: f64) -> Cumulants {
+        gaussian_cumulants(self.sigma * self.sigma * t)
+    }
+
+    fn validate(&self) -> FourierResult<()> {
+        positive("sigma", self.sigma)?;
+        Ok(())
+    }
+}
+
+/// Heston stochastic volatility:
+///
+/// ```text
+/// dS/S = (r - q) dt + √v dW₁
+/// dv   = κ(θ - v) dt + ξ√v dW₂,   d⟨W₁, W₂⟩ = ρ dt
+/// ```
+///
+/// The characteristic function uses the Albrecher et al. ("little trap")
+/// form, which stays on the principal branch of the complex logarithm for
+/// long maturities.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HestonCf {
+    /// Initial variance v₀
+    pub v0: f64,
+    /// Mean-reversion speed κ
+    pub kappa: f64,
+    /// Long-run variance θ
+    pub theta: f64,
+    /// Volatility of variance ξ
+    pub xi: f64,
+    /// Spot-variance correlation ρ
+    pub rho: f64,
+}
+
+impl HestonCf {
+    /// Creates a Heston model.
+    #[inline]
+    pub fn new(v0: f64, kappa: f64, theta: f64, xi: f64, rho: f64) -> Self {
+        Self {
+            v0,
+            kappa,
+            theta,
+            xi,
+            rho,
+        }
+    }
+}
+
+impl CharacteristicFunction for HestonCf {
+    fn log_cf(&self, u: Complex64, t: f64) -> Complex64 {
+        let Self {
+            v0,
+            kappa,
+            theta,
+            xi,
+            rho,
+        } = *self;
+        let iu = Complex64::i() * u;
+        let xi2 = xi * xi;
+
+        let beta = kappa - rho * xi * iu;
+        let d = (beta * beta + xi2 * (iu + u * u)).sqrt();
+        let g = (beta - d) / (beta + d);
+        let e = (-d * t).exp();
+
+        let c = kappa * theta / xi2 * ((beta - d) * t - 2.0 * ((1.0 - g * e) / (1.0 - g)).ln());
+        let dv = (beta - d) / xi2 * (1.0 - e) / (1.0 - g * e);
+        c + dv * v0
+    }
+
+    fn cumulants(&self, t: f64) -> Cumulants {
+        let Self {
+            v0,
+            kappa: k,
+            theta,
+            xi,
+            rho,
+        } = *self;
+        // ∫₀ᵗ E[v_s] e^{-nκ(t-s)} ds for n = 0, 1, 2
+        let e1 = (-k * t).exp();
+        let e2 = e1 * e1;
+        let k0 = theta * t + (v0 - theta) * (1.0 - e1) / k;
+        let k1 = theta * (1.0 - e1) / k + (v0 - theta) * e1 * t;
+        let k2 = theta * (1.0 - e2) / (2.0 * k) + (v0 - theta) * (e1 - e2) / k;
+
+        // X = -I/2 + ∫√v dW₁ with I = ∫v ds, so
+        // Var X = E[I] + Var(I)/4 - Cov(I, ∫√v dW₁)
+        let var_integrated = xi * xi / (k * k) * (k0 - 2.0 * k1 + k2);
+        let covariance = xi * rho / k * (k0 - k1);
+        Cumulants {
+            c1: -0.5 * k0,
+            c2: k0 + 0.25 * var_integrated - covariance,
+            c4: 0.0,
+        }
+    }
+
+    fn validate(&self) -> FourierResult<()> {
+        non_negative("v0", self.v0)?;
+        positive("kappa", self.kappa)?;
+        positive("theta", self.theta)?;
+        positive("xi", self.xi)?;
+        correlation("rho", self.rho)?;
+        Ok(())
+    }
+}
+
+/// Merton jump-diffusion: Black-Scholes with compound Poisson jumps whose
+/// log-sizes are normal.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MertonCf {
+    /// Diffusion volatility σ
+    pub sigma: f64,
+    /// Jump intensity λ (jumps per year)
+    pub lambda: f64,
+    /// Mean log jump size μⱼ
+    pub jump_mean: f64,
+    /// Log jump size volatility σⱼ
+    pub jump_vol: f64,
+}
+
+impl MertonCf {
+    /// Creates a Merton jump-diffusion model.
+    #[inline]
+    pub fn new(sigma: f64, lambda: f64, jump_mean: f64, jump_vol: f64) -> Self {
+        Self {
+            sigma,
+            lambda,
+            jump_mean,
+            jump_vol,
+        }
+    }
+
+    /// Returns the jump part on its own, shared with [`BatesCf`].
+    fn jumps(&self) -> Jumps {
+        Jumps {
+            lambda: self.lambda,
+            mean: self.jump_mean,
+            vol: self.jump_vol,
+        }
+    }
+}
+
+impl CharacteristicFunction for MertonCf {
+    fn log_cf(&self, u: Complex64, t: f64) -> Complex64 {
+        gaussian_log_cf(u, self.sigma * self.sigma * t) + self.jumps().log_cf(u, t)
+    }
+
+    fn cumulants(&self, t: f64) -> Cumulants {
+        gaussian_cumulants(self.sigma * self.sigma * t).add(self.jumps().cumulants(t))
+    }
+
+    fn validate(&self) -> FourierResult<()> {
+        positive("sigma", self.sigma)?;
+        self.jumps().validate()
+    }
+}
+
+/// Bates: Heston stochastic volatility with Merton log-normal jumps.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BatesCf {
+    /// Diffusive Heston part
+    pub heston: HestonCf,
+    /// Jump intensity λ (jumps per year)
+    pub lambda: f64,
+    /// Mean log jump size μⱼ
+    pub jump_mean: f64,
+    /// Log jump size volatility σⱼ
+    pub jump_vol: f64,
+}
+
+impl BatesCf {
+    /// Creates a Bates model from its Heston part and jump parameters.
+    #[inline]
+    pub fn new(heston: HestonCf, lambda: f64, jump_mean: f64, jump_vol: f64) -> Self {
+        Self {
+            heston,
+            lambda,
+            jump_mean,
+            jump_vol,
+        }
+    }
+
+    fn jumps(&self) -> Jumps {
+        Jumps {
+            lambda: self.lambda,
+            mean: self.jump_mean,
+            vol: self.jump_vol,
+        }
+    }
+}
+
+impl CharacteristicFunction for BatesCf {
+    fn log_cf(&self, u: Complex64, t: f64) -> Complex64 {
+        self.heston.log_cf(u, t) + self.jumps().log_cf(u, t)
+    }
+
+    fn cumulants(&self, t: f64) -> Cumulants {
+        self.heston.cumulants(t).add(self.jumps().cumulants(t))
+    }
+
+    fn validate(&self) -> FourierResult<()> {
+        self.heston.validate()?;
+        self.jumps().validate()
+    }
+}
+
+/// Black-Scholes equity with Hull-White stochastic rates.
+///
+/// ```text
+/// dS/S = r dt + σ dW₁
+/// dr   = (θ(t) - a r) dt + σᵣ dW₂,   d⟨W₁, W₂⟩ = ρ dt
+/// ```
+///
+/// Under the `T`-forward measure the forward `S/P(t, T)` is log-normal with
+/// total variance
+///
+/// ```text
+/// V(T) = σ²T + σᵣ²/a² (T - 2B + (1 - e^{-2aT}) / 2a) + 2ρσσᵣ/a (T - B),
+/// B    = (1 - e^{-aT}) / a
+/// ```
+///
+/// so the characteristic function is Gaussian. The pricers discount with
+/// the flat `rate`, i.e. `P(0, T) = e^{-rT}`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BlackScholesHullWhiteCf {
+    /// Equity volatility σ
+    pub sigma: f64,
+    /// Short-rate mean reversion a
+    pub mean_reversion: f64,
+    /// Short-rate volatility σᵣ
+    pub rate_vol: f64,
+    /// Equity-rate correlation ρ
+    pub rho: f64,
+}
+
+impl BlackScholesHullWhiteCf {
+    /// Creates a Black-Scholes-Hull-White model.
+    #[inline]
+    pub fn new(sigma: f64, mean_reversion: f64, rate_vol: f64, rho: f64) -> Self {
+        Self {
+            sigma,
+            mean_reversion,
+            rate_vol,
+            rho,
+        }
+    }
+
+    /// Returns the total variance `V(t)` of the log-forward.
+    pub fn total_variance(&self, t: f64) -> f64 {
+        let a = self.mean_reversion;
+        let b = (1.0 - (-a * t).exp()) / a;
+        let bond = self.rate_vol * self.rate_vol / (a * a)
+            * (t - 2.0 * b + (1.0 - (-2.0 * a * t).exp()) / (2.0 * a));
+        let cross = 2.0 * self.rho * self.sigma * self.rate_vol / a * (t - b);
+        self.sigma * self.sigma * t + bond + cross
+    }
+}
+
+impl CharacteristicFunction for BlackScholesHullWhiteCf {
+    fn log_cf(&self, u: Complex64, t: f64) -> Complex64 {
+        gaussian_log_cf(u, self.total_variance(t))
+    }
+
+    fn cumulants(&self, t: f64) -> Cumulants {
+        gaussian_cumulants(self.total_variance(t))
+    }
+
+    fn validate(&self) -> FourierResult<()> {
+        positive("sigma", self.sigma)?;
+        positive("mean_reversion", self.mean_reversion)?;
+        non_negative("rate_vol", self.rate_vol)?;
+        correlation("rho", self.rho)?;
+        Ok(())
+    }
+}
+
+/// Compound Poisson jumps with normal log-sizes, compensated so that
+/// `E[exp(J_t)] = 1`.
+#[derive(Clone, Copy, Debug)]
+struct Jumps {
+    lambda: f64,
+    mean: f64,
+    vol: f64,
+}
+
+impl Jumps {
+    /// Mean relative jump size `E[e^J] - 1`.
+    fn kappa(&self) -> f64 {
+        (self.mean + 0.5 * self.vol * self.vol).exp() - 1.0
+    }
+
+    fn log_cf(&self, u: Complex64, t: f64) -> Complex64 {
+        let iu = Complex64::i() * u;
+        let jump = (iu * self.mean - 0.5 * self.vol * self.vol * u * u).exp();
+        self.lambda * t * (jump - 1.0 - iu * self.kappa())
+    }
+
+    fn cumulants(&self, t: f64) -> Cumulants {
+        let (m, s2) = (self.mean, self.vol * self.vol);
+        let lt = self.lambda * t;
+        Cumulants {
+            c1: lt * (m - self.kappa()),
+            c2: lt * (m * m + s2),
+            c4: lt * (m.powi(4) + 6.0 * s2 * m * m + 3.0 * s2 * s2),
+        }
+    }
+
+    fn validate(&self) -> FourierResult<()> {
+        non_negative("lambda", self.lambda)?;
+        finite("jump_mean", self.mean)?;
+        non_negative("jump_vol", self.vol)?;
+        Ok(())
+    }
+}
+
+/// Log characteristic function of `N(-V/2, V)`.
+#[inline]
+fn gaussian_log_cf(u: Complex64, variance: f64) -> Complex64 {
+    let iu = Complex64::i() * u;
+    -0.5 * variance * (iu + u * u)
+}
+
+#[inline]
+fn gaussian_cumulants(variance: f64) -> Cumulants {
+    Cumulants {
+        c1: -0.5 * variance,
+        c2: variance,
+        c4: 0.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn heston() -> HestonCf {
+        HestonCf::new(0.0175, 1.5768, 0.0398, 0.5751, -0.5711)
+    }
+
+    #[test]
+    fn test_martingale_condition() {
+        let minus_i = Complex64::new(0.0, -1.0);
+        let models: [&dyn CharacteristicFunction; 5] = [
+            &BlackScholesCf::new(0.2),
+            &heston(),
+            &MertonCf::new(0.15, 0.5, -0.1, 0.2),
+            &BatesCf::new(heston(), 0.5, -0.1, 0.2),
+            &BlackScholesHullWhiteCf::new(0.2, 0.05, 0.01, 0.3),
+        ];
+        for model in models {
+            for t in [0.1, 1.0, 10.0] {
+                let phi = model.cf(minus_i, t);
+                assert_relative_eq!(phi.re, 1.0, epsilon = 1e-12);
+                assert!(phi.im.abs() < 1e-12);
+                // φ(0) = 1 trivially
+                assert_relative_eq!(model.cf(Complex64::new(0.0, 0.0), t).re, 1.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_heston_cumulants_match_cf_derivatives() {
+        // c1 = -i φ'(0), c2 = -(ln φ)''(0), by central differences
+        let model = heston();
+        let t = 2.0;
+        let h = 1e-4;
+        let f = |u: f64| model.log_cf(Complex64::new(u, 0.0), t);
+        let c1 = ((f(h) - f(-h)) / (2.0 * h)).im;
+        let c2 = -((f(h) - 2.0 * f(0.0) + f(-h)) / (h * h)).re;
+
+        let cumulants = model.cumulants(t);
+        assert_relative_eq!(cumulants.c1, c1, max_relative = 1e-6);
+        assert_relative_eq!(cumulants.c2, c2, max_relative = 1e-5);
+    }
+
+    #[test]
+    fn test_bates_without_jumps_is_heston() {
+        let bates = BatesCf::new(heston(), 0.0, -0.1, 0.2);
+        let u = Complex64::new(1.3, -0.4);
+        assert_eq!(bates.log_cf(u, 1.5), heston().log_cf(u, 1.5));
+        assert_eq!(bates.cumulants(1.5), heston().cumulants(1.5));
+    }
+
+    #[test]
+    fn test_hull_white_variance() {
+        let flat = BlackScholesHullWhiteCf::new(0.2, 0.1, 0.0, 0.5);
+        assert_relative_eq!(flat.total_variance(2.0), 0.08, max_relative = 1e-12);
+
+        // Rate volatility adds variance, negative correlation can offset it
+        let stochastic = BlackScholesHullWhiteCf::new(0.2, 0.1, 0.01, 0.0);
+        assert!(stochastic.total_variance(2.0) > 0.08);
+        let hedged = BlackScholesHullWhiteCf::new(0.2, 0.1, 0.01, -1.0);
+        assert!(hedged.total_variance(2.0) < 0.08);
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(heston().validate().is_ok());
+        assert!(HestonCf::new(0.04, 1.0, 0.04, 0.3, 1.5).validate().is_err());
+        assert!(MertonCf::new(0.2, -1.0, 0.0, 0.1).validate().is_err());
+        assert!(BlackScholesCf::new(0.0).validate().is_err());
+        assert!(BlackScholesHullWhiteCf::new(0.2, 0.0, 0.01, 0.0)
+            .validate()
+            .is_err());
+    }
+}
@@ -0,0 +1,433 @@
+//! # Risk-Factor Dependency Registry
+//!
+//! Maps booked trades to the market risk factors (curves, volatility
+//! surfaces, FX pairs) their valuation requires, and back again. Trades
+//! are registered at booking time; intraday and scenario engines then ask
+//! the registry for the minimal set of trades to revalue after a market
+//! perturbation instead of repricing the whole book.
+//!
+//! The registry is exported as a bipartite `ComputationGraph` with risk
+//! factors as input nodes and trades as output nodes, so it can be
+//! rendered alongside the AD computation graphs.
+//!
+//! # Example
+//!
+//! ```rust
+//! use pricer_pricing::graph::{DependencyRegistry, RiskFactor};
+//!
+//! let mut registry = DependencyRegistry::new();
+//! registry.book_trade("SWAP1", [RiskFactor::curve("USD-SOFR")]);
+//! registry.book_trade(
+//!     "FXO1",
+//!     [
+//!         RiskFactor::curve("USD-SOFR"),
+//!         RiskFactor::curve("EUR-ESTR"),
+//!         RiskFactor::fx_pair("EUR", "USD"),
+//!         RiskFactor::vol("EURUSD"),
+//!     ],
+//! );
+//!
+//! // An FX spot move only requires the option to be revalued
+//! let set = registry.revaluation_set(&[RiskFactor::fx_pair("USD", "EUR")]);
+//! assert_eq!(set, vec!["FXO1".to_string()]);
+//! ```
+
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+use super::error::GraphError;
+use super::extractor::GraphBuilder;
+use super::types::{ComputationGraph, GraphEdge, GraphNode, NodeGroup, NodeType};
+
+// =============================================================================
+// RiskFactor
+// =============================================================================
+
+/// A market risk factor a trade valuation depends on.
+///
+/// FX pairs are stored in a canonical order, so `EUR/USD` and `USD/EUR`
+/// denote the same factor.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", content = "name"))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum RiskFactor {
+    /// Yield curve (discount or forwarding), by curve name
+    Curve(String),
+    /// Volatility surface, by surface name
+    Vol(String),
+    /// FX spot rate, as `BASE/QUOTE` in canonical order
+    FxPair(String),
+}
+
+impl RiskFactor {
+    /// Create a yield curve factor.
+    pub fn curve(name: impl Into<String>) -> Self {
+        Self::Curve(name.into())
+    }
+
+    /// Create a volatility surface factor.
+    pub fn vol(name: impl Into<String>) -> Self {
+        Self::Vol(name.into())
+    }
+
+    /// Create an FX spot factor from two ISO currency codes.
+    ///
+    /// The pair is normalised so that the order of the arguments does
+    /// not matter.
+    pub fn fx_pair(ccy1: &str, ccy2: &str) -> Self {
+        let (a, b) = (ccy1.to_ascii_uppercase(), ccy2.to_ascii_uppercase());
+        if a <= b {
+            Self::FxPair(format!("{}/{}", a, b))
+        } else {
+            Self::FxPair(format!("{}/{}", b, a))
+        }
+    }
+
+    /// Graph node ID for this factor.
+    fn node_id(&self) -> String {
+        format!("rf:{}", self)
+    }
+}
+
+impl fmt::Display for RiskFactor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RiskFactor::Curve(name) => write!(f, "curve:{}", name),
+            RiskFactor::Vol(name) => write!(f, "vol:{}", name),
+            RiskFactor::FxPair(pair) => write!(f, "fx:{}", pair),
+        }
+    }
+}
+
+// =============================================================================
+// DependencyRegistry
+// =============================================================================
+
+/// Bidirectional index between trades and the risk factors they require.
+///
+/// # Complexity
+///
+/// Booking and removal are O(k) in the number of factors of the trade;
+/// a revaluation set query is O(Σ dependents) of the perturbed factors.
+#[derive(Debug, Clone, Default)]
+pub struct DependencyRegistry {
+    /// Trade ID -> required risk factors
+    trade_factors: HashMap<String, BTreeSet<RiskFactor>>,
+    /// Risk factor -> dependent trade IDs
+    factor_trades: HashMap<RiskFactor, BTreeSet<String>>,
+}
+
+impl DependencyRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a trade and its required risk factors.
+    ///
+    /// Re-booking an existing trade replaces its dependencies, e.g. after
+    /// an amendment.
+    pub fn book_trade<I>(&mut self, trade_id: &str, factors: I)
+    where
+        I: IntoIterator<Item = RiskFactor>,
+    {
+        self.remove_trade(trade_id);
+
+        let factors: BTreeSet<RiskFactor> = factors.into_iter().collect();
+        for factor in &factors {
+            self.factor_trades
+                .entry(factor.clone())
+                .or_default()
+                .insert(trade_id.to_string());
+        }
+        self.trade_factors.insert(trade_id.to_string(), factors);
+    }
+
+    /// Remove a trade, e.g. on maturity or cancellation.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the trade was registered.
+    pub fn remove_trade(&mut self, trade_id: &str) -> bool {
+        let Some(factors) = self.trade_factors.remove(trade_id) else {
+            return false;
+        };
+        for factor in factors {
+            if let Some(trades) = self.factor_trades.get_mut(&factor) {
+                trades.remove(trade_id);
+                if trades.is_empty() {
+                    self.factor_trades.remove(&factor);
+                }
+            }
+        }
+        true
+    }
+
+    /// Risk factors a trade depends on, in sorted order.
+    pub fn factors_for(&self, trade_id: &str) -> Option<Vec<&RiskFactor>> {
+        self.trade_factors
+            .get(trade_id)
+            .map(|factors| factors.iter().collect())
+    }
+
+    /// Trades depending on a risk factor, in sorted order.
+    pub fn dependents_of(&self, factor: &RiskFactor) -> Vec<&str> {
+        self.factor_trades
+            .get(factor)
+            .map(|trades| trades.iter().map(String::as_str).collect())
+            .unwrap_or_default()
+    }
+
+    /// Minimal set of trades to revalue after the given factors move.
+    ///
+    /// # Returns
+    ///
+    /// Sorted, de-duplicated trade IDs. Factors no trade depends on
+    /// contribute nothing.
+    pub fn revaluation_set<'a, I>(&self, perturbed: I) -> Vec<String>
+    where
+        I: IntoIterator<Item = &'a RiskFactor>,
+    {
+        let mut set: BTreeSet<&String> = BTreeSet::new();
+        for factor in perturbed {
+            if let Some(trades) = self.factor_trades.get(factor) {
+                set.extend(trades);
+            }
+        }
+        set.into_iter().cloned().collect()
+    }
+
+    /// All risk factors the book depends on, in sorted order.
+    pub fn risk_factors(&self) -> Vec<&RiskFactor> {
+        let mut factors: Vec<&RiskFactor> = self.factor_trades.keys().collect();
+        factors.sort();
+        factors
+    }
+
+    /// Check whether a trade is registered.
+    pub fn contains_trade(&self, trade_id: &str) -> bool {
+        self.trade_factors.contains_key(trade_id)
+    }
+
+    /// Number of registered trades.
+    pub fn len(&self) -> usize {
+        self.trade_factors.len()
+    }
+
+    /// Check whether the registry is empty.
+    pub fn is_empty(&self) -> bool {
+        self.trade_factors.is_empty()
+    }
+
+    /// Export dependencies as a computation graph.
+    ///
+    /// # Arguments
+    ///
+    /// * `trade_id` - Restrict to one trade and its factors, or `None` for
+    ///   the whole book
+    ///
+    /// # Returns
+    ///
+    /// - `Ok(ComputationGraph)` - Factor input nodes linked to trade output nodes
+    /// - `Err(GraphError::TradeNotFound)` - If the trade is not registered
+    pub fn dependency_graph(&self, trade_id: Option<&str>) -> Result<ComputationGraph, GraphError> {
+        match trade_id {
+            Some(id) => {
+                if !self.contains_trade(id) {
+                    return Err(GraphError::TradeNotFound(id.to_string()));
+                }
+                Ok(self.build_graph([id], Some(id.to_string())))
+            }
+            None => {
+                let mut trades: Vec<&str> = self.trade_factors.keys().map(String::as_str).collect();
+                trades.sort_unstable();
+                Ok(self.build_graph(trades, None))
+            }
+        }
+    }
+
+    /// Export the subgraph of trades affected by a market perturbation.
+    ///
+    /// Contains the revaluation set and all factors those trades depend
+    /// on; the perturbed factors are flagged as sensitivity targets.
+    pub fn revaluation_graph(&self, perturbed: &[RiskFactor]) -> ComputationGraph {
+        let trades = self.revaluation_set(perturbed);
+        let mut graph = self.build_graph(trades.iter().map(String::as_str), None);
+        for factor in perturbed {
+            let id = factor.node_id();
+            if let Some(node) = graph.nodes.iter_mut().find(|n| n.id == id) {
+                node.is_sensitivity_target = true;
+                node.group = NodeGroup::Sensitivity;
+            }
+        }
+        graph
+    }
+
+    /// Build a bipartite factor -> trade graph for the given trades.
+    fn build_graph<'a, I>(&self, trades: I, trade_id: Option<String>) -> ComputationGraph
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let mut builder = GraphBuilder::new();
+        for trade in trades {
+            let Some(factors) = self.trade_factors.get(trade) else {
+                continue;
+            };
+            let trade_node = format!("trade:{}", trade);
+            builder.add_node(GraphNode {
+                id: trade_node.clone(),
+                node_type: NodeType::Output,
+                label: trade.to_string(),
+                value: None,
+                is_sensitivity_target: false,
+                group: NodeGroup::Output,
+            });
+            for factor in factors {
+                let factor_node = factor.node_id();
+                if !builder.has_node(&factor_node) {
+                    builder.add_node(GraphNode {
+                        id: factor_node.clone(),
+                        node_type: NodeType::Input,
+                        label: factor.to_string(),
+                        value: None,
+                        is_sensitivity_target: false,
+                        group: NodeGroup::Input,
+                    });
+                }
+                builder.add_edge(GraphEdge {
+                    source: factor_node,
+                    target: trade_node.clone(),
+                    weight: None,
+                });
+            }
+        }
+        // Every edge runs factor -> trade
+        let depth = if builder.edge_count() > 0 {
+            2
+        } else {
+            usize::from(builder.node_count() > 0)
+        };
+        builder.build_with_depth(trade_id, depth)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn book() -> DependencyRegistry {
+        let mut registry = DependencyRegistry::new();
+        registry.book_trade("IRS1", [RiskFactor::curve("USD-SOFR")]);
+        registry.book_trade(
+            "IRS2",
+            [RiskFactor::curve("USD-SOFR"), RiskFactor::curve("USD-OIS")],
+        );
+        registry.book_trade(
+            "FXO1",
+            [
+                RiskFactor::curve("USD-OIS"),
+                RiskFactor::curve("EUR-ESTR"),
+                RiskFactor::fx_pair("EUR", "USD"),
+                RiskFactor::vol("EURUSD"),
+            ],
+        );
+        registry
+    }
+
+    #[test]
+    fn test_fx_pair_is_canonical() {
+        assert_eq!(
+            RiskFactor::fx_pair("usd", "EUR"),
+            RiskFactor::fx_pair("EUR", "USD")
+        );
+        assert_eq!(RiskFactor::fx_pair("EUR", "USD").to_string(), "fx:EUR/USD");
+    }
+
+    #[test]
+    fn test_revaluation_set_is_minimal() {
+        let registry = book();
+
+        assert_eq!(
+            registry.revaluation_set(&[RiskFactor::curve("USD-SOFR")]),
+            vec!["IRS1", "IRS2"]
+        );
+        assert_eq!(
+            registry.revaluation_set(&[RiskFactor::vol("EURUSD"), RiskFactor::curve("USD-OIS")]),
+            vec!["FXO1", "IRS2"]
+        );
+        assert!(registry
+            .revaluation_set(&[RiskFactor::curve("JPY-TONA")])
+            .is_empty());
+        assert_eq!(registry.risk_factors().len(), 5);
+    }
+
+    #[test]
+    fn test_rebooking_and_removal() {
+        let mut registry = book();
+
+        // Amendment drops the SOFR dependency
+        registry.book_trade("IRS2", [RiskFactor::curve("USD-OIS")]);
+        assert_eq!(
+            registry.dependents_of(&RiskFactor::curve("USD-SOFR")),
+            vec!["IRS1"]
+        );
+
+        assert!(registry.remove_trade("IRS1"));
+        assert!(!registry.remove_trade("IRS1"));
+        assert!(registry
+            .dependents_of(&RiskFactor::curve("USD-SOFR"))
+            .is_empty());
+        assert!(!registry
+            .risk_factors()
+            .contains(&&RiskFactor::curve("USD-SOFR")));
+        assert_eq!(registry.len(), 2);
+        assert_eq!(
+            registry.factors_for("IRS2").unwrap(),
+            vec![&RiskFactor::curve("USD-OIS")]
+        );
+    }
+
+    #[test]
+    fn test_dependency_graph_export() {
+        let registry = book();
+
+        let graph = registry.dependency_graph(None).unwrap();
+        assert_eq!(graph.metadata.node_count, 3 + 5);
+        assert_eq!(graph.metadata.edge_count, 1 + 2 + 4);
+        assert_eq!(graph.metadata.depth, 2);
+        assert!(graph.find_path("rf:curve:USD-OIS", "trade:FXO1").is_some());
+
+        let single = registry.dependency_graph(Some("IRS1")).unwrap();
+        assert_eq!(single.nodes.len(), 2);
+        assert_eq!(single.metadata.trade_id.as_deref(), Some("IRS1"));
+
+        assert!(matches!(
+            registry.dependency_graph(Some("NOPE")),
+            Err(GraphError::TradeNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_revaluation_graph_flags_perturbed_factors() {
+        let registry = book();
+        let graph = registry.revaluation_graph(&[RiskFactor::curve("USD-SOFR")]);
+
+        let trades: Vec<&str> = graph
+            .nodes
+            .iter()
+            .filter(|n| n.node_type == NodeType::Output)
+            .map(|n| n.label.as_str())
+            .collect();
+        assert_eq!(trades, vec!["IRS1", "IRS2"]);
+
+        let sofr = graph.find_node("rf:curve:USD-SOFR").unwrap();
+        assert!(sofr.is_sensitivity_target);
+        assert_eq!(sofr.group, NodeGroup::Sensitivity);
+        let ois = graph.find_node("rf:curve:USD-OIS").unwrap();
+        assert!(!ois.is_sensitivity_target);
+    }
+}
@@ -8,6 +8,7 @@
 //! - `types`: Core data structures (GraphNode, GraphEdge, ComputationGraph)
 //! - `error`: Error types for graph operations
 //! - `extractor`: Graph extraction trait and implementations
+//! - `dependency`: Trade -> risk factor registry for selective revaluation
 //!
 //! ## D3.js Compatibility
 //!
@@ -31,10 +32,12 @@
 //! assert!(graph.nodes.len() > 0);
 //! ```
 
+mod dependency;
 mod error;
 mod extractor;
 mod types;
 
+pub use dependency::{DependencyRegistry, RiskFactor};
 pub use error::GraphError;
 pub use extractor::{GraphBuilder, GraphExtractable, SimpleGraphExtractor};
 pub use types::{
@@ -1,7 +1,8 @@
 //! Greeks calculation configuration.
 //!
 //! Provides [`GreeksConfig`] for configuring bump widths and calculation modes,
-//! and [`GreeksMode`] for selecting between different calculation methods.
+//! [`GreeksMode`] for selecting between different calculation methods, and
+//! [`GreeksEstimator`] for choosing the Monte Carlo sensitivity estimator.
 
 /// Calculation mode for Greeks computation.
 ///
@@ -13,6 +14,7 @@
 /// * `NumDual` - Forward-mode AD using num-dual library
 /// * `EnzymeAAD` - Enzyme LLVM-level AAD (requires `enzyme-ad` feature)
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GreeksMode {
     /// Bump-and-revalue using finite differences.
     ///
@@ -38,6 +40,32 @@ pub enum GreeksMode {
     EnzymeAAD,
 }
 
+/// Monte Carlo estimator for Greeks.
+///
+/// Pathwise differentiation is unbiased and low-variance for payoffs that
+/// are Lipschitz in the terminal price (vanillas); it is biased for
+/// discontinuous payoffs, whose derivative vanishes almost surely. The
+/// likelihood-ratio method differentiates the transition density instead,
+/// so it handles digitals and barriers at the cost of higher variance.
+///
+/// # Variants
+///
+/// * `Auto` - Pathwise for smooth payoffs, likelihood ratio otherwise
+/// * `Pathwise` - Pathwise derivative (smooth payoffs only)
+/// * `LikelihoodRatio` - Likelihood-ratio (score function) method
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+pub enum GreeksEstimator {
+    /// Select per payoff: pathwise when smooth, likelihood ratio otherwise.
+    #[default]
+    Auto,
+
+    /// Pathwise derivative estimator.
+    Pathwise,
+
+    /// Likelihood-ratio (score function) estimator.
+    LikelihoodRatio,
+}
+
 /// Configuration for Greeks calculation.
 ///
 /// Controls bump widths for finite differences and verification tolerances.
@@ -52,6 +80,7 @@ pub enum GreeksMode {
 /// | `time_bump_years` | 1/252 | Time bump in years (1 trading day) |
 /// | `rate_bump_absolute` | 0.01 | Absolute bump for interest rate |
 /// | `verification_tolerance` | 1e-6 | Tolerance for mode comparison |
+/// | `estimator` | `Auto` | Monte Carlo estimator (pathwise / likelihood ratio) |
 ///
 /// # Examples
 ///
@@ -90,6 +119,9 @@ pub struct GreeksConfig {
 
     /// Tolerance for verification between calculation modes (default: 1e-6).
     pub verification_tolerance: f64,
+
+    /// Monte Carlo Greeks estimator (default: `Auto`).
+    pub estimator: GreeksEstimator,
 }
 
 impl Default for GreeksConfig {
@@ -101,6 +133,7 @@ impl Default for GreeksConfig {
             time_bump_years: 1.0 / 252.0,
             rate_bump_absolute: 0.01,
             verification_tolerance: 1e-6,
+            estimator: GreeksEstimator::default(),
         }
     }
 }
@@ -213,6 +246,7 @@ pub struct GreeksConfigBuilder {
     time_bump_years: Option<f64>,
     rate_bump_absolute: Option<f64>,
     verification_tolerance: Option<f64>,
+    estimator: Option<GreeksEstimator>,
 }
 
 impl GreeksConfigBuilder {
@@ -252,6 +286,12 @@ impl GreeksConfigBuilder {
         self
     }
 
+    /// Sets the Monte Carlo Greeks estimator (default: `Auto`).
+    pub fn estimator(mut self, estimator: GreeksEstimator) -> Self {
+        self.estimator = Some(estimator);
+        self
+    }
+
     /// Builds the configuration, validating all parameters.
     ///
     /// # Errors
@@ -265,6 +305,7 @@ impl GreeksConfigBuilder {
             time_bump_years: self.time_bump_years.unwrap_or(1.0 / 252.0),
             rate_bump_absolute: self.rate_bump_absolute.unwrap_or(0.01),
             verification_tolerance: self.verification_tolerance.unwrap_or(1e-6),
+            estimator: self.estimator.unwrap_or_default(),
         };
 
         config.validate()?;
@@ -4,12 +4,16 @@
 //! - [`GreeksResult<T>`]: Generic result type for Greeks calculations (AD-compatible)
 //! - [`GreeksConfig`]: Configuration for bump widths and calculation modes
 //! - [`GreeksMode`]: Calculation mode selection (Bump-and-Revalue, AAD, num-dual)
+//! - [`GreeksEstimator`]: Monte Carlo estimator selection (pathwise, likelihood ratio)
+//! - [`StructuredGreeks`]: Per-risk-factor sensitivities with units, currency and bump spec
 
 mod config;
 mod result;
+mod schema;
 
-pub use config::{GreeksConfig, GreeksConfigBuilder, GreeksMode};
+pub use config::{GreeksConfig, GreeksConfigBuilder, GreeksEstimator, GreeksMode};
 pub use result::GreeksResult;
+pub use schema::{BumpSpec, GreekUnit, Sensitivity, StructuredGreeks};
 
 #[cfg(test)]
 mod tests;
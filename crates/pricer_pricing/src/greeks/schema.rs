@@ -0,0 +1,347 @@
+//! Structured Greeks schema with explicit units and bump specifications.
+//!
+//! [`GreeksResult<T>`] carries raw derivatives whose conventions are implicit:
+//! vega is per unit of volatility, rho per unit of rate, theta per year.
+//! [`StructuredGreeks`] makes those conventions explicit so that every
+//! consumer (server, CLI, reports) serialises risk the same way:
+//!
+//! - each [`Sensitivity`] names its risk factor and optional bucket,
+//! - its value is quoted in a market [`GreekUnit`] (per 1bp, per 1% vol,
+//!   per 1 unit spot, per day),
+//! - the [`BumpSpec`] records how the number was produced, and
+//! - the whole result carries the currency of measure.
+
+use std::fmt;
+
+use num_traits::Float;
+
+use super::{GreeksConfig, GreeksMode, GreeksResult};
+use crate::mc::Greek;
+
+/// One volatility point (1%) in absolute volatility units.
+const VOL_POINT: f64 = 0.01;
+
+/// One basis point in absolute rate units.
+const BASIS_POINT: f64 = 1e-4;
+
+/// Calendar days per year used to quote theta per day.
+const DAYS_PER_YEAR: f64 = 365.0;
+
+/// Market quoting unit of a sensitivity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum GreekUnit {
+    /// Value change per 1 unit move in spot.
+    PerUnitSpot,
+    /// Delta change per 1 unit move in spot.
+    PerUnitSpotSquared,
+    /// Value change per 1% absolute move in volatility.
+    PerVolPoint,
+    /// Vega change per 1% absolute move in volatility.
+    PerVolPointSquared,
+    /// Delta change per 1% absolute move in volatility.
+    PerUnitSpotPerVolPoint,
+    /// Value change per 1bp parallel move in rates.
+    PerBasisPoint,
+    /// Value change over one calendar day.
+    PerDay,
+}
+
+impl GreekUnit {
+    /// Factor converting a raw derivative into this unit.
+    ///
+    /// Raw derivatives are taken with respect to spot, absolute volatility,
+    /// absolute rate and time in years.
+    #[inline]
+    pub fn scale(self) -> f64 {
+        match self {
+            GreekUnit::PerUnitSpot | GreekUnit::PerUnitSpotSquared => 1.0,
+            GreekUnit::PerVolPoint | GreekUnit::PerUnitSpotPerVolPoint => VOL_POINT,
+            GreekUnit::PerVolPointSquared => VOL_POINT * VOL_POINT,
+            GreekUnit::PerBasisPoint => BASIS_POINT,
+            GreekUnit::PerDay => 1.0 / DAYS_PER_YEAR,
+        }
+    }
+
+    /// Short human-readable label.
+    pub fn label(self) -> &'static str {
+        match self {
+            GreekUnit::PerUnitSpot => "per 1 spot",
+            GreekUnit::PerUnitSpotSquared => "per 1 spot^2",
+            GreekUnit::PerVolPoint => "per 1% vol",
+            GreekUnit::PerVolPointSquared => "per 1% vol^2",
+            GreekUnit::PerUnitSpotPerVolPoint => "per 1 spot per 1% vol",
+            GreekUnit::PerBasisPoint => "per 1bp",
+            GreekUnit::PerDay => "per day",
+        }
+    }
+}
+
+impl fmt::Display for GreekUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+impl Greek {
+    /// Lower-case identifier used in serialised output.
+    pub fn name(self) -> &'static str {
+        match self {
+            Greek::Delta => "delta",
+            Greek::Vega => "vega",
+            Greek::Theta => "theta",
+            Greek::Rho => "rho",
+            Greek::Gamma => "gamma",
+            Greek::Vanna => "vanna",
+            Greek::Volga => "volga",
+        }
+    }
+
+    /// Market quoting unit of this Greek.
+    pub fn unit(self) -> GreekUnit {
+        match self {
+            Greek::Delta => GreekUnit::PerUnitSpot,
+            Greek::Gamma => GreekUnit::PerUnitSpotSquared,
+            Greek::Vega => GreekUnit::PerVolPoint,
+            Greek::Volga => GreekUnit::PerVolPointSquared,
+            Greek::Vanna => GreekUnit::PerUnitSpotPerVolPoint,
+            Greek::Rho => GreekUnit::PerBasisPoint,
+            Greek::Theta => GreekUnit::PerDay,
+        }
+    }
+
+    /// Risk factor the Greek is taken against when no finer label is known.
+    pub fn risk_factor(self) -> &'static str {
+        match self {
+            Greek::Delta | Greek::Gamma => "spot",
+            Greek::Vega | Greek::Volga => "vol",
+            Greek::Vanna => "spot/vol",
+            Greek::Rho => "rate",
+            Greek::Theta => "time",
+        }
+    }
+}
+
+impl fmt::Display for Greek {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// How a sensitivity was produced.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "method", rename_all = "snake_case"))]
+pub enum BumpSpec {
+    /// Closed-form derivative.
+    Analytic,
+    /// Automatic differentiation (forward or adjoint).
+    AlgorithmicDifferentiation,
+    /// Central finite difference with a bump relative to the factor level.
+    Relative {
+        /// Bump as a fraction of the factor level.
+        size: f64,
+    },
+    /// Central finite difference with an absolute bump.
+    Absolute {
+        /// Bump in the factor's own units.
+        size: f64,
+    },
+}
+
+impl BumpSpec {
+    /// Bump specification implied by `config` for `greek`.
+    ///
+    /// Spot Greeks use the relative spot bump, volatility Greeks the
+    /// absolute vol bump, theta the time bump and rho the rate bump.
+    pub fn from_config(greek: Greek, config: &GreeksConfig) -> Self {
+        if config.mode != GreeksMode::BumpRevalue {
+            return BumpSpec::AlgorithmicDifferentiation;
+        }
+        match greek {
+            Greek::Delta | Greek::Gamma | Greek::Vanna => BumpSpec::Relative {
+                size: config.spot_bump_relative,
+            },
+            Greek::Vega | Greek::Volga => BumpSpec::Absolute {
+                size: config.vol_bump_absolute,
+            },
+            Greek::Theta => BumpSpec::Absolute {
+                size: config.time_bump_years,
+            },
+            Greek::Rho => BumpSpec::Absolute {
+                size: config.rate_bump_absolute,
+            },
+        }
+    }
+}
+
+impl fmt::Display for BumpSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BumpSpec::Analytic => write!(f, "analytic"),
+            BumpSpec::AlgorithmicDifferentiation => write!(f, "ad"),
+            BumpSpec::Relative { size } => write!(f, "relative {}", size),
+            BumpSpec::Absolute { size } => write!(f, "absolute {}", size),
+        }
+    }
+}
+
+/// A single sensitivity against one risk factor.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sensitivity {
+    /// Which Greek this is.
+    pub greek: Greek,
+    /// Risk factor the sensitivity is taken against (e.g. `"spot"`, `"USD.rate"`).
+    pub risk_factor: String,
+    /// Optional bucket within the risk factor (tenor pillar, expiry, strike).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub bucket: Option<String>,
+    /// Sensitivity quoted in `unit`.
+    pub value: f64,
+    /// Quoting unit of `value`.
+    pub unit: GreekUnit,
+    /// How the sensitivity was produced.
+    pub bump: BumpSpec,
+}
+
+impl Sensitivity {
+    /// Creates a sensitivity whose value is already quoted in the Greek's unit.
+    pub fn new(greek: Greek, risk_factor: impl Into<String>, value: f64, bump: BumpSpec) -> Self {
+        Self {
+            greek,
+            risk_factor: risk_factor.into(),
+            bucket: None,
+            value,
+            unit: greek.unit(),
+            bump,
+        }
+    }
+
+    /// Creates a sensitivity from a raw derivative, converting it to the
+    /// Greek's quoting unit.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pricer_pricing::greeks::{BumpSpec, GreekUnit, Sensitivity};
+    /// use pricer_pricing::mc::Greek;
+    ///
+    /// // ∂V/∂σ = 40 per unit vol is 0.40 per vol point
+    /// let vega = Sensitivity::from_raw(Greek::Vega, "vol", 40.0, BumpSpec::Analytic);
+    /// assert_eq!(vega.unit, GreekUnit::PerVolPoint);
+    /// assert!((vega.value - 0.40).abs() < 1e-12);
+    /// ```
+    pub fn from_raw(
+        greek: Greek,
+        risk_factor: impl Into<String>,
+        raw: f64,
+        bump: BumpSpec,
+    ) -> Self {
+        Self::new(greek, risk_factor, raw * greek.unit().scale(), bump)
+    }
+
+    /// Sets the bucket and returns self for method chaining.
+    pub fn with_bucket(mut self, bucket: impl Into<String>) -> Self {
+        self.bucket = Some(bucket.into());
+        self
+    }
+}
+
+/// Price and sensitivities with explicit currency, units and bumps.
+///
+/// # Examples
+///
+/// ```rust
+/// use pricer_pricing::greeks::{GreeksConfig, GreeksResult};
+/// use pricer_pricing::mc::Greek;
+///
+/// let raw = GreeksResult::new(10.45, 0.0).with_delta(0.64).with_rho(53.2);
+/// let structured = raw.to_structured(&GreeksConfig::default(), "USD");
+///
+/// assert_eq!(structured.currency, "USD");
+/// assert!((structured.value(Greek::Rho).unwrap() - 0.00532).abs() < 1e-12);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StructuredGreeks {
+    /// Present value in `currency`.
+    pub price: f64,
+    /// Standard error of the price estimate (zero when exact).
+    pub std_error: f64,
+    /// ISO 4217 code of the currency all values are measured in.
+    pub currency: String,
+    /// Sensitivities, one per Greek, risk factor and bucket.
+    pub sensitivities: Vec<Sensitivity>,
+}
+
+impl StructuredGreeks {
+    /// Creates a result with no sensitivities.
+    pub fn new(price: f64, std_error: f64, currency: impl Into<String>) -> Self {
+        Self {
+            price,
+            std_error,
+            currency: currency.into(),
+            sensitivities: Vec::new(),
+        }
+    }
+
+    /// Appends a sensitivity and returns self for method chaining.
+    pub fn with_sensitivity(mut self, sensitivity: Sensitivity) -> Self {
+        self.sensitivities.push(sensitivity);
+        self
+    }
+
+    /// Sensitivities for `greek`, in insertion order.
+    pub fn get(&self, greek: Greek) -> impl Iterator<Item = &Sensitivity> {
+        self.sensitivities.iter().filter(move |s| s.greek == greek)
+    }
+
+    /// Total of `greek` across risk factors and buckets, or `None` if absent.
+    pub fn value(&self, greek: Greek) -> Option<f64> {
+        self.get(greek)
+            .map(|s| s.value)
+            .fold(None, |acc, v| Some(acc.unwrap_or(0.0) + v))
+    }
+}
+
+impl<T: Float> GreeksResult<T> {
+    /// Converts to a [`StructuredGreeks`] quoted in market units.
+    ///
+    /// Each computed Greek becomes one [`Sensitivity`] against the Greek's
+    /// default risk factor, with the bump taken from `config`.
+    pub fn to_structured(
+        &self,
+        config: &GreeksConfig,
+        currency: impl Into<String>,
+    ) -> StructuredGreeks {
+        let to_f64 = |v: T| v.to_f64().unwrap_or(f64::NAN);
+        let raw = [
+            (Greek::Delta, self.delta),
+            (Greek::Gamma, self.gamma),
+            (Greek::Vega, self.vega),
+            (Greek::Theta, self.theta),
+            (Greek::Rho, self.rho),
+            (Greek::Vanna, self.vanna),
+            (Greek::Volga, self.volga),
+        ];
+
+        let mut result =
+            StructuredGreeks::new(to_f64(self.price), to_f64(self.std_error), currency);
+        for (greek, value) in raw {
+            if let Some(value) = value {
+                result.sensitivities.push(Sensitivity::from_raw(
+                    greek,
+                    greek.risk_factor(),
+                    to_f64(value),
+                    BumpSpec::from_config(greek, config),
+                ));
+            }
+        }
+        result
+    }
+}
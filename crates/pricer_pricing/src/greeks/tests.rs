@@ -162,6 +162,17 @@ mod greeks_config_tests {
         assert_relative_eq!(config.rate_bump_absolute, 0.01, epsilon = 1e-10);
         assert_relative_eq!(config.verification_tolerance, 1e-6, epsilon = 1e-15);
         assert_eq!(config.mode, GreeksMode::BumpRevalue);
+        assert_eq!(config.estimator, GreeksEstimator::Auto);
+    }
+
+    #[test]
+    fn test_greeks_config_builder_estimator() {
+        let config = GreeksConfig::builder()
+            .estimator(GreeksEstimator::LikelihoodRatio)
+            .build()
+            .unwrap();
+
+        assert_eq!(config.estimator, GreeksEstimator::LikelihoodRatio);
     }
 
     #[test]
@@ -362,3 +373,101 @@ mod greek_enum_tests {
         assert_eq!(set.len(), 3);
     }
 }
+
+// =============================================================================
+// Structured Greeks schema
+// =============================================================================
+
+mod structured_greeks_tests {
+    use super::*;
+    use crate::mc::Greek;
+
+    #[test]
+    fn test_units_scale_raw_derivatives() {
+        assert_eq!(Greek::Delta.unit(), GreekUnit::PerUnitSpot);
+        assert_eq!(Greek::Vega.unit(), GreekUnit::PerVolPoint);
+        assert_eq!(Greek::Rho.unit(), GreekUnit::PerBasisPoint);
+        assert_eq!(Greek::Theta.unit(), GreekUnit::PerDay);
+
+        assert_relative_eq!(GreekUnit::PerVolPoint.scale(), 0.01);
+        assert_relative_eq!(GreekUnit::PerVolPointSquared.scale(), 1e-4);
+        assert_relative_eq!(GreekUnit::PerBasisPoint.scale(), 1e-4);
+        assert_relative_eq!(GreekUnit::PerDay.scale(), 1.0 / 365.0);
+        assert_eq!(GreekUnit::PerBasisPoint.to_string(), "per 1bp");
+    }
+
+    #[test]
+    fn test_bump_spec_from_config() {
+        let config = GreeksConfig::default();
+        assert_eq!(
+            BumpSpec::from_config(Greek::Delta, &config),
+            BumpSpec::Relative { size: 0.01 }
+        );
+        assert_eq!(
+            BumpSpec::from_config(Greek::Rho, &config),
+            BumpSpec::Absolute { size: 0.01 }
+        );
+
+        let ad = GreeksConfig::builder()
+            .mode(GreeksMode::NumDual)
+            .build()
+            .unwrap();
+        assert_eq!(
+            BumpSpec::from_config(Greek::Vega, &ad),
+            BumpSpec::AlgorithmicDifferentiation
+        );
+    }
+
+    #[test]
+    fn test_to_structured_converts_units() {
+        let raw = GreeksResult::new(10.45, 0.02)
+            .with_delta(0.64)
+            .with_vega(37.5)
+            .with_theta(-6.4)
+            .with_rho(53.2);
+        let structured = raw.to_structured(&GreeksConfig::default(), "EUR");
+
+        assert_eq!(structured.currency, "EUR");
+        assert_eq!(structured.sensitivities.len(), 4);
+        assert_relative_eq!(structured.value(Greek::Delta).unwrap(), 0.64);
+        assert_relative_eq!(structured.value(Greek::Vega).unwrap(), 0.375);
+        assert_relative_eq!(structured.value(Greek::Theta).unwrap(), -6.4 / 365.0);
+        assert_relative_eq!(structured.value(Greek::Rho).unwrap(), 0.00532);
+        assert!(structured.value(Greek::Gamma).is_none());
+
+        let vega = structured.get(Greek::Vega).next().unwrap();
+        assert_eq!(vega.risk_factor, "vol");
+        assert_eq!(vega.bump, BumpSpec::Absolute { size: 0.01 });
+    }
+
+    #[test]
+    fn test_bucketed_sensitivities_aggregate() {
+        let structured = StructuredGreeks::new(0.0, 0.0, "USD")
+            .with_sensitivity(
+                Sensitivity::new(Greek::Rho, "USD.rate", 1.5, BumpSpec::Analytic).with_bucket("1Y"),
+            )
+            .with_sensitivity(
+                Sensitivity::new(Greek::Rho, "USD.rate", -0.5, BumpSpec::Analytic)
+                    .with_bucket("5Y"),
+            );
+
+        assert_eq!(structured.get(Greek::Rho).count(), 2);
+        assert_relative_eq!(structured.value(Greek::Rho).unwrap(), 1.0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_structured_greeks_serde_roundtrip() {
+        let structured = GreeksResult::new(1.0, 0.0)
+            .with_rho(100.0)
+            .to_structured(&GreeksConfig::default(), "USD");
+
+        let json = serde_json::to_string(&structured).unwrap();
+        assert!(json.contains("\"unit\":\"per_basis_point\""));
+        assert!(json.contains("\"method\":\"absolute\""));
+        assert!(!json.contains("bucket"));
+
+        let back: StructuredGreeks = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, structured);
+    }
+}
@@ -79,12 +79,16 @@
 //! # Cargo.toml
 //! pricer_kernel = { package = "pricer_pricing", version = "0.7" }
 //! ```
+//!
+//! New code should depend on the `neutryx` facade crate, whose `pricing`
+//! module re-exports the blessed subset of this crate.
 
 // Enzyme AD: Enable autodiff feature when enzyme-ad feature is active
 // This requires nightly Rust (nightly-2025-01-15) with Enzyme LLVM plugin
 // Requirement 1.1: #![feature(autodiff)] を有効化する仕組み
 // Requirement 1.2: enzyme-ad feature が無効時は stable Rust でコンパイル可能
 #![cfg_attr(feature = "enzyme-ad", feature(autodiff))]
+#![cfg_attr(feature = "simd", feature(portable_simd))]
 #![deny(missing_docs)]
 #![deny(rustdoc::broken_intra_doc_links)]
 #![deny(rustdoc::private_intra_doc_links)]
@@ -123,6 +127,33 @@ pub mod checkpoint;
 // Phase 4: Analytical solutions for verification
 pub mod analytical;
 
+// Parameter checks shared by the PDE, tree and Fourier engines
+pub(crate) mod validate;
+
+// Finite-difference PDE engines
+pub mod pde;
+
+// Binomial, trinomial and short-rate tree engines
+pub mod tree;
+
+// Characteristic-function (COS, Carr-Madan) engines
+pub mod fourier;
+
+// Engine selection for routing instruments
+pub mod engine;
+
+// Per-instrument engine routing over pricer_models instruments
+#[cfg(feature = "l1l2-integration")]
+pub mod router;
+
+// Cross-engine validation of prices and Greeks against a reference engine
+#[cfg(feature = "l1l2-integration")]
+pub mod validation;
+
+// Per-instrument payoff smoothing width selection
+#[cfg(feature = "l1l2-integration")]
+pub mod smoothing;
+
 // Greeks calculation types and configuration
 pub mod greeks;
 
@@ -137,10 +168,11 @@ pub mod pool;
 pub mod graph;
 
 // Re-export commonly used items for convenience
+pub use engine::{Engine, ProductKind};
 pub use enzyme::{gradient, gradient_with_step, ADMode, Activity};
 pub use graph::{
-    ComputationGraph, GraphBuilder, GraphEdge, GraphError, GraphExtractable, GraphMetadata,
-    GraphNode, GraphNodeUpdate, NodeGroup, NodeType, SimpleGraphExtractor,
+    ComputationGraph, DependencyRegistry, GraphBuilder, GraphEdge, GraphError, GraphExtractable,
+    GraphMetadata, GraphNode, GraphNodeUpdate, NodeGroup, NodeType, SimpleGraphExtractor,
 };
 pub use greeks::{GreeksConfig, GreeksMode, GreeksResult};
 pub use mc::{GbmParams, Greek, MonteCarloConfig, MonteCarloPricer, PayoffParams, PricingResult};
@@ -161,24 +193,25 @@ pub use irs_greeks::{
 // =============================================================================
 
 /// Deprecated: This module is provided for backward compatibility.
-/// Please use `pricer_pricing` directly instead of `pricer_kernel`.
+/// Please use `pricer_pricing` directly, or the `neutryx` facade crate,
+/// instead of `pricer_kernel`.
 ///
 /// # Migration
 ///
-/// Replace `pricer_kernel` with `pricer_pricing` in your imports:
+/// Replace `pricer_kernel` with `neutryx::pricing` in your imports:
 ///
 /// ```rust,ignore
 /// // Before
-/// use pricer_pricing::mc::MonteCarloPricer;
+/// use pricer_pricing::pricer_kernel::mc::MonteCarloPricer;
 ///
 /// // After
-/// use pricer_pricing::mc::MonteCarloPricer;
+/// use neutryx::pricing::MonteCarloPricer;
 /// ```
 ///
 /// This module is deprecated and will be removed in a future version.
 #[deprecated(
     since = "0.7.0",
-    note = "pricer_kernel has been renamed to pricer_pricing. Please update your imports."
+    note = "pricer_kernel has been renamed to pricer_pricing; prefer the neutryx facade crate"
 )]
 pub mod pricer_kernel {
     pub use crate::analytical;
@@ -187,6 +220,8 @@ pub mod pricer_kernel {
     pub use crate::greeks;
     pub use crate::mc;
     pub use crate::path_dependent;
+    pub use crate::pde;
     pub use crate::rng;
+    pub use crate::tree;
     pub use crate::verify;
 }
@@ -0,0 +1,366 @@
+//! Adaptive path-count control.
+//!
+//! When [`MonteCarloConfig::adaptive`](super::MonteCarloConfig::adaptive) is
+//! set, [`MonteCarloPricer::price_adaptive`] simulates batches of `n_paths`
+//! paths, continuing the random stream between batches, until the standard
+//! error of the target quantity falls below the tolerance or the path budget
+//! is spent. The achieved precision is reported in
+//! [`PricingResult::convergence`](super::PricingResult::convergence).
+//!
+//! # Greek Targets
+//!
+//! Greek standard errors come from per-path pathwise estimates on the
+//! smoothed payoff, so only Delta, Vega and Rho can be targeted:
+//!
+//! | Greek | Per-path sample |
+//! |-------|-----------------|
+//! | Delta | `DF × f'(S_T) × S_T / S₀` |
+//! | Vega | `DF × f'(S_T) × S_T × (√dt ΣZ - σT)` |
+//! | Rho | `DF × T × (f'(S_T) × S_T - f(S_T))` |
+//!
+//! The batch size is the configured `n_paths`, so a single batch reproduces
+//! [`MonteCarloPricer::price_european`] exactly.
+
+use super::bridge::shape_normals;
+use super::config::ConvergenceTarget;
+use super::diagnostics::{exact_payoff, Fallback, PricingDiagnostics};
+use super::error::ConfigError;
+use super::estimators::Moments;
+use super::paths::{generate_gbm_paths, GbmParams};
+use super::payoff::{compute_payoffs, soft_plus_derivative, PayoffParams, PayoffType};
+use super::pricer::{Greek, MonteCarloPricer, PricingResult};
+
+/// Achieved precision of an adaptive simulation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ConvergenceReport {
+    /// Quantity the tolerance was applied to.
+    pub target: ConvergenceTarget,
+    /// Standard error of the target at termination.
+    pub std_error: f64,
+    /// Requested tolerance.
+    pub tolerance: f64,
+    /// Total number of simulated paths.
+    pub n_paths: usize,
+    /// Number of batches simulated.
+    pub n_batches: usize,
+    /// Whether the tolerance was met within the path budget.
+    pub converged: bool,
+}
+
+impl ConvergenceReport {
+    /// Returns the 95% confidence interval half-width of the target.
+    #[inline]
+    pub fn confidence_95(&self) -> f64 {
+        1.96 * self.std_error
+    }
+}
+
+impl MonteCarloPricer {
+    /// Prices a European option, adding paths until the target converges.
+    ///
+    /// Uses the adaptive settings from the configuration. The price and its
+    /// standard error are always returned; for a Greek target the Greek is
+    /// filled in as well (Rho per unit rate, with the discount factor
+    /// treated as `exp(-rT)`).
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::InvalidParameter` if adaptive mode is not
+    /// enabled in the configuration, or if a
+    /// [term structure](MonteCarloPricer::with_term_structure) is set: the
+    /// per-path Greek samples assume flat GBM coefficients.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pricer_pricing::mc::{
+    ///     AdaptiveConfig, GbmParams, MonteCarloConfig, MonteCarloPricer, PayoffParams,
+    /// };
+    ///
+    /// let config = MonteCarloConfig::builder()
+    ///     .n_paths(5_000)
+    ///     .n_steps(1)
+    ///     .seed(42)
+    ///     .adaptive(AdaptiveConfig::new(0.05, 200_000))
+    ///     .build()
+    ///     .unwrap();
+    /// let mut pricer = MonteCarloPricer::new(config).unwrap();
+    ///
+    /// let result = pricer
+    ///     .price_adaptive(GbmParams::default(), PayoffParams::call(100.0), (-0.05_f64).exp())
+    ///     .unwrap();
+    /// let report = result.convergence.unwrap();
+    /// println!(
+    ///     "Price: {} +/- {} ({} paths)",
+    ///     result.price,
+    ///     report.confidence_95(),
+    ///     report.n_paths
+    /// );
+    /// ```
+    pub fn price_adaptive(
+        &mut self,
+        gbm: GbmParams,
+        payoff: PayoffParams,
+        discount_factor: f64,
+    ) -> Result<PricingResult, ConfigError> {
+        let adaptive = self
+            .config()
+            .adaptive()
+            .ok_or(ConfigError::InvalidParameter {
+                name: "adaptive",
+                value: "adaptive mode is not enabled".to_string(),
+            })?;
+        if self.term_structure().is_some() {
+            return Err(ConfigError::InvalidParameter {
+                name: "term_structure",
+                value: "adaptive samples assume flat GBM coefficients".to_string(),
+            });
+        }
+
+        let batch_size = self.config().n_paths();
+        let n_steps = self.config().n_steps();
+        let dt = gbm.maturity / n_steps as f64;
+        let sqrt_dt = dt.sqrt();
+        let sign = match payoff.payoff_type {
+            PayoffType::Call => 1.0,
+            PayoffType::Put => -1.0,
+        };
+
+        let mut price = Moments::default();
+        let mut greek = Moments::default();
+        let mut bias = 0.0;
+        let mut n_paths = 0;
+        let mut n_batches = 0;
+
+        let (std_error, converged) = loop {
+            let batch = batch_size.min(adaptive.max_paths - n_paths);
+
+            self.workspace.ensure_capacity(batch, n_steps);
+            self.rng.fill_normal(self.workspace.randoms_mut());
+            shape_normals(&self.config, self.workspace.randoms_mut(), batch, n_steps);
+            generate_gbm_paths(&mut self.workspace, gbm, batch, n_steps);
+            compute_payoffs(&mut self.workspace, payoff, batch, n_steps);
+
+            let paths = self.workspace.paths();
+            let randoms = self.workspace.randoms();
+            for (path_idx, &value) in self.workspace.payoffs().iter().enumerate() {
+                price.add(discount_factor * value);
+                let terminal = paths[path_idx * (n_steps + 1) + n_steps];
+                bias += discount_factor * (value - exact_payoff(terminal, payoff));
+
+                let ConvergenceTarget::Greek(target) = adaptive.target else {
+                    continue;
+                };
+                let slope = soft_plus_derivative(
+                    sign * (terminal - payoff.strike),
+                    payoff.smoothing_epsilon,
+                ) * sign;
+                let sample = match target {
+                    Greek::Delta => slope * terminal / gbm.spot,
+                    Greek::Vega => {
+                        let z_sum: f64 = randoms[path_idx * n_steps..][..n_steps].iter().sum();
+                        slope * terminal * (sqrt_dt * z_sum - gbm.volatility * gbm.maturity)
+                    }
+                    Greek::Rho => gbm.maturity * (slope * terminal - value),
+                    _ => unreachable!("rejected by MonteCarloConfig::validate"),
+                };
+                greek.add(discount_factor * sample);
+            }
+
+            n_paths += batch;
+            n_batches += 1;
+
+            let n = n_paths as f64;
+            let target_moments = match adaptive.target {
+                ConvergenceTarget::Price => &price,
+                ConvergenceTarget::Greek(_) => &greek,
+            };
+            let std_error = (target_moments.variance(n) / n).sqrt();
+            if std_error <= adaptive.tolerance {
+                break (std_error, true);
+            }
+            if n_paths >= adaptive.max_paths {
+                break (std_error, false);
+            }
+        };
+
+        let n = n_paths as f64;
+        let (mean, price_error) = (price.mean(n), (price.variance(n) / n).sqrt());
+        let mut diagnostics =
+            PricingDiagnostics::new(self.config(), n_paths, gbm.maturity, mean, price_error)
+                .with_smoothing(payoff.smoothing_epsilon, Some(bias / n));
+        if !converged {
+            diagnostics.push_fallback(Fallback::AdaptiveNotConverged);
+        }
+        let mut result = PricingResult {
+            price: mean,
+            std_error: price_error,
+            diagnostics: Some(diagnostics),
+            #[cfg(feature = "l1l2-integration")]
+            metadata: Some(self.run_metadata(&(gbm, payoff, discount_factor))),
+            ..Default::default()
+        };
+        if let ConvergenceTarget::Greek(target) = adaptive.target {
+            let value = Some(greek.mean(n));
+            match target {
+                Greek::Delta => result.delta = value,
+                Greek::Vega => result.vega = value,
+                Greek::Rho => result.rho = value,
+                _ => unreachable!("rejected by MonteCarloConfig::validate"),
+            }
+        }
+        result.convergence = Some(ConvergenceReport {
+            target: adaptive.target,
+            std_error,
+            tolerance: adaptive.tolerance,
+            n_paths,
+            n_batches,
+            converged,
+        });
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mc::{AdaptiveConfig, GbmTermStructure, MonteCarloConfig, PiecewiseConstant};
+    use approx::assert_relative_eq;
+
+    fn adaptive_pricer(adaptive: AdaptiveConfig) -> MonteCarloPricer {
+        let config = MonteCarloConfig::builder()
+            .n_paths(2_000)
+            .n_steps(1)
+            .seed(42)
+            .adaptive(adaptive)
+            .build()
+            .unwrap();
+        MonteCarloPricer::new(config).unwrap()
+    }
+
+    #[test]
+    fn test_single_batch_matches_price_european() {
+        let gbm = GbmParams::default();
+        let payoff = PayoffParams::call(100.0);
+        let df = (-0.05_f64).exp();
+
+        let mut pricer = adaptive_pricer(AdaptiveConfig::new(1e6, 2_000));
+        let adaptive = pricer.price_adaptive(gbm, payoff, df).unwrap();
+
+        pricer.reset();
+        let fixed = pricer.price_european(gbm, payoff, df);
+
+        assert_relative_eq!(adaptive.price, fixed.price, max_relative = 1e-12);
+        assert_relative_eq!(adaptive.std_error, fixed.std_error, max_relative = 1e-9);
+        let report = adaptive.convergence.unwrap();
+        assert_eq!(report.n_batches, 1);
+        assert!(report.converged);
+    }
+
+    #[test]
+    fn test_price_target_meets_tolerance() {
+        let mut pricer = adaptive_pricer(AdaptiveConfig::new(0.05, 1_000_000));
+        let result = pricer
+            .price_adaptive(
+                GbmParams::default(),
+                PayoffParams::call(100.0),
+                (-0.05_f64).exp(),
+            )
+            .unwrap();
+
+        let report = result.convergence.unwrap();
+        assert!(report.converged);
+        assert!(report.n_batches > 1);
+        assert!(result.std_error <= 0.05);
+        assert_eq!(report.std_error, result.std_error);
+        // Black-Scholes: 10.4506
+        assert!((result.price - 10.4506).abs() < 4.0 * report.std_error);
+    }
+
+    #[test]
+    fn test_budget_exhausted() {
+        let mut pricer = adaptive_pricer(AdaptiveConfig::new(1e-6, 5_000));
+        let result = pricer
+            .price_adaptive(GbmParams::default(), PayoffParams::put(100.0), 0.95)
+            .unwrap();
+
+        let report = result.convergence.unwrap();
+        assert!(!report.converged);
+        // Two full batches plus a partial one
+        assert_eq!(report.n_paths, 5_000);
+        assert_eq!(report.n_batches, 3);
+    }
+
+    #[test]
+    fn test_greek_targets_match_black_scholes() {
+        let gbm = GbmParams::default();
+        let df = (-0.05_f64).exp();
+        // Black-Scholes call (S=K=100, r=5%, σ=20%, T=1): Δ, vega, rho
+        for (greek, expected) in [
+            (Greek::Delta, 0.6368),
+            (Greek::Vega, 37.524),
+            (Greek::Rho, 53.232),
+        ] {
+            let mut pricer = adaptive_pricer(
+                AdaptiveConfig::new(0.002 * expected, 2_000_000)
+                    .with_target(ConvergenceTarget::Greek(greek)),
+            );
+            let result = pricer
+                .price_adaptive(gbm, PayoffParams::call(100.0), df)
+                .unwrap();
+
+            let report = result.convergence.unwrap();
+            assert!(report.converged, "{:?} did not converge", greek);
+            let value = match greek {
+                Greek::Delta => result.delta,
+                Greek::Vega => result.vega,
+                _ => result.rho,
+            }
+            .unwrap();
+            assert!(
+                (value - expected).abs() < 4.0 * report.std_error,
+                "{:?}: {} vs {}",
+                greek,
+                value,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_requires_adaptive_config() {
+        let config = MonteCarloConfig::builder()
+            .n_paths(1_000)
+            .n_steps(1)
+            .build()
+            .unwrap();
+        let mut pricer = MonteCarloPricer::new(config).unwrap();
+
+        let result = pricer.price_adaptive(GbmParams::default(), PayoffParams::call(100.0), 0.95);
+        assert!(matches!(
+            result,
+            Err(ConfigError::InvalidParameter {
+                name: "adaptive",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_rejects_term_structure() {
+        let volatility = PiecewiseConstant::new(vec![0.5, 1.0], vec![0.1, 0.3]).unwrap();
+        let term_structure = GbmTermStructure::new().with_volatility(volatility);
+        let mut pricer =
+            adaptive_pricer(AdaptiveConfig::new(0.05, 100_000)).with_term_structure(term_structure);
+
+        let result = pricer.price_adaptive(GbmParams::default(), PayoffParams::call(100.0), 0.95);
+        assert!(matches!(
+            result,
+            Err(ConfigError::InvalidParameter {
+                name: "term_structure",
+                ..
+            })
+        ));
+    }
+}
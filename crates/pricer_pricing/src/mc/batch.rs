@@ -0,0 +1,264 @@
+//! Multi-instrument batch pricing with shared paths.
+//!
+//! [`MonteCarloPricer::price_batch`] groups instruments by underlying (equal
+//! [`GbmParams`]) and generates one set of paths per group in the pricer's
+//! [`PathWorkspace`](super::PathWorkspace). Every instrument in the group is
+//! then evaluated on the same paths, and path statistics for path-dependent
+//! payoffs are observed once per path rather than once per instrument.
+//!
+//! Each group restarts the random stream from the pricer's seed, so every
+//! instrument gets exactly the price it would get from a fresh single-instrument
+//! call (common random numbers across the batch).
+
+use super::bridge::shape_normals;
+use super::diagnostics::{exact_payoff, PricingDiagnostics};
+use super::estimators::{EstimatorPayoff, Moments};
+use super::paths::GbmParams;
+use super::payoff::compute_payoff;
+use super::pricer::{MonteCarloPricer, PricingResult};
+use crate::path_dependent::PathObserver;
+
+/// One instrument in a batch pricing request.
+#[derive(Clone, Copy, Debug)]
+pub struct InstrumentSpec {
+    /// Underlying dynamics; instruments with equal parameters share paths.
+    pub underlying: GbmParams,
+    /// Payoff (vanilla, digital or path-dependent).
+    pub payoff: EstimatorPayoff,
+    /// Present value discount factor.
+    pub discount_factor: f64,
+}
+
+impl InstrumentSpec {
+    /// Creates an instrument specification.
+    #[inline]
+    pub fn new(underlying: GbmParams, payoff: EstimatorPayoff, discount_factor: f64) -> Self {
+        Self {
+            underlying,
+            payoff,
+            discount_factor,
+        }
+    }
+}
+
+impl MonteCarloPricer {
+    /// Prices a batch of instruments, sharing paths per underlying.
+    ///
+    /// Results are returned in the order of `instruments`. Path generation
+    /// cost is paid once per distinct underlying instead of once per
+    /// instrument.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pricer_pricing::mc::{
+    ///     EstimatorPayoff, GbmParams, InstrumentSpec, MonteCarloConfig, MonteCarloPricer,
+    ///     PayoffParams,
+    /// };
+    ///
+    /// let config = MonteCarloConfig::builder()
+    ///     .n_paths(10_000)
+    ///     .n_steps(50)
+    ///     .seed(42)
+    ///     .build()
+    ///     .unwrap();
+    /// let mut pricer = MonteCarloPricer::new(config).unwrap();
+    ///
+    /// let gbm = GbmParams::default();
+    /// let df = (-0.05_f64).exp();
+    /// let book: Vec<InstrumentSpec> = [90.0, 100.0, 110.0]
+    ///     .iter()
+    ///     .map(|&k| InstrumentSpec::new(gbm, EstimatorPayoff::Vanilla(PayoffParams::call(k)), df))
+    ///     .collect();
+    ///
+    /// let results = pricer.price_batch(&book);
+    /// assert!(results[0].price > results[1].price);
+    /// assert!(results[1].price > results[2].price);
+    /// ```
+    pub fn price_batch(&mut self, instruments: &[InstrumentSpec]) -> Vec<PricingResult> {
+        let n_paths = self.config().n_paths();
+        let n_steps = self.config().n_steps();
+        let seed = self.rng.seed();
+
+        // Group instrument indices by underlying, in order of first appearance
+        let mut groups: Vec<(GbmParams, Vec<usize>)> = Vec::new();
+        for (idx, instrument) in instruments.iter().enumerate() {
+            match groups
+                .iter_mut()
+                .find(|(gbm, _)| *gbm == instrument.underlying)
+            {
+                Some((_, members)) => members.push(idx),
+                None => groups.push((instrument.underlying, vec![idx])),
+            }
+        }
+
+        let mut results = vec![PricingResult::default(); instruments.len()];
+        let mut moments = vec![Moments::default(); instruments.len()];
+        let mut bias = vec![0.0; instruments.len()];
+
+        for (gbm, members) in &groups {
+            self.reset_with_seed(seed);
+            self.workspace.ensure_capacity(n_paths, n_steps);
+            self.rng.fill_normal(self.workspace.randoms_mut());
+            shape_normals(&self.config, self.workspace.randoms_mut(), n_paths, n_steps);
+            self.generate_paths(*gbm, n_paths, n_steps);
+
+            let needs_observer = members
+                .iter()
+                .any(|&idx| matches!(instruments[idx].payoff, EstimatorPayoff::PathDependent(_)));
+
+            for path in self.workspace.paths().chunks_exact(n_steps + 1) {
+                let terminal = path[n_steps];
+                let observer = needs_observer.then(|| {
+                    let mut observer: PathObserver<f64> = PathObserver::new();
+                    for &s in path {
+                        observer.observe(s);
+                    }
+                    observer.set_terminal(terminal);
+                    observer
+                });
+
+                for &idx in members {
+                    let value = match (&instruments[idx].payoff, &observer) {
+                        (EstimatorPayoff::Vanilla(params), _) => {
+                            let value = compute_payoff(terminal, *params);
+                            bias[idx] += value - exact_payoff(terminal, *params);
+                            value
+                        }
+                        (EstimatorPayoff::PathDependent(payoff), Some(observer)) => {
+                            payoff.compute(&[], observer)
+                        }
+                        (payoff, _) => payoff.evaluate(path),
+                    };
+                    moments[idx].add(value);
+                }
+            }
+
+            let n = n_paths as f64;
+            for &idx in members {
+                let df = instruments[idx].discount_factor;
+                let price = moments[idx].mean(n) * df;
+                let std_error = (moments[idx].variance(n) / n).sqrt() * df;
+                let diagnostics =
+                    PricingDiagnostics::new(&self.config, n_paths, gbm.maturity, price, std_error);
+                let diagnostics = match &instruments[idx].payoff {
+                    EstimatorPayoff::Vanilla(params) => diagnostics
+                        .with_smoothing(params.smoothing_epsilon, Some(bias[idx] / n * df)),
+                    EstimatorPayoff::PathDependent(payoff) => {
+                        diagnostics.with_smoothing(payoff.smoothing_epsilon(), None)
+                    }
+                    _ => diagnostics,
+                };
+                results[idx] = PricingResult {
+                    price,
+                    std_error,
+                    diagnostics: Some(diagnostics),
+                    #[cfg(feature = "l1l2-integration")]
+                    metadata: Some(self.run_metadata(&instruments[idx])),
+                    ..Default::default()
+                };
+            }
+        }
+
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mc::{MonteCarloConfig, PayoffParams};
+    use crate::path_dependent::PathPayoffType;
+    use approx::assert_relative_eq;
+
+    fn create_pricer() -> MonteCarloPricer {
+        let config = MonteCarloConfig::builder()
+            .n_paths(5_000)
+            .n_steps(20)
+            .seed(42)
+            .build()
+            .unwrap();
+        MonteCarloPricer::new(config).unwrap()
+    }
+
+    #[test]
+    fn test_batch_matches_individual_pricing() {
+        let gbm = GbmParams::default();
+        let other = GbmParams {
+            spot: 80.0,
+            volatility: 0.3,
+            ..gbm
+        };
+        let df = (-0.05_f64).exp();
+        let asian = PathPayoffType::asian_arithmetic_call(100.0, 1e-6);
+
+        let book = [
+            InstrumentSpec::new(gbm, EstimatorPayoff::Vanilla(PayoffParams::call(100.0)), df),
+            InstrumentSpec::new(other, EstimatorPayoff::Vanilla(PayoffParams::put(85.0)), df),
+            InstrumentSpec::new(gbm, EstimatorPayoff::PathDependent(asian), df),
+            InstrumentSpec::new(gbm, EstimatorPayoff::Vanilla(PayoffParams::put(95.0)), 0.9),
+        ];
+
+        let mut pricer = create_pricer();
+        let batch = pricer.price_batch(&book);
+
+        let mut single = create_pricer();
+        let expected = [
+            single.price_european(gbm, PayoffParams::call(100.0), df),
+            {
+                single.reset();
+                single.price_european(other, PayoffParams::put(85.0), df)
+            },
+            {
+                single.reset();
+                single.price_path_dependent(gbm, asian, df)
+            },
+            {
+                single.reset();
+                single.price_european(gbm, PayoffParams::put(95.0), 0.9)
+            },
+        ];
+
+        for (got, want) in batch.iter().zip(&expected) {
+            assert_relative_eq!(got.price, want.price, max_relative = 1e-12);
+            // price_path_dependent divides the variance by n rather than n - 1
+            assert_relative_eq!(got.std_error, want.std_error, max_relative = 1e-3);
+            let (got, want) = (got.diagnostics.as_ref(), want.diagnostics.as_ref());
+            let (got, want) = (got.unwrap(), want.unwrap());
+            assert_eq!(got.smoothing_epsilon, want.smoothing_epsilon);
+            assert_eq!(got.smoothing_bias.is_some(), want.smoothing_bias.is_some());
+        }
+    }
+
+    #[test]
+    fn test_batch_digital_and_empty() {
+        let mut pricer = create_pricer();
+        assert!(pricer.price_batch(&[]).is_empty());
+
+        let gbm = GbmParams::default();
+        let book = [
+            InstrumentSpec::new(gbm, EstimatorPayoff::digital_call(100.0), 1.0),
+            InstrumentSpec::new(gbm, EstimatorPayoff::digital_put(100.0), 1.0),
+        ];
+        let results = pricer.price_batch(&book);
+
+        // Shared paths: call and put digitals partition the outcomes
+        assert_relative_eq!(results[0].price + results[1].price, 1.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_batch_is_reproducible() {
+        let gbm = GbmParams::default();
+        let book = [InstrumentSpec::new(
+            gbm,
+            EstimatorPayoff::Vanilla(PayoffParams::call(105.0)),
+            0.95,
+        )];
+
+        let mut pricer = create_pricer();
+        let first = pricer.price_batch(&book);
+        let second = pricer.price_batch(&book);
+
+        assert_eq!(first, second);
+    }
+}
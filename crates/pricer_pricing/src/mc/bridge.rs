@@ -0,0 +1,363 @@
+//! Brownian-bridge path construction and terminal stratification.
+//!
+//! Both options reshape the standard normal draws of each path before
+//! they reach the path generators, so every pricing method picks them up
+//! without changes to the GBM kernels:
+//!
+//! - [`PathConstruction::BrownianBridge`] maps the draws of a path to
+//!   step increments so that draw 0 fixes `W(T)`, draw 1 fixes `W(T/2)`
+//!   given `W(T)`, and so on by bisection. The map is orthogonal, so the
+//!   increments are still i.i.d. standard normals, but the leading draws
+//!   now carry most of the path's variance. This is what makes low-
+//!   discrepancy sequences effective on Asian and barrier payoffs, whose
+//!   value depends mostly on the coarse shape of the path.
+//! - Terminal stratification replaces draw 0 of path `i` (of `n`) with
+//!   `Φ⁻¹((i + u)/n)`, where `u = Φ(z)` is the original draw mapped to a
+//!   uniform. Each path then samples its own terminal stratum, which
+//!   removes most of the variance of payoffs driven by the terminal value.
+//!
+//! # Standard Errors
+//!
+//! Stratified paths are not independent, so the reported i.i.d. standard
+//! error overstates the true error; treat it as a conservative bound.
+
+use super::config::{MonteCarloConfig, PathConstruction};
+use crate::rng::inverse_normal_cdf;
+
+/// Brownian-bridge construction on a uniform time grid.
+///
+/// # Examples
+///
+/// ```rust
+/// use pricer_pricing::mc::BrownianBridge;
+///
+/// let bridge = BrownianBridge::new(4);
+/// let normals = [1.0, 0.0, 0.0, 0.0];
+/// let mut increments = [0.0; 4];
+/// bridge.transform(&normals, &mut increments);
+///
+/// // Draw 0 alone fixes the terminal value √n × z₀, spread evenly
+/// assert!(increments.iter().all(|&dw| (dw - 0.5).abs() < 1e-12));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct BrownianBridge {
+    /// Grid point (0-based, time `index + 1`) filled by each draw.
+    bridge_index: Vec<usize>,
+    /// Left neighbour as a time (0 means `W(0) = 0`).
+    left_index: Vec<usize>,
+    /// Right neighbour as a 0-based grid point.
+    right_index: Vec<usize>,
+    left_weight: Vec<f64>,
+    right_weight: Vec<f64>,
+    std_dev: Vec<f64>,
+}
+
+impl BrownianBridge {
+    /// Builds the bisection order for `n_steps` equal steps.
+    pub fn new(n_steps: usize) -> Self {
+        let mut bridge = Self {
+            bridge_index: vec![0; n_steps],
+            left_index: vec![0; n_steps],
+            right_index: vec![0; n_steps],
+            left_weight: vec![0.0; n_steps],
+            right_weight: vec![0.0; n_steps],
+            std_dev: vec![0.0; n_steps],
+        };
+        if n_steps == 0 {
+            return bridge;
+        }
+
+        // `filled[k]` is non-zero once grid point k has been assigned a draw
+        let mut filled = vec![0usize; n_steps];
+        filled[n_steps - 1] = 1;
+        bridge.bridge_index[0] = n_steps - 1;
+        bridge.std_dev[0] = (n_steps as f64).sqrt();
+
+        let mut j = 0;
+        for i in 1..n_steps {
+            while filled[j] != 0 {
+                j += 1;
+            }
+            let mut k = j;
+            while filled[k] == 0 {
+                k += 1;
+            }
+            let l = j + ((k - 1 - j) >> 1);
+            filled[l] = i;
+
+            let span = (k + 1 - j) as f64;
+            bridge.bridge_index[i] = l;
+            bridge.left_index[i] = j;
+            bridge.right_index[i] = k;
+            bridge.left_weight[i] = (k - l) as f64 / span;
+            bridge.right_weight[i] = (l + 1 - j) as f64 / span;
+            bridge.std_dev[i] = ((l + 1 - j) as f64 * (k - l) as f64 / span).sqrt();
+
+            j = k + 1;
+            if j >= n_steps {
+                j = 0;
+            }
+        }
+        bridge
+    }
+
+    /// Returns the number of steps.
+    #[inline]
+    pub fn n_steps(&self) -> usize {
+        self.bridge_index.len()
+    }
+
+    /// Maps one path's normals to standardised step increments.
+    ///
+    /// `increments[k]` is `ΔW_k / √dt`, so it can be used wherever a
+    /// per-step standard normal is expected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either slice is shorter than `n_steps`.
+    pub fn transform(&self, normals: &[f64], increments: &mut [f64]) {
+        let n = self.n_steps();
+        if n == 0 {
+            return;
+        }
+        let normals = &normals[..n];
+        let w = &mut increments[..n];
+
+        w[n - 1] = self.std_dev[0] * normals[0];
+        for (i, &z) in normals.iter().enumerate().skip(1) {
+            let j = self.left_index[i];
+            let k = self.right_index[i];
+            let l = self.bridge_index[i];
+            let left = if j == 0 { 0.0 } else { w[j - 1] };
+            w[l] = self.left_weight[i] * left + self.right_weight[i] * w[k] + self.std_dev[i] * z;
+        }
+
+        // Levels to increments
+        for k in (1..n).rev() {
+            w[k] -= w[k - 1];
+        }
+    }
+}
+
+/// Applies the configured path construction to a block of draws in place.
+///
+/// `randoms` holds `n_paths × n_steps` standard normals, path-major. Does
+/// nothing for the default incremental construction without
+/// stratification, so the random stream is unchanged in that case.
+pub(crate) fn shape_normals(
+    config: &MonteCarloConfig,
+    randoms: &mut [f64],
+    n_paths: usize,
+    n_steps: usize,
+) {
+    shape_normals_block(config, randoms, 0, n_paths, n_paths, n_steps);
+}
+
+/// Applies the path construction to paths `first_path..first_path + n_paths`
+/// of a run of `total_paths`, with `randoms` holding only that block.
+///
+/// Lets callers draw a large run in chunks; the result matches
+/// [`shape_normals`] on the full buffer.
+pub(crate) fn shape_normals_block(
+    config: &MonteCarloConfig,
+    randoms: &mut [f64],
+    first_path: usize,
+    n_paths: usize,
+    total_paths: usize,
+    n_steps: usize,
+) {
+    let stratify = config.stratify_terminal();
+    if !stratify && config.path_construction() == PathConstruction::Incremental {
+        return;
+    }
+
+    let bridge = BrownianBridge::new(n_steps);
+    let mut normals = vec![0.0; n_steps];
+    let n = total_paths as f64;
+    for (path_idx, path) in randoms[..n_paths * n_steps]
+        .chunks_exact_mut(n_steps)
+        .enumerate()
+    {
+        normals.copy_from_slice(path);
+        if stratify {
+            let u = normal_cdf(normals[0]);
+            let stratum = (first_path + path_idx) as f64;
+            let p = ((stratum + u) / n).clamp(f64::MIN_POSITIVE, 1.0 - f64::EPSILON);
+            normals[0] = inverse_normal_cdf(p);
+        }
+        bridge.transform(&normals, path);
+    }
+}
+
+/// Standard normal CDF via the complementary error function.
+fn normal_cdf(x: f64) -> f64 {
+    0.5 * erfc(-x / std::f64::consts::SQRT_2)
+}
+
+/// Complementary error function (Chebyshev fit, relative error < 1.2e-7).
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let poly = -z * z - 1.265_512_23
+        + t * (1.000_023_68
+            + t * (0.374_091_96
+                + t * (0.096_784_18
+                    + t * (-0.186_288_06
+                        + t * (0.278_868_07
+                            + t * (-1.135_203_98
+                                + t * (1.488_515_87 + t * (-0.822_152_23 + t * 0.170_872_77))))))));
+    let r = t * poly.exp();
+    if x >= 0.0 {
+        r
+    } else {
+        2.0 - r
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mc::{GbmParams, MonteCarloPricer, PayoffParams};
+    use crate::path_dependent::PathPayoffType;
+    use crate::rng::PricerRng;
+    use approx::assert_relative_eq;
+
+    fn create_pricer(
+        seed: u64,
+        n_steps: usize,
+        construction: PathConstruction,
+        stratify: bool,
+    ) -> MonteCarloPricer {
+        let config = MonteCarloConfig::builder()
+            .n_paths(1_000)
+            .n_steps(n_steps)
+            .seed(seed)
+            .path_construction(construction)
+            .stratify_terminal(stratify)
+            .build()
+            .unwrap();
+        MonteCarloPricer::new(config).unwrap()
+    }
+
+    /// Standard deviation of prices across independent seeds.
+    fn spread(prices: &[f64]) -> f64 {
+        let n = prices.len() as f64;
+        let mean = prices.iter().sum::<f64>() / n;
+        (prices.iter().map(|p| (p - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+    }
+
+    #[test]
+    fn test_bridge_is_orthogonal() {
+        // Non-power-of-two sizes exercise uneven bisection
+        for n_steps in [1, 2, 3, 7, 12, 64, 100] {
+            let bridge = BrownianBridge::new(n_steps);
+            let mut normals = vec![0.0; n_steps];
+            PricerRng::from_seed(n_steps as u64).fill_normal(&mut normals);
+            let mut increments = vec![0.0; n_steps];
+            bridge.transform(&normals, &mut increments);
+
+            let norm_in: f64 = normals.iter().map(|z| z * z).sum();
+            let norm_out: f64 = increments.iter().map(|z| z * z).sum();
+            assert_relative_eq!(norm_in, norm_out, max_relative = 1e-12);
+
+            // Terminal level depends on draw 0 only
+            let terminal: f64 = increments.iter().sum();
+            assert_relative_eq!(
+                terminal,
+                (n_steps as f64).sqrt() * normals[0],
+                epsilon = 1e-12
+            );
+        }
+    }
+
+    #[test]
+    fn test_bridge_fills_every_point_once() {
+        let bridge = BrownianBridge::new(37);
+        let mut seen = bridge.bridge_index.clone();
+        seen.sort_unstable();
+        assert_eq!(seen, (0..37).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_inverse_normal_cdf_round_trip() {
+        for &x in &[-5.0, -2.5, -1.0, -0.1, 0.0, 0.3, 1.7, 4.0] {
+            assert_relative_eq!(inverse_normal_cdf(normal_cdf(x)), x, epsilon = 1e-6);
+        }
+        assert_eq!(inverse_normal_cdf(0.5), 0.0);
+    }
+
+    #[test]
+    fn test_stratified_strata_are_ordered() {
+        let config = MonteCarloConfig::builder()
+            .n_paths(100)
+            .n_steps(5)
+            .stratify_terminal(true)
+            .build()
+            .unwrap();
+        let mut randoms = vec![0.0; 500];
+        PricerRng::from_seed(3).fill_normal(&mut randoms);
+        shape_normals(&config, &mut randoms, 100, 5);
+
+        let terminals: Vec<f64> = randoms.chunks_exact(5).map(|p| p.iter().sum()).collect();
+        assert!(terminals.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_bridge_prices_unbiased() {
+        let gbm = GbmParams::default();
+        let df = (-0.05_f64).exp();
+        // Black-Scholes call (S=K=100, r=5%, σ=20%, T=1)
+        let bs = 10.4506;
+
+        let mut pricer = create_pricer(42, 16, PathConstruction::BrownianBridge, false);
+        let result = pricer.price_european(gbm, PayoffParams::call(100.0), df);
+        assert!((result.price - bs).abs() < 4.0 * result.std_error);
+    }
+
+    #[test]
+    fn test_stratification_reduces_variance() {
+        let gbm = GbmParams::default();
+        let df = (-0.05_f64).exp();
+        let asian = PathPayoffType::asian_arithmetic_call(100.0, 1e-6);
+
+        let prices = |stratify: bool, asian_payoff: bool| -> Vec<f64> {
+            (0..20)
+                .map(|seed| {
+                    let construction = if stratify {
+                        PathConstruction::BrownianBridge
+                    } else {
+                        PathConstruction::Incremental
+                    };
+                    let mut pricer = create_pricer(seed, 16, construction, stratify);
+                    if asian_payoff {
+                        pricer.price_path_dependent(gbm, asian, df).price
+                    } else {
+                        pricer
+                            .price_european(gbm, PayoffParams::call(100.0), df)
+                            .price
+                    }
+                })
+                .collect()
+        };
+
+        let european = (spread(&prices(false, false)), spread(&prices(true, false)));
+        let asian = (spread(&prices(false, true)), spread(&prices(true, true)));
+        assert!(european.1 < 0.2 * european.0, "european {:?}", european);
+        // The average depends on more than the terminal value, so the gain is smaller
+        assert!(asian.1 < 0.8 * asian.0, "asian {:?}", asian);
+    }
+
+    #[test]
+    fn test_default_leaves_stream_unchanged() {
+        let config = MonteCarloConfig::builder()
+            .n_paths(10)
+            .n_steps(4)
+            .build()
+            .unwrap();
+        let mut randoms = vec![0.0; 40];
+        PricerRng::from_seed(1).fill_normal(&mut randoms);
+        let original = randoms.clone();
+        shape_normals(&config, &mut randoms, 10, 4);
+        assert_eq!(randoms, original);
+    }
+}
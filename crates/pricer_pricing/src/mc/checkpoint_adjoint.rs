@@ -0,0 +1,392 @@
+//! Checkpointed adjoint Greeks for long path-dependent simulations.
+//!
+//! [`CheckpointPricer::price_with_adjoint`] computes pathwise Delta and Vega
+//! of Asian options by a hand-written reverse sweep over the time loop,
+//! driven by the [`CheckpointSchedule`] built from the configured
+//! [`CheckpointStrategy`](crate::checkpoint::CheckpointStrategy). Memory is
+//! bounded by the schedule's stored states rather than the number of steps,
+//! so 10-year daily-step simulations can be differentiated with a fixed
+//! number of path-state slots.
+//!
+//! # Random Numbers
+//!
+//! Draws for step `k` come from an independent stream seeded from
+//! `(seed, k)`, so they are regenerated during recomputation instead of
+//! being stored. Prices therefore differ sample-by-sample from
+//! [`price_path_dependent_with_checkpoints`](CheckpointPricer::price_path_dependent_with_checkpoints),
+//! but not in distribution.
+//!
+//! # Adjoint Recursion
+//!
+//! Per path the state is `(S, Σ S, Σ ln S)`. Reversing `S' = S·g` with
+//! `g = exp((r - σ²/2)dt + σ√dt Z)`:
+//!
+//! ```text
+//! S̄'_total = S̄' + Σ̄' + L̄' / S'
+//! S̄ = S̄'_total × g
+//! σ̄ += S̄'_total × S' × (√dt Z - σ dt)
+//! ```
+
+use crate::checkpoint::{
+    AdjointStep, CheckpointError, CheckpointResult, CheckpointSchedule, ScheduleReport,
+};
+use crate::mc::estimators::Moments;
+use crate::mc::payoff::{soft_plus, soft_plus_derivative};
+use crate::mc::pricer_checkpoint::CheckpointPricer;
+use crate::mc::{GbmParams, PricingResult};
+use crate::path_dependent::{AsianParams, PathPayoffType};
+use crate::rng::PricerRng;
+
+/// Golden-ratio increment separating per-step seeds.
+const STEP_SEED_INCREMENT: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// Per-path simulation state at a time step.
+#[derive(Clone, Debug)]
+struct AsianState {
+    spots: Vec<f64>,
+    sums: Vec<f64>,
+    log_sums: Vec<f64>,
+}
+
+/// Adjoints of [`AsianState`] plus the accumulated volatility sensitivity.
+#[derive(Clone, Debug)]
+struct AsianAdjoint {
+    spots: Vec<f64>,
+    sums: Vec<f64>,
+    log_sums: Vec<f64>,
+    vega: f64,
+}
+
+/// Which average the payoff is written on.
+#[derive(Clone, Copy, Debug)]
+enum Average {
+    Arithmetic,
+    Geometric,
+}
+
+/// GBM time loop with an Asian terminal payoff.
+struct AsianStepper {
+    seed: u64,
+    drift_dt: f64,
+    vol_sqrt_dt: f64,
+    sqrt_dt: f64,
+    vol_dt: f64,
+    n_observations: f64,
+    average: Average,
+    params: AsianParams<f64>,
+    randoms: Vec<f64>,
+    payoff_moments: Moments,
+}
+
+impl AsianStepper {
+    /// Regenerates the draws for `step` into `self.randoms`.
+    fn draw(&mut self, step: usize) {
+        let step_seed = self
+            .seed
+            .wrapping_add((step as u64 + 1).wrapping_mul(STEP_SEED_INCREMENT));
+        PricerRng::from_seed(step_seed).fill_normal(&mut self.randoms);
+    }
+}
+
+impl AdjointStep for AsianStepper {
+    type State = AsianState;
+    type Adjoint = AsianAdjoint;
+
+    fn advance(&mut self, step: usize, state: &mut AsianState) {
+        self.draw(step);
+        for (i, &z) in self.randoms.iter().enumerate() {
+            let spot = state.spots[i] * (self.drift_dt + self.vol_sqrt_dt * z).exp();
+            state.spots[i] = spot;
+            state.sums[i] += spot;
+            state.log_sums[i] += spot.ln();
+        }
+    }
+
+    fn terminal(&mut self, state: &AsianState, adjoint: &mut AsianAdjoint) {
+        let sign = if self.params.is_call { 1.0 } else { -1.0 };
+        let epsilon = self.params.smoothing_epsilon;
+        for i in 0..state.spots.len() {
+            let (average, d_average) = match self.average {
+                Average::Arithmetic => (state.sums[i] / self.n_observations, (1.0, 0.0)),
+                Average::Geometric => {
+                    let g = (state.log_sums[i] / self.n_observations).exp();
+                    (g, (0.0, g))
+                }
+            };
+            let intrinsic = sign * (average - self.params.strike);
+            self.payoff_moments.add(soft_plus(intrinsic, epsilon));
+
+            let slope = sign * soft_plus_derivative(intrinsic, epsilon) / self.n_observations;
+            adjoint.spots[i] = 0.0;
+            adjoint.sums[i] = slope * d_average.0;
+            adjoint.log_sums[i] = slope * d_average.1;
+        }
+    }
+
+    fn reverse(&mut self, step: usize, state: &AsianState, adjoint: &mut AsianAdjoint) {
+        self.draw(step);
+        for (i, &z) in self.randoms.iter().enumerate() {
+            let growth = (self.drift_dt + self.vol_sqrt_dt * z).exp();
+            let next_spot = state.spots[i] * growth;
+            let total = adjoint.spots[i] + adjoint.sums[i] + adjoint.log_sums[i] / next_spot;
+            adjoint.spots[i] = total * growth;
+            adjoint.vega += total * next_spot * (self.sqrt_dt * z - self.vol_dt);
+        }
+    }
+
+    fn state_bytes(&self, state: &AsianState) -> usize {
+        std::mem::size_of::<f64>() * (state.spots.len() + state.sums.len() + state.log_sums.len())
+    }
+}
+
+impl CheckpointPricer {
+    /// Prices an Asian option with adjoint Delta and Vega under bounded memory.
+    ///
+    /// The reverse sweep follows the schedule for the configured checkpoint
+    /// strategy: `Binomial { memory_slots }` stores at most `memory_slots`
+    /// path states (Revolve), `None` stores every step, and the interval
+    /// strategies store their checkpoints plus one segment. The returned
+    /// [`ScheduleReport`] records the measured peak memory, recomputed steps
+    /// and elapsed time.
+    ///
+    /// # Arguments
+    ///
+    /// * `gbm` - GBM parameters
+    /// * `payoff` - Arithmetic or geometric Asian payoff
+    /// * `discount_factor` - Discount factor
+    ///
+    /// # Errors
+    ///
+    /// Returns `CheckpointError::InvalidState` for barrier and lookback
+    /// payoffs, which are not supported by the adjoint sweep, and when a
+    /// [term structure](CheckpointPricer::with_term_structure) is set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pricer_pricing::checkpoint::CheckpointStrategy;
+    /// use pricer_pricing::mc::pricer_checkpoint::{CheckpointPricer, CheckpointPricingConfig};
+    /// use pricer_pricing::mc::{GbmParams, MonteCarloConfig};
+    /// use pricer_pricing::path_dependent::PathPayoffType;
+    ///
+    /// // 10 years of daily steps, at most 16 stored path states
+    /// let mc_config = MonteCarloConfig::builder()
+    ///     .n_paths(100)
+    ///     .n_steps(2520)
+    ///     .seed(42)
+    ///     .build()
+    ///     .unwrap();
+    /// let config = CheckpointPricingConfig::new(
+    ///     mc_config,
+    ///     CheckpointStrategy::Binomial { memory_slots: 16 },
+    /// );
+    /// let pricer = CheckpointPricer::new(config).unwrap();
+    ///
+    /// let gbm = GbmParams { maturity: 10.0, ..Default::default() };
+    /// let payoff = PathPayoffType::asian_arithmetic_call(100.0, 1e-6);
+    /// let (result, report) = pricer
+    ///     .price_with_adjoint(gbm, payoff, (-0.5_f64).exp())
+    ///     .unwrap();
+    ///
+    /// assert!(result.delta.unwrap() > 0.0);
+    /// assert!(report.peak_stored_states <= 16);
+    /// println!(
+    ///     "{} bytes peak, {:.2}x forward work in {:?}",
+    ///     report.peak_memory_bytes,
+    ///     report.recomputation_ratio(),
+    ///     report.elapsed
+    /// );
+    /// ```
+    pub fn price_with_adjoint(
+        &self,
+        gbm: GbmParams,
+        payoff: PathPayoffType<f64>,
+        discount_factor: f64,
+    ) -> CheckpointResult<(PricingResult, ScheduleReport)> {
+        let (average, params) = match payoff {
+            PathPayoffType::AsianArithmetic(asian) => (Average::Arithmetic, *asian.params()),
+            PathPayoffType::AsianGeometric(asian) => (Average::Geometric, *asian.params()),
+            _ => {
+                return Err(CheckpointError::InvalidState {
+                    message: "adjoint sweep supports Asian payoffs only".to_string(),
+                })
+            }
+        };
+        if self.term_structure().is_some() {
+            return Err(CheckpointError::InvalidState {
+                message: "adjoint sweep assumes flat GBM coefficients".to_string(),
+            });
+        }
+
+        let mc_config = &self.config().mc_config;
+        let n_paths = mc_config.n_paths();
+        let n_steps = mc_config.n_steps();
+        let dt = gbm.maturity / n_steps as f64;
+
+        let mut stepper = AsianStepper {
+            seed: mc_config.seed().unwrap_or(0),
+            drift_dt: (gbm.rate - 0.5 * gbm.volatility * gbm.volatility) * dt,
+            vol_sqrt_dt: gbm.volatility * dt.sqrt(),
+            sqrt_dt: dt.sqrt(),
+            vol_dt: gbm.volatility * dt,
+            n_observations: (n_steps + 1) as f64,
+            average,
+            params,
+            randoms: vec![0.0; n_paths],
+            payoff_moments: Moments::default(),
+        };
+        let initial = AsianState {
+            spots: vec![gbm.spot; n_paths],
+            sums: vec![gbm.spot; n_paths],
+            log_sums: vec![gbm.spot.ln(); n_paths],
+        };
+        let mut adjoint = AsianAdjoint {
+            spots: vec![0.0; n_paths],
+            sums: vec![0.0; n_paths],
+            log_sums: vec![0.0; n_paths],
+            vega: 0.0,
+        };
+
+        let schedule =
+            CheckpointSchedule::from_strategy(&self.config().checkpoint_strategy, n_steps);
+        let report = schedule.execute(&mut stepper, initial, &mut adjoint);
+
+        // The initial spot also enters the running sum and log-sum
+        let delta_sum: f64 = (0..n_paths)
+            .map(|i| adjoint.spots[i] + adjoint.sums[i] + adjoint.log_sums[i] / gbm.spot)
+            .sum();
+
+        let n = n_paths as f64;
+        let moments = &stepper.payoff_moments;
+        let result = PricingResult {
+            price: moments.mean(n) * discount_factor,
+            std_error: (moments.variance(n) / n).sqrt() * discount_factor,
+            delta: Some(delta_sum / n * discount_factor),
+            vega: Some(adjoint.vega / n * discount_factor),
+            ..Default::default()
+        };
+        Ok((result, report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkpoint::CheckpointStrategy;
+    use crate::mc::pricer_checkpoint::CheckpointPricingConfig;
+    use crate::mc::MonteCarloConfig;
+    use approx::assert_relative_eq;
+
+    fn create_pricer(n_steps: usize, strategy: CheckpointStrategy) -> CheckpointPricer {
+        let mc_config = MonteCarloConfig::builder()
+            .n_paths(500)
+            .n_steps(n_steps)
+            .seed(42)
+            .build()
+            .unwrap();
+        CheckpointPricer::new(CheckpointPricingConfig::new(mc_config, strategy)).unwrap()
+    }
+
+    #[test]
+    fn test_adjoint_matches_finite_differences() {
+        let pricer = create_pricer(50, CheckpointStrategy::Binomial { memory_slots: 4 });
+        let gbm = GbmParams::default();
+        let df = 0.95;
+
+        for payoff in [
+            PathPayoffType::asian_arithmetic_call(100.0, 0.5),
+            PathPayoffType::asian_arithmetic_put(100.0, 0.5),
+            PathPayoffType::asian_geometric_call(95.0, 0.5),
+        ] {
+            let (result, _) = pricer.price_with_adjoint(gbm, payoff, df).unwrap();
+            let price =
+                |gbm: GbmParams| pricer.price_with_adjoint(gbm, payoff, df).unwrap().0.price;
+
+            let h = 1e-4;
+            let fd_delta = (price(GbmParams {
+                spot: gbm.spot + h,
+                ..gbm
+            }) - price(GbmParams {
+                spot: gbm.spot - h,
+                ..gbm
+            })) / (2.0 * h);
+            let fd_vega = (price(GbmParams {
+                volatility: gbm.volatility + h,
+                ..gbm
+            }) - price(GbmParams {
+                volatility: gbm.volatility - h,
+                ..gbm
+            })) / (2.0 * h);
+
+            assert_relative_eq!(result.delta.unwrap(), fd_delta, max_relative = 1e-5);
+            assert_relative_eq!(result.vega.unwrap(), fd_vega, max_relative = 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_schedules_agree_and_trade_memory_for_time() {
+        let gbm = GbmParams::default();
+        let payoff = PathPayoffType::asian_arithmetic_call(100.0, 1e-6);
+        let n_steps = 200;
+
+        let (tape, tape_report) = create_pricer(n_steps, CheckpointStrategy::None)
+            .price_with_adjoint(gbm, payoff, 1.0)
+            .unwrap();
+        assert_eq!(tape_report.recomputed_steps, 0);
+        assert_eq!(tape_report.peak_stored_states, n_steps);
+
+        for strategy in [
+            CheckpointStrategy::Uniform { interval: 20 },
+            CheckpointStrategy::Binomial { memory_slots: 8 },
+        ] {
+            let (result, report) = create_pricer(n_steps, strategy)
+                .price_with_adjoint(gbm, payoff, 1.0)
+                .unwrap();
+
+            assert_eq!(result.price, tape.price);
+            assert_relative_eq!(
+                result.delta.unwrap(),
+                tape.delta.unwrap(),
+                max_relative = 1e-12
+            );
+            assert_relative_eq!(
+                result.vega.unwrap(),
+                tape.vega.unwrap(),
+                max_relative = 1e-12
+            );
+            assert!(report.peak_memory_bytes < tape_report.peak_memory_bytes / 4);
+            assert!(report.recomputed_steps > 0);
+        }
+    }
+
+    #[test]
+    fn test_ten_year_daily_bounded_memory() {
+        let pricer = create_pricer(2520, CheckpointStrategy::Binomial { memory_slots: 10 });
+        let gbm = GbmParams {
+            maturity: 10.0,
+            ..Default::default()
+        };
+        let (result, report) = pricer
+            .price_with_adjoint(gbm, PathPayoffType::asian_geometric_call(100.0, 1e-6), 1.0)
+            .unwrap();
+
+        assert!(report.peak_stored_states <= 10);
+        assert_eq!(
+            report.peak_memory_bytes,
+            report.peak_stored_states * 3 * 500 * 8
+        );
+        assert!(result.delta.unwrap() > 0.0 && result.delta.unwrap() < 1.0);
+        assert!(result.vega.unwrap() > 0.0);
+    }
+
+    #[test]
+    fn test_rejects_unsupported_payoff() {
+        let pricer = create_pricer(10, CheckpointStrategy::default());
+        let barrier = PathPayoffType::barrier_up_out_call(100.0, 120.0, 1e-6);
+
+        assert!(matches!(
+            pricer.price_with_adjoint(GbmParams::default(), barrier, 1.0),
+            Err(CheckpointError::InvalidState { .. })
+        ));
+    }
+}
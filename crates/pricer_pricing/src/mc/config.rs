@@ -4,6 +4,7 @@
 //! pricing simulations with automatic differentiation support.
 
 use super::error::ConfigError;
+use super::pricer::Greek;
 
 /// Maximum number of simulation paths allowed.
 pub const MAX_PATHS: usize = 10_000_000;
@@ -41,6 +42,99 @@ pub enum AdMode {
     Reverse,
 }
 
+/// Floating-point precision for path simulation.
+///
+/// `F32` stores random draws and paths in single precision, halving memory
+/// traffic, while payoffs are still evaluated and accumulated in `f64`
+/// with compensated summation (see [`precision`](super::precision)).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Precision {
+    /// Double precision throughout.
+    #[default]
+    F64,
+
+    /// Single-precision paths with `f64` accumulation.
+    F32,
+}
+
+/// How the per-step normal draws of a path are turned into increments.
+///
+/// See [`bridge`](super::bridge) for the construction.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PathConstruction {
+    /// Draw `k` drives the increment of step `k`.
+    #[default]
+    Incremental,
+
+    /// Brownian bridge: the first draw fixes the terminal value and later
+    /// draws fill in midpoints, concentrating variance in the leading
+    /// dimensions of the sampler.
+    BrownianBridge,
+}
+
+/// Quantity whose standard error drives adaptive stopping.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ConvergenceTarget {
+    /// Standard error of the discounted price.
+    #[default]
+    Price,
+
+    /// Standard error of a pathwise Greek (Delta, Vega or Rho).
+    Greek(Greek),
+}
+
+/// Adaptive path-count settings.
+///
+/// In adaptive mode the configured `n_paths` is the batch size: batches are
+/// simulated until the standard error of the target falls below
+/// `tolerance` or `max_paths` paths have been used.
+///
+/// # Examples
+///
+/// ```rust
+/// use pricer_pricing::mc::{AdaptiveConfig, ConvergenceTarget, Greek, MonteCarloConfig};
+///
+/// let config = MonteCarloConfig::builder()
+///     .n_paths(10_000)
+///     .n_steps(1)
+///     .adaptive(
+///         AdaptiveConfig::new(0.01, 500_000)
+///             .with_target(ConvergenceTarget::Greek(Greek::Delta)),
+///     )
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(config.adaptive().unwrap().max_paths, 500_000);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AdaptiveConfig {
+    /// Target standard error (absolute).
+    pub tolerance: f64,
+    /// Path budget across all batches.
+    pub max_paths: usize,
+    /// Quantity the tolerance applies to.
+    pub target: ConvergenceTarget,
+}
+
+impl AdaptiveConfig {
+    /// Creates adaptive settings targeting the price standard error.
+    #[inline]
+    pub fn new(tolerance: f64, max_paths: usize) -> Self {
+        Self {
+            tolerance,
+            max_paths,
+            target: ConvergenceTarget::Price,
+        }
+    }
+
+    /// Sets the convergence target.
+    #[inline]
+    pub fn with_target(mut self, target: ConvergenceTarget) -> Self {
+        self.target = target;
+        self
+    }
+}
+
 /// Monte Carlo simulation configuration.
 ///
 /// Immutable configuration specifying simulation parameters.
@@ -72,6 +166,14 @@ pub struct MonteCarloConfig {
     ad_mode: AdMode,
     /// Optional seed for reproducibility.
     seed: Option<u64>,
+    /// Adaptive path-count settings (`None` for a fixed path count).
+    adaptive: Option<AdaptiveConfig>,
+    /// Path simulation precision.
+    precision: Precision,
+    /// Path construction from normal draws.
+    path_construction: PathConstruction,
+    /// Whether the terminal normal is stratified across paths.
+    stratify_terminal: bool,
 }
 
 impl MonteCarloConfig {
@@ -117,6 +219,30 @@ impl MonteCarloConfig {
         self.seed
     }
 
+    /// Returns the adaptive path-count settings, if enabled.
+    #[inline]
+    pub fn adaptive(&self) -> Option<AdaptiveConfig> {
+        self.adaptive
+    }
+
+    /// Returns the path simulation precision.
+    #[inline]
+    pub fn precision(&self) -> Precision {
+        self.precision
+    }
+
+    /// Returns the path construction.
+    #[inline]
+    pub fn path_construction(&self) -> PathConstruction {
+        self.path_construction
+    }
+
+    /// Returns whether the terminal normal is stratified across paths.
+    #[inline]
+    pub fn stratify_terminal(&self) -> bool {
+        self.stratify_terminal
+    }
+
     /// Validates the configuration.
     ///
     /// # Errors
@@ -124,6 +250,8 @@ impl MonteCarloConfig {
     /// Returns `ConfigError` if:
     /// - `n_paths` is 0 or greater than 10,000,000
     /// - `n_steps` is 0 or greater than 10,000
+    /// - adaptive settings have a non-positive tolerance, a path budget
+    ///   below `n_paths`, or a Greek target other than Delta, Vega or Rho
     pub fn validate(&self) -> Result<(), ConfigError> {
         if self.n_paths == 0 || self.n_paths > MAX_PATHS {
             return Err(ConfigError::InvalidPathCount(self.n_paths));
@@ -131,6 +259,31 @@ impl MonteCarloConfig {
         if self.n_steps == 0 || self.n_steps > MAX_STEPS {
             return Err(ConfigError::InvalidStepCount(self.n_steps));
         }
+        if let Some(adaptive) = self.adaptive {
+            if !(adaptive.tolerance > 0.0 && adaptive.tolerance.is_finite()) {
+                return Err(ConfigError::InvalidParameter {
+                    name: "tolerance",
+                    value: format!("{} must be positive and finite", adaptive.tolerance),
+                });
+            }
+            if adaptive.max_paths < self.n_paths {
+                return Err(ConfigError::InvalidParameter {
+                    name: "max_paths",
+                    value: format!(
+                        "{} must be at least the batch size {}",
+                        adaptive.max_paths, self.n_paths
+                    ),
+                });
+            }
+            if let ConvergenceTarget::Greek(greek) = adaptive.target {
+                if !matches!(greek, Greek::Delta | Greek::Vega | Greek::Rho) {
+                    return Err(ConfigError::InvalidParameter {
+                        name: "target",
+                        value: format!("{:?} has no pathwise estimator", greek),
+                    });
+                }
+            }
+        }
         Ok(())
     }
 }
@@ -158,6 +311,10 @@ pub struct MonteCarloConfigBuilder {
     n_steps: Option<usize>,
     ad_mode: AdMode,
     seed: Option<u64>,
+    adaptive: Option<AdaptiveConfig>,
+    precision: Precision,
+    path_construction: PathConstruction,
+    stratify_terminal: bool,
 }
 
 impl MonteCarloConfigBuilder {
@@ -205,6 +362,55 @@ impl MonteCarloConfigBuilder {
         self
     }
 
+    /// Enables adaptive path-count control.
+    ///
+    /// # Arguments
+    ///
+    /// * `adaptive` - Tolerance, path budget and convergence target
+    #[inline]
+    pub fn adaptive(mut self, adaptive: AdaptiveConfig) -> Self {
+        self.adaptive = Some(adaptive);
+        self
+    }
+
+    /// Sets the path simulation precision.
+    ///
+    /// # Arguments
+    ///
+    /// * `precision` - `F64` (default) or `F32`
+    #[inline]
+    pub fn precision(mut self, precision: Precision) -> Self {
+        self.precision = precision;
+        self
+    }
+
+    /// Sets the path construction.
+    ///
+    /// # Arguments
+    ///
+    /// * `path_construction` - `Incremental` (default) or `BrownianBridge`
+    #[inline]
+    pub fn path_construction(mut self, path_construction: PathConstruction) -> Self {
+        self.path_construction = path_construction;
+        self
+    }
+
+    /// Enables stratification of the terminal normal across paths.
+    ///
+    /// Path `i` of `n` draws its terminal normal from the `i`-th of `n`
+    /// equiprobable strata. Because only the first draw of a Brownian
+    /// bridge fixes the terminal value, paths are then built with the
+    /// bridge regardless of [`path_construction`](Self::path_construction).
+    ///
+    /// # Arguments
+    ///
+    /// * `stratify` - Whether to stratify the terminal normal
+    #[inline]
+    pub fn stratify_terminal(mut self, stratify: bool) -> Self {
+        self.stratify_terminal = stratify;
+        self
+    }
+
     /// Builds the configuration.
     ///
     /// # Errors
@@ -212,6 +418,7 @@ impl MonteCarloConfigBuilder {
     /// Returns `ConfigError` if:
     /// - `n_paths` not set or invalid
     /// - `n_steps` not set or invalid
+    /// - adaptive settings are invalid
     pub fn build(self) -> Result<MonteCarloConfig, ConfigError> {
         let n_paths = self.n_paths.ok_or(ConfigError::InvalidParameter {
             name: "n_paths",
@@ -228,6 +435,10 @@ impl MonteCarloConfigBuilder {
             n_steps,
             ad_mode: self.ad_mode,
             seed: self.seed,
+            adaptive: self.adaptive,
+            precision: self.precision,
+            path_construction: self.path_construction,
+            stratify_terminal: self.stratify_terminal,
         };
 
         config.validate()?;
@@ -251,6 +462,8 @@ mod tests {
         assert_eq!(config.n_steps(), 252);
         assert_eq!(config.ad_mode(), AdMode::NoAd);
         assert_eq!(config.seed(), None);
+        assert_eq!(config.adaptive(), None);
+        assert_eq!(config.precision(), Precision::F64);
     }
 
     #[test]
@@ -341,4 +554,72 @@ mod tests {
     fn test_ad_mode_default() {
         assert_eq!(AdMode::default(), AdMode::NoAd);
     }
+
+    #[test]
+    fn test_config_adaptive_valid() {
+        let config = MonteCarloConfig::builder()
+            .n_paths(1000)
+            .n_steps(10)
+            .adaptive(AdaptiveConfig::new(0.05, 100_000))
+            .build()
+            .unwrap();
+
+        let adaptive = config.adaptive().unwrap();
+        assert_eq!(adaptive.tolerance, 0.05);
+        assert_eq!(adaptive.target, ConvergenceTarget::Price);
+    }
+
+    #[test]
+    fn test_config_adaptive_invalid() {
+        let build = |adaptive| {
+            MonteCarloConfig::builder()
+                .n_paths(1000)
+                .n_steps(10)
+                .adaptive(adaptive)
+                .build()
+        };
+
+        assert!(matches!(
+            build(AdaptiveConfig::new(0.0, 10_000)),
+            Err(ConfigError::InvalidParameter {
+                name: "tolerance",
+                ..
+            })
+        ));
+        assert!(matches!(
+            build(AdaptiveConfig::new(0.01, 500)),
+            Err(ConfigError::InvalidParameter {
+                name: "max_paths",
+                ..
+            })
+        ));
+        assert!(matches!(
+            build(
+                AdaptiveConfig::new(0.01, 10_000)
+                    .with_target(ConvergenceTarget::Greek(Greek::Gamma))
+            ),
+            Err(ConfigError::InvalidParameter { name: "target", .. })
+        ));
+    }
+
+    #[test]
+    fn test_config_path_construction() {
+        let default = MonteCarloConfig::builder()
+            .n_paths(1000)
+            .n_steps(10)
+            .build()
+            .unwrap();
+        assert_eq!(default.path_construction(), PathConstruction::Incremental);
+        assert!(!default.stratify_terminal());
+
+        let config = MonteCarloConfig::builder()
+            .n_paths(1000)
+            .n_steps(10)
+            .path_construction(PathConstruction::BrownianBridge)
+            .stratify_terminal(true)
+            .build()
+            .unwrap();
+        assert_eq!(config.path_construction(), PathConstruction::BrownianBridge);
+        assert!(config.stratify_terminal());
+    }
 }
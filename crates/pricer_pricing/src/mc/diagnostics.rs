@@ -0,0 +1,251 @@
+//! Numerical error budget and condition diagnostics.
+//!
+//! Every Monte Carlo result carries a [`PricingDiagnostics`] record in
+//! [`PricingResult::diagnostics`](super::PricingResult::diagnostics)
+//! describing how much the number can be trusted:
+//!
+//! - **Statistical error**: standard error and 95% confidence interval
+//! - **Smoothing bias**: mean difference between the smoothed payoff used for
+//!   AD and the exact payoff, measured on the simulated paths
+//! - **Discretisation**: steps and step size (the log-Euler GBM scheme is
+//!   exact at the grid points, so the step size only biases path-dependent
+//!   payoffs through discrete monitoring)
+//! - **Fallbacks**: any numerical shortcut taken in place of the preferred
+//!   method, such as finite differences instead of Enzyme AD
+//!
+//! [`PricingDiagnostics::error_budget`] combines the statistical and
+//! smoothing terms so downstream systems can flag low-confidence numbers
+//! with a single comparison.
+
+use super::config::{MonteCarloConfig, PathConstruction, Precision};
+use super::payoff::{PayoffParams, PayoffType};
+use super::pricer::Greek;
+
+/// Two-sided 95% normal quantile.
+const Z_95: f64 = 1.96;
+
+/// A numerical fallback taken while producing a result.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Fallback {
+    /// Greek computed by central finite differences instead of Enzyme AD.
+    FiniteDifference(Greek),
+    /// Greek requested but not available for this payoff; left unset.
+    Unsupported(Greek),
+    /// Adaptive simulation exhausted its path budget before converging.
+    AdaptiveNotConverged,
+}
+
+/// Error budget and condition metadata for a Monte Carlo result.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PricingDiagnostics {
+    /// Number of simulated paths.
+    pub n_paths: usize,
+    /// Number of time steps per path.
+    pub n_steps: usize,
+    /// Time step in years (maturity / steps).
+    pub time_step: f64,
+    /// Standard error of the discounted price.
+    pub std_error: f64,
+    /// 95% confidence interval for the price.
+    pub confidence_interval: (f64, f64),
+    /// Payoff smoothing width, when a smoothed payoff was used.
+    pub smoothing_epsilon: Option<f64>,
+    /// Discounted mean of smoothed minus exact payoff, when measurable.
+    pub smoothing_bias: Option<f64>,
+    /// Floating-point precision of the simulated paths.
+    pub precision: Precision,
+    /// Path construction used for the normal draws.
+    pub path_construction: PathConstruction,
+    /// Fallbacks taken, in the order they occurred.
+    pub fallbacks: Vec<Fallback>,
+}
+
+impl PricingDiagnostics {
+    /// Creates diagnostics for a simulation run under `config`.
+    pub fn new(
+        config: &MonteCarloConfig,
+        n_paths: usize,
+        maturity: f64,
+        price: f64,
+        std_error: f64,
+    ) -> Self {
+        let half_width = Z_95 * std_error;
+        Self {
+            n_paths,
+            n_steps: config.n_steps(),
+            time_step: maturity / config.n_steps() as f64,
+            std_error,
+            confidence_interval: (price - half_width, price + half_width),
+            smoothing_epsilon: None,
+            smoothing_bias: None,
+            precision: config.precision(),
+            path_construction: config.path_construction(),
+            fallbacks: Vec::new(),
+        }
+    }
+
+    /// Records the payoff smoothing width and its measured bias.
+    pub fn with_smoothing(mut self, epsilon: f64, bias: Option<f64>) -> Self {
+        self.smoothing_epsilon = Some(epsilon);
+        self.smoothing_bias = bias;
+        self
+    }
+
+    /// Records a fallback.
+    pub fn push_fallback(&mut self, fallback: Fallback) {
+        self.fallbacks.push(fallback);
+    }
+
+    /// Returns whether any fallback was taken.
+    #[inline]
+    pub fn has_fallbacks(&self) -> bool {
+        !self.fallbacks.is_empty()
+    }
+
+    /// Returns the absolute error budget: the 95% confidence half-width plus
+    /// the magnitude of the smoothing bias.
+    #[inline]
+    pub fn error_budget(&self) -> f64 {
+        Z_95 * self.std_error + self.smoothing_bias.map_or(0.0, f64::abs)
+    }
+
+    /// Returns whether the error budget exceeds `tolerance` relative to
+    /// `price` (absolute `tolerance` when the price is zero).
+    pub fn is_low_confidence(&self, price: f64, tolerance: f64) -> bool {
+        let scale = if price == 0.0 { 1.0 } else { price.abs() };
+        self.error_budget() > tolerance * scale
+    }
+}
+
+/// Discounted mean of smoothed minus exact European payoffs.
+///
+/// `terminals` and `smoothed` are the per-path terminal prices and the
+/// smoothed payoffs evaluated on them.
+pub(crate) fn smoothing_bias(
+    terminals: impl Iterator<Item = f64>,
+    smoothed: &[f64],
+    payoff: PayoffParams,
+    discount_factor: f64,
+) -> f64 {
+    if smoothed.is_empty() {
+        return 0.0;
+    }
+    let total: f64 = terminals
+        .zip(smoothed)
+        .map(|(terminal, &value)| value - exact_payoff(terminal, payoff))
+        .sum();
+    discount_factor * total / smoothed.len() as f64
+}
+
+/// Unsmoothed European payoff.
+#[inline]
+pub(crate) fn exact_payoff(terminal: f64, payoff: PayoffParams) -> f64 {
+    match payoff.payoff_type {
+        PayoffType::Call => (terminal - payoff.strike).max(0.0),
+        PayoffType::Put => (payoff.strike - terminal).max(0.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mc::{GbmParams, MonteCarloPricer};
+    use approx::assert_relative_eq;
+
+    fn pricer() -> MonteCarloPricer {
+        let config = MonteCarloConfig::builder()
+            .n_paths(5_000)
+            .n_steps(10)
+            .seed(7)
+            .build()
+            .unwrap();
+        MonteCarloPricer::new(config).unwrap()
+    }
+
+    #[test]
+    fn test_european_diagnostics() {
+        let mut pricer = pricer();
+        let gbm = GbmParams::default();
+        let result = pricer.price_european(gbm, PayoffParams::call(100.0), 0.95);
+        let diag = result.diagnostics.as_ref().unwrap();
+
+        assert_eq!(diag.n_paths, 5_000);
+        assert_eq!(diag.n_steps, 10);
+        assert_relative_eq!(diag.time_step, gbm.maturity / 10.0);
+        assert_eq!(diag.std_error, result.std_error);
+        let (lo, hi) = diag.confidence_interval;
+        assert_relative_eq!(hi - lo, 2.0 * result.confidence_95(), max_relative = 1e-12);
+        assert!(lo < result.price && result.price < hi);
+        assert_eq!(diag.smoothing_epsilon, Some(1e-4));
+        assert!(!diag.has_fallbacks());
+    }
+
+    #[test]
+    fn test_smoothing_bias_grows_with_epsilon() {
+        let gbm = GbmParams::default();
+        let sharp = pricer().price_european(gbm, PayoffParams::call(100.0), 1.0);
+        let blunt = pricer().price_european(gbm, PayoffParams::call(100.0).with_epsilon(1.0), 1.0);
+        let sharp_bias = sharp.diagnostics.unwrap().smoothing_bias.unwrap();
+        let blunt_bias = blunt.diagnostics.unwrap().smoothing_bias.unwrap();
+
+        // Softplus lies above max(x, 0), so the bias is positive
+        assert!((0.0..1e-4).contains(&sharp_bias));
+        assert!(blunt_bias > 10.0 * sharp_bias);
+        // Same paths, so the price difference is exactly the bias difference
+        assert_relative_eq!(
+            blunt.price - sharp.price,
+            blunt_bias - sharp_bias,
+            max_relative = 1e-9
+        );
+    }
+
+    #[test]
+    fn test_finite_difference_greeks_are_flagged() {
+        let mut pricer = pricer();
+        let result = pricer.price_with_greeks(
+            GbmParams::default(),
+            PayoffParams::call(100.0),
+            0.95,
+            &[Greek::Delta, Greek::Vega],
+        );
+        let diag = result.diagnostics.unwrap();
+        assert_eq!(
+            diag.fallbacks,
+            [
+                Fallback::FiniteDifference(Greek::Delta),
+                Fallback::FiniteDifference(Greek::Vega)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unsupported_path_dependent_greek_is_flagged() {
+        use crate::path_dependent::PathPayoffType;
+
+        let mut pricer = pricer();
+        let result = pricer.price_path_dependent_with_greeks(
+            GbmParams::default(),
+            PathPayoffType::asian_arithmetic_call(100.0, 1e-4),
+            0.95,
+            &[Greek::Vanna],
+        );
+        assert!(result.vanna.is_none());
+        let diag = result.diagnostics.unwrap();
+        assert_eq!(diag.fallbacks, [Fallback::Unsupported(Greek::Vanna)]);
+        assert_eq!(diag.smoothing_epsilon, Some(1e-4));
+        assert_eq!(diag.smoothing_bias, None);
+    }
+
+    #[test]
+    fn test_error_budget_and_low_confidence() {
+        let diag = PricingDiagnostics {
+            std_error: 0.1,
+            smoothing_bias: Some(-0.05),
+            ..Default::default()
+        };
+        assert_relative_eq!(diag.error_budget(), 0.246, max_relative = 1e-12);
+        assert!(diag.is_low_confidence(10.0, 0.01));
+        assert!(!diag.is_low_confidence(10.0, 0.05));
+        assert!(diag.is_low_confidence(0.0, 0.1));
+    }
+}
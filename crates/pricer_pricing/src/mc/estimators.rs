@@ -0,0 +1,601 @@
+//! Pathwise and likelihood-ratio Greeks estimators.
+//!
+//! Both estimators compute sensitivities from a single set of simulated
+//! paths, without revaluation:
+//!
+//! - **Pathwise**: differentiates the payoff along each path,
+//!   `∂V/∂θ = DF × E[f'(S_T) × ∂S_T/∂θ]`. Unbiased and low-variance for
+//!   payoffs that are Lipschitz in the path (vanillas), but biased for
+//!   digitals and barriers whose derivative vanishes almost surely.
+//! - **Likelihood ratio**: differentiates the transition density instead,
+//!   `∂V/∂θ = DF × E[f(S) × ∂ln p/∂θ]`. Needs no payoff derivative, so it
+//!   handles discontinuous payoffs, at the cost of higher variance.
+//!
+//! The estimator is selected per instrument through
+//! [`GreeksConfig::estimator`](crate::greeks::GreeksConfig). Whenever both
+//! estimators are valid for the payoff, the per-path variance of each is
+//! reported alongside the Greeks so the choice can be checked.
+//!
+//! # Score Functions
+//!
+//! For the log-space GBM step `ln S[i+1] = ln S[i] + (r - σ²/2)dt + σ√dt Z[i]`:
+//!
+//! | Parameter | Score |
+//! |-----------|-------|
+//! | Spot | `Z[0] / (S₀ σ √dt)` |
+//! | Volatility | `Σ ((Z[i]² - 1)/σ - Z[i] √dt)` |
+//! | Rate | `Σ Z[i] √dt / σ` |
+//!
+//! Gamma uses the second-order spot weight
+//! `(Z[0]² - 1)/(S₀² σ² dt) - Z[0]/(S₀² σ √dt)` with either estimator, since
+//! the pathwise second derivative of a kinked payoff is zero almost surely.
+
+use super::bridge::shape_normals;
+use super::error::ConfigError;
+use super::paths::GbmParams;
+use super::payoff::{compute_payoff, PayoffParams, PayoffType};
+use super::pricer::{Greek, MonteCarloPricer};
+use crate::greeks::{GreeksConfig, GreeksEstimator, GreeksResult};
+use crate::path_dependent::{PathObserver, PathPayoffType};
+use crate::pool::with_run_arena;
+
+/// Payoff specification for the estimator-based Greeks engine.
+#[derive(Clone, Copy, Debug)]
+pub enum EstimatorPayoff {
+    /// European call or put (smoothed payoff).
+    Vanilla(PayoffParams),
+    /// Cash-or-nothing digital paying `cash` when in the money at expiry.
+    Digital {
+        /// Strike price.
+        strike: f64,
+        /// Call pays above the strike, put below.
+        payoff_type: PayoffType,
+        /// Cash amount paid when in the money.
+        cash: f64,
+    },
+    /// Path-dependent payoff (Asian, barrier, lookback).
+    PathDependent(PathPayoffType<f64>),
+}
+
+impl EstimatorPayoff {
+    /// Creates a cash-or-nothing digital call paying 1.
+    #[inline]
+    pub fn digital_call(strike: f64) -> Self {
+        Self::Digital {
+            strike,
+            payoff_type: PayoffType::Call,
+            cash: 1.0,
+        }
+    }
+
+    /// Creates a cash-or-nothing digital put paying 1.
+    #[inline]
+    pub fn digital_put(strike: f64) -> Self {
+        Self::Digital {
+            strike,
+            payoff_type: PayoffType::Put,
+            cash: 1.0,
+        }
+    }
+
+    /// Returns whether the payoff admits an unbiased pathwise derivative.
+    ///
+    /// Only terminal vanillas qualify; digitals are discontinuous and the
+    /// pathwise engine does not differentiate through path statistics.
+    #[inline]
+    pub fn supports_pathwise(&self) -> bool {
+        matches!(self, Self::Vanilla(_))
+    }
+
+    /// Evaluates the undiscounted payoff on a path `[S₀, S₁, ..., S_T]`.
+    pub fn evaluate(&self, path: &[f64]) -> f64 {
+        let terminal = path[path.len() - 1];
+        match *self {
+            Self::Vanilla(params) => compute_payoff(terminal, params),
+            Self::Digital {
+                strike,
+                payoff_type,
+                cash,
+            } => {
+                let in_the_money = match payoff_type {
+                    PayoffType::Call => terminal > strike,
+                    PayoffType::Put => terminal < strike,
+                };
+                if in_the_money {
+                    cash
+                } else {
+                    0.0
+                }
+            }
+            Self::PathDependent(path_payoff) => {
+                let mut observer: PathObserver<f64> = PathObserver::new();
+                for &s in path {
+                    observer.observe(s);
+                }
+                observer.set_terminal(terminal);
+                path_payoff.compute(&[], &observer)
+            }
+        }
+    }
+
+    /// Resolves `Auto` to a concrete estimator for this payoff.
+    #[inline]
+    pub fn resolve(&self, estimator: GreeksEstimator) -> GreeksEstimator {
+        match estimator {
+            GreeksEstimator::Auto if self.supports_pathwise() => GreeksEstimator::Pathwise,
+            GreeksEstimator::Auto => GreeksEstimator::LikelihoodRatio,
+            other => other,
+        }
+    }
+}
+
+/// Per-path variance of each estimator for one Greek.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EstimatorVariance {
+    /// The Greek being estimated.
+    pub greek: Greek,
+    /// Variance of the pathwise estimator (`None` if not applicable).
+    pub pathwise: Option<f64>,
+    /// Variance of the likelihood-ratio estimator.
+    pub likelihood_ratio: f64,
+}
+
+impl EstimatorVariance {
+    /// Likelihood-ratio variance divided by pathwise variance.
+    ///
+    /// Values above 1 mean the pathwise estimator is more efficient.
+    #[inline]
+    pub fn ratio(&self) -> Option<f64> {
+        self.pathwise
+            .filter(|&v| v > 0.0)
+            .map(|v| self.likelihood_ratio / v)
+    }
+}
+
+/// Greeks computed with a Monte Carlo estimator.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EstimatorResult {
+    /// Price and Greeks (Delta, Gamma, Vega, Rho).
+    pub greeks: GreeksResult<f64>,
+    /// Estimator used for the first-order Greeks.
+    pub estimator: GreeksEstimator,
+    /// Number of simulated paths.
+    pub n_paths: usize,
+    /// Estimator variances for Delta, Gamma, Vega and Rho.
+    pub variances: Vec<EstimatorVariance>,
+}
+
+impl EstimatorResult {
+    /// Returns the variance comparison for a Greek.
+    pub fn variance(&self, greek: Greek) -> Option<&EstimatorVariance> {
+        self.variances.iter().find(|v| v.greek == greek)
+    }
+
+    /// Standard error of a Greek under the selected estimator.
+    pub fn std_error(&self, greek: Greek) -> Option<f64> {
+        let variance = self.variance(greek)?;
+        let var = match self.estimator {
+            GreeksEstimator::Pathwise => variance.pathwise?,
+            _ => variance.likelihood_ratio,
+        };
+        Some((var / self.n_paths as f64).sqrt())
+    }
+}
+
+/// Running sum and sum of squares of per-path samples.
+#[derive(Clone, Copy, Debug, Default)]
+pub(super) struct Moments {
+    sum: f64,
+    sum_sq: f64,
+}
+
+impl Moments {
+    #[inline]
+    pub(super) fn add(&mut self, x: f64) {
+        self.sum += x;
+        self.sum_sq += x * x;
+    }
+
+    #[inline]
+    pub(super) fn mean(&self, n: f64) -> f64 {
+        self.sum / n
+    }
+
+    #[inline]
+    pub(super) fn variance(&self, n: f64) -> f64 {
+        let mean = self.mean(n);
+        ((self.sum_sq - n * mean * mean) / (n - 1.0)).max(0.0)
+    }
+}
+
+impl MonteCarloPricer {
+    /// Prices a payoff with Greeks from the estimator chosen in `config`.
+    ///
+    /// Delta, Vega and Rho use the pathwise or likelihood-ratio estimator
+    /// (`Auto` picks pathwise for vanillas, likelihood ratio otherwise);
+    /// Gamma always uses the likelihood-ratio weight. Rho is `∂V/∂r` with
+    /// the discount factor treated as `exp(-rT)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::InvalidParameter` if the pathwise estimator is
+    /// requested for a payoff that does not support it, if the volatility
+    /// is not positive (the score functions divide by σ), or if a
+    /// [term structure](MonteCarloPricer::with_term_structure) is set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pricer_pricing::greeks::{GreeksConfig, GreeksEstimator};
+    /// use pricer_pricing::mc::{EstimatorPayoff, GbmParams, MonteCarloConfig, MonteCarloPricer};
+    ///
+    /// let config = MonteCarloConfig::builder()
+    ///     .n_paths(10_000)
+    ///     .n_steps(1)
+    ///     .seed(42)
+    ///     .build()
+    ///     .unwrap();
+    /// let mut pricer = MonteCarloPricer::new(config).unwrap();
+    ///
+    /// let greeks_config = GreeksConfig::builder()
+    ///     .estimator(GreeksEstimator::LikelihoodRatio)
+    ///     .build()
+    ///     .unwrap();
+    /// let result = pricer
+    ///     .price_with_estimator(
+    ///         GbmParams::default(),
+    ///         EstimatorPayoff::digital_call(100.0),
+    ///         (-0.05_f64).exp(),
+    ///         &greeks_config,
+    ///     )
+    ///     .unwrap();
+    /// println!("Digital delta: {:?}", result.greeks.delta);
+    /// ```
+    pub fn price_with_estimator(
+        &mut self,
+        gbm: GbmParams,
+        payoff: EstimatorPayoff,
+        discount_factor: f64,
+        config: &GreeksConfig,
+    ) -> Result<EstimatorResult, ConfigError> {
+        let estimator = payoff.resolve(config.estimator);
+        if estimator == GreeksEstimator::Pathwise && !payoff.supports_pathwise() {
+            return Err(ConfigError::InvalidParameter {
+                name: "estimator",
+                value: "pathwise estimator requires a Lipschitz payoff; \
+                        use the likelihood ratio for digitals and path-dependent payoffs"
+                    .to_string(),
+            });
+        }
+        if self.term_structure().is_some() {
+            return Err(ConfigError::InvalidParameter {
+                name: "term_structure",
+                value: "estimator scores assume flat GBM coefficients".to_string(),
+            });
+        }
+        if gbm.volatility <= 0.0 || !gbm.volatility.is_finite() {
+            return Err(ConfigError::InvalidParameter {
+                name: "volatility",
+                value: format!("{} (must be positive)", gbm.volatility),
+            });
+        }
+
+        let n_paths = self.config().n_paths();
+        let n_steps = self.config().n_steps();
+
+        // Per-call scratch comes from the thread's run arena
+        with_run_arena(|arena| {
+            let randoms = arena.alloc_slice(n_paths * n_steps);
+            self.rng.fill_normal(randoms);
+            shape_normals(&self.config, randoms, n_paths, n_steps);
+
+            let dt = gbm.maturity / n_steps as f64;
+            let sqrt_dt = dt.sqrt();
+            let sigma = gbm.volatility;
+            let spot = gbm.spot;
+            let drift_dt = (gbm.rate - 0.5 * sigma * sigma) * dt;
+            let vol_sqrt_dt = sigma * sqrt_dt;
+            let pathwise = payoff.supports_pathwise();
+
+            let path = arena.alloc_slice(n_steps + 1);
+
+            let mut price = Moments::default();
+            let mut lr_delta = Moments::default();
+            let mut lr_gamma = Moments::default();
+            let mut lr_vega = Moments::default();
+            let mut lr_rho = Moments::default();
+            let mut pw_delta = Moments::default();
+            let mut pw_vega = Moments::default();
+            let mut pw_rho = Moments::default();
+
+            for z_path in randoms.chunks_exact(n_steps) {
+                path[0] = spot;
+                let mut vega_score = 0.0;
+                let mut z_sum = 0.0;
+                for (step, &z) in z_path.iter().enumerate() {
+                    path[step + 1] = path[step] * (drift_dt + vol_sqrt_dt * z).exp();
+                    vega_score += (z * z - 1.0) / sigma - z * sqrt_dt;
+                    z_sum += z;
+                }
+                let terminal = path[n_steps];
+
+                let value = payoff.evaluate(path);
+                let pv = value * discount_factor;
+                price.add(pv);
+
+                // Likelihood ratio: payoff × score
+                let z0 = z_path[0];
+                let delta_score = z0 / (spot * vol_sqrt_dt);
+                let gamma_score = (z0 * z0 - 1.0) / (spot * spot * vol_sqrt_dt * vol_sqrt_dt)
+                    - z0 / (spot * spot * vol_sqrt_dt);
+                let rho_score = z_sum * sqrt_dt / sigma;
+
+                lr_delta.add(pv * delta_score);
+                lr_gamma.add(pv * gamma_score);
+                lr_vega.add(pv * vega_score);
+                lr_rho.add(pv * (rho_score - gbm.maturity));
+
+                // Pathwise: f'(S_T) × ∂S_T/∂θ
+                if let EstimatorPayoff::Vanilla(params) = payoff {
+                    let slope = match params.payoff_type {
+                        PayoffType::Call if terminal > params.strike => 1.0,
+                        PayoffType::Put if terminal < params.strike => -1.0,
+                        _ => 0.0,
+                    } * discount_factor;
+                    let log_return = (terminal / spot).ln();
+                    let d_terminal_d_vol = terminal
+                        * (log_return - (gbm.rate + 0.5 * sigma * sigma) * gbm.maturity)
+                        / sigma;
+
+                    pw_delta.add(slope * terminal / spot);
+                    pw_vega.add(slope * d_terminal_d_vol);
+                    pw_rho.add(slope * terminal * gbm.maturity - pv * gbm.maturity);
+                }
+            }
+
+            let n = n_paths as f64;
+            let variance = |greek, pw: &Moments, lr: &Moments| EstimatorVariance {
+                greek,
+                pathwise: pathwise.then(|| pw.variance(n)),
+                likelihood_ratio: lr.variance(n),
+            };
+            let variances = vec![
+                variance(Greek::Delta, &pw_delta, &lr_delta),
+                EstimatorVariance {
+                    greek: Greek::Gamma,
+                    pathwise: None,
+                    likelihood_ratio: lr_gamma.variance(n),
+                },
+                variance(Greek::Vega, &pw_vega, &lr_vega),
+                variance(Greek::Rho, &pw_rho, &lr_rho),
+            ];
+
+            let (delta, vega, rho) = if estimator == GreeksEstimator::Pathwise {
+                (pw_delta.mean(n), pw_vega.mean(n), pw_rho.mean(n))
+            } else {
+                (lr_delta.mean(n), lr_vega.mean(n), lr_rho.mean(n))
+            };
+
+            let greeks = GreeksResult::new(price.mean(n), (price.variance(n) / n).sqrt())
+                .with_delta(delta)
+                .with_gamma(lr_gamma.mean(n))
+                .with_vega(vega)
+                .with_rho(rho);
+
+            Ok(EstimatorResult {
+                greeks,
+                estimator,
+                n_paths,
+                variances,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mc::MonteCarloConfig;
+
+    fn create_pricer(n_paths: usize, n_steps: usize) -> MonteCarloPricer {
+        let config = MonteCarloConfig::builder()
+            .n_paths(n_paths)
+            .n_steps(n_steps)
+            .seed(42)
+            .build()
+            .unwrap();
+        MonteCarloPricer::new(config).unwrap()
+    }
+
+    fn config(estimator: GreeksEstimator) -> GreeksConfig {
+        GreeksConfig::builder()
+            .estimator(estimator)
+            .build()
+            .unwrap()
+    }
+
+    fn df() -> f64 {
+        (-0.05_f64).exp()
+    }
+
+    #[test]
+    fn test_auto_resolution() {
+        let vanilla = EstimatorPayoff::Vanilla(PayoffParams::call(100.0));
+        let digital = EstimatorPayoff::digital_call(100.0);
+
+        assert_eq!(
+            vanilla.resolve(GreeksEstimator::Auto),
+            GreeksEstimator::Pathwise
+        );
+        assert_eq!(
+            digital.resolve(GreeksEstimator::Auto),
+            GreeksEstimator::LikelihoodRatio
+        );
+        assert_eq!(
+            vanilla.resolve(GreeksEstimator::LikelihoodRatio),
+            GreeksEstimator::LikelihoodRatio
+        );
+    }
+
+    #[test]
+    fn test_pathwise_rejected_for_digital() {
+        let mut pricer = create_pricer(1_000, 1);
+        let result = pricer.price_with_estimator(
+            GbmParams::default(),
+            EstimatorPayoff::digital_call(100.0),
+            df(),
+            &config(GreeksEstimator::Pathwise),
+        );
+
+        assert!(matches!(
+            result,
+            Err(ConfigError::InvalidParameter {
+                name: "estimator",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_pathwise_vanilla_matches_black_scholes() {
+        let mut pricer = create_pricer(100_000, 1);
+        let result = pricer
+            .price_with_estimator(
+                GbmParams::default(),
+                EstimatorPayoff::Vanilla(PayoffParams::call(100.0)),
+                df(),
+                &config(GreeksEstimator::Auto),
+            )
+            .unwrap();
+
+        assert_eq!(result.estimator, GreeksEstimator::Pathwise);
+        // Black-Scholes: S=100, K=100, r=5%, σ=20%, T=1
+        assert!((result.greeks.price - 10.4506).abs() < 0.15);
+        assert!((result.greeks.delta.unwrap() - 0.6368).abs() < 0.01);
+        assert!((result.greeks.vega.unwrap() - 37.524).abs() < 0.6);
+        assert!((result.greeks.rho.unwrap() - 53.232).abs() < 0.8);
+        assert!((result.greeks.gamma.unwrap() - 0.018762).abs() < 0.002);
+    }
+
+    #[test]
+    fn test_likelihood_ratio_digital_matches_black_scholes() {
+        let mut pricer = create_pricer(200_000, 1);
+        let result = pricer
+            .price_with_estimator(
+                GbmParams::default(),
+                EstimatorPayoff::digital_call(100.0),
+                df(),
+                &config(GreeksEstimator::Auto),
+            )
+            .unwrap();
+
+        assert_eq!(result.estimator, GreeksEstimator::LikelihoodRatio);
+        // Cash-or-nothing call: DF × N(d2) = 0.5323, delta = DF × n(d2)/(Sσ√T) = 0.01876
+        assert!((result.greeks.price - 0.5323).abs() < 0.005);
+        assert!((result.greeks.delta.unwrap() - 0.018762).abs() < 0.0005);
+        assert!(result.variance(Greek::Delta).unwrap().pathwise.is_none());
+    }
+
+    #[test]
+    fn test_likelihood_ratio_agrees_with_pathwise_on_vanilla() {
+        let payoff = EstimatorPayoff::Vanilla(PayoffParams::put(100.0));
+        let gbm = GbmParams::default();
+
+        let pw = create_pricer(100_000, 4)
+            .price_with_estimator(gbm, payoff, df(), &config(GreeksEstimator::Pathwise))
+            .unwrap();
+        let lr = create_pricer(100_000, 4)
+            .price_with_estimator(gbm, payoff, df(), &config(GreeksEstimator::LikelihoodRatio))
+            .unwrap();
+
+        let delta_se = lr.std_error(Greek::Delta).unwrap();
+        let vega_se = lr.std_error(Greek::Vega).unwrap();
+        assert!((pw.greeks.delta.unwrap() - lr.greeks.delta.unwrap()).abs() < 4.0 * delta_se);
+        assert!((pw.greeks.vega.unwrap() - lr.greeks.vega.unwrap()).abs() < 4.0 * vega_se);
+    }
+
+    #[test]
+    fn test_variance_comparison_favours_pathwise_for_vanilla() {
+        let mut pricer = create_pricer(20_000, 1);
+        let result = pricer
+            .price_with_estimator(
+                GbmParams::default(),
+                EstimatorPayoff::Vanilla(PayoffParams::call(100.0)),
+                df(),
+                &config(GreeksEstimator::Auto),
+            )
+            .unwrap();
+
+        for greek in [Greek::Delta, Greek::Vega, Greek::Rho] {
+            let ratio = result.variance(greek).unwrap().ratio().unwrap();
+            assert!(ratio > 1.0, "{:?} variance ratio {}", greek, ratio);
+        }
+        assert!(result.std_error(Greek::Delta).unwrap() > 0.0);
+    }
+
+    #[test]
+    fn test_likelihood_ratio_barrier_delta_matches_bump() {
+        let payoff = PathPayoffType::barrier_up_out_call(100.0, 130.0, 1e-6);
+        let gbm = GbmParams::default();
+        let n_paths = 100_000;
+        let n_steps = 20;
+
+        let lr = create_pricer(n_paths, n_steps)
+            .price_with_estimator(
+                gbm,
+                EstimatorPayoff::PathDependent(payoff),
+                df(),
+                &config(GreeksEstimator::Auto),
+            )
+            .unwrap();
+
+        let bump = 1.0;
+        let up = create_pricer(n_paths, n_steps)
+            .price_path_dependent(
+                GbmParams {
+                    spot: gbm.spot + bump,
+                    ..gbm
+                },
+                payoff,
+                df(),
+            )
+            .price;
+        let down = create_pricer(n_paths, n_steps)
+            .price_path_dependent(
+                GbmParams {
+                    spot: gbm.spot - bump,
+                    ..gbm
+                },
+                payoff,
+                df(),
+            )
+            .price;
+        let fd_delta = (up - down) / (2.0 * bump);
+
+        let se = lr.std_error(Greek::Delta).unwrap();
+        assert!(
+            (lr.greeks.delta.unwrap() - fd_delta).abs() < 4.0 * se,
+            "LR delta {} vs bump {} (se {})",
+            lr.greeks.delta.unwrap(),
+            fd_delta,
+            se
+        );
+    }
+
+    #[test]
+    fn test_rejects_zero_volatility() {
+        let mut pricer = create_pricer(1_000, 1);
+        let gbm = GbmParams {
+            volatility: 0.0,
+            ..GbmParams::default()
+        };
+        let result = pricer.price_with_estimator(
+            gbm,
+            EstimatorPayoff::digital_call(100.0),
+            df(),
+            &config(GreeksEstimator::LikelihoodRatio),
+        );
+
+        assert!(result.is_err());
+    }
+}
@@ -12,6 +12,14 @@
 //! - Smooth payoff functions for AD compatibility
 //! - Greeks via bump-and-revalue (placeholder for Enzyme AD)
 //! - Manual tangent propagation for Delta (forward-mode AD prototype)
+//! - Pathwise and likelihood-ratio Greeks estimators ([`estimators`])
+//! - Runtime-dispatched SIMD path kernels (`simd` feature)
+//! - Adaptive path counts with convergence targets ([`adaptive`])
+//! - Multi-instrument batch pricing with shared paths ([`batch`])
+//! - Single-precision paths with `f64` accumulation ([`precision`])
+//! - Error budget and fallback diagnostics on every result ([`diagnostics`])
+//! - Piecewise-constant volatility, rate and dividend term structures
+//!   ([`term_structure`])
 //!
 //! Phase 4 will integrate actual Enzyme `#[autodiff]` macros.
 //!
@@ -143,26 +151,51 @@
 //! println!("Price: {:.4}, Delta: {:.4}", price, delta);
 //! ```
 
+pub mod adaptive;
+pub mod batch;
+pub mod bridge;
+pub mod checkpoint_adjoint;
 pub mod config;
+pub mod diagnostics;
 pub mod error;
+pub mod estimators;
 pub mod paths;
 pub mod payoff;
+pub mod precision;
 pub mod pricer;
 pub mod pricer_checkpoint;
+#[cfg(feature = "simd")]
+pub mod simd;
+pub mod term_structure;
 pub mod thread_local;
 pub mod workspace;
 pub mod workspace_checkpoint;
 
 // Re-exports for convenient access
-pub use config::{AdMode, MonteCarloConfig, MonteCarloConfigBuilder};
+pub use adaptive::ConvergenceReport;
+pub use batch::InstrumentSpec;
+pub use bridge::BrownianBridge;
+pub use config::{
+    AdMode, AdaptiveConfig, ConvergenceTarget, MonteCarloConfig, MonteCarloConfigBuilder,
+    PathConstruction, Precision,
+};
+pub use diagnostics::{Fallback, PricingDiagnostics};
 pub use error::ConfigError;
-pub use paths::{generate_gbm_paths, GbmParams};
+pub use estimators::{EstimatorPayoff, EstimatorResult, EstimatorVariance};
+pub use paths::{
+    generate_gbm_paths, generate_gbm_paths_scalar, generate_gbm_paths_stepwise,
+    generate_gbm_paths_term_structure, GbmParams,
+};
 pub use payoff::{
     asian_arithmetic_call_smooth, asian_arithmetic_put_smooth, compute_payoff, compute_payoffs,
     european_call_smooth, european_put_smooth, soft_plus, soft_plus_derivative, PayoffParams,
     PayoffType,
 };
+pub use precision::KahanSum;
 pub use pricer::{Greek, MonteCarloPricer, PricingResult};
+#[cfg(feature = "simd")]
+pub use simd::SimdLevel;
+pub use term_structure::{GbmTermStructure, PiecewiseConstant};
 pub use thread_local::{
     current_thread_index, DefaultWorkspaceFactory, ParallelWorkspaces, ThreadLocalWorkspacePool,
     WorkspaceFactory,
@@ -17,7 +17,9 @@
 //! Paths are stored in row-major order: `paths[path_idx * (n_steps + 1) + step_idx]`
 //! where `step_idx = 0` contains the initial spot price.
 
+use super::term_structure::GbmTermStructure;
 use super::workspace::PathWorkspace;
+use num_traits::Float;
 
 /// Parameters for Geometric Brownian Motion path generation.
 ///
@@ -134,6 +136,8 @@ impl Default for GbmParams {
 /// - No heap allocations within the loop
 /// - Uses precomputed constants for efficiency
 /// - Cache-friendly row-major traversal
+/// - With the `simd` feature, dispatches at runtime to a vectorised kernel
+///   (see [`SimdLevel`](super::simd::SimdLevel)) when the CPU supports it
 pub fn generate_gbm_paths(
     workspace: &mut PathWorkspace,
     params: GbmParams,
@@ -151,6 +155,58 @@ pub fn generate_gbm_paths(
     let vol_sqrt_dt = params.volatility * dt.sqrt();
 
     let (paths, randoms) = workspace.paths_mut_and_randoms();
+
+    // Vectorised kernel when the CPU supports it
+    #[cfg(feature = "simd")]
+    if super::simd::generate_gbm_paths_simd(
+        paths,
+        randoms,
+        params.spot,
+        drift_dt,
+        vol_sqrt_dt,
+        n_paths,
+        n_steps,
+    ) {
+        return;
+    }
+
+    generate_gbm_paths_scalar(
+        paths,
+        randoms,
+        params.spot,
+        drift_dt,
+        vol_sqrt_dt,
+        n_paths,
+        n_steps,
+    );
+}
+
+/// Scalar GBM path kernel over raw row-major buffers.
+///
+/// This is the reference implementation behind [`generate_gbm_paths`]; with
+/// the `simd` feature it is used only when no vector unit is detected. It is
+/// public so benchmarks and parity tests can compare against it directly.
+/// Generic over the float type so the same kernel serves the `f32`
+/// simulation mode (see [`Precision`](super::Precision)).
+///
+/// # Arguments
+///
+/// * `paths` - Output buffer of `n_paths × (n_steps + 1)` prices
+/// * `randoms` - Standard normal draws, `n_paths × n_steps`
+/// * `spot` - Initial spot price
+/// * `drift_dt` - Precomputed `(r - 0.5σ²)dt`
+/// * `vol_sqrt_dt` - Precomputed `σ√dt`
+/// * `n_paths` - Number of paths
+/// * `n_steps` - Number of time steps
+pub fn generate_gbm_paths_scalar<T: Float>(
+    paths: &mut [T],
+    randoms: &[T],
+    spot: T,
+    drift_dt: T,
+    vol_sqrt_dt: T,
+    n_paths: usize,
+    n_steps: usize,
+) {
     let n_steps_plus_1 = n_steps + 1;
 
     // Generate paths (outer loop over paths, inner over steps)
@@ -159,7 +215,7 @@ pub fn generate_gbm_paths(
         let random_offset = path_idx * n_steps;
 
         // Set initial spot
-        paths[path_offset] = params.spot;
+        paths[path_offset] = spot;
 
         // Evolve path
         for step in 0..n_steps {
@@ -170,6 +226,75 @@ pub fn generate_gbm_paths(
     }
 }
 
+/// Generates GBM paths with time-dependent coefficients.
+///
+/// Each step uses the drift and volatility integrated from
+/// `term_structure` over the step (see
+/// [`GbmTermStructure::step_coefficients`]); components the term structure
+/// leaves unset fall back to `params`.
+///
+/// # Arguments
+///
+/// * `workspace` - Pre-allocated workspace with random samples filled
+/// * `params` - GBM parameters (spot, maturity and flat fallbacks)
+/// * `term_structure` - Time-dependent volatility, rate and dividend yield
+/// * `n_paths` - Number of paths to generate
+/// * `n_steps` - Number of time steps
+pub fn generate_gbm_paths_term_structure(
+    workspace: &mut PathWorkspace,
+    params: GbmParams,
+    term_structure: &GbmTermStructure,
+    n_paths: usize,
+    n_steps: usize,
+) {
+    debug_assert!(n_paths <= workspace.capacity_paths());
+    debug_assert!(n_steps <= workspace.capacity_steps());
+
+    let (drift, vol) = term_structure.step_coefficients(&params, n_steps);
+    let (paths, randoms) = workspace.paths_mut_and_randoms();
+    generate_gbm_paths_stepwise(paths, randoms, params.spot, &drift, &vol, n_paths, n_steps);
+}
+
+/// Scalar GBM path kernel with a drift and volatility per step.
+///
+/// Used by [`generate_gbm_paths_term_structure`] for time-dependent
+/// parameters.
+///
+/// # Arguments
+///
+/// * `paths` - Output buffer of `n_paths × (n_steps + 1)` prices
+/// * `randoms` - Standard normal draws, `n_paths × n_steps`
+/// * `spot` - Initial spot price
+/// * `drift` - Log drift of each step, `n_steps` values
+/// * `vol` - Volatility of each step (`√∫σ²dt`), `n_steps` values
+/// * `n_paths` - Number of paths
+/// * `n_steps` - Number of time steps
+pub fn generate_gbm_paths_stepwise<T: Float>(
+    paths: &mut [T],
+    randoms: &[T],
+    spot: T,
+    drift: &[T],
+    vol: &[T],
+    n_paths: usize,
+    n_steps: usize,
+) {
+    debug_assert!(drift.len() >= n_steps && vol.len() >= n_steps);
+    let n_steps_plus_1 = n_steps + 1;
+
+    for path_idx in 0..n_paths {
+        let path_offset = path_idx * n_steps_plus_1;
+        let random_offset = path_idx * n_steps;
+
+        paths[path_offset] = spot;
+
+        for step in 0..n_steps {
+            let z = randoms[random_offset + step];
+            let increment = drift[step] + vol[step] * z;
+            paths[path_offset + step + 1] = paths[path_offset + step] * increment.exp();
+        }
+    }
+}
+
 /// Generates GBM paths with dual (tangent) values for forward-mode AD.
 ///
 /// Computes both primal paths and their tangent with respect to spot.
@@ -266,6 +391,7 @@ pub fn terminal_prices(workspace: &PathWorkspace, n_paths: usize, n_steps: usize
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::mc::term_structure::PiecewiseConstant;
     use crate::rng::PricerRng;
     use approx::assert_relative_eq;
 
@@ -425,4 +551,104 @@ mod tests {
             }
         }
     }
+
+    fn term_structure() -> GbmTermStructure {
+        GbmTermStructure::new()
+            .with_volatility(PiecewiseConstant::new(vec![0.3, 1.0], vec![0.1, 0.3]).unwrap())
+            .with_rate(PiecewiseConstant::new(vec![0.5, 2.0], vec![0.02, 0.04]).unwrap())
+            .with_dividend_yield(PiecewiseConstant::flat(0.01))
+    }
+
+    #[test]
+    fn test_step_coefficients_integrate_term_structure() {
+        let params = GbmParams::default();
+        let ts = term_structure();
+        // Pillars at 0.3 and 0.5 fall inside steps
+        let (drift, vol) = ts.step_coefficients(&params, 4);
+
+        let variance: f64 = vol.iter().map(|v| v * v).sum();
+        assert_relative_eq!(variance, 0.3 * 0.01 + 0.7 * 0.09, epsilon = 1e-14);
+        assert_relative_eq!(ts.total_variance(&params), variance, epsilon = 1e-14);
+        // First step [0, 0.25] sits entirely in the first vol period
+        assert_relative_eq!(vol[0], 0.1 * 0.25_f64.sqrt(), epsilon = 1e-14);
+
+        let carry = 0.5 * 0.02 + 0.5 * 0.04 - 0.01;
+        assert_relative_eq!(
+            drift.iter().sum::<f64>(),
+            carry - 0.5 * variance,
+            epsilon = 1e-14
+        );
+        assert_relative_eq!(ts.forward(&params), 100.0 * carry.exp(), epsilon = 1e-12);
+        assert_relative_eq!(
+            ts.discount_factor(&params),
+            (-0.03_f64).exp(),
+            epsilon = 1e-14
+        );
+    }
+
+    #[test]
+    fn test_flat_term_structure_matches_flat_params() {
+        let params = GbmParams::default();
+        let ts = GbmTermStructure::new()
+            .with_volatility(PiecewiseConstant::flat(params.volatility))
+            .with_rate(PiecewiseConstant::flat(params.rate));
+        let mut ws1 = setup_workspace_with_randoms(20, 12, 7);
+        let mut ws2 = setup_workspace_with_randoms(20, 12, 7);
+
+        generate_gbm_paths(&mut ws1, params, 20, 12);
+        generate_gbm_paths_term_structure(&mut ws2, params, &ts, 20, 12);
+
+        for (p1, p2) in ws1.paths().iter().zip(ws2.paths()) {
+            assert_relative_eq!(*p1, *p2, max_relative = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_term_structure_terminal_moments() {
+        // E[S(T)] is the forward and Var[ln S(T)] the integrated variance
+        let n_paths = 50_000;
+        let n_steps = 8;
+        let mut workspace = setup_workspace_with_randoms(n_paths, n_steps, 42);
+        let params = GbmParams::default();
+        let ts = term_structure();
+
+        generate_gbm_paths_term_structure(&mut workspace, params, &ts, n_paths, n_steps);
+
+        let terminals = terminal_prices(&workspace, n_paths, n_steps);
+        let mean = terminals.iter().sum::<f64>() / n_paths as f64;
+        assert_relative_eq!(mean, ts.forward(&params), max_relative = 0.01);
+
+        let logs: Vec<f64> = terminals.iter().map(|s| s.ln()).collect();
+        let log_mean = logs.iter().sum::<f64>() / n_paths as f64;
+        let log_var =
+            logs.iter().map(|l| (l - log_mean).powi(2)).sum::<f64>() / (n_paths - 1) as f64;
+        assert_relative_eq!(log_var, ts.total_variance(&params), max_relative = 0.02);
+    }
+
+    #[test]
+    fn test_shifts_apply_to_term_structure() {
+        let params = GbmParams::default();
+        let ts = term_structure();
+
+        let up = ts.with_volatility_shift(0.01, 0.001);
+        assert_relative_eq!(
+            up.total_variance(&params),
+            0.3 * 0.11 * 0.11 + 0.7 * 0.31 * 0.31,
+            epsilon = 1e-14
+        );
+        let down = ts.with_volatility_shift(-0.2, 0.001);
+        assert!(down.is_valid());
+        assert_relative_eq!(
+            down.total_variance(&params),
+            0.3 * 1e-6 + 0.7 * 0.01,
+            epsilon = 1e-14
+        );
+
+        let rate_up = ts.with_rate_shift(0.01);
+        assert_relative_eq!(
+            rate_up.discount_factor(&params),
+            ts.discount_factor(&params) * (-0.01_f64).exp(),
+            epsilon = 1e-14
+        );
+    }
 }
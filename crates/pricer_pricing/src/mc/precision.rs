@@ -0,0 +1,330 @@
+//! Single-precision path simulation with mixed-precision accumulation.
+//!
+//! With [`Precision::F32`](super::Precision) the pricer stores random draws
+//! and paths as `f32`, halving the memory traffic of the path buffers, which
+//! dominates large runs. Only the storage is narrowed:
+//!
+//! - Draws come from the same `f64` stream, generated in chunks of
+//!   [`F32_CHUNK_PATHS`] paths and rounded once to `f32`, so no full-size
+//!   `f64` buffer is allocated
+//! - Paths are evolved in `f32` by the generic
+//!   [`generate_gbm_paths_scalar`](super::generate_gbm_paths_scalar)
+//! - Payoffs are evaluated in `f64` from the `f32` terminal prices
+//! - Sums and sums of squares use [`KahanSum`] in `f64`
+//!
+//! # Accuracy
+//!
+//! Each path accumulates roughly `√n_steps` ulps of `f32` rounding
+//! (~1e-7 relative per step), so prices agree with the `f64` mode to about
+//! 1e-5 relative for a few hundred steps, well inside the Monte Carlo
+//! standard error of any practical path count.
+
+use super::bridge::shape_normals_block;
+use super::config::Precision;
+use super::diagnostics::{exact_payoff, PricingDiagnostics};
+use super::paths::{generate_gbm_paths_scalar, generate_gbm_paths_stepwise, GbmParams};
+use super::payoff::{compute_payoff, PayoffParams};
+use super::pricer::{MonteCarloPricer, PricingResult};
+
+/// Kahan-Babuška (Neumaier) compensated `f64` summation.
+///
+/// # Examples
+///
+/// ```rust
+/// use pricer_pricing::mc::KahanSum;
+///
+/// let mut sum = KahanSum::default();
+/// sum.add(1.0);
+/// for _ in 0..10 {
+///     sum.add(1e-16);
+/// }
+/// assert_eq!(sum.value(), 1.000_000_000_000_001);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct KahanSum {
+    sum: f64,
+    compensation: f64,
+}
+
+impl KahanSum {
+    /// Adds a value to the running sum.
+    #[inline]
+    pub fn add(&mut self, value: f64) {
+        let t = self.sum + value;
+        if self.sum.abs() >= value.abs() {
+            self.compensation += (self.sum - t) + value;
+        } else {
+            self.compensation += (value - t) + self.sum;
+        }
+        self.sum = t;
+    }
+
+    /// Returns the compensated sum.
+    #[inline]
+    pub fn value(&self) -> f64 {
+        self.sum + self.compensation
+    }
+}
+
+/// Number of paths whose `f64` draws are generated at once in `f32` mode.
+pub const F32_CHUNK_PATHS: usize = 1024;
+
+/// Single-precision simulation buffers, allocated on first use.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SinglePrecisionBuffers {
+    randoms: Vec<f32>,
+    paths: Vec<f32>,
+    /// `f64` draws of one chunk before narrowing.
+    chunk: Vec<f64>,
+}
+
+impl SinglePrecisionBuffers {
+    /// Grows the buffers to hold `n_paths × n_steps` draws (never shrinks).
+    fn ensure_capacity(&mut self, n_paths: usize, n_steps: usize) {
+        let n_randoms = n_paths * n_steps;
+        let n_prices = n_paths * (n_steps + 1);
+        let n_chunk = n_paths.min(F32_CHUNK_PATHS) * n_steps;
+        if self.chunk.len() < n_chunk {
+            self.chunk.resize(n_chunk, 0.0);
+        }
+        if self.randoms.len() < n_randoms {
+            self.randoms.resize(n_randoms, 0.0);
+        }
+        if self.paths.len() < n_prices {
+            self.paths.resize(n_prices, 0.0);
+        }
+    }
+}
+
+impl MonteCarloPricer {
+    /// Prices a European option with `f32` paths and `f64` accumulation.
+    ///
+    /// Called by [`price_european`](Self::price_european) when the
+    /// configuration selects [`Precision::F32`]; consumes the same random
+    /// stream as the `f64` mode without touching the `f64` workspace.
+    pub(crate) fn price_european_f32(
+        &mut self,
+        gbm: GbmParams,
+        payoff: PayoffParams,
+        discount_factor: f64,
+    ) -> PricingResult {
+        debug_assert_eq!(self.config().precision(), Precision::F32);
+        let n_paths = self.config().n_paths();
+        let n_steps = self.config().n_steps();
+
+        // Draw in f64 (shared stream) chunk by chunk, narrowing each once
+        self.f32_buffers.ensure_capacity(n_paths, n_steps);
+        let n_randoms = n_paths * n_steps;
+        let buffers = &mut self.f32_buffers;
+        let mut first_path = 0;
+        while first_path < n_paths {
+            let chunk_paths = F32_CHUNK_PATHS.min(n_paths - first_path);
+            let chunk = &mut buffers.chunk[..chunk_paths * n_steps];
+            self.rng.fill_normal(chunk);
+            shape_normals_block(
+                &self.config,
+                chunk,
+                first_path,
+                chunk_paths,
+                n_paths,
+                n_steps,
+            );
+            let narrow = &mut buffers.randoms[first_path * n_steps..][..chunk_paths * n_steps];
+            for (narrow, &z) in narrow.iter_mut().zip(chunk.iter()) {
+                *narrow = z as f32;
+            }
+            first_path += chunk_paths;
+        }
+
+        let paths = &mut self.f32_buffers.paths[..n_paths * (n_steps + 1)];
+        let randoms = &self.f32_buffers.randoms[..n_randoms];
+        match &self.term_structure {
+            Some(ts) => {
+                let (drift, vol) = ts.step_coefficients(&gbm, n_steps);
+                let narrow = |v: Vec<f64>| v.into_iter().map(|x| x as f32).collect::<Vec<_>>();
+                generate_gbm_paths_stepwise(
+                    paths,
+                    randoms,
+                    gbm.spot as f32,
+                    &narrow(drift),
+                    &narrow(vol),
+                    n_paths,
+                    n_steps,
+                );
+            }
+            None => {
+                let dt = gbm.maturity / n_steps as f64;
+                let drift_dt = (gbm.rate - 0.5 * gbm.volatility * gbm.volatility) * dt;
+                let vol_sqrt_dt = gbm.volatility * dt.sqrt();
+                generate_gbm_paths_scalar(
+                    paths,
+                    randoms,
+                    gbm.spot as f32,
+                    drift_dt as f32,
+                    vol_sqrt_dt as f32,
+                    n_paths,
+                    n_steps,
+                );
+            }
+        }
+
+        let mut sum = KahanSum::default();
+        let mut sum_sq = KahanSum::default();
+        let mut bias = KahanSum::default();
+        for path in paths.chunks_exact(n_steps + 1) {
+            let terminal = f64::from(path[n_steps]);
+            let value = compute_payoff(terminal, payoff);
+            sum.add(value);
+            sum_sq.add(value * value);
+            bias.add(value - exact_payoff(terminal, payoff));
+        }
+
+        let n = n_paths as f64;
+        let mean = sum.value() / n;
+        let variance = ((sum_sq.value() - n * mean * mean) / (n - 1.0)).max(0.0);
+        let std_error = (variance / n).sqrt();
+
+        let price = mean * discount_factor;
+        let std_error = std_error * discount_factor;
+        PricingResult {
+            price,
+            std_error,
+            diagnostics: Some(
+                PricingDiagnostics::new(&self.config, n_paths, gbm.maturity, price, std_error)
+                    .with_smoothing(
+                        payoff.smoothing_epsilon,
+                        Some(bias.value() / n * discount_factor),
+                    ),
+            ),
+            #[cfg(feature = "l1l2-integration")]
+            metadata: Some(self.run_metadata(&(gbm, payoff, discount_factor))),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mc::{Greek, MonteCarloConfig, PathConstruction};
+    use approx::assert_relative_eq;
+
+    fn create_pricer(precision: Precision, n_steps: usize) -> MonteCarloPricer {
+        let config = MonteCarloConfig::builder()
+            .n_paths(20_000)
+            .n_steps(n_steps)
+            .seed(42)
+            .precision(precision)
+            .build()
+            .unwrap();
+        MonteCarloPricer::new(config).unwrap()
+    }
+
+    #[test]
+    fn test_kahan_sum_recovers_small_terms() {
+        let mut naive = 0.0_f64;
+        let mut kahan = KahanSum::default();
+        naive += 1e8;
+        kahan.add(1e8);
+        for _ in 0..1_000_000 {
+            naive += 1e-8;
+            kahan.add(1e-8);
+        }
+
+        assert_relative_eq!(kahan.value(), 1e8 + 1e-2, max_relative = 1e-15);
+        assert!((naive - (1e8 + 1e-2)).abs() > 1e-6);
+    }
+
+    #[test]
+    fn test_kahan_sum_cancellation() {
+        let mut sum = KahanSum::default();
+        for value in [1.0, 1e100, 1.0, -1e100] {
+            sum.add(value);
+        }
+        assert_eq!(sum.value(), 2.0);
+    }
+
+    #[test]
+    fn test_f32_matches_f64_price() {
+        let gbm = GbmParams::default();
+        let df = (-0.05_f64).exp();
+
+        for n_steps in [1, 50, 252] {
+            for payoff in [PayoffParams::call(100.0), PayoffParams::put(90.0)] {
+                let single = create_pricer(Precision::F32, n_steps).price_european(gbm, payoff, df);
+                let double = create_pricer(Precision::F64, n_steps).price_european(gbm, payoff, df);
+
+                assert_relative_eq!(single.price, double.price, max_relative = 1e-4);
+                assert_relative_eq!(single.std_error, double.std_error, max_relative = 1e-3);
+                // Precision loss must stay far below the statistical error
+                assert!((single.price - double.price).abs() < 0.01 * double.std_error);
+            }
+        }
+    }
+
+    #[test]
+    fn test_f32_greeks_match_f64() {
+        let gbm = GbmParams::default();
+        let payoff = PayoffParams::call(100.0);
+        let df = (-0.05_f64).exp();
+
+        let single = create_pricer(Precision::F32, 20).price_with_greeks(
+            gbm,
+            payoff,
+            df,
+            &[Greek::Delta, Greek::Vega],
+        );
+        let double = create_pricer(Precision::F64, 20).price_with_greeks(
+            gbm,
+            payoff,
+            df,
+            &[Greek::Delta, Greek::Vega],
+        );
+
+        assert_relative_eq!(
+            single.delta.unwrap(),
+            double.delta.unwrap(),
+            max_relative = 1e-3
+        );
+        assert_relative_eq!(
+            single.vega.unwrap(),
+            double.vega.unwrap(),
+            max_relative = 1e-3
+        );
+    }
+
+    #[test]
+    fn test_f32_draws_in_chunks_without_f64_workspace() {
+        let mut pricer = create_pricer(Precision::F32, 50);
+        pricer.price_european(GbmParams::default(), PayoffParams::call(100.0), 0.95);
+
+        assert_eq!(pricer.workspace.capacity_paths(), 0);
+        assert_eq!(pricer.f32_buffers.chunk.len(), F32_CHUNK_PATHS * 50);
+    }
+
+    #[test]
+    fn test_f32_chunks_match_f64_with_stratified_bridge() {
+        // Strata are indexed across chunks, so the shaped draws must agree
+        let create = |precision| {
+            let config = MonteCarloConfig::builder()
+                .n_paths(20_000)
+                .n_steps(16)
+                .seed(7)
+                .precision(precision)
+                .path_construction(PathConstruction::BrownianBridge)
+                .stratify_terminal(true)
+                .build()
+                .unwrap();
+            MonteCarloPricer::new(config).unwrap()
+        };
+        let gbm = GbmParams::default();
+        let payoff = PayoffParams::call(100.0);
+        let df = (-0.05_f64).exp();
+
+        let single = create(Precision::F32).price_european(gbm, payoff, df);
+        let double = create(Precision::F64).price_european(gbm, payoff, df);
+
+        assert_relative_eq!(single.price, double.price, max_relative = 1e-4);
+        assert_relative_eq!(single.std_error, double.std_error, max_relative = 1e-3);
+    }
+}
@@ -17,13 +17,25 @@
 //! The pricer maintains an internal [`PathWorkspace`](super::workspace::PathWorkspace)
 //! that is reused across pricing calls, minimising memory allocations.
 
-use super::config::MonteCarloConfig;
+use super::adaptive::ConvergenceReport;
+use super::bridge::shape_normals;
+use super::config::{MonteCarloConfig, Precision};
+use super::diagnostics::{smoothing_bias, Fallback, PricingDiagnostics};
 use super::error::ConfigError;
-use super::paths::{generate_gbm_paths, generate_gbm_paths_tangent_spot, GbmParams};
+use super::paths::{
+    generate_gbm_paths, generate_gbm_paths_tangent_spot, generate_gbm_paths_term_structure,
+    GbmParams,
+};
 use super::payoff::{compute_payoff, compute_payoffs, PayoffParams};
+use super::precision::SinglePrecisionBuffers;
+use super::term_structure::GbmTermStructure;
 use super::workspace::PathWorkspace;
 use crate::path_dependent::{PathObserver, PathPayoffType};
 use crate::rng::PricerRng;
+#[cfg(feature = "l1l2-integration")]
+use pricer_core::types::{fingerprint, RunMetadata};
+#[cfg(feature = "l1l2-integration")]
+use std::fmt;
 
 /// Greek type for selection.
 ///
@@ -42,6 +54,7 @@ use crate::rng::PricerRng;
 /// - `Vanna`: ∂²V/∂S∂σ - Cross sensitivity (delta-vol)
 /// - `Volga`: ∂²V/∂σ² - Volatility convexity (also known as vomma)
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Greek {
     // First-order Greeks
     /// Delta: ∂V/∂S (sensitivity to spot price)
@@ -94,6 +107,9 @@ pub enum Greek {
 ///     rho: None,
 ///     vanna: None,
 ///     volga: None,
+///     convergence: None,
+///     diagnostics: None,
+///     ..Default::default()
 /// };
 ///
 /// println!("Price: {} +/- {}", result.price, result.std_error * 1.96);
@@ -122,6 +138,18 @@ pub struct PricingResult {
     pub vanna: Option<f64>,
     /// Volga: ∂²V/∂σ² (volatility convexity, also known as vomma).
     pub volga: Option<f64>,
+
+    /// Achieved precision when priced in adaptive mode.
+    pub convergence: Option<ConvergenceReport>,
+
+    /// Error budget and condition metadata (see [`diagnostics`](super::diagnostics)).
+    pub diagnostics: Option<PricingDiagnostics>,
+
+    /// Provenance for reproducing the number: seed, versions, input hashes.
+    ///
+    /// Requires the `l1l2-integration` feature, which provides `RunMetadata`.
+    #[cfg(feature = "l1l2-integration")]
+    pub metadata: Option<RunMetadata>,
 }
 
 impl PricingResult {
@@ -136,6 +164,32 @@ impl PricingResult {
     pub fn confidence_99(&self) -> f64 {
         2.576 * self.std_error
     }
+
+    /// Returns whether the numerical error budget exceeds `tolerance`
+    /// relative to the price.
+    ///
+    /// Falls back to the 95% confidence half-width when no diagnostics
+    /// were recorded.
+    pub fn is_low_confidence(&self, tolerance: f64) -> bool {
+        match &self.diagnostics {
+            Some(diagnostics) => diagnostics.is_low_confidence(self.price, tolerance),
+            None => {
+                let scale = if self.price == 0.0 {
+                    1.0
+                } else {
+                    self.price.abs()
+                };
+                self.confidence_95() > tolerance * scale
+            }
+        }
+    }
+
+    /// Records a fallback in the diagnostics, if any are attached.
+    pub(crate) fn push_fallback(&mut self, fallback: Fallback) {
+        if let Some(diagnostics) = &mut self.diagnostics {
+            diagnostics.push_fallback(fallback);
+        }
+    }
 }
 
 /// Monte Carlo pricing engine.
@@ -172,10 +226,16 @@ impl PricingResult {
 /// println!("Price: {} +/- {}", result.price, result.std_error);
 /// ```
 pub struct MonteCarloPricer {
-    config: MonteCarloConfig,
-    workspace: PathWorkspace,
+    /// Configuration (pub(crate) so samplers can borrow it beside the workspace).
+    pub(crate) config: MonteCarloConfig,
+    /// Path workspace (pub(crate) for batched engines).
+    pub(crate) workspace: PathWorkspace,
+    /// Single-precision buffers for `Precision::F32` (empty until used).
+    pub(crate) f32_buffers: SinglePrecisionBuffers,
     /// Random number generator (pub(crate) for Enzyme AD access).
     pub(crate) rng: PricerRng,
+    /// Optional piecewise-constant coefficients overriding the flat GBM ones.
+    pub(crate) term_structure: Option<GbmTermStructure>,
 }
 
 impl MonteCarloPricer {
@@ -192,13 +252,15 @@ impl MonteCarloPricer {
         config.validate()?;
 
         let seed = config.seed().unwrap_or(0);
-        let workspace = PathWorkspace::new(config.n_paths(), config.n_steps());
+        let workspace = Self::initial_workspace(&config);
         let rng = PricerRng::from_seed(seed);
 
         Ok(Self {
             config,
             workspace,
+            f32_buffers: SinglePrecisionBuffers::default(),
             rng,
+            term_structure: None,
         })
     }
 
@@ -217,22 +279,109 @@ impl MonteCarloPricer {
     pub fn with_seed(config: MonteCarloConfig, seed: u64) -> Result<Self, ConfigError> {
         config.validate()?;
 
-        let workspace = PathWorkspace::new(config.n_paths(), config.n_steps());
+        let workspace = Self::initial_workspace(&config);
         let rng = PricerRng::from_seed(seed);
 
         Ok(Self {
             config,
             workspace,
+            f32_buffers: SinglePrecisionBuffers::default(),
             rng,
+            term_structure: None,
         })
     }
 
+    /// Pre-sizes the `f64` workspace, leaving it empty in `f32` mode where
+    /// European pricing runs out of the single-precision buffers; other
+    /// methods grow it on demand.
+    fn initial_workspace(config: &MonteCarloConfig) -> PathWorkspace {
+        match config.precision() {
+            Precision::F32 => PathWorkspace::new(0, 0),
+            Precision::F64 => PathWorkspace::new(config.n_paths(), config.n_steps()),
+        }
+    }
+
     /// Returns a reference to the configuration.
     #[inline]
     pub fn config(&self) -> &MonteCarloConfig {
         &self.config
     }
 
+    /// Simulates paths under piecewise-constant volatility, rate and
+    /// dividend curves instead of the flat [`GbmParams`] coefficients.
+    ///
+    /// Curves left unset in `term_structure` fall back to the flat values,
+    /// so the spot and maturity still come from the `GbmParams` passed to
+    /// each pricing call.
+    pub fn with_term_structure(mut self, term_structure: GbmTermStructure) -> Self {
+        self.term_structure = Some(term_structure);
+        self
+    }
+
+    /// Returns the term structure, if one is set.
+    #[inline]
+    pub fn term_structure(&self) -> Option<&GbmTermStructure> {
+        self.term_structure.as_ref()
+    }
+
+    /// Returns the discount factor to maturity implied by the pricer's
+    /// rate: the integrated term structure if set, otherwise `exp(-r T)`.
+    pub fn discount_factor(&self, gbm: GbmParams) -> f64 {
+        match &self.term_structure {
+            Some(ts) => ts.discount_factor(&gbm),
+            None => (-gbm.rate * gbm.maturity).exp(),
+        }
+    }
+
+    /// Generates paths into the workspace from the normals already drawn,
+    /// honouring the term structure if one is set.
+    pub(crate) fn generate_paths(&mut self, gbm: GbmParams, n_paths: usize, n_steps: usize) {
+        match &self.term_structure {
+            Some(ts) => {
+                generate_gbm_paths_term_structure(&mut self.workspace, gbm, ts, n_paths, n_steps)
+            }
+            None => generate_gbm_paths(&mut self.workspace, gbm, n_paths, n_steps),
+        }
+    }
+
+    /// Runs `f` with the term structure's volatility and rate curves
+    /// shifted, restoring the original afterwards.
+    ///
+    /// Without a term structure this just runs `f`; bump Greeks shift the
+    /// flat `GbmParams` alongside, which then covers both cases.
+    pub(crate) fn with_shifted_term_structure<R>(
+        &mut self,
+        vol_shift: f64,
+        rate_shift: f64,
+        f: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        let base = self.term_structure.take();
+        self.term_structure = base.as_ref().map(|ts| {
+            let mut shifted = ts.clone();
+            if vol_shift != 0.0 {
+                shifted = shifted.with_volatility_shift(vol_shift, 0.001);
+            }
+            if rate_shift != 0.0 {
+                shifted = shifted.with_rate_shift(rate_shift);
+            }
+            shifted
+        });
+        let result = f(self);
+        self.term_structure = base;
+        result
+    }
+
+    /// Returns the provenance of a run on `market` inputs: the seed, the
+    /// engine versions and fingerprints of the configuration and inputs.
+    #[cfg(feature = "l1l2-integration")]
+    pub(crate) fn run_metadata(&self, market: &impl fmt::Debug) -> RunMetadata {
+        RunMetadata::current()
+            .with_engine_version("pricer_pricing", env!("CARGO_PKG_VERSION"))
+            .with_seed(self.rng.seed())
+            .with_config_hash(fingerprint(&self.config))
+            .with_market_snapshot_hash(fingerprint(market))
+    }
+
     /// Resets the pricer state for a new simulation.
     ///
     /// Resets the workspace and RNG (using original seed).
@@ -258,12 +407,19 @@ impl MonteCarloPricer {
     /// # Returns
     ///
     /// Price and standard error.
+    ///
+    /// With [`Precision::F32`] configured, paths are simulated in single
+    /// precision (see [`precision`](super::precision)).
     pub fn price_european(
         &mut self,
         gbm: GbmParams,
         payoff: PayoffParams,
         discount_factor: f64,
     ) -> PricingResult {
+        if self.config.precision() == Precision::F32 {
+            return self.price_european_f32(gbm, payoff, discount_factor);
+        }
+
         let n_paths = self.config.n_paths();
         let n_steps = self.config.n_steps();
 
@@ -272,9 +428,10 @@ impl MonteCarloPricer {
 
         // Generate random samples
         self.rng.fill_normal(self.workspace.randoms_mut());
+        shape_normals(&self.config, self.workspace.randoms_mut(), n_paths, n_steps);
 
         // Generate paths
-        generate_gbm_paths(&mut self.workspace, gbm, n_paths, n_steps);
+        self.generate_paths(gbm, n_paths, n_steps);
 
         // Compute payoffs
         compute_payoffs(&mut self.workspace, payoff, n_paths, n_steps);
@@ -289,9 +446,24 @@ impl MonteCarloPricer {
         let std_dev = variance.sqrt();
         let std_error = std_dev / (n_paths as f64).sqrt();
 
+        let terminals = self
+            .workspace
+            .paths()
+            .chunks_exact(n_steps + 1)
+            .map(|path| path[n_steps]);
+        let bias = smoothing_bias(terminals, &payoffs[..n_paths], payoff, discount_factor);
+
+        let price = mean * discount_factor;
+        let std_error = std_error * discount_factor;
         PricingResult {
-            price: mean * discount_factor,
-            std_error: std_error * discount_factor,
+            price,
+            std_error,
+            diagnostics: Some(
+                PricingDiagnostics::new(&self.config, n_paths, gbm.maturity, price, std_error)
+                    .with_smoothing(payoff.smoothing_epsilon, Some(bias)),
+            ),
+            #[cfg(feature = "l1l2-integration")]
+            metadata: Some(self.run_metadata(&(gbm, payoff, discount_factor))),
             ..Default::default()
         }
     }
@@ -325,6 +497,7 @@ impl MonteCarloPricer {
 
         // Compute requested Greeks
         for greek in greeks {
+            result.push_fallback(Fallback::FiniteDifference(*greek));
             match greek {
                 // First-order Greeks
                 Greek::Delta => {
@@ -428,7 +601,9 @@ impl MonteCarloPricer {
             volatility: gbm.volatility + bump,
             ..gbm
         };
-        let price_up = self.price_european(gbm_up, payoff, discount_factor).price;
+        let price_up = self.with_shifted_term_structure(bump, 0.0, |p| {
+            p.price_european(gbm_up, payoff, discount_factor).price
+        });
 
         // Price at vol - bump
         self.reset_with_seed(seed);
@@ -436,7 +611,9 @@ impl MonteCarloPricer {
             volatility: (gbm.volatility - bump).max(0.001),
             ..gbm
         };
-        let price_down = self.price_european(gbm_down, payoff, discount_factor).price;
+        let price_down = self.with_shifted_term_structure(-bump, 0.0, |p| {
+            p.price_european(gbm_down, payoff, discount_factor).price
+        });
 
         // Central difference (scaled to 1% vol move)
         (price_up - price_down) / (2.0 * bump)
@@ -479,8 +656,10 @@ impl MonteCarloPricer {
             rate: gbm.rate + bump,
             ..gbm
         };
-        let df_up = (-(gbm.rate + bump) * gbm.maturity).exp();
-        let price_up = self.price_european(gbm_up, payoff, df_up).price;
+        let price_up = self.with_shifted_term_structure(0.0, bump, |p| {
+            let df_up = p.discount_factor(gbm_up);
+            p.price_european(gbm_up, payoff, df_up).price
+        });
 
         // Price at r - bump
         self.reset_with_seed(seed);
@@ -488,8 +667,10 @@ impl MonteCarloPricer {
             rate: gbm.rate - bump,
             ..gbm
         };
-        let df_down = (-(gbm.rate - bump) * gbm.maturity).exp();
-        let price_down = self.price_european(gbm_down, payoff, df_down).price;
+        let price_down = self.with_shifted_term_structure(0.0, -bump, |p| {
+            let df_down = p.discount_factor(gbm_down);
+            p.price_european(gbm_down, payoff, df_down).price
+        });
 
         // Central difference (scaled to 1% rate move)
         (price_up - price_down) / (2.0 * bump)
@@ -513,9 +694,9 @@ impl MonteCarloPricer {
             volatility: gbm.volatility + vol_bump,
             ..gbm
         };
-        let price_up_up = self
-            .price_european(gbm_up_up, payoff, discount_factor)
-            .price;
+        let price_up_up = self.with_shifted_term_structure(vol_bump, 0.0, |p| {
+            p.price_european(gbm_up_up, payoff, discount_factor).price
+        });
 
         // V(S+h, σ-k)
         self.reset_with_seed(seed);
@@ -524,9 +705,9 @@ impl MonteCarloPricer {
             volatility: (gbm.volatility - vol_bump).max(0.001),
             ..gbm
         };
-        let price_up_down = self
-            .price_european(gbm_up_down, payoff, discount_factor)
-            .price;
+        let price_up_down = self.with_shifted_term_structure(-vol_bump, 0.0, |p| {
+            p.price_european(gbm_up_down, payoff, discount_factor).price
+        });
 
         // V(S-h, σ+k)
         self.reset_with_seed(seed);
@@ -535,9 +716,9 @@ impl MonteCarloPricer {
             volatility: gbm.volatility + vol_bump,
             ..gbm
         };
-        let price_down_up = self
-            .price_european(gbm_down_up, payoff, discount_factor)
-            .price;
+        let price_down_up = self.with_shifted_term_structure(vol_bump, 0.0, |p| {
+            p.price_european(gbm_down_up, payoff, discount_factor).price
+        });
 
         // V(S-h, σ-k)
         self.reset_with_seed(seed);
@@ -546,9 +727,10 @@ impl MonteCarloPricer {
             volatility: (gbm.volatility - vol_bump).max(0.001),
             ..gbm
         };
-        let price_down_down = self
-            .price_european(gbm_down_down, payoff, discount_factor)
-            .price;
+        let price_down_down = self.with_shifted_term_structure(-vol_bump, 0.0, |p| {
+            p.price_european(gbm_down_down, payoff, discount_factor)
+                .price
+        });
 
         // Cross-difference formula
         (price_up_up - price_up_down - price_down_up + price_down_down)
@@ -575,7 +757,9 @@ impl MonteCarloPricer {
             volatility: gbm.volatility + bump,
             ..gbm
         };
-        let price_up = self.price_european(gbm_up, payoff, discount_factor).price;
+        let price_up = self.with_shifted_term_structure(bump, 0.0, |p| {
+            p.price_european(gbm_up, payoff, discount_factor).price
+        });
 
         // V(σ-h)
         self.reset_with_seed(seed);
@@ -583,7 +767,9 @@ impl MonteCarloPricer {
             volatility: (gbm.volatility - bump).max(0.001),
             ..gbm
         };
-        let price_down = self.price_european(gbm_down, payoff, discount_factor).price;
+        let price_down = self.with_shifted_term_structure(-bump, 0.0, |p| {
+            p.price_european(gbm_down, payoff, discount_factor).price
+        });
 
         // Three-point formula for second derivative
         (price_up - 2.0 * price_mid + price_down) / (bump * bump)
@@ -734,15 +920,26 @@ impl MonteCarloPricer {
 
         // Generate random samples
         self.rng.fill_normal(self.workspace.randoms_mut());
+        shape_normals(&self.config, self.workspace.randoms_mut(), n_paths, n_steps);
 
         // Generate paths with tangent (d/dS₀)
-        let tangent_paths = generate_gbm_paths_tangent_spot(
-            &mut self.workspace,
-            gbm,
-            1.0, // d_spot = 1.0 (seed tangent)
-            n_paths,
-            n_steps,
-        );
+        let tangent_paths = match &self.term_structure {
+            // Paths are linear in S₀, so the tangent is the path over spot.
+            Some(ts) => {
+                generate_gbm_paths_term_structure(&mut self.workspace, gbm, ts, n_paths, n_steps);
+                self.workspace.paths()[..n_paths * (n_steps + 1)]
+                    .iter()
+                    .map(|s| s / gbm.spot)
+                    .collect()
+            }
+            None => generate_gbm_paths_tangent_spot(
+                &mut self.workspace,
+                gbm,
+                1.0, // d_spot = 1.0 (seed tangent)
+                n_paths,
+                n_steps,
+            ),
+        };
 
         // Compute payoffs and their tangents
         let paths = self.workspace.paths();
@@ -839,9 +1036,10 @@ impl MonteCarloPricer {
 
         // Generate random samples
         self.rng.fill_normal(self.workspace.randoms_mut());
+        shape_normals(&self.config, self.workspace.randoms_mut(), n_paths, n_steps);
 
         // Generate GBM paths
-        generate_gbm_paths(&mut self.workspace, gbm, n_paths, n_steps);
+        self.generate_paths(gbm, n_paths, n_steps);
 
         let paths = self.workspace.paths();
 
@@ -874,9 +1072,18 @@ impl MonteCarloPricer {
         let std_dev = variance.max(0.0).sqrt();
         let std_error = std_dev / (n_paths as f64).sqrt();
 
+        let price = mean * discount_factor;
+        let std_error = std_error * discount_factor;
         PricingResult {
-            price: mean * discount_factor,
-            std_error: std_error * discount_factor,
+            price,
+            std_error,
+            // No closed-form exact payoff to compare against on the path
+            diagnostics: Some(
+                PricingDiagnostics::new(&self.config, n_paths, gbm.maturity, price, std_error)
+                    .with_smoothing(payoff.smoothing_epsilon(), None),
+            ),
+            #[cfg(feature = "l1l2-integration")]
+            metadata: Some(self.run_metadata(&(gbm, payoff, discount_factor))),
             ..Default::default()
         }
     }
@@ -912,28 +1119,34 @@ impl MonteCarloPricer {
         for greek in greeks {
             match greek {
                 Greek::Delta => {
+                    result.push_fallback(Fallback::FiniteDifference(*greek));
                     result.delta =
                         Some(self.compute_delta_path_dependent(gbm, payoff, discount_factor));
                 }
                 Greek::Gamma => {
+                    result.push_fallback(Fallback::FiniteDifference(*greek));
                     result.gamma =
                         Some(self.compute_gamma_path_dependent(gbm, payoff, discount_factor));
                 }
                 Greek::Vega => {
+                    result.push_fallback(Fallback::FiniteDifference(*greek));
                     result.vega =
                         Some(self.compute_vega_path_dependent(gbm, payoff, discount_factor));
                 }
                 Greek::Theta => {
+                    result.push_fallback(Fallback::FiniteDifference(*greek));
                     result.theta =
                         Some(self.compute_theta_path_dependent(gbm, payoff, discount_factor));
                 }
                 Greek::Rho => {
+                    result.push_fallback(Fallback::FiniteDifference(*greek));
                     result.rho =
                         Some(self.compute_rho_path_dependent(gbm, payoff, discount_factor));
                 }
                 Greek::Vanna | Greek::Volga => {
                     // Second-order cross Greeks for path-dependent not yet implemented
                     // Will be added with Enzyme AD + checkpointing integration
+                    result.push_fallback(Fallback::Unsupported(*greek));
                 }
             }
         }
@@ -1029,9 +1242,10 @@ impl MonteCarloPricer {
             volatility: gbm.volatility + bump,
             ..gbm
         };
-        let price_up = self
-            .price_path_dependent(gbm_up, payoff, discount_factor)
-            .price;
+        let price_up = self.with_shifted_term_structure(bump, 0.0, |p| {
+            p.price_path_dependent(gbm_up, payoff, discount_factor)
+                .price
+        });
 
         // Price at vol - bump
         self.reset_with_seed(seed);
@@ -1039,9 +1253,10 @@ impl MonteCarloPricer {
             volatility: (gbm.volatility - bump).max(0.001),
             ..gbm
         };
-        let price_down = self
-            .price_path_dependent(gbm_down, payoff, discount_factor)
-            .price;
+        let price_down = self.with_shifted_term_structure(-bump, 0.0, |p| {
+            p.price_path_dependent(gbm_down, payoff, discount_factor)
+                .price
+        });
 
         (price_up - price_down) / (2.0 * bump)
     }
@@ -1091,8 +1306,10 @@ impl MonteCarloPricer {
             rate: gbm.rate + bump,
             ..gbm
         };
-        let df_up = (-(gbm.rate + bump) * gbm.maturity).exp();
-        let price_up = self.price_path_dependent(gbm_up, payoff, df_up).price;
+        let price_up = self.with_shifted_term_structure(0.0, bump, |p| {
+            let df_up = p.discount_factor(gbm_up);
+            p.price_path_dependent(gbm_up, payoff, df_up).price
+        });
 
         // Price at r - bump
         self.reset_with_seed(seed);
@@ -1100,8 +1317,10 @@ impl MonteCarloPricer {
             rate: gbm.rate - bump,
             ..gbm
         };
-        let df_down = (-(gbm.rate - bump) * gbm.maturity).exp();
-        let price_down = self.price_path_dependent(gbm_down, payoff, df_down).price;
+        let price_down = self.with_shifted_term_structure(0.0, -bump, |p| {
+            let df_down = p.discount_factor(gbm_down);
+            p.price_path_dependent(gbm_down, payoff, df_down).price
+        });
 
         (price_up - price_down) / (2.0 * bump)
     }
@@ -1189,6 +1408,29 @@ mod tests {
         assert_eq!(result1.std_error, result2.std_error);
     }
 
+    #[test]
+    #[cfg(feature = "l1l2-integration")]
+    fn test_run_metadata_identifies_inputs() {
+        let gbm = GbmParams::default();
+        let payoff = PayoffParams::call(100.0);
+
+        let result = create_test_pricer().price_european(gbm, payoff, 0.95);
+        let meta = result.metadata.unwrap();
+        assert_eq!(meta.seed, Some(42));
+        assert!(meta.engine_versions.contains_key("pricer_pricing"));
+        assert!(meta.engine_versions.contains_key("pricer_core"));
+
+        let rerun = create_test_pricer().price_european(gbm, payoff, 0.95);
+        assert!(meta.same_inputs(rerun.metadata.as_ref().unwrap()));
+
+        let other_market = create_test_pricer().price_european(gbm, payoff, 0.9);
+        assert_ne!(
+            meta.market_snapshot_hash,
+            other_market.metadata.unwrap().market_snapshot_hash
+        );
+        assert_eq!(meta.config_hash, rerun.metadata.unwrap().config_hash);
+    }
+
     #[test]
     fn test_pricer_reset() {
         let config = MonteCarloConfig::builder()
@@ -1562,4 +1804,45 @@ mod tests {
         // Vega should be positive for options
         assert!(vega > 0.0, "Vega = {}", vega);
     }
+
+    #[test]
+    fn test_term_structure_prices_as_equivalent_flat() {
+        use crate::mc::PiecewiseConstant;
+
+        // With one step the terminal draw only sees integrated moments, so a
+        // term structure prices exactly like its equivalent flat parameters
+        let config = MonteCarloConfig::builder()
+            .n_paths(10_000)
+            .n_steps(1)
+            .seed(42)
+            .build()
+            .unwrap();
+        let gbm = GbmParams::default();
+        let ts = GbmTermStructure::new()
+            .with_volatility(PiecewiseConstant::new(vec![0.5, 1.0], vec![0.1, 0.3]).unwrap())
+            .with_rate(PiecewiseConstant::new(vec![0.25, 1.0], vec![0.01, 0.05]).unwrap());
+        let flat = GbmParams::new(100.0, 0.04, ts.total_variance(&gbm).sqrt(), 1.0);
+        let payoff = PayoffParams::call(100.0);
+
+        let mut ts_pricer = MonteCarloPricer::new(config.clone())
+            .unwrap()
+            .with_term_structure(ts);
+        let ts_df = ts_pricer.discount_factor(gbm);
+        let ts_result = ts_pricer.price_with_greeks(gbm, payoff, ts_df, &[Greek::Vega, Greek::Rho]);
+        let flat_result = MonteCarloPricer::new(config).unwrap().price_with_greeks(
+            flat,
+            payoff,
+            (-0.04_f64).exp(),
+            &[Greek::Rho],
+        );
+
+        assert_relative_eq!(ts_result.price, flat_result.price, max_relative = 1e-10);
+        // Rate bumps shift the whole curve
+        assert_relative_eq!(
+            ts_result.rho.unwrap(),
+            flat_result.rho.unwrap(),
+            max_relative = 1e-8
+        );
+        assert!(ts_result.vega.unwrap() > 0.0);
+    }
 }
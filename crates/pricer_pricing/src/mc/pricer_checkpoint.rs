@@ -25,7 +25,7 @@ use crate::checkpoint::{
     CheckpointManager, CheckpointResult, CheckpointStrategy, MemoryBudget, SimulationState,
 };
 use crate::mc::workspace_checkpoint::CheckpointWorkspace;
-use crate::mc::{GbmParams, MonteCarloConfig, PricingResult};
+use crate::mc::{GbmParams, GbmTermStructure, MonteCarloConfig, PricingResult};
 use crate::path_dependent::{PathObserverState, PathPayoffType};
 use crate::rng::PricerRng;
 
@@ -97,6 +97,7 @@ pub struct CheckpointPricer {
     workspace: CheckpointWorkspace<f64>,
     rng: PricerRng,
     checkpoint_manager: CheckpointManager<f64>,
+    term_structure: Option<GbmTermStructure>,
 }
 
 impl CheckpointPricer {
@@ -132,9 +133,23 @@ impl CheckpointPricer {
             workspace,
             rng,
             checkpoint_manager,
+            term_structure: None,
         })
     }
 
+    /// Simulates paths under piecewise-constant volatility, rate and
+    /// dividend curves instead of the flat [`GbmParams`] coefficients.
+    pub fn with_term_structure(mut self, term_structure: GbmTermStructure) -> Self {
+        self.term_structure = Some(term_structure);
+        self
+    }
+
+    /// Returns the term structure, if one is set.
+    #[inline]
+    pub fn term_structure(&self) -> Option<&GbmTermStructure> {
+        self.term_structure.as_ref()
+    }
+
     /// Returns a reference to the configuration.
     #[inline]
     pub fn config(&self) -> &CheckpointPricingConfig {
@@ -204,10 +219,17 @@ impl CheckpointPricer {
         // Pre-generate all random numbers
         self.rng.fill_normal(self.workspace.randoms_mut());
 
-        // GBM parameters
-        let dt = gbm.maturity / n_steps as f64;
-        let drift = (gbm.rate - 0.5 * gbm.volatility * gbm.volatility) * dt;
-        let vol_sqrt_dt = gbm.volatility * dt.sqrt();
+        // GBM drift and volatility of each step
+        let (drift, vol_sqrt_dt) = match &self.term_structure {
+            Some(ts) => ts.step_coefficients(&gbm, n_steps),
+            None => {
+                let dt = gbm.maturity / n_steps as f64;
+                (
+                    vec![(gbm.rate - 0.5 * gbm.volatility * gbm.volatility) * dt; n_steps],
+                    vec![gbm.volatility * dt.sqrt(); n_steps],
+                )
+            }
+        };
 
         // Initialize paths with spot price
         let paths = self.workspace.paths_mut();
@@ -241,7 +263,8 @@ impl CheckpointPricer {
                     let random = self.workspace.randoms()[rand_idx];
 
                     // Log-normal GBM: S_t = S_{t-1} * exp(drift + vol * sqrt(dt) * Z)
-                    let curr_price = prev_price * (drift + vol_sqrt_dt * random).exp();
+                    let curr_price =
+                        prev_price * (drift[step - 1] + vol_sqrt_dt[step - 1] * random).exp();
                     self.workspace.paths_mut()[curr_idx] = curr_price;
                 }
             }
@@ -0,0 +1,325 @@
+//! SIMD kernels for GBM path generation.
+//!
+//! Enabled by the `simd` feature (nightly `portable_simd`). Paths are
+//! processed in blocks of `LANES` paths: each block's random draws are
+//! transposed into a structure-of-arrays buffer (one vector per time step),
+//! evolved with vector arithmetic and a vectorised `exp`, then written back
+//! to the row-major path layout used by [`PathWorkspace`](super::PathWorkspace).
+//!
+//! # Runtime Dispatch
+//!
+//! The instruction set is detected once per process:
+//!
+//! | Target | Level | Lanes |
+//! |--------|-------|-------|
+//! | x86_64 with AVX2 + FMA | `Avx2` | 4 |
+//! | aarch64 | `Neon` | 2 |
+//! | anything else | `Scalar` | 1 (falls back to the scalar kernel) |
+//!
+//! # Accuracy
+//!
+//! The vector `exp` uses Cody-Waite range reduction and a degree-12
+//! polynomial, agreeing with `f64::exp` to within a few ulp. Paths therefore
+//! match the scalar kernel to ~1e-14 relative, not bit-for-bit.
+
+use std::simd::cmp::SimdPartialOrd;
+use std::simd::num::SimdFloat;
+use std::simd::{LaneCount, Simd, StdFloat, SupportedLaneCount};
+use std::sync::OnceLock;
+
+/// Instruction set used by the SIMD path kernels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SimdLevel {
+    /// No supported vector unit; the scalar kernel is used.
+    Scalar,
+    /// x86_64 AVX2 with FMA (4 × f64 lanes).
+    Avx2,
+    /// aarch64 NEON (2 × f64 lanes).
+    Neon,
+}
+
+impl SimdLevel {
+    /// Detects the best available level for this CPU (cached).
+    pub fn detect() -> Self {
+        static LEVEL: OnceLock<SimdLevel> = OnceLock::new();
+        *LEVEL.get_or_init(|| {
+            #[cfg(target_arch = "x86_64")]
+            {
+                if is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma") {
+                    return SimdLevel::Avx2;
+                }
+            }
+            #[cfg(target_arch = "aarch64")]
+            {
+                if std::arch::is_aarch64_feature_detected!("neon") {
+                    return SimdLevel::Neon;
+                }
+            }
+            SimdLevel::Scalar
+        })
+    }
+
+    /// Number of f64 lanes processed per block.
+    #[inline]
+    pub fn lanes(&self) -> usize {
+        match self {
+            Self::Scalar => 1,
+            Self::Avx2 => 4,
+            Self::Neon => 2,
+        }
+    }
+}
+
+/// Generates GBM paths with the SIMD kernel for the detected CPU.
+///
+/// Returns `false` (leaving `paths` untouched) when no vector unit is
+/// available, so the caller can fall back to the scalar kernel.
+pub(crate) fn generate_gbm_paths_simd(
+    paths: &mut [f64],
+    randoms: &[f64],
+    spot: f64,
+    drift_dt: f64,
+    vol_sqrt_dt: f64,
+    n_paths: usize,
+    n_steps: usize,
+) -> bool {
+    match SimdLevel::detect() {
+        #[cfg(target_arch = "x86_64")]
+        SimdLevel::Avx2 => {
+            // SAFETY: AVX2 and FMA support was verified at runtime.
+            unsafe {
+                generate_avx2(
+                    paths,
+                    randoms,
+                    spot,
+                    drift_dt,
+                    vol_sqrt_dt,
+                    n_paths,
+                    n_steps,
+                )
+            };
+            true
+        }
+        #[cfg(target_arch = "aarch64")]
+        SimdLevel::Neon => {
+            generate_block::<2>(
+                paths,
+                randoms,
+                spot,
+                drift_dt,
+                vol_sqrt_dt,
+                n_paths,
+                n_steps,
+            );
+            true
+        }
+        _ => false,
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2,fma")]
+unsafe fn generate_avx2(
+    paths: &mut [f64],
+    randoms: &[f64],
+    spot: f64,
+    drift_dt: f64,
+    vol_sqrt_dt: f64,
+    n_paths: usize,
+    n_steps: usize,
+) {
+    generate_block::<4>(
+        paths,
+        randoms,
+        spot,
+        drift_dt,
+        vol_sqrt_dt,
+        n_paths,
+        n_steps,
+    );
+}
+
+/// Block kernel: `LANES` paths at a time through an SoA buffer.
+#[inline(always)]
+fn generate_block<const LANES: usize>(
+    paths: &mut [f64],
+    randoms: &[f64],
+    spot: f64,
+    drift_dt: f64,
+    vol_sqrt_dt: f64,
+    n_paths: usize,
+    n_steps: usize,
+) where
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    let n_steps_plus_1 = n_steps + 1;
+    let drift = Simd::<f64, LANES>::splat(drift_dt);
+    let vol = Simd::<f64, LANES>::splat(vol_sqrt_dt);
+
+    // SoA scratch: one vector per step (hoisted out of the block loop)
+    let mut block = vec![Simd::<f64, LANES>::splat(0.0); n_steps];
+    let n_blocks = n_paths / LANES;
+
+    for block_idx in 0..n_blocks {
+        let first_path = block_idx * LANES;
+
+        // Transpose randoms (row-major per path) into SoA
+        for lane in 0..LANES {
+            let row = &randoms[(first_path + lane) * n_steps..][..n_steps];
+            for (step, &z) in row.iter().enumerate() {
+                block[step][lane] = z;
+            }
+        }
+
+        // Evolve all lanes together, overwriting the draws with spots
+        let mut s = Simd::<f64, LANES>::splat(spot);
+        for slot in block.iter_mut() {
+            s *= exp(vol.mul_add(*slot, drift));
+            *slot = s;
+        }
+
+        // Transpose back to the row-major path layout
+        for lane in 0..LANES {
+            let row = &mut paths[(first_path + lane) * n_steps_plus_1..][..n_steps_plus_1];
+            row[0] = spot;
+            for (step, spots) in block.iter().enumerate() {
+                row[step + 1] = spots[lane];
+            }
+        }
+    }
+
+    // Scalar tail for the remaining paths
+    for path_idx in n_blocks * LANES..n_paths {
+        let path_offset = path_idx * n_steps_plus_1;
+        let random_offset = path_idx * n_steps;
+        paths[path_offset] = spot;
+        for step in 0..n_steps {
+            let increment = drift_dt + vol_sqrt_dt * randoms[random_offset + step];
+            paths[path_offset + step + 1] = paths[path_offset + step] * increment.exp();
+        }
+    }
+}
+
+/// Vectorised `exp` (Cody-Waite reduction, degree-12 Taylor polynomial).
+#[inline(always)]
+fn exp<const LANES: usize>(input: Simd<f64, LANES>) -> Simd<f64, LANES>
+where
+    LaneCount<LANES>: SupportedLaneCount,
+{
+    const LN2_HI: f64 = 6.931_471_803_691_238e-1;
+    const LN2_LO: f64 = 1.908_214_929_270_587_7e-10;
+    // 1/k! for k = 12 down to 2
+    const COEFFS: [f64; 11] = [
+        2.087_675_698_786_81e-9,
+        2.505_210_838_544_172e-8,
+        2.755_731_922_398_589e-7,
+        2.755_731_922_398_589e-6,
+        2.480_158_730_158_73e-5,
+        1.984_126_984_126_984e-4,
+        1.388_888_888_888_889e-3,
+        8.333_333_333_333_333e-3,
+        4.166_666_666_666_666e-2,
+        1.666_666_666_666_666_6e-1,
+        0.5,
+    ];
+
+    // Round x/ln2 to the nearest integer with the 1.5 × 2^52 shifter, which
+    // leaves n in the low mantissa bits (no float-to-int conversion needed)
+    let shifter = Simd::splat(6_755_399_441_055_744.0);
+    let x = input.simd_clamp(Simd::splat(-708.0), Simd::splat(709.0));
+    let t = x.mul_add(Simd::splat(std::f64::consts::LOG2_E), shifter);
+    let n = t - shifter;
+    let r = n.mul_add(Simd::splat(-LN2_LO), n.mul_add(Simd::splat(-LN2_HI), x));
+
+    let mut p = Simd::splat(COEFFS[0]);
+    for &c in &COEFFS[1..] {
+        p = p.mul_add(r, Simd::splat(c));
+    }
+    let p = p.mul_add(r, Simd::splat(1.0)).mul_add(r, Simd::splat(1.0));
+
+    // Scale by 2^n through the exponent bits
+    let n_bits = t.to_bits() - shifter.to_bits();
+    let scale = Simd::<f64, LANES>::from_bits((n_bits + Simd::splat(1023)) << Simd::splat(52));
+
+    // Inputs beyond the clamp limits saturate like f64::exp
+    let result = p * scale;
+    let result = input
+        .simd_lt(Simd::splat(-708.0))
+        .select(Simd::splat(0.0), result);
+    input
+        .simd_gt(Simd::splat(709.0))
+        .select(Simd::splat(f64::INFINITY), result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mc::paths::generate_gbm_paths_scalar;
+    use crate::rng::PricerRng;
+
+    fn assert_paths_close(simd: &[f64], scalar: &[f64]) {
+        for (a, b) in simd.iter().zip(scalar) {
+            assert!((a - b).abs() <= 1e-13 * b.abs(), "{} vs {}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_exp_matches_std() {
+        let xs = [
+            -30.0, -1.5, -0.3465, -1e-12, 0.0, 1e-9, 0.2, 0.6931, 3.7, 50.0,
+        ];
+        for chunk in xs.chunks_exact(2) {
+            let v = exp(Simd::<f64, 2>::from_slice(chunk));
+            for (lane, &x) in chunk.iter().enumerate() {
+                let expected = x.exp();
+                assert!(
+                    (v[lane] - expected).abs() <= 4.0 * f64::EPSILON * expected,
+                    "exp({}) = {} vs {}",
+                    x,
+                    v[lane],
+                    expected
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_block_kernel_matches_scalar_with_tail() {
+        // 4-lane blocks plus a 3-path scalar tail
+        let (n_paths, n_steps) = (19, 12);
+        let mut randoms = vec![0.0; n_paths * n_steps];
+        PricerRng::from_seed(7).fill_normal(&mut randoms);
+
+        let mut simd = vec![0.0; n_paths * (n_steps + 1)];
+        let mut scalar = simd.clone();
+        generate_block::<4>(&mut simd, &randoms, 100.0, 0.0003, 0.05, n_paths, n_steps);
+        generate_gbm_paths_scalar(&mut scalar, &randoms, 100.0, 0.0003, 0.05, n_paths, n_steps);
+
+        assert_paths_close(&simd, &scalar);
+    }
+
+    #[test]
+    fn test_dispatch_matches_scalar() {
+        let (n_paths, n_steps) = (64, 50);
+        let mut randoms = vec![0.0; n_paths * n_steps];
+        PricerRng::from_seed(11).fill_normal(&mut randoms);
+
+        let mut simd = vec![0.0; n_paths * (n_steps + 1)];
+        let mut scalar = simd.clone();
+        let used =
+            generate_gbm_paths_simd(&mut simd, &randoms, 50.0, -0.001, 0.03, n_paths, n_steps);
+        generate_gbm_paths_scalar(&mut scalar, &randoms, 50.0, -0.001, 0.03, n_paths, n_steps);
+
+        assert_eq!(used, SimdLevel::detect() != SimdLevel::Scalar);
+        if used {
+            assert_paths_close(&simd, &scalar);
+        }
+    }
+
+    #[test]
+    fn test_lanes() {
+        assert_eq!(SimdLevel::Scalar.lanes(), 1);
+        assert_eq!(SimdLevel::Avx2.lanes(), 4);
+        assert_eq!(SimdLevel::Neon.lanes(), 2);
+    }
+}
@@ -0,0 +1,420 @@
+//! Piecewise-constant term structures for GBM path generation.
+//!
+//! A [`GbmTermStructure`] lets volatility, the risk-free rate and the
+//! dividend yield vary with time, so simulated paths reproduce the forwards
+//! and total variances of bootstrapped curves rather than flat inputs.
+//!
+//! Each step of the simulation grid uses the exact integrals of the
+//! term structures over the step:
+//!
+//! ```text
+//! drift_k = ∫(r(t) - q(t))dt - ½∫σ²(t)dt
+//! vol_k   = √(∫σ²(t)dt)
+//! ```
+//!
+//! so pillars need not line up with the time steps.
+//!
+//! [`GbmParams`] stays flat; a term structure is set on the pricer with
+//! [`MonteCarloPricer::with_term_structure`](super::MonteCarloPricer::with_term_structure)
+//! and falls back to the flat parameters for any component it leaves unset.
+
+use super::error::ConfigError;
+use super::paths::GbmParams;
+
+/// Piecewise-constant function of time.
+///
+/// Each value applies up to and including its pillar; the last value
+/// extends flat beyond the last pillar.
+///
+/// # Examples
+///
+/// ```rust
+/// use pricer_pricing::mc::PiecewiseConstant;
+///
+/// // 20% to one year, 25% thereafter
+/// let vol = PiecewiseConstant::new(vec![1.0, 2.0], vec![0.20, 0.25]).unwrap();
+/// assert_eq!(vol.value_at(0.5), 0.20);
+/// assert_eq!(vol.value_at(5.0), 0.25);
+/// assert!((vol.integral(0.0, 2.0) - 0.45).abs() < 1e-12);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct PiecewiseConstant {
+    /// Times at which the value changes, strictly increasing.
+    breaks: Vec<f64>,
+    /// Values, one more than `breaks`.
+    values: Vec<f64>,
+}
+
+impl PiecewiseConstant {
+    /// Creates a term structure from pillar times (in years) and the value
+    /// on the period ending at each pillar.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ConfigError::InvalidParameter` if the inputs are empty or of
+    /// different lengths, if the pillars are not positive and strictly
+    /// increasing, or if any value is not finite.
+    pub fn new(pillars: Vec<f64>, values: Vec<f64>) -> Result<Self, ConfigError> {
+        if pillars.is_empty() || pillars.len() != values.len() {
+            return Err(ConfigError::InvalidParameter {
+                name: "term_structure",
+                value: format!(
+                    "{} pillars and {} values (must be equal and non-empty)",
+                    pillars.len(),
+                    values.len()
+                ),
+            });
+        }
+        if pillars[0] <= 0.0 || pillars.windows(2).any(|w| w[1] <= w[0]) {
+            return Err(ConfigError::InvalidParameter {
+                name: "term_structure",
+                value: format!("{:?} (pillars must be positive and increasing)", pillars),
+            });
+        }
+        if pillars.iter().chain(&values).any(|v| !v.is_finite()) {
+            return Err(ConfigError::InvalidParameter {
+                name: "term_structure",
+                value: "pillars and values must be finite".to_string(),
+            });
+        }
+        let mut breaks = pillars;
+        breaks.pop();
+        Ok(Self { breaks, values })
+    }
+
+    /// Creates forward volatilities from implied volatilities quoted to each
+    /// pillar, so that `∫σ²dt` to each pillar is `σᵢ²tᵢ`.
+    ///
+    /// # Errors
+    ///
+    /// As [`PiecewiseConstant::new`], and `ConfigError::InvalidParameter` if
+    /// a volatility is negative or the total variance decreases between
+    /// pillars (calendar arbitrage).
+    pub fn from_implied_vols(pillars: Vec<f64>, vols: Vec<f64>) -> Result<Self, ConfigError> {
+        let implied = Self::new(pillars.clone(), vols)?;
+        if implied.values.iter().any(|&v| v < 0.0) {
+            return Err(ConfigError::InvalidParameter {
+                name: "term_structure",
+                value: format!("{:?} (volatilities must be non-negative)", implied.values),
+            });
+        }
+        let mut forward = Vec::with_capacity(pillars.len());
+        let (mut prev_t, mut prev_var) = (0.0, 0.0);
+        for (&t, &vol) in pillars.iter().zip(&implied.values) {
+            let var = vol * vol * t;
+            if var < prev_var {
+                return Err(ConfigError::InvalidParameter {
+                    name: "term_structure",
+                    value: format!("total variance decreases before pillar {}", t),
+                });
+            }
+            forward.push(((var - prev_var) / (t - prev_t)).sqrt());
+            (prev_t, prev_var) = (t, var);
+        }
+        Self::new(pillars, forward)
+    }
+
+    /// Creates instantaneous forward rates of `curve` between successive
+    /// pillars, so that `∫r dt` to each pillar reprices its discount factor.
+    ///
+    /// # Errors
+    ///
+    /// As [`PiecewiseConstant::new`], and `ConfigError::InvalidParameter` if
+    /// the curve cannot be evaluated at a pillar.
+    #[cfg(feature = "l1l2-integration")]
+    pub fn from_curve<C>(curve: &C, pillars: Vec<f64>) -> Result<Self, ConfigError>
+    where
+        C: pricer_core::market_data::curves::YieldCurve<f64>,
+    {
+        let mut rates = Vec::with_capacity(pillars.len());
+        let mut prev_t = 0.0;
+        for &t in &pillars {
+            let rate =
+                curve
+                    .forward_rate(prev_t, t)
+                    .map_err(|e| ConfigError::InvalidParameter {
+                        name: "curve",
+                        value: e.to_string(),
+                    })?;
+            rates.push(rate);
+            prev_t = t;
+        }
+        Self::new(pillars, rates)
+    }
+
+    /// Creates a term structure with the same value at all times.
+    pub fn flat(value: f64) -> Self {
+        Self {
+            breaks: Vec::new(),
+            values: vec![value],
+        }
+    }
+
+    /// Returns the value at time `t`.
+    pub fn value_at(&self, t: f64) -> f64 {
+        let idx = self.breaks.partition_point(|&b| b < t);
+        self.values[idx]
+    }
+
+    /// Returns the values, one per period.
+    #[inline]
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Returns `∫f(t)dt` over `[t0, t1]`.
+    pub fn integral(&self, t0: f64, t1: f64) -> f64 {
+        self.integrate(t0, t1, |v| v)
+    }
+
+    /// Returns `∫f(t)²dt` over `[t0, t1]`.
+    pub fn integral_squared(&self, t0: f64, t1: f64) -> f64 {
+        self.integrate(t0, t1, |v| v * v)
+    }
+
+    /// Returns the term structure with every value shifted by `shift` and
+    /// floored at `floor`.
+    pub fn shifted(&self, shift: f64, floor: f64) -> Self {
+        Self {
+            breaks: self.breaks.clone(),
+            values: self.values.iter().map(|v| (v + shift).max(floor)).collect(),
+        }
+    }
+
+    fn integrate(&self, t0: f64, t1: f64, f: impl Fn(f64) -> f64) -> f64 {
+        let mut total = 0.0;
+        let mut start = t0;
+        for (i, &value) in self.values.iter().enumerate() {
+            if start >= t1 {
+                break;
+            }
+            let end = self.breaks.get(i).map_or(t1, |&b| b.min(t1));
+            if end > start {
+                total += f(value) * (end - start);
+                start = end;
+            }
+        }
+        total
+    }
+}
+
+/// Time-dependent GBM coefficients.
+///
+/// Components left unset fall back to the flat fields of [`GbmParams`];
+/// the dividend yield defaults to zero.
+///
+/// # Examples
+///
+/// ```rust
+/// use pricer_pricing::mc::{GbmParams, GbmTermStructure, PiecewiseConstant};
+///
+/// let ts = GbmTermStructure::new()
+///     .with_volatility(PiecewiseConstant::new(vec![0.5, 1.0], vec![0.15, 0.25]).unwrap())
+///     .with_dividend_yield(PiecewiseConstant::flat(0.02));
+/// let gbm = GbmParams::default();
+///
+/// // Forward uses r - q = 3% over the year
+/// assert!((ts.forward(&gbm) - 100.0 * 0.03_f64.exp()).abs() < 1e-10);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GbmTermStructure {
+    /// Volatility (σ) - annualised.
+    pub volatility: Option<PiecewiseConstant>,
+    /// Risk-free rate (r) - annualised, continuously compounded.
+    pub rate: Option<PiecewiseConstant>,
+    /// Dividend yield (q) - annualised, continuously compounded.
+    pub dividend_yield: Option<PiecewiseConstant>,
+}
+
+impl GbmTermStructure {
+    /// Creates an empty term structure.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the volatility term structure.
+    pub fn with_volatility(mut self, volatility: PiecewiseConstant) -> Self {
+        self.volatility = Some(volatility);
+        self
+    }
+
+    /// Sets the rate term structure.
+    pub fn with_rate(mut self, rate: PiecewiseConstant) -> Self {
+        self.rate = Some(rate);
+        self
+    }
+
+    /// Sets the dividend yield term structure.
+    pub fn with_dividend_yield(mut self, dividend_yield: PiecewiseConstant) -> Self {
+        self.dividend_yield = Some(dividend_yield);
+        self
+    }
+
+    /// Returns the term structure with volatility shifted by `shift`,
+    /// floored at `floor`.
+    pub fn with_volatility_shift(&self, shift: f64, floor: f64) -> Self {
+        Self {
+            volatility: self
+                .volatility
+                .as_ref()
+                .map(|vol| vol.shifted(shift, floor)),
+            ..self.clone()
+        }
+    }
+
+    /// Returns the term structure with the rate shifted by `shift`.
+    pub fn with_rate_shift(&self, shift: f64) -> Self {
+        Self {
+            rate: self
+                .rate
+                .as_ref()
+                .map(|rate| rate.shifted(shift, f64::NEG_INFINITY)),
+            ..self.clone()
+        }
+    }
+
+    /// Returns whether every volatility is non-negative.
+    pub fn is_valid(&self) -> bool {
+        self.volatility
+            .as_ref()
+            .is_none_or(|vol| vol.values().iter().all(|&v| v >= 0.0))
+    }
+
+    /// Returns the risk-free discount factor to `params.maturity`.
+    pub fn discount_factor(&self, params: &GbmParams) -> f64 {
+        (-self.integrated_rate(params, 0.0, params.maturity)).exp()
+    }
+
+    /// Returns the forward price at `params.maturity`.
+    pub fn forward(&self, params: &GbmParams) -> f64 {
+        params.spot
+            * (self.integrated_rate(params, 0.0, params.maturity)
+                - self.integrated_dividend_yield(0.0, params.maturity))
+            .exp()
+    }
+
+    /// Returns the total variance `∫σ²(t)dt` to `params.maturity`.
+    pub fn total_variance(&self, params: &GbmParams) -> f64 {
+        self.integrated_variance(params, 0.0, params.maturity)
+    }
+
+    /// Returns the log drift and volatility of each of `n_steps` equal
+    /// steps to `params.maturity`.
+    ///
+    /// Step `k` over `[tₖ, tₖ₊₁]` has drift `∫(r - q)dt - ½∫σ²dt` and
+    /// volatility `√∫σ²dt`, which reduce to `(r - 0.5σ²)dt` and `σ√dt` for
+    /// an empty term structure.
+    pub fn step_coefficients(&self, params: &GbmParams, n_steps: usize) -> (Vec<f64>, Vec<f64>) {
+        let dt = params.maturity / n_steps as f64;
+        (0..n_steps)
+            .map(|k| {
+                let (t0, t1) = (k as f64 * dt, (k + 1) as f64 * dt);
+                let variance = self.integrated_variance(params, t0, t1);
+                let drift = self.integrated_rate(params, t0, t1)
+                    - self.integrated_dividend_yield(t0, t1)
+                    - 0.5 * variance;
+                (drift, variance.sqrt())
+            })
+            .unzip()
+    }
+
+    /// Returns `∫r(t)dt` over `[t0, t1]`.
+    fn integrated_rate(&self, params: &GbmParams, t0: f64, t1: f64) -> f64 {
+        match &self.rate {
+            Some(rate) => rate.integral(t0, t1),
+            None => params.rate * (t1 - t0),
+        }
+    }
+
+    /// Returns `∫q(t)dt` over `[t0, t1]`.
+    fn integrated_dividend_yield(&self, t0: f64, t1: f64) -> f64 {
+        self.dividend_yield
+            .as_ref()
+            .map_or(0.0, |q| q.integral(t0, t1))
+    }
+
+    /// Returns `∫σ²(t)dt` over `[t0, t1]`.
+    fn integrated_variance(&self, params: &GbmParams, t0: f64, t1: f64) -> f64 {
+        match &self.volatility {
+            Some(vol) => vol.integral_squared(t0, t1),
+            None => params.volatility * params.volatility * (t1 - t0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_value_at_pillars() {
+        let pc = PiecewiseConstant::new(vec![1.0, 2.0, 5.0], vec![0.1, 0.2, 0.3]).unwrap();
+        assert_eq!(pc.value_at(0.0), 0.1);
+        assert_eq!(pc.value_at(1.0), 0.1);
+        assert_eq!(pc.value_at(1.5), 0.2);
+        assert_eq!(pc.value_at(5.0), 0.3);
+        assert_eq!(pc.value_at(10.0), 0.3);
+        assert_eq!(PiecewiseConstant::flat(0.4).value_at(3.0), 0.4);
+    }
+
+    #[test]
+    fn test_integrals_across_pillars() {
+        let pc = PiecewiseConstant::new(vec![1.0, 2.0], vec![0.1, 0.3]).unwrap();
+        assert_relative_eq!(pc.integral(0.0, 2.0), 0.4, epsilon = 1e-14);
+        assert_relative_eq!(pc.integral(0.5, 1.5), 0.05 + 0.15, epsilon = 1e-14);
+        // Flat extrapolation beyond the last pillar
+        assert_relative_eq!(pc.integral(1.5, 4.0), 0.3 * 2.5, epsilon = 1e-14);
+        assert_relative_eq!(pc.integral_squared(0.0, 2.0), 0.01 + 0.09, epsilon = 1e-14);
+        assert_eq!(pc.integral(1.0, 1.0), 0.0);
+
+        let shifted = pc.shifted(-0.2, 0.0);
+        assert_eq!(shifted.values()[0], 0.0);
+        assert_relative_eq!(shifted.values()[1], 0.1, epsilon = 1e-14);
+    }
+
+    #[test]
+    fn test_forward_vols_reprice_implied_variance() {
+        let vol = PiecewiseConstant::from_implied_vols(vec![0.5, 1.0, 2.0], vec![0.3, 0.25, 0.2])
+            .unwrap();
+        assert_relative_eq!(vol.integral_squared(0.0, 0.5), 0.09 * 0.5, epsilon = 1e-14);
+        assert_relative_eq!(vol.integral_squared(0.0, 1.0), 0.0625, epsilon = 1e-14);
+        assert_relative_eq!(vol.integral_squared(0.0, 2.0), 0.08, epsilon = 1e-14);
+
+        // Variance falls from 0.09 to 0.08
+        assert!(PiecewiseConstant::from_implied_vols(vec![1.0, 2.0], vec![0.3, 0.2]).is_err());
+        assert!(PiecewiseConstant::from_implied_vols(vec![1.0], vec![-0.2]).is_err());
+    }
+
+    #[cfg(feature = "l1l2-integration")]
+    #[test]
+    fn test_forward_rates_reprice_curve() {
+        use pricer_core::market_data::curves::{CurveInterpolation, InterpolatedCurve, YieldCurve};
+
+        let pillars = vec![0.5, 1.0, 2.0, 5.0];
+        let curve = InterpolatedCurve::new(
+            &pillars,
+            &[0.02, 0.025, 0.03, 0.035],
+            CurveInterpolation::Linear,
+            false,
+        )
+        .unwrap();
+        let rate = PiecewiseConstant::from_curve(&curve, pillars.clone()).unwrap();
+        for &t in &pillars {
+            assert_relative_eq!(
+                (-rate.integral(0.0, t)).exp(),
+                curve.discount_factor(t).unwrap(),
+                epsilon = 1e-14
+            );
+        }
+    }
+
+    #[test]
+    fn test_rejects_invalid_pillars() {
+        assert!(PiecewiseConstant::new(vec![], vec![]).is_err());
+        assert!(PiecewiseConstant::new(vec![1.0], vec![0.1, 0.2]).is_err());
+        assert!(PiecewiseConstant::new(vec![0.0, 1.0], vec![0.1, 0.2]).is_err());
+        assert!(PiecewiseConstant::new(vec![2.0, 1.0], vec![0.1, 0.2]).is_err());
+        assert!(PiecewiseConstant::new(vec![1.0], vec![f64::NAN]).is_err());
+    }
+}
@@ -92,6 +92,12 @@ impl<T: Float> AsianArithmeticPayoff<T> {
     pub fn put(strike: T, epsilon: T) -> Self {
         Self::new(AsianParams::put(strike, epsilon))
     }
+
+    /// Returns the payoff parameters.
+    #[inline]
+    pub fn params(&self) -> &AsianParams<T> {
+        &self.params
+    }
 }
 
 impl<T: Float + Send + Sync> PathDependentPayoff<T> for AsianArithmeticPayoff<T> {
@@ -145,6 +151,12 @@ impl<T: Float> AsianGeometricPayoff<T> {
     pub fn put(strike: T, epsilon: T) -> Self {
         Self::new(AsianParams::put(strike, epsilon))
     }
+
+    /// Returns the payoff parameters.
+    #[inline]
+    pub fn params(&self) -> &AsianParams<T> {
+        &self.params
+    }
 }
 
 impl<T: Float + Send + Sync> PathDependentPayoff<T> for AsianGeometricPayoff<T> {
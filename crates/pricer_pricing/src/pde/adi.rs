@@ -0,0 +1,505 @@
+//! 2-D Douglas ADI solver for the Heston PDE.
+//!
+//! In spot `S`, variance `v` and time to maturity `τ` the pricing PDE is
+//!
+//! ```text
+//! V_τ = ½vS² V_SS + ρσvS V_Sv + ½σ²v V_vv + (r - q)S V_S + κ(θ - v) V_v - rV
+//! ```
+//!
+//! The operator is split as `A = A₀ + A₁ + A₂` (mixed, spot and variance
+//! parts, with `-rV` shared equally between `A₁` and `A₂`). Each Douglas
+//! step takes an explicit predictor with the full operator followed by
+//! implicit corrections in the spot and variance directions, so only
+//! tridiagonal systems are solved. Both grids are non-uniform: spot points
+//! cluster around the strike and variance points around zero.
+
+use super::error::{PdeError, PdeResult};
+use super::grid::{lagrange3, Grid};
+use super::instrument::{Exercise, PdeOption, PdePricingResult};
+use super::tridiagonal::solve_tridiagonal;
+use crate::analytical::BarrierDirection;
+use crate::validate::{finite, positive};
+
+/// Grid settings for [`HestonPde`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AdiConfig {
+    /// Number of spot intervals.
+    pub n_spot: usize,
+    /// Number of variance intervals.
+    pub n_variance: usize,
+    /// Number of time steps.
+    pub n_time: usize,
+    /// Number of initial steps replaced by two fully implicit (`θ = 1`)
+    /// half steps.
+    pub damping_steps: usize,
+}
+
+impl AdiConfig {
+    /// Creates a configuration with the given grid size.
+    ///
+    /// # Arguments
+    ///
+    /// * `n_spot` - Number of spot intervals
+    /// * `n_variance` - Number of variance intervals
+    /// * `n_time` - Number of time steps
+    #[inline]
+    pub fn new(n_spot: usize, n_variance: usize, n_time: usize) -> Self {
+        Self {
+            n_spot,
+            n_variance,
+            n_time,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the number of damping steps.
+    #[inline]
+    pub fn with_damping_steps(mut self, damping_steps: usize) -> Self {
+        self.damping_steps = damping_steps;
+        self
+    }
+
+    /// Validates the grid settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PdeError::InvalidParameter` if either direction has fewer
+    /// than 4 intervals or there are no time steps.
+    pub fn validate(&self) -> PdeResult<()> {
+        for (name, n) in [("n_spot", self.n_spot), ("n_variance", self.n_variance)] {
+            if n < 4 {
+                return Err(PdeError::InvalidParameter {
+                    name,
+                    value: format!("{} must be at least 4", n),
+                });
+            }
+        }
+        if self.n_time == 0 {
+            return Err(PdeError::InvalidParameter {
+                name: "n_time",
+                value: "must be at least 1".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+impl Default for AdiConfig {
+    /// Returns a 100 × 50 grid with 100 time steps and 2 damping steps.
+    fn default() -> Self {
+        Self {
+            n_spot: 100,
+            n_variance: 50,
+            n_time: 100,
+            damping_steps: 2,
+        }
+    }
+}
+
+/// Heston stochastic volatility model for the 2-D PDE engine.
+///
+/// # Examples
+///
+/// ```rust
+/// use pricer_pricing::pde::{AdiConfig, HestonPde, PdeOption};
+///
+/// let model = HestonPde::new(0.05, 0.0, 2.0, 0.04, 0.3, -0.7);
+/// let call = model
+///     .price(&PdeOption::european_call(100.0, 1.0), 100.0, 0.04, &AdiConfig::default())
+///     .unwrap();
+/// assert!(call.price > 9.0 && call.price < 11.5);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HestonPde {
+    /// Risk-free rate
+    pub rate: f64,
+    /// Continuous dividend yield
+    pub dividend: f64,
+    /// Mean-reversion speed κ
+    pub kappa: f64,
+    /// Long-run variance θ
+    pub theta: f64,
+    /// Volatility of variance σ
+    pub sigma: f64,
+    /// Spot-variance correlation ρ
+    pub rho: f64,
+}
+
+/// Coefficients of a three-point operator row.
+#[derive(Clone, Copy, Debug, Default)]
+struct Row {
+    lower: f64,
+    diag: f64,
+    upper: f64,
+}
+
+impl HestonPde {
+    /// Creates a Heston model.
+    #[inline]
+    pub fn new(rate: f64, dividend: f64, kappa: f64, theta: f64, sigma: f64, rho: f64) -> Self {
+        Self {
+            rate,
+            dividend,
+            kappa,
+            theta,
+            sigma,
+            rho,
+        }
+    }
+
+    /// Prices an option by Douglas ADI.
+    ///
+    /// # Arguments
+    ///
+    /// * `option` - Instrument to price
+    /// * `spot` - Current spot price
+    /// * `variance` - Current instantaneous variance
+    /// * `config` - Grid settings
+    ///
+    /// # Errors
+    ///
+    /// Returns `PdeError` if the model, instrument or grid is invalid, or
+    /// for American knock-in options.
+    pub fn price(
+        &self,
+        option: &PdeOption,
+        spot: f64,
+        variance: f64,
+        config: &AdiConfig,
+    ) -> PdeResult<PdePricingResult> {
+        config.validate()?;
+        option.validate(spot)?;
+        finite("rate", self.rate)?;
+        finite("dividend", self.dividend)?;
+        positive("kappa", self.kappa)?;
+        positive("theta", self.theta)?;
+        positive("sigma", self.sigma)?;
+        if !(-1.0..=1.0).contains(&self.rho) {
+            return Err(PdeError::InvalidParameter {
+                name: "rho",
+                value: format!("{} must be in [-1, 1]", self.rho),
+            });
+        }
+        if !(variance >= 0.0 && variance.is_finite()) {
+            return Err(PdeError::InvalidParameter {
+                name: "variance",
+                value: format!("{} must be non-negative and finite", variance),
+            });
+        }
+
+        Ok(option.price_with(spot, |option| self.solve(option, spot, variance, config)))
+    }
+
+    /// Solves for a vanilla or knock-out option with the spot inside the
+    /// domain.
+    fn solve(
+        &self,
+        option: &PdeOption,
+        spot: f64,
+        variance: f64,
+        config: &AdiConfig,
+    ) -> PdePricingResult {
+        let strike = option.strike;
+        let mut s_lo = 0.0;
+        let mut s_hi = 8.0 * strike.max(spot);
+        if let Some(barrier) = option.barrier {
+            match barrier.direction {
+                BarrierDirection::Down => s_lo = barrier.level,
+                BarrierDirection::Up => s_hi = barrier.level,
+            }
+        }
+        let v_hi = 5.0_f64.max(5.0 * variance);
+        let s_grid = Grid::sinh(
+            s_lo,
+            s_hi,
+            config.n_spot,
+            strike.clamp(s_lo, s_hi),
+            strike / 5.0,
+        );
+        let v_grid = Grid::sinh(0.0, v_hi, config.n_variance, 0.0, v_hi / 500.0);
+        let (ns, nv) = (s_grid.len(), v_grid.len());
+        let (s, v) = (s_grid.points(), v_grid.points());
+        let idx = |i: usize, j: usize| i * nv + j;
+
+        // Operator rows: A₁ along spot, A₂ along variance, A₀ mixed weights
+        let half_r = 0.5 * self.rate;
+        let mut a1 = vec![Row::default(); ns * nv];
+        let mut a2 = vec![Row::default(); ns * nv];
+        let mut a0 = vec![0.0; ns * nv];
+        for i in 1..ns - 1 {
+            let st = s_grid.stencil(i);
+            for j in 0..nv {
+                let diffusion = 0.5 * v[j] * s[i] * s[i];
+                let drift = (self.rate - self.dividend) * s[i];
+                a1[idx(i, j)] = Row {
+                    lower: diffusion * st.second[0] + drift * st.first[0],
+                    diag: diffusion * st.second[1] + drift * st.first[1] - half_r,
+                    upper: diffusion * st.second[2] + drift * st.first[2],
+                };
+            }
+        }
+        for i in 0..ns {
+            // v = 0: the diffusion vanishes and the drift κθ points into the
+            // domain, so a one-sided forward difference is used
+            let h = v[1] - v[0];
+            let inflow = self.kappa * self.theta / h;
+            a2[idx(i, 0)] = Row {
+                lower: 0.0,
+                diag: -inflow - half_r,
+                upper: inflow,
+            };
+            for j in 1..nv - 1 {
+                let st = v_grid.stencil(j);
+                let diffusion = 0.5 * self.sigma * self.sigma * v[j];
+                let drift = self.kappa * (self.theta - v[j]);
+                a2[idx(i, j)] = Row {
+                    lower: diffusion * st.second[0] + drift * st.first[0],
+                    diag: diffusion * st.second[1] + drift * st.first[1] - half_r,
+                    upper: diffusion * st.second[2] + drift * st.first[2],
+                };
+                if i > 0 && i < ns - 1 {
+                    a0[idx(i, j)] = self.rho * self.sigma * v[j] * s[i];
+                }
+            }
+        }
+
+        let intrinsic: Vec<f64> = s.iter().map(|&x| option.intrinsic(x)).collect();
+        let mut u = vec![0.0; ns * nv];
+        for i in 0..ns {
+            u[idx(i, 0)..idx(i, 0) + nv].fill(intrinsic[i]);
+        }
+        if let Some(barrier) = option.barrier {
+            let i = match barrier.direction {
+                BarrierDirection::Down => 0,
+                BarrierDirection::Up => ns - 1,
+            };
+            u[idx(i, 0)..idx(i, 0) + nv].fill(barrier.rebate);
+        }
+
+        let dt = option.maturity / config.n_time as f64;
+        let n_damped = config.damping_steps.min(config.n_time);
+        let steps = std::iter::repeat((0.5 * dt, 1.0))
+            .take(2 * n_damped)
+            .chain(std::iter::repeat((dt, 0.5)).take(config.n_time - n_damped));
+
+        let apply_a1 = |u: &[f64], out: &mut [f64]| {
+            out.fill(0.0);
+            for i in 1..ns - 1 {
+                for j in 0..nv {
+                    let k = idx(i, j);
+                    let r = a1[k];
+                    out[k] = r.lower * u[k - nv] + r.diag * u[k] + r.upper * u[k + nv];
+                }
+            }
+        };
+        let apply_a2 = |u: &[f64], out: &mut [f64]| {
+            out.fill(0.0);
+            for i in 1..ns - 1 {
+                let k = idx(i, 0);
+                out[k] = a2[k].diag * u[k] + a2[k].upper * u[k + 1];
+                for j in 1..nv - 1 {
+                    let k = idx(i, j);
+                    let r = a2[k];
+                    out[k] = r.lower * u[k - 1] + r.diag * u[k] + r.upper * u[k + 1];
+                }
+            }
+        };
+        let apply_a0 = |u: &[f64], out: &mut [f64]| {
+            out.fill(0.0);
+            for i in 1..ns - 1 {
+                let ws = s_grid.stencil(i).first;
+                for j in 1..nv - 1 {
+                    let wv = v_grid.stencil(j).first;
+                    let mut cross = 0.0;
+                    for (a, &w_s) in ws.iter().enumerate() {
+                        for (b, &w_v) in wv.iter().enumerate() {
+                            cross += w_s * w_v * u[idx(i + a - 1, j + b - 1)];
+                        }
+                    }
+                    out[idx(i, j)] = a0[idx(i, j)] * cross;
+                }
+            }
+        };
+
+        let mut f0 = vec![0.0; ns * nv];
+        let mut f1 = vec![0.0; ns * nv];
+        let mut f2 = vec![0.0; ns * nv];
+        let mut y = vec![0.0; ns * nv];
+        let (mut lower, mut diag, mut upper, mut rhs, mut x) = (
+            vec![0.0; ns],
+            vec![0.0; ns],
+            vec![0.0; ns],
+            vec![0.0; ns],
+            vec![0.0; ns],
+        );
+        let (mut v_lower, mut v_diag, mut v_upper, mut v_rhs, mut v_x) = (
+            vec![0.0; nv],
+            vec![0.0; nv],
+            vec![0.0; nv],
+            vec![0.0; nv],
+            vec![0.0; nv],
+        );
+        let mut tau = 0.0;
+
+        for (step_dt, theta) in steps {
+            tau += step_dt;
+            let implicit = theta * step_dt;
+
+            // Explicit predictor with the full operator
+            apply_a0(&u, &mut f0);
+            apply_a1(&u, &mut f1);
+            apply_a2(&u, &mut f2);
+            for k in 0..ns * nv {
+                y[k] = u[k] + step_dt * (f0[k] + f1[k] + f2[k]);
+            }
+
+            // Implicit spot correction with Dirichlet spot boundaries
+            let (left, right) = option.boundary_values(s_lo, s_hi, self.rate, self.dividend, tau);
+            for j in 0..nv {
+                for i in 1..ns - 1 {
+                    let k = idx(i, j);
+                    let r = a1[k];
+                    lower[i] = -implicit * r.lower;
+                    diag[i] = 1.0 - implicit * r.diag;
+                    upper[i] = -implicit * r.upper;
+                    rhs[i] = y[k] - implicit * f1[k];
+                }
+                diag[0] = 1.0;
+                upper[0] = 0.0;
+                rhs[0] = left;
+                lower[ns - 1] = 0.0;
+                diag[ns - 1] = 1.0;
+                rhs[ns - 1] = right;
+                solve_tridiagonal(&lower, &diag, &upper, &rhs, &mut x);
+                for (i, &value) in x.iter().enumerate() {
+                    y[idx(i, j)] = value;
+                }
+            }
+
+            // Implicit variance correction with V_v = 0 at the top boundary
+            for i in 1..ns - 1 {
+                for j in 0..nv - 1 {
+                    let k = idx(i, j);
+                    let r = a2[k];
+                    v_lower[j] = -implicit * r.lower;
+                    v_diag[j] = 1.0 - implicit * r.diag;
+                    v_upper[j] = -implicit * r.upper;
+                    v_rhs[j] = y[k] - implicit * f2[k];
+                }
+                v_lower[nv - 1] = -1.0;
+                v_diag[nv - 1] = 1.0;
+                v_rhs[nv - 1] = 0.0;
+                solve_tridiagonal(&v_lower, &v_diag, &v_upper, &v_rhs, &mut v_x);
+                y[idx(i, 0)..idx(i, 0) + nv].copy_from_slice(&v_x);
+            }
+
+            std::mem::swap(&mut u, &mut y);
+            if let Exercise::American(_) = option.exercise {
+                for i in 1..ns - 1 {
+                    for value in &mut u[idx(i, 0)..idx(i, 0) + nv] {
+                        *value = value.max(intrinsic[i]);
+                    }
+                }
+            }
+        }
+
+        // Quadratic interpolation in variance, then in spot
+        let i0 = s_grid.locate(spot);
+        let j0 = v_grid.locate(variance);
+        let vs = [v[j0], v[j0 + 1], v[j0 + 2]];
+        let mut at_variance = [0.0; 3];
+        for (a, value) in at_variance.iter_mut().enumerate() {
+            let row = idx(i0 + a, j0);
+            *value = lagrange3(vs, [u[row], u[row + 1], u[row + 2]], variance).0;
+        }
+        let (price, delta, gamma) = lagrange3([s[i0], s[i0 + 1], s[i0 + 2]], at_variance, spot);
+        PdePricingResult {
+            price,
+            delta,
+            gamma,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    const BS_CALL: f64 = 10.450_583_572_185_565;
+    const BS_PUT: f64 = 5.573_526_022_256_971;
+
+    fn heston() -> HestonPde {
+        HestonPde::new(0.05, 0.0, 1.5, 0.04, 0.3, -0.9)
+    }
+
+    #[test]
+    fn test_small_vol_of_vol_matches_black_scholes() {
+        // With v₀ = θ and σ → 0 the variance stays at 0.04 (20% volatility)
+        let model = HestonPde::new(0.05, 0.0, 1.5, 0.04, 1e-4, 0.0);
+        let config = AdiConfig::new(200, 50, 100);
+        let call = model
+            .price(&PdeOption::european_call(100.0, 1.0), 100.0, 0.04, &config)
+            .unwrap();
+        let put = model
+            .price(&PdeOption::european_put(100.0, 1.0), 100.0, 0.04, &config)
+            .unwrap();
+
+        assert_relative_eq!(call.price, BS_CALL, epsilon = 1e-2);
+        assert_relative_eq!(put.price, BS_PUT, epsilon = 1e-2);
+        assert_relative_eq!(call.delta, 0.6368, epsilon = 5e-3);
+    }
+
+    #[test]
+    fn test_put_call_parity() {
+        let config = AdiConfig::default();
+        let call = heston()
+            .price(&PdeOption::european_call(100.0, 1.0), 100.0, 0.04, &config)
+            .unwrap();
+        let put = heston()
+            .price(&PdeOption::european_put(100.0, 1.0), 100.0, 0.04, &config)
+            .unwrap();
+
+        let forward = 100.0 - 100.0 * (-0.05_f64).exp();
+        assert_relative_eq!(call.price - put.price, forward, epsilon = 2e-2);
+        assert_relative_eq!(call.delta - put.delta, 1.0, epsilon = 1e-2);
+    }
+
+    #[test]
+    fn test_american_put_dominates_european() {
+        let config = AdiConfig::default();
+        let european = heston()
+            .price(&PdeOption::european_put(100.0, 1.0), 100.0, 0.04, &config)
+            .unwrap();
+        let american = heston()
+            .price(&PdeOption::american_put(100.0, 1.0), 100.0, 0.04, &config)
+            .unwrap();
+
+        assert!(american.price > european.price + 0.1);
+        assert!(american.price >= 0.0);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        let option = PdeOption::european_call(100.0, 1.0);
+        let config = AdiConfig::default();
+        assert!(matches!(
+            HestonPde::new(0.05, 0.0, 1.5, 0.04, 0.3, -1.5).price(&option, 100.0, 0.04, &config),
+            Err(PdeError::InvalidParameter { name: "rho", .. })
+        ));
+        assert!(matches!(
+            heston().price(&option, 100.0, -0.01, &config),
+            Err(PdeError::InvalidParameter {
+                name: "variance",
+                ..
+            })
+        ));
+        assert!(matches!(
+            heston().price(&option, 100.0, 0.04, &AdiConfig::new(100, 2, 10)),
+            Err(PdeError::InvalidParameter {
+                name: "n_variance",
+                ..
+            })
+        ));
+    }
+}
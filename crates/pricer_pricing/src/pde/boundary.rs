@@ -0,0 +1,222 @@
+//! Early-exercise boundary extraction.
+//!
+//! An American option is exercised wherever its value equals the payoff.
+//! For a put that region is `S ≤ S*(t)` and for a call `S ≥ S*(t)`, and
+//! [`ExerciseBoundary`] records the critical spot `S*` after every time
+//! step of the backward solve. Between grid nodes the boundary is placed
+//! at the geometric midpoint of the last exercised and first continuation
+//! node, so its resolution is half a log-spot cell.
+//!
+//! [`expiry_level`] and [`perpetual_level`] give the known limits of the
+//! boundary as `t → T` and `T → ∞`, against which model validation checks
+//! the fitted curve.
+
+use crate::analytical::OptionType;
+
+/// Value above the payoff, relative to the largest payoff on the grid,
+/// below which a node counts as exercised (absorbs the residual of the PSOR and penalty
+/// iterations).
+const EXERCISE_TOLERANCE: f64 = 1e-6;
+
+/// Critical spot levels of an American option by calendar time.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExerciseBoundary {
+    /// Call or put
+    pub option_type: OptionType,
+    /// Times from valuation in years, ascending
+    pub times: Vec<f64>,
+    /// Critical spot at each time, or `None` where early exercise is never
+    /// optimal
+    pub levels: Vec<Option<f64>>,
+}
+
+impl ExerciseBoundary {
+    /// Creates an empty boundary.
+    pub(crate) fn new(option_type: OptionType) -> Self {
+        Self {
+            option_type,
+            times: Vec::new(),
+            levels: Vec::new(),
+        }
+    }
+
+    /// Records the boundary from the option values at one time step.
+    ///
+    /// Nodes `0` and `n - 1` carry Dirichlet values and are skipped.
+    pub(crate) fn record(&mut self, time: f64, spots: &[f64], values: &[f64], intrinsic: &[f64]) {
+        let n = spots.len();
+        let tolerance = EXERCISE_TOLERANCE * intrinsic.iter().fold(1.0_f64, |m, &g| m.max(g));
+        let exercised = |i: usize| intrinsic[i] > 0.0 && values[i] <= intrinsic[i] + tolerance;
+        let level = match self.option_type {
+            OptionType::Put => (1..n - 1)
+                .rev()
+                .find(|&i| exercised(i))
+                .map(|i| (spots[i] * spots[i + 1]).sqrt()),
+            OptionType::Call => (1..n - 1)
+                .find(|&i| exercised(i))
+                .map(|i| (spots[i - 1] * spots[i]).sqrt()),
+        };
+        self.times.push(time);
+        self.levels.push(level);
+    }
+
+    /// Sorts the records into ascending time.
+    pub(crate) fn finish(mut self) -> Self {
+        self.times.reverse();
+        self.levels.reverse();
+        self
+    }
+
+    /// Returns the number of recorded time steps.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.times.len()
+    }
+
+    /// Returns `true` if no time steps were recorded.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.times.is_empty()
+    }
+
+    /// Returns `(time, critical spot)` pairs where early exercise is
+    /// optimal, ready for plotting.
+    pub fn points(&self) -> Vec<(f64, f64)> {
+        self.times
+            .iter()
+            .zip(&self.levels)
+            .filter_map(|(&t, level)| level.map(|s| (t, s)))
+            .collect()
+    }
+
+    /// Returns the critical spot at `time`, interpolated linearly between
+    /// the neighbouring records.
+    ///
+    /// Returns `None` outside the recorded times or where either neighbour
+    /// has no exercise region.
+    pub fn level_at(&self, time: f64) -> Option<f64> {
+        let first = *self.times.first()?;
+        let last = *self.times.last()?;
+        if !(first..=last).contains(&time) {
+            return None;
+        }
+        let j = self.times.partition_point(|&t| t < time);
+        if self.times[j] == time {
+            return self.levels[j];
+        }
+        let (t0, t1) = (self.times[j - 1], self.times[j]);
+        let (s0, s1) = (self.levels[j - 1]?, self.levels[j]?);
+        Some(s0 + (s1 - s0) * (time - t0) / (t1 - t0))
+    }
+}
+
+/// Limit of the exercise boundary as time to maturity goes to zero.
+///
+/// A put tends to `K·min(1, r/q)` and a call to `K·max(1, r/q)`. Returns
+/// `None` when early exercise is never optimal (a put with `r ≤ 0` or a
+/// call with `q ≤ 0`).
+pub fn expiry_level(option_type: OptionType, strike: f64, rate: f64, dividend: f64) -> Option<f64> {
+    match option_type {
+        OptionType::Put if rate <= 0.0 => None,
+        OptionType::Put if dividend <= rate => Some(strike),
+        OptionType::Put => Some(strike * rate / dividend),
+        OptionType::Call if dividend <= 0.0 => None,
+        OptionType::Call => Some(strike * (rate / dividend).max(1.0)),
+    }
+}
+
+/// Exercise boundary of the perpetual American option (McKean, Merton).
+///
+/// With `γ` the root of `½σ²γ(γ - 1) + (r - q)γ - r = 0` (negative for a
+/// put, above one for a call) the boundary is `K·γ/(γ - 1)`; finite-maturity
+/// boundaries lie between this level and the strike. Returns `None` when
+/// early exercise is never optimal.
+pub fn perpetual_level(
+    option_type: OptionType,
+    strike: f64,
+    rate: f64,
+    dividend: f64,
+    volatility: f64,
+) -> Option<f64> {
+    expiry_level(option_type, strike, rate, dividend)?;
+    let a = 0.5 * volatility * volatility;
+    let b = rate - dividend - a;
+    let discriminant = b * b + 4.0 * a * rate;
+    if discriminant <= 0.0 {
+        return None;
+    }
+    let root = discriminant.sqrt();
+    let gamma = match option_type {
+        OptionType::Put => (-b - root) / (2.0 * a),
+        OptionType::Call => (-b + root) / (2.0 * a),
+    };
+    Some(strike * gamma / (gamma - 1.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_record_finds_put_and_call_edges() {
+        let spots: [f64; 7] = [60.0, 70.0, 80.0, 90.0, 100.0, 110.0, 120.0];
+        let put_payoff: Vec<f64> = spots.iter().map(|s| (100.0 - s).max(0.0)).collect();
+        let mut put_values = put_payoff.clone();
+        put_values[3] += 1.0;
+        put_values[4] += 2.0;
+        let mut put = ExerciseBoundary::new(OptionType::Put);
+        put.record(0.5, &spots, &put_values, &put_payoff);
+        assert_relative_eq!(put.levels[0].unwrap(), (80.0_f64 * 90.0).sqrt());
+
+        let call_payoff: Vec<f64> = spots.iter().map(|s| (s - 80.0).max(0.0)).collect();
+        let mut call_values = call_payoff.clone();
+        call_values[3] += 0.5;
+        let mut call = ExerciseBoundary::new(OptionType::Call);
+        call.record(0.5, &spots, &call_values, &call_payoff);
+        assert_relative_eq!(call.levels[0].unwrap(), (90.0_f64 * 100.0).sqrt());
+
+        // Out of the money everywhere: no exercise region
+        let mut none = ExerciseBoundary::new(OptionType::Put);
+        none.record(0.5, &spots, &[1.0; 7], &[0.0; 7]);
+        assert_eq!(none.levels[0], None);
+    }
+
+    #[test]
+    fn test_points_and_interpolation() {
+        let boundary = ExerciseBoundary {
+            option_type: OptionType::Put,
+            times: vec![0.0, 0.5, 1.0],
+            levels: vec![Some(80.0), Some(90.0), None],
+        };
+        assert_eq!(boundary.points(), vec![(0.0, 80.0), (0.5, 90.0)]);
+        assert_relative_eq!(boundary.level_at(0.25).unwrap(), 85.0);
+        assert_eq!(boundary.level_at(0.5), Some(90.0));
+        assert_eq!(boundary.level_at(0.75), None);
+        assert_eq!(boundary.level_at(1.5), None);
+    }
+
+    #[test]
+    fn test_asymptotic_levels() {
+        assert_eq!(expiry_level(OptionType::Put, 100.0, 0.05, 0.0), Some(100.0));
+        assert_relative_eq!(
+            expiry_level(OptionType::Put, 100.0, 0.02, 0.04).unwrap(),
+            50.0
+        );
+        assert_eq!(expiry_level(OptionType::Call, 100.0, 0.05, 0.0), None);
+        assert_relative_eq!(
+            expiry_level(OptionType::Call, 100.0, 0.05, 0.02).unwrap(),
+            250.0
+        );
+
+        // Without dividends the perpetual put boundary is 2rK / (2r + σ²)
+        assert_relative_eq!(
+            perpetual_level(OptionType::Put, 100.0, 0.05, 0.0, 0.2).unwrap(),
+            100.0 * 0.1 / 0.14,
+            epsilon = 1e-12
+        );
+        assert_eq!(perpetual_level(OptionType::Put, 100.0, 0.0, 0.0, 0.2), None);
+        assert!(perpetual_level(OptionType::Call, 100.0, 0.05, 0.03, 0.2).unwrap() > 100.0);
+    }
+}
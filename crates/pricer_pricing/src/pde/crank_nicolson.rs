@@ -0,0 +1,585 @@
+//! 1-D Crank-Nicolson solver for the Black-Scholes PDE.
+//!
+//! In log-spot `x = ln S` and time to maturity `τ` the pricing PDE is
+//!
+//! ```text
+//! V_τ = ½σ² V_xx + (r - q - ½σ²) V_x - r V
+//! ```
+//!
+//! which is solved on a uniform `x` grid spanning `n_std_devs` standard
+//! deviations either side of the spot and strike. The first
+//! `rannacher_steps` steps are each replaced by two fully implicit half
+//! steps, which removes the oscillations Crank-Nicolson otherwise produces
+//! from the non-smooth payoff and spoils the gamma.
+
+use super::boundary::ExerciseBoundary;
+use super::error::{PdeError, PdeResult};
+use super::grid::{lagrange3, Grid};
+use super::instrument::{AmericanMethod, Exercise, PdeOption, PdePricingResult};
+use super::tridiagonal::{solve_penalty, solve_psor, solve_tridiagonal};
+use crate::analytical::{BarrierDirection, KnockType};
+use crate::validate::{finite, positive};
+
+/// Grid settings for [`BlackScholesPde`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PdeConfig {
+    /// Number of space intervals.
+    pub n_space: usize,
+    /// Number of time steps.
+    pub n_time: usize,
+    /// Half-width of the log-spot domain in standard deviations `σ√T`.
+    pub n_std_devs: f64,
+    /// Number of initial Crank-Nicolson steps replaced by implicit half steps.
+    pub rannacher_steps: usize,
+}
+
+impl PdeConfig {
+    /// Creates a configuration with the given grid size.
+    ///
+    /// # Arguments
+    ///
+    /// * `n_space` - Number of space intervals
+    /// * `n_time` - Number of time steps
+    #[inline]
+    pub fn new(n_space: usize, n_time: usize) -> Self {
+        Self {
+            n_space,
+            n_time,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the domain half-width in standard deviations.
+    #[inline]
+    pub fn with_n_std_devs(mut self, n_std_devs: f64) -> Self {
+        self.n_std_devs = n_std_devs;
+        self
+    }
+
+    /// Sets the number of Rannacher start-up steps.
+    #[inline]
+    pub fn with_rannacher_steps(mut self, rannacher_steps: usize) -> Self {
+        self.rannacher_steps = rannacher_steps;
+        self
+    }
+
+    /// Validates the grid settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PdeError::InvalidParameter` if there are fewer than 4 space
+    /// intervals, no time steps, or a non-positive domain width.
+    pub fn validate(&self) -> PdeResult<()> {
+        if self.n_space < 4 {
+            return Err(PdeError::InvalidParameter {
+                name: "n_space",
+                value: format!("{} must be at least 4", self.n_space),
+            });
+        }
+        if self.n_time == 0 {
+            return Err(PdeError::InvalidParameter {
+                name: "n_time",
+                value: "must be at least 1".to_string(),
+            });
+        }
+        positive("n_std_devs", self.n_std_devs)?;
+        Ok(())
+    }
+}
+
+impl Default for PdeConfig {
+    /// Returns 400 space intervals, 200 time steps, ±5σ√T and 2 Rannacher
+    /// steps.
+    fn default() -> Self {
+        Self {
+            n_space: 400,
+            n_time: 200,
+            n_std_devs: 5.0,
+            rannacher_steps: 2,
+        }
+    }
+}
+
+/// Black-Scholes model for the 1-D PDE engine.
+///
+/// # Examples
+///
+/// ```rust
+/// use pricer_pricing::analytical::{down_out_call, BarrierDirection, KnockType};
+/// use pricer_pricing::pde::{BlackScholesPde, PdeBarrier, PdeConfig, PdeOption};
+///
+/// let model = BlackScholesPde::new(0.05, 0.0, 0.2);
+/// let option = PdeOption::european_call(100.0, 1.0)
+///     .with_barrier(PdeBarrier::new(90.0, BarrierDirection::Down, KnockType::Out));
+///
+/// let pde = model.price(&option, 100.0, &PdeConfig::default()).unwrap();
+/// let exact = down_out_call(100.0, 100.0, 90.0, 0.05, 0.0, 0.2, 1.0);
+/// assert!((pde.price - exact).abs() < 1e-2);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BlackScholesPde {
+    /// Risk-free rate
+    pub rate: f64,
+    /// Continuous dividend yield
+    pub dividend: f64,
+    /// Volatility
+    pub volatility: f64,
+}
+
+impl BlackScholesPde {
+    /// Creates a Black-Scholes model.
+    #[inline]
+    pub fn new(rate: f64, dividend: f64, volatility: f64) -> Self {
+        Self {
+            rate,
+            dividend,
+            volatility,
+        }
+    }
+
+    /// Prices an option by Crank-Nicolson.
+    ///
+    /// # Arguments
+    ///
+    /// * `option` - Instrument to price
+    /// * `spot` - Current spot price
+    /// * `config` - Grid settings
+    ///
+    /// # Errors
+    ///
+    /// Returns `PdeError` if the model, instrument or grid is invalid, or
+    /// for American knock-in options.
+    pub fn price(
+        &self,
+        option: &PdeOption,
+        spot: f64,
+        config: &PdeConfig,
+    ) -> PdeResult<PdePricingResult> {
+        config.validate()?;
+        option.validate(spot)?;
+        positive("volatility", self.volatility)?;
+        finite("rate", self.rate)?;
+        finite("dividend", self.dividend)?;
+
+        Ok(option.price_with(spot, |option| self.solve(option, spot, config, None)))
+    }
+
+    /// Extracts the early-exercise boundary of an American option from the
+    /// Crank-Nicolson solve, one level per time step.
+    ///
+    /// # Arguments
+    ///
+    /// * `option` - American option, vanilla or knock-out
+    /// * `spot` - Current spot price
+    /// * `config` - Grid settings
+    ///
+    /// # Errors
+    ///
+    /// Returns `PdeError` if the model, instrument or grid is invalid, and
+    /// `PdeError::Unsupported` for European exercise, knock-in barriers or
+    /// an already breached barrier.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pricer_pricing::analytical::OptionType;
+    /// use pricer_pricing::pde::{perpetual_level, BlackScholesPde, PdeConfig, PdeOption};
+    ///
+    /// let model = BlackScholesPde::new(0.05, 0.0, 0.2);
+    /// let boundary = model
+    ///     .exercise_boundary(&PdeOption::american_put(100.0, 1.0), 100.0, &PdeConfig::default())
+    ///     .unwrap();
+    ///
+    /// // The put boundary lies between the perpetual level and the strike
+    /// let floor = perpetual_level(OptionType::Put, 100.0, 0.05, 0.0, 0.2).unwrap();
+    /// assert!(boundary.points().iter().all(|&(_, s)| floor < s && s < 100.0));
+    /// ```
+    pub fn exercise_boundary(
+        &self,
+        option: &PdeOption,
+        spot: f64,
+        config: &PdeConfig,
+    ) -> PdeResult<ExerciseBoundary> {
+        config.validate()?;
+        option.validate(spot)?;
+        positive("volatility", self.volatility)?;
+        finite("rate", self.rate)?;
+        finite("dividend", self.dividend)?;
+        if !option.is_american() {
+            return Err(PdeError::Unsupported {
+                message: "exercise boundary requires American exercise".to_string(),
+            });
+        }
+        if let Some(barrier) = option.barrier {
+            if barrier.knock == KnockType::In || barrier.is_breached(spot) {
+                return Err(PdeError::Unsupported {
+                    message: "exercise boundary requires a live knock-out barrier".to_string(),
+                });
+            }
+        }
+
+        let mut boundary = ExerciseBoundary::new(option.option_type);
+        self.solve(option, spot, config, Some(&mut boundary));
+        Ok(boundary.finish())
+    }
+
+    /// Solves for a vanilla or knock-out option with the spot inside the
+    /// domain, recording the exercise boundary after each step if requested.
+    fn solve(
+        &self,
+        option: &PdeOption,
+        spot: f64,
+        config: &PdeConfig,
+        mut boundary: Option<&mut ExerciseBoundary>,
+    ) -> PdePricingResult {
+        let x0 = spot.ln();
+        let x_strike = option.strike.ln();
+        let width = config.n_std_devs * self.volatility * option.maturity.sqrt();
+        let mut lo = x0.min(x_strike) - width;
+        let mut hi = x0.max(x_strike) + width;
+        if let Some(barrier) = option.barrier {
+            match barrier.direction {
+                BarrierDirection::Down => lo = barrier.level.ln(),
+                BarrierDirection::Up => hi = barrier.level.ln(),
+            }
+        }
+
+        let grid = Grid::uniform(lo, hi, config.n_space);
+        let n = grid.len();
+        let spots: Vec<f64> = grid.points().iter().map(|x| x.exp()).collect();
+        let intrinsic: Vec<f64> = spots.iter().map(|&s| option.intrinsic(s)).collect();
+
+        // Operator coefficients (constant in time)
+        let a = 0.5 * self.volatility * self.volatility;
+        let b = self.rate - self.dividend - a;
+        let mut op_lower = vec![0.0; n];
+        let mut op_diag = vec![0.0; n];
+        let mut op_upper = vec![0.0; n];
+        for i in 1..n - 1 {
+            let s = grid.stencil(i);
+            op_lower[i] = a * s.second[0] + b * s.first[0];
+            op_diag[i] = a * s.second[1] + b * s.first[1] - self.rate;
+            op_upper[i] = a * s.second[2] + b * s.first[2];
+        }
+
+        let mut values = intrinsic.clone();
+        if let Some(barrier) = option.barrier {
+            match barrier.direction {
+                BarrierDirection::Down => values[0] = barrier.rebate,
+                BarrierDirection::Up => values[n - 1] = barrier.rebate,
+            }
+        }
+
+        // Time steps as (dτ, θ): Rannacher half steps, then Crank-Nicolson
+        let dt = option.maturity / config.n_time as f64;
+        let n_damped = config.rannacher_steps.min(config.n_time);
+        let steps = std::iter::repeat((0.5 * dt, 1.0))
+            .take(2 * n_damped)
+            .chain(std::iter::repeat((dt, 0.5)).take(config.n_time - n_damped));
+
+        let mut lower = vec![0.0; n];
+        let mut diag = vec![0.0; n];
+        let mut upper = vec![0.0; n];
+        let mut rhs = vec![0.0; n];
+        let mut next = vec![0.0; n];
+        let mut floor = vec![f64::NEG_INFINITY; n];
+        let mut tau = 0.0;
+
+        for (step_dt, theta) in steps {
+            tau += step_dt;
+            let explicit = (1.0 - theta) * step_dt;
+            let implicit = theta * step_dt;
+            for i in 1..n - 1 {
+                rhs[i] = values[i]
+                    + explicit
+                        * (op_lower[i] * values[i - 1]
+                            + op_diag[i] * values[i]
+                            + op_upper[i] * values[i + 1]);
+                lower[i] = -implicit * op_lower[i];
+                diag[i] = 1.0 - implicit * op_diag[i];
+                upper[i] = -implicit * op_upper[i];
+            }
+            // Dirichlet rows
+            let (left, right) =
+                option.boundary_values(spots[0], spots[n - 1], self.rate, self.dividend, tau);
+            diag[0] = 1.0;
+            upper[0] = 0.0;
+            rhs[0] = left;
+            lower[n - 1] = 0.0;
+            diag[n - 1] = 1.0;
+            rhs[n - 1] = right;
+
+            match option.exercise {
+                Exercise::European => solve_tridiagonal(&lower, &diag, &upper, &rhs, &mut next),
+                Exercise::American(method) => {
+                    floor[1..n - 1].copy_from_slice(&intrinsic[1..n - 1]);
+                    next.copy_from_slice(&values);
+                    next[0] = left;
+                    next[n - 1] = right;
+                    match method {
+                        AmericanMethod::Psor {
+                            omega,
+                            tolerance,
+                            max_iterations,
+                        } => solve_psor(
+                            &lower,
+                            &diag,
+                            &upper,
+                            &rhs,
+                            &floor,
+                            &mut next,
+                            omega,
+                            tolerance,
+                            max_iterations,
+                        ),
+                        AmericanMethod::Penalty {
+                            penalty,
+                            tolerance,
+                            max_iterations,
+                        } => solve_penalty(
+                            &lower,
+                            &diag,
+                            &upper,
+                            &rhs,
+                            &floor,
+                            &mut next,
+                            penalty,
+                            tolerance,
+                            max_iterations,
+                        ),
+                    };
+                }
+            }
+            std::mem::swap(&mut values, &mut next);
+            if let Some(boundary) = boundary.as_deref_mut() {
+                boundary.record(option.maturity - tau, &spots, &values, &intrinsic);
+            }
+        }
+
+        let start = grid.locate(x0);
+        let xs = [
+            grid.points()[start],
+            grid.points()[start + 1],
+            grid.points()[start + 2],
+        ];
+        let ys = [values[start], values[start + 1], values[start + 2]];
+        let (price, v_x, v_xx) = lagrange3(xs, ys, x0);
+        PdePricingResult {
+            price,
+            delta: v_x / spot,
+            gamma: (v_xx - v_x) / (spot * spot),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analytical::{down_in_call, down_out_call, up_out_call, OptionType};
+    use crate::pde::{perpetual_level, PdeBarrier};
+    use approx::assert_relative_eq;
+
+    // Black-Scholes (S=K=100, r=5%, q=0, σ=20%, T=1)
+    const BS_CALL: f64 = 10.450_583_572_185_565;
+    const BS_PUT: f64 = 5.573_526_022_256_971;
+    const BS_CALL_DELTA: f64 = 0.636_830_651_175_619;
+    const BS_GAMMA: f64 = 0.018_762_017_345_847;
+    // American put reference (high-resolution binomial tree)
+    const AMERICAN_PUT: f64 = 6.090_4;
+
+    fn model() -> BlackScholesPde {
+        BlackScholesPde::new(0.05, 0.0, 0.2)
+    }
+
+    #[test]
+    fn test_european_matches_black_scholes() {
+        let config = PdeConfig::default();
+        let call = model()
+            .price(&PdeOption::european_call(100.0, 1.0), 100.0, &config)
+            .unwrap();
+        let put = model()
+            .price(&PdeOption::european_put(100.0, 1.0), 100.0, &config)
+            .unwrap();
+
+        assert_relative_eq!(call.price, BS_CALL, epsilon = 2e-3);
+        assert_relative_eq!(put.price, BS_PUT, epsilon = 2e-3);
+        assert_relative_eq!(call.delta, BS_CALL_DELTA, epsilon = 1e-3);
+        assert_relative_eq!(put.delta, BS_CALL_DELTA - 1.0, epsilon = 1e-3);
+        assert_relative_eq!(call.gamma, BS_GAMMA, epsilon = 1e-4);
+        assert_relative_eq!(put.gamma, BS_GAMMA, epsilon = 1e-4);
+    }
+
+    #[test]
+    fn test_convergence_is_second_order() {
+        let option = PdeOption::european_call(100.0, 1.0);
+        let errors: Vec<f64> = [50, 100, 200]
+            .iter()
+            .map(|&n| {
+                let config = PdeConfig::new(2 * n, n);
+                (model().price(&option, 100.0, &config).unwrap().price - BS_CALL).abs()
+            })
+            .collect();
+
+        assert!(errors[1] < 0.35 * errors[0], "{:?}", errors);
+        assert!(errors[2] < 0.35 * errors[1], "{:?}", errors);
+    }
+
+    #[test]
+    fn test_american_put_psor_and_penalty() {
+        let config = PdeConfig::default();
+        let psor = model()
+            .price(&PdeOption::american_put(100.0, 1.0), 100.0, &config)
+            .unwrap();
+        let penalty = model()
+            .price(
+                &PdeOption::european_put(100.0, 1.0).with_exercise(Exercise::American(
+                    AmericanMethod::Penalty {
+                        penalty: 1e8,
+                        tolerance: 1e-8,
+                        max_iterations: 50,
+                    },
+                )),
+                100.0,
+                &config,
+            )
+            .unwrap();
+
+        assert_relative_eq!(psor.price, AMERICAN_PUT, epsilon = 5e-3);
+        assert_relative_eq!(psor.price, penalty.price, epsilon = 1e-4);
+        assert!(psor.price > BS_PUT);
+
+        // Deep in the money the put is exercised immediately
+        let deep = model()
+            .price(&PdeOption::american_put(100.0, 1.0), 60.0, &config)
+            .unwrap();
+        assert_relative_eq!(deep.price, 40.0, epsilon = 1e-6);
+        assert_relative_eq!(deep.delta, -1.0, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn test_american_call_without_dividends_is_european() {
+        let config = PdeConfig::default();
+        let american = model()
+            .price(&PdeOption::american_call(100.0, 1.0), 100.0, &config)
+            .unwrap();
+        assert_relative_eq!(american.price, BS_CALL, epsilon = 2e-3);
+    }
+
+    #[test]
+    fn test_barriers_match_closed_form() {
+        let config = PdeConfig::default();
+        let down = PdeBarrier::new(90.0, BarrierDirection::Down, KnockType::Out);
+        let up = PdeBarrier::new(130.0, BarrierDirection::Up, KnockType::Out);
+
+        let cases = [
+            (
+                PdeOption::european_call(100.0, 1.0).with_barrier(down),
+                down_out_call(100.0, 100.0, 90.0, 0.05, 0.0, 0.2, 1.0),
+            ),
+            (
+                PdeOption::european_call(100.0, 1.0).with_barrier(up),
+                up_out_call(100.0, 100.0, 130.0, 0.05, 0.0, 0.2, 1.0),
+            ),
+            (
+                PdeOption::european_call(100.0, 1.0).with_barrier(PdeBarrier {
+                    knock: KnockType::In,
+                    ..down
+                }),
+                down_in_call(100.0, 100.0, 90.0, 0.05, 0.0, 0.2, 1.0),
+            ),
+        ];
+        for (option, exact) in cases {
+            let pde = model().price(&option, 100.0, &config).unwrap();
+            assert_relative_eq!(pde.price, exact, epsilon = 5e-3);
+        }
+    }
+
+    #[test]
+    fn test_exercise_boundary_put_asymptotics() {
+        let config = PdeConfig::new(800, 400);
+        let boundary = model()
+            .exercise_boundary(&PdeOption::american_put(100.0, 1.0), 100.0, &config)
+            .unwrap();
+        // Rannacher half steps record twice
+        assert_eq!(boundary.len(), config.n_time + config.rannacher_steps);
+        assert!(boundary.times.windows(2).all(|w| w[0] < w[1]));
+        assert_relative_eq!(boundary.times[0], 0.0, epsilon = 1e-12);
+
+        let points = boundary.points();
+        assert_eq!(points.len(), boundary.len());
+        // Rises monotonically towards the strike as expiry approaches
+        let tolerance = 0.01 * 100.0;
+        assert!(points.windows(2).all(|w| w[1].1 >= w[0].1 - tolerance));
+        let perpetual = perpetual_level(OptionType::Put, 100.0, 0.05, 0.0, 0.2).unwrap();
+        assert!(points.iter().all(|&(_, s)| perpetual < s && s < 100.0));
+        // K - S* shrinks only like σK√(τ ln(1/τ)) as τ → 0
+        let near_expiry = points.last().unwrap().1;
+        assert_relative_eq!(near_expiry, 100.0, max_relative = 0.03);
+        // One-year critical spot from a 2000-step binomial tree
+        assert_relative_eq!(points[0].1, 81.1, max_relative = 5e-3);
+    }
+
+    #[test]
+    fn test_exercise_boundary_call() {
+        let config = PdeConfig::default();
+        let call = PdeOption::american_call(100.0, 1.0);
+
+        // Never exercised early without dividends
+        let boundary = model().exercise_boundary(&call, 100.0, &config).unwrap();
+        assert!(boundary.points().is_empty());
+
+        let dividend = BlackScholesPde::new(0.03, 0.06, 0.2);
+        let boundary = dividend.exercise_boundary(&call, 100.0, &config).unwrap();
+        let points = boundary.points();
+        assert!(!points.is_empty());
+        let perpetual = perpetual_level(OptionType::Call, 100.0, 0.03, 0.06, 0.2).unwrap();
+        assert!(points.iter().all(|&(_, s)| 100.0 < s && s < perpetual));
+    }
+
+    #[test]
+    fn test_exercise_boundary_rejects_european() {
+        let config = PdeConfig::default();
+        assert!(matches!(
+            model().exercise_boundary(&PdeOption::european_put(100.0, 1.0), 100.0, &config),
+            Err(PdeError::Unsupported { .. })
+        ));
+        let breached = PdeOption::american_put(100.0, 1.0).with_barrier(PdeBarrier::new(
+            120.0,
+            BarrierDirection::Up,
+            KnockType::Out,
+        ));
+        assert!(matches!(
+            model().exercise_boundary(&breached, 125.0, &config),
+            Err(PdeError::Unsupported { .. })
+        ));
+    }
+
+    #[test]
+    fn test_breached_barrier() {
+        let config = PdeConfig::default();
+        let option = PdeOption::european_call(100.0, 1.0).with_barrier(
+            PdeBarrier::new(90.0, BarrierDirection::Down, KnockType::Out).with_rebate(2.0),
+        );
+        let result = model().price(&option, 85.0, &config).unwrap();
+        assert_eq!(result.price, 2.0);
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        let option = PdeOption::european_call(100.0, 1.0);
+        assert!(matches!(
+            model().price(&option, 100.0, &PdeConfig::new(2, 10)),
+            Err(PdeError::InvalidParameter {
+                name: "n_space",
+                ..
+            })
+        ));
+        assert!(matches!(
+            BlackScholesPde::new(0.05, 0.0, 0.0).price(&option, 100.0, &PdeConfig::default()),
+            Err(PdeError::InvalidParameter {
+                name: "volatility",
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,32 @@
+//! Error types for the PDE engines.
+
+use thiserror::Error;
+
+/// Errors from PDE grid construction and solving.
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum PdeError {
+    /// Invalid model, instrument or grid parameter.
+    #[error("Invalid parameter '{name}': {value}")]
+    InvalidParameter {
+        /// Parameter name
+        name: &'static str,
+        /// Description of the invalid value
+        value: String,
+    },
+
+    /// The requested combination is not supported by the solver.
+    #[error("Unsupported: {message}")]
+    Unsupported {
+        /// Description of the unsupported feature
+        message: String,
+    },
+}
+
+/// Result type for PDE pricing.
+pub type PdeResult<T> = Result<T, PdeError>;
+
+impl From<crate::validate::Invalid> for PdeError {
+    fn from((name, value): crate::validate::Invalid) -> Self {
+        PdeError::InvalidParameter { name, value }
+    }
+}
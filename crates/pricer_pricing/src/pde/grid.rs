@@ -0,0 +1,156 @@
+//! Non-uniform 1-D grids and finite-difference weights.
+
+/// Three-point finite-difference weights at an interior node.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) struct Stencil {
+    /// First-derivative weights for nodes `i-1, i, i+1`.
+    pub first: [f64; 3],
+    /// Second-derivative weights for nodes `i-1, i, i+1`.
+    pub second: [f64; 3],
+}
+
+/// Monotone grid with precomputed central-difference weights.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Grid {
+    points: Vec<f64>,
+    stencils: Vec<Stencil>,
+}
+
+impl Grid {
+    /// Uniform grid of `n + 1` points on `[lo, hi]`.
+    pub fn uniform(lo: f64, hi: f64, n: usize) -> Self {
+        let h = (hi - lo) / n as f64;
+        Self::from_points((0..=n).map(|i| lo + h * i as f64).collect())
+    }
+
+    /// Grid of `n + 1` points on `[lo, hi]` concentrated around `centre`.
+    ///
+    /// Points are `centre + c·sinh(ξ)` for uniform `ξ`; smaller `c` gives
+    /// stronger concentration.
+    pub fn sinh(lo: f64, hi: f64, n: usize, centre: f64, c: f64) -> Self {
+        let xi_lo = ((lo - centre) / c).asinh();
+        let xi_hi = ((hi - centre) / c).asinh();
+        let d_xi = (xi_hi - xi_lo) / n as f64;
+        let mut points: Vec<f64> = (0..=n)
+            .map(|i| centre + c * (xi_lo + d_xi * i as f64).sinh())
+            .collect();
+        // Pin the end points exactly (barriers sit on them)
+        points[0] = lo;
+        points[n] = hi;
+        Self::from_points(points)
+    }
+
+    fn from_points(points: Vec<f64>) -> Self {
+        let n = points.len();
+        let mut stencils = vec![Stencil::default(); n];
+        for i in 1..n.saturating_sub(1) {
+            let hl = points[i] - points[i - 1];
+            let hr = points[i + 1] - points[i];
+            let sum = hl + hr;
+            stencils[i] = Stencil {
+                first: [-hr / (hl * sum), (hr - hl) / (hl * hr), hl / (hr * sum)],
+                second: [2.0 / (hl * sum), -2.0 / (hl * hr), 2.0 / (hr * sum)],
+            };
+        }
+        Self { points, stencils }
+    }
+
+    /// Returns the grid points.
+    #[inline]
+    pub fn points(&self) -> &[f64] {
+        &self.points
+    }
+
+    /// Returns the number of points.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    /// Returns the stencil at interior node `i`.
+    #[inline]
+    pub fn stencil(&self, i: usize) -> &Stencil {
+        &self.stencils[i]
+    }
+
+    /// Returns the start of the three-point stencil nearest `x`.
+    pub fn locate(&self, x: f64) -> usize {
+        let n = self.points.len();
+        let upper = self.points.partition_point(|&p| p < x).clamp(1, n - 1);
+        // Centre the stencil on the closer of the bracketing points
+        let nearest = if x - self.points[upper - 1] < self.points[upper] - x {
+            upper - 1
+        } else {
+            upper
+        };
+        nearest.clamp(1, n - 2) - 1
+    }
+}
+
+/// Quadratic Lagrange interpolation through three points.
+///
+/// Returns the value, first and second derivative at `x`.
+pub(crate) fn lagrange3(xs: [f64; 3], ys: [f64; 3], x: f64) -> (f64, f64, f64) {
+    let [x0, x1, x2] = xs;
+    let [y0, y1, y2] = ys;
+    let d0 = (x0 - x1) * (x0 - x2);
+    let d1 = (x1 - x0) * (x1 - x2);
+    let d2 = (x2 - x0) * (x2 - x1);
+
+    let value = y0 * (x - x1) * (x - x2) / d0
+        + y1 * (x - x0) * (x - x2) / d1
+        + y2 * (x - x0) * (x - x1) / d2;
+    let first = y0 * (2.0 * x - x1 - x2) / d0
+        + y1 * (2.0 * x - x0 - x2) / d1
+        + y2 * (2.0 * x - x0 - x1) / d2;
+    let second = 2.0 * (y0 / d0 + y1 / d1 + y2 / d2);
+    (value, first, second)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_stencils_exact_for_quadratics() {
+        let grid = Grid::sinh(0.0, 10.0, 20, 4.0, 1.0);
+        let f = |x: f64| 3.0 * x * x - 2.0 * x + 1.0;
+        for i in 1..grid.len() - 1 {
+            let s = grid.stencil(i);
+            let p = grid.points();
+            let values = [f(p[i - 1]), f(p[i]), f(p[i + 1])];
+            let d1: f64 = s.first.iter().zip(values).map(|(w, v)| w * v).sum();
+            let d2: f64 = s.second.iter().zip(values).map(|(w, v)| w * v).sum();
+            assert_relative_eq!(d1, 6.0 * p[i] - 2.0, epsilon = 1e-9);
+            assert_relative_eq!(d2, 6.0, epsilon = 1e-7);
+        }
+    }
+
+    #[test]
+    fn test_sinh_grid_concentrates_and_pins_ends() {
+        let grid = Grid::sinh(0.0, 400.0, 100, 100.0, 10.0);
+        let p = grid.points();
+        assert_eq!(p[0], 0.0);
+        assert_eq!(p[100], 400.0);
+        let near = grid.locate(100.0);
+        let far = grid.locate(350.0);
+        assert!(p[near + 1] - p[near] < 0.2 * (p[far + 1] - p[far]));
+    }
+
+    #[test]
+    fn test_lagrange3() {
+        let (v, d1, d2) = lagrange3([1.0, 2.0, 4.0], [1.0, 4.0, 16.0], 3.0);
+        assert_relative_eq!(v, 9.0, epsilon = 1e-12);
+        assert_relative_eq!(d1, 6.0, epsilon = 1e-12);
+        assert_relative_eq!(d2, 2.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_locate() {
+        let grid = Grid::uniform(0.0, 10.0, 10);
+        assert_eq!(grid.locate(5.2), 4);
+        assert_eq!(grid.locate(0.0), 0);
+        assert_eq!(grid.locate(10.0), 8);
+    }
+}
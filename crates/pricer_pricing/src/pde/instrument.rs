@@ -0,0 +1,382 @@
+//! Instrument and result types shared by the PDE solvers.
+
+use super::error::{PdeError, PdeResult};
+use crate::analytical::{BarrierDirection, KnockType, OptionType};
+use crate::validate::positive;
+
+/// Method for enforcing the early-exercise constraint `V ≥ payoff`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AmericanMethod {
+    /// Projected successive over-relaxation on the linear complementarity
+    /// problem.
+    Psor {
+        /// Relaxation factor in (0, 2)
+        omega: f64,
+        /// Convergence tolerance on the maximum update
+        tolerance: f64,
+        /// Maximum sweeps per time step
+        max_iterations: usize,
+    },
+
+    /// Forsyth-Vetzal penalty iteration: `(A + P) V = b + P g` with a large
+    /// penalty on nodes where the constraint is active.
+    Penalty {
+        /// Penalty factor (typically `1 / tolerance`)
+        penalty: f64,
+        /// Convergence tolerance on the relative update
+        tolerance: f64,
+        /// Maximum iterations per time step
+        max_iterations: usize,
+    },
+}
+
+impl Default for AmericanMethod {
+    /// Returns PSOR with `ω = 1.2`, tolerance `1e-10` and 500 sweeps.
+    fn default() -> Self {
+        AmericanMethod::Psor {
+            omega: 1.2,
+            tolerance: 1e-10,
+            max_iterations: 500,
+        }
+    }
+}
+
+/// Exercise style.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Exercise {
+    /// Exercise at maturity only.
+    #[default]
+    European,
+    /// Exercise at any time up to maturity.
+    American(AmericanMethod),
+}
+
+/// Continuously monitored barrier.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PdeBarrier {
+    /// Barrier level
+    pub level: f64,
+    /// Whether the barrier is above or below the spot
+    pub direction: BarrierDirection,
+    /// Knock-in or knock-out
+    pub knock: KnockType,
+    /// Rebate paid immediately when a knock-out barrier is hit
+    pub rebate: f64,
+}
+
+impl PdeBarrier {
+    /// Creates a barrier without rebate.
+    #[inline]
+    pub fn new(level: f64, direction: BarrierDirection, knock: KnockType) -> Self {
+        Self {
+            level,
+            direction,
+            knock,
+            rebate: 0.0,
+        }
+    }
+
+    /// Sets the knock-out rebate.
+    #[inline]
+    pub fn with_rebate(mut self, rebate: f64) -> Self {
+        self.rebate = rebate;
+        self
+    }
+
+    /// Returns whether `spot` has already breached the barrier.
+    #[inline]
+    pub(crate) fn is_breached(&self, spot: f64) -> bool {
+        match self.direction {
+            BarrierDirection::Up => spot >= self.level,
+            BarrierDirection::Down => spot <= self.level,
+        }
+    }
+}
+
+/// Option priced by the PDE engines.
+///
+/// # Examples
+///
+/// ```rust
+/// use pricer_pricing::analytical::{BarrierDirection, KnockType};
+/// use pricer_pricing::pde::{PdeBarrier, PdeOption};
+///
+/// let option = PdeOption::european_call(100.0, 1.0)
+///     .with_barrier(PdeBarrier::new(90.0, BarrierDirection::Down, KnockType::Out));
+/// assert!(option.barrier.is_some());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PdeOption {
+    /// Strike price
+    pub strike: f64,
+    /// Time to maturity in years
+    pub maturity: f64,
+    /// Call or put
+    pub option_type: OptionType,
+    /// Exercise style
+    pub exercise: Exercise,
+    /// Optional barrier
+    pub barrier: Option<PdeBarrier>,
+}
+
+impl PdeOption {
+    /// Creates a European call.
+    #[inline]
+    pub fn european_call(strike: f64, maturity: f64) -> Self {
+        Self::new(strike, maturity, OptionType::Call, Exercise::European)
+    }
+
+    /// Creates a European put.
+    #[inline]
+    pub fn european_put(strike: f64, maturity: f64) -> Self {
+        Self::new(strike, maturity, OptionType::Put, Exercise::European)
+    }
+
+    /// Creates an American call with the default PSOR method.
+    #[inline]
+    pub fn american_call(strike: f64, maturity: f64) -> Self {
+        Self::new(
+            strike,
+            maturity,
+            OptionType::Call,
+            Exercise::American(AmericanMethod::default()),
+        )
+    }
+
+    /// Creates an American put with the default PSOR method.
+    #[inline]
+    pub fn american_put(strike: f64, maturity: f64) -> Self {
+        Self::new(
+            strike,
+            maturity,
+            OptionType::Put,
+            Exercise::American(AmericanMethod::default()),
+        )
+    }
+
+    /// Creates an option with explicit type and exercise.
+    #[inline]
+    pub fn new(strike: f64, maturity: f64, option_type: OptionType, exercise: Exercise) -> Self {
+        Self {
+            strike,
+            maturity,
+            option_type,
+            exercise,
+            barrier: None,
+        }
+    }
+
+    /// Adds a barrier.
+    #[inline]
+    pub fn with_barrier(mut self, barrier: PdeBarrier) -> Self {
+        self.barrier = Some(barrier);
+        self
+    }
+
+    /// Sets the exercise style.
+    #[inline]
+    pub fn with_exercise(mut self, exercise: Exercise) -> Self {
+        self.exercise = exercise;
+        self
+    }
+
+    /// Returns the exercise value at `spot`.
+    #[inline]
+    pub fn intrinsic(&self, spot: f64) -> f64 {
+        match self.option_type {
+            OptionType::Call => (spot - self.strike).max(0.0),
+            OptionType::Put => (self.strike - spot).max(0.0),
+        }
+    }
+
+    /// Returns whether early exercise is allowed.
+    #[inline]
+    pub fn is_american(&self) -> bool {
+        matches!(self.exercise, Exercise::American(_))
+    }
+
+    /// Returns the same option with the barrier removed.
+    #[inline]
+    pub(crate) fn without_barrier(&self) -> Self {
+        Self {
+            barrier: None,
+            ..*self
+        }
+    }
+
+    /// Prices via a solver for vanilla and knock-out options, handling an
+    /// already breached barrier and European knock-ins by in-out parity.
+    pub(crate) fn price_with(
+        &self,
+        spot: f64,
+        mut solve: impl FnMut(&PdeOption) -> PdePricingResult,
+    ) -> PdePricingResult {
+        match self.barrier {
+            Some(barrier) if barrier.knock == KnockType::In => {
+                let vanilla = solve(&self.without_barrier());
+                if barrier.is_breached(spot) {
+                    return vanilla;
+                }
+                let knock_out = Self {
+                    barrier: Some(PdeBarrier {
+                        knock: KnockType::Out,
+                        rebate: 0.0,
+                        ..barrier
+                    }),
+                    ..*self
+                };
+                vanilla - solve(&knock_out)
+            }
+            Some(barrier) if barrier.is_breached(spot) => PdePricingResult {
+                price: barrier.rebate,
+                ..Default::default()
+            },
+            _ => solve(self),
+        }
+    }
+
+    /// Returns the Dirichlet values at the lower and upper spot boundaries
+    /// with `tau` years to maturity.
+    ///
+    /// Knock-out barriers pay the rebate; otherwise the discounted forward
+    /// intrinsic value is used (floored at the payoff for American options).
+    pub(crate) fn boundary_values(
+        &self,
+        s_lo: f64,
+        s_hi: f64,
+        rate: f64,
+        dividend: f64,
+        tau: f64,
+    ) -> (f64, f64) {
+        let df = (-rate * tau).exp();
+        let div_df = (-dividend * tau).exp();
+        let far = |s: f64| {
+            let european = match self.option_type {
+                OptionType::Call => (s * div_df - self.strike * df).max(0.0),
+                OptionType::Put => (self.strike * df - s * div_df).max(0.0),
+            };
+            if self.is_american() {
+                european.max(self.intrinsic(s))
+            } else {
+                european
+            }
+        };
+
+        let mut left = far(s_lo);
+        let mut right = far(s_hi);
+        if let Some(barrier) = self.barrier {
+            match barrier.direction {
+                BarrierDirection::Down => left = barrier.rebate,
+                BarrierDirection::Up => right = barrier.rebate,
+            }
+        }
+        (left, right)
+    }
+
+    /// Validates the instrument against a spot level.
+    pub(crate) fn validate(&self, spot: f64) -> PdeResult<()> {
+        positive("spot", spot)?;
+        positive("strike", self.strike)?;
+        positive("maturity", self.maturity)?;
+        if let Exercise::American(method) = self.exercise {
+            match method {
+                AmericanMethod::Psor {
+                    omega, tolerance, ..
+                } => {
+                    if !(omega > 0.0 && omega < 2.0) {
+                        return Err(PdeError::InvalidParameter {
+                            name: "omega",
+                            value: format!("{} must be in (0, 2)", omega),
+                        });
+                    }
+                    positive("tolerance", tolerance)?;
+                }
+                AmericanMethod::Penalty {
+                    penalty, tolerance, ..
+                } => {
+                    positive("penalty", penalty)?;
+                    positive("tolerance", tolerance)?;
+                }
+            }
+        }
+        if let Some(barrier) = self.barrier {
+            positive("barrier", barrier.level)?;
+            if barrier.knock == KnockType::In && self.is_american() {
+                return Err(PdeError::Unsupported {
+                    message: "American knock-in options have no in-out parity".to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Price and spot Greeks from a PDE solve.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PdePricingResult {
+    /// Present value
+    pub price: f64,
+    /// ∂V/∂S
+    pub delta: f64,
+    /// ∂²V/∂S²
+    pub gamma: f64,
+}
+
+impl std::ops::Sub for PdePricingResult {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            price: self.price - rhs.price,
+            delta: self.delta - rhs.delta,
+            gamma: self.gamma - rhs.gamma,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_option_constructors() {
+        let call = PdeOption::european_call(100.0, 1.0);
+        assert_eq!(call.option_type, OptionType::Call);
+        assert!(!call.is_american());
+        assert_eq!(call.intrinsic(120.0), 20.0);
+
+        let put = PdeOption::american_put(100.0, 1.0);
+        assert!(put.is_american());
+        assert_eq!(put.intrinsic(80.0), 20.0);
+        assert_eq!(put.intrinsic(120.0), 0.0);
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(PdeOption::european_call(100.0, 1.0).validate(100.0).is_ok());
+        assert!(matches!(
+            PdeOption::european_call(100.0, 0.0).validate(100.0),
+            Err(PdeError::InvalidParameter {
+                name: "maturity",
+                ..
+            })
+        ));
+        let knock_in = PdeOption::american_put(100.0, 1.0).with_barrier(PdeBarrier::new(
+            80.0,
+            BarrierDirection::Down,
+            KnockType::In,
+        ));
+        assert!(matches!(
+            knock_in.validate(100.0),
+            Err(PdeError::Unsupported { .. })
+        ));
+        let bad_omega = PdeOption::european_put(100.0, 1.0).with_exercise(Exercise::American(
+            AmericanMethod::Psor {
+                omega: 2.5,
+                tolerance: 1e-8,
+                max_iterations: 10,
+            },
+        ));
+        assert!(bad_omega.validate(100.0).is_err());
+    }
+}
@@ -0,0 +1,56 @@
+//! Finite-difference PDE pricing engines.
+//!
+//! This module provides deterministic grid-based pricers as an alternative
+//! to Monte Carlo for vanilla and barrier instruments, where simulation
+//! noise in prices and Greeks is undesirable.
+//!
+//! # Solvers
+//!
+//! - [`BlackScholesPde`]: 1-D Crank-Nicolson in log-spot with Rannacher
+//!   start-up steps to damp the payoff kink
+//! - [`HestonPde`]: 2-D Douglas ADI on a non-uniform (spot, variance) grid
+//!
+//! # Instruments
+//!
+//! [`PdeOption`] describes a call or put with European or American
+//! exercise and an optional continuously monitored barrier. Knock-out
+//! barriers are imposed as Dirichlet boundaries at the barrier level;
+//! European knock-ins are priced by in-out parity.
+//!
+//! # American Exercise
+//!
+//! The 1-D solver enforces `V ≥ payoff` at every step with either
+//! projected SOR or the Forsyth-Vetzal penalty iteration (see
+//! [`AmericanMethod`]). The ADI solver applies the early-exercise
+//! constraint by projection after each step.
+//!
+//! [`BlackScholesPde::exercise_boundary`] returns the critical spot after
+//! each step as an [`ExerciseBoundary`]; [`expiry_level`] and
+//! [`perpetual_level`] give its known limits for validation.
+//!
+//! # Example
+//!
+//! ```rust
+//! use pricer_pricing::pde::{BlackScholesPde, PdeConfig, PdeOption};
+//!
+//! let model = BlackScholesPde::new(0.05, 0.0, 0.2);
+//! let put = PdeOption::american_put(100.0, 1.0);
+//!
+//! let result = model.price(&put, 100.0, &PdeConfig::default()).unwrap();
+//! assert!((result.price - 6.0904).abs() < 5e-3);
+//! ```
+
+mod adi;
+mod boundary;
+mod crank_nicolson;
+mod error;
+mod grid;
+mod instrument;
+mod tridiagonal;
+
+pub use adi::{AdiConfig, HestonPde};
+pub use boundary::{expiry_level, perpetual_level, ExerciseBoundary};
+pub use crank_nicolson::{BlackScholesPde, PdeConfig};
+pub use error::{PdeError, PdeResult};
+pub use instrument::{AmericanMethod, Exercise, PdeBarrier, PdeOption, PdePricingResult};
+pub use tridiagonal::solve_tridiagonal;
@@ -0,0 +1,214 @@
+//! Tridiagonal linear solvers.
+
+/// Solves a tridiagonal system with the Thomas algorithm.
+///
+/// Row `i` reads `lower[i]·x[i-1] + diag[i]·x[i] + upper[i]·x[i+1] = rhs[i]`;
+/// `lower[0]` and `upper[n-1]` are ignored. The system must be diagonally
+/// dominant (as all implicit finite-difference operators here are).
+///
+/// # Panics
+///
+/// Panics if the slices have different lengths.
+///
+/// # Examples
+///
+/// ```rust
+/// use pricer_pricing::pde::solve_tridiagonal;
+///
+/// // [2 1 0; 1 2 1; 0 1 2] x = [3, 4, 3]  =>  x = [1, 1, 1]
+/// let mut x = [0.0; 3];
+/// solve_tridiagonal(&[0.0, 1.0, 1.0], &[2.0; 3], &[1.0, 1.0, 0.0], &[3.0, 4.0, 3.0], &mut x);
+/// assert!(x.iter().all(|&v| (v - 1.0).abs() < 1e-12));
+/// ```
+pub fn solve_tridiagonal(lower: &[f64], diag: &[f64], upper: &[f64], rhs: &[f64], x: &mut [f64]) {
+    let n = diag.len();
+    assert!(lower.len() == n && upper.len() == n && rhs.len() == n && x.len() == n);
+    if n == 0 {
+        return;
+    }
+
+    // Forward sweep stores the modified upper diagonal in `c` and the
+    // modified right-hand side in `x`
+    let mut c = vec![0.0; n];
+    c[0] = upper[0] / diag[0];
+    x[0] = rhs[0] / diag[0];
+    for i in 1..n {
+        let m = diag[i] - lower[i] * c[i - 1];
+        c[i] = upper[i] / m;
+        x[i] = (rhs[i] - lower[i] * x[i - 1]) / m;
+    }
+    for i in (0..n - 1).rev() {
+        x[i] -= c[i] * x[i + 1];
+    }
+}
+
+/// Projected SOR for `A x = rhs` subject to `x ≥ floor`, with `A`
+/// tridiagonal. `x` holds the initial guess. Returns the number of sweeps.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn solve_psor(
+    lower: &[f64],
+    diag: &[f64],
+    upper: &[f64],
+    rhs: &[f64],
+    floor: &[f64],
+    x: &mut [f64],
+    omega: f64,
+    tolerance: f64,
+    max_iterations: usize,
+) -> usize {
+    let n = diag.len();
+    for sweep in 1..=max_iterations {
+        let mut max_change: f64 = 0.0;
+        for i in 0..n {
+            let mut residual = rhs[i] - diag[i] * x[i];
+            if i > 0 {
+                residual -= lower[i] * x[i - 1];
+            }
+            if i + 1 < n {
+                residual -= upper[i] * x[i + 1];
+            }
+            let updated = (x[i] + omega * residual / diag[i]).max(floor[i]);
+            max_change = max_change.max((updated - x[i]).abs());
+            x[i] = updated;
+        }
+        if max_change <= tolerance {
+            return sweep;
+        }
+    }
+    max_iterations
+}
+
+/// Penalty iteration for `A x = rhs` subject to `x ≥ floor`, with `A`
+/// tridiagonal. `x` holds the initial guess. Returns the number of
+/// iterations.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn solve_penalty(
+    lower: &[f64],
+    diag: &[f64],
+    upper: &[f64],
+    rhs: &[f64],
+    floor: &[f64],
+    x: &mut [f64],
+    penalty: f64,
+    tolerance: f64,
+    max_iterations: usize,
+) -> usize {
+    let n = diag.len();
+    let mut penalised_diag = vec![0.0; n];
+    let mut penalised_rhs = vec![0.0; n];
+    let mut next = vec![0.0; n];
+    for iteration in 1..=max_iterations {
+        for i in 0..n {
+            // Unconstrained nodes may carry an infinite floor
+            if x[i] < floor[i] {
+                penalised_diag[i] = diag[i] + penalty;
+                penalised_rhs[i] = rhs[i] + penalty * floor[i];
+            } else {
+                penalised_diag[i] = diag[i];
+                penalised_rhs[i] = rhs[i];
+            }
+        }
+        solve_tridiagonal(lower, &penalised_diag, upper, &penalised_rhs, &mut next);
+
+        let converged = next
+            .iter()
+            .zip(x.iter())
+            .all(|(&new, &old)| (new - old).abs() <= tolerance * new.abs().max(1.0));
+        x.copy_from_slice(&next);
+        if converged {
+            return iteration;
+        }
+    }
+    max_iterations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn system() -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+        let n = 6;
+        (vec![-1.0; n], vec![2.5; n], vec![-1.0; n])
+    }
+
+    #[test]
+    fn test_thomas_matches_product() {
+        let (lower, diag, upper) = system();
+        let expected = [1.0, -2.0, 3.0, 0.5, 4.0, -1.0];
+        let n = expected.len();
+        let rhs: Vec<f64> = (0..n)
+            .map(|i| {
+                let mut r = diag[i] * expected[i];
+                if i > 0 {
+                    r += lower[i] * expected[i - 1];
+                }
+                if i + 1 < n {
+                    r += upper[i] * expected[i + 1];
+                }
+                r
+            })
+            .collect();
+
+        let mut x = vec![0.0; n];
+        solve_tridiagonal(&lower, &diag, &upper, &rhs, &mut x);
+        for (got, want) in x.iter().zip(expected.iter()) {
+            assert_relative_eq!(got, want, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_psor_and_penalty_agree() {
+        let (lower, diag, upper) = system();
+        let rhs = [1.0, -3.0, 0.5, -2.0, 1.0, 0.0];
+        let floor = [0.0, 0.2, 0.0, 0.1, 0.0, 0.3];
+
+        let mut psor = floor.to_vec();
+        let sweeps = solve_psor(
+            &lower, &diag, &upper, &rhs, &floor, &mut psor, 1.2, 1e-13, 1000,
+        );
+        assert!(sweeps < 1000);
+
+        let mut penalty = floor.to_vec();
+        solve_penalty(
+            &lower,
+            &diag,
+            &upper,
+            &rhs,
+            &floor,
+            &mut penalty,
+            1e10,
+            1e-12,
+            100,
+        );
+
+        for i in 0..6 {
+            assert!(psor[i] >= floor[i] - 1e-15);
+            assert_relative_eq!(psor[i], penalty[i], epsilon = 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_psor_inactive_constraint_is_linear_solve() {
+        let (lower, diag, upper) = system();
+        let rhs = [1.0; 6];
+        let mut exact = vec![0.0; 6];
+        solve_tridiagonal(&lower, &diag, &upper, &rhs, &mut exact);
+
+        let mut x = vec![0.0; 6];
+        solve_psor(
+            &lower,
+            &diag,
+            &upper,
+            &rhs,
+            &[-10.0; 6],
+            &mut x,
+            1.0,
+            1e-14,
+            10_000,
+        );
+        for (got, want) in x.iter().zip(exact.iter()) {
+            assert_relative_eq!(got, want, epsilon = 1e-12);
+        }
+    }
+}
@@ -0,0 +1,359 @@
+//! Bump arena for transient per-run allocations.
+//!
+//! [`RunArena`] hands out zeroed `f64` slices by bumping an offset through
+//! large chunks. Individual slices are never freed; instead the whole arena
+//! is [`reset`](RunArena::reset) between pricing runs, keeping its chunks so
+//! that steady-state runs make no system allocations at all.
+//!
+//! [`with_run_arena`] provides a thread-local arena that is reset on entry,
+//! which is the intended pattern for per-call scratch (schedules, cashflow
+//! vectors, regression matrices) in batch workloads. On exit it is shrunk
+//! back to [`RETAINED_CAPACITY`], so one unusually large run does not pin
+//! its memory in every worker thread.
+
+use std::cell::{Cell, RefCell};
+use std::ptr::NonNull;
+
+/// Default chunk size in elements (512 KiB of `f64`).
+const DEFAULT_CHUNK_LEN: usize = 64 * 1024;
+
+/// Elements the thread-local arena keeps between runs (4 MiB of `f64`).
+pub const RETAINED_CAPACITY: usize = 8 * DEFAULT_CHUNK_LEN;
+
+/// Statistics about arena usage.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ArenaStats {
+    /// Slices handed out since the last reset.
+    pub allocations: usize,
+    /// Elements handed out since the last reset.
+    pub elements_used: usize,
+    /// Total elements held across all chunks.
+    pub capacity: usize,
+    /// Number of chunks held.
+    pub chunks: usize,
+    /// System allocations made over the arena's lifetime.
+    pub chunk_allocations: usize,
+    /// Number of resets.
+    pub resets: usize,
+}
+
+/// Bump allocator for `f64` scratch buffers.
+///
+/// Allocation takes `&self`, so many slices can be live at once; resetting
+/// takes `&mut self`, so the borrow checker guarantees none outlive the run.
+///
+/// # Examples
+///
+/// ```rust
+/// use pricer_pricing::pool::RunArena;
+///
+/// let mut arena = RunArena::new();
+///
+/// for _run in 0..3 {
+///     let times = arena.alloc_copy(&[0.5, 1.0, 1.5, 2.0]);
+///     let cashflows = arena.alloc_slice(times.len());
+///     for (cf, &t) in cashflows.iter_mut().zip(times.iter()) {
+///         *cf = 100.0 * (-0.03 * t).exp();
+///     }
+///     assert_eq!(cashflows.len(), 4);
+///
+///     arena.reset();
+/// }
+///
+/// // Only the first run touched the system allocator
+/// assert_eq!(arena.stats().chunk_allocations, 1);
+/// ```
+pub struct RunArena {
+    /// Owned chunks (leaked boxes, freed on drop). Stored as raw pointers so
+    /// that pushing a chunk never re-borrows memory already handed out.
+    chunks: RefCell<Vec<NonNull<[f64]>>>,
+    /// Index of the chunk being bumped.
+    current: Cell<usize>,
+    /// Next free element in the current chunk.
+    offset: Cell<usize>,
+    chunk_len: usize,
+    stats: Cell<ArenaStats>,
+}
+
+// SAFETY: the arena exclusively owns its chunks; moving it to another thread
+// moves that ownership. It is not `Sync` (interior mutability via `Cell`).
+unsafe impl Send for RunArena {}
+
+impl RunArena {
+    /// Creates an empty arena with the default chunk size.
+    pub fn new() -> Self {
+        Self::with_chunk_len(DEFAULT_CHUNK_LEN)
+    }
+
+    /// Creates an empty arena whose chunks hold at least `chunk_len` elements.
+    pub fn with_chunk_len(chunk_len: usize) -> Self {
+        Self {
+            chunks: RefCell::new(Vec::new()),
+            current: Cell::new(0),
+            offset: Cell::new(0),
+            chunk_len: chunk_len.max(1),
+            stats: Cell::new(ArenaStats::default()),
+        }
+    }
+
+    /// Allocates a zeroed slice of `len` elements.
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc_slice(&self, len: usize) -> &mut [f64] {
+        let ptr = self.bump(len);
+        // SAFETY: `bump` returns a pointer to `len` initialised elements in a
+        // live chunk, disjoint from every other slice handed out since the
+        // last reset; chunks are only freed or reused through `&mut self`.
+        let slice = unsafe { std::slice::from_raw_parts_mut(ptr.as_ptr(), len) };
+        slice.fill(0.0);
+        slice
+    }
+
+    /// Allocates a slice initialised with a copy of `values`.
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc_copy(&self, values: &[f64]) -> &mut [f64] {
+        let slice = self.alloc_slice(values.len());
+        slice.copy_from_slice(values);
+        slice
+    }
+
+    /// Releases all slices, keeping the chunks for the next run.
+    pub fn reset(&mut self) {
+        self.current.set(0);
+        self.offset.set(0);
+        let mut stats = self.stats.get();
+        stats.allocations = 0;
+        stats.elements_used = 0;
+        stats.resets += 1;
+        self.stats.set(stats);
+    }
+
+    /// Resets the arena and frees chunks until at most `max_capacity`
+    /// elements are held.
+    ///
+    /// Chunks are freed from the most recently allocated, which are the
+    /// ones only large runs reach.
+    pub fn shrink_to(&mut self, max_capacity: usize) {
+        self.reset();
+        let chunks = self.chunks.get_mut();
+        let mut stats = self.stats.get();
+        while stats.capacity > max_capacity {
+            let Some(chunk) = chunks.pop() else {
+                break;
+            };
+            stats.capacity -= chunk.len();
+            stats.chunks -= 1;
+            // SAFETY: the chunk came from `Box::into_raw`, is freed once, and
+            // no slices are live through `&mut self`.
+            drop(unsafe { Box::from_raw(chunk.as_ptr()) });
+        }
+        self.stats.set(stats);
+    }
+
+    /// Returns usage statistics.
+    pub fn stats(&self) -> ArenaStats {
+        self.stats.get()
+    }
+
+    /// Reserves `len` elements and returns a pointer to the first.
+    fn bump(&self, len: usize) -> NonNull<f64> {
+        let mut chunks = self.chunks.borrow_mut();
+        let mut current = self.current.get();
+        let mut offset = self.offset.get();
+        let mut stats = self.stats.get();
+
+        // Advance to the first chunk (from the current one) with room
+        while current < chunks.len() && offset + len > chunks[current].len() {
+            current += 1;
+            offset = 0;
+        }
+        if current == chunks.len() {
+            let chunk = vec![0.0; len.max(self.chunk_len)].into_boxed_slice();
+            stats.capacity += chunk.len();
+            stats.chunks += 1;
+            stats.chunk_allocations += 1;
+            // SAFETY: `Box::into_raw` never returns null.
+            chunks.push(unsafe { NonNull::new_unchecked(Box::into_raw(chunk)) });
+        }
+
+        // SAFETY: `offset + len <= chunks[current].len()`, so the offset stays
+        // within (or one past the end of) the chunk allocation.
+        let ptr =
+            unsafe { NonNull::new_unchecked(chunks[current].as_ptr().cast::<f64>().add(offset)) };
+
+        self.current.set(current);
+        self.offset.set(offset + len);
+        stats.allocations += 1;
+        stats.elements_used += len;
+        self.stats.set(stats);
+        ptr
+    }
+}
+
+impl Default for RunArena {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for RunArena {
+    fn drop(&mut self) {
+        for chunk in self.chunks.get_mut().drain(..) {
+            // SAFETY: each chunk came from `Box::into_raw` and is freed once.
+            drop(unsafe { Box::from_raw(chunk.as_ptr()) });
+        }
+    }
+}
+
+impl std::fmt::Debug for RunArena {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RunArena")
+            .field("chunk_len", &self.chunk_len)
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+thread_local! {
+    static RUN_ARENA: RefCell<RunArena> = RefCell::new(RunArena::new());
+}
+
+/// Runs `f` with this thread's arena, reset on entry and shrunk to
+/// [`RETAINED_CAPACITY`] on exit.
+///
+/// Nested calls on the same thread (the arena is already in use) get a
+/// temporary arena instead, so they stay correct but do not benefit from
+/// chunk reuse.
+///
+/// # Examples
+///
+/// ```rust
+/// use pricer_pricing::pool::with_run_arena;
+///
+/// let total = with_run_arena(|arena| {
+///     let buffer = arena.alloc_slice(1_000);
+///     buffer.iter_mut().enumerate().for_each(|(i, x)| *x = i as f64);
+///     buffer.iter().sum::<f64>()
+/// });
+/// assert_eq!(total, 499_500.0);
+/// ```
+pub fn with_run_arena<F, R>(f: F) -> R
+where
+    F: FnOnce(&RunArena) -> R,
+{
+    RUN_ARENA.with(|cell| match cell.try_borrow_mut() {
+        Ok(mut arena) => {
+            arena.reset();
+            let result = f(&arena);
+            arena.shrink_to(RETAINED_CAPACITY);
+            result
+        }
+        Err(_) => f(&RunArena::new()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arena_slices_are_disjoint_and_zeroed() {
+        let arena = RunArena::with_chunk_len(16);
+        let a = arena.alloc_slice(10);
+        let b = arena.alloc_slice(10);
+        let c = arena.alloc_copy(&[1.0, 2.0, 3.0]);
+
+        a.fill(1.0);
+        b.fill(2.0);
+        assert!(a.iter().all(|&x| x == 1.0));
+        assert!(b.iter().all(|&x| x == 2.0));
+        assert_eq!(c, &[1.0, 2.0, 3.0]);
+
+        let stats = arena.stats();
+        assert_eq!(stats.allocations, 3);
+        assert_eq!(stats.elements_used, 23);
+        // 10 fits the first chunk, 10 + 3 spill into a second
+        assert_eq!(stats.chunks, 2);
+    }
+
+    #[test]
+    fn test_arena_reset_reuses_chunks() {
+        let mut arena = RunArena::with_chunk_len(100);
+        for _ in 0..10 {
+            let x = arena.alloc_slice(60);
+            let y = arena.alloc_slice(60);
+            x[0] = 1.0;
+            y[59] = 1.0;
+            arena.reset();
+        }
+
+        let stats = arena.stats();
+        assert_eq!(stats.chunk_allocations, 2);
+        assert_eq!(stats.resets, 10);
+        assert_eq!(stats.allocations, 0);
+    }
+
+    #[test]
+    fn test_arena_reset_zeroes_reused_memory() {
+        let mut arena = RunArena::with_chunk_len(8);
+        arena.alloc_slice(8).fill(7.0);
+        arena.reset();
+        assert!(arena.alloc_slice(8).iter().all(|&x| x == 0.0));
+    }
+
+    #[test]
+    fn test_arena_oversized_request() {
+        let arena = RunArena::with_chunk_len(4);
+        let big = arena.alloc_slice(100);
+        assert_eq!(big.len(), 100);
+        assert_eq!(arena.stats().capacity, 100);
+
+        let empty = arena.alloc_slice(0);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_arena_shrink_frees_latest_chunks() {
+        let mut arena = RunArena::with_chunk_len(10);
+        arena.alloc_slice(10);
+        arena.alloc_slice(10);
+        arena.alloc_slice(50);
+        arena.shrink_to(25);
+
+        let stats = arena.stats();
+        assert_eq!(stats.chunks, 2);
+        assert_eq!(stats.capacity, 20);
+        assert_eq!(stats.elements_used, 0);
+
+        // The kept chunks are reused without touching the allocator
+        arena.alloc_slice(10);
+        arena.alloc_slice(10);
+        assert_eq!(arena.stats().chunk_allocations, 3);
+    }
+
+    #[test]
+    fn test_with_run_arena_releases_large_runs() {
+        with_run_arena(|arena| {
+            arena.alloc_slice(2 * RETAINED_CAPACITY);
+        });
+        let capacity = RUN_ARENA.with(|cell| cell.borrow().stats().capacity);
+        assert!(capacity <= RETAINED_CAPACITY);
+    }
+
+    #[test]
+    fn test_with_run_arena_resets_and_nests() {
+        with_run_arena(|arena| {
+            arena.alloc_slice(10);
+        });
+        let (outer, inner) = with_run_arena(|arena| {
+            arena.alloc_slice(5);
+            let inner = with_run_arena(|nested| {
+                nested.alloc_slice(3);
+                nested.stats().allocations
+            });
+            (arena.stats().allocations, inner)
+        });
+
+        assert_eq!(outer, 1);
+        assert_eq!(inner, 1);
+    }
+}
@@ -11,6 +11,9 @@
 //! - **RAII semantics**: Buffers auto-return to pool when dropped
 //! - **Statistics tracking**: Monitor pool efficiency
 //!
+//! For many short-lived scratch slices per pricing call, [`RunArena`] offers
+//! bump allocation with a single reset per run instead of per-buffer returns.
+//!
 //! # Example
 //!
 //! ```rust
@@ -34,6 +37,10 @@
 //! assert!(pool.stats().allocations_avoided >= 1);
 //! ```
 
+mod arena;
+
+pub use arena::{with_run_arena, ArenaStats, RunArena, RETAINED_CAPACITY};
+
 use std::cell::RefCell;
 use std::ops::{Deref, DerefMut};
 
@@ -0,0 +1,203 @@
+//! Inverse cumulative normal transforms for quasi-Monte Carlo sampling.
+//!
+//! Low-discrepancy points are mapped to normals one coordinate at a time
+//! through the inverse normal CDF, which, unlike the Ziggurat or Box-Muller
+//! methods, preserves the stratification of the sequence. Two standard
+//! rational approximations are provided:
+//!
+//! - [`acklam_inverse_normal`]: Acklam (2003), relative error below
+//!   1.15e-9 over the whole unit interval
+//! - [`moro_inverse_normal`]: Beasley-Springer (1977) in the centre with
+//!   Moro's (1995) Chebyshev tail, absolute error below 3e-9 for
+//!   `|z| < 7`, the usual choice in QMC engines
+//!
+//! [`inverse_normal_cdf`] is the default, currently Acklam's.
+
+/// Acklam central region numerator coefficients.
+#[allow(clippy::excessive_precision)]
+const ACKLAM_A: [f64; 6] = [
+    -3.969683028665376e+01,
+    2.209460984245205e+02,
+    -2.759285104469687e+02,
+    1.383577518672690e+02,
+    -3.066479806614716e+01,
+    2.506628277459239e+00,
+];
+
+/// Acklam central region denominator coefficients.
+#[allow(clippy::excessive_precision)]
+const ACKLAM_B: [f64; 5] = [
+    -5.447609879822406e+01,
+    1.615858368580409e+02,
+    -1.556989798598866e+02,
+    6.680131188771972e+01,
+    -1.328068155288572e+01,
+];
+
+/// Acklam tail numerator coefficients.
+#[allow(clippy::excessive_precision)]
+const ACKLAM_C: [f64; 6] = [
+    -7.784894002430293e-03,
+    -3.223964580411365e-01,
+    -2.400758277161838e+00,
+    -2.549732539343734e+00,
+    4.374664141464968e+00,
+    2.938163982698783e+00,
+];
+
+/// Acklam tail denominator coefficients.
+#[allow(clippy::excessive_precision)]
+const ACKLAM_D: [f64; 4] = [
+    7.784695709041462e-03,
+    3.224671290700398e-01,
+    2.445134137142996e+00,
+    3.754408661907416e+00,
+];
+
+/// Probability below which Acklam's tail approximation applies.
+const ACKLAM_LOW: f64 = 0.02425;
+
+/// Beasley-Springer numerator coefficients.
+const MORO_A: [f64; 4] = [
+    2.50662823884,
+    -18.61500062529,
+    41.39119773534,
+    -25.44106049637,
+];
+
+/// Beasley-Springer denominator coefficients.
+const MORO_B: [f64; 4] = [
+    -8.47351093090,
+    23.08336743743,
+    -21.06224101826,
+    3.13082909833,
+];
+
+/// Moro tail Chebyshev coefficients.
+const MORO_C: [f64; 9] = [
+    0.3374754822726147,
+    0.9761690190917186,
+    0.1607979714918209,
+    0.0276438810333863,
+    0.0038405729373609,
+    0.0003951896511919,
+    0.0000321767881768,
+    0.0000002888167364,
+    0.0000003960315187,
+];
+
+/// Inverse standard normal CDF, `Φ⁻¹(p)`.
+///
+/// Returns `-∞` for `p ≤ 0`, `+∞` for `p ≥ 1` and NaN for NaN.
+///
+/// # Examples
+///
+/// ```rust
+/// use pricer_pricing::rng::inverse_normal_cdf;
+///
+/// assert_eq!(inverse_normal_cdf(0.5), 0.0);
+/// assert!((inverse_normal_cdf(0.975) - 1.959964).abs() < 1e-6);
+/// ```
+#[inline]
+pub fn inverse_normal_cdf(p: f64) -> f64 {
+    acklam_inverse_normal(p)
+}
+
+/// Acklam's rational approximation to the inverse normal CDF.
+///
+/// # Algorithm Reference
+///
+/// - Acklam, P. J. (2003). "An algorithm for computing the inverse normal
+///   cumulative distribution function".
+#[inline]
+pub fn acklam_inverse_normal(p: f64) -> f64 {
+    if p.is_nan() {
+        return f64::NAN;
+    }
+    if p <= 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if p >= 1.0 {
+        return f64::INFINITY;
+    }
+
+    let [a0, a1, a2, a3, a4, a5] = ACKLAM_A;
+    let [b0, b1, b2, b3, b4] = ACKLAM_B;
+    let [c0, c1, c2, c3, c4, c5] = ACKLAM_C;
+    let [d0, d1, d2, d3] = ACKLAM_D;
+    let tail = |q: f64| {
+        (((((c0 * q + c1) * q + c2) * q + c3) * q + c4) * q + c5)
+            / ((((d0 * q + d1) * q + d2) * q + d3) * q + 1.0)
+    };
+
+    if p < ACKLAM_LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p <= 1.0 - ACKLAM_LOW {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((a0 * r + a1) * r + a2) * r + a3) * r + a4) * r + a5) * q
+            / (((((b0 * r + b1) * r + b2) * r + b3) * r + b4) * r + 1.0)
+    } else {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    }
+}
+
+/// Beasley-Springer-Moro approximation to the inverse normal CDF.
+///
+/// # Algorithm Reference
+///
+/// - Beasley, J. D. & Springer, S. G. (1977). "The Percentage Points of
+///   the Normal Distribution". Applied Statistics.
+/// - Moro, B. (1995). "The Full Monte". Risk.
+#[inline]
+pub fn moro_inverse_normal(p: f64) -> f64 {
+    if p.is_nan() {
+        return f64::NAN;
+    }
+    if p <= 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if p >= 1.0 {
+        return f64::INFINITY;
+    }
+
+    let y = p - 0.5;
+    if y.abs() < 0.42 {
+        let r = y * y;
+        let [a0, a1, a2, a3] = MORO_A;
+        let [b0, b1, b2, b3] = MORO_B;
+        y * (((a3 * r + a2) * r + a1) * r + a0) / ((((b3 * r + b2) * r + b1) * r + b0) * r + 1.0)
+    } else {
+        let tail = if y < 0.0 { p } else { 1.0 - p };
+        let r = (-tail.ln()).ln();
+        let x = MORO_C.iter().rev().fold(0.0, |sum, &c| sum * r + c);
+        if y < 0.0 {
+            -x
+        } else {
+            x
+        }
+    }
+}
+
+/// Maps uniforms in `buffer` to standard normals in place by
+/// [`inverse_normal_cdf`].
+///
+/// Intended for quasi-Monte Carlo points, whose coordinates lie strictly
+/// inside the unit interval.
+///
+/// # Examples
+///
+/// ```rust
+/// use pricer_pricing::rng::uniforms_to_normals;
+///
+/// let mut buffer = [0.25, 0.5, 0.75];
+/// uniforms_to_normals(&mut buffer);
+/// assert_eq!(buffer[1], 0.0);
+/// assert!((buffer[0] + buffer[2]).abs() < 1e-12);
+/// ```
+#[inline]
+pub fn uniforms_to_normals(buffer: &mut [f64]) {
+    for value in buffer.iter_mut() {
+        *value = inverse_normal_cdf(*value);
+    }
+}
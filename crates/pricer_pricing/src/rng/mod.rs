@@ -24,6 +24,7 @@
 //! ## Module Structure
 //!
 //! - [`prng`]: Pseudo-random number generator wrapper with seed management
+//! - [`distributions`]: Inverse normal CDF transforms for QMC points
 //! - [`qmc`]: Quasi-Monte Carlo sequence traits and placeholders
 //!
 //! ## Usage Example
@@ -57,13 +58,20 @@
 //! - PRNG wrapper around `rand::StdRng`
 //! - Normal distribution via Ziggurat algorithm (`rand_distr::StandardNormal`)
 //! - QMC trait definitions (placeholders only)
+//! - Inverse-CDF normals (Acklam, Beasley-Springer-Moro) for QMC points
+//! - Poisson, gamma and non-central chi-square sampling for jump-diffusion
+//!   and square-root variance processes
 //!
 //! Sobol sequence implementation is deferred to a future phase.
 
+mod distributions;
 mod prng;
 mod qmc;
 
 // Public re-exports
+pub use distributions::{
+    acklam_inverse_normal, inverse_normal_cdf, moro_inverse_normal, uniforms_to_normals,
+};
 pub use prng::PricerRng;
 pub use qmc::{LowDiscrepancySequence, SobolPlaceholder};
 
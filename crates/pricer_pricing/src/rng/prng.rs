@@ -5,12 +5,14 @@
 
 use rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
-use rand_distr::{Distribution, StandardNormal};
+use rand_distr::{Distribution, Gamma, Poisson, StandardNormal};
 
 /// Monte Carlo simulation random number generator.
 ///
 /// Provides seeded, reproducible random number generation with efficient
-/// batch operations for uniform and normal distributions.
+/// batch operations for uniform and normal distributions, and single draws
+/// from the Poisson, gamma and non-central chi-square distributions used by
+/// jump-diffusion and square-root (CIR, Heston) variance schemes.
 ///
 /// # Enzyme Compatibility
 ///
@@ -212,4 +214,118 @@ impl PricerRng {
             *value = StandardNormal.sample(&mut self.inner);
         }
     }
+
+    /// Generates a Poisson variate with mean `lambda`.
+    ///
+    /// Draws the number of jumps of a compound Poisson process over a time
+    /// step, with `lambda` the intensity times the step length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lambda` is negative or not finite.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pricer_pricing::rng::PricerRng;
+    ///
+    /// let mut rng = PricerRng::from_seed(42);
+    /// let jumps = rng.gen_poisson(0.5);
+    /// assert_eq!(rng.gen_poisson(0.0), 0);
+    /// # let _ = jumps;
+    /// ```
+    #[inline]
+    pub fn gen_poisson(&mut self, lambda: f64) -> u64 {
+        assert!(
+            lambda >= 0.0 && lambda.is_finite(),
+            "Poisson mean {} must be non-negative and finite",
+            lambda
+        );
+        if lambda == 0.0 {
+            return 0;
+        }
+        match Poisson::new(lambda) {
+            Ok(poisson) => {
+                let draw: f64 = poisson.sample(&mut self.inner);
+                draw as u64
+            }
+            Err(_) => 0,
+        }
+    }
+
+    /// Generates a gamma variate with the given `shape` and `scale`.
+    ///
+    /// Uses the Marsaglia-Tsang method via `rand_distr::Gamma`, with the
+    /// usual boost for shapes below one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `shape` or `scale` is not positive and finite.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pricer_pricing::rng::PricerRng;
+    ///
+    /// let mut rng = PricerRng::from_seed(42);
+    /// assert!(rng.gen_gamma(0.5, 2.0) >= 0.0);
+    /// ```
+    #[inline]
+    pub fn gen_gamma(&mut self, shape: f64, scale: f64) -> f64 {
+        assert!(
+            shape > 0.0 && shape.is_finite() && scale > 0.0 && scale.is_finite(),
+            "Gamma shape {} and scale {} must be positive and finite",
+            shape,
+            scale
+        );
+        match Gamma::new(shape, scale) {
+            Ok(gamma) => gamma.sample(&mut self.inner),
+            Err(_) => f64::NAN,
+        }
+    }
+
+    /// Generates a non-central chi-square variate with `dof` degrees of
+    /// freedom and non-centrality `non_centrality`.
+    ///
+    /// This is the exact transition law of the square-root (CIR) process:
+    /// `r(t + Δ) = c · χ'²(d, λ)` with `c = σ²(1 - e^{-κΔ}) / 4κ`,
+    /// `d = 4κθ / σ²` and `λ = r(t) e^{-κΔ} / c`.
+    ///
+    /// For `dof > 1` the variate is `(Z + √λ)² + χ²(dof - 1)`; otherwise it
+    /// is drawn as a Poisson mixture of central chi-squares,
+    /// `χ²(dof + 2N)` with `N ~ Poisson(λ / 2)`, which also covers the
+    /// `dof < 1` regime where the Feller condition fails.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `dof` is not positive and finite or `non_centrality` is
+    /// negative or not finite.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pricer_pricing::rng::PricerRng;
+    ///
+    /// let mut rng = PricerRng::from_seed(42);
+    /// assert!(rng.gen_noncentral_chi_squared(0.8, 3.0) >= 0.0);
+    /// ```
+    pub fn gen_noncentral_chi_squared(&mut self, dof: f64, non_centrality: f64) -> f64 {
+        assert!(
+            dof > 0.0 && dof.is_finite(),
+            "Chi-square degrees of freedom {} must be positive and finite",
+            dof
+        );
+        assert!(
+            non_centrality >= 0.0 && non_centrality.is_finite(),
+            "Non-centrality {} must be non-negative and finite",
+            non_centrality
+        );
+        if dof > 1.0 {
+            let shifted = self.gen_normal() + non_centrality.sqrt();
+            shifted * shifted + self.gen_gamma(0.5 * (dof - 1.0), 2.0)
+        } else {
+            let mixing = self.gen_poisson(0.5 * non_centrality);
+            self.gen_gamma(0.5 * dof + mixing as f64, 2.0)
+        }
+    }
 }
@@ -295,6 +295,160 @@ proptest! {
     }
 }
 
+// ============================================================================
+// Distribution Extensions: Inverse CDF, Poisson, Gamma, Non-Central Chi-Square
+// ============================================================================
+
+/// Sample mean and variance.
+fn moments(samples: &[f64]) -> (f64, f64) {
+    let n = samples.len() as f64;
+    let mean = samples.iter().sum::<f64>() / n;
+    let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+    (mean, variance)
+}
+
+/// Verifies both inverse normal approximations against reference quantiles.
+#[test]
+fn test_inverse_normal_quantiles() {
+    let quantiles = [
+        (1e-10, -6.361340902404056),
+        (0.001, -3.090232306167813),
+        (0.02, -2.053748910631823),
+        (0.3, -0.524400512708041),
+        (0.5, 0.0),
+        (0.975, 1.959963984540054),
+        (0.999, 3.090232306167813),
+    ];
+    for &(p, z) in &quantiles {
+        let acklam = acklam_inverse_normal(p);
+        let moro = moro_inverse_normal(p);
+        assert!(
+            (acklam - z).abs() <= 1.2e-9 * z.abs().max(1.0),
+            "Acklam({}) = {}, expected {}",
+            p,
+            acklam,
+            z
+        );
+        assert!(
+            (moro - z).abs() < 3e-9,
+            "Moro({}) = {}, expected {}",
+            p,
+            moro,
+            z
+        );
+    }
+
+    assert_eq!(inverse_normal_cdf(0.0), f64::NEG_INFINITY);
+    assert_eq!(inverse_normal_cdf(1.0), f64::INFINITY);
+    assert!(inverse_normal_cdf(f64::NAN).is_nan());
+    assert_eq!(moro_inverse_normal(-0.5), f64::NEG_INFINITY);
+}
+
+/// Verifies the inverse CDF is increasing and odd about one half, so that
+/// QMC points keep their stratification and antithetic symmetry.
+#[test]
+fn test_inverse_normal_monotone_and_symmetric() {
+    let mut previous = f64::NEG_INFINITY;
+    for i in 1..10_000 {
+        let p = i as f64 / 10_000.0;
+        let z = inverse_normal_cdf(p);
+        assert!(z > previous, "Not increasing at p = {}", p);
+        assert!((z + inverse_normal_cdf(1.0 - p)).abs() < 1e-9);
+        previous = z;
+    }
+
+    // Midpoint-rule uniforms map to normals with unit variance
+    let mut buffer: Vec<f64> = (0..100_000).map(|i| (i as f64 + 0.5) / 100_000.0).collect();
+    uniforms_to_normals(&mut buffer);
+    let (mean, variance) = moments(&buffer);
+    assert!(mean.abs() < 1e-12);
+    assert!((variance - 1.0).abs() < 1e-3);
+}
+
+/// Verifies Poisson and gamma sample moments.
+#[test]
+fn test_poisson_and_gamma_moments() {
+    let mut rng = PricerRng::from_seed(7);
+    let n = 200_000;
+
+    for &lambda in &[0.3, 4.0, 50.0] {
+        let draws: Vec<f64> = (0..n).map(|_| rng.gen_poisson(lambda) as f64).collect();
+        let (mean, variance) = moments(&draws);
+        assert!(
+            (mean - lambda).abs() < 0.02 * lambda.max(1.0),
+            "λ = {}",
+            lambda
+        );
+        assert!(
+            (variance - lambda).abs() < 0.05 * lambda.max(1.0),
+            "λ = {}",
+            lambda
+        );
+    }
+    assert_eq!(rng.gen_poisson(0.0), 0);
+
+    for &(shape, scale) in &[(0.4, 2.0), (3.0, 0.5)] {
+        let draws: Vec<f64> = (0..n).map(|_| rng.gen_gamma(shape, scale)).collect();
+        let (mean, variance) = moments(&draws);
+        assert!(draws.iter().all(|&x| x >= 0.0));
+        assert!((mean / (shape * scale) - 1.0).abs() < 0.02);
+        assert!((variance / (shape * scale * scale) - 1.0).abs() < 0.05);
+    }
+}
+
+/// Verifies non-central chi-square moments on both sides of one degree of
+/// freedom, and the exact CIR transition mean built from it.
+#[test]
+fn test_noncentral_chi_squared() {
+    let mut rng = PricerRng::from_seed(11);
+    let n = 200_000;
+
+    // Mean k + λ, variance 2(k + 2λ)
+    for &(dof, non_centrality) in &[(0.5, 2.0), (3.0, 0.0), (4.0, 6.0)] {
+        let draws: Vec<f64> = (0..n)
+            .map(|_| rng.gen_noncentral_chi_squared(dof, non_centrality))
+            .collect();
+        let (mean, variance) = moments(&draws);
+        let expected_mean = dof + non_centrality;
+        let expected_variance = 2.0 * (dof + 2.0 * non_centrality);
+        assert!(draws.iter().all(|&x| x >= 0.0));
+        assert!(
+            (mean / expected_mean - 1.0).abs() < 0.02,
+            "k = {}, λ = {}: mean {}",
+            dof,
+            non_centrality,
+            mean
+        );
+        assert!(
+            (variance / expected_variance - 1.0).abs() < 0.05,
+            "k = {}, λ = {}: variance {}",
+            dof,
+            non_centrality,
+            variance
+        );
+    }
+
+    // CIR with the Feller condition violated: 2κθ < σ²
+    let (kappa, theta, sigma, r0, dt) = (0.5_f64, 0.04, 0.4, 0.03, 1.0);
+    let c = sigma * sigma * (1.0 - (-kappa * dt).exp()) / (4.0 * kappa);
+    let dof = 4.0 * kappa * theta / (sigma * sigma);
+    let non_centrality = r0 * (-kappa * dt).exp() / c;
+    let draws: Vec<f64> = (0..n)
+        .map(|_| c * rng.gen_noncentral_chi_squared(dof, non_centrality))
+        .collect();
+    let (mean, _) = moments(&draws);
+    let expected = theta + (r0 - theta) * (-kappa * dt).exp();
+    assert!((mean / expected - 1.0).abs() < 0.02);
+}
+
+/// Verifies invalid distribution parameters are rejected.
+#[test]
+#[should_panic(expected = "must be positive and finite")]
+fn test_gamma_rejects_invalid_shape() {
+    let mut rng = PricerRng::from_seed(1);
+    rng.gen_gamma(0.0, 1.0);
+}
+
 // ============================================================================
 // Task 6.1: Module Publication and API Consistency
 // ============================================================================
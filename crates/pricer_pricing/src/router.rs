@@ -0,0 +1,700 @@
+//! Per-instrument engine routing.
+//!
+//! [`EngineRouter`] classifies a `pricer_models` [`Instrument`] into a
+//! [`ProductKind`], selects an [`Engine`] for it and prices it there, so
+//! early-exercise, digital and path-dependent trades no longer have to go
+//! through the European GBM Monte Carlo path.
+//!
+//! # Selection Order
+//!
+//! 1. A per-trade override registered with [`EngineRouter::with_trade_override`]
+//! 2. A per-kind default registered with [`EngineRouter::with_default_engine`]
+//! 3. [`Engine::preferred`]
+//!
+//! An explicit choice that cannot price the product is an error rather than
+//! a silent fallback.
+//!
+//! # Example
+//!
+//! ```rust
+//! use pricer_models::instruments::{
+//!     ExerciseStyle, Instrument, InstrumentParams, PayoffType, VanillaOption,
+//! };
+//! use pricer_pricing::engine::Engine;
+//! use pricer_pricing::router::{EngineRouter, MarketInputs};
+//!
+//! let params = InstrumentParams::new(100.0, 1.0, 1.0).unwrap();
+//! let put = Instrument::Vanilla(VanillaOption::new(
+//!     params,
+//!     PayoffType::Put,
+//!     ExerciseStyle::American,
+//!     1e-6,
+//! ));
+//! let market = MarketInputs::new(100.0, 0.05, 0.2);
+//!
+//! let router = EngineRouter::new().with_trade_override("T2", Engine::Pde);
+//! let tree = router.price(Some("T1"), &put, &market).unwrap();
+//! let pde = router.price(Some("T2"), &put, &market).unwrap();
+//! assert_eq!(tree.engine, Engine::Tree);
+//! assert_eq!(pde.engine, Engine::Pde);
+//! assert!((tree.price - pde.price).abs() < 1e-2);
+//! ```
+
+use std::collections::HashMap;
+
+use pricer_models::analytical::{norm_cdf, norm_pdf, BlackScholes};
+use pricer_models::instruments::{ExerciseStyle, Forward, Instrument, PayoffType, VanillaOption};
+use thiserror::Error;
+
+use crate::analytical::OptionType;
+use crate::engine::{Engine, ProductKind};
+use crate::mc::{
+    ConfigError, GbmParams, Greek, MonteCarloConfig, MonteCarloPricer, PayoffParams,
+    PayoffType as McPayoffType,
+};
+use crate::path_dependent::PathPayoffType;
+use crate::pde::{BlackScholesPde, Exercise, PdeConfig, PdeError, PdeOption};
+use crate::tree::{BlackScholesTree, TreeConfig, TreeError, TreeExercise, TreeOption};
+
+/// Errors from engine routing and routed pricing.
+#[derive(Debug, Error)]
+pub enum RouterError {
+    /// The instrument has no engine-routable product kind.
+    #[error("Cannot route instrument: {0}")]
+    Unroutable(String),
+
+    /// The selected engine cannot price the product kind.
+    #[error("Engine '{engine}' does not support {kind:?}")]
+    UnsupportedEngine {
+        /// Selected engine
+        engine: Engine,
+        /// Product kind of the instrument
+        kind: ProductKind,
+    },
+
+    /// Invalid market inputs.
+    #[error("Invalid market input '{name}': {value}")]
+    InvalidMarket {
+        /// Input name
+        name: &'static str,
+        /// Offending value
+        value: f64,
+    },
+
+    /// Monte Carlo configuration error.
+    #[error("Monte Carlo: {0}")]
+    MonteCarlo(#[from] ConfigError),
+
+    /// PDE engine error.
+    #[error("PDE: {0}")]
+    Pde(#[from] PdeError),
+
+    /// Tree engine error.
+    #[error("Tree: {0}")]
+    Tree(#[from] TreeError),
+}
+
+/// Result type for routed pricing.
+pub type RouterResult<T> = Result<T, RouterError>;
+
+/// Flat Black-Scholes market for a single underlying.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MarketInputs {
+    /// Spot price
+    pub spot: f64,
+    /// Continuously compounded risk-free rate
+    pub rate: f64,
+    /// Volatility
+    pub volatility: f64,
+}
+
+impl MarketInputs {
+    /// Creates market inputs.
+    #[inline]
+    pub fn new(spot: f64, rate: f64, volatility: f64) -> Self {
+        Self {
+            spot,
+            rate,
+            volatility,
+        }
+    }
+
+    pub(crate) fn validate(&self) -> RouterResult<()> {
+        for (name, value) in [("spot", self.spot), ("volatility", self.volatility)] {
+            if !(value > 0.0 && value.is_finite()) {
+                return Err(RouterError::InvalidMarket { name, value });
+            }
+        }
+        if !self.rate.is_finite() {
+            return Err(RouterError::InvalidMarket {
+                name: "rate",
+                value: self.rate,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Price of a routed trade.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RoutedPrice {
+    /// Engine that produced the price
+    pub engine: Engine,
+    /// Present value (scaled by notional)
+    pub price: f64,
+    /// Spot delta, when the engine provides it
+    pub delta: Option<f64>,
+    /// Spot gamma, when the engine provides it
+    pub gamma: Option<f64>,
+    /// Monte Carlo standard error
+    pub std_error: Option<f64>,
+    /// Discounted mean of smoothed minus exact payoff, when the engine
+    /// priced a smoothed payoff and measured it
+    pub smoothing_bias: Option<f64>,
+}
+
+impl RoutedPrice {
+    fn exact(engine: Engine, price: f64, delta: f64, gamma: f64) -> Self {
+        Self {
+            engine,
+            price,
+            delta: Some(delta),
+            gamma: Some(gamma),
+            std_error: None,
+            smoothing_bias: None,
+        }
+    }
+
+    fn scaled(self, notional: f64) -> Self {
+        Self {
+            price: self.price * notional,
+            delta: self.delta.map(|d| d * notional),
+            gamma: self.gamma.map(|g| g * notional),
+            std_error: self.std_error.map(|e| e * notional.abs()),
+            smoothing_bias: self.smoothing_bias.map(|b| b * notional),
+            ..self
+        }
+    }
+}
+
+/// Maps instruments to pricing engines with per-kind and per-trade
+/// overrides.
+#[derive(Clone, Debug)]
+pub struct EngineRouter {
+    mc_config: MonteCarloConfig,
+    pde_config: PdeConfig,
+    tree_config: TreeConfig,
+    defaults: HashMap<ProductKind, Engine>,
+    overrides: HashMap<String, Engine>,
+}
+
+impl Default for EngineRouter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EngineRouter {
+    /// Creates a router with default engine settings: 50,000 seeded Monte
+    /// Carlo paths of 52 steps, the default PDE grid and a 500-step tree
+    /// with Richardson extrapolation.
+    pub fn new() -> Self {
+        let mc_config = MonteCarloConfig::builder()
+            .n_paths(50_000)
+            .n_steps(52)
+            .seed(42)
+            .build()
+            .expect("default Monte Carlo settings are valid");
+        Self {
+            mc_config,
+            pde_config: PdeConfig::default(),
+            tree_config: TreeConfig::default().with_richardson(true),
+            defaults: HashMap::new(),
+            overrides: HashMap::new(),
+        }
+    }
+
+    /// Sets the Monte Carlo configuration.
+    #[inline]
+    pub fn with_mc_config(mut self, config: MonteCarloConfig) -> Self {
+        self.mc_config = config;
+        self
+    }
+
+    /// Sets the PDE grid.
+    #[inline]
+    pub fn with_pde_config(mut self, config: PdeConfig) -> Self {
+        self.pde_config = config;
+        self
+    }
+
+    /// Sets the tree lattice.
+    #[inline]
+    pub fn with_tree_config(mut self, config: TreeConfig) -> Self {
+        self.tree_config = config;
+        self
+    }
+
+    /// Routes every instrument of `kind` to `engine` unless a trade
+    /// override applies.
+    #[inline]
+    pub fn with_default_engine(mut self, kind: ProductKind, engine: Engine) -> Self {
+        self.defaults.insert(kind, engine);
+        self
+    }
+
+    /// Routes the trade `trade_id` to `engine`.
+    #[inline]
+    pub fn with_trade_override(mut self, trade_id: impl Into<String>, engine: Engine) -> Self {
+        self.overrides.insert(trade_id.into(), engine);
+        self
+    }
+
+    /// Classifies an instrument for routing.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RouterError::Unroutable` for swaps (which need a curve
+    /// rather than a spot model) and for non-European digitals.
+    pub fn classify(instrument: &Instrument<f64>) -> RouterResult<ProductKind> {
+        match instrument {
+            Instrument::Vanilla(option) => {
+                let digital = matches!(
+                    option.payoff_type(),
+                    PayoffType::DigitalCall | PayoffType::DigitalPut
+                );
+                match (option.exercise_style(), digital) {
+                    (ExerciseStyle::European, true) => Ok(ProductKind::Digital),
+                    (_, true) => Err(RouterError::Unroutable(
+                        "digital options are European only".to_string(),
+                    )),
+                    (ExerciseStyle::European, false) => Ok(ProductKind::EuropeanVanilla),
+                    (ExerciseStyle::American, false) => Ok(ProductKind::AmericanVanilla),
+                    (ExerciseStyle::Bermudan { .. }, false) => Ok(ProductKind::BermudanVanilla),
+                    (ExerciseStyle::Asian { .. }, false) => Ok(ProductKind::Asian),
+                }
+            }
+            Instrument::Forward(_) => Ok(ProductKind::Forward),
+            Instrument::Swap(_) => Err(RouterError::Unroutable(
+                "swaps are priced from curves, not a spot model".to_string(),
+            )),
+        }
+    }
+
+    /// Selects the engine for a trade.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RouterError::UnsupportedEngine` if an override names an
+    /// engine that cannot price the instrument.
+    pub fn route(
+        &self,
+        trade_id: Option<&str>,
+        instrument: &Instrument<f64>,
+    ) -> RouterResult<Engine> {
+        let kind = Self::classify(instrument)?;
+        let engine = trade_id
+            .and_then(|id| self.overrides.get(id))
+            .or_else(|| self.defaults.get(&kind))
+            .copied()
+            .unwrap_or_else(|| Engine::preferred(kind));
+        if engine.supports(kind) {
+            Ok(engine)
+        } else {
+            Err(RouterError::UnsupportedEngine { engine, kind })
+        }
+    }
+
+    /// Prices a trade on its routed engine.
+    ///
+    /// # Arguments
+    ///
+    /// * `trade_id` - Trade identifier for override lookup
+    /// * `instrument` - Instrument to price
+    /// * `market` - Spot, rate and volatility
+    ///
+    /// # Errors
+    ///
+    /// Returns `RouterError` if the trade cannot be routed, the market is
+    /// invalid, or the selected engine fails.
+    pub fn price(
+        &self,
+        trade_id: Option<&str>,
+        instrument: &Instrument<f64>,
+        market: &MarketInputs,
+    ) -> RouterResult<RoutedPrice> {
+        market.validate()?;
+        let engine = self.route(trade_id, instrument)?;
+        match instrument {
+            Instrument::Forward(forward) => Ok(price_forward(forward, market)),
+            Instrument::Vanilla(option) => {
+                let unit = match engine {
+                    Engine::Analytic => price_analytic(option, market),
+                    Engine::MonteCarlo => self.price_mc(option, market)?,
+                    Engine::Pde => self.price_pde(option, market)?,
+                    Engine::Tree => self.price_tree(option, market)?,
+                };
+                Ok(unit.scaled(option.notional()))
+            }
+            Instrument::Swap(_) => unreachable!("swaps are rejected by classify"),
+        }
+    }
+
+    fn price_mc(
+        &self,
+        option: &VanillaOption<f64>,
+        market: &MarketInputs,
+    ) -> RouterResult<RoutedPrice> {
+        let mut pricer = MonteCarloPricer::new(self.mc_config.clone())?;
+        let gbm = GbmParams::new(market.spot, market.rate, market.volatility, option.expiry());
+        let df = (-market.rate * option.expiry()).exp();
+        let is_call = option.payoff_type() == PayoffType::Call;
+
+        let result = if option.exercise_style().is_asian() {
+            // Averaging runs over the simulation grid
+            let payoff = if is_call {
+                PathPayoffType::asian_arithmetic_call(option.strike(), option.epsilon())
+            } else {
+                PathPayoffType::asian_arithmetic_put(option.strike(), option.epsilon())
+            };
+            pricer.price_path_dependent(gbm, payoff, df)
+        } else {
+            let payoff = PayoffParams {
+                strike: option.strike(),
+                payoff_type: if is_call {
+                    McPayoffType::Call
+                } else {
+                    McPayoffType::Put
+                },
+                smoothing_epsilon: option.epsilon(),
+            };
+            pricer.price_with_greeks(gbm, payoff, df, &[Greek::Delta, Greek::Gamma])
+        };
+        Ok(RoutedPrice {
+            engine: Engine::MonteCarlo,
+            price: result.price,
+            delta: result.delta,
+            gamma: result.gamma,
+            std_error: Some(result.std_error),
+            smoothing_bias: result.diagnostics.and_then(|d| d.smoothing_bias),
+        })
+    }
+
+    fn price_pde(
+        &self,
+        option: &VanillaOption<f64>,
+        market: &MarketInputs,
+    ) -> RouterResult<RoutedPrice> {
+        let exercise = if option.exercise_style().is_american() {
+            Exercise::American(Default::default())
+        } else {
+            Exercise::European
+        };
+        let pde_option = PdeOption::new(
+            option.strike(),
+            option.expiry(),
+            option_type(option),
+            exercise,
+        );
+        let model = BlackScholesPde::new(market.rate, 0.0, market.volatility);
+        let result = model.price(&pde_option, market.spot, &self.pde_config)?;
+        Ok(RoutedPrice::exact(
+            Engine::Pde,
+            result.price,
+            result.delta,
+            result.gamma,
+        ))
+    }
+
+    fn price_tree(
+        &self,
+        option: &VanillaOption<f64>,
+        market: &MarketInputs,
+    ) -> RouterResult<RoutedPrice> {
+        let exercise = match option.exercise_style() {
+            ExerciseStyle::American => TreeExercise::American,
+            ExerciseStyle::Bermudan { exercise_dates } => {
+                TreeExercise::Bermudan(exercise_dates.clone())
+            }
+            _ => TreeExercise::European,
+        };
+        let tree_option = TreeOption::new(
+            option.strike(),
+            option.expiry(),
+            option_type(option),
+            exercise,
+        );
+        let model = BlackScholesTree::new(market.rate, 0.0, market.volatility);
+        let result = model.price(&tree_option, market.spot, &self.tree_config)?;
+        Ok(RoutedPrice::exact(
+            Engine::Tree,
+            result.price,
+            result.delta,
+            result.gamma,
+        ))
+    }
+}
+
+/// Maps a vanilla payoff onto the call/put flag used by the grid engines.
+fn option_type(option: &VanillaOption<f64>) -> OptionType {
+    match option.payoff_type() {
+        PayoffType::Call | PayoffType::DigitalCall => OptionType::Call,
+        PayoffType::Put | PayoffType::DigitalPut => OptionType::Put,
+    }
+}
+
+/// Closed-form Black-Scholes price per unit notional.
+fn price_analytic(option: &VanillaOption<f64>, market: &MarketInputs) -> RoutedPrice {
+    let (strike, expiry) = (option.strike(), option.expiry());
+    // Inputs were validated, so construction cannot fail
+    let bs = BlackScholes::new(market.spot, market.rate, market.volatility)
+        .expect("validated market inputs");
+    let (price, delta, gamma) = match option.payoff_type() {
+        PayoffType::Call => (
+            bs.price_call(strike, expiry),
+            bs.delta(strike, expiry, true),
+            bs.gamma(strike, expiry),
+        ),
+        PayoffType::Put => (
+            bs.price_put(strike, expiry),
+            bs.delta(strike, expiry, false),
+            bs.gamma(strike, expiry),
+        ),
+        PayoffType::DigitalCall | PayoffType::DigitalPut => {
+            let sign = if option.payoff_type() == PayoffType::DigitalCall {
+                1.0
+            } else {
+                -1.0
+            };
+            let df = (-market.rate * expiry).exp();
+            let d2 = bs.d2(strike, expiry);
+            let vol_sqrt_t = market.volatility * expiry.sqrt();
+            let density = norm_pdf(d2);
+            let d1 = d2 + vol_sqrt_t;
+            (
+                df * norm_cdf(sign * d2),
+                sign * df * density / (market.spot * vol_sqrt_t),
+                -sign * df * density * d1 / (market.spot * market.spot * vol_sqrt_t * vol_sqrt_t),
+            )
+        }
+    };
+    RoutedPrice::exact(Engine::Analytic, price, delta, gamma)
+}
+
+/// Forward value `±N·(S - K·e^{-rT})`.
+fn price_forward(forward: &Forward<f64>, market: &MarketInputs) -> RoutedPrice {
+    let sign = if forward.is_long() { 1.0 } else { -1.0 };
+    let notional = sign * forward.notional();
+    let df = (-market.rate * forward.expiry()).exp();
+    RoutedPrice::exact(
+        Engine::Analytic,
+        notional * (market.spot - forward.strike() * df),
+        notional,
+        0.0,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use pricer_models::instruments::{Direction, InstrumentParams};
+
+    const BS_CALL: f64 = 10.450_583_572_185_565;
+
+    fn vanilla(payoff: PayoffType, exercise: ExerciseStyle<f64>) -> Instrument<f64> {
+        let params = InstrumentParams::new(100.0, 1.0, 1.0).unwrap();
+        Instrument::Vanilla(VanillaOption::new(params, payoff, exercise, 1e-6))
+    }
+
+    fn market() -> MarketInputs {
+        MarketInputs::new(100.0, 0.05, 0.2)
+    }
+
+    #[test]
+    fn test_default_routing() {
+        let router = EngineRouter::new();
+        let cases = [
+            (
+                vanilla(PayoffType::Call, ExerciseStyle::European),
+                Engine::Analytic,
+            ),
+            (
+                vanilla(PayoffType::DigitalPut, ExerciseStyle::European),
+                Engine::Analytic,
+            ),
+            (
+                vanilla(PayoffType::Put, ExerciseStyle::American),
+                Engine::Tree,
+            ),
+            (
+                vanilla(
+                    PayoffType::Put,
+                    ExerciseStyle::Bermudan {
+                        exercise_dates: vec![0.5, 1.0],
+                    },
+                ),
+                Engine::Tree,
+            ),
+            (
+                vanilla(
+                    PayoffType::Call,
+                    ExerciseStyle::Asian {
+                        averaging_start: 0.0,
+                        averaging_end: 1.0,
+                        num_observations: 12,
+                    },
+                ),
+                Engine::MonteCarlo,
+            ),
+        ];
+        for (instrument, engine) in cases {
+            assert_eq!(router.route(None, &instrument).unwrap(), engine);
+        }
+    }
+
+    #[test]
+    fn test_overrides_take_precedence() {
+        let call = vanilla(PayoffType::Call, ExerciseStyle::European);
+        let router = EngineRouter::new()
+            .with_default_engine(ProductKind::EuropeanVanilla, Engine::Pde)
+            .with_trade_override("T1", Engine::Tree);
+
+        assert_eq!(router.route(None, &call).unwrap(), Engine::Pde);
+        assert_eq!(router.route(Some("T0"), &call).unwrap(), Engine::Pde);
+        assert_eq!(router.route(Some("T1"), &call).unwrap(), Engine::Tree);
+
+        let american = vanilla(PayoffType::Put, ExerciseStyle::American);
+        let bad = EngineRouter::new().with_trade_override("T1", Engine::MonteCarlo);
+        assert!(matches!(
+            bad.route(Some("T1"), &american),
+            Err(RouterError::UnsupportedEngine {
+                engine: Engine::MonteCarlo,
+                kind: ProductKind::AmericanVanilla,
+            })
+        ));
+    }
+
+    #[test]
+    fn test_engines_agree_on_european_call() {
+        let call = vanilla(PayoffType::Call, ExerciseStyle::European);
+        for engine in Engine::ALL {
+            let router = EngineRouter::new().with_trade_override("T", engine);
+            let result = router.price(Some("T"), &call, &market()).unwrap();
+            assert_eq!(result.engine, engine);
+            let tolerance = match result.std_error {
+                Some(se) => 4.0 * se + 0.05,
+                None => 5e-3,
+            };
+            assert!(
+                (result.price - BS_CALL).abs() < tolerance,
+                "{}: {}",
+                engine,
+                result.price
+            );
+            assert_relative_eq!(result.delta.unwrap(), 0.6368, epsilon = 2e-2);
+        }
+    }
+
+    #[test]
+    fn test_mc_reports_smoothing_bias_of_override() {
+        let router = EngineRouter::new().with_trade_override("T", Engine::MonteCarlo);
+        let price = |params: InstrumentParams<f64>| {
+            let call = Instrument::Vanilla(VanillaOption::new(
+                params,
+                PayoffType::Call,
+                ExerciseStyle::European,
+                1e-6,
+            ));
+            router.price(Some("T"), &call, &market()).unwrap()
+        };
+        let params = InstrumentParams::new(100.0, 1.0, 2.0).unwrap();
+        let sharp = price(params);
+        let blunt = price(params.with_smoothing_epsilon(2.0).unwrap());
+
+        assert!(sharp.smoothing_bias.unwrap().abs() < 1e-4);
+        // Same paths, so the price moves by exactly the extra bias
+        assert_relative_eq!(
+            blunt.price - sharp.price,
+            blunt.smoothing_bias.unwrap() - sharp.smoothing_bias.unwrap(),
+            max_relative = 1e-9
+        );
+        assert!(blunt.smoothing_bias.unwrap() > 0.1);
+
+        let analytic = EngineRouter::new()
+            .price(
+                None,
+                &vanilla(PayoffType::Call, ExerciseStyle::European),
+                &market(),
+            )
+            .unwrap();
+        assert_eq!(analytic.smoothing_bias, None);
+    }
+
+    #[test]
+    fn test_notional_and_forward() {
+        let params = InstrumentParams::new(100.0, 1.0, 10.0).unwrap();
+        let call = Instrument::Vanilla(VanillaOption::new(
+            params,
+            PayoffType::Call,
+            ExerciseStyle::European,
+            1e-6,
+        ));
+        let result = EngineRouter::new().price(None, &call, &market()).unwrap();
+        assert_relative_eq!(result.price, 10.0 * BS_CALL, epsilon = 1e-3);
+
+        let short = Instrument::Forward(Forward::new(100.0, 1.0, 2.0, Direction::Short).unwrap());
+        let result = EngineRouter::new().price(None, &short, &market()).unwrap();
+        assert_relative_eq!(
+            result.price,
+            -2.0 * (100.0 - 100.0 * (-0.05_f64).exp()),
+            epsilon = 1e-12
+        );
+        assert_eq!(result.delta, Some(-2.0));
+    }
+
+    #[test]
+    fn test_digital_parity() {
+        let router = EngineRouter::new();
+        let call = router
+            .price(
+                None,
+                &vanilla(PayoffType::DigitalCall, ExerciseStyle::European),
+                &market(),
+            )
+            .unwrap();
+        let put = router
+            .price(
+                None,
+                &vanilla(PayoffType::DigitalPut, ExerciseStyle::European),
+                &market(),
+            )
+            .unwrap();
+        assert_relative_eq!(call.price + put.price, (-0.05_f64).exp(), epsilon = 1e-12);
+        assert_relative_eq!(
+            call.delta.unwrap() + put.delta.unwrap(),
+            0.0,
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn test_unroutable() {
+        let digital = vanilla(PayoffType::DigitalCall, ExerciseStyle::American);
+        assert!(matches!(
+            EngineRouter::classify(&digital),
+            Err(RouterError::Unroutable(_))
+        ));
+        assert!(matches!(
+            EngineRouter::new().price(
+                None,
+                &vanilla(PayoffType::Call, ExerciseStyle::European),
+                &MarketInputs::new(100.0, 0.05, -0.2)
+            ),
+            Err(RouterError::InvalidMarket {
+                name: "volatility",
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,461 @@
+//! Per-instrument payoff smoothing widths.
+//!
+//! Smoothed payoffs keep pathwise Greeks differentiable at the price of a
+//! bias against the exact payoff. Narrow widths keep the bias small but
+//! make the first Greek that differentiates the kink noisy: a spike of
+//! height `1/ε` whose variance grows like `1/ε`. That Greek is the delta of
+//! a digital and the gamma of a call or put. [`SmoothingTuner`] evaluates a
+//! grid of candidate widths on one pilot simulation of terminal prices and
+//! picks the width minimising
+//!
+//! ```text
+//! bias(ε)² + w · (h · SE_Δ(ε))²       digitals
+//! bias(ε)² + w · (½h² · SE_Γ(ε))²     calls and puts
+//! ```
+//!
+//! where `bias` is the discounted mean of smoothed minus exact payoff, `SE`
+//! the standard error of the pathwise Greek and `h = S₀σ√T` a one standard
+//! deviation move of the underlying to expiry, so both terms are price
+//! errors squared. The chosen width is applied through
+//! [`InstrumentParams::with_smoothing_epsilon`], which every engine reading
+//! [`VanillaOption::epsilon`] honours.
+//!
+//! The pilot uses the terminal distribution only, so for early-exercise and
+//! Asian options the widths are tuned on the European payoff.
+//!
+//! # Example
+//!
+//! ```rust
+//! use pricer_models::instruments::{
+//!     ExerciseStyle, InstrumentParams, PayoffType, VanillaOption,
+//! };
+//! use pricer_pricing::router::MarketInputs;
+//! use pricer_pricing::smoothing::SmoothingTuner;
+//!
+//! let params = InstrumentParams::new(100.0, 1.0, 1.0).unwrap();
+//! let digital = VanillaOption::new(
+//!     params,
+//!     PayoffType::DigitalCall,
+//!     ExerciseStyle::European,
+//!     1e-6,
+//! );
+//! let market = MarketInputs::new(100.0, 0.05, 0.2);
+//!
+//! let choice = SmoothingTuner::new().tune(&digital, &market).unwrap();
+//! let tuned = choice.apply(&digital);
+//!
+//! assert_eq!(tuned.epsilon(), choice.epsilon());
+//! assert!(choice.epsilon() > 1e-6);
+//! assert!(choice.bias().abs() < 1e-2);
+//! ```
+
+use pricer_core::math::smoothing::smooth_indicator;
+use pricer_models::instruments::{InstrumentParams, PayoffType, VanillaOption};
+use thiserror::Error;
+
+use crate::mc::Greek;
+use crate::rng::PricerRng;
+use crate::router::{MarketInputs, RouterError};
+
+/// Default candidate widths, relative to the strike.
+const RELATIVE_CANDIDATES: [f64; 6] = [1e-6, 1e-5, 1e-4, 1e-3, 1e-2, 1e-1];
+
+/// Errors from smoothing width selection.
+#[derive(Debug, Error)]
+pub enum SmoothingError {
+    /// Invalid tuner setting.
+    #[error("Invalid smoothing setting '{name}': {value}")]
+    InvalidSetting {
+        /// Setting name
+        name: &'static str,
+        /// Offending value
+        value: f64,
+    },
+
+    /// Invalid market inputs.
+    #[error(transparent)]
+    Market(#[from] RouterError),
+}
+
+/// Result type for smoothing width selection.
+pub type SmoothingResult<T> = Result<T, SmoothingError>;
+
+/// Pilot measurements for one candidate width.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SmoothingCandidate {
+    /// Absolute smoothing width
+    pub epsilon: f64,
+    /// Discounted mean of smoothed minus exact payoff per unit notional
+    pub bias: f64,
+    /// Standard error of the pathwise Greek per unit notional
+    pub greek_std_error: f64,
+    /// Value of the tuning objective
+    pub objective: f64,
+}
+
+/// Width selected by [`SmoothingTuner::tune`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SmoothingChoice {
+    /// Greek whose variance the width was traded against
+    pub greek: Greek,
+    /// The candidate minimising the objective
+    pub chosen: SmoothingCandidate,
+    /// Every candidate, in increasing width
+    pub candidates: Vec<SmoothingCandidate>,
+}
+
+impl SmoothingChoice {
+    /// Returns the selected width.
+    #[inline]
+    pub fn epsilon(&self) -> f64 {
+        self.chosen.epsilon
+    }
+
+    /// Returns the estimated price bias at the selected width.
+    #[inline]
+    pub fn bias(&self) -> f64 {
+        self.chosen.bias
+    }
+
+    /// Returns a copy of `option` whose parameters override its smoothing
+    /// width with the selected one.
+    pub fn apply(&self, option: &VanillaOption<f64>) -> VanillaOption<f64> {
+        let params: InstrumentParams<f64> = option
+            .params()
+            .with_smoothing_epsilon(self.chosen.epsilon)
+            .expect("candidate widths are validated positive");
+        VanillaOption::new(
+            params,
+            option.payoff_type(),
+            option.exercise_style().clone(),
+            option.epsilon(),
+        )
+    }
+}
+
+/// Selects payoff smoothing widths by trading price bias against Greek
+/// variance on a pilot simulation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SmoothingTuner {
+    relative_candidates: Vec<f64>,
+    n_paths: usize,
+    seed: u64,
+    greek_weight: f64,
+}
+
+impl Default for SmoothingTuner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SmoothingTuner {
+    /// Creates a tuner with widths of 10⁻⁶ to 10⁻¹ times the strike, a
+    /// 20,000-path pilot seeded with 42 and unit Greek weight.
+    pub fn new() -> Self {
+        Self {
+            relative_candidates: RELATIVE_CANDIDATES.to_vec(),
+            n_paths: 20_000,
+            seed: 42,
+            greek_weight: 1.0,
+        }
+    }
+
+    /// Sets the candidate widths as fractions of the strike.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SmoothingError::InvalidSetting` if the list is empty or any
+    /// width is not positive and finite.
+    pub fn with_relative_candidates(mut self, candidates: Vec<f64>) -> SmoothingResult<Self> {
+        if candidates.is_empty() {
+            return Err(SmoothingError::InvalidSetting {
+                name: "candidates",
+                value: 0.0,
+            });
+        }
+        if let Some(&bad) = candidates.iter().find(|&&c| !(c > 0.0 && c.is_finite())) {
+            return Err(SmoothingError::InvalidSetting {
+                name: "candidate",
+                value: bad,
+            });
+        }
+        self.relative_candidates = candidates;
+        self.relative_candidates.sort_by(f64::total_cmp);
+        Ok(self)
+    }
+
+    /// Sets the number of pilot paths.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SmoothingError::InvalidSetting` for fewer than two paths.
+    pub fn with_n_paths(mut self, n_paths: usize) -> SmoothingResult<Self> {
+        if n_paths < 2 {
+            return Err(SmoothingError::InvalidSetting {
+                name: "n_paths",
+                value: n_paths as f64,
+            });
+        }
+        self.n_paths = n_paths;
+        Ok(self)
+    }
+
+    /// Sets the pilot seed.
+    #[inline]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Sets the weight of the Greek variance term against the squared
+    /// bias.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SmoothingError::InvalidSetting` if `weight` is negative or
+    /// not finite.
+    pub fn with_greek_weight(mut self, weight: f64) -> SmoothingResult<Self> {
+        if !(weight >= 0.0 && weight.is_finite()) {
+            return Err(SmoothingError::InvalidSetting {
+                name: "greek_weight",
+                value: weight,
+            });
+        }
+        self.greek_weight = weight;
+        Ok(self)
+    }
+
+    /// Selects the smoothing width for `option` under a flat Black-Scholes
+    /// market.
+    ///
+    /// Every candidate is measured on the same pilot paths. Bias and
+    /// standard error are per unit notional.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SmoothingError::Market` if the market inputs are invalid.
+    pub fn tune(
+        &self,
+        option: &VanillaOption<f64>,
+        market: &MarketInputs,
+    ) -> SmoothingResult<SmoothingChoice> {
+        market.validate()?;
+        let (strike, expiry) = (option.strike(), option.expiry());
+        let df = (-market.rate * expiry).exp();
+        let drift = (market.rate - 0.5 * market.volatility * market.volatility) * expiry;
+        let vol_sqrt_t = market.volatility * expiry.sqrt();
+
+        let mut normals = vec![0.0; self.n_paths];
+        PricerRng::from_seed(self.seed).fill_normal(&mut normals);
+        let terminals: Vec<f64> = normals
+            .iter()
+            .map(|z| market.spot * (drift + vol_sqrt_t * z).exp())
+            .collect();
+
+        let payoff_type = option.payoff_type();
+        let greek = match payoff_type {
+            PayoffType::DigitalCall | PayoffType::DigitalPut => Greek::Delta,
+            PayoffType::Call | PayoffType::Put => Greek::Gamma,
+        };
+        let move_size = market.spot * vol_sqrt_t;
+        let n = self.n_paths as f64;
+        let candidates: Vec<SmoothingCandidate> = self
+            .relative_candidates
+            .iter()
+            .map(|&relative| {
+                let epsilon = relative * strike;
+                let (mut bias, mut sum, mut sum_sq) = (0.0, 0.0, 0.0);
+                for &terminal in &terminals {
+                    bias += payoff_type.evaluate(terminal, strike, epsilon)
+                        - exact_payoff(payoff_type, terminal, strike);
+                    // dS_T/dS₀ = S_T/S₀ under GBM, once per order of the Greek
+                    let growth = terminal / market.spot;
+                    let scale = match greek {
+                        Greek::Delta => growth,
+                        _ => growth * growth,
+                    };
+                    let value =
+                        df * kink_derivative(payoff_type, terminal, strike, epsilon) * scale;
+                    sum += value;
+                    sum_sq += value * value;
+                }
+                let bias = df * bias / n;
+                let mean = sum / n;
+                let variance = ((sum_sq - n * mean * mean) / (n - 1.0)).max(0.0);
+                let greek_std_error = (variance / n).sqrt();
+                let greek_error = match greek {
+                    Greek::Delta => move_size * greek_std_error,
+                    _ => 0.5 * move_size * move_size * greek_std_error,
+                };
+                SmoothingCandidate {
+                    epsilon,
+                    bias,
+                    greek_std_error,
+                    objective: bias * bias + self.greek_weight * greek_error * greek_error,
+                }
+            })
+            .collect();
+
+        let chosen = *candidates
+            .iter()
+            .min_by(|a, b| a.objective.total_cmp(&b.objective))
+            .expect("at least one candidate");
+        Ok(SmoothingChoice {
+            greek,
+            chosen,
+            candidates,
+        })
+    }
+}
+
+/// Unsmoothed payoff per unit notional.
+fn exact_payoff(payoff_type: PayoffType, terminal: f64, strike: f64) -> f64 {
+    match payoff_type {
+        PayoffType::Call => (terminal - strike).max(0.0),
+        PayoffType::Put => (strike - terminal).max(0.0),
+        PayoffType::DigitalCall => f64::from(u8::from(terminal > strike)),
+        PayoffType::DigitalPut => f64::from(u8::from(terminal < strike)),
+    }
+}
+
+/// Pathwise Greek of the smoothed payoff with respect to the terminal
+/// price: the slope of a digital or the curvature of a call or put.
+///
+/// Both are the slope `σ(1 − σ)/ε` of the sigmoid `σ(x/ε)`, which is itself
+/// the slope of the softplus `ε·ln(1 + e^{x/ε})`.
+fn kink_derivative(payoff_type: PayoffType, terminal: f64, strike: f64, epsilon: f64) -> f64 {
+    let (sign, x) = match payoff_type {
+        PayoffType::Call | PayoffType::DigitalCall => (1.0, terminal - strike),
+        PayoffType::Put => (1.0, strike - terminal),
+        PayoffType::DigitalPut => (-1.0, strike - terminal),
+    };
+    let s = smooth_indicator(x, epsilon);
+    sign * s * (1.0 - s) / epsilon
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pricer_models::instruments::ExerciseStyle;
+
+    fn option(payoff_type: PayoffType) -> VanillaOption<f64> {
+        let params = InstrumentParams::new(100.0, 1.0, 1.0).unwrap();
+        VanillaOption::new(params, payoff_type, ExerciseStyle::European, 1e-6)
+    }
+
+    fn market() -> MarketInputs {
+        MarketInputs::new(100.0, 0.05, 0.2)
+    }
+
+    #[test]
+    fn test_chosen_candidate_minimises_objective() {
+        for payoff_type in [
+            PayoffType::Call,
+            PayoffType::Put,
+            PayoffType::DigitalCall,
+            PayoffType::DigitalPut,
+        ] {
+            let choice = SmoothingTuner::new()
+                .tune(&option(payoff_type), &market())
+                .unwrap();
+            assert_eq!(choice.candidates.len(), RELATIVE_CANDIDATES.len());
+            assert!(choice
+                .candidates
+                .iter()
+                .all(|c| c.objective >= choice.chosen.objective));
+            assert!(choice
+                .candidates
+                .windows(2)
+                .all(|w| w[0].epsilon < w[1].epsilon));
+        }
+    }
+
+    #[test]
+    fn test_bias_grows_with_width() {
+        // Softplus lies above max(x, 0) by at most ε·ln 2, so the bias is
+        // positive and grows with the width
+        let choice = SmoothingTuner::new()
+            .tune(&option(PayoffType::Call), &market())
+            .unwrap();
+        for pair in choice.candidates.windows(2) {
+            assert!(pair[1].bias > pair[0].bias);
+        }
+        for c in &choice.candidates {
+            assert!(c.bias > 0.0 && c.bias <= c.epsilon * 2.0_f64.ln());
+        }
+    }
+
+    #[test]
+    fn test_width_balances_bias_against_greek_noise() {
+        let call = option(PayoffType::Call);
+        let choice = SmoothingTuner::new().tune(&call, &market()).unwrap();
+        let narrowest = choice.candidates[0];
+        let widest = choice.candidates[choice.candidates.len() - 1];
+
+        // The narrowest width leaves the pathwise gamma to a handful of
+        // paths at the strike; the widest biases the price
+        assert_eq!(choice.greek, Greek::Gamma);
+        assert!(narrowest.greek_std_error > 5.0 * choice.chosen.greek_std_error);
+        assert!(widest.bias > 10.0 * choice.bias());
+        assert!(choice.epsilon() > narrowest.epsilon && choice.epsilon() < widest.epsilon);
+
+        // Ignoring the Greek falls back to the least biased width, and more
+        // weight on it never narrows the choice
+        let bias_only = SmoothingTuner::new()
+            .with_greek_weight(0.0)
+            .unwrap()
+            .tune(&call, &market())
+            .unwrap();
+        assert_eq!(bias_only.epsilon(), narrowest.epsilon);
+        let greek_heavy = SmoothingTuner::new()
+            .with_greek_weight(100.0)
+            .unwrap()
+            .tune(&call, &market())
+            .unwrap();
+        assert!(greek_heavy.epsilon() >= choice.epsilon());
+    }
+
+    #[test]
+    fn test_digital_trades_against_delta() {
+        let choice = SmoothingTuner::new()
+            .tune(&option(PayoffType::DigitalCall), &market())
+            .unwrap();
+        assert_eq!(choice.greek, Greek::Delta);
+        assert!(choice.candidates[0].greek_std_error > 10.0 * choice.chosen.greek_std_error);
+        assert!(choice.bias().abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_apply_overrides_instrument_width() {
+        let digital = option(PayoffType::DigitalPut);
+        let choice = SmoothingTuner::new().tune(&digital, &market()).unwrap();
+        let tuned = choice.apply(&digital);
+
+        assert_eq!(tuned.params().smoothing_epsilon(), Some(choice.epsilon()));
+        assert_eq!(tuned.epsilon(), choice.epsilon());
+        assert_eq!(tuned.payoff_type(), PayoffType::DigitalPut);
+        assert_eq!(tuned.strike(), digital.strike());
+    }
+
+    #[test]
+    fn test_invalid_settings() {
+        assert!(SmoothingTuner::new()
+            .with_relative_candidates(vec![])
+            .is_err());
+        assert!(SmoothingTuner::new()
+            .with_relative_candidates(vec![1e-3, 0.0])
+            .is_err());
+        assert!(SmoothingTuner::new().with_n_paths(1).is_err());
+        assert!(SmoothingTuner::new().with_greek_weight(-1.0).is_err());
+        assert!(matches!(
+            SmoothingTuner::new().tune(
+                &option(PayoffType::Call),
+                &MarketInputs::new(100.0, 0.05, 0.0)
+            ),
+            Err(SmoothingError::Market(RouterError::InvalidMarket { .. }))
+        ));
+    }
+}
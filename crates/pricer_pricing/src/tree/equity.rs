@@ -0,0 +1,493 @@
+//! Recombining binomial and trinomial trees for equity options.
+
+use super::error::{TreeError, TreeResult};
+use crate::analytical::OptionType;
+use crate::validate::{finite, positive};
+
+/// Lattice used by the equity tree.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TreeMethod {
+    /// Cox-Ross-Rubinstein binomial tree (`u = e^{σ√Δt}`, `d = 1/u`).
+    #[default]
+    Crr,
+    /// Trinomial tree in log-spot with spacing `σ√(3Δt)`.
+    Trinomial,
+}
+
+/// Lattice settings shared by the tree engines.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TreeConfig {
+    /// Number of time steps.
+    pub n_steps: usize,
+    /// Equity lattice type (the Hull-White tree is always trinomial).
+    pub method: TreeMethod,
+    /// Combine `n_steps` and `2·n_steps` as `2·V(2N) - V(N)` to cancel the
+    /// leading `O(1/N)` error term.
+    pub richardson: bool,
+}
+
+impl TreeConfig {
+    /// Creates a configuration with the given number of steps.
+    #[inline]
+    pub fn new(n_steps: usize) -> Self {
+        Self {
+            n_steps,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the lattice type.
+    #[inline]
+    pub fn with_method(mut self, method: TreeMethod) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Enables or disables Richardson extrapolation.
+    #[inline]
+    pub fn with_richardson(mut self, richardson: bool) -> Self {
+        self.richardson = richardson;
+        self
+    }
+
+    /// Validates the lattice settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TreeError::InvalidParameter` if there are fewer than 2 steps.
+    pub fn validate(&self) -> TreeResult<()> {
+        if self.n_steps < 2 {
+            return Err(TreeError::InvalidParameter {
+                name: "n_steps",
+                value: format!("{} must be at least 2", self.n_steps),
+            });
+        }
+        Ok(())
+    }
+
+    /// Runs `solve` at `n_steps` and, with Richardson enabled, also at
+    /// `2·n_steps`, combining the two.
+    pub(crate) fn extrapolate<R>(
+        &self,
+        mut solve: impl FnMut(usize) -> TreeResult<R>,
+    ) -> TreeResult<R>
+    where
+        R: std::ops::Mul<f64, Output = R> + std::ops::Sub<Output = R>,
+    {
+        let coarse = solve(self.n_steps)?;
+        if !self.richardson {
+            return Ok(coarse);
+        }
+        let fine = solve(2 * self.n_steps)?;
+        Ok(fine * 2.0 - coarse)
+    }
+}
+
+impl Default for TreeConfig {
+    /// Returns a 500-step CRR tree without extrapolation.
+    fn default() -> Self {
+        Self {
+            n_steps: 500,
+            method: TreeMethod::Crr,
+            richardson: false,
+        }
+    }
+}
+
+/// Exercise style for tree-priced options.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum TreeExercise {
+    /// Exercise at maturity only.
+    #[default]
+    European,
+    /// Exercise at any step.
+    American,
+    /// Exercise on the given dates (years from today), each snapped to the
+    /// nearest step.
+    Bermudan(Vec<f64>),
+}
+
+/// Vanilla option priced on a tree.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TreeOption {
+    /// Strike price
+    pub strike: f64,
+    /// Time to maturity in years
+    pub maturity: f64,
+    /// Call or put
+    pub option_type: OptionType,
+    /// Exercise style
+    pub exercise: TreeExercise,
+}
+
+impl TreeOption {
+    /// Creates an option.
+    #[inline]
+    pub fn new(
+        strike: f64,
+        maturity: f64,
+        option_type: OptionType,
+        exercise: TreeExercise,
+    ) -> Self {
+        Self {
+            strike,
+            maturity,
+            option_type,
+            exercise,
+        }
+    }
+
+    /// Creates an American put.
+    #[inline]
+    pub fn american_put(strike: f64, maturity: f64) -> Self {
+        Self::new(strike, maturity, OptionType::Put, TreeExercise::American)
+    }
+
+    /// Creates an American call.
+    #[inline]
+    pub fn american_call(strike: f64, maturity: f64) -> Self {
+        Self::new(strike, maturity, OptionType::Call, TreeExercise::American)
+    }
+
+    /// Returns the exercise value at `spot`.
+    #[inline]
+    pub fn intrinsic(&self, spot: f64) -> f64 {
+        match self.option_type {
+            OptionType::Call => (spot - self.strike).max(0.0),
+            OptionType::Put => (self.strike - spot).max(0.0),
+        }
+    }
+
+    /// Validates the instrument against a spot level.
+    fn validate(&self, spot: f64) -> TreeResult<()> {
+        positive("spot", spot)?;
+        positive("strike", self.strike)?;
+        positive("maturity", self.maturity)?;
+        if let TreeExercise::Bermudan(dates) = &self.exercise {
+            if let Some(&t) = dates.iter().find(|&&t| !(t > 0.0 && t <= self.maturity)) {
+                return Err(TreeError::InvalidParameter {
+                    name: "exercise_date",
+                    value: format!("{} must be in (0, {}]", t, self.maturity),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns which of the `n + 1` steps allow early exercise.
+    fn exercisable(&self, n: usize) -> Vec<bool> {
+        match &self.exercise {
+            TreeExercise::European => vec![false; n + 1],
+            TreeExercise::American => vec![true; n + 1],
+            TreeExercise::Bermudan(dates) => {
+                let dt = self.maturity / n as f64;
+                let mut steps = vec![false; n + 1];
+                for &t in dates {
+                    steps[((t / dt).round() as usize).min(n)] = true;
+                }
+                steps
+            }
+        }
+    }
+}
+
+/// Price and spot Greeks from a tree.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TreePricingResult {
+    /// Present value
+    pub price: f64,
+    /// ∂V/∂S from the first-step nodes
+    pub delta: f64,
+    /// ∂²V/∂S² from the first nodes spanning three spot levels
+    pub gamma: f64,
+}
+
+impl std::ops::Sub for TreePricingResult {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self {
+            price: self.price - rhs.price,
+            delta: self.delta - rhs.delta,
+            gamma: self.gamma - rhs.gamma,
+        }
+    }
+}
+
+impl std::ops::Mul<f64> for TreePricingResult {
+    type Output = Self;
+
+    fn mul(self, rhs: f64) -> Self {
+        Self {
+            price: self.price * rhs,
+            delta: self.delta * rhs,
+            gamma: self.gamma * rhs,
+        }
+    }
+}
+
+/// Black-Scholes model for the equity tree engine.
+///
+/// # Examples
+///
+/// ```rust
+/// use pricer_pricing::tree::{BlackScholesTree, TreeConfig, TreeOption};
+///
+/// let model = BlackScholesTree::new(0.05, 0.0, 0.2);
+/// let config = TreeConfig::new(200).with_richardson(true);
+///
+/// let put = model.price(&TreeOption::american_put(100.0, 1.0), 100.0, &config).unwrap();
+/// assert!((put.price - 6.0904).abs() < 5e-3);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BlackScholesTree {
+    /// Risk-free rate
+    pub rate: f64,
+    /// Continuous dividend yield
+    pub dividend: f64,
+    /// Volatility
+    pub volatility: f64,
+}
+
+impl BlackScholesTree {
+    /// Creates a Black-Scholes model.
+    #[inline]
+    pub fn new(rate: f64, dividend: f64, volatility: f64) -> Self {
+        Self {
+            rate,
+            dividend,
+            volatility,
+        }
+    }
+
+    /// Prices an option by backward induction.
+    ///
+    /// # Arguments
+    ///
+    /// * `option` - Instrument to price
+    /// * `spot` - Current spot price
+    /// * `config` - Lattice settings
+    ///
+    /// # Errors
+    ///
+    /// Returns `TreeError` if the model, instrument or lattice is invalid,
+    /// or if the step size yields negative branching probabilities.
+    pub fn price(
+        &self,
+        option: &TreeOption,
+        spot: f64,
+        config: &TreeConfig,
+    ) -> TreeResult<TreePricingResult> {
+        config.validate()?;
+        option.validate(spot)?;
+        positive("volatility", self.volatility)?;
+        finite("rate", self.rate)?;
+        finite("dividend", self.dividend)?;
+
+        config.extrapolate(|n| match config.method {
+            TreeMethod::Crr => self.binomial(option, spot, n),
+            TreeMethod::Trinomial => self.trinomial(option, spot, n),
+        })
+    }
+
+    fn binomial(&self, option: &TreeOption, spot: f64, n: usize) -> TreeResult<TreePricingResult> {
+        let dt = option.maturity / n as f64;
+        let u = (self.volatility * dt.sqrt()).exp();
+        let d = 1.0 / u;
+        let p = (((self.rate - self.dividend) * dt).exp() - d) / (u - d);
+        if !(0.0..=1.0).contains(&p) {
+            return Err(TreeError::NegativeProbability { dt });
+        }
+        let disc = (-self.rate * dt).exp();
+        let exercisable = option.exercisable(n);
+        let node_spot = |m: usize, j: usize| spot * u.powi(2 * j as i32 - m as i32);
+
+        let mut values: Vec<f64> = (0..=n).map(|j| option.intrinsic(node_spot(n, j))).collect();
+        let mut step_two = [0.0; 3];
+        let mut step_one = [0.0; 2];
+        for m in (0..n).rev() {
+            for j in 0..=m {
+                let mut value = disc * (p * values[j + 1] + (1.0 - p) * values[j]);
+                if exercisable[m] {
+                    value = value.max(option.intrinsic(node_spot(m, j)));
+                }
+                values[j] = value;
+            }
+            match m {
+                2 => step_two.copy_from_slice(&values[..3]),
+                1 => step_one.copy_from_slice(&values[..2]),
+                _ => {}
+            }
+        }
+
+        // Node spots: step one (Sd, Su), step two (Sd², S, Su²)
+        let delta = (step_one[1] - step_one[0]) / (spot * (u - d));
+        let (s_down, s_up) = (spot * d * d, spot * u * u);
+        let gamma = ((step_two[2] - step_two[1]) / (s_up - spot)
+            - (step_two[1] - step_two[0]) / (spot - s_down))
+            / (0.5 * (s_up - s_down));
+        Ok(TreePricingResult {
+            price: values[0],
+            delta,
+            gamma,
+        })
+    }
+
+    fn trinomial(&self, option: &TreeOption, spot: f64, n: usize) -> TreeResult<TreePricingResult> {
+        let dt = option.maturity / n as f64;
+        let var = self.volatility * self.volatility;
+        let dx = (3.0 * var * dt).sqrt();
+        let nu = self.rate - self.dividend - 0.5 * var;
+        let spread = (var * dt + nu * nu * dt * dt) / (dx * dx);
+        let drift = nu * dt / dx;
+        let (pu, pd) = (0.5 * (spread + drift), 0.5 * (spread - drift));
+        let pm = 1.0 - pu - pd;
+        if pu < 0.0 || pd < 0.0 || pm < 0.0 {
+            return Err(TreeError::NegativeProbability { dt });
+        }
+        let disc = (-self.rate * dt).exp();
+        let exercisable = option.exercisable(n);
+        // Node k at step m sits at log-offset (k - m)·dx
+        let node_spot = |m: usize, k: usize| spot * ((k as f64 - m as f64) * dx).exp();
+
+        let mut values: Vec<f64> = (0..=2 * n)
+            .map(|k| option.intrinsic(node_spot(n, k)))
+            .collect();
+        let mut step_one = [0.0; 3];
+        for m in (0..n).rev() {
+            for k in 0..=2 * m {
+                let mut value = disc * (pd * values[k] + pm * values[k + 1] + pu * values[k + 2]);
+                if exercisable[m] {
+                    value = value.max(option.intrinsic(node_spot(m, k)));
+                }
+                values[k] = value;
+            }
+            if m == 1 {
+                step_one.copy_from_slice(&values[..3]);
+            }
+        }
+
+        let (s_down, s_up) = (spot * (-dx).exp(), spot * dx.exp());
+        let delta = (step_one[2] - step_one[0]) / (s_up - s_down);
+        let gamma = ((step_one[2] - step_one[1]) / (s_up - spot)
+            - (step_one[1] - step_one[0]) / (spot - s_down))
+            / (0.5 * (s_up - s_down));
+        Ok(TreePricingResult {
+            price: values[0],
+            delta,
+            gamma,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    const BS_CALL: f64 = 10.450_583_572_185_565;
+    const BS_CALL_DELTA: f64 = 0.636_830_651_175_619;
+    const BS_GAMMA: f64 = 0.018_762_017_345_847;
+    const AMERICAN_PUT: f64 = 6.090_4;
+
+    fn model() -> BlackScholesTree {
+        BlackScholesTree::new(0.05, 0.0, 0.2)
+    }
+
+    fn european_call() -> TreeOption {
+        TreeOption::new(100.0, 1.0, OptionType::Call, TreeExercise::European)
+    }
+
+    #[test]
+    fn test_european_converges_to_black_scholes() {
+        for method in [TreeMethod::Crr, TreeMethod::Trinomial] {
+            let config = TreeConfig::new(1000).with_method(method);
+            let result = model().price(&european_call(), 100.0, &config).unwrap();
+            assert_relative_eq!(result.price, BS_CALL, epsilon = 5e-3);
+            assert_relative_eq!(result.delta, BS_CALL_DELTA, epsilon = 2e-3);
+            assert_relative_eq!(result.gamma, BS_GAMMA, epsilon = 5e-4);
+        }
+    }
+
+    #[test]
+    fn test_richardson_reduces_error() {
+        for method in [TreeMethod::Crr, TreeMethod::Trinomial] {
+            let plain = TreeConfig::new(100).with_method(method);
+            let extrapolated = plain.with_richardson(true);
+            let error = |config: &TreeConfig| {
+                (model()
+                    .price(&european_call(), 100.0, config)
+                    .unwrap()
+                    .price
+                    - BS_CALL)
+                    .abs()
+            };
+            assert!(error(&extrapolated) < 0.2 * error(&plain));
+        }
+    }
+
+    #[test]
+    fn test_american_put() {
+        for method in [TreeMethod::Crr, TreeMethod::Trinomial] {
+            let config = TreeConfig::new(250)
+                .with_method(method)
+                .with_richardson(true);
+            let put = model()
+                .price(&TreeOption::american_put(100.0, 1.0), 100.0, &config)
+                .unwrap();
+            assert_relative_eq!(put.price, AMERICAN_PUT, epsilon = 3e-3);
+            assert!(put.delta < -0.4 && put.delta > -0.5);
+        }
+
+        // Without dividends an American call is never exercised early
+        let config = TreeConfig::new(500);
+        let american = model()
+            .price(&TreeOption::american_call(100.0, 1.0), 100.0, &config)
+            .unwrap();
+        let european = model().price(&european_call(), 100.0, &config).unwrap();
+        assert_relative_eq!(american.price, european.price, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_bermudan_between_european_and_american() {
+        let config = TreeConfig::new(400);
+        let price = |exercise| {
+            let option = TreeOption::new(100.0, 1.0, OptionType::Put, exercise);
+            model().price(&option, 100.0, &config).unwrap().price
+        };
+        let european = price(TreeExercise::European);
+        let bermudan = price(TreeExercise::Bermudan(vec![0.25, 0.5, 0.75, 1.0]));
+        let american = price(TreeExercise::American);
+
+        assert!(european < bermudan && bermudan < american);
+        assert_relative_eq!(
+            price(TreeExercise::Bermudan(vec![1.0])),
+            european,
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert!(matches!(
+            model().price(&european_call(), 100.0, &TreeConfig::new(1)),
+            Err(TreeError::InvalidParameter {
+                name: "n_steps",
+                ..
+            })
+        ));
+        let late = TreeOption::new(
+            100.0,
+            1.0,
+            OptionType::Put,
+            TreeExercise::Bermudan(vec![2.0]),
+        );
+        assert!(model().price(&late, 100.0, &TreeConfig::default()).is_err());
+        // Drift dominates diffusion on a coarse CRR tree
+        let drifting = BlackScholesTree::new(0.5, 0.0, 0.01);
+        assert!(matches!(
+            drifting.price(&european_call(), 100.0, &TreeConfig::new(2)),
+            Err(TreeError::NegativeProbability { .. })
+        ));
+    }
+}
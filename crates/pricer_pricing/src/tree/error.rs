@@ -0,0 +1,32 @@
+//! Error types for the tree engines.
+
+use thiserror::Error;
+
+/// Errors from lattice construction and backward induction.
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum TreeError {
+    /// Invalid model, instrument or lattice parameter.
+    #[error("Invalid parameter '{name}': {value}")]
+    InvalidParameter {
+        /// Parameter name
+        name: &'static str,
+        /// Description of the invalid value
+        value: String,
+    },
+
+    /// Branching probabilities fall outside [0, 1] for the chosen step size.
+    #[error("Negative branching probability at step size {dt}; increase n_steps")]
+    NegativeProbability {
+        /// Time step in years
+        dt: f64,
+    },
+}
+
+/// Result type for tree pricing.
+pub type TreeResult<T> = Result<T, TreeError>;
+
+impl From<crate::validate::Invalid> for TreeError {
+    fn from((name, value): crate::validate::Invalid) -> Self {
+        TreeError::InvalidParameter { name, value }
+    }
+}
@@ -0,0 +1,347 @@
+//! Hull-White trinomial short-rate tree for callable bonds.
+//!
+//! The tree follows Hull and White (1994): the Ornstein-Uhlenbeck factor
+//! `dx = -a x dt + σ dW` is discretised on a trinomial lattice with spacing
+//! `√(3V)`, branching switches to one-sided at `±j_max` to keep
+//! probabilities positive, and the drift `α(t)` at each step is fitted by
+//! forward induction so the tree reprices the supplied discount curve
+//! exactly.
+
+use super::equity::TreeConfig;
+use super::error::{TreeError, TreeResult};
+use crate::pool::with_run_arena;
+use crate::validate::positive;
+
+/// Fixed-coupon bond with an issuer call schedule.
+///
+/// # Examples
+///
+/// ```rust
+/// use pricer_pricing::tree::CallableBond;
+///
+/// let bond = CallableBond::new(100.0, 0.06, 2, 5.0).with_call(3.0, 100.0);
+/// assert_eq!(bond.coupon_times().len(), 10);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct CallableBond {
+    /// Face value
+    pub face: f64,
+    /// Annual coupon rate
+    pub coupon_rate: f64,
+    /// Coupons per year
+    pub frequency: usize,
+    /// Time to maturity in years
+    pub maturity: f64,
+    /// Call dates (years) and call prices, exercisable after the coupon
+    /// due on that date has been paid
+    pub call_schedule: Vec<(f64, f64)>,
+}
+
+impl CallableBond {
+    /// Creates a bullet bond without calls.
+    #[inline]
+    pub fn new(face: f64, coupon_rate: f64, frequency: usize, maturity: f64) -> Self {
+        Self {
+            face,
+            coupon_rate,
+            frequency,
+            maturity,
+            call_schedule: Vec::new(),
+        }
+    }
+
+    /// Adds a call date.
+    #[inline]
+    pub fn with_call(mut self, time: f64, price: f64) -> Self {
+        self.call_schedule.push((time, price));
+        self
+    }
+
+    /// Returns the coupon payment times, counted back from maturity.
+    pub fn coupon_times(&self) -> Vec<f64> {
+        let period = 1.0 / self.frequency as f64;
+        let mut times: Vec<f64> = (0..)
+            .map(|k| self.maturity - k as f64 * period)
+            .take_while(|&t| t > 1e-12)
+            .collect();
+        times.reverse();
+        times
+    }
+
+    fn validate(&self) -> TreeResult<()> {
+        positive("face", self.face)?;
+        positive("maturity", self.maturity)?;
+        if self.frequency == 0 {
+            return Err(TreeError::InvalidParameter {
+                name: "frequency",
+                value: "must be at least 1".to_string(),
+            });
+        }
+        if !(self.coupon_rate >= 0.0 && self.coupon_rate.is_finite()) {
+            return Err(TreeError::InvalidParameter {
+                name: "coupon_rate",
+                value: format!("{} must be non-negative and finite", self.coupon_rate),
+            });
+        }
+        for &(time, price) in &self.call_schedule {
+            if !(time > 0.0 && time <= self.maturity) {
+                return Err(TreeError::InvalidParameter {
+                    name: "call_date",
+                    value: format!("{} must be in (0, {}]", time, self.maturity),
+                });
+            }
+            positive("call_price", price)?;
+        }
+        Ok(())
+    }
+}
+
+/// Hull-White one-factor short-rate model on a trinomial tree.
+///
+/// # Examples
+///
+/// ```rust
+/// use pricer_pricing::tree::{CallableBond, HullWhiteTree, TreeConfig};
+///
+/// let model = HullWhiteTree::new(0.1, 0.01);
+/// let curve = |t: f64| (-0.05 * t).exp();
+/// let config = TreeConfig::new(100);
+///
+/// let straight = CallableBond::new(100.0, 0.06, 2, 5.0);
+/// let callable = straight.clone().with_call(3.0, 100.0);
+/// let p_straight = model.price_bond(&straight, curve, &config).unwrap();
+/// let p_callable = model.price_bond(&callable, curve, &config).unwrap();
+/// assert!(p_callable < p_straight);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HullWhiteTree {
+    /// Mean-reversion speed `a`
+    pub mean_reversion: f64,
+    /// Short-rate volatility `σ`
+    pub volatility: f64,
+}
+
+impl HullWhiteTree {
+    /// Creates a Hull-White model.
+    #[inline]
+    pub fn new(mean_reversion: f64, volatility: f64) -> Self {
+        Self {
+            mean_reversion,
+            volatility,
+        }
+    }
+
+    /// Prices a callable bond.
+    ///
+    /// # Arguments
+    ///
+    /// * `bond` - Bond and call schedule
+    /// * `discount` - Initial discount curve `P(0, t)`
+    /// * `config` - Lattice settings (`method` is ignored)
+    ///
+    /// # Errors
+    ///
+    /// Returns `TreeError::InvalidParameter` if the model, bond or lattice
+    /// is invalid.
+    pub fn price_bond(
+        &self,
+        bond: &CallableBond,
+        discount: impl Fn(f64) -> f64,
+        config: &TreeConfig,
+    ) -> TreeResult<f64> {
+        config.validate()?;
+        bond.validate()?;
+        positive("mean_reversion", self.mean_reversion)?;
+        positive("volatility", self.volatility)?;
+
+        config.extrapolate(|n| Ok(self.backward_induction(bond, &discount, n)))
+    }
+
+    fn backward_induction(
+        &self,
+        bond: &CallableBond,
+        discount: &impl Fn(f64) -> f64,
+        n: usize,
+    ) -> f64 {
+        let a = self.mean_reversion;
+        let dt = bond.maturity / n as f64;
+        let m = (-a * dt).exp() - 1.0;
+        let variance =
+            self.volatility * self.volatility * (1.0 - (-2.0 * a * dt).exp()) / (2.0 * a);
+        let dx = (3.0 * variance).sqrt();
+        let j_max = ((0.1835 / (a * dt)).ceil() as usize).max(1);
+        let width = 2 * j_max + 1;
+        let offset = j_max as i64;
+
+        // Branching: middle child and probabilities (down, middle, up)
+        let branches: Vec<(i64, [f64; 3])> = (-offset..=offset)
+            .map(|j| {
+                let (jm, jm2) = (j as f64 * m, (j as f64 * m).powi(2));
+                if j == offset {
+                    let p = [
+                        1.0 / 6.0 + 0.5 * (jm2 + jm),
+                        -1.0 / 3.0 - jm2 - 2.0 * jm,
+                        7.0 / 6.0 + 0.5 * (jm2 + 3.0 * jm),
+                    ];
+                    (j - 1, p)
+                } else if j == -offset {
+                    let p = [
+                        7.0 / 6.0 + 0.5 * (jm2 - 3.0 * jm),
+                        -1.0 / 3.0 - jm2 + 2.0 * jm,
+                        1.0 / 6.0 + 0.5 * (jm2 - jm),
+                    ];
+                    (j + 1, p)
+                } else {
+                    let p = [
+                        1.0 / 6.0 + 0.5 * (jm2 - jm),
+                        2.0 / 3.0 - jm2,
+                        1.0 / 6.0 + 0.5 * (jm2 + jm),
+                    ];
+                    (j, p)
+                }
+            })
+            .collect();
+        let slot = |j: i64| (j + offset) as usize;
+        let reach = |step: usize| (step as i64).min(offset);
+
+        // Lattice and cash-flow scratch comes from the thread's run arena
+        with_run_arena(|arena| {
+            // Forward induction of Arrow-Debreu prices to fit α at each step
+            let alpha = arena.alloc_slice(n);
+            let mut arrow = arena.alloc_slice(width);
+            let mut next = arena.alloc_slice(width);
+            arrow[slot(0)] = 1.0;
+            for (step, alpha_step) in alpha.iter_mut().enumerate() {
+                let r = reach(step);
+                let sum: f64 = (-r..=r)
+                    .map(|j| arrow[slot(j)] * (-(j as f64) * dx * dt).exp())
+                    .sum();
+                *alpha_step = (sum.ln() - discount((step + 1) as f64 * dt).ln()) / dt;
+                next.fill(0.0);
+                for j in -r..=r {
+                    let (centre, p) = branches[slot(j)];
+                    let flow = arrow[slot(j)] * (-(*alpha_step + j as f64 * dx) * dt).exp();
+                    for (c, prob) in p.iter().enumerate() {
+                        next[slot(centre + c as i64 - 1)] += flow * prob;
+                    }
+                }
+                std::mem::swap(&mut arrow, &mut next);
+            }
+
+            // Cash flows and call prices snapped to steps
+            let to_step = |t: f64| ((t / dt).round() as usize).min(n);
+            let cash = arena.alloc_slice(n + 1);
+            let coupon = bond.face * bond.coupon_rate / bond.frequency as f64;
+            for t in bond.coupon_times() {
+                cash[to_step(t)] += coupon;
+            }
+            let call = arena.alloc_slice(n + 1);
+            call.fill(f64::INFINITY);
+            for &(t, price) in &bond.call_schedule {
+                let step = to_step(t);
+                call[step] = call[step].min(price);
+            }
+
+            let mut values = arena.alloc_slice(width);
+            let r = reach(n);
+            for j in -r..=r {
+                values[slot(j)] = cash[n] + bond.face.min(call[n]);
+            }
+            for step in (0..n).rev() {
+                let r = reach(step);
+                next.fill(0.0);
+                for j in -r..=r {
+                    let (centre, p) = branches[slot(j)];
+                    let expected: f64 = p
+                        .iter()
+                        .enumerate()
+                        .map(|(c, prob)| prob * values[slot(centre + c as i64 - 1)])
+                        .sum();
+                    let continuation = expected * (-(alpha[step] + j as f64 * dx) * dt).exp();
+                    next[slot(j)] = cash[step] + continuation.min(call[step]);
+                }
+                std::mem::swap(&mut values, &mut next);
+            }
+            values[slot(0)]
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn curve(t: f64) -> f64 {
+        (-(0.03 + 0.005 * t) * t).exp()
+    }
+
+    #[test]
+    fn test_tree_reprices_curve() {
+        let model = HullWhiteTree::new(0.1, 0.01);
+        let config = TreeConfig::new(120);
+
+        let zero = CallableBond::new(100.0, 0.0, 1, 5.0);
+        let price = model.price_bond(&zero, curve, &config).unwrap();
+        assert_relative_eq!(price, 100.0 * curve(5.0), epsilon = 1e-9);
+
+        let bullet = CallableBond::new(100.0, 0.05, 2, 5.0);
+        let exact: f64 = bullet
+            .coupon_times()
+            .iter()
+            .map(|&t| 2.5 * curve(t))
+            .sum::<f64>()
+            + 100.0 * curve(5.0);
+        let price = model.price_bond(&bullet, curve, &config).unwrap();
+        assert_relative_eq!(price, exact, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_call_option_value() {
+        let config = TreeConfig::new(120);
+        let bond = CallableBond::new(100.0, 0.06, 2, 5.0);
+        let callable = (2..=4).fold(bond.clone(), |b, year| b.with_call(year as f64, 100.0));
+
+        let low_vol = HullWhiteTree::new(0.1, 0.005);
+        let high_vol = HullWhiteTree::new(0.1, 0.02);
+        let straight = low_vol.price_bond(&bond, curve, &config).unwrap();
+        let call_low = straight - low_vol.price_bond(&callable, curve, &config).unwrap();
+        let call_high = straight - high_vol.price_bond(&callable, curve, &config).unwrap();
+
+        assert!(call_low > 0.0);
+        assert!(call_high > call_low);
+    }
+
+    #[test]
+    fn test_richardson_is_stable() {
+        let model = HullWhiteTree::new(0.1, 0.01);
+        let bond = CallableBond::new(100.0, 0.06, 2, 5.0).with_call(2.5, 100.0);
+        let fine = model
+            .price_bond(&bond, curve, &TreeConfig::new(400))
+            .unwrap();
+        let extrapolated = model
+            .price_bond(&bond, curve, &TreeConfig::new(100).with_richardson(true))
+            .unwrap();
+        assert_relative_eq!(extrapolated, fine, epsilon = 2e-2);
+    }
+
+    #[test]
+    fn test_invalid_bond() {
+        let model = HullWhiteTree::new(0.1, 0.01);
+        let bond = CallableBond::new(100.0, 0.06, 2, 5.0).with_call(6.0, 100.0);
+        assert!(matches!(
+            model.price_bond(&bond, curve, &TreeConfig::default()),
+            Err(TreeError::InvalidParameter {
+                name: "call_date",
+                ..
+            })
+        ));
+        assert!(HullWhiteTree::new(0.0, 0.01)
+            .price_bond(
+                &CallableBond::new(100.0, 0.06, 2, 5.0),
+                curve,
+                &TreeConfig::default()
+            )
+            .is_err());
+    }
+}
@@ -0,0 +1,38 @@
+//! Lattice (tree) pricing engines.
+//!
+//! Trees price early-exercise products by backward induction, where Monte
+//! Carlo needs regression and the PDE engines need a free-boundary solve.
+//!
+//! # Engines
+//!
+//! - [`BlackScholesTree`]: Cox-Ross-Rubinstein binomial or trinomial tree
+//!   for European, American and Bermudan vanillas (see [`TreeMethod`])
+//! - [`HullWhiteTree`]: Hull-White trinomial short-rate tree fitted to the
+//!   initial discount curve, for callable bonds
+//!
+//! Both support Richardson extrapolation via [`TreeConfig::richardson`],
+//! which combines `N` and `2N` step prices to cancel the leading error term.
+//!
+//! # Example
+//!
+//! ```rust
+//! use pricer_pricing::tree::{BlackScholesTree, TreeConfig, TreeMethod, TreeOption};
+//!
+//! let model = BlackScholesTree::new(0.05, 0.0, 0.2);
+//! let config = TreeConfig::new(200)
+//!     .with_method(TreeMethod::Trinomial)
+//!     .with_richardson(true);
+//!
+//! let put = model.price(&TreeOption::american_put(100.0, 1.0), 100.0, &config).unwrap();
+//! assert!((put.price - 6.0904).abs() < 5e-3);
+//! ```
+
+mod equity;
+mod error;
+mod hull_white;
+
+pub use equity::{
+    BlackScholesTree, TreeConfig, TreeExercise, TreeMethod, TreeOption, TreePricingResult,
+};
+pub use error::{TreeError, TreeResult};
+pub use hull_white::{CallableBond, HullWhiteTree};
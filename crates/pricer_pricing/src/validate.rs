@@ -0,0 +1,93 @@
+//! Parameter checks shared by the PDE, tree and Fourier engines.
+//!
+//! Each check returns the offending parameter as `(name, description)`,
+//! which the engine error types convert into their `InvalidParameter`
+//! variant, so `?` works directly at call sites.
+
+/// A parameter that failed validation: its name and a description of why.
+pub(crate) type Invalid = (&'static str, String);
+
+/// Checks that a parameter is positive and finite.
+pub(crate) fn positive(name: &'static str, value: f64) -> Result<(), Invalid> {
+    if value > 0.0 && value.is_finite() {
+        Ok(())
+    } else {
+        Err((name, format!("{} must be positive and finite", value)))
+    }
+}
+
+/// Checks that a parameter is non-negative and finite.
+pub(crate) fn non_negative(name: &'static str, value: f64) -> Result<(), Invalid> {
+    if value >= 0.0 && value.is_finite() {
+        Ok(())
+    } else {
+        Err((name, format!("{} must be non-negative and finite", value)))
+    }
+}
+
+/// Checks that a parameter is finite.
+pub(crate) fn finite(name: &'static str, value: f64) -> Result<(), Invalid> {
+    if value.is_finite() {
+        Ok(())
+    } else {
+        Err((name, format!("{} must be finite", value)))
+    }
+}
+
+/// Checks that a correlation lies in [-1, 1].
+pub(crate) fn correlation(name: &'static str, value: f64) -> Result<(), Invalid> {
+    if (-1.0..=1.0).contains(&value) {
+        Ok(())
+    } else {
+        Err((name, format!("{} must be in [-1, 1]", value)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fourier::FourierError;
+    use crate::pde::PdeError;
+    use crate::tree::TreeError;
+
+    #[test]
+    fn test_checks() {
+        assert!(positive("x", 1.0).is_ok());
+        assert!(positive("x", 0.0).is_err());
+        assert!(positive("x", f64::INFINITY).is_err());
+        assert!(non_negative("x", 0.0).is_ok());
+        assert!(non_negative("x", -1e-12).is_err());
+        assert!(finite("x", -3.0).is_ok());
+        assert!(finite("x", f64::NAN).is_err());
+        assert!(correlation("x", -1.0).is_ok());
+        assert!(correlation("x", 1.5).is_err());
+    }
+
+    #[test]
+    fn test_maps_into_engine_errors() {
+        let invalid = positive("spot", -1.0).unwrap_err();
+        assert_eq!(invalid.0, "spot");
+        let value = invalid.1.clone();
+        assert_eq!(
+            PdeError::from(invalid.clone()),
+            PdeError::InvalidParameter {
+                name: "spot",
+                value: value.clone()
+            }
+        );
+        assert_eq!(
+            TreeError::from(invalid.clone()),
+            TreeError::InvalidParameter {
+                name: "spot",
+                value: value.clone()
+            }
+        );
+        assert_eq!(
+            FourierError::from(invalid),
+            FourierError::InvalidParameter {
+                name: "spot",
+                value
+            }
+        );
+    }
+}
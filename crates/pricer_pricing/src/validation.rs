@@ -0,0 +1,600 @@
+//! Cross-engine model validation.
+//!
+//! [`ValidationHarness`] prices every case of a [`ValidationGrid`] on each
+//! engine under test and on a reference engine (closed form by default),
+//! and compares prices, deltas and gammas against per-engine
+//! [`Tolerance`]s. The resulting [`ValidationReport`] records every
+//! deviation, summarises the maximum per engine and serialises to CSV, so
+//! it can gate engine changes in CI.
+//!
+//! Pricing goes through [`EngineRouter`], so the harness exercises the same
+//! code paths and configurations as production routing.
+//!
+//! # Example
+//!
+//! ```rust
+//! use pricer_pricing::engine::Engine;
+//! use pricer_pricing::validation::{ValidationGrid, ValidationHarness};
+//!
+//! let grid = ValidationGrid::new()
+//!     .with_strikes(vec![90.0, 110.0])
+//!     .with_expiries(vec![1.0])
+//!     .with_volatilities(vec![0.2])
+//!     .with_rates(vec![0.03]);
+//! let report = ValidationHarness::new(grid)
+//!     .with_engines(vec![Engine::Pde, Engine::Tree])
+//!     .run()
+//!     .unwrap();
+//!
+//! assert_eq!(report.results.len(), 8);
+//! assert!(report.passed(), "{}", report.to_csv());
+//! ```
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+use pricer_models::instruments::{
+    ExerciseStyle, Instrument, InstrumentParams, PayoffType, VanillaOption,
+};
+
+use crate::analytical::OptionType;
+use crate::engine::Engine;
+use crate::router::{EngineRouter, MarketInputs, RoutedPrice, RouterError, RouterResult};
+
+/// Trade identifier the harness uses to pin each engine via a router
+/// override.
+const TRADE_ID: &str = "validation";
+
+/// Smoothing width for the vanilla payoffs priced by the harness.
+const PAYOFF_EPSILON: f64 = 1e-6;
+
+/// One point of the validation grid: a European vanilla in a flat
+/// Black-Scholes market.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValidationCase {
+    /// Spot price
+    pub spot: f64,
+    /// Strike price
+    pub strike: f64,
+    /// Time to expiry in years
+    pub expiry: f64,
+    /// Volatility
+    pub volatility: f64,
+    /// Continuously compounded risk-free rate
+    pub rate: f64,
+    /// Call or put
+    pub option_type: OptionType,
+}
+
+impl ValidationCase {
+    fn instrument(&self) -> RouterResult<Instrument<f64>> {
+        let params = InstrumentParams::new(self.strike, self.expiry, 1.0)
+            .map_err(|e| RouterError::Unroutable(e.to_string()))?;
+        let payoff = match self.option_type {
+            OptionType::Call => PayoffType::Call,
+            OptionType::Put => PayoffType::Put,
+        };
+        Ok(Instrument::Vanilla(VanillaOption::new(
+            params,
+            payoff,
+            ExerciseStyle::European,
+            PAYOFF_EPSILON,
+        )))
+    }
+
+    fn market(&self) -> MarketInputs {
+        MarketInputs::new(self.spot, self.rate, self.volatility)
+    }
+}
+
+/// Cartesian parameter grid of validation cases.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValidationGrid {
+    spots: Vec<f64>,
+    strikes: Vec<f64>,
+    expiries: Vec<f64>,
+    volatilities: Vec<f64>,
+    rates: Vec<f64>,
+    option_types: Vec<OptionType>,
+}
+
+impl Default for ValidationGrid {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ValidationGrid {
+    /// Creates the default grid: spot 100, strikes 80/100/120, expiries
+    /// 0.25/1/2 years, volatilities 10%/30%, rates 0%/5%, calls and puts
+    /// (72 cases).
+    pub fn new() -> Self {
+        Self {
+            spots: vec![100.0],
+            strikes: vec![80.0, 100.0, 120.0],
+            expiries: vec![0.25, 1.0, 2.0],
+            volatilities: vec![0.1, 0.3],
+            rates: vec![0.0, 0.05],
+            option_types: vec![OptionType::Call, OptionType::Put],
+        }
+    }
+
+    /// Sets the spot prices.
+    #[inline]
+    pub fn with_spots(mut self, spots: Vec<f64>) -> Self {
+        self.spots = spots;
+        self
+    }
+
+    /// Sets the strikes.
+    #[inline]
+    pub fn with_strikes(mut self, strikes: Vec<f64>) -> Self {
+        self.strikes = strikes;
+        self
+    }
+
+    /// Sets the expiries in years.
+    #[inline]
+    pub fn with_expiries(mut self, expiries: Vec<f64>) -> Self {
+        self.expiries = expiries;
+        self
+    }
+
+    /// Sets the volatilities.
+    #[inline]
+    pub fn with_volatilities(mut self, volatilities: Vec<f64>) -> Self {
+        self.volatilities = volatilities;
+        self
+    }
+
+    /// Sets the risk-free rates.
+    #[inline]
+    pub fn with_rates(mut self, rates: Vec<f64>) -> Self {
+        self.rates = rates;
+        self
+    }
+
+    /// Sets the option types.
+    #[inline]
+    pub fn with_option_types(mut self, option_types: Vec<OptionType>) -> Self {
+        self.option_types = option_types;
+        self
+    }
+
+    /// Returns every combination of the grid axes.
+    pub fn cases(&self) -> Vec<ValidationCase> {
+        let mut cases = Vec::new();
+        for &spot in &self.spots {
+            for &strike in &self.strikes {
+                for &expiry in &self.expiries {
+                    for &volatility in &self.volatilities {
+                        for &rate in &self.rates {
+                            for &option_type in &self.option_types {
+                                cases.push(ValidationCase {
+                                    spot,
+                                    strike,
+                                    expiry,
+                                    volatility,
+                                    rate,
+                                    option_type,
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        cases
+    }
+}
+
+/// Absolute deviation limits for one engine.
+///
+/// For engines that report a standard error the price limit widens to
+/// `price + std_errors × std_error`, so Monte Carlo noise alone does not
+/// fail a case.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tolerance {
+    /// Maximum absolute price error
+    pub price: f64,
+    /// Maximum absolute delta error
+    pub delta: f64,
+    /// Maximum absolute gamma error
+    pub gamma: f64,
+    /// Standard errors added to the price limit
+    pub std_errors: f64,
+}
+
+impl Tolerance {
+    /// Creates a tolerance without a standard-error allowance.
+    #[inline]
+    pub fn new(price: f64, delta: f64, gamma: f64) -> Self {
+        Self {
+            price,
+            delta,
+            gamma,
+            std_errors: 0.0,
+        }
+    }
+
+    /// Sets the number of standard errors added to the price limit.
+    #[inline]
+    pub fn with_std_errors(mut self, std_errors: f64) -> Self {
+        self.std_errors = std_errors;
+        self
+    }
+
+    /// Returns the default tolerance for `engine` under the router's
+    /// default settings.
+    pub fn default_for(engine: Engine) -> Self {
+        match engine {
+            Engine::Analytic => Self::new(1e-10, 1e-10, 1e-10),
+            Engine::MonteCarlo => Self::new(2e-2, 2e-2, 5e-3).with_std_errors(4.0),
+            Engine::Pde => Self::new(2e-2, 2e-3, 5e-4),
+            Engine::Tree => Self::new(2e-2, 5e-3, 1e-3),
+        }
+    }
+}
+
+/// Comparison of one engine against the reference on one case.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CaseResult {
+    /// Validated case
+    pub case: ValidationCase,
+    /// Engine under test
+    pub engine: Engine,
+    /// Reference price
+    pub reference_price: f64,
+    /// Engine price
+    pub price: f64,
+    /// Absolute price error
+    pub price_error: f64,
+    /// Absolute delta error, if both engines report delta
+    pub delta_error: Option<f64>,
+    /// Absolute gamma error, if both engines report gamma
+    pub gamma_error: Option<f64>,
+    /// Engine standard error, if stochastic
+    pub std_error: Option<f64>,
+    /// Whether every error is within tolerance
+    pub passed: bool,
+}
+
+/// Maximum deviations of one engine over the grid.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EngineSummary {
+    /// Engine under test
+    pub engine: Engine,
+    /// Number of cases compared
+    pub cases: usize,
+    /// Number of cases outside tolerance
+    pub failures: usize,
+    /// Maximum absolute price error
+    pub max_price_error: f64,
+    /// Maximum absolute delta error
+    pub max_delta_error: f64,
+    /// Maximum absolute gamma error
+    pub max_gamma_error: f64,
+}
+
+/// Outcome of a validation run.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValidationReport {
+    /// Engine the others were compared against
+    pub reference: Engine,
+    /// Per-case comparisons, grouped by engine in harness order
+    pub results: Vec<CaseResult>,
+}
+
+impl ValidationReport {
+    /// Returns whether every case passed.
+    pub fn passed(&self) -> bool {
+        self.results.iter().all(|r| r.passed)
+    }
+
+    /// Returns the cases outside tolerance.
+    pub fn failures(&self) -> Vec<&CaseResult> {
+        self.results.iter().filter(|r| !r.passed).collect()
+    }
+
+    /// Summarises the maximum deviations of `engine`, or `None` if it was
+    /// not validated.
+    pub fn summary(&self, engine: Engine) -> Option<EngineSummary> {
+        let results: Vec<&CaseResult> =
+            self.results.iter().filter(|r| r.engine == engine).collect();
+        if results.is_empty() {
+            return None;
+        }
+        let max = |f: fn(&CaseResult) -> Option<f64>| {
+            results.iter().filter_map(|r| f(r)).fold(0.0, f64::max)
+        };
+        Some(EngineSummary {
+            engine,
+            cases: results.len(),
+            failures: results.iter().filter(|r| !r.passed).count(),
+            max_price_error: max(|r| Some(r.price_error)),
+            max_delta_error: max(|r| r.delta_error),
+            max_gamma_error: max(|r| r.gamma_error),
+        })
+    }
+
+    /// Returns one summary per validated engine, in harness order.
+    pub fn summaries(&self) -> Vec<EngineSummary> {
+        let mut engines: Vec<Engine> = Vec::new();
+        for result in &self.results {
+            if !engines.contains(&result.engine) {
+                engines.push(result.engine);
+            }
+        }
+        engines
+            .into_iter()
+            .filter_map(|engine| self.summary(engine))
+            .collect()
+    }
+
+    /// Renders the per-case results as CSV with a header row. Missing
+    /// Greeks and standard errors are left empty.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "engine,option_type,spot,strike,expiry,volatility,rate,reference_price,price,\
+             price_error,delta_error,gamma_error,std_error,passed\n",
+        );
+        let opt = |value: Option<f64>| value.map(|v| v.to_string()).unwrap_or_default();
+        for r in &self.results {
+            let c = &r.case;
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+                r.engine,
+                match c.option_type {
+                    OptionType::Call => "call",
+                    OptionType::Put => "put",
+                },
+                c.spot,
+                c.strike,
+                c.expiry,
+                c.volatility,
+                c.rate,
+                r.reference_price,
+                r.price,
+                r.price_error,
+                opt(r.delta_error),
+                opt(r.gamma_error),
+                opt(r.std_error),
+                r.passed,
+            );
+        }
+        csv
+    }
+}
+
+/// Prices a [`ValidationGrid`] on several engines and compares them with a
+/// reference engine.
+#[derive(Clone, Debug)]
+pub struct ValidationHarness {
+    grid: ValidationGrid,
+    router: EngineRouter,
+    reference: Engine,
+    engines: Vec<Engine>,
+    tolerances: HashMap<Engine, Tolerance>,
+}
+
+impl ValidationHarness {
+    /// Creates a harness validating Monte Carlo, PDE and tree engines
+    /// against closed form on the default router settings.
+    pub fn new(grid: ValidationGrid) -> Self {
+        Self {
+            grid,
+            router: EngineRouter::new(),
+            reference: Engine::Analytic,
+            engines: vec![Engine::MonteCarlo, Engine::Pde, Engine::Tree],
+            tolerances: HashMap::new(),
+        }
+    }
+
+    /// Sets the router whose engine configurations are validated.
+    ///
+    /// Trade overrides on the router are ignored; the harness pins each
+    /// engine itself.
+    #[inline]
+    pub fn with_router(mut self, router: EngineRouter) -> Self {
+        self.router = router;
+        self
+    }
+
+    /// Sets the reference engine.
+    #[inline]
+    pub fn with_reference(mut self, reference: Engine) -> Self {
+        self.reference = reference;
+        self
+    }
+
+    /// Sets the engines under test.
+    #[inline]
+    pub fn with_engines(mut self, engines: Vec<Engine>) -> Self {
+        self.engines = engines;
+        self
+    }
+
+    /// Overrides the tolerance for `engine`.
+    #[inline]
+    pub fn with_tolerance(mut self, engine: Engine, tolerance: Tolerance) -> Self {
+        self.tolerances.insert(engine, tolerance);
+        self
+    }
+
+    /// Returns the tolerance applied to `engine`.
+    pub fn tolerance(&self, engine: Engine) -> Tolerance {
+        self.tolerances
+            .get(&engine)
+            .copied()
+            .unwrap_or_else(|| Tolerance::default_for(engine))
+    }
+
+    /// Runs the validation.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RouterError` if a case is invalid or an engine fails to
+    /// price it. Deviations outside tolerance are reported in the
+    /// [`ValidationReport`], not as errors.
+    pub fn run(&self) -> RouterResult<ValidationReport> {
+        let cases = self.grid.cases();
+        let reference_router = self
+            .router
+            .clone()
+            .with_trade_override(TRADE_ID, self.reference);
+        let references = cases
+            .iter()
+            .map(|case| reference_router.price(Some(TRADE_ID), &case.instrument()?, &case.market()))
+            .collect::<RouterResult<Vec<RoutedPrice>>>()?;
+
+        let mut results = Vec::with_capacity(cases.len() * self.engines.len());
+        for &engine in &self.engines {
+            let router = self.router.clone().with_trade_override(TRADE_ID, engine);
+            let tolerance = self.tolerance(engine);
+            for (case, reference) in cases.iter().zip(&references) {
+                let priced = router.price(Some(TRADE_ID), &case.instrument()?, &case.market())?;
+                results.push(compare(*case, engine, reference, &priced, &tolerance));
+            }
+        }
+        Ok(ValidationReport {
+            reference: self.reference,
+            results,
+        })
+    }
+}
+
+fn compare(
+    case: ValidationCase,
+    engine: Engine,
+    reference: &RoutedPrice,
+    priced: &RoutedPrice,
+    tolerance: &Tolerance,
+) -> CaseResult {
+    let diff = |a: Option<f64>, b: Option<f64>| Some((a? - b?).abs());
+    let price_error = (priced.price - reference.price).abs();
+    let delta_error = diff(priced.delta, reference.delta);
+    let gamma_error = diff(priced.gamma, reference.gamma);
+    let price_limit = tolerance.price + tolerance.std_errors * priced.std_error.unwrap_or(0.0);
+    // NaN errors compare false and therefore fail
+    let within = |error: Option<f64>, limit: f64| error.is_none_or(|e| e <= limit);
+    let passed = price_error <= price_limit
+        && within(delta_error, tolerance.delta)
+        && within(gamma_error, tolerance.gamma);
+    CaseResult {
+        case,
+        engine,
+        reference_price: reference.price,
+        price: priced.price,
+        price_error,
+        delta_error,
+        gamma_error,
+        std_error: priced.std_error,
+        passed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mc::MonteCarloConfig;
+
+    fn small_grid() -> ValidationGrid {
+        ValidationGrid::new()
+            .with_strikes(vec![90.0, 100.0, 110.0])
+            .with_expiries(vec![0.5, 1.0])
+            .with_volatilities(vec![0.25])
+            .with_rates(vec![0.03])
+    }
+
+    #[test]
+    fn test_default_grid_size() {
+        let cases = ValidationGrid::new().cases();
+        assert_eq!(cases.len(), 3 * 3 * 2 * 2 * 2);
+        assert!(cases.iter().any(|c| c.option_type == OptionType::Put));
+    }
+
+    #[test]
+    fn test_pde_and_tree_pass_default_tolerances() {
+        let report = ValidationHarness::new(small_grid())
+            .with_engines(vec![Engine::Pde, Engine::Tree])
+            .run()
+            .unwrap();
+        assert!(report.passed(), "{}", report.to_csv());
+        for engine in [Engine::Pde, Engine::Tree] {
+            let summary = report.summary(engine).unwrap();
+            assert_eq!(summary.cases, 12);
+            assert_eq!(summary.failures, 0);
+            assert!(summary.max_delta_error > 0.0);
+        }
+        assert!(report.summary(Engine::MonteCarlo).is_none());
+    }
+
+    #[test]
+    fn test_monte_carlo_within_standard_errors() {
+        let config = MonteCarloConfig::builder()
+            .n_paths(20_000)
+            .n_steps(1)
+            .seed(7)
+            .build()
+            .unwrap();
+        let report = ValidationHarness::new(small_grid())
+            .with_router(EngineRouter::new().with_mc_config(config))
+            .with_engines(vec![Engine::MonteCarlo])
+            .run()
+            .unwrap();
+        assert!(report.passed(), "{}", report.to_csv());
+        assert!(report.results.iter().all(|r| r.std_error.is_some()));
+    }
+
+    #[test]
+    fn test_tight_tolerance_reports_failures() {
+        let report = ValidationHarness::new(small_grid())
+            .with_engines(vec![Engine::Tree])
+            .with_tolerance(Engine::Tree, Tolerance::new(1e-12, 1e-12, 1e-12))
+            .run()
+            .unwrap();
+        assert!(!report.passed());
+        assert_eq!(
+            report.failures().len(),
+            report.summary(Engine::Tree).unwrap().failures
+        );
+        assert!(!report.failures().is_empty());
+    }
+
+    #[test]
+    fn test_csv_layout() {
+        let report = ValidationHarness::new(
+            small_grid()
+                .with_strikes(vec![100.0])
+                .with_expiries(vec![1.0]),
+        )
+        .with_engines(vec![Engine::Pde])
+        .run()
+        .unwrap();
+        let csv = report.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        let columns = lines[0].split(',').count();
+        assert!(lines.iter().all(|l| l.split(',').count() == columns));
+        assert!(lines[1].starts_with("pde,call,100,100,1,0.25,0.03,"));
+        assert_eq!(report.summaries().len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_case_is_error() {
+        let harness = ValidationHarness::new(small_grid().with_volatilities(vec![-0.2]));
+        assert!(matches!(
+            harness.run(),
+            Err(RouterError::InvalidMarket {
+                name: "volatility",
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,178 @@
+//! Strong-scaling benchmark of the portfolio pricing service.
+//!
+//! Prices a 100k-trade portfolio of American options, spread over 1,000
+//! netting sets of skewed sizes, on 1 to 32 threads and reports the
+//! parallel efficiency `T(1) / (n · T(n))`. The run fails if efficiency
+//! drops below 80% at any thread count.
+//!
+//! Thread counts above the available cores are skipped, so the full
+//! 32-core figure requires a 32-core machine:
+//!
+//! ```text
+//! cargo bench -p pricer_risk --bench pricing_service_scaling
+//! ```
+//!
+//! `NEUTRYX_SCALING_TRADES` overrides the portfolio size.
+
+use std::process::ExitCode;
+use std::time::Duration;
+
+use pricer_core::types::Currency;
+use pricer_models::instruments::{
+    ExerciseStyle, Instrument, InstrumentParams, PayoffType, VanillaOption,
+};
+use pricer_risk::parallel::{PinningPolicy, PricingService, PricingServiceConfig};
+use pricer_risk::portfolio::{
+    Counterparty, CounterpartyId, CreditParams, NettingSet, NettingSetId, Portfolio,
+    PortfolioBuilder, Trade, TradeId,
+};
+
+const DEFAULT_TRADES: usize = 100_000;
+const NETTING_SETS: usize = 1_000;
+const THREAD_COUNTS: [usize; 6] = [1, 2, 4, 8, 16, 32];
+const REPETITIONS: usize = 3;
+const TARGET_EFFICIENCY: f64 = 0.8;
+const TREE_STEPS: usize = 100;
+
+/// Portfolio with netting set sizes decaying geometrically, so a few large
+/// netting sets dominate as in a real book.
+fn portfolio(n_trades: usize) -> Portfolio {
+    let mut builder = PortfolioBuilder::new();
+    let weights: Vec<f64> = (0..NETTING_SETS)
+        .map(|i| 0.995_f64.powi(i as i32))
+        .collect();
+    let total: f64 = weights.iter().sum();
+
+    let mut assigned = 0;
+    for (n, weight) in weights.iter().enumerate() {
+        let cp = CounterpartyId::new(format!("CP{}", n));
+        let ns = NettingSetId::new(format!("NS{}", n));
+        builder = builder
+            .add_counterparty(Counterparty::new(
+                cp.clone(),
+                CreditParams::new(0.01, 0.6).unwrap(),
+            ))
+            .add_netting_set(NettingSet::new(ns.clone(), cp.clone()));
+
+        let size = if n + 1 == NETTING_SETS {
+            n_trades - assigned
+        } else {
+            ((weight / total * n_trades as f64) as usize).min(n_trades - assigned)
+        };
+        for i in 0..size {
+            let strike = 80.0 + (assigned + i) as f64 % 40.0;
+            let expiry = 0.25 + ((assigned + i) % 20) as f64 * 0.25;
+            let params = InstrumentParams::new(100.0, strike, expiry).unwrap();
+            let payoff = if i % 2 == 0 {
+                PayoffType::Put
+            } else {
+                PayoffType::Call
+            };
+            let option = VanillaOption::new(params, payoff, ExerciseStyle::American, 1e-6);
+            builder = builder.add_trade(Trade::new(
+                TradeId::new(format!("T{}", assigned + i)),
+                Instrument::Vanilla(option),
+                Currency::USD,
+                cp.clone(),
+                ns.clone(),
+                1.0,
+            ));
+        }
+        assigned += size;
+    }
+    builder.build().unwrap()
+}
+
+/// Cox-Ross-Rubinstein American option price.
+fn price(trade: &Trade) -> f64 {
+    let (spot, rate, vol) = (100.0, 0.03, 0.2);
+    let strike = trade.strike().unwrap_or(spot);
+    let is_call = trade.payoff_type() == Some(PayoffType::Call);
+    let dt = trade.expiry() / TREE_STEPS as f64;
+    let u = (vol * dt.sqrt()).exp();
+    let d = 1.0 / u;
+    let disc = (-rate * dt).exp();
+    let p = ((rate * dt).exp() - d) / (u - d);
+    let payoff = |s: f64| {
+        if is_call {
+            (s - strike).max(0.0)
+        } else {
+            (strike - s).max(0.0)
+        }
+    };
+
+    let mut values: Vec<f64> = (0..=TREE_STEPS)
+        .map(|j| payoff(spot * u.powi(j as i32) * d.powi((TREE_STEPS - j) as i32)))
+        .collect();
+    for step in (0..TREE_STEPS).rev() {
+        for j in 0..=step {
+            let continuation = disc * (p * values[j + 1] + (1.0 - p) * values[j]);
+            let s = spot * u.powi(j as i32) * d.powi((step - j) as i32);
+            values[j] = continuation.max(payoff(s));
+        }
+    }
+    values[0] * trade.notional()
+}
+
+fn time(service: &PricingService, portfolio: &Portfolio) -> Duration {
+    (0..REPETITIONS)
+        .map(|_| service.price(portfolio, price).stats.elapsed)
+        .min()
+        .unwrap_or_default()
+}
+
+fn main() -> ExitCode {
+    let n_trades = std::env::var("NEUTRYX_SCALING_TRADES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_TRADES);
+    let cores = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let portfolio = portfolio(n_trades);
+
+    println!(
+        "Pricing service scaling: {} trades, {} netting sets, {} cores",
+        portfolio.trade_count(),
+        portfolio.netting_set_count(),
+        cores
+    );
+    println!(
+        "{:>8} {:>12} {:>10} {:>10}",
+        "threads", "time (s)", "speed-up", "efficiency"
+    );
+
+    let mut baseline = None;
+    let mut passed = true;
+    for threads in THREAD_COUNTS.into_iter().filter(|&n| n <= cores) {
+        let service = PricingService::new(
+            PricingServiceConfig::new()
+                .with_threads(threads)
+                .with_pinning(PinningPolicy::Scatter),
+        )
+        .unwrap();
+        // Warm up the pool and caches
+        service.price(&portfolio, price);
+
+        let elapsed = time(&service, &portfolio).as_secs_f64();
+        let t1 = *baseline.get_or_insert(elapsed);
+        let speedup = t1 / elapsed;
+        let efficiency = speedup / threads as f64;
+        passed &= efficiency >= TARGET_EFFICIENCY;
+        println!(
+            "{:>8} {:>12.3} {:>10.2} {:>9.1}%",
+            threads,
+            elapsed,
+            speedup,
+            efficiency * 100.0
+        );
+    }
+
+    if passed {
+        ExitCode::SUCCESS
+    } else {
+        eprintln!(
+            "Parallel efficiency below {:.0}%",
+            TARGET_EFFICIENCY * 100.0
+        );
+        ExitCode::FAILURE
+    }
+}
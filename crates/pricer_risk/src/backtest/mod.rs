@@ -0,0 +1,369 @@
+//! Backtesting of VaR and PFE models.
+//!
+//! A [`Backtester`] compares forecasts with what was realised over a
+//! historical window, one observation per date:
+//!
+//! - VaR: an exception is a loss (negative P&L) beyond the forecast VaR
+//! - PFE: an exception is a realised exposure above the forecast PFE
+//!
+//! At confidence `c`, exceptions should occur independently at rate
+//! `1 - c`. The [`BacktestReport`] tests this with:
+//!
+//! - Kupiec proportion of failures: is the exception rate right?
+//! - Christoffersen independence: do exceptions cluster?
+//! - Christoffersen conditional coverage: both jointly
+//!
+//! and classifies the exception count into the Basel [`TrafficLight`]
+//! zones, which for 250 days at 99% are green up to 4 exceptions, yellow
+//! from 5 to 9 and red from 10.
+//!
+//! # Examples
+//!
+//! ```
+//! use pricer_risk::backtest::{Backtester, TrafficLight};
+//!
+//! let var = vec![1_000_000.0; 250];
+//! let mut pnl = vec![250_000.0; 250];
+//! for day in [20, 90, 160] {
+//!     pnl[day] = -1_500_000.0;
+//! }
+//!
+//! let report = Backtester::new(0.99).unwrap().var(&var, &pnl).unwrap();
+//! assert_eq!(report.exception_count(), 3);
+//! assert_eq!(report.traffic_light, TrafficLight::Green);
+//! assert!(!report.kupiec.rejected);
+//! ```
+
+mod statistics;
+
+use std::fmt;
+
+use thiserror::Error;
+
+/// Basel backtesting window in business days.
+pub const BASEL_WINDOW: usize = 250;
+
+/// Errors from backtesting.
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum BacktestError {
+    /// Confidence level or significance is outside `(0, 1)`.
+    #[error("Invalid level: {0}")]
+    InvalidLevel(f64),
+
+    /// Forecast and realised series differ in length.
+    #[error("{forecasts} forecasts but {realised} realised values")]
+    LengthMismatch {
+        /// Number of forecasts
+        forecasts: usize,
+        /// Number of realised values
+        realised: usize,
+    },
+
+    /// The window has no observations.
+    #[error("Backtest window is empty")]
+    EmptyWindow,
+
+    /// A forecast or realised value is not finite.
+    #[error("Non-finite value at observation {0}")]
+    NonFinite(usize),
+}
+
+/// Result type for backtesting.
+pub type BacktestResult<T> = Result<T, BacktestError>;
+
+/// Model being backtested.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BacktestKind {
+    /// Value at risk against realised P&L
+    Var,
+    /// Potential future exposure against realised exposure
+    Pfe,
+}
+
+impl fmt::Display for BacktestKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            BacktestKind::Var => "VaR",
+            BacktestKind::Pfe => "PFE",
+        })
+    }
+}
+
+/// Basel traffic-light zone.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TrafficLight {
+    /// Model acceptable
+    Green,
+    /// Model questionable
+    Yellow,
+    /// Model rejected
+    Red,
+}
+
+impl fmt::Display for TrafficLight {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            TrafficLight::Green => "green",
+            TrafficLight::Yellow => "yellow",
+            TrafficLight::Red => "red",
+        })
+    }
+}
+
+/// Outcome of a likelihood-ratio test.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CoverageTest {
+    /// Likelihood-ratio statistic
+    pub statistic: f64,
+    /// Chi-squared p-value
+    pub p_value: f64,
+    /// Whether the model is rejected at the backtester's significance
+    pub rejected: bool,
+}
+
+/// Compares forecasts with realised values.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Backtester {
+    confidence: f64,
+    significance: f64,
+    window: Option<usize>,
+}
+
+impl Backtester {
+    /// Creates a backtester for forecasts at `confidence` (e.g. 0.99),
+    /// testing at 5% significance over the full series.
+    pub fn new(confidence: f64) -> BacktestResult<Self> {
+        Ok(Self {
+            confidence: unit_level(confidence)?,
+            significance: 0.05,
+            window: None,
+        })
+    }
+
+    /// Sets the significance of the coverage tests.
+    pub fn with_significance(mut self, significance: f64) -> BacktestResult<Self> {
+        self.significance = unit_level(significance)?;
+        Ok(self)
+    }
+
+    /// Restricts the backtest to the most recent `window` observations,
+    /// e.g. [`BASEL_WINDOW`].
+    pub fn with_window(mut self, window: usize) -> Self {
+        self.window = Some(window);
+        self
+    }
+
+    /// Backtests VaR forecasts, quoted as positive losses, against
+    /// realised P&L.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the series differ in length, the window is empty, or a
+    /// value is not finite.
+    pub fn var(&self, var: &[f64], pnl: &[f64]) -> BacktestResult<BacktestReport> {
+        self.run(BacktestKind::Var, var, pnl, |var, pnl| -pnl > var)
+    }
+
+    /// Backtests PFE forecasts against realised exposures.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the series differ in length, the window is empty, or a
+    /// value is not finite.
+    pub fn pfe(&self, pfe: &[f64], exposures: &[f64]) -> BacktestResult<BacktestReport> {
+        self.run(BacktestKind::Pfe, pfe, exposures, |pfe, exposure| {
+            exposure > pfe
+        })
+    }
+
+    fn run<F>(
+        &self,
+        kind: BacktestKind,
+        forecasts: &[f64],
+        realised: &[f64],
+        is_exception: F,
+    ) -> BacktestResult<BacktestReport>
+    where
+        F: Fn(f64, f64) -> bool,
+    {
+        if forecasts.len() != realised.len() {
+            return Err(BacktestError::LengthMismatch {
+                forecasts: forecasts.len(),
+                realised: realised.len(),
+            });
+        }
+        let start = self
+            .window
+            .map_or(0, |window| forecasts.len().saturating_sub(window));
+        if start == forecasts.len() {
+            return Err(BacktestError::EmptyWindow);
+        }
+
+        let mut exceptions = Vec::with_capacity(forecasts.len() - start);
+        for i in start..forecasts.len() {
+            if !forecasts[i].is_finite() || !realised[i].is_finite() {
+                return Err(BacktestError::NonFinite(i));
+            }
+            exceptions.push(is_exception(forecasts[i], realised[i]));
+        }
+
+        let rate = 1.0 - self.confidence;
+        let kupiec = statistics::kupiec(&exceptions, rate, self.significance);
+        let independence = statistics::christoffersen(&exceptions, self.significance);
+        let conditional_coverage =
+            statistics::conditional_coverage(&kupiec, &independence, self.significance);
+        let count = exceptions.iter().filter(|&&e| e).count();
+
+        Ok(BacktestReport {
+            kind,
+            confidence: self.confidence,
+            observations: exceptions.len(),
+            exceptions: exceptions
+                .iter()
+                .enumerate()
+                .filter(|(_, &e)| e)
+                .map(|(i, _)| start + i)
+                .collect(),
+            kupiec,
+            independence,
+            conditional_coverage,
+            traffic_light: statistics::traffic_light(count, exceptions.len(), rate),
+        })
+    }
+}
+
+/// Validates a level in `(0, 1)`.
+fn unit_level(level: f64) -> BacktestResult<f64> {
+    if level > 0.0 && level < 1.0 {
+        Ok(level)
+    } else {
+        Err(BacktestError::InvalidLevel(level))
+    }
+}
+
+/// Result of backtesting one forecast series.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BacktestReport {
+    /// Model backtested
+    pub kind: BacktestKind,
+    /// Forecast confidence level
+    pub confidence: f64,
+    /// Observations in the window
+    pub observations: usize,
+    /// Indices of the exceptions in the input series
+    pub exceptions: Vec<usize>,
+    /// Kupiec proportion-of-failures test
+    pub kupiec: CoverageTest,
+    /// Christoffersen independence test
+    pub independence: CoverageTest,
+    /// Christoffersen conditional coverage test
+    pub conditional_coverage: CoverageTest,
+    /// Basel traffic-light zone
+    pub traffic_light: TrafficLight,
+}
+
+impl BacktestReport {
+    /// Number of exceptions.
+    pub fn exception_count(&self) -> usize {
+        self.exceptions.len()
+    }
+
+    /// Realised exception rate.
+    pub fn exception_rate(&self) -> f64 {
+        self.exceptions.len() as f64 / self.observations as f64
+    }
+
+    /// Exceptions expected at the forecast confidence.
+    pub fn expected_exceptions(&self) -> f64 {
+        (1.0 - self.confidence) * self.observations as f64
+    }
+}
+
+impl fmt::Display for BacktestReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} backtest at {:.1}%: {} exceptions in {} observations ({:.1} expected), {}",
+            self.kind,
+            self.confidence * 100.0,
+            self.exception_count(),
+            self.observations,
+            self.expected_exceptions(),
+            self.traffic_light
+        )?;
+        for (name, test) in [
+            ("Kupiec", &self.kupiec),
+            ("Independence", &self.independence),
+            ("Conditional coverage", &self.conditional_coverage),
+        ] {
+            writeln!(
+                f,
+                "  {:<22} LR {:>8.3}  p {:.4}  {}",
+                name,
+                test.statistic,
+                test.p_value,
+                if test.rejected { "reject" } else { "accept" }
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_uses_latest_observations() {
+        // Ten early exceptions fall outside the Basel window
+        let pfe = vec![100.0; 300];
+        let mut exposure = vec![50.0; 300];
+        exposure[..10].iter_mut().for_each(|e| *e = 150.0);
+        exposure[299] = 150.0;
+
+        let full = Backtester::new(0.99).unwrap().pfe(&pfe, &exposure).unwrap();
+        assert_eq!(full.exception_count(), 11);
+        assert_eq!(full.traffic_light, TrafficLight::Red);
+
+        let basel = Backtester::new(0.99)
+            .unwrap()
+            .with_window(BASEL_WINDOW)
+            .pfe(&pfe, &exposure)
+            .unwrap();
+        assert_eq!(basel.observations, BASEL_WINDOW);
+        assert_eq!(basel.exceptions, vec![299]);
+        assert_eq!(basel.traffic_light, TrafficLight::Green);
+    }
+
+    #[test]
+    fn test_var_exceptions_are_losses_beyond_var() {
+        let report = Backtester::new(0.95)
+            .unwrap()
+            .var(&[10.0, 10.0, 10.0], &[-11.0, 11.0, -10.0])
+            .unwrap();
+        assert_eq!(report.exceptions, vec![0]);
+        assert!(report.to_string().contains("VaR backtest at 95.0%"));
+    }
+
+    #[test]
+    fn test_invalid_inputs() {
+        assert_eq!(Backtester::new(1.0), Err(BacktestError::InvalidLevel(1.0)));
+        let backtester = Backtester::new(0.99).unwrap();
+        assert_eq!(
+            backtester.var(&[1.0], &[]),
+            Err(BacktestError::LengthMismatch {
+                forecasts: 1,
+                realised: 0
+            })
+        );
+        assert_eq!(backtester.var(&[], &[]), Err(BacktestError::EmptyWindow));
+        assert_eq!(
+            backtester.pfe(&[1.0, f64::NAN], &[0.0, 0.0]),
+            Err(BacktestError::NonFinite(1))
+        );
+    }
+}
@@ -0,0 +1,169 @@
+//! Likelihood-ratio coverage tests and the Basel traffic light.
+
+use pricer_models::analytical::distributions::norm_cdf;
+
+use super::{CoverageTest, TrafficLight};
+
+/// Cumulative binomial probability at which the yellow zone starts.
+const YELLOW_THRESHOLD: f64 = 0.95;
+/// Cumulative binomial probability at which the red zone starts.
+const RED_THRESHOLD: f64 = 0.9999;
+
+/// `x ln p`, taken as zero when `x` is zero.
+fn xlogp(x: f64, p: f64) -> f64 {
+    if x == 0.0 {
+        0.0
+    } else {
+        x * p.ln()
+    }
+}
+
+/// Bernoulli log-likelihood of `hits` in `n` trials with probability `p`.
+fn log_likelihood(n: f64, hits: f64, p: f64) -> f64 {
+    xlogp(n - hits, 1.0 - p) + xlogp(hits, p)
+}
+
+/// Upper tail of the chi-squared distribution with one or two degrees of
+/// freedom.
+fn chi_squared_sf(x: f64, dof: u32) -> f64 {
+    let x = x.max(0.0);
+    match dof {
+        1 => 2.0 * (1.0 - norm_cdf(x.sqrt())),
+        _ => (-0.5 * x).exp(),
+    }
+}
+
+fn test(statistic: f64, dof: u32, significance: f64) -> CoverageTest {
+    // Rounding can leave a tiny negative statistic at a perfect fit
+    let statistic = statistic.max(0.0);
+    let p_value = chi_squared_sf(statistic, dof);
+    CoverageTest {
+        statistic,
+        p_value,
+        rejected: p_value < significance,
+    }
+}
+
+/// Kupiec proportion-of-failures test: is the exception rate `p`?
+pub(crate) fn kupiec(exceptions: &[bool], p: f64, significance: f64) -> CoverageTest {
+    let n = exceptions.len() as f64;
+    let x = exceptions.iter().filter(|&&e| e).count() as f64;
+    let statistic = -2.0 * (log_likelihood(n, x, p) - log_likelihood(n, x, x / n));
+    test(statistic, 1, significance)
+}
+
+/// Christoffersen independence test: do exceptions cluster?
+///
+/// Compares a first-order Markov chain of exceptions against independent
+/// draws with the same rate.
+pub(crate) fn christoffersen(exceptions: &[bool], significance: f64) -> CoverageTest {
+    // counts[i][j]: transitions from state i to state j
+    let mut counts = [[0.0_f64; 2]; 2];
+    for pair in exceptions.windows(2) {
+        counts[pair[0] as usize][pair[1] as usize] += 1.0;
+    }
+    let [[n00, n01], [n10, n11]] = counts;
+    let rate = |stay: f64, hit: f64| {
+        if stay + hit > 0.0 {
+            hit / (stay + hit)
+        } else {
+            0.0
+        }
+    };
+    let pi = rate(n00 + n10, n01 + n11);
+    let restricted = log_likelihood(n00 + n01 + n10 + n11, n01 + n11, pi);
+    let unrestricted = log_likelihood(n00 + n01, n01, rate(n00, n01))
+        + log_likelihood(n10 + n11, n11, rate(n10, n11));
+    test(-2.0 * (restricted - unrestricted), 1, significance)
+}
+
+/// Christoffersen conditional coverage test: the sum of the Kupiec and
+/// independence statistics, with two degrees of freedom.
+pub(crate) fn conditional_coverage(
+    unconditional: &CoverageTest,
+    independence: &CoverageTest,
+    significance: f64,
+) -> CoverageTest {
+    test(
+        unconditional.statistic + independence.statistic,
+        2,
+        significance,
+    )
+}
+
+/// Probability of at most `k` exceptions in `n` observations at rate `p`.
+pub(crate) fn binomial_cdf(k: usize, n: usize, p: f64) -> f64 {
+    if p <= 0.0 {
+        return 1.0;
+    }
+    if p >= 1.0 {
+        return if k >= n { 1.0 } else { 0.0 };
+    }
+    // Accumulate in log space so long windows do not underflow
+    let odds = (p / (1.0 - p)).ln();
+    let mut log_pmf = n as f64 * (1.0 - p).ln();
+    let mut total = log_pmf.exp();
+    for i in 0..k.min(n) {
+        log_pmf += ((n - i) as f64).ln() - ((i + 1) as f64).ln() + odds;
+        total += log_pmf.exp();
+    }
+    total.min(1.0)
+}
+
+/// Basel traffic-light zone for `k` exceptions in `n` observations at
+/// exception rate `p`.
+///
+/// The zone is green while the cumulative probability of up to `k`
+/// exceptions is below 95%, yellow below 99.99% and red beyond.
+pub(crate) fn traffic_light(k: usize, n: usize, p: f64) -> TrafficLight {
+    let cumulative = binomial_cdf(k, n, p);
+    if cumulative < YELLOW_THRESHOLD {
+        TrafficLight::Green
+    } else if cumulative < RED_THRESHOLD {
+        TrafficLight::Yellow
+    } else {
+        TrafficLight::Red
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_basel_zones_for_250_days_at_99_percent() {
+        let zone = |k| traffic_light(k, 250, 0.01);
+        assert_eq!(zone(4), TrafficLight::Green);
+        assert_eq!(zone(5), TrafficLight::Yellow);
+        assert_eq!(zone(9), TrafficLight::Yellow);
+        assert_eq!(zone(10), TrafficLight::Red);
+    }
+
+    #[test]
+    fn test_chi_squared_critical_values() {
+        assert_relative_eq!(chi_squared_sf(3.841_458_8, 1), 0.05, epsilon = 1e-6);
+        assert_relative_eq!(chi_squared_sf(5.991_464_5, 2), 0.05, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_kupiec_known_value() {
+        // 10 exceptions in 250 days against a 1% rate
+        let mut exceptions = vec![false; 250];
+        exceptions.iter_mut().take(10).for_each(|e| *e = true);
+        let result = kupiec(&exceptions, 0.01, 0.05);
+        assert_relative_eq!(result.statistic, 12.955, epsilon = 1e-3);
+        assert!(result.rejected);
+    }
+
+    #[test]
+    fn test_christoffersen_detects_clustering() {
+        let mut clustered = vec![false; 250];
+        clustered[100..105].iter_mut().for_each(|e| *e = true);
+        let mut spread = vec![false; 250];
+        (0..5).for_each(|i| spread[i * 50 + 10] = true);
+
+        assert!(christoffersen(&clustered, 0.05).rejected);
+        assert!(!christoffersen(&spread, 0.05).rejected);
+    }
+}
@@ -0,0 +1,508 @@
+//! Revaluation of trades at the carry horizon.
+
+use std::collections::HashMap;
+
+use pricer_core::market_data::curves::{CurveEnum, YieldCurve};
+use pricer_core::types::time::Date;
+use pricer_core::types::Currency;
+use pricer_models::analytical::{norm_cdf, BlackScholes};
+use pricer_models::instruments::{Forward, Instrument, PayoffType, Swap, VanillaOption};
+
+use super::{CarryComponents, CarryError, CarryReport, CarryResult, TradeCarry};
+use crate::cashflows::{accrual_periods, exercise_value};
+use crate::portfolio::{Portfolio, Trade};
+
+/// Days per year converting the horizon to a year fraction (Act/365).
+const DAYS_PER_YEAR: f64 = 365.0;
+
+/// Computes theta, realised cashflows and roll-down to a horizon date.
+///
+/// Each trade is valued three times: today, at the horizon with today's
+/// forwards realised, and at the horizon on unchanged market data. Swaps
+/// need a discount curve in the trade currency, forwards a curve and a
+/// spot, and options a curve, a spot and a volatility; trades missing one
+/// are listed in [`CarryReport::unvalued`].
+///
+/// Floating coupons fixing before the horizon, and forwards and options
+/// expiring before it, settle at today's forwards in both horizon
+/// scenarios, so that the cashflows are common to both. Options are
+/// valued as European under Black-Scholes.
+///
+/// A negative trade notional marks a short position, as in
+/// [`crate::cashflows::CashflowProjector`].
+#[derive(Clone, Debug)]
+pub struct CarryEngine {
+    as_of: Date,
+    horizon: Date,
+    curves: HashMap<Currency, CurveEnum<f64>>,
+    spots: HashMap<Currency, f64>,
+    volatilities: HashMap<Currency, f64>,
+}
+
+/// Market as seen from a valuation time.
+struct MarketView<'a> {
+    curve: &'a CurveEnum<f64>,
+    /// Years from today at which the curve is read forward
+    shift: f64,
+    /// Years from today of the valuation
+    time: f64,
+    spot: Option<f64>,
+    volatility: Option<f64>,
+}
+
+impl MarketView<'_> {
+    /// Discount factor from the valuation time to `time` years after it.
+    fn discount(&self, time: f64) -> Option<f64> {
+        let start = self.curve.discount_factor(self.shift).ok()?;
+        let end = self.curve.discount_factor(self.shift + time).ok()?;
+        Some(end / start)
+    }
+}
+
+impl CarryEngine {
+    /// Creates an engine rolling from `as_of` to the next weekday.
+    ///
+    /// Use [`CarryEngine::with_horizon`] to skip holidays.
+    pub fn new(as_of: Date) -> Self {
+        Self {
+            as_of,
+            horizon: next_weekday(as_of),
+            curves: HashMap::new(),
+            spots: HashMap::new(),
+            volatilities: HashMap::new(),
+        }
+    }
+
+    /// Rolls to `horizon` instead of the next weekday.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CarryError::InvalidInput` if `horizon` is not after the
+    /// valuation date.
+    pub fn with_horizon(mut self, horizon: Date) -> CarryResult<Self> {
+        if horizon <= self.as_of {
+            return Err(CarryError::InvalidInput(format!(
+                "horizon {} must be after {}",
+                horizon, self.as_of
+            )));
+        }
+        self.horizon = horizon;
+        Ok(self)
+    }
+
+    /// Sets the discount curve of `currency`.
+    pub fn with_curve(mut self, currency: Currency, curve: CurveEnum<f64>) -> Self {
+        self.curves.insert(currency, curve);
+        self
+    }
+
+    /// Sets the spot of the underlying of trades in `currency`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CarryError::InvalidInput` if `spot` is not positive.
+    pub fn with_spot(mut self, currency: Currency, spot: f64) -> CarryResult<Self> {
+        if !(spot > 0.0 && spot.is_finite()) {
+            return Err(CarryError::InvalidInput(format!(
+                "{} spot {} must be positive and finite",
+                currency, spot
+            )));
+        }
+        self.spots.insert(currency, spot);
+        Ok(self)
+    }
+
+    /// Sets the Black-Scholes volatility of options in `currency`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CarryError::InvalidInput` if `volatility` is not positive.
+    pub fn with_volatility(mut self, currency: Currency, volatility: f64) -> CarryResult<Self> {
+        if !(volatility > 0.0 && volatility.is_finite()) {
+            return Err(CarryError::InvalidInput(format!(
+                "{} volatility {} must be positive and finite",
+                currency, volatility
+            )));
+        }
+        self.volatilities.insert(currency, volatility);
+        Ok(self)
+    }
+
+    /// Returns the valuation date.
+    #[inline]
+    pub fn as_of(&self) -> Date {
+        self.as_of
+    }
+
+    /// Returns the horizon date.
+    #[inline]
+    pub fn horizon(&self) -> Date {
+        self.horizon
+    }
+
+    /// Computes the carry of every trade in `portfolio`.
+    pub fn compute(&self, portfolio: &Portfolio) -> CarryReport {
+        let mut trades = Vec::new();
+        let mut unvalued = Vec::new();
+        for trade in portfolio.trades() {
+            match self.trade_carry(trade) {
+                Some(carry) => trades.push(TradeCarry {
+                    trade_id: trade.id().clone(),
+                    currency: trade.currency(),
+                    carry,
+                }),
+                None => unvalued.push(trade.id().clone()),
+            }
+        }
+        trades.sort_by(|a, b| a.trade_id.as_str().cmp(b.trade_id.as_str()));
+        unvalued.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        CarryReport {
+            as_of: self.as_of,
+            horizon: self.horizon,
+            trades,
+            unvalued,
+        }
+    }
+
+    /// Carry of one trade; `None` if market data is missing.
+    fn trade_carry(&self, trade: &Trade) -> Option<CarryComponents> {
+        let currency = trade.currency();
+        let curve = self.curves.get(&currency)?;
+        let spot = self.spots.get(&currency).copied();
+        let volatility = self.volatilities.get(&currency).copied();
+        let horizon = (self.horizon - self.as_of) as f64 / DAYS_PER_YEAR;
+
+        let today = MarketView {
+            curve,
+            shift: 0.0,
+            time: 0.0,
+            spot,
+            volatility,
+        };
+        let growth = 1.0 / curve.discount_factor(horizon).ok()?;
+        let realised = MarketView {
+            curve,
+            shift: horizon,
+            time: horizon,
+            spot: spot.map(|s| s * growth),
+            volatility,
+        };
+        let unchanged = MarketView {
+            curve,
+            shift: 0.0,
+            time: horizon,
+            spot,
+            volatility,
+        };
+
+        let (pv, _) = value(trade.instrument(), &today, &today)?;
+        let (forward_value, cashflows) = value(trade.instrument(), &realised, &today)?;
+        let (unchanged_value, _) = value(trade.instrument(), &unchanged, &today)?;
+        let position = if trade.notional() < 0.0 { -1.0 } else { 1.0 };
+        Some(CarryComponents {
+            pv: position * pv,
+            theta: position * (forward_value - pv),
+            cashflows: position * cashflows,
+            roll_down: position * (unchanged_value - forward_value),
+        })
+    }
+}
+
+/// Value at `market` and cashflows settled since today.
+///
+/// Amounts fixed before the valuation time are set from `today`.
+fn value(
+    instrument: &Instrument<f64>,
+    market: &MarketView<'_>,
+    today: &MarketView<'_>,
+) -> Option<(f64, f64)> {
+    match instrument {
+        Instrument::Swap(swap) => swap_value(swap, market, today),
+        Instrument::Forward(forward) => forward_value(forward, market, today),
+        Instrument::Vanilla(option) => option_value(option, market, today),
+    }
+}
+
+/// Fixed coupons paid, floating coupons received.
+fn swap_value(
+    swap: &Swap<f64>,
+    market: &MarketView<'_>,
+    today: &MarketView<'_>,
+) -> Option<(f64, f64)> {
+    let notional = swap.notional();
+    let mut pv = 0.0;
+    let mut cashflows = 0.0;
+    for (pay, year_fraction) in accrual_periods(swap) {
+        if pay <= 0.0 {
+            continue;
+        }
+        let start = pay - year_fraction;
+        let floating = if start < market.time {
+            // Fixed before the valuation time at today's forward
+            let from = start.max(0.0);
+            let growth = today.discount(from)? / today.discount(pay)?;
+            notional * (growth - 1.0) * year_fraction / (pay - from)
+        } else {
+            let growth =
+                market.discount(start - market.time)? / market.discount(pay - market.time)?;
+            notional * (growth - 1.0)
+        };
+        let amount = floating - swap.fixed_leg_cashflow(year_fraction);
+        if pay <= market.time {
+            cashflows += amount;
+        } else {
+            pv += amount * market.discount(pay - market.time)?;
+        }
+    }
+    Some((pv, cashflows))
+}
+
+/// Value of a forward, cash settled at today's forward on expiry.
+fn forward_value(
+    forward: &Forward<f64>,
+    market: &MarketView<'_>,
+    today: &MarketView<'_>,
+) -> Option<(f64, f64)> {
+    let expiry = forward.expiry();
+    let unit = if expiry <= market.time {
+        if expiry <= 0.0 {
+            return Some((0.0, 0.0));
+        }
+        let settlement = forward.payoff(today.spot? / today.discount(expiry)?);
+        return Some((0.0, settlement));
+    } else {
+        forward.payoff(market.spot? / market.discount(expiry - market.time)?)
+    };
+    Some((unit * market.discount(expiry - market.time)?, 0.0))
+}
+
+/// Black-Scholes value, exercised at today's forward on expiry.
+fn option_value(
+    option: &VanillaOption<f64>,
+    market: &MarketView<'_>,
+    today: &MarketView<'_>,
+) -> Option<(f64, f64)> {
+    let expiry = option.expiry();
+    if expiry <= market.time {
+        if expiry <= 0.0 {
+            return Some((0.0, 0.0));
+        }
+        let forward = today.spot? / today.discount(expiry)?;
+        return Some((0.0, exercise_value(option, forward)));
+    }
+
+    let remaining = expiry - market.time;
+    let discount = market.discount(remaining)?;
+    let rate = -discount.ln() / remaining;
+    let model = BlackScholes::new(market.spot?, rate, market.volatility?).ok()?;
+    let strike = option.strike();
+    let unit = match option.payoff_type() {
+        PayoffType::Call => model.price_call(strike, remaining),
+        PayoffType::Put => model.price_put(strike, remaining),
+        PayoffType::DigitalCall => discount * norm_cdf(model.d2(strike, remaining)),
+        PayoffType::DigitalPut => discount * norm_cdf(-model.d2(strike, remaining)),
+    };
+    Some((option.notional() * unit, 0.0))
+}
+
+/// The weekday after `date`, skipping Saturday and Sunday.
+fn next_weekday(date: Date) -> Date {
+    // 1970-01-05 was a Monday
+    let monday = Date::from_ymd(1970, 1, 5).expect("valid date");
+    let days = match (date - monday).rem_euclid(7) {
+        4 => 3,
+        5 => 2,
+        _ => 1,
+    };
+    date + days
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::portfolio::{
+        Counterparty, CounterpartyId, CreditParams, NettingSet, NettingSetId, PortfolioBuilder,
+        TradeId,
+    };
+    use approx::assert_relative_eq;
+    use pricer_core::market_data::curves::{CurveInterpolation, InterpolatedCurve};
+    use pricer_models::instruments::{
+        Direction, ExerciseStyle, InstrumentParams, PaymentFrequency,
+    };
+
+    fn as_of() -> Date {
+        Date::from_ymd(2026, 3, 13).unwrap()
+    }
+
+    fn portfolio(trades: Vec<(&str, Instrument<f64>, Currency, f64)>) -> Portfolio {
+        let cp = CounterpartyId::new("CP001");
+        let mut builder = PortfolioBuilder::new()
+            .add_counterparty(Counterparty::new(
+                cp.clone(),
+                CreditParams::new(0.02, 0.4).unwrap(),
+            ))
+            .add_netting_set(NettingSet::new(NettingSetId::new("NS001"), cp.clone()));
+        for (id, instrument, currency, notional) in trades {
+            builder = builder.add_trade(Trade::new(
+                TradeId::new(id),
+                instrument,
+                currency,
+                cp.clone(),
+                NettingSetId::new("NS001"),
+                notional,
+            ));
+        }
+        builder.build().unwrap()
+    }
+
+    fn swap(payment_dates: Vec<f64>) -> Instrument<f64> {
+        Instrument::Swap(
+            Swap::new(
+                1_000_000.0,
+                0.04,
+                payment_dates,
+                PaymentFrequency::Annual,
+                Currency::USD,
+            )
+            .unwrap(),
+        )
+    }
+
+    fn upward_curve() -> CurveEnum<f64> {
+        InterpolatedCurve::new(&[0.5, 5.0], &[0.02, 0.04], CurveInterpolation::Linear, true)
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn test_horizon_skips_weekend() {
+        // Friday 13 March 2026
+        assert_eq!(
+            CarryEngine::new(as_of()).horizon(),
+            Date::from_ymd(2026, 3, 16).unwrap()
+        );
+        let thursday = Date::from_ymd(2026, 3, 12).unwrap();
+        assert_eq!(CarryEngine::new(thursday).horizon(), as_of());
+        assert!(CarryEngine::new(as_of()).with_horizon(as_of()).is_err());
+        assert!(CarryEngine::new(as_of())
+            .with_volatility(Currency::USD, 0.0)
+            .is_err());
+    }
+
+    #[test]
+    fn test_swap_theta_and_roll_down() {
+        let p = portfolio(vec![
+            ("PAY", swap(vec![1.0, 2.0, 3.0]), Currency::USD, 1.0),
+            ("REC", swap(vec![1.0, 2.0, 3.0]), Currency::USD, -1.0),
+        ]);
+        let report = CarryEngine::new(as_of())
+            .with_curve(Currency::USD, upward_curve())
+            .compute(&p);
+        assert!(report.unvalued.is_empty());
+
+        let pay = report.trade(&TradeId::new("PAY")).unwrap().carry;
+        let rec = report.trade(&TradeId::new("REC")).unwrap().carry;
+        // With forwards realised the value accretes at the short rate
+        let growth = 1.0 / upward_curve().discount_factor(3.0 / 365.0).unwrap();
+        assert_relative_eq!(pay.theta, pay.pv * (growth - 1.0), epsilon = 1e-6);
+        assert_relative_eq!(pay.cashflows, 0.0);
+        // On an upward curve the fixed receiver earns the roll-down
+        assert!(rec.roll_down > 0.0);
+        assert_relative_eq!(pay.total(), -rec.total(), epsilon = 1e-9);
+
+        let totals = report.totals();
+        assert_eq!(totals.len(), 1);
+        assert_relative_eq!(totals[0].1.total(), 0.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_swap_coupon_within_horizon() {
+        let p = portfolio(vec![(
+            "S1",
+            swap(vec![1.0 / 365.0, 1.0 + 1.0 / 365.0]),
+            Currency::USD,
+            1.0,
+        )]);
+        let report = CarryEngine::new(as_of())
+            .with_curve(Currency::USD, CurveEnum::flat(0.05))
+            .compute(&p);
+        let carry = report.trades[0].carry;
+        // Fixed 40,000 paid against the floating coupon fixed a year ago
+        // at today's 1-day rate, annualised
+        let floating = 1_000_000.0 * ((0.05_f64 / 365.0).exp() - 1.0) * 365.0;
+        assert_relative_eq!(carry.cashflows, floating - 40_000.0, epsilon = 1e-6);
+        assert_relative_eq!(carry.roll_down, 0.0, epsilon = 1e-6);
+        // The remaining coupon accretes over the horizon, the paid one
+        // only up to its payment date
+        let paid_df = (-0.05_f64 / 365.0).exp();
+        let remaining = carry.pv - carry.cashflows * paid_df;
+        let expected =
+            remaining * ((0.15_f64 / 365.0).exp() - 1.0) + carry.cashflows * (1.0 - paid_df);
+        assert_relative_eq!(carry.total(), expected, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_forward_and_option_carry() {
+        let forward = Forward::new(100.0, 0.5, 10.0, Direction::Long).unwrap();
+        let expiring = Forward::new(95.0, 1.0 / 365.0, 10.0, Direction::Long).unwrap();
+        let params = InstrumentParams::new(100.0, 0.5, 10.0).unwrap();
+        let call = VanillaOption::new(params, PayoffType::Call, ExerciseStyle::European, 1e-6);
+        let params = InstrumentParams::new(100.0, 0.5, 10.0).unwrap();
+        let digital = VanillaOption::new(
+            params,
+            PayoffType::DigitalPut,
+            ExerciseStyle::European,
+            1e-6,
+        );
+        let p = portfolio(vec![
+            ("F1", Instrument::Forward(forward), Currency::EUR, 1.0),
+            ("F2", Instrument::Forward(expiring), Currency::EUR, 1.0),
+            ("C1", Instrument::Vanilla(call), Currency::EUR, -1.0),
+            ("D1", Instrument::Vanilla(digital), Currency::USD, 1.0),
+        ]);
+
+        let engine = CarryEngine::new(as_of())
+            .with_curve(Currency::EUR, CurveEnum::flat(0.03))
+            .with_curve(Currency::USD, CurveEnum::flat(0.03))
+            .with_spot(Currency::EUR, 100.0)
+            .unwrap()
+            .with_volatility(Currency::EUR, 0.2)
+            .unwrap();
+        let report = engine.clone().compute(&p);
+        assert_eq!(report.unvalued, vec![TradeId::new("D1")]);
+
+        let horizon: f64 = 3.0 / 365.0;
+        let f1 = report.trade(&TradeId::new("F1")).unwrap().carry;
+        assert_relative_eq!(
+            f1.pv,
+            10.0 * 100.0 * (1.0 - (-0.015_f64).exp()),
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(
+            f1.theta,
+            f1.pv * ((0.03 * horizon).exp() - 1.0),
+            epsilon = 1e-9
+        );
+        // Unchanged spot gives up the forward drift
+        assert!(f1.roll_down < 0.0);
+
+        let f2 = report.trade(&TradeId::new("F2")).unwrap().carry;
+        let settlement = 10.0 * (100.0 * (0.03_f64 / 365.0).exp() - 95.0);
+        assert_relative_eq!(f2.cashflows, settlement, epsilon = 1e-9);
+        assert_relative_eq!(f2.theta, -f2.pv, epsilon = 1e-9);
+
+        // A written call earns time decay on unchanged market data
+        let c1 = report.trade(&TradeId::new("C1")).unwrap().carry;
+        assert!(c1.pv < 0.0);
+        assert!(c1.total() > 0.0);
+
+        let report = engine
+            .with_spot(Currency::USD, 100.0)
+            .unwrap()
+            .with_volatility(Currency::USD, 0.2)
+            .unwrap()
+            .compute(&p);
+        let d1 = report.trade(&TradeId::new("D1")).unwrap().carry;
+        assert!(d1.pv > 0.0 && d1.pv < 10.0);
+    }
+}
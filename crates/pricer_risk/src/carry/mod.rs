@@ -0,0 +1,167 @@
+//! Theta and carry decomposition over a short horizon.
+//!
+//! The [`CarryEngine`] rolls a [`Portfolio`](crate::portfolio::Portfolio)
+//! forward to a horizon date, by default the next business day, and splits
+//! the value change of each trade into:
+//!
+//! - **Theta**: the value change from the passage of time alone, with the
+//!   market moving along today's forwards (spots accrete at the discount
+//!   rate, curves roll into their forwards)
+//! - **Cashflows**: coupons, forward settlements and option exercises
+//!   falling within the horizon, which leave the trade value as cash
+//! - **Roll-down**: the difference between revaluing on unchanged market
+//!   data (same spots, same curves by tenor) and the forwards being
+//!   realised, i.e. the curve slope earned by ageing the trade
+//!
+//! Their sum is the carry: the P&L of holding the portfolio to the horizon
+//! when the market does not move, and the carry component of a P&L
+//! explain. Amounts are in trade currency, signed from our side.
+//!
+//! # Examples
+//!
+//! ```
+//! use pricer_core::market_data::curves::CurveEnum;
+//! use pricer_core::types::time::Date;
+//! use pricer_core::types::Currency;
+//! use pricer_models::instruments::{Instrument, PaymentFrequency, Swap};
+//! use pricer_risk::carry::CarryEngine;
+//! use pricer_risk::portfolio::{
+//!     Counterparty, CounterpartyId, CreditParams, NettingSet, NettingSetId, PortfolioBuilder,
+//!     Trade, TradeId,
+//! };
+//!
+//! let swap = Swap::new(
+//!     1_000_000.0,
+//!     0.03,
+//!     vec![1.0, 2.0],
+//!     PaymentFrequency::Annual,
+//!     Currency::EUR,
+//! )
+//! .unwrap();
+//! let portfolio = PortfolioBuilder::new()
+//!     .add_counterparty(Counterparty::new(
+//!         CounterpartyId::new("CP001"),
+//!         CreditParams::new(0.02, 0.4).unwrap(),
+//!     ))
+//!     .add_netting_set(NettingSet::new(NettingSetId::new("NS001"), CounterpartyId::new("CP001")))
+//!     .add_trade(Trade::new(
+//!         TradeId::new("T001"),
+//!         Instrument::Swap(swap),
+//!         Currency::EUR,
+//!         CounterpartyId::new("CP001"),
+//!         NettingSetId::new("NS001"),
+//!         1_000_000.0,
+//!     ))
+//!     .build()
+//!     .unwrap();
+//!
+//! // Friday to Monday
+//! let report = CarryEngine::new(Date::from_ymd(2026, 1, 16).unwrap())
+//!     .with_curve(Currency::EUR, CurveEnum::flat(0.02))
+//!     .compute(&portfolio);
+//! assert_eq!(report.horizon, Date::from_ymd(2026, 1, 19).unwrap());
+//!
+//! // On a flat curve there is no roll-down
+//! let carry = &report.trades[0].carry;
+//! assert!(carry.roll_down.abs() < 1e-6);
+//! assert!((carry.total() - carry.theta).abs() < 1e-6);
+//! ```
+
+mod engine;
+
+pub use engine::CarryEngine;
+
+use std::collections::BTreeMap;
+
+use pricer_core::types::time::Date;
+use pricer_core::types::Currency;
+use thiserror::Error;
+
+use crate::portfolio::TradeId;
+
+/// Errors from carry engine inputs.
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum CarryError {
+    /// A horizon, spot or volatility is out of range.
+    #[error("Invalid carry input: {0}")]
+    InvalidInput(String),
+}
+
+/// Result type for the carry engine.
+pub type CarryResult<T> = Result<T, CarryError>;
+
+/// Value change components over the carry horizon.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CarryComponents {
+    /// Present value on the valuation date
+    pub pv: f64,
+    /// Value change with the forwards realised, cashflows excluded
+    pub theta: f64,
+    /// Cashflows paid or received within the horizon
+    pub cashflows: f64,
+    /// Unchanged-market value less forwards-realised value at the horizon
+    pub roll_down: f64,
+}
+
+impl CarryComponents {
+    /// Carry: theta plus cashflows plus roll-down.
+    #[inline]
+    pub fn total(&self) -> f64 {
+        self.theta + self.cashflows + self.roll_down
+    }
+
+    fn add(&mut self, other: &CarryComponents) {
+        self.pv += other.pv;
+        self.theta += other.theta;
+        self.cashflows += other.cashflows;
+        self.roll_down += other.roll_down;
+    }
+}
+
+/// Carry of one trade.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TradeCarry {
+    /// Trade identifier
+    pub trade_id: TradeId,
+    /// Trade currency of all amounts
+    pub currency: Currency,
+    /// Carry components
+    pub carry: CarryComponents,
+}
+
+/// Carry of a portfolio to a horizon date.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CarryReport {
+    /// Valuation date
+    pub as_of: Date,
+    /// Date the portfolio is rolled to
+    pub horizon: Date,
+    /// Carry per trade, sorted by trade ID
+    pub trades: Vec<TradeCarry>,
+    /// Trades that could not be valued for lack of a curve, spot or
+    /// volatility in their currency, sorted
+    pub unvalued: Vec<TradeId>,
+}
+
+impl CarryReport {
+    /// Carry summed per currency, sorted by currency code.
+    pub fn totals(&self) -> Vec<(Currency, CarryComponents)> {
+        let mut totals: BTreeMap<&'static str, (Currency, CarryComponents)> = BTreeMap::new();
+        for trade in &self.trades {
+            totals
+                .entry(trade.currency.code())
+                .or_insert((trade.currency, CarryComponents::default()))
+                .1
+                .add(&trade.carry);
+        }
+        totals.into_values().collect()
+    }
+
+    /// Carry of one trade, if valued.
+    pub fn trade(&self, id: &TradeId) -> Option<&TradeCarry> {
+        self.trades.iter().find(|t| &t.trade_id == id)
+    }
+}
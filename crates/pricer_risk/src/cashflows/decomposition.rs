@@ -0,0 +1,148 @@
+//! Expected cashflows of a single trade, discounted.
+
+use pricer_core::market_data::MarketSnapshot;
+use pricer_core::types::time::Date;
+use pricer_models::instruments::ValuedCashflow;
+
+use super::{CashflowError, CashflowResult};
+use crate::portfolio::{CounterpartyId, Trade, TradeId};
+
+/// Expected cashflows of one trade on a market snapshot, each with its
+/// discount factor and present value.
+///
+/// Built from [`Instrument::cashflows`](pricer_models::instruments::Instrument::cashflows)
+/// and signed from our side like a [`CashflowProjection`](super::CashflowProjection):
+/// a negative trade notional reverses every cashflow.
+///
+/// # Examples
+///
+/// ```
+/// use pricer_core::market_data::curves::CurveEnum;
+/// use pricer_core::market_data::MarketSnapshot;
+/// use pricer_core::types::time::Date;
+/// use pricer_core::types::Currency;
+/// use pricer_models::instruments::{Instrument, PaymentFrequency, Swap};
+/// use pricer_risk::cashflows::TradeCashflows;
+/// use pricer_risk::portfolio::{CounterpartyId, NettingSetId, Trade, TradeId};
+///
+/// let swap = Swap::new(1_000_000.0, 0.035, vec![1.0, 2.0], PaymentFrequency::Annual, Currency::USD)
+///     .unwrap();
+/// let trade = Trade::new(
+///     TradeId::new("T001"),
+///     Instrument::Swap(swap),
+///     Currency::USD,
+///     CounterpartyId::new("CP001"),
+///     NettingSetId::new("NS001"),
+///     -1_000_000.0,
+/// );
+/// let market = MarketSnapshot::new(
+///     Date::from_ymd(2026, 1, 15).unwrap(),
+///     Currency::USD,
+///     CurveEnum::flat(0.03),
+/// );
+///
+/// let decomposition = TradeCashflows::new(&trade, &market).unwrap();
+/// // Receiver side: fixed coupons received, floating paid
+/// assert_eq!(decomposition.cashflows.len(), 4);
+/// assert!((decomposition.cashflows[0].amount - 35_000.0).abs() < 1e-9);
+/// assert!(decomposition.present_value() > 0.0);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TradeCashflows {
+    /// Snapshot date
+    pub as_of: Date,
+    /// Trade the cashflows belong to
+    pub trade_id: TradeId,
+    /// Counterparty paying or receiving
+    pub counterparty_id: CounterpartyId,
+    /// Cashflows ordered by payment time
+    pub cashflows: Vec<ValuedCashflow<f64>>,
+}
+
+impl TradeCashflows {
+    /// Decomposes `trade` into its expected cashflows on `market`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CashflowError::InvalidInput` if the instrument cannot be
+    /// valued on `market`, e.g. an option on a snapshot without a spot.
+    pub fn new(trade: &Trade, market: &MarketSnapshot<f64>) -> CashflowResult<Self> {
+        let position = if trade.notional() < 0.0 { -1.0 } else { 1.0 };
+        let cashflows = trade
+            .instrument()
+            .cashflows(market)
+            .map_err(|e| CashflowError::InvalidInput(format!("trade {}: {}", trade.id(), e)))?
+            .into_iter()
+            .map(|cf| ValuedCashflow {
+                amount: position * cf.amount,
+                present_value: position * cf.present_value,
+                ..cf
+            })
+            .collect();
+        Ok(Self {
+            as_of: market.as_of(),
+            trade_id: trade.id().clone(),
+            counterparty_id: trade.counterparty_id().clone(),
+            cashflows,
+        })
+    }
+
+    /// Sum of the cashflows' present values.
+    pub fn present_value(&self) -> f64 {
+        self.cashflows.iter().map(|cf| cf.present_value).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use pricer_core::market_data::curves::CurveEnum;
+    use pricer_core::types::Currency;
+    use pricer_models::instruments::{
+        CashflowType, ExerciseStyle, Instrument, InstrumentParams, PayoffType, VanillaOption,
+    };
+
+    use crate::portfolio::NettingSetId;
+
+    fn option_trade(notional: f64) -> Trade {
+        let params = InstrumentParams::new(100.0, 0.5, 10.0).unwrap();
+        Trade::new(
+            TradeId::new("OPT1"),
+            Instrument::Vanilla(VanillaOption::new(
+                params,
+                PayoffType::Put,
+                ExerciseStyle::European,
+                1e-6,
+            )),
+            Currency::EUR,
+            CounterpartyId::new("CP1"),
+            NettingSetId::new("NS1"),
+            notional,
+        )
+    }
+
+    #[test]
+    fn test_written_option_and_missing_spot() {
+        let as_of = Date::from_ymd(2026, 1, 15).unwrap();
+        let market = MarketSnapshot::new(as_of, Currency::EUR, CurveEnum::flat(0.02));
+        assert!(matches!(
+            TradeCashflows::new(&option_trade(10.0), &market),
+            Err(CashflowError::InvalidInput(_))
+        ));
+
+        let market = market.with_spot(95.0).with_volatility(0.25);
+        let bought = TradeCashflows::new(&option_trade(10.0), &market).unwrap();
+        let written = TradeCashflows::new(&option_trade(-10.0), &market).unwrap();
+        assert_eq!(bought.cashflows.len(), 1);
+        assert_eq!(bought.cashflows[0].kind, CashflowType::OptionPayoff);
+        assert_eq!(bought.cashflows[0].payment_date, as_of + 183);
+        assert!(bought.present_value() > 50.0);
+        assert_relative_eq!(written.present_value(), -bought.present_value());
+        assert_eq!(
+            written.cashflows[0].discount_factor,
+            bought.cashflows[0].discount_factor
+        );
+    }
+}
@@ -0,0 +1,337 @@
+//! Contractual cashflow projection for treasury and liquidity reporting.
+//!
+//! The [`CashflowProjector`] enumerates every future cashflow of a
+//! [`Portfolio`](crate::portfolio::Portfolio):
+//!
+//! - Swaps: fixed coupons paid and floating coupons received on each
+//!   payment date, floating coupons projected at a flat rate per currency
+//! - Forwards: the strike payment exchanged for the underlying at expiry
+//!   (paid by the long, received by the short)
+//! - Options: the expected exercise at expiry, when the option is in the
+//!   money at the projection spot of its currency
+//! - Repos and securities loans of a [`FinancingBook`](crate::financing::FinancingBook):
+//!   cash exchanged at the start and returned with interest at the end, and
+//!   lending fees, through [`CashflowProjector::project_financing`]
+//!
+//! Amounts are signed from our side: positive received, negative paid. A
+//! negative trade notional marks a short position (a receiver swap, a
+//! written option) and reverses the trade's cashflows.
+//! Times are converted to dates from the valuation date on Act/365, and a
+//! [`CashflowProjection`] nets them by date and currency or into a
+//! liquidity ladder of tenor buckets. The `cashflows` report renders both
+//! through [`crate::reporting::cashflow_report_data`].
+//!
+//! For a single trade, [`TradeCashflows`] gives the expected cashflows on a
+//! market snapshot with the discount factor and present value of each,
+//! rendered by the `trade_cashflows` report through
+//! [`crate::reporting::trade_cashflow_report_data`].
+//!
+//! # Examples
+//!
+//! ```
+//! use pricer_core::types::time::Date;
+//! use pricer_core::types::Currency;
+//! use pricer_models::instruments::{Instrument, PaymentFrequency, Swap};
+//! use pricer_risk::cashflows::{CashflowProjector, STANDARD_BUCKETS};
+//! use pricer_risk::portfolio::{
+//!     Counterparty, CounterpartyId, CreditParams, NettingSet, NettingSetId, PortfolioBuilder,
+//!     Trade, TradeId,
+//! };
+//!
+//! let swap = Swap::new(
+//!     1_000_000.0,
+//!     0.03,
+//!     vec![0.5, 1.0],
+//!     PaymentFrequency::SemiAnnual,
+//!     Currency::EUR,
+//! )
+//! .unwrap();
+//! let portfolio = PortfolioBuilder::new()
+//!     .add_counterparty(Counterparty::new(
+//!         CounterpartyId::new("CP001"),
+//!         CreditParams::new(0.02, 0.4).unwrap(),
+//!     ))
+//!     .add_netting_set(NettingSet::new(NettingSetId::new("NS001"), CounterpartyId::new("CP001")))
+//!     .add_trade(Trade::new(
+//!         TradeId::new("T001"),
+//!         Instrument::Swap(swap),
+//!         Currency::EUR,
+//!         CounterpartyId::new("CP001"),
+//!         NettingSetId::new("NS001"),
+//!         1_000_000.0,
+//!     ))
+//!     .build()
+//!     .unwrap();
+//!
+//! let projection = CashflowProjector::new(Date::from_ymd(2026, 1, 15).unwrap())
+//!     .with_floating_rate(Currency::EUR, 0.02)
+//!     .unwrap()
+//!     .project(&portfolio);
+//!
+//! // Two fixed and two floating coupons, netting to -5,000 per period
+//! assert_eq!(projection.cashflows.len(), 4);
+//! let by_date = projection.by_date();
+//! assert_eq!(by_date[0].date, Date::from_ymd(2026, 7, 17).unwrap());
+//! assert!((by_date[0].amount + 5_000.0).abs() < 1e-6);
+//!
+//! let ladder = projection.ladder(STANDARD_BUCKETS);
+//! assert_eq!(ladder[0].bucket, "6M");
+//! ```
+
+mod decomposition;
+mod projector;
+
+pub use decomposition::TradeCashflows;
+pub use projector::CashflowProjector;
+pub(crate) use projector::{accrual_periods, exercise_value};
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use pricer_core::types::time::Date;
+use pricer_core::types::Currency;
+use thiserror::Error;
+
+use crate::portfolio::{CounterpartyId, TradeId};
+
+/// Errors from cashflow projection inputs.
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum CashflowError {
+    /// A projection spot, rate or horizon is out of range.
+    #[error("Invalid projection input: {0}")]
+    InvalidInput(String),
+}
+
+/// Result type for cashflow projection.
+pub type CashflowResult<T> = Result<T, CashflowError>;
+
+/// Days per year converting projection times to dates (Act/365).
+const DAYS_PER_YEAR: f64 = 365.0;
+
+/// Liquidity ladder buckets: label and upper edge in years.
+///
+/// Cashflows beyond the last edge fall into a final `>30Y` bucket.
+pub const STANDARD_BUCKETS: &[(&str, f64)] = &[
+    ("1W", 7.0 / DAYS_PER_YEAR),
+    ("1M", 1.0 / 12.0),
+    ("3M", 0.25),
+    ("6M", 0.5),
+    ("1Y", 1.0),
+    ("2Y", 2.0),
+    ("5Y", 5.0),
+    ("10Y", 10.0),
+    ("30Y", 30.0),
+];
+
+/// Origin of a projected cashflow.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CashflowKind {
+    /// Fixed leg coupon of a swap
+    FixedCoupon,
+    /// Floating leg coupon of a swap, at the projected rate
+    FloatingCoupon,
+    /// Strike payment of a forward against delivery
+    NotionalExchange,
+    /// Expected exercise of an in-the-money option
+    OptionExercise,
+    /// Cash lent or borrowed under a repo, at its start and end dates
+    RepoPrincipal,
+    /// Interest on a repo's cash, paid at its end date
+    RepoInterest,
+    /// Fee on a securities loan, paid at its end date
+    LendingFee,
+}
+
+impl CashflowKind {
+    /// Snake-case name used in reports.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CashflowKind::FixedCoupon => "fixed_coupon",
+            CashflowKind::FloatingCoupon => "floating_coupon",
+            CashflowKind::NotionalExchange => "notional_exchange",
+            CashflowKind::OptionExercise => "option_exercise",
+            CashflowKind::RepoPrincipal => "repo_principal",
+            CashflowKind::RepoInterest => "repo_interest",
+            CashflowKind::LendingFee => "lending_fee",
+        }
+    }
+}
+
+impl fmt::Display for CashflowKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One projected cashflow of a trade.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cashflow {
+    /// Trade the cashflow belongs to
+    pub trade_id: TradeId,
+    /// Counterparty paying or receiving
+    pub counterparty_id: CounterpartyId,
+    /// Origin of the cashflow
+    pub kind: CashflowKind,
+    /// Payment time in years from the valuation date
+    pub time: f64,
+    /// Payment date
+    pub date: Date,
+    /// Payment currency
+    pub currency: Currency,
+    /// Signed amount: positive received, negative paid
+    pub amount: f64,
+}
+
+/// Net cashflow of one currency on one date.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DatedCashflow {
+    /// Payment date
+    pub date: Date,
+    /// Payment currency
+    pub currency: Currency,
+    /// Net amount
+    pub amount: f64,
+}
+
+/// Cashflows of one currency in one ladder bucket.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LadderRow {
+    /// Bucket label, e.g. `3M`
+    pub bucket: String,
+    /// Currency
+    pub currency: Currency,
+    /// Sum of amounts received
+    pub inflows: f64,
+    /// Sum of amounts paid, as a negative number
+    pub outflows: f64,
+    /// Inflows plus outflows
+    pub net: f64,
+    /// Net amount of this and all earlier buckets in the currency
+    pub cumulative: f64,
+}
+
+/// Projected cashflows of a portfolio.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CashflowProjection {
+    /// Valuation date
+    pub as_of: Date,
+    /// Cashflows sorted by date, currency, trade and kind
+    pub cashflows: Vec<Cashflow>,
+    /// Trades with cashflows that could not be projected for lack of a
+    /// spot or floating rate in their currency, sorted
+    pub unprojected: Vec<TradeId>,
+}
+
+impl CashflowProjection {
+    /// Net amounts by date and currency, sorted by date then currency code.
+    pub fn by_date(&self) -> Vec<DatedCashflow> {
+        let mut net: BTreeMap<(Date, &'static str), (Currency, f64)> = BTreeMap::new();
+        for cf in &self.cashflows {
+            net.entry((cf.date, cf.currency.code()))
+                .or_insert((cf.currency, 0.0))
+                .1 += cf.amount;
+        }
+        net.into_iter()
+            .map(|((date, _), (currency, amount))| DatedCashflow {
+                date,
+                currency,
+                amount,
+            })
+            .collect()
+    }
+
+    /// Liquidity ladder over `buckets` of (label, upper edge in years).
+    ///
+    /// A cashflow falls into the first bucket whose edge is at or after its
+    /// time; those beyond the last edge into a `>{label}` bucket. Rows are
+    /// ordered by currency code then bucket, and only buckets with
+    /// cashflows appear.
+    pub fn ladder(&self, buckets: &[(&str, f64)]) -> Vec<LadderRow> {
+        let mut sums: BTreeMap<(&'static str, usize), (Currency, f64, f64)> = BTreeMap::new();
+        for cf in &self.cashflows {
+            let index = buckets
+                .iter()
+                .position(|&(_, end)| cf.time <= end)
+                .unwrap_or(buckets.len());
+            let entry = sums
+                .entry((cf.currency.code(), index))
+                .or_insert((cf.currency, 0.0, 0.0));
+            if cf.amount >= 0.0 {
+                entry.1 += cf.amount;
+            } else {
+                entry.2 += cf.amount;
+            }
+        }
+
+        let mut rows = Vec::with_capacity(sums.len());
+        let mut cumulative = 0.0;
+        let mut current = "";
+        for ((code, index), (currency, inflows, outflows)) in sums {
+            if code != current {
+                current = code;
+                cumulative = 0.0;
+            }
+            let net = inflows + outflows;
+            cumulative += net;
+            let bucket = match buckets.get(index) {
+                Some(&(label, _)) => label.to_string(),
+                None => format!(">{}", buckets.last().map_or("0", |&(label, _)| label)),
+            };
+            rows.push(LadderRow {
+                bucket,
+                currency,
+                inflows,
+                outflows,
+                net,
+                cumulative,
+            });
+        }
+        rows
+    }
+
+    /// Combines two projections from the same valuation date, e.g. a
+    /// portfolio's and its financing book's.
+    pub fn merge(mut self, other: CashflowProjection) -> CashflowProjection {
+        self.cashflows.extend(other.cashflows);
+        sort_cashflows(&mut self.cashflows);
+        self.unprojected.extend(other.unprojected);
+        self.unprojected.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        self
+    }
+
+    /// Total net amount of each currency, sorted by currency code.
+    pub fn totals(&self) -> Vec<(Currency, f64)> {
+        let mut totals: BTreeMap<&'static str, (Currency, f64)> = BTreeMap::new();
+        for cf in &self.cashflows {
+            totals
+                .entry(cf.currency.code())
+                .or_insert((cf.currency, 0.0))
+                .1 += cf.amount;
+        }
+        totals.into_values().collect()
+    }
+}
+
+/// Date `time` years after `as_of` on Act/365.
+pub(crate) fn date_after(as_of: Date, time: f64) -> Date {
+    as_of + (time * DAYS_PER_YEAR).round() as i64
+}
+
+/// Years from `as_of` to `date` on Act/365.
+fn time_until(as_of: Date, date: Date) -> f64 {
+    (date - as_of) as f64 / DAYS_PER_YEAR
+}
+
+/// Sorts cashflows by date, currency, trade and kind.
+fn sort_cashflows(cashflows: &mut [Cashflow]) {
+    cashflows.sort_by(|a, b| {
+        (a.date, a.currency.code(), a.trade_id.as_str(), a.kind)
+            .cmp(&(b.date, b.currency.code(), b.trade_id.as_str(), b.kind))
+            .then(a.time.total_cmp(&b.time))
+    });
+}
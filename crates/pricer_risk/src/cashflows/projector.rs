@@ -0,0 +1,534 @@
+//! Enumeration of trade cashflows from contract terms.
+
+use std::collections::HashMap;
+
+use pricer_core::types::time::Date;
+use pricer_core::types::Currency;
+use pricer_models::instruments::{Direction, Forward, Instrument, PayoffType, Swap, VanillaOption};
+
+use super::{
+    date_after, sort_cashflows, time_until, Cashflow, CashflowError, CashflowKind,
+    CashflowProjection, CashflowResult,
+};
+use crate::financing::{FinancingBook, FinancingInstrument};
+use crate::portfolio::{Portfolio, Trade};
+
+/// Projects the future cashflows of a portfolio.
+///
+/// Fixed coupons and forward strike payments follow from the contract
+/// terms alone. Floating coupons need a projection rate and option
+/// exercises a spot for the trade currency; trades missing one are listed
+/// in [`CashflowProjection::unprojected`] with their known cashflows kept.
+///
+/// A negative trade notional marks a short position: the receiver side of
+/// a swap or a written option, with every cashflow of the trade reversed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CashflowProjector {
+    as_of: Date,
+    horizon: Option<f64>,
+    spots: HashMap<Currency, f64>,
+    floating_rates: HashMap<Currency, f64>,
+}
+
+impl CashflowProjector {
+    /// Creates a projector valuing on `as_of`, without a horizon.
+    pub fn new(as_of: Date) -> Self {
+        Self {
+            as_of,
+            horizon: None,
+            spots: HashMap::new(),
+            floating_rates: HashMap::new(),
+        }
+    }
+
+    /// Projects only cashflows up to `years` from the valuation date.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CashflowError::InvalidInput` if `years` is not positive.
+    pub fn with_horizon(mut self, years: f64) -> CashflowResult<Self> {
+        if !(years > 0.0 && years.is_finite()) {
+            return Err(CashflowError::InvalidInput(format!(
+                "horizon {} must be positive and finite",
+                years
+            )));
+        }
+        self.horizon = Some(years);
+        Ok(self)
+    }
+
+    /// Sets the spot deciding the exercise of options in `currency`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CashflowError::InvalidInput` if `spot` is not positive.
+    pub fn with_spot(mut self, currency: Currency, spot: f64) -> CashflowResult<Self> {
+        if !(spot > 0.0 && spot.is_finite()) {
+            return Err(CashflowError::InvalidInput(format!(
+                "{} spot {} must be positive and finite",
+                currency, spot
+            )));
+        }
+        self.spots.insert(currency, spot);
+        Ok(self)
+    }
+
+    /// Sets the flat rate projecting floating coupons in `currency`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CashflowError::InvalidInput` if `rate` is not finite.
+    pub fn with_floating_rate(mut self, currency: Currency, rate: f64) -> CashflowResult<Self> {
+        if !rate.is_finite() {
+            return Err(CashflowError::InvalidInput(format!(
+                "{} floating rate {} must be finite",
+                currency, rate
+            )));
+        }
+        self.floating_rates.insert(currency, rate);
+        Ok(self)
+    }
+
+    /// Returns the valuation date.
+    #[inline]
+    pub fn as_of(&self) -> Date {
+        self.as_of
+    }
+
+    /// Projects the cashflows of every trade in `portfolio`.
+    pub fn project(&self, portfolio: &Portfolio) -> CashflowProjection {
+        let mut cashflows = Vec::new();
+        let mut unprojected = Vec::new();
+        for trade in portfolio.trades() {
+            if !self.project_trade(trade, &mut cashflows) {
+                unprojected.push(trade.id().clone());
+            }
+        }
+        sort_cashflows(&mut cashflows);
+        unprojected.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        CashflowProjection {
+            as_of: self.as_of,
+            cashflows,
+            unprojected,
+        }
+    }
+
+    /// Projects the cashflows of every trade in a financing book.
+    ///
+    /// A repo exchanges its cash at the start date and returns it with
+    /// interest at the end date. A securities loan only pays its fee: the
+    /// securities and any collateral move outside the cash ladder. Combine
+    /// with a portfolio projection through [`CashflowProjection::merge`].
+    pub fn project_financing(&self, book: &FinancingBook) -> CashflowProjection {
+        let mut cashflows = Vec::new();
+        for trade in book.trades() {
+            let instrument = trade.instrument();
+            let mut push = |kind: CashflowKind, date: Date, amount: f64| {
+                let time = time_until(self.as_of, date);
+                if self.in_range(time) && amount != 0.0 {
+                    cashflows.push(Cashflow {
+                        trade_id: trade.id().clone(),
+                        counterparty_id: trade.counterparty_id().clone(),
+                        kind,
+                        time,
+                        date,
+                        currency: instrument.currency(),
+                        amount,
+                    });
+                }
+            };
+            match instrument {
+                FinancingInstrument::Repo(repo) => {
+                    let lender = repo.direction().sign::<f64>();
+                    let cash = repo.cash_amount();
+                    push(
+                        CashflowKind::RepoPrincipal,
+                        repo.start_date(),
+                        -lender * cash,
+                    );
+                    push(CashflowKind::RepoPrincipal, repo.end_date(), lender * cash);
+                    push(
+                        CashflowKind::RepoInterest,
+                        repo.end_date(),
+                        lender * repo.interest(),
+                    );
+                }
+                FinancingInstrument::SecuritiesLoan(loan) => {
+                    let lender = loan.direction().sign::<f64>();
+                    push(
+                        CashflowKind::LendingFee,
+                        loan.end_date(),
+                        lender * loan.fee(),
+                    );
+                }
+            }
+        }
+        sort_cashflows(&mut cashflows);
+        CashflowProjection {
+            as_of: self.as_of,
+            cashflows,
+            unprojected: Vec::new(),
+        }
+    }
+
+    /// Appends the cashflows of `trade`; false if some were not projected.
+    fn project_trade(&self, trade: &Trade, out: &mut Vec<Cashflow>) -> bool {
+        let currency = trade.currency();
+        let position = if trade.notional() < 0.0 { -1.0 } else { 1.0 };
+        let mut push = |kind: CashflowKind, time: f64, amount: f64| {
+            if self.in_range(time) && amount != 0.0 {
+                out.push(Cashflow {
+                    trade_id: trade.id().clone(),
+                    counterparty_id: trade.counterparty_id().clone(),
+                    kind,
+                    time,
+                    date: date_after(self.as_of, time),
+                    currency,
+                    amount: position * amount,
+                });
+            }
+        };
+        match trade.instrument() {
+            Instrument::Swap(swap) => {
+                let rate = self.floating_rates.get(&currency).copied();
+                for (time, year_fraction) in accrual_periods(swap) {
+                    push(
+                        CashflowKind::FixedCoupon,
+                        time,
+                        -swap.fixed_leg_cashflow(year_fraction),
+                    );
+                    if let Some(rate) = rate {
+                        push(
+                            CashflowKind::FloatingCoupon,
+                            time,
+                            swap.notional() * rate * year_fraction,
+                        );
+                    }
+                }
+                rate.is_some() || !self.in_range_any(swap.payment_dates())
+            }
+            Instrument::Forward(forward) => {
+                push(
+                    CashflowKind::NotionalExchange,
+                    forward.expiry(),
+                    strike_payment(forward),
+                );
+                true
+            }
+            Instrument::Vanilla(option) => {
+                if !self.in_range(option.expiry()) {
+                    return true;
+                }
+                match self.spots.get(&currency) {
+                    Some(&spot) => {
+                        push(
+                            CashflowKind::OptionExercise,
+                            option.expiry(),
+                            exercise_value(option, spot),
+                        );
+                        true
+                    }
+                    None => false,
+                }
+            }
+        }
+    }
+
+    /// Whether a cashflow at `time` is in the future and within the horizon.
+    fn in_range(&self, time: f64) -> bool {
+        time > 0.0 && self.horizon.is_none_or(|h| time <= h)
+    }
+
+    fn in_range_any(&self, times: &[f64]) -> bool {
+        times.iter().any(|&t| self.in_range(t))
+    }
+}
+
+/// Payment times and accrual year fractions of a swap.
+///
+/// The first period accrues one period of the payment frequency; later
+/// periods run between consecutive payment dates.
+pub(crate) fn accrual_periods(swap: &Swap<f64>) -> Vec<(f64, f64)> {
+    let dates = swap.payment_dates();
+    let first = swap.frequency().period_fraction::<f64>();
+    dates
+        .iter()
+        .enumerate()
+        .map(|(i, &pay)| (pay, if i == 0 { first } else { pay - dates[i - 1] }))
+        .collect()
+}
+
+/// Strike paid by the long (received by the short) at delivery.
+fn strike_payment(forward: &Forward<f64>) -> f64 {
+    let amount = forward.strike() * forward.notional();
+    match forward.direction() {
+        Direction::Long => -amount,
+        Direction::Short => amount,
+    }
+}
+
+/// Cash received on exercise at `spot`, zero if out of the money.
+///
+/// Evaluated without the payoff smoothing used for pricing, so that an
+/// option at the money projects no cashflow.
+pub(crate) fn exercise_value(option: &VanillaOption<f64>, spot: f64) -> f64 {
+    let strike = option.strike();
+    let unit = match option.payoff_type() {
+        PayoffType::Call => (spot - strike).max(0.0),
+        PayoffType::Put => (strike - spot).max(0.0),
+        PayoffType::DigitalCall => f64::from(u8::from(spot > strike)),
+        PayoffType::DigitalPut => f64::from(u8::from(spot < strike)),
+    };
+    option.notional() * unit
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cashflows::STANDARD_BUCKETS;
+    use crate::portfolio::{
+        Counterparty, CounterpartyId, CreditParams, NettingSet, NettingSetId, PortfolioBuilder,
+        TradeId,
+    };
+    use approx::assert_relative_eq;
+    use pricer_models::instruments::{ExerciseStyle, InstrumentParams, PaymentFrequency};
+
+    fn as_of() -> Date {
+        Date::from_ymd(2026, 1, 15).unwrap()
+    }
+
+    fn portfolio(trades: Vec<(&str, Instrument<f64>, Currency)>) -> Portfolio {
+        let cp = CounterpartyId::new("CP001");
+        let mut builder = PortfolioBuilder::new()
+            .add_counterparty(Counterparty::new(
+                cp.clone(),
+                CreditParams::new(0.02, 0.4).unwrap(),
+            ))
+            .add_netting_set(NettingSet::new(NettingSetId::new("NS001"), cp.clone()));
+        for (id, instrument, currency) in trades {
+            builder = builder.add_trade(Trade::new(
+                TradeId::new(id),
+                instrument,
+                currency,
+                cp.clone(),
+                NettingSetId::new("NS001"),
+                1.0,
+            ));
+        }
+        builder.build().unwrap()
+    }
+
+    fn swap(payment_dates: Vec<f64>) -> Instrument<f64> {
+        Instrument::Swap(
+            Swap::new(
+                1_000_000.0,
+                0.04,
+                payment_dates,
+                PaymentFrequency::Annual,
+                Currency::USD,
+            )
+            .unwrap(),
+        )
+    }
+
+    fn option(payoff: PayoffType, strike: f64, expiry: f64) -> Instrument<f64> {
+        let params = InstrumentParams::new(strike, expiry, 1_000.0).unwrap();
+        Instrument::Vanilla(VanillaOption::new(
+            params,
+            payoff,
+            ExerciseStyle::European,
+            1e-6,
+        ))
+    }
+
+    #[test]
+    fn test_swap_coupons() {
+        let p = portfolio(vec![("S1", swap(vec![1.0, 2.0, 2.5]), Currency::USD)]);
+
+        let fixed_only = CashflowProjector::new(as_of()).project(&p);
+        assert_eq!(fixed_only.cashflows.len(), 3);
+        assert_relative_eq!(fixed_only.cashflows[0].amount, -40_000.0);
+        assert_relative_eq!(fixed_only.cashflows[2].amount, -20_000.0);
+        assert_eq!(fixed_only.unprojected, vec![TradeId::new("S1")]);
+
+        let projection = CashflowProjector::new(as_of())
+            .with_floating_rate(Currency::USD, 0.05)
+            .unwrap()
+            .with_horizon(2.0)
+            .unwrap()
+            .project(&p);
+        assert!(projection.unprojected.is_empty());
+        let kinds: Vec<_> = projection.cashflows.iter().map(|c| c.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                CashflowKind::FixedCoupon,
+                CashflowKind::FloatingCoupon,
+                CashflowKind::FixedCoupon,
+                CashflowKind::FloatingCoupon,
+            ]
+        );
+        assert_eq!(
+            projection.cashflows[0].date,
+            Date::from_ymd(2027, 1, 15).unwrap()
+        );
+        let totals = projection.totals();
+        assert_eq!(totals.len(), 1);
+        assert_relative_eq!(totals[0].1, 2.0 * 10_000.0, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_forward_and_option_exercise() {
+        let long = Forward::new(50.0, 0.25, 100.0, Direction::Long).unwrap();
+        let short = Forward::new(50.0, 0.25, 40.0, Direction::Short).unwrap();
+        let p = portfolio(vec![
+            ("F1", Instrument::Forward(long), Currency::EUR),
+            ("F2", Instrument::Forward(short), Currency::EUR),
+            ("C1", option(PayoffType::Call, 100.0, 0.5), Currency::USD),
+            ("P1", option(PayoffType::Put, 100.0, 0.5), Currency::USD),
+            (
+                "D1",
+                option(PayoffType::DigitalPut, 120.0, 0.75),
+                Currency::USD,
+            ),
+        ]);
+
+        let without_spot = CashflowProjector::new(as_of()).project(&p);
+        assert_eq!(without_spot.cashflows.len(), 2);
+        assert_eq!(
+            without_spot.unprojected,
+            vec![TradeId::new("C1"), TradeId::new("D1"), TradeId::new("P1")]
+        );
+
+        let projection = CashflowProjector::new(as_of())
+            .with_spot(Currency::USD, 110.0)
+            .unwrap()
+            .project(&p);
+        assert!(projection.unprojected.is_empty());
+        let amounts: Vec<(&str, f64)> = projection
+            .cashflows
+            .iter()
+            .map(|c| (c.trade_id.as_str(), c.amount))
+            .collect();
+        // The put expires out of the money and projects nothing
+        assert_eq!(
+            amounts,
+            vec![
+                ("F1", -5_000.0),
+                ("F2", 2_000.0),
+                ("C1", 10_000.0),
+                ("D1", 1_000.0),
+            ]
+        );
+
+        let by_date = projection.by_date();
+        assert_eq!(by_date.len(), 3);
+        assert_eq!(by_date[0].currency, Currency::EUR);
+        assert_relative_eq!(by_date[0].amount, -3_000.0);
+
+        let ladder = projection.ladder(STANDARD_BUCKETS);
+        let rows: Vec<(&str, &str, f64, f64)> = ladder
+            .iter()
+            .map(|r| (r.currency.code(), r.bucket.as_str(), r.net, r.cumulative))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                ("EUR", "3M", -3_000.0, -3_000.0),
+                ("USD", "6M", 10_000.0, 10_000.0),
+                ("USD", "1Y", 1_000.0, 11_000.0),
+            ]
+        );
+        assert_relative_eq!(ladder[0].inflows, 2_000.0);
+        assert_relative_eq!(ladder[0].outflows, -5_000.0);
+    }
+
+    #[test]
+    fn test_ladder_overflow_bucket_and_invalid_inputs() {
+        let p = portfolio(vec![("S1", swap(vec![1.0, 40.0]), Currency::USD)]);
+        let ladder = CashflowProjector::new(as_of())
+            .project(&p)
+            .ladder(STANDARD_BUCKETS);
+        assert_eq!(ladder.len(), 2);
+        assert_eq!(ladder[1].bucket, ">30Y");
+
+        assert!(CashflowProjector::new(as_of()).with_horizon(0.0).is_err());
+        assert!(CashflowProjector::new(as_of())
+            .with_spot(Currency::USD, f64::NAN)
+            .is_err());
+        assert!(CashflowProjector::new(as_of())
+            .with_floating_rate(Currency::USD, f64::INFINITY)
+            .is_err());
+    }
+
+    #[test]
+    fn test_financing_cashflows_merge_into_ladder() {
+        use crate::financing::FinancingTrade;
+        use pricer_models::instruments::rates::{
+            CollateralPosition, CollateralSchedule, LendingDirection, Repo, RepoDirection,
+            SecuritiesLoan,
+        };
+
+        let start = as_of() + 7;
+        let collateral =
+            CollateralSchedule::constant(start, CollateralPosition::new(1_100_000.0, 0.1).unwrap());
+        let repo = Repo::new(
+            1_000_000.0,
+            0.036,
+            start,
+            start + 100,
+            RepoDirection::Repo,
+            collateral.clone(),
+            Currency::USD,
+        )
+        .unwrap();
+        let loan = SecuritiesLoan::new(
+            2_000_000.0,
+            0.0036,
+            as_of() + (-30),
+            as_of() + 335,
+            LendingDirection::Lend,
+            collateral,
+            Currency::USD,
+        )
+        .unwrap();
+        let book = FinancingBook::new(as_of())
+            .with_trade(FinancingTrade::new(
+                TradeId::new("R1"),
+                repo,
+                CounterpartyId::new("CP001"),
+                NettingSetId::new("NS001"),
+            ))
+            .with_trade(FinancingTrade::new(
+                TradeId::new("L1"),
+                loan,
+                CounterpartyId::new("CP001"),
+                NettingSetId::new("NS001"),
+            ));
+
+        let financing = CashflowProjector::new(as_of()).project_financing(&book);
+        let flows: Vec<(&str, CashflowKind, f64)> = financing
+            .cashflows
+            .iter()
+            .map(|cf| (cf.trade_id.as_str(), cf.kind, cf.amount))
+            .collect();
+        assert_eq!(flows.len(), 4);
+        assert_eq!(flows[0], ("R1", CashflowKind::RepoPrincipal, 1_000_000.0));
+        assert_eq!(flows[1].1, CashflowKind::RepoPrincipal);
+        assert_relative_eq!(flows[1].2, -1_000_000.0);
+        assert_relative_eq!(flows[2].2, -10_000.0, epsilon = 1e-9);
+        assert_eq!(flows[3].0, "L1");
+        assert_relative_eq!(
+            flows[3].2,
+            2_000_000.0 * 0.0036 * 365.0 / 360.0,
+            epsilon = 1e-9
+        );
+        assert_relative_eq!(financing.cashflows[0].time, 7.0 / 365.0);
+
+        let p = portfolio(vec![("S1", swap(vec![0.5, 1.0]), Currency::USD)]);
+        let merged = CashflowProjector::new(as_of()).project(&p).merge(financing);
+        assert_eq!(merged.cashflows.len(), 6);
+        assert!(merged.cashflows.windows(2).all(|w| w[0].date <= w[1].date));
+        assert_eq!(merged.ladder(STANDARD_BUCKETS)[0].bucket, "1W");
+    }
+}
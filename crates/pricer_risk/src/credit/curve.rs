@@ -0,0 +1,301 @@
+//! Rating-implied hazard curves and the credit parameter service.
+
+use pricer_core::market_data::curves::CreditCurve;
+use pricer_core::market_data::error::MarketDataError;
+
+use super::transition::TransitionMatrix;
+use super::CreditError;
+use crate::portfolio::{CreditParams, CreditRating};
+
+/// Hazard curve implied by a rating's cumulative default probabilities.
+///
+/// Holds one forward hazard rate per year, piecewise flat, so survival
+/// probabilities at whole years reproduce the transition matrix exactly.
+/// The last rate is extrapolated flat beyond the horizon.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RatingHazardCurve {
+    rating: CreditRating,
+    forward_hazards: Vec<f64>,
+}
+
+impl RatingHazardCurve {
+    /// Bootstraps the curve for `rating` over `horizon_years` years.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CreditError::InvalidHorizon` for a zero horizon and
+    /// `CreditError::DefaultedRating` for rating `D`.
+    pub fn from_matrix(
+        matrix: &TransitionMatrix,
+        rating: CreditRating,
+        horizon_years: u32,
+    ) -> Result<Self, CreditError> {
+        if horizon_years == 0 {
+            return Err(CreditError::InvalidHorizon(0.0));
+        }
+        if rating.is_default() {
+            return Err(CreditError::DefaultedRating);
+        }
+
+        let mut forward_hazards = Vec::with_capacity(horizon_years as usize);
+        let mut cumulative = TransitionMatrix::identity();
+        let mut previous_survival = 1.0_f64;
+        for _ in 0..horizon_years {
+            cumulative = cumulative.then(matrix);
+            let survival = 1.0 - cumulative.probability(rating, CreditRating::D);
+            forward_hazards.push((previous_survival / survival).ln());
+            previous_survival = survival;
+        }
+
+        Ok(Self {
+            rating,
+            forward_hazards,
+        })
+    }
+
+    /// Returns the rating this curve was built for.
+    #[inline]
+    pub fn rating(&self) -> CreditRating {
+        self.rating
+    }
+
+    /// Returns the annual forward hazard rates.
+    #[inline]
+    pub fn forward_hazards(&self) -> &[f64] {
+        &self.forward_hazards
+    }
+
+    /// Returns the flat hazard rate giving the same survival to
+    /// `horizon`: -ln Q(horizon) / horizon.
+    pub fn average_hazard_rate(&self, horizon: f64) -> f64 {
+        if horizon <= 0.0 {
+            return self.forward_hazards[0];
+        }
+        self.integrated_hazard(horizon) / horizon
+    }
+
+    fn integrated_hazard(&self, t: f64) -> f64 {
+        let whole = t.floor() as usize;
+        let last = self.forward_hazards.len() - 1;
+        let full: f64 = self.forward_hazards.iter().take(whole).sum();
+        let beyond = whole.saturating_sub(last + 1) as f64 * self.forward_hazards[last];
+        full + beyond + (t - t.floor()) * self.forward_hazards[whole.min(last)]
+    }
+}
+
+impl CreditCurve<f64> for RatingHazardCurve {
+    fn hazard_rate(&self, t: f64) -> Result<f64, MarketDataError> {
+        if t < 0.0 {
+            return Err(MarketDataError::InvalidMaturity { t });
+        }
+        let year = (t.floor() as usize).min(self.forward_hazards.len() - 1);
+        Ok(self.forward_hazards[year])
+    }
+
+    fn survival_probability(&self, t: f64) -> Result<f64, MarketDataError> {
+        if t < 0.0 {
+            return Err(MarketDataError::InvalidMaturity { t });
+        }
+        Ok((-self.integrated_hazard(t)).exp())
+    }
+}
+
+/// Maps credit ratings to hazard curves and [`CreditParams`].
+///
+/// Intended for counterparties without observable CDS spreads: curves
+/// are implied from a transition matrix and built once per rating.
+///
+/// # Examples
+///
+/// ```
+/// use pricer_core::market_data::curves::CreditCurve;
+/// use pricer_risk::credit::{RatingCreditService, TransitionMatrix};
+/// use pricer_risk::portfolio::CreditRating;
+///
+/// let service = RatingCreditService::new(TransitionMatrix::standard(), 10).unwrap();
+///
+/// let curve = service.hazard_curve(CreditRating::BB).unwrap();
+/// let pd_3y = curve.default_probability(3.0).unwrap();
+/// assert!(pd_3y > 0.0);
+///
+/// // Flat-hazard parameters for the existing CVA machinery
+/// let params = service.credit_params(CreditRating::BB, 0.6).unwrap();
+/// assert_eq!(params.rating(), Some(CreditRating::BB));
+/// ```
+#[derive(Clone, Debug)]
+pub struct RatingCreditService {
+    matrix: TransitionMatrix,
+    horizon_years: u32,
+    curves: Vec<RatingHazardCurve>,
+}
+
+impl RatingCreditService {
+    /// Builds hazard curves for every non-default rating.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CreditError::InvalidHorizon` for a zero horizon.
+    pub fn new(matrix: TransitionMatrix, horizon_years: u32) -> Result<Self, CreditError> {
+        let curves = CreditRating::ALL
+            .iter()
+            .filter(|rating| !rating.is_default())
+            .map(|&rating| RatingHazardCurve::from_matrix(&matrix, rating, horizon_years))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            matrix,
+            horizon_years,
+            curves,
+        })
+    }
+
+    /// Returns the transition matrix.
+    #[inline]
+    pub fn matrix(&self) -> &TransitionMatrix {
+        &self.matrix
+    }
+
+    /// Returns the curve horizon in years.
+    #[inline]
+    pub fn horizon_years(&self) -> u32 {
+        self.horizon_years
+    }
+
+    /// Returns the hazard curve for `rating`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CreditError::DefaultedRating` for rating `D`.
+    pub fn hazard_curve(&self, rating: CreditRating) -> Result<&RatingHazardCurve, CreditError> {
+        // Curves are stored in rating order and exclude default
+        self.curves
+            .get(rating.index())
+            .ok_or(CreditError::DefaultedRating)
+    }
+
+    /// Returns survival probabilities for `rating` on a time grid.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CreditError::DefaultedRating` for rating `D` and
+    /// `CreditError::InvalidHorizon` for a negative time.
+    pub fn survival_probabilities(
+        &self,
+        rating: CreditRating,
+        time_grid: &[f64],
+    ) -> Result<Vec<f64>, CreditError> {
+        let curve = self.hazard_curve(rating)?;
+        time_grid
+            .iter()
+            .map(|&t| {
+                curve
+                    .survival_probability(t)
+                    .map_err(|_| CreditError::InvalidHorizon(t))
+            })
+            .collect()
+    }
+
+    /// Returns flat-hazard credit parameters for `rating`, matching the
+    /// curve's survival probability at the service horizon.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CreditError::DefaultedRating` for rating `D` and
+    /// `CreditError::Portfolio` for an LGD outside [0, 1].
+    pub fn credit_params(
+        &self,
+        rating: CreditRating,
+        lgd: f64,
+    ) -> Result<CreditParams, CreditError> {
+        let hazard = self
+            .hazard_curve(rating)?
+            .average_hazard_rate(f64::from(self.horizon_years));
+        Ok(CreditParams::new(hazard, lgd)?.with_rating(rating))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn service() -> RatingCreditService {
+        RatingCreditService::new(TransitionMatrix::standard(), 10).unwrap()
+    }
+
+    #[test]
+    fn test_curve_reproduces_matrix_default_probabilities() {
+        let matrix = TransitionMatrix::standard();
+        let service = service();
+        for rating in CreditRating::ALL.iter().filter(|r| !r.is_default()) {
+            let curve = service.hazard_curve(*rating).unwrap();
+            for years in [1, 3, 7, 10] {
+                assert_relative_eq!(
+                    curve.default_probability(f64::from(years)).unwrap(),
+                    matrix.cumulative_default_probability(*rating, years),
+                    epsilon = 1e-12
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_curve_interpolation_and_extrapolation() {
+        let curve = service().hazard_curve(CreditRating::B).unwrap().clone();
+        let hazards = curve.forward_hazards().to_vec();
+        assert_eq!(hazards.len(), 10);
+
+        assert_eq!(curve.hazard_rate(0.5).unwrap(), hazards[0]);
+        assert_eq!(curve.hazard_rate(2.5).unwrap(), hazards[2]);
+        assert_eq!(curve.hazard_rate(25.0).unwrap(), hazards[9]);
+
+        let s2 = curve.survival_probability(2.0).unwrap();
+        let s25 = curve.survival_probability(2.5).unwrap();
+        assert_relative_eq!(s25, s2 * (-0.5 * hazards[2]).exp(), epsilon = 1e-12);
+
+        let s10 = curve.survival_probability(10.0).unwrap();
+        let s12 = curve.survival_probability(12.0).unwrap();
+        assert_relative_eq!(s12, s10 * (-2.0 * hazards[9]).exp(), epsilon = 1e-12);
+        assert!(curve.survival_probability(-1.0).is_err());
+    }
+
+    #[test]
+    fn test_credit_params_match_horizon_survival() {
+        let service = service();
+        let params = service.credit_params(CreditRating::BBB, 0.6).unwrap();
+        let curve = service.hazard_curve(CreditRating::BBB).unwrap();
+
+        assert_relative_eq!(
+            params.survival_prob(10.0),
+            curve.survival_probability(10.0).unwrap(),
+            epsilon = 1e-12
+        );
+        assert_eq!(params.lgd(), 0.6);
+
+        let worse = service.credit_params(CreditRating::CCC, 0.6).unwrap();
+        assert!(worse.hazard_rate() > params.hazard_rate());
+    }
+
+    #[test]
+    fn test_service_errors() {
+        let service = service();
+        assert!(matches!(
+            service.hazard_curve(CreditRating::D),
+            Err(CreditError::DefaultedRating)
+        ));
+        assert!(matches!(
+            service.credit_params(CreditRating::A, 1.5),
+            Err(CreditError::Portfolio(_))
+        ));
+        assert!(matches!(
+            RatingCreditService::new(TransitionMatrix::standard(), 0),
+            Err(CreditError::InvalidHorizon(_))
+        ));
+
+        let survival = service
+            .survival_probabilities(CreditRating::A, &[0.0, 1.0, 2.0])
+            .unwrap();
+        assert_eq!(survival[0], 1.0);
+        assert!(survival[2] < survival[1]);
+    }
+}
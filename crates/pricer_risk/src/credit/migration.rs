@@ -0,0 +1,255 @@
+//! Monte Carlo rating migration over exposure horizons.
+
+use pricer_pricing::rng::PricerRng;
+use rayon::prelude::*;
+
+use super::transition::{TransitionMatrix, N_RATINGS};
+use super::CreditError;
+use crate::portfolio::CreditRating;
+
+/// Simulates rating paths on an exposure time grid.
+///
+/// Steps longer than a year are split into sub-steps of at most one year;
+/// each (sub-)step draws the next rating from
+/// [`TransitionMatrix::for_step`]. Default is absorbing.
+///
+/// # Examples
+///
+/// ```
+/// use pricer_risk::credit::{MigrationSimulator, TransitionMatrix};
+/// use pricer_risk::portfolio::CreditRating;
+///
+/// let simulator = MigrationSimulator::new(TransitionMatrix::standard(), 42);
+/// let grid = [0.0, 0.5, 1.0, 2.0, 5.0];
+///
+/// let paths = simulator.simulate(CreditRating::BB, &grid, 5_000).unwrap();
+///
+/// assert_eq!(paths.default_probability(0), 0.0);
+/// assert!(paths.default_probability(4) > paths.default_probability(2));
+/// ```
+#[derive(Clone, Debug)]
+pub struct MigrationSimulator {
+    matrix: TransitionMatrix,
+    seed: u64,
+}
+
+/// Simulated rating paths `[path_idx][time_idx]`.
+#[derive(Clone, Debug)]
+pub struct MigrationPaths {
+    time_grid: Vec<f64>,
+    ratings: Vec<Vec<CreditRating>>,
+}
+
+impl MigrationSimulator {
+    /// Creates a simulator. Path `p` draws from a generator seeded with
+    /// `seed + p`, so results do not depend on thread scheduling.
+    pub fn new(matrix: TransitionMatrix, seed: u64) -> Self {
+        Self { matrix, seed }
+    }
+
+    /// Simulates `n_paths` rating paths starting from `initial`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CreditError::InvalidTimeGrid` if the grid is empty, starts
+    /// below zero or is not non-decreasing.
+    pub fn simulate(
+        &self,
+        initial: CreditRating,
+        time_grid: &[f64],
+        n_paths: usize,
+    ) -> Result<MigrationPaths, CreditError> {
+        validate_grid(time_grid)?;
+
+        // Per grid step: number of sub-steps and their cumulative table
+        let steps: Vec<(usize, Vec<[f64; N_RATINGS]>)> = time_grid
+            .windows(2)
+            .map(|w| {
+                let dt = w[1] - w[0];
+                let n_sub = dt.ceil().max(1.0) as usize;
+                let sub = self.matrix.for_step(dt / n_sub as f64);
+                (n_sub, cumulative_rows(&sub))
+            })
+            .collect();
+
+        let ratings = (0..n_paths)
+            .into_par_iter()
+            .map(|p| {
+                let mut rng = PricerRng::from_seed(self.seed.wrapping_add(p as u64));
+                let mut current = initial;
+                let mut path = Vec::with_capacity(time_grid.len());
+                path.push(current);
+                for (n_sub, table) in &steps {
+                    for _ in 0..*n_sub {
+                        if !current.is_default() {
+                            current = draw(&table[current.index()], rng.gen_uniform());
+                        }
+                    }
+                    path.push(current);
+                }
+                path
+            })
+            .collect();
+
+        Ok(MigrationPaths {
+            time_grid: time_grid.to_vec(),
+            ratings,
+        })
+    }
+}
+
+impl MigrationPaths {
+    /// Returns the time grid.
+    #[inline]
+    pub fn time_grid(&self) -> &[f64] {
+        &self.time_grid
+    }
+
+    /// Returns the simulated ratings `[path_idx][time_idx]`.
+    #[inline]
+    pub fn ratings(&self) -> &[Vec<CreditRating>] {
+        &self.ratings
+    }
+
+    /// Returns the number of paths.
+    #[inline]
+    pub fn n_paths(&self) -> usize {
+        self.ratings.len()
+    }
+
+    /// Returns the fraction of paths in each rating at `time_idx`,
+    /// indexed like [`CreditRating::ALL`].
+    pub fn rating_distribution(&self, time_idx: usize) -> [f64; N_RATINGS] {
+        let mut distribution = [0.0; N_RATINGS];
+        if self.ratings.is_empty() {
+            return distribution;
+        }
+        for path in &self.ratings {
+            distribution[path[time_idx].index()] += 1.0;
+        }
+        let n = self.ratings.len() as f64;
+        distribution.iter_mut().for_each(|p| *p /= n);
+        distribution
+    }
+
+    /// Returns the fraction of paths in default at `time_idx`.
+    pub fn default_probability(&self, time_idx: usize) -> f64 {
+        self.rating_distribution(time_idx)[CreditRating::D.index()]
+    }
+
+    /// Returns, per path, the first grid time at which the path is in
+    /// default, or `None` if it survives the grid.
+    pub fn default_times(&self) -> Vec<Option<f64>> {
+        self.ratings
+            .iter()
+            .map(|path| {
+                path.iter()
+                    .position(CreditRating::is_default)
+                    .map(|i| self.time_grid[i])
+            })
+            .collect()
+    }
+}
+
+pub(super) fn validate_grid(time_grid: &[f64]) -> Result<(), CreditError> {
+    let starts_valid = time_grid.first().is_some_and(|&t| t >= 0.0);
+    let ordered = time_grid.windows(2).all(|w| w[1] >= w[0]);
+    if starts_valid && ordered {
+        Ok(())
+    } else {
+        Err(CreditError::InvalidTimeGrid)
+    }
+}
+
+fn cumulative_rows(matrix: &TransitionMatrix) -> Vec<[f64; N_RATINGS]> {
+    CreditRating::ALL
+        .iter()
+        .map(|&rating| {
+            let mut running = 0.0;
+            let mut cumulative = [0.0; N_RATINGS];
+            for (c, &p) in cumulative.iter_mut().zip(matrix.row(rating)) {
+                running += p;
+                *c = running;
+            }
+            cumulative
+        })
+        .collect()
+}
+
+fn draw(cumulative: &[f64; N_RATINGS], u: f64) -> CreditRating {
+    // Rows sum to one within tolerance; anything past the end stays in the
+    // last state with positive probability
+    let idx = cumulative
+        .iter()
+        .position(|&c| u < c)
+        .unwrap_or_else(|| cumulative.iter().rposition(|&c| c > 0.0).unwrap_or(0));
+    CreditRating::ALL[idx]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_annual_default_frequency_matches_matrix() {
+        let matrix = TransitionMatrix::standard();
+        let simulator = MigrationSimulator::new(matrix.clone(), 7);
+        let grid = [0.0, 1.0, 2.0, 3.0];
+
+        let paths = simulator.simulate(CreditRating::B, &grid, 20_000).unwrap();
+
+        for (i, years) in [(1, 1), (3, 3)] {
+            let expected = matrix.cumulative_default_probability(CreditRating::B, years);
+            let se = (expected * (1.0 - expected) / 20_000.0).sqrt();
+            assert!((paths.default_probability(i) - expected).abs() < 4.0 * se);
+        }
+
+        let distribution = paths.rating_distribution(1);
+        assert!((distribution.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_default_is_absorbing_and_paths_deterministic() {
+        let simulator = MigrationSimulator::new(TransitionMatrix::standard(), 3);
+        let grid: Vec<f64> = (0..=20).map(|i| i as f64 * 0.25).collect();
+
+        let paths = simulator.simulate(CreditRating::CCC, &grid, 500).unwrap();
+        for path in paths.ratings() {
+            if let Some(first) = path.iter().position(CreditRating::is_default) {
+                assert!(path[first..].iter().all(CreditRating::is_default));
+            }
+        }
+
+        let default_times = paths.default_times();
+        assert_eq!(default_times.len(), 500);
+        assert!(default_times.iter().any(Option::is_some));
+
+        let again = simulator.simulate(CreditRating::CCC, &grid, 500).unwrap();
+        assert_eq!(paths.ratings(), again.ratings());
+    }
+
+    #[test]
+    fn test_long_steps_are_subdivided() {
+        let matrix = TransitionMatrix::standard();
+        let simulator = MigrationSimulator::new(matrix.clone(), 11);
+
+        let paths = simulator
+            .simulate(CreditRating::BB, &[0.0, 5.0], 20_000)
+            .unwrap();
+
+        let expected = matrix.cumulative_default_probability(CreditRating::BB, 5);
+        let se = (expected * (1.0 - expected) / 20_000.0).sqrt();
+        assert!((paths.default_probability(1) - expected).abs() < 4.0 * se);
+    }
+
+    #[test]
+    fn test_invalid_grid() {
+        let simulator = MigrationSimulator::new(TransitionMatrix::standard(), 1);
+        for grid in [&[][..], &[-1.0, 1.0][..], &[0.0, 2.0, 1.0][..]] {
+            assert!(matches!(
+                simulator.simulate(CreditRating::A, grid, 10),
+                Err(CreditError::InvalidTimeGrid)
+            ));
+        }
+    }
+}
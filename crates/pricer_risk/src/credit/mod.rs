@@ -0,0 +1,109 @@
+//! Rating-based credit parameters.
+//!
+//! For counterparties without observable CDS spreads, default risk is
+//! implied from their rating through a one-year transition matrix:
+//!
+//! - [`TransitionMatrix`]: validated migration probabilities, with matrix
+//!   powers for multi-year horizons
+//! - [`RatingHazardCurve`]: piecewise-flat hazard curve reproducing the
+//!   matrix's cumulative default probabilities, usable as a
+//!   [`CreditCurve`](pricer_core::market_data::curves::CreditCurve)
+//! - [`RatingCreditService`]: curves and flat [`CreditParams`] per rating
+//! - [`MigrationSimulator`]: Monte Carlo rating paths on an exposure grid
+//! - [`DefaultSimulator`]: correlated default times across counterparties
+//!   through a Gaussian or Student t copula, giving the
+//!   [`LossDistribution`] of the portfolio's receivables with expected loss
+//!   and credit VaR
+//! - [`ProxyCurveBuilder`]: proxy hazard curves for names without CDS
+//!   quotes, regressed cross-sectionally on sector, rating and region
+//!   cohorts and assigned to a portfolio's unquoted counterparties
+//!
+//! [`CreditParams`]: crate::portfolio::CreditParams
+//!
+//! # Examples
+//!
+//! ```
+//! use pricer_risk::credit::{RatingCreditService, TransitionMatrix};
+//! use pricer_risk::portfolio::{Counterparty, CounterpartyId, CreditRating};
+//!
+//! let service = RatingCreditService::new(TransitionMatrix::standard(), 10).unwrap();
+//! let params = service.credit_params(CreditRating::BBB, 0.6).unwrap();
+//!
+//! let counterparty = Counterparty::new(CounterpartyId::new("UNLISTED"), params);
+//! assert!(counterparty.default_prob(5.0) > 0.0);
+//! ```
+
+mod curve;
+mod migration;
+mod proxy;
+mod simulation;
+mod transition;
+
+pub use curve::{RatingCreditService, RatingHazardCurve};
+pub use migration::{MigrationPaths, MigrationSimulator};
+pub use proxy::{
+    Cohort, CohortFactor, ProxyAssignment, ProxyCurveBuilder, ProxyCurveConfig, ProxyCurveModel,
+};
+pub use simulation::{
+    CounterpartyLoss, CreditExposure, DefaultCopula, DefaultSimulator, LossDistribution,
+};
+pub use transition::{TransitionMatrix, N_RATINGS};
+
+use pricer_core::market_data::error::MarketDataError;
+use thiserror::Error;
+
+use crate::portfolio::PortfolioError;
+
+/// Errors from rating-based credit modelling.
+#[derive(Debug, Error)]
+pub enum CreditError {
+    /// Transition matrix is not a valid stochastic matrix.
+    #[error("Invalid transition matrix: {0}")]
+    InvalidTransitionMatrix(String),
+
+    /// Horizon is zero or negative.
+    #[error("Invalid horizon: {0}")]
+    InvalidHorizon(f64),
+
+    /// Time grid is empty, negative or decreasing.
+    #[error("Time grid must be non-empty, non-negative and non-decreasing")]
+    InvalidTimeGrid,
+
+    /// A defaulted rating has no hazard curve.
+    #[error("Rating D is already in default")]
+    DefaultedRating,
+
+    /// Copula correlation is outside `[0, 1]`, or a t copula has zero
+    /// degrees of freedom.
+    #[error("Invalid copula correlation: {0}")]
+    InvalidCorrelation(f64),
+
+    /// Exposure profile is malformed, a counterparty is repeated, or no
+    /// paths were requested.
+    #[error("Invalid exposure: {0}")]
+    InvalidExposure(String),
+
+    /// Proxy curve configuration is malformed.
+    #[error("Invalid proxy curve config: {0}")]
+    InvalidProxyConfig(String),
+
+    /// A quoted name has no quotes, or a non-positive tenor or spread.
+    #[error("Invalid proxy quotes for {0}")]
+    InvalidProxyQuotes(String),
+
+    /// Cross-sectional regression is under-determined or collinear.
+    #[error("Proxy regression failed: {0}")]
+    ProxyRegression(String),
+
+    /// Cohort is incomplete or has a level absent from the quoted sample.
+    #[error("Unknown proxy cohort: {0}")]
+    UnknownCohort(String),
+
+    /// Proxy hazard curve could not be built.
+    #[error(transparent)]
+    Curve(#[from] MarketDataError),
+
+    /// Credit parameters could not be built.
+    #[error(transparent)]
+    Portfolio(#[from] PortfolioError),
+}
@@ -0,0 +1,581 @@
+//! Proxy credit curves from sector, rating and region cohorts.
+//!
+//! Counterparties without liquid CDS quotes are assigned a proxy curve
+//! estimated cross-sectionally from the names that do quote. At each
+//! configured tenor the log spreads of the quoted names are regressed on
+//! categorical dummies for their sector, rating and region:
+//!
+//! ```text
+//! ln s(i, T) = β₀(T) + β_sector(i)(T) + β_rating(i)(T) + β_region(i)(T) + ε
+//! ```
+//!
+//! The first level seen of each factor is the baseline. A cohort's proxy
+//! spread is the exponential of its fitted value, converted to a hazard
+//! rate by the credit triangle `λ = s / LGD`.
+
+use std::collections::BTreeMap;
+
+use pricer_core::market_data::curves::HazardRateCurve;
+
+use super::CreditError;
+use crate::portfolio::{CounterpartyId, CreditRating, Portfolio};
+
+/// Pivot magnitude below which the normal equations are singular.
+const SINGULAR_PIVOT: f64 = 1e-12;
+
+/// Cohort attribute used as a regression factor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CohortFactor {
+    /// Industry sector.
+    Sector,
+    /// Credit rating.
+    Rating,
+    /// Region (legal-entity jurisdiction).
+    Region,
+}
+
+/// Sector, rating and region of a name.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cohort {
+    /// Industry sector.
+    pub sector: String,
+    /// Credit rating.
+    pub rating: CreditRating,
+    /// Region.
+    pub region: String,
+}
+
+impl Cohort {
+    /// Creates a cohort.
+    pub fn new(sector: impl Into<String>, rating: CreditRating, region: impl Into<String>) -> Self {
+        Self {
+            sector: sector.into(),
+            rating,
+            region: region.into(),
+        }
+    }
+
+    /// Level of `factor` for this cohort.
+    fn level(&self, factor: CohortFactor) -> String {
+        match factor {
+            CohortFactor::Sector => self.sector.clone(),
+            CohortFactor::Rating => format!("{:?}", self.rating),
+            CohortFactor::Region => self.region.clone(),
+        }
+    }
+}
+
+/// Configuration of the proxy curve regression.
+///
+/// | Parameter | Default | Description |
+/// |-----------|---------|-------------|
+/// | `tenors` | 1, 3, 5, 7, 10 | Curve pillars in years |
+/// | `lgd` | 0.6 | Loss given default for the credit triangle |
+/// | `factors` | sector, rating, region | Regression factors |
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProxyCurveConfig {
+    /// Curve pillars in years, ascending.
+    pub tenors: Vec<f64>,
+    /// Loss given default converting spreads to hazard rates.
+    pub lgd: f64,
+    /// Factors included in the regression.
+    pub factors: Vec<CohortFactor>,
+}
+
+impl Default for ProxyCurveConfig {
+    fn default() -> Self {
+        Self {
+            tenors: vec![1.0, 3.0, 5.0, 7.0, 10.0],
+            lgd: 0.6,
+            factors: vec![
+                CohortFactor::Sector,
+                CohortFactor::Rating,
+                CohortFactor::Region,
+            ],
+        }
+    }
+}
+
+impl ProxyCurveConfig {
+    /// Sets the curve pillars.
+    pub fn with_tenors(mut self, tenors: Vec<f64>) -> Self {
+        self.tenors = tenors;
+        self
+    }
+
+    /// Sets the loss given default.
+    pub fn with_lgd(mut self, lgd: f64) -> Self {
+        self.lgd = lgd;
+        self
+    }
+
+    /// Sets the regression factors.
+    pub fn with_factors(mut self, factors: Vec<CohortFactor>) -> Self {
+        self.factors = factors;
+        self
+    }
+
+    fn validate(&self) -> Result<(), CreditError> {
+        let invalid = |reason: &str| Err(CreditError::InvalidProxyConfig(reason.to_string()));
+        if self.tenors.len() < 2 {
+            return invalid("at least two tenors are required");
+        }
+        if self.tenors[0] <= 0.0 || self.tenors.windows(2).any(|w| w[1] <= w[0]) {
+            return invalid("tenors must be positive and strictly increasing");
+        }
+        if !(self.lgd > 0.0 && self.lgd <= 1.0) {
+            return invalid("lgd must be in (0, 1]");
+        }
+        Ok(())
+    }
+}
+
+/// Fits proxy curves from the CDS quotes of liquid names.
+///
+/// # Examples
+///
+/// ```
+/// use pricer_core::market_data::curves::CreditCurve;
+/// use pricer_risk::credit::{Cohort, ProxyCurveBuilder, ProxyCurveConfig};
+/// use pricer_risk::portfolio::CreditRating;
+///
+/// let tenors = [1.0, 5.0];
+/// let model = ProxyCurveBuilder::new(ProxyCurveConfig::default().with_tenors(tenors.to_vec()))
+///     .with_quotes(Cohort::new("Energy", CreditRating::A, "EU"), &tenors, &[0.006, 0.010])
+///     .with_quotes(Cohort::new("Energy", CreditRating::BBB, "EU"), &tenors, &[0.012, 0.020])
+///     .with_quotes(Cohort::new("Banks", CreditRating::A, "EU"), &tenors, &[0.005, 0.008])
+///     .with_quotes(Cohort::new("Banks", CreditRating::A, "US"), &tenors, &[0.004, 0.007])
+///     .fit()
+///     .unwrap();
+///
+/// // No Banks/BBB/US name quotes, but every level has been seen
+/// let cohort = Cohort::new("Banks", CreditRating::BBB, "US");
+/// let spreads = model.spreads(&cohort).unwrap();
+/// assert!(spreads[1] > spreads[0]);
+///
+/// let curve = model.hazard_curve(&cohort).unwrap();
+/// assert!(curve.survival_probability(5.0).unwrap() < 1.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ProxyCurveBuilder {
+    config: ProxyCurveConfig,
+    observations: Vec<(Cohort, Vec<(f64, f64)>)>,
+}
+
+impl ProxyCurveBuilder {
+    /// Creates a builder with no observations.
+    pub fn new(config: ProxyCurveConfig) -> Self {
+        Self {
+            config,
+            observations: Vec::new(),
+        }
+    }
+
+    /// Adds the CDS par spreads of one quoted name, as decimals
+    /// (0.01 = 100bp), at the given tenors.
+    ///
+    /// Spreads are interpolated linearly onto the configured tenors and held
+    /// flat outside the quoted range.
+    pub fn with_quotes(mut self, cohort: Cohort, tenors: &[f64], spreads: &[f64]) -> Self {
+        let quotes = tenors
+            .iter()
+            .copied()
+            .zip(spreads.iter().copied())
+            .collect();
+        self.observations.push((cohort, quotes));
+        self
+    }
+
+    /// Number of quoted names.
+    pub fn len(&self) -> usize {
+        self.observations.len()
+    }
+
+    /// Returns true if no names have been added.
+    pub fn is_empty(&self) -> bool {
+        self.observations.is_empty()
+    }
+
+    /// Runs the cross-sectional regression at each tenor.
+    ///
+    /// # Errors
+    ///
+    /// - `CreditError::InvalidProxyConfig` for a malformed configuration
+    /// - `CreditError::InvalidProxyQuotes` for empty or non-positive quotes
+    /// - `CreditError::ProxyRegression` if there are fewer names than
+    ///   coefficients or the design is collinear
+    pub fn fit(&self) -> Result<ProxyCurveModel, CreditError> {
+        self.config.validate()?;
+
+        let mut levels: Vec<(CohortFactor, Vec<String>)> = self
+            .config
+            .factors
+            .iter()
+            .map(|&factor| (factor, Vec::new()))
+            .collect();
+        let mut log_spreads = Vec::with_capacity(self.observations.len());
+        for (cohort, quotes) in &self.observations {
+            if quotes.is_empty() || quotes.iter().any(|&(t, s)| !(t > 0.0 && s > 0.0)) {
+                return Err(CreditError::InvalidProxyQuotes(format!("{:?}", cohort)));
+            }
+            for (factor, seen) in &mut levels {
+                let level = cohort.level(*factor);
+                if !seen.contains(&level) {
+                    seen.push(level);
+                }
+            }
+            log_spreads.push(
+                self.config
+                    .tenors
+                    .iter()
+                    .map(|&t| interpolate(quotes, t).ln())
+                    .collect::<Vec<f64>>(),
+            );
+        }
+
+        let model = ProxyCurveModel {
+            config: self.config.clone(),
+            levels,
+            coefficients: Vec::new(),
+        };
+        let design: Vec<Vec<f64>> = self
+            .observations
+            .iter()
+            .map(|(cohort, _)| model.regressors(cohort))
+            .collect::<Result<_, _>>()?;
+        let n_params = model.n_params();
+        if design.len() < n_params {
+            return Err(CreditError::ProxyRegression(format!(
+                "{} quoted names for {} coefficients",
+                design.len(),
+                n_params
+            )));
+        }
+
+        let coefficients = (0..self.config.tenors.len())
+            .map(|j| {
+                let y: Vec<f64> = log_spreads.iter().map(|row| row[j]).collect();
+                least_squares(&design, &y)
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(ProxyCurveModel {
+            coefficients,
+            ..model
+        })
+    }
+}
+
+/// Fitted proxy curve regression.
+#[derive(Clone, Debug)]
+pub struct ProxyCurveModel {
+    config: ProxyCurveConfig,
+    levels: Vec<(CohortFactor, Vec<String>)>,
+    /// One coefficient vector per tenor: intercept, then the non-baseline
+    /// levels of each factor in order.
+    coefficients: Vec<Vec<f64>>,
+}
+
+impl ProxyCurveModel {
+    /// Configuration the model was fitted with.
+    pub fn config(&self) -> &ProxyCurveConfig {
+        &self.config
+    }
+
+    /// Proxy par spreads of `cohort` at the configured tenors.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CreditError::UnknownCohort` if a level of the cohort was not
+    /// represented among the quoted names.
+    pub fn spreads(&self, cohort: &Cohort) -> Result<Vec<f64>, CreditError> {
+        let x = self.regressors(cohort)?;
+        Ok(self
+            .coefficients
+            .iter()
+            .map(|beta| x.iter().zip(beta).map(|(a, b)| a * b).sum::<f64>().exp())
+            .collect())
+    }
+
+    /// Proxy hazard curve of `cohort`, `λ = s / LGD` at each tenor,
+    /// extrapolated flat.
+    pub fn hazard_curve(&self, cohort: &Cohort) -> Result<HazardRateCurve<f64>, CreditError> {
+        let hazards: Vec<f64> = self
+            .spreads(cohort)?
+            .into_iter()
+            .map(|s| s / self.config.lgd)
+            .collect();
+        Ok(HazardRateCurve::new(&self.config.tenors, &hazards, true)?)
+    }
+
+    /// Assigns proxy curves to the portfolio's counterparties without quotes.
+    ///
+    /// A counterparty's cohort is its sector, its rating and its region,
+    /// the jurisdiction inherited through the counterparty hierarchy.
+    /// Counterparties for which `has_quotes` is true are skipped.
+    /// Assignments are ordered by counterparty ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CreditError::UnknownCohort` if a counterparty lacks a sector,
+    /// rating or region, or one of its levels was not in the sample.
+    pub fn assign<F>(
+        &self,
+        portfolio: &Portfolio,
+        has_quotes: F,
+    ) -> Result<Vec<ProxyAssignment>, CreditError>
+    where
+        F: Fn(&CounterpartyId) -> bool,
+    {
+        let mut assignments = BTreeMap::new();
+        for counterparty in portfolio.counterparties() {
+            let id = counterparty.id();
+            if has_quotes(id) {
+                continue;
+            }
+            let missing = |what: &str| CreditError::UnknownCohort(format!("{}: no {}", id, what));
+            let cohort = Cohort::new(
+                counterparty.sector().ok_or_else(|| missing("sector"))?,
+                counterparty
+                    .credit_params()
+                    .rating()
+                    .ok_or_else(|| missing("rating"))?,
+                portfolio
+                    .jurisdiction(id)
+                    .ok_or_else(|| missing("region"))?,
+            );
+            let curve = self.hazard_curve(&cohort)?;
+            assignments.insert(
+                id.to_string(),
+                ProxyAssignment {
+                    counterparty: id.clone(),
+                    cohort,
+                    curve,
+                },
+            );
+        }
+        Ok(assignments.into_values().collect())
+    }
+
+    fn n_params(&self) -> usize {
+        1 + self
+            .levels
+            .iter()
+            .map(|(_, seen)| seen.len().saturating_sub(1))
+            .sum::<usize>()
+    }
+
+    /// Intercept followed by one dummy per non-baseline level.
+    fn regressors(&self, cohort: &Cohort) -> Result<Vec<f64>, CreditError> {
+        let mut x = vec![1.0];
+        for (factor, seen) in &self.levels {
+            let level = cohort.level(*factor);
+            let index = seen.iter().position(|l| *l == level).ok_or_else(|| {
+                CreditError::UnknownCohort(format!("{:?} {} not in sample", factor, level))
+            })?;
+            x.extend((1..seen.len()).map(|i| if i == index { 1.0 } else { 0.0 }));
+        }
+        Ok(x)
+    }
+}
+
+/// Proxy curve assigned to an unquoted counterparty.
+#[derive(Clone, Debug)]
+pub struct ProxyAssignment {
+    /// Counterparty receiving the proxy.
+    pub counterparty: CounterpartyId,
+    /// Cohort the proxy was built for.
+    pub cohort: Cohort,
+    /// Proxy hazard curve.
+    pub curve: HazardRateCurve<f64>,
+}
+
+/// Linear interpolation in `(tenor, spread)` quotes, flat outside.
+fn interpolate(quotes: &[(f64, f64)], t: f64) -> f64 {
+    let mut sorted = quotes.to_vec();
+    sorted.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let (first, last) = (sorted[0], sorted[sorted.len() - 1]);
+    if t <= first.0 {
+        return first.1;
+    }
+    if t >= last.0 {
+        return last.1;
+    }
+    let i = sorted.partition_point(|q| q.0 <= t);
+    let ((t0, s0), (t1, s1)) = (sorted[i - 1], sorted[i]);
+    s0 + (s1 - s0) * (t - t0) / (t1 - t0)
+}
+
+/// Ordinary least squares through the normal equations.
+fn least_squares(x: &[Vec<f64>], y: &[f64]) -> Result<Vec<f64>, CreditError> {
+    let p = x[0].len();
+    let mut a = vec![vec![0.0; p + 1]; p];
+    for (row, &yi) in x.iter().zip(y) {
+        for i in 0..p {
+            for j in 0..p {
+                a[i][j] += row[i] * row[j];
+            }
+            a[i][p] += row[i] * yi;
+        }
+    }
+
+    for col in 0..p {
+        let pivot = (col..p)
+            .max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))
+            .unwrap_or(col);
+        if a[pivot][col].abs() < SINGULAR_PIVOT {
+            return Err(CreditError::ProxyRegression(
+                "collinear cohort factors".to_string(),
+            ));
+        }
+        a.swap(col, pivot);
+        for row in col + 1..p {
+            let factor = a[row][col] / a[col][col];
+            for k in col..=p {
+                a[row][k] -= factor * a[col][k];
+            }
+        }
+    }
+
+    let mut beta = vec![0.0; p];
+    for i in (0..p).rev() {
+        let tail: f64 = (i + 1..p).map(|k| a[i][k] * beta[k]).sum();
+        beta[i] = (a[i][p] - tail) / a[i][i];
+    }
+    Ok(beta)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::portfolio::{
+        Counterparty, CreditParams, NettingSet, NettingSetId, PortfolioBuilder,
+    };
+    use approx::assert_relative_eq;
+    use pricer_core::market_data::curves::CreditCurve;
+
+    const TENORS: [f64; 2] = [1.0, 5.0];
+
+    /// Spreads generated from an exactly multiplicative model.
+    fn spread(sector: &str, rating: CreditRating, region: &str, tenor: f64) -> f64 {
+        let sector = if sector == "Energy" { 1.5 } else { 1.0 };
+        let rating = if rating == CreditRating::BBB {
+            2.0
+        } else {
+            1.0
+        };
+        let region = if region == "US" { 0.8 } else { 1.0 };
+        0.005 * sector * rating * region * (1.0 + 0.1 * tenor)
+    }
+
+    fn builder() -> ProxyCurveBuilder {
+        let names = [
+            ("Energy", CreditRating::A, "EU"),
+            ("Energy", CreditRating::BBB, "EU"),
+            ("Banks", CreditRating::A, "EU"),
+            ("Banks", CreditRating::A, "US"),
+            ("Energy", CreditRating::BBB, "US"),
+        ];
+        names.iter().fold(
+            ProxyCurveBuilder::new(ProxyCurveConfig::default().with_tenors(TENORS.to_vec())),
+            |b, &(sector, rating, region)| {
+                let spreads: Vec<f64> = TENORS
+                    .iter()
+                    .map(|&t| spread(sector, rating, region, t))
+                    .collect();
+                b.with_quotes(Cohort::new(sector, rating, region), &TENORS, &spreads)
+            },
+        )
+    }
+
+    #[test]
+    fn test_regression_recovers_multiplicative_cohort_effects() {
+        let model = builder().fit().unwrap();
+        let cohort = Cohort::new("Banks", CreditRating::BBB, "US");
+        let spreads = model.spreads(&cohort).unwrap();
+        for (&t, s) in TENORS.iter().zip(&spreads) {
+            assert_relative_eq!(
+                *s,
+                spread("Banks", CreditRating::BBB, "US", t),
+                epsilon = 1e-12
+            );
+        }
+
+        let curve = model.hazard_curve(&cohort).unwrap();
+        assert_relative_eq!(
+            curve.hazard_rate(5.0).unwrap(),
+            spreads[1] / 0.6,
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn test_quotes_are_interpolated_onto_tenors() {
+        assert_relative_eq!(interpolate(&[(5.0, 0.02), (1.0, 0.01)], 3.0), 0.015);
+        assert_relative_eq!(interpolate(&[(1.0, 0.01), (5.0, 0.02)], 10.0), 0.02);
+    }
+
+    #[test]
+    fn test_unknown_levels_and_small_samples_fail() {
+        let model = builder().fit().unwrap();
+        assert!(matches!(
+            model.spreads(&Cohort::new("Utilities", CreditRating::A, "EU")),
+            Err(CreditError::UnknownCohort(_))
+        ));
+
+        let small = ProxyCurveBuilder::new(ProxyCurveConfig::default()).with_quotes(
+            Cohort::new("Banks", CreditRating::A, "EU"),
+            &[5.0],
+            &[0.01],
+        );
+        // One name fits the intercept alone
+        assert!(small.fit().is_ok());
+        let small = small.with_quotes(
+            Cohort::new("Energy", CreditRating::BBB, "US"),
+            &[5.0],
+            &[0.02],
+        );
+        assert!(matches!(small.fit(), Err(CreditError::ProxyRegression(_))));
+    }
+
+    #[test]
+    fn test_assign_skips_quoted_counterparties() {
+        let model = builder().fit().unwrap();
+        let counterparty = |id: &str, rating: CreditRating| {
+            Counterparty::new(
+                CounterpartyId::new(id),
+                CreditParams::new(0.02, 0.6).unwrap().with_rating(rating),
+            )
+            .with_sector("Banks")
+        };
+        let portfolio = PortfolioBuilder::new()
+            .add_counterparty(counterparty("PARENT", CreditRating::A).with_jurisdiction("US"))
+            .add_counterparty(
+                counterparty("SUB", CreditRating::BBB).with_parent(CounterpartyId::new("PARENT")),
+            )
+            .add_counterparty(counterparty("LIQUID", CreditRating::A).with_jurisdiction("EU"))
+            .add_netting_set(NettingSet::new(
+                NettingSetId::new("NS"),
+                CounterpartyId::new("PARENT"),
+            ))
+            .build()
+            .unwrap();
+
+        let assignments = model
+            .assign(&portfolio, |id| id.as_str() == "LIQUID")
+            .unwrap();
+        let ids: Vec<&str> = assignments
+            .iter()
+            .map(|a| a.counterparty.as_str())
+            .collect();
+        assert_eq!(ids, vec!["PARENT", "SUB"]);
+        // The subsidiary inherits its parent's region
+        assert_eq!(
+            assignments[1].cohort,
+            Cohort::new("Banks", CreditRating::BBB, "US")
+        );
+    }
+}
@@ -0,0 +1,245 @@
+//! Rating transition matrices.
+
+use super::CreditError;
+use crate::portfolio::CreditRating;
+
+/// Number of rating states, including default.
+pub const N_RATINGS: usize = CreditRating::ALL.len();
+
+/// Tolerance on row sums of a transition matrix.
+const ROW_SUM_TOLERANCE: f64 = 1e-6;
+
+/// One-year rating transition matrix.
+///
+/// Row `i`, column `j` is the probability of migrating from
+/// `CreditRating::ALL[i]` to `CreditRating::ALL[j]` within one year. Rows
+/// are probability distributions and default (`D`) is absorbing.
+///
+/// # Examples
+///
+/// ```
+/// use pricer_risk::credit::TransitionMatrix;
+/// use pricer_risk::portfolio::CreditRating;
+///
+/// let matrix = TransitionMatrix::standard();
+///
+/// let pd_1y = matrix.probability(CreditRating::BBB, CreditRating::D);
+/// let pd_5y = matrix.cumulative_default_probability(CreditRating::BBB, 5);
+/// assert!(pd_5y > 4.0 * pd_1y);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransitionMatrix {
+    rows: [[f64; N_RATINGS]; N_RATINGS],
+}
+
+impl TransitionMatrix {
+    /// Creates a transition matrix from one-year migration probabilities.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CreditError::InvalidTransitionMatrix` if an entry is
+    /// negative or not finite, a row does not sum to one, or default is
+    /// not absorbing.
+    pub fn new(rows: [[f64; N_RATINGS]; N_RATINGS]) -> Result<Self, CreditError> {
+        for (i, row) in rows.iter().enumerate() {
+            let from = CreditRating::ALL[i];
+            if row.iter().any(|p| !(p.is_finite() && *p >= 0.0)) {
+                return Err(CreditError::InvalidTransitionMatrix(format!(
+                    "row {:?} has a negative or non-finite probability",
+                    from
+                )));
+            }
+            let sum: f64 = row.iter().sum();
+            if (sum - 1.0).abs() > ROW_SUM_TOLERANCE {
+                return Err(CreditError::InvalidTransitionMatrix(format!(
+                    "row {:?} sums to {}",
+                    from, sum
+                )));
+            }
+        }
+        let default = CreditRating::D.index();
+        if (rows[default][default] - 1.0).abs() > ROW_SUM_TOLERANCE {
+            return Err(CreditError::InvalidTransitionMatrix(
+                "default must be absorbing".to_string(),
+            ));
+        }
+        Ok(Self { rows })
+    }
+
+    /// Returns an indicative one-year matrix in the shape of published
+    /// agency averages.
+    ///
+    /// The figures are illustrative only; calibrate to the agency or
+    /// internal matrix in use for production figures.
+    pub fn standard() -> Self {
+        // Off-diagonal probabilities; the diagonal takes the remainder.
+        //          AAA     AA      A       BBB     BB      B       CCC     CC      C       D
+        let off_diagonal = [
+            [
+                0.0, 0.0850, 0.0050, 0.0006, 0.0008, 0.0003, 0.0005, 0.0, 0.0, 0.0001,
+            ],
+            [
+                0.0060, 0.0, 0.0800, 0.0050, 0.0006, 0.0008, 0.0002, 0.0, 0.0, 0.0002,
+            ],
+            [
+                0.0004, 0.0200, 0.0, 0.0550, 0.0040, 0.0015, 0.0002, 0.0, 0.0, 0.0006,
+            ],
+            [
+                0.0001, 0.0010, 0.0380, 0.0, 0.0400, 0.0070, 0.0015, 0.0, 0.0, 0.0018,
+            ],
+            [
+                0.0, 0.0003, 0.0010, 0.0530, 0.0, 0.0750, 0.0080, 0.0010, 0.0, 0.0070,
+            ],
+            [
+                0.0, 0.0002, 0.0008, 0.0020, 0.0550, 0.0, 0.0450, 0.0050, 0.0020, 0.0350,
+            ],
+            [
+                0.0, 0.0, 0.0015, 0.0020, 0.0060, 0.1300, 0.0, 0.0800, 0.0300, 0.2500,
+            ],
+            [0.0, 0.0, 0.0, 0.0, 0.0, 0.0500, 0.1500, 0.0, 0.1500, 0.3500],
+            [0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.1000, 0.1000, 0.0, 0.5000],
+            [0.0; N_RATINGS],
+        ];
+
+        let mut rows = off_diagonal;
+        for (i, row) in rows.iter_mut().enumerate() {
+            row[i] = 1.0 - row.iter().sum::<f64>();
+        }
+        Self::new(rows).expect("standard matrix is valid")
+    }
+
+    /// Returns the one-year probability of migrating from `from` to `to`.
+    #[inline]
+    pub fn probability(&self, from: CreditRating, to: CreditRating) -> f64 {
+        self.rows[from.index()][to.index()]
+    }
+
+    /// Returns the one-year migration distribution from `from`.
+    #[inline]
+    pub fn row(&self, from: CreditRating) -> &[f64; N_RATINGS] {
+        &self.rows[from.index()]
+    }
+
+    /// Returns the `years`-year transition matrix (the matrix power).
+    pub fn power(&self, years: u32) -> Self {
+        let mut result = Self::identity();
+        for _ in 0..years {
+            result = result.then(self);
+        }
+        result
+    }
+
+    /// Returns the transition matrix for a sub-annual step `dt` ≤ 1,
+    /// using the first-order approximation `I + dt·(P - I)`.
+    ///
+    /// The result is a valid transition matrix for `dt` in [0, 1] and
+    /// equals the one-year matrix at `dt = 1`.
+    pub fn for_step(&self, dt: f64) -> Self {
+        let dt = dt.clamp(0.0, 1.0);
+        let mut rows = self.rows;
+        for (i, row) in rows.iter_mut().enumerate() {
+            for (j, p) in row.iter_mut().enumerate() {
+                let identity = if i == j { 1.0 } else { 0.0 };
+                *p = identity + dt * (*p - identity);
+            }
+        }
+        Self { rows }
+    }
+
+    /// Returns the probability of defaulting within `years` from `rating`.
+    pub fn cumulative_default_probability(&self, rating: CreditRating, years: u32) -> f64 {
+        self.power(years).probability(rating, CreditRating::D)
+    }
+
+    pub(crate) fn identity() -> Self {
+        let mut rows = [[0.0; N_RATINGS]; N_RATINGS];
+        for (i, row) in rows.iter_mut().enumerate() {
+            row[i] = 1.0;
+        }
+        Self { rows }
+    }
+
+    /// Returns the matrix product `self · next`: migrations under `self`
+    /// followed by migrations under `next`.
+    pub(crate) fn then(&self, next: &Self) -> Self {
+        let mut rows = [[0.0; N_RATINGS]; N_RATINGS];
+        for (i, row) in rows.iter_mut().enumerate() {
+            for (k, &p_ik) in self.rows[i].iter().enumerate() {
+                for (j, p) in row.iter_mut().enumerate() {
+                    *p += p_ik * next.rows[k][j];
+                }
+            }
+        }
+        Self { rows }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_standard_matrix_is_valid() {
+        let matrix = TransitionMatrix::standard();
+        for rating in CreditRating::ALL {
+            assert_relative_eq!(matrix.row(rating).iter().sum::<f64>(), 1.0, epsilon = 1e-12);
+        }
+        assert_eq!(matrix.probability(CreditRating::D, CreditRating::D), 1.0);
+        assert!(matrix.probability(CreditRating::AAA, CreditRating::AAA) > 0.9);
+    }
+
+    #[test]
+    fn test_default_probability_monotone_in_rating_and_time() {
+        let matrix = TransitionMatrix::standard();
+        for years in [1, 5, 10] {
+            let pds: Vec<f64> = CreditRating::ALL
+                .iter()
+                .map(|&r| matrix.cumulative_default_probability(r, years))
+                .collect();
+            for pair in pds.windows(2) {
+                assert!(pair[0] < pair[1]);
+            }
+        }
+        for rating in CreditRating::ALL {
+            let pd1 = matrix.cumulative_default_probability(rating, 1);
+            let pd5 = matrix.cumulative_default_probability(rating, 5);
+            assert!(pd5 >= pd1);
+        }
+    }
+
+    #[test]
+    fn test_power_and_step() {
+        let matrix = TransitionMatrix::standard();
+        assert_eq!(matrix.power(1), matrix);
+        assert_eq!(matrix.power(0), TransitionMatrix::identity());
+        assert_eq!(matrix.for_step(1.0), matrix);
+        assert_eq!(matrix.for_step(0.0), TransitionMatrix::identity());
+
+        let quarter = matrix.for_step(0.25);
+        for rating in CreditRating::ALL {
+            assert_relative_eq!(
+                quarter.row(rating).iter().sum::<f64>(),
+                1.0,
+                epsilon = 1e-12
+            );
+        }
+    }
+
+    #[test]
+    fn test_invalid_matrices() {
+        let mut rows = TransitionMatrix::standard().rows;
+        rows[0][0] += 0.1;
+        assert!(TransitionMatrix::new(rows).is_err());
+
+        let mut rows = TransitionMatrix::standard().rows;
+        rows[1][1] += 0.1;
+        rows[1][0] -= 0.1;
+        assert!(TransitionMatrix::new(rows).is_err());
+
+        let mut rows = TransitionMatrix::standard().rows;
+        rows[9] = [0.1, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.9];
+        assert!(TransitionMatrix::new(rows).is_err());
+    }
+}
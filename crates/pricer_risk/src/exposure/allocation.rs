@@ -0,0 +1,275 @@
+//! Euler allocation of netting-set exposure to trades.
+//!
+//! Netted exposure is homogeneous of degree one in the trade values, so
+//! by Euler's theorem it splits into marginal contributions that add up to
+//! the netting-set figure:
+//!
+//! ```text
+//! EEᵢ(t)  = E[Vᵢ(t) · 1{V(t) > 0}]
+//! PFEᵢ(t) = E[Vᵢ(t) | V(t) = PFE(t)]
+//! ```
+//!
+//! The PFE contribution is estimated on the paths ranked next to the
+//! quantile and rescaled to sum exactly to the netting set's PFE. A trade
+//! that hedges the others receives a negative allocation.
+
+use rayon::prelude::*;
+
+use super::ExposureCalculator;
+
+/// Half-width of the PFE estimation window, as a fraction of the scenario
+/// count.
+const PFE_WINDOW: f64 = 0.005;
+
+/// Netting-set exposure allocated to its trades.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExposureAllocation {
+    /// Time points in years
+    pub time_grid: Vec<f64>,
+    /// Netted expected exposure at each time point
+    pub expected_exposure: Vec<f64>,
+    /// Netted potential future exposure at each time point
+    pub potential_future_exposure: Vec<f64>,
+    /// EE contribution of each trade, `[trade_idx][time_idx]`
+    pub trade_ee: Vec<Vec<f64>>,
+    /// PFE contribution of each trade, `[trade_idx][time_idx]`
+    pub trade_pfe: Vec<Vec<f64>>,
+}
+
+impl ExposureAllocation {
+    /// Returns the number of trades.
+    #[inline]
+    pub fn n_trades(&self) -> usize {
+        self.trade_ee.len()
+    }
+
+    /// Returns the netting set's EPE.
+    pub fn expected_positive_exposure(&self) -> f64 {
+        ExposureCalculator::expected_positive_exposure(&self.expected_exposure, &self.time_grid)
+    }
+
+    /// Returns each trade's contribution to the netting set's EPE.
+    ///
+    /// EPE is linear in the EE profile, so the contributions sum to
+    /// [`expected_positive_exposure`](Self::expected_positive_exposure).
+    pub fn trade_epe(&self) -> Vec<f64> {
+        self.trade_ee
+            .iter()
+            .map(|ee| ExposureCalculator::expected_positive_exposure(ee, &self.time_grid))
+            .collect()
+    }
+
+    /// Returns the time index of the netting set's peak PFE.
+    pub fn peak_index(&self) -> Option<usize> {
+        self.potential_future_exposure
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(i, _)| i)
+    }
+
+    /// Returns the fraction of a credit limit used by each trade, measured
+    /// at the netting set's peak PFE.
+    ///
+    /// The fractions sum to the netting set's peak PFE over `limit`.
+    pub fn limit_utilisation(&self, limit: f64) -> Vec<f64> {
+        match self.peak_index() {
+            Some(t) => self.trade_pfe.iter().map(|pfe| pfe[t] / limit).collect(),
+            None => vec![0.0; self.n_trades()],
+        }
+    }
+}
+
+impl ExposureCalculator {
+    /// Allocates a netting set's EE and PFE to its trades on the simulated
+    /// paths.
+    ///
+    /// Trade contributions sum to the netted EE and PFE at every time
+    /// point; see [`euler_contributions`](Self::euler_contributions) for the
+    /// EE allocation alone.
+    ///
+    /// # Arguments
+    ///
+    /// * `trade_values` - Simulated values `[trade_idx][scenario_idx][time_idx]`
+    /// * `time_grid` - Time points in years
+    /// * `confidence` - PFE confidence level (e.g., 0.95)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pricer_risk::exposure::ExposureCalculator;
+    ///
+    /// let trade_values = vec![
+    ///     vec![vec![0.0, 10.0], vec![0.0, 2.0], vec![0.0, -5.0]], // Trade 1
+    ///     vec![vec![0.0, -4.0], vec![0.0, -6.0], vec![0.0, 1.0]], // Trade 2
+    /// ];
+    ///
+    /// let allocation = ExposureCalculator::allocate_exposure(&trade_values, &[0.0, 1.0], 0.95);
+    ///
+    /// // Trade 2 offsets trade 1 on the only path with positive exposure
+    /// assert_eq!(allocation.trade_ee[0][1], 10.0 / 3.0);
+    /// assert_eq!(allocation.trade_ee[1][1], -4.0 / 3.0);
+    /// let utilisation = allocation.limit_utilisation(10.0);
+    /// assert!((utilisation.iter().sum::<f64>() - 0.6).abs() < 1e-12);
+    /// ```
+    pub fn allocate_exposure(
+        trade_values: &[Vec<Vec<f64>>],
+        time_grid: &[f64],
+        confidence: f64,
+    ) -> ExposureAllocation {
+        let net = Self::net_values(trade_values);
+        let expected_exposure = Self::expected_exposure(&net);
+        let potential_future_exposure = Self::potential_future_exposure(&net, confidence);
+        let (trade_ee, _) = Self::euler_contributions(trade_values);
+
+        let n_scenarios = net.len();
+        let n_times = expected_exposure.len();
+        let quantile_idx = if n_scenarios == 0 {
+            0
+        } else {
+            (((n_scenarios as f64 - 1.0) * confidence.clamp(0.0, 1.0)).round() as usize)
+                .min(n_scenarios - 1)
+        };
+        let half_width = ((n_scenarios as f64 * PFE_WINDOW).ceil() as usize).max(1);
+
+        // [time_idx][trade_idx]
+        let pfe_by_time: Vec<Vec<f64>> = (0..n_times)
+            .into_par_iter()
+            .map(|t| {
+                let mut order: Vec<usize> = (0..n_scenarios).collect();
+                order.sort_by(|&a, &b| net[a][t].max(0.0).total_cmp(&net[b][t].max(0.0)));
+                let window = &order[quantile_idx.saturating_sub(half_width)
+                    ..(quantile_idx + half_width + 1).min(n_scenarios)];
+                let mean = |f: &dyn Fn(usize) -> f64| {
+                    window.iter().map(|&s| f(s)).sum::<f64>() / window.len() as f64
+                };
+                let window_net = mean(&|s| net[s][t]);
+                let pfe = potential_future_exposure[t];
+                trade_values
+                    .iter()
+                    .map(|trade| {
+                        if window_net > 0.0 {
+                            mean(&|s| trade[s][t]) * pfe / window_net
+                        } else {
+                            0.0
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        let trade_pfe = (0..trade_values.len())
+            .map(|i| pfe_by_time.iter().map(|row| row[i]).collect())
+            .collect();
+
+        ExposureAllocation {
+            time_grid: time_grid.to_vec(),
+            expected_exposure,
+            potential_future_exposure,
+            trade_ee,
+            trade_pfe,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    /// Two correlated trades and a partial hedge on a deterministic grid of
+    /// scenarios.
+    fn trade_values(n_scenarios: usize) -> Vec<Vec<Vec<f64>>> {
+        let shocks: Vec<f64> = (0..n_scenarios)
+            .map(|s| (s as f64 + 0.5) / n_scenarios as f64 - 0.5)
+            .collect();
+        let trade = |scale: f64, offset: f64| -> Vec<Vec<f64>> {
+            shocks
+                .iter()
+                .enumerate()
+                .map(|(s, &z)| {
+                    let wobble = ((s * 7919) % 13) as f64 - 6.0;
+                    (1..=3)
+                        .map(|t| scale * z * t as f64 + offset + 0.1 * wobble)
+                        .collect()
+                })
+                .collect()
+        };
+        vec![trade(100.0, 5.0), trade(40.0, 0.0), trade(-60.0, 2.0)]
+    }
+
+    #[test]
+    fn test_contributions_sum_to_netting_set() {
+        let values = trade_values(2_000);
+        let allocation = ExposureCalculator::allocate_exposure(&values, &[1.0, 2.0, 3.0], 0.95);
+
+        assert_eq!(allocation.n_trades(), 3);
+        for t in 0..3 {
+            let ee: f64 = allocation.trade_ee.iter().map(|c| c[t]).sum();
+            let pfe: f64 = allocation.trade_pfe.iter().map(|c| c[t]).sum();
+            assert_relative_eq!(ee, allocation.expected_exposure[t], epsilon = 1e-10);
+            assert_relative_eq!(
+                pfe,
+                allocation.potential_future_exposure[t],
+                epsilon = 1e-10
+            );
+        }
+        assert_relative_eq!(
+            allocation.trade_epe().iter().sum::<f64>(),
+            allocation.expected_positive_exposure(),
+            epsilon = 1e-10
+        );
+
+        // The hedge reduces both EE and PFE
+        assert!(allocation.trade_ee[2][2] < 0.0);
+        assert!(allocation.trade_pfe[2][2] < 0.0);
+        assert!(allocation.trade_pfe[0][2] > allocation.trade_pfe[1][2]);
+    }
+
+    #[test]
+    fn test_pfe_allocation_is_proportional_for_scaled_trades() {
+        // Trade 2 is twice trade 1, so it takes two thirds of everything
+        let base = trade_values(1_000).remove(0);
+        let doubled = base
+            .iter()
+            .map(|path| path.iter().map(|v| 2.0 * v).collect())
+            .collect();
+        let allocation =
+            ExposureCalculator::allocate_exposure(&[base, doubled], &[1.0, 2.0, 3.0], 0.99);
+        for t in 0..3 {
+            assert_relative_eq!(
+                allocation.trade_pfe[1][t],
+                2.0 * allocation.trade_pfe[0][t],
+                max_relative = 1e-10
+            );
+        }
+    }
+
+    #[test]
+    fn test_limit_utilisation_at_peak() {
+        let values = trade_values(500);
+        let allocation = ExposureCalculator::allocate_exposure(&values, &[1.0, 2.0, 3.0], 0.95);
+        let peak = allocation.peak_index().unwrap();
+        assert_eq!(peak, 2);
+
+        let utilisation = allocation.limit_utilisation(100.0);
+        assert_relative_eq!(
+            utilisation.iter().sum::<f64>(),
+            allocation.potential_future_exposure[peak] / 100.0,
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn test_out_of_the_money_netting_set() {
+        let values = vec![vec![vec![-1.0, -2.0]; 10], vec![vec![-3.0, -1.0]; 10]];
+        let allocation = ExposureCalculator::allocate_exposure(&values, &[0.5, 1.0], 0.95);
+        assert_eq!(allocation.potential_future_exposure, vec![0.0, 0.0]);
+        assert!(allocation.trade_pfe.iter().flatten().all(|&v| v == 0.0));
+        assert_eq!(allocation.limit_utilisation(1.0), vec![0.0, 0.0]);
+
+        let empty = ExposureCalculator::allocate_exposure(&[], &[], 0.95);
+        assert_eq!(empty.n_trades(), 0);
+        assert_eq!(empty.peak_index(), None);
+    }
+}
@@ -0,0 +1,341 @@
+//! Collateral simulation over exposure scenarios.
+//!
+//! Given simulated netting-set values and a [`CollateralAgreement`], the
+//! simulator rebuilds the collateral balance along each path and returns
+//! collateralised values:
+//!
+//! - Collateral is called on every grid date both ways: the counterparty
+//!   posts V - threshold when V exceeds its threshold, and we post
+//!   V + own threshold (a negative balance) when -V exceeds ours. The
+//!   balance only moves when the change reaches the minimum transfer
+//!   amount.
+//! - At time t the balance held is the one called at t - MPoR, so the
+//!   exposure that builds up over the margin period of risk is not covered.
+//! - Over that lag the market value of the posted collateral moves with
+//!   the asset's price volatility and, when collateral and exposure
+//!   currencies differ, the FX volatility (driftless lognormal).
+//! - The moved value is credited after the agreement's effective haircut
+//!   (asset haircut plus FX haircut on a currency mismatch): collateral
+//!   received is worth `1 - h` of its market value, collateral posted
+//!   costs `1 + h`.
+
+use pricer_core::types::Currency;
+use pricer_pricing::rng::PricerRng;
+use rayon::prelude::*;
+
+use crate::portfolio::CollateralAgreement;
+
+/// Tolerance when locating the collateral call date one MPoR back.
+const TIME_EPSILON: f64 = 1e-12;
+
+/// Simulates posted collateral along exposure paths.
+///
+/// # Examples
+///
+/// ```
+/// use pricer_core::types::Currency;
+/// use pricer_risk::exposure::{CollateralSimulator, ExposureCalculator};
+/// use pricer_risk::portfolio::{CollateralAgreement, CollateralAsset};
+///
+/// let csa = CollateralAgreement::zero_threshold(Currency::EUR, 0.25)
+///     .unwrap()
+///     .with_asset(CollateralAsset::security(0.02, 0.05).unwrap());
+/// let time_grid = vec![0.0, 0.25, 0.5, 0.75, 1.0];
+/// let values = vec![vec![0.0, 10.0, 20.0, 15.0, 5.0]; 100];
+///
+/// let collateralised = CollateralSimulator::new(7)
+///     .with_fx_volatility(0.1)
+///     .collateralised_values(&values, &time_grid, &csa, Currency::USD);
+///
+/// let ee = ExposureCalculator::expected_exposure(&collateralised);
+/// let ee_uncollateralised = ExposureCalculator::expected_exposure(&values);
+/// assert!(ee[4] < ee_uncollateralised[4]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct CollateralSimulator {
+    fx_volatility: f64,
+    seed: u64,
+}
+
+impl CollateralSimulator {
+    /// Creates a simulator with no FX volatility.
+    ///
+    /// Scenario `s` draws from a generator seeded with `seed + s`, so
+    /// results do not depend on thread scheduling.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            fx_volatility: 0.0,
+            seed,
+        }
+    }
+
+    /// Sets the annualised volatility of the collateral/exposure FX rate,
+    /// applied only when the currencies differ.
+    pub fn with_fx_volatility(mut self, fx_volatility: f64) -> Self {
+        self.fx_volatility = fx_volatility.max(0.0);
+        self
+    }
+
+    /// Returns the FX volatility.
+    #[inline]
+    pub fn fx_volatility(&self) -> f64 {
+        self.fx_volatility
+    }
+
+    /// Returns the annualised volatility of the posted collateral's value
+    /// in `exposure_currency`.
+    pub fn collateral_volatility(
+        &self,
+        agreement: &CollateralAgreement,
+        exposure_currency: Currency,
+    ) -> f64 {
+        let asset = agreement.asset().volatility();
+        let fx = if agreement.has_fx_mismatch(exposure_currency) {
+            self.fx_volatility
+        } else {
+            0.0
+        };
+        // Asset price and FX rate are taken as independent
+        (asset * asset + fx * fx).sqrt()
+    }
+
+    /// Computes collateralised values `V(t) - C(t)` for each scenario.
+    ///
+    /// # Arguments
+    ///
+    /// * `values` - Netting-set values in `exposure_currency`, `[scenario_idx][time_idx]`
+    /// * `time_grid` - Increasing time points in years
+    /// * `agreement` - Collateral terms
+    /// * `exposure_currency` - Currency of `values`
+    ///
+    /// # Returns
+    ///
+    /// Collateralised values with the same shape as `values`; pass them to
+    /// [`ExposureCalculator`](super::ExposureCalculator) for EE, ENE or PFE.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a scenario's length differs from `time_grid.len()`.
+    pub fn collateralised_values(
+        &self,
+        values: &[Vec<f64>],
+        time_grid: &[f64],
+        agreement: &CollateralAgreement,
+        exposure_currency: Currency,
+    ) -> Vec<Vec<f64>> {
+        let lags = call_indices(time_grid, agreement.mpor());
+        let volatility = self.collateral_volatility(agreement, exposure_currency);
+        let haircut = agreement.effective_haircut(exposure_currency);
+
+        values
+            .par_iter()
+            .enumerate()
+            .map(|(scenario, path)| {
+                assert_eq!(path.len(), time_grid.len());
+                let factors = self.value_factors(scenario, time_grid, volatility);
+                let balances = collateral_balances(path, agreement);
+
+                path.iter()
+                    .enumerate()
+                    .map(|(i, &value)| {
+                        let (called, reference) = match lags[i] {
+                            Some(j) => (balances[j], factors[j]),
+                            None => (0.0, factors[0]),
+                        };
+                        let market_value =
+                            (called + agreement.independent_amount()) * factors[i] / reference;
+                        let credit = if market_value >= 0.0 {
+                            1.0 - haircut
+                        } else {
+                            1.0 + haircut
+                        };
+                        value - market_value * credit
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Simulates the collateral value factor X(t), with X(t₀) = 1.
+    fn value_factors(&self, scenario: usize, time_grid: &[f64], volatility: f64) -> Vec<f64> {
+        let mut factors = vec![1.0; time_grid.len()];
+        if volatility == 0.0 {
+            return factors;
+        }
+
+        let mut rng = PricerRng::from_seed(self.seed.wrapping_add(scenario as u64));
+        for k in 1..time_grid.len() {
+            let dt = (time_grid[k] - time_grid[k - 1]).max(0.0);
+            let shock =
+                volatility * dt.sqrt() * rng.gen_normal() - 0.5 * volatility * volatility * dt;
+            factors[k] = factors[k - 1] * shock.exp();
+        }
+        factors
+    }
+}
+
+/// For each grid index, the index of the latest grid date at or before
+/// `t - mpor`, or `None` if that precedes the grid.
+fn call_indices(time_grid: &[f64], mpor: f64) -> Vec<Option<usize>> {
+    time_grid
+        .iter()
+        .map(|&t| {
+            let call_time = t - mpor + TIME_EPSILON;
+            time_grid
+                .partition_point(|&s| s <= call_time)
+                .checked_sub(1)
+        })
+        .collect()
+}
+
+/// Collateral balance after each grid date's margin call, honouring both
+/// thresholds and the minimum transfer amount: positive when held from the
+/// counterparty, negative when posted by us.
+fn collateral_balances(path: &[f64], agreement: &CollateralAgreement) -> Vec<f64> {
+    let mut held = 0.0_f64;
+    path.iter()
+        .map(|&value| {
+            let required = (value - agreement.threshold()).max(0.0)
+                + (value + agreement.own_threshold()).min(0.0);
+            if (required - held).abs() >= agreement.mta() {
+                held = required;
+            }
+            held
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exposure::ExposureCalculator;
+    use crate::portfolio::CollateralAsset;
+    use approx::assert_relative_eq;
+
+    fn grid() -> Vec<f64> {
+        (0..=8).map(|i| i as f64 * 0.125).collect()
+    }
+
+    fn csa(currency: Currency) -> CollateralAgreement {
+        CollateralAgreement::zero_threshold(currency, 0.125).unwrap()
+    }
+
+    #[test]
+    fn test_call_indices_lag_one_mpor() {
+        let lags = call_indices(&grid(), 0.125);
+        assert_eq!(lags[0], None);
+        assert_eq!(lags[1], Some(0));
+        assert_eq!(lags[8], Some(7));
+
+        let lags = call_indices(&grid(), 0.2);
+        assert_eq!(lags[1], None);
+        assert_eq!(lags[2], Some(0));
+    }
+
+    #[test]
+    fn test_cash_same_currency_covers_lagged_value() {
+        let path: Vec<f64> = (0..=8).map(|i| 10.0 * i as f64).collect();
+        let out = CollateralSimulator::new(1).collateralised_values(
+            &[path.clone()],
+            &grid(),
+            &csa(Currency::USD),
+            Currency::USD,
+        );
+
+        assert_eq!(out[0][0], 0.0);
+        // Only the move over one MPoR remains uncollateralised
+        for i in 1..=8 {
+            assert_relative_eq!(out[0][i], 10.0, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_threshold_and_mta() {
+        let agreement = CollateralAgreement::new(15.0, 20.0, 0.0, Currency::USD, 0.125).unwrap();
+        let path = vec![0.0, 10.0, 30.0, 40.0, 60.0, 60.0, 60.0, 60.0, 60.0];
+        let balances = collateral_balances(&path, &agreement);
+
+        // 15 (< MTA) not called, 25 called, 25 → 45 called
+        assert_eq!(
+            balances,
+            vec![0.0, 0.0, 0.0, 25.0, 45.0, 45.0, 45.0, 45.0, 45.0]
+        );
+    }
+
+    #[test]
+    fn test_posted_collateral_on_negative_value() {
+        let agreement = CollateralAgreement::new(10.0, 5.0, 0.0, Currency::USD, 0.125)
+            .unwrap()
+            .with_own_threshold(20.0)
+            .unwrap();
+        let path = vec![0.0, -15.0, -24.0, -40.0, -42.0, -30.0, 5.0, 12.0, 30.0];
+        let balances = collateral_balances(&path, &agreement);
+
+        // -4 (< MTA) not posted, -20 posted, -22 (< MTA) kept, -10 returned,
+        // 0 back to flat, 2 (< MTA) not called, 20 called
+        assert_eq!(
+            balances,
+            vec![0.0, 0.0, 0.0, -20.0, -20.0, -10.0, 0.0, 0.0, 20.0]
+        );
+
+        // Posted collateral offsets the lagged negative value, grossed up by
+        // the haircut
+        let bond = agreement.with_asset(CollateralAsset::security(0.05, 0.0).unwrap());
+        let out = CollateralSimulator::new(1).collateralised_values(
+            &[path],
+            &grid(),
+            &bond,
+            Currency::USD,
+        );
+        assert_relative_eq!(out[0][4], -42.0 + 20.0 * 1.05, epsilon = 1e-12);
+        assert_relative_eq!(out[0][6], 5.0 + 10.0 * 1.05, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_haircuts_reduce_collateral_credit() {
+        let path = vec![100.0; 9];
+        let bond = csa(Currency::EUR).with_asset(CollateralAsset::security(0.05, 0.0).unwrap());
+
+        let same = CollateralSimulator::new(1).collateralised_values(
+            &[path.clone()],
+            &grid(),
+            &bond,
+            Currency::EUR,
+        );
+        let mismatch = CollateralSimulator::new(1).collateralised_values(
+            &[path],
+            &grid(),
+            &bond,
+            Currency::USD,
+        );
+
+        assert_relative_eq!(same[0][4], 5.0, epsilon = 1e-9);
+        assert_relative_eq!(mismatch[0][4], 13.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_fx_volatility_adds_exposure() {
+        let agreement = csa(Currency::EUR).with_fx_haircut(0.0).unwrap();
+        let values = vec![vec![100.0; 9]; 4_000];
+        let simulator = CollateralSimulator::new(11).with_fx_volatility(0.15);
+
+        let same = simulator.collateralised_values(&values, &grid(), &agreement, Currency::EUR);
+        let mismatch = simulator.collateralised_values(&values, &grid(), &agreement, Currency::USD);
+
+        let ee_same = ExposureCalculator::expected_exposure(&same);
+        let ee_mismatch = ExposureCalculator::expected_exposure(&mismatch);
+        assert_eq!(ee_same[4], 0.0);
+        // E[max(1 - X, 0)] ≈ σ√τ·φ(0) for a driftless lognormal over one MPoR
+        let expected = 100.0 * 0.15 * 0.125_f64.sqrt() * 0.398_942;
+        assert_relative_eq!(ee_mismatch[4], expected, max_relative = 0.1);
+
+        // Deterministic for a given seed
+        let again = simulator.collateralised_values(&values, &grid(), &agreement, Currency::USD);
+        assert_eq!(mismatch, again);
+        assert_relative_eq!(
+            simulator.collateral_volatility(&agreement, Currency::USD),
+            0.15,
+            epsilon = 1e-12
+        );
+    }
+}
@@ -0,0 +1,202 @@
+//! Exposure of daily-margined futures.
+//!
+//! Listed futures settle every day's gain or loss as variation margin with
+//! the clearing house, so after each settlement the position is worth zero
+//! and there is no counterparty credit exposure to simulate. The margin
+//! flows themselves still have to be funded, so they are kept per scenario
+//! alongside the (zero) values that enter netting-set aggregation.
+//!
+//! Settlement happens on the simulation grid, which should contain the
+//! contract expiry (see [`TimeGridBuilder::with_event_date`](super::TimeGridBuilder::with_event_date));
+//! grid points after expiry carry no flows.
+
+use pricer_models::instruments::equity::{DividendFuture, EquityIndexFuture};
+
+use super::ExposureCalculator;
+
+/// Simulated margin flows of a futures position.
+///
+/// # Examples
+///
+/// ```
+/// use pricer_core::types::Currency;
+/// use pricer_models::instruments::equity::EquityIndexFuture;
+/// use pricer_models::instruments::Direction;
+/// use pricer_risk::exposure::{ExposureCalculator, FuturesExposure};
+///
+/// let future =
+///     EquityIndexFuture::new(4500.0, 0.5, 1.0, 50.0, Direction::Long, Currency::USD).unwrap();
+/// let time_grid = [0.0, 0.25, 0.5, 0.75];
+/// let prices = vec![
+///     vec![4500.0, 4550.0, 4600.0, 4700.0],
+///     vec![4500.0, 4450.0, 4400.0, 4300.0],
+/// ];
+///
+/// let exposure = FuturesExposure::index_future(&future, &time_grid, &prices);
+///
+/// // No credit exposure...
+/// let ee = ExposureCalculator::expected_exposure(&exposure.values());
+/// assert_eq!(ee, vec![0.0; 4]);
+///
+/// // ...but margin paid and received until expiry
+/// assert_eq!(exposure.margin_flows()[1], vec![0.0, -2_500.0, -2_500.0, 0.0]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct FuturesExposure {
+    margin_flows: Vec<Vec<f64>>,
+}
+
+impl FuturesExposure {
+    /// Margin flows of an index future from settlement prices
+    /// `[scenario_idx][time_idx]` on `time_grid`.
+    pub fn index_future(
+        future: &EquityIndexFuture<f64>,
+        time_grid: &[f64],
+        settlement_prices: &[Vec<f64>],
+    ) -> Self {
+        Self::settle(time_grid, future.expiry(), settlement_prices, |prices| {
+            future.margin_flows(prices)
+        })
+    }
+
+    /// Margin flows of an index future whose settlement prices are the
+    /// fair futures prices on simulated index levels `[scenario_idx][time_idx]`
+    /// with deterministic carry.
+    pub fn index_future_on_spot(
+        future: &EquityIndexFuture<f64>,
+        time_grid: &[f64],
+        spot_paths: &[Vec<f64>],
+        rate: f64,
+        dividend_yield: f64,
+    ) -> Self {
+        let prices: Vec<Vec<f64>> = spot_paths
+            .iter()
+            .map(|path| {
+                path.iter()
+                    .zip(time_grid)
+                    .map(|(&spot, &t)| future.fair_price(spot, rate, dividend_yield, t))
+                    .collect()
+            })
+            .collect();
+        Self::index_future(future, time_grid, &prices)
+    }
+
+    /// Margin flows of a dividend future from settlement prices
+    /// `[scenario_idx][time_idx]` on `time_grid`.
+    pub fn dividend_future(
+        future: &DividendFuture<f64>,
+        time_grid: &[f64],
+        settlement_prices: &[Vec<f64>],
+    ) -> Self {
+        Self::settle(time_grid, future.expiry(), settlement_prices, |prices| {
+            future.margin_flows(prices)
+        })
+    }
+
+    fn settle(
+        time_grid: &[f64],
+        expiry: f64,
+        settlement_prices: &[Vec<f64>],
+        flows: impl Fn(&[f64]) -> Vec<f64>,
+    ) -> Self {
+        let n_live = time_grid.iter().take_while(|&&t| t <= expiry).count();
+        let margin_flows = settlement_prices
+            .iter()
+            .map(|path| {
+                let live = n_live.min(path.len());
+                let mut path_flows = flows(&path[..live]);
+                // A grid starting today begins from the settled price, not the trade price
+                if time_grid.first() == Some(&0.0) {
+                    if let Some(first) = path_flows.first_mut() {
+                        *first = 0.0;
+                    }
+                }
+                path_flows.resize(path.len(), 0.0);
+                path_flows
+            })
+            .collect();
+        Self { margin_flows }
+    }
+
+    /// Trade values `[scenario_idx][time_idx]` for exposure aggregation:
+    /// zero, since every settlement resets the position's value.
+    pub fn values(&self) -> Vec<Vec<f64>> {
+        self.margin_flows
+            .iter()
+            .map(|path| vec![0.0; path.len()])
+            .collect()
+    }
+
+    /// Variation margin received (positive) or paid (negative) at each grid
+    /// point, `[scenario_idx][time_idx]`.
+    #[inline]
+    pub fn margin_flows(&self) -> &[Vec<f64>] {
+        &self.margin_flows
+    }
+
+    /// Mean margin flow at each grid point.
+    pub fn expected_margin_flows(&self) -> Vec<f64> {
+        let n_scenarios = self.margin_flows.len();
+        let n_times = self.margin_flows.first().map_or(0, Vec::len);
+        (0..n_times)
+            .map(|t| self.margin_flows.iter().map(|path| path[t]).sum::<f64>() / n_scenarios as f64)
+            .collect()
+    }
+
+    /// Margin paid at each grid point at the given confidence, as a
+    /// non-negative amount: the funding analogue of PFE.
+    pub fn peak_margin_outflows(&self, confidence: f64) -> Vec<f64> {
+        let outflows: Vec<Vec<f64>> = self
+            .margin_flows
+            .iter()
+            .map(|path| path.iter().map(|flow| -flow).collect())
+            .collect();
+        ExposureCalculator::potential_future_exposure(&outflows, confidence)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use pricer_core::types::Currency;
+    use pricer_models::instruments::Direction;
+
+    #[test]
+    fn test_flows_stop_at_expiry_and_sum_to_pnl() {
+        let future =
+            DividendFuture::new(100.0, 0.0, 1.0, 2.0, 10.0, Direction::Short, Currency::EUR)
+                .unwrap();
+        let time_grid = [0.25, 0.5, 1.0, 1.5];
+        let prices = vec![vec![105.0, 110.0, 108.0, 200.0]];
+
+        let exposure = FuturesExposure::dividend_future(&future, &time_grid, &prices);
+        let flows = &exposure.margin_flows()[0];
+        assert_eq!(flows, &vec![-100.0, -100.0, 40.0, 0.0]);
+        assert_relative_eq!(flows.iter().sum::<f64>(), future.payoff(108.0));
+        assert_eq!(exposure.values(), vec![vec![0.0; 4]]);
+    }
+
+    #[test]
+    fn test_expected_and_peak_margin() {
+        let future =
+            EquityIndexFuture::new(100.0, 2.0, 1.0, 1.0, Direction::Long, Currency::USD).unwrap();
+        let time_grid = [0.0, 1.0, 2.0];
+        let spots: Vec<Vec<f64>> = (0..101)
+            .map(|s| vec![100.0, 50.0 + s as f64, 100.0])
+            .collect();
+
+        let exposure = FuturesExposure::index_future_on_spot(&future, &time_grid, &spots, 0.0, 0.0);
+        let expected = exposure.expected_margin_flows();
+        assert_relative_eq!(expected[0], 0.0);
+        assert_relative_eq!(expected[1], 0.0, epsilon = 1e-12);
+
+        // 95% of scenarios pay at most 45 at t = 1
+        let peak = exposure.peak_margin_outflows(0.95);
+        assert_relative_eq!(peak[1], 45.0);
+        assert_relative_eq!(peak[0], 0.0);
+
+        let ee = ExposureCalculator::expected_exposure(&exposure.values());
+        assert_eq!(ee, vec![0.0; 3]);
+    }
+}
@@ -7,9 +7,44 @@
 //! - Expected Positive Exposure (EPE)
 //! - Potential Future Exposure (PFE)
 //! - Netting benefit analysis
-
+//! - Net or gross aggregation of trade values per netting set ([`NettingTreatment`])
+//! - Euler allocation of netted EE/ENE and PFE to trades ([`ExposureAllocation`])
+//! - Streaming accumulation over scenario blocks ([`StreamingExposure`])
+//! - Collateralised values with haircuts and FX mismatch ([`CollateralSimulator`])
+//! - Grids aligned to coupon, expiry, call and margin dates ([`TimeGridBuilder`])
+//! - Zero exposure and variation margin flows of listed futures ([`FuturesExposure`])
+
+mod allocation;
+mod collateral;
+mod futures;
+mod streaming;
+mod time_grid;
+
+pub use allocation::ExposureAllocation;
+pub use collateral::CollateralSimulator;
+pub use futures::FuturesExposure;
+pub use streaming::{StreamingExposure, TDigest};
+pub use time_grid::{
+    TimeGridBuilder, TimeGridError, DEFAULT_MIN_SPACING, DEFAULT_POST_EVENT_OFFSET,
+};
+
+use pricer_core::math::quadrature::trapezoid;
 use rayon::prelude::*;
 
+/// How trade values within a netting set are combined into exposure.
+///
+/// Legally enforceable netting sets are aggregated [`Net`](Self::Net);
+/// where netting would not survive a close-out, each trade is a separate
+/// claim and exposure is aggregated [`Gross`](Self::Gross).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NettingTreatment {
+    /// Exposure of the summed values: max(Σᵢ Vᵢ, 0)
+    Net,
+    /// Sum of per-trade exposures: Σᵢ max(Vᵢ, 0)
+    Gross,
+}
+
 /// Exposure calculation utilities.
 ///
 /// Provides methods for computing standard exposure metrics
@@ -95,12 +130,7 @@ impl ExposureCalculator {
             return ee.first().copied().unwrap_or(0.0);
         }
 
-        // Trapezoidal integration
-        let mut integral = 0.0;
-        for i in 0..time_grid.len() - 1 {
-            let dt = time_grid[i + 1] - time_grid[i];
-            integral += 0.5 * (ee[i] + ee[i + 1]) * dt;
-        }
+        let integral = trapezoid(time_grid, ee);
 
         let total_time = time_grid.last().unwrap() - time_grid.first().unwrap();
         if total_time > 0.0 {
@@ -287,6 +317,218 @@ impl ExposureCalculator {
         }
     }
 
+    /// Computes exposure metrics without materialising the exposure cube.
+    ///
+    /// Scenarios are split into blocks of `block_size`; `simulate_block` is
+    /// called (in parallel) with each block's scenario range and returns the
+    /// netted values `[scenario_idx][time_idx]` for that block only. Blocks
+    /// are folded into per-thread [`StreamingExposure`] accumulators and
+    /// merged, so peak memory is one block per thread plus the sketches.
+    ///
+    /// Summation order depends on scheduling, so EE/ENE may differ from a
+    /// sequential run in the last few bits.
+    ///
+    /// # Arguments
+    ///
+    /// * `n_times` - Number of time points per scenario
+    /// * `n_scenarios` - Total number of scenarios
+    /// * `block_size` - Scenarios per block
+    /// * `compression` - t-digest compression for the PFE sketches
+    /// * `simulate_block` - Produces netted values for a scenario range
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pricer_risk::exposure::ExposureCalculator;
+    ///
+    /// let mut stream = ExposureCalculator::stream_exposure(4, 10_000, 1_000, 100.0, |range| {
+    ///     range
+    ///         .map(|s| (0..4).map(|t| (s % 10) as f64 - 4.5 + t as f64).collect())
+    ///         .collect()
+    /// });
+    ///
+    /// assert_eq!(stream.n_scenarios(), 10_000);
+    /// let pfe = stream.potential_future_exposure(0.95);
+    /// assert_eq!(pfe.len(), 4);
+    /// ```
+    pub fn stream_exposure<F>(
+        n_times: usize,
+        n_scenarios: usize,
+        block_size: usize,
+        compression: f64,
+        simulate_block: F,
+    ) -> StreamingExposure
+    where
+        F: Fn(std::ops::Range<usize>) -> Vec<Vec<f64>> + Sync,
+    {
+        let block_size = block_size.max(1);
+        let n_blocks = n_scenarios.div_ceil(block_size);
+
+        (0..n_blocks)
+            .into_par_iter()
+            .fold(
+                || StreamingExposure::new(n_times, compression),
+                |mut acc, block_idx| {
+                    let start = block_idx * block_size;
+                    let end = (start + block_size).min(n_scenarios);
+                    acc.add_block(&simulate_block(start..end));
+                    acc
+                },
+            )
+            .reduce(
+                || StreamingExposure::new(n_times, compression),
+                |mut a, b| {
+                    a.merge(&b);
+                    a
+                },
+            )
+    }
+
+    /// Aggregates trade-level values into a netting set's exposure paths.
+    ///
+    /// Returns `(positive, negative)` paths `[scenario_idx][time_idx]`,
+    /// both non-negative: under [`NettingTreatment::Net`] these are
+    /// max(Σᵢ Vᵢ, 0) and max(-Σᵢ Vᵢ, 0); under [`NettingTreatment::Gross`]
+    /// they are Σᵢ max(Vᵢ, 0) and Σᵢ max(-Vᵢ, 0). Pass them to
+    /// [`expected_exposure`](Self::expected_exposure) (for EE/ENE) or
+    /// [`potential_future_exposure`](Self::potential_future_exposure).
+    ///
+    /// # Arguments
+    ///
+    /// * `trade_values` - Per-trade simulated values `[trade_idx][scenario_idx][time_idx]`
+    /// * `treatment` - Net or gross aggregation
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pricer_risk::exposure::{ExposureCalculator, NettingTreatment};
+    ///
+    /// let trade_values = vec![
+    ///     vec![vec![10.0, 4.0]], // Trade 1, one scenario
+    ///     vec![vec![-6.0, -1.0]], // Trade 2
+    /// ];
+    ///
+    /// let (net, _) = ExposureCalculator::aggregate_netting_set(&trade_values, NettingTreatment::Net);
+    /// let (gross, gross_neg) =
+    ///     ExposureCalculator::aggregate_netting_set(&trade_values, NettingTreatment::Gross);
+    ///
+    /// assert_eq!(net, vec![vec![4.0, 3.0]]);
+    /// assert_eq!(gross, vec![vec![10.0, 4.0]]);
+    /// assert_eq!(gross_neg, vec![vec![6.0, 1.0]]);
+    /// ```
+    pub fn aggregate_netting_set(
+        trade_values: &[Vec<Vec<f64>>],
+        treatment: NettingTreatment,
+    ) -> (Vec<Vec<f64>>, Vec<Vec<f64>>) {
+        let Some(first) = trade_values.first() else {
+            return (Vec::new(), Vec::new());
+        };
+        let n_scenarios = first.len();
+
+        (0..n_scenarios)
+            .into_par_iter()
+            .map(|s| {
+                let n_times = first[s].len();
+                let mut positive = vec![0.0; n_times];
+                let mut negative = vec![0.0; n_times];
+                match treatment {
+                    NettingTreatment::Net => {
+                        for t in 0..n_times {
+                            let net: f64 = trade_values.iter().map(|trade| trade[s][t]).sum();
+                            positive[t] = net.max(0.0);
+                            negative[t] = (-net).max(0.0);
+                        }
+                    }
+                    NettingTreatment::Gross => {
+                        for trade in trade_values {
+                            for (t, &v) in trade[s].iter().enumerate() {
+                                positive[t] += v.max(0.0);
+                                negative[t] += (-v).max(0.0);
+                            }
+                        }
+                    }
+                }
+                (positive, negative)
+            })
+            .unzip()
+    }
+
+    /// Computes Euler (marginal) contributions of each trade to the
+    /// netting set's EE and ENE.
+    ///
+    /// EEᵢ(t) = E[Vᵢ(t) × 1{V(t) > 0}] and ENEᵢ(t) = -E[Vᵢ(t) × 1{V(t) < 0}],
+    /// where V = Σᵢ Vᵢ. Contributions sum exactly to the netted EE and ENE
+    /// and may be negative for trades that reduce exposure.
+    ///
+    /// # Arguments
+    ///
+    /// * `trade_values` - Simulated values `[trade_idx][scenario_idx][time_idx]`
+    ///
+    /// # Returns
+    ///
+    /// `(ee, ene)` contributions, each `[trade_idx][time_idx]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use pricer_risk::exposure::ExposureCalculator;
+    ///
+    /// let trade_values = vec![
+    ///     vec![vec![10.0], vec![2.0]], // Trade 1, two scenarios
+    ///     vec![vec![-4.0], vec![-6.0]], // Trade 2
+    /// ];
+    ///
+    /// let (ee, ene) = ExposureCalculator::euler_contributions(&trade_values);
+    ///
+    /// // Netted: scenario 1 = 6 (positive), scenario 2 = -4 (negative)
+    /// assert_eq!((ee[0][0], ee[1][0]), (5.0, -2.0));
+    /// assert_eq!((ene[0][0], ene[1][0]), (-1.0, 3.0));
+    /// ```
+    pub fn euler_contributions(trade_values: &[Vec<Vec<f64>>]) -> (Vec<Vec<f64>>, Vec<Vec<f64>>) {
+        let net = Self::net_values(trade_values);
+        let n_scenarios = net.len();
+        if n_scenarios == 0 {
+            let empty = vec![Vec::new(); trade_values.len()];
+            return (empty.clone(), empty);
+        }
+
+        trade_values
+            .par_iter()
+            .map(|trade| {
+                let n_times = net[0].len();
+                let mut ee = vec![0.0; n_times];
+                let mut ene = vec![0.0; n_times];
+                for (path, net_path) in trade.iter().zip(&net) {
+                    for t in 0..n_times {
+                        if net_path[t] > 0.0 {
+                            ee[t] += path[t];
+                        } else if net_path[t] < 0.0 {
+                            ene[t] -= path[t];
+                        }
+                    }
+                }
+                ee.iter_mut().for_each(|v| *v /= n_scenarios as f64);
+                ene.iter_mut().for_each(|v| *v /= n_scenarios as f64);
+                (ee, ene)
+            })
+            .unzip()
+    }
+
+    /// Sums trade values per scenario and time.
+    fn net_values(trade_values: &[Vec<Vec<f64>>]) -> Vec<Vec<f64>> {
+        let Some(first) = trade_values.first() else {
+            return Vec::new();
+        };
+        (0..first.len())
+            .into_par_iter()
+            .map(|s| {
+                (0..first[s].len())
+                    .map(|t| trade_values.iter().map(|trade| trade[s][t]).sum())
+                    .collect()
+            })
+            .collect()
+    }
+
     /// Computes Expected Negative Exposure (ENE) at each time point.
     ///
     /// ENE(t) = E[max(-V(t), 0)] = E[min(V(t), 0).abs()]
@@ -437,6 +679,28 @@ mod tests {
         assert_relative_eq!(ene[1], 10.0, epsilon = 1e-10);
     }
 
+    #[test]
+    fn test_stream_exposure_matches_full_cube() {
+        let simulate = |range: std::ops::Range<usize>| -> Vec<Vec<f64>> {
+            range
+                .map(|s| {
+                    (0..3)
+                        .map(|t| ((s * 37 + t * 11) % 101) as f64 - 40.0)
+                        .collect()
+                })
+                .collect()
+        };
+        let cube = simulate(0..5_000);
+
+        let stream = ExposureCalculator::stream_exposure(3, 5_000, 256, 100.0, simulate);
+
+        assert_eq!(stream.n_scenarios(), 5_000);
+        let ee = ExposureCalculator::expected_exposure(&cube);
+        for (a, b) in stream.expected_exposure().iter().zip(&ee) {
+            assert_relative_eq!(*a, *b, max_relative = 1e-10);
+        }
+    }
+
     #[test]
     fn test_effective_epe() {
         // EE that decreases then increases
@@ -449,4 +713,64 @@ mod tests {
         // Should be higher than standard EPE due to non-decreasing constraint
         assert!(eepe > 0.0);
     }
+
+    #[test]
+    fn test_aggregate_netting_set_net_vs_gross() {
+        // [trade][scenario][time]
+        let trade_values = vec![
+            vec![vec![10.0, -2.0], vec![-4.0, 6.0]],
+            vec![vec![-6.0, -3.0], vec![1.0, -8.0]],
+        ];
+
+        let (net_pos, net_neg) =
+            ExposureCalculator::aggregate_netting_set(&trade_values, NettingTreatment::Net);
+        assert_eq!(net_pos, vec![vec![4.0, 0.0], vec![0.0, 0.0]]);
+        assert_eq!(net_neg, vec![vec![0.0, 5.0], vec![3.0, 2.0]]);
+
+        let (gross_pos, gross_neg) =
+            ExposureCalculator::aggregate_netting_set(&trade_values, NettingTreatment::Gross);
+        assert_eq!(gross_pos, vec![vec![10.0, 0.0], vec![1.0, 6.0]]);
+        assert_eq!(gross_neg, vec![vec![6.0, 5.0], vec![4.0, 8.0]]);
+
+        // Gross EE is never below net EE
+        let net_ee = ExposureCalculator::expected_exposure(&net_pos);
+        let gross_ee = ExposureCalculator::expected_exposure(&gross_pos);
+        for (g, n) in gross_ee.iter().zip(&net_ee) {
+            assert!(g >= n);
+        }
+
+        assert!(
+            ExposureCalculator::aggregate_netting_set(&[], NettingTreatment::Net)
+                .0
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_euler_contributions_sum_to_netted_exposure() {
+        let trade_values = vec![
+            vec![vec![10.0, -3.0, 4.0], vec![-2.0, 5.0, 1.0]],
+            vec![vec![-4.0, 1.0, -7.0], vec![1.0, -8.0, 2.0]],
+            vec![vec![1.0, 1.0, 1.0], vec![-1.0, -1.0, -1.0]],
+        ];
+        let (positive, negative) =
+            ExposureCalculator::aggregate_netting_set(&trade_values, NettingTreatment::Net);
+        let ee = ExposureCalculator::expected_exposure(&positive);
+        let ene = ExposureCalculator::expected_exposure(&negative);
+
+        let (ee_k, ene_k) = ExposureCalculator::euler_contributions(&trade_values);
+
+        assert_eq!(ee_k.len(), 3);
+        for t in 0..3 {
+            let ee_sum: f64 = ee_k.iter().map(|c| c[t]).sum();
+            let ene_sum: f64 = ene_k.iter().map(|c| c[t]).sum();
+            assert_relative_eq!(ee_sum, ee[t], epsilon = 1e-12);
+            assert_relative_eq!(ene_sum, ene[t], epsilon = 1e-12);
+        }
+        // Trade 2 offsets trade 1 in the positive scenario at t=0
+        assert!(ee_k[1][0] < 0.0);
+
+        let (ee_k, ene_k) = ExposureCalculator::euler_contributions(&[]);
+        assert!(ee_k.is_empty() && ene_k.is_empty());
+    }
 }
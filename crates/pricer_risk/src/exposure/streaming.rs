@@ -0,0 +1,448 @@
+//! Streaming exposure accumulation over scenario blocks.
+//!
+//! A full exposure cube (trades × dates × scenarios) does not fit in memory
+//! for large portfolios. [`StreamingExposure`] consumes netted values one
+//! block of scenarios at a time and keeps only per-date running sums (for
+//! EE/ENE) and a [`TDigest`] quantile sketch (for PFE), so memory is
+//! `O(n_times × compression)` regardless of the scenario count.
+//!
+//! Accumulators built on separate blocks can be merged, which lets blocks
+//! be simulated in parallel
+//! (see [`ExposureCalculator::stream_exposure`](super::ExposureCalculator::stream_exposure)).
+
+/// Merging t-digest quantile sketch.
+///
+/// Points are buffered and periodically merged into weighted centroids using
+/// the `k1` scale function `k(q) = δ/(2π) asin(2q − 1)`, which keeps
+/// centroids small near the tails where PFE quantiles live.
+///
+/// # Examples
+///
+/// ```
+/// use pricer_risk::exposure::TDigest;
+///
+/// let mut digest = TDigest::new(100.0);
+/// for i in 0..=1000 {
+///     digest.add(i as f64);
+/// }
+///
+/// let p95 = digest.quantile(0.95);
+/// assert!((p95 - 950.0).abs() < 2.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct TDigest {
+    compression: f64,
+    /// Merged centroids `(mean, weight)`, sorted by mean.
+    centroids: Vec<(f64, f64)>,
+    /// Unmerged points.
+    buffer: Vec<f64>,
+    total_weight: f64,
+    min: f64,
+    max: f64,
+}
+
+impl TDigest {
+    /// Creates an empty digest with compression `δ` (typically 100–500).
+    ///
+    /// Larger values keep more centroids and give more accurate quantiles.
+    pub fn new(compression: f64) -> Self {
+        let compression = compression.max(10.0);
+        Self {
+            compression,
+            centroids: Vec::new(),
+            buffer: Vec::with_capacity(Self::buffer_limit(compression)),
+            total_weight: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+
+    #[inline]
+    fn buffer_limit(compression: f64) -> usize {
+        (5.0 * compression) as usize
+    }
+
+    /// Adds a single observation.
+    #[inline]
+    pub fn add(&mut self, value: f64) {
+        self.buffer.push(value);
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        if self.buffer.len() >= Self::buffer_limit(self.compression) {
+            self.compress();
+        }
+    }
+
+    /// Merges another digest into this one.
+    pub fn merge(&mut self, other: &TDigest) {
+        let mut other = other.clone();
+        other.compress();
+        self.compress();
+
+        self.centroids.extend_from_slice(&other.centroids);
+        self.total_weight += other.total_weight;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.merge_centroids();
+    }
+
+    /// Returns the number of observations.
+    #[inline]
+    pub fn count(&self) -> usize {
+        (self.total_weight + self.buffer.len() as f64) as usize
+    }
+
+    /// Returns `true` if no observations have been added.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.count() == 0
+    }
+
+    /// Returns the number of centroids after compression.
+    pub fn n_centroids(&mut self) -> usize {
+        self.compress();
+        self.centroids.len()
+    }
+
+    /// Estimates the `q`-quantile (`q` clamped to [0, 1]).
+    ///
+    /// Returns 0.0 for an empty digest.
+    pub fn quantile(&mut self, q: f64) -> f64 {
+        self.compress();
+        if self.centroids.is_empty() {
+            return 0.0;
+        }
+        let q = q.clamp(0.0, 1.0);
+        if self.centroids.len() == 1 {
+            return self.centroids[0].0;
+        }
+
+        // Interpolate between centroid centres, anchored at min and max
+        let target = q * self.total_weight;
+        let (first_mean, first_weight) = self.centroids[0];
+        if target <= first_weight / 2.0 {
+            let t = target / (first_weight / 2.0);
+            return self.min + t * (first_mean - self.min);
+        }
+
+        let mut cumulative = 0.0;
+        for pair in self.centroids.windows(2) {
+            let (mean_a, weight_a) = pair[0];
+            let (mean_b, weight_b) = pair[1];
+            let centre_a = cumulative + weight_a / 2.0;
+            let centre_b = cumulative + weight_a + weight_b / 2.0;
+            if target <= centre_b {
+                let t = (target - centre_a) / (centre_b - centre_a);
+                return mean_a + t * (mean_b - mean_a);
+            }
+            cumulative += weight_a;
+        }
+
+        let (last_mean, last_weight) = self.centroids[self.centroids.len() - 1];
+        let centre = self.total_weight - last_weight / 2.0;
+        let t = ((target - centre) / (last_weight / 2.0)).min(1.0);
+        last_mean + t * (self.max - last_mean)
+    }
+
+    /// Merges buffered points into the centroid list.
+    fn compress(&mut self) {
+        if self.buffer.is_empty() {
+            return;
+        }
+        self.total_weight += self.buffer.len() as f64;
+        self.centroids
+            .extend(self.buffer.drain(..).map(|value| (value, 1.0)));
+        self.merge_centroids();
+    }
+
+    /// Sorts centroids and merges neighbours within one unit of `k`.
+    fn merge_centroids(&mut self) {
+        if self.centroids.len() < 2 {
+            return;
+        }
+        self.centroids
+            .sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        let scale = self.compression / (2.0 * std::f64::consts::PI);
+        let k = |q: f64| scale * (2.0 * q - 1.0).clamp(-1.0, 1.0).asin();
+
+        let mut merged = Vec::with_capacity(self.centroids.len());
+        let mut current = self.centroids[0];
+        let mut weight_before = 0.0;
+
+        for &next in &self.centroids[1..] {
+            let q_left = weight_before / self.total_weight;
+            let q_right = (weight_before + current.1 + next.1) / self.total_weight;
+            if k(q_right) - k(q_left) <= 1.0 {
+                let weight = current.1 + next.1;
+                current.0 += (next.0 - current.0) * next.1 / weight;
+                current.1 = weight;
+            } else {
+                weight_before += current.1;
+                merged.push(current);
+                current = next;
+            }
+        }
+        merged.push(current);
+        self.centroids = merged;
+    }
+}
+
+/// Incremental EE/ENE/PFE accumulator over scenario blocks.
+///
+/// Feed netted portfolio values block by block with
+/// [`add_block`](Self::add_block); the block layout matches
+/// [`ExposureCalculator`](super::ExposureCalculator): `[scenario_idx][time_idx]`.
+///
+/// # Examples
+///
+/// ```
+/// use pricer_risk::exposure::StreamingExposure;
+///
+/// let mut stream = StreamingExposure::new(2, 100.0);
+/// stream.add_block(&[vec![10.0, 20.0], vec![-5.0, 15.0]]);
+/// stream.add_block(&[vec![5.0, -10.0]]);
+///
+/// let ee = stream.expected_exposure();
+/// assert_eq!(ee[0], 5.0); // (10 + 0 + 5) / 3
+/// assert_eq!(stream.n_scenarios(), 3);
+/// ```
+#[derive(Clone, Debug)]
+pub struct StreamingExposure {
+    positive_sums: Vec<f64>,
+    negative_sums: Vec<f64>,
+    digests: Vec<TDigest>,
+    n_scenarios: usize,
+}
+
+impl StreamingExposure {
+    /// Creates an empty accumulator for `n_times` dates.
+    ///
+    /// # Arguments
+    ///
+    /// * `n_times` - Number of time points per scenario
+    /// * `compression` - t-digest compression for the PFE sketches
+    pub fn new(n_times: usize, compression: f64) -> Self {
+        Self {
+            positive_sums: vec![0.0; n_times],
+            negative_sums: vec![0.0; n_times],
+            digests: vec![TDigest::new(compression); n_times],
+            n_scenarios: 0,
+        }
+    }
+
+    /// Returns the number of time points.
+    #[inline]
+    pub fn n_times(&self) -> usize {
+        self.positive_sums.len()
+    }
+
+    /// Returns the number of scenarios accumulated so far.
+    #[inline]
+    pub fn n_scenarios(&self) -> usize {
+        self.n_scenarios
+    }
+
+    /// Accumulates one scenario's values across all dates.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values.len()` differs from the number of time points.
+    pub fn add_scenario(&mut self, values: &[f64]) {
+        assert_eq!(values.len(), self.n_times(), "time grid length mismatch");
+        for (t, &value) in values.iter().enumerate() {
+            let exposure = value.max(0.0);
+            self.positive_sums[t] += exposure;
+            self.negative_sums[t] += (-value).max(0.0);
+            self.digests[t].add(exposure);
+        }
+        self.n_scenarios += 1;
+    }
+
+    /// Accumulates a block of scenarios `[scenario_idx][time_idx]`.
+    pub fn add_block(&mut self, block: &[Vec<f64>]) {
+        for values in block {
+            self.add_scenario(values);
+        }
+    }
+
+    /// Merges an accumulator built on a disjoint set of scenarios.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the time grids have different lengths.
+    pub fn merge(&mut self, other: &StreamingExposure) {
+        assert_eq!(other.n_times(), self.n_times(), "time grid length mismatch");
+        for t in 0..self.n_times() {
+            self.positive_sums[t] += other.positive_sums[t];
+            self.negative_sums[t] += other.negative_sums[t];
+            self.digests[t].merge(&other.digests[t]);
+        }
+        self.n_scenarios += other.n_scenarios;
+    }
+
+    /// Expected Exposure `EE(t) = E[max(V(t), 0)]` at each time point.
+    pub fn expected_exposure(&self) -> Vec<f64> {
+        self.means(&self.positive_sums)
+    }
+
+    /// Expected Negative Exposure `ENE(t) = E[max(-V(t), 0)]` at each time point.
+    pub fn expected_negative_exposure(&self) -> Vec<f64> {
+        self.means(&self.negative_sums)
+    }
+
+    /// Potential Future Exposure at the given confidence, from the sketches.
+    pub fn potential_future_exposure(&mut self, confidence: f64) -> Vec<f64> {
+        self.digests
+            .iter_mut()
+            .map(|digest| digest.quantile(confidence))
+            .collect()
+    }
+
+    fn means(&self, sums: &[f64]) -> Vec<f64> {
+        if self.n_scenarios == 0 {
+            return vec![0.0; sums.len()];
+        }
+        let n = self.n_scenarios as f64;
+        sums.iter().map(|sum| sum / n).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::ExposureCalculator;
+    use super::*;
+    use approx::assert_relative_eq;
+
+    /// Deterministic pseudo-random values in [-1, 1).
+    fn lcg_values(n: usize, seed: u64) -> Vec<f64> {
+        let mut state = seed;
+        (0..n)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6_364_136_223_846_793_005)
+                    .wrapping_add(1_442_695_040_888_963_407);
+                (state >> 11) as f64 / (1u64 << 52) as f64 - 1.0
+            })
+            .collect()
+    }
+
+    fn scenarios(n_scenarios: usize, n_times: usize) -> Vec<Vec<f64>> {
+        lcg_values(n_scenarios * n_times, 17)
+            .chunks(n_times)
+            .enumerate()
+            .map(|(s, chunk)| {
+                chunk
+                    .iter()
+                    .enumerate()
+                    .map(|(t, &u)| 100.0 * u * (1.0 + t as f64) + (s % 7) as f64)
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_tdigest_uniform_quantiles() {
+        let mut digest = TDigest::new(100.0);
+        for u in lcg_values(50_000, 3) {
+            digest.add(u);
+        }
+
+        assert_eq!(digest.count(), 50_000);
+        for q in [0.01, 0.25, 0.5, 0.9, 0.99] {
+            assert!(
+                (digest.quantile(q) - (2.0 * q - 1.0)).abs() < 0.01,
+                "q = {}",
+                q
+            );
+        }
+        assert!(digest.n_centroids() < 200);
+    }
+
+    #[test]
+    fn test_tdigest_merge_matches_single() {
+        let values = lcg_values(20_000, 5);
+        let mut single = TDigest::new(100.0);
+        let mut left = TDigest::new(100.0);
+        let mut right = TDigest::new(100.0);
+        for (i, &v) in values.iter().enumerate() {
+            single.add(v);
+            if i % 2 == 0 {
+                left.add(v);
+            } else {
+                right.add(v);
+            }
+        }
+        left.merge(&right);
+
+        assert_eq!(left.count(), single.count());
+        for q in [0.05, 0.5, 0.95] {
+            assert!((left.quantile(q) - single.quantile(q)).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn test_tdigest_empty_and_single() {
+        let mut digest = TDigest::new(100.0);
+        assert!(digest.is_empty());
+        assert_eq!(digest.quantile(0.5), 0.0);
+
+        digest.add(42.0);
+        assert_eq!(digest.quantile(0.99), 42.0);
+    }
+
+    #[test]
+    fn test_streaming_matches_full_cube() {
+        let cube = scenarios(10_000, 5);
+
+        let mut stream = StreamingExposure::new(5, 200.0);
+        for block in cube.chunks(777) {
+            stream.add_block(block);
+        }
+
+        let ee = ExposureCalculator::expected_exposure(&cube);
+        let ene = ExposureCalculator::expected_negative_exposure(&cube);
+        let pfe = ExposureCalculator::potential_future_exposure(&cube, 0.95);
+
+        for (a, b) in stream.expected_exposure().iter().zip(&ee) {
+            assert_relative_eq!(*a, *b, max_relative = 1e-10);
+        }
+        for (a, b) in stream.expected_negative_exposure().iter().zip(&ene) {
+            assert_relative_eq!(*a, *b, max_relative = 1e-10);
+        }
+        for (a, b) in stream.potential_future_exposure(0.95).iter().zip(&pfe) {
+            assert_relative_eq!(*a, *b, max_relative = 5e-3);
+        }
+    }
+
+    #[test]
+    fn test_streaming_merge() {
+        let cube = scenarios(4_000, 3);
+        let (left_block, right_block) = cube.split_at(1_500);
+
+        let mut left = StreamingExposure::new(3, 100.0);
+        left.add_block(left_block);
+        let mut right = StreamingExposure::new(3, 100.0);
+        right.add_block(right_block);
+        left.merge(&right);
+
+        let mut full = StreamingExposure::new(3, 100.0);
+        full.add_block(&cube);
+
+        assert_eq!(left.n_scenarios(), 4_000);
+        for (a, b) in left
+            .expected_exposure()
+            .iter()
+            .zip(full.expected_exposure())
+        {
+            assert_relative_eq!(*a, b, max_relative = 1e-12);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "time grid length mismatch")]
+    fn test_streaming_length_mismatch() {
+        let mut stream = StreamingExposure::new(3, 100.0);
+        stream.add_scenario(&[1.0, 2.0]);
+    }
+}
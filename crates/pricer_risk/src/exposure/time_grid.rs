@@ -0,0 +1,447 @@
+//! Exposure time grids aligned to trade events.
+//!
+//! Exposure profiles jump when a trade pays a coupon, expires or can be
+//! called, and a uniform grid either steps over the jump or lands on it
+//! by accident. [`TimeGridBuilder`] merges a base grid of configurable
+//! density with the dates that matter for the portfolio:
+//!
+//! - trade expiries and swap payment dates
+//! - Bermudan exercise (call) dates
+//! - margin call dates, and the date one margin period of risk before
+//!   each trade event, where the last collateral call is observed
+//! - any other dates supplied by the caller
+//!
+//! Every trade event is followed by a point a short offset later, so the
+//! profile shows the value both before and after a cash flow settles.
+
+use pricer_models::instruments::{ExerciseStyle, Instrument};
+use thiserror::Error;
+
+use crate::portfolio::{Portfolio, Trade};
+
+/// Default gap between a trade event and the point recording the value
+/// after it: one day.
+pub const DEFAULT_POST_EVENT_OFFSET: f64 = 1.0 / 365.0;
+
+/// Default tolerance below which grid points are merged: half a day.
+pub const DEFAULT_MIN_SPACING: f64 = 0.5 / 365.0;
+
+/// Errors from building an exposure time grid.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum TimeGridError {
+    /// A grid step is zero, negative or not finite.
+    #[error("Grid step must be positive, got {0}")]
+    InvalidStep(f64),
+
+    /// The horizon is not positive, or there is neither a horizon nor an
+    /// event to infer it from.
+    #[error("Grid horizon must be positive, got {0}")]
+    InvalidHorizon(f64),
+
+    /// An event date, offset or spacing is negative or not finite.
+    #[error("Grid date or offset must be non-negative, got {0}")]
+    InvalidDate(f64),
+}
+
+/// Builds exposure time grids aligned to portfolio events.
+///
+/// The base grid uses `step` everywhere except where a denser segment is
+/// configured with [`with_density`](Self::with_density). Base points that
+/// fall within the minimum spacing of an event are dropped in favour of
+/// the event.
+///
+/// # Examples
+///
+/// ```
+/// use pricer_risk::exposure::TimeGridBuilder;
+///
+/// let grid = TimeGridBuilder::new(0.25)
+///     .with_density(0.5, 1.0 / 12.0)
+///     .with_event_dates([0.6, 1.0])
+///     .with_post_event_offset(0.01)
+///     .build()
+///     .unwrap();
+///
+/// // Monthly to six months, then quarterly to the last event at 1.0
+/// assert_eq!(grid.first(), Some(&0.0));
+/// assert_eq!(grid.last(), Some(&1.0));
+/// assert!(grid.contains(&0.6));
+/// assert!(grid.iter().any(|&t| (t - 0.61).abs() < 1e-12));
+/// assert!(grid.windows(2).all(|w| w[1] > w[0]));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimeGridBuilder {
+    step: f64,
+    density: Vec<(f64, f64)>,
+    horizon: Option<f64>,
+    events: Vec<f64>,
+    margin_call_frequency: Option<f64>,
+    margin_period_of_risk: Option<f64>,
+    post_event_offset: f64,
+    min_spacing: f64,
+}
+
+impl TimeGridBuilder {
+    /// Creates a builder with base step `step` in years and no events.
+    pub fn new(step: f64) -> Self {
+        Self {
+            step,
+            density: Vec::new(),
+            horizon: None,
+            events: Vec::new(),
+            margin_call_frequency: None,
+            margin_period_of_risk: None,
+            post_event_offset: DEFAULT_POST_EVENT_OFFSET,
+            min_spacing: DEFAULT_MIN_SPACING,
+        }
+    }
+
+    /// Uses step `step` for times before `until`, e.g. a monthly grid over
+    /// the first year ahead of a quarterly one. Where segments overlap the
+    /// finest step applies.
+    pub fn with_density(mut self, until: f64, step: f64) -> Self {
+        self.density.push((until, step));
+        self
+    }
+
+    /// Sets the grid horizon. Without one, the grid ends at the last
+    /// event; events after the horizon are ignored.
+    pub fn with_horizon(mut self, horizon: f64) -> Self {
+        self.horizon = Some(horizon);
+        self
+    }
+
+    /// Adds an event date, treated like a trade cash flow.
+    pub fn with_event_date(mut self, t: f64) -> Self {
+        self.events.push(t);
+        self
+    }
+
+    /// Adds several event dates.
+    pub fn with_event_dates(mut self, dates: impl IntoIterator<Item = f64>) -> Self {
+        self.events.extend(dates);
+        self
+    }
+
+    /// Adds the expiry, payment and exercise dates of `trade`.
+    pub fn with_trade(mut self, trade: &Trade) -> Self {
+        self.events.extend(trade_event_dates(trade.instrument()));
+        self
+    }
+
+    /// Adds the event dates of every trade in `portfolio`.
+    pub fn with_portfolio(self, portfolio: &Portfolio) -> Self {
+        portfolio
+            .trades()
+            .fold(self, |builder, trade| builder.with_trade(trade))
+    }
+
+    /// Adds margin call dates every `frequency` years up to the horizon.
+    pub fn with_margin_calls(mut self, frequency: f64) -> Self {
+        self.margin_call_frequency = Some(frequency);
+        self
+    }
+
+    /// Adds the date one margin period of risk before each event, where
+    /// [`CollateralSimulator`](super::CollateralSimulator) looks up the
+    /// collateral call that covers it.
+    pub fn with_margin_period_of_risk(mut self, mpor: f64) -> Self {
+        self.margin_period_of_risk = Some(mpor);
+        self
+    }
+
+    /// Sets the gap between an event and the point after it; zero
+    /// disables the post-event points.
+    pub fn with_post_event_offset(mut self, offset: f64) -> Self {
+        self.post_event_offset = offset;
+        self
+    }
+
+    /// Sets the tolerance below which grid points are merged.
+    pub fn with_min_spacing(mut self, spacing: f64) -> Self {
+        self.min_spacing = spacing;
+        self
+    }
+
+    /// Builds the grid: increasing times starting at 0 and ending at the
+    /// horizon.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TimeGridError::InvalidStep` for a non-positive step,
+    /// `TimeGridError::InvalidHorizon` for a non-positive horizon or no
+    /// horizon and no events, and `TimeGridError::InvalidDate` for a
+    /// negative event date, offset, spacing or margin period.
+    pub fn build(&self) -> Result<Vec<f64>, TimeGridError> {
+        self.validate()?;
+        let horizon = match self.horizon {
+            Some(horizon) => horizon,
+            None => self
+                .events
+                .iter()
+                .copied()
+                .fold(None, |max: Option<f64>, t| {
+                    Some(max.map_or(t, |m| m.max(t)))
+                })
+                .ok_or(TimeGridError::InvalidHorizon(0.0))?,
+        };
+        if !(horizon.is_finite() && horizon > 0.0) {
+            return Err(TimeGridError::InvalidHorizon(horizon));
+        }
+
+        let mut anchors = vec![0.0, horizon];
+        for &event in self.events.iter().filter(|&&t| t <= horizon) {
+            anchors.push(event);
+            if self.post_event_offset > 0.0 && event + self.post_event_offset < horizon {
+                anchors.push(event + self.post_event_offset);
+            }
+            if let Some(mpor) = self.margin_period_of_risk {
+                if event >= mpor {
+                    anchors.push(event - mpor);
+                }
+            }
+        }
+        if let Some(frequency) = self.margin_call_frequency {
+            let n_calls = (horizon / frequency).floor() as usize;
+            anchors.extend((1..=n_calls).map(|k| k as f64 * frequency));
+        }
+        anchors.sort_by(f64::total_cmp);
+        let mut anchors = merge_close(anchors, self.min_spacing);
+        // An event just short of the horizon must not swallow it
+        if let Some(last) = anchors.last_mut() {
+            *last = horizon;
+        }
+
+        let mut grid = anchors.clone();
+        let mut t = 0.0;
+        loop {
+            t += self.step_at(t);
+            if t >= horizon {
+                break;
+            }
+            let next = anchors.partition_point(|&a| a < t);
+            let near_anchor = [next.checked_sub(1), Some(next)]
+                .into_iter()
+                .flatten()
+                .filter_map(|i| anchors.get(i))
+                .any(|a| (a - t).abs() < self.min_spacing);
+            if !near_anchor {
+                grid.push(t);
+            }
+        }
+        grid.sort_by(f64::total_cmp);
+        Ok(grid)
+    }
+
+    fn validate(&self) -> Result<(), TimeGridError> {
+        let steps = std::iter::once(self.step)
+            .chain(self.density.iter().map(|&(_, step)| step))
+            .chain(self.margin_call_frequency);
+        for step in steps {
+            if !(step.is_finite() && step > 0.0) {
+                return Err(TimeGridError::InvalidStep(step));
+            }
+        }
+        let dates = self
+            .events
+            .iter()
+            .copied()
+            .chain(self.margin_period_of_risk)
+            .chain([self.post_event_offset, self.min_spacing]);
+        for date in dates {
+            if !(date.is_finite() && date >= 0.0) {
+                return Err(TimeGridError::InvalidDate(date));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the base step in force at time `t`.
+    fn step_at(&self, t: f64) -> f64 {
+        self.density
+            .iter()
+            .filter(|&&(until, _)| t < until)
+            .map(|&(_, step)| step)
+            .fold(self.step, f64::min)
+    }
+}
+
+/// Returns the dates on which the value of `instrument` can jump.
+fn trade_event_dates(instrument: &Instrument<f64>) -> Vec<f64> {
+    let mut dates = vec![instrument.expiry()];
+    if let Some(swap) = instrument.as_swap() {
+        dates.extend_from_slice(swap.payment_dates());
+    }
+    if let Some(option) = instrument.as_vanilla() {
+        if let ExerciseStyle::Bermudan { exercise_dates } = option.exercise_style() {
+            dates.extend_from_slice(exercise_dates);
+        }
+    }
+    dates
+}
+
+/// Collapses sorted points closer than `spacing` to the first of each run.
+fn merge_close(sorted: Vec<f64>, spacing: f64) -> Vec<f64> {
+    let mut merged: Vec<f64> = Vec::with_capacity(sorted.len());
+    for t in sorted {
+        match merged.last() {
+            Some(&last) if t - last < spacing.max(f64::EPSILON) => {}
+            _ => merged.push(t),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use pricer_core::types::Currency;
+    use pricer_models::instruments::{
+        InstrumentParams, PaymentFrequency, PayoffType, Swap, VanillaOption,
+    };
+
+    fn assert_increasing(grid: &[f64]) {
+        assert!(grid.windows(2).all(|w| w[1] > w[0]), "{grid:?}");
+    }
+
+    #[test]
+    fn test_uniform_grid_without_events() {
+        let grid = TimeGridBuilder::new(0.25)
+            .with_horizon(1.0)
+            .build()
+            .unwrap();
+        assert_eq!(grid, vec![0.0, 0.25, 0.5, 0.75, 1.0]);
+    }
+
+    #[test]
+    fn test_events_and_post_event_points_are_inserted() {
+        let grid = TimeGridBuilder::new(0.5)
+            .with_event_dates([0.3, 2.0])
+            .with_post_event_offset(0.02)
+            .build()
+            .unwrap();
+        assert_increasing(&grid);
+        assert_eq!(grid.last(), Some(&2.0));
+        assert!(grid.contains(&0.3));
+        assert!(grid.iter().any(|&t| (t - 0.32).abs() < 1e-12));
+        // No post-event point beyond the horizon
+        assert!(grid.iter().all(|&t| t <= 2.0));
+    }
+
+    #[test]
+    fn test_base_points_near_events_are_dropped() {
+        let grid = TimeGridBuilder::new(0.25)
+            .with_event_date(0.501)
+            .with_horizon(1.0)
+            .with_post_event_offset(0.0)
+            .build()
+            .unwrap();
+        assert_eq!(grid, vec![0.0, 0.25, 0.501, 0.75, 1.0]);
+    }
+
+    #[test]
+    fn test_grid_ends_at_horizon() {
+        let grid = TimeGridBuilder::new(0.5)
+            .with_event_date(0.9995)
+            .with_horizon(1.0)
+            .build()
+            .unwrap();
+        assert_eq!(grid, vec![0.0, 0.5, 1.0]);
+    }
+
+    #[test]
+    fn test_density_segments() {
+        let grid = TimeGridBuilder::new(1.0)
+            .with_density(1.0, 0.25)
+            .with_horizon(3.0)
+            .build()
+            .unwrap();
+        assert_eq!(grid, vec![0.0, 0.25, 0.5, 0.75, 1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_margin_calls_and_mpor_lookback() {
+        let mpor = 10.0 / 365.0;
+        let grid = TimeGridBuilder::new(1.0)
+            .with_event_date(0.5)
+            .with_margin_calls(0.25)
+            .with_margin_period_of_risk(mpor)
+            .with_horizon(1.0)
+            .build()
+            .unwrap();
+        assert_increasing(&grid);
+        for t in [0.25, 0.5, 0.75, 1.0] {
+            assert!(grid.contains(&t), "missing margin call {t}");
+        }
+        assert!(grid.iter().any(|&t| (t - (0.5 - mpor)).abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_aligned_to_trade_events() {
+        let params = InstrumentParams::new(100.0, 1.5, 1.0).unwrap();
+        let bermudan = ExerciseStyle::Bermudan {
+            exercise_dates: vec![0.5, 1.0, 1.5],
+        };
+        let option =
+            Instrument::Vanilla(VanillaOption::new(params, PayoffType::Call, bermudan, 1e-6));
+        let swap = Instrument::Swap(
+            Swap::new(
+                1_000_000.0,
+                0.03,
+                vec![0.4, 0.9, 1.4, 1.9, 2.0],
+                PaymentFrequency::SemiAnnual,
+                Currency::USD,
+            )
+            .unwrap(),
+        );
+        let trade = |id: &str, instrument| {
+            Trade::new(
+                id.into(),
+                instrument,
+                Currency::USD,
+                "CP".into(),
+                "NS".into(),
+                1.0,
+            )
+        };
+
+        let grid = TimeGridBuilder::new(1.0)
+            .with_trade(&trade("OPT", option))
+            .with_trade(&trade("SWP", swap))
+            .build()
+            .unwrap();
+        assert_increasing(&grid);
+        for t in [0.4, 0.5, 0.9, 1.0, 1.4, 1.5, 1.9, 2.0] {
+            assert!(grid.contains(&t), "missing event {t}");
+        }
+        assert_relative_eq!(*grid.last().unwrap(), 2.0);
+    }
+
+    #[test]
+    fn test_invalid_configuration() {
+        assert_eq!(
+            TimeGridBuilder::new(0.0).with_horizon(1.0).build(),
+            Err(TimeGridError::InvalidStep(0.0))
+        );
+        assert_eq!(
+            TimeGridBuilder::new(0.25).build(),
+            Err(TimeGridError::InvalidHorizon(0.0))
+        );
+        assert_eq!(
+            TimeGridBuilder::new(0.25).with_horizon(-1.0).build(),
+            Err(TimeGridError::InvalidHorizon(-1.0))
+        );
+        assert_eq!(
+            TimeGridBuilder::new(0.25).with_event_date(-0.5).build(),
+            Err(TimeGridError::InvalidDate(-0.5))
+        );
+        assert_eq!(
+            TimeGridBuilder::new(0.25)
+                .with_horizon(1.0)
+                .with_density(0.5, -0.1)
+                .build(),
+            Err(TimeGridError::InvalidStep(-0.1))
+        );
+    }
+}
@@ -0,0 +1,358 @@
+//! Repo and securities-lending funding books.
+//!
+//! Financing trades are collateralised cash or securities loans rather than
+//! derivatives, so they are held in a [`FinancingBook`] beside the
+//! portfolio instead of as portfolio trades. The book feeds the same
+//! downstream calculations:
+//!
+//! - FVA: [`FinancingBook::values`] gives each trade's exposure net of its
+//!   haircut-adjusted collateral on a time grid, as a single deterministic
+//!   scenario `[1][time_idx]` for [`ExposureCalculator`](crate::exposure::ExposureCalculator)
+//!   and [`compute_fva`](crate::xva::compute_fva)
+//! - Liquidity: [`CashflowProjector::project_financing`](crate::cashflows::CashflowProjector::project_financing)
+//!   projects cash legs, interest and lending fees into the ladder
+//! - Valuation: [`FinancingBook::present_value`] discounts on the repo curve
+//!
+//! # Examples
+//!
+//! ```
+//! use pricer_core::types::time::Date;
+//! use pricer_core::types::Currency;
+//! use pricer_models::instruments::rates::{
+//!     CollateralPosition, CollateralSchedule, Repo, RepoDirection,
+//! };
+//! use pricer_risk::exposure::ExposureCalculator;
+//! use pricer_risk::financing::{FinancingBook, FinancingTrade};
+//! use pricer_risk::portfolio::{CounterpartyId, NettingSetId, TradeId};
+//! use pricer_risk::xva::{compute_fva, FundingParams};
+//!
+//! let as_of = Date::from_ymd(2026, 1, 15).unwrap();
+//! // 1m lent against bonds that fall to 1.0m after a quarter, 2% haircut
+//! let collateral = CollateralSchedule::new(vec![
+//!     (as_of, CollateralPosition::new(1_020_408.17, 0.02).unwrap()),
+//!     (as_of + 91, CollateralPosition::new(1_000_000.0, 0.02).unwrap()),
+//! ])
+//! .unwrap();
+//! let repo = Repo::new(
+//!     1_000_000.0, 0.03, as_of, as_of + 365, RepoDirection::ReverseRepo, collateral, Currency::USD,
+//! )
+//! .unwrap();
+//!
+//! let book = FinancingBook::new(as_of).with_trade(FinancingTrade::new(
+//!     TradeId::new("REPO001"),
+//!     repo,
+//!     CounterpartyId::new("CP001"),
+//!     NettingSetId::new("NS001"),
+//! ));
+//!
+//! let time_grid = [0.0, 0.25, 0.5, 0.75];
+//! let values = book.values(&time_grid);
+//! let ee = ExposureCalculator::expected_exposure(&values);
+//! let ene = ExposureCalculator::expected_negative_exposure(&values);
+//! assert!(ee[0] < 1.0);
+//! assert!(ee[2] > 20_000.0);
+//!
+//! let funding = FundingParams::symmetric(0.01);
+//! let (fca, _, _) = compute_fva(&ee, &ene, &time_grid, &funding, &[1.0; 4], false);
+//! assert!(fca > 0.0);
+//! ```
+
+use pricer_core::market_data::curves::CurveSet;
+use pricer_core::types::time::Date;
+use pricer_core::types::Currency;
+use pricer_models::instruments::rates::{price_repo, price_securities_loan, Repo, SecuritiesLoan};
+
+use crate::cashflows::date_after;
+use crate::portfolio::{CounterpartyId, NettingSetId, TradeId};
+
+/// A repo or securities loan.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FinancingInstrument {
+    /// Repo or reverse repo
+    Repo(Repo<f64>),
+    /// Securities lent or borrowed
+    SecuritiesLoan(SecuritiesLoan<f64>),
+}
+
+impl FinancingInstrument {
+    /// Returns the start date.
+    pub fn start_date(&self) -> Date {
+        match self {
+            FinancingInstrument::Repo(repo) => repo.start_date(),
+            FinancingInstrument::SecuritiesLoan(loan) => loan.start_date(),
+        }
+    }
+
+    /// Returns the end date.
+    pub fn end_date(&self) -> Date {
+        match self {
+            FinancingInstrument::Repo(repo) => repo.end_date(),
+            FinancingInstrument::SecuritiesLoan(loan) => loan.end_date(),
+        }
+    }
+
+    /// Returns the currency.
+    pub fn currency(&self) -> Currency {
+        match self {
+            FinancingInstrument::Repo(repo) => repo.currency(),
+            FinancingInstrument::SecuritiesLoan(loan) => loan.currency(),
+        }
+    }
+
+    /// Exposure on `date` net of haircut-adjusted collateral.
+    pub fn net_exposure(&self, date: Date) -> f64 {
+        match self {
+            FinancingInstrument::Repo(repo) => repo.net_exposure(date),
+            FinancingInstrument::SecuritiesLoan(loan) => loan.net_exposure(date),
+        }
+    }
+
+    /// Present value on `valuation_date` off the repo curve of `curves`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `curves` has neither a repo nor a discount curve.
+    pub fn present_value(&self, curves: &CurveSet<f64>, valuation_date: Date) -> f64 {
+        match self {
+            FinancingInstrument::Repo(repo) => price_repo(repo, curves, valuation_date),
+            FinancingInstrument::SecuritiesLoan(loan) => {
+                price_securities_loan(loan, curves, valuation_date)
+            }
+        }
+    }
+}
+
+impl From<Repo<f64>> for FinancingInstrument {
+    fn from(repo: Repo<f64>) -> Self {
+        FinancingInstrument::Repo(repo)
+    }
+}
+
+impl From<SecuritiesLoan<f64>> for FinancingInstrument {
+    fn from(loan: SecuritiesLoan<f64>) -> Self {
+        FinancingInstrument::SecuritiesLoan(loan)
+    }
+}
+
+/// A financing trade booked against a counterparty.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FinancingTrade {
+    id: TradeId,
+    instrument: FinancingInstrument,
+    counterparty_id: CounterpartyId,
+    netting_set_id: NettingSetId,
+}
+
+impl FinancingTrade {
+    /// Creates a financing trade.
+    pub fn new(
+        id: TradeId,
+        instrument: impl Into<FinancingInstrument>,
+        counterparty_id: CounterpartyId,
+        netting_set_id: NettingSetId,
+    ) -> Self {
+        Self {
+            id,
+            instrument: instrument.into(),
+            counterparty_id,
+            netting_set_id,
+        }
+    }
+
+    /// Returns the trade identifier.
+    #[inline]
+    pub fn id(&self) -> &TradeId {
+        &self.id
+    }
+
+    /// Returns the repo or securities loan.
+    #[inline]
+    pub fn instrument(&self) -> &FinancingInstrument {
+        &self.instrument
+    }
+
+    /// Returns the counterparty identifier.
+    #[inline]
+    pub fn counterparty_id(&self) -> &CounterpartyId {
+        &self.counterparty_id
+    }
+
+    /// Returns the netting set identifier.
+    #[inline]
+    pub fn netting_set_id(&self) -> &NettingSetId {
+        &self.netting_set_id
+    }
+}
+
+/// Financing trades valued from one date.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FinancingBook {
+    as_of: Date,
+    trades: Vec<FinancingTrade>,
+}
+
+impl FinancingBook {
+    /// Creates an empty book valued on `as_of`.
+    pub fn new(as_of: Date) -> Self {
+        Self {
+            as_of,
+            trades: Vec::new(),
+        }
+    }
+
+    /// Adds a trade to the book.
+    pub fn with_trade(mut self, trade: FinancingTrade) -> Self {
+        self.trades.push(trade);
+        self
+    }
+
+    /// Returns the valuation date.
+    #[inline]
+    pub fn as_of(&self) -> Date {
+        self.as_of
+    }
+
+    /// Returns the trades.
+    #[inline]
+    pub fn trades(&self) -> &[FinancingTrade] {
+        &self.trades
+    }
+
+    /// Present value of the book, off the repo curve of `curves`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `curves` has neither a repo nor a discount curve.
+    pub fn present_value(&self, curves: &CurveSet<f64>) -> f64 {
+        self.trades
+            .iter()
+            .map(|trade| trade.instrument.present_value(curves, self.as_of))
+            .sum()
+    }
+
+    /// Net exposure of the whole book at each point of `time_grid` (years
+    /// from the valuation date, Act/365), as one scenario `[1][time_idx]`.
+    pub fn values(&self, time_grid: &[f64]) -> Vec<Vec<f64>> {
+        self.profile(time_grid, |_| true)
+    }
+
+    /// Net exposure of the trades in `netting_set`, as one scenario
+    /// `[1][time_idx]`.
+    pub fn netting_set_values(
+        &self,
+        netting_set: &NettingSetId,
+        time_grid: &[f64],
+    ) -> Vec<Vec<f64>> {
+        self.profile(time_grid, |trade| trade.netting_set_id == *netting_set)
+    }
+
+    fn profile(
+        &self,
+        time_grid: &[f64],
+        include: impl Fn(&FinancingTrade) -> bool,
+    ) -> Vec<Vec<f64>> {
+        let values = time_grid
+            .iter()
+            .map(|&t| {
+                let date = date_after(self.as_of, t);
+                self.trades
+                    .iter()
+                    .filter(|trade| include(trade))
+                    .map(|trade| trade.instrument.net_exposure(date))
+                    .sum()
+            })
+            .collect();
+        vec![values]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use pricer_core::market_data::curves::{CurveEnum, CurveName};
+    use pricer_models::instruments::rates::{
+        CollateralPosition, CollateralSchedule, LendingDirection, RepoDirection,
+    };
+
+    fn book() -> FinancingBook {
+        let as_of = Date::from_ymd(2026, 1, 15).unwrap();
+        let collateral = |value| {
+            CollateralSchedule::constant(as_of, CollateralPosition::new(value, 0.0).unwrap())
+        };
+        let repo = Repo::new(
+            1_000_000.0,
+            0.04,
+            as_of,
+            as_of + 180,
+            RepoDirection::Repo,
+            collateral(1_030_000.0),
+            Currency::USD,
+        )
+        .unwrap();
+        let loan = SecuritiesLoan::new(
+            500_000.0,
+            0.002,
+            as_of,
+            as_of + 365,
+            LendingDirection::Lend,
+            collateral(510_000.0),
+            Currency::USD,
+        )
+        .unwrap();
+        FinancingBook::new(as_of)
+            .with_trade(FinancingTrade::new(
+                TradeId::new("R1"),
+                repo,
+                CounterpartyId::new("CP1"),
+                NettingSetId::new("NS1"),
+            ))
+            .with_trade(FinancingTrade::new(
+                TradeId::new("L1"),
+                loan,
+                CounterpartyId::new("CP2"),
+                NettingSetId::new("NS2"),
+            ))
+    }
+
+    #[test]
+    fn test_exposure_profile_by_netting_set() {
+        let book = book();
+        let time_grid = [0.0, 0.75, 1.5];
+
+        // Repo: over-delivered 30k of collateral; loan: 10k over-collateralised
+        let ns1 = book.netting_set_values(&NettingSetId::new("NS1"), &time_grid);
+        assert_relative_eq!(ns1[0][0], 30_000.0);
+        assert_eq!(ns1[0][1], 0.0);
+        let ns2 = book.netting_set_values(&NettingSetId::new("NS2"), &time_grid);
+        assert_relative_eq!(ns2[0][0], -10_000.0);
+        assert!(ns2[0][1] > -10_000.0);
+        assert_eq!(ns2[0][2], 0.0);
+
+        let total = book.values(&time_grid);
+        assert_eq!(total.len(), 1);
+        assert_relative_eq!(total[0][0], 20_000.0);
+    }
+
+    #[test]
+    fn test_present_value_off_repo_curve() {
+        let book = book();
+        let mut curves = CurveSet::new();
+        curves.insert(CurveName::Repo, CurveEnum::flat(0.04));
+
+        let expected: f64 = book
+            .trades()
+            .iter()
+            .map(|trade| trade.instrument().present_value(&curves, book.as_of()))
+            .sum();
+        assert_relative_eq!(book.present_value(&curves), expected);
+        // Borrowing at the repo curve's rate is close to flat
+        let repo_pv = book.trades()[0]
+            .instrument()
+            .present_value(&curves, book.as_of());
+        assert!(repo_pv.abs() < 500.0);
+    }
+}
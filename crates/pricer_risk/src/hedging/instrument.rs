@@ -0,0 +1,144 @@
+//! CDS hedge instruments and their bucketed CS01.
+
+use super::HedgeError;
+
+/// One basis point.
+const BASIS_POINT: f64 = 1e-4;
+
+/// A CDS usable as a CVA hedge.
+///
+/// `cs01[k]` is the value change of one unit of bought protection for a
+/// 1bp rise of the reference credit spread in bucket `k`, on the same
+/// bucket grid as the [`CvaSpreadDeltas`](super::CvaSpreadDeltas) it
+/// hedges.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CdsHedgeInstrument {
+    tenor: f64,
+    cs01: Vec<f64>,
+}
+
+impl CdsHedgeInstrument {
+    /// Creates an instrument from its bucketed CS01 per unit notional.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HedgeError::InvalidInstrument` if the tenor is not
+    /// positive or a CS01 is not finite.
+    pub fn new(tenor: f64, cs01: Vec<f64>) -> Result<Self, HedgeError> {
+        if !(tenor > 0.0 && tenor.is_finite()) {
+            return Err(HedgeError::InvalidInstrument(format!(
+                "tenor must be positive, got {}",
+                tenor
+            )));
+        }
+        if cs01.iter().any(|v| !v.is_finite()) {
+            return Err(HedgeError::InvalidInstrument(
+                "CS01 must be finite".to_string(),
+            ));
+        }
+        Ok(Self { tenor, cs01 })
+    }
+
+    /// Approximates bucketed CS01 from flat rate and hazard curves.
+    ///
+    /// A spread bump in bucket (b₍ₖ₋₁₎, bₖ] changes the CDS value by its
+    /// risky annuity over that part of the premium leg:
+    ///
+    /// CS01ₖ = 1bp × ∫ exp(-(r + λ)t) dt over (b₍ₖ₋₁₎, min(bₖ, T)]
+    ///
+    /// # Arguments
+    ///
+    /// * `tenor` - CDS maturity T in years
+    /// * `bucket_tenors` - Increasing bucket end points, starting after zero
+    /// * `rate` - Flat risk-free rate r
+    /// * `hazard_rate` - Flat reference-entity hazard rate λ
+    ///
+    /// # Errors
+    ///
+    /// Returns `HedgeError::InvalidInstrument` for a non-positive tenor or
+    /// hazard-plus-rate below zero, and `HedgeError::InvalidDeltas` if the
+    /// bucket grid is not increasing and positive.
+    pub fn from_flat_curves(
+        tenor: f64,
+        bucket_tenors: &[f64],
+        rate: f64,
+        hazard_rate: f64,
+    ) -> Result<Self, HedgeError> {
+        super::optimiser::validate_buckets(bucket_tenors)?;
+        let decay = rate + hazard_rate;
+        if !(decay >= 0.0 && decay.is_finite()) {
+            return Err(HedgeError::InvalidInstrument(
+                "rate plus hazard rate must be non-negative".to_string(),
+            ));
+        }
+
+        let annuity = |t: f64| {
+            if decay == 0.0 {
+                t
+            } else {
+                (1.0 - (-decay * t).exp()) / decay
+            }
+        };
+        let mut start = 0.0_f64;
+        let cs01 = bucket_tenors
+            .iter()
+            .map(|&end| {
+                let bucket_start = start;
+                start = end;
+                let bucket_end = end.min(tenor);
+                if bucket_end <= bucket_start {
+                    0.0
+                } else {
+                    BASIS_POINT * (annuity(bucket_end) - annuity(bucket_start))
+                }
+            })
+            .collect();
+        Self::new(tenor, cs01)
+    }
+
+    /// Returns the CDS maturity in years.
+    #[inline]
+    pub fn tenor(&self) -> f64 {
+        self.tenor
+    }
+
+    /// Returns the bucketed CS01 per unit notional.
+    #[inline]
+    pub fn cs01(&self) -> &[f64] {
+        &self.cs01
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_flat_curve_cs01() {
+        let buckets = [1.0, 3.0, 5.0];
+        let cds = CdsHedgeInstrument::from_flat_curves(3.0, &buckets, 0.0, 0.0).unwrap();
+
+        // Undiscounted: one basis point per year of premium leg
+        assert_relative_eq!(cds.cs01()[0], 1e-4, epsilon = 1e-15);
+        assert_relative_eq!(cds.cs01()[1], 2e-4, epsilon = 1e-15);
+        assert_eq!(cds.cs01()[2], 0.0);
+
+        let discounted = CdsHedgeInstrument::from_flat_curves(5.0, &buckets, 0.03, 0.02).unwrap();
+        let total: f64 = discounted.cs01().iter().sum();
+        assert_relative_eq!(
+            total,
+            1e-4 * (1.0 - (-0.25_f64).exp()) / 0.05,
+            epsilon = 1e-15
+        );
+    }
+
+    #[test]
+    fn test_invalid_instruments() {
+        assert!(CdsHedgeInstrument::new(0.0, vec![1.0]).is_err());
+        assert!(CdsHedgeInstrument::new(1.0, vec![f64::NAN]).is_err());
+        assert!(CdsHedgeInstrument::from_flat_curves(1.0, &[2.0, 1.0], 0.0, 0.0).is_err());
+        assert!(CdsHedgeInstrument::from_flat_curves(1.0, &[1.0], -0.5, 0.0).is_err());
+    }
+}
@@ -0,0 +1,73 @@
+//! CVA hedge recommendations.
+//!
+//! Given a counterparty's CVA sensitivities to its credit spread, bucketed
+//! by tenor, the optimiser proposes CDS protection notionals per hedge
+//! tenor that minimise the residual sensitivity within trade-size limits:
+//!
+//! - [`CvaSpreadDeltas`]: CVA change per 1bp spread bump in each bucket
+//! - [`CdsHedgeInstrument`]: bucketed CS01 of one unit of CDS protection
+//! - [`HedgeConstraints`]: maximum notional, lot size, minimum trade size
+//!   and whether protection may be sold
+//! - [`CvaHedgeOptimiser`]: bound-constrained least squares via L-BFGS-B
+//! - [`HedgeReport`]: proposed trades and residuals, rendered as a table
+//!
+//! # Examples
+//!
+//! ```
+//! use pricer_risk::hedging::{
+//!     CdsHedgeInstrument, CvaHedgeOptimiser, CvaSpreadDeltas, HedgeConstraints,
+//! };
+//! use pricer_risk::portfolio::CounterpartyId;
+//!
+//! let buckets = vec![1.0, 3.0, 5.0];
+//! let deltas = CvaSpreadDeltas::new(
+//!     CounterpartyId::new("CP001"),
+//!     buckets.clone(),
+//!     vec![300.0, 310.0, 180.0], // CVA change per 1bp, by bucket
+//! )
+//! .unwrap();
+//! let instruments: Vec<CdsHedgeInstrument> = [1.0, 3.0, 5.0]
+//!     .iter()
+//!     .map(|&tenor| CdsHedgeInstrument::from_flat_curves(tenor, &buckets, 0.03, 0.02).unwrap())
+//!     .collect();
+//!
+//! let optimiser = CvaHedgeOptimiser::new(HedgeConstraints::new(50_000_000.0).unwrap());
+//! let report = optimiser
+//!     .recommend(&[deltas], |_| instruments.clone())
+//!     .unwrap();
+//!
+//! assert!(report.hedges[0].effectiveness() > 0.99);
+//! println!("{}", report);
+//! ```
+
+mod instrument;
+mod optimiser;
+
+pub use instrument::CdsHedgeInstrument;
+pub use optimiser::{
+    CounterpartyHedge, CvaHedgeOptimiser, CvaSpreadDeltas, HedgeConstraints, HedgeReport,
+    HedgeTrade,
+};
+
+use pricer_optimiser::OptimiserError;
+use thiserror::Error;
+
+/// Errors from hedge recommendation.
+#[derive(Debug, Error)]
+pub enum HedgeError {
+    /// Sensitivities are inconsistent or not finite.
+    #[error("Invalid CVA spread deltas: {0}")]
+    InvalidDeltas(String),
+
+    /// Hedge instrument is inconsistent with the sensitivity buckets.
+    #[error("Invalid hedge instrument: {0}")]
+    InvalidInstrument(String),
+
+    /// Trade-size constraints are invalid.
+    #[error("Invalid hedge constraints: {0}")]
+    InvalidConstraints(String),
+
+    /// The optimiser failed.
+    #[error("Optimiser: {0}")]
+    Optimiser(#[from] OptimiserError),
+}
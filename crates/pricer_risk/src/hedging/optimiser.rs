@@ -0,0 +1,578 @@
+//! Bound-constrained CDS hedge optimisation and the hedge report.
+
+use std::fmt;
+
+use pricer_optimiser::solvers::{Bounds, LbfgsB, LbfgsBConfig};
+use rayon::prelude::*;
+
+use super::{CdsHedgeInstrument, HedgeError};
+use crate::portfolio::CounterpartyId;
+
+/// CVA sensitivities of one counterparty to its credit spread.
+///
+/// `deltas[k]` is the CVA change for a 1bp rise of the counterparty's
+/// spread in bucket (b₍ₖ₋₁₎, bₖ], with b₀ = 0 and bₖ = `bucket_tenors[k]`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CvaSpreadDeltas {
+    counterparty_id: CounterpartyId,
+    bucket_tenors: Vec<f64>,
+    deltas: Vec<f64>,
+}
+
+impl CvaSpreadDeltas {
+    /// Creates bucketed CVA spread deltas.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HedgeError::InvalidDeltas` if the buckets are empty, not
+    /// positive and increasing, differ in length from the deltas, or a
+    /// delta is not finite.
+    pub fn new(
+        counterparty_id: CounterpartyId,
+        bucket_tenors: Vec<f64>,
+        deltas: Vec<f64>,
+    ) -> Result<Self, HedgeError> {
+        validate_buckets(&bucket_tenors)?;
+        if deltas.len() != bucket_tenors.len() {
+            return Err(HedgeError::InvalidDeltas(format!(
+                "{} deltas for {} buckets",
+                deltas.len(),
+                bucket_tenors.len()
+            )));
+        }
+        if deltas.iter().any(|d| !d.is_finite()) {
+            return Err(HedgeError::InvalidDeltas(
+                "deltas must be finite".to_string(),
+            ));
+        }
+        Ok(Self {
+            counterparty_id,
+            bucket_tenors,
+            deltas,
+        })
+    }
+
+    /// Returns the counterparty.
+    #[inline]
+    pub fn counterparty_id(&self) -> &CounterpartyId {
+        &self.counterparty_id
+    }
+
+    /// Returns the bucket end points in years.
+    #[inline]
+    pub fn bucket_tenors(&self) -> &[f64] {
+        &self.bucket_tenors
+    }
+
+    /// Returns the CVA change per 1bp, by bucket.
+    #[inline]
+    pub fn deltas(&self) -> &[f64] {
+        &self.deltas
+    }
+}
+
+/// Trade-size constraints on proposed hedges.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HedgeConstraints {
+    max_notional: f64,
+    lot_size: f64,
+    min_trade_size: f64,
+    allow_sell_protection: bool,
+}
+
+impl HedgeConstraints {
+    /// Creates constraints with a maximum notional per hedge tenor, no lot
+    /// rounding or minimum size, and protection buying only.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HedgeError::InvalidConstraints` if `max_notional` is not
+    /// positive and finite.
+    pub fn new(max_notional: f64) -> Result<Self, HedgeError> {
+        if !(max_notional > 0.0 && max_notional.is_finite()) {
+            return Err(HedgeError::InvalidConstraints(format!(
+                "maximum notional must be positive, got {}",
+                max_notional
+            )));
+        }
+        Ok(Self {
+            max_notional,
+            lot_size: 0.0,
+            min_trade_size: 0.0,
+            allow_sell_protection: false,
+        })
+    }
+
+    /// Rounds notionals to multiples of `lot_size` (zero disables).
+    pub fn with_lot_size(mut self, lot_size: f64) -> Self {
+        self.lot_size = lot_size.max(0.0);
+        self
+    }
+
+    /// Drops trades smaller than `min_trade_size` in absolute notional.
+    pub fn with_min_trade_size(mut self, min_trade_size: f64) -> Self {
+        self.min_trade_size = min_trade_size.max(0.0);
+        self
+    }
+
+    /// Allows selling protection (negative notionals).
+    pub fn allow_sell_protection(mut self) -> Self {
+        self.allow_sell_protection = true;
+        self
+    }
+
+    /// Returns the maximum absolute notional per hedge tenor.
+    #[inline]
+    pub fn max_notional(&self) -> f64 {
+        self.max_notional
+    }
+
+    /// Returns the lot size (zero if notionals are not rounded).
+    #[inline]
+    pub fn lot_size(&self) -> f64 {
+        self.lot_size
+    }
+
+    /// Returns the minimum trade size.
+    #[inline]
+    pub fn min_trade_size(&self) -> f64 {
+        self.min_trade_size
+    }
+
+    /// Returns whether protection may be sold.
+    #[inline]
+    pub fn allows_sell_protection(&self) -> bool {
+        self.allow_sell_protection
+    }
+
+    /// Applies lot rounding and the minimum size to an optimal notional.
+    fn round(&self, notional: f64) -> f64 {
+        let mut rounded = notional;
+        if self.lot_size > 0.0 {
+            rounded = (notional / self.lot_size).round() * self.lot_size;
+            if rounded.abs() > self.max_notional {
+                rounded -= self.lot_size * rounded.signum();
+            }
+        }
+        if rounded.abs() < self.min_trade_size {
+            0.0
+        } else {
+            rounded
+        }
+    }
+}
+
+/// A proposed CDS protection trade; positive notional buys protection.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HedgeTrade {
+    /// CDS maturity in years.
+    pub tenor: f64,
+    /// Protection notional.
+    pub notional: f64,
+}
+
+/// Proposed hedge for one counterparty.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CounterpartyHedge {
+    /// Counterparty hedged.
+    pub counterparty_id: CounterpartyId,
+    /// Trades with a non-zero notional, in hedge tenor order.
+    pub trades: Vec<HedgeTrade>,
+    /// CVA spread deltas before hedging, by bucket.
+    pub initial_deltas: Vec<f64>,
+    /// CVA spread deltas net of the hedge, by bucket.
+    pub residual_deltas: Vec<f64>,
+}
+
+impl CounterpartyHedge {
+    /// Returns the Euclidean norm of the unhedged deltas.
+    pub fn initial_norm(&self) -> f64 {
+        norm(&self.initial_deltas)
+    }
+
+    /// Returns the Euclidean norm of the residual deltas.
+    pub fn residual_norm(&self) -> f64 {
+        norm(&self.residual_deltas)
+    }
+
+    /// Returns the fraction of sensitivity removed, 1 - |residual| / |initial|.
+    ///
+    /// One when there was nothing to hedge.
+    pub fn effectiveness(&self) -> f64 {
+        let initial = self.initial_norm();
+        if initial == 0.0 {
+            1.0
+        } else {
+            1.0 - self.residual_norm() / initial
+        }
+    }
+
+    /// Returns the total absolute protection notional.
+    pub fn gross_notional(&self) -> f64 {
+        self.trades.iter().map(|t| t.notional.abs()).sum()
+    }
+}
+
+/// Hedge recommendations across counterparties.
+///
+/// `Display` renders one row per proposed trade and a per-counterparty
+/// summary of residual sensitivity.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HedgeReport {
+    /// Hedges in the order the deltas were given.
+    pub hedges: Vec<CounterpartyHedge>,
+}
+
+impl HedgeReport {
+    /// Returns the hedge for `counterparty_id`, if any.
+    pub fn hedge(&self, counterparty_id: &CounterpartyId) -> Option<&CounterpartyHedge> {
+        self.hedges
+            .iter()
+            .find(|h| &h.counterparty_id == counterparty_id)
+    }
+
+    /// Returns the total absolute protection notional.
+    pub fn gross_notional(&self) -> f64 {
+        self.hedges
+            .iter()
+            .map(CounterpartyHedge::gross_notional)
+            .sum()
+    }
+}
+
+impl fmt::Display for HedgeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .hedges
+            .iter()
+            .map(|h| h.counterparty_id.as_str().len())
+            .max()
+            .unwrap_or(0)
+            .max("counterparty".len());
+        writeln!(
+            f,
+            "{:<width$}  {:>6}  {:>16}",
+            "counterparty", "tenor", "notional"
+        )?;
+        for hedge in &self.hedges {
+            for trade in &hedge.trades {
+                writeln!(
+                    f,
+                    "{:<width$}  {:>5}y  {:>16.0}",
+                    hedge.counterparty_id.as_str(),
+                    trade.tenor,
+                    trade.notional
+                )?;
+            }
+        }
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:<width$}  {:>14}  {:>14}  {:>13}",
+            "counterparty", "initial |Δ|", "residual |Δ|", "effectiveness"
+        )?;
+        for hedge in &self.hedges {
+            writeln!(
+                f,
+                "{:<width$}  {:>14.2}  {:>14.2}  {:>12.1}%",
+                hedge.counterparty_id.as_str(),
+                hedge.initial_norm(),
+                hedge.residual_norm(),
+                100.0 * hedge.effectiveness()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Proposes CDS hedges for CVA spread sensitivities.
+///
+/// For each counterparty, solves
+///
+/// min ‖Δ - Σⱼ Nⱼ CS01ⱼ‖²  subject to  Nⱼ ∈ [0, N_max]  (or [-N_max, N_max])
+///
+/// with L-BFGS-B on notionals scaled by N_max, then applies lot rounding
+/// and the minimum trade size. Residuals are reported after rounding.
+#[derive(Clone, Debug)]
+pub struct CvaHedgeOptimiser {
+    constraints: HedgeConstraints,
+    solver_config: LbfgsBConfig,
+}
+
+impl CvaHedgeOptimiser {
+    /// Creates an optimiser with the given constraints.
+    pub fn new(constraints: HedgeConstraints) -> Self {
+        Self {
+            constraints,
+            solver_config: LbfgsBConfig {
+                gradient_tolerance: 1e-10,
+                ..LbfgsBConfig::default()
+            },
+        }
+    }
+
+    /// Returns the constraints.
+    #[inline]
+    pub fn constraints(&self) -> &HedgeConstraints {
+        &self.constraints
+    }
+
+    /// Proposes a hedge for one counterparty.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HedgeError::InvalidInstrument` if an instrument's CS01 is
+    /// not on the deltas' bucket grid, and `HedgeError::Optimiser` if the
+    /// solver fails.
+    pub fn hedge(
+        &self,
+        deltas: &CvaSpreadDeltas,
+        instruments: &[CdsHedgeInstrument],
+    ) -> Result<CounterpartyHedge, HedgeError> {
+        let n_buckets = deltas.deltas().len();
+        if let Some(bad) = instruments.iter().find(|i| i.cs01().len() != n_buckets) {
+            return Err(HedgeError::InvalidInstrument(format!(
+                "{}y CDS has {} CS01 buckets, deltas have {}",
+                bad.tenor(),
+                bad.cs01().len(),
+                n_buckets
+            )));
+        }
+
+        let scale = self.constraints.max_notional;
+        let scale_sq = norm(deltas.deltas()).powi(2);
+        let notionals = if instruments.is_empty() || scale_sq == 0.0 {
+            vec![0.0; instruments.len()]
+        } else {
+            // Residual and objective in units where |Δ| = 1 and N_max = 1
+            let residual = |x: &[f64]| -> Vec<f64> {
+                let mut r = deltas.deltas().to_vec();
+                for (xj, instrument) in x.iter().zip(instruments) {
+                    for (rk, hk) in r.iter_mut().zip(instrument.cs01()) {
+                        *rk -= scale * xj * hk;
+                    }
+                }
+                r
+            };
+            let objective = |x: &[f64]| residual(x).iter().map(|r| r * r).sum::<f64>() / scale_sq;
+            let gradient = |x: &[f64]| -> Vec<f64> {
+                let r = residual(x);
+                instruments
+                    .iter()
+                    .map(|instrument| {
+                        let dot: f64 = r.iter().zip(instrument.cs01()).map(|(a, b)| a * b).sum();
+                        -2.0 * scale * dot / scale_sq
+                    })
+                    .collect()
+            };
+
+            let lower = if self.constraints.allow_sell_protection {
+                -1.0
+            } else {
+                0.0
+            };
+            let bounds = Bounds::new(vec![lower; instruments.len()], vec![1.0; instruments.len()])?;
+            let result = LbfgsB::with_config(self.solver_config.clone()).minimise_with_gradient(
+                &vec![0.0; instruments.len()],
+                &bounds,
+                objective,
+                gradient,
+            )?;
+            result
+                .parameters
+                .iter()
+                .map(|x| self.constraints.round(x * scale))
+                .collect()
+        };
+
+        let mut residual_deltas = deltas.deltas().to_vec();
+        for (notional, instrument) in notionals.iter().zip(instruments) {
+            for (rk, hk) in residual_deltas.iter_mut().zip(instrument.cs01()) {
+                *rk -= notional * hk;
+            }
+        }
+        let trades = notionals
+            .iter()
+            .zip(instruments)
+            .filter(|(notional, _)| **notional != 0.0)
+            .map(|(&notional, instrument)| HedgeTrade {
+                tenor: instrument.tenor(),
+                notional,
+            })
+            .collect();
+
+        Ok(CounterpartyHedge {
+            counterparty_id: deltas.counterparty_id().clone(),
+            trades,
+            initial_deltas: deltas.deltas().to_vec(),
+            residual_deltas,
+        })
+    }
+
+    /// Proposes hedges for several counterparties in parallel.
+    ///
+    /// `instruments_for` supplies the available CDS for each counterparty,
+    /// typically built from its own hazard rate.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error from [`hedge`](Self::hedge).
+    pub fn recommend<F>(
+        &self,
+        deltas: &[CvaSpreadDeltas],
+        instruments_for: F,
+    ) -> Result<HedgeReport, HedgeError>
+    where
+        F: Fn(&CounterpartyId) -> Vec<CdsHedgeInstrument> + Sync,
+    {
+        let hedges = deltas
+            .par_iter()
+            .map(|d| self.hedge(d, &instruments_for(d.counterparty_id())))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(HedgeReport { hedges })
+    }
+}
+
+pub(super) fn validate_buckets(bucket_tenors: &[f64]) -> Result<(), HedgeError> {
+    let positive = bucket_tenors.first().is_some_and(|&t| t > 0.0);
+    let increasing = bucket_tenors.windows(2).all(|w| w[1] > w[0]);
+    if positive && increasing && bucket_tenors.iter().all(|t| t.is_finite()) {
+        Ok(())
+    } else {
+        Err(HedgeError::InvalidDeltas(
+            "bucket tenors must be non-empty, positive and increasing".to_string(),
+        ))
+    }
+}
+
+fn norm(values: &[f64]) -> f64 {
+    values.iter().map(|v| v * v).sum::<f64>().sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    const BUCKETS: [f64; 4] = [1.0, 3.0, 5.0, 10.0];
+
+    fn instruments() -> Vec<CdsHedgeInstrument> {
+        [1.0, 3.0, 5.0, 10.0]
+            .iter()
+            .map(|&tenor| {
+                CdsHedgeInstrument::from_flat_curves(tenor, &BUCKETS, 0.03, 0.02).unwrap()
+            })
+            .collect()
+    }
+
+    fn deltas_for(notionals: &[f64]) -> CvaSpreadDeltas {
+        // Deltas replicated exactly by the given protection notionals
+        let mut deltas = vec![0.0; BUCKETS.len()];
+        for (n, instrument) in notionals.iter().zip(instruments()) {
+            for (d, h) in deltas.iter_mut().zip(instrument.cs01()) {
+                *d += n * h;
+            }
+        }
+        CvaSpreadDeltas::new(CounterpartyId::new("CP001"), BUCKETS.to_vec(), deltas).unwrap()
+    }
+
+    #[test]
+    fn test_replicable_deltas_fully_hedged() {
+        let target = [2e6, 5e6, 0.0, 8e6];
+        let optimiser = CvaHedgeOptimiser::new(HedgeConstraints::new(2e7).unwrap());
+
+        let hedge = optimiser
+            .hedge(&deltas_for(&target), &instruments())
+            .unwrap();
+
+        assert!(hedge.effectiveness() > 1.0 - 1e-6);
+        let notional = |tenor: f64| {
+            hedge
+                .trades
+                .iter()
+                .find(|t| t.tenor == tenor)
+                .map_or(0.0, |t| t.notional)
+        };
+        for (tenor, expected) in [(1.0, 2e6), (3.0, 5e6), (5.0, 0.0), (10.0, 8e6)] {
+            assert!((notional(tenor) - expected).abs() < 1e-4 * 2e7);
+        }
+    }
+
+    #[test]
+    fn test_notional_limit_and_rounding() {
+        let target = [0.0, 0.0, 12.3e6, 0.0];
+        let capped = CvaHedgeOptimiser::new(HedgeConstraints::new(1e7).unwrap());
+        let hedge = capped.hedge(&deltas_for(&target), &instruments()).unwrap();
+        assert!(hedge.trades.iter().all(|t| t.notional <= 1e7 + 1e-6));
+        assert!(hedge.effectiveness() < 1.0);
+
+        let lots = CvaHedgeOptimiser::new(
+            HedgeConstraints::new(5e7)
+                .unwrap()
+                .with_lot_size(1e6)
+                .with_min_trade_size(2e6),
+        );
+        let hedge = lots.hedge(&deltas_for(&target), &instruments()).unwrap();
+        assert_eq!(hedge.trades.len(), 1);
+        assert_eq!(hedge.trades[0].tenor, 5.0);
+        assert_relative_eq!(hedge.trades[0].notional, 12e6, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn test_sell_protection() {
+        // Negative deltas need sold protection
+        let target = [0.0, -4e6, 0.0, 0.0];
+        let buy_only = CvaHedgeOptimiser::new(HedgeConstraints::new(1e7).unwrap());
+        let hedge = buy_only
+            .hedge(&deltas_for(&target), &instruments())
+            .unwrap();
+        assert!(hedge.trades.is_empty());
+
+        let two_way =
+            CvaHedgeOptimiser::new(HedgeConstraints::new(1e7).unwrap().allow_sell_protection());
+        let hedge = two_way.hedge(&deltas_for(&target), &instruments()).unwrap();
+        assert!(hedge.effectiveness() > 1.0 - 1e-6);
+        assert!(hedge.trades.iter().any(|t| t.notional < 0.0));
+    }
+
+    #[test]
+    fn test_report_and_errors() {
+        let optimiser = CvaHedgeOptimiser::new(HedgeConstraints::new(2e7).unwrap());
+        let mut other = deltas_for(&[1e6, 0.0, 0.0, 3e6]);
+        other.counterparty_id = CounterpartyId::new("CP002");
+        let zero =
+            CvaSpreadDeltas::new(CounterpartyId::new("CP003"), BUCKETS.to_vec(), vec![0.0; 4])
+                .unwrap();
+
+        let report = optimiser
+            .recommend(&[deltas_for(&[0.0, 2e6, 0.0, 0.0]), other, zero], |_| {
+                instruments()
+            })
+            .unwrap();
+
+        assert_eq!(report.hedges.len(), 3);
+        assert!(report
+            .hedge(&CounterpartyId::new("CP003"))
+            .unwrap()
+            .trades
+            .is_empty());
+        assert_relative_eq!(report.gross_notional(), 6e6, max_relative = 1e-6);
+        let text = report.to_string();
+        assert!(text.contains("CP002"));
+        assert!(text.contains("effectiveness"));
+
+        assert!(optimiser
+            .hedge(
+                &deltas_for(&[1e6, 0.0, 0.0, 0.0]),
+                &[CdsHedgeInstrument::new(1.0, vec![1e-4]).unwrap()]
+            )
+            .is_err());
+        assert!(CvaSpreadDeltas::new(CounterpartyId::new("X"), vec![1.0], vec![1.0, 2.0]).is_err());
+        assert!(HedgeConstraints::new(0.0).is_err());
+    }
+}
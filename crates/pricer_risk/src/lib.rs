@@ -2,17 +2,32 @@
 //!
 //! Portfolio risk management, XVA calculations, and parallelisation.
 //!
-//! **Note**: This crate was renamed from `pricer_risk` to `pricer_risk` in version 0.7.0.
+//! **Note**: This crate was renamed from `pricer_xva` to `pricer_risk` in version 0.7.0.
 //! The new name better reflects the broader risk management capabilities including
-//! risk factors, scenario analysis, and Greeks aggregation.
+//! risk factors, scenario analysis, and Greeks aggregation. Downstream code should
+//! prefer the `neutryx` facade crate, which re-exports the portfolio, exposure and
+//! XVA modules under a single import path.
 //!
 //! This crate provides:
 //! - Portfolio and trade structures with netting sets
-//! - Counterparty credit parameters
+//! - Counterparty credit parameters, including rating-implied hazard curves,
+//!   and portfolio default loss distributions
+//! - Joint rates, equity and FX scenario simulation for exposure
 //! - Exposure aggregation (EE, EPE, PFE)
-//! - CVA, DVA, FVA calculations
+//! - CVA, DVA, FVA, ColVA calculations
+//! - CVA hedge recommendations with CDS protection
+//! - BA-CVA and SA-CVA regulatory capital
+//! - Counterparty PFE, notional and settlement limit monitoring
+//! - VaR and PFE model backtesting with Kupiec, Christoffersen and
+//!   traffic-light results
 //! - Structure of Arrays (SoA) for cache efficiency
 //! - Rayon-based parallelisation for Greeks computation
+//! - Golden-master regression suite for a reference portfolio
+//! - Contractual cashflow projection and liquidity ladders
+//! - Repo and securities-lending funding books for FVA and liquidity
+//! - Theta, cashflow and roll-down carry over a horizon
+//! - Templated XVA, exposure, Greeks and cashflow reports (CSV, JSON, XLSX,
+//!   PDF)
 //!
 //! ## Architecture
 //!
@@ -22,10 +37,21 @@
 //! ├─────────────────────────────────────────┤
 //! │  portfolio/  - Trade, Counterparty,    │
 //! │               NettingSet, Portfolio     │
+//! │  credit/     - Rating transition curves │
+//! │  simulation/ - Hybrid rates/EQ/FX paths │
 //! │  exposure/   - EE, EPE, PFE metrics    │
-//! │  xva/        - CVA, DVA, FVA           │
+//! │  xva/        - CVA, DVA, FVA, ColVA    │
+//! │  hedging/    - CVA CDS hedge proposals │
+//! │  regulatory/ - BA-CVA, SA-CVA capital  │
+//! │  limits/     - Credit limit monitoring │
+//! │  backtest/   - VaR and PFE backtesting │
+//! │  cashflows/  - Cashflow projection     │
+//! │  financing/  - Repo and lending books  │
+//! │  carry/      - Theta and roll-down     │
 //! │  soa/        - Structure of Arrays     │
 //! │  parallel/   - Rayon utilities         │
+//! │  regression/ - Golden-master suite     │
+//! │  reporting/  - Templated risk reports  │
 //! └─────────────────────────────────────────┘
 //!          ↓
 //! ┌─────────────────────────────────────────┐
@@ -87,16 +113,30 @@
 #![deny(rustdoc::broken_intra_doc_links)]
 #![deny(rustdoc::private_intra_doc_links)]
 
+pub mod backtest;
+pub mod carry;
+pub mod cashflows;
+pub mod credit;
 pub mod demo;
 pub mod exposure;
+pub mod financing;
+pub mod hedging;
+pub mod limits;
 pub mod parallel;
 pub mod portfolio;
+pub mod regression;
+pub mod regulatory;
+pub mod reporting;
 pub mod scenarios;
+pub mod simulation;
 pub mod soa;
 pub mod xva;
 
 // Re-export commonly used types
-pub use exposure::ExposureCalculator;
+pub use exposure::{
+    CollateralSimulator, ExposureCalculator, FuturesExposure, NettingTreatment, TimeGridBuilder,
+    TimeGridError,
+};
 pub use parallel::{
     create_shared_monitor, MemoryMonitor, MemoryMonitorConfig, MemoryStats, ParallelConfig,
     ParallelGreeksConfig, ParallelGreeksError, ParallelGreeksStats,
@@ -104,8 +144,9 @@ pub use parallel::{
     DEFAULT_BATCH_SIZE,
 };
 pub use portfolio::{
-    CollateralAgreement, Counterparty, CounterpartyId, CreditParams, CreditRating, NettingSet,
-    NettingSetId, Portfolio, PortfolioBuilder, PortfolioError, Trade, TradeBuilder, TradeId,
+    CollateralAgreement, CollateralAsset, Counterparty, CounterpartyId, CreditParams, CreditRating,
+    MarketDataRequirement, MarketDataRequirements, NettingRules, NettingSet, NettingSetId,
+    Portfolio, PortfolioBuilder, PortfolioError, Trade, TradeBuilder, TradeId,
 };
 pub use scenarios::{
     AggregationMethod, BucketDv01Calculator, BucketDv01Config, BucketDv01Entry, BucketDv01Error,
@@ -115,17 +156,21 @@ pub use scenarios::{
     PresetScenario, PresetScenarioType, RiskFactorId, RiskFactorShift, Scenario, ScenarioEngine,
     ScenarioPnL, ScenarioResult, STANDARD_TENOR_LABELS, STANDARD_TENOR_POINTS,
 };
-pub use soa::{ExposureSoA, TradeSoA};
+pub use soa::{ColumnGreeks, ExposureSoA, TradeSoA};
 pub use xva::{
-    compute_cva, compute_cva_with_survival, compute_dva, compute_dva_with_survival, compute_fba,
-    compute_fca, compute_fva, generate_flat_discount_factors, CounterpartyXva, FundingParams,
-    NettingSetXva, OwnCreditParams, PortfolioXva, XvaCalculator, XvaConfig, XvaError,
+    compute_colva, compute_cva, compute_cva_with_survival, compute_dva, compute_dva_with_survival,
+    compute_fba, compute_fca, compute_fva, generate_flat_discount_factors, ColvaBreakdown,
+    CounterpartyXva, FundingParams, NettingSetXva, OwnCreditParams, PortfolioXva, TradeXva,
+    XvaCalculator, XvaConfig, XvaDiagnostics, XvaError,
 };
 
 // Backward compatibility: provide deprecated alias for migration
-/// Deprecated module alias for backward compatibility.
-/// Use `pricer_risk` directly instead.
-#[deprecated(since = "0.7.0", note = "Use pricer_risk instead of pricer_risk")]
+/// Deprecated module alias for backward compatibility with `pricer_xva`.
+/// Use `pricer_risk` directly, or the `neutryx` facade crate, instead.
+#[deprecated(
+    since = "0.7.0",
+    note = "pricer_xva has been renamed to pricer_risk; prefer the neutryx facade crate"
+)]
 pub mod pricer_risk {
     pub use crate::*;
 }
@@ -0,0 +1,320 @@
+//! Evaluation of limits against a portfolio and exposure run.
+
+use std::collections::HashMap;
+
+use super::{Limit, LimitCheck, LimitError, LimitReport, LimitResult, LimitStatus, LimitType};
+use crate::portfolio::{CounterpartyId, Portfolio};
+
+/// Exposure measures of one run, by counterparty.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LimitInputs {
+    time_grid: Vec<f64>,
+    pfe: HashMap<CounterpartyId, Vec<f64>>,
+    settlement: HashMap<CounterpartyId, f64>,
+}
+
+impl LimitInputs {
+    /// Creates inputs on the run's time grid, in years.
+    pub fn new(time_grid: Vec<f64>) -> Self {
+        Self {
+            time_grid,
+            ..Self::default()
+        }
+    }
+
+    /// Sets a counterparty's PFE profile on the time grid.
+    pub fn with_pfe(mut self, counterparty_id: CounterpartyId, pfe: Vec<f64>) -> Self {
+        self.pfe.insert(counterparty_id, pfe);
+        self
+    }
+
+    /// Sets the largest amount due from a counterparty on any single
+    /// settlement date. Counterparties without one have nothing settling.
+    pub fn with_settlement(mut self, counterparty_id: CounterpartyId, amount: f64) -> Self {
+        self.settlement.insert(counterparty_id, amount);
+        self
+    }
+
+    /// Returns the time grid.
+    #[inline]
+    pub fn time_grid(&self) -> &[f64] {
+        &self.time_grid
+    }
+}
+
+/// Checks counterparty limits.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LimitChecker {
+    limits: Vec<Limit>,
+    warning_level: f64,
+}
+
+impl Default for LimitChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LimitChecker {
+    /// Creates a checker without limits, warning at 80% utilisation.
+    pub fn new() -> Self {
+        Self {
+            limits: Vec::new(),
+            warning_level: 0.8,
+        }
+    }
+
+    /// Adds a limit.
+    ///
+    /// # Errors
+    ///
+    /// Returns `LimitError::DuplicateLimit` if the counterparty already has
+    /// a limit of the same type (and tenor).
+    pub fn with_limit(mut self, limit: Limit) -> LimitResult<Self> {
+        if self
+            .limits
+            .iter()
+            .any(|l| l.counterparty_id == limit.counterparty_id && l.limit_type == limit.limit_type)
+        {
+            return Err(LimitError::DuplicateLimit(format!(
+                "{} {}",
+                limit.counterparty_id, limit.limit_type
+            )));
+        }
+        self.limits.push(limit);
+        Ok(self)
+    }
+
+    /// Adds several limits.
+    ///
+    /// # Errors
+    ///
+    /// As [`LimitChecker::with_limit`].
+    pub fn with_limits(self, limits: impl IntoIterator<Item = Limit>) -> LimitResult<Self> {
+        limits.into_iter().try_fold(self, Self::with_limit)
+    }
+
+    /// Sets the utilisation from which a limit raises a warning.
+    ///
+    /// # Errors
+    ///
+    /// Returns `LimitError::InvalidLimit` if `warning_level` is outside
+    /// `[0, 1]`.
+    pub fn with_warning_level(mut self, warning_level: f64) -> LimitResult<Self> {
+        if !(0.0..=1.0).contains(&warning_level) {
+            return Err(LimitError::InvalidLimit(format!(
+                "warning level {} must be in [0, 1]",
+                warning_level
+            )));
+        }
+        self.warning_level = warning_level;
+        Ok(self)
+    }
+
+    /// Returns the limits.
+    #[inline]
+    pub fn limits(&self) -> &[Limit] {
+        &self.limits
+    }
+
+    /// Returns the warning level.
+    #[inline]
+    pub fn warning_level(&self) -> f64 {
+        self.warning_level
+    }
+
+    /// Measures every limit.
+    ///
+    /// Gross notional is the sum of the absolute notionals of the
+    /// counterparty's trades in `portfolio`, without currency conversion.
+    ///
+    /// # Errors
+    ///
+    /// Returns `LimitError::MissingExposure` if a counterparty with a PFE
+    /// limit has no PFE profile, and `LimitError::TimeGridMismatch` if a
+    /// profile does not match the time grid.
+    pub fn check(&self, portfolio: &Portfolio, inputs: &LimitInputs) -> LimitResult<LimitReport> {
+        let checks = self
+            .limits
+            .iter()
+            .map(|limit| {
+                let (usage, peak_time) = match limit.limit_type {
+                    LimitType::Pfe { tenor } => {
+                        let (usage, time) = Self::peak_pfe(limit, tenor, inputs)?;
+                        (usage, Some(time))
+                    }
+                    LimitType::GrossNotional => (
+                        portfolio
+                            .trades_for_counterparty(&limit.counterparty_id)
+                            .iter()
+                            .map(|t| t.notional().abs())
+                            .sum(),
+                        None,
+                    ),
+                    LimitType::Settlement => (
+                        inputs
+                            .settlement
+                            .get(&limit.counterparty_id)
+                            .copied()
+                            .unwrap_or(0.0),
+                        None,
+                    ),
+                };
+                let status = if usage > limit.amount {
+                    LimitStatus::Breach
+                } else if usage >= self.warning_level * limit.amount && usage > 0.0 {
+                    LimitStatus::Warning
+                } else {
+                    LimitStatus::Within
+                };
+                Ok(LimitCheck {
+                    limit: limit.clone(),
+                    usage,
+                    peak_time,
+                    status,
+                })
+            })
+            .collect::<LimitResult<Vec<_>>>()?;
+        Ok(LimitReport { checks })
+    }
+
+    /// Peak PFE over grid times up to `tenor`, and the time it occurs.
+    fn peak_pfe(limit: &Limit, tenor: f64, inputs: &LimitInputs) -> LimitResult<(f64, f64)> {
+        let pfe = inputs
+            .pfe
+            .get(&limit.counterparty_id)
+            .ok_or_else(|| LimitError::MissingExposure(limit.counterparty_id.to_string()))?;
+        if pfe.len() != inputs.time_grid.len() {
+            return Err(LimitError::TimeGridMismatch {
+                counterparty: limit.counterparty_id.to_string(),
+                expected: inputs.time_grid.len(),
+                actual: pfe.len(),
+            });
+        }
+        Ok(inputs
+            .time_grid
+            .iter()
+            .zip(pfe)
+            .filter(|(&t, _)| t <= tenor)
+            .fold(
+                (0.0, 0.0),
+                |(peak, at), (&t, &p)| {
+                    if p > peak {
+                        (p, t)
+                    } else {
+                        (peak, at)
+                    }
+                },
+            ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::portfolio::{
+        Counterparty, CreditParams, NettingSet, NettingSetId, PortfolioBuilder, Trade, TradeId,
+    };
+    use pricer_core::types::Currency;
+    use pricer_models::instruments::{
+        ExerciseStyle, Instrument, InstrumentParams, PayoffType, VanillaOption,
+    };
+
+    fn portfolio() -> Portfolio {
+        let params = InstrumentParams::new(100.0, 1.0, 1.0).unwrap();
+        let call = VanillaOption::new(params, PayoffType::Call, ExerciseStyle::European, 1e-6);
+        let trade = |id: &str, notional: f64| {
+            Trade::new(
+                TradeId::new(id),
+                Instrument::Vanilla(call.clone()),
+                Currency::USD,
+                CounterpartyId::new("CP1"),
+                NettingSetId::new("NS1"),
+                notional,
+            )
+        };
+        let mut netting_set = NettingSet::new(NettingSetId::new("NS1"), CounterpartyId::new("CP1"));
+        netting_set.add_trades([TradeId::new("T1"), TradeId::new("T2")]);
+        PortfolioBuilder::new()
+            .add_counterparty(Counterparty::new(
+                CounterpartyId::new("CP1"),
+                CreditParams::new(0.02, 0.4).unwrap(),
+            ))
+            .add_netting_set(netting_set)
+            .add_trades([trade("T1", 4e6), trade("T2", -3e6)])
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_pfe_limits_by_tenor() {
+        let cp = CounterpartyId::new("CP1");
+        let checker = LimitChecker::new()
+            .with_limits([
+                Limit::pfe(cp.clone(), 1.0, 10.0).unwrap(),
+                Limit::pfe(cp.clone(), 10.0, 20.0).unwrap(),
+            ])
+            .unwrap();
+        let inputs =
+            LimitInputs::new(vec![0.5, 1.0, 3.0, 10.0]).with_pfe(cp, vec![4.0, 7.0, 21.0, 5.0]);
+        let report = checker.check(&portfolio(), &inputs).unwrap();
+
+        assert_eq!(report.checks[0].usage, 7.0);
+        assert_eq!(report.checks[0].peak_time, Some(1.0));
+        assert_eq!(report.checks[0].status, LimitStatus::Within);
+        assert_eq!(report.checks[1].usage, 21.0);
+        assert_eq!(report.checks[1].peak_time, Some(3.0));
+        assert_eq!(report.checks[1].status, LimitStatus::Breach);
+    }
+
+    #[test]
+    fn test_notional_and_settlement_limits() {
+        let cp = CounterpartyId::new("CP1");
+        let checker = LimitChecker::new()
+            .with_limits([
+                Limit::gross_notional(cp.clone(), 8e6).unwrap(),
+                Limit::settlement(cp.clone(), 1e6).unwrap(),
+                Limit::settlement(CounterpartyId::new("CP2"), 1e6).unwrap(),
+            ])
+            .unwrap()
+            .with_warning_level(0.9)
+            .unwrap();
+        let inputs = LimitInputs::new(vec![1.0]).with_settlement(cp, 0.95e6);
+        let report = checker.check(&portfolio(), &inputs).unwrap();
+
+        // |4m| + |-3m| = 7m of 8m is below the 90% warning level
+        assert_eq!(report.checks[0].usage, 7e6);
+        assert_eq!(report.checks[0].status, LimitStatus::Within);
+        assert_eq!(report.checks[1].status, LimitStatus::Warning);
+        assert_eq!(report.checks[2].usage, 0.0);
+        assert!(!report.has_breaches());
+    }
+
+    #[test]
+    fn test_rejects_inconsistent_inputs() {
+        let cp = CounterpartyId::new("CP1");
+        assert!(matches!(
+            LimitChecker::new()
+                .with_limit(Limit::settlement(cp.clone(), 1.0).unwrap())
+                .unwrap()
+                .with_limit(Limit::settlement(cp.clone(), 2.0).unwrap()),
+            Err(LimitError::DuplicateLimit(_))
+        ));
+        assert!(LimitChecker::new().with_warning_level(1.5).is_err());
+
+        let checker = LimitChecker::new()
+            .with_limit(Limit::pfe(cp.clone(), 1.0, 1.0).unwrap())
+            .unwrap();
+        assert!(matches!(
+            checker.check(&portfolio(), &LimitInputs::new(vec![1.0])),
+            Err(LimitError::MissingExposure(_))
+        ));
+        assert!(matches!(
+            checker.check(
+                &portfolio(),
+                &LimitInputs::new(vec![1.0]).with_pfe(cp, vec![1.0, 2.0])
+            ),
+            Err(LimitError::TimeGridMismatch { .. })
+        ));
+    }
+}
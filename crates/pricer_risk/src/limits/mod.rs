@@ -0,0 +1,381 @@
+//! Counterparty credit limit monitoring.
+//!
+//! Credit officers set limits per counterparty; after each exposure run
+//! the [`LimitChecker`] measures every limit and reports its utilisation:
+//!
+//! - [`LimitType::Pfe`]: peak potential future exposure up to a tenor,
+//!   usually one limit per tenor of a ladder (1Y, 5Y, 10Y)
+//! - [`LimitType::GrossNotional`]: sum of absolute trade notionals
+//! - [`LimitType::Settlement`]: payments due from the counterparty on one
+//!   settlement date, at risk while ours are paid away
+//!
+//! Limits used beyond the warning level or breached are reported as
+//! [`LimitEvent`]s, which serialise (with the `serde` feature) for
+//! downstream alerting and dashboards.
+//!
+//! # Examples
+//!
+//! ```
+//! use pricer_risk::limits::{Limit, LimitChecker, LimitInputs, LimitStatus};
+//! use pricer_risk::portfolio::{CounterpartyId, PortfolioBuilder};
+//!
+//! let cp = CounterpartyId::new("CP001");
+//! let checker = LimitChecker::new()
+//!     .with_limit(Limit::pfe(cp.clone(), 1.0, 10_000_000.0).unwrap())
+//!     .unwrap()
+//!     .with_limit(Limit::pfe(cp.clone(), 5.0, 15_000_000.0).unwrap())
+//!     .unwrap();
+//!
+//! let inputs = LimitInputs::new(vec![0.5, 1.0, 2.0, 5.0])
+//!     .with_pfe(cp, vec![6e6, 9e6, 16e6, 12e6]);
+//! let report = checker.check(&PortfolioBuilder::new().build().unwrap(), &inputs).unwrap();
+//!
+//! assert_eq!(report.checks[0].status, LimitStatus::Warning); // 9m of 10m
+//! assert_eq!(report.checks[1].status, LimitStatus::Breach); // 16m of 15m
+//! assert_eq!(report.events().len(), 2);
+//! ```
+
+mod checker;
+
+pub use checker::{LimitChecker, LimitInputs};
+
+use std::fmt;
+
+use thiserror::Error;
+
+use crate::portfolio::CounterpartyId;
+
+/// Errors from limit definitions and checks.
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum LimitError {
+    /// A limit amount, tenor or warning level is out of range.
+    #[error("Invalid limit: {0}")]
+    InvalidLimit(String),
+
+    /// The same limit is defined twice for a counterparty.
+    #[error("Duplicate limit: {0}")]
+    DuplicateLimit(String),
+
+    /// No PFE profile was supplied for a counterparty with a PFE limit.
+    #[error("Missing PFE profile for counterparty: {0}")]
+    MissingExposure(String),
+
+    /// A PFE profile does not match the time grid.
+    #[error("PFE profile of {counterparty} has {actual} points, time grid has {expected}")]
+    TimeGridMismatch {
+        /// Counterparty
+        counterparty: String,
+        /// Time grid length
+        expected: usize,
+        /// Profile length
+        actual: usize,
+    },
+}
+
+/// Result type for limit monitoring.
+pub type LimitResult<T> = Result<T, LimitError>;
+
+/// Measure a limit caps.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LimitType {
+    /// Peak PFE over `[0, tenor]`
+    Pfe {
+        /// Tenor in years
+        tenor: f64,
+    },
+    /// Sum of absolute trade notionals
+    GrossNotional,
+    /// Payments due from the counterparty on one settlement date
+    Settlement,
+}
+
+impl fmt::Display for LimitType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitType::Pfe { tenor } => write!(f, "PFE {}Y", tenor),
+            LimitType::GrossNotional => f.write_str("Gross notional"),
+            LimitType::Settlement => f.write_str("Settlement"),
+        }
+    }
+}
+
+/// Limit on one measure of a counterparty's exposure.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Limit {
+    /// Counterparty
+    pub counterparty_id: CounterpartyId,
+    /// Measure the limit caps
+    pub limit_type: LimitType,
+    /// Maximum allowed usage
+    pub amount: f64,
+}
+
+impl Limit {
+    /// Creates a limit.
+    ///
+    /// # Errors
+    ///
+    /// Returns `LimitError::InvalidLimit` if `amount` is negative or not
+    /// finite, or a PFE tenor is not positive.
+    pub fn new(
+        counterparty_id: CounterpartyId,
+        limit_type: LimitType,
+        amount: f64,
+    ) -> LimitResult<Self> {
+        if !(amount >= 0.0 && amount.is_finite()) {
+            return Err(LimitError::InvalidLimit(format!(
+                "{} limit {} of {} must be non-negative and finite",
+                limit_type, amount, counterparty_id
+            )));
+        }
+        if let LimitType::Pfe { tenor } = limit_type {
+            if !(tenor > 0.0 && tenor.is_finite()) {
+                return Err(LimitError::InvalidLimit(format!(
+                    "PFE tenor {} of {} must be positive and finite",
+                    tenor, counterparty_id
+                )));
+            }
+        }
+        Ok(Self {
+            counterparty_id,
+            limit_type,
+            amount,
+        })
+    }
+
+    /// Creates a PFE limit up to `tenor` years.
+    ///
+    /// # Errors
+    ///
+    /// As [`Limit::new`].
+    pub fn pfe(counterparty_id: CounterpartyId, tenor: f64, amount: f64) -> LimitResult<Self> {
+        Self::new(counterparty_id, LimitType::Pfe { tenor }, amount)
+    }
+
+    /// Creates a gross notional limit.
+    ///
+    /// # Errors
+    ///
+    /// As [`Limit::new`].
+    pub fn gross_notional(counterparty_id: CounterpartyId, amount: f64) -> LimitResult<Self> {
+        Self::new(counterparty_id, LimitType::GrossNotional, amount)
+    }
+
+    /// Creates a settlement limit.
+    ///
+    /// # Errors
+    ///
+    /// As [`Limit::new`].
+    pub fn settlement(counterparty_id: CounterpartyId, amount: f64) -> LimitResult<Self> {
+        Self::new(counterparty_id, LimitType::Settlement, amount)
+    }
+}
+
+/// Utilisation band of a limit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LimitStatus {
+    /// Below the warning level
+    Within,
+    /// At or above the warning level, within the limit
+    Warning,
+    /// Above the limit
+    Breach,
+}
+
+impl fmt::Display for LimitStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            LimitStatus::Within => "within",
+            LimitStatus::Warning => "warning",
+            LimitStatus::Breach => "breach",
+        })
+    }
+}
+
+/// Measured usage of one limit.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LimitCheck {
+    /// Limit checked
+    pub limit: Limit,
+    /// Measured usage
+    pub usage: f64,
+    /// Time in years at which a PFE limit's usage peaks
+    pub peak_time: Option<f64>,
+    /// Utilisation band
+    pub status: LimitStatus,
+}
+
+impl LimitCheck {
+    /// Returns usage as a fraction of the limit (infinite for a zero limit
+    /// with positive usage).
+    pub fn utilisation(&self) -> f64 {
+        if self.limit.amount > 0.0 {
+            self.usage / self.limit.amount
+        } else if self.usage > 0.0 {
+            f64::INFINITY
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns the usage above the limit, zero if within.
+    pub fn excess(&self) -> f64 {
+        (self.usage - self.limit.amount).max(0.0)
+    }
+}
+
+/// Warning or breach raised by a limit check.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LimitEvent {
+    /// Counterparty
+    pub counterparty_id: CounterpartyId,
+    /// Measure the limit caps
+    pub limit_type: LimitType,
+    /// Warning or breach
+    pub status: LimitStatus,
+    /// Limit amount
+    pub amount: f64,
+    /// Measured usage
+    pub usage: f64,
+    /// Usage as a fraction of the limit
+    pub utilisation: f64,
+    /// Time in years at which a PFE limit's usage peaks
+    pub peak_time: Option<f64>,
+}
+
+impl fmt::Display for LimitEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} limit {}: usage {:.0} of {:.0} ({:.1}%)",
+            self.counterparty_id,
+            self.limit_type,
+            self.status,
+            self.usage,
+            self.amount,
+            100.0 * self.utilisation
+        )
+    }
+}
+
+/// Result of checking all limits against one exposure run.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LimitReport {
+    /// One check per limit, in the checker's order
+    pub checks: Vec<LimitCheck>,
+}
+
+impl LimitReport {
+    /// Returns the warnings and breaches, breaches first.
+    pub fn events(&self) -> Vec<LimitEvent> {
+        let mut events: Vec<LimitEvent> = self
+            .checks
+            .iter()
+            .filter(|c| c.status != LimitStatus::Within)
+            .map(|c| LimitEvent {
+                counterparty_id: c.limit.counterparty_id.clone(),
+                limit_type: c.limit.limit_type,
+                status: c.status,
+                amount: c.limit.amount,
+                usage: c.usage,
+                utilisation: c.utilisation(),
+                peak_time: c.peak_time,
+            })
+            .collect();
+        events.sort_by(|a, b| b.status.cmp(&a.status));
+        events
+    }
+
+    /// Returns the breached checks.
+    pub fn breaches(&self) -> impl Iterator<Item = &LimitCheck> {
+        self.checks
+            .iter()
+            .filter(|c| c.status == LimitStatus::Breach)
+    }
+
+    /// Returns `true` if any limit is breached.
+    pub fn has_breaches(&self) -> bool {
+        self.breaches().next().is_some()
+    }
+
+    /// Returns the checks of one counterparty.
+    pub fn for_counterparty<'a>(
+        &'a self,
+        counterparty_id: &'a CounterpartyId,
+    ) -> impl Iterator<Item = &'a LimitCheck> {
+        self.checks
+            .iter()
+            .filter(move |c| &c.limit.counterparty_id == counterparty_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(amount: f64, usage: f64, status: LimitStatus) -> LimitCheck {
+        LimitCheck {
+            limit: Limit::gross_notional(CounterpartyId::new("CP"), amount).unwrap(),
+            usage,
+            peak_time: None,
+            status,
+        }
+    }
+
+    #[test]
+    fn test_limit_validation() {
+        assert!(Limit::pfe(CounterpartyId::new("CP"), 1.0, 1e6).is_ok());
+        assert!(matches!(
+            Limit::pfe(CounterpartyId::new("CP"), 0.0, 1e6),
+            Err(LimitError::InvalidLimit(_))
+        ));
+        assert!(matches!(
+            Limit::settlement(CounterpartyId::new("CP"), -1.0),
+            Err(LimitError::InvalidLimit(_))
+        ));
+        assert!(Limit::gross_notional(CounterpartyId::new("CP"), 0.0).is_ok());
+    }
+
+    #[test]
+    fn test_utilisation_and_excess() {
+        let c = check(100.0, 120.0, LimitStatus::Breach);
+        assert_eq!(c.utilisation(), 1.2);
+        assert_eq!(c.excess(), 20.0);
+        assert_eq!(check(100.0, 50.0, LimitStatus::Within).excess(), 0.0);
+        assert_eq!(
+            check(0.0, 1.0, LimitStatus::Breach).utilisation(),
+            f64::INFINITY
+        );
+        assert_eq!(check(0.0, 0.0, LimitStatus::Within).utilisation(), 0.0);
+    }
+
+    #[test]
+    fn test_report_events_breaches_first() {
+        let report = LimitReport {
+            checks: vec![
+                check(100.0, 85.0, LimitStatus::Warning),
+                check(100.0, 10.0, LimitStatus::Within),
+                check(100.0, 130.0, LimitStatus::Breach),
+            ],
+        };
+        let events = report.events();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].status, LimitStatus::Breach);
+        assert_eq!(
+            events[0].to_string(),
+            "CP Gross notional limit breach: usage 130 of 100 (130.0%)"
+        );
+        assert!(report.has_breaches());
+        assert_eq!(report.breaches().count(), 1);
+        assert_eq!(
+            report.for_counterparty(&CounterpartyId::new("CP")).count(),
+            3
+        );
+    }
+}
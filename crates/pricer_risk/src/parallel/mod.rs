@@ -5,7 +5,8 @@
 //!
 //! # Performance Targets
 //!
-//! - >80% parallel efficiency on 8+ cores
+//! - Over 80% parallel efficiency on 8+ cores, and on 32 cores for the
+//!   [`PricingService`] on 100k trades (`benches/pricing_service_scaling.rs`)
 //! - Minimal memory allocation in hot paths
 //! - Batch processing for optimal cache utilisation
 //!
@@ -13,9 +14,12 @@
 //!
 //! - [`ParallelPortfolioGreeksCalculator`] - Parallel portfolio Greeks calculation for 1000+ trades
 //! - [`MemoryMonitor`] - Memory monitoring and auto-checkpoint mechanism
+//! - [`PricingService`] - Portfolio pricing as per-netting-set task graphs on
+//!   a work-stealing pool with NUMA-aware thread pinning
 
 mod memory_monitor;
 mod portfolio_greeks;
+mod pricing_service;
 
 pub use memory_monitor::{
     create_shared_monitor, MemoryMonitor, MemoryMonitorConfig, MemoryStats, SharedMemoryMonitor,
@@ -24,6 +28,10 @@ pub use portfolio_greeks::{
     ParallelGreeksConfig, ParallelGreeksError, ParallelGreeksStats,
     ParallelPortfolioGreeksCalculator, PortfolioGreeksResult,
 };
+pub use pricing_service::{
+    CpuTopology, NettingSetValuation, PinningPolicy, PortfolioValuation, PricingRunStats,
+    PricingService, PricingServiceConfig, DEFAULT_CHUNK_SIZE,
+};
 
 use rayon::prelude::*;
 
@@ -148,7 +156,7 @@ mod tests {
         let sums: Vec<i32> = process_in_batches(&items, 10, |batch| batch.iter().sum());
 
         assert_eq!(sums.len(), 10);
-        assert_eq!(sums.iter().sum::<i32>(), (0..100).sum());
+        assert_eq!(sums.iter().sum::<i32>(), (0..100).sum::<i32>());
     }
 
     #[test]
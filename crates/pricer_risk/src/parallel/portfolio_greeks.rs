@@ -46,6 +46,10 @@ pub enum ParallelGreeksError {
         /// Error message.
         message: String,
     },
+
+    /// Thread pool could not be built.
+    #[error("Thread pool error: {0}")]
+    ThreadPoolError(String),
 }
 
 /// Statistics for parallel computation.
@@ -0,0 +1,503 @@
+//! Throughput-oriented portfolio pricing service.
+//!
+//! A [`PricingService`] owns a dedicated Rayon pool and prices a portfolio
+//! as one task graph per netting set:
+//!
+//! ```text
+//! portfolio ──┬── NS1 ──┬── chunk ── chunk ── chunk   (price trades)
+//!             │         └── net                       (sum, exposure)
+//!             ├── NS2 ──┬── chunk
+//!             │         └── net
+//!             └── ...
+//! ```
+//!
+//! Netting sets are scheduled largest first and split into chunks of
+//! trades, so a single large netting set does not leave the tail of the run
+//! on one core; idle workers steal chunks from busy ones. Each netting set
+//! is netted as soon as its own chunks complete.
+//!
+//! Workers can be pinned to cores following the machine's NUMA topology
+//! ([`CpuTopology`]): [`PinningPolicy::Compact`] fills one node before the
+//! next, keeping a small pool on local memory, while
+//! [`PinningPolicy::Scatter`] spreads workers across nodes for memory
+//! bandwidth. Pinning is applied on Linux and ignored elsewhere.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+
+use super::ParallelGreeksError;
+use crate::portfolio::{NettingSetId, Portfolio, Trade, TradeId};
+
+/// Default number of trades per pricing task.
+pub const DEFAULT_CHUNK_SIZE: usize = 256;
+
+/// Logical cores grouped by NUMA node.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CpuTopology {
+    nodes: Vec<Vec<usize>>,
+}
+
+impl CpuTopology {
+    /// Creates a topology from the cores of each node.
+    ///
+    /// Empty nodes are dropped; with no cores at all, core 0 forms a single
+    /// node.
+    pub fn from_nodes(nodes: Vec<Vec<usize>>) -> Self {
+        let nodes: Vec<Vec<usize>> = nodes.into_iter().filter(|n| !n.is_empty()).collect();
+        if nodes.is_empty() {
+            Self {
+                nodes: vec![vec![0]],
+            }
+        } else {
+            Self { nodes }
+        }
+    }
+
+    /// Detects the topology of this machine.
+    ///
+    /// On Linux the nodes are read from `/sys/devices/system/node`; if that
+    /// is unavailable, or on other platforms, all available cores form a
+    /// single node.
+    pub fn detect() -> Self {
+        let nodes = Self::linux_nodes().unwrap_or_default();
+        if nodes.is_empty() {
+            let cores = std::thread::available_parallelism().map_or(1, |n| n.get());
+            Self::from_nodes(vec![(0..cores).collect()])
+        } else {
+            Self::from_nodes(nodes)
+        }
+    }
+
+    fn linux_nodes() -> Option<Vec<Vec<usize>>> {
+        let mut nodes: Vec<(usize, Vec<usize>)> = std::fs::read_dir("/sys/devices/system/node")
+            .ok()?
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                let name = entry.file_name().into_string().ok()?;
+                let index = name.strip_prefix("node")?.parse().ok()?;
+                let cpulist = std::fs::read_to_string(entry.path().join("cpulist")).ok()?;
+                Some((index, parse_cpulist(&cpulist)?))
+            })
+            .collect();
+        nodes.sort_by_key(|(index, _)| *index);
+        Some(nodes.into_iter().map(|(_, cores)| cores).collect())
+    }
+
+    /// Number of NUMA nodes.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Number of logical cores.
+    pub fn core_count(&self) -> usize {
+        self.nodes.iter().map(Vec::len).sum()
+    }
+
+    /// Cores of each node.
+    pub fn nodes(&self) -> &[Vec<usize>] {
+        &self.nodes
+    }
+
+    /// Core that worker `index` is pinned to under `policy`.
+    ///
+    /// Workers beyond the number of cores wrap around.
+    pub fn core_for_worker(&self, index: usize, policy: PinningPolicy) -> Option<usize> {
+        match policy {
+            PinningPolicy::None => None,
+            PinningPolicy::Compact => {
+                let cores: Vec<usize> = self.nodes.iter().flatten().copied().collect();
+                Some(cores[index % cores.len()])
+            }
+            PinningPolicy::Scatter => {
+                // Round-robin over nodes, then over the cores within each
+                let mut order = Vec::with_capacity(self.core_count());
+                let depth = self.nodes.iter().map(Vec::len).max().unwrap_or(0);
+                for slot in 0..depth {
+                    order.extend(self.nodes.iter().filter_map(|node| node.get(slot)));
+                }
+                Some(order[index % order.len()])
+            }
+        }
+    }
+}
+
+/// Parses a Linux cpulist such as `0-3,8-11`.
+fn parse_cpulist(list: &str) -> Option<Vec<usize>> {
+    let mut cores = Vec::new();
+    for part in list.trim().split(',').filter(|p| !p.is_empty()) {
+        match part.split_once('-') {
+            Some((lo, hi)) => cores.extend(lo.parse::<usize>().ok()?..=hi.parse().ok()?),
+            None => cores.push(part.parse().ok()?),
+        }
+    }
+    Some(cores)
+}
+
+/// How pool workers are pinned to cores.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PinningPolicy {
+    /// Leave placement to the operating system
+    #[default]
+    None,
+    /// Fill the cores of one NUMA node before the next
+    Compact,
+    /// Spread workers round-robin across NUMA nodes
+    Scatter,
+}
+
+/// Pins the calling thread to `core`, returning whether it succeeded.
+///
+/// Cores the process may not run on are skipped rather than requested.
+fn pin_current_thread(core: usize) -> bool {
+    let core = core_affinity::CoreId { id: core };
+    core_affinity::get_core_ids().is_some_and(|ids| ids.contains(&core))
+        && core_affinity::set_for_current(core)
+}
+
+/// Configuration of a [`PricingService`].
+///
+/// | Parameter | Default | Description |
+/// |-----------|---------|-------------|
+/// | `threads` | all cores | Pool size |
+/// | `chunk_size` | 256 | Trades per pricing task |
+/// | `pinning` | none | Worker placement |
+#[derive(Clone, Debug, PartialEq)]
+pub struct PricingServiceConfig {
+    /// Worker threads (0 for one per core of the topology)
+    pub threads: usize,
+    /// Trades per pricing task
+    pub chunk_size: usize,
+    /// Worker placement
+    pub pinning: PinningPolicy,
+    /// Core topology used for pinning
+    pub topology: CpuTopology,
+}
+
+impl Default for PricingServiceConfig {
+    fn default() -> Self {
+        Self {
+            threads: 0,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            pinning: PinningPolicy::None,
+            topology: CpuTopology::detect(),
+        }
+    }
+}
+
+impl PricingServiceConfig {
+    /// Creates the default configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of worker threads.
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads;
+        self
+    }
+
+    /// Sets the number of trades per pricing task.
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Sets the pinning policy.
+    pub fn with_pinning(mut self, pinning: PinningPolicy) -> Self {
+        self.pinning = pinning;
+        self
+    }
+
+    /// Sets the core topology.
+    pub fn with_topology(mut self, topology: CpuTopology) -> Self {
+        self.topology = topology;
+        self
+    }
+}
+
+/// Value of one netting set.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NettingSetValuation {
+    /// Netting set
+    pub netting_set_id: NettingSetId,
+    /// Net mark-to-market
+    pub value: f64,
+    /// Current exposure, `max(value, 0)`
+    pub exposure: f64,
+    /// Trades priced
+    pub trade_count: usize,
+}
+
+/// Run statistics of a pricing service.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PricingRunStats {
+    /// Worker threads
+    pub threads: usize,
+    /// Pricing tasks (trade chunks) executed
+    pub tasks: usize,
+    /// Trades priced
+    pub trades: usize,
+    /// Wall-clock time
+    pub elapsed: Duration,
+}
+
+impl PricingRunStats {
+    /// Trades priced per second.
+    pub fn throughput(&self) -> f64 {
+        self.trades as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+/// Netted value, trade values and task count of one netting set graph.
+type GraphResult = (Option<NettingSetValuation>, Vec<(TradeId, f64)>, usize);
+
+/// Prices and nets a whole portfolio.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PortfolioValuation {
+    /// Value of each trade
+    pub trade_values: HashMap<TradeId, f64>,
+    /// Netting sets, largest first
+    pub netting_sets: Vec<NettingSetValuation>,
+    /// Run statistics
+    pub stats: PricingRunStats,
+}
+
+impl PortfolioValuation {
+    /// Sum of the netting set values.
+    pub fn total_value(&self) -> f64 {
+        self.netting_sets.iter().map(|ns| ns.value).sum()
+    }
+
+    /// Sum of the netting set exposures.
+    pub fn total_exposure(&self) -> f64 {
+        self.netting_sets.iter().map(|ns| ns.exposure).sum()
+    }
+}
+
+/// Portfolio pricing on a dedicated work-stealing pool.
+///
+/// # Examples
+///
+/// ```
+/// use pricer_risk::parallel::{PinningPolicy, PricingService, PricingServiceConfig};
+/// use pricer_risk::portfolio::PortfolioBuilder;
+///
+/// let service = PricingService::new(
+///     PricingServiceConfig::new()
+///         .with_threads(2)
+///         .with_pinning(PinningPolicy::Scatter),
+/// )
+/// .unwrap();
+///
+/// let portfolio = PortfolioBuilder::new().build().unwrap();
+/// let valuation = service.price(&portfolio, |trade| trade.notional() * 0.01);
+/// assert_eq!(valuation.total_value(), 0.0);
+/// assert_eq!(valuation.stats.threads, 2);
+/// ```
+pub struct PricingService {
+    pool: ThreadPool,
+    config: PricingServiceConfig,
+}
+
+impl PricingService {
+    /// Creates the service and its thread pool, pinning workers as
+    /// configured.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ParallelGreeksError::ThreadPoolError` if the pool cannot be
+    /// built.
+    pub fn new(config: PricingServiceConfig) -> Result<Self, ParallelGreeksError> {
+        let threads = if config.threads == 0 {
+            config.topology.core_count()
+        } else {
+            config.threads
+        };
+        let topology = config.topology.clone();
+        let pinning = config.pinning;
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .thread_name(|i| format!("neutryx-pricing-{}", i))
+            .start_handler(move |i| {
+                if let Some(core) = topology.core_for_worker(i, pinning) {
+                    pin_current_thread(core);
+                }
+            })
+            .build()
+            .map_err(|e| ParallelGreeksError::ThreadPoolError(e.to_string()))?;
+        Ok(Self { pool, config })
+    }
+
+    /// Configuration of the service.
+    pub fn config(&self) -> &PricingServiceConfig {
+        &self.config
+    }
+
+    /// Number of worker threads.
+    pub fn threads(&self) -> usize {
+        self.pool.current_num_threads()
+    }
+
+    /// Prices every trade with `pricer` and nets each netting set.
+    ///
+    /// Trades outside any netting set are priced but not netted.
+    pub fn price<F>(&self, portfolio: &Portfolio, pricer: F) -> PortfolioValuation
+    where
+        F: Fn(&Trade) -> f64 + Sync,
+    {
+        let start = Instant::now();
+        let chunk_size = self.config.chunk_size.max(1);
+
+        // Largest netting sets first, so that they start before the pool
+        // is busy with small ones
+        let mut groups: HashMap<Option<&NettingSetId>, Vec<&Trade>> = HashMap::new();
+        for trade in portfolio.trades() {
+            let id = trade.netting_set_id();
+            let key = portfolio.netting_set(id).map(|_| id);
+            groups.entry(key).or_default().push(trade);
+        }
+        let mut graphs: Vec<(Option<&NettingSetId>, Vec<&Trade>)> = groups.into_iter().collect();
+        graphs.sort_by(|a, b| {
+            b.1.len()
+                .cmp(&a.1.len())
+                .then_with(|| a.0.map(|id| id.as_str()).cmp(&b.0.map(|id| id.as_str())))
+        });
+
+        let results: Vec<GraphResult> = self.pool.install(|| {
+            graphs
+                .par_iter()
+                .map(|(id, trades)| {
+                    let chunks: Vec<Vec<(TradeId, f64)>> = trades
+                        .par_chunks(chunk_size)
+                        .map(|chunk| {
+                            chunk
+                                .iter()
+                                .map(|trade| (trade.id().clone(), pricer(trade)))
+                                .collect()
+                        })
+                        .collect();
+                    let tasks = chunks.len();
+                    let values: Vec<(TradeId, f64)> = chunks.into_iter().flatten().collect();
+                    let netted = id.map(|id| {
+                        let value: f64 = values.iter().map(|(_, v)| v).sum();
+                        NettingSetValuation {
+                            netting_set_id: id.clone(),
+                            value,
+                            exposure: value.max(0.0),
+                            trade_count: values.len(),
+                        }
+                    });
+                    (netted, values, tasks)
+                })
+                .collect()
+        });
+
+        let mut trade_values = HashMap::with_capacity(portfolio.trade_count());
+        let mut netting_sets = Vec::with_capacity(portfolio.netting_set_count());
+        let mut tasks = 0;
+        for (netted, values, n) in results {
+            netting_sets.extend(netted);
+            trade_values.extend(values);
+            tasks += n;
+        }
+
+        PortfolioValuation {
+            stats: PricingRunStats {
+                threads: self.threads(),
+                tasks,
+                trades: trade_values.len(),
+                elapsed: start.elapsed(),
+            },
+            trade_values,
+            netting_sets,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::portfolio::{
+        Counterparty, CounterpartyId, CreditParams, NettingSet, PortfolioBuilder,
+    };
+    use pricer_core::types::Currency;
+    use pricer_models::instruments::{
+        ExerciseStyle, Instrument, InstrumentParams, PayoffType, VanillaOption,
+    };
+
+    fn portfolio(sizes: &[usize]) -> Portfolio {
+        let cp = CounterpartyId::new("CP");
+        let mut builder = PortfolioBuilder::new().add_counterparty(Counterparty::new(
+            cp.clone(),
+            CreditParams::new(0.01, 0.6).unwrap(),
+        ));
+        for (n, &size) in sizes.iter().enumerate() {
+            let ns = NettingSetId::new(format!("NS{}", n));
+            builder = builder.add_netting_set(NettingSet::new(ns.clone(), cp.clone()));
+            for i in 0..size {
+                let params = InstrumentParams::new(100.0, 1.0, 1.0).unwrap();
+                let option =
+                    VanillaOption::new(params, PayoffType::Call, ExerciseStyle::European, 1e-6);
+                builder = builder.add_trade(Trade::new(
+                    TradeId::new(format!("T{}-{}", n, i)),
+                    Instrument::Vanilla(option),
+                    Currency::USD,
+                    cp.clone(),
+                    ns.clone(),
+                    if i % 2 == 0 { 1.0 } else { -2.0 },
+                ));
+            }
+        }
+        builder.build().unwrap()
+    }
+
+    #[test]
+    fn test_parse_cpulist() {
+        assert_eq!(
+            parse_cpulist("0-2,8,10-11\n"),
+            Some(vec![0, 1, 2, 8, 10, 11])
+        );
+        assert_eq!(parse_cpulist("a-b"), None);
+    }
+
+    #[test]
+    fn test_pinning_policies() {
+        let topology = CpuTopology::from_nodes(vec![vec![0, 1, 2], vec![4, 5, 6], vec![]]);
+        assert_eq!(topology.node_count(), 2);
+        let cores = |policy| {
+            (0..7)
+                .map(|i| topology.core_for_worker(i, policy).unwrap())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(cores(PinningPolicy::Compact), vec![0, 1, 2, 4, 5, 6, 0]);
+        assert_eq!(cores(PinningPolicy::Scatter), vec![0, 4, 1, 5, 2, 6, 0]);
+        assert_eq!(topology.core_for_worker(0, PinningPolicy::None), None);
+    }
+
+    #[test]
+    fn test_price_nets_each_netting_set() {
+        let portfolio = portfolio(&[5, 600, 1]);
+        let service = PricingService::new(
+            PricingServiceConfig::new()
+                .with_threads(3)
+                .with_chunk_size(64)
+                .with_pinning(PinningPolicy::Compact),
+        )
+        .unwrap();
+
+        let valuation = service.price(&portfolio, |trade| trade.notional());
+        assert_eq!(valuation.trade_values.len(), 606);
+        assert_eq!(valuation.stats.tasks, 1 + 10 + 1);
+
+        let largest = &valuation.netting_sets[0];
+        assert_eq!(largest.netting_set_id, NettingSetId::new("NS1"));
+        assert_eq!(largest.value, 300.0 - 600.0);
+        assert_eq!(largest.exposure, 0.0);
+        assert_eq!(valuation.netting_sets[2].exposure, 1.0);
+        assert_eq!(valuation.total_value(), -300.0 - 1.0 + 1.0);
+    }
+}
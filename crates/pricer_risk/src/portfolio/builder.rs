@@ -126,6 +126,7 @@ impl PortfolioBuilder {
     /// - All trades reference valid counterparties
     /// - All trades reference valid netting sets
     /// - All netting sets reference valid counterparties
+    /// - All parent counterparties exist and the hierarchy has no cycles
     ///
     /// # Errors
     ///
@@ -185,6 +186,40 @@ impl PortfolioBuilder {
             }
         }
 
+        // Validate counterparty → parent references
+        for cp in &self.counterparties {
+            if let Some(parent) = cp.parent_id() {
+                if !cp_ids.contains(parent) {
+                    return Err(PortfolioError::UnknownParentCounterparty(
+                        cp.id().to_string(),
+                        parent.to_string(),
+                    ));
+                }
+            }
+        }
+
+        // Validate the hierarchy is acyclic: every parent chain must end
+        // within as many steps as there are counterparties
+        let parents: HashMap<&CounterpartyId, &CounterpartyId> = self
+            .counterparties
+            .iter()
+            .filter_map(|cp| cp.parent_id().map(|parent| (cp.id(), parent)))
+            .collect();
+        for cp in &self.counterparties {
+            let mut current = cp.id();
+            for _ in 0..=parents.len() {
+                match parents.get(current) {
+                    Some(&parent) => current = parent,
+                    None => break,
+                }
+            }
+            if parents.contains_key(current) {
+                return Err(PortfolioError::CounterpartyHierarchyCycle(
+                    cp.id().to_string(),
+                ));
+            }
+        }
+
         // Build HashMaps
         let trades: HashMap<TradeId, Trade> = self
             .trades
@@ -433,4 +468,41 @@ mod tests {
         assert_eq!(builder.counterparty_count(), 1);
         assert_eq!(builder.netting_set_count(), 1);
     }
+
+    #[test]
+    fn test_builder_unknown_parent_counterparty() {
+        let child = create_test_counterparty("CP002").with_parent(CounterpartyId::new("CP999"));
+
+        let result = PortfolioBuilder::new()
+            .add_counterparty(create_test_counterparty("CP001"))
+            .add_counterparty(child)
+            .build();
+
+        assert!(matches!(
+            result,
+            Err(PortfolioError::UnknownParentCounterparty(_, _))
+        ));
+    }
+
+    #[test]
+    fn test_builder_counterparty_hierarchy_cycle() {
+        let a = create_test_counterparty("A").with_parent(CounterpartyId::new("B"));
+        let b = create_test_counterparty("B").with_parent(CounterpartyId::new("C"));
+        let c = create_test_counterparty("C").with_parent(CounterpartyId::new("A"));
+
+        let result = PortfolioBuilder::new()
+            .add_counterparties(vec![a, b, c])
+            .build();
+        assert!(matches!(
+            result,
+            Err(PortfolioError::CounterpartyHierarchyCycle(_))
+        ));
+
+        let own_parent = create_test_counterparty("A").with_parent(CounterpartyId::new("A"));
+        let result = PortfolioBuilder::new().add_counterparty(own_parent).build();
+        assert!(matches!(
+            result,
+            Err(PortfolioError::CounterpartyHierarchyCycle(_))
+        ));
+    }
 }
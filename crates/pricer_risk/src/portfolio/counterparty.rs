@@ -35,6 +35,32 @@ pub enum CreditRating {
 }
 
 impl CreditRating {
+    /// All ratings from best to worst, ending with default.
+    pub const ALL: [CreditRating; 10] = [
+        CreditRating::AAA,
+        CreditRating::AA,
+        CreditRating::A,
+        CreditRating::BBB,
+        CreditRating::BB,
+        CreditRating::B,
+        CreditRating::CCC,
+        CreditRating::CC,
+        CreditRating::C,
+        CreditRating::D,
+    ];
+
+    /// Returns the position of this rating in [`CreditRating::ALL`].
+    #[inline]
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Returns whether this is the default state.
+    #[inline]
+    pub fn is_default(&self) -> bool {
+        matches!(self, CreditRating::D)
+    }
+
     /// Returns whether this rating is investment grade (BBB or better).
     #[inline]
     pub fn is_investment_grade(&self) -> bool {
@@ -223,6 +249,11 @@ impl CreditParams {
 
 /// Counterparty entity with credit parameters.
 ///
+/// A counterparty may belong to a corporate group through a parent
+/// counterparty and may record the jurisdiction of its legal entity; both
+/// feed netting-enforceability decisions (see
+/// [`Portfolio::netting_treatment`](super::Portfolio::netting_treatment)).
+///
 /// # Examples
 ///
 /// ```
@@ -236,6 +267,16 @@ impl CreditParams {
 ///
 /// assert_eq!(cp.id().as_str(), "CP001");
 /// assert_eq!(cp.name(), Some("Acme Corp"));
+///
+/// let subsidiary = Counterparty::new(
+///     CounterpartyId::new("CP001-UK"),
+///     CreditParams::new(0.02, 0.4).unwrap(),
+/// )
+/// .with_parent(CounterpartyId::new("CP001"))
+/// .with_jurisdiction("GB");
+///
+/// assert_eq!(subsidiary.parent_id().map(|p| p.as_str()), Some("CP001"));
+/// assert_eq!(subsidiary.jurisdiction(), Some("GB"));
 /// ```
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -243,6 +284,14 @@ pub struct Counterparty {
     id: CounterpartyId,
     name: Option<String>,
     credit_params: CreditParams,
+    #[cfg_attr(feature = "serde", serde(default))]
+    parent_id: Option<CounterpartyId>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    jurisdiction: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    sector: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    lei: Option<String>,
 }
 
 impl Counterparty {
@@ -253,6 +302,10 @@ impl Counterparty {
             id,
             name: None,
             credit_params,
+            parent_id: None,
+            jurisdiction: None,
+            sector: None,
+            lei: None,
         }
     }
 
@@ -262,6 +315,31 @@ impl Counterparty {
         self
     }
 
+    /// Sets the parent counterparty in the corporate hierarchy.
+    pub fn with_parent(mut self, parent_id: CounterpartyId) -> Self {
+        self.parent_id = Some(parent_id);
+        self
+    }
+
+    /// Sets the jurisdiction of the legal entity (e.g. an ISO country code).
+    pub fn with_jurisdiction(mut self, jurisdiction: impl Into<String>) -> Self {
+        self.jurisdiction = Some(jurisdiction.into());
+        self
+    }
+
+    /// Sets the industry sector (e.g. `"Financials"`), used to proxy
+    /// missing credit curves.
+    pub fn with_sector(mut self, sector: impl Into<String>) -> Self {
+        self.sector = Some(sector.into());
+        self
+    }
+
+    /// Sets the Legal Entity Identifier (ISO 17442) of the counterparty.
+    pub fn with_lei(mut self, lei: impl Into<String>) -> Self {
+        self.lei = Some(lei.into());
+        self
+    }
+
     /// Returns the counterparty ID.
     #[inline]
     pub fn id(&self) -> &CounterpartyId {
@@ -274,6 +352,34 @@ impl Counterparty {
         self.name.as_deref()
     }
 
+    /// Returns the parent counterparty ID if set.
+    #[inline]
+    pub fn parent_id(&self) -> Option<&CounterpartyId> {
+        self.parent_id.as_ref()
+    }
+
+    /// Returns the legal-entity jurisdiction if set.
+    ///
+    /// This is the counterparty's own jurisdiction; see
+    /// [`Portfolio::jurisdiction`](super::Portfolio::jurisdiction) for the
+    /// value inherited through the hierarchy.
+    #[inline]
+    pub fn jurisdiction(&self) -> Option<&str> {
+        self.jurisdiction.as_deref()
+    }
+
+    /// Returns the Legal Entity Identifier if set.
+    #[inline]
+    pub fn lei(&self) -> Option<&str> {
+        self.lei.as_deref()
+    }
+
+    /// Returns the industry sector if set.
+    #[inline]
+    pub fn sector(&self) -> Option<&str> {
+        self.sector.as_deref()
+    }
+
     /// Returns the credit parameters.
     #[inline]
     pub fn credit_params(&self) -> &CreditParams {
@@ -321,6 +427,15 @@ mod tests {
         assert!(!CreditRating::D.is_investment_grade());
     }
 
+    #[test]
+    fn test_credit_rating_index() {
+        for (i, rating) in CreditRating::ALL.iter().enumerate() {
+            assert_eq!(rating.index(), i);
+        }
+        assert!(CreditRating::D.is_default());
+        assert!(!CreditRating::C.is_default());
+    }
+
     #[test]
     fn test_credit_rating_ordering() {
         assert!(CreditRating::AAA < CreditRating::AA);
@@ -459,4 +574,18 @@ mod tests {
 
         assert_eq!(cp1.id(), cp2.id());
     }
+
+    #[test]
+    fn test_counterparty_hierarchy_fields() {
+        let credit = CreditParams::new(0.02, 0.4).unwrap();
+        let cp = Counterparty::new(CounterpartyId::new("SUB"), credit);
+        assert!(cp.parent_id().is_none());
+        assert!(cp.jurisdiction().is_none());
+
+        let cp = cp
+            .with_parent(CounterpartyId::new("PARENT"))
+            .with_jurisdiction("DE");
+        assert_eq!(cp.parent_id(), Some(&CounterpartyId::new("PARENT")));
+        assert_eq!(cp.jurisdiction(), Some("DE"));
+    }
 }
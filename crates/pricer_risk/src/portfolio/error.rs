@@ -52,10 +52,22 @@ pub enum PortfolioError {
     #[error("Netting set references unknown counterparty: netting_set={0}, counterparty={1}")]
     NettingSetUnknownCounterparty(String, String),
 
+    /// Counterparty references an unknown parent counterparty.
+    #[error("Counterparty references unknown parent: counterparty={0}, parent={1}")]
+    UnknownParentCounterparty(String, String),
+
+    /// Counterparty hierarchy contains a cycle.
+    #[error("Counterparty hierarchy cycle at: {0}")]
+    CounterpartyHierarchyCycle(String),
+
     /// Builder error during portfolio construction.
     #[error("Builder error: {0}")]
     BuilderError(String),
 
+    /// Market data required by the portfolio is unavailable.
+    #[error("Missing market data: {0}")]
+    MissingMarketData(String),
+
     /// Empty portfolio (no trades).
     #[error("Portfolio is empty")]
     EmptyPortfolio,
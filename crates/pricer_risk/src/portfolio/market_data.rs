@@ -0,0 +1,602 @@
+//! Market data requirements of a portfolio.
+//!
+//! [`Portfolio::market_data_requirements`] scans every trade and
+//! counterparty and rolls the result up into one [`MarketDataRequirements`]
+//! set: discount and projection curves with the longest tenor needed,
+//! volatility surfaces with their expiry and strike range, FX pairs into
+//! the reporting currency, past fixings of running swap periods and
+//! credit curves with standard CDS tenors. Loaders fetch exactly this set
+//! and call [`MarketDataRequirements::ensure`] to fail fast on gaps before
+//! a run starts.
+//!
+//! Every requirement has a canonical [`key`](MarketDataRequirement::key)
+//! (`curve/USD/discount`, `vol/USD`, `fx/EUR/USD`, `fixing/USD`,
+//! `credit/CP001`) and a [`horizon`](MarketDataRequirement::horizon) in
+//! years: how far forward a curve, surface or credit curve must reach, or
+//! how far back fixings must go. A [`MarketSnapshot`] reports the coverage
+//! it holds per key; gaps can be filled according to a
+//! [`MarketDataPolicy`](super::MarketDataPolicy).
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use pricer_core::types::Currency;
+use pricer_models::instruments::Instrument;
+
+use super::{Counterparty, CounterpartyId, CreditRating, Portfolio, PortfolioError, Trade};
+
+/// Standard CDS tenors in years used for counterparty credit curves.
+pub const STANDARD_CREDIT_TENORS: [f64; 11] =
+    [0.5, 1.0, 2.0, 3.0, 4.0, 5.0, 7.0, 10.0, 15.0, 20.0, 30.0];
+
+/// Role of an interest rate curve.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CurveRole {
+    /// Discounting curve of a currency.
+    Discount,
+    /// Forward projection curve of a currency's floating index.
+    Projection,
+}
+
+impl CurveRole {
+    /// Lower-case name used in requirement keys.
+    pub fn name(self) -> &'static str {
+        match self {
+            CurveRole::Discount => "discount",
+            CurveRole::Projection => "projection",
+        }
+    }
+}
+
+/// A single piece of market data a portfolio needs.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MarketDataRequirement {
+    /// Interest rate curve out to `max_tenor` years.
+    Curve {
+        /// Curve currency.
+        currency: Currency,
+        /// Discount or projection.
+        role: CurveRole,
+        /// Longest tenor needed in years.
+        max_tenor: f64,
+    },
+    /// Volatility surface covering expiries and strikes of the options.
+    VolSurface {
+        /// Currency the surface is keyed by.
+        currency: Currency,
+        /// Longest option expiry in years.
+        max_expiry: f64,
+        /// Lowest option strike.
+        min_strike: f64,
+        /// Highest option strike.
+        max_strike: f64,
+    },
+    /// FX rate converting `base` into `quote`.
+    FxPair {
+        /// Trade currency.
+        base: Currency,
+        /// Reporting currency.
+        quote: Currency,
+    },
+    /// Historical fixings of a currency's floating index.
+    Fixings {
+        /// Index currency.
+        currency: Currency,
+        /// Fixing times in years, non-positive and ascending.
+        times: Vec<f64>,
+    },
+    /// Counterparty credit curve at the given tenors.
+    CreditCurve {
+        /// Counterparty.
+        counterparty: CounterpartyId,
+        /// Tenors in years, ascending.
+        tenors: Vec<f64>,
+        /// Counterparty rating, for proxying a missing curve.
+        rating: Option<CreditRating>,
+        /// Counterparty industry sector, for proxying a missing curve.
+        sector: Option<String>,
+    },
+}
+
+/// Kind of a [`MarketDataRequirement`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RequirementKind {
+    /// Interest rate curve.
+    Curve,
+    /// Volatility surface.
+    VolSurface,
+    /// FX rate.
+    FxPair,
+    /// Index fixings.
+    Fixings,
+    /// Counterparty credit curve.
+    CreditCurve,
+}
+
+/// Market data held by a snapshot, queried by requirement key.
+pub trait MarketSnapshot {
+    /// Years of coverage held for `key`, `f64::INFINITY` if unbounded, or
+    /// `None` if the snapshot has no data for it.
+    fn coverage(&self, key: &str) -> Option<f64>;
+
+    /// Whether the snapshot fully covers `requirement`.
+    fn covers(&self, requirement: &MarketDataRequirement) -> bool {
+        self.coverage(&requirement.key())
+            .is_some_and(|available| requirement.horizon().is_none_or(|h| available >= h))
+    }
+}
+
+impl MarketSnapshot for BTreeMap<String, f64> {
+    fn coverage(&self, key: &str) -> Option<f64> {
+        self.get(key).copied()
+    }
+}
+
+impl MarketDataRequirement {
+    /// Kind of market data required.
+    pub fn kind(&self) -> RequirementKind {
+        match self {
+            MarketDataRequirement::Curve { .. } => RequirementKind::Curve,
+            MarketDataRequirement::VolSurface { .. } => RequirementKind::VolSurface,
+            MarketDataRequirement::FxPair { .. } => RequirementKind::FxPair,
+            MarketDataRequirement::Fixings { .. } => RequirementKind::Fixings,
+            MarketDataRequirement::CreditCurve { .. } => RequirementKind::CreditCurve,
+        }
+    }
+
+    /// Canonical identifier, unique within a [`MarketDataRequirements`] set.
+    pub fn key(&self) -> String {
+        match self {
+            MarketDataRequirement::Curve { currency, role, .. } => {
+                format!("curve/{}/{}", currency.code(), role.name())
+            }
+            MarketDataRequirement::VolSurface { currency, .. } => {
+                format!("vol/{}", currency.code())
+            }
+            MarketDataRequirement::FxPair { base, quote } => {
+                format!("fx/{}/{}", base.code(), quote.code())
+            }
+            MarketDataRequirement::Fixings { currency, .. } => {
+                format!("fixing/{}", currency.code())
+            }
+            MarketDataRequirement::CreditCurve { counterparty, .. } => {
+                format!("credit/{}", counterparty)
+            }
+        }
+    }
+
+    /// Years of coverage needed: forward for curves, surfaces and credit
+    /// curves, backward for fixings. `None` for FX spot rates.
+    pub fn horizon(&self) -> Option<f64> {
+        match self {
+            MarketDataRequirement::Curve { max_tenor, .. } => Some(*max_tenor),
+            MarketDataRequirement::VolSurface { max_expiry, .. } => Some(*max_expiry),
+            MarketDataRequirement::FxPair { .. } => None,
+            MarketDataRequirement::Fixings { times, .. } => Some(times.first().map_or(0.0, |t| -t)),
+            MarketDataRequirement::CreditCurve { tenors, .. } => tenors.last().copied(),
+        }
+    }
+
+    /// Widen `self` to also cover `other`, which must have the same key.
+    fn absorb(&mut self, other: MarketDataRequirement) {
+        match (self, other) {
+            (
+                MarketDataRequirement::Curve { max_tenor, .. },
+                MarketDataRequirement::Curve { max_tenor: t, .. },
+            ) => *max_tenor = max_tenor.max(t),
+            (
+                MarketDataRequirement::VolSurface {
+                    max_expiry,
+                    min_strike,
+                    max_strike,
+                    ..
+                },
+                MarketDataRequirement::VolSurface {
+                    max_expiry: e,
+                    min_strike: lo,
+                    max_strike: hi,
+                    ..
+                },
+            ) => {
+                *max_expiry = max_expiry.max(e);
+                *min_strike = min_strike.min(lo);
+                *max_strike = max_strike.max(hi);
+            }
+            (
+                MarketDataRequirement::Fixings { times, .. },
+                MarketDataRequirement::Fixings { times: more, .. },
+            ) => merge_sorted(times, more),
+            (
+                MarketDataRequirement::CreditCurve { tenors, .. },
+                MarketDataRequirement::CreditCurve { tenors: more, .. },
+            ) => merge_sorted(tenors, more),
+            _ => {}
+        }
+    }
+}
+
+impl fmt::Display for MarketDataRequirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.horizon() {
+            Some(horizon) => write!(f, "{} ({}Y)", self.key(), horizon),
+            None => write!(f, "{}", self.key()),
+        }
+    }
+}
+
+/// Market data needed to value a portfolio, one entry per key.
+///
+/// # Examples
+///
+/// ```
+/// use pricer_risk::portfolio::{
+///     Counterparty, CounterpartyId, CreditParams, NettingSet, NettingSetId,
+///     PortfolioBuilder, Trade, TradeId,
+/// };
+/// use pricer_core::types::Currency;
+/// use pricer_models::instruments::{
+///     ExerciseStyle, Instrument, InstrumentParams, PayoffType, VanillaOption,
+/// };
+///
+/// let params = InstrumentParams::new(100.0, 2.0, 1.0).unwrap();
+/// let call = VanillaOption::new(params, PayoffType::Call, ExerciseStyle::European, 1e-6);
+/// let portfolio = PortfolioBuilder::new()
+///     .add_counterparty(Counterparty::new(
+///         CounterpartyId::new("CP001"),
+///         CreditParams::new(0.02, 0.4).unwrap(),
+///     ))
+///     .add_netting_set(NettingSet::new(NettingSetId::new("NS001"), CounterpartyId::new("CP001")))
+///     .add_trade(Trade::new(
+///         TradeId::new("T001"),
+///         Instrument::Vanilla(call),
+///         Currency::EUR,
+///         CounterpartyId::new("CP001"),
+///         NettingSetId::new("NS001"),
+///         1.0,
+///     ))
+///     .build()
+///     .unwrap();
+///
+/// let requirements = portfolio.market_data_requirements(Currency::USD);
+/// assert!(requirements.get("curve/EUR/discount").is_some());
+/// assert!(requirements.get("vol/EUR").is_some());
+/// assert!(requirements.get("fx/EUR/USD").is_some());
+/// assert_eq!(requirements.get("credit/CP001").unwrap().horizon(), Some(2.0));
+///
+/// // Nothing is available yet: every requirement is a gap
+/// assert!(requirements.ensure(|_| false).is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarketDataRequirements {
+    reporting_currency: Currency,
+    requirements: BTreeMap<String, MarketDataRequirement>,
+}
+
+impl MarketDataRequirements {
+    /// Creates an empty set for results reported in `reporting_currency`.
+    pub fn new(reporting_currency: Currency) -> Self {
+        Self {
+            reporting_currency,
+            requirements: BTreeMap::new(),
+        }
+    }
+
+    /// Adds a requirement, widening any existing entry with the same key.
+    pub fn add(&mut self, requirement: MarketDataRequirement) {
+        let key = requirement.key();
+        match self.requirements.get_mut(&key) {
+            Some(existing) => existing.absorb(requirement),
+            None => {
+                self.requirements.insert(key, requirement);
+            }
+        }
+    }
+
+    /// Adds the requirements of one trade.
+    ///
+    /// Every trade needs its currency's discount curve to maturity and, in
+    /// a foreign currency, the FX rate into the reporting currency. Options
+    /// add a volatility surface; swaps add the projection curve and the
+    /// fixing of any period that has started but not yet paid.
+    pub fn add_trade(&mut self, trade: &Trade) {
+        let currency = trade.currency();
+        let maturity = trade.expiry();
+
+        self.add(MarketDataRequirement::Curve {
+            currency,
+            role: CurveRole::Discount,
+            max_tenor: maturity,
+        });
+        if currency != self.reporting_currency {
+            self.add(MarketDataRequirement::FxPair {
+                base: currency,
+                quote: self.reporting_currency,
+            });
+        }
+
+        match trade.instrument() {
+            Instrument::Vanilla(option) => self.add(MarketDataRequirement::VolSurface {
+                currency,
+                max_expiry: option.expiry(),
+                min_strike: option.strike(),
+                max_strike: option.strike(),
+            }),
+            Instrument::Forward(_) => {}
+            Instrument::Swap(swap) => {
+                self.add(MarketDataRequirement::Curve {
+                    currency,
+                    role: CurveRole::Projection,
+                    max_tenor: maturity,
+                });
+                let period: f64 = swap.frequency().period_fraction();
+                let times: Vec<f64> = swap
+                    .payment_dates()
+                    .iter()
+                    .filter(|&&pay| pay > 0.0 && pay - period <= 0.0)
+                    .map(|&pay| pay - period)
+                    .collect();
+                if !times.is_empty() {
+                    self.add(MarketDataRequirement::Fixings { currency, times });
+                }
+            }
+        }
+    }
+
+    /// Adds a credit curve for `counterparty` reaching `maturity` years.
+    ///
+    /// Uses the [`STANDARD_CREDIT_TENORS`] up to and including the first
+    /// tenor at or beyond `maturity`. The counterparty's rating and sector
+    /// are carried along for proxying.
+    pub fn add_credit_curve(&mut self, counterparty: &Counterparty, maturity: f64) {
+        let cutoff = STANDARD_CREDIT_TENORS
+            .iter()
+            .position(|&t| t >= maturity)
+            .unwrap_or(STANDARD_CREDIT_TENORS.len() - 1);
+        self.add(MarketDataRequirement::CreditCurve {
+            counterparty: counterparty.id().clone(),
+            tenors: STANDARD_CREDIT_TENORS[..=cutoff].to_vec(),
+            rating: counterparty.credit_params().rating(),
+            sector: counterparty.sector().map(str::to_string),
+        });
+    }
+
+    /// Currency results are reported in.
+    #[inline]
+    pub fn reporting_currency(&self) -> Currency {
+        self.reporting_currency
+    }
+
+    /// Requirement with the given key.
+    pub fn get(&self, key: &str) -> Option<&MarketDataRequirement> {
+        self.requirements.get(key)
+    }
+
+    /// All requirements, ordered by key.
+    pub fn iter(&self) -> impl Iterator<Item = &MarketDataRequirement> {
+        self.requirements.values()
+    }
+
+    /// Number of requirements.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.requirements.len()
+    }
+
+    /// Returns true if nothing is required.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.requirements.is_empty()
+    }
+
+    /// Requirements for which `is_available` returns false, ordered by key.
+    pub fn gaps<F>(&self, is_available: F) -> Vec<&MarketDataRequirement>
+    where
+        F: Fn(&MarketDataRequirement) -> bool,
+    {
+        self.iter().filter(|r| !is_available(r)).collect()
+    }
+
+    /// Fails with every gap listed if any requirement is unavailable.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PortfolioError::MissingMarketData`] naming each gap.
+    pub fn ensure<F>(&self, is_available: F) -> Result<(), PortfolioError>
+    where
+        F: Fn(&MarketDataRequirement) -> bool,
+    {
+        let gaps = self.gaps(is_available);
+        if gaps.is_empty() {
+            return Ok(());
+        }
+        Err(PortfolioError::MissingMarketData(
+            gaps.iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", "),
+        ))
+    }
+}
+
+impl Portfolio {
+    /// Market data needed to value every trade and price counterparty credit.
+    ///
+    /// FX pairs convert each trade currency into `reporting_currency`; credit
+    /// curves reach the longest maturity traded with each counterparty.
+    pub fn market_data_requirements(&self, reporting_currency: Currency) -> MarketDataRequirements {
+        let mut requirements = MarketDataRequirements::new(reporting_currency);
+        let mut credit_horizon: BTreeMap<String, (&CounterpartyId, f64)> = BTreeMap::new();
+
+        for trade in self.trades() {
+            requirements.add_trade(trade);
+            let entry = credit_horizon
+                .entry(trade.counterparty_id().to_string())
+                .or_insert((trade.counterparty_id(), 0.0));
+            entry.1 = entry.1.max(trade.expiry());
+        }
+        for (id, maturity) in credit_horizon.into_values() {
+            if let Some(counterparty) = self.counterparty(id) {
+                requirements.add_credit_curve(counterparty, maturity);
+            }
+        }
+
+        requirements
+    }
+}
+
+/// Merge `more` into the ascending `values`, dropping duplicates.
+fn merge_sorted(values: &mut Vec<f64>, more: Vec<f64>) {
+    values.extend(more);
+    values.sort_by(|a, b| a.total_cmp(b));
+    values.dedup();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::portfolio::{
+        Counterparty, CreditParams, NettingSet, NettingSetId, PortfolioBuilder, TradeId,
+    };
+    use pricer_models::instruments::{
+        Direction, ExerciseStyle, Forward, InstrumentParams, PaymentFrequency, PayoffType, Swap,
+        VanillaOption,
+    };
+
+    fn option(strike: f64, expiry: f64) -> Instrument<f64> {
+        let params = InstrumentParams::new(strike, expiry, 1.0).unwrap();
+        Instrument::Vanilla(VanillaOption::new(
+            params,
+            PayoffType::Call,
+            ExerciseStyle::European,
+            1e-6,
+        ))
+    }
+
+    fn swap(currency: Currency, payment_dates: Vec<f64>) -> Instrument<f64> {
+        Instrument::Swap(
+            Swap::new(
+                1_000_000.0,
+                0.03,
+                payment_dates,
+                PaymentFrequency::SemiAnnual,
+                currency,
+            )
+            .unwrap(),
+        )
+    }
+
+    fn portfolio(trades: Vec<(&str, &str, Instrument<f64>, Currency)>) -> Portfolio {
+        let mut builder = PortfolioBuilder::new();
+        for cp in ["CP001", "CP002"] {
+            builder = builder
+                .add_counterparty(Counterparty::new(
+                    CounterpartyId::new(cp),
+                    CreditParams::new(0.02, 0.4).unwrap(),
+                ))
+                .add_netting_set(NettingSet::new(
+                    NettingSetId::new(format!("NS-{}", cp)),
+                    CounterpartyId::new(cp),
+                ));
+        }
+        for (id, cp, instrument, currency) in trades {
+            builder = builder.add_trade(Trade::new(
+                TradeId::new(id),
+                instrument,
+                currency,
+                CounterpartyId::new(cp),
+                NettingSetId::new(format!("NS-{}", cp)),
+                1.0,
+            ));
+        }
+        builder.build().unwrap()
+    }
+
+    #[test]
+    fn test_options_roll_up_into_one_surface() {
+        let portfolio = portfolio(vec![
+            ("T1", "CP001", option(90.0, 1.0), Currency::USD),
+            ("T2", "CP001", option(120.0, 3.0), Currency::USD),
+        ]);
+        let requirements = portfolio.market_data_requirements(Currency::USD);
+
+        assert_eq!(
+            requirements.get("vol/USD"),
+            Some(&MarketDataRequirement::VolSurface {
+                currency: Currency::USD,
+                max_expiry: 3.0,
+                min_strike: 90.0,
+                max_strike: 120.0,
+            })
+        );
+        assert_eq!(
+            requirements.get("curve/USD/discount").unwrap().horizon(),
+            Some(3.0)
+        );
+        // Reported in the trade currency: no FX needed
+        assert!(requirements.iter().all(|r| !r.key().starts_with("fx/")));
+    }
+
+    #[test]
+    fn test_swap_needs_projection_curve_and_running_fixing() {
+        // Valued 0.2Y into a semi-annual period that pays at 0.3Y
+        let portfolio = portfolio(vec![(
+            "S1",
+            "CP001",
+            swap(Currency::EUR, vec![0.3, 0.8, 1.3, 1.8]),
+            Currency::EUR,
+        )]);
+        let requirements = portfolio.market_data_requirements(Currency::USD);
+
+        assert_eq!(
+            requirements.get("curve/EUR/projection").unwrap().horizon(),
+            Some(1.8)
+        );
+        match requirements.get("fixing/EUR").unwrap() {
+            MarketDataRequirement::Fixings { times, .. } => {
+                assert_eq!(times.len(), 1);
+                assert!((times[0] + 0.2).abs() < 1e-12);
+            }
+            other => panic!("unexpected requirement {:?}", other),
+        }
+        assert!(requirements.get("fx/EUR/USD").is_some());
+    }
+
+    #[test]
+    fn test_credit_curve_tenors_per_counterparty() {
+        let forward = Instrument::Forward(Forward::new(100.0, 4.5, 1.0, Direction::Long).unwrap());
+        let portfolio = portfolio(vec![
+            ("T1", "CP001", option(100.0, 0.75), Currency::USD),
+            ("T2", "CP002", forward, Currency::USD),
+        ]);
+        let requirements = portfolio.market_data_requirements(Currency::USD);
+
+        let tenors = |key: &str| match requirements.get(key).unwrap() {
+            MarketDataRequirement::CreditCurve { tenors, .. } => tenors.clone(),
+            other => panic!("unexpected requirement {:?}", other),
+        };
+        assert_eq!(tenors("credit/CP001"), vec![0.5, 1.0]);
+        assert_eq!(tenors("credit/CP002"), vec![0.5, 1.0, 2.0, 3.0, 4.0, 5.0]);
+    }
+
+    #[test]
+    fn test_ensure_lists_gaps() {
+        let portfolio = portfolio(vec![("T1", "CP001", option(100.0, 1.0), Currency::GBP)]);
+        let requirements = portfolio.market_data_requirements(Currency::USD);
+
+        let available = |r: &MarketDataRequirement| r.key().starts_with("curve/");
+        let gaps: Vec<String> = requirements
+            .gaps(available)
+            .iter()
+            .map(|r| r.key())
+            .collect();
+        assert_eq!(gaps, vec!["credit/CP001", "fx/GBP/USD", "vol/GBP"]);
+
+        let err = requirements.ensure(available).unwrap_err();
+        assert!(err.to_string().contains("vol/GBP (1Y)"));
+        assert!(requirements.ensure(|_| true).is_ok());
+    }
+}
@@ -0,0 +1,393 @@
+//! Policies for market data missing from a snapshot.
+//!
+//! A [`MarketDataPolicy`] decides, per [`RequirementKind`], what happens
+//! when a [`MarketSnapshot`] does not cover a requirement rolled up by
+//! [`Portfolio::market_data_requirements`](super::Portfolio::market_data_requirements):
+//!
+//! - [`MissingDataPolicy::Fail`]: the run stops, naming the gap
+//! - [`MissingDataPolicy::Proxy`]: an explicitly mapped proxy key, or for
+//!   credit curves a sector/rating proxy curve, falling back to the
+//!   rating-implied hazard rate
+//! - [`MissingDataPolicy::FlatExtrapolate`]: data that stops short of the
+//!   horizon is held flat beyond its last point
+//! - [`MissingDataPolicy::Zero`]: the quantity is taken as zero
+//!
+//! Every substitution is returned as a [`MarketDataDecision`] in a
+//! [`MarketDataResolution`], which records them in the run's
+//! [`RunMetadata`] for audit.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use pricer_core::types::RunMetadata;
+
+use super::{
+    CreditRating, MarketDataRequirement, MarketDataRequirements, MarketSnapshot, PortfolioError,
+    RequirementKind,
+};
+
+/// What to do when a requirement is not covered by the snapshot.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MissingDataPolicy {
+    /// Stop the run.
+    #[default]
+    Fail,
+    /// Substitute proxy data.
+    Proxy,
+    /// Hold partial data flat beyond its last point.
+    FlatExtrapolate,
+    /// Take the missing quantity as zero.
+    Zero,
+}
+
+/// Substitution applied to one uncovered requirement.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MarketDataDecision {
+    /// Data taken from another snapshot key.
+    Proxy {
+        /// Key the data was taken from.
+        source: String,
+    },
+    /// Credit curve implied by the counterparty's rating.
+    RatingImplied {
+        /// Rating whose indicative hazard rate is used.
+        rating: CreditRating,
+    },
+    /// Data held flat beyond the available horizon.
+    FlatExtrapolate {
+        /// Years of coverage actually available.
+        from: f64,
+    },
+    /// Quantity taken as zero.
+    Zero,
+}
+
+impl fmt::Display for MarketDataDecision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MarketDataDecision::Proxy { source } => write!(f, "proxy {}", source),
+            MarketDataDecision::RatingImplied { rating } => {
+                write!(f, "rating-implied {:?}", rating)
+            }
+            MarketDataDecision::FlatExtrapolate { from } => write!(f, "flat beyond {}Y", from),
+            MarketDataDecision::Zero => write!(f, "zero"),
+        }
+    }
+}
+
+/// Configurable handling of missing market data.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::BTreeMap;
+///
+/// use pricer_core::types::{Currency, RunMetadata};
+/// use pricer_risk::portfolio::{
+///     CurveRole, MarketDataPolicy, MarketDataRequirement, MarketDataRequirements,
+///     MissingDataPolicy, RequirementKind,
+/// };
+///
+/// let mut requirements = MarketDataRequirements::new(Currency::USD);
+/// requirements.add(MarketDataRequirement::Curve {
+///     currency: Currency::USD,
+///     role: CurveRole::Discount,
+///     max_tenor: 30.0,
+/// });
+///
+/// // The snapshot's curve stops at 20Y
+/// let snapshot = BTreeMap::from([("curve/USD/discount".to_string(), 20.0)]);
+///
+/// assert!(MarketDataPolicy::default().resolve(&requirements, &snapshot).is_err());
+///
+/// let policy = MarketDataPolicy::default()
+///     .with_policy(RequirementKind::Curve, MissingDataPolicy::FlatExtrapolate);
+/// let resolution = policy.resolve(&requirements, &snapshot).unwrap();
+/// let run = resolution.record(RunMetadata::default());
+/// assert_eq!(
+///     run.market_data_decisions.get("curve/USD/discount").map(String::as_str),
+///     Some("flat beyond 20Y")
+/// );
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MarketDataPolicy {
+    default: MissingDataPolicy,
+    by_kind: HashMap<RequirementKind, MissingDataPolicy>,
+    proxies: BTreeMap<String, String>,
+}
+
+impl MarketDataPolicy {
+    /// Creates a policy applying `default` to every kind of requirement.
+    pub fn new(default: MissingDataPolicy) -> Self {
+        Self {
+            default,
+            ..Self::default()
+        }
+    }
+
+    /// Overrides the policy for one kind of requirement.
+    pub fn with_policy(mut self, kind: RequirementKind, policy: MissingDataPolicy) -> Self {
+        self.by_kind.insert(kind, policy);
+        self
+    }
+
+    /// Maps a requirement key to the snapshot key used as its proxy.
+    pub fn with_proxy(mut self, key: impl Into<String>, proxy: impl Into<String>) -> Self {
+        self.proxies.insert(key.into(), proxy.into());
+        self
+    }
+
+    /// Policy applied to `kind`.
+    pub fn policy_for(&self, kind: RequirementKind) -> MissingDataPolicy {
+        self.by_kind.get(&kind).copied().unwrap_or(self.default)
+    }
+
+    /// Decides how each requirement not covered by `snapshot` is filled.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PortfolioError::MissingMarketData`] naming every
+    /// requirement whose policy is `Fail` or whose policy cannot be applied:
+    /// no proxy is available, or there is no data to extrapolate.
+    pub fn resolve(
+        &self,
+        requirements: &MarketDataRequirements,
+        snapshot: &impl MarketSnapshot,
+    ) -> Result<MarketDataResolution, PortfolioError> {
+        let mut decisions = BTreeMap::new();
+        let mut gaps = Vec::new();
+
+        for requirement in requirements.iter() {
+            if snapshot.covers(requirement) {
+                continue;
+            }
+            let decision = match self.policy_for(requirement.kind()) {
+                MissingDataPolicy::Fail => None,
+                MissingDataPolicy::Proxy => self.proxy(requirement, snapshot),
+                MissingDataPolicy::FlatExtrapolate => snapshot
+                    .coverage(&requirement.key())
+                    .map(|from| MarketDataDecision::FlatExtrapolate { from }),
+                MissingDataPolicy::Zero => Some(MarketDataDecision::Zero),
+            };
+            match decision {
+                Some(decision) => {
+                    decisions.insert(requirement.key(), decision);
+                }
+                None => gaps.push(requirement.to_string()),
+            }
+        }
+
+        if !gaps.is_empty() {
+            return Err(PortfolioError::MissingMarketData(gaps.join(", ")));
+        }
+        Ok(MarketDataResolution { decisions })
+    }
+
+    /// First proxy covering the requirement's horizon.
+    ///
+    /// Explicit proxies come first; credit curves then try
+    /// `credit/proxy/{sector}/{rating}`, `credit/proxy/{sector}` and
+    /// `credit/proxy/{rating}` before the rating-implied hazard rate.
+    fn proxy(
+        &self,
+        requirement: &MarketDataRequirement,
+        snapshot: &impl MarketSnapshot,
+    ) -> Option<MarketDataDecision> {
+        let mut candidates: Vec<String> = self
+            .proxies
+            .get(&requirement.key())
+            .cloned()
+            .into_iter()
+            .collect();
+        let mut rating_implied = None;
+        if let MarketDataRequirement::CreditCurve { rating, sector, .. } = requirement {
+            match (sector, rating) {
+                (Some(sector), Some(rating)) => {
+                    candidates.push(format!("credit/proxy/{}/{:?}", sector, rating));
+                    candidates.push(format!("credit/proxy/{}", sector));
+                    candidates.push(format!("credit/proxy/{:?}", rating));
+                }
+                (Some(sector), None) => candidates.push(format!("credit/proxy/{}", sector)),
+                (None, Some(rating)) => candidates.push(format!("credit/proxy/{:?}", rating)),
+                (None, None) => {}
+            }
+            rating_implied = rating.map(|rating| MarketDataDecision::RatingImplied { rating });
+        }
+
+        candidates
+            .into_iter()
+            .find(|source| {
+                snapshot
+                    .coverage(source)
+                    .is_some_and(|available| requirement.horizon().is_none_or(|h| available >= h))
+            })
+            .map(|source| MarketDataDecision::Proxy { source })
+            .or(rating_implied)
+    }
+}
+
+/// Substitutions decided for a run, by requirement key.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MarketDataResolution {
+    decisions: BTreeMap<String, MarketDataDecision>,
+}
+
+impl MarketDataResolution {
+    /// Decision for a requirement key, if it was not covered.
+    pub fn decision(&self, key: &str) -> Option<&MarketDataDecision> {
+        self.decisions.get(key)
+    }
+
+    /// All decisions, ordered by requirement key.
+    pub fn decisions(&self) -> impl Iterator<Item = (&str, &MarketDataDecision)> {
+        self.decisions.iter().map(|(key, d)| (key.as_str(), d))
+    }
+
+    /// Returns true if the snapshot covered every requirement.
+    pub fn is_clean(&self) -> bool {
+        self.decisions.is_empty()
+    }
+
+    /// Records every decision in the run's metadata.
+    pub fn record(&self, run: RunMetadata) -> RunMetadata {
+        self.decisions().fold(run, |run, (key, decision)| {
+            run.with_market_data_decision(key, decision.to_string())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::portfolio::{CounterpartyId, CurveRole};
+    use pricer_core::types::Currency;
+
+    fn credit(rating: Option<CreditRating>, sector: Option<&str>) -> MarketDataRequirement {
+        MarketDataRequirement::CreditCurve {
+            counterparty: CounterpartyId::new("CP001"),
+            tenors: vec![1.0, 5.0],
+            rating,
+            sector: sector.map(str::to_string),
+        }
+    }
+
+    fn requirements(items: Vec<MarketDataRequirement>) -> MarketDataRequirements {
+        let mut requirements = MarketDataRequirements::new(Currency::USD);
+        for item in items {
+            requirements.add(item);
+        }
+        requirements
+    }
+
+    fn snapshot(entries: &[(&str, f64)]) -> BTreeMap<String, f64> {
+        entries.iter().map(|(k, v)| (k.to_string(), *v)).collect()
+    }
+
+    #[test]
+    fn test_covered_requirements_need_no_decision() {
+        let reqs = requirements(vec![credit(None, None)]);
+        let resolution = MarketDataPolicy::default()
+            .resolve(&reqs, &snapshot(&[("credit/CP001", 10.0)]))
+            .unwrap();
+        assert!(resolution.is_clean());
+    }
+
+    #[test]
+    fn test_credit_proxy_prefers_sector_and_rating() {
+        let reqs = requirements(vec![credit(Some(CreditRating::BBB), Some("Energy"))]);
+        let policy = MarketDataPolicy::new(MissingDataPolicy::Proxy);
+
+        let both = snapshot(&[
+            ("credit/proxy/Energy/BBB", 10.0),
+            ("credit/proxy/BBB", 10.0),
+        ]);
+        let resolution = policy.resolve(&reqs, &both).unwrap();
+        assert_eq!(
+            resolution.decision("credit/CP001"),
+            Some(&MarketDataDecision::Proxy {
+                source: "credit/proxy/Energy/BBB".to_string()
+            })
+        );
+
+        // A proxy curve too short for the horizon is skipped
+        let short = snapshot(&[("credit/proxy/Energy/BBB", 2.0), ("credit/proxy/BBB", 10.0)]);
+        let resolution = policy.resolve(&reqs, &short).unwrap();
+        assert_eq!(
+            resolution.decision("credit/CP001").unwrap().to_string(),
+            "proxy credit/proxy/BBB"
+        );
+
+        // No proxy curve at all: rating-implied hazard rate
+        let resolution = policy.resolve(&reqs, &snapshot(&[])).unwrap();
+        assert_eq!(
+            resolution.decision("credit/CP001"),
+            Some(&MarketDataDecision::RatingImplied {
+                rating: CreditRating::BBB
+            })
+        );
+    }
+
+    #[test]
+    fn test_unresolvable_items_fail() {
+        let reqs = requirements(vec![
+            credit(None, None),
+            MarketDataRequirement::VolSurface {
+                currency: Currency::GBP,
+                max_expiry: 2.0,
+                min_strike: 90.0,
+                max_strike: 110.0,
+            },
+        ]);
+        let policy = MarketDataPolicy::new(MissingDataPolicy::Proxy).with_policy(
+            RequirementKind::VolSurface,
+            MissingDataPolicy::FlatExtrapolate,
+        );
+
+        let err = policy.resolve(&reqs, &snapshot(&[])).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("credit/CP001"));
+        assert!(message.contains("vol/GBP"));
+    }
+
+    #[test]
+    fn test_decisions_recorded_in_run_metadata() {
+        let reqs = requirements(vec![
+            MarketDataRequirement::Curve {
+                currency: Currency::EUR,
+                role: CurveRole::Projection,
+                max_tenor: 10.0,
+            },
+            MarketDataRequirement::Fixings {
+                currency: Currency::EUR,
+                times: vec![-0.1],
+            },
+            MarketDataRequirement::VolSurface {
+                currency: Currency::GBP,
+                max_expiry: 2.0,
+                min_strike: 90.0,
+                max_strike: 110.0,
+            },
+        ]);
+        let policy = MarketDataPolicy::default()
+            .with_policy(RequirementKind::Fixings, MissingDataPolicy::Zero)
+            .with_policy(RequirementKind::VolSurface, MissingDataPolicy::Proxy)
+            .with_proxy("vol/GBP", "vol/EUR");
+        let resolution = policy
+            .resolve(
+                &reqs,
+                &snapshot(&[("curve/EUR/projection", 30.0), ("vol/EUR", 5.0)]),
+            )
+            .unwrap();
+
+        let run = resolution.record(RunMetadata::default());
+        assert_eq!(run.market_data_decisions.len(), 2);
+        assert_eq!(run.market_data_decisions["fixing/EUR"], "zero");
+        assert_eq!(run.market_data_decisions["vol/GBP"], "proxy vol/EUR");
+        assert!(run
+            .to_string()
+            .contains("market_data.vol/GBP=proxy vol/EUR"));
+    }
+}
@@ -4,7 +4,10 @@
 //! - Trade structures with instrument references
 //! - Counterparty definitions with credit parameters
 //! - Netting sets for exposure aggregation
+//! - Counterparty hierarchies and legal-entity netting rules
 //! - Portfolio container with parallel iteration support
+//! - Market data requirements rolled up across the portfolio, with
+//!   configurable policies for data missing from a snapshot
 //!
 //! # Architecture
 //!
@@ -14,6 +17,11 @@
 //! - **Counterparty**: Credit entity with default risk parameters
 //! - **NettingSet**: Group of trades for exposure netting
 //!
+//! Counterparties may point to a parent counterparty and carry a legal
+//! jurisdiction; together with each netting set's enforceability flag and
+//! a set of [`NettingRules`], [`Portfolio::netting_treatment`] decides
+//! whether a netting set's exposure is aggregated net or gross.
+//!
 //! # Examples
 //!
 //! ```
@@ -68,6 +76,8 @@ mod builder;
 mod counterparty;
 mod error;
 mod ids;
+mod market_data;
+mod market_data_policy;
 mod netting_set;
 mod trade;
 
@@ -76,13 +86,22 @@ pub use builder::PortfolioBuilder;
 pub use counterparty::{Counterparty, CreditParams, CreditRating};
 pub use error::PortfolioError;
 pub use ids::{CounterpartyId, NettingSetId, TradeId};
-pub use netting_set::{CollateralAgreement, NettingSet};
+pub use market_data::{
+    CurveRole, MarketDataRequirement, MarketDataRequirements, MarketSnapshot, RequirementKind,
+    STANDARD_CREDIT_TENORS,
+};
+pub use market_data_policy::{
+    MarketDataDecision, MarketDataPolicy, MarketDataResolution, MissingDataPolicy,
+};
+pub use netting_set::{CollateralAgreement, CollateralAsset, NettingRules, NettingSet};
 pub use trade::{Trade, TradeBuilder};
 
 use std::collections::HashMap;
 
 use rayon::prelude::*;
 
+use crate::exposure::NettingTreatment;
+
 /// Portfolio container for trades, counterparties, and netting sets.
 ///
 /// Provides O(1) lookup by ID and supports parallel iteration via Rayon.
@@ -131,12 +150,37 @@ use rayon::prelude::*;
 /// assert!(t.is_some());
 /// ```
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "PortfolioData"))]
 pub struct Portfolio {
     trades: HashMap<TradeId, Trade>,
     counterparties: HashMap<CounterpartyId, Counterparty>,
     netting_sets: HashMap<NettingSetId, NettingSet>,
 }
 
+/// Unvalidated portfolio contents; deserialised portfolios are rebuilt
+/// through [`PortfolioBuilder`] so that references are checked.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct PortfolioData {
+    trades: HashMap<TradeId, Trade>,
+    counterparties: HashMap<CounterpartyId, Counterparty>,
+    netting_sets: HashMap<NettingSetId, NettingSet>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<PortfolioData> for Portfolio {
+    type Error = PortfolioError;
+
+    fn try_from(data: PortfolioData) -> Result<Self, Self::Error> {
+        PortfolioBuilder::new()
+            .add_counterparties(data.counterparties.into_values())
+            .add_netting_sets(data.netting_sets.into_values())
+            .add_trades(data.trades.into_values())
+            .build()
+    }
+}
+
 impl Portfolio {
     /// Returns the number of trades in the portfolio.
     #[inline]
@@ -261,6 +305,84 @@ impl Portfolio {
             .collect()
     }
 
+    /// Gets the parent of a counterparty, if it has one.
+    pub fn parent(&self, cp_id: &CounterpartyId) -> Option<&Counterparty> {
+        self.counterparties
+            .get(cp_id)?
+            .parent_id()
+            .and_then(|parent| self.counterparties.get(parent))
+    }
+
+    /// Gets the top of a counterparty's hierarchy (itself if it has no
+    /// parent).
+    pub fn ultimate_parent(&self, cp_id: &CounterpartyId) -> Option<&Counterparty> {
+        let mut current = self.counterparties.get(cp_id)?;
+        // The builder rejects cycles, so every chain terminates
+        while let Some(parent) = self.parent(current.id()) {
+            current = parent;
+        }
+        Some(current)
+    }
+
+    /// Gets the direct children of a counterparty.
+    pub fn children(&self, cp_id: &CounterpartyId) -> Vec<&Counterparty> {
+        self.counterparties
+            .values()
+            .filter(|cp| cp.parent_id() == Some(cp_id))
+            .collect()
+    }
+
+    /// Gets a counterparty and all its descendants, parents before
+    /// children.
+    pub fn counterparty_group(&self, cp_id: &CounterpartyId) -> Vec<&Counterparty> {
+        let mut group: Vec<&Counterparty> = self.counterparties.get(cp_id).into_iter().collect();
+        let mut next = 0;
+        while next < group.len() {
+            let id = group[next].id();
+            group.extend(self.children(id));
+            next += 1;
+        }
+        group
+    }
+
+    /// Returns the jurisdiction governing a counterparty: its own, or the
+    /// nearest ancestor's if it has none.
+    pub fn jurisdiction(&self, cp_id: &CounterpartyId) -> Option<&str> {
+        let mut current = self.counterparties.get(cp_id)?;
+        loop {
+            if let Some(jurisdiction) = current.jurisdiction() {
+                return Some(jurisdiction);
+            }
+            current = self.parent(current.id())?;
+        }
+    }
+
+    /// Returns whether a netting set's netting is legally enforceable.
+    ///
+    /// Requires the netting set's own flag and that `rules` accept the
+    /// jurisdiction of its counterparty. Unknown netting sets are not
+    /// enforceable.
+    pub fn is_netting_enforceable(&self, ns_id: &NettingSetId, rules: &NettingRules) -> bool {
+        self.netting_sets.get(ns_id).is_some_and(|ns| {
+            ns.is_enforceable() && rules.is_enforceable_in(self.jurisdiction(ns.counterparty_id()))
+        })
+    }
+
+    /// Returns how a netting set's exposure should be aggregated:
+    /// [`NettingTreatment::Net`] if enforceable, otherwise
+    /// [`NettingTreatment::Gross`].
+    pub fn netting_treatment(
+        &self,
+        ns_id: &NettingSetId,
+        rules: &NettingRules,
+    ) -> NettingTreatment {
+        if self.is_netting_enforceable(ns_id, rules) {
+            NettingTreatment::Net
+        } else {
+            NettingTreatment::Gross
+        }
+    }
+
     /// Returns a parallel iterator over trades.
     ///
     /// Uses Rayon for parallel iteration across multiple threads.
@@ -536,4 +658,143 @@ mod tests {
         let non_empty = create_test_portfolio();
         assert!(!non_empty.is_empty());
     }
+
+    fn create_group_portfolio() -> Portfolio {
+        let credit = || CreditParams::new(0.02, 0.4).unwrap();
+        let parent =
+            Counterparty::new(CounterpartyId::new("GROUP"), credit()).with_jurisdiction("US");
+        let uk = Counterparty::new(CounterpartyId::new("GROUP-UK"), credit())
+            .with_parent(CounterpartyId::new("GROUP"))
+            .with_jurisdiction("GB");
+        let branch = Counterparty::new(CounterpartyId::new("GROUP-UK-BR"), credit())
+            .with_parent(CounterpartyId::new("GROUP-UK"));
+        let offshore = Counterparty::new(CounterpartyId::new("GROUP-XX"), credit())
+            .with_parent(CounterpartyId::new("GROUP"))
+            .with_jurisdiction("XX");
+
+        let ns_uk = NettingSet::new(NettingSetId::new("NS-UK"), CounterpartyId::new("GROUP-UK"));
+        let ns_branch = NettingSet::new(
+            NettingSetId::new("NS-BR"),
+            CounterpartyId::new("GROUP-UK-BR"),
+        );
+        let ns_offshore =
+            NettingSet::new(NettingSetId::new("NS-XX"), CounterpartyId::new("GROUP-XX"));
+        let mut ns_no_opinion =
+            NettingSet::new(NettingSetId::new("NS-NO"), CounterpartyId::new("GROUP"));
+        ns_no_opinion.set_enforceable(false);
+
+        PortfolioBuilder::new()
+            .add_counterparties(vec![parent, uk, branch, offshore])
+            .add_netting_sets(vec![ns_uk, ns_branch, ns_offshore, ns_no_opinion])
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_counterparty_hierarchy() {
+        let portfolio = create_group_portfolio();
+        let branch = CounterpartyId::new("GROUP-UK-BR");
+
+        assert_eq!(portfolio.parent(&branch).unwrap().id().as_str(), "GROUP-UK");
+        assert_eq!(
+            portfolio.ultimate_parent(&branch).unwrap().id().as_str(),
+            "GROUP"
+        );
+        assert!(portfolio.parent(&CounterpartyId::new("GROUP")).is_none());
+        assert_eq!(portfolio.children(&CounterpartyId::new("GROUP")).len(), 2);
+
+        let group = portfolio.counterparty_group(&CounterpartyId::new("GROUP"));
+        assert_eq!(group.len(), 4);
+        assert_eq!(group[0].id().as_str(), "GROUP");
+        assert_eq!(group[3].id().as_str(), "GROUP-UK-BR");
+        assert!(portfolio
+            .counterparty_group(&CounterpartyId::new("UNKNOWN"))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_jurisdiction_inherited_from_parent() {
+        let portfolio = create_group_portfolio();
+
+        assert_eq!(
+            portfolio.jurisdiction(&CounterpartyId::new("GROUP-UK-BR")),
+            Some("GB")
+        );
+        assert_eq!(
+            portfolio.jurisdiction(&CounterpartyId::new("GROUP-XX")),
+            Some("XX")
+        );
+        assert_eq!(portfolio.jurisdiction(&CounterpartyId::new("NONE")), None);
+    }
+
+    #[test]
+    fn test_netting_treatment() {
+        let portfolio = create_group_portfolio();
+        let rules = NettingRules::new().with_non_enforceable_jurisdiction("XX");
+        let treatment = |id: &str| portfolio.netting_treatment(&NettingSetId::new(id), &rules);
+
+        assert_eq!(treatment("NS-UK"), NettingTreatment::Net);
+        assert_eq!(treatment("NS-BR"), NettingTreatment::Net);
+        assert_eq!(treatment("NS-XX"), NettingTreatment::Gross);
+        assert_eq!(treatment("NS-NO"), NettingTreatment::Gross);
+        assert_eq!(treatment("NS-UNKNOWN"), NettingTreatment::Gross);
+
+        // Default rules only honour the netting set flags
+        let permissive = NettingRules::new();
+        assert!(portfolio.is_netting_enforceable(&NettingSetId::new("NS-XX"), &permissive));
+        assert!(!portfolio.is_netting_enforceable(&NettingSetId::new("NS-NO"), &permissive));
+    }
+
+    #[cfg(feature = "serde")]
+    mod serde_tests {
+        use super::*;
+
+        #[test]
+        fn test_trade_schema() {
+            let trade = Trade::new(
+                TradeId::new("T001"),
+                create_test_instrument(),
+                Currency::USD,
+                CounterpartyId::new("CP001"),
+                NettingSetId::new("NS001"),
+                1_000_000.0,
+            );
+            let json = serde_json::to_string(&trade).unwrap();
+            assert_eq!(
+                json,
+                r#"{"id":"T001","instrument":{"Vanilla":{"params":{"strike":100.0,"expiry":1.0,"notional":1.0},"payoff_type":"Call","exercise_style":"European","epsilon":1e-6}},"currency":"USD","counterparty_id":"CP001","netting_set_id":"NS001","notional":1000000.0}"#
+            );
+        }
+
+        #[test]
+        fn test_portfolio_roundtrip() {
+            let portfolio = create_test_portfolio();
+            let json = serde_json::to_string(&portfolio).unwrap();
+            let parsed: Portfolio = serde_json::from_str(&json).unwrap();
+
+            assert_eq!(parsed.trade_count(), 3);
+            assert_eq!(parsed.counterparty_count(), 2);
+            assert_eq!(parsed.netting_set_count(), 2);
+            assert_eq!(parsed.total_notional(), portfolio.total_notional());
+            assert_eq!(
+                parsed
+                    .trades_in_netting_set(&NettingSetId::new("NS001"))
+                    .len(),
+                2
+            );
+        }
+
+        #[test]
+        fn test_deserialise_validates_references() {
+            let portfolio = create_test_portfolio();
+            let mut value = serde_json::to_value(&portfolio).unwrap();
+            value["netting_sets"]
+                .as_object_mut()
+                .unwrap()
+                .remove("NS002");
+
+            let err = serde_json::from_value::<Portfolio>(value).unwrap_err();
+            assert!(err.to_string().contains("NS002"));
+        }
+    }
 }
@@ -3,11 +3,92 @@
 //! This module provides netting set definitions for grouping trades
 //! and managing collateral agreements.
 
+use std::collections::HashSet;
+
 use pricer_core::types::Currency;
 
 use super::error::PortfolioError;
 use super::ids::{CounterpartyId, NettingSetId, TradeId};
 
+/// Asset posted as collateral.
+///
+/// Cash carries no price risk. Securities (bonds, equities) are credited
+/// at their market value less a haircut, and their price moves over the
+/// margin period of risk with the given annualised volatility.
+///
+/// # Examples
+///
+/// ```
+/// use pricer_risk::portfolio::CollateralAsset;
+///
+/// let bond = CollateralAsset::security(0.02, 0.05).unwrap();
+/// assert_eq!(bond.haircut(), 0.02);
+/// assert_eq!(CollateralAsset::Cash.haircut(), 0.0);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CollateralAsset {
+    /// Cash in the agreement currency
+    #[default]
+    Cash,
+    /// Non-cash collateral
+    Security {
+        /// Haircut as a fraction of market value, in [0, 1)
+        haircut: f64,
+        /// Annualised price volatility
+        volatility: f64,
+    },
+}
+
+impl CollateralAsset {
+    /// Creates a non-cash collateral asset.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PortfolioError::InvalidCollateralAgreement` if the haircut
+    /// is outside [0, 1) or the volatility is negative.
+    pub fn security(haircut: f64, volatility: f64) -> Result<Self, PortfolioError> {
+        if !(0.0..1.0).contains(&haircut) {
+            return Err(PortfolioError::InvalidCollateralAgreement(
+                "Haircut must be in range [0, 1)".to_string(),
+            ));
+        }
+        if volatility.is_nan() || volatility < 0.0 {
+            return Err(PortfolioError::InvalidCollateralAgreement(
+                "Collateral volatility must be non-negative".to_string(),
+            ));
+        }
+        Ok(Self::Security {
+            haircut,
+            volatility,
+        })
+    }
+
+    /// Returns the haircut (zero for cash).
+    #[inline]
+    pub fn haircut(&self) -> f64 {
+        match self {
+            Self::Cash => 0.0,
+            Self::Security { haircut, .. } => *haircut,
+        }
+    }
+
+    /// Returns the annualised price volatility (zero for cash).
+    #[inline]
+    pub fn volatility(&self) -> f64 {
+        match self {
+            Self::Cash => 0.0,
+            Self::Security { volatility, .. } => *volatility,
+        }
+    }
+
+    /// Returns whether this is cash collateral.
+    #[inline]
+    pub fn is_cash(&self) -> bool {
+        matches!(self, Self::Cash)
+    }
+}
+
 /// Collateral agreement parameters.
 ///
 /// Defines the terms of a Credit Support Annex (CSA) or similar
@@ -30,8 +111,12 @@ use super::ids::{CounterpartyId, NettingSetId, TradeId};
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CollateralAgreement {
-    /// Threshold amount below which no collateral is required
+    /// Counterparty threshold: exposure below which they post no collateral
     threshold: f64,
+    /// Own threshold: negative exposure below which we post no collateral
+    /// (the counterparty threshold when unset)
+    #[cfg_attr(feature = "serde", serde(default))]
+    own_threshold: Option<f64>,
     /// Minimum transfer amount
     mta: f64,
     /// Independent amount (positive = we post, negative = we receive)
@@ -40,9 +125,26 @@ pub struct CollateralAgreement {
     currency: Currency,
     /// Margin period of risk in years
     mpor: f64,
+    /// Posted collateral asset
+    #[cfg_attr(feature = "serde", serde(default))]
+    asset: CollateralAsset,
+    /// Additional haircut when collateral and exposure currencies differ
+    #[cfg_attr(feature = "serde", serde(default = "standard_fx_haircut"))]
+    fx_haircut: f64,
+    /// Contractual collateral rate minus the OIS rate
+    #[cfg_attr(feature = "serde", serde(default))]
+    remuneration_spread: f64,
+}
+
+#[cfg(feature = "serde")]
+fn standard_fx_haircut() -> f64 {
+    CollateralAgreement::STANDARD_FX_HAIRCUT
 }
 
 impl CollateralAgreement {
+    /// Standard supervisory haircut for a currency mismatch (8%).
+    pub const STANDARD_FX_HAIRCUT: f64 = 0.08;
+
     /// Standard bilateral margin period of risk (10 business days).
     ///
     /// Assumes 252 business days per year.
@@ -63,7 +165,9 @@ impl CollateralAgreement {
     ///
     /// # Arguments
     ///
-    /// * `threshold` - Threshold amount (must be non-negative)
+    /// * `threshold` - Threshold amount (must be non-negative), applied to
+    ///   both sides until [`with_own_threshold`](Self::with_own_threshold)
+    ///   sets our own
     /// * `mta` - Minimum transfer amount (must be non-negative)
     /// * `independent_amount` - Independent amount (can be positive or negative)
     /// * `currency` - Collateral currency
@@ -100,13 +204,77 @@ impl CollateralAgreement {
 
         Ok(Self {
             threshold,
+            own_threshold: None,
             mta,
             independent_amount,
             currency,
             mpor,
+            asset: CollateralAsset::Cash,
+            fx_haircut: Self::STANDARD_FX_HAIRCUT,
+            remuneration_spread: 0.0,
         })
     }
 
+    /// Sets our own threshold: the collateral we post covers a negative
+    /// value beyond it. Use `f64::INFINITY` for a one-way agreement under
+    /// which we never post.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PortfolioError::InvalidCollateralAgreement` if the
+    /// threshold is negative or NaN.
+    pub fn with_own_threshold(mut self, own_threshold: f64) -> Result<Self, PortfolioError> {
+        if own_threshold.is_nan() || own_threshold < 0.0 {
+            return Err(PortfolioError::InvalidCollateralAgreement(
+                "Own threshold must be non-negative".to_string(),
+            ));
+        }
+        self.own_threshold = Some(own_threshold);
+        Ok(self)
+    }
+
+    /// Sets the posted collateral asset (cash by default).
+    pub fn with_asset(mut self, asset: CollateralAsset) -> Self {
+        self.asset = asset;
+        self
+    }
+
+    /// Sets the currency-mismatch haircut (8% by default).
+    ///
+    /// # Errors
+    ///
+    /// Returns `PortfolioError::InvalidCollateralAgreement` if the haircut
+    /// is outside [0, 1).
+    pub fn with_fx_haircut(mut self, fx_haircut: f64) -> Result<Self, PortfolioError> {
+        if !(0.0..1.0).contains(&fx_haircut) {
+            return Err(PortfolioError::InvalidCollateralAgreement(
+                "FX haircut must be in range [0, 1)".to_string(),
+            ));
+        }
+        self.fx_haircut = fx_haircut;
+        Ok(self)
+    }
+
+    /// Sets the collateral remuneration spread: the contractual rate paid
+    /// on collateral minus the OIS rate (zero, i.e. OIS flat, by default).
+    ///
+    /// # Errors
+    ///
+    /// Returns `PortfolioError::InvalidCollateralAgreement` if the spread
+    /// is not finite.
+    pub fn with_remuneration_spread(
+        mut self,
+        remuneration_spread: f64,
+    ) -> Result<Self, PortfolioError> {
+        if !remuneration_spread.is_finite() {
+            return Err(PortfolioError::InvalidCollateralAgreement(
+                "Remuneration spread must be finite".to_string(),
+            ));
+        }
+        self.remuneration_spread = remuneration_spread;
+        Ok(self)
+    }
+
     /// Creates a zero-threshold (fully collateralised) agreement.
     ///
     /// # Arguments
@@ -117,12 +285,18 @@ impl CollateralAgreement {
         Self::new(0.0, 0.0, 0.0, currency, mpor)
     }
 
-    /// Returns the threshold amount.
+    /// Returns the counterparty threshold.
     #[inline]
     pub fn threshold(&self) -> f64 {
         self.threshold
     }
 
+    /// Returns our own threshold.
+    #[inline]
+    pub fn own_threshold(&self) -> f64 {
+        self.own_threshold.unwrap_or(self.threshold)
+    }
+
     /// Returns the minimum transfer amount.
     #[inline]
     pub fn mta(&self) -> f64 {
@@ -147,6 +321,50 @@ impl CollateralAgreement {
         self.mpor
     }
 
+    /// Returns the posted collateral asset.
+    #[inline]
+    pub fn asset(&self) -> CollateralAsset {
+        self.asset
+    }
+
+    /// Returns the remuneration spread over OIS.
+    #[inline]
+    pub fn remuneration_spread(&self) -> f64 {
+        self.remuneration_spread
+    }
+
+    /// Returns the currency-mismatch haircut.
+    #[inline]
+    pub fn fx_haircut(&self) -> f64 {
+        self.fx_haircut
+    }
+
+    /// Returns whether collateral is posted in a currency other than
+    /// `exposure_currency`.
+    #[inline]
+    pub fn has_fx_mismatch(&self, exposure_currency: Currency) -> bool {
+        self.currency != exposure_currency
+    }
+
+    /// Returns the total haircut applied to posted collateral against an
+    /// exposure in `exposure_currency`: the asset haircut plus the FX
+    /// haircut on a currency mismatch, capped at 1.
+    pub fn effective_haircut(&self, exposure_currency: Currency) -> f64 {
+        let fx = if self.has_fx_mismatch(exposure_currency) {
+            self.fx_haircut
+        } else {
+            0.0
+        };
+        (self.asset.haircut() + fx).min(1.0)
+    }
+
+    /// Returns the value credited for collateral of the given market value
+    /// (both in `exposure_currency`) after haircuts.
+    #[inline]
+    pub fn haircut_value(&self, market_value: f64, exposure_currency: Currency) -> f64 {
+        market_value * (1.0 - self.effective_haircut(exposure_currency))
+    }
+
     /// Returns the margin period of risk in business days (assuming 252 days/year).
     #[inline]
     pub fn mpor_days(&self) -> f64 {
@@ -174,7 +392,10 @@ impl CollateralAgreement {
 /// Netting set grouping trades for exposure aggregation.
 ///
 /// A netting set represents a collection of trades with the same counterparty
-/// that can be legally netted in the event of default.
+/// that can be legally netted in the event of default. Where the netting
+/// agreement is not legally enforceable (no opinion, or a jurisdiction that
+/// does not recognise close-out netting), mark it with
+/// [`NettingSet::set_enforceable`] so exposure can be aggregated gross.
 ///
 /// # Examples
 ///
@@ -198,6 +419,13 @@ pub struct NettingSet {
     counterparty_id: CounterpartyId,
     trade_ids: Vec<TradeId>,
     collateral: Option<CollateralAgreement>,
+    #[cfg_attr(feature = "serde", serde(default = "enforceable_by_default"))]
+    enforceable: bool,
+}
+
+#[cfg(feature = "serde")]
+fn enforceable_by_default() -> bool {
+    true
 }
 
 impl NettingSet {
@@ -209,6 +437,7 @@ impl NettingSet {
             counterparty_id,
             trade_ids: Vec::new(),
             collateral: None,
+            enforceable: true,
         }
     }
 
@@ -224,6 +453,7 @@ impl NettingSet {
             counterparty_id,
             trade_ids: Vec::new(),
             collateral: Some(collateral),
+            enforceable: true,
         }
     }
 
@@ -237,6 +467,19 @@ impl NettingSet {
         self.collateral = None;
     }
 
+    /// Sets whether the netting agreement is legally enforceable.
+    ///
+    /// Netting sets are enforceable by default.
+    pub fn set_enforceable(&mut self, enforceable: bool) {
+        self.enforceable = enforceable;
+    }
+
+    /// Returns whether the netting agreement is legally enforceable.
+    #[inline]
+    pub fn is_enforceable(&self) -> bool {
+        self.enforceable
+    }
+
     /// Returns the netting set ID.
     #[inline]
     pub fn id(&self) -> &NettingSetId {
@@ -333,6 +576,60 @@ impl NettingSet {
     }
 }
 
+/// Legal-entity netting rules by jurisdiction.
+///
+/// Lists the jurisdictions in which close-out netting is not enforceable.
+/// A netting set is treated as enforceable only if its own flag is set and
+/// its counterparty's jurisdiction is not listed; counterparties without a
+/// known jurisdiction are enforceable unless
+/// [`NettingRules::with_unknown_jurisdiction_non_enforceable`] is set.
+///
+/// # Examples
+///
+/// ```
+/// use pricer_risk::portfolio::NettingRules;
+///
+/// let rules = NettingRules::new().with_non_enforceable_jurisdiction("XX");
+///
+/// assert!(rules.is_enforceable_in(Some("GB")));
+/// assert!(!rules.is_enforceable_in(Some("XX")));
+/// assert!(rules.is_enforceable_in(None));
+/// ```
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NettingRules {
+    non_enforceable_jurisdictions: HashSet<String>,
+    unknown_non_enforceable: bool,
+}
+
+impl NettingRules {
+    /// Creates rules under which netting is enforceable everywhere.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Marks close-out netting as not enforceable in `jurisdiction`.
+    pub fn with_non_enforceable_jurisdiction(mut self, jurisdiction: impl Into<String>) -> Self {
+        self.non_enforceable_jurisdictions
+            .insert(jurisdiction.into());
+        self
+    }
+
+    /// Treats counterparties without a known jurisdiction as not enforceable.
+    pub fn with_unknown_jurisdiction_non_enforceable(mut self) -> Self {
+        self.unknown_non_enforceable = true;
+        self
+    }
+
+    /// Returns whether netting is enforceable in `jurisdiction`.
+    pub fn is_enforceable_in(&self, jurisdiction: Option<&str>) -> bool {
+        match jurisdiction {
+            Some(j) => !self.non_enforceable_jurisdictions.contains(j),
+            None => !self.unknown_non_enforceable,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -350,6 +647,7 @@ mod tests {
         .unwrap();
 
         assert_eq!(csa.threshold(), 1_000_000.0);
+        assert_eq!(csa.own_threshold(), 1_000_000.0);
         assert_eq!(csa.mta(), 500_000.0);
         assert_eq!(csa.independent_amount(), 0.0);
         assert_eq!(csa.currency(), Currency::USD);
@@ -368,6 +666,15 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_collateral_agreement_own_threshold() {
+        let csa = CollateralAgreement::zero_threshold(Currency::USD, 0.25).unwrap();
+        let csa = csa.with_own_threshold(2_000_000.0).unwrap();
+        assert_eq!(csa.threshold(), 0.0);
+        assert_eq!(csa.own_threshold(), 2_000_000.0);
+        assert!(csa.with_own_threshold(-1.0).is_err());
+    }
+
     #[test]
     fn test_collateral_agreement_invalid_mta() {
         let result = CollateralAgreement::new(
@@ -542,4 +849,83 @@ mod tests {
         let ns2 = ns1.clone();
         assert_eq!(ns1.trade_count(), ns2.trade_count());
     }
+
+    #[test]
+    fn test_netting_set_enforceability() {
+        let mut ns = NettingSet::new(NettingSetId::new("NS001"), CounterpartyId::new("CP001"));
+        assert!(ns.is_enforceable());
+
+        ns.set_enforceable(false);
+        assert!(!ns.is_enforceable());
+    }
+
+    #[test]
+    fn test_netting_rules() {
+        let rules = NettingRules::new();
+        assert!(rules.is_enforceable_in(Some("US")));
+        assert!(rules.is_enforceable_in(None));
+
+        let rules = rules
+            .with_non_enforceable_jurisdiction("XX")
+            .with_unknown_jurisdiction_non_enforceable();
+        assert!(rules.is_enforceable_in(Some("US")));
+        assert!(!rules.is_enforceable_in(Some("XX")));
+        assert!(!rules.is_enforceable_in(None));
+    }
+
+    #[test]
+    fn test_collateral_asset_security() {
+        let bond = CollateralAsset::security(0.04, 0.06).unwrap();
+        assert!(!bond.is_cash());
+        assert_eq!(bond.haircut(), 0.04);
+        assert_eq!(bond.volatility(), 0.06);
+
+        assert!(CollateralAsset::Cash.is_cash());
+        assert_eq!(CollateralAsset::Cash.volatility(), 0.0);
+
+        assert!(CollateralAsset::security(1.0, 0.1).is_err());
+        assert!(CollateralAsset::security(-0.1, 0.1).is_err());
+        assert!(CollateralAsset::security(0.1, -0.1).is_err());
+        assert!(CollateralAsset::security(0.1, f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_collateral_effective_haircut() {
+        let csa = CollateralAgreement::zero_threshold(
+            Currency::EUR,
+            CollateralAgreement::bilateral_mpor(),
+        )
+        .unwrap();
+        assert!(csa.asset().is_cash());
+        assert_eq!(csa.effective_haircut(Currency::EUR), 0.0);
+        assert_relative_eq!(csa.effective_haircut(Currency::USD), 0.08, epsilon = 1e-12);
+
+        let csa = csa
+            .with_asset(CollateralAsset::security(0.05, 0.1).unwrap())
+            .with_fx_haircut(0.1)
+            .unwrap();
+        assert!(csa.has_fx_mismatch(Currency::USD));
+        assert_relative_eq!(csa.effective_haircut(Currency::EUR), 0.05, epsilon = 1e-12);
+        assert_relative_eq!(csa.effective_haircut(Currency::USD), 0.15, epsilon = 1e-12);
+        assert_relative_eq!(
+            csa.haircut_value(1_000.0, Currency::USD),
+            850.0,
+            epsilon = 1e-9
+        );
+        assert!(csa.clone().with_fx_haircut(1.5).is_err());
+    }
+
+    #[test]
+    fn test_collateral_remuneration_spread() {
+        let csa = CollateralAgreement::zero_threshold(
+            Currency::EUR,
+            CollateralAgreement::bilateral_mpor(),
+        )
+        .unwrap();
+        assert_eq!(csa.remuneration_spread(), 0.0);
+
+        let csa = csa.with_remuneration_spread(-0.0005).unwrap();
+        assert_eq!(csa.remuneration_spread(), -0.0005);
+        assert!(csa.with_remuneration_spread(f64::NAN).is_err());
+    }
 }
@@ -39,6 +39,7 @@ use super::ids::{CounterpartyId, NettingSetId, TradeId};
 /// assert_eq!(trade.notional(), 1_000_000.0);
 /// ```
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Trade {
     id: TradeId,
     instrument: Instrument<f64>,
@@ -46,6 +47,8 @@ pub struct Trade {
     counterparty_id: CounterpartyId,
     netting_set_id: NettingSetId,
     notional: f64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    booking_entity: Option<String>,
 }
 
 impl Trade {
@@ -75,9 +78,16 @@ impl Trade {
             counterparty_id,
             netting_set_id,
             notional,
+            booking_entity: None,
         }
     }
 
+    /// Sets the Legal Entity Identifier of the entity booking the trade.
+    pub fn with_booking_entity(mut self, lei: impl Into<String>) -> Self {
+        self.booking_entity = Some(lei.into());
+        self
+    }
+
     /// Returns the trade ID.
     #[inline]
     pub fn id(&self) -> &TradeId {
@@ -114,6 +124,12 @@ impl Trade {
         self.notional
     }
 
+    /// Returns the Legal Entity Identifier of the booking entity if set.
+    #[inline]
+    pub fn booking_entity(&self) -> Option<&str> {
+        self.booking_entity.as_deref()
+    }
+
     /// Computes the payoff at given spot price.
     ///
     /// The payoff is scaled by the notional amount.
@@ -0,0 +1,449 @@
+//! Golden-master regression testing for portfolio pricing.
+//!
+//! This module provides:
+//! - [`GoldenSet`]: named metrics (prices, Greeks, XVA) with a plain-text
+//!   `key value` serialisation suitable for checking into the repository
+//! - [`RegressionTolerance`]: absolute/relative limits, overridable per
+//!   metric prefix
+//! - [`RegressionReport`]: metric-by-metric diff against the golden values,
+//!   rendered as a table on failure
+//! - [`ReferencePortfolio`]: a fixed portfolio with pinned market data and
+//!   seeds whose results form the golden master
+//!
+//! # Workflow
+//!
+//! The golden file lives at `crates/pricer_risk/golden/reference_portfolio.golden`
+//! and is checked by this crate's unit tests. After an intentional change
+//! to pricing numbers, regenerate it with
+//!
+//! ```bash
+//! NEUTRYX_UPDATE_GOLDEN=1 cargo test -p pricer_risk regression
+//! ```
+//!
+//! and review the diff before committing.
+//!
+//! # Examples
+//!
+//! ```
+//! use pricer_risk::regression::{GoldenSet, RegressionTolerance};
+//!
+//! let golden = GoldenSet::parse("price.T1 10.0\nprice.T2 5.0\n").unwrap();
+//! let mut actual = golden.clone();
+//! actual.insert("price.T2", 5.1);
+//!
+//! let report = golden.compare(&actual, &RegressionTolerance::new(1e-8, 1e-8));
+//! assert!(!report.passed());
+//! assert_eq!(report.failures().count(), 1);
+//! ```
+
+mod reference;
+
+pub use reference::ReferencePortfolio;
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Write as _};
+
+use pricer_pricing::router::RouterError;
+use thiserror::Error;
+
+use crate::portfolio::PortfolioError;
+use crate::xva::XvaError;
+
+/// Errors from golden-master regression runs.
+#[derive(Debug, Error)]
+pub enum RegressionError {
+    /// Malformed line in a golden file.
+    #[error("Golden file line {line}: {message}")]
+    Parse {
+        /// One-based line number
+        line: usize,
+        /// Description of the problem
+        message: String,
+    },
+
+    /// Reference portfolio construction failed.
+    #[error("Portfolio: {0}")]
+    Portfolio(#[from] PortfolioError),
+
+    /// Trade pricing failed.
+    #[error("Pricing: {0}")]
+    Pricing(#[from] RouterError),
+
+    /// XVA calculation failed.
+    #[error("XVA: {0}")]
+    Xva(#[from] XvaError),
+}
+
+/// Named metric values, ordered by name.
+///
+/// Names are dot-separated paths such as `price.EQ-CALL-1Y` or
+/// `xva.portfolio.cva`; tolerances can be overridden per prefix.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GoldenSet {
+    metrics: BTreeMap<String, f64>,
+}
+
+impl GoldenSet {
+    /// Creates an empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses the text format written by [`GoldenSet::to_text`].
+    ///
+    /// Each non-empty line not starting with `#` holds a metric name and a
+    /// value separated by whitespace.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RegressionError::Parse` for lines without exactly two
+    /// fields, unparsable values, or duplicate names.
+    pub fn parse(text: &str) -> Result<Self, RegressionError> {
+        let mut set = Self::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let parse_error = |message: String| RegressionError::Parse {
+                line: index + 1,
+                message,
+            };
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            let [name, value] = fields[..] else {
+                return Err(parse_error(format!(
+                    "expected 'name value', got '{}'",
+                    line
+                )));
+            };
+            let value: f64 = value
+                .parse()
+                .map_err(|_| parse_error(format!("invalid value '{}'", value)))?;
+            if set.metrics.insert(name.to_string(), value).is_some() {
+                return Err(parse_error(format!("duplicate metric '{}'", name)));
+            }
+        }
+        Ok(set)
+    }
+
+    /// Renders the set one metric per line, sorted by name, with values in
+    /// shortest round-trip form.
+    pub fn to_text(&self) -> String {
+        self.metrics
+            .iter()
+            .fold(String::new(), |mut text, (name, value)| {
+                let _ = writeln!(text, "{} {:?}", name, value);
+                text
+            })
+    }
+
+    /// Inserts or replaces a metric.
+    #[inline]
+    pub fn insert(&mut self, name: impl Into<String>, value: f64) {
+        self.metrics.insert(name.into(), value);
+    }
+
+    /// Returns a metric value.
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<f64> {
+        self.metrics.get(name).copied()
+    }
+
+    /// Returns the number of metrics.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.metrics.len()
+    }
+
+    /// Returns whether the set is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.metrics.is_empty()
+    }
+
+    /// Iterates over metrics in name order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, f64)> {
+        self.metrics
+            .iter()
+            .map(|(name, &value)| (name.as_str(), value))
+    }
+
+    /// Compares `actual` against this golden set.
+    ///
+    /// Metrics present on only one side are reported as missing or
+    /// unexpected and fail the comparison.
+    pub fn compare(&self, actual: &GoldenSet, tolerance: &RegressionTolerance) -> RegressionReport {
+        let diffs = self
+            .metrics
+            .iter()
+            .filter_map(|(name, &golden)| {
+                let actual = actual.get(name)?;
+                let limit = tolerance.limit_for(name);
+                Some(MetricDiff {
+                    name: name.clone(),
+                    golden,
+                    actual,
+                    passed: limit.accepts(golden, actual),
+                })
+            })
+            .collect();
+        let missing = self
+            .metrics
+            .keys()
+            .filter(|name| !actual.metrics.contains_key(*name))
+            .cloned()
+            .collect();
+        let unexpected = actual
+            .metrics
+            .keys()
+            .filter(|name| !self.metrics.contains_key(*name))
+            .cloned()
+            .collect();
+        RegressionReport {
+            diffs,
+            missing,
+            unexpected,
+        }
+    }
+}
+
+/// Absolute and relative limit for one metric.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MetricLimit {
+    /// Maximum absolute difference
+    pub absolute: f64,
+    /// Maximum difference relative to the golden value
+    pub relative: f64,
+}
+
+impl MetricLimit {
+    /// Returns whether `actual` is within `absolute + relative × |golden|`.
+    ///
+    /// NaN on either side fails.
+    #[inline]
+    pub fn accepts(&self, golden: f64, actual: f64) -> bool {
+        (actual - golden).abs() <= self.absolute + self.relative * golden.abs()
+    }
+}
+
+/// Tolerance policy for a regression comparison.
+///
+/// The default limit applies unless a registered prefix matches the metric
+/// name; the longest matching prefix wins.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegressionTolerance {
+    default: MetricLimit,
+    overrides: Vec<(String, MetricLimit)>,
+}
+
+impl Default for RegressionTolerance {
+    /// Tight limits (1e-10 absolute, 1e-9 relative) that admit only
+    /// floating-point summation-order noise, since every input is pinned.
+    fn default() -> Self {
+        Self::new(1e-10, 1e-9)
+    }
+}
+
+impl RegressionTolerance {
+    /// Creates a policy with the given default limits.
+    #[inline]
+    pub fn new(absolute: f64, relative: f64) -> Self {
+        Self {
+            default: MetricLimit { absolute, relative },
+            overrides: Vec::new(),
+        }
+    }
+
+    /// Overrides the limits for metrics whose name starts with `prefix`.
+    pub fn with_prefix(mut self, prefix: impl Into<String>, absolute: f64, relative: f64) -> Self {
+        self.overrides
+            .push((prefix.into(), MetricLimit { absolute, relative }));
+        self
+    }
+
+    /// Returns the limit applied to `name`.
+    pub fn limit_for(&self, name: &str) -> MetricLimit {
+        self.overrides
+            .iter()
+            .filter(|(prefix, _)| name.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, limit)| *limit)
+            .unwrap_or(self.default)
+    }
+}
+
+/// Comparison of one metric.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MetricDiff {
+    /// Metric name
+    pub name: String,
+    /// Golden value
+    pub golden: f64,
+    /// Newly computed value
+    pub actual: f64,
+    /// Whether the difference is within tolerance
+    pub passed: bool,
+}
+
+impl MetricDiff {
+    /// Returns `actual - golden`.
+    #[inline]
+    pub fn difference(&self) -> f64 {
+        self.actual - self.golden
+    }
+
+    /// Returns the difference relative to the golden value, or `None` if
+    /// the golden value is zero.
+    #[inline]
+    pub fn relative_difference(&self) -> Option<f64> {
+        (self.golden != 0.0).then(|| self.difference() / self.golden.abs())
+    }
+}
+
+/// Outcome of comparing a run against its golden master.
+///
+/// `Display` renders a diff table of failing metrics, followed by missing
+/// and unexpected names.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegressionReport {
+    /// Comparisons of metrics present on both sides, in name order
+    pub diffs: Vec<MetricDiff>,
+    /// Golden metrics absent from the run
+    pub missing: Vec<String>,
+    /// Run metrics absent from the golden set
+    pub unexpected: Vec<String>,
+}
+
+impl RegressionReport {
+    /// Returns whether every metric matched within tolerance.
+    pub fn passed(&self) -> bool {
+        self.missing.is_empty() && self.unexpected.is_empty() && self.diffs.iter().all(|d| d.passed)
+    }
+
+    /// Iterates over metrics outside tolerance.
+    pub fn failures(&self) -> impl Iterator<Item = &MetricDiff> {
+        self.diffs.iter().filter(|d| !d.passed)
+    }
+}
+
+impl fmt::Display for RegressionReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.passed() {
+            return write!(f, "{} metrics match the golden master", self.diffs.len());
+        }
+        let failures: Vec<&MetricDiff> = self.failures().collect();
+        if !failures.is_empty() {
+            let width = failures.iter().map(|d| d.name.len()).max().unwrap_or(0);
+            writeln!(
+                f,
+                "{:<width$}  {:>24}  {:>24}  {:>12}  {:>10}",
+                "metric", "golden", "actual", "diff", "rel diff"
+            )?;
+            for d in &failures {
+                let relative = d
+                    .relative_difference()
+                    .map(|r| format!("{:.3e}", r))
+                    .unwrap_or_else(|| "-".to_string());
+                writeln!(
+                    f,
+                    "{:<width$}  {:>24?}  {:>24?}  {:>12.3e}  {:>10}",
+                    d.name,
+                    d.golden,
+                    d.actual,
+                    d.difference(),
+                    relative
+                )?;
+            }
+        }
+        for name in &self.missing {
+            writeln!(f, "missing: {}", name)?;
+        }
+        for name in &self.unexpected {
+            writeln!(f, "unexpected: {}", name)?;
+        }
+        write!(
+            f,
+            "{} of {} metrics outside tolerance, {} missing, {} unexpected",
+            failures.len(),
+            self.diffs.len(),
+            self.missing.len(),
+            self.unexpected.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_round_trip() {
+        let mut set = GoldenSet::new();
+        set.insert("price.B", 0.1 + 0.2);
+        set.insert("price.A", -1.5e-12);
+        let text = set.to_text();
+        assert!(text.starts_with("price.A "));
+        assert_eq!(GoldenSet::parse(&text).unwrap(), set);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let parsed = GoldenSet::parse("# header\n\nprice.A 1.0\n").unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert!(matches!(
+            GoldenSet::parse("price.A 1.0\nprice.B\n"),
+            Err(RegressionError::Parse { line: 2, .. })
+        ));
+        assert!(matches!(
+            GoldenSet::parse("price.A one\n"),
+            Err(RegressionError::Parse { line: 1, .. })
+        ));
+        assert!(matches!(
+            GoldenSet::parse("price.A 1.0\nprice.A 2.0\n"),
+            Err(RegressionError::Parse { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_prefix_tolerance() {
+        let tolerance = RegressionTolerance::new(0.0, 0.0)
+            .with_prefix("xva.", 1e-6, 0.0)
+            .with_prefix("xva.portfolio.", 1e-3, 0.0);
+        assert_eq!(tolerance.limit_for("price.A").absolute, 0.0);
+        assert_eq!(tolerance.limit_for("xva.NS1.cva").absolute, 1e-6);
+        assert_eq!(tolerance.limit_for("xva.portfolio.cva").absolute, 1e-3);
+    }
+
+    #[test]
+    fn test_compare_reports_diffs() {
+        let golden = GoldenSet::parse("a 1.0\nb 2.0\nc 3.0\n").unwrap();
+        let actual = GoldenSet::parse("a 1.0\nb 2.5\nd 4.0\n").unwrap();
+        let report = golden.compare(&actual, &RegressionTolerance::default());
+
+        assert!(!report.passed());
+        assert_eq!(report.diffs.len(), 2);
+        assert_eq!(report.missing, vec!["c".to_string()]);
+        assert_eq!(report.unexpected, vec!["d".to_string()]);
+        let failure = report.failures().next().unwrap();
+        assert_eq!(failure.name, "b");
+        assert_eq!(failure.relative_difference(), Some(0.25));
+
+        let table = report.to_string();
+        assert!(table.contains("missing: c"));
+        assert!(table.contains("unexpected: d"));
+        assert!(table.ends_with("1 of 2 metrics outside tolerance, 1 missing, 1 unexpected"));
+    }
+
+    #[test]
+    fn test_nan_fails() {
+        let golden = GoldenSet::parse("a 1.0\n").unwrap();
+        let mut actual = GoldenSet::new();
+        actual.insert("a", f64::NAN);
+        assert!(!golden
+            .compare(&actual, &RegressionTolerance::new(f64::MAX, 0.0))
+            .passed());
+        assert!(golden
+            .compare(&golden, &RegressionTolerance::new(0.0, 0.0))
+            .passed());
+    }
+}
@@ -0,0 +1,430 @@
+//! Reference portfolio for the golden master.
+
+use std::collections::HashMap;
+
+use pricer_core::types::Currency;
+use pricer_models::instruments::{
+    Direction, ExerciseStyle, Forward, Instrument, InstrumentParams, PayoffType, VanillaOption,
+};
+use pricer_pricing::engine::{Engine, ProductKind};
+use pricer_pricing::mc::MonteCarloConfig;
+use pricer_pricing::rng::PricerRng;
+use pricer_pricing::router::{EngineRouter, MarketInputs, RouterError};
+
+use super::{GoldenSet, RegressionError};
+use crate::exposure::ExposureCalculator;
+use crate::portfolio::{
+    Counterparty, CounterpartyId, CreditParams, NettingSet, NettingSetId, Portfolio,
+    PortfolioBuilder, Trade, TradeId,
+};
+use crate::xva::{generate_flat_discount_factors, FundingParams, OwnCreditParams, XvaCalculator};
+
+/// Simulated values `[path][time]`.
+type PathValues = Vec<Vec<f64>>;
+
+/// Fixed portfolio, market and seeds whose results form the golden master.
+///
+/// Two counterparties hold seven equity trades across two netting sets:
+/// European vanillas and an arithmetic Asian priced by Monte Carlo, an
+/// American put on the tree, a digital in closed form and long and short
+/// forwards. Every input that influences the numbers is pinned here, so any
+/// movement in [`ReferencePortfolio::run`] comes from code changes.
+///
+/// Exposure paths revalue each trade at its European closed-form value on
+/// the remaining tenor; early exercise and averaging are ignored there so
+/// the exposure simulation stays cheap and independent of the engines
+/// under test.
+///
+/// # Examples
+///
+/// ```
+/// use pricer_risk::regression::ReferencePortfolio;
+///
+/// let reference = ReferencePortfolio::standard();
+/// assert_eq!(reference.portfolio().trade_count(), 7);
+/// ```
+#[derive(Debug)]
+pub struct ReferencePortfolio {
+    portfolio: Portfolio,
+    market: MarketInputs,
+    router: EngineRouter,
+    time_grid: Vec<f64>,
+    exposure_paths: usize,
+    exposure_seed: u64,
+    own_credit: OwnCreditParams,
+    funding: FundingParams,
+}
+
+impl ReferencePortfolio {
+    /// Creates the standard reference portfolio.
+    ///
+    /// Market: spot 100, rate 3%, volatility 20%. Monte Carlo uses 10,000
+    /// paths of 12 steps with seed 2024; exposures use 1,000 paths on a
+    /// quarterly grid to two years with seed 7.
+    pub fn standard() -> Self {
+        let mc_config = MonteCarloConfig::builder()
+            .n_paths(10_000)
+            .n_steps(12)
+            .seed(2024)
+            .build()
+            .expect("reference Monte Carlo settings are valid");
+        let router = EngineRouter::new()
+            .with_mc_config(mc_config)
+            .with_default_engine(ProductKind::EuropeanVanilla, Engine::MonteCarlo);
+
+        Self {
+            portfolio: reference_portfolio(),
+            market: MarketInputs::new(100.0, 0.03, 0.2),
+            router,
+            time_grid: (0..=8).map(|i| i as f64 * 0.25).collect(),
+            exposure_paths: 1_000,
+            exposure_seed: 7,
+            own_credit: OwnCreditParams::new(0.01, 0.6).expect("valid own credit"),
+            funding: FundingParams::from_bps(50.0, 30.0),
+        }
+    }
+
+    /// Returns the portfolio.
+    #[inline]
+    pub fn portfolio(&self) -> &Portfolio {
+        &self.portfolio
+    }
+
+    /// Returns the market inputs.
+    #[inline]
+    pub fn market(&self) -> &MarketInputs {
+        &self.market
+    }
+
+    /// Prices the portfolio and computes its XVA.
+    ///
+    /// Produces, per trade, `price.<id>`, `delta.<id>` and `gamma.<id>`
+    /// (scaled by trade notional); per netting set, `exposure.<ns>.epe`,
+    /// `exposure.<ns>.peak_pfe` and `xva.<ns>.{cva,dva,fca,fba}`; and
+    /// portfolio totals `xva.portfolio.{cva,dva,fca,fba}`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RegressionError` if a trade cannot be priced or the XVA
+    /// inputs are inconsistent.
+    pub fn run(&self) -> Result<GoldenSet, RegressionError> {
+        let mut golden = GoldenSet::new();
+
+        for trade in self.portfolio.trades() {
+            let id = trade.id();
+            let priced = self
+                .router
+                .price(Some(id.as_str()), trade.instrument(), &self.market)?;
+            let notional = trade.notional();
+            golden.insert(format!("price.{}", id), priced.price * notional);
+            if let Some(delta) = priced.delta {
+                golden.insert(format!("delta.{}", id), delta * notional);
+            }
+            if let Some(gamma) = priced.gamma {
+                golden.insert(format!("gamma.{}", id), gamma * notional);
+            }
+        }
+
+        let values = self.simulate_netting_set_values()?;
+        let mut ee_profiles = HashMap::new();
+        let mut ene_profiles = HashMap::new();
+        for (ns_id, paths) in values {
+            let ee = ExposureCalculator::expected_exposure(&paths);
+            let pfe = ExposureCalculator::potential_future_exposure(&paths, 0.95);
+            golden.insert(
+                format!("exposure.{}.epe", ns_id),
+                ExposureCalculator::expected_positive_exposure(&ee, &self.time_grid),
+            );
+            golden.insert(
+                format!("exposure.{}.peak_pfe", ns_id),
+                ExposureCalculator::peak_pfe(&pfe),
+            );
+            ene_profiles.insert(
+                ns_id.clone(),
+                ExposureCalculator::expected_negative_exposure(&paths),
+            );
+            ee_profiles.insert(ns_id, ee);
+        }
+
+        let xva = XvaCalculator::new()
+            .with_own_credit(self.own_credit.clone())
+            .with_funding(self.funding.clone())
+            .compute_portfolio_xva(
+                &self.portfolio,
+                &ee_profiles,
+                &ene_profiles,
+                &self.time_grid,
+                &generate_flat_discount_factors(self.market.rate, &self.time_grid),
+            )?;
+        for ns in xva
+            .by_counterparty
+            .iter()
+            .flat_map(|cp| &cp.netting_set_xvas)
+        {
+            let id = &ns.netting_set_id;
+            golden.insert(format!("xva.{}.cva", id), ns.cva);
+            golden.insert(format!("xva.{}.dva", id), ns.dva);
+            golden.insert(format!("xva.{}.fca", id), ns.fca);
+            golden.insert(format!("xva.{}.fba", id), ns.fba);
+        }
+        golden.insert("xva.portfolio.cva", xva.cva);
+        golden.insert("xva.portfolio.dva", xva.dva);
+        golden.insert("xva.portfolio.fca", xva.fca);
+        golden.insert("xva.portfolio.fba", xva.fba);
+
+        Ok(golden)
+    }
+
+    /// Simulates GBM spot paths on the time grid and returns netting-set
+    /// values `[path][time]`.
+    fn simulate_netting_set_values(
+        &self,
+    ) -> Result<Vec<(NettingSetId, PathValues)>, RegressionError> {
+        let MarketInputs {
+            spot,
+            rate,
+            volatility,
+        } = self.market;
+        let mut rng = PricerRng::from_seed(self.exposure_seed);
+        let spot_paths: Vec<Vec<f64>> = (0..self.exposure_paths)
+            .map(|_| {
+                let mut s = spot;
+                let mut previous = 0.0;
+                self.time_grid
+                    .iter()
+                    .map(|&t| {
+                        let dt = t - previous;
+                        previous = t;
+                        s *= ((rate - 0.5 * volatility * volatility) * dt
+                            + volatility * dt.sqrt() * rng.gen_normal())
+                        .exp();
+                        s
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let closed_form = EngineRouter::new();
+        let mut netting_sets: Vec<&NettingSet> = self.portfolio.netting_sets().collect();
+        netting_sets.sort_by(|a, b| a.id().as_str().cmp(b.id().as_str()));
+        netting_sets
+            .into_iter()
+            .map(|ns| {
+                let trades = self.portfolio.trades_in_netting_set(ns.id());
+                let mut paths = vec![vec![0.0; self.time_grid.len()]; self.exposure_paths];
+                for (path, spots) in paths.iter_mut().zip(&spot_paths) {
+                    for ((value, &t), &s) in path.iter_mut().zip(&self.time_grid).zip(spots) {
+                        for trade in &trades {
+                            *value += mark_to_market(&closed_form, trade, t, s, rate, volatility)?;
+                        }
+                    }
+                }
+                Ok((ns.id().clone(), paths))
+            })
+            .collect()
+    }
+}
+
+/// European closed-form value of `trade` at time `t` and spot `s`, scaled
+/// by trade notional. Trades are worth their payoff at expiry and nothing
+/// after it.
+fn mark_to_market(
+    router: &EngineRouter,
+    trade: &Trade,
+    t: f64,
+    s: f64,
+    rate: f64,
+    volatility: f64,
+) -> Result<f64, RegressionError> {
+    let remaining = trade.expiry() - t;
+    if remaining < -1e-12 {
+        return Ok(0.0);
+    }
+    if remaining <= 1e-12 {
+        return Ok(trade.payoff(s));
+    }
+    let instrument = match trade.instrument() {
+        Instrument::Vanilla(option) => {
+            let params = InstrumentParams::new(option.strike(), remaining, option.notional())
+                .map_err(|e| RouterError::Unroutable(e.to_string()))?;
+            Instrument::Vanilla(VanillaOption::new(
+                params,
+                option.payoff_type(),
+                ExerciseStyle::European,
+                option.epsilon(),
+            ))
+        }
+        Instrument::Forward(forward) => Instrument::Forward(
+            Forward::new(
+                forward.strike(),
+                remaining,
+                forward.notional(),
+                forward.direction(),
+            )
+            .map_err(|e| RouterError::Unroutable(e.to_string()))?,
+        ),
+        Instrument::Swap(_) => {
+            return Err(
+                RouterError::Unroutable("swaps are not in the reference portfolio".into()).into(),
+            )
+        }
+    };
+    let priced = router.price(None, &instrument, &MarketInputs::new(s, rate, volatility))?;
+    Ok(priced.price * trade.notional())
+}
+
+fn reference_portfolio() -> Portfolio {
+    let vanilla = |strike, expiry, payoff, exercise| {
+        let params = InstrumentParams::new(strike, expiry, 1.0).expect("valid reference trade");
+        Instrument::Vanilla(VanillaOption::new(params, payoff, exercise, 1e-6))
+    };
+    let forward = |strike, expiry, direction| {
+        Instrument::Forward(Forward::new(strike, expiry, 1.0, direction).expect("valid forward"))
+    };
+    let trade = |id: &str, instrument, ns: &str, notional| {
+        let (cp, ns) = match ns {
+            "NS-A" => ("CP-A", "NS-A"),
+            _ => ("CP-B", "NS-B"),
+        };
+        Trade::new(
+            TradeId::new(id),
+            instrument,
+            Currency::USD,
+            CounterpartyId::new(cp),
+            NettingSetId::new(ns),
+            notional,
+        )
+    };
+
+    let trades = vec![
+        trade(
+            "EQ-CALL-1Y",
+            vanilla(100.0, 1.0, PayoffType::Call, ExerciseStyle::European),
+            "NS-A",
+            1_000.0,
+        ),
+        trade(
+            "EQ-PUT-6M",
+            vanilla(95.0, 0.5, PayoffType::Put, ExerciseStyle::European),
+            "NS-A",
+            500.0,
+        ),
+        trade(
+            "FWD-LONG-2Y",
+            forward(105.0, 2.0, Direction::Long),
+            "NS-A",
+            200.0,
+        ),
+        trade(
+            "AM-PUT-1Y",
+            vanilla(100.0, 1.0, PayoffType::Put, ExerciseStyle::American),
+            "NS-B",
+            800.0,
+        ),
+        trade(
+            "DIG-CALL-1Y",
+            vanilla(110.0, 1.0, PayoffType::DigitalCall, ExerciseStyle::European),
+            "NS-B",
+            10_000.0,
+        ),
+        trade(
+            "ASIAN-CALL-1Y",
+            vanilla(
+                100.0,
+                1.0,
+                PayoffType::Call,
+                ExerciseStyle::asian(0.0, 1.0, 12),
+            ),
+            "NS-B",
+            600.0,
+        ),
+        trade(
+            "FWD-SHORT-18M",
+            forward(100.0, 1.5, Direction::Short),
+            "NS-B",
+            1_500.0,
+        ),
+    ];
+
+    let netting_set = |ns: &str, cp: &str| {
+        let mut netting_set = NettingSet::new(NettingSetId::new(ns), CounterpartyId::new(cp));
+        netting_set.add_trades(
+            trades
+                .iter()
+                .filter(|t| t.netting_set_id().as_str() == ns)
+                .map(|t| t.id().clone()),
+        );
+        netting_set
+    };
+
+    PortfolioBuilder::new()
+        .add_counterparty(Counterparty::new(
+            CounterpartyId::new("CP-A"),
+            CreditParams::new(0.02, 0.6).expect("valid credit"),
+        ))
+        .add_counterparty(Counterparty::new(
+            CounterpartyId::new("CP-B"),
+            CreditParams::new(0.01, 0.4).expect("valid credit"),
+        ))
+        .add_netting_set(netting_set("NS-A", "CP-A"))
+        .add_netting_set(netting_set("NS-B", "CP-B"))
+        .add_trades(trades)
+        .build()
+        .expect("reference portfolio is consistent")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::regression::RegressionTolerance;
+
+    const GOLDEN_PATH: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/golden/reference_portfolio.golden"
+    );
+
+    const GOLDEN_HEADER: &str = "\
+# Golden master for pricer_risk::regression::ReferencePortfolio.
+# Regenerate with: NEUTRYX_UPDATE_GOLDEN=1 cargo test -p pricer_risk regression
+";
+
+    #[test]
+    fn test_reference_portfolio_matches_golden() {
+        let actual = ReferencePortfolio::standard().run().unwrap();
+        if std::env::var_os("NEUTRYX_UPDATE_GOLDEN").is_some() {
+            let text = format!("{}{}", GOLDEN_HEADER, actual.to_text());
+            std::fs::write(GOLDEN_PATH, text).unwrap();
+            return;
+        }
+        let golden = GoldenSet::parse(&std::fs::read_to_string(GOLDEN_PATH).unwrap()).unwrap();
+        let report = golden.compare(&actual, &RegressionTolerance::default());
+        assert!(report.passed(), "golden master mismatch:\n{}", report);
+    }
+
+    #[test]
+    fn test_run_is_deterministic() {
+        let reference = ReferencePortfolio::standard();
+        let first = reference.run().unwrap();
+        let second = reference.run().unwrap();
+        assert!(first
+            .compare(&second, &RegressionTolerance::new(0.0, 1e-12))
+            .passed());
+        // Greeks for all but the Asian, 2×(2 exposure + 4 XVA), 4 totals
+        assert_eq!(first.len(), 7 + 6 * 2 + 2 * 6 + 4);
+    }
+
+    #[test]
+    fn test_metrics_are_plausible() {
+        let golden = ReferencePortfolio::standard().run().unwrap();
+        let call = golden.get("price.EQ-CALL-1Y").unwrap();
+        assert!((call / 1_000.0 - 9.41).abs() < 0.5, "{}", call);
+        assert!(golden.get("delta.FWD-SHORT-18M").unwrap() < 0.0);
+        assert!(golden.get("xva.NS-A.cva").unwrap() > 0.0);
+        assert!(golden.get("xva.NS-B.dva").unwrap() > 0.0);
+        let total: f64 = ["NS-A", "NS-B"]
+            .iter()
+            .map(|ns| golden.get(&format!("xva.{}.cva", ns)).unwrap())
+            .sum();
+        assert!((golden.get("xva.portfolio.cva").unwrap() - total).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,337 @@
+//! Basic approach to CVA capital, reduced version (MAR50.14).
+//!
+//! Each counterparty's standalone CVA capital is
+//!
+//! ```text
+//! SCVA_c = RW_c / α · Σ_NS M_NS · EAD_NS · DF_NS
+//! DF_NS  = (1 - exp(-0.05 M_NS)) / (0.05 M_NS)
+//! ```
+//!
+//! and the portfolio capital aggregates them with a single systematic
+//! factor:
+//!
+//! ```text
+//! K = DS · √((ρ Σ_c SCVA_c)² + (1 - ρ²) Σ_c SCVA_c²)
+//! ```
+
+use std::collections::HashSet;
+
+use super::{
+    sorted, CounterpartyCapital, CvaCapital, CvaCapitalMethod, CvaSector, RegulatoryError,
+    RegulatoryResult,
+};
+use crate::portfolio::{CounterpartyId, CreditRating, NettingSetId};
+
+/// Exposure of one netting set.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BaCvaExposure {
+    /// Netting set
+    pub netting_set_id: NettingSetId,
+    /// Exposure at default
+    pub ead: f64,
+    /// Effective maturity in years
+    pub effective_maturity: f64,
+}
+
+impl BaCvaExposure {
+    /// Supervisory discount factor `(1 - e^{-0.05M}) / (0.05M)`.
+    fn discount_factor(&self) -> f64 {
+        let x = 0.05 * self.effective_maturity;
+        if x < 1e-12 {
+            1.0
+        } else {
+            -(-x).exp_m1() / x
+        }
+    }
+}
+
+/// BA-CVA inputs for one counterparty.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BaCvaCounterparty {
+    /// Counterparty
+    pub counterparty_id: CounterpartyId,
+    /// Sector, for the risk weight
+    pub sector: CvaSector,
+    /// Credit rating, for the risk weight
+    pub rating: CreditRating,
+    /// Netting sets with the counterparty
+    pub netting_sets: Vec<BaCvaExposure>,
+}
+
+impl BaCvaCounterparty {
+    /// Creates a counterparty without netting sets.
+    pub fn new(counterparty_id: CounterpartyId, sector: CvaSector, rating: CreditRating) -> Self {
+        Self {
+            counterparty_id,
+            sector,
+            rating,
+            netting_sets: Vec::new(),
+        }
+    }
+
+    /// Adds a netting set with its EAD and effective maturity in years.
+    pub fn with_netting_set(
+        mut self,
+        netting_set_id: NettingSetId,
+        ead: f64,
+        effective_maturity: f64,
+    ) -> Self {
+        self.netting_sets.push(BaCvaExposure {
+            netting_set_id,
+            ead,
+            effective_maturity,
+        });
+        self
+    }
+
+    fn validate(&self) -> RegulatoryResult<()> {
+        for exposure in &self.netting_sets {
+            if !(exposure.ead >= 0.0 && exposure.ead.is_finite()) {
+                return Err(RegulatoryError::InvalidExposure(format!(
+                    "EAD {} of netting set {} must be non-negative and finite",
+                    exposure.ead,
+                    exposure.netting_set_id.as_str()
+                )));
+            }
+            if !(exposure.effective_maturity >= 0.0 && exposure.effective_maturity.is_finite()) {
+                return Err(RegulatoryError::InvalidExposure(format!(
+                    "effective maturity {} of netting set {} must be non-negative and finite",
+                    exposure.effective_maturity,
+                    exposure.netting_set_id.as_str()
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reduced BA-CVA calculator.
+///
+/// # Examples
+///
+/// ```
+/// use pricer_risk::portfolio::{CounterpartyId, CreditRating, NettingSetId};
+/// use pricer_risk::regulatory::{BaCvaCalculator, BaCvaCounterparty, CvaSector};
+///
+/// let counterparty = BaCvaCounterparty::new(
+///     CounterpartyId::new("CP001"),
+///     CvaSector::Financial,
+///     CreditRating::A,
+/// )
+/// .with_netting_set(NettingSetId::new("NS001"), 1_000_000.0, 1.0);
+///
+/// // DS · RW / α · M · EAD · DF = 0.65 · 5% / 1.4 · 1 · 1m · 0.9754
+/// let capital = BaCvaCalculator::default().calculate(&[counterparty]).unwrap();
+/// assert!((capital.total - 22_643.5).abs() < 1.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BaCvaCalculator {
+    /// Correlation ρ between counterparty credit spreads and the systematic
+    /// factor
+    pub correlation: f64,
+    /// Discount scalar DS applied to the reduced capital
+    pub discount_scalar: f64,
+    /// Alpha multiplier α divided out of the EAD
+    pub alpha: f64,
+    /// Whether to apply the supervisory discount factor (banks without IMM
+    /// approval; IMM EADs are already discounted)
+    pub supervisory_discount: bool,
+}
+
+impl Default for BaCvaCalculator {
+    /// Returns `ρ = 0.5`, `DS = 0.65`, `α = 1.4` with supervisory
+    /// discounting.
+    fn default() -> Self {
+        Self {
+            correlation: 0.5,
+            discount_scalar: 0.65,
+            alpha: 1.4,
+            supervisory_discount: true,
+        }
+    }
+}
+
+impl BaCvaCalculator {
+    /// Sets whether the supervisory discount factor is applied.
+    pub fn with_supervisory_discount(mut self, supervisory_discount: bool) -> Self {
+        self.supervisory_discount = supervisory_discount;
+        self
+    }
+
+    /// Computes BA-CVA capital.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RegulatoryError::InvalidParameter` for a correlation outside
+    /// `[0, 1]` or a non-positive scalar, and
+    /// `RegulatoryError::InvalidExposure` for invalid exposures or a
+    /// repeated counterparty.
+    pub fn calculate(&self, counterparties: &[BaCvaCounterparty]) -> RegulatoryResult<CvaCapital> {
+        self.validate()?;
+        let mut seen = HashSet::new();
+        for counterparty in counterparties {
+            counterparty.validate()?;
+            if !seen.insert(&counterparty.counterparty_id) {
+                return Err(RegulatoryError::InvalidExposure(format!(
+                    "counterparty {} appears twice",
+                    counterparty.counterparty_id.as_str()
+                )));
+            }
+        }
+
+        let scva: Vec<f64> = counterparties.iter().map(|c| self.scva(c)).collect();
+        let systematic = self.correlation * scva.iter().sum::<f64>();
+        let idiosyncratic =
+            (1.0 - self.correlation * self.correlation) * scva.iter().map(|s| s * s).sum::<f64>();
+        let total = self.discount_scalar * (systematic * systematic + idiosyncratic).sqrt();
+
+        let standalone = counterparties
+            .iter()
+            .zip(&scva)
+            .map(|(c, s)| CounterpartyCapital {
+                counterparty_id: c.counterparty_id.clone(),
+                standalone: self.discount_scalar * s,
+            })
+            .collect();
+        Ok(CvaCapital {
+            method: CvaCapitalMethod::BaCva,
+            total,
+            counterparties: sorted(standalone),
+            risk_classes: Vec::new(),
+        })
+    }
+
+    /// Standalone CVA capital `SCVA_c` before the discount scalar.
+    fn scva(&self, counterparty: &BaCvaCounterparty) -> f64 {
+        let weighted: f64 = counterparty
+            .netting_sets
+            .iter()
+            .map(|e| {
+                let df = if self.supervisory_discount {
+                    e.discount_factor()
+                } else {
+                    1.0
+                };
+                e.effective_maturity * e.ead * df
+            })
+            .sum();
+        counterparty.sector.risk_weight(counterparty.rating) / self.alpha * weighted
+    }
+
+    fn validate(&self) -> RegulatoryResult<()> {
+        if !(0.0..=1.0).contains(&self.correlation) {
+            return Err(RegulatoryError::InvalidParameter(format!(
+                "correlation {} must be in [0, 1]",
+                self.correlation
+            )));
+        }
+        for (name, value) in [
+            ("discount scalar", self.discount_scalar),
+            ("alpha", self.alpha),
+        ] {
+            if !(value > 0.0 && value.is_finite()) {
+                return Err(RegulatoryError::InvalidParameter(format!(
+                    "{} {} must be positive and finite",
+                    name, value
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn counterparty(id: &str, sector: CvaSector, rating: CreditRating) -> BaCvaCounterparty {
+        BaCvaCounterparty::new(CounterpartyId::new(id), sector, rating)
+    }
+
+    #[test]
+    fn test_single_counterparty_matches_formula() {
+        let cp = counterparty("CP1", CvaSector::Financial, CreditRating::A)
+            .with_netting_set(NettingSetId::new("NS1"), 1_000_000.0, 2.0)
+            .with_netting_set(NettingSetId::new("NS2"), 500_000.0, 5.0);
+        let capital = BaCvaCalculator::default().calculate(&[cp]).unwrap();
+
+        let df = |m: f64| (1.0 - (-0.05 * m).exp()) / (0.05 * m);
+        let scva = 0.05 / 1.4 * (2.0 * 1_000_000.0 * df(2.0) + 5.0 * 500_000.0 * df(5.0));
+        assert_relative_eq!(capital.total, 0.65 * scva, max_relative = 1e-12);
+        assert_relative_eq!(
+            capital.standalone_sum(),
+            capital.total,
+            max_relative = 1e-12
+        );
+    }
+
+    #[test]
+    fn test_diversification_between_counterparties() {
+        let a = counterparty("A", CvaSector::Financial, CreditRating::A).with_netting_set(
+            NettingSetId::new("NS1"),
+            1_000_000.0,
+            1.0,
+        );
+        let b = counterparty("B", CvaSector::Technology, CreditRating::BB).with_netting_set(
+            NettingSetId::new("NS2"),
+            2_000_000.0,
+            1.0,
+        );
+        let capital = BaCvaCalculator::default().calculate(&[b, a]).unwrap();
+
+        let s_a = capital.standalone(&CounterpartyId::new("A")).unwrap();
+        let s_b = capital.standalone(&CounterpartyId::new("B")).unwrap();
+        let expected = (0.25 * (s_a + s_b).powi(2) + 0.75 * (s_a * s_a + s_b * s_b)).sqrt();
+        assert_relative_eq!(capital.total, expected, max_relative = 1e-12);
+        assert!(capital.diversification_benefit() > 0.0);
+        assert_eq!(capital.counterparties[0].counterparty_id.as_str(), "A");
+    }
+
+    #[test]
+    fn test_imm_exposures_are_not_discounted() {
+        let cp = counterparty("CP1", CvaSector::Sovereign, CreditRating::AA).with_netting_set(
+            NettingSetId::new("NS1"),
+            1_000_000.0,
+            10.0,
+        );
+        let discounted = BaCvaCalculator::default()
+            .calculate(std::slice::from_ref(&cp))
+            .unwrap();
+        let imm = BaCvaCalculator::default()
+            .with_supervisory_discount(false)
+            .calculate(&[cp])
+            .unwrap();
+        assert_relative_eq!(imm.total, 0.65 * 0.005 / 1.4 * 10.0 * 1_000_000.0);
+        assert!(discounted.total < imm.total);
+    }
+
+    #[test]
+    fn test_rejects_invalid_inputs() {
+        let calculator = BaCvaCalculator::default();
+        let negative = counterparty("CP1", CvaSector::Other, CreditRating::B).with_netting_set(
+            NettingSetId::new("NS1"),
+            -1.0,
+            1.0,
+        );
+        assert!(matches!(
+            calculator.calculate(&[negative]),
+            Err(RegulatoryError::InvalidExposure(_))
+        ));
+        let cp = counterparty("CP1", CvaSector::Other, CreditRating::B);
+        assert!(matches!(
+            calculator.calculate(&[cp.clone(), cp.clone()]),
+            Err(RegulatoryError::InvalidExposure(_))
+        ));
+        let bad = BaCvaCalculator {
+            correlation: 1.5,
+            ..calculator
+        };
+        assert!(matches!(
+            bad.calculate(&[cp]),
+            Err(RegulatoryError::InvalidParameter(_))
+        ));
+    }
+}
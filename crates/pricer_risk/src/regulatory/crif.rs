@@ -0,0 +1,699 @@
+//! CRIF (Common Risk Interchange Format) import and export.
+//!
+//! CRIF is the ISDA file format for exchanging the sensitivities that feed
+//! SIMM initial margin. Each row is one sensitivity of one trade:
+//!
+//! | Column | Required | Content |
+//! |--------|----------|---------|
+//! | `TradeID` | no | Trade identifier |
+//! | `PortfolioID` | no | Portfolio or netting set identifier |
+//! | `ProductClass` | yes | `RatesFX`, `Credit`, `Equity` or `Commodity` |
+//! | `RiskType` | yes | SIMM risk type, e.g. `Risk_IRCurve` |
+//! | `Qualifier` | yes | Currency, issuer or underlying |
+//! | `Bucket` | no | SIMM bucket |
+//! | `Label1` | no | Tenor or expiry |
+//! | `Label2` | no | Sub-curve or payment frequency |
+//! | `Amount` | no | Sensitivity in `AmountCurrency` |
+//! | `AmountCurrency` | no | ISO 4217 code of `Amount` |
+//! | `AmountUSD` | yes | Sensitivity in USD |
+//!
+//! Files are read tab or comma separated, detected from the header, with
+//! case-insensitive column names; other columns are ignored. They are
+//! written tab separated with the columns above. Risk types outside the
+//! SIMM delta and vega set, such as `Param_` and schedule rows, are kept as
+//! [`CrifRiskType::Other`] so that files round-trip.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use pricer_pricing::greeks::{Sensitivity, StructuredGreeks};
+
+use super::{RegulatoryError, RegulatoryResult};
+
+/// Columns in written order.
+const COLUMNS: [&str; 11] = [
+    "TradeID",
+    "PortfolioID",
+    "ProductClass",
+    "RiskType",
+    "Qualifier",
+    "Bucket",
+    "Label1",
+    "Label2",
+    "Amount",
+    "AmountCurrency",
+    "AmountUSD",
+];
+
+/// SIMM product class.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CrifProductClass {
+    /// Interest rates and FX
+    RatesFx,
+    /// Credit
+    Credit,
+    /// Equity
+    Equity,
+    /// Commodity
+    Commodity,
+}
+
+impl CrifProductClass {
+    /// Returns the CRIF code.
+    pub fn code(self) -> &'static str {
+        match self {
+            CrifProductClass::RatesFx => "RatesFX",
+            CrifProductClass::Credit => "Credit",
+            CrifProductClass::Equity => "Equity",
+            CrifProductClass::Commodity => "Commodity",
+        }
+    }
+}
+
+impl fmt::Display for CrifProductClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for CrifProductClass {
+    type Err = RegulatoryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [
+            CrifProductClass::RatesFx,
+            CrifProductClass::Credit,
+            CrifProductClass::Equity,
+            CrifProductClass::Commodity,
+        ]
+        .into_iter()
+        .find(|class| class.code().eq_ignore_ascii_case(s))
+        .ok_or_else(|| RegulatoryError::InvalidSensitivity(format!("product class '{}'", s)))
+    }
+}
+
+/// SIMM risk type.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CrifRiskType {
+    /// Interest rate curve delta
+    IrCurve,
+    /// Inflation delta
+    Inflation,
+    /// Cross-currency basis delta
+    XCcyBasis,
+    /// Interest rate volatility
+    IrVol,
+    /// Inflation volatility
+    InflationVol,
+    /// Qualifying credit spread delta
+    CreditQ,
+    /// Non-qualifying credit spread delta
+    CreditNonQ,
+    /// Base correlation
+    BaseCorr,
+    /// Qualifying credit volatility
+    CreditVol,
+    /// Non-qualifying credit volatility
+    CreditVolNonQ,
+    /// Equity delta
+    Equity,
+    /// Equity volatility
+    EquityVol,
+    /// Commodity delta
+    Commodity,
+    /// Commodity volatility
+    CommodityVol,
+    /// FX delta
+    Fx,
+    /// FX volatility
+    FxVol,
+    /// Any other row type, by its code
+    Other(String),
+}
+
+impl CrifRiskType {
+    const KNOWN: [CrifRiskType; 16] = [
+        CrifRiskType::IrCurve,
+        CrifRiskType::Inflation,
+        CrifRiskType::XCcyBasis,
+        CrifRiskType::IrVol,
+        CrifRiskType::InflationVol,
+        CrifRiskType::CreditQ,
+        CrifRiskType::CreditNonQ,
+        CrifRiskType::BaseCorr,
+        CrifRiskType::CreditVol,
+        CrifRiskType::CreditVolNonQ,
+        CrifRiskType::Equity,
+        CrifRiskType::EquityVol,
+        CrifRiskType::Commodity,
+        CrifRiskType::CommodityVol,
+        CrifRiskType::Fx,
+        CrifRiskType::FxVol,
+    ];
+
+    /// Returns the CRIF code.
+    pub fn code(&self) -> &str {
+        match self {
+            CrifRiskType::IrCurve => "Risk_IRCurve",
+            CrifRiskType::Inflation => "Risk_Inflation",
+            CrifRiskType::XCcyBasis => "Risk_XCcyBasis",
+            CrifRiskType::IrVol => "Risk_IRVol",
+            CrifRiskType::InflationVol => "Risk_InflationVol",
+            CrifRiskType::CreditQ => "Risk_CreditQ",
+            CrifRiskType::CreditNonQ => "Risk_CreditNonQ",
+            CrifRiskType::BaseCorr => "Risk_BaseCorr",
+            CrifRiskType::CreditVol => "Risk_CreditVol",
+            CrifRiskType::CreditVolNonQ => "Risk_CreditVolNonQ",
+            CrifRiskType::Equity => "Risk_Equity",
+            CrifRiskType::EquityVol => "Risk_EquityVol",
+            CrifRiskType::Commodity => "Risk_Commodity",
+            CrifRiskType::CommodityVol => "Risk_CommodityVol",
+            CrifRiskType::Fx => "Risk_FX",
+            CrifRiskType::FxVol => "Risk_FXVol",
+            CrifRiskType::Other(code) => code,
+        }
+    }
+
+    /// Returns true for volatility (vega) risk types.
+    pub fn is_vega(&self) -> bool {
+        matches!(
+            self,
+            CrifRiskType::IrVol
+                | CrifRiskType::InflationVol
+                | CrifRiskType::CreditVol
+                | CrifRiskType::CreditVolNonQ
+                | CrifRiskType::EquityVol
+                | CrifRiskType::CommodityVol
+                | CrifRiskType::FxVol
+        )
+    }
+
+    /// Parses a CRIF code, keeping unknown codes as [`CrifRiskType::Other`].
+    pub fn parse(code: &str) -> Self {
+        Self::KNOWN
+            .into_iter()
+            .find(|risk_type| risk_type.code().eq_ignore_ascii_case(code))
+            .unwrap_or_else(|| CrifRiskType::Other(code.to_string()))
+    }
+}
+
+impl fmt::Display for CrifRiskType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// One CRIF row.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CrifRecord {
+    /// Trade identifier (empty for portfolio-level rows)
+    pub trade_id: String,
+    /// Portfolio or netting set identifier
+    pub portfolio_id: Option<String>,
+    /// Product class
+    pub product_class: CrifProductClass,
+    /// Risk type
+    pub risk_type: CrifRiskType,
+    /// Currency, issuer or underlying
+    pub qualifier: String,
+    /// SIMM bucket
+    pub bucket: Option<String>,
+    /// Tenor or expiry
+    pub label1: Option<String>,
+    /// Sub-curve or payment frequency
+    pub label2: Option<String>,
+    /// Sensitivity in `amount_currency`
+    pub amount: f64,
+    /// ISO 4217 code of `amount`
+    pub amount_currency: String,
+    /// Sensitivity in USD
+    pub amount_usd: f64,
+}
+
+impl CrifRecord {
+    /// Creates a record with the amount already in USD.
+    pub fn new(
+        trade_id: impl Into<String>,
+        product_class: CrifProductClass,
+        risk_type: CrifRiskType,
+        qualifier: impl Into<String>,
+        amount_usd: f64,
+    ) -> Self {
+        Self {
+            trade_id: trade_id.into(),
+            portfolio_id: None,
+            product_class,
+            risk_type,
+            qualifier: qualifier.into(),
+            bucket: None,
+            label1: None,
+            label2: None,
+            amount: amount_usd,
+            amount_currency: "USD".to_string(),
+            amount_usd,
+        }
+    }
+
+    /// Sets the portfolio identifier.
+    pub fn with_portfolio(mut self, portfolio_id: impl Into<String>) -> Self {
+        self.portfolio_id = Some(portfolio_id.into());
+        self
+    }
+
+    /// Sets the SIMM bucket.
+    pub fn with_bucket(mut self, bucket: impl Into<String>) -> Self {
+        self.bucket = Some(bucket.into());
+        self
+    }
+
+    /// Sets the first label.
+    pub fn with_label1(mut self, label1: impl Into<String>) -> Self {
+        self.label1 = Some(label1.into());
+        self
+    }
+
+    /// Sets the second label.
+    pub fn with_label2(mut self, label2: impl Into<String>) -> Self {
+        self.label2 = Some(label2.into());
+        self
+    }
+
+    /// Sets the amount in its original currency.
+    pub fn with_amount(mut self, amount: f64, currency: impl Into<String>) -> Self {
+        self.amount = amount;
+        self.amount_currency = currency.into();
+        self
+    }
+
+    /// Risk factor key: every field except the trade, portfolio and amounts.
+    fn risk_key(&self) -> (CrifProductClass, CrifRiskType, String, [String; 3]) {
+        let label = |field: &Option<String>| field.clone().unwrap_or_default();
+        (
+            self.product_class,
+            self.risk_type.clone(),
+            self.qualifier.clone(),
+            [
+                label(&self.bucket),
+                label(&self.label1),
+                label(&self.label2),
+            ],
+        )
+    }
+}
+
+/// A set of CRIF records.
+///
+/// # Examples
+///
+/// ```
+/// use pricer_risk::regulatory::{Crif, CrifProductClass, CrifRecord, CrifRiskType};
+///
+/// let crif = Crif::new()
+///     .with_record(
+///         CrifRecord::new("T1", CrifProductClass::RatesFx, CrifRiskType::IrCurve, "USD", 1200.0)
+///             .with_bucket("1")
+///             .with_label1("5y")
+///             .with_label2("OIS"),
+///     )
+///     .with_record(CrifRecord::new(
+///         "T2",
+///         CrifProductClass::RatesFx,
+///         CrifRiskType::Fx,
+///         "EUR",
+///         -350.0,
+///     ));
+///
+/// let text = crif.render();
+/// assert!(text.starts_with("TradeID\tPortfolioID\tProductClass"));
+/// assert_eq!(Crif::parse(&text).unwrap(), crif);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Crif {
+    records: Vec<CrifRecord>,
+}
+
+impl Crif {
+    /// Creates an empty CRIF.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a record.
+    pub fn with_record(mut self, record: CrifRecord) -> Self {
+        self.records.push(record);
+        self
+    }
+
+    /// Adds a record in place.
+    pub fn push(&mut self, record: CrifRecord) {
+        self.records.push(record);
+    }
+
+    /// Returns the records.
+    pub fn records(&self) -> &[CrifRecord] {
+        &self.records
+    }
+
+    /// Iterates over the records.
+    pub fn iter(&self) -> impl Iterator<Item = &CrifRecord> {
+        self.records.iter()
+    }
+
+    /// Number of records.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns true if there are no records.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Converts internally computed Greeks of one trade to CRIF records.
+    ///
+    /// `classify` maps a sensitivity to its product class, risk type and
+    /// qualifier, returning `None` for sensitivities SIMM does not use.
+    /// The amount is the sensitivity value as quoted, so `classify` should
+    /// only accept sensitivities already in SIMM's convention (per 1bp for
+    /// rates and credit). The sensitivity bucket becomes `Label1` and
+    /// `usd_rate` converts the Greeks' currency to USD.
+    pub fn from_greeks<F>(
+        trade_id: &str,
+        greeks: &StructuredGreeks,
+        usd_rate: f64,
+        classify: F,
+    ) -> Self
+    where
+        F: Fn(&Sensitivity) -> Option<(CrifProductClass, CrifRiskType, String)>,
+    {
+        let records = greeks
+            .sensitivities
+            .iter()
+            .filter_map(|sensitivity| {
+                let (product_class, risk_type, qualifier) = classify(sensitivity)?;
+                let mut record = CrifRecord::new(
+                    trade_id,
+                    product_class,
+                    risk_type,
+                    qualifier,
+                    sensitivity.value * usd_rate,
+                )
+                .with_amount(sensitivity.value, greeks.currency.clone());
+                record.label1 = sensitivity.bucket.clone();
+                Some(record)
+            })
+            .collect();
+        Self { records }
+    }
+
+    /// Nets records on the same risk factor across trades.
+    ///
+    /// The result has one USD record per product class, risk type,
+    /// qualifier, bucket and labels, with no trade or portfolio, ordered by
+    /// that key. This is the aggregation SIMM applies before weighting.
+    pub fn netted(&self) -> Self {
+        let mut netted: BTreeMap<_, CrifRecord> = BTreeMap::new();
+        for record in &self.records {
+            netted
+                .entry(record.risk_key())
+                .and_modify(|r| {
+                    r.amount_usd += record.amount_usd;
+                    r.amount = r.amount_usd;
+                })
+                .or_insert_with(|| CrifRecord {
+                    trade_id: String::new(),
+                    portfolio_id: None,
+                    amount: record.amount_usd,
+                    amount_currency: "USD".to_string(),
+                    ..record.clone()
+                });
+        }
+        Self {
+            records: netted.into_values().collect(),
+        }
+    }
+
+    /// Parses tab or comma separated CRIF text with a header row.
+    ///
+    /// An empty `Amount` defaults to `AmountUSD` in USD. Blank lines are
+    /// skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RegulatoryError::InvalidCrif` naming the line for a missing
+    /// required column, an unknown product class or an invalid amount.
+    pub fn parse(text: &str) -> RegulatoryResult<Self> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty());
+        let Some((_, header)) = lines.next() else {
+            return Ok(Self::new());
+        };
+        let delimiter = if header.contains('\t') { '\t' } else { ',' };
+        let header = split(header, delimiter);
+        let column = |name: &str| header.iter().position(|h| h.eq_ignore_ascii_case(name));
+        let required = |name: &str| {
+            column(name).ok_or_else(|| RegulatoryError::InvalidCrif {
+                line: 1,
+                message: format!("missing column {}", name),
+            })
+        };
+        let product_class = required("ProductClass")?;
+        let risk_type = required("RiskType")?;
+        let qualifier = required("Qualifier")?;
+        let amount_usd = required("AmountUSD")?;
+        let trade_id = column("TradeID");
+        let portfolio_id = column("PortfolioID");
+        let bucket = column("Bucket");
+        let label1 = column("Label1");
+        let label2 = column("Label2");
+        let amount = column("Amount");
+        let amount_currency = column("AmountCurrency");
+
+        let mut crif = Self::new();
+        for (idx, line) in lines {
+            let line_no = idx + 1;
+            let fields = split(line, delimiter);
+            let field = |col: Option<usize>| {
+                col.and_then(|c| fields.get(c))
+                    .map(|f| f.as_str())
+                    .filter(|f| !f.is_empty())
+            };
+            let invalid = |message: String| RegulatoryError::InvalidCrif {
+                line: line_no,
+                message,
+            };
+            let number = |col: Option<usize>, name: &str| -> RegulatoryResult<Option<f64>> {
+                field(col)
+                    .map(|value| {
+                        value
+                            .parse::<f64>()
+                            .ok()
+                            .filter(|v| v.is_finite())
+                            .ok_or_else(|| invalid(format!("invalid {} '{}'", name, value)))
+                    })
+                    .transpose()
+            };
+
+            let usd = number(Some(amount_usd), "AmountUSD")?
+                .ok_or_else(|| invalid("missing AmountUSD".to_string()))?;
+            let mut record = CrifRecord::new(
+                field(trade_id).unwrap_or_default(),
+                field(Some(product_class))
+                    .unwrap_or_default()
+                    .parse()
+                    .map_err(|e: RegulatoryError| invalid(e.to_string()))?,
+                CrifRiskType::parse(
+                    field(Some(risk_type))
+                        .ok_or_else(|| invalid("missing RiskType".to_string()))?,
+                ),
+                field(Some(qualifier)).unwrap_or_default(),
+                usd,
+            );
+            record.portfolio_id = field(portfolio_id).map(str::to_string);
+            record.bucket = field(bucket).map(str::to_string);
+            record.label1 = field(label1).map(str::to_string);
+            record.label2 = field(label2).map(str::to_string);
+            if let Some(value) = number(amount, "Amount")? {
+                record.amount = value;
+                record.amount_currency = field(amount_currency).unwrap_or("USD").to_string();
+            }
+            crif.push(record);
+        }
+        Ok(crif)
+    }
+
+    /// Renders the records as tab separated CRIF with a header row.
+    pub fn render(&self) -> String {
+        let mut out = COLUMNS.join("\t");
+        out.push('\n');
+        for record in &self.records {
+            let optional = |field: &Option<String>| field.as_deref().unwrap_or("").to_string();
+            let fields = [
+                record.trade_id.clone(),
+                optional(&record.portfolio_id),
+                record.product_class.code().to_string(),
+                record.risk_type.code().to_string(),
+                record.qualifier.clone(),
+                optional(&record.bucket),
+                optional(&record.label1),
+                optional(&record.label2),
+                record.amount.to_string(),
+                record.amount_currency.clone(),
+                record.amount_usd.to_string(),
+            ];
+            out.push_str(&fields.join("\t"));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Split a line on `delimiter`, honouring double-quoted fields, and trim.
+fn split(line: &str, delimiter: char) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            c if c == delimiter && !quoted => {
+                fields.push(field.trim().to_string());
+                field.clear();
+            }
+            c => field.push(c),
+        }
+    }
+    fields.push(field.trim().to_string());
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pricer_pricing::greeks::BumpSpec;
+    use pricer_pricing::Greek;
+
+    #[test]
+    fn test_parse_comma_separated_with_extra_columns() {
+        let text = "\
+tradeid,IMModel,ProductClass,RiskType,Qualifier,Bucket,Label1,Label2,Amount,AmountCurrency,AmountUSD
+T1,SIMM,RatesFX,Risk_IRCurve,EUR,1,10y,Libor3m,1000,EUR,1100
+
+T2,SIMM,Credit,Param_AddOnFixedAmount,,,,,,,5000
+";
+        let crif = Crif::parse(text).unwrap();
+        assert_eq!(crif.len(), 2);
+
+        let ir = &crif.records()[0];
+        assert_eq!(ir.risk_type, CrifRiskType::IrCurve);
+        assert_eq!(ir.label1.as_deref(), Some("10y"));
+        assert_eq!((ir.amount, ir.amount_currency.as_str()), (1000.0, "EUR"));
+        assert_eq!(ir.amount_usd, 1100.0);
+
+        let param = &crif.records()[1];
+        assert_eq!(
+            param.risk_type,
+            CrifRiskType::Other("Param_AddOnFixedAmount".to_string())
+        );
+        assert_eq!(
+            (param.amount, param.amount_currency.as_str()),
+            (5000.0, "USD")
+        );
+        assert!(crif.render().contains("Param_AddOnFixedAmount"));
+    }
+
+    #[test]
+    fn test_parse_errors_name_the_line() {
+        assert_eq!(
+            Crif::parse("RiskType\tQualifier\tAmountUSD\n"),
+            Err(RegulatoryError::InvalidCrif {
+                line: 1,
+                message: "missing column ProductClass".to_string()
+            })
+        );
+
+        let text = "ProductClass\tRiskType\tQualifier\tAmountUSD\n\
+                    RatesFX\tRisk_FX\tEUR\t1\n\
+                    Rates\tRisk_FX\tEUR\t1\n";
+        assert!(matches!(
+            Crif::parse(text),
+            Err(RegulatoryError::InvalidCrif { line: 3, .. })
+        ));
+
+        let text = "ProductClass\tRiskType\tQualifier\tAmountUSD\nRatesFX\tRisk_FX\tEUR\tNaN\n";
+        assert!(matches!(
+            Crif::parse(text),
+            Err(RegulatoryError::InvalidCrif { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_netted_sums_across_trades() {
+        let record = |trade: &str, tenor: &str, usd: f64| {
+            CrifRecord::new(
+                trade,
+                CrifProductClass::RatesFx,
+                CrifRiskType::IrCurve,
+                "USD",
+                usd,
+            )
+            .with_label1(tenor)
+        };
+        let crif = Crif::new()
+            .with_record(record("T1", "5y", 100.0))
+            .with_record(record("T2", "5y", -40.0).with_portfolio("P1"))
+            .with_record(record("T2", "10y", 25.0));
+
+        let netted = crif.netted();
+        assert_eq!(netted.len(), 2);
+        assert_eq!(netted.records()[0].label1.as_deref(), Some("10y"));
+        assert_eq!(netted.records()[1].amount_usd, 60.0);
+        assert!(netted
+            .iter()
+            .all(|r| r.trade_id.is_empty() && r.portfolio_id.is_none()));
+    }
+
+    #[test]
+    fn test_from_greeks_classifies_and_converts() {
+        let greeks = StructuredGreeks::new(10.0, 0.0, "EUR")
+            .with_sensitivity(
+                Sensitivity::new(Greek::Rho, "EUR.rate", 2.0, BumpSpec::Analytic).with_bucket("5y"),
+            )
+            .with_sensitivity(Sensitivity::new(
+                Greek::Gamma,
+                "spot",
+                0.1,
+                BumpSpec::Analytic,
+            ));
+
+        let crif = Crif::from_greeks("T1", &greeks, 1.1, |s| {
+            (s.greek == Greek::Rho).then(|| {
+                (
+                    CrifProductClass::RatesFx,
+                    CrifRiskType::IrCurve,
+                    "EUR".to_string(),
+                )
+            })
+        });
+        assert_eq!(crif.len(), 1);
+        let record = &crif.records()[0];
+        assert_eq!(record.label1.as_deref(), Some("5y"));
+        assert_eq!(
+            (record.amount, record.amount_currency.as_str()),
+            (2.0, "EUR")
+        );
+        assert!((record.amount_usd - 2.2).abs() < 1e-12);
+    }
+}
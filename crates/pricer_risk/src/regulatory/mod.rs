@@ -0,0 +1,291 @@
+//! Regulatory CVA capital under the Basel III CVA risk framework (MAR50).
+//!
+//! - [`BaCvaCalculator`]: reduced basic approach (BA-CVA) from netting-set
+//!   EADs and effective maturities, with supervisory risk weights by
+//!   counterparty sector and credit quality
+//! - [`SaCvaCalculator`]: standardised approach (SA-CVA) from delta
+//!   sensitivities of regulatory CVA to interest rates, FX and counterparty
+//!   credit spreads, such as the bucketed spread deltas of the AD pipeline
+//!   ([`CvaSpreadDeltas`](crate::hedging::CvaSpreadDeltas))
+//!
+//! Both return a [`CvaCapital`] holding the total capital and the
+//! standalone capital of each counterparty. The total is below the sum of
+//! the standalone numbers by the diversification between counterparties.
+//!
+//! [`Crif`] reads and writes sensitivities in ISDA's Common Risk
+//! Interchange Format, importing externally computed sensitivities and
+//! exporting internal ones to SIMM initial margin utilities.
+//!
+//! Eligible CVA hedges are not recognised, which gives the unhedged
+//! capital. SA-CVA covers delta risk only; vega and the reference credit,
+//! equity and commodity risk classes are not modelled.
+//!
+//! # Examples
+//!
+//! ```
+//! use pricer_risk::portfolio::{CounterpartyId, CreditRating, NettingSetId};
+//! use pricer_risk::regulatory::{BaCvaCalculator, BaCvaCounterparty, CvaSector};
+//!
+//! let bank = BaCvaCounterparty::new(
+//!     CounterpartyId::new("BANK"),
+//!     CvaSector::Financial,
+//!     CreditRating::A,
+//! )
+//! .with_netting_set(NettingSetId::new("NS1"), 10_000_000.0, 3.0);
+//! let utility = BaCvaCounterparty::new(
+//!     CounterpartyId::new("UTIL"),
+//!     CvaSector::HealthCare,
+//!     CreditRating::BB,
+//! )
+//! .with_netting_set(NettingSetId::new("NS2"), 5_000_000.0, 5.0);
+//!
+//! let capital = BaCvaCalculator::default().calculate(&[bank, utility]).unwrap();
+//! assert!(capital.total < capital.standalone_sum());
+//! println!("{}", capital);
+//! ```
+
+mod ba_cva;
+mod crif;
+mod sa_cva;
+
+pub use ba_cva::{BaCvaCalculator, BaCvaCounterparty, BaCvaExposure};
+pub use crif::{Crif, CrifProductClass, CrifRecord, CrifRiskType};
+pub use sa_cva::{SaCvaCalculator, SaCvaRiskClass, SaCvaRiskFactor, SaCvaSensitivity};
+
+use std::fmt;
+
+use thiserror::Error;
+
+use crate::portfolio::{CounterpartyId, CreditRating};
+
+/// Errors from regulatory capital calculations.
+#[derive(Clone, Debug, Error, PartialEq)]
+pub enum RegulatoryError {
+    /// An exposure or maturity is negative or not finite, or a counterparty
+    /// appears twice.
+    #[error("Invalid exposure: {0}")]
+    InvalidExposure(String),
+
+    /// A sensitivity is not finite or refers to an unsupported risk factor.
+    #[error("Invalid sensitivity: {0}")]
+    InvalidSensitivity(String),
+
+    /// A CRIF file is malformed.
+    #[error("Invalid CRIF at line {line}: {message}")]
+    InvalidCrif {
+        /// One-based line number
+        line: usize,
+        /// What is wrong
+        message: String,
+    },
+
+    /// A calculator parameter is out of range.
+    #[error("Invalid parameter: {0}")]
+    InvalidParameter(String),
+}
+
+/// Result type for regulatory capital calculations.
+pub type RegulatoryResult<T> = Result<T, RegulatoryError>;
+
+/// Counterparty sector for supervisory CVA risk weights.
+///
+/// The sectors are the buckets of MAR50.16 (BA-CVA) and MAR50.63
+/// (SA-CVA counterparty credit spread), which share risk weights.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CvaSector {
+    /// Sovereigns, central banks and multilateral development banks
+    Sovereign,
+    /// Local governments, government-backed non-financials, education and
+    /// public administration
+    LocalGovernment,
+    /// Financials, including government-backed financials
+    Financial,
+    /// Basic materials, energy, industrials, agriculture, manufacturing and
+    /// mining
+    BasicMaterials,
+    /// Consumer goods and services, transportation and storage,
+    /// administrative and support services
+    ConsumerGoods,
+    /// Technology and telecommunications
+    Technology,
+    /// Health care, utilities, professional and technical activities
+    HealthCare,
+    /// Other sector
+    Other,
+}
+
+impl CvaSector {
+    /// Returns the supervisory risk weight for a counterparty of the given
+    /// rating (investment grade or high yield and non-rated).
+    pub fn risk_weight(self, rating: CreditRating) -> f64 {
+        let (investment_grade, high_yield) = match self {
+            CvaSector::Sovereign => (0.005, 0.020),
+            CvaSector::LocalGovernment => (0.010, 0.040),
+            CvaSector::Financial => (0.050, 0.120),
+            CvaSector::BasicMaterials => (0.030, 0.070),
+            CvaSector::ConsumerGoods => (0.030, 0.085),
+            CvaSector::Technology => (0.020, 0.055),
+            CvaSector::HealthCare => (0.015, 0.050),
+            CvaSector::Other => (0.050, 0.120),
+        };
+        if rating.is_investment_grade() {
+            investment_grade
+        } else {
+            high_yield
+        }
+    }
+}
+
+/// Standalone capital of one counterparty.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CounterpartyCapital {
+    /// Counterparty
+    pub counterparty_id: CounterpartyId,
+    /// Capital if this were the only counterparty
+    pub standalone: f64,
+}
+
+/// Capital of one SA-CVA risk class.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RiskClassCapital {
+    /// Risk class
+    pub risk_class: SaCvaRiskClass,
+    /// Delta capital of the risk class
+    pub capital: f64,
+}
+
+/// CVA capital approach.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CvaCapitalMethod {
+    /// Basic approach, reduced version (MAR50.14)
+    BaCva,
+    /// Standardised approach, delta (MAR50.42)
+    SaCva,
+}
+
+impl fmt::Display for CvaCapitalMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            CvaCapitalMethod::BaCva => "BA-CVA",
+            CvaCapitalMethod::SaCva => "SA-CVA",
+        })
+    }
+}
+
+/// CVA capital of a portfolio.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CvaCapital {
+    /// Approach used
+    pub method: CvaCapitalMethod,
+    /// Total capital requirement
+    pub total: f64,
+    /// Standalone capital by counterparty, sorted by identifier
+    pub counterparties: Vec<CounterpartyCapital>,
+    /// Capital by risk class (SA-CVA only)
+    pub risk_classes: Vec<RiskClassCapital>,
+}
+
+impl CvaCapital {
+    /// Returns the standalone capital of a counterparty.
+    pub fn standalone(&self, counterparty_id: &CounterpartyId) -> Option<f64> {
+        self.counterparties
+            .iter()
+            .find(|c| &c.counterparty_id == counterparty_id)
+            .map(|c| c.standalone)
+    }
+
+    /// Returns the sum of the standalone capitals.
+    pub fn standalone_sum(&self) -> f64 {
+        self.counterparties.iter().map(|c| c.standalone).sum()
+    }
+
+    /// Returns the capital saved by diversification between counterparties.
+    pub fn diversification_benefit(&self) -> f64 {
+        self.standalone_sum() - self.total
+    }
+}
+
+impl fmt::Display for CvaCapital {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .counterparties
+            .iter()
+            .map(|c| c.counterparty_id.as_str().len())
+            .max()
+            .unwrap_or(0)
+            .max("counterparty".len());
+        writeln!(f, "{:<width$}  {:>16}", "counterparty", "standalone")?;
+        for capital in &self.counterparties {
+            writeln!(
+                f,
+                "{:<width$}  {:>16.0}",
+                capital.counterparty_id.as_str(),
+                capital.standalone
+            )?;
+        }
+        for capital in &self.risk_classes {
+            writeln!(
+                f,
+                "{:<width$}  {:>16.0}",
+                capital.risk_class.to_string(),
+                capital.capital
+            )?;
+        }
+        write!(
+            f,
+            "{:<width$}  {:>16.0}",
+            self.method.to_string(),
+            self.total
+        )
+    }
+}
+
+/// Sorts standalone capitals by counterparty identifier.
+fn sorted(mut counterparties: Vec<CounterpartyCapital>) -> Vec<CounterpartyCapital> {
+    counterparties.sort_by(|a, b| a.counterparty_id.as_str().cmp(b.counterparty_id.as_str()));
+    counterparties
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sector_risk_weights() {
+        assert_eq!(CvaSector::Sovereign.risk_weight(CreditRating::AAA), 0.005);
+        assert_eq!(CvaSector::Sovereign.risk_weight(CreditRating::BB), 0.02);
+        assert_eq!(CvaSector::Financial.risk_weight(CreditRating::BBB), 0.05);
+        assert_eq!(CvaSector::Financial.risk_weight(CreditRating::D), 0.12);
+    }
+
+    #[test]
+    fn test_capital_summary() {
+        let capital = CvaCapital {
+            method: CvaCapitalMethod::BaCva,
+            total: 150.0,
+            counterparties: vec![
+                CounterpartyCapital {
+                    counterparty_id: CounterpartyId::new("A"),
+                    standalone: 100.0,
+                },
+                CounterpartyCapital {
+                    counterparty_id: CounterpartyId::new("B"),
+                    standalone: 80.0,
+                },
+            ],
+            risk_classes: Vec::new(),
+        };
+        assert_eq!(capital.standalone(&CounterpartyId::new("B")), Some(80.0));
+        assert_eq!(capital.standalone(&CounterpartyId::new("C")), None);
+        assert_eq!(capital.diversification_benefit(), 30.0);
+        let table = capital.to_string();
+        assert!(table.starts_with("counterparty"));
+        let last = table.lines().last().unwrap();
+        assert!(last.starts_with("BA-CVA") && last.ends_with(" 150"));
+    }
+}
@@ -0,0 +1,760 @@
+//! Standardised approach to CVA capital, delta risk (MAR50.42-MAR50.66).
+//!
+//! A sensitivity `s_k` is the change in regulatory CVA per unit move of
+//! risk factor `k`: per unit of rate or spread for a 1bp bump, and per unit
+//! of relative move for a 1% FX bump. Within each risk class:
+//!
+//! ```text
+//! WS_k = RW_k · s_k
+//! K_b  = √(Σ_k Σ_l ρ_kl WS_k WS_l)
+//! S_b  = max(min(Σ_k WS_k, K_b), -K_b)
+//! K    = m_CVA · √(Σ_b K_b² + Σ_b Σ_c≠b γ_bc S_b S_c)
+//! ```
+//!
+//! and the capital is the sum over risk classes. Sensitivities to the same
+//! risk factor are netted across counterparties before weighting.
+//!
+//! | Risk class | Bucket | Factors | Cross-bucket `γ` |
+//! |------------|--------|---------|------------------|
+//! | Interest rate | currency | 1y, 2y, 5y, 10y, 30y for USD, EUR, GBP, JPY and the reporting currency; one parallel factor otherwise | 0.5 |
+//! | FX | currency | spot against the reporting currency | 0.6 |
+//! | Counterparty credit spread | [`CvaSector`] | counterparty and tenor | sector table |
+
+use std::fmt;
+
+use pricer_core::types::Currency;
+
+use super::{
+    sorted, CounterpartyCapital, CvaCapital, CvaCapitalMethod, CvaSector, RegulatoryError,
+    RegulatoryResult, RiskClassCapital,
+};
+use crate::hedging::CvaSpreadDeltas;
+use crate::portfolio::{CounterpartyId, CreditRating};
+
+/// Interest rate tenors with their own risk factor, in years.
+const IR_TENORS: [f64; 5] = [1.0, 2.0, 5.0, 10.0, 30.0];
+/// Interest rate risk weights by tenor for the specified currencies.
+const IR_RISK_WEIGHTS: [f64; 5] = [0.0111, 0.0093, 0.0074, 0.0074, 0.0074];
+/// Interest rate risk weight of the parallel factor of other currencies.
+const IR_OTHER_RISK_WEIGHT: f64 = 0.0158;
+/// Correlations between interest rate tenors.
+const IR_TENOR_CORRELATION: [[f64; 5]; 5] = [
+    [1.00, 0.91, 0.72, 0.55, 0.31],
+    [0.91, 1.00, 0.87, 0.72, 0.45],
+    [0.72, 0.87, 1.00, 0.91, 0.68],
+    [0.55, 0.72, 0.91, 1.00, 0.83],
+    [0.31, 0.45, 0.68, 0.83, 1.00],
+];
+/// Correlation between interest rate buckets.
+const IR_CROSS_BUCKET: f64 = 0.5;
+/// FX risk weight.
+const FX_RISK_WEIGHT: f64 = 0.11;
+/// Correlation between FX buckets.
+const FX_CROSS_BUCKET: f64 = 0.6;
+/// Counterparty credit spread correlation between different tenors.
+const CREDIT_TENOR_CORRELATION: f64 = 0.9;
+/// Counterparty credit spread correlation between unrelated names.
+const CREDIT_NAME_CORRELATION: f64 = 0.5;
+/// Counterparty credit spread correlation between investment grade and
+/// high yield.
+const CREDIT_QUALITY_CORRELATION: f64 = 0.8;
+/// Correlations between counterparty credit spread sector buckets, with
+/// sovereigns and local governments sharing bucket 1.
+const CREDIT_CROSS_BUCKET: [[f64; 7]; 7] = [
+    [1.00, 0.10, 0.20, 0.25, 0.20, 0.15, 0.00],
+    [0.10, 1.00, 0.05, 0.15, 0.20, 0.05, 0.00],
+    [0.20, 0.05, 1.00, 0.20, 0.25, 0.05, 0.00],
+    [0.25, 0.15, 0.20, 1.00, 0.25, 0.05, 0.00],
+    [0.20, 0.20, 0.25, 0.25, 1.00, 0.05, 0.00],
+    [0.15, 0.05, 0.05, 0.05, 0.05, 1.00, 0.00],
+    [0.00, 0.00, 0.00, 0.00, 0.00, 0.00, 1.00],
+];
+
+/// SA-CVA risk class.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SaCvaRiskClass {
+    /// Interest rate delta
+    InterestRate,
+    /// FX delta
+    ForeignExchange,
+    /// Counterparty credit spread delta
+    CounterpartyCreditSpread,
+}
+
+impl SaCvaRiskClass {
+    /// All risk classes in reporting order.
+    pub const ALL: [SaCvaRiskClass; 3] = [
+        SaCvaRiskClass::InterestRate,
+        SaCvaRiskClass::ForeignExchange,
+        SaCvaRiskClass::CounterpartyCreditSpread,
+    ];
+}
+
+impl fmt::Display for SaCvaRiskClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            SaCvaRiskClass::InterestRate => "IR delta",
+            SaCvaRiskClass::ForeignExchange => "FX delta",
+            SaCvaRiskClass::CounterpartyCreditSpread => "CCS delta",
+        })
+    }
+}
+
+/// Risk factor of a CVA sensitivity.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SaCvaRiskFactor {
+    /// Risk-free curve of a currency at a tenor in years
+    InterestRate {
+        /// Curve currency
+        currency: Currency,
+        /// Tenor in years
+        tenor: f64,
+    },
+    /// Spot rate of a currency against the reporting currency
+    Fx {
+        /// Foreign currency
+        currency: Currency,
+    },
+    /// Credit spread of the counterparty itself at a tenor in years
+    CounterpartySpread {
+        /// Counterparty sector
+        sector: CvaSector,
+        /// Counterparty rating
+        rating: CreditRating,
+        /// Tenor in years
+        tenor: f64,
+    },
+}
+
+impl SaCvaRiskFactor {
+    /// Returns the risk class of the factor.
+    pub fn risk_class(&self) -> SaCvaRiskClass {
+        match self {
+            SaCvaRiskFactor::InterestRate { .. } => SaCvaRiskClass::InterestRate,
+            SaCvaRiskFactor::Fx { .. } => SaCvaRiskClass::ForeignExchange,
+            SaCvaRiskFactor::CounterpartySpread { .. } => SaCvaRiskClass::CounterpartyCreditSpread,
+        }
+    }
+}
+
+/// Delta of one counterparty's regulatory CVA to a risk factor.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SaCvaSensitivity {
+    /// Counterparty whose CVA is bumped
+    pub counterparty_id: CounterpartyId,
+    /// Risk factor
+    pub factor: SaCvaRiskFactor,
+    /// CVA change per unit move of the risk factor
+    pub sensitivity: f64,
+}
+
+impl SaCvaSensitivity {
+    /// Creates an interest rate sensitivity from the CVA change for a 1bp
+    /// rise of the curve at `tenor`.
+    pub fn interest_rate(
+        counterparty_id: CounterpartyId,
+        currency: Currency,
+        tenor: f64,
+        cva_per_bp: f64,
+    ) -> Self {
+        Self {
+            counterparty_id,
+            factor: SaCvaRiskFactor::InterestRate { currency, tenor },
+            sensitivity: cva_per_bp * 1e4,
+        }
+    }
+
+    /// Creates an FX sensitivity from the CVA change for a 1% rise of the
+    /// currency against the reporting currency.
+    pub fn fx(counterparty_id: CounterpartyId, currency: Currency, cva_per_percent: f64) -> Self {
+        Self {
+            counterparty_id,
+            factor: SaCvaRiskFactor::Fx { currency },
+            sensitivity: cva_per_percent * 1e2,
+        }
+    }
+
+    /// Creates a counterparty credit spread sensitivity from the CVA change
+    /// for a 1bp rise of the counterparty's spread at `tenor`.
+    pub fn counterparty_spread(
+        counterparty_id: CounterpartyId,
+        sector: CvaSector,
+        rating: CreditRating,
+        tenor: f64,
+        cva_per_bp: f64,
+    ) -> Self {
+        Self {
+            counterparty_id,
+            factor: SaCvaRiskFactor::CounterpartySpread {
+                sector,
+                rating,
+                tenor,
+            },
+            sensitivity: cva_per_bp * 1e4,
+        }
+    }
+
+    /// Converts bucketed CVA spread deltas into counterparty credit spread
+    /// sensitivities, one per bucket.
+    pub fn from_spread_deltas(
+        deltas: &CvaSpreadDeltas,
+        sector: CvaSector,
+        rating: CreditRating,
+    ) -> Vec<Self> {
+        deltas
+            .bucket_tenors()
+            .iter()
+            .zip(deltas.deltas())
+            .map(|(&tenor, &delta)| {
+                Self::counterparty_spread(
+                    deltas.counterparty_id().clone(),
+                    sector,
+                    rating,
+                    tenor,
+                    delta,
+                )
+            })
+            .collect()
+    }
+}
+
+/// SA-CVA delta capital calculator.
+///
+/// # Examples
+///
+/// ```
+/// use pricer_core::types::Currency;
+/// use pricer_risk::portfolio::{CounterpartyId, CreditRating};
+/// use pricer_risk::regulatory::{CvaSector, SaCvaCalculator, SaCvaSensitivity};
+///
+/// let cp = CounterpartyId::new("CP001");
+/// let sensitivities = vec![
+///     SaCvaSensitivity::interest_rate(cp.clone(), Currency::USD, 5.0, -120.0),
+///     SaCvaSensitivity::counterparty_spread(
+///         cp.clone(),
+///         CvaSector::Financial,
+///         CreditRating::A,
+///         5.0,
+///         450.0,
+///     ),
+/// ];
+///
+/// let capital = SaCvaCalculator::default().calculate(&sensitivities).unwrap();
+/// assert_eq!(capital.risk_classes.len(), 2);
+/// assert!(capital.total > 0.0);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SaCvaCalculator {
+    /// Reporting currency, which always has tenor-level rate factors
+    pub reporting_currency: Currency,
+    /// Aggregation multiplier `m_CVA`
+    pub multiplier: f64,
+}
+
+impl Default for SaCvaCalculator {
+    /// Returns USD reporting with `m_CVA = 1.25`.
+    fn default() -> Self {
+        Self {
+            reporting_currency: Currency::USD,
+            multiplier: 1.25,
+        }
+    }
+}
+
+impl SaCvaCalculator {
+    /// Sets the reporting currency.
+    pub fn with_reporting_currency(mut self, currency: Currency) -> Self {
+        self.reporting_currency = currency;
+        self
+    }
+
+    /// Sets the aggregation multiplier.
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Computes SA-CVA delta capital.
+    ///
+    /// # Errors
+    ///
+    /// Returns `RegulatoryError::InvalidParameter` for a multiplier below
+    /// one, and `RegulatoryError::InvalidSensitivity` for a non-finite
+    /// sensitivity, an interest rate tenor other than 1y, 2y, 5y, 10y or 30y
+    /// in a currency with tenor-level factors, or an FX sensitivity to the
+    /// reporting currency.
+    pub fn calculate(&self, sensitivities: &[SaCvaSensitivity]) -> RegulatoryResult<CvaCapital> {
+        if !(self.multiplier >= 1.0 && self.multiplier.is_finite()) {
+            return Err(RegulatoryError::InvalidParameter(format!(
+                "multiplier {} must be at least 1",
+                self.multiplier
+            )));
+        }
+        let weighted = sensitivities
+            .iter()
+            .map(|s| self.weight(s))
+            .collect::<RegulatoryResult<Vec<_>>>()?;
+
+        let risk_classes: Vec<RiskClassCapital> = SaCvaRiskClass::ALL
+            .iter()
+            .filter(|&&class| weighted.iter().any(|w| w.class == class))
+            .map(|&risk_class| RiskClassCapital {
+                risk_class,
+                capital: self.risk_class_capital(weighted.iter().filter(|w| w.class == risk_class)),
+            })
+            .collect();
+        let total = risk_classes.iter().map(|c| c.capital).sum();
+
+        let mut ids: Vec<&CounterpartyId> = Vec::new();
+        for s in sensitivities {
+            if !ids.contains(&&s.counterparty_id) {
+                ids.push(&s.counterparty_id);
+            }
+        }
+        let counterparties = ids
+            .into_iter()
+            .map(|id| CounterpartyCapital {
+                counterparty_id: id.clone(),
+                standalone: SaCvaRiskClass::ALL
+                    .iter()
+                    .map(|&class| {
+                        self.risk_class_capital(
+                            weighted
+                                .iter()
+                                .filter(|w| w.class == class && &w.counterparty_id == id),
+                        )
+                    })
+                    .sum(),
+            })
+            .collect();
+
+        Ok(CvaCapital {
+            method: CvaCapitalMethod::SaCva,
+            total,
+            counterparties: sorted(counterparties),
+            risk_classes,
+        })
+    }
+
+    /// Maps a sensitivity to its bucket, factor and weighted sensitivity.
+    fn weight(&self, s: &SaCvaSensitivity) -> RegulatoryResult<Weighted> {
+        if !s.sensitivity.is_finite() {
+            return Err(RegulatoryError::InvalidSensitivity(format!(
+                "sensitivity {} of counterparty {} must be finite",
+                s.sensitivity,
+                s.counterparty_id.as_str()
+            )));
+        }
+        let (bucket, factor, risk_weight) = match s.factor {
+            SaCvaRiskFactor::InterestRate { currency, tenor } => {
+                if self.has_tenor_factors(currency) {
+                    let k = IR_TENORS
+                        .iter()
+                        .position(|&t| (t - tenor).abs() < 1e-9)
+                        .ok_or_else(|| {
+                            RegulatoryError::InvalidSensitivity(format!(
+                                "{} rate tenor {}y is not one of 1y, 2y, 5y, 10y, 30y",
+                                currency, tenor
+                            ))
+                        })?;
+                    (
+                        Bucket::Currency(currency),
+                        Factor::Tenor(k),
+                        IR_RISK_WEIGHTS[k],
+                    )
+                } else {
+                    (
+                        Bucket::Currency(currency),
+                        Factor::Single,
+                        IR_OTHER_RISK_WEIGHT,
+                    )
+                }
+            }
+            SaCvaRiskFactor::Fx { currency } => {
+                if currency == self.reporting_currency {
+                    return Err(RegulatoryError::InvalidSensitivity(format!(
+                        "FX sensitivity to the reporting currency {}",
+                        currency
+                    )));
+                }
+                (Bucket::Currency(currency), Factor::Single, FX_RISK_WEIGHT)
+            }
+            SaCvaRiskFactor::CounterpartySpread {
+                sector,
+                rating,
+                tenor,
+            } => (
+                Bucket::Sector(credit_bucket(sector)),
+                Factor::Name {
+                    tenor,
+                    investment_grade: rating.is_investment_grade(),
+                },
+                sector.risk_weight(rating),
+            ),
+        };
+        Ok(Weighted {
+            class: s.factor.risk_class(),
+            counterparty_id: s.counterparty_id.clone(),
+            bucket,
+            factor,
+            value: risk_weight * s.sensitivity,
+        })
+    }
+
+    /// Returns whether a currency has tenor-level interest rate factors.
+    fn has_tenor_factors(&self, currency: Currency) -> bool {
+        currency == self.reporting_currency
+            || matches!(
+                currency,
+                Currency::USD | Currency::EUR | Currency::GBP | Currency::JPY
+            )
+    }
+
+    /// Aggregates the weighted sensitivities of one risk class.
+    fn risk_class_capital<'a>(&self, weighted: impl Iterator<Item = &'a Weighted>) -> f64 {
+        // Net identical risk factors across counterparties
+        let mut netted: Vec<Weighted> = Vec::new();
+        for w in weighted {
+            match netted.iter_mut().find(|n| n.same_factor(w)) {
+                Some(n) => n.value += w.value,
+                None => netted.push(w.clone()),
+            }
+        }
+        let Some(class) = netted.first().map(|w| w.class) else {
+            return 0.0;
+        };
+
+        let mut buckets: Vec<Bucket> = Vec::new();
+        for w in &netted {
+            if !buckets.contains(&w.bucket) {
+                buckets.push(w.bucket);
+            }
+        }
+        let (k, s): (Vec<f64>, Vec<f64>) = buckets
+            .iter()
+            .map(|&bucket| {
+                let members: Vec<&Weighted> =
+                    netted.iter().filter(|w| w.bucket == bucket).collect();
+                let variance: f64 = members
+                    .iter()
+                    .flat_map(|a| {
+                        members
+                            .iter()
+                            .map(move |b| a.correlation(b) * a.value * b.value)
+                    })
+                    .sum();
+                let k_b = variance.max(0.0).sqrt();
+                let sum: f64 = members.iter().map(|w| w.value).sum();
+                (k_b, sum.clamp(-k_b, k_b))
+            })
+            .unzip();
+
+        let mut variance = 0.0;
+        for b in 0..buckets.len() {
+            variance += k[b] * k[b];
+            for c in 0..buckets.len() {
+                if b != c {
+                    variance += buckets[b].correlation(&buckets[c], class) * s[b] * s[c];
+                }
+            }
+        }
+        self.multiplier * variance.max(0.0).sqrt()
+    }
+}
+
+/// Bucket of a weighted sensitivity.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Bucket {
+    /// Interest rate or FX currency
+    Currency(Currency),
+    /// Counterparty credit spread sector bucket (index into the table)
+    Sector(usize),
+}
+
+impl Bucket {
+    /// Cross-bucket correlation `γ` within a risk class.
+    fn correlation(&self, other: &Bucket, class: SaCvaRiskClass) -> f64 {
+        match (class, self, other) {
+            (_, Bucket::Sector(b), Bucket::Sector(c)) => CREDIT_CROSS_BUCKET[*b][*c],
+            (SaCvaRiskClass::ForeignExchange, _, _) => FX_CROSS_BUCKET,
+            _ => IR_CROSS_BUCKET,
+        }
+    }
+}
+
+/// Risk factor within a bucket.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Factor {
+    /// The bucket's only factor
+    Single,
+    /// Interest rate tenor index
+    Tenor(usize),
+    /// Counterparty credit spread at a tenor
+    Name { tenor: f64, investment_grade: bool },
+}
+
+/// Weighted sensitivity `WS_k`.
+#[derive(Clone, Debug)]
+struct Weighted {
+    class: SaCvaRiskClass,
+    counterparty_id: CounterpartyId,
+    bucket: Bucket,
+    factor: Factor,
+    value: f64,
+}
+
+impl Weighted {
+    /// Returns whether two sensitivities are to the same risk factor.
+    ///
+    /// Rate and FX factors are market-wide; a credit spread factor belongs
+    /// to its counterparty.
+    fn same_factor(&self, other: &Weighted) -> bool {
+        self.class == other.class
+            && self.bucket == other.bucket
+            && self.factor == other.factor
+            && (self.class != SaCvaRiskClass::CounterpartyCreditSpread
+                || self.counterparty_id == other.counterparty_id)
+    }
+
+    /// Correlation `ρ_kl` between two factors of the same bucket.
+    fn correlation(&self, other: &Weighted) -> f64 {
+        match (self.factor, other.factor) {
+            (Factor::Tenor(k), Factor::Tenor(l)) => IR_TENOR_CORRELATION[k][l],
+            (
+                Factor::Name {
+                    tenor: t1,
+                    investment_grade: ig1,
+                },
+                Factor::Name {
+                    tenor: t2,
+                    investment_grade: ig2,
+                },
+            ) => {
+                let tenor = if (t1 - t2).abs() < 1e-9 {
+                    1.0
+                } else {
+                    CREDIT_TENOR_CORRELATION
+                };
+                let name = if self.counterparty_id == other.counterparty_id {
+                    1.0
+                } else {
+                    CREDIT_NAME_CORRELATION
+                };
+                let quality = if ig1 == ig2 {
+                    1.0
+                } else {
+                    CREDIT_QUALITY_CORRELATION
+                };
+                tenor * name * quality
+            }
+            _ => 1.0,
+        }
+    }
+}
+
+/// Index of a sector in the cross-bucket table.
+fn credit_bucket(sector: CvaSector) -> usize {
+    match sector {
+        CvaSector::Sovereign | CvaSector::LocalGovernment => 0,
+        CvaSector::Financial => 1,
+        CvaSector::BasicMaterials => 2,
+        CvaSector::ConsumerGoods => 3,
+        CvaSector::Technology => 4,
+        CvaSector::HealthCare => 5,
+        CvaSector::Other => 6,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn cp(id: &str) -> CounterpartyId {
+        CounterpartyId::new(id)
+    }
+
+    #[test]
+    fn test_single_factor_is_weighted_sensitivity() {
+        let capital = SaCvaCalculator::default()
+            .calculate(&[SaCvaSensitivity::interest_rate(
+                cp("A"),
+                Currency::USD,
+                5.0,
+                -100.0,
+            )])
+            .unwrap();
+        // |RW · s| · m_CVA
+        assert_relative_eq!(
+            capital.total,
+            1.25 * 0.0074 * 100.0 * 1e4,
+            max_relative = 1e-12
+        );
+        assert_eq!(
+            capital.risk_classes[0].risk_class,
+            SaCvaRiskClass::InterestRate
+        );
+        assert_relative_eq!(
+            capital.standalone_sum(),
+            capital.total,
+            max_relative = 1e-12
+        );
+    }
+
+    #[test]
+    fn test_interest_rate_tenors_and_currencies() {
+        let calculator = SaCvaCalculator::default();
+        let ws1: f64 = 0.0111 * 50.0 * 1e4;
+        let ws10 = 0.0074 * 80.0 * 1e4;
+        let capital = calculator
+            .calculate(&[
+                SaCvaSensitivity::interest_rate(cp("A"), Currency::EUR, 1.0, 50.0),
+                SaCvaSensitivity::interest_rate(cp("A"), Currency::EUR, 10.0, 80.0),
+            ])
+            .unwrap();
+        let expected = 1.25 * (ws1 * ws1 + ws10 * ws10 + 2.0 * 0.55 * ws1 * ws10).sqrt();
+        assert_relative_eq!(capital.total, expected, max_relative = 1e-12);
+
+        // Other currencies collapse to one parallel factor
+        let chf = calculator
+            .calculate(&[
+                SaCvaSensitivity::interest_rate(cp("A"), Currency::CHF, 3.0, 50.0),
+                SaCvaSensitivity::interest_rate(cp("A"), Currency::CHF, 7.0, 80.0),
+            ])
+            .unwrap();
+        assert_relative_eq!(chf.total, 1.25 * 0.0158 * 130.0 * 1e4, max_relative = 1e-12);
+
+        // ...unless they are the reporting currency
+        assert!(calculator
+            .with_reporting_currency(Currency::CHF)
+            .calculate(&[SaCvaSensitivity::interest_rate(
+                cp("A"),
+                Currency::CHF,
+                3.0,
+                50.0
+            )])
+            .is_err());
+    }
+
+    #[test]
+    fn test_cross_bucket_aggregation() {
+        let capital = SaCvaCalculator::default()
+            .calculate(&[
+                SaCvaSensitivity::fx(cp("A"), Currency::EUR, 10.0),
+                SaCvaSensitivity::fx(cp("B"), Currency::GBP, 20.0),
+            ])
+            .unwrap();
+        let (eur, gbp): (f64, f64) = (0.11 * 1e3, 0.11 * 2e3);
+        let expected = 1.25 * (eur * eur + gbp * gbp + 2.0 * 0.6 * eur * gbp).sqrt();
+        assert_relative_eq!(capital.total, expected, max_relative = 1e-12);
+        assert_relative_eq!(capital.standalone(&cp("A")).unwrap(), 1.25 * eur);
+        assert_relative_eq!(capital.standalone(&cp("B")).unwrap(), 1.25 * gbp);
+    }
+
+    #[test]
+    fn test_market_factors_net_across_counterparties() {
+        // Offsetting rate deltas of two counterparties cancel in the total
+        let capital = SaCvaCalculator::default()
+            .calculate(&[
+                SaCvaSensitivity::interest_rate(cp("A"), Currency::USD, 2.0, 40.0),
+                SaCvaSensitivity::interest_rate(cp("B"), Currency::USD, 2.0, -40.0),
+            ])
+            .unwrap();
+        assert_relative_eq!(capital.total, 0.0);
+        assert!(capital.standalone(&cp("A")).unwrap() > 0.0);
+    }
+
+    #[test]
+    fn test_counterparty_spread_correlations() {
+        let (a, b) = (
+            SaCvaSensitivity::counterparty_spread(
+                cp("A"),
+                CvaSector::Financial,
+                CreditRating::A,
+                5.0,
+                10.0,
+            ),
+            SaCvaSensitivity::counterparty_spread(
+                cp("B"),
+                CvaSector::Financial,
+                CreditRating::BB,
+                1.0,
+                10.0,
+            ),
+        );
+        let capital = SaCvaCalculator::default().calculate(&[a, b]).unwrap();
+        let (ws_a, ws_b): (f64, f64) = (0.05 * 1e5, 0.12 * 1e5);
+        let rho = 0.9 * 0.5 * 0.8;
+        let expected = 1.25 * (ws_a * ws_a + ws_b * ws_b + 2.0 * rho * ws_a * ws_b).sqrt();
+        assert_relative_eq!(capital.total, expected, max_relative = 1e-12);
+        assert!(capital.diversification_benefit() > 0.0);
+    }
+
+    #[test]
+    fn test_opposite_sector_buckets_offset() {
+        // Sovereign and financial buckets: γ = 0.1
+        let capital = SaCvaCalculator::default()
+            .calculate(&[
+                SaCvaSensitivity::counterparty_spread(
+                    cp("A"),
+                    CvaSector::Sovereign,
+                    CreditRating::AA,
+                    5.0,
+                    100.0,
+                ),
+                SaCvaSensitivity::counterparty_spread(
+                    cp("B"),
+                    CvaSector::Financial,
+                    CreditRating::A,
+                    5.0,
+                    -10.0,
+                ),
+            ])
+            .unwrap();
+        let (k1, k2): (f64, f64) = (0.005 * 1e6, 0.05 * 1e5);
+        let expected = 1.25 * (k1 * k1 + k2 * k2 - 2.0 * 0.1 * k1 * k2).sqrt();
+        assert_relative_eq!(capital.total, expected, max_relative = 1e-12);
+    }
+
+    #[test]
+    fn test_from_spread_deltas() {
+        let deltas = CvaSpreadDeltas::new(cp("A"), vec![1.0, 5.0], vec![3.0, 7.0]).unwrap();
+        let sensitivities =
+            SaCvaSensitivity::from_spread_deltas(&deltas, CvaSector::Technology, CreditRating::A);
+        assert_eq!(sensitivities.len(), 2);
+        assert_eq!(sensitivities[1].sensitivity, 7e4);
+        assert_eq!(
+            sensitivities[0].factor.risk_class(),
+            SaCvaRiskClass::CounterpartyCreditSpread
+        );
+    }
+
+    #[test]
+    fn test_rejects_invalid_sensitivities() {
+        let calculator = SaCvaCalculator::default();
+        assert!(matches!(
+            calculator.calculate(&[SaCvaSensitivity::interest_rate(
+                cp("A"),
+                Currency::USD,
+                3.0,
+                1.0
+            )]),
+            Err(RegulatoryError::InvalidSensitivity(_))
+        ));
+        assert!(matches!(
+            calculator.calculate(&[SaCvaSensitivity::fx(cp("A"), Currency::USD, 1.0)]),
+            Err(RegulatoryError::InvalidSensitivity(_))
+        ));
+        assert!(matches!(
+            calculator.calculate(&[SaCvaSensitivity::fx(cp("A"), Currency::EUR, f64::NAN)]),
+            Err(RegulatoryError::InvalidSensitivity(_))
+        ));
+        assert!(matches!(
+            calculator.with_multiplier(0.5).calculate(&[]),
+            Err(RegulatoryError::InvalidParameter(_))
+        ));
+    }
+}
@@ -0,0 +1,60 @@
+//! CSV renderer.
+//!
+//! Each table becomes a section: a title line, a header row and the data
+//! rows. Sections are separated by a blank line.
+
+use super::data::ReportData;
+
+/// Render report data as CSV.
+pub(crate) fn render(data: &ReportData) -> String {
+    let mut out = String::new();
+    for (i, table) in data.tables.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        out.push_str(&escape(&table.title));
+        out.push('\n');
+        let header: Vec<String> = table.columns.iter().map(|c| escape(&c.name)).collect();
+        out.push_str(&header.join(","));
+        out.push('\n');
+        for row in 0..table.len() {
+            let cells: Vec<String> = (0..table.columns.len())
+                .map(|c| escape(&table.formatted(row, c)))
+                .collect();
+            out.push_str(&cells.join(","));
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Quote a field if it contains a separator, quote or line break.
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporting::DataTable;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("plain"), "plain");
+        assert_eq!(escape("a,b"), "\"a,b\"");
+        assert_eq!(escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+
+    #[test]
+    fn test_sections_separated_by_blank_line() {
+        let mut a = DataTable::new("a", "First").with_column("x");
+        a.push_row(vec!["1".into()]).unwrap();
+        let b = DataTable::new("b", "Second").with_column("y");
+        let data = ReportData::new("R", "d").with_table(a).with_table(b);
+        assert_eq!(render(&data), "First\nx\n1\n\nSecond\ny\n");
+    }
+}
@@ -0,0 +1,311 @@
+//! Tabular report data shared by every renderer.
+
+use std::fmt;
+
+use pricer_core::types::RunMetadata;
+
+use super::ReportError;
+
+/// A single cell in a [`DataTable`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CellValue {
+    /// Free text
+    Text(String),
+    /// Floating-point number, formatted with the column's decimals
+    Number(f64),
+    /// Integer count or index
+    Integer(i64),
+    /// Missing value
+    Empty,
+}
+
+impl CellValue {
+    /// Numeric value of the cell, if any.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Number(v) => Some(*v),
+            Self::Integer(v) => Some(*v as f64),
+            Self::Text(_) | Self::Empty => None,
+        }
+    }
+
+    /// Format the cell for text output.
+    ///
+    /// Numbers use `decimals` places when given, otherwise Rust's shortest
+    /// round-trip representation.
+    pub fn format(&self, decimals: Option<usize>) -> String {
+        match self {
+            Self::Text(s) => s.clone(),
+            Self::Number(v) => match decimals {
+                Some(d) => format!("{:.*}", d, v),
+                None => v.to_string(),
+            },
+            Self::Integer(v) => v.to_string(),
+            Self::Empty => String::new(),
+        }
+    }
+}
+
+impl fmt::Display for CellValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.format(None))
+    }
+}
+
+impl From<&str> for CellValue {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl From<String> for CellValue {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<f64> for CellValue {
+    fn from(value: f64) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl From<i64> for CellValue {
+    fn from(value: i64) -> Self {
+        Self::Integer(value)
+    }
+}
+
+impl From<usize> for CellValue {
+    fn from(value: usize) -> Self {
+        Self::Integer(value as i64)
+    }
+}
+
+impl<T: Into<CellValue>> From<Option<T>> for CellValue {
+    fn from(value: Option<T>) -> Self {
+        value.map_or(Self::Empty, Into::into)
+    }
+}
+
+/// Column definition of a [`DataTable`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Column {
+    /// Column name, used as header and template variable
+    pub name: String,
+    /// Decimal places for numeric cells (`None` for full precision)
+    pub decimals: Option<usize>,
+}
+
+/// Named table of rows.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DataTable {
+    /// Table identifier referenced by templates
+    pub name: String,
+    /// Human-readable title
+    pub title: String,
+    /// Column definitions
+    pub columns: Vec<Column>,
+    /// Row cells, one entry per column
+    pub rows: Vec<Vec<CellValue>>,
+}
+
+impl DataTable {
+    /// Create an empty table without columns.
+    pub fn new(name: impl Into<String>, title: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            title: title.into(),
+            columns: Vec::new(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Add a text or integer column.
+    pub fn with_column(mut self, name: impl Into<String>) -> Self {
+        self.columns.push(Column {
+            name: name.into(),
+            decimals: None,
+        });
+        self
+    }
+
+    /// Add a numeric column formatted to `decimals` places.
+    pub fn with_number_column(mut self, name: impl Into<String>, decimals: usize) -> Self {
+        self.columns.push(Column {
+            name: name.into(),
+            decimals: Some(decimals),
+        });
+        self
+    }
+
+    /// Append a row.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ReportError::InvalidData`] if the row width differs from the
+    /// number of columns.
+    pub fn push_row(&mut self, row: Vec<CellValue>) -> Result<(), ReportError> {
+        if row.len() != self.columns.len() {
+            return Err(ReportError::InvalidData(format!(
+                "table '{}' has {} columns but row has {} cells",
+                self.name,
+                self.columns.len(),
+                row.len()
+            )));
+        }
+        self.rows.push(row);
+        Ok(())
+    }
+
+    /// Index of a column by name.
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.columns.iter().position(|c| c.name == name)
+    }
+
+    /// Formatted text of a cell, using the column's decimals.
+    pub fn formatted(&self, row: usize, column: usize) -> String {
+        self.rows[row][column].format(self.columns[column].decimals)
+    }
+
+    /// Sum of the numeric cells in a column.
+    pub fn column_sum(&self, column: usize) -> f64 {
+        self.rows.iter().filter_map(|r| r[column].as_f64()).sum()
+    }
+
+    /// Number of rows.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Whether the table has no rows.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+}
+
+/// Everything a report renderer needs: title, valuation date, free-form
+/// metadata and an ordered list of tables.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReportData {
+    /// Report title
+    pub title: String,
+    /// Valuation date or timestamp, supplied by the caller so that output
+    /// is reproducible
+    pub as_of: String,
+    /// Ordered key/value metadata
+    pub metadata: Vec<(String, String)>,
+    /// Ordered tables
+    pub tables: Vec<DataTable>,
+}
+
+impl ReportData {
+    /// Create report data without tables.
+    pub fn new(title: impl Into<String>, as_of: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            as_of: as_of.into(),
+            metadata: Vec::new(),
+            tables: Vec::new(),
+        }
+    }
+
+    /// Add a metadata entry, replacing an existing key.
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        let key = key.into();
+        let value = value.into();
+        match self.metadata.iter_mut().find(|(k, _)| *k == key) {
+            Some(entry) => entry.1 = value,
+            None => self.metadata.push((key, value)),
+        }
+        self
+    }
+
+    /// Add the entries of a run's provenance (seed, hashes, git commit and
+    /// engine versions) as metadata, so the published numbers can be
+    /// reproduced from the report alone.
+    pub fn with_run_metadata(self, run: &RunMetadata) -> Self {
+        run.entries()
+            .into_iter()
+            .fold(self, |data, (key, value)| data.with_metadata(key, value))
+    }
+
+    /// Add a table.
+    pub fn with_table(mut self, table: DataTable) -> Self {
+        self.tables.push(table);
+        self
+    }
+
+    /// Look up a metadata value.
+    pub fn metadata(&self, key: &str) -> Option<&str> {
+        self.metadata
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Look up a table by name.
+    pub fn table(&self, name: &str) -> Option<&DataTable> {
+        self.tables.iter().find(|t| t.name == name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_row_checks_width() {
+        let mut table = DataTable::new("t", "T")
+            .with_column("id")
+            .with_number_column("value", 2);
+        assert!(table.push_row(vec!["a".into(), 1.0.into()]).is_ok());
+        assert!(matches!(
+            table.push_row(vec!["b".into()]),
+            Err(ReportError::InvalidData(_))
+        ));
+        assert_eq!(table.len(), 1);
+        assert_eq!(table.formatted(0, 1), "1.00");
+        assert_eq!(table.column_index("value"), Some(1));
+    }
+
+    #[test]
+    fn test_column_sum_skips_text() {
+        let mut table = DataTable::new("t", "T")
+            .with_column("id")
+            .with_number_column("value", 0);
+        table.push_row(vec!["a".into(), 1.5.into()]).unwrap();
+        table.push_row(vec!["b".into(), CellValue::Empty]).unwrap();
+        table.push_row(vec!["c".into(), 2i64.into()]).unwrap();
+        assert_eq!(table.column_sum(1), 3.5);
+        assert_eq!(table.column_sum(0), 0.0);
+    }
+
+    #[test]
+    fn test_metadata_replaces_existing_key() {
+        let data = ReportData::new("R", "2026-10-15")
+            .with_metadata("source", "a")
+            .with_metadata("source", "b");
+        assert_eq!(data.metadata("source"), Some("b"));
+        assert_eq!(data.metadata.len(), 1);
+    }
+
+    #[test]
+    fn test_run_metadata_entries() {
+        let run = RunMetadata::default()
+            .with_seed(42)
+            .with_config_hash(0xff)
+            .with_engine_version("pricer_risk", "0.1.0");
+        let data = ReportData::new("R", "2026-10-15")
+            .with_metadata("trades", "3")
+            .with_run_metadata(&run);
+        assert_eq!(data.metadata("seed"), Some("42"));
+        assert_eq!(data.metadata("config_hash"), Some("00000000000000ff"));
+        assert_eq!(data.metadata("version.pricer_risk"), Some("0.1.0"));
+        assert_eq!(data.metadata[0].0, "trades");
+    }
+}
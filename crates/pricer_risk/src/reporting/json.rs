@@ -0,0 +1,129 @@
+//! JSON renderer.
+//!
+//! Written by hand rather than through serde so that key order is fixed and
+//! the crate's `serde` feature stays optional. Rows are objects keyed by
+//! column name; numbers keep full precision and non-finite values become
+//! `null`.
+
+use std::fmt::Write as _;
+
+use super::data::{CellValue, ReportData};
+
+/// Render report data as a pretty-printed JSON document.
+pub(crate) fn render(data: &ReportData) -> String {
+    let mut out = String::new();
+    out.push_str("{\n");
+    let _ = writeln!(out, "  \"title\": {},", string(&data.title));
+    let _ = writeln!(out, "  \"as_of\": {},", string(&data.as_of));
+
+    if data.metadata.is_empty() {
+        out.push_str("  \"metadata\": {},\n");
+    } else {
+        out.push_str("  \"metadata\": {\n");
+        for (i, (key, value)) in data.metadata.iter().enumerate() {
+            let comma = if i + 1 < data.metadata.len() { "," } else { "" };
+            let _ = writeln!(out, "    {}: {}{}", string(key), string(value), comma);
+        }
+        out.push_str("  },\n");
+    }
+
+    if data.tables.is_empty() {
+        out.push_str("  \"tables\": []\n");
+    } else {
+        out.push_str("  \"tables\": [\n");
+        for (t, table) in data.tables.iter().enumerate() {
+            out.push_str("    {\n");
+            let _ = writeln!(out, "      \"name\": {},", string(&table.name));
+            let _ = writeln!(out, "      \"title\": {},", string(&table.title));
+            let columns: Vec<String> = table.columns.iter().map(|c| string(&c.name)).collect();
+            let _ = writeln!(out, "      \"columns\": [{}],", columns.join(", "));
+            if table.is_empty() {
+                out.push_str("      \"rows\": []\n");
+            } else {
+                out.push_str("      \"rows\": [\n");
+                for (r, row) in table.rows.iter().enumerate() {
+                    let fields: Vec<String> = table
+                        .columns
+                        .iter()
+                        .zip(row)
+                        .map(|(col, cell)| format!("{}: {}", string(&col.name), value(cell)))
+                        .collect();
+                    let comma = if r + 1 < table.len() { "," } else { "" };
+                    let _ = writeln!(out, "        {{{}}}{}", fields.join(", "), comma);
+                }
+                out.push_str("      ]\n");
+            }
+            let comma = if t + 1 < data.tables.len() { "," } else { "" };
+            let _ = writeln!(out, "    }}{}", comma);
+        }
+        out.push_str("  ]\n");
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn value(cell: &CellValue) -> String {
+    match cell {
+        CellValue::Text(s) => string(s),
+        CellValue::Number(v) if v.is_finite() => {
+            // Keep a decimal point so consumers read the value as a float
+            let s = v.to_string();
+            if s.contains(['.', 'e', 'E']) {
+                s
+            } else {
+                format!("{}.0", s)
+            }
+        }
+        CellValue::Number(_) | CellValue::Empty => "null".to_string(),
+        CellValue::Integer(v) => v.to_string(),
+    }
+}
+
+/// Quote and escape a JSON string.
+fn string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_string_escaping() {
+        assert_eq!(string("a\"b\\c\n\u{1}"), "\"a\\\"b\\\\c\\n\\u0001\"");
+    }
+
+    #[test]
+    fn test_values() {
+        assert_eq!(value(&CellValue::Number(2.0)), "2.0");
+        assert_eq!(value(&CellValue::Number(0.125)), "0.125");
+        assert_eq!(value(&CellValue::Number(f64::NAN)), "null");
+        assert_eq!(value(&CellValue::Integer(3)), "3");
+        assert_eq!(value(&CellValue::Empty), "null");
+    }
+
+    #[test]
+    fn test_empty_report() {
+        let data = ReportData::new("R", "2026-10-15");
+        assert_eq!(
+            render(&data),
+            "{\n  \"title\": \"R\",\n  \"as_of\": \"2026-10-15\",\n  \"metadata\": {},\n  \"tables\": []\n}\n"
+        );
+    }
+}
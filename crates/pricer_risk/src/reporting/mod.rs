@@ -0,0 +1,178 @@
+//! Report generation for XVA, exposure, Greeks and cashflow results.
+//!
+//! This module provides:
+//! - [`ReportData`] / [`DataTable`]: format-neutral tables built from risk
+//!   results by [`xva_report_data`], [`exposure_report_data`],
+//!   [`greeks_report_data`], [`cashflow_report_data`] and
+//!   [`trade_cashflow_report_data`], with
+//!   [`sensitivities_table`] for Greeks quoted in explicit units
+//! - [`Template`]: a small `{{ ... }}` template engine that lays out the
+//!   human-readable (text and PDF) form of a report
+//! - [`ReportDefinition`] / [`ReportRegistry`]: named report layouts with
+//!   the tables they require and the formats they support
+//! - Renderers for [`ReportFormat::Csv`], [`ReportFormat::Json`],
+//!   [`ReportFormat::Xlsx`] and [`ReportFormat::Pdf`], written without
+//!   external dependencies and byte-for-byte deterministic
+//!
+//! # Golden files
+//!
+//! Rendered output of the standard reports is checked against files in
+//! `crates/pricer_risk/golden/reporting/`. After an intentional layout
+//! change, regenerate them with
+//!
+//! ```bash
+//! NEUTRYX_UPDATE_GOLDEN=1 cargo test -p pricer_risk reporting
+//! ```
+//!
+//! and review the diff before committing.
+//!
+//! # Examples
+//!
+//! ```
+//! use pricer_risk::reporting::{xva_report_data, ReportFormat, ReportRegistry};
+//! use pricer_risk::xva::{CounterpartyXva, NettingSetXva, PortfolioXva};
+//! use pricer_risk::portfolio::{CounterpartyId, NettingSetId};
+//!
+//! let ns = NettingSetXva::new(
+//!     NettingSetId::new("NS001"),
+//!     CounterpartyId::new("CP001"),
+//!     1_200.0,
+//!     300.0,
+//!     150.0,
+//!     50.0,
+//! );
+//! let cp = CounterpartyXva::from_netting_sets(CounterpartyId::new("CP001"), vec![ns]);
+//! let xva = PortfolioXva::from_counterparties(vec![cp]);
+//!
+//! let registry = ReportRegistry::standard();
+//! let data = xva_report_data(&xva, "2026-10-15");
+//! let report = registry.render("xva", &data, ReportFormat::Csv).unwrap();
+//! assert_eq!(report.file_name, "xva_2026-10-15.csv");
+//! assert!(report.as_text().unwrap().contains("CVA,1200.00"));
+//! ```
+
+mod csv;
+mod data;
+mod json;
+mod pdf;
+mod registry;
+mod sources;
+mod template;
+mod xlsx;
+
+pub use data::{CellValue, Column, DataTable, ReportData};
+pub use registry::{RenderedReport, ReportDefinition, ReportRegistry};
+pub use sources::{
+    cashflow_report_data, exposure_report_data, greeks_report_data, sensitivities_table,
+    trade_cashflow_report_data, xva_report_data, ExposureSeries,
+};
+pub use template::Template;
+
+use std::fmt;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+/// Errors from report definition and rendering.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum ReportError {
+    /// No report definition with this identifier.
+    #[error("Unknown report: {0}")]
+    UnknownReport(String),
+
+    /// A report definition with this identifier is already registered.
+    #[error("Report already registered: {0}")]
+    DuplicateReport(String),
+
+    /// The report definition does not offer this format.
+    #[error("Report '{report}' does not support format {format}")]
+    UnsupportedFormat {
+        /// Report identifier
+        report: String,
+        /// Requested format
+        format: ReportFormat,
+    },
+
+    /// Template parse or render failure.
+    #[error("Template error: {message}")]
+    Template {
+        /// Description of the problem
+        message: String,
+    },
+
+    /// The report data lacks a table the report needs.
+    #[error("Missing table: {0}")]
+    MissingTable(String),
+
+    /// Inconsistent report data.
+    #[error("Invalid report data: {0}")]
+    InvalidData(String),
+}
+
+/// Output format of a rendered report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum ReportFormat {
+    /// Comma-separated values, one section per table
+    Csv,
+    /// JSON document with metadata and tables
+    Json,
+    /// Office Open XML workbook, one sheet per table
+    Xlsx,
+    /// PDF document laid out by the report template
+    Pdf,
+}
+
+impl ReportFormat {
+    /// All formats, in display order.
+    pub const ALL: [ReportFormat; 4] = [Self::Csv, Self::Json, Self::Xlsx, Self::Pdf];
+
+    /// File extension without the leading dot.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Json => "json",
+            Self::Xlsx => "xlsx",
+            Self::Pdf => "pdf",
+        }
+    }
+
+    /// MIME type of the rendered bytes.
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            Self::Csv => "text/csv",
+            Self::Json => "application/json",
+            Self::Xlsx => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+            Self::Pdf => "application/pdf",
+        }
+    }
+
+    /// Whether the rendered bytes are UTF-8 text.
+    pub fn is_text(&self) -> bool {
+        matches!(self, Self::Csv | Self::Json)
+    }
+}
+
+impl fmt::Display for ReportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.extension())
+    }
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            "xlsx" | "excel" => Ok(Self::Xlsx),
+            "pdf" => Ok(Self::Pdf),
+            _ => Err(format!("Unknown report format: {}", s)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
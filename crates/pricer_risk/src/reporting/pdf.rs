@@ -0,0 +1,204 @@
+//! PDF renderer.
+//!
+//! Lays out the text produced by a report template in 9pt Courier on A4
+//! pages, with a page footer. Only the standard Type 1 Courier font is used,
+//! so nothing is embedded; characters outside printable ASCII are replaced
+//! by `?`. No creation date is written, keeping output reproducible.
+
+use std::fmt::Write as _;
+
+/// A4 page size in points.
+const PAGE_WIDTH: f64 = 595.0;
+const PAGE_HEIGHT: f64 = 842.0;
+/// Page margin in points.
+const MARGIN: f64 = 50.0;
+/// Font size and line spacing in points.
+const FONT_SIZE: f64 = 9.0;
+const LEADING: f64 = 11.0;
+/// Courier glyphs are 0.6 em wide.
+const MAX_COLUMNS: usize = ((PAGE_WIDTH - 2.0 * MARGIN) / (0.6 * FONT_SIZE)) as usize;
+/// Body lines per page, leaving two lines for the footer.
+const LINES_PER_PAGE: usize = ((PAGE_HEIGHT - 2.0 * MARGIN) / LEADING) as usize - 2;
+
+/// Render pre-formatted text as a PDF document.
+pub(crate) fn render(title: &str, text: &str) -> Vec<u8> {
+    let lines = wrap_lines(text);
+    let pages: Vec<&[String]> = if lines.is_empty() {
+        vec![&[]]
+    } else {
+        lines.chunks(LINES_PER_PAGE).collect()
+    };
+    let page_count = pages.len();
+
+    // Objects: 1 catalog, 2 page tree, 3 font, 4 info, then a page and its
+    // content stream per page
+    let page_id = |i: usize| 5 + 2 * i;
+    let mut objects: Vec<String> = Vec::new();
+    objects.push("<< /Type /Catalog /Pages 2 0 R >>".to_string());
+    let kids: Vec<String> = (0..page_count)
+        .map(|i| format!("{} 0 R", page_id(i)))
+        .collect();
+    objects.push(format!(
+        "<< /Type /Pages /Kids [{}] /Count {} >>",
+        kids.join(" "),
+        page_count
+    ));
+    objects.push(
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Courier /Encoding /WinAnsiEncoding >>"
+            .to_string(),
+    );
+    objects.push(format!(
+        "<< /Title ({}) /Producer (neutryx pricer_risk) >>",
+        escape(title)
+    ));
+
+    for (i, body) in pages.iter().enumerate() {
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+             /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+            PAGE_WIDTH,
+            PAGE_HEIGHT,
+            page_id(i) + 1
+        ));
+        let stream = content_stream(body, i + 1, page_count);
+        objects.push(format!(
+            "<< /Length {} >>\nstream\n{}endstream",
+            stream.len(),
+            stream
+        ));
+    }
+
+    let mut out = String::from("%PDF-1.4\n");
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(out.len());
+        let _ = write!(out, "{} 0 obj\n{}\nendobj\n", i + 1, object);
+    }
+    let xref = out.len();
+    let _ = write!(out, "xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for offset in offsets {
+        let _ = writeln!(out, "{:010} 00000 n ", offset);
+    }
+    let _ = write!(
+        out,
+        "trailer\n<< /Size {} /Root 1 0 R /Info 4 0 R >>\nstartxref\n{}\n%%EOF\n",
+        objects.len() + 1,
+        xref
+    );
+    out.into_bytes()
+}
+
+fn content_stream(lines: &[String], page: usize, page_count: usize) -> String {
+    let mut s = String::new();
+    // The `'` operator moves down one line before drawing
+    let top = PAGE_HEIGHT - MARGIN;
+    let _ = write!(
+        s,
+        "BT\n/F1 {} Tf\n{} TL\n{} {} Td\n",
+        FONT_SIZE, LEADING, MARGIN, top
+    );
+    for line in lines {
+        let _ = writeln!(s, "({}) '", escape(line));
+    }
+    s.push_str("ET\n");
+    let footer = format!("Page {} of {}", page, page_count);
+    let footer_x = PAGE_WIDTH - MARGIN - footer.len() as f64 * 0.6 * FONT_SIZE;
+    let _ = write!(
+        s,
+        "BT\n/F1 {} Tf\n{:.1} {} Td\n({}) Tj\nET\n",
+        FONT_SIZE,
+        footer_x,
+        MARGIN - FONT_SIZE,
+        footer
+    );
+    s
+}
+
+/// Split text into printable lines no wider than the page.
+fn wrap_lines(text: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    for raw in text.lines() {
+        let printable: Vec<char> = raw
+            .chars()
+            .map(|c| match c {
+                '\t' => ' ',
+                c if c.is_ascii() && !c.is_ascii_control() => c,
+                _ => '?',
+            })
+            .collect();
+        if printable.is_empty() {
+            lines.push(String::new());
+            continue;
+        }
+        for chunk in printable.chunks(MAX_COLUMNS) {
+            lines.push(chunk.iter().collect());
+        }
+    }
+    lines
+}
+
+/// Escape a PDF literal string.
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' | '(' | ')' => {
+                out.push('\\');
+                out.push(c);
+            }
+            c if c.is_ascii() && !c.is_ascii_control() => out.push(c),
+            _ => out.push('?'),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("a (b) \\c"), "a \\(b\\) \\\\c");
+        assert_eq!(escape("€"), "?");
+    }
+
+    #[test]
+    fn test_wrap_lines() {
+        let long = "x".repeat(MAX_COLUMNS + 5);
+        let lines = wrap_lines(&format!("a\n\n{}", long));
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[2].len(), MAX_COLUMNS);
+        assert_eq!(lines[3], "xxxxx");
+    }
+
+    #[test]
+    fn test_pagination_and_xref() {
+        let text = (0..LINES_PER_PAGE + 1)
+            .map(|i| format!("line {}", i))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let bytes = render("Test", &text);
+        let pdf = String::from_utf8(bytes).unwrap();
+        assert!(pdf.starts_with("%PDF-1.4\n"));
+        assert!(pdf.ends_with("%%EOF\n"));
+        assert!(pdf.contains("/Count 2"));
+        assert!(pdf.contains("(Page 2 of 2) Tj"));
+
+        // Every xref offset points at its object header
+        let xref_start = pdf.find("xref\n").unwrap();
+        let entries: Vec<&str> = pdf[xref_start..].lines().skip(3).take(8).collect();
+        for (i, entry) in entries.iter().enumerate() {
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(&format!("{} 0 obj", i + 1)));
+        }
+        let startxref: usize = pdf.lines().rev().nth(1).unwrap().parse().unwrap();
+        assert_eq!(startxref, xref_start);
+    }
+
+    #[test]
+    fn test_empty_text_has_one_page() {
+        let pdf = String::from_utf8(render("Empty", "")).unwrap();
+        assert!(pdf.contains("/Count 1"));
+    }
+}
@@ -0,0 +1,356 @@
+//! Report definitions and the registry that renders them.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::data::ReportData;
+use super::template::Template;
+use super::{csv, json, pdf, xlsx, ReportError, ReportFormat};
+
+/// Layout of the standard XVA report.
+const XVA_TEMPLATE: &str = "\
+{{title}}
+As of {{as_of}}
+
+Portfolio XVA
+{{table summary}}
+Counterparties
+{{table counterparties}}
+Netting sets
+{{table netting_sets}}
+Total XVA: {{sum counterparties.total}}
+";
+
+/// Layout of the standard exposure report.
+const EXPOSURE_TEMPLATE: &str = "\
+{{title}}
+As of {{as_of}}
+
+Exposure summary
+{{#each summary}}{{@index}}. {{id}}
+   EPE {{epe}}  effective EPE {{effective_epe}}  peak EE {{peak_ee}}  peak PFE {{peak_pfe}}
+{{/each}}
+Exposure profiles
+{{table profiles}}";
+
+/// Layout of the standard Greeks report.
+const GREEKS_TEMPLATE: &str = "\
+{{title}}
+As of {{as_of}}
+
+Portfolio Greeks
+{{table totals}}
+Greeks by trade
+{{table trades}}
+Greeks by risk factor
+{{table factors}}";
+
+/// Layout of the standard cashflow projection report.
+const CASHFLOWS_TEMPLATE: &str = "\
+{{title}}
+As of {{as_of}}
+
+Liquidity ladder
+{{table ladder}}
+Net cashflows by date
+{{table by_date}}
+Projected cashflows
+{{table cashflows}}";
+
+/// Layout of the single-trade cashflow decomposition report.
+const TRADE_CASHFLOWS_TEMPLATE: &str = "\
+{{title}}
+As of {{as_of}}
+Trade {{meta.trade}} with {{meta.counterparty}}
+
+Expected cashflows
+{{table cashflows}}
+Present value {{meta.present_value}}
+";
+
+/// A named report layout.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportDefinition {
+    /// Unique identifier, also the output file stem
+    pub id: String,
+    /// Human-readable title
+    pub title: String,
+    /// Description shown in listings
+    pub description: String,
+    /// Layout for the PDF format
+    pub template: Template,
+    /// Formats the report can be rendered to
+    pub formats: Vec<ReportFormat>,
+    /// Tables the report data must contain
+    pub required_tables: Vec<String>,
+}
+
+impl ReportDefinition {
+    /// Create a definition from template text.
+    ///
+    /// Supports every format and requires the tables the template refers to.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ReportError::Template`] if the template does not parse.
+    pub fn new(
+        id: impl Into<String>,
+        title: impl Into<String>,
+        template: &str,
+    ) -> Result<Self, ReportError> {
+        let template = Template::parse(template)?;
+        let required_tables = template
+            .referenced_tables()
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        Ok(Self {
+            id: id.into(),
+            title: title.into(),
+            description: String::new(),
+            template,
+            formats: ReportFormat::ALL.to_vec(),
+            required_tables,
+        })
+    }
+
+    /// Set the description.
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// Restrict the supported formats.
+    pub fn with_formats(mut self, formats: &[ReportFormat]) -> Self {
+        self.formats = formats.to_vec();
+        self
+    }
+
+    /// Replace the required tables.
+    pub fn with_required_tables(mut self, tables: &[&str]) -> Self {
+        self.required_tables = tables.iter().map(|t| t.to_string()).collect();
+        self
+    }
+
+    /// Whether the definition supports a format.
+    pub fn supports(&self, format: ReportFormat) -> bool {
+        self.formats.contains(&format)
+    }
+
+    /// Render the template to text.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ReportError::MissingTable`] if a required table is absent,
+    /// or a template error.
+    pub fn render_text(&self, data: &ReportData) -> Result<String, ReportError> {
+        self.check_tables(data)?;
+        self.template.render(data)
+    }
+
+    /// Render report data in a format.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ReportError::UnsupportedFormat`],
+    /// [`ReportError::MissingTable`] or a template error.
+    pub fn render(
+        &self,
+        data: &ReportData,
+        format: ReportFormat,
+    ) -> Result<RenderedReport, ReportError> {
+        if !self.supports(format) {
+            return Err(ReportError::UnsupportedFormat {
+                report: self.id.clone(),
+                format,
+            });
+        }
+        self.check_tables(data)?;
+
+        let bytes = match format {
+            ReportFormat::Csv => csv::render(data).into_bytes(),
+            ReportFormat::Json => json::render(data).into_bytes(),
+            ReportFormat::Xlsx => xlsx::render(data),
+            ReportFormat::Pdf => pdf::render(&data.title, &self.template.render(data)?),
+        };
+        Ok(RenderedReport {
+            report_id: self.id.clone(),
+            format,
+            file_name: self.file_name(data, format),
+            bytes,
+        })
+    }
+
+    /// Output file name: report id and valuation date.
+    fn file_name(&self, data: &ReportData, format: ReportFormat) -> String {
+        let as_of: String = data
+            .as_of
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        if as_of.is_empty() {
+            format!("{}.{}", self.id, format.extension())
+        } else {
+            format!("{}_{}.{}", self.id, as_of, format.extension())
+        }
+    }
+
+    fn check_tables(&self, data: &ReportData) -> Result<(), ReportError> {
+        match self
+            .required_tables
+            .iter()
+            .find(|t| data.table(t).is_none())
+        {
+            Some(missing) => Err(ReportError::MissingTable(missing.clone())),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A report rendered to bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderedReport {
+    /// Identifier of the definition that produced the report
+    pub report_id: String,
+    /// Output format
+    pub format: ReportFormat,
+    /// Suggested file name
+    pub file_name: String,
+    /// Rendered content
+    pub bytes: Vec<u8>,
+}
+
+impl RenderedReport {
+    /// Content as text, for CSV and JSON output.
+    pub fn as_text(&self) -> Option<&str> {
+        if self.format.is_text() {
+            std::str::from_utf8(&self.bytes).ok()
+        } else {
+            None
+        }
+    }
+
+    /// Write the report into a directory, creating it if needed.
+    ///
+    /// Returns the path of the written file.
+    pub fn write_to(&self, dir: impl AsRef<Path>) -> io::Result<PathBuf> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+        let path = dir.join(&self.file_name);
+        std::fs::write(&path, &self.bytes)?;
+        Ok(path)
+    }
+}
+
+/// Registry of report definitions, in registration order.
+#[derive(Debug, Clone, Default)]
+pub struct ReportRegistry {
+    definitions: Vec<ReportDefinition>,
+}
+
+impl ReportRegistry {
+    /// Create an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry with the standard `xva`, `exposure`, `greeks`,
+    /// `cashflows` and `trade_cashflows` reports.
+    ///
+    /// Their data is built by [`super::xva_report_data`],
+    /// [`super::exposure_report_data`], [`super::greeks_report_data`],
+    /// [`super::cashflow_report_data`] and
+    /// [`super::trade_cashflow_report_data`].
+    pub fn standard() -> Self {
+        let mut registry = Self::new();
+        let standard = [
+            ReportDefinition::new("xva", "XVA Report", XVA_TEMPLATE).map(|d| {
+                d.with_description("CVA, DVA, FVA and ColVA by counterparty and netting set")
+            }),
+            ReportDefinition::new("exposure", "Exposure Report", EXPOSURE_TEMPLATE)
+                .map(|d| d.with_description("EE and PFE profiles with EPE summary")),
+            ReportDefinition::new("greeks", "Greeks Report", GREEKS_TEMPLATE)
+                .map(|d| d.with_description("Portfolio Greeks by trade and risk factor")),
+            ReportDefinition::new(
+                "cashflows",
+                "Cashflow Projection Report",
+                CASHFLOWS_TEMPLATE,
+            )
+            .map(|d| {
+                d.with_description("Future contractual cashflows by date, currency and bucket")
+            }),
+            ReportDefinition::new(
+                "trade_cashflows",
+                "Trade Cashflow Report",
+                TRADE_CASHFLOWS_TEMPLATE,
+            )
+            .map(|d| {
+                d.with_description("Expected cashflows of one trade with discount factors and PVs")
+            }),
+        ];
+        for definition in standard {
+            registry
+                .register(definition.expect("standard templates parse"))
+                .expect("standard report ids are unique");
+        }
+        registry
+    }
+
+    /// Register a definition.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ReportError::DuplicateReport`] if the id is taken.
+    pub fn register(&mut self, definition: ReportDefinition) -> Result<(), ReportError> {
+        if self.get(&definition.id).is_some() {
+            return Err(ReportError::DuplicateReport(definition.id));
+        }
+        self.definitions.push(definition);
+        Ok(())
+    }
+
+    /// Look up a definition.
+    pub fn get(&self, id: &str) -> Option<&ReportDefinition> {
+        self.definitions.iter().find(|d| d.id == id)
+    }
+
+    /// All definitions, in registration order.
+    pub fn definitions(&self) -> &[ReportDefinition] {
+        &self.definitions
+    }
+
+    /// Render a registered report.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ReportError::UnknownReport`] for unregistered ids, otherwise
+    /// as [`ReportDefinition::render`].
+    pub fn render(
+        &self,
+        id: &str,
+        data: &ReportData,
+        format: ReportFormat,
+    ) -> Result<RenderedReport, ReportError> {
+        self.definition(id)?.render(data, format)
+    }
+
+    /// Render a registered report's template to text.
+    ///
+    /// # Errors
+    ///
+    /// As [`ReportRegistry::render`].
+    pub fn render_text(&self, id: &str, data: &ReportData) -> Result<String, ReportError> {
+        self.definition(id)?.render_text(data)
+    }
+
+    fn definition(&self, id: &str) -> Result<&ReportDefinition, ReportError> {
+        self.get(id)
+            .ok_or_else(|| ReportError::UnknownReport(id.to_string()))
+    }
+}
@@ -0,0 +1,495 @@
+//! Builders turning risk results into [`ReportData`].
+//!
+//! Table and column names produced here are what the standard report
+//! templates refer to.
+
+use std::collections::BTreeMap;
+
+use super::data::{CellValue, DataTable, ReportData};
+use pricer_pricing::greeks::StructuredGreeks;
+
+use crate::cashflows::{CashflowProjection, TradeCashflows};
+use crate::exposure::ExposureCalculator;
+use crate::scenarios::PortfolioGreeks;
+use crate::xva::PortfolioXva;
+
+/// Decimal places for currency amounts.
+const AMOUNT_DECIMALS: usize = 2;
+/// Decimal places for sensitivities.
+const GREEK_DECIMALS: usize = 4;
+/// Decimal places for time grid points in years.
+const TIME_DECIMALS: usize = 4;
+/// Decimal places for discount factors.
+const DISCOUNT_FACTOR_DECIMALS: usize = 6;
+
+/// Build data for the `xva` report.
+///
+/// Produces the tables `summary` (metric, value), `counterparties` and
+/// `netting_sets`. Counterparties and netting sets keep the order of the
+/// calculation result. The result's run metadata, if any, is added to the
+/// report metadata.
+pub fn xva_report_data(xva: &PortfolioXva, as_of: impl Into<String>) -> ReportData {
+    let mut summary = DataTable::new("summary", "Portfolio XVA")
+        .with_column("metric")
+        .with_number_column("value", AMOUNT_DECIMALS);
+    for (metric, value) in [
+        ("CVA", xva.cva),
+        ("DVA", xva.dva),
+        ("FCA", xva.fca),
+        ("FBA", xva.fba),
+        ("FVA", xva.fva()),
+        ("ColVA", xva.colva.total()),
+        ("Total XVA", xva.total_xva()),
+    ] {
+        push(&mut summary, vec![metric.into(), value.into()]);
+    }
+
+    let mut counterparties = DataTable::new("counterparties", "XVA by Counterparty")
+        .with_column("counterparty")
+        .with_number_column("cva", AMOUNT_DECIMALS)
+        .with_number_column("dva", AMOUNT_DECIMALS)
+        .with_number_column("fva", AMOUNT_DECIMALS)
+        .with_number_column("colva", AMOUNT_DECIMALS)
+        .with_number_column("total", AMOUNT_DECIMALS);
+    let mut netting_sets = DataTable::new("netting_sets", "XVA by Netting Set")
+        .with_column("netting_set")
+        .with_column("counterparty")
+        .with_number_column("cva", AMOUNT_DECIMALS)
+        .with_number_column("dva", AMOUNT_DECIMALS)
+        .with_number_column("fca", AMOUNT_DECIMALS)
+        .with_number_column("fba", AMOUNT_DECIMALS)
+        .with_number_column("colva", AMOUNT_DECIMALS)
+        .with_number_column("total", AMOUNT_DECIMALS);
+
+    for cp in &xva.by_counterparty {
+        push(
+            &mut counterparties,
+            vec![
+                cp.counterparty_id.as_str().into(),
+                cp.cva.into(),
+                cp.dva.into(),
+                cp.fva().into(),
+                cp.colva.total().into(),
+                cp.total_xva().into(),
+            ],
+        );
+        for ns in &cp.netting_set_xvas {
+            push(
+                &mut netting_sets,
+                vec![
+                    ns.netting_set_id.as_str().into(),
+                    ns.counterparty_id.as_str().into(),
+                    ns.cva.into(),
+                    ns.dva.into(),
+                    ns.fca.into(),
+                    ns.fba.into(),
+                    ns.colva.total().into(),
+                    ns.total_xva().into(),
+                ],
+            );
+        }
+    }
+
+    let data = ReportData::new("XVA Report", as_of)
+        .with_metadata("counterparties", xva.counterparty_count().to_string())
+        .with_metadata("netting_sets", xva.netting_set_count().to_string());
+    let data = match &xva.metadata {
+        Some(run) => data.with_run_metadata(run),
+        None => data,
+    };
+    data.with_table(summary)
+        .with_table(counterparties)
+        .with_table(netting_sets)
+}
+
+/// Exposure profile of one netting set (or portfolio) on a time grid.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExposureSeries {
+    /// Netting set or portfolio identifier
+    pub id: String,
+    /// Time points in years
+    pub time_grid: Vec<f64>,
+    /// Expected exposure at each time point
+    pub ee: Vec<f64>,
+    /// Potential future exposure at each time point
+    pub pfe: Vec<f64>,
+}
+
+impl ExposureSeries {
+    /// Create a series.
+    pub fn new(id: impl Into<String>, time_grid: Vec<f64>, ee: Vec<f64>, pfe: Vec<f64>) -> Self {
+        Self {
+            id: id.into(),
+            time_grid,
+            ee,
+            pfe,
+        }
+    }
+}
+
+/// Build data for the `exposure` report.
+///
+/// Produces `summary` (EPE, one-year effective EPE, peak EE and PFE per
+/// series) and `profiles` (one row per series and time point). Time points
+/// beyond the shorter of the EE and PFE vectors are left empty.
+pub fn exposure_report_data(series: &[ExposureSeries], as_of: impl Into<String>) -> ReportData {
+    let mut summary = DataTable::new("summary", "Exposure Summary")
+        .with_column("id")
+        .with_number_column("epe", AMOUNT_DECIMALS)
+        .with_number_column("effective_epe", AMOUNT_DECIMALS)
+        .with_number_column("peak_ee", AMOUNT_DECIMALS)
+        .with_number_column("peak_pfe", AMOUNT_DECIMALS);
+    let mut profiles = DataTable::new("profiles", "Exposure Profiles")
+        .with_column("id")
+        .with_number_column("time", TIME_DECIMALS)
+        .with_number_column("ee", AMOUNT_DECIMALS)
+        .with_number_column("pfe", AMOUNT_DECIMALS);
+
+    for s in series {
+        // Summary metrics need EE on every grid point used
+        let n = s.ee.len().min(s.time_grid.len());
+        let (ee, grid) = (&s.ee[..n], &s.time_grid[..n]);
+        let epe = ExposureCalculator::expected_positive_exposure(ee, grid);
+        let effective_epe = ExposureCalculator::effective_epe(ee, grid, 1.0);
+        let peak_ee = s.ee.iter().copied().fold(0.0, f64::max);
+        push(
+            &mut summary,
+            vec![
+                s.id.as_str().into(),
+                epe.into(),
+                effective_epe.into(),
+                peak_ee.into(),
+                ExposureCalculator::peak_pfe(&s.pfe).into(),
+            ],
+        );
+        for (i, t) in s.time_grid.iter().enumerate() {
+            push(
+                &mut profiles,
+                vec![
+                    s.id.as_str().into(),
+                    (*t).into(),
+                    s.ee.get(i).copied().into(),
+                    s.pfe.get(i).copied().into(),
+                ],
+            );
+        }
+    }
+
+    ReportData::new("Exposure Report", as_of)
+        .with_metadata("series", series.len().to_string())
+        .with_table(summary)
+        .with_table(profiles)
+}
+
+/// Build data for the `greeks` report from per-trade Greeks.
+///
+/// Produces `trades` (one row per entry, in input order), `totals` (the
+/// sum over trades) and `factors` (factor Greeks summed over trades, sorted
+/// by factor key).
+pub fn greeks_report_data(
+    greeks: &[(String, PortfolioGreeks<f64>)],
+    as_of: impl Into<String>,
+) -> ReportData {
+    let greek_columns = |table: DataTable| {
+        ["delta", "gamma", "vega", "theta", "rho"]
+            .into_iter()
+            .fold(table, |t, name| t.with_number_column(name, GREEK_DECIMALS))
+    };
+    let mut trades =
+        greek_columns(DataTable::new("trades", "Greeks by Trade").with_column("trade"));
+
+    let mut total = PortfolioGreeks::<f64>::new();
+    for (trade, g) in greeks {
+        push(
+            &mut trades,
+            vec![
+                trade.as_str().into(),
+                g.delta.into(),
+                g.gamma.into(),
+                g.vega.into(),
+                g.theta.into(),
+                g.rho.into(),
+            ],
+        );
+        total = total.add(g);
+    }
+
+    let mut totals = DataTable::new("totals", "Portfolio Greeks")
+        .with_column("greek")
+        .with_number_column("value", GREEK_DECIMALS);
+    for (name, value) in [
+        ("delta", total.delta),
+        ("gamma", total.gamma),
+        ("vega", total.vega),
+        ("theta", total.theta),
+        ("rho", total.rho),
+    ] {
+        push(&mut totals, vec![name.into(), value.into()]);
+    }
+
+    let mut factors = DataTable::new("factors", "Greeks by Risk Factor")
+        .with_column("factor")
+        .with_number_column("value", GREEK_DECIMALS);
+    let sorted: BTreeMap<&String, &f64> = total.factor_greeks().iter().collect();
+    for (factor, value) in sorted {
+        push(&mut factors, vec![factor.as_str().into(), (*value).into()]);
+    }
+
+    ReportData::new("Greeks Report", as_of)
+        .with_metadata("trades", greeks.len().to_string())
+        .with_table(totals)
+        .with_table(trades)
+        .with_table(factors)
+}
+
+/// Build the long-format `sensitivities` table from structured Greeks.
+///
+/// One row per trade and sensitivity, in input order, with the risk factor,
+/// bucket, quoting unit, currency of measure and bump specification spelled
+/// out. Append it to [`greeks_report_data`] output so that every format
+/// carries the conventions alongside the numbers.
+pub fn sensitivities_table(greeks: &[(String, StructuredGreeks)]) -> DataTable {
+    let mut table = DataTable::new("sensitivities", "Sensitivities")
+        .with_column("trade")
+        .with_column("greek")
+        .with_column("risk_factor")
+        .with_column("bucket")
+        .with_number_column("value", GREEK_DECIMALS)
+        .with_column("unit")
+        .with_column("currency")
+        .with_column("bump");
+    for (trade, g) in greeks {
+        for s in &g.sensitivities {
+            push(
+                &mut table,
+                vec![
+                    trade.as_str().into(),
+                    s.greek.name().into(),
+                    s.risk_factor.as_str().into(),
+                    s.bucket.as_deref().unwrap_or("").into(),
+                    s.value.into(),
+                    s.unit.label().into(),
+                    g.currency.as_str().into(),
+                    s.bump.to_string().into(),
+                ],
+            );
+        }
+    }
+    table
+}
+
+/// Build data for the `cashflows` report from a cashflow projection.
+///
+/// Produces `ladder` (one row per currency and bucket of `buckets`, see
+/// [`CashflowProjection::ladder`]), `by_date` (net amount per date and
+/// currency) and `cashflows` (one row per projected cashflow). Trades whose
+/// cashflows could not all be projected are listed in the `unprojected`
+/// metadata.
+pub fn cashflow_report_data(
+    projection: &CashflowProjection,
+    buckets: &[(&str, f64)],
+) -> ReportData {
+    let mut ladder = DataTable::new("ladder", "Liquidity Ladder")
+        .with_column("currency")
+        .with_column("bucket")
+        .with_number_column("inflows", AMOUNT_DECIMALS)
+        .with_number_column("outflows", AMOUNT_DECIMALS)
+        .with_number_column("net", AMOUNT_DECIMALS)
+        .with_number_column("cumulative", AMOUNT_DECIMALS);
+    for row in projection.ladder(buckets) {
+        push(
+            &mut ladder,
+            vec![
+                row.currency.code().into(),
+                row.bucket.into(),
+                row.inflows.into(),
+                row.outflows.into(),
+                row.net.into(),
+                row.cumulative.into(),
+            ],
+        );
+    }
+
+    let mut by_date = DataTable::new("by_date", "Net Cashflows by Date")
+        .with_column("date")
+        .with_column("currency")
+        .with_number_column("amount", AMOUNT_DECIMALS);
+    for dated in projection.by_date() {
+        push(
+            &mut by_date,
+            vec![
+                dated.date.to_string().into(),
+                dated.currency.code().into(),
+                dated.amount.into(),
+            ],
+        );
+    }
+
+    let mut cashflows = DataTable::new("cashflows", "Projected Cashflows")
+        .with_column("date")
+        .with_number_column("time", TIME_DECIMALS)
+        .with_column("trade")
+        .with_column("counterparty")
+        .with_column("kind")
+        .with_column("currency")
+        .with_number_column("amount", AMOUNT_DECIMALS);
+    for cf in &projection.cashflows {
+        push(
+            &mut cashflows,
+            vec![
+                cf.date.to_string().into(),
+                cf.time.into(),
+                cf.trade_id.as_str().into(),
+                cf.counterparty_id.as_str().into(),
+                cf.kind.as_str().into(),
+                cf.currency.code().into(),
+                cf.amount.into(),
+            ],
+        );
+    }
+
+    let mut data = ReportData::new("Cashflow Projection Report", projection.as_of.to_string())
+        .with_metadata("cashflows", projection.cashflows.len().to_string());
+    if !projection.unprojected.is_empty() {
+        let ids: Vec<&str> = projection
+            .unprojected
+            .iter()
+            .map(|id| id.as_str())
+            .collect();
+        data = data.with_metadata("unprojected", ids.join(", "));
+    }
+    data.with_table(ladder)
+        .with_table(by_date)
+        .with_table(cashflows)
+}
+
+/// Build data for the `trade_cashflows` report from one trade's
+/// decomposition.
+///
+/// Produces `cashflows` (one row per expected cashflow with its discount
+/// factor and present value), with the trade, counterparty and total
+/// present value in the metadata.
+pub fn trade_cashflow_report_data(decomposition: &TradeCashflows) -> ReportData {
+    let mut cashflows = DataTable::new("cashflows", "Expected Cashflows")
+        .with_column("date")
+        .with_number_column("time", TIME_DECIMALS)
+        .with_column("kind")
+        .with_column("currency")
+        .with_number_column("amount", AMOUNT_DECIMALS)
+        .with_number_column("discount_factor", DISCOUNT_FACTOR_DECIMALS)
+        .with_number_column("present_value", AMOUNT_DECIMALS);
+    for cf in &decomposition.cashflows {
+        push(
+            &mut cashflows,
+            vec![
+                cf.payment_date.to_string().into(),
+                cf.payment_time.into(),
+                cf.kind.as_str().into(),
+                cf.currency.code().into(),
+                cf.amount.into(),
+                cf.discount_factor.into(),
+                cf.present_value.into(),
+            ],
+        );
+    }
+
+    ReportData::new("Trade Cashflow Report", decomposition.as_of.to_string())
+        .with_metadata("trade", decomposition.trade_id.as_str())
+        .with_metadata("counterparty", decomposition.counterparty_id.as_str())
+        .with_metadata(
+            "present_value",
+            format!("{:.*}", AMOUNT_DECIMALS, decomposition.present_value()),
+        )
+        .with_table(cashflows)
+}
+
+/// Push a row whose width is correct by construction.
+fn push(table: &mut DataTable, row: Vec<CellValue>) {
+    table
+        .push_row(row)
+        .expect("row matches the table's columns");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exposure_summary_metrics() {
+        let series = ExposureSeries::new(
+            "NS1",
+            vec![0.0, 0.5, 1.0],
+            vec![0.0, 10.0, 20.0],
+            vec![0.0, 30.0, 50.0],
+        );
+        let data = exposure_report_data(&[series], "2026-10-15");
+        let summary = data.table("summary").unwrap();
+        // Trapezoidal average of the EE profile
+        assert_eq!(summary.rows[0][1], CellValue::Number(10.0));
+        assert_eq!(summary.rows[0][3], CellValue::Number(20.0));
+        assert_eq!(summary.rows[0][4], CellValue::Number(50.0));
+        assert_eq!(data.table("profiles").unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_xva_report_carries_run_metadata() {
+        let xva = PortfolioXva::default();
+        assert_eq!(xva_report_data(&xva, "d").metadata("seed"), None);
+
+        let xva = xva.with_seed(42);
+        let data = xva_report_data(&xva, "d");
+        assert_eq!(data.metadata("seed"), Some("42"));
+        assert_eq!(data.metadata("netting_sets"), Some("0"));
+    }
+
+    #[test]
+    fn test_exposure_profiles_pad_short_vectors() {
+        let series = ExposureSeries::new("NS1", vec![0.0, 1.0], vec![1.0], vec![]);
+        let data = exposure_report_data(&[series], "d");
+        let profiles = data.table("profiles").unwrap();
+        assert_eq!(profiles.rows[1][2], CellValue::Empty);
+        assert_eq!(profiles.rows[1][3], CellValue::Empty);
+    }
+
+    #[test]
+    fn test_greeks_totals_and_factors() {
+        let mut a = PortfolioGreeks::with_values(1.0, 0.1, 2.0, -0.5, 0.3);
+        a.add_factor_greek("USD.delta", 1.0);
+        let mut b = PortfolioGreeks::with_values(-0.5, 0.2, 1.0, -0.25, 0.1);
+        b.add_factor_greek("USD.delta", 0.5);
+        b.add_factor_greek("EUR.delta", 2.0);
+        let data = greeks_report_data(&[("T1".into(), a), ("T2".into(), b)], "d");
+
+        let totals = data.table("totals").unwrap();
+        assert_eq!(totals.rows[0][1], CellValue::Number(0.5));
+        let factors = data.table("factors").unwrap();
+        assert_eq!(factors.rows[0][0], CellValue::from("EUR.delta"));
+        assert_eq!(factors.rows[1][1], CellValue::Number(1.5));
+    }
+
+    #[test]
+    fn test_sensitivities_table_spells_out_conventions() {
+        use pricer_pricing::greeks::{BumpSpec, Sensitivity};
+        use pricer_pricing::mc::Greek;
+
+        let g = StructuredGreeks::new(1.0, 0.0, "EUR").with_sensitivity(
+            Sensitivity::new(
+                Greek::Rho,
+                "EUR.rate",
+                -12.5,
+                BumpSpec::Absolute { size: 1e-4 },
+            )
+            .with_bucket("5Y"),
+        );
+        let table = sensitivities_table(&[("T1".into(), g)]);
+
+        assert_eq!(table.len(), 1);
+        let row = &table.rows[0];
+        assert_eq!(row[1], CellValue::from("rho"));
+        assert_eq!(row[3], CellValue::from("5Y"));
+        assert_eq!(row[4], CellValue::Number(-12.5));
+        assert_eq!(row[5], CellValue::from("per 1bp"));
+        assert_eq!(row[6], CellValue::from("EUR"));
+        assert_eq!(row[7], CellValue::from("absolute 0.0001"));
+    }
+}
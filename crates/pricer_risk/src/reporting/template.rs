@@ -0,0 +1,428 @@
+//! Minimal text template engine for report layouts.
+//!
+//! Templates are plain text with `{{ ... }}` tags:
+//!
+//! | Tag | Expands to |
+//! |-----|------------|
+//! | `{{title}}`, `{{as_of}}` | Report title and valuation date |
+//! | `{{meta.KEY}}` | Metadata value `KEY` |
+//! | `{{table NAME}}` | Table `NAME` as aligned columns |
+//! | `{{sum NAME.COLUMN}}` | Sum of a numeric column, with its decimals |
+//! | `{{#each NAME}} ... {{/each}}` | Body repeated per row of table `NAME` |
+//!
+//! Inside an `each` block, `{{COLUMN}}` expands to the row's cell and
+//! `{{@index}}` to the one-based row number. Blocks do not nest. Unknown
+//! variables, tables and columns are errors rather than blanks, so a typo
+//! in a report definition fails loudly.
+
+use std::fmt::Write as _;
+
+use super::data::{CellValue, DataTable, ReportData};
+use super::ReportError;
+
+/// A parsed template node.
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Text(String),
+    Var(String),
+    Table(String),
+    Sum { table: String, column: String },
+    Each { table: String, body: Vec<Node> },
+}
+
+/// Parsed report template.
+///
+/// # Examples
+///
+/// ```
+/// use pricer_risk::reporting::{DataTable, ReportData, Template};
+///
+/// let mut table = DataTable::new("trades", "Trades")
+///     .with_column("id")
+///     .with_number_column("pv", 2);
+/// table.push_row(vec!["T1".into(), 10.0.into()]).unwrap();
+/// table.push_row(vec!["T2".into(), 2.5.into()]).unwrap();
+/// let data = ReportData::new("Book", "2026-10-15").with_table(table);
+///
+/// let template = Template::parse(
+///     "{{title}}\n{{#each trades}}{{@index}}. {{id}} {{pv}}\n{{/each}}Total {{sum trades.pv}}\n",
+/// )
+/// .unwrap();
+/// assert_eq!(
+///     template.render(&data).unwrap(),
+///     "Book\n1. T1 10.00\n2. T2 2.50\nTotal 12.50\n"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    source: String,
+    nodes: Vec<Node>,
+}
+
+impl Template {
+    /// Parse template text.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ReportError::Template`] for unterminated tags, unbalanced or
+    /// nested `each` blocks and malformed tag arguments.
+    pub fn parse(source: &str) -> Result<Self, ReportError> {
+        let mut nodes: Vec<Node> = Vec::new();
+        // Open `each` block: table name and the nodes collected so far
+        let mut block: Option<(String, Vec<Node>)> = None;
+        let mut rest = source;
+
+        while let Some(start) = rest.find("{{") {
+            let (text, after) = rest.split_at(start);
+            let end = after.find("}}").ok_or_else(|| {
+                template_error(format!("unterminated tag at '{}'", preview(after)))
+            })?;
+            let tag = after[2..end].trim();
+            rest = &after[end + 2..];
+
+            let target = match block.as_mut() {
+                Some((_, body)) => body,
+                None => &mut nodes,
+            };
+            if !text.is_empty() {
+                target.push(Node::Text(text.to_string()));
+            }
+
+            let (keyword, arg) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+            match keyword {
+                "#each" => {
+                    let table = single_argument("#each", arg)?;
+                    if block.is_some() {
+                        return Err(template_error("nested {{#each}} blocks are not supported"));
+                    }
+                    block = Some((table, Vec::new()));
+                }
+                "/each" if arg.is_empty() => {
+                    let (table, body) = block
+                        .take()
+                        .ok_or_else(|| template_error("{{/each}} without matching {{#each}}"))?;
+                    nodes.push(Node::Each { table, body });
+                }
+                "table" => target.push(Node::Table(single_argument("table", arg)?)),
+                "sum" => {
+                    let arg = single_argument("sum", arg)?;
+                    let (table, column) = arg.split_once('.').ok_or_else(|| {
+                        template_error(format!("sum expects TABLE.COLUMN, got '{}'", arg))
+                    })?;
+                    target.push(Node::Sum {
+                        table: table.to_string(),
+                        column: column.to_string(),
+                    });
+                }
+                _ if !keyword.is_empty() && arg.is_empty() => {
+                    target.push(Node::Var(keyword.to_string()))
+                }
+                _ => return Err(template_error(format!("invalid tag '{{{{{}}}}}'", tag))),
+            }
+        }
+
+        if let Some((table, _)) = block {
+            return Err(template_error(format!(
+                "{{{{#each {}}}}} is not closed",
+                table
+            )));
+        }
+        if !rest.is_empty() {
+            nodes.push(Node::Text(rest.to_string()));
+        }
+
+        Ok(Self {
+            source: source.to_string(),
+            nodes,
+        })
+    }
+
+    /// Original template text.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Names of the tables the template refers to, in order of first use.
+    pub fn referenced_tables(&self) -> Vec<&str> {
+        fn collect<'a>(nodes: &'a [Node], out: &mut Vec<&'a str>) {
+            for node in nodes {
+                let name = match node {
+                    Node::Table(t) | Node::Sum { table: t, .. } => t,
+                    Node::Each { table, body } => {
+                        if !out.contains(&table.as_str()) {
+                            out.push(table);
+                        }
+                        collect(body, out);
+                        continue;
+                    }
+                    Node::Text(_) | Node::Var(_) => continue,
+                };
+                if !out.contains(&name.as_str()) {
+                    out.push(name);
+                }
+            }
+        }
+        let mut out = Vec::new();
+        collect(&self.nodes, &mut out);
+        out
+    }
+
+    /// Render the template against report data.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ReportError::MissingTable`] for unknown tables and
+    /// [`ReportError::Template`] for unknown variables or columns.
+    pub fn render(&self, data: &ReportData) -> Result<String, ReportError> {
+        let mut out = String::new();
+        render_nodes(&self.nodes, data, None, &mut out)?;
+        Ok(out)
+    }
+}
+
+/// Row context inside an `each` block.
+struct RowContext<'a> {
+    table: &'a DataTable,
+    row: usize,
+}
+
+fn render_nodes(
+    nodes: &[Node],
+    data: &ReportData,
+    row: Option<&RowContext<'_>>,
+    out: &mut String,
+) -> Result<(), ReportError> {
+    for node in nodes {
+        match node {
+            Node::Text(text) => out.push_str(text),
+            Node::Var(name) => out.push_str(&resolve_var(name, data, row)?),
+            Node::Table(name) => out.push_str(&format_table(find_table(data, name)?)),
+            Node::Sum { table, column } => {
+                let t = find_table(data, table)?;
+                let index = find_column(t, column)?;
+                let decimals = t.columns[index].decimals;
+                out.push_str(&CellValue::Number(t.column_sum(index)).format(decimals));
+            }
+            Node::Each { table, body } => {
+                let t = find_table(data, table)?;
+                for index in 0..t.len() {
+                    let ctx = RowContext {
+                        table: t,
+                        row: index,
+                    };
+                    render_nodes(body, data, Some(&ctx), out)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn resolve_var(
+    name: &str,
+    data: &ReportData,
+    row: Option<&RowContext<'_>>,
+) -> Result<String, ReportError> {
+    if let Some(ctx) = row {
+        if name == "@index" {
+            return Ok((ctx.row + 1).to_string());
+        }
+        if let Some(column) = ctx.table.column_index(name) {
+            return Ok(ctx.table.formatted(ctx.row, column));
+        }
+    }
+    match name {
+        "title" => Ok(data.title.clone()),
+        "as_of" => Ok(data.as_of.clone()),
+        _ => {
+            if let Some(key) = name.strip_prefix("meta.") {
+                return data
+                    .metadata(key)
+                    .map(str::to_string)
+                    .ok_or_else(|| template_error(format!("unknown metadata key '{}'", key)));
+            }
+            Err(template_error(format!("unknown variable '{}'", name)))
+        }
+    }
+}
+
+/// Lay a table out as aligned text columns.
+///
+/// Numeric columns are right-aligned, everything else left-aligned. Trailing
+/// spaces are trimmed so output is stable under editors that strip them.
+pub(crate) fn format_table(table: &DataTable) -> String {
+    let cells: Vec<Vec<String>> = (0..table.len())
+        .map(|r| {
+            (0..table.columns.len())
+                .map(|c| table.formatted(r, c))
+                .collect()
+        })
+        .collect();
+    let widths: Vec<usize> = table
+        .columns
+        .iter()
+        .enumerate()
+        .map(|(c, col)| {
+            cells
+                .iter()
+                .map(|row| row[c].chars().count())
+                .chain(std::iter::once(col.name.chars().count()))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let numeric: Vec<bool> = (0..table.columns.len())
+        .map(|c| {
+            !table.rows.is_empty()
+                && table
+                    .rows
+                    .iter()
+                    .all(|r| matches!(r[c], CellValue::Number(_) | CellValue::Integer(_)))
+        })
+        .collect();
+
+    let line = |values: &[String]| {
+        let mut s = String::new();
+        for (c, value) in values.iter().enumerate() {
+            if c > 0 {
+                s.push_str("  ");
+            }
+            if numeric[c] {
+                let _ = write!(s, "{:>width$}", value, width = widths[c]);
+            } else {
+                let _ = write!(s, "{:<width$}", value, width = widths[c]);
+            }
+        }
+        s.trim_end().to_string()
+    };
+
+    let header: Vec<String> = table.columns.iter().map(|c| c.name.clone()).collect();
+    let rule: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+    let mut out = String::new();
+    out.push_str(&line(&header));
+    out.push('\n');
+    out.push_str(&line(&rule));
+    out.push('\n');
+    for row in &cells {
+        out.push_str(&line(row));
+        out.push('\n');
+    }
+    out
+}
+
+fn find_table<'a>(data: &'a ReportData, name: &str) -> Result<&'a DataTable, ReportError> {
+    data.table(name)
+        .ok_or_else(|| ReportError::MissingTable(name.to_string()))
+}
+
+fn find_column(table: &DataTable, name: &str) -> Result<usize, ReportError> {
+    table
+        .column_index(name)
+        .ok_or_else(|| template_error(format!("table '{}' has no column '{}'", table.name, name)))
+}
+
+fn single_argument(tag: &str, arg: &str) -> Result<String, ReportError> {
+    let arg = arg.trim();
+    if arg.is_empty() || arg.contains(char::is_whitespace) {
+        return Err(template_error(format!(
+            "{{{{{}}}}} expects exactly one argument",
+            tag
+        )));
+    }
+    Ok(arg.to_string())
+}
+
+fn template_error(message: impl Into<String>) -> ReportError {
+    ReportError::Template {
+        message: message.into(),
+    }
+}
+
+fn preview(text: &str) -> String {
+    text.chars().take(20).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data() -> ReportData {
+        let mut table = DataTable::new("rows", "Rows")
+            .with_column("name")
+            .with_number_column("value", 1);
+        table.push_row(vec!["alpha".into(), 1.25.into()]).unwrap();
+        table.push_row(vec!["b".into(), (-20.0).into()]).unwrap();
+        ReportData::new("Title", "2026-10-15")
+            .with_metadata("desk", "Rates")
+            .with_table(table)
+    }
+
+    #[test]
+    fn test_variables_and_metadata() {
+        let t = Template::parse("{{ title }} / {{as_of}} / {{meta.desk}}").unwrap();
+        assert_eq!(t.render(&data()).unwrap(), "Title / 2026-10-15 / Rates");
+    }
+
+    #[test]
+    fn test_table_layout_aligns_columns() {
+        let t = Template::parse("{{table rows}}").unwrap();
+        assert_eq!(
+            t.render(&data()).unwrap(),
+            "name   value\n-----  -----\nalpha    1.2\nb      -20.0\n"
+        );
+    }
+
+    #[test]
+    fn test_each_and_sum() {
+        let t = Template::parse(
+            "{{#each rows}}[{{@index}}:{{name}}={{value}}]{{/each}} {{sum rows.value}}",
+        )
+        .unwrap();
+        assert_eq!(t.render(&data()).unwrap(), "[1:alpha=1.2][2:b=-20.0] -18.8");
+        assert_eq!(t.referenced_tables(), vec!["rows"]);
+    }
+
+    #[test]
+    fn test_each_falls_back_to_report_variables() {
+        let t = Template::parse("{{#each rows}}{{title}}{{/each}}").unwrap();
+        assert_eq!(t.render(&data()).unwrap(), "TitleTitle");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for source in [
+            "{{title",
+            "{{#each rows}}",
+            "{{/each}}",
+            "{{#each a}}{{#each b}}{{/each}}{{/each}}",
+            "{{sum rows}}",
+            "{{table}}",
+            "{{two words}}",
+        ] {
+            assert!(
+                matches!(Template::parse(source), Err(ReportError::Template { .. })),
+                "'{}' should fail to parse",
+                source
+            );
+        }
+    }
+
+    #[test]
+    fn test_render_errors() {
+        let d = data();
+        assert!(matches!(
+            Template::parse("{{table missing}}").unwrap().render(&d),
+            Err(ReportError::MissingTable(_))
+        ));
+        assert!(matches!(
+            Template::parse("{{unknown}}").unwrap().render(&d),
+            Err(ReportError::Template { .. })
+        ));
+        assert!(matches!(
+            Template::parse("{{sum rows.nope}}").unwrap().render(&d),
+            Err(ReportError::Template { .. })
+        ));
+        // Column variables are only visible inside an each block
+        assert!(Template::parse("{{name}}").unwrap().render(&d).is_err());
+    }
+}
@@ -0,0 +1,411 @@
+//! Registry and golden-file tests for the standard reports.
+//!
+//! Regenerate the golden files with
+//! `NEUTRYX_UPDATE_GOLDEN=1 cargo test -p pricer_risk reporting`.
+
+use super::*;
+use crate::cashflows::{
+    Cashflow, CashflowKind, CashflowProjection, TradeCashflows, STANDARD_BUCKETS,
+};
+use crate::portfolio::{CounterpartyId, NettingSetId, TradeId};
+use crate::scenarios::PortfolioGreeks;
+use crate::xva::{ColvaBreakdown, CounterpartyXva, NettingSetXva, PortfolioXva};
+use pricer_core::types::time::Date;
+use pricer_core::types::Currency;
+use pricer_models::instruments::{CashflowType, ValuedCashflow};
+
+const GOLDEN_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/golden/reporting");
+
+const AS_OF: &str = "2026-10-15";
+
+/// Compare rendered output with a golden file, or rewrite it when
+/// `NEUTRYX_UPDATE_GOLDEN` is set.
+fn check_golden(file: &str, actual: &str) {
+    let path = format!("{}/{}", GOLDEN_DIR, file);
+    if std::env::var_os("NEUTRYX_UPDATE_GOLDEN").is_some() {
+        std::fs::create_dir_all(GOLDEN_DIR).unwrap();
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("cannot read golden file {}: {}", path, e));
+    assert!(
+        expected == actual,
+        "{} differs from golden file; rerun with NEUTRYX_UPDATE_GOLDEN=1 and review the diff\n\
+         --- expected\n{}\n--- actual\n{}",
+        file,
+        expected,
+        actual
+    );
+}
+
+fn sample_xva() -> PortfolioXva {
+    let ns = |ns: &str, cp: &str, cva, dva, fca, fba| {
+        NettingSetXva::new(
+            NettingSetId::new(ns),
+            CounterpartyId::new(cp),
+            cva,
+            dva,
+            fca,
+            fba,
+        )
+    };
+    let bank = CounterpartyXva::from_netting_sets(
+        CounterpartyId::new("CP-BANK"),
+        vec![
+            ns(
+                "NS-BANK-IRS",
+                "CP-BANK",
+                12_450.75,
+                3_120.5,
+                1_850.25,
+                420.0,
+            )
+            .with_colva(ColvaBreakdown {
+                received: 310.4,
+                posted: -95.2,
+            }),
+            ns("NS-BANK-FX", "CP-BANK", 4_210.1, 980.0, 615.5, 140.25),
+        ],
+    );
+    let fund = CounterpartyXva::from_netting_sets(
+        CounterpartyId::new("CP-FUND"),
+        vec![ns("NS-FUND", "CP-FUND", 8_930.0, 0.0, 1_205.8, 0.0)],
+    );
+    PortfolioXva::from_counterparties(vec![bank, fund])
+}
+
+fn sample_exposures() -> Vec<ExposureSeries> {
+    let grid = vec![0.0, 0.25, 0.5, 0.75, 1.0, 1.5, 2.0];
+    vec![
+        ExposureSeries::new(
+            "NS-BANK-IRS",
+            grid.clone(),
+            vec![
+                0.0, 41_250.0, 55_800.0, 61_300.0, 58_900.0, 44_100.0, 21_500.0,
+            ],
+            vec![
+                0.0, 98_400.0, 131_200.0, 146_900.0, 141_000.0, 108_300.0, 56_200.0,
+            ],
+        ),
+        ExposureSeries::new(
+            "NS-FUND",
+            grid,
+            vec![
+                0.0, 12_800.0, 19_400.0, 23_100.0, 25_600.0, 27_900.0, 29_300.0,
+            ],
+            vec![
+                0.0, 35_600.0, 52_300.0, 61_800.0, 68_400.0, 75_100.0, 79_900.0,
+            ],
+        ),
+    ]
+}
+
+fn sample_greeks() -> Vec<(String, PortfolioGreeks<f64>)> {
+    let mut swap = PortfolioGreeks::with_values(0.0, 0.0, 0.0, -12.5, 4_530.25);
+    swap.add_factor_greek("USD-SOFR.5Y", 3_980.5);
+    swap.add_factor_greek("USD-SOFR.2Y", 549.75);
+    let mut option = PortfolioGreeks::with_values(5_927.93, 186.51, 402.3, -35.125, 210.0);
+    option.add_factor_greek("SPX.spot", 5_927.93);
+    option.add_factor_greek("USD-SOFR.2Y", 210.0);
+    let mut forward = PortfolioGreeks::with_values(-1_500.0, 0.0, 0.0, 0.0, -87.6);
+    forward.add_factor_greek("EURUSD.spot", -1_500.0);
+    vec![
+        ("IRS-USD-5Y".to_string(), swap),
+        ("EQ-CALL-1Y".to_string(), option),
+        ("FXF-EURUSD-18M".to_string(), forward),
+    ]
+}
+
+#[test]
+fn test_xva_golden() {
+    let registry = ReportRegistry::standard();
+    let data = xva_report_data(&sample_xva(), AS_OF);
+    for format in [ReportFormat::Csv, ReportFormat::Json] {
+        let report = registry.render("xva", &data, format).unwrap();
+        check_golden(&report.file_name, report.as_text().unwrap());
+    }
+    check_golden("xva.txt", &registry.render_text("xva", &data).unwrap());
+}
+
+#[test]
+fn test_exposure_golden() {
+    let registry = ReportRegistry::standard();
+    let data = exposure_report_data(&sample_exposures(), AS_OF);
+    let report = registry
+        .render("exposure", &data, ReportFormat::Csv)
+        .unwrap();
+    check_golden(&report.file_name, report.as_text().unwrap());
+    check_golden(
+        "exposure.txt",
+        &registry.render_text("exposure", &data).unwrap(),
+    );
+}
+
+#[test]
+fn test_greeks_golden() {
+    let registry = ReportRegistry::standard();
+    let data = greeks_report_data(&sample_greeks(), AS_OF);
+    let report = registry
+        .render("greeks", &data, ReportFormat::Json)
+        .unwrap();
+    check_golden(&report.file_name, report.as_text().unwrap());
+    check_golden(
+        "greeks.txt",
+        &registry.render_text("greeks", &data).unwrap(),
+    );
+}
+
+fn sample_cashflows() -> CashflowProjection {
+    let as_of = Date::parse(AS_OF).unwrap();
+    let cf = |trade: &str, cp: &str, kind, time: f64, currency, amount| Cashflow {
+        trade_id: TradeId::new(trade),
+        counterparty_id: CounterpartyId::new(cp),
+        kind,
+        time,
+        date: as_of + (time * 365.0).round() as i64,
+        currency,
+        amount,
+    };
+    CashflowProjection {
+        as_of,
+        cashflows: vec![
+            cf(
+                "FWD-EUR",
+                "CP-FUND",
+                CashflowKind::NotionalExchange,
+                0.25,
+                Currency::EUR,
+                -1_087_500.0,
+            ),
+            cf(
+                "IRS-USD",
+                "CP-BANK",
+                CashflowKind::FixedCoupon,
+                0.5,
+                Currency::USD,
+                -212_500.0,
+            ),
+            cf(
+                "IRS-USD",
+                "CP-BANK",
+                CashflowKind::FloatingCoupon,
+                0.5,
+                Currency::USD,
+                198_750.0,
+            ),
+            cf(
+                "OPT-USD",
+                "CP-FUND",
+                CashflowKind::OptionExercise,
+                0.75,
+                Currency::USD,
+                45_000.0,
+            ),
+            cf(
+                "IRS-USD",
+                "CP-BANK",
+                CashflowKind::FixedCoupon,
+                1.0,
+                Currency::USD,
+                -212_500.0,
+            ),
+            cf(
+                "IRS-USD",
+                "CP-BANK",
+                CashflowKind::FloatingCoupon,
+                1.0,
+                Currency::USD,
+                203_100.0,
+            ),
+        ],
+        unprojected: vec![TradeId::new("OPT-EUR")],
+    }
+}
+
+#[test]
+fn test_cashflows_golden() {
+    let registry = ReportRegistry::standard();
+    let data = cashflow_report_data(&sample_cashflows(), STANDARD_BUCKETS);
+    assert_eq!(data.metadata("unprojected"), Some("OPT-EUR"));
+    let ladder = data.table("ladder").unwrap();
+    // EUR 3M, then USD 6M and 1Y accumulating to the USD total
+    assert_eq!(ladder.len(), 3);
+    assert_eq!(ladder.rows[2][5], CellValue::Number(21_850.0));
+
+    let report = registry
+        .render("cashflows", &data, ReportFormat::Csv)
+        .unwrap();
+    check_golden(&report.file_name, report.as_text().unwrap());
+    check_golden(
+        "cashflows.txt",
+        &registry.render_text("cashflows", &data).unwrap(),
+    );
+}
+
+fn sample_trade_cashflows() -> TradeCashflows {
+    let as_of = Date::parse(AS_OF).unwrap();
+    let cf = |kind, time: f64, amount: f64, discount_factor: f64| ValuedCashflow {
+        kind,
+        payment_time: time,
+        payment_date: as_of + (time * 365.0).round() as i64,
+        currency: Currency::USD,
+        amount,
+        discount_factor,
+        present_value: amount * discount_factor,
+    };
+    TradeCashflows {
+        as_of,
+        trade_id: TradeId::new("IRS-USD"),
+        counterparty_id: CounterpartyId::new("CP-BANK"),
+        cashflows: vec![
+            cf(CashflowType::FixedCoupon, 1.0, -350_000.0, 0.960789),
+            cf(CashflowType::FloatingCoupon, 1.0, 408_108.0, 0.960789),
+            cf(CashflowType::FixedCoupon, 2.0, -350_000.0, 0.923116),
+            cf(CashflowType::FloatingCoupon, 2.0, 408_108.0, 0.923116),
+        ],
+    }
+}
+
+#[test]
+fn test_trade_cashflows_golden() {
+    let registry = ReportRegistry::standard();
+    let data = trade_cashflow_report_data(&sample_trade_cashflows());
+    assert_eq!(data.metadata("trade"), Some("IRS-USD"));
+    assert_eq!(data.metadata("present_value"), Some("109469.95"));
+    assert_eq!(data.table("cashflows").unwrap().len(), 4);
+
+    let report = registry
+        .render("trade_cashflows", &data, ReportFormat::Csv)
+        .unwrap();
+    check_golden(&report.file_name, report.as_text().unwrap());
+    check_golden(
+        "trade_cashflows.txt",
+        &registry.render_text("trade_cashflows", &data).unwrap(),
+    );
+}
+
+#[test]
+fn test_pdf_golden() {
+    let registry = ReportRegistry::standard();
+    let data = xva_report_data(&sample_xva(), AS_OF);
+    let report = registry.render("xva", &data, ReportFormat::Pdf).unwrap();
+    assert_eq!(report.file_name, "xva_2026-10-15.pdf");
+    assert!(report.as_text().is_none());
+    // The PDF writer emits ASCII only, so the golden file is diffable text
+    check_golden(
+        &report.file_name,
+        &String::from_utf8(report.bytes.clone()).unwrap(),
+    );
+}
+
+#[test]
+fn test_xlsx_golden_manifest() {
+    let registry = ReportRegistry::standard();
+    let data = greeks_report_data(&sample_greeks(), AS_OF);
+    let report = registry
+        .render("greeks", &data, ReportFormat::Xlsx)
+        .unwrap();
+
+    // Binary golden files are opaque in review; pin the package contents
+    // instead: entry names, sizes and checksums, plus the first data sheet
+    let entries = xlsx::read_stored_zip(&report.bytes);
+    let mut manifest = String::new();
+    for (name, bytes) in &entries {
+        manifest.push_str(&format!(
+            "{} {} {:08x}\n",
+            name,
+            bytes.len(),
+            xlsx::crc32(bytes)
+        ));
+    }
+    check_golden("greeks_2026-10-15.xlsx.manifest", &manifest);
+
+    let totals = entries
+        .iter()
+        .find(|(name, _)| name == "xl/worksheets/sheet2.xml")
+        .unwrap();
+    check_golden(
+        "greeks_2026-10-15.xlsx.sheet2.xml",
+        std::str::from_utf8(&totals.1).unwrap(),
+    );
+}
+
+#[test]
+fn test_rendering_is_deterministic() {
+    let registry = ReportRegistry::standard();
+    let data = xva_report_data(&sample_xva(), AS_OF);
+    for format in ReportFormat::ALL {
+        let first = registry.render("xva", &data, format).unwrap();
+        let second = registry.render("xva", &data, format).unwrap();
+        assert_eq!(first, second, "{} output differs between runs", format);
+    }
+}
+
+#[test]
+fn test_standard_registry() {
+    let registry = ReportRegistry::standard();
+    let ids: Vec<&str> = registry
+        .definitions()
+        .iter()
+        .map(|d| d.id.as_str())
+        .collect();
+    assert_eq!(
+        ids,
+        vec!["xva", "exposure", "greeks", "cashflows", "trade_cashflows"]
+    );
+    assert_eq!(
+        registry.get("xva").unwrap().required_tables,
+        vec!["summary", "counterparties", "netting_sets"]
+    );
+}
+
+#[test]
+fn test_registry_errors() {
+    let mut registry = ReportRegistry::standard();
+    let data = xva_report_data(&sample_xva(), AS_OF);
+
+    assert_eq!(
+        registry.render("pnl", &data, ReportFormat::Csv),
+        Err(ReportError::UnknownReport("pnl".into()))
+    );
+    // XVA data lacks the exposure report's profiles table
+    assert_eq!(
+        registry.render("exposure", &data, ReportFormat::Json),
+        Err(ReportError::MissingTable("profiles".into()))
+    );
+
+    let csv_only = ReportDefinition::new("custom", "Custom", "{{table summary}}")
+        .unwrap()
+        .with_formats(&[ReportFormat::Csv]);
+    registry.register(csv_only.clone()).unwrap();
+    assert_eq!(
+        registry.register(csv_only),
+        Err(ReportError::DuplicateReport("custom".into()))
+    );
+    assert!(matches!(
+        registry.render("custom", &data, ReportFormat::Pdf),
+        Err(ReportError::UnsupportedFormat { .. })
+    ));
+    assert!(registry.render("custom", &data, ReportFormat::Csv).is_ok());
+}
+
+#[test]
+fn test_write_to_directory() {
+    let registry = ReportRegistry::standard();
+    let data = xva_report_data(&sample_xva(), AS_OF);
+    let report = registry.render("xva", &data, ReportFormat::Csv).unwrap();
+
+    let dir = std::env::temp_dir().join(format!("neutryx_reporting_{}", std::process::id()));
+    let path = report.write_to(dir.join("nested")).unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), report.bytes);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_report_format_parsing() {
+    assert_eq!("CSV".parse::<ReportFormat>(), Ok(ReportFormat::Csv));
+    assert_eq!("excel".parse::<ReportFormat>(), Ok(ReportFormat::Xlsx));
+    assert!("docx".parse::<ReportFormat>().is_err());
+    assert_eq!(ReportFormat::Pdf.to_string(), "pdf");
+}
@@ -0,0 +1,418 @@
+//! XLSX renderer.
+//!
+//! Writes the smallest Office Open XML package spreadsheet applications
+//! accept: content types, relationships, a workbook and one worksheet per
+//! table, preceded by a summary sheet holding the title, valuation date and
+//! metadata. Strings are stored inline and entries are written to an
+//! uncompressed ("stored") zip archive with a fixed timestamp so that the
+//! output is reproducible.
+
+use std::fmt::Write as _;
+
+use super::data::{CellValue, ReportData};
+
+/// Maximum sheet name length accepted by spreadsheet applications.
+const MAX_SHEET_NAME: usize = 31;
+
+/// Render report data as an XLSX workbook.
+pub(crate) fn render(data: &ReportData) -> Vec<u8> {
+    let mut sheets: Vec<(String, String)> = Vec::new();
+    sheets.push(("Summary".to_string(), summary_sheet(data)));
+    for table in &data.tables {
+        let name = unique_sheet_name(&table.title, &sheets);
+        let mut rows: Vec<Vec<Cell>> = Vec::new();
+        rows.push(
+            table
+                .columns
+                .iter()
+                .map(|c| Cell::Text(c.name.clone()))
+                .collect(),
+        );
+        for row in &table.rows {
+            rows.push(row.iter().map(Cell::from).collect());
+        }
+        sheets.push((name, worksheet(&rows)));
+    }
+
+    let mut zip = ZipWriter::default();
+    zip.add(
+        "[Content_Types].xml",
+        content_types(sheets.len()).as_bytes(),
+    );
+    zip.add("_rels/.rels", ROOT_RELS.as_bytes());
+    zip.add("xl/workbook.xml", workbook(&sheets).as_bytes());
+    zip.add(
+        "xl/_rels/workbook.xml.rels",
+        workbook_rels(sheets.len()).as_bytes(),
+    );
+    for (i, (_, xml)) in sheets.iter().enumerate() {
+        zip.add(&format!("xl/worksheets/sheet{}.xml", i + 1), xml.as_bytes());
+    }
+    zip.finish()
+}
+
+/// Worksheet cell.
+enum Cell {
+    Text(String),
+    Number(f64),
+    Empty,
+}
+
+impl From<&CellValue> for Cell {
+    fn from(value: &CellValue) -> Self {
+        match value {
+            CellValue::Text(s) => Self::Text(s.clone()),
+            CellValue::Number(v) if v.is_finite() => Self::Number(*v),
+            CellValue::Integer(v) => Self::Number(*v as f64),
+            CellValue::Number(_) | CellValue::Empty => Self::Empty,
+        }
+    }
+}
+
+fn summary_sheet(data: &ReportData) -> String {
+    let mut rows = vec![
+        vec![Cell::Text("Title".into()), Cell::Text(data.title.clone())],
+        vec![Cell::Text("As of".into()), Cell::Text(data.as_of.clone())],
+    ];
+    for (key, value) in &data.metadata {
+        rows.push(vec![Cell::Text(key.clone()), Cell::Text(value.clone())]);
+    }
+    worksheet(&rows)
+}
+
+fn worksheet(rows: &[Vec<Cell>]) -> String {
+    let mut xml = String::from(XML_DECL);
+    xml.push_str(
+        "<worksheet xmlns=\"http://schemas.openxmlformats.org/spreadsheetml/2006/main\"><sheetData>",
+    );
+    for (r, row) in rows.iter().enumerate() {
+        let _ = write!(xml, "<row r=\"{}\">", r + 1);
+        for (c, cell) in row.iter().enumerate() {
+            let reference = format!("{}{}", column_letters(c), r + 1);
+            match cell {
+                Cell::Text(s) => {
+                    let _ = write!(
+                        xml,
+                        "<c r=\"{}\" t=\"inlineStr\"><is><t xml:space=\"preserve\">{}</t></is></c>",
+                        reference,
+                        escape_xml(s)
+                    );
+                }
+                Cell::Number(v) => {
+                    let _ = write!(xml, "<c r=\"{}\"><v>{}</v></c>", reference, v);
+                }
+                Cell::Empty => {}
+            }
+        }
+        xml.push_str("</row>");
+    }
+    xml.push_str("</sheetData></worksheet>");
+    xml
+}
+
+fn workbook(sheets: &[(String, String)]) -> String {
+    let mut xml = String::from(XML_DECL);
+    xml.push_str(
+        "<workbook xmlns=\"http://schemas.openxmlformats.org/spreadsheetml/2006/main\" \
+         xmlns:r=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships\"><sheets>",
+    );
+    for (i, (name, _)) in sheets.iter().enumerate() {
+        let _ = write!(
+            xml,
+            "<sheet name=\"{}\" sheetId=\"{}\" r:id=\"rId{}\"/>",
+            escape_xml(name),
+            i + 1,
+            i + 1
+        );
+    }
+    xml.push_str("</sheets></workbook>");
+    xml
+}
+
+fn workbook_rels(sheet_count: usize) -> String {
+    let mut xml = String::from(XML_DECL);
+    xml.push_str(
+        "<Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">",
+    );
+    for i in 1..=sheet_count {
+        let _ = write!(
+            xml,
+            "<Relationship Id=\"rId{}\" \
+             Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet\" \
+             Target=\"worksheets/sheet{}.xml\"/>",
+            i, i
+        );
+    }
+    xml.push_str("</Relationships>");
+    xml
+}
+
+fn content_types(sheet_count: usize) -> String {
+    let mut xml = String::from(XML_DECL);
+    xml.push_str(
+        "<Types xmlns=\"http://schemas.openxmlformats.org/package/2006/content-types\">\
+         <Default Extension=\"rels\" ContentType=\"application/vnd.openxmlformats-package.relationships+xml\"/>\
+         <Default Extension=\"xml\" ContentType=\"application/xml\"/>\
+         <Override PartName=\"/xl/workbook.xml\" \
+         ContentType=\"application/vnd.openxmlformats-officedocument.spreadsheetml.sheet.main+xml\"/>",
+    );
+    for i in 1..=sheet_count {
+        let _ = write!(
+            xml,
+            "<Override PartName=\"/xl/worksheets/sheet{}.xml\" \
+             ContentType=\"application/vnd.openxmlformats-officedocument.spreadsheetml.worksheet+xml\"/>",
+            i
+        );
+    }
+    xml.push_str("</Types>");
+    xml
+}
+
+const XML_DECL: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n";
+
+const ROOT_RELS: &str = "<?xml version=\"1.0\" encoding=\"UTF-8\" standalone=\"yes\"?>\n\
+<Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">\
+<Relationship Id=\"rId1\" \
+Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument\" \
+Target=\"xl/workbook.xml\"/></Relationships>";
+
+/// Spreadsheet column letters for a zero-based index (0 -> A, 26 -> AA).
+fn column_letters(mut index: usize) -> String {
+    let mut letters = Vec::new();
+    loop {
+        letters.push(b'A' + (index % 26) as u8);
+        if index < 26 {
+            break;
+        }
+        index = index / 26 - 1;
+    }
+    letters.reverse();
+    String::from_utf8(letters).expect("ASCII letters")
+}
+
+/// Sheet name with forbidden characters replaced, truncated to the length
+/// limit and made unique among the sheets so far.
+fn unique_sheet_name(title: &str, existing: &[(String, String)]) -> String {
+    let base: String = title
+        .chars()
+        .map(|c| match c {
+            '[' | ']' | ':' | '*' | '?' | '/' | '\\' => '_',
+            c => c,
+        })
+        .take(MAX_SHEET_NAME)
+        .collect();
+    let base = if base.trim().is_empty() {
+        "Sheet".to_string()
+    } else {
+        base
+    };
+    let taken = |name: &str| existing.iter().any(|(n, _)| n.eq_ignore_ascii_case(name));
+    if !taken(&base) {
+        return base;
+    }
+    (2..)
+        .map(|n| {
+            let suffix = format!(" ({})", n);
+            let keep = MAX_SHEET_NAME - suffix.chars().count();
+            format!("{}{}", base.chars().take(keep).collect::<String>(), suffix)
+        })
+        .find(|name| !taken(name))
+        .expect("unbounded suffixes")
+}
+
+fn escape_xml(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            // Control characters other than tab and line breaks are not valid XML
+            c if (c as u32) < 0x20 && !matches!(c, '\t' | '\n' | '\r') => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Minimal zip writer producing stored (uncompressed) entries.
+#[derive(Default)]
+struct ZipWriter {
+    buffer: Vec<u8>,
+    central: Vec<u8>,
+    entries: u16,
+}
+
+/// DOS date for 1980-01-01, the earliest zip timestamp.
+const DOS_DATE: u16 = (1 << 5) | 1;
+
+impl ZipWriter {
+    fn add(&mut self, name: &str, data: &[u8]) {
+        let offset = self.buffer.len() as u32;
+        let crc = crc32(data);
+        let size = data.len() as u32;
+
+        let b = &mut self.buffer;
+        b.extend_from_slice(&0x0403_4b50u32.to_le_bytes());
+        b.extend_from_slice(&20u16.to_le_bytes()); // version needed
+        b.extend_from_slice(&0u16.to_le_bytes()); // flags
+        b.extend_from_slice(&0u16.to_le_bytes()); // method: stored
+        b.extend_from_slice(&0u16.to_le_bytes()); // time
+        b.extend_from_slice(&DOS_DATE.to_le_bytes());
+        b.extend_from_slice(&crc.to_le_bytes());
+        b.extend_from_slice(&size.to_le_bytes());
+        b.extend_from_slice(&size.to_le_bytes());
+        b.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        b.extend_from_slice(&0u16.to_le_bytes()); // extra length
+        b.extend_from_slice(name.as_bytes());
+        b.extend_from_slice(data);
+
+        let c = &mut self.central;
+        c.extend_from_slice(&0x0201_4b50u32.to_le_bytes());
+        c.extend_from_slice(&20u16.to_le_bytes()); // version made by
+        c.extend_from_slice(&20u16.to_le_bytes()); // version needed
+        c.extend_from_slice(&0u16.to_le_bytes()); // flags
+        c.extend_from_slice(&0u16.to_le_bytes()); // method
+        c.extend_from_slice(&0u16.to_le_bytes()); // time
+        c.extend_from_slice(&DOS_DATE.to_le_bytes());
+        c.extend_from_slice(&crc.to_le_bytes());
+        c.extend_from_slice(&size.to_le_bytes());
+        c.extend_from_slice(&size.to_le_bytes());
+        c.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        c.extend_from_slice(&0u16.to_le_bytes()); // extra length
+        c.extend_from_slice(&0u16.to_le_bytes()); // comment length
+        c.extend_from_slice(&0u16.to_le_bytes()); // disk number
+        c.extend_from_slice(&0u16.to_le_bytes()); // internal attributes
+        c.extend_from_slice(&0u32.to_le_bytes()); // external attributes
+        c.extend_from_slice(&offset.to_le_bytes());
+        c.extend_from_slice(name.as_bytes());
+
+        self.entries += 1;
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        let central_offset = self.buffer.len() as u32;
+        let central_size = self.central.len() as u32;
+        self.buffer.extend_from_slice(&self.central);
+
+        let b = &mut self.buffer;
+        b.extend_from_slice(&0x0605_4b50u32.to_le_bytes());
+        b.extend_from_slice(&0u16.to_le_bytes()); // disk number
+        b.extend_from_slice(&0u16.to_le_bytes()); // central directory disk
+        b.extend_from_slice(&self.entries.to_le_bytes());
+        b.extend_from_slice(&self.entries.to_le_bytes());
+        b.extend_from_slice(&central_size.to_le_bytes());
+        b.extend_from_slice(&central_offset.to_le_bytes());
+        b.extend_from_slice(&0u16.to_le_bytes()); // comment length
+        self.buffer
+    }
+}
+
+/// CRC-32 (IEEE 802.3) as used by zip.
+pub(crate) fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+/// Entries of a stored zip archive as `(name, data)` pairs.
+///
+/// Only understands archives written by [`ZipWriter`]; used by tests to
+/// check workbook contents.
+#[cfg(test)]
+pub(crate) fn read_stored_zip(bytes: &[u8]) -> Vec<(String, Vec<u8>)> {
+    let u16_at = |i: usize| u16::from_le_bytes([bytes[i], bytes[i + 1]]) as usize;
+    let u32_at = |i: usize| u32::from_le_bytes(bytes[i..i + 4].try_into().unwrap());
+
+    let mut entries = Vec::new();
+    let mut pos = 0;
+    while u32_at(pos) == 0x0403_4b50 {
+        let crc = u32_at(pos + 14);
+        let size = u32_at(pos + 18) as usize;
+        let name_len = u16_at(pos + 26);
+        let extra_len = u16_at(pos + 28);
+        let name_start = pos + 30;
+        let data_start = name_start + name_len + extra_len;
+        let name = String::from_utf8(bytes[name_start..name_start + name_len].to_vec()).unwrap();
+        let data = bytes[data_start..data_start + size].to_vec();
+        assert_eq!(crc32(&data), crc, "CRC mismatch for {}", name);
+        entries.push((name, data));
+        pos = data_start + size;
+    }
+    assert_eq!(
+        u32_at(pos),
+        0x0201_4b50,
+        "central directory follows entries"
+    );
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reporting::DataTable;
+
+    #[test]
+    fn test_crc32_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(crc32(b""), 0);
+    }
+
+    #[test]
+    fn test_column_letters() {
+        assert_eq!(column_letters(0), "A");
+        assert_eq!(column_letters(25), "Z");
+        assert_eq!(column_letters(26), "AA");
+        assert_eq!(column_letters(27), "AB");
+        assert_eq!(column_letters(701), "ZZ");
+        assert_eq!(column_letters(702), "AAA");
+    }
+
+    #[test]
+    fn test_sheet_names_are_sanitised_and_unique() {
+        let existing = vec![("Summary".to_string(), String::new())];
+        assert_eq!(unique_sheet_name("a/b:c", &existing), "a_b_c");
+        assert_eq!(unique_sheet_name("summary", &existing), "summary (2)");
+        let long = "x".repeat(40);
+        assert_eq!(unique_sheet_name(&long, &existing).len(), MAX_SHEET_NAME);
+    }
+
+    #[test]
+    fn test_workbook_package_structure() {
+        let mut table = DataTable::new("t", "Trades & Fees")
+            .with_column("id")
+            .with_number_column("pv", 2);
+        table.push_row(vec!["<T1>".into(), 1.5.into()]).unwrap();
+        let data = ReportData::new("R", "2026-10-15").with_table(table);
+
+        let bytes = render(&data);
+        let entries = read_stored_zip(&bytes);
+        let names: Vec<&str> = entries.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "[Content_Types].xml",
+                "_rels/.rels",
+                "xl/workbook.xml",
+                "xl/_rels/workbook.xml.rels",
+                "xl/worksheets/sheet1.xml",
+                "xl/worksheets/sheet2.xml",
+            ]
+        );
+        let workbook = String::from_utf8(entries[2].1.clone()).unwrap();
+        assert!(workbook.contains("<sheet name=\"Trades &amp; Fees\" sheetId=\"2\""));
+        let sheet = String::from_utf8(entries[5].1.clone()).unwrap();
+        assert!(sheet.contains("&lt;T1&gt;"));
+        assert!(sheet.contains("<c r=\"B2\"><v>1.5</v></c>"));
+
+        // Reproducible output
+        assert_eq!(bytes, render(&data));
+    }
+}
@@ -56,8 +56,10 @@ use serde::Serialize;
 /// - Requirement 1.1, 1.2, 1.3, 1.5
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(bound(serialize = "T: Serialize")))]
 pub struct GreeksResultByFactor<T: Float> {
     /// Greeks results keyed by risk factor.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_by_factor"))]
     by_factor: HashMap<RiskFactorId, GreeksResult<T>>,
 
     /// Computation mode used (AAD or Bump).
@@ -67,6 +69,20 @@ pub struct GreeksResultByFactor<T: Float> {
     computation_time_ns: u64,
 }
 
+/// Serialises the factor map as `[factor, greeks]` pairs, since risk factor
+/// identifiers cannot be JSON object keys.
+#[cfg(feature = "serde")]
+fn serialize_by_factor<T, S>(
+    by_factor: &HashMap<RiskFactorId, GreeksResult<T>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    T: Float + Serialize,
+    S: serde::Serializer,
+{
+    serializer.collect_seq(by_factor.iter())
+}
+
 impl<T: Float> GreeksResultByFactor<T> {
     /// Creates a new empty container with the specified computation mode.
     ///
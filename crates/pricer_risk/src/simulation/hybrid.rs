@@ -0,0 +1,1256 @@
+//! Hybrid rates, equity and FX scenario generation.
+//!
+//! Every factor is simulated under the domestic risk-neutral measure:
+//!
+//! ```text
+//! r(t)    = x(t) + φ(t)                      (Hull-White, G1++ form)
+//! dx      = -a x dt + σ dW_r
+//! dS / S  = (r - q) dt + √v dW_S            (v = σ² for GBM)
+//! dv      = κ (θ - v) dt + ξ √v dW_v         (Heston only)
+//! dX / X  = (r_d - r_f) dt + σ_X dW_X
+//! dx_f    = (-a_f x_f - ρ_{f,X} σ_f σ_X) dt + σ_f dW_f
+//! ```
+//!
+//! φ(t) fits the initial curve, so `E[D(t)] = P(0, t)` with
+//! `D(t) = exp(-∫₀ᵗ r(s) ds)`. The foreign short rate carries the quanto
+//! drift from its correlation with the FX rate, which keeps foreign assets
+//! converted into domestic currency martingales after discounting.
+//!
+//! The Hull-White states are stepped exactly; rate integrals use the
+//! trapezoidal rule and Heston variance uses full truncation, so a finer
+//! grid reduces discretisation bias.
+//!
+//! # Factor ordering
+//!
+//! The correlation matrix is indexed by factor, in this order:
+//!
+//! 1. the domestic short rate;
+//! 2. each equity in the order added: its spot, then its variance if the
+//!    equity follows Heston;
+//! 3. each FX rate in the order added: its spot, then its foreign short
+//!    rate.
+//!
+//! [`HybridSimulator::factor_labels`] lists the factors in that order.
+
+use pricer_core::market_data::curves::{CurveEnum, YieldCurve};
+use pricer_core::market_data::error::MarketDataError;
+use pricer_models::models::hybrid::{CholeskyFactor, CorrelationError, CorrelationMatrix};
+use pricer_pricing::rng::PricerRng;
+use rayon::prelude::*;
+use thiserror::Error;
+
+/// Time step used to difference the initial curve for instantaneous forwards.
+const FORWARD_BUMP: f64 = 1e-4;
+
+/// Errors from hybrid simulation.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum HybridError {
+    /// Hull-White mean reversion or volatility is invalid.
+    #[error("Invalid Hull-White parameters: {0}")]
+    InvalidRateModel(String),
+
+    /// An equity factor has invalid parameters.
+    #[error("Invalid equity factor {name}: {reason}")]
+    InvalidEquity {
+        /// Equity name
+        name: String,
+        /// What is wrong
+        reason: String,
+    },
+
+    /// An FX factor has invalid parameters.
+    #[error("Invalid FX factor {name}: {reason}")]
+    InvalidFx {
+        /// Currency pair name
+        name: String,
+        /// What is wrong
+        reason: String,
+    },
+
+    /// Two equities or two FX rates share a name.
+    #[error("Duplicate factor name: {0}")]
+    DuplicateFactor(String),
+
+    /// Correlation matrix dimension does not match the factor count.
+    #[error("Correlation matrix has dimension {got}, expected {expected}")]
+    CorrelationDimension {
+        /// Number of simulated factors
+        expected: usize,
+        /// Dimension supplied
+        got: usize,
+    },
+
+    /// Correlation matrix cannot be factorised.
+    #[error("Invalid correlation matrix: {0}")]
+    Correlation(#[from] CorrelationError),
+
+    /// Initial curve could not be evaluated.
+    #[error("Initial curve error: {0}")]
+    Curve(#[from] MarketDataError),
+
+    /// Time grid is empty, negative, non-finite or not increasing.
+    #[error("Time grid must be non-empty, non-negative and increasing")]
+    InvalidTimeGrid,
+
+    /// Bond maturity precedes the observation time.
+    #[error("Bond maturity {maturity} precedes observation time {time}")]
+    InvalidMaturity {
+        /// Observation time in years
+        time: f64,
+        /// Bond maturity in years
+        maturity: f64,
+    },
+
+    /// No paths were requested.
+    #[error("Number of paths must be positive")]
+    NoPaths,
+}
+
+/// Hull-White one-factor short rate fitted to an initial curve.
+///
+/// The short rate is split as `r(t) = x(t) + φ(t)`, where `x` is a
+/// zero-mean Ornstein-Uhlenbeck state and the deterministic shift `φ`
+/// reprices the initial curve.
+///
+/// # Examples
+///
+/// ```
+/// use pricer_core::market_data::curves::CurveEnum;
+/// use pricer_risk::simulation::HullWhiteFactor;
+///
+/// let rates = HullWhiteFactor::new(0.1, 0.01, CurveEnum::flat(0.03)).unwrap();
+///
+/// // With x = 0 a bond seen at t reprices close to the forward curve
+/// let bond = rates.zero_bond(1.0, 5.0, 0.0).unwrap();
+/// assert!((bond - (-0.03_f64 * 4.0).exp()).abs() < 1e-3);
+/// ```
+#[derive(Clone, Debug)]
+pub struct HullWhiteFactor {
+    mean_reversion: f64,
+    volatility: f64,
+    curve: CurveEnum<f64>,
+}
+
+impl HullWhiteFactor {
+    /// Creates a Hull-White factor.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HybridError::InvalidRateModel` unless the mean reversion
+    /// is positive and the volatility non-negative, both finite.
+    pub fn new(
+        mean_reversion: f64,
+        volatility: f64,
+        curve: impl Into<CurveEnum<f64>>,
+    ) -> Result<Self, HybridError> {
+        if !(mean_reversion.is_finite() && mean_reversion > 0.0) {
+            return Err(HybridError::InvalidRateModel(format!(
+                "mean reversion must be positive, got {mean_reversion}"
+            )));
+        }
+        if !(volatility.is_finite() && volatility >= 0.0) {
+            return Err(HybridError::InvalidRateModel(format!(
+                "volatility must be non-negative, got {volatility}"
+            )));
+        }
+        Ok(Self {
+            mean_reversion,
+            volatility,
+            curve: curve.into(),
+        })
+    }
+
+    /// Returns the mean reversion speed `a`.
+    #[inline]
+    pub fn mean_reversion(&self) -> f64 {
+        self.mean_reversion
+    }
+
+    /// Returns the short-rate volatility `σ`.
+    #[inline]
+    pub fn volatility(&self) -> f64 {
+        self.volatility
+    }
+
+    /// Returns the initial curve.
+    #[inline]
+    pub fn curve(&self) -> &CurveEnum<f64> {
+        &self.curve
+    }
+
+    /// Returns `B(t, T) = (1 - e^{-a(T - t)}) / a`, the sensitivity of
+    /// `ln P(t, T)` to the state `x(t)`.
+    pub fn bond_factor(&self, t: f64, maturity: f64) -> f64 {
+        let a = self.mean_reversion;
+        (1.0 - (-a * (maturity - t)).exp()) / a
+    }
+
+    /// Returns the zero-coupon bond price `P(t, T)` given the state `x(t)`.
+    ///
+    /// ```text
+    /// P(t, T) = P(0, T) / P(0, t) · exp(-B x - σ² (1 - e^{-2at}) B² / (4a))
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns `HybridError::InvalidMaturity` if `maturity < t`, and
+    /// `HybridError::Curve` if the initial curve cannot be evaluated.
+    pub fn zero_bond(&self, t: f64, maturity: f64, x: f64) -> Result<f64, HybridError> {
+        if maturity < t {
+            return Err(HybridError::InvalidMaturity { time: t, maturity });
+        }
+        let a = self.mean_reversion;
+        let sigma = self.volatility;
+        let b = self.bond_factor(t, maturity);
+        let convexity = sigma * sigma * (1.0 - (-2.0 * a * t).exp()) * b * b / (4.0 * a);
+        let forward = (self.log_discount(maturity)? - self.log_discount(t)?).exp();
+        Ok(forward * (-b * x - convexity).exp())
+    }
+
+    /// Returns the deterministic shift `φ(t) = f(0, t) + σ² (1 - e^{-at})² / (2a²)`.
+    fn shift(&self, t: f64) -> Result<f64, HybridError> {
+        let a = self.mean_reversion;
+        let sigma = self.volatility;
+        let forward = (self.log_discount(t)? - self.log_discount(t + FORWARD_BUMP)?) / FORWARD_BUMP;
+        let g = 1.0 - (-a * t).exp();
+        Ok(forward + sigma * sigma * g * g / (2.0 * a * a))
+    }
+
+    /// Returns `∫₀ᵗ φ(s) ds`.
+    fn shift_integral(&self, t: f64) -> Result<f64, HybridError> {
+        let a = self.mean_reversion;
+        let sigma = self.volatility;
+        let integral =
+            t - 2.0 * (1.0 - (-a * t).exp()) / a + (1.0 - (-2.0 * a * t).exp()) / (2.0 * a);
+        Ok(-self.log_discount(t)? + sigma * sigma * integral / (2.0 * a * a))
+    }
+
+    /// Returns `ln P(0, t)`, taking `P(0, t) = 1` for `t <= 0`.
+    fn log_discount(&self, t: f64) -> Result<f64, HybridError> {
+        if t <= 0.0 {
+            return Ok(0.0);
+        }
+        Ok(self.curve.discount_factor(t)?.ln())
+    }
+
+    /// Returns the exact OU step over `dt` with constant drift `drift`:
+    /// decay factor, mean increment and standard deviation.
+    fn step(&self, dt: f64, drift: f64) -> (f64, f64, f64) {
+        let a = self.mean_reversion;
+        let decay = (-a * dt).exp();
+        let mean = drift * (1.0 - decay) / a;
+        let std_dev = self.volatility * ((1.0 - decay * decay) / (2.0 * a)).sqrt();
+        (decay, mean, std_dev)
+    }
+}
+
+/// Equity price dynamics.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EquityDynamics {
+    /// Geometric Brownian motion with constant volatility.
+    Gbm {
+        /// Annualised volatility
+        volatility: f64,
+    },
+    /// Heston stochastic variance.
+    ///
+    /// The spot-variance correlation is taken from the simulator's
+    /// correlation matrix.
+    Heston {
+        /// Initial variance
+        v0: f64,
+        /// Mean reversion speed of the variance
+        kappa: f64,
+        /// Long-run variance
+        theta: f64,
+        /// Volatility of variance
+        xi: f64,
+    },
+}
+
+/// An equity underlying in a hybrid simulation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EquityFactor {
+    name: String,
+    spot: f64,
+    dividend_yield: f64,
+    dynamics: EquityDynamics,
+}
+
+impl EquityFactor {
+    /// Creates an equity factor with no dividend yield.
+    pub fn new(name: impl Into<String>, spot: f64, dynamics: EquityDynamics) -> Self {
+        Self {
+            name: name.into(),
+            spot,
+            dividend_yield: 0.0,
+            dynamics,
+        }
+    }
+
+    /// Sets the continuous dividend yield.
+    pub fn with_dividend_yield(mut self, dividend_yield: f64) -> Self {
+        self.dividend_yield = dividend_yield;
+        self
+    }
+
+    /// Returns the equity name.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the initial spot.
+    #[inline]
+    pub fn spot(&self) -> f64 {
+        self.spot
+    }
+
+    /// Returns the continuous dividend yield.
+    #[inline]
+    pub fn dividend_yield(&self) -> f64 {
+        self.dividend_yield
+    }
+
+    /// Returns the price dynamics.
+    #[inline]
+    pub fn dynamics(&self) -> EquityDynamics {
+        self.dynamics
+    }
+
+    /// Returns the number of Brownian factors this equity uses.
+    fn factor_count(&self) -> usize {
+        match self.dynamics {
+            EquityDynamics::Gbm { .. } => 1,
+            EquityDynamics::Heston { .. } => 2,
+        }
+    }
+
+    fn validate(&self) -> Result<(), HybridError> {
+        let invalid = |reason: &str| HybridError::InvalidEquity {
+            name: self.name.clone(),
+            reason: reason.to_string(),
+        };
+        if !(self.spot.is_finite() && self.spot > 0.0) {
+            return Err(invalid("spot must be positive"));
+        }
+        if !self.dividend_yield.is_finite() {
+            return Err(invalid("dividend yield must be finite"));
+        }
+        match self.dynamics {
+            EquityDynamics::Gbm { volatility } => {
+                if !(volatility.is_finite() && volatility >= 0.0) {
+                    return Err(invalid("volatility must be non-negative"));
+                }
+            }
+            EquityDynamics::Heston {
+                v0,
+                kappa,
+                theta,
+                xi,
+            } => {
+                if ![v0, kappa, theta, xi]
+                    .iter()
+                    .all(|p| p.is_finite() && *p >= 0.0)
+                {
+                    return Err(invalid("Heston parameters must be non-negative"));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An FX rate in a hybrid simulation, quoted as domestic currency per
+/// unit of foreign currency, with its foreign short rate.
+#[derive(Clone, Debug)]
+pub struct FxFactor {
+    name: String,
+    spot: f64,
+    volatility: f64,
+    foreign: HullWhiteFactor,
+}
+
+impl FxFactor {
+    /// Creates an FX factor with lognormal volatility `volatility` and
+    /// foreign short rate `foreign`.
+    pub fn new(
+        name: impl Into<String>,
+        spot: f64,
+        volatility: f64,
+        foreign: HullWhiteFactor,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            spot,
+            volatility,
+            foreign,
+        }
+    }
+
+    /// Returns the currency pair name.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the initial FX spot.
+    #[inline]
+    pub fn spot(&self) -> f64 {
+        self.spot
+    }
+
+    /// Returns the FX volatility.
+    #[inline]
+    pub fn volatility(&self) -> f64 {
+        self.volatility
+    }
+
+    /// Returns the foreign short-rate model.
+    #[inline]
+    pub fn foreign(&self) -> &HullWhiteFactor {
+        &self.foreign
+    }
+
+    fn validate(&self) -> Result<(), HybridError> {
+        let invalid = |reason: &str| HybridError::InvalidFx {
+            name: self.name.clone(),
+            reason: reason.to_string(),
+        };
+        if !(self.spot.is_finite() && self.spot > 0.0) {
+            return Err(invalid("spot must be positive"));
+        }
+        if !(self.volatility.is_finite() && self.volatility >= 0.0) {
+            return Err(invalid("volatility must be non-negative"));
+        }
+        Ok(())
+    }
+}
+
+/// Joint Monte Carlo simulation of rates, equity and FX.
+///
+/// # Examples
+///
+/// ```
+/// use pricer_core::market_data::curves::CurveEnum;
+/// use pricer_models::models::hybrid::CorrelationMatrix;
+/// use pricer_risk::simulation::{
+///     EquityDynamics, EquityFactor, FxFactor, HullWhiteFactor, HybridSimulator,
+/// };
+///
+/// let usd = HullWhiteFactor::new(0.05, 0.01, CurveEnum::flat(0.04)).unwrap();
+/// let eur = HullWhiteFactor::new(0.05, 0.008, CurveEnum::flat(0.02)).unwrap();
+///
+/// // Factors: USD rate, SPX, EURUSD, EUR rate
+/// let correlation = CorrelationMatrix::new(&[
+///     1.0, 0.2, 0.1, 0.5,
+///     0.2, 1.0, 0.3, 0.0,
+///     0.1, 0.3, 1.0, -0.2,
+///     0.5, 0.0, -0.2, 1.0,
+/// ], 4).unwrap();
+///
+/// let simulator = HybridSimulator::new(usd, 42)
+///     .with_equity(EquityFactor::new("SPX", 100.0, EquityDynamics::Gbm { volatility: 0.2 }))
+///     .with_fx(FxFactor::new("EURUSD", 1.1, 0.1, eur))
+///     .with_correlation(correlation);
+/// assert_eq!(simulator.factor_count(), 4);
+///
+/// let time_grid = [0.0, 0.25, 0.5, 0.75, 1.0];
+/// let paths = simulator.simulate(&time_grid, 500).unwrap();
+///
+/// assert_eq!(paths.n_paths(), 500);
+/// assert_eq!(paths.discount_factors()[0].len(), time_grid.len());
+/// assert_eq!(paths.equity("SPX").unwrap()[0][0], 100.0);
+/// assert_eq!(paths.fx("EURUSD").unwrap()[0][0], 1.1);
+/// ```
+#[derive(Clone, Debug)]
+pub struct HybridSimulator {
+    domestic: HullWhiteFactor,
+    equities: Vec<EquityFactor>,
+    fx: Vec<FxFactor>,
+    correlation: Option<CorrelationMatrix<f64>>,
+    seed: u64,
+}
+
+impl HybridSimulator {
+    /// Creates a simulator with only the domestic short rate and
+    /// independent factors.
+    ///
+    /// Path `p` draws from a generator seeded with `seed + p`, so results
+    /// do not depend on thread scheduling.
+    pub fn new(domestic: HullWhiteFactor, seed: u64) -> Self {
+        Self {
+            domestic,
+            equities: Vec::new(),
+            fx: Vec::new(),
+            correlation: None,
+            seed,
+        }
+    }
+
+    /// Adds an equity underlying.
+    pub fn with_equity(mut self, equity: EquityFactor) -> Self {
+        self.equities.push(equity);
+        self
+    }
+
+    /// Adds an FX rate and its foreign short rate.
+    pub fn with_fx(mut self, fx: FxFactor) -> Self {
+        self.fx.push(fx);
+        self
+    }
+
+    /// Sets the factor correlation matrix, ordered as described in the
+    /// [module documentation](self). Without one, factors are independent.
+    pub fn with_correlation(mut self, correlation: CorrelationMatrix<f64>) -> Self {
+        self.correlation = Some(correlation);
+        self
+    }
+
+    /// Returns the domestic short-rate model.
+    #[inline]
+    pub fn domestic(&self) -> &HullWhiteFactor {
+        &self.domestic
+    }
+
+    /// Returns the number of Brownian factors, i.e. the required
+    /// correlation matrix dimension.
+    pub fn factor_count(&self) -> usize {
+        1 + self
+            .equities
+            .iter()
+            .map(EquityFactor::factor_count)
+            .sum::<usize>()
+            + 2 * self.fx.len()
+    }
+
+    /// Returns a label per Brownian factor in correlation-matrix order.
+    pub fn factor_labels(&self) -> Vec<String> {
+        let mut labels = vec!["rate:domestic".to_string()];
+        for equity in &self.equities {
+            labels.push(format!("equity:{}", equity.name));
+            if let EquityDynamics::Heston { .. } = equity.dynamics {
+                labels.push(format!("variance:{}", equity.name));
+            }
+        }
+        for fx in &self.fx {
+            labels.push(format!("fx:{}", fx.name));
+            labels.push(format!("rate:{}", fx.name));
+        }
+        labels
+    }
+
+    /// Simulates `n_paths` joint scenarios observed on `time_grid`.
+    ///
+    /// The simulation starts at time 0; a grid that does not begin at 0 is
+    /// stepped to its first point before anything is recorded.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HybridError::InvalidTimeGrid` for an empty, negative or
+    /// non-increasing grid, `HybridError::NoPaths` for zero paths,
+    /// `HybridError::InvalidEquity`, `HybridError::InvalidFx` or
+    /// `HybridError::DuplicateFactor` for a bad factor, and
+    /// `HybridError::CorrelationDimension` or `HybridError::Correlation`
+    /// for a correlation matrix that does not fit the factors.
+    pub fn simulate(&self, time_grid: &[f64], n_paths: usize) -> Result<HybridPaths, HybridError> {
+        self.validate()?;
+        if time_grid.is_empty()
+            || time_grid.iter().any(|t| !t.is_finite())
+            || time_grid[0] < 0.0
+            || time_grid.windows(2).any(|w| w[1] <= w[0])
+        {
+            return Err(HybridError::InvalidTimeGrid);
+        }
+        if n_paths == 0 {
+            return Err(HybridError::NoPaths);
+        }
+
+        let n_factors = self.factor_count();
+        let cholesky = match &self.correlation {
+            Some(matrix) if matrix.dim() != n_factors => {
+                return Err(HybridError::CorrelationDimension {
+                    expected: n_factors,
+                    got: matrix.dim(),
+                })
+            }
+            Some(matrix) => matrix.cholesky()?,
+            None => CorrelationMatrix::identity(n_factors).cholesky()?,
+        };
+        let plan = self.plan(time_grid)?;
+
+        let simulated: Vec<PathValues> = (0..n_paths)
+            .into_par_iter()
+            .map(|p| self.simulate_path(p, &plan, &cholesky))
+            .collect();
+
+        let n_equities = self.equities.len();
+        let n_fx = self.fx.len();
+        let mut paths = HybridPaths {
+            time_grid: time_grid.to_vec(),
+            domestic: self.domestic.clone(),
+            domestic_shift: plan.domestic_shift,
+            discount_factors: Vec::with_capacity(n_paths),
+            domestic_state: Vec::with_capacity(n_paths),
+            equity_names: self.equities.iter().map(|e| e.name.clone()).collect(),
+            equities: vec![Vec::with_capacity(n_paths); n_equities],
+            fx_names: self.fx.iter().map(|f| f.name.clone()).collect(),
+            fx: vec![Vec::with_capacity(n_paths); n_fx],
+            foreign: self.fx.iter().map(|f| f.foreign.clone()).collect(),
+            foreign_shift: plan.foreign_shift,
+            foreign_state: vec![Vec::with_capacity(n_paths); n_fx],
+        };
+        for path in simulated {
+            paths.discount_factors.push(path.discount);
+            paths.domestic_state.push(path.domestic_state);
+            for (out, values) in paths.equities.iter_mut().zip(path.equities) {
+                out.push(values);
+            }
+            for (out, values) in paths.fx.iter_mut().zip(path.fx) {
+                out.push(values);
+            }
+            for (out, values) in paths.foreign_state.iter_mut().zip(path.foreign_state) {
+                out.push(values);
+            }
+        }
+        Ok(paths)
+    }
+
+    fn validate(&self) -> Result<(), HybridError> {
+        for (i, equity) in self.equities.iter().enumerate() {
+            equity.validate()?;
+            if self.equities[..i].iter().any(|e| e.name == equity.name) {
+                return Err(HybridError::DuplicateFactor(equity.name.clone()));
+            }
+        }
+        for (i, fx) in self.fx.iter().enumerate() {
+            fx.validate()?;
+            if self.fx[..i].iter().any(|f| f.name == fx.name) {
+                return Err(HybridError::DuplicateFactor(fx.name.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the correlation between factors `i` and `j`.
+    fn correlation(&self, i: usize, j: usize) -> f64 {
+        match &self.correlation {
+            Some(matrix) => matrix.get(i, j),
+            None if i == j => 1.0,
+            None => 0.0,
+        }
+    }
+
+    /// Returns the index of the first FX factor.
+    fn fx_offset(&self) -> usize {
+        1 + self
+            .equities
+            .iter()
+            .map(EquityFactor::factor_count)
+            .sum::<usize>()
+    }
+
+    /// Precomputes the path-independent quantities of every step.
+    fn plan(&self, time_grid: &[f64]) -> Result<SimulationPlan, HybridError> {
+        let fx_offset = self.fx_offset();
+        // Quanto drift of each foreign short rate under the domestic measure
+        let quanto: Vec<f64> = self
+            .fx
+            .iter()
+            .enumerate()
+            .map(|(k, fx)| {
+                let spot = fx_offset + 2 * k;
+                -self.correlation(spot, spot + 1) * fx.foreign.volatility * fx.volatility
+            })
+            .collect();
+
+        let mut steps = Vec::with_capacity(time_grid.len());
+        let mut previous = 0.0;
+        for &t in time_grid {
+            let dt = t - previous;
+            let domestic = RateStep::new(&self.domestic, previous, t, 0.0)?;
+            let foreign = self
+                .fx
+                .iter()
+                .zip(&quanto)
+                .map(|(fx, &drift)| RateStep::new(&fx.foreign, previous, t, drift))
+                .collect::<Result<Vec<_>, _>>()?;
+            steps.push(Step {
+                dt,
+                sqrt_dt: dt.sqrt(),
+                domestic,
+                foreign,
+            });
+            previous = t;
+        }
+
+        let domestic_shift = time_grid
+            .iter()
+            .map(|&t| self.domestic.shift(t))
+            .collect::<Result<Vec<_>, _>>()?;
+        let foreign_shift = self
+            .fx
+            .iter()
+            .map(|fx| {
+                time_grid
+                    .iter()
+                    .map(|&t| fx.foreign.shift(t))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(SimulationPlan {
+            steps,
+            domestic_shift,
+            foreign_shift,
+        })
+    }
+
+    fn simulate_path(
+        &self,
+        path: usize,
+        plan: &SimulationPlan,
+        cholesky: &CholeskyFactor<f64>,
+    ) -> PathValues {
+        let n_times = plan.steps.len();
+        let n_fx = self.fx.len();
+        let mut rng = PricerRng::from_seed(self.seed.wrapping_add(path as u64));
+        let mut dw = vec![0.0; cholesky.dim()];
+
+        let mut x = 0.0;
+        let mut integrated_rate = 0.0;
+        let mut spot: Vec<f64> = self.equities.iter().map(|e| e.spot).collect();
+        let mut variance: Vec<f64> = self
+            .equities
+            .iter()
+            .map(|e| match e.dynamics {
+                EquityDynamics::Gbm { volatility } => volatility * volatility,
+                EquityDynamics::Heston { v0, .. } => v0,
+            })
+            .collect();
+        let mut fx_spot: Vec<f64> = self.fx.iter().map(|f| f.spot).collect();
+        let mut x_foreign = vec![0.0; n_fx];
+
+        let mut values = PathValues {
+            discount: Vec::with_capacity(n_times),
+            domestic_state: Vec::with_capacity(n_times),
+            equities: vec![Vec::with_capacity(n_times); self.equities.len()],
+            fx: vec![Vec::with_capacity(n_times); n_fx],
+            foreign_state: vec![Vec::with_capacity(n_times); n_fx],
+        };
+
+        for step in &plan.steps {
+            if step.dt > 0.0 {
+                rng.fill_normal(&mut dw);
+                cholesky.transform_inplace(&mut dw);
+
+                let x_next = step.domestic.evolve(x, dw[0]);
+                let rate_integral = step.domestic.integral(x, x_next, step.dt);
+                x = x_next;
+                integrated_rate += rate_integral;
+
+                let mut factor = 1;
+                for (i, equity) in self.equities.iter().enumerate() {
+                    let v = variance[i].max(0.0);
+                    spot[i] *=
+                        (rate_integral - equity.dividend_yield * step.dt - 0.5 * v * step.dt
+                            + v.sqrt() * step.sqrt_dt * dw[factor])
+                            .exp();
+                    if let EquityDynamics::Heston {
+                        kappa, theta, xi, ..
+                    } = equity.dynamics
+                    {
+                        // Full truncation Euler
+                        variance[i] += kappa * (theta - v) * step.dt
+                            + xi * v.sqrt() * step.sqrt_dt * dw[factor + 1];
+                    }
+                    factor += equity.factor_count();
+                }
+
+                for (k, fx) in self.fx.iter().enumerate() {
+                    let foreign = &step.foreign[k];
+                    let x_next = foreign.evolve(x_foreign[k], dw[factor + 1]);
+                    let foreign_integral = foreign.integral(x_foreign[k], x_next, step.dt);
+                    x_foreign[k] = x_next;
+                    fx_spot[k] *= (rate_integral
+                        - foreign_integral
+                        - 0.5 * fx.volatility * fx.volatility * step.dt
+                        + fx.volatility * step.sqrt_dt * dw[factor])
+                        .exp();
+                    factor += 2;
+                }
+            }
+
+            values.discount.push((-integrated_rate).exp());
+            values.domestic_state.push(x);
+            for (out, &s) in values.equities.iter_mut().zip(&spot) {
+                out.push(s);
+            }
+            for k in 0..n_fx {
+                values.fx[k].push(fx_spot[k]);
+                values.foreign_state[k].push(x_foreign[k]);
+            }
+        }
+        values
+    }
+}
+
+/// Exact Hull-White step over one grid interval.
+struct RateStep {
+    decay: f64,
+    mean: f64,
+    std_dev: f64,
+    shift_integral: f64,
+}
+
+impl RateStep {
+    fn new(model: &HullWhiteFactor, t0: f64, t1: f64, drift: f64) -> Result<Self, HybridError> {
+        let (decay, mean, std_dev) = model.step(t1 - t0, drift);
+        Ok(Self {
+            decay,
+            mean,
+            std_dev,
+            shift_integral: model.shift_integral(t1)? - model.shift_integral(t0)?,
+        })
+    }
+
+    #[inline]
+    fn evolve(&self, x: f64, dw: f64) -> f64 {
+        x * self.decay + self.mean + self.std_dev * dw
+    }
+
+    /// Returns `∫ r ds` over the step, trapezoidal in the state.
+    #[inline]
+    fn integral(&self, x0: f64, x1: f64, dt: f64) -> f64 {
+        self.shift_integral + 0.5 * (x0 + x1) * dt
+    }
+}
+
+struct Step {
+    dt: f64,
+    sqrt_dt: f64,
+    domestic: RateStep,
+    foreign: Vec<RateStep>,
+}
+
+struct SimulationPlan {
+    steps: Vec<Step>,
+    domestic_shift: Vec<f64>,
+    foreign_shift: Vec<Vec<f64>>,
+}
+
+struct PathValues {
+    discount: Vec<f64>,
+    domestic_state: Vec<f64>,
+    equities: Vec<Vec<f64>>,
+    fx: Vec<Vec<f64>>,
+    foreign_state: Vec<Vec<f64>>,
+}
+
+/// Joint scenarios from a [`HybridSimulator`].
+///
+/// Path-valued accessors return `[scenario_idx][time_idx]` slices, the
+/// layout taken by [`ExposureCalculator`](crate::exposure::ExposureCalculator).
+#[derive(Clone, Debug)]
+pub struct HybridPaths {
+    time_grid: Vec<f64>,
+    domestic: HullWhiteFactor,
+    domestic_shift: Vec<f64>,
+    discount_factors: Vec<Vec<f64>>,
+    domestic_state: Vec<Vec<f64>>,
+    equity_names: Vec<String>,
+    equities: Vec<Vec<Vec<f64>>>,
+    fx_names: Vec<String>,
+    fx: Vec<Vec<Vec<f64>>>,
+    foreign: Vec<HullWhiteFactor>,
+    foreign_shift: Vec<Vec<f64>>,
+    foreign_state: Vec<Vec<Vec<f64>>>,
+}
+
+impl HybridPaths {
+    /// Returns the observation times.
+    #[inline]
+    pub fn time_grid(&self) -> &[f64] {
+        &self.time_grid
+    }
+
+    /// Returns the number of scenarios.
+    #[inline]
+    pub fn n_paths(&self) -> usize {
+        self.discount_factors.len()
+    }
+
+    /// Returns the stochastic discount factors `D(t) = exp(-∫₀ᵗ r ds)`.
+    ///
+    /// Multiply a future value by `D(t)` and average over scenarios to
+    /// obtain its present value.
+    #[inline]
+    pub fn discount_factors(&self) -> &[Vec<f64>] {
+        &self.discount_factors
+    }
+
+    /// Returns the domestic short rates `r(t)`.
+    pub fn short_rates(&self) -> Vec<Vec<f64>> {
+        short_rates(&self.domestic_state, &self.domestic_shift)
+    }
+
+    /// Returns the paths of the named equity.
+    pub fn equity(&self, name: &str) -> Option<&[Vec<f64>]> {
+        let index = self.equity_names.iter().position(|n| n == name)?;
+        Some(&self.equities[index])
+    }
+
+    /// Returns the paths of the named FX rate.
+    pub fn fx(&self, name: &str) -> Option<&[Vec<f64>]> {
+        let index = self.fx_index(name)?;
+        Some(&self.fx[index])
+    }
+
+    /// Returns the foreign short rates of the named FX pair.
+    pub fn foreign_short_rates(&self, name: &str) -> Option<Vec<Vec<f64>>> {
+        let index = self.fx_index(name)?;
+        Some(short_rates(
+            &self.foreign_state[index],
+            &self.foreign_shift[index],
+        ))
+    }
+
+    /// Returns the domestic zero-coupon bond `P(t, T)` on scenario `path`
+    /// at grid point `time_index`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HybridError::InvalidMaturity` if `maturity` precedes the
+    /// observation time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `path` or `time_index` is out of range.
+    pub fn zero_bond(
+        &self,
+        path: usize,
+        time_index: usize,
+        maturity: f64,
+    ) -> Result<f64, HybridError> {
+        self.domestic.zero_bond(
+            self.time_grid[time_index],
+            maturity,
+            self.domestic_state[path][time_index],
+        )
+    }
+
+    /// Returns the foreign zero-coupon bond `P_f(t, T)` of the named FX
+    /// pair, in foreign currency, or `None` for an unknown pair.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HybridError::InvalidMaturity` if `maturity` precedes the
+    /// observation time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `path` or `time_index` is out of range.
+    pub fn foreign_zero_bond(
+        &self,
+        name: &str,
+        path: usize,
+        time_index: usize,
+        maturity: f64,
+    ) -> Option<Result<f64, HybridError>> {
+        let index = self.fx_index(name)?;
+        Some(self.foreign[index].zero_bond(
+            self.time_grid[time_index],
+            maturity,
+            self.foreign_state[index][path][time_index],
+        ))
+    }
+
+    fn fx_index(&self, name: &str) -> Option<usize> {
+        self.fx_names.iter().position(|n| n == name)
+    }
+}
+
+fn short_rates(state: &[Vec<f64>], shift: &[f64]) -> Vec<Vec<f64>> {
+    state
+        .iter()
+        .map(|path| path.iter().zip(shift).map(|(x, phi)| x + phi).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use pricer_core::market_data::curves::{CurveInterpolation, InterpolatedCurve};
+
+    const N_PATHS: usize = 20_000;
+
+    fn mean_and_error(samples: &[f64]) -> (f64, f64) {
+        let n = samples.len() as f64;
+        let mean = samples.iter().sum::<f64>() / n;
+        let var = samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        (mean, (var / n).sqrt())
+    }
+
+    fn upward_curve() -> CurveEnum<f64> {
+        InterpolatedCurve::new(
+            &[0.5, 1.0, 2.0, 5.0],
+            &[0.02, 0.025, 0.03, 0.035],
+            CurveInterpolation::Linear,
+            true,
+        )
+        .unwrap()
+        .into()
+    }
+
+    fn grid(horizon: f64, n: usize) -> Vec<f64> {
+        (0..=n).map(|i| horizon * i as f64 / n as f64).collect()
+    }
+
+    #[test]
+    fn test_discount_factors_reprice_initial_curve() {
+        let rates = HullWhiteFactor::new(0.1, 0.015, upward_curve()).unwrap();
+        let paths = HybridSimulator::new(rates.clone(), 1)
+            .simulate(&grid(5.0, 50), N_PATHS)
+            .unwrap();
+
+        for i in [10, 25, 50] {
+            let t = paths.time_grid()[i];
+            let dfs: Vec<f64> = paths.discount_factors().iter().map(|d| d[i]).collect();
+            let (mean, error) = mean_and_error(&dfs);
+            let expected = rates.curve().discount_factor(t).unwrap();
+            assert!(
+                (mean - expected).abs() < 4.0 * error + 1e-4,
+                "t={t}: {mean} vs {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_zero_bond_consistent_with_discounting() {
+        // E[D(t) P(t, T)] = P(0, T)
+        let rates = HullWhiteFactor::new(0.05, 0.01, upward_curve()).unwrap();
+        let paths = HybridSimulator::new(rates.clone(), 2)
+            .simulate(&grid(2.0, 20), N_PATHS)
+            .unwrap();
+
+        let discounted: Vec<f64> = (0..paths.n_paths())
+            .map(|p| paths.discount_factors()[p][20] * paths.zero_bond(p, 20, 5.0).unwrap())
+            .collect();
+        let (mean, error) = mean_and_error(&discounted);
+        let expected = rates.curve().discount_factor(5.0).unwrap();
+        assert!((mean - expected).abs() < 4.0 * error + 1e-4);
+
+        assert_relative_eq!(
+            paths.zero_bond(0, 0, 3.0).unwrap(),
+            rates.curve().discount_factor(3.0).unwrap(),
+            epsilon = 1e-12
+        );
+        assert!(matches!(
+            paths.zero_bond(0, 20, 1.0),
+            Err(HybridError::InvalidMaturity { .. })
+        ));
+    }
+
+    #[test]
+    fn test_short_rate_starts_on_forward_curve() {
+        let rates = HullWhiteFactor::new(0.1, 0.01, CurveEnum::flat(0.03)).unwrap();
+        let paths = HybridSimulator::new(rates, 3)
+            .simulate(&[0.0, 1.0], 100)
+            .unwrap();
+        let short = paths.short_rates();
+        assert!(short.iter().all(|p| (p[0] - 0.03).abs() < 1e-8));
+        assert!(paths.discount_factors().iter().all(|p| p[0] == 1.0));
+    }
+
+    #[test]
+    fn test_discounted_equity_is_martingale() {
+        let rates = HullWhiteFactor::new(0.1, 0.01, upward_curve()).unwrap();
+        let correlation =
+            CorrelationMatrix::new(&[1.0, 0.3, 0.0, 0.3, 1.0, -0.7, 0.0, -0.7, 1.0], 3).unwrap();
+        let heston = EquityDynamics::Heston {
+            v0: 0.04,
+            kappa: 1.5,
+            theta: 0.04,
+            xi: 0.3,
+        };
+        let simulator = HybridSimulator::new(rates, 4)
+            .with_equity(EquityFactor::new("ACME", 100.0, heston).with_dividend_yield(0.01))
+            .with_correlation(correlation);
+        assert_eq!(
+            simulator.factor_labels(),
+            vec!["rate:domestic", "equity:ACME", "variance:ACME"]
+        );
+
+        let paths = simulator.simulate(&grid(2.0, 40), N_PATHS).unwrap();
+        let equity = paths.equity("ACME").unwrap();
+        let discounted: Vec<f64> = (0..paths.n_paths())
+            .map(|p| paths.discount_factors()[p][40] * equity[p][40])
+            .collect();
+        let (mean, error) = mean_and_error(&discounted);
+        let expected = 100.0 * (-0.01_f64 * 2.0).exp();
+        assert!(
+            (mean - expected).abs() < 4.0 * error,
+            "{mean} vs {expected}"
+        );
+    }
+
+    #[test]
+    fn test_converted_foreign_bond_is_martingale() {
+        // E[D_d(T) X(T)] = X(0) P_f(0, T), which relies on the quanto drift
+        let usd = HullWhiteFactor::new(0.05, 0.01, CurveEnum::flat(0.04)).unwrap();
+        let eur = HullWhiteFactor::new(0.05, 0.015, CurveEnum::flat(0.01)).unwrap();
+        let correlation = CorrelationMatrix::new(
+            &[
+                1.0, 0.3, 0.5, //
+                0.3, 1.0, -0.6, //
+                0.5, -0.6, 1.0,
+            ],
+            3,
+        )
+        .unwrap();
+        let paths = HybridSimulator::new(usd, 5)
+            .with_fx(FxFactor::new("EURUSD", 1.1, 0.12, eur))
+            .with_correlation(correlation)
+            .simulate(&grid(5.0, 50), N_PATHS)
+            .unwrap();
+
+        let fx = paths.fx("EURUSD").unwrap();
+        let converted: Vec<f64> = (0..paths.n_paths())
+            .map(|p| paths.discount_factors()[p][50] * fx[p][50])
+            .collect();
+        let (mean, error) = mean_and_error(&converted);
+        let expected = 1.1 * (-0.01_f64 * 5.0).exp();
+        assert!(
+            (mean - expected).abs() < 4.0 * error,
+            "{mean} vs {expected}"
+        );
+
+        let foreign = paths.foreign_short_rates("EURUSD").unwrap();
+        assert!((foreign[0][0] - 0.01).abs() < 1e-8);
+        assert!(paths.foreign_zero_bond("EURUSD", 0, 0, 1.0).is_some());
+        assert!(paths.fx("GBPUSD").is_none());
+    }
+
+    #[test]
+    fn test_correlation_is_recovered() {
+        let rates = HullWhiteFactor::new(0.1, 0.01, CurveEnum::flat(0.02)).unwrap();
+        let eur = HullWhiteFactor::new(0.1, 0.01, CurveEnum::flat(0.01)).unwrap();
+        let rho = 0.6;
+        let correlation = CorrelationMatrix::new(
+            &[
+                1.0, 0.0, 0.0, 0.0, //
+                0.0, 1.0, rho, 0.0, //
+                0.0, rho, 1.0, 0.0, //
+                0.0, 0.0, 0.0, 1.0,
+            ],
+            4,
+        )
+        .unwrap();
+        let paths = HybridSimulator::new(rates, 6)
+            .with_equity(EquityFactor::new(
+                "SPX",
+                100.0,
+                EquityDynamics::Gbm { volatility: 0.2 },
+            ))
+            .with_fx(FxFactor::new("EURUSD", 1.1, 0.1, eur))
+            .with_correlation(correlation)
+            .simulate(&[0.0, 0.1], N_PATHS)
+            .unwrap();
+
+        let equity: Vec<f64> = paths
+            .equity("SPX")
+            .unwrap()
+            .iter()
+            .map(|p| p[1].ln())
+            .collect();
+        let fx: Vec<f64> = paths
+            .fx("EURUSD")
+            .unwrap()
+            .iter()
+            .map(|p| p[1].ln())
+            .collect();
+        let (me, _) = mean_and_error(&equity);
+        let (mf, _) = mean_and_error(&fx);
+        let cov: f64 = equity
+            .iter()
+            .zip(&fx)
+            .map(|(e, f)| (e - me) * (f - mf))
+            .sum();
+        let ve: f64 = equity.iter().map(|e| (e - me).powi(2)).sum();
+        let vf: f64 = fx.iter().map(|f| (f - mf).powi(2)).sum();
+        assert_relative_eq!(cov / (ve * vf).sqrt(), rho, epsilon = 0.03);
+    }
+
+    #[test]
+    fn test_same_seed_reproduces_paths() {
+        let rates = HullWhiteFactor::new(0.1, 0.01, CurveEnum::flat(0.02)).unwrap();
+        let simulator = HybridSimulator::new(rates, 7).with_equity(EquityFactor::new(
+            "SPX",
+            100.0,
+            EquityDynamics::Gbm { volatility: 0.2 },
+        ));
+        let a = simulator.simulate(&[0.5, 1.0], 50).unwrap();
+        let b = simulator.simulate(&[0.5, 1.0], 50).unwrap();
+        assert_eq!(a.discount_factors(), b.discount_factors());
+        assert_eq!(a.equity("SPX"), b.equity("SPX"));
+        // A grid not starting at zero is stepped to its first point
+        assert!(a.equity("SPX").unwrap()[0][0] != 100.0);
+    }
+
+    #[test]
+    fn test_invalid_inputs_are_rejected() {
+        assert!(HullWhiteFactor::new(0.0, 0.01, CurveEnum::flat(0.02)).is_err());
+        assert!(HullWhiteFactor::new(0.1, -0.01, CurveEnum::flat(0.02)).is_err());
+
+        let rates = HullWhiteFactor::new(0.1, 0.01, CurveEnum::flat(0.02)).unwrap();
+        let simulator = HybridSimulator::new(rates.clone(), 0);
+        assert_eq!(
+            simulator.simulate(&[], 10).unwrap_err(),
+            HybridError::InvalidTimeGrid
+        );
+        assert_eq!(
+            simulator.simulate(&[0.0, 1.0, 1.0], 10).unwrap_err(),
+            HybridError::InvalidTimeGrid
+        );
+        assert_eq!(
+            simulator.simulate(&[0.0, 1.0], 0).unwrap_err(),
+            HybridError::NoPaths
+        );
+
+        let gbm = EquityDynamics::Gbm { volatility: 0.2 };
+        let bad_spot =
+            HybridSimulator::new(rates.clone(), 0).with_equity(EquityFactor::new("SPX", -1.0, gbm));
+        assert!(matches!(
+            bad_spot.simulate(&[1.0], 10),
+            Err(HybridError::InvalidEquity { .. })
+        ));
+
+        let duplicate = HybridSimulator::new(rates.clone(), 0)
+            .with_equity(EquityFactor::new("SPX", 100.0, gbm))
+            .with_equity(EquityFactor::new("SPX", 100.0, gbm));
+        assert_eq!(
+            duplicate.simulate(&[1.0], 10).unwrap_err(),
+            HybridError::DuplicateFactor("SPX".to_string())
+        );
+
+        let wrong_dimension = HybridSimulator::new(rates.clone(), 0)
+            .with_equity(EquityFactor::new("SPX", 100.0, gbm))
+            .with_correlation(CorrelationMatrix::identity(3));
+        assert_eq!(
+            wrong_dimension.simulate(&[1.0], 10).unwrap_err(),
+            HybridError::CorrelationDimension {
+                expected: 2,
+                got: 3
+            }
+        );
+
+        let bad_fx = HybridSimulator::new(rates.clone(), 0)
+            .with_fx(FxFactor::new("EURUSD", 1.1, -0.1, rates));
+        assert!(matches!(
+            bad_fx.simulate(&[1.0], 10),
+            Err(HybridError::InvalidFx { .. })
+        ));
+    }
+}
@@ -0,0 +1,29 @@
+//! Joint risk-factor simulation for exposure.
+//!
+//! Exposure on a cross-asset portfolio needs every trade revalued on the
+//! same scenarios, so rates, equity and FX must be evolved together. This
+//! module provides:
+//!
+//! - [`HybridSimulator`]: Hull-White domestic and foreign short rates,
+//!   GBM or Heston equities and lognormal FX rates driven by one
+//!   user-supplied correlation matrix on a common time grid
+//! - [`HybridPaths`]: the simulated numeraire discount factors, short
+//!   rates, underlyings and zero-coupon bond prices per scenario
+//! - [`NestedSimulator`]: statistics conditional on the state at future
+//!   dates, such as forward initial margin, by inner Monte Carlo or
+//!   regression
+//!
+//! All hybrid factors are simulated under the domestic risk-neutral measure, so
+//! discounted equity and converted foreign assets are martingales and
+//! `E[D(t)] = P(0, t)` on the domestic curve.
+
+mod hybrid;
+mod nested;
+
+pub use hybrid::{
+    EquityDynamics, EquityFactor, FxFactor, HullWhiteFactor, HybridError, HybridPaths,
+    HybridSimulator,
+};
+pub use nested::{
+    ConditionalStatistic, NestedError, NestedMethod, NestedModel, NestedProfile, NestedSimulator,
+};
@@ -0,0 +1,825 @@
+//! Nested (MC-in-MC) simulation of conditional statistics.
+//!
+//! Some exposure quantities need a distribution conditional on the state
+//! at a future date, not just a value per scenario:
+//!
+//! - forward initial margin for MVA, a quantile of the portfolio value
+//!   change over the margin period of risk seen from date t
+//! - future PFE under a dynamic hedge, a quantile of the hedged P&L over
+//!   the rebalancing window
+//!
+//! [`NestedSimulator`] evolves outer scenarios on a time grid and, at
+//! each date, estimates a [`ConditionalStatistic`] of a user-supplied
+//! sample over a short inner horizon. [`NestedMethod::Nested`] runs an
+//! inner Monte Carlo per outer scenario and date, which is unbiased but
+//! costs outer × inner paths. [`NestedMethod::Regression`] is the fast
+//! alternative: a few inner samples per scenario are regressed on
+//! polynomial features of the outer state to give the conditional mean
+//! and variance, and quantiles follow from a Gaussian assumption.
+
+use pricer_pricing::pool::{with_run_arena, RunArena};
+use pricer_pricing::rng::{inverse_normal_cdf, PricerRng};
+use rayon::prelude::*;
+use thiserror::Error;
+
+/// Features whose cross-scenario standard deviation, relative to their
+/// mean, is below this are treated as constant and dropped from the
+/// regression.
+const FEATURE_EPSILON: f64 = 1e-12;
+
+/// Errors from nested simulation.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum NestedError {
+    /// Time grid is empty, negative, non-finite or not increasing.
+    #[error("Time grid must be non-empty, non-negative and increasing")]
+    InvalidTimeGrid,
+
+    /// Inner horizon is negative or not finite.
+    #[error("Inner horizon must be non-negative, got {0}")]
+    InvalidHorizon(f64),
+
+    /// No outer or inner paths were requested.
+    #[error("Number of outer and inner paths must be positive")]
+    NoPaths,
+
+    /// Confidence level is outside `(0, 1)`.
+    #[error("Confidence level must be in (0, 1), got {0}")]
+    InvalidConfidence(f64),
+
+    /// Regression normal equations are singular.
+    #[error("Regression is singular at time index {0}")]
+    SingularRegression(usize),
+}
+
+/// Statistic of the inner samples estimated per outer scenario and date.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConditionalStatistic {
+    /// Conditional mean, e.g. a future mark-to-market.
+    Mean,
+    /// Conditional quantile at the given confidence, e.g. initial margin.
+    Quantile(f64),
+    /// Conditional mean beyond the quantile at the given confidence.
+    ExpectedShortfall(f64),
+}
+
+impl ConditionalStatistic {
+    fn confidence(self) -> Option<f64> {
+        match self {
+            Self::Mean => None,
+            Self::Quantile(q) | Self::ExpectedShortfall(q) => Some(q),
+        }
+    }
+
+    /// Evaluates the statistic on an empirical sample, sorting it.
+    fn empirical(self, samples: &mut [f64]) -> f64 {
+        let n = samples.len();
+        match self {
+            Self::Mean => samples.iter().sum::<f64>() / n as f64,
+            Self::Quantile(q) => {
+                samples.sort_by(f64::total_cmp);
+                samples[quantile_rank(q, n)]
+            }
+            Self::ExpectedShortfall(q) => {
+                samples.sort_by(f64::total_cmp);
+                let tail = &samples[quantile_rank(q, n)..];
+                tail.iter().sum::<f64>() / tail.len() as f64
+            }
+        }
+    }
+
+    /// Evaluates the statistic of a normal with the given moments.
+    fn gaussian(self, mean: f64, std_dev: f64) -> f64 {
+        match self {
+            Self::Mean => mean,
+            Self::Quantile(q) => mean + std_dev * inverse_normal_cdf(q),
+            Self::ExpectedShortfall(q) => {
+                let z = inverse_normal_cdf(q);
+                let density = (-0.5 * z * z).exp() / (2.0 * std::f64::consts::PI).sqrt();
+                mean + std_dev * density / (1.0 - q)
+            }
+        }
+    }
+}
+
+/// How the conditional statistic is estimated.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum NestedMethod {
+    /// Full inner Monte Carlo with `inner_paths` per outer scenario and date.
+    Nested {
+        /// Inner paths per outer scenario and date
+        inner_paths: usize,
+    },
+    /// Least-squares regression across outer scenarios on monomials of
+    /// each state feature up to `degree`, using `inner_paths` samples per
+    /// scenario and date (one is usually enough).
+    Regression {
+        /// Inner samples per outer scenario and date
+        inner_paths: usize,
+        /// Highest monomial degree per feature
+        degree: usize,
+    },
+}
+
+impl NestedMethod {
+    fn inner_paths(self) -> usize {
+        match self {
+            Self::Nested { inner_paths } | Self::Regression { inner_paths, .. } => inner_paths,
+        }
+    }
+}
+
+/// A stochastic model the nested simulator can step from any state.
+///
+/// # Examples
+///
+/// ```
+/// use pricer_pricing::rng::PricerRng;
+/// use pricer_risk::simulation::NestedModel;
+///
+/// /// Driftless GBM spot.
+/// struct Gbm {
+///     spot: f64,
+///     volatility: f64,
+/// }
+///
+/// impl NestedModel for Gbm {
+///     type State = f64;
+///
+///     fn initial_state(&self) -> f64 {
+///         self.spot
+///     }
+///
+///     fn evolve(&self, spot: &f64, t0: f64, t1: f64, rng: &mut PricerRng) -> f64 {
+///         let dt = t1 - t0;
+///         let sigma = self.volatility;
+///         spot * (-0.5 * sigma * sigma * dt + sigma * dt.sqrt() * rng.gen_normal()).exp()
+///     }
+///
+///     fn features(&self, _t: f64, spot: &f64) -> Vec<f64> {
+///         vec![*spot]
+///     }
+/// }
+/// ```
+pub trait NestedModel: Sync {
+    /// Model state at a date, e.g. spot and short rate.
+    type State: Clone + Send + Sync;
+
+    /// Returns the state at time 0.
+    fn initial_state(&self) -> Self::State;
+
+    /// Evolves `state` from `t0` to `t1`, drawing from `rng`.
+    fn evolve(&self, state: &Self::State, t0: f64, t1: f64, rng: &mut PricerRng) -> Self::State;
+
+    /// Returns the regression features of `state` at time `t`.
+    fn features(&self, t: f64, state: &Self::State) -> Vec<f64>;
+}
+
+/// Conditional statistic per outer scenario from a [`NestedSimulator`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct NestedProfile {
+    time_grid: Vec<f64>,
+    values: Vec<Vec<f64>>,
+}
+
+impl NestedProfile {
+    /// Returns the observation times.
+    #[inline]
+    pub fn time_grid(&self) -> &[f64] {
+        &self.time_grid
+    }
+
+    /// Returns the statistic as `[scenario_idx][time_idx]`.
+    #[inline]
+    pub fn values(&self) -> &[Vec<f64>] {
+        &self.values
+    }
+
+    /// Returns the mean over scenarios at each date, e.g. the expected
+    /// initial margin profile that MVA integrates.
+    pub fn expected_profile(&self) -> Vec<f64> {
+        let n = self.values.len() as f64;
+        (0..self.time_grid.len())
+            .map(|i| self.values.iter().map(|path| path[i]).sum::<f64>() / n)
+            .collect()
+    }
+
+    /// Returns the quantile over scenarios at confidence `q` at each date,
+    /// e.g. a PFE of the conditional statistic.
+    pub fn quantile_profile(&self, q: f64) -> Vec<f64> {
+        (0..self.time_grid.len())
+            .map(|i| {
+                let mut column: Vec<f64> = self.values.iter().map(|path| path[i]).collect();
+                column.sort_by(f64::total_cmp);
+                column[quantile_rank(q, column.len())]
+            })
+            .collect()
+    }
+}
+
+/// Nested Monte Carlo for conditional statistics at future dates.
+///
+/// Outer scenario `p` draws from a generator seeded with `seed + p`, so
+/// results do not depend on thread scheduling.
+///
+/// # Examples
+///
+/// Forward 99% initial margin of a long spot position over a ten-day
+/// margin period of risk:
+///
+/// ```
+/// # use pricer_pricing::rng::PricerRng;
+/// # use pricer_risk::simulation::NestedModel;
+/// # struct Gbm { spot: f64, volatility: f64 }
+/// # impl NestedModel for Gbm {
+/// #     type State = f64;
+/// #     fn initial_state(&self) -> f64 { self.spot }
+/// #     fn evolve(&self, s: &f64, t0: f64, t1: f64, rng: &mut PricerRng) -> f64 {
+/// #         let (dt, v) = (t1 - t0, self.volatility);
+/// #         s * (-0.5 * v * v * dt + v * dt.sqrt() * rng.gen_normal()).exp()
+/// #     }
+/// #     fn features(&self, _t: f64, s: &f64) -> Vec<f64> { vec![*s] }
+/// # }
+/// use pricer_risk::simulation::{ConditionalStatistic, NestedMethod, NestedSimulator};
+///
+/// let model = Gbm { spot: 100.0, volatility: 0.2 };
+/// let mpor = 10.0 / 365.0;
+///
+/// let nested = NestedSimulator::new(200, mpor, 1)
+///     .with_statistic(ConditionalStatistic::Quantile(0.99))
+///     .with_method(NestedMethod::Nested { inner_paths: 500 });
+/// let fast = nested
+///     .clone()
+///     .with_method(NestedMethod::Regression { inner_paths: 1, degree: 2 });
+///
+/// // Loss of the long position over the MPoR
+/// let loss = |_t: f64, start: &f64, end: &f64| start - end;
+/// let grid = [0.0, 0.5, 1.0];
+/// let im = nested.simulate(&model, &grid, loss).unwrap().expected_profile();
+/// let im_fast = fast.simulate(&model, &grid, loss).unwrap().expected_profile();
+///
+/// assert!((im[2] - im_fast[2]).abs() < 0.15 * im[2]);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct NestedSimulator {
+    outer_paths: usize,
+    inner_horizon: f64,
+    statistic: ConditionalStatistic,
+    method: NestedMethod,
+    seed: u64,
+}
+
+impl NestedSimulator {
+    /// Creates a simulator of `outer_paths` scenarios whose inner paths
+    /// run for `inner_horizon` years past each grid date.
+    ///
+    /// Defaults to the conditional mean by full nesting with 1,000 inner
+    /// paths.
+    pub fn new(outer_paths: usize, inner_horizon: f64, seed: u64) -> Self {
+        Self {
+            outer_paths,
+            inner_horizon,
+            statistic: ConditionalStatistic::Mean,
+            method: NestedMethod::Nested { inner_paths: 1_000 },
+            seed,
+        }
+    }
+
+    /// Sets the conditional statistic.
+    pub fn with_statistic(mut self, statistic: ConditionalStatistic) -> Self {
+        self.statistic = statistic;
+        self
+    }
+
+    /// Sets the estimation method.
+    pub fn with_method(mut self, method: NestedMethod) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Returns the conditional statistic.
+    #[inline]
+    pub fn statistic(&self) -> ConditionalStatistic {
+        self.statistic
+    }
+
+    /// Returns the estimation method.
+    #[inline]
+    pub fn method(&self) -> NestedMethod {
+        self.method
+    }
+
+    /// Estimates the conditional statistic of `sample` on every outer
+    /// scenario and date of `time_grid`.
+    ///
+    /// `sample(t, state, end)` maps the outer state at `t` and an inner
+    /// state at `t + inner_horizon` to the quantity of interest, such as
+    /// the portfolio value change over the margin period of risk.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NestedError::InvalidTimeGrid` for an empty, negative or
+    /// non-increasing grid, `NestedError::InvalidHorizon` for a negative
+    /// inner horizon, `NestedError::NoPaths` for zero outer or inner
+    /// paths, `NestedError::InvalidConfidence` for a confidence outside
+    /// `(0, 1)`, and `NestedError::SingularRegression` if the regression
+    /// cannot be solved.
+    pub fn simulate<M, F>(
+        &self,
+        model: &M,
+        time_grid: &[f64],
+        sample: F,
+    ) -> Result<NestedProfile, NestedError>
+    where
+        M: NestedModel,
+        F: Fn(f64, &M::State, &M::State) -> f64 + Sync,
+    {
+        self.validate(time_grid)?;
+        let inner_paths = self.method.inner_paths();
+
+        // Per outer scenario: per date, the outer state's features and
+        // inner samples (or, when nesting, the statistic itself)
+        let outer: Vec<Vec<(Vec<f64>, Vec<f64>)>> = (0..self.outer_paths)
+            .into_par_iter()
+            .map(|p| {
+                let mut rng = PricerRng::from_seed(self.seed.wrapping_add(p as u64));
+                let mut state = model.initial_state();
+                let mut previous = 0.0;
+                time_grid
+                    .iter()
+                    .map(|&t| {
+                        if t > previous {
+                            state = model.evolve(&state, previous, t, &mut rng);
+                        }
+                        previous = t;
+                        let mut samples: Vec<f64> = (0..inner_paths)
+                            .map(|_| {
+                                let end = model.evolve(&state, t, t + self.inner_horizon, &mut rng);
+                                sample(t, &state, &end)
+                            })
+                            .collect();
+                        match self.method {
+                            NestedMethod::Nested { .. } => {
+                                (Vec::new(), vec![self.statistic.empirical(&mut samples)])
+                            }
+                            NestedMethod::Regression { .. } => (model.features(t, &state), samples),
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let values = match self.method {
+            NestedMethod::Nested { .. } => outer
+                .into_iter()
+                .map(|dates| dates.into_iter().map(|(_, value)| value[0]).collect())
+                .collect(),
+            NestedMethod::Regression { degree, .. } => {
+                let mut values = vec![Vec::with_capacity(time_grid.len()); self.outer_paths];
+                for i in 0..time_grid.len() {
+                    let column: Vec<&(Vec<f64>, Vec<f64>)> =
+                        outer.iter().map(|dates| &dates[i]).collect();
+                    let fitted = self.regress(&column, degree, i)?;
+                    for (path, value) in values.iter_mut().zip(fitted) {
+                        path.push(value);
+                    }
+                }
+                values
+            }
+        };
+
+        Ok(NestedProfile {
+            time_grid: time_grid.to_vec(),
+            values,
+        })
+    }
+
+    fn validate(&self, time_grid: &[f64]) -> Result<(), NestedError> {
+        if time_grid.is_empty()
+            || time_grid.iter().any(|t| !t.is_finite())
+            || time_grid[0] < 0.0
+            || time_grid.windows(2).any(|w| w[1] <= w[0])
+        {
+            return Err(NestedError::InvalidTimeGrid);
+        }
+        if !(self.inner_horizon.is_finite() && self.inner_horizon >= 0.0) {
+            return Err(NestedError::InvalidHorizon(self.inner_horizon));
+        }
+        if self.outer_paths == 0 || self.method.inner_paths() == 0 {
+            return Err(NestedError::NoPaths);
+        }
+        if let Some(q) = self.statistic.confidence() {
+            if !(q > 0.0 && q < 1.0) {
+                return Err(NestedError::InvalidConfidence(q));
+            }
+        }
+        Ok(())
+    }
+
+    /// Fits the conditional mean (and, for tail statistics, variance) of
+    /// the inner samples on one date and evaluates the statistic per
+    /// scenario.
+    ///
+    /// The design matrix and normal equations live in the thread's run
+    /// arena, so repeated dates and runs reuse the same scratch.
+    fn regress(
+        &self,
+        column: &[&(Vec<f64>, Vec<f64>)],
+        degree: usize,
+        time_index: usize,
+    ) -> Result<Vec<f64>, NestedError> {
+        let basis = PolynomialBasis::fit(column.iter().map(|(f, _)| f.as_slice()), degree);
+        let k = basis.len();
+
+        with_run_arena(|arena| {
+            let rows = arena.alloc_slice(column.len() * k);
+            for ((features, _), row) in column.iter().zip(rows.chunks_exact_mut(k)) {
+                basis.evaluate_into(features, row);
+            }
+            let rows = &*rows;
+
+            let fit = |targets: &dyn Fn(usize, f64) -> f64| {
+                let samples = column.iter().enumerate().flat_map(|(p, (_, samples))| {
+                    let row = &rows[p * k..][..k];
+                    samples.iter().map(move |&s| (row, targets(p, s)))
+                });
+                least_squares(arena, k, samples).ok_or(NestedError::SingularRegression(time_index))
+            };
+
+            let mean_coefficients = fit(&|_, s| s)?;
+            let means: Vec<f64> = rows
+                .chunks_exact(k)
+                .map(|r| dot(r, mean_coefficients))
+                .collect();
+            if self.statistic == ConditionalStatistic::Mean {
+                return Ok(means);
+            }
+
+            let variance_coefficients = fit(&|p, s| (s - means[p]).powi(2))?;
+            Ok(rows
+                .chunks_exact(k)
+                .zip(&means)
+                .map(|(r, &mean)| {
+                    let variance = dot(r, variance_coefficients).max(0.0);
+                    self.statistic.gaussian(mean, variance.sqrt())
+                })
+                .collect())
+        })
+    }
+}
+
+/// Monomials of standardised features, without cross terms.
+struct PolynomialBasis {
+    centre: Vec<f64>,
+    scale: Vec<f64>,
+    degree: usize,
+}
+
+impl PolynomialBasis {
+    fn fit<'a>(features: impl Iterator<Item = &'a [f64]> + Clone, degree: usize) -> Self {
+        let n = features.clone().count() as f64;
+        let dim = features.clone().next().map_or(0, <[f64]>::len);
+        let mut centre = vec![0.0; dim];
+        let mut scale = vec![0.0; dim];
+        for f in features.clone() {
+            for (c, x) in centre.iter_mut().zip(f) {
+                *c += x / n;
+            }
+        }
+        for f in features {
+            for ((s, x), c) in scale.iter_mut().zip(f).zip(&centre) {
+                *s += (x - c).powi(2) / n;
+            }
+        }
+        for s in &mut scale {
+            *s = s.sqrt();
+        }
+        Self {
+            centre,
+            scale,
+            degree,
+        }
+    }
+
+    /// Returns whether feature `i` varies across scenarios.
+    fn is_varying(&self, i: usize) -> bool {
+        // Constant features add nothing to the intercept
+        self.scale[i] > FEATURE_EPSILON * self.centre[i].abs().max(1.0)
+    }
+
+    /// Number of regressors: the intercept plus `degree` monomials per
+    /// varying feature.
+    fn len(&self) -> usize {
+        1 + self.degree
+            * (0..self.scale.len())
+                .filter(|&i| self.is_varying(i))
+                .count()
+    }
+
+    /// Writes the regressors of `features` into `row` (of length
+    /// [`len`](Self::len)).
+    fn evaluate_into(&self, features: &[f64], row: &mut [f64]) {
+        row[0] = 1.0;
+        let mut next = 1;
+        for (i, x) in features.iter().enumerate() {
+            if !self.is_varying(i) {
+                continue;
+            }
+            let z = (x - self.centre[i]) / self.scale[i];
+            let mut power = 1.0;
+            for _ in 0..self.degree {
+                power *= z;
+                row[next] = power;
+                next += 1;
+            }
+        }
+    }
+}
+
+/// Solves the normal equations `XᵀX β = Xᵀy` over `(row, target)` samples
+/// with `k` regressors by Gaussian elimination with partial pivoting;
+/// `None` if they are singular.
+///
+/// The augmented `k × (k + 1)` system and the coefficients are allocated
+/// from `arena`.
+fn least_squares<'r, 'a>(
+    arena: &'r RunArena,
+    k: usize,
+    samples: impl IntoIterator<Item = (&'a [f64], f64)>,
+) -> Option<&'r [f64]> {
+    let width = k + 1;
+    let a = arena.alloc_slice(k * width);
+    let mut n_samples = 0;
+    for (row, target) in samples {
+        for i in 0..k {
+            for j in 0..k {
+                a[i * width + j] += row[i] * row[j];
+            }
+            a[i * width + k] += row[i] * target;
+        }
+        n_samples += 1;
+    }
+
+    for col in 0..k {
+        let pivot = (col..k).max_by(|&i, &j| {
+            a[i * width + col]
+                .abs()
+                .total_cmp(&a[j * width + col].abs())
+        })?;
+        if a[pivot * width + col].abs() < FEATURE_EPSILON * n_samples as f64 {
+            return None;
+        }
+        for j in 0..width {
+            a.swap(col * width + j, pivot * width + j);
+        }
+        for row in col + 1..k {
+            let factor = a[row * width + col] / a[col * width + col];
+            for j in col..width {
+                a[row * width + j] -= factor * a[col * width + j];
+            }
+        }
+    }
+
+    let beta = arena.alloc_slice(k);
+    for i in (0..k).rev() {
+        let tail: f64 = (i + 1..k).map(|j| a[i * width + j] * beta[j]).sum();
+        beta[i] = (a[i * width + k] - tail) / a[i * width + i];
+    }
+    Some(beta)
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// Zero-based index of the `q` quantile of `n` sorted samples.
+fn quantile_rank(q: f64, n: usize) -> usize {
+    let rank = (q.clamp(0.0, 1.0) * n as f64).ceil() as usize;
+    rank.clamp(1, n) - 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    const MPOR: f64 = 10.0 / 365.0;
+
+    /// GBM spot with drift `mu`.
+    struct Gbm {
+        spot: f64,
+        mu: f64,
+        volatility: f64,
+    }
+
+    impl NestedModel for Gbm {
+        type State = f64;
+
+        fn initial_state(&self) -> f64 {
+            self.spot
+        }
+
+        fn evolve(&self, spot: &f64, t0: f64, t1: f64, rng: &mut PricerRng) -> f64 {
+            let dt = t1 - t0;
+            let sigma = self.volatility;
+            spot * ((self.mu - 0.5 * sigma * sigma) * dt + sigma * dt.sqrt() * rng.gen_normal())
+                .exp()
+        }
+
+        fn features(&self, _t: f64, spot: &f64) -> Vec<f64> {
+            vec![*spot]
+        }
+    }
+
+    fn model() -> Gbm {
+        Gbm {
+            spot: 100.0,
+            mu: 0.05,
+            volatility: 0.25,
+        }
+    }
+
+    /// Analytic 99% loss quantile of a long spot position over the MPoR.
+    fn analytic_margin(model: &Gbm, spot: f64, q: f64) -> f64 {
+        let sigma = model.volatility;
+        let z = inverse_normal_cdf(1.0 - q);
+        spot * (1.0 - ((model.mu - 0.5 * sigma * sigma) * MPOR + sigma * MPOR.sqrt() * z).exp())
+    }
+
+    fn loss(_t: f64, start: &f64, end: &f64) -> f64 {
+        start - end
+    }
+
+    #[test]
+    fn test_nested_quantile_matches_analytic() {
+        let model = model();
+        let grid = [0.0, 0.5, 1.0];
+        let profile = NestedSimulator::new(20, MPOR, 3)
+            .with_statistic(ConditionalStatistic::Quantile(0.99))
+            .with_method(NestedMethod::Nested {
+                inner_paths: 20_000,
+            })
+            .simulate(&model, &grid, loss)
+            .unwrap();
+
+        assert_eq!(profile.values().len(), 20);
+        // Outer states are not exposed, so check the t=0 column, which
+        // conditions on the known spot
+        for path in profile.values() {
+            let expected = analytic_margin(&model, 100.0, 0.99);
+            assert_relative_eq!(path[0], expected, max_relative = 0.05);
+        }
+    }
+
+    #[test]
+    fn test_regression_mean_is_conditional_expectation() {
+        // E[S(t + δ) | S(t)] = S(t) e^{μδ}, linear in the feature
+        let model = model();
+        let grid = [0.0, 1.0, 2.0];
+        let nested = NestedSimulator::new(2_000, 0.5, 4)
+            .with_method(NestedMethod::Nested { inner_paths: 1 })
+            .simulate(&model, &grid, |_, _, end| *end)
+            .unwrap();
+        let regressed = NestedSimulator::new(2_000, 0.5, 4)
+            .with_method(NestedMethod::Regression {
+                inner_paths: 1,
+                degree: 1,
+            })
+            .simulate(&model, &grid, |_, start, end| {
+                end - start * (0.05_f64 * 0.5).exp()
+            })
+            .unwrap();
+
+        // Residuals of the exact conditional mean regress to zero
+        for path in regressed.values() {
+            for &v in path {
+                assert!(v.abs() < 3.0, "{v}");
+            }
+        }
+        // Expected profile is E[S(t + δ)] = S0 e^{μ(t + δ)}
+        let expected = nested.expected_profile();
+        for (i, &t) in grid.iter().enumerate() {
+            let exact = 100.0 * (0.05_f64 * (t + 0.5)).exp();
+            assert_relative_eq!(expected[i], exact, max_relative = 0.03);
+        }
+    }
+
+    #[test]
+    fn test_regression_tracks_nested_margin_profile() {
+        let model = model();
+        let grid = [0.0, 0.5, 1.0, 2.0];
+        let base =
+            NestedSimulator::new(400, MPOR, 5).with_statistic(ConditionalStatistic::Quantile(0.99));
+        let nested = base
+            .clone()
+            .with_method(NestedMethod::Nested { inner_paths: 2_000 })
+            .simulate(&model, &grid, loss)
+            .unwrap();
+        let regressed = base
+            .with_method(NestedMethod::Regression {
+                inner_paths: 4,
+                degree: 2,
+            })
+            .simulate(&model, &grid, loss)
+            .unwrap();
+
+        let nested_im = nested.expected_profile();
+        let regressed_im = regressed.expected_profile();
+        for (n, r) in nested_im.iter().zip(&regressed_im) {
+            assert_relative_eq!(n, r, max_relative = 0.1);
+        }
+        // Margin scales with spot, so its spread across scenarios grows
+        let pfe = nested.quantile_profile(0.95);
+        assert!(pfe[3] > pfe[0]);
+    }
+
+    #[test]
+    fn test_expected_shortfall_exceeds_quantile() {
+        let model = model();
+        let grid = [0.0, 1.0];
+        for method in [
+            NestedMethod::Nested { inner_paths: 5_000 },
+            NestedMethod::Regression {
+                inner_paths: 2,
+                degree: 2,
+            },
+        ] {
+            let run = |statistic| {
+                NestedSimulator::new(200, MPOR, 6)
+                    .with_statistic(statistic)
+                    .with_method(method)
+                    .simulate(&model, &grid, loss)
+                    .unwrap()
+                    .expected_profile()
+            };
+            let var = run(ConditionalStatistic::Quantile(0.975));
+            let es = run(ConditionalStatistic::ExpectedShortfall(0.975));
+            assert!(es[1] > var[1], "{method:?}");
+        }
+    }
+
+    #[test]
+    fn test_same_seed_reproduces_profile() {
+        let model = model();
+        let simulator = NestedSimulator::new(20, MPOR, 7)
+            .with_statistic(ConditionalStatistic::Quantile(0.9))
+            .with_method(NestedMethod::Nested { inner_paths: 50 });
+        assert_eq!(
+            simulator.simulate(&model, &[0.5, 1.0], loss).unwrap(),
+            simulator.simulate(&model, &[0.5, 1.0], loss).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_invalid_inputs_are_rejected() {
+        let model = model();
+        let simulator = NestedSimulator::new(10, MPOR, 0);
+        assert_eq!(
+            simulator.simulate(&model, &[], loss),
+            Err(NestedError::InvalidTimeGrid)
+        );
+        assert_eq!(
+            simulator.simulate(&model, &[1.0, 0.5], loss),
+            Err(NestedError::InvalidTimeGrid)
+        );
+        assert_eq!(
+            NestedSimulator::new(10, -1.0, 0).simulate(&model, &[1.0], loss),
+            Err(NestedError::InvalidHorizon(-1.0))
+        );
+        assert_eq!(
+            NestedSimulator::new(0, MPOR, 0).simulate(&model, &[1.0], loss),
+            Err(NestedError::NoPaths)
+        );
+        assert_eq!(
+            simulator
+                .clone()
+                .with_method(NestedMethod::Nested { inner_paths: 0 })
+                .simulate(&model, &[1.0], loss),
+            Err(NestedError::NoPaths)
+        );
+        assert_eq!(
+            simulator
+                .with_statistic(ConditionalStatistic::Quantile(1.0))
+                .simulate(&model, &[1.0], loss),
+            Err(NestedError::InvalidConfidence(1.0))
+        );
+    }
+
+    #[test]
+    fn test_least_squares_recovers_line() {
+        let arena = RunArena::new();
+        let rows: Vec<Vec<f64>> = (0..10).map(|i| vec![1.0, i as f64]).collect();
+        let samples = rows
+            .iter()
+            .enumerate()
+            .map(|(i, row)| (row.as_slice(), 2.0 + 3.0 * i as f64));
+        let beta = least_squares(&arena, 2, samples).unwrap();
+        assert_relative_eq!(beta[0], 2.0, epsilon = 1e-10);
+        assert_relative_eq!(beta[1], 3.0, epsilon = 1e-10);
+
+        let singular: [&[f64]; 2] = [&[1.0, 1.0], &[2.0, 2.0]];
+        let samples = singular.into_iter().zip([1.0, 2.0]);
+        assert!(least_squares(&arena, 2, samples).is_none());
+    }
+}
@@ -0,0 +1,254 @@
+//! Columnar Black-Scholes kernel for vanilla trade populations.
+//!
+//! Prices European calls and puts straight from contiguous input columns,
+//! writing price and first/second-order Greeks into matching output
+//! columns. Calls and puts share one code path through the payoff sign
+//! `s` (+1 call, -1 put):
+//!
+//! ```text
+//! V     = s·(S·N(s·d₁) - K·e^(-rT)·N(s·d₂))
+//! Delta = s·N(s·d₁)
+//! Gamma = φ(d₁) / (S·σ·√T)
+//! Vega  = S·√T·φ(d₁)
+//! Theta = -(S·σ·φ(d₁))/(2√T) - s·r·K·e^(-rT)·N(s·d₂)
+//! Rho   = s·K·T·e^(-rT)·N(s·d₂)
+//! ```
+//!
+//! The loop body has no data-dependent branches beyond a final select for
+//! expired trades, so it runs over the columns without any per-trade
+//! dispatch.
+
+use pricer_models::analytical::distributions::{norm_cdf, norm_pdf};
+use rayon::prelude::*;
+
+/// Expiries at or below this are treated as expired (intrinsic value).
+const EXPIRY_EPSILON: f64 = 1e-10;
+
+/// Lower bound on `σ·√T` keeping `d₁` finite for zero volatility.
+const MIN_STD_DEV: f64 = 1e-12;
+
+/// Trades per Rayon task in the parallel kernel.
+const PAR_CHUNK: usize = 1024;
+
+/// Notional-scaled Black-Scholes prices and Greeks, one entry per trade.
+///
+/// Theta is the derivative with respect to calendar time (usually
+/// negative); all other Greeks follow [`pricer_models::analytical::BlackScholes`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ColumnGreeks {
+    /// Present values
+    pub prices: Vec<f64>,
+    /// ∂V/∂S
+    pub deltas: Vec<f64>,
+    /// ∂²V/∂S²
+    pub gammas: Vec<f64>,
+    /// ∂V/∂σ
+    pub vegas: Vec<f64>,
+    /// ∂V/∂t
+    pub thetas: Vec<f64>,
+    /// ∂V/∂r
+    pub rhos: Vec<f64>,
+}
+
+impl ColumnGreeks {
+    /// Creates zero-filled columns for `len` trades.
+    pub fn zeros(len: usize) -> Self {
+        Self {
+            prices: vec![0.0; len],
+            deltas: vec![0.0; len],
+            gammas: vec![0.0; len],
+            vegas: vec![0.0; len],
+            thetas: vec![0.0; len],
+            rhos: vec![0.0; len],
+        }
+    }
+
+    /// Returns the number of trades.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.prices.len()
+    }
+
+    /// Returns whether there are no trades.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.prices.is_empty()
+    }
+
+    /// Returns the sum of all prices.
+    pub fn total_price(&self) -> f64 {
+        self.prices.iter().sum()
+    }
+
+    /// Returns the sum of all deltas.
+    pub fn total_delta(&self) -> f64 {
+        self.deltas.iter().sum()
+    }
+}
+
+/// Input columns for [`price_columns`], all of equal length.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct BlackScholesInputs<'a> {
+    pub spots: &'a [f64],
+    pub strikes: &'a [f64],
+    pub maturities: &'a [f64],
+    pub volatilities: &'a [f64],
+    pub notionals: &'a [f64],
+    pub payoff_signs: &'a [i8],
+    pub rate: f64,
+}
+
+impl BlackScholesInputs<'_> {
+    fn len(&self) -> usize {
+        self.strikes.len()
+    }
+
+    fn slice(&self, start: usize, end: usize) -> Self {
+        Self {
+            spots: &self.spots[start..end],
+            strikes: &self.strikes[start..end],
+            maturities: &self.maturities[start..end],
+            volatilities: &self.volatilities[start..end],
+            notionals: &self.notionals[start..end],
+            payoff_signs: &self.payoff_signs[start..end],
+            rate: self.rate,
+        }
+    }
+}
+
+/// Output columns for one chunk of trades.
+struct ColumnsMut<'a> {
+    prices: &'a mut [f64],
+    deltas: &'a mut [f64],
+    gammas: &'a mut [f64],
+    vegas: &'a mut [f64],
+    thetas: &'a mut [f64],
+    rhos: &'a mut [f64],
+}
+
+/// Prices every trade sequentially.
+pub(crate) fn price_columns(inputs: BlackScholesInputs<'_>) -> ColumnGreeks {
+    let mut out = ColumnGreeks::zeros(inputs.len());
+    kernel(
+        inputs,
+        ColumnsMut {
+            prices: &mut out.prices,
+            deltas: &mut out.deltas,
+            gammas: &mut out.gammas,
+            vegas: &mut out.vegas,
+            thetas: &mut out.thetas,
+            rhos: &mut out.rhos,
+        },
+    );
+    out
+}
+
+/// Prices every trade with Rayon, splitting the columns into fixed chunks.
+pub(crate) fn price_columns_par(inputs: BlackScholesInputs<'_>) -> ColumnGreeks {
+    let mut out = ColumnGreeks::zeros(inputs.len());
+    out.prices
+        .par_chunks_mut(PAR_CHUNK)
+        .zip(out.deltas.par_chunks_mut(PAR_CHUNK))
+        .zip(out.gammas.par_chunks_mut(PAR_CHUNK))
+        .zip(out.vegas.par_chunks_mut(PAR_CHUNK))
+        .zip(out.thetas.par_chunks_mut(PAR_CHUNK))
+        .zip(out.rhos.par_chunks_mut(PAR_CHUNK))
+        .enumerate()
+        .for_each(
+            |(chunk, (((((prices, deltas), gammas), vegas), thetas), rhos))| {
+                let start = chunk * PAR_CHUNK;
+                kernel(
+                    inputs.slice(start, start + prices.len()),
+                    ColumnsMut {
+                        prices,
+                        deltas,
+                        gammas,
+                        vegas,
+                        thetas,
+                        rhos,
+                    },
+                );
+            },
+        );
+    out
+}
+
+#[inline]
+fn kernel(inputs: BlackScholesInputs<'_>, out: ColumnsMut<'_>) {
+    let n = inputs.len();
+    let rate = inputs.rate;
+    // Re-slice to `n` so the optimiser can drop bounds checks in the loop.
+    let (spots, strikes, maturities) = (
+        &inputs.spots[..n],
+        &inputs.strikes[..n],
+        &inputs.maturities[..n],
+    );
+    let (vols, notionals, signs) = (
+        &inputs.volatilities[..n],
+        &inputs.notionals[..n],
+        &inputs.payoff_signs[..n],
+    );
+    let (prices, deltas, gammas) = (
+        &mut out.prices[..n],
+        &mut out.deltas[..n],
+        &mut out.gammas[..n],
+    );
+    let (vegas, thetas, rhos) = (
+        &mut out.vegas[..n],
+        &mut out.thetas[..n],
+        &mut out.rhos[..n],
+    );
+
+    for i in 0..n {
+        let spot = spots[i];
+        let strike = strikes[i];
+        let vol = vols[i];
+        let notional = notionals[i];
+        let sign = f64::from(signs[i]);
+        let t = maturities[i].max(0.0);
+
+        let sqrt_t = t.sqrt();
+        let std_dev = (vol * sqrt_t).max(MIN_STD_DEV);
+        let discount = (-rate * t).exp();
+        let d1 = ((spot / strike).ln() + (rate + 0.5 * vol * vol) * t) / std_dev;
+        let d2 = d1 - std_dev;
+        let n_d1 = norm_cdf(sign * d1);
+        let n_d2 = norm_cdf(sign * d2);
+        let pdf_d1 = norm_pdf(d1);
+        let strike_df = strike * discount;
+
+        let live = t > EXPIRY_EPSILON;
+        let intrinsic = sign * (spot - strike);
+        let itm = intrinsic > 0.0;
+
+        let price = if live {
+            sign * (spot * n_d1 - strike_df * n_d2)
+        } else {
+            intrinsic.max(0.0)
+        };
+        let delta = if live {
+            sign * n_d1
+        } else if itm {
+            sign
+        } else {
+            0.0
+        };
+        let (gamma, vega, theta, rho) = if live {
+            (
+                pdf_d1 / (spot * std_dev),
+                spot * sqrt_t * pdf_d1,
+                -(spot * vol * pdf_d1) / (2.0 * sqrt_t) - sign * rate * strike_df * n_d2,
+                sign * strike_df * t * n_d2,
+            )
+        } else {
+            (0.0, 0.0, 0.0, 0.0)
+        };
+
+        prices[i] = price * notional;
+        deltas[i] = delta * notional;
+        gammas[i] = gamma * notional;
+        vegas[i] = vega * notional;
+        thetas[i] = theta * notional;
+        rhos[i] = rho * notional;
+    }
+}
@@ -19,9 +19,16 @@
 //!
 //! SoA provides better cache locality for operations that iterate
 //! over a single field across many elements.
+//!
+//! For vanilla portfolios, [`TradeSoA::price_black_scholes`] prices the
+//! European call/put population from these columns in a single loop,
+//! returning per-trade Greeks as [`ColumnGreeks`] without going through
+//! the `Instrument` enum for each trade.
 
+mod black_scholes;
 mod exposure_soa;
 mod trade_soa;
 
+pub use black_scholes::ColumnGreeks;
 pub use exposure_soa::ExposureSoA;
 pub use trade_soa::TradeSoA;
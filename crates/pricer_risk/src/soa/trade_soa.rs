@@ -2,8 +2,9 @@
 //!
 //! Provides vectorised storage of trade parameters for batch processing.
 
+use super::black_scholes::{price_columns, price_columns_par, BlackScholesInputs, ColumnGreeks};
 use crate::portfolio::{Trade, TradeId};
-use pricer_models::instruments::PayoffType;
+use pricer_models::instruments::{ExerciseStyle, PayoffType};
 use rayon::prelude::*;
 
 /// SoA representation of trade data for vectorised operations.
@@ -23,6 +24,11 @@ use rayon::prelude::*;
 /// let spots = vec![110.0; soa.len()];
 /// let mut payoffs = vec![0.0; soa.len()];
 /// soa.compute_payoffs(&spots, &mut payoffs);
+///
+/// // Price European vanillas column-wise with Black-Scholes
+/// let (vanillas, others) = TradeSoA::partition_vanillas(&trades);
+/// let vanillas = vanillas.with_flat_volatility(0.2);
+/// let greeks = vanillas.price_black_scholes(&vec![100.0; vanillas.len()], 0.03);
 /// ```
 #[derive(Debug, Clone)]
 pub struct TradeSoA {
@@ -36,6 +42,8 @@ pub struct TradeSoA {
     pub notionals: Vec<f64>,
     /// Payoff signs: +1 for Call, -1 for Put
     pub payoff_signs: Vec<i8>,
+    /// Black-Scholes volatilities (zero until set)
+    pub volatilities: Vec<f64>,
 }
 
 impl TradeSoA {
@@ -47,6 +55,7 @@ impl TradeSoA {
             maturities: Vec::with_capacity(capacity),
             notionals: Vec::with_capacity(capacity),
             payoff_signs: Vec::with_capacity(capacity),
+            volatilities: Vec::with_capacity(capacity),
         }
     }
 
@@ -69,12 +78,47 @@ impl TradeSoA {
                 soa.maturities.push(trade.expiry());
                 soa.notionals.push(trade.notional());
                 soa.payoff_signs.push(sign);
+                soa.volatilities.push(0.0);
             }
         }
 
         soa
     }
 
+    /// Splits trades into European calls and puts, which are collected
+    /// into an SoA for column-wise pricing, and everything else.
+    ///
+    /// The remainder (American, Bermudan and digital options, forwards,
+    /// swaps, ...) keeps its original order and still needs per-trade
+    /// pricing.
+    pub fn partition_vanillas<'a>(trades: &[&'a Trade]) -> (Self, Vec<&'a Trade>) {
+        let (vanillas, others): (Vec<&Trade>, Vec<&Trade>) =
+            trades.iter().copied().partition(|trade| {
+                trade.instrument().as_vanilla().is_some_and(|option| {
+                    matches!(option.exercise_style(), ExerciseStyle::European)
+                        && matches!(option.payoff_type(), PayoffType::Call | PayoffType::Put)
+                })
+            });
+        (Self::from_trades(&vanillas), others)
+    }
+
+    /// Sets one volatility per trade.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `volatilities.len() != self.len()`.
+    pub fn with_volatilities(mut self, volatilities: Vec<f64>) -> Self {
+        assert_eq!(volatilities.len(), self.len());
+        self.volatilities = volatilities;
+        self
+    }
+
+    /// Sets the same volatility for every trade.
+    pub fn with_flat_volatility(mut self, volatility: f64) -> Self {
+        self.volatilities = vec![volatility; self.len()];
+        self
+    }
+
     /// Returns the number of trades in the SoA.
     #[inline]
     pub fn len(&self) -> usize {
@@ -185,6 +229,53 @@ impl TradeSoA {
     pub fn payoff_signs(&self) -> &[i8] {
         &self.payoff_signs
     }
+
+    /// Returns slice of volatilities.
+    #[inline]
+    pub fn volatilities(&self) -> &[f64] {
+        &self.volatilities
+    }
+
+    /// Prices all trades with Black-Scholes and returns notional-scaled
+    /// prices and Greeks column by column.
+    ///
+    /// Every trade is treated as a European call or put; build the SoA
+    /// with [`TradeSoA::partition_vanillas`] to guarantee that. Expired
+    /// trades are valued at intrinsic.
+    ///
+    /// # Arguments
+    ///
+    /// * `spots` - Current spot prices (one per trade)
+    /// * `rate` - Continuously compounded risk-free rate
+    ///
+    /// # Panics
+    ///
+    /// Panics if `spots.len() != self.len()`.
+    pub fn price_black_scholes(&self, spots: &[f64], rate: f64) -> ColumnGreeks {
+        price_columns(self.black_scholes_inputs(spots, rate))
+    }
+
+    /// Parallel version of [`TradeSoA::price_black_scholes`] using Rayon.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `spots.len() != self.len()`.
+    pub fn price_black_scholes_par(&self, spots: &[f64], rate: f64) -> ColumnGreeks {
+        price_columns_par(self.black_scholes_inputs(spots, rate))
+    }
+
+    fn black_scholes_inputs<'a>(&'a self, spots: &'a [f64], rate: f64) -> BlackScholesInputs<'a> {
+        assert_eq!(spots.len(), self.len());
+        BlackScholesInputs {
+            spots,
+            strikes: &self.strikes,
+            maturities: &self.maturities,
+            volatilities: &self.volatilities,
+            notionals: &self.notionals,
+            payoff_signs: &self.payoff_signs,
+            rate,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -330,4 +421,157 @@ mod tests {
         let soa = TradeSoA::with_capacity(100);
         assert!(soa.is_empty());
     }
+
+    fn create_option_trade(
+        id: &str,
+        strike: f64,
+        expiry: f64,
+        payoff: PayoffType,
+        exercise: ExerciseStyle<f64>,
+    ) -> Trade {
+        let params = InstrumentParams::new(strike, expiry, 1.0).unwrap();
+        let option = VanillaOption::new(params, payoff, exercise, 1e-6);
+        Trade::new(
+            TradeId::new(id),
+            Instrument::Vanilla(option),
+            Currency::USD,
+            CounterpartyId::new("CP001"),
+            NettingSetId::new("NS001"),
+            2.0,
+        )
+    }
+
+    #[test]
+    fn test_soa_partition_vanillas() {
+        let call = create_option_trade("C", 100.0, 1.0, PayoffType::Call, ExerciseStyle::European);
+        let american =
+            create_option_trade("A", 100.0, 1.0, PayoffType::Put, ExerciseStyle::American);
+        let digital = create_option_trade(
+            "D",
+            100.0,
+            1.0,
+            PayoffType::DigitalCall,
+            ExerciseStyle::European,
+        );
+        let put = create_option_trade("P", 90.0, 0.5, PayoffType::Put, ExerciseStyle::European);
+        let trades: Vec<&Trade> = vec![&call, &american, &digital, &put];
+
+        let (soa, others) = TradeSoA::partition_vanillas(&trades);
+
+        assert_eq!(soa.trade_ids, vec![TradeId::new("C"), TradeId::new("P")]);
+        assert_eq!(soa.maturities(), &[1.0, 0.5]);
+        assert_eq!(soa.volatilities(), &[0.0, 0.0]);
+        let other_ids: Vec<&str> = others.iter().map(|t| t.id().as_str()).collect();
+        assert_eq!(other_ids, vec!["A", "D"]);
+    }
+
+    #[test]
+    fn test_soa_black_scholes_matches_analytical() {
+        use pricer_models::analytical::BlackScholes;
+
+        let strikes = [80.0, 95.0, 100.0, 105.0, 130.0];
+        let expiries = [0.1, 0.5, 1.0, 2.0, 5.0];
+        let mut trades = Vec::new();
+        for (i, (&strike, &expiry)) in strikes.iter().zip(&expiries).enumerate() {
+            for payoff in [PayoffType::Call, PayoffType::Put] {
+                let id = format!("T{}{:?}", i, payoff);
+                trades.push(create_option_trade(
+                    &id,
+                    strike,
+                    expiry,
+                    payoff,
+                    ExerciseStyle::European,
+                ));
+            }
+        }
+        let refs: Vec<&Trade> = trades.iter().collect();
+        let vols: Vec<f64> = (0..refs.len()).map(|i| 0.1 + 0.03 * i as f64).collect();
+        let soa = TradeSoA::from_trades(&refs).with_volatilities(vols.clone());
+        let spots = vec![102.0; soa.len()];
+        let rate = 0.03;
+
+        let greeks = soa.price_black_scholes(&spots, rate);
+        assert_eq!(greeks.len(), soa.len());
+
+        for i in 0..soa.len() {
+            let bs = BlackScholes::new(spots[i], rate, vols[i]).unwrap();
+            let (k, t) = (soa.strikes[i], soa.maturities[i]);
+            let is_call = soa.payoff_signs[i] > 0;
+            let price = if is_call {
+                bs.price_call(k, t)
+            } else {
+                bs.price_put(k, t)
+            };
+            assert_relative_eq!(greeks.prices[i], 2.0 * price, epsilon = 1e-9);
+            assert_relative_eq!(
+                greeks.deltas[i],
+                2.0 * bs.delta(k, t, is_call),
+                epsilon = 1e-9
+            );
+            assert_relative_eq!(greeks.gammas[i], 2.0 * bs.gamma(k, t), epsilon = 1e-9);
+            assert_relative_eq!(greeks.vegas[i], 2.0 * bs.vega(k, t), epsilon = 1e-9);
+            assert_relative_eq!(
+                greeks.thetas[i],
+                2.0 * bs.theta(k, t, is_call),
+                epsilon = 1e-9
+            );
+            assert_relative_eq!(greeks.rhos[i], 2.0 * bs.rho(k, t, is_call), epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_soa_black_scholes_par_matches_sequential() {
+        let trades: Vec<Trade> = (0..2_500)
+            .map(|i| {
+                let payoff = if i % 3 == 0 {
+                    PayoffType::Put
+                } else {
+                    PayoffType::Call
+                };
+                create_option_trade(
+                    &format!("T{}", i),
+                    70.0 + (i % 60) as f64,
+                    0.25 + (i % 8) as f64 * 0.5,
+                    payoff,
+                    ExerciseStyle::European,
+                )
+            })
+            .collect();
+        let refs: Vec<&Trade> = trades.iter().collect();
+        let soa = TradeSoA::from_trades(&refs).with_flat_volatility(0.25);
+        let spots = vec![100.0; soa.len()];
+
+        let sequential = soa.price_black_scholes(&spots, 0.02);
+        let parallel = soa.price_black_scholes_par(&spots, 0.02);
+
+        assert_eq!(sequential, parallel);
+        assert_relative_eq!(
+            sequential.total_price(),
+            sequential.prices.iter().sum::<f64>(),
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn test_soa_black_scholes_degenerate_inputs() {
+        let expired =
+            create_option_trade("E", 100.0, 1.0, PayoffType::Call, ExerciseStyle::European);
+        let flat = create_option_trade("F", 100.0, 1.0, PayoffType::Put, ExerciseStyle::European);
+        let trades: Vec<&Trade> = vec![&expired, &flat];
+        let mut soa = TradeSoA::from_trades(&trades).with_volatilities(vec![0.2, 0.0]);
+        // Instruments reject zero expiry; age the first trade to expiry
+        soa.maturities[0] = 0.0;
+
+        let greeks = soa.price_black_scholes(&[110.0, 90.0], 0.05);
+
+        // Expired ITM call: intrinsic with unit delta
+        assert_relative_eq!(greeks.prices[0], 20.0, epsilon = 1e-12);
+        assert_relative_eq!(greeks.deltas[0], 2.0, epsilon = 1e-12);
+        assert_eq!(greeks.gammas[0], 0.0);
+        // Zero-vol put: discounted forward intrinsic, all finite
+        let df = (-0.05_f64).exp();
+        assert_relative_eq!(greeks.prices[1], 2.0 * (100.0 * df - 90.0), epsilon = 1e-9);
+        assert_relative_eq!(greeks.deltas[1], -2.0, epsilon = 1e-12);
+        assert!(greeks.thetas[1].is_finite() && greeks.gammas[1].is_finite());
+    }
 }
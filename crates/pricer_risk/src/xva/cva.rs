@@ -11,6 +11,8 @@
 //! - EE(t) = Expected Exposure at time t
 //! - dPD(t) = Marginal default probability
 
+use pricer_core::math::quadrature::trapezoid_stieltjes;
+
 use crate::portfolio::CreditParams;
 
 /// Computes unilateral CVA for a netting set.
@@ -45,22 +47,12 @@ pub fn compute_cva(ee: &[f64], time_grid: &[f64], credit_params: &CreditParams)
         return 0.0;
     }
 
-    let lgd = credit_params.lgd();
-    let mut cva = 0.0;
-
-    // Numerical integration using trapezoidal rule
-    for i in 0..time_grid.len() - 1 {
-        let t1 = time_grid[i];
-        let t2 = time_grid[i + 1];
-
-        // Marginal default probability over interval [t1, t2]
-        let marginal_pd = credit_params.marginal_default_prob(t1, t2);
-
-        // Average EE over interval (trapezoidal)
-        let avg_ee = 0.5 * (ee[i] + ee[i + 1]);
-
-        cva += lgd * avg_ee * marginal_pd;
-    }
+    // Trapezoidal integral of EE against the cumulative default probability
+    let default_prob: Vec<f64> = time_grid
+        .iter()
+        .map(|&t| 1.0 - credit_params.survival_prob(t))
+        .collect();
+    let cva = credit_params.lgd() * trapezoid_stieltjes(ee, &default_prob);
 
     cva.max(0.0) // Ensure non-negative
 }
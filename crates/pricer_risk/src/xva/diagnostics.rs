@@ -0,0 +1,101 @@
+//! Numerical diagnostics for XVA results.
+//!
+//! XVA integrals are evaluated by the trapezoidal rule on the exposure time
+//! grid, so their accuracy depends on the grid spacing and on the Monte Carlo
+//! error of the exposure profiles fed in. [`XvaDiagnostics`] records both,
+//! along with any netting sets that were silently left out because their
+//! exposure profiles or counterparty were missing.
+
+use crate::portfolio::NettingSetId;
+
+/// Two-sided 95% normal quantile.
+const Z_95: f64 = 1.96;
+
+/// Error budget and condition metadata for a portfolio XVA result.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct XvaDiagnostics {
+    /// Number of points in the integration time grid.
+    pub n_time_points: usize,
+    /// Smallest grid spacing in years.
+    pub min_time_step: f64,
+    /// Largest grid spacing in years.
+    pub max_time_step: f64,
+    /// Netting sets left out of the result, sorted by identifier.
+    pub skipped_netting_sets: Vec<NettingSetId>,
+    /// Standard error of the portfolio CVA from exposure simulation, if known.
+    pub cva_std_error: Option<f64>,
+}
+
+impl XvaDiagnostics {
+    /// Creates diagnostics for an integration over `time_grid`.
+    pub fn new(time_grid: &[f64]) -> Self {
+        let steps = time_grid.windows(2).map(|w| w[1] - w[0]);
+        let (min_time_step, max_time_step) = steps
+            .fold(None, |acc: Option<(f64, f64)>, dt| {
+                Some(acc.map_or((dt, dt), |(lo, hi)| (lo.min(dt), hi.max(dt))))
+            })
+            .unwrap_or_default();
+        Self {
+            n_time_points: time_grid.len(),
+            min_time_step,
+            max_time_step,
+            skipped_netting_sets: Vec::new(),
+            cva_std_error: None,
+        }
+    }
+
+    /// Records the netting sets that were left out of the result.
+    pub fn with_skipped(mut self, mut skipped: Vec<NettingSetId>) -> Self {
+        skipped.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        self.skipped_netting_sets = skipped;
+        self
+    }
+
+    /// Returns whether every netting set in the portfolio was included.
+    #[inline]
+    pub fn is_complete(&self) -> bool {
+        self.skipped_netting_sets.is_empty()
+    }
+
+    /// Returns the 95% confidence interval around `cva`, if the CVA
+    /// standard error is known.
+    pub fn cva_confidence_interval(&self, cva: f64) -> Option<(f64, f64)> {
+        self.cva_std_error
+            .map(|se| (cva - Z_95 * se, cva + Z_95 * se))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_steps() {
+        let diag = XvaDiagnostics::new(&[0.0, 0.25, 0.5, 1.0, 2.0]);
+        assert_eq!(diag.n_time_points, 5);
+        assert_eq!(diag.min_time_step, 0.25);
+        assert_eq!(diag.max_time_step, 1.0);
+        assert!(diag.is_complete());
+    }
+
+    #[test]
+    fn test_single_point_grid() {
+        let diag = XvaDiagnostics::new(&[1.0]);
+        assert_eq!(diag.n_time_points, 1);
+        assert_eq!(diag.min_time_step, 0.0);
+        assert_eq!(diag.max_time_step, 0.0);
+    }
+
+    #[test]
+    fn test_skipped_and_confidence_interval() {
+        let mut diag = XvaDiagnostics::new(&[0.0, 1.0])
+            .with_skipped(vec![NettingSetId::new("NS2"), NettingSetId::new("NS1")]);
+        assert!(!diag.is_complete());
+        assert_eq!(diag.skipped_netting_sets[0], NettingSetId::new("NS1"));
+
+        assert_eq!(diag.cva_confidence_interval(10.0), None);
+        diag.cva_std_error = Some(0.5);
+        assert_eq!(diag.cva_confidence_interval(10.0), Some((9.02, 10.98)));
+    }
+}
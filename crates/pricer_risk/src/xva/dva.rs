@@ -11,6 +11,8 @@
 //! - ENE(t) = Expected Negative Exposure at time t
 //! - dPD_own(t) = Own marginal default probability
 
+use pricer_core::math::quadrature::trapezoid_stieltjes;
+
 use super::params::OwnCreditParams;
 
 /// Computes DVA for a netting set.
@@ -44,22 +46,12 @@ pub fn compute_dva(ene: &[f64], time_grid: &[f64], own_credit: &OwnCreditParams)
         return 0.0;
     }
 
-    let lgd = own_credit.lgd();
-    let mut dva = 0.0;
-
-    // Numerical integration using trapezoidal rule
-    for i in 0..time_grid.len() - 1 {
-        let t1 = time_grid[i];
-        let t2 = time_grid[i + 1];
-
-        // Own marginal default probability
-        let marginal_pd = own_credit.marginal_pd(t1, t2);
-
-        // Average ENE over interval
-        let avg_ene = 0.5 * (ene[i] + ene[i + 1]);
-
-        dva += lgd * avg_ene * marginal_pd;
-    }
+    // Trapezoidal integral of ENE against our cumulative default probability
+    let default_prob: Vec<f64> = time_grid
+        .iter()
+        .map(|&t| 1.0 - own_credit.survival_prob(t))
+        .collect();
+    let dva = own_credit.lgd() * trapezoid_stieltjes(ene, &default_prob);
 
     dva.max(0.0)
 }
@@ -9,6 +9,14 @@ pub enum XvaError {
     #[error("Missing exposure profile for netting set: {0}")]
     MissingExposureProfile(String),
 
+    /// Netting set not found in the portfolio.
+    #[error("Netting set not found: {0}")]
+    NettingSetNotFound(String),
+
+    /// Missing simulated values for a trade.
+    #[error("Missing trade values: {0}")]
+    MissingTradeValues(String),
+
     /// Missing credit parameters for a counterparty.
     #[error("Missing credit parameters for counterparty: {0}")]
     MissingCreditParams(String),
@@ -38,6 +46,15 @@ pub enum XvaError {
         actual: usize,
     },
 
+    /// Scenario count mismatch between trades of a netting set.
+    #[error("Scenario count mismatch: expected {expected} scenarios, got {actual}")]
+    ScenarioCountMismatch {
+        /// Expected number of scenarios.
+        expected: usize,
+        /// Actual number of scenarios.
+        actual: usize,
+    },
+
     /// Empty time grid.
     #[error("Time grid is empty")]
     EmptyTimeGrid,
@@ -14,6 +14,21 @@
 //! - s_borrow = Borrowing spread
 //! - s_lend = Lending spread
 //! - df(t) = Discount factor
+//!
+//! # Funding Curve and Asymmetry
+//!
+//! [`compute_fva`] takes its spreads and conventions from
+//! [`FundingParams`]:
+//!
+//! - With a funding curve spread s_f, funding cash flows are discounted on
+//!   the bank's own curve, df_f(t) = df(t) × exp(-s_f × t), while CVA and
+//!   DVA remain on risk-free discounting.
+//! - With asymmetric funding, collateralised netting sets carry no FCA
+//!   (the CSA funds the exposure) and FBA is dropped, since the benefit
+//!   of funding negative exposure at the bank's own spread is already
+//!   captured by DVA.
+
+use super::params::FundingParams;
 
 /// Computes Funding Cost Adjustment (FCA).
 ///
@@ -109,7 +124,7 @@ pub fn compute_fba(
     fba.max(0.0)
 }
 
-/// Computes combined FVA (FCA - FBA).
+/// Computes combined FVA (FCA - FBA) for one netting set.
 ///
 /// Positive FVA = net funding cost
 /// Negative FVA = net funding benefit
@@ -119,25 +134,55 @@ pub fn compute_fba(
 /// * `ee` - Expected Exposure profile
 /// * `ene` - Expected Negative Exposure profile
 /// * `time_grid` - Time points
-/// * `spread_borrow` - Borrowing spread
-/// * `spread_lend` - Lending spread
-/// * `discount_factors` - Discount factors
+/// * `funding` - Funding spreads, funding curve and asymmetry
+/// * `discount_factors` - Risk-free discount factors
+/// * `collateralised` - Whether the netting set is under a CSA
 ///
 /// # Returns
 ///
 /// Tuple of (FCA, FBA, FVA).
+///
+/// # Examples
+///
+/// ```
+/// use pricer_risk::xva::{compute_fva, FundingParams};
+///
+/// let ee = vec![100.0; 5];
+/// let ene = vec![40.0; 5];
+/// let time_grid = vec![0.0, 0.25, 0.5, 0.75, 1.0];
+/// let df = vec![1.0; 5];
+///
+/// let symmetric = FundingParams::symmetric(0.01);
+/// let (fca, fba, _) = compute_fva(&ee, &ene, &time_grid, &symmetric, &df, false);
+/// assert!((fca - 1.0).abs() < 1e-12);
+/// assert!((fba - 0.4).abs() < 1e-12);
+///
+/// let asymmetric = FundingParams::symmetric(0.01).with_asymmetric_funding();
+/// let (_, fba, fva) = compute_fva(&ee, &ene, &time_grid, &asymmetric, &df, true);
+/// assert_eq!((fba, fva), (0.0, 0.0));
+/// ```
 pub fn compute_fva(
     ee: &[f64],
     ene: &[f64],
     time_grid: &[f64],
-    spread_borrow: f64,
-    spread_lend: f64,
+    funding: &FundingParams,
     discount_factors: &[f64],
+    collateralised: bool,
 ) -> (f64, f64, f64) {
-    let fca = compute_fca(ee, time_grid, spread_borrow, discount_factors);
-    let fba = compute_fba(ene, time_grid, spread_lend, discount_factors);
-    let fva = fca - fba;
-    (fca, fba, fva)
+    let funding_df = funding.funding_discount_factors(time_grid, discount_factors);
+
+    let fca = if funding.asymmetric_funding && collateralised {
+        0.0
+    } else {
+        compute_fca(ee, time_grid, funding.spread_borrow, &funding_df)
+    };
+    let fba = if funding.asymmetric_funding {
+        0.0
+    } else {
+        compute_fba(ene, time_grid, funding.spread_lend, &funding_df)
+    };
+
+    (fca, fba, fca - fba)
 }
 
 #[cfg(test)]
@@ -219,7 +264,14 @@ mod tests {
         let time_grid = vec![0.0, 0.25, 0.5, 0.75, 1.0];
         let df = create_flat_df(0.05, &time_grid);
 
-        let (fca, fba, fva) = compute_fva(&ee, &ene, &time_grid, 0.005, 0.003, &df);
+        let (fca, fba, fva) = compute_fva(
+            &ee,
+            &ene,
+            &time_grid,
+            &FundingParams::asymmetric(0.005, 0.003),
+            &df,
+            false,
+        );
 
         assert!(fca > 0.0);
         assert!(fba > 0.0);
@@ -235,7 +287,14 @@ mod tests {
         let time_grid = vec![0.0, 0.25, 0.5, 0.75, 1.0];
         let df = create_flat_df(0.05, &time_grid);
 
-        let (_fca, _fba, fva) = compute_fva(&ee, &ene, &time_grid, 0.005, 0.005, &df);
+        let (_fca, _fba, fva) = compute_fva(
+            &ee,
+            &ene,
+            &time_grid,
+            &FundingParams::symmetric(0.005),
+            &df,
+            false,
+        );
 
         assert!(fva < 0.0); // Net benefit
     }
@@ -248,7 +307,14 @@ mod tests {
         let df = create_flat_df(0.05, &time_grid);
 
         // With symmetric spreads and equal EE/ENE, FVA should be ~0
-        let (fca, fba, fva) = compute_fva(&ee, &ene, &time_grid, 0.005, 0.005, &df);
+        let (fca, fba, fva) = compute_fva(
+            &ee,
+            &ene,
+            &time_grid,
+            &FundingParams::symmetric(0.005),
+            &df,
+            false,
+        );
 
         assert_relative_eq!(fva, 0.0, epsilon = 1e-10);
         assert_relative_eq!(fca, fba, epsilon = 1e-10);
@@ -256,7 +322,14 @@ mod tests {
 
     #[test]
     fn test_fva_empty_inputs() {
-        let (fca, fba, fva) = compute_fva(&[], &[], &[], 0.005, 0.003, &[]);
+        let (fca, fba, fva) = compute_fva(
+            &[],
+            &[],
+            &[],
+            &FundingParams::asymmetric(0.005, 0.003),
+            &[],
+            false,
+        );
         assert_eq!(fca, 0.0);
         assert_eq!(fba, 0.0);
         assert_eq!(fva, 0.0);
@@ -271,4 +344,78 @@ mod tests {
         let fca = compute_fca(&ee, &time_grid, 0.005, &df);
         assert!(fca >= 0.0);
     }
+
+    // Closed-form examples: flat profiles on a fine grid, where the
+    // trapezoidal sums converge to the textbook integrals.
+
+    fn fine_grid(maturity: f64, steps: usize) -> Vec<f64> {
+        (0..=steps)
+            .map(|i| maturity * i as f64 / steps as f64)
+            .collect()
+    }
+
+    #[test]
+    fn test_textbook_flat_exposure_risk_free_discounting() {
+        // FCA = EE × s_B × ∫₀ᵀ e^{-rt} dt = EE × s_B × (1 - e^{-rT}) / r
+        let time_grid = fine_grid(5.0, 500);
+        let ee = vec![1_000_000.0; time_grid.len()];
+        let ene = vec![400_000.0; time_grid.len()];
+        let df = create_flat_df(0.03, &time_grid);
+        let funding = FundingParams::from_bps(100.0, 50.0);
+
+        let (fca, fba, fva) = compute_fva(&ee, &ene, &time_grid, &funding, &df, false);
+
+        let annuity = (1.0 - (-0.03_f64 * 5.0).exp()) / 0.03;
+        assert_relative_eq!(fca, 1_000_000.0 * 0.01 * annuity, max_relative = 1e-5);
+        assert_relative_eq!(fba, 400_000.0 * 0.005 * annuity, max_relative = 1e-5);
+        assert_relative_eq!(fva, fca - fba, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_textbook_own_funding_curve_discounting() {
+        // Discounting at r + s_f: FCA = EE × s_B × (1 - e^{-(r+s_f)T}) / (r + s_f)
+        let time_grid = fine_grid(5.0, 500);
+        let ee = vec![1_000_000.0; time_grid.len()];
+        let ene = vec![0.0; time_grid.len()];
+        let df = create_flat_df(0.03, &time_grid);
+        let risk_free = FundingParams::from_bps(100.0, 100.0);
+        let own_curve = risk_free.clone().with_funding_curve_spread(0.01);
+
+        let (fca_rf, _, _) = compute_fva(&ee, &ene, &time_grid, &risk_free, &df, false);
+        let (fca_own, _, _) = compute_fva(&ee, &ene, &time_grid, &own_curve, &df, false);
+
+        let annuity = (1.0 - (-0.04_f64 * 5.0).exp()) / 0.04;
+        assert_relative_eq!(fca_own, 1_000_000.0 * 0.01 * annuity, max_relative = 1e-5);
+        assert!(fca_own < fca_rf);
+
+        // A zero funding curve spread reproduces risk-free discounting
+        let zero_spread = risk_free.clone().with_funding_curve_spread(0.0);
+        let (fca_zero, _, _) = compute_fva(&ee, &ene, &time_grid, &zero_spread, &df, false);
+        assert_relative_eq!(fca_zero, fca_rf, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_textbook_asymmetric_funding() {
+        let time_grid = fine_grid(2.0, 200);
+        let ee = vec![500.0; time_grid.len()];
+        let ene = vec![300.0; time_grid.len()];
+        let df = vec![1.0; time_grid.len()];
+        let funding = FundingParams::from_bps(80.0, 80.0).with_asymmetric_funding();
+
+        // Uncollateralised: FCA = 500 × 0.008 × 2, no FBA
+        let (fca, fba, fva) = compute_fva(&ee, &ene, &time_grid, &funding, &df, false);
+        assert_relative_eq!(fca, 8.0, epsilon = 1e-10);
+        assert_eq!(fba, 0.0);
+        assert_relative_eq!(fva, 8.0, epsilon = 1e-10);
+
+        // Collateralised: funded through the CSA
+        let (fca, fba, fva) = compute_fva(&ee, &ene, &time_grid, &funding, &df, true);
+        assert_eq!((fca, fba, fva), (0.0, 0.0, 0.0));
+
+        // Symmetric funding charges collateralised sets as before
+        let symmetric = FundingParams::from_bps(80.0, 80.0);
+        let (fca, fba, _) = compute_fva(&ee, &ene, &time_grid, &symmetric, &df, true);
+        assert_relative_eq!(fca, 8.0, epsilon = 1e-10);
+        assert_relative_eq!(fba, 4.8, epsilon = 1e-10);
+    }
 }
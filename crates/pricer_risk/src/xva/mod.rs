@@ -10,6 +10,15 @@
 //! - **FVA** (Funding Valuation Adjustment): Cost/benefit of funding exposures
 //!   - FCA (Funding Cost Adjustment): Cost of funding positive exposure
 //!   - FBA (Funding Benefit Adjustment): Benefit from negative exposure
+//! - **ColVA** (Collateral Valuation Adjustment): Value of collateral
+//!   remuneration differing from OIS, split into received and posted
+//!
+//! Portfolio results carry [`XvaDiagnostics`]: integration grid spacing,
+//! netting sets left out for missing profiles, and an optional CVA standard
+//! error, so downstream systems can flag low-confidence numbers.
+//!
+//! Netting-set XVA can be allocated back to trades by Euler allocation
+//! ([`XvaCalculator::allocate_to_trades`]).
 //!
 //! # Architecture
 //!
@@ -21,13 +30,16 @@
 //! │    - Portfolio (trades, counterparties, netting)   │
 //! │    - ExposureSoA (EE/ENE profiles)                 │
 //! │    - OwnCreditParams (own hazard rate, LGD)        │
-//! │    - FundingParams (borrow/lend spreads)           │
+//! │    - FundingParams (spreads, funding curve)        │
+//! │    - Collateral profiles (received/posted)         │
 //! │    - DiscountFactors (risk-free)                   │
 //! ├─────────────────────────────────────────────────────┤
 //! │  Outputs:                                           │
 //! │    - NettingSetXva (per netting set)               │
 //! │    - CounterpartyXva (aggregated per counterparty) │
 //! │    - PortfolioXva (total portfolio XVA)            │
+//! │    - TradeXva (Euler allocation per trade)         │
+//! │    - XvaDiagnostics (grid, skipped netting sets)   │
 //! └─────────────────────────────────────────────────────┘
 //! ```
 //!
@@ -53,22 +65,30 @@
 //! println!("Net FVA: {}", xva.fva());
 //! ```
 
+mod allocation;
+mod colva;
 mod cva;
+mod diagnostics;
 mod dva;
 mod error;
 mod fva;
 mod params;
 mod result;
 
+pub use colva::{compute_colva, ColvaBreakdown};
 pub use cva::{compute_cva, compute_cva_with_survival};
+pub use diagnostics::XvaDiagnostics;
 pub use dva::{compute_dva, compute_dva_with_survival};
 pub use error::XvaError;
 pub use fva::{compute_fba, compute_fca, compute_fva};
 pub use params::{FundingParams, OwnCreditParams};
-pub use result::{CounterpartyXva, NettingSetXva, PortfolioXva};
+pub use result::{CounterpartyXva, NettingSetXva, PortfolioXva, TradeXva};
 
-use crate::portfolio::{CounterpartyId, CreditParams, NettingSetId, Portfolio};
+use crate::portfolio::{
+    CollateralAgreement, CounterpartyId, CreditParams, NettingSetId, Portfolio,
+};
 use crate::soa::ExposureSoA;
+use pricer_core::types::{fingerprint, RunMetadata};
 use rayon::prelude::*;
 use std::collections::HashMap;
 
@@ -76,6 +96,7 @@ use std::collections::HashMap;
 ///
 /// Holds parameters that apply across all netting sets and counterparties.
 #[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct XvaConfig {
     /// Own credit parameters for DVA calculation.
     pub own_credit: Option<OwnCreditParams>,
@@ -181,7 +202,8 @@ impl XvaCalculator {
     ///
     /// # Returns
     ///
-    /// XVA result for the netting set.
+    /// XVA result for the netting set, treated as uncollateralised for
+    /// asymmetric funding.
     #[allow(clippy::too_many_arguments)]
     pub fn compute_netting_set_xva(
         &self,
@@ -192,6 +214,30 @@ impl XvaCalculator {
         time_grid: &[f64],
         credit_params: &CreditParams,
         discount_factors: &[f64],
+    ) -> NettingSetXva {
+        self.netting_set_xva(
+            netting_set_id,
+            counterparty_id,
+            ee,
+            ene,
+            time_grid,
+            credit_params,
+            discount_factors,
+            false,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn netting_set_xva(
+        &self,
+        netting_set_id: NettingSetId,
+        counterparty_id: CounterpartyId,
+        ee: &[f64],
+        ene: &[f64],
+        time_grid: &[f64],
+        credit_params: &CreditParams,
+        discount_factors: &[f64],
+        collateralised: bool,
     ) -> NettingSetXva {
         // Compute CVA
         let cva = compute_cva(ee, time_grid, credit_params);
@@ -209,9 +255,9 @@ impl XvaCalculator {
             ee,
             ene,
             time_grid,
-            self.config.funding.spread_borrow,
-            self.config.funding.spread_lend,
+            &self.config.funding,
             discount_factors,
+            collateralised,
         );
 
         NettingSetXva::new(netting_set_id, counterparty_id, cva, dva, fca, fba)
@@ -231,7 +277,8 @@ impl XvaCalculator {
     ///
     /// # Returns
     ///
-    /// Aggregated XVA for the counterparty.
+    /// Aggregated XVA for the counterparty, with every netting set
+    /// treated as uncollateralised for asymmetric funding.
     #[allow(clippy::too_many_arguments)]
     pub fn compute_counterparty_xva(
         &self,
@@ -242,14 +289,41 @@ impl XvaCalculator {
         time_grid: &[f64],
         credit_params: &CreditParams,
         discount_factors: &[f64],
+    ) -> CounterpartyXva {
+        self.counterparty_xva(
+            counterparty_id,
+            netting_set_ids,
+            ee_profiles,
+            ene_profiles,
+            None,
+            time_grid,
+            credit_params,
+            discount_factors,
+            |_| None,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn counterparty_xva<'p>(
+        &self,
+        counterparty_id: CounterpartyId,
+        netting_set_ids: &[NettingSetId],
+        ee_profiles: &HashMap<NettingSetId, Vec<f64>>,
+        ene_profiles: &HashMap<NettingSetId, Vec<f64>>,
+        collateral_profiles: Option<CollateralProfiles<'_>>,
+        time_grid: &[f64],
+        credit_params: &CreditParams,
+        discount_factors: &[f64],
+        collateral: impl Fn(&NettingSetId) -> Option<&'p CollateralAgreement>,
     ) -> CounterpartyXva {
         let netting_set_xvas: Vec<NettingSetXva> = netting_set_ids
             .iter()
             .filter_map(|ns_id| {
                 let ee = ee_profiles.get(ns_id)?;
                 let ene = ene_profiles.get(ns_id)?;
+                let agreement = collateral(ns_id);
 
-                Some(self.compute_netting_set_xva(
+                let xva = self.netting_set_xva(
                     ns_id.clone(),
                     counterparty_id.clone(),
                     ee,
@@ -257,7 +331,29 @@ impl XvaCalculator {
                     time_grid,
                     credit_params,
                     discount_factors,
-                ))
+                    agreement.is_some(),
+                );
+                let colva = agreement
+                    .zip(collateral_profiles)
+                    .and_then(|(csa, profiles)| {
+                        Some((
+                            csa,
+                            profiles.received.get(ns_id)?,
+                            profiles.posted.get(ns_id)?,
+                        ))
+                    })
+                    .map(|(csa, received, posted)| {
+                        compute_colva(
+                            received,
+                            posted,
+                            time_grid,
+                            csa.remuneration_spread(),
+                            discount_factors,
+                        )
+                    })
+                    .unwrap_or_default();
+
+                Some(xva.with_colva(colva))
             })
             .collect();
 
@@ -267,6 +363,8 @@ impl XvaCalculator {
     /// Computes XVA for the entire portfolio.
     ///
     /// Uses parallel processing across counterparties for efficiency.
+    /// Under asymmetric funding, netting sets with a collateral agreement
+    /// carry no FCA.
     ///
     /// # Arguments
     ///
@@ -286,6 +384,77 @@ impl XvaCalculator {
         ene_profiles: &HashMap<NettingSetId, Vec<f64>>,
         time_grid: &[f64],
         discount_factors: &[f64],
+    ) -> Result<PortfolioXva, XvaError> {
+        self.portfolio_xva(
+            portfolio,
+            ee_profiles,
+            ene_profiles,
+            None,
+            time_grid,
+            discount_factors,
+        )
+    }
+
+    /// Computes XVA for the entire portfolio, including ColVA.
+    ///
+    /// ColVA is computed for each collateralised netting set with both
+    /// collateral profiles, at its agreement's remuneration spread.
+    ///
+    /// # Arguments
+    ///
+    /// * `portfolio` - Portfolio containing counterparties and netting sets
+    /// * `ee_profiles` - Expected Exposure profiles by netting set
+    /// * `ene_profiles` - Expected Negative Exposure profiles by netting set
+    /// * `received_collateral` - Expected collateral received by netting set
+    /// * `posted_collateral` - Expected collateral posted by netting set
+    /// * `time_grid` - Shared time grid
+    /// * `discount_factors` - Risk-free discount factors
+    ///
+    /// # Returns
+    ///
+    /// Portfolio-level XVA result, or error if required data is missing.
+    #[allow(clippy::too_many_arguments)]
+    pub fn compute_portfolio_xva_with_collateral(
+        &self,
+        portfolio: &Portfolio,
+        ee_profiles: &HashMap<NettingSetId, Vec<f64>>,
+        ene_profiles: &HashMap<NettingSetId, Vec<f64>>,
+        received_collateral: &HashMap<NettingSetId, Vec<f64>>,
+        posted_collateral: &HashMap<NettingSetId, Vec<f64>>,
+        time_grid: &[f64],
+        discount_factors: &[f64],
+    ) -> Result<PortfolioXva, XvaError> {
+        let collateral_profiles = CollateralProfiles {
+            received: received_collateral,
+            posted: posted_collateral,
+        };
+        self.portfolio_xva(
+            portfolio,
+            ee_profiles,
+            ene_profiles,
+            Some(collateral_profiles),
+            time_grid,
+            discount_factors,
+        )
+    }
+
+    /// Provenance of a portfolio run: engine versions and fingerprints of
+    /// the configuration and the discounting inputs.
+    fn run_metadata(&self, time_grid: &[f64], discount_factors: &[f64]) -> RunMetadata {
+        RunMetadata::current()
+            .with_engine_version("pricer_risk", env!("CARGO_PKG_VERSION"))
+            .with_config_hash(fingerprint(&self.config))
+            .with_market_snapshot_hash(fingerprint(&(time_grid, discount_factors)))
+    }
+
+    fn portfolio_xva(
+        &self,
+        portfolio: &Portfolio,
+        ee_profiles: &HashMap<NettingSetId, Vec<f64>>,
+        ene_profiles: &HashMap<NettingSetId, Vec<f64>>,
+        collateral_profiles: Option<CollateralProfiles<'_>>,
+        time_grid: &[f64],
+        discount_factors: &[f64],
     ) -> Result<PortfolioXva, XvaError> {
         // Validate inputs
         if time_grid.is_empty() {
@@ -316,19 +485,26 @@ impl XvaCalculator {
                 let counterparty = portfolio.counterparty(cp_id)?;
                 let credit_params = counterparty.credit_params();
 
-                Some(self.compute_counterparty_xva(
+                Some(self.counterparty_xva(
                     cp_id.clone(),
                     ns_ids,
                     ee_profiles,
                     ene_profiles,
+                    collateral_profiles,
                     time_grid,
                     credit_params,
                     discount_factors,
+                    |ns_id| collateral_agreement(portfolio, ns_id),
                 ))
             })
             .collect();
 
-        Ok(PortfolioXva::from_counterparties(counterparty_xvas))
+        let skipped = skipped_netting_sets(portfolio, |ns_id| {
+            ee_profiles.contains_key(ns_id) && ene_profiles.contains_key(ns_id)
+        });
+        Ok(PortfolioXva::from_counterparties(counterparty_xvas)
+            .with_diagnostics(XvaDiagnostics::new(time_grid).with_skipped(skipped))
+            .with_metadata(self.run_metadata(time_grid, discount_factors)))
     }
 
     /// Computes XVA using ExposureSoA for efficient memory access.
@@ -414,7 +590,7 @@ impl XvaCalculator {
                         let ee = ee_soa.exposure_profile(*ee_idx);
                         let ene = ene_soa.exposure_profile(*ene_idx);
 
-                        Some(self.compute_netting_set_xva(
+                        Some(self.netting_set_xva(
                             (*ns_id).clone(),
                             cp_id.clone(),
                             ee,
@@ -422,6 +598,7 @@ impl XvaCalculator {
                             time_grid,
                             credit_params,
                             discount_factors,
+                            collateral_agreement(portfolio, ns_id).is_some(),
                         ))
                     })
                     .collect();
@@ -433,10 +610,46 @@ impl XvaCalculator {
             })
             .collect();
 
-        Ok(PortfolioXva::from_counterparties(counterparty_xvas))
+        let skipped = skipped_netting_sets(portfolio, |ns_id| {
+            ee_lookup.contains_key(ns_id) && ene_lookup.contains_key(ns_id)
+        });
+        Ok(PortfolioXva::from_counterparties(counterparty_xvas)
+            .with_diagnostics(XvaDiagnostics::new(time_grid).with_skipped(skipped))
+            .with_metadata(self.run_metadata(time_grid, discount_factors)))
     }
 }
 
+/// Netting sets that drop out of a portfolio XVA run: those whose
+/// counterparty is unknown or that lack exposure profiles.
+fn skipped_netting_sets(
+    portfolio: &Portfolio,
+    has_profiles: impl Fn(&NettingSetId) -> bool,
+) -> Vec<NettingSetId> {
+    portfolio
+        .netting_sets()
+        .filter(|ns| {
+            portfolio.counterparty(ns.counterparty_id()).is_none() || !has_profiles(ns.id())
+        })
+        .map(|ns| ns.id().clone())
+        .collect()
+}
+
+/// Expected collateral profiles by netting set, for ColVA.
+#[derive(Clone, Copy)]
+struct CollateralProfiles<'a> {
+    received: &'a HashMap<NettingSetId, Vec<f64>>,
+    posted: &'a HashMap<NettingSetId, Vec<f64>>,
+}
+
+fn collateral_agreement<'p>(
+    portfolio: &'p Portfolio,
+    netting_set_id: &NettingSetId,
+) -> Option<&'p CollateralAgreement> {
+    portfolio
+        .netting_set(netting_set_id)
+        .and_then(|ns| ns.collateral())
+}
+
 /// Generates flat discount factors for a given rate and time grid.
 ///
 /// Useful for testing and simple cases with flat yield curves.
@@ -468,8 +681,9 @@ pub fn generate_flat_discount_factors(rate: f64, time_grid: &[f64]) -> Vec<f64>
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::portfolio::{Counterparty, NettingSet, PortfolioBuilder};
+    use crate::portfolio::{CollateralAgreement, Counterparty, NettingSet, PortfolioBuilder};
     use approx::assert_relative_eq;
+    use pricer_core::types::Currency;
 
     fn create_test_portfolio() -> Portfolio {
         let credit1 = CreditParams::new(0.02, 0.4).unwrap();
@@ -625,6 +839,67 @@ mod tests {
         assert!(xva.fba > 0.0);
         assert_eq!(xva.counterparty_count(), 2);
         assert_eq!(xva.netting_set_count(), 3);
+
+        let diag = xva.diagnostics.as_ref().unwrap();
+        assert_eq!(diag.n_time_points, 5);
+        assert_relative_eq!(diag.max_time_step, 0.25);
+        assert!(diag.is_complete());
+        assert!(!xva.is_low_confidence(0.01));
+
+        let meta = xva.metadata.as_ref().unwrap();
+        assert!(meta.engine_versions.contains_key("pricer_risk"));
+        let rerun = calc
+            .compute_portfolio_xva(&portfolio, &ee_profiles, &ene_profiles, &time_grid, &df)
+            .unwrap();
+        assert!(meta.same_inputs(rerun.metadata.as_ref().unwrap()));
+
+        let bumped = generate_flat_discount_factors(0.051, &time_grid);
+        let bumped = calc
+            .compute_portfolio_xva(&portfolio, &ee_profiles, &ene_profiles, &time_grid, &bumped)
+            .unwrap();
+        assert!(!meta.same_inputs(bumped.metadata.as_ref().unwrap()));
+    }
+
+    #[test]
+    fn test_portfolio_xva_diagnostics_flag_missing_profiles() {
+        let portfolio = create_test_portfolio();
+        let time_grid = create_test_time_grid();
+        let mut ee_profiles = create_test_ee_profiles();
+        ee_profiles.remove(&NettingSetId::new("NS002"));
+        let ene_profiles = create_test_ene_profiles();
+        let df = generate_flat_discount_factors(0.05, &time_grid);
+
+        let xva = XvaCalculator::new()
+            .compute_portfolio_xva(&portfolio, &ee_profiles, &ene_profiles, &time_grid, &df)
+            .unwrap();
+
+        assert_eq!(xva.netting_set_count(), 2);
+        let diag = xva.diagnostics.as_ref().unwrap();
+        assert_eq!(diag.skipped_netting_sets, [NettingSetId::new("NS002")]);
+        assert!(xva.is_low_confidence(1.0));
+    }
+
+    #[test]
+    fn test_portfolio_xva_cva_std_error() {
+        let portfolio = create_test_portfolio();
+        let time_grid = create_test_time_grid();
+        let df = generate_flat_discount_factors(0.05, &time_grid);
+
+        let xva = XvaCalculator::new()
+            .compute_portfolio_xva(
+                &portfolio,
+                &create_test_ee_profiles(),
+                &create_test_ene_profiles(),
+                &time_grid,
+                &df,
+            )
+            .unwrap();
+        let tight = xva.clone().with_cva_std_error(0.001 * xva.cva);
+        let loose = xva.clone().with_cva_std_error(0.1 * xva.cva);
+
+        assert_eq!(tight.diagnostics.as_ref().unwrap().n_time_points, 5);
+        assert!(!tight.is_low_confidence(0.01));
+        assert!(loose.is_low_confidence(0.01));
     }
 
     #[test]
@@ -748,6 +1023,138 @@ mod tests {
         assert_relative_eq!(xva.fba, sum_fba, epsilon = 1e-10);
     }
 
+    #[test]
+    fn test_asymmetric_funding_skips_collateralised_netting_sets() {
+        let csa = CollateralAgreement::zero_threshold(Currency::USD, 10.0 / 365.0).unwrap();
+        let portfolio = PortfolioBuilder::new()
+            .add_counterparty(Counterparty::new(
+                CounterpartyId::new("CP001"),
+                CreditParams::new(0.02, 0.4).unwrap(),
+            ))
+            .add_netting_set(NettingSet::with_collateral(
+                NettingSetId::new("NS001"),
+                CounterpartyId::new("CP001"),
+                csa,
+            ))
+            .add_netting_set(NettingSet::new(
+                NettingSetId::new("NS002"),
+                CounterpartyId::new("CP001"),
+            ))
+            .build()
+            .unwrap();
+        let time_grid = create_test_time_grid();
+        let df = generate_flat_discount_factors(0.05, &time_grid);
+        let funding = FundingParams::from_bps(50.0, 30.0).with_funding_curve_spread(0.005);
+
+        let xva_for = |funding: FundingParams| {
+            XvaCalculator::new()
+                .with_own_credit(OwnCreditParams::new(0.025, 0.4).unwrap())
+                .with_funding(funding)
+                .compute_portfolio_xva(
+                    &portfolio,
+                    &create_test_ee_profiles(),
+                    &create_test_ene_profiles(),
+                    &time_grid,
+                    &df,
+                )
+                .unwrap()
+        };
+        let symmetric = xva_for(funding.clone());
+        let asymmetric = xva_for(funding.with_asymmetric_funding());
+
+        let by_ns = |xva: &PortfolioXva, id: &str| {
+            xva.by_counterparty[0]
+                .netting_set_xvas
+                .iter()
+                .find(|ns| ns.netting_set_id.as_str() == id)
+                .unwrap()
+                .clone()
+        };
+        assert!(by_ns(&symmetric, "NS001").fca > 0.0);
+        assert_eq!(by_ns(&asymmetric, "NS001").fca, 0.0);
+        assert_relative_eq!(
+            by_ns(&asymmetric, "NS002").fca,
+            by_ns(&symmetric, "NS002").fca,
+            epsilon = 1e-12
+        );
+        assert!(symmetric.fba > 0.0);
+        assert_eq!(asymmetric.fba, 0.0);
+
+        // Credit adjustments stay on risk-free discounting
+        assert_relative_eq!(asymmetric.cva, symmetric.cva, epsilon = 1e-12);
+        assert_relative_eq!(asymmetric.dva, symmetric.dva, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_portfolio_colva() {
+        let csa = CollateralAgreement::zero_threshold(Currency::USD, 10.0 / 365.0)
+            .unwrap()
+            .with_remuneration_spread(0.001)
+            .unwrap();
+        let portfolio = PortfolioBuilder::new()
+            .add_counterparty(Counterparty::new(
+                CounterpartyId::new("CP001"),
+                CreditParams::new(0.02, 0.4).unwrap(),
+            ))
+            .add_netting_set(NettingSet::with_collateral(
+                NettingSetId::new("NS001"),
+                CounterpartyId::new("CP001"),
+                csa,
+            ))
+            .add_netting_set(NettingSet::new(
+                NettingSetId::new("NS002"),
+                CounterpartyId::new("CP001"),
+            ))
+            .build()
+            .unwrap();
+        let time_grid = create_test_time_grid();
+        let df = vec![1.0; time_grid.len()];
+        let ee_profiles = create_test_ee_profiles();
+        let ene_profiles = create_test_ene_profiles();
+
+        // Uncollateralised NS002 has collateral profiles but no CSA
+        let mut received = HashMap::new();
+        let mut posted = HashMap::new();
+        for id in ["NS001", "NS002"] {
+            received.insert(NettingSetId::new(id), vec![1_000.0; 5]);
+            posted.insert(NettingSetId::new(id), vec![200.0; 5]);
+        }
+
+        let calc = XvaCalculator::new().with_funding(FundingParams::from_bps(50.0, 30.0));
+        let with_colva = calc
+            .compute_portfolio_xva_with_collateral(
+                &portfolio,
+                &ee_profiles,
+                &ene_profiles,
+                &received,
+                &posted,
+                &time_grid,
+                &df,
+            )
+            .unwrap();
+        let without = calc
+            .compute_portfolio_xva(&portfolio, &ee_profiles, &ene_profiles, &time_grid, &df)
+            .unwrap();
+
+        // 1,000 × 10bp × 1y received, 200 × 10bp × 1y posted
+        assert_relative_eq!(with_colva.colva.received, 1.0, epsilon = 1e-12);
+        assert_relative_eq!(with_colva.colva.posted, -0.2, epsilon = 1e-12);
+        let ns002 = with_colva.by_counterparty[0]
+            .netting_set_xvas
+            .iter()
+            .find(|ns| ns.netting_set_id.as_str() == "NS002")
+            .unwrap();
+        assert_eq!(ns002.colva, ColvaBreakdown::default());
+
+        assert_eq!(without.colva, ColvaBreakdown::default());
+        assert_relative_eq!(with_colva.cva, without.cva, epsilon = 1e-12);
+        assert_relative_eq!(
+            with_colva.total_xva(),
+            without.total_xva() + 0.8,
+            epsilon = 1e-12
+        );
+    }
+
     #[test]
     fn test_xva_config_builder() {
         let config = XvaConfig::new()
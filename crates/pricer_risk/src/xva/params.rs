@@ -22,6 +22,13 @@ use super::error::XvaError;
 ///
 /// // From basis points
 /// let params = FundingParams::from_bps(60.0, 40.0);
+///
+/// // Discount on the bank's funding curve (OIS + 60bp) and charge FCA
+/// // on uncollateralised netting sets only
+/// let params = FundingParams::from_bps(60.0, 40.0)
+///     .with_funding_curve_spread(0.006)
+///     .with_asymmetric_funding();
+/// assert!(params.validate().is_ok());
 /// ```
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -31,6 +38,17 @@ pub struct FundingParams {
     pub spread_borrow: f64,
     /// Funding spread for lending (negative exposure).
     pub spread_lend: f64,
+    /// Spread of the bank's funding curve over the risk-free curve.
+    ///
+    /// When set, funding cash flows are discounted at
+    /// df(t) × exp(-s × t) rather than the risk-free df(t).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub funding_curve_spread: Option<f64>,
+    /// Asymmetric funding: FCA is charged on uncollateralised netting
+    /// sets only and no FBA is recognised, so the funding benefit is not
+    /// counted a second time alongside DVA.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub asymmetric_funding: bool,
 }
 
 impl FundingParams {
@@ -39,10 +57,7 @@ impl FundingParams {
     /// Both borrowing and lending use the same spread.
     #[inline]
     pub fn symmetric(spread: f64) -> Self {
-        Self {
-            spread_borrow: spread,
-            spread_lend: spread,
-        }
+        Self::asymmetric(spread, spread)
     }
 
     /// Creates asymmetric funding spreads.
@@ -53,6 +68,8 @@ impl FundingParams {
         Self {
             spread_borrow,
             spread_lend,
+            funding_curve_spread: None,
+            asymmetric_funding: false,
         }
     }
 
@@ -64,10 +81,7 @@ impl FundingParams {
     /// * `lend_bps` - Lending spread in basis points
     #[inline]
     pub fn from_bps(borrow_bps: f64, lend_bps: f64) -> Self {
-        Self {
-            spread_borrow: borrow_bps / 10_000.0,
-            spread_lend: lend_bps / 10_000.0,
-        }
+        Self::asymmetric(borrow_bps / 10_000.0, lend_bps / 10_000.0)
     }
 
     /// Creates zero funding spreads (no FVA impact).
@@ -76,6 +90,39 @@ impl FundingParams {
         Self::symmetric(0.0)
     }
 
+    /// Discounts funding cash flows on the bank's funding curve, given as
+    /// a spread over the risk-free curve.
+    pub fn with_funding_curve_spread(mut self, spread: f64) -> Self {
+        self.funding_curve_spread = Some(spread);
+        self
+    }
+
+    /// Enables asymmetric funding (FCA on uncollateralised netting sets
+    /// only, no FBA).
+    pub fn with_asymmetric_funding(mut self) -> Self {
+        self.asymmetric_funding = true;
+        self
+    }
+
+    /// Returns the discount factors used for funding cash flows.
+    ///
+    /// Without a funding curve spread these are the risk-free factors;
+    /// otherwise each df(t) is scaled by exp(-s × t).
+    pub fn funding_discount_factors(
+        &self,
+        time_grid: &[f64],
+        discount_factors: &[f64],
+    ) -> Vec<f64> {
+        match self.funding_curve_spread {
+            Some(spread) => time_grid
+                .iter()
+                .zip(discount_factors)
+                .map(|(&t, &df)| df * (-spread * t).exp())
+                .collect(),
+            None => discount_factors.to_vec(),
+        }
+    }
+
     /// Validates the funding parameters.
     pub fn validate(&self) -> Result<(), XvaError> {
         if self.spread_borrow < 0.0 {
@@ -88,6 +135,11 @@ impl FundingParams {
                 "Lending spread must be non-negative".to_string(),
             ));
         }
+        if self.funding_curve_spread.is_some_and(|s| !s.is_finite()) {
+            return Err(XvaError::InvalidFundingSpread(
+                "Funding curve spread must be finite".to_string(),
+            ));
+        }
         Ok(())
     }
 }
@@ -243,6 +295,29 @@ mod tests {
         assert!(params.validate().is_err());
     }
 
+    #[test]
+    fn test_funding_params_curve_and_asymmetry() {
+        let params = FundingParams::symmetric(0.005);
+        assert_eq!(params.funding_curve_spread, None);
+        assert!(!params.asymmetric_funding);
+
+        let times = [0.0, 1.0, 2.0];
+        let df = [1.0, 0.97, 0.94];
+        assert_eq!(params.funding_discount_factors(&times, &df), df.to_vec());
+
+        let params = params
+            .with_funding_curve_spread(0.01)
+            .with_asymmetric_funding();
+        let funding_df = params.funding_discount_factors(&times, &df);
+        assert_relative_eq!(funding_df[2], 0.94 * (-0.02_f64).exp(), epsilon = 1e-15);
+        assert!(params.asymmetric_funding);
+
+        assert!(FundingParams::zero()
+            .with_funding_curve_spread(f64::NAN)
+            .validate()
+            .is_err());
+    }
+
     #[test]
     fn test_own_credit_params_valid() {
         let params = OwnCreditParams::new(0.02, 0.4).unwrap();
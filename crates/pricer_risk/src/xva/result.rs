@@ -3,11 +3,14 @@
 //! Provides structured result types for XVA calculations at
 //! netting set, counterparty, and portfolio levels.
 
-use crate::portfolio::{CounterpartyId, NettingSetId};
+use super::colva::ColvaBreakdown;
+use super::diagnostics::XvaDiagnostics;
+use crate::portfolio::{CounterpartyId, NettingSetId, TradeId};
+use pricer_core::types::RunMetadata;
 
 /// XVA results for a single netting set.
 ///
-/// Contains CVA, DVA, and FVA (FCA/FBA) for one netting set.
+/// Contains CVA, DVA, FVA (FCA/FBA) and ColVA for one netting set.
 #[derive(Clone, Debug, Default)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NettingSetXva {
@@ -23,6 +26,9 @@ pub struct NettingSetXva {
     pub fca: f64,
     /// Funding Benefit Adjustment (always non-negative).
     pub fba: f64,
+    /// Collateral Valuation Adjustment (positive = cost).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub colva: ColvaBreakdown,
 }
 
 impl NettingSetXva {
@@ -42,9 +48,16 @@ impl NettingSetXva {
             dva,
             fca,
             fba,
+            colva: ColvaBreakdown::default(),
         }
     }
 
+    /// Sets the ColVA breakdown.
+    pub fn with_colva(mut self, colva: ColvaBreakdown) -> Self {
+        self.colva = colva;
+        self
+    }
+
     /// Returns the net Funding Valuation Adjustment.
     ///
     /// FVA = FCA - FBA
@@ -57,13 +70,13 @@ impl NettingSetXva {
 
     /// Returns the total XVA impact.
     ///
-    /// Total XVA = CVA - DVA + FVA
+    /// Total XVA = CVA - DVA + FVA + ColVA
     ///
     /// This represents the total valuation adjustment to apply
     /// to the risk-free price.
     #[inline]
     pub fn total_xva(&self) -> f64 {
-        self.cva - self.dva + self.fva()
+        self.cva - self.dva + self.fva() + self.colva.total()
     }
 
     /// Returns bilateral CVA (CVA - DVA).
@@ -73,6 +86,42 @@ impl NettingSetXva {
     }
 }
 
+/// XVA allocated to a single trade.
+///
+/// Produced by [`XvaCalculator::allocate_to_trades`](super::XvaCalculator::allocate_to_trades);
+/// allocations within a netting set sum to the netting set's XVA. A trade
+/// that reduces netted exposure carries negative components.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TradeXva {
+    /// Trade identifier.
+    pub trade_id: TradeId,
+    /// Netting set identifier.
+    pub netting_set_id: NettingSetId,
+    /// Allocated CVA.
+    pub cva: f64,
+    /// Allocated DVA.
+    pub dva: f64,
+    /// Allocated FCA.
+    pub fca: f64,
+    /// Allocated FBA.
+    pub fba: f64,
+}
+
+impl TradeXva {
+    /// Returns the allocated FVA (FCA - FBA).
+    #[inline]
+    pub fn fva(&self) -> f64 {
+        self.fca - self.fba
+    }
+
+    /// Returns the allocated total XVA (CVA - DVA + FVA).
+    #[inline]
+    pub fn total_xva(&self) -> f64 {
+        self.cva - self.dva + self.fva()
+    }
+}
+
 /// Aggregated XVA results for a counterparty.
 ///
 /// Contains the sum of XVA metrics across all netting sets
@@ -90,6 +139,9 @@ pub struct CounterpartyXva {
     pub fca: f64,
     /// Total FBA across all netting sets.
     pub fba: f64,
+    /// Total ColVA across all netting sets.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub colva: ColvaBreakdown,
     /// Individual netting set results.
     pub netting_set_xvas: Vec<NettingSetXva>,
 }
@@ -104,6 +156,7 @@ impl CounterpartyXva {
         let dva = netting_set_xvas.iter().map(|x| x.dva).sum();
         let fca = netting_set_xvas.iter().map(|x| x.fca).sum();
         let fba = netting_set_xvas.iter().map(|x| x.fba).sum();
+        let colva = netting_set_xvas.iter().map(|x| x.colva).sum();
 
         Self {
             counterparty_id,
@@ -111,6 +164,7 @@ impl CounterpartyXva {
             dva,
             fca,
             fba,
+            colva,
             netting_set_xvas,
         }
     }
@@ -124,7 +178,7 @@ impl CounterpartyXva {
     /// Returns the total XVA.
     #[inline]
     pub fn total_xva(&self) -> f64 {
-        self.cva - self.dva + self.fva()
+        self.cva - self.dva + self.fva() + self.colva.total()
     }
 
     /// Returns bilateral CVA.
@@ -155,8 +209,17 @@ pub struct PortfolioXva {
     pub fca: f64,
     /// Total FBA across all counterparties.
     pub fba: f64,
+    /// Total ColVA across all counterparties.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub colva: ColvaBreakdown,
     /// Results by counterparty.
     pub by_counterparty: Vec<CounterpartyXva>,
+    /// Numerical diagnostics, when produced by [`XvaCalculator`](super::XvaCalculator).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub diagnostics: Option<XvaDiagnostics>,
+    /// Provenance for reproducing the result: versions and input hashes.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub metadata: Option<RunMetadata>,
 }
 
 impl PortfolioXva {
@@ -166,16 +229,65 @@ impl PortfolioXva {
         let dva = by_counterparty.iter().map(|c| c.dva).sum();
         let fca = by_counterparty.iter().map(|c| c.fca).sum();
         let fba = by_counterparty.iter().map(|c| c.fba).sum();
+        let colva = by_counterparty.iter().map(|c| c.colva).sum();
 
         Self {
             cva,
             dva,
             fca,
             fba,
+            colva,
             by_counterparty,
+            diagnostics: None,
+            metadata: None,
         }
     }
 
+    /// Attaches numerical diagnostics.
+    pub fn with_diagnostics(mut self, diagnostics: XvaDiagnostics) -> Self {
+        self.diagnostics = Some(diagnostics);
+        self
+    }
+
+    /// Attaches run provenance.
+    pub fn with_metadata(mut self, metadata: RunMetadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    /// Records the seed of the exposure simulation behind the profiles.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.metadata.get_or_insert_with(Default::default).seed = Some(seed);
+        self
+    }
+
+    /// Records the CVA standard error from exposure simulation.
+    ///
+    /// Exposure profiles arrive as plain averages, so the calculator cannot
+    /// measure their Monte Carlo error itself; callers that simulated them
+    /// pass it through here.
+    pub fn with_cva_std_error(mut self, std_error: f64) -> Self {
+        self.diagnostics
+            .get_or_insert_with(Default::default)
+            .cva_std_error = Some(std_error);
+        self
+    }
+
+    /// Returns whether the result should be treated as low confidence.
+    ///
+    /// True when netting sets were skipped, or when the 95% CVA confidence
+    /// half-width exceeds `tolerance` relative to the CVA.
+    pub fn is_low_confidence(&self, tolerance: f64) -> bool {
+        let Some(diag) = &self.diagnostics else {
+            return false;
+        };
+        let scale = if self.cva == 0.0 { 1.0 } else { self.cva.abs() };
+        !diag.is_complete()
+            || diag
+                .cva_confidence_interval(self.cva)
+                .is_some_and(|(lo, hi)| 0.5 * (hi - lo) > tolerance * scale)
+    }
+
     /// Returns the net FVA.
     #[inline]
     pub fn fva(&self) -> f64 {
@@ -185,7 +297,7 @@ impl PortfolioXva {
     /// Returns the total XVA.
     #[inline]
     pub fn total_xva(&self) -> f64 {
-        self.cva - self.dva + self.fva()
+        self.cva - self.dva + self.fva() + self.colva.total()
     }
 
     /// Returns bilateral CVA.
@@ -293,6 +405,7 @@ mod tests {
             dva: 20.0,
             fca: 30.0,
             fba: 10.0,
+            colva: ColvaBreakdown::default(),
             netting_set_xvas: vec![],
         };
         let cp2 = CounterpartyXva {
@@ -301,6 +414,7 @@ mod tests {
             dva: 10.0,
             fca: 15.0,
             fba: 5.0,
+            colva: ColvaBreakdown::default(),
             netting_set_xvas: vec![],
         };
 
@@ -320,13 +434,49 @@ mod tests {
             dva: 30.0,
             fca: 40.0,
             fba: 10.0,
+            colva: ColvaBreakdown::default(),
             by_counterparty: vec![],
+            diagnostics: None,
+            metadata: None,
         };
 
         // Total = 100 - 30 + (40 - 10) = 100
         assert_eq!(portfolio.total_xva(), 100.0);
     }
 
+    #[test]
+    fn test_colva_aggregation_and_total() {
+        let colva = |received, posted| ColvaBreakdown { received, posted };
+        let ns1 = NettingSetXva::new(
+            NettingSetId::new("NS001"),
+            CounterpartyId::new("CP001"),
+            100.0,
+            20.0,
+            50.0,
+            10.0,
+        )
+        .with_colva(colva(6.0, -2.0));
+        let ns2 = NettingSetXva::new(
+            NettingSetId::new("NS002"),
+            CounterpartyId::new("CP001"),
+            0.0,
+            0.0,
+            0.0,
+            0.0,
+        )
+        .with_colva(colva(1.0, -3.0));
+
+        // Total = 100 - 20 + 40 + 4 = 124
+        assert_eq!(ns1.total_xva(), 124.0);
+
+        let cp = CounterpartyXva::from_netting_sets(CounterpartyId::new("CP001"), vec![ns1, ns2]);
+        assert_eq!(cp.colva, colva(7.0, -5.0));
+
+        let portfolio = PortfolioXva::from_counterparties(vec![cp]);
+        assert_eq!(portfolio.colva.total(), 2.0);
+        assert_eq!(portfolio.total_xva(), 122.0);
+    }
+
     #[test]
     fn test_default() {
         let xva = NettingSetXva::default();
@@ -335,4 +485,32 @@ mod tests {
         assert_eq!(xva.fva(), 0.0);
         assert_eq!(xva.total_xva(), 0.0);
     }
+
+    #[cfg(feature = "serde")]
+    mod serde_tests {
+        use super::*;
+
+        #[test]
+        fn test_portfolio_xva_roundtrip() {
+            let ns = NettingSetXva::new(
+                NettingSetId::new("NS001"),
+                CounterpartyId::new("CP001"),
+                100.0,
+                20.0,
+                15.0,
+                5.0,
+            );
+            let cp = CounterpartyXva::from_netting_sets(CounterpartyId::new("CP001"), vec![ns]);
+            let xva = PortfolioXva::from_counterparties(vec![cp]);
+
+            let json = serde_json::to_value(&xva).unwrap();
+            for key in ["cva", "dva", "fca", "fba", "colva", "by_counterparty"] {
+                assert!(json.get(key).is_some(), "missing {}", key);
+            }
+
+            let parsed: PortfolioXva = serde_json::from_value(json).unwrap();
+            assert_eq!(parsed.total_xva(), xva.total_xva());
+            assert_eq!(parsed.netting_set_count(), 1);
+        }
+    }
 }
@@ -0,0 +1,259 @@
+#ifndef NEUTRYX_CAPI_H
+#define NEUTRYX_CAPI_H
+
+/* Generated by cbindgen from crates/service_capi. Do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Status code returned by every fallible function
+typedef enum NxStatus {
+  // Success
+  NX_STATUS_OK = 0,
+  // A required pointer argument was null
+  NX_STATUS_NULL_POINTER = 1,
+  // A string argument was not valid UTF-8
+  NX_STATUS_INVALID_UTF8 = 2,
+  // A numeric or enumerated argument was out of range
+  NX_STATUS_INVALID_ARGUMENT = 3,
+  // Portfolio validation failed
+  NX_STATUS_PORTFOLIO = 4,
+  // Pricing failed or the instrument is not supported
+  NX_STATUS_PRICING = 5,
+  // XVA calculation failed
+  NX_STATUS_XVA = 6,
+  // The engine panicked; the handle involved should be freed
+  NX_STATUS_PANIC = 99,
+} NxStatus;
+
+// Opaque validated portfolio
+typedef struct NxPortfolio NxPortfolio;
+
+// Opaque portfolio builder
+typedef struct NxPortfolioBuilder NxPortfolioBuilder;
+
+// Opaque XVA calculator
+typedef struct NxXvaCalculator NxXvaCalculator;
+
+// Portfolio-level valuation adjustments
+typedef struct NxXvaResult {
+  // Credit Valuation Adjustment
+  double cva;
+  // Debit Valuation Adjustment
+  double dva;
+  // Funding Cost Adjustment
+  double fca;
+  // Funding Benefit Adjustment
+  double fba;
+  // Total XVA: CVA - DVA + FCA - FBA
+  double total;
+} NxXvaResult;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Message for the most recent failure on the calling thread
+//
+// Returns null when the last call on this thread succeeded. The pointer
+// stays valid until the next API call on the same thread and must not be
+// freed.
+const char *nx_last_error_message(void);
+
+// Create an empty portfolio builder
+//
+// Free with `nx_portfolio_builder_free`.
+NxPortfolioBuilder *nx_portfolio_builder_new(void);
+
+// Free a portfolio builder; null is ignored
+//
+// # Safety
+//
+// `builder` must be null or a pointer from `nx_portfolio_builder_new`
+// that has not been freed.
+void nx_portfolio_builder_free(NxPortfolioBuilder *builder);
+
+// Add a counterparty with a flat hazard rate and loss given default
+//
+// # Safety
+//
+// `builder` must be a live builder and `counterparty_id` a NUL-terminated
+// string.
+NxStatus nx_portfolio_builder_add_counterparty(NxPortfolioBuilder *builder,
+                                               const char *counterparty_id,
+                                               double hazard_rate,
+                                               double lgd);
+
+// Add an uncollateralised netting set with a counterparty
+//
+// # Safety
+//
+// `builder` must be a live builder and the identifiers NUL-terminated
+// strings.
+NxStatus nx_portfolio_builder_add_netting_set(NxPortfolioBuilder *builder,
+                                              const char *netting_set_id,
+                                              const char *counterparty_id);
+
+// Add a European option trade
+//
+// `is_call` is non-zero for a call; `currency` is an ISO 4217 code.
+//
+// # Safety
+//
+// `builder` must be a live builder and the string arguments
+// NUL-terminated.
+NxStatus nx_portfolio_builder_add_option(NxPortfolioBuilder *builder,
+                                         const char *trade_id,
+                                         const char *counterparty_id,
+                                         const char *netting_set_id,
+                                         double strike,
+                                         double expiry,
+                                         int32_t is_call,
+                                         double notional,
+                                         const char *currency);
+
+// Add a forward trade
+//
+// `is_long` is non-zero for a long position; `currency` is an ISO 4217
+// code.
+//
+// # Safety
+//
+// `builder` must be a live builder and the string arguments
+// NUL-terminated.
+NxStatus nx_portfolio_builder_add_forward(NxPortfolioBuilder *builder,
+                                          const char *trade_id,
+                                          const char *counterparty_id,
+                                          const char *netting_set_id,
+                                          double strike,
+                                          double maturity,
+                                          int32_t is_long,
+                                          double notional,
+                                          const char *currency);
+
+// Validate the builder and create a portfolio
+//
+// The builder is left unchanged and must still be freed. On success
+// `*out` receives a portfolio to free with `nx_portfolio_free`.
+//
+// # Safety
+//
+// `builder` must be a live builder and `out` a valid pointer.
+NxStatus nx_portfolio_builder_build(const NxPortfolioBuilder *builder, NxPortfolio **out);
+
+// Free a portfolio; null is ignored
+//
+// # Safety
+//
+// `portfolio` must be null or a pointer from `nx_portfolio_builder_build`
+// that has not been freed.
+void nx_portfolio_free(NxPortfolio *portfolio);
+
+// Number of trades in a portfolio
+//
+// # Safety
+//
+// `portfolio` must be a live portfolio and `out` a valid pointer.
+NxStatus nx_portfolio_trade_count(const NxPortfolio *portfolio, size_t *out);
+
+// Number of netting sets in a portfolio
+//
+// # Safety
+//
+// `portfolio` must be a live portfolio and `out` a valid pointer.
+NxStatus nx_portfolio_netting_set_count(const NxPortfolio *portfolio, size_t *out);
+
+// Black-Scholes price of a European option
+//
+// `is_call` is non-zero for a call.
+//
+// # Safety
+//
+// `out_price` must be a valid pointer.
+NxStatus nx_price_black_scholes(double spot,
+                                double strike,
+                                double expiry,
+                                double rate,
+                                double vol,
+                                int32_t is_call,
+                                double *out_price);
+
+// Present value of a portfolio under Black-Scholes
+//
+// Every trade is priced off the same spot, volatility and rate and scaled
+// by its notional.
+//
+// # Safety
+//
+// `portfolio` must be a live portfolio and `out_value` a valid pointer.
+NxStatus nx_portfolio_price(const NxPortfolio *portfolio,
+                            double spot,
+                            double vol,
+                            double rate,
+                            double *out_value);
+
+// Create an XVA calculator with default configuration
+//
+// Free with `nx_xva_calculator_free`.
+NxXvaCalculator *nx_xva_calculator_new(void);
+
+// Free an XVA calculator; null is ignored
+//
+// # Safety
+//
+// `calculator` must be null or a pointer from `nx_xva_calculator_new`
+// that has not been freed.
+void nx_xva_calculator_free(NxXvaCalculator *calculator);
+
+// Set own credit for DVA
+//
+// # Safety
+//
+// `calculator` must be a live calculator.
+NxStatus nx_xva_calculator_set_own_credit(NxXvaCalculator *calculator,
+                                          double hazard_rate,
+                                          double lgd);
+
+// Set borrowing and lending funding spreads in basis points for FVA
+//
+// # Safety
+//
+// `calculator` must be a live calculator.
+NxStatus nx_xva_calculator_set_funding(NxXvaCalculator *calculator,
+                                       double borrow_bps,
+                                       double lend_bps);
+
+// Compute portfolio XVA from exposure profiles
+//
+// `netting_set_ids` holds `n_netting_sets` identifiers; `ee` and `ene`
+// are `n_netting_sets × n_times` row-major matrices in the same order.
+// `discount_factors` may be null, meaning 1.0 at every point. Every
+// identifier must name a netting set in the portfolio.
+//
+// # Safety
+//
+// All handles must be live, `netting_set_ids` must point to
+// `n_netting_sets` NUL-terminated strings, the arrays must hold the
+// stated number of values and `out` must be a valid pointer.
+NxStatus nx_xva_compute_portfolio(const NxXvaCalculator *calculator,
+                                  const NxPortfolio *portfolio,
+                                  const char *const *netting_set_ids,
+                                  size_t n_netting_sets,
+                                  const double *ee,
+                                  const double *ene,
+                                  const double *time_grid,
+                                  const double *discount_factors,
+                                  size_t n_times,
+                                  NxXvaResult *out);
+
+// Library version as a static NUL-terminated string
+const char *nx_version(void);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* NEUTRYX_CAPI_H */
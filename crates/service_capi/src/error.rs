@@ -0,0 +1,142 @@
+//! Status codes and error reporting across the C boundary
+//!
+//! Every fallible entry point returns an [`NxStatus`]. The message for the
+//! most recent failure on the calling thread is available from
+//! [`nx_last_error_message`].
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use thiserror::Error;
+
+/// Status code returned by every fallible function
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NxStatus {
+    /// Success
+    Ok = 0,
+    /// A required pointer argument was null
+    NullPointer = 1,
+    /// A string argument was not valid UTF-8
+    InvalidUtf8 = 2,
+    /// A numeric or enumerated argument was out of range
+    InvalidArgument = 3,
+    /// Portfolio validation failed
+    Portfolio = 4,
+    /// Pricing failed or the instrument is not supported
+    Pricing = 5,
+    /// XVA calculation failed
+    Xva = 6,
+    /// The engine panicked; the handle involved should be freed
+    Panic = 99,
+}
+
+/// Errors raised inside the C API
+#[derive(Debug, Error)]
+pub(crate) enum CapiError {
+    /// A required pointer argument was null
+    #[error("Null pointer: {0}")]
+    NullPointer(&'static str),
+
+    /// A string argument was not valid UTF-8
+    #[error("Invalid UTF-8 in {0}")]
+    InvalidUtf8(&'static str),
+
+    /// A numeric or enumerated argument was out of range
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+
+    /// Portfolio validation failed
+    #[error("Portfolio error: {0}")]
+    Portfolio(String),
+
+    /// Pricing failed or the instrument is not supported
+    #[error("Pricing error: {0}")]
+    Pricing(String),
+
+    /// XVA calculation failed
+    #[error("XVA error: {0}")]
+    Xva(String),
+}
+
+impl CapiError {
+    fn status(&self) -> NxStatus {
+        match self {
+            CapiError::NullPointer(_) => NxStatus::NullPointer,
+            CapiError::InvalidUtf8(_) => NxStatus::InvalidUtf8,
+            CapiError::InvalidArgument(_) => NxStatus::InvalidArgument,
+            CapiError::Portfolio(_) => NxStatus::Portfolio,
+            CapiError::Pricing(_) => NxStatus::Pricing,
+            CapiError::Xva(_) => NxStatus::Xva,
+        }
+    }
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(message: String) {
+    // Interior NULs cannot cross the boundary; replace them
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|slot| *slot.borrow_mut() = Some(message));
+}
+
+fn clear_last_error() {
+    LAST_ERROR.with(|slot| *slot.borrow_mut() = None);
+}
+
+/// Run an entry point body, converting errors and panics into a status
+pub(crate) fn ffi_call(body: impl FnOnce() -> Result<(), CapiError>) -> NxStatus {
+    clear_last_error();
+    match catch_unwind(AssertUnwindSafe(body)) {
+        Ok(Ok(())) => NxStatus::Ok,
+        Ok(Err(err)) => {
+            let status = err.status();
+            set_last_error(err.to_string());
+            status
+        }
+        Err(panic) => {
+            let message = panic
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            set_last_error(format!("Panic: {}", message));
+            NxStatus::Panic
+        }
+    }
+}
+
+/// Borrow a C string argument as UTF-8
+///
+/// # Safety
+///
+/// `ptr` must be null or point to a NUL-terminated string that outlives
+/// the returned reference.
+pub(crate) unsafe fn c_str<'a>(
+    ptr: *const c_char,
+    name: &'static str,
+) -> Result<&'a str, CapiError> {
+    if ptr.is_null() {
+        return Err(CapiError::NullPointer(name));
+    }
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map_err(|_| CapiError::InvalidUtf8(name))
+}
+
+/// Message for the most recent failure on the calling thread
+///
+/// Returns null when the last call on this thread succeeded. The pointer
+/// stays valid until the next API call on the same thread and must not be
+/// freed.
+#[no_mangle]
+pub extern "C" fn nx_last_error_message() -> *const c_char {
+    LAST_ERROR.with(|slot| {
+        slot.borrow()
+            .as_ref()
+            .map_or(std::ptr::null(), |message| message.as_ptr())
+    })
+}
@@ -0,0 +1,287 @@
+//! Neutryx C API
+//!
+//! This crate exposes a stable C ABI so that C, C++ and JVM risk systems
+//! can embed the Neutryx engine in-process.
+//!
+//! # Architecture
+//!
+//! As part of the **S**ervice layer in the A-I-P-S architecture, this crate
+//! wraps the pricing and risk crates behind opaque handles and integer
+//! status codes. No Rust types cross the boundary other than `#[repr(C)]`
+//! structs and enums.
+//!
+//! # Conventions
+//!
+//! - Handles are created by `nx_*_new` / `nx_*_build` and released with the
+//!   matching `nx_*_free`; freeing null is a no-op.
+//! - Fallible functions return [`NxStatus`] and write results through out
+//!   pointers. On failure [`nx_last_error_message`] describes the error.
+//! - Strings are NUL-terminated UTF-8 and are copied, never retained.
+//! - Panics are caught at the boundary and reported as `NX_STATUS_PANIC`.
+//!
+//! The C header lives in `include/neutryx_capi.h` and is generated with
+//! cbindgen (see `cbindgen.toml`).
+//!
+//! # Usage
+//!
+//! ```c
+//! #include "neutryx_capi.h"
+//!
+//! NxPortfolioBuilder *builder = nx_portfolio_builder_new();
+//! nx_portfolio_builder_add_counterparty(builder, "CP001", 0.02, 0.6);
+//! nx_portfolio_builder_add_netting_set(builder, "NS001", "CP001");
+//! nx_portfolio_builder_add_option(builder, "T001", "CP001", "NS001",
+//!                                 100.0, 1.0, 1, 1e6, "USD");
+//!
+//! NxPortfolio *portfolio = NULL;
+//! if (nx_portfolio_builder_build(builder, &portfolio) != NX_STATUS_OK) {
+//!     fprintf(stderr, "%s\n", nx_last_error_message());
+//! }
+//! nx_portfolio_builder_free(builder);
+//!
+//! double pv;
+//! nx_portfolio_price(portfolio, 100.0, 0.2, 0.05, &pv);
+//!
+//! const char *ids[] = {"NS001"};
+//! NxXvaCalculator *calc = nx_xva_calculator_new();
+//! NxXvaResult xva;
+//! nx_xva_compute_portfolio(calc, portfolio, ids, 1, ee, ene,
+//!                          times, NULL, n_times, &xva);
+//!
+//! nx_xva_calculator_free(calc);
+//! nx_portfolio_free(portfolio);
+//! ```
+
+mod error;
+mod portfolio;
+mod pricing;
+mod xva;
+
+pub use error::{nx_last_error_message, NxStatus};
+pub use portfolio::{
+    nx_portfolio_builder_add_counterparty, nx_portfolio_builder_add_forward,
+    nx_portfolio_builder_add_netting_set, nx_portfolio_builder_add_option,
+    nx_portfolio_builder_build, nx_portfolio_builder_free, nx_portfolio_builder_new,
+    nx_portfolio_free, nx_portfolio_netting_set_count, nx_portfolio_trade_count, NxPortfolio,
+    NxPortfolioBuilder,
+};
+pub use pricing::{nx_portfolio_price, nx_price_black_scholes};
+pub use xva::{
+    nx_xva_calculator_free, nx_xva_calculator_new, nx_xva_calculator_set_funding,
+    nx_xva_calculator_set_own_credit, nx_xva_compute_portfolio, NxXvaCalculator, NxXvaResult,
+};
+
+use std::ffi::c_char;
+
+/// Library version as a static NUL-terminated string
+#[no_mangle]
+pub extern "C" fn nx_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+    use std::ptr;
+
+    const HEADER: &str = include_str!("../include/neutryx_capi.h");
+
+    fn c(s: &str) -> std::ffi::CString {
+        std::ffi::CString::new(s).unwrap()
+    }
+
+    #[test]
+    fn test_header_declares_every_entry_point() {
+        let sources = [
+            include_str!("lib.rs"),
+            include_str!("error.rs"),
+            include_str!("portfolio.rs"),
+            include_str!("pricing.rs"),
+            include_str!("xva.rs"),
+        ];
+        for source in sources {
+            for line in source.lines() {
+                let Some(rest) = line.split("extern \"C\" fn ").nth(1) else {
+                    continue;
+                };
+                let name = rest.split('(').next().unwrap();
+                assert!(
+                    HEADER.contains(&format!("{}(", name)),
+                    "{} missing from include/neutryx_capi.h",
+                    name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_version() {
+        let version = unsafe { CStr::from_ptr(nx_version()) };
+        assert_eq!(version.to_str().unwrap(), env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn test_portfolio_price_and_xva_roundtrip() {
+        unsafe {
+            let builder = nx_portfolio_builder_new();
+            let (cp, ns) = (c("CP001"), c("NS001"));
+            let usd = c("USD");
+            assert_eq!(
+                nx_portfolio_builder_add_counterparty(builder, cp.as_ptr(), 0.02, 0.6),
+                NxStatus::Ok
+            );
+            assert_eq!(
+                nx_portfolio_builder_add_netting_set(builder, ns.as_ptr(), cp.as_ptr()),
+                NxStatus::Ok
+            );
+            assert_eq!(
+                nx_portfolio_builder_add_option(
+                    builder,
+                    c("T001").as_ptr(),
+                    cp.as_ptr(),
+                    ns.as_ptr(),
+                    100.0,
+                    1.0,
+                    1,
+                    1_000.0,
+                    usd.as_ptr(),
+                ),
+                NxStatus::Ok
+            );
+            assert_eq!(
+                nx_portfolio_builder_add_forward(
+                    builder,
+                    c("T002").as_ptr(),
+                    cp.as_ptr(),
+                    ns.as_ptr(),
+                    100.0,
+                    1.0,
+                    0,
+                    10.0,
+                    usd.as_ptr(),
+                ),
+                NxStatus::Ok
+            );
+
+            let mut portfolio = ptr::null_mut();
+            assert_eq!(
+                nx_portfolio_builder_build(builder, &mut portfolio),
+                NxStatus::Ok
+            );
+            nx_portfolio_builder_free(builder);
+
+            let (mut trades, mut netting_sets) = (0, 0);
+            assert_eq!(
+                nx_portfolio_trade_count(portfolio, &mut trades),
+                NxStatus::Ok
+            );
+            assert_eq!(
+                nx_portfolio_netting_set_count(portfolio, &mut netting_sets),
+                NxStatus::Ok
+            );
+            assert_eq!((trades, netting_sets), (2, 1));
+
+            let mut call = 0.0;
+            nx_price_black_scholes(100.0, 100.0, 1.0, 0.05, 0.2, 1, &mut call);
+            let forward = -10.0 * (100.0 - 100.0 * (-0.05_f64).exp());
+            let mut pv = 0.0;
+            assert_eq!(
+                nx_portfolio_price(portfolio, 100.0, 0.2, 0.05, &mut pv),
+                NxStatus::Ok
+            );
+            assert!((pv - (1_000.0 * call + forward)).abs() < 1e-8);
+
+            let calculator = nx_xva_calculator_new();
+            assert_eq!(
+                nx_xva_calculator_set_own_credit(calculator, 0.01, 0.6),
+                NxStatus::Ok
+            );
+            assert_eq!(
+                nx_xva_calculator_set_funding(calculator, 50.0, 25.0),
+                NxStatus::Ok
+            );
+
+            let ids = [ns.as_ptr()];
+            let time_grid = [0.0, 0.5, 1.0];
+            let ee = [0.0, 8_000.0, 10_000.0];
+            let ene = [0.0, 1_000.0, 1_500.0];
+            let mut result = NxXvaResult::default();
+            assert_eq!(
+                nx_xva_compute_portfolio(
+                    calculator,
+                    portfolio,
+                    ids.as_ptr(),
+                    1,
+                    ee.as_ptr(),
+                    ene.as_ptr(),
+                    time_grid.as_ptr(),
+                    ptr::null(),
+                    time_grid.len(),
+                    &mut result,
+                ),
+                NxStatus::Ok
+            );
+            assert!(nx_last_error_message().is_null());
+            assert!(result.cva > 0.0);
+            assert!(result.dva > 0.0);
+            assert!(result.fca > 0.0);
+            assert!(
+                (result.total - (result.cva - result.dva + result.fca - result.fba)).abs() < 1e-9
+            );
+
+            // Unknown netting set
+            let missing = c("NS999");
+            let ids = [missing.as_ptr()];
+            let status = nx_xva_compute_portfolio(
+                calculator,
+                portfolio,
+                ids.as_ptr(),
+                1,
+                ee.as_ptr(),
+                ene.as_ptr(),
+                time_grid.as_ptr(),
+                ptr::null(),
+                time_grid.len(),
+                &mut result,
+            );
+            assert_eq!(status, NxStatus::InvalidArgument);
+            assert!(!nx_last_error_message().is_null());
+
+            nx_xva_calculator_free(calculator);
+            nx_portfolio_free(portfolio);
+        }
+    }
+
+    #[test]
+    fn test_build_reports_unknown_counterparty() {
+        unsafe {
+            let builder = nx_portfolio_builder_new();
+            let (ns, cp) = (c("NS001"), c("CP404"));
+            nx_portfolio_builder_add_netting_set(builder, ns.as_ptr(), cp.as_ptr());
+
+            let mut portfolio = ptr::null_mut();
+            let status = nx_portfolio_builder_build(builder, &mut portfolio);
+            assert_eq!(status, NxStatus::Portfolio);
+            assert!(portfolio.is_null());
+
+            let message = CStr::from_ptr(nx_last_error_message()).to_str().unwrap();
+            assert!(message.starts_with("Portfolio error"));
+            nx_portfolio_builder_free(builder);
+        }
+    }
+
+    #[test]
+    fn test_null_handles_are_rejected() {
+        unsafe {
+            let mut out = 0;
+            assert_eq!(
+                nx_portfolio_trade_count(ptr::null(), &mut out),
+                NxStatus::NullPointer
+            );
+            nx_portfolio_free(ptr::null_mut());
+            nx_portfolio_builder_free(ptr::null_mut());
+            nx_xva_calculator_free(ptr::null_mut());
+        }
+    }
+}
@@ -0,0 +1,327 @@
+//! Portfolio handles
+//!
+//! A builder collects counterparties, netting sets and trades; building it
+//! validates references and yields an immutable portfolio handle. Trades
+//! are attached to their netting sets automatically.
+
+use std::ffi::c_char;
+
+use pricer_core::types::Currency;
+use pricer_models::instruments::{
+    Direction, ExerciseStyle, Forward, Instrument, InstrumentParams, PayoffType, VanillaOption,
+};
+use pricer_risk::portfolio::{
+    Counterparty, CounterpartyId, CreditParams, NettingSet, NettingSetId, Portfolio,
+    PortfolioBuilder, Trade, TradeId,
+};
+
+use crate::error::{c_str, ffi_call, CapiError, NxStatus};
+
+/// Smoothing width for option payoffs held in a portfolio
+const PAYOFF_SMOOTHING: f64 = 1e-6;
+
+/// Opaque portfolio builder
+pub struct NxPortfolioBuilder {
+    counterparties: Vec<Counterparty>,
+    netting_sets: Vec<NettingSet>,
+    trades: Vec<Trade>,
+}
+
+/// Opaque validated portfolio
+pub struct NxPortfolio {
+    pub(crate) inner: Portfolio,
+}
+
+/// Trade identifiers and references shared by the trade constructors
+struct TradeRefs {
+    trade_id: TradeId,
+    counterparty_id: CounterpartyId,
+    netting_set_id: NettingSetId,
+    currency: Currency,
+}
+
+impl TradeRefs {
+    unsafe fn read(
+        trade_id: *const c_char,
+        counterparty_id: *const c_char,
+        netting_set_id: *const c_char,
+        currency: *const c_char,
+    ) -> Result<Self, CapiError> {
+        let currency = c_str(currency, "currency")?;
+        Ok(Self {
+            trade_id: TradeId::new(c_str(trade_id, "trade_id")?),
+            counterparty_id: CounterpartyId::new(c_str(counterparty_id, "counterparty_id")?),
+            netting_set_id: NettingSetId::new(c_str(netting_set_id, "netting_set_id")?),
+            currency: currency
+                .parse()
+                .map_err(|_| CapiError::InvalidArgument(format!("currency {}", currency)))?,
+        })
+    }
+
+    fn into_trade(self, instrument: Instrument<f64>, notional: f64) -> Trade {
+        Trade::new(
+            self.trade_id,
+            instrument,
+            self.currency,
+            self.counterparty_id,
+            self.netting_set_id,
+            notional,
+        )
+    }
+}
+
+unsafe fn builder_mut<'a>(
+    builder: *mut NxPortfolioBuilder,
+) -> Result<&'a mut NxPortfolioBuilder, CapiError> {
+    builder.as_mut().ok_or(CapiError::NullPointer("builder"))
+}
+
+pub(crate) unsafe fn portfolio_ref<'a>(
+    portfolio: *const NxPortfolio,
+) -> Result<&'a NxPortfolio, CapiError> {
+    portfolio
+        .as_ref()
+        .ok_or(CapiError::NullPointer("portfolio"))
+}
+
+/// Create an empty portfolio builder
+///
+/// Free with `nx_portfolio_builder_free`.
+#[no_mangle]
+pub extern "C" fn nx_portfolio_builder_new() -> *mut NxPortfolioBuilder {
+    Box::into_raw(Box::new(NxPortfolioBuilder {
+        counterparties: Vec::new(),
+        netting_sets: Vec::new(),
+        trades: Vec::new(),
+    }))
+}
+
+/// Free a portfolio builder; null is ignored
+///
+/// # Safety
+///
+/// `builder` must be null or a pointer from `nx_portfolio_builder_new`
+/// that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn nx_portfolio_builder_free(builder: *mut NxPortfolioBuilder) {
+    if !builder.is_null() {
+        drop(Box::from_raw(builder));
+    }
+}
+
+/// Add a counterparty with a flat hazard rate and loss given default
+///
+/// # Safety
+///
+/// `builder` must be a live builder and `counterparty_id` a NUL-terminated
+/// string.
+#[no_mangle]
+pub unsafe extern "C" fn nx_portfolio_builder_add_counterparty(
+    builder: *mut NxPortfolioBuilder,
+    counterparty_id: *const c_char,
+    hazard_rate: f64,
+    lgd: f64,
+) -> NxStatus {
+    ffi_call(|| {
+        let builder = builder_mut(builder)?;
+        let id = c_str(counterparty_id, "counterparty_id")?;
+        let credit = CreditParams::new(hazard_rate, lgd)
+            .map_err(|e| CapiError::InvalidArgument(e.to_string()))?;
+        builder
+            .counterparties
+            .push(Counterparty::new(CounterpartyId::new(id), credit));
+        Ok(())
+    })
+}
+
+/// Add an uncollateralised netting set with a counterparty
+///
+/// # Safety
+///
+/// `builder` must be a live builder and the identifiers NUL-terminated
+/// strings.
+#[no_mangle]
+pub unsafe extern "C" fn nx_portfolio_builder_add_netting_set(
+    builder: *mut NxPortfolioBuilder,
+    netting_set_id: *const c_char,
+    counterparty_id: *const c_char,
+) -> NxStatus {
+    ffi_call(|| {
+        let builder = builder_mut(builder)?;
+        let netting_set_id = c_str(netting_set_id, "netting_set_id")?;
+        let counterparty_id = c_str(counterparty_id, "counterparty_id")?;
+        builder.netting_sets.push(NettingSet::new(
+            NettingSetId::new(netting_set_id),
+            CounterpartyId::new(counterparty_id),
+        ));
+        Ok(())
+    })
+}
+
+/// Add a European option trade
+///
+/// `is_call` is non-zero for a call; `currency` is an ISO 4217 code.
+///
+/// # Safety
+///
+/// `builder` must be a live builder and the string arguments
+/// NUL-terminated.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn nx_portfolio_builder_add_option(
+    builder: *mut NxPortfolioBuilder,
+    trade_id: *const c_char,
+    counterparty_id: *const c_char,
+    netting_set_id: *const c_char,
+    strike: f64,
+    expiry: f64,
+    is_call: i32,
+    notional: f64,
+    currency: *const c_char,
+) -> NxStatus {
+    ffi_call(|| {
+        let builder = builder_mut(builder)?;
+        let refs = TradeRefs::read(trade_id, counterparty_id, netting_set_id, currency)?;
+        let params = InstrumentParams::new(strike, expiry, 1.0)
+            .map_err(|e| CapiError::InvalidArgument(e.to_string()))?;
+        let payoff = if is_call != 0 {
+            PayoffType::Call
+        } else {
+            PayoffType::Put
+        };
+        let option = VanillaOption::new(params, payoff, ExerciseStyle::European, PAYOFF_SMOOTHING);
+        builder
+            .trades
+            .push(refs.into_trade(Instrument::Vanilla(option), notional));
+        Ok(())
+    })
+}
+
+/// Add a forward trade
+///
+/// `is_long` is non-zero for a long position; `currency` is an ISO 4217
+/// code.
+///
+/// # Safety
+///
+/// `builder` must be a live builder and the string arguments
+/// NUL-terminated.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn nx_portfolio_builder_add_forward(
+    builder: *mut NxPortfolioBuilder,
+    trade_id: *const c_char,
+    counterparty_id: *const c_char,
+    netting_set_id: *const c_char,
+    strike: f64,
+    maturity: f64,
+    is_long: i32,
+    notional: f64,
+    currency: *const c_char,
+) -> NxStatus {
+    ffi_call(|| {
+        let builder = builder_mut(builder)?;
+        let refs = TradeRefs::read(trade_id, counterparty_id, netting_set_id, currency)?;
+        let direction = if is_long != 0 {
+            Direction::Long
+        } else {
+            Direction::Short
+        };
+        let forward = Forward::new(strike, maturity, 1.0, direction)
+            .map_err(|e| CapiError::InvalidArgument(e.to_string()))?;
+        builder
+            .trades
+            .push(refs.into_trade(Instrument::Forward(forward), notional));
+        Ok(())
+    })
+}
+
+/// Validate the builder and create a portfolio
+///
+/// The builder is left unchanged and must still be freed. On success
+/// `*out` receives a portfolio to free with `nx_portfolio_free`.
+///
+/// # Safety
+///
+/// `builder` must be a live builder and `out` a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn nx_portfolio_builder_build(
+    builder: *const NxPortfolioBuilder,
+    out: *mut *mut NxPortfolio,
+) -> NxStatus {
+    ffi_call(|| {
+        let builder = builder.as_ref().ok_or(CapiError::NullPointer("builder"))?;
+        if out.is_null() {
+            return Err(CapiError::NullPointer("out"));
+        }
+
+        let mut netting_sets = builder.netting_sets.clone();
+        for netting_set in &mut netting_sets {
+            let trade_ids: Vec<TradeId> = builder
+                .trades
+                .iter()
+                .filter(|trade| trade.netting_set_id() == netting_set.id())
+                .map(|trade| trade.id().clone())
+                .collect();
+            netting_set.add_trades(trade_ids);
+        }
+
+        let portfolio = PortfolioBuilder::new()
+            .add_counterparties(builder.counterparties.iter().cloned())
+            .add_netting_sets(netting_sets)
+            .add_trades(builder.trades.iter().cloned())
+            .build()
+            .map_err(|e| CapiError::Portfolio(e.to_string()))?;
+        *out = Box::into_raw(Box::new(NxPortfolio { inner: portfolio }));
+        Ok(())
+    })
+}
+
+/// Free a portfolio; null is ignored
+///
+/// # Safety
+///
+/// `portfolio` must be null or a pointer from `nx_portfolio_builder_build`
+/// that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn nx_portfolio_free(portfolio: *mut NxPortfolio) {
+    if !portfolio.is_null() {
+        drop(Box::from_raw(portfolio));
+    }
+}
+
+/// Number of trades in a portfolio
+///
+/// # Safety
+///
+/// `portfolio` must be a live portfolio and `out` a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn nx_portfolio_trade_count(
+    portfolio: *const NxPortfolio,
+    out: *mut usize,
+) -> NxStatus {
+    ffi_call(|| {
+        let portfolio = portfolio_ref(portfolio)?;
+        let out = out.as_mut().ok_or(CapiError::NullPointer("out"))?;
+        *out = portfolio.inner.trade_count();
+        Ok(())
+    })
+}
+
+/// Number of netting sets in a portfolio
+///
+/// # Safety
+///
+/// `portfolio` must be a live portfolio and `out` a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn nx_portfolio_netting_set_count(
+    portfolio: *const NxPortfolio,
+    out: *mut usize,
+) -> NxStatus {
+    ffi_call(|| {
+        let portfolio = portfolio_ref(portfolio)?;
+        let out = out.as_mut().ok_or(CapiError::NullPointer("out"))?;
+        *out = portfolio.inner.netting_set_count();
+        Ok(())
+    })
+}
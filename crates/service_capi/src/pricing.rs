@@ -0,0 +1,115 @@
+//! Pricing entry points
+//!
+//! Closed-form Black-Scholes pricing of single options and of every trade
+//! in a portfolio under one set of market inputs.
+
+use pricer_models::analytical::BlackScholes;
+use pricer_models::instruments::Instrument;
+
+use crate::error::{ffi_call, CapiError, NxStatus};
+use crate::portfolio::{portfolio_ref, NxPortfolio};
+
+fn model(spot: f64, vol: f64, rate: f64) -> Result<BlackScholes<f64>, CapiError> {
+    BlackScholes::new(spot, rate, vol).map_err(|e| CapiError::InvalidArgument(e.to_string()))
+}
+
+/// Present value of one unit of notional of an instrument
+fn unit_price(model: &BlackScholes<f64>, instrument: &Instrument<f64>) -> Result<f64, CapiError> {
+    match instrument {
+        Instrument::Vanilla(option) => model
+            .price_option(option)
+            .map_err(|e| CapiError::Pricing(e.to_string())),
+        Instrument::Forward(forward) => {
+            let df = (-model.rate() * forward.expiry()).exp();
+            let value = forward.notional() * (model.spot() - forward.strike() * df);
+            Ok(if forward.is_long() { value } else { -value })
+        }
+        Instrument::Swap(_) => Err(CapiError::Pricing(
+            "swaps are not supported by the Black-Scholes pricer".to_string(),
+        )),
+    }
+}
+
+/// Black-Scholes price of a European option
+///
+/// `is_call` is non-zero for a call.
+///
+/// # Safety
+///
+/// `out_price` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn nx_price_black_scholes(
+    spot: f64,
+    strike: f64,
+    expiry: f64,
+    rate: f64,
+    vol: f64,
+    is_call: i32,
+    out_price: *mut f64,
+) -> NxStatus {
+    ffi_call(|| {
+        let out_price = out_price
+            .as_mut()
+            .ok_or(CapiError::NullPointer("out_price"))?;
+        let model = model(spot, vol, rate)?;
+        *out_price = if is_call != 0 {
+            model.price_call(strike, expiry)
+        } else {
+            model.price_put(strike, expiry)
+        };
+        Ok(())
+    })
+}
+
+/// Present value of a portfolio under Black-Scholes
+///
+/// Every trade is priced off the same spot, volatility and rate and scaled
+/// by its notional.
+///
+/// # Safety
+///
+/// `portfolio` must be a live portfolio and `out_value` a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn nx_portfolio_price(
+    portfolio: *const NxPortfolio,
+    spot: f64,
+    vol: f64,
+    rate: f64,
+    out_value: *mut f64,
+) -> NxStatus {
+    ffi_call(|| {
+        let portfolio = portfolio_ref(portfolio)?;
+        let out_value = out_value
+            .as_mut()
+            .ok_or(CapiError::NullPointer("out_value"))?;
+        let model = model(spot, vol, rate)?;
+
+        let mut total = 0.0;
+        for trade in portfolio.inner.trades() {
+            total += trade.notional() * unit_price(&model, trade.instrument())?;
+        }
+        *out_value = total;
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ptr;
+
+    #[test]
+    fn test_price_black_scholes() {
+        let mut price = 0.0;
+        let status = unsafe { nx_price_black_scholes(100.0, 100.0, 1.0, 0.05, 0.2, 1, &mut price) };
+        assert_eq!(status, NxStatus::Ok);
+        assert!((price - 10.4506).abs() < 1e-3);
+
+        let status = unsafe { nx_price_black_scholes(-1.0, 100.0, 1.0, 0.05, 0.2, 1, &mut price) };
+        assert_eq!(status, NxStatus::InvalidArgument);
+
+        let status =
+            unsafe { nx_price_black_scholes(100.0, 100.0, 1.0, 0.05, 0.2, 1, ptr::null_mut()) };
+        assert_eq!(status, NxStatus::NullPointer);
+    }
+}
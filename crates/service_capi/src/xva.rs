@@ -0,0 +1,217 @@
+//! XVA entry points
+//!
+//! Exposure profiles are passed as row-major matrices with one row per
+//! netting set and one column per time grid point.
+
+use std::collections::HashMap;
+use std::ffi::c_char;
+use std::slice;
+
+use pricer_risk::portfolio::NettingSetId;
+use pricer_risk::xva::{FundingParams, OwnCreditParams, XvaCalculator};
+
+use crate::error::{c_str, ffi_call, CapiError, NxStatus};
+use crate::portfolio::{portfolio_ref, NxPortfolio};
+
+/// Opaque XVA calculator
+pub struct NxXvaCalculator {
+    inner: XvaCalculator,
+}
+
+/// Portfolio-level valuation adjustments
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct NxXvaResult {
+    /// Credit Valuation Adjustment
+    pub cva: f64,
+    /// Debit Valuation Adjustment
+    pub dva: f64,
+    /// Funding Cost Adjustment
+    pub fca: f64,
+    /// Funding Benefit Adjustment
+    pub fba: f64,
+    /// Total XVA: CVA - DVA + FCA - FBA
+    pub total: f64,
+}
+
+unsafe fn calculator_mut<'a>(
+    calculator: *mut NxXvaCalculator,
+) -> Result<&'a mut NxXvaCalculator, CapiError> {
+    calculator
+        .as_mut()
+        .ok_or(CapiError::NullPointer("calculator"))
+}
+
+/// Borrow `len` values, rejecting null unless `len` is zero
+unsafe fn values<'a>(
+    ptr: *const f64,
+    len: usize,
+    name: &'static str,
+) -> Result<&'a [f64], CapiError> {
+    if len == 0 {
+        return Ok(&[]);
+    }
+    if ptr.is_null() {
+        return Err(CapiError::NullPointer(name));
+    }
+    Ok(slice::from_raw_parts(ptr, len))
+}
+
+/// Create an XVA calculator with default configuration
+///
+/// Free with `nx_xva_calculator_free`.
+#[no_mangle]
+pub extern "C" fn nx_xva_calculator_new() -> *mut NxXvaCalculator {
+    Box::into_raw(Box::new(NxXvaCalculator {
+        inner: XvaCalculator::new(),
+    }))
+}
+
+/// Free an XVA calculator; null is ignored
+///
+/// # Safety
+///
+/// `calculator` must be null or a pointer from `nx_xva_calculator_new`
+/// that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn nx_xva_calculator_free(calculator: *mut NxXvaCalculator) {
+    if !calculator.is_null() {
+        drop(Box::from_raw(calculator));
+    }
+}
+
+/// Set own credit for DVA
+///
+/// # Safety
+///
+/// `calculator` must be a live calculator.
+#[no_mangle]
+pub unsafe extern "C" fn nx_xva_calculator_set_own_credit(
+    calculator: *mut NxXvaCalculator,
+    hazard_rate: f64,
+    lgd: f64,
+) -> NxStatus {
+    ffi_call(|| {
+        let calculator = calculator_mut(calculator)?;
+        let own = OwnCreditParams::new(hazard_rate, lgd)
+            .map_err(|e| CapiError::InvalidArgument(e.to_string()))?;
+        calculator.inner = calculator.inner.clone().with_own_credit(own);
+        Ok(())
+    })
+}
+
+/// Set borrowing and lending funding spreads in basis points for FVA
+///
+/// # Safety
+///
+/// `calculator` must be a live calculator.
+#[no_mangle]
+pub unsafe extern "C" fn nx_xva_calculator_set_funding(
+    calculator: *mut NxXvaCalculator,
+    borrow_bps: f64,
+    lend_bps: f64,
+) -> NxStatus {
+    ffi_call(|| {
+        let calculator = calculator_mut(calculator)?;
+        let funding = FundingParams::from_bps(borrow_bps, lend_bps);
+        funding
+            .validate()
+            .map_err(|e| CapiError::InvalidArgument(e.to_string()))?;
+        calculator.inner = calculator.inner.clone().with_funding(funding);
+        Ok(())
+    })
+}
+
+/// Compute portfolio XVA from exposure profiles
+///
+/// `netting_set_ids` holds `n_netting_sets` identifiers; `ee` and `ene`
+/// are `n_netting_sets × n_times` row-major matrices in the same order.
+/// `discount_factors` may be null, meaning 1.0 at every point. Every
+/// identifier must name a netting set in the portfolio.
+///
+/// # Safety
+///
+/// All handles must be live, `netting_set_ids` must point to
+/// `n_netting_sets` NUL-terminated strings, the arrays must hold the
+/// stated number of values and `out` must be a valid pointer.
+#[no_mangle]
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn nx_xva_compute_portfolio(
+    calculator: *const NxXvaCalculator,
+    portfolio: *const NxPortfolio,
+    netting_set_ids: *const *const c_char,
+    n_netting_sets: usize,
+    ee: *const f64,
+    ene: *const f64,
+    time_grid: *const f64,
+    discount_factors: *const f64,
+    n_times: usize,
+    out: *mut NxXvaResult,
+) -> NxStatus {
+    ffi_call(|| {
+        let calculator = calculator
+            .as_ref()
+            .ok_or(CapiError::NullPointer("calculator"))?;
+        let portfolio = portfolio_ref(portfolio)?;
+        let out = out.as_mut().ok_or(CapiError::NullPointer("out"))?;
+        if n_times == 0 {
+            return Err(CapiError::InvalidArgument(
+                "time grid must not be empty".to_string(),
+            ));
+        }
+
+        let size = n_netting_sets
+            .checked_mul(n_times)
+            .ok_or_else(|| CapiError::InvalidArgument("profile size overflows".to_string()))?;
+        let ids = if n_netting_sets == 0 {
+            &[]
+        } else if netting_set_ids.is_null() {
+            return Err(CapiError::NullPointer("netting_set_ids"));
+        } else {
+            slice::from_raw_parts(netting_set_ids, n_netting_sets)
+        };
+        let ee = values(ee, size, "ee")?;
+        let ene = values(ene, size, "ene")?;
+        let time_grid = values(time_grid, n_times, "time_grid")?;
+        let discount_factors = if discount_factors.is_null() {
+            vec![1.0; n_times]
+        } else {
+            values(discount_factors, n_times, "discount_factors")?.to_vec()
+        };
+
+        let mut ee_profiles = HashMap::with_capacity(n_netting_sets);
+        let mut ene_profiles = HashMap::with_capacity(n_netting_sets);
+        for (row, &id) in ids.iter().enumerate() {
+            let id = NettingSetId::new(c_str(id, "netting_set_ids")?);
+            if portfolio.inner.netting_set(&id).is_none() {
+                return Err(CapiError::InvalidArgument(format!(
+                    "unknown netting set {}",
+                    id
+                )));
+            }
+            let range = row * n_times..(row + 1) * n_times;
+            ee_profiles.insert(id.clone(), ee[range.clone()].to_vec());
+            ene_profiles.insert(id, ene[range].to_vec());
+        }
+
+        let xva = calculator
+            .inner
+            .compute_portfolio_xva(
+                &portfolio.inner,
+                &ee_profiles,
+                &ene_profiles,
+                time_grid,
+                &discount_factors,
+            )
+            .map_err(|e| CapiError::Xva(e.to_string()))?;
+
+        *out = NxXvaResult {
+            cva: xva.cva,
+            dva: xva.dva,
+            fca: xva.fca,
+            fba: xva.fba,
+            total: xva.total_xva(),
+        };
+        Ok(())
+    })
+}
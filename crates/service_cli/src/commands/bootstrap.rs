@@ -0,0 +1,587 @@
+//! Bootstrap command implementation
+//!
+//! Builds a yield curve set from market quotes using the pricer_optimiser
+//! bootstrapping engine and reports a repricing-error diagnostic table (plus
+//! the curve pillars) so curve construction can be validated outside the
+//! server. The nested curve set can also be written to JSON.
+//!
+//! # Quotes File
+//!
+//! CSV with a header row and the columns `curve,instrument,start,end,quote`:
+//!
+//! ```text
+//! curve,instrument,start,end,quote
+//! USD-SOFR,OIS,,1.0,0.0300
+//! USD-SOFR,OIS,,2.0,0.0320
+//! USD-TERM-3M,FRA,0.25,0.5,0.0350
+//! USD-TERM-3M,IRS,,2.0,0.0370
+//! USD-TERM-3M,FUT,,1.0,96.40
+//! ```
+//!
+//! `start` is only used by FRAs; futures are quoted as price (100 - rate).
+//!
+//! # Curve Configuration
+//!
+//! ```toml
+//! discount_curve = "USD-SOFR"
+//! interpolation = "log-linear"
+//! tolerance = 1e-12
+//! max_iterations = 100
+//! allow_negative_rates = false
+//!
+//! [forward_curves]
+//! "USD-TERM-3M" = "3M"
+//! ```
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use adapter_loader::{CsvLoader, CsvRecord, LoaderError};
+use pricer_core::market_data::curves::YieldCurve;
+use pricer_optimiser::bootstrapping::{
+    BootstrapInstrument, BootstrapInterpolation, BootstrappedCurve, CurveSet,
+    GenericBootstrapConfig, MultiCurveBuilder, Tenor,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+use crate::report::{Cell, Report, ReportTable};
+use crate::{CliError, Result};
+
+/// Curve construction settings loaded from the `--curve-config` TOML file.
+#[derive(Debug, Deserialize)]
+pub struct CurveConfig {
+    /// Name of the curve used for discounting (e.g. "USD-SOFR")
+    pub discount_curve: String,
+
+    /// Forward curve names mapped to their index tenor (e.g. "3M")
+    #[serde(default)]
+    pub forward_curves: HashMap<String, String>,
+
+    /// Interpolation method
+    #[serde(default = "default_interpolation")]
+    pub interpolation: String,
+
+    /// Solver tolerance per pillar
+    #[serde(default = "default_tolerance")]
+    pub tolerance: f64,
+
+    /// Maximum solver iterations per pillar
+    #[serde(default = "default_max_iterations")]
+    pub max_iterations: usize,
+
+    /// Allow negative implied zero rates
+    #[serde(default)]
+    pub allow_negative_rates: bool,
+}
+
+fn default_interpolation() -> String {
+    "log-linear".to_string()
+}
+
+fn default_tolerance() -> f64 {
+    1e-12
+}
+
+fn default_max_iterations() -> usize {
+    100
+}
+
+impl CurveConfig {
+    /// Load curve configuration from a TOML file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Err(CliError::FileNotFound(path.display().to_string()));
+        }
+
+        let content = std::fs::read_to_string(path)?;
+        toml::from_str(&content)
+            .map_err(|e| CliError::Parse(format!("Failed to parse curve config: {}", e)))
+    }
+
+    /// Convert to the generic bootstrap configuration used by the optimiser.
+    pub fn bootstrap_config(&self) -> Result<GenericBootstrapConfig<f64>> {
+        Ok(GenericBootstrapConfig::builder()
+            .interpolation(parse_interpolation(&self.interpolation)?)
+            .tolerance(self.tolerance)
+            .max_iterations(self.max_iterations)
+            .allow_negative_rates(self.allow_negative_rates)
+            .build())
+    }
+}
+
+/// A single market quote for a curve instrument.
+#[derive(Debug, Clone)]
+pub struct CurveQuote {
+    /// Curve the quote belongs to
+    pub curve: String,
+    /// Bootstrap instrument built from the quote
+    pub instrument: BootstrapInstrument<f64>,
+}
+
+impl CurveQuote {
+    /// Parse a quote from a CSV record (`curve,instrument,start,end,quote`).
+    pub fn from_record(record: &CsvRecord) -> Result<Self> {
+        let field = |idx: usize, name: &str| -> Result<&str> {
+            record.fields.get(idx).map(|s| s.trim()).ok_or_else(|| {
+                CliError::Parse(format!("Row {}: missing column '{}'", record.row, name))
+            })
+        };
+        let number = |idx: usize, name: &str| -> Result<f64> {
+            let raw = field(idx, name)?;
+            raw.parse::<f64>().map_err(|_| {
+                CliError::Parse(format!("Row {}: invalid {} '{}'", record.row, name, raw))
+            })
+        };
+
+        let curve = field(0, "curve")?.to_string();
+        let end = number(3, "end")?;
+        let quote = number(4, "quote")?;
+
+        let instrument = match field(1, "instrument")?.to_ascii_uppercase().as_str() {
+            "OIS" => BootstrapInstrument::ois(end, quote),
+            "IRS" | "SWAP" => BootstrapInstrument::irs(end, quote),
+            "FRA" => BootstrapInstrument::fra(number(2, "start")?, end, quote),
+            "FUT" | "FUTURE" => BootstrapInstrument::future(end, quote, 0.0),
+            other => {
+                return Err(CliError::Parse(format!(
+                    "Row {}: unknown instrument '{}'. Supported: OIS, IRS, FRA, FUT",
+                    record.row, other
+                )))
+            }
+        };
+
+        instrument
+            .validate(f64::MAX)
+            .map_err(|e| CliError::InvalidArgument(format!("Row {}: {}", record.row, e)))?;
+
+        Ok(Self { curve, instrument })
+    }
+}
+
+/// Repricing diagnostic for one input instrument.
+#[derive(Debug, Clone, Serialize)]
+pub struct RepricingDiagnostic {
+    /// Curve name
+    pub curve: String,
+    /// Instrument label (e.g. "OIS 2Y")
+    pub instrument: String,
+    /// Market quote as a rate
+    pub market_quote: f64,
+    /// Rate implied by the bootstrapped curve
+    pub model_quote: f64,
+    /// Model minus market, in basis points
+    pub error_bp: f64,
+}
+
+/// Serialised form of one bootstrapped curve.
+#[derive(Debug, Clone, Serialize)]
+pub struct CurveOutput {
+    /// Curve name
+    pub name: String,
+    /// Curve role ("discount" or "forward")
+    pub role: String,
+    /// Index tenor for forward curves
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tenor: Option<String>,
+    /// Interpolation method
+    pub interpolation: String,
+    /// Pillar maturities in years
+    pub pillars: Vec<f64>,
+    /// Discount factors at each pillar
+    pub discount_factors: Vec<f64>,
+}
+
+/// Serialised curve set together with its repricing diagnostics.
+#[derive(Debug, Clone, Serialize)]
+pub struct CurveSetOutput {
+    /// Bootstrapped curves
+    pub curves: Vec<CurveOutput>,
+    /// Repricing errors for every input quote
+    pub diagnostics: Vec<RepricingDiagnostic>,
+}
+
+/// Run the bootstrap command
+///
+/// Returns the repricing diagnostics and curve pillars as a report. If any
+/// repricing error exceeds `tolerance_bp`, the report is marked as a
+/// calibration failure so the CLI exits non-zero after writing it.
+pub fn run(
+    quotes: &str,
+    curve_config: &str,
+    curve_output: Option<&str>,
+    tolerance_bp: f64,
+) -> Result<Report> {
+    info!("Starting curve bootstrap...");
+    info!("  Quotes: {}", quotes);
+    info!("  Curve config: {}", curve_config);
+
+    let config = CurveConfig::from_file(curve_config)?;
+    let records = CsvLoader::load(quotes).map_err(|e| match e {
+        LoaderError::FileNotFound(path) => CliError::FileNotFound(path),
+        other => CliError::Parse(other.to_string()),
+    })?;
+    let quotes = records
+        .iter()
+        .map(CurveQuote::from_record)
+        .collect::<Result<Vec<_>>>()?;
+
+    let result = bootstrap(&config, &quotes)?;
+
+    if let Some(path) = curve_output {
+        let json = serde_json::to_string_pretty(&result)
+            .map_err(|e| CliError::Parse(format!("Failed to serialise curve set: {}", e)))?;
+        std::fs::write(path, json)?;
+        info!("Curve set written to: {}", path);
+    }
+
+    let mut report = to_report(&result);
+
+    let worst = result
+        .diagnostics
+        .iter()
+        .map(|d| d.error_bp.abs())
+        .fold(0.0, f64::max);
+    if worst > tolerance_bp {
+        let message = format!(
+            "Maximum repricing error {:.6}bp exceeds tolerance {}bp",
+            worst, tolerance_bp
+        );
+        warn!("{}", message);
+        report = report.with_failure(CliError::Calibration(message));
+    } else {
+        info!("Bootstrap complete");
+    }
+
+    Ok(report)
+}
+
+/// Convert a bootstrap result into report tables.
+pub fn to_report(result: &CurveSetOutput) -> Report {
+    let mut diagnostics = ReportTable::new(
+        "diagnostics",
+        "Repricing Diagnostics",
+        &[
+            "curve",
+            "instrument",
+            "market_quote",
+            "model_quote",
+            "error_bp",
+        ],
+    );
+    for d in &result.diagnostics {
+        diagnostics.push(vec![
+            d.curve.clone().into(),
+            d.instrument.clone().into(),
+            Cell::number(d.market_quote, 8),
+            Cell::number(d.model_quote, 8),
+            Cell::number(d.error_bp, 6),
+        ]);
+    }
+
+    let mut curves = ReportTable::new(
+        "curves",
+        "Curve Pillars",
+        &["curve", "role", "tenor", "pillar", "discount_factor"],
+    );
+    for curve in &result.curves {
+        for (pillar, df) in curve.pillars.iter().zip(&curve.discount_factors) {
+            curves.push(vec![
+                curve.name.clone().into(),
+                curve.role.clone().into(),
+                curve.tenor.clone().into(),
+                Cell::number(*pillar, 4),
+                Cell::number(*df, 10),
+            ]);
+        }
+    }
+
+    Report::new().with_table(diagnostics).with_table(curves)
+}
+
+/// Bootstrap the configured curve set and reprice every input quote.
+pub fn bootstrap(config: &CurveConfig, quotes: &[CurveQuote]) -> Result<CurveSetOutput> {
+    let mut forward_names: Vec<(&String, Tenor)> = config
+        .forward_curves
+        .iter()
+        .map(|(name, tenor)| parse_tenor(tenor).map(|t| (name, t)))
+        .collect::<Result<_>>()?;
+    forward_names.sort_by(|a, b| a.0.cmp(b.0));
+
+    for quote in quotes {
+        if quote.curve != config.discount_curve && !config.forward_curves.contains_key(&quote.curve)
+        {
+            return Err(CliError::InvalidArgument(format!(
+                "Quote references unconfigured curve '{}'",
+                quote.curve
+            )));
+        }
+    }
+
+    let instruments_for = |name: &str| -> Vec<BootstrapInstrument<f64>> {
+        quotes
+            .iter()
+            .filter(|q| q.curve == name)
+            .map(|q| q.instrument.clone())
+            .collect()
+    };
+
+    let discount_instruments = instruments_for(&config.discount_curve);
+    if discount_instruments.is_empty() {
+        return Err(CliError::InvalidArgument(format!(
+            "No quotes for discount curve '{}'",
+            config.discount_curve
+        )));
+    }
+    let forward_instruments: Vec<(Tenor, Vec<BootstrapInstrument<f64>>)> = forward_names
+        .iter()
+        .map(|(name, tenor)| (*tenor, instruments_for(name)))
+        .collect();
+
+    let bootstrap_config = config.bootstrap_config()?;
+    let interpolation = config.interpolation.clone();
+    let builder = MultiCurveBuilder::new(bootstrap_config);
+    let curve_set: CurveSet<f64> = builder
+        .build(&discount_instruments, &forward_instruments)
+        .map_err(|e| CliError::Calibration(e.to_string()))?;
+
+    let mut curves = vec![curve_output(
+        &config.discount_curve,
+        "discount",
+        None,
+        &interpolation,
+        curve_set.discount_curve(),
+    )];
+    for (name, tenor) in &forward_names {
+        if curve_set.has_forward_curve(*tenor) {
+            curves.push(curve_output(
+                name,
+                "forward",
+                Some(*tenor),
+                &interpolation,
+                curve_set.forward_curve(*tenor),
+            ));
+        }
+    }
+
+    let tenor_of: HashMap<&str, Tenor> = forward_names
+        .iter()
+        .map(|(name, tenor)| (name.as_str(), *tenor))
+        .collect();
+
+    let diagnostics = quotes
+        .iter()
+        .map(|quote| {
+            let curve = match tenor_of.get(quote.curve.as_str()) {
+                Some(tenor) => curve_set.forward_curve(*tenor),
+                None => curve_set.discount_curve(),
+            };
+            reprice(&quote.curve, &quote.instrument, curve)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(CurveSetOutput {
+        curves,
+        diagnostics,
+    })
+}
+
+/// Reprice an instrument off a bootstrapped curve.
+fn reprice(
+    curve_name: &str,
+    instrument: &BootstrapInstrument<f64>,
+    curve: &BootstrappedCurve<f64>,
+) -> Result<RepricingDiagnostic> {
+    let df_maturity = curve
+        .discount_factor(instrument.maturity())
+        .map_err(|e| CliError::Pricing(e.to_string()))?;
+    let residual = instrument.residual(df_maturity, |t| curve.discount_factor(t).unwrap_or(1.0));
+    let market_quote = instrument.rate();
+
+    Ok(RepricingDiagnostic {
+        curve: curve_name.to_string(),
+        instrument: instrument_label(instrument),
+        market_quote,
+        model_quote: market_quote + residual,
+        error_bp: residual * 1e4,
+    })
+}
+
+fn curve_output(
+    name: &str,
+    role: &str,
+    tenor: Option<Tenor>,
+    interpolation: &str,
+    curve: &BootstrappedCurve<f64>,
+) -> CurveOutput {
+    CurveOutput {
+        name: name.to_string(),
+        role: role.to_string(),
+        tenor: tenor.map(|t| t.name().to_string()),
+        interpolation: interpolation.to_string(),
+        pillars: curve.pillars().to_vec(),
+        discount_factors: curve.discount_factors_at_pillars().to_vec(),
+    }
+}
+
+fn instrument_label(instrument: &BootstrapInstrument<f64>) -> String {
+    match instrument {
+        BootstrapInstrument::Fra { start, end, .. } => format!("FRA {}x{}", start, end),
+        other => format!("{} {}Y", other.instrument_type(), other.maturity()),
+    }
+}
+
+fn parse_interpolation(name: &str) -> Result<BootstrapInterpolation> {
+    match name {
+        "log-linear" => Ok(BootstrapInterpolation::LogLinear),
+        "linear-zero" => Ok(BootstrapInterpolation::LinearZeroRate),
+        "cubic-spline" => Ok(BootstrapInterpolation::CubicSpline),
+        "monotonic-cubic" => Ok(BootstrapInterpolation::MonotonicCubic),
+        "flat-forward" => Ok(BootstrapInterpolation::FlatForward),
+        other => Err(CliError::InvalidArgument(format!(
+            "Unknown interpolation: {}. Supported: log-linear, linear-zero, cubic-spline, monotonic-cubic, flat-forward",
+            other
+        ))),
+    }
+}
+
+fn parse_tenor(name: &str) -> Result<Tenor> {
+    match name.to_ascii_uppercase().as_str() {
+        "ON" => Ok(Tenor::Overnight),
+        "1M" => Ok(Tenor::OneMonth),
+        "3M" => Ok(Tenor::ThreeMonth),
+        "6M" => Ok(Tenor::SixMonth),
+        "12M" | "1Y" => Ok(Tenor::TwelveMonth),
+        other => Err(CliError::InvalidArgument(format!(
+            "Unknown tenor: {}. Supported: ON, 1M, 3M, 6M, 12M",
+            other
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(fields: &[&str]) -> CsvRecord {
+        CsvRecord {
+            row: 1,
+            fields: fields.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    fn config() -> CurveConfig {
+        toml::from_str(
+            r#"
+            discount_curve = "USD-SOFR"
+
+            [forward_curves]
+            "USD-TERM-3M" = "3M"
+            "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_quote_record() {
+        let quote =
+            CurveQuote::from_record(&record(&["USD-SOFR", "ois", "", "2.0", "0.03"])).unwrap();
+        assert_eq!(quote.curve, "USD-SOFR");
+        assert!(quote.instrument.is_ois());
+        assert!((quote.instrument.maturity() - 2.0).abs() < 1e-12);
+
+        let fra = CurveQuote::from_record(&record(&["X", "FRA", "0.25", "0.5", "0.035"])).unwrap();
+        assert!((fra.instrument.start() - 0.25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_parse_quote_record_rejects_unknown_instrument() {
+        let result = CurveQuote::from_record(&record(&["X", "CDS", "", "1.0", "0.01"]));
+        assert!(matches!(result, Err(CliError::Parse(_))));
+    }
+
+    #[test]
+    fn test_bootstrap_reprices_quotes() {
+        let quotes = vec![
+            CurveQuote::from_record(&record(&["USD-SOFR", "OIS", "", "1.0", "0.030"])).unwrap(),
+            CurveQuote::from_record(&record(&["USD-SOFR", "OIS", "", "2.0", "0.032"])).unwrap(),
+            CurveQuote::from_record(&record(&["USD-SOFR", "OIS", "", "3.0", "0.034"])).unwrap(),
+            CurveQuote::from_record(&record(&["USD-TERM-3M", "IRS", "", "1.0", "0.035"])).unwrap(),
+            CurveQuote::from_record(&record(&["USD-TERM-3M", "IRS", "", "2.0", "0.037"])).unwrap(),
+        ];
+
+        let output = bootstrap(&config(), &quotes).unwrap();
+        assert_eq!(output.curves.len(), 2);
+        assert_eq!(output.curves[0].role, "discount");
+        assert_eq!(output.curves[1].tenor.as_deref(), Some("3M"));
+        assert_eq!(output.diagnostics.len(), quotes.len());
+        for d in &output.diagnostics {
+            assert!(d.error_bp.abs() < 1e-4, "{:?}", d);
+        }
+    }
+
+    #[test]
+    fn test_bootstrap_rejects_unconfigured_curve() {
+        let quotes = vec![CurveQuote::from_record(&record(&[
+            "EUR-ESTR", "OIS", "", "1.0", "0.02",
+        ]))
+        .unwrap()];
+        let result = bootstrap(&config(), &quotes);
+        assert!(matches!(result, Err(CliError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn test_run_writes_curve_set() {
+        let dir = tempfile::tempdir().unwrap();
+        let quotes_path = dir.path().join("quotes.csv");
+        let config_path = dir.path().join("curves.toml");
+        let output_path = dir.path().join("curves.json");
+
+        std::fs::write(
+            &quotes_path,
+            "curve,instrument,start,end,quote\nUSD-SOFR,OIS,,1.0,0.03\nUSD-SOFR,OIS,,2.0,0.032\n",
+        )
+        .unwrap();
+        std::fs::write(&config_path, "discount_curve = \"USD-SOFR\"\n").unwrap();
+
+        let report = run(
+            quotes_path.to_str().unwrap(),
+            config_path.to_str().unwrap(),
+            output_path.to_str(),
+            0.01,
+        )
+        .unwrap();
+        assert!(report.failure.is_none());
+        assert_eq!(report.table("diagnostics").unwrap().rows.len(), 2);
+
+        let json = std::fs::read_to_string(&output_path).unwrap();
+        assert!(json.contains("USD-SOFR"));
+        assert!(json.contains("diagnostics"));
+    }
+
+    #[test]
+    fn test_run_flags_tolerance_breach() {
+        let dir = tempfile::tempdir().unwrap();
+        let quotes_path = dir.path().join("quotes.csv");
+        let config_path = dir.path().join("curves.toml");
+
+        // The 2Y-5Y gap is extrapolated flat during stripping but
+        // interpolated log-linearly afterwards, leaving a visible error.
+        std::fs::write(
+            &quotes_path,
+            "curve,instrument,start,end,quote\nUSD-SOFR,OIS,,1.0,0.03\nUSD-SOFR,OIS,,2.0,0.032\nUSD-SOFR,OIS,,5.0,0.034\n",
+        )
+        .unwrap();
+        std::fs::write(&config_path, "discount_curve = \"USD-SOFR\"\n").unwrap();
+
+        let report = run(
+            quotes_path.to_str().unwrap(),
+            config_path.to_str().unwrap(),
+            None,
+            0.01,
+        )
+        .unwrap();
+        assert!(matches!(report.failure, Some(CliError::Calibration(_))));
+    }
+}
@@ -4,10 +4,11 @@
 
 use tracing::{info, warn};
 
+use crate::report::{Report, ReportTable};
 use crate::{CliError, Result};
 
 /// Run the calibrate command
-pub fn run(market_data: &str, model_type: &str, output: Option<&str>) -> Result<()> {
+pub fn run(market_data: &str, model_type: &str) -> Result<Report> {
     info!("Starting calibration...");
     info!("  Market data: {}", market_data);
     info!("  Model type: {}", model_type);
@@ -38,11 +39,13 @@ pub fn run(market_data: &str, model_type: &str, output: Option<&str>) -> Result<
         }
     }
 
-    if let Some(output_path) = output {
-        info!("Writing calibrated parameters to: {}", output_path);
-        // TODO: Serialise and write parameters
-    }
+    // TODO: Populate with calibrated parameters
+    let parameters = ReportTable::new(
+        "parameters",
+        "Calibrated Parameters",
+        &["model_type", "parameter", "value"],
+    );
 
     info!("Calibration complete");
-    Ok(())
+    Ok(Report::new().with_table(parameters))
 }
@@ -4,63 +4,84 @@
 
 use tracing::info;
 
+use crate::report::{Report, ReportTable};
 use crate::Result;
 
+/// Crates of the A-I-P-S architecture, grouped by layer.
+const MODULES: [(&str, &[&str]); 4] = [
+    (
+        "Adapter Layer",
+        &["adapter_feeds", "adapter_fpml", "adapter_loader"],
+    ),
+    (
+        "Infra Layer",
+        &["infra_config", "infra_master", "infra_store"],
+    ),
+    (
+        "Pricer Layer",
+        &[
+            "pricer_core (L1)",
+            "pricer_models (L2)",
+            "pricer_optimiser (L2.5)",
+            "pricer_pricing (L3)",
+            "pricer_risk (L4)",
+            "neutryx (facade)",
+        ],
+    ),
+    (
+        "Service Layer",
+        &["service_cli", "service_gateway", "service_python"],
+    ),
+];
+
 /// Run the check command
-pub fn run() -> Result<()> {
-    info!("Checking system configuration...\n");
+pub fn run() -> Result<Report> {
+    info!("Checking system configuration...");
 
-    println!("Neutryx System Check");
-    println!("====================\n");
+    let mut checks = ReportTable::new(
+        "checks",
+        "Neutryx System Check",
+        &["section", "item", "value"],
+    );
 
     // Check Rust version
-    println!("Rust Toolchain:");
-    println!("  Version: {}", env!("CARGO_PKG_VERSION"));
-    println!("  Edition: 2021");
-    println!();
+    checks.push(vec![
+        "Rust Toolchain".into(),
+        "Version".into(),
+        env!("CARGO_PKG_VERSION").into(),
+    ]);
+    checks.push(vec![
+        "Rust Toolchain".into(),
+        "Edition".into(),
+        "2021".into(),
+    ]);
 
     // Check for Enzyme (nightly feature)
-    println!("Enzyme AD:");
     #[cfg(feature = "enzyme-ad")]
-    println!("  Status: ✓ Enabled");
+    let enzyme = "Enabled";
     #[cfg(not(feature = "enzyme-ad"))]
-    println!("  Status: ✗ Disabled (pricer_pricing not built with Enzyme)");
-    println!();
+    let enzyme = "Disabled (pricer_pricing not built with Enzyme)";
+    checks.push(vec!["Enzyme AD".into(), "Status".into(), enzyme.into()]);
 
     // Check thread pool
-    let num_threads = rayon::current_num_threads();
-    println!("Parallelisation:");
-    println!("  Rayon threads: {}", num_threads);
-    println!("  CPU cores: {}", num_cpus::get());
-    println!();
+    checks.push(vec![
+        "Parallelisation".into(),
+        "Rayon threads".into(),
+        rayon::current_num_threads().to_string().into(),
+    ]);
+    checks.push(vec![
+        "Parallelisation".into(),
+        "CPU cores".into(),
+        num_cpus::get().to_string().into(),
+    ]);
 
     // Check available modules (A-I-P-S architecture)
-    println!("Available Modules (A-I-P-S Architecture):");
-    println!();
-    println!("  [A] Adapter Layer:");
-    println!("    ✓ adapter_feeds");
-    println!("    ✓ adapter_fpml");
-    println!("    ✓ adapter_loader");
-    println!();
-    println!("  [I] Infra Layer:");
-    println!("    ✓ infra_config");
-    println!("    ✓ infra_master");
-    println!("    ✓ infra_store");
-    println!();
-    println!("  [P] Pricer Layer:");
-    println!("    ✓ pricer_core (L1)");
-    println!("    ✓ pricer_models (L2)");
-    println!("    ✓ pricer_optimiser (L2.5)");
-    println!("    ✓ pricer_pricing (L3)");
-    println!("    ✓ pricer_risk (L4)");
-    println!();
-    println!("  [S] Service Layer:");
-    println!("    ✓ service_cli");
-    println!("    ✓ service_gateway");
-    println!("    ✓ service_python");
-    println!();
-
-    println!("All checks passed!");
+    for (layer, modules) in MODULES {
+        for module in modules {
+            checks.push(vec![layer.into(), (*module).into(), "Available".into()]);
+        }
+    }
 
-    Ok(())
+    info!("All checks passed");
+    Ok(Report::new().with_table(checks))
 }
@@ -19,6 +19,7 @@
 //! - USD SABR calibration only for CMS trade (lazy evaluation working)
 //! - No SABR calibration for VanillaSwap trades
 
+use crate::report::{Report, ReportTable};
 use crate::Result;
 use pricer_core::types::Currency;
 use pricer_optimiser::provider::MarketProvider;
@@ -34,8 +35,8 @@ use pricer_risk::demo::{run_portfolio_pricing, DemoTrade};
 ///
 /// # Returns
 ///
-/// `Ok(())` on success, `Err` on failure.
-pub fn run() -> Result<()> {
+/// `Ok(report)` with the per-trade PVs on success, `Err` on failure.
+pub fn run() -> Result<Report> {
     println!("========================================");
     println!("Lazy-Arc-Pricing-Kernel Demo");
     println!("========================================");
@@ -69,20 +70,19 @@ pub fn run() -> Result<()> {
 
     let results = run_portfolio_pricing(&trades, &market);
 
-    // Step 4: Display results
-    println!();
-    println!("[Demo] Pricing Results:");
-    println!("----------------------------------------");
-    println!("{:<10} {:<10} {:<15}", "Trade ID", "Currency", "PV");
-    println!("----------------------------------------");
-
+    // Step 4: Collect results
+    let mut table = ReportTable::new(
+        "results",
+        "Pricing Results",
+        &["trade_id", "currency", "pv"],
+    );
     for (trade, result) in trades.iter().zip(results.iter()) {
-        println!(
-            "{:<10} {:<10} {:<15.6}",
-            result.trade_id, trade.ccy, result.pv
-        );
+        table.push(vec![
+            result.trade_id.clone().into(),
+            trade.ccy.to_string().into(),
+            result.pv.into(),
+        ]);
     }
-    println!("----------------------------------------");
     println!();
 
     // Step 5: Architecture verification summary
@@ -95,8 +95,9 @@ pub fn run() -> Result<()> {
     println!("========================================");
     println!("Demo completed successfully!");
     println!("========================================");
+    println!();
 
-    Ok(())
+    Ok(Report::new().with_table(table))
 }
 
 #[cfg(test)]
@@ -106,7 +107,7 @@ mod tests {
     #[test]
     fn test_demo_run() {
         // Just verify the demo runs without error
-        let result = run();
-        assert!(result.is_ok());
+        let report = run().unwrap();
+        assert_eq!(report.table("results").unwrap().rows.len(), 4);
     }
 }
@@ -0,0 +1,505 @@
+//! Diff command implementation
+//!
+//! Reconciles two portfolio snapshots (e.g. end-of-day against intraday),
+//! reporting added, removed and amended trades together with notional deltas
+//! per counterparty. Optionally revalues the delta population so the PV
+//! impact of booking changes can be checked before a full risk run.
+//!
+//! Snapshots are read via `adapter_loader::PortfolioSnapshot`: CSV by
+//! default, Parquet when built with the `parquet` feature.
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use adapter_loader::{LoaderError, PortfolioSnapshot, SnapshotTrade};
+use pricer_core::types::Currency;
+use pricer_optimiser::provider::MarketProvider;
+use pricer_risk::demo::{run_portfolio_pricing, DemoTrade};
+use tracing::{info, warn};
+
+use crate::report::{Cell, Report, ReportTable};
+use crate::{CliError, Result};
+
+/// Tolerance below which notional differences are ignored.
+const NOTIONAL_TOLERANCE: f64 = 1e-6;
+
+/// A trade present in both snapshots whose booking differs.
+#[derive(Debug, Clone)]
+pub struct AmendedTrade {
+    /// Trade as booked in the left snapshot
+    pub left: SnapshotTrade,
+    /// Trade as booked in the right snapshot
+    pub right: SnapshotTrade,
+    /// Names of the fields that differ
+    pub changed_fields: Vec<String>,
+}
+
+/// Notional movement for one counterparty and currency.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CounterpartyDelta {
+    /// Counterparty identifier
+    pub counterparty_id: String,
+    /// Notional currency
+    pub currency: String,
+    /// Total notional in the left snapshot
+    pub left_notional: f64,
+    /// Total notional in the right snapshot
+    pub right_notional: f64,
+}
+
+impl CounterpartyDelta {
+    /// Right minus left notional.
+    pub fn delta(&self) -> f64 {
+        self.right_notional - self.left_notional
+    }
+}
+
+/// Result of reconciling two portfolio snapshots.
+#[derive(Debug, Clone, Default)]
+pub struct Reconciliation {
+    /// Trades only in the right snapshot
+    pub added: Vec<SnapshotTrade>,
+    /// Trades only in the left snapshot
+    pub removed: Vec<SnapshotTrade>,
+    /// Trades in both snapshots with differing bookings
+    pub amended: Vec<AmendedTrade>,
+    /// Number of trades identical in both snapshots
+    pub unchanged: usize,
+    /// Notional deltas per counterparty and currency (non-zero only)
+    pub counterparty_deltas: Vec<CounterpartyDelta>,
+}
+
+impl Reconciliation {
+    /// Whether the two snapshots are identical.
+    pub fn is_clean(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.amended.is_empty()
+    }
+}
+
+/// PV impact of the delta population.
+#[derive(Debug, Clone, Default)]
+pub struct DeltaRevaluation {
+    /// PV change per trade ID (right PV minus left PV)
+    pub pv_changes: Vec<(String, f64)>,
+    /// Trade IDs that could not be revalued
+    pub skipped: Vec<String>,
+}
+
+impl DeltaRevaluation {
+    /// Total PV change across the revalued trades.
+    pub fn total(&self) -> f64 {
+        self.pv_changes.iter().map(|(_, pv)| pv).sum()
+    }
+}
+
+/// Run the diff command
+pub fn run(left: &str, right: &str, revalue: bool) -> Result<Report> {
+    info!("Reconciling portfolio snapshots...");
+    info!("  Left: {}", left);
+    info!("  Right: {}", right);
+
+    let left_snapshot = load_snapshot(left)?;
+    let right_snapshot = load_snapshot(right)?;
+    info!(
+        "  Loaded {} / {} trades",
+        left_snapshot.len(),
+        right_snapshot.len()
+    );
+
+    let recon = reconcile(&left_snapshot, &right_snapshot)?;
+    if recon.is_clean() {
+        info!("Snapshots are identical");
+    }
+    let mut report = reconciliation_report(&recon);
+
+    if revalue {
+        info!("Revaluing delta population...");
+        let reval = revalue_delta(&recon);
+        if !reval.skipped.is_empty() {
+            warn!(
+                "{} trades skipped (no pricer for instrument type)",
+                reval.skipped.len()
+            );
+        }
+        report = report.with_table(revaluation_table(&reval));
+    }
+
+    info!("Reconciliation complete");
+    Ok(report)
+}
+
+pub(crate) fn load_snapshot(path: &str) -> Result<PortfolioSnapshot> {
+    PortfolioSnapshot::load(path).map_err(|e| match e {
+        LoaderError::FileNotFound(path) => CliError::FileNotFound(path),
+        LoaderError::IoError(io) => CliError::Io(io),
+        other => CliError::Parse(other.to_string()),
+    })
+}
+
+/// Reconcile two snapshots by trade ID.
+pub fn reconcile(left: &PortfolioSnapshot, right: &PortfolioSnapshot) -> Result<Reconciliation> {
+    let left_index = left
+        .by_trade_id()
+        .map_err(|e| CliError::Parse(format!("Left snapshot: {}", e)))?;
+    let right_index = right
+        .by_trade_id()
+        .map_err(|e| CliError::Parse(format!("Right snapshot: {}", e)))?;
+
+    let mut recon = Reconciliation::default();
+
+    for (id, left_trade) in &left_index {
+        match right_index.get(id) {
+            None => recon.removed.push((*left_trade).clone()),
+            Some(right_trade) => {
+                let changed_fields = changed_fields(left_trade, right_trade);
+                if changed_fields.is_empty() {
+                    recon.unchanged += 1;
+                } else {
+                    recon.amended.push(AmendedTrade {
+                        left: (*left_trade).clone(),
+                        right: (*right_trade).clone(),
+                        changed_fields,
+                    });
+                }
+            }
+        }
+    }
+    for (id, right_trade) in &right_index {
+        if !left_index.contains_key(id) {
+            recon.added.push((*right_trade).clone());
+        }
+    }
+
+    let mut totals: BTreeMap<(String, String), (f64, f64)> = BTreeMap::new();
+    for trade in left.trades() {
+        let key = (trade.counterparty_id.clone(), trade.currency.clone());
+        totals.entry(key).or_default().0 += trade.notional;
+    }
+    for trade in right.trades() {
+        let key = (trade.counterparty_id.clone(), trade.currency.clone());
+        totals.entry(key).or_default().1 += trade.notional;
+    }
+    recon.counterparty_deltas = totals
+        .into_iter()
+        .filter(|(_, (l, r))| (r - l).abs() > NOTIONAL_TOLERANCE)
+        .map(
+            |((counterparty_id, currency), (left_notional, right_notional))| CounterpartyDelta {
+                counterparty_id,
+                currency,
+                left_notional,
+                right_notional,
+            },
+        )
+        .collect();
+
+    Ok(recon)
+}
+
+fn changed_fields(left: &SnapshotTrade, right: &SnapshotTrade) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut check = |name: &str, differs: bool| {
+        if differs {
+            fields.push(name.to_string());
+        }
+    };
+
+    check(
+        "instrument_type",
+        left.instrument_type != right.instrument_type,
+    );
+    check(
+        "counterparty_id",
+        left.counterparty_id != right.counterparty_id,
+    );
+    check(
+        "netting_set_id",
+        left.netting_set_id != right.netting_set_id,
+    );
+    check(
+        "notional",
+        (left.notional - right.notional).abs() > NOTIONAL_TOLERANCE,
+    );
+    check("currency", left.currency != right.currency);
+    check("trade_date", left.trade_date != right.trade_date);
+    check("maturity_date", left.maturity_date != right.maturity_date);
+
+    let mut names: Vec<&String> = left.attributes.keys().collect();
+    names.extend(right.attributes.keys());
+    names.sort();
+    names.dedup();
+    for name in names {
+        check(
+            name,
+            left.attributes.get(name) != right.attributes.get(name),
+        );
+    }
+
+    fields
+}
+
+/// Revalue added, removed and amended trades.
+///
+/// Interest rate swaps are priced through the lazy-arc pricing kernel using
+/// `MarketProvider` curves; other instrument types are reported as skipped.
+pub fn revalue_delta(recon: &Reconciliation) -> DeltaRevaluation {
+    // (trade ID, sign, trade) - sign is +1 for the right booking, -1 for the left
+    let mut legs: Vec<(&str, f64, &SnapshotTrade)> = Vec::new();
+    for trade in &recon.added {
+        legs.push((&trade.trade_id, 1.0, trade));
+    }
+    for trade in &recon.removed {
+        legs.push((&trade.trade_id, -1.0, trade));
+    }
+    for amended in &recon.amended {
+        legs.push((&amended.right.trade_id, 1.0, &amended.right));
+        legs.push((&amended.left.trade_id, -1.0, &amended.left));
+    }
+
+    let mut reval = DeltaRevaluation::default();
+    let mut priceable = Vec::new();
+    let mut scales = Vec::new();
+    for (id, sign, trade) in legs {
+        match to_demo_trade(trade) {
+            Some((demo, direction)) => {
+                priceable.push(demo);
+                scales.push((id.to_string(), sign * direction * trade.notional));
+            }
+            None => {
+                if !reval.skipped.iter().any(|s| s == id) {
+                    reval.skipped.push(id.to_string());
+                }
+            }
+        }
+    }
+
+    let market = MarketProvider::new();
+    let results = run_portfolio_pricing(&priceable, &market);
+
+    let mut by_trade: BTreeMap<String, f64> = BTreeMap::new();
+    for ((id, scale), result) in scales.into_iter().zip(results) {
+        *by_trade.entry(id).or_default() += scale * result.pv;
+    }
+    reval.pv_changes = by_trade.into_iter().collect();
+    reval
+}
+
+/// Map a snapshot trade onto a priceable demo trade and its pay/receive sign.
+pub(crate) fn to_demo_trade(trade: &SnapshotTrade) -> Option<(DemoTrade, f64)> {
+    if trade.instrument_type != "InterestRateSwap" {
+        return None;
+    }
+    let ccy = Currency::from_str(&trade.currency).ok()?;
+    let fixed_rate = trade.attribute("fixed_rate")?.parse::<f64>().ok()?;
+    let direction = match trade.attribute("pay_fixed") {
+        Some(v) if v.eq_ignore_ascii_case("false") || v.eq_ignore_ascii_case("RCV") => -1.0,
+        _ => 1.0,
+    };
+    Some((
+        DemoTrade::new_vanilla_swap(trade.trade_id.clone(), ccy, fixed_rate),
+        direction,
+    ))
+}
+
+/// Convert a reconciliation into report tables.
+pub fn reconciliation_report(recon: &Reconciliation) -> Report {
+    let mut summary = ReportTable::new("summary", "Reconciliation Summary", &["status", "count"]);
+    summary.push(vec!["added".into(), recon.added.len().into()]);
+    summary.push(vec!["removed".into(), recon.removed.len().into()]);
+    summary.push(vec!["amended".into(), recon.amended.len().into()]);
+    summary.push(vec!["unchanged".into(), recon.unchanged.into()]);
+
+    let mut trades = ReportTable::new(
+        "trades",
+        "Trade Breaks",
+        &[
+            "status",
+            "trade_id",
+            "instrument_type",
+            "counterparty_id",
+            "currency",
+            "notional",
+            "changed_fields",
+        ],
+    );
+    let mut push_trade = |status: &str, trade: &SnapshotTrade, changed: Option<String>| {
+        trades.push(vec![
+            status.into(),
+            trade.trade_id.clone().into(),
+            trade.instrument_type.clone().into(),
+            trade.counterparty_id.clone().into(),
+            trade.currency.clone().into(),
+            Cell::number(trade.notional, 2),
+            changed.into(),
+        ]);
+    };
+    for trade in &recon.added {
+        push_trade("added", trade, None);
+    }
+    for trade in &recon.removed {
+        push_trade("removed", trade, None);
+    }
+    for amended in &recon.amended {
+        push_trade(
+            "amended",
+            &amended.right,
+            Some(amended.changed_fields.join(";")),
+        );
+    }
+
+    let mut deltas = ReportTable::new(
+        "counterparty_deltas",
+        "Notional Deltas by Counterparty",
+        &[
+            "counterparty_id",
+            "currency",
+            "left_notional",
+            "right_notional",
+            "delta",
+        ],
+    );
+    for d in &recon.counterparty_deltas {
+        deltas.push(vec![
+            d.counterparty_id.clone().into(),
+            d.currency.clone().into(),
+            Cell::number(d.left_notional, 2),
+            Cell::number(d.right_notional, 2),
+            Cell::number(d.delta(), 2),
+        ]);
+    }
+
+    Report::new()
+        .with_table(summary)
+        .with_table(trades)
+        .with_table(deltas)
+}
+
+/// Convert a delta revaluation into a report table.
+///
+/// Skipped trades are listed with an empty PV change.
+pub fn revaluation_table(reval: &DeltaRevaluation) -> ReportTable {
+    let mut table = ReportTable::new(
+        "revaluation",
+        "Delta Revaluation",
+        &["trade_id", "status", "pv_change"],
+    );
+    for (id, pv) in &reval.pv_changes {
+        table.push(vec![
+            id.clone().into(),
+            "revalued".into(),
+            Cell::number(*pv, 2),
+        ]);
+    }
+    for id in &reval.skipped {
+        table.push(vec![id.clone().into(), "skipped".into(), Cell::Empty]);
+    }
+    table.push(vec![
+        "TOTAL".into(),
+        "revalued".into(),
+        Cell::number(reval.total(), 2),
+    ]);
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn irs(id: &str, cp: &str, notional: f64, fixed_rate: &str) -> SnapshotTrade {
+        let mut attributes = BTreeMap::new();
+        attributes.insert("fixed_rate".to_string(), fixed_rate.to_string());
+        attributes.insert("pay_fixed".to_string(), "true".to_string());
+        SnapshotTrade {
+            trade_id: id.to_string(),
+            instrument_type: "InterestRateSwap".to_string(),
+            counterparty_id: cp.to_string(),
+            netting_set_id: "NS001".to_string(),
+            notional,
+            currency: "USD".to_string(),
+            trade_date: "2026-01-10".to_string(),
+            maturity_date: "2031-01-10".to_string(),
+            attributes,
+        }
+    }
+
+    #[test]
+    fn test_reconcile_classifies_trades() {
+        let left = PortfolioSnapshot::new(vec![
+            irs("T1", "CP001", 100.0, "0.04"),
+            irs("T2", "CP001", 200.0, "0.04"),
+            irs("T3", "CP002", 300.0, "0.04"),
+        ]);
+        let right = PortfolioSnapshot::new(vec![
+            irs("T1", "CP001", 100.0, "0.04"),
+            irs("T2", "CP001", 250.0, "0.045"),
+            irs("T4", "CP002", 50.0, "0.04"),
+        ]);
+
+        let recon = reconcile(&left, &right).unwrap();
+        assert_eq!(recon.unchanged, 1);
+        assert_eq!(recon.added.len(), 1);
+        assert_eq!(recon.added[0].trade_id, "T4");
+        assert_eq!(recon.removed.len(), 1);
+        assert_eq!(recon.removed[0].trade_id, "T3");
+        assert_eq!(recon.amended.len(), 1);
+        assert_eq!(
+            recon.amended[0].changed_fields,
+            vec!["notional".to_string(), "fixed_rate".to_string()]
+        );
+
+        assert_eq!(recon.counterparty_deltas.len(), 2);
+        let cp1 = &recon.counterparty_deltas[0];
+        assert_eq!(cp1.counterparty_id, "CP001");
+        assert!((cp1.delta() - 50.0).abs() < 1e-12);
+        let cp2 = &recon.counterparty_deltas[1];
+        assert!((cp2.delta() + 250.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_reconcile_identical_snapshots_is_clean() {
+        let snapshot = PortfolioSnapshot::new(vec![irs("T1", "CP001", 100.0, "0.04")]);
+        let recon = reconcile(&snapshot, &snapshot).unwrap();
+        assert!(recon.is_clean());
+        assert!(recon.counterparty_deltas.is_empty());
+
+        let report = reconciliation_report(&recon);
+        assert!(report.table("trades").unwrap().rows.is_empty());
+        assert_eq!(
+            report.table("summary").unwrap().rows[3][1],
+            Cell::Integer(1)
+        );
+    }
+
+    #[test]
+    fn test_revalue_delta_skips_unsupported_instruments() {
+        let mut option = irs("O1", "CP001", 100.0, "0.04");
+        option.instrument_type = "EquityOption".to_string();
+        let recon = Reconciliation {
+            added: vec![irs("T1", "CP001", 1_000_000.0, "0.02"), option],
+            ..Default::default()
+        };
+
+        let reval = revalue_delta(&recon);
+        assert_eq!(reval.pv_changes.len(), 1);
+        assert_eq!(reval.pv_changes[0].0, "T1");
+        assert!(reval.total().is_finite());
+        assert_eq!(reval.skipped, vec!["O1".to_string()]);
+    }
+
+    #[test]
+    fn test_revalue_unchanged_economics_nets_to_zero() {
+        let left = irs("T1", "CP001", 1_000_000.0, "0.02");
+        let mut right = left.clone();
+        right.netting_set_id = "NS002".to_string();
+        let recon = Reconciliation {
+            amended: vec![AmendedTrade {
+                left,
+                right,
+                changed_fields: vec!["netting_set_id".to_string()],
+            }],
+            ..Default::default()
+        };
+
+        let reval = revalue_delta(&recon);
+        assert!(reval.total().abs() < 1e-9);
+    }
+}
@@ -2,8 +2,11 @@
 //!
 //! Each submodule implements a specific CLI command.
 
+pub mod bootstrap;
 pub mod calibrate;
 pub mod check;
 pub mod demo;
+pub mod diff;
 pub mod price;
 pub mod report;
+pub mod validate;
@@ -4,15 +4,15 @@
 
 use tracing::info;
 
+use crate::report::{Report, ReportTable};
 use crate::{CliError, Result};
 
 /// Run the price command
-pub fn run(portfolio: &str, date: Option<&str>, num_paths: usize, format: &str) -> Result<()> {
+pub fn run(portfolio: &str, date: Option<&str>, num_paths: usize) -> Result<Report> {
     info!("Starting pricing...");
     info!("  Portfolio: {}", portfolio);
     info!("  Date: {}", date.unwrap_or("today"));
     info!("  Monte Carlo paths: {}", num_paths);
-    info!("  Output format: {}", format);
 
     // Validate portfolio file exists
     if !std::path::Path::new(portfolio).exists() {
@@ -22,31 +22,8 @@ pub fn run(portfolio: &str, date: Option<&str>, num_paths: usize, format: &str)
     // TODO: Load portfolio using adapter_loader
     // TODO: Load market data
     // TODO: Run pricing using pricer_pricing
-    // TODO: Output results in requested format
-
-    match format {
-        "json" => {
-            info!("Outputting results as JSON...");
-        }
-        "csv" => {
-            info!("Outputting results as CSV...");
-        }
-        "table" => {
-            info!("Outputting results as table...");
-            println!("\n┌────────────┬────────────┬────────────┐");
-            println!("│ Trade ID   │ PV         │ Delta      │");
-            println!("├────────────┼────────────┼────────────┤");
-            println!("│ (no data)  │            │            │");
-            println!("└────────────┴────────────┴────────────┘");
-        }
-        other => {
-            return Err(CliError::InvalidArgument(format!(
-                "Unknown format: {}. Supported: json, csv, table",
-                other
-            )));
-        }
-    }
+    let results = ReportTable::new("results", "Pricing Results", &["trade_id", "pv", "delta"]);
 
     info!("Pricing complete");
-    Ok(())
+    Ok(Report::new().with_table(results))
 }
@@ -1,50 +1,738 @@
 //! Report command implementation
 //!
-//! Generates risk reports using the pricer_risk crate.
+//! Generates XVA, exposure, Greeks and cashflow projection reports for a
+//! portfolio snapshot and writes them to the output directory in each
+//! requested format, using the report definitions in
+//! `pricer_risk::reporting`.
+//!
+//! Interest rate swaps are priced through the lazy-arc pricing kernel with
+//! `MarketProvider` curves; other instrument types are skipped. Netting set
+//! exposure uses the closed-form Gaussian profile of the EOD batch: the MtM
+//! amortises linearly from today's PV to zero at the longest maturity, with
+//! standard deviation `σ √t (T - t) N` over gross notional `N`.
+//!
+//! The snapshot carries no payment schedules, so the cashflow report takes
+//! swap fixed coupons as annual, counted back from maturity, and projects
+//! floating coupons at the flat curve rate of the trade currency.
+//!
+//! With `--store`, every table of the report data is also persisted to a
+//! Parquet result store under the run ID, so the API and GUI can query the
+//! EOD results without re-reading the rendered files.
 
-use tracing::info;
+use std::collections::BTreeMap;
 
+use adapter_loader::{PortfolioSnapshot, SnapshotTrade};
+use chrono::NaiveDate;
+use infra_store::storage::{ColumnData, ParquetResultStore};
+use infra_store::{ResultFrame, ResultKey, ResultKind, ResultStore};
+use pricer_core::types::time::Date;
+use pricer_core::types::Currency;
+use pricer_models::demo::{CurveEnum, FlatCurve};
+use pricer_models::instruments::{Instrument, PaymentFrequency, Swap};
+use pricer_optimiser::provider::MarketProvider;
+use pricer_pricing::greeks::{BumpSpec, Sensitivity, StructuredGreeks};
+use pricer_pricing::mc::Greek;
+use pricer_risk::cashflows::{CashflowProjection, CashflowProjector, STANDARD_BUCKETS};
+use pricer_risk::demo::{run_portfolio_pricing, DemoTrade};
+use pricer_risk::exposure::TimeGridBuilder;
+use pricer_risk::portfolio::{Counterparty, NettingSet, PortfolioBuilder, Trade, TradeId};
+use pricer_risk::reporting::{
+    cashflow_report_data, exposure_report_data, greeks_report_data, sensitivities_table,
+    xva_report_data, CellValue, DataTable, ExposureSeries, ReportData, ReportFormat,
+    ReportRegistry,
+};
+use pricer_risk::xva::{
+    compute_cva, compute_dva, compute_fva, CounterpartyXva, FundingParams, NettingSetXva,
+    OwnCreditParams, PortfolioXva,
+};
+use pricer_risk::{CounterpartyId, CreditParams, NettingSetId, PortfolioGreeks};
+use tracing::{info, warn};
+
+use super::diff::{load_snapshot, to_demo_trade};
+use crate::report::{Cell, Report, ReportTable};
 use crate::{CliError, Result};
 
+/// Exposure time grid spacing in years (quarterly)
+const GRID_STEP: f64 = 0.25;
+/// Normal rate volatility driving the exposure profile
+const NORMAL_RATE_VOL: f64 = 0.01;
+/// Counterparty hazard rate and loss given default
+const CP_HAZARD_RATE: f64 = 0.02;
+const CP_LGD: f64 = 0.6;
+/// Own hazard rate and loss given default
+const OWN_HAZARD_RATE: f64 = 0.01;
+const OWN_LGD: f64 = 0.6;
+/// Symmetric funding spread over the discount curve
+const FUNDING_SPREAD: f64 = 0.005;
+/// Parallel curve shift for rate sensitivities (1bp)
+const RATE_BUMP: f64 = 1e-4;
+
+/// A snapshot trade priced for reporting.
+#[derive(Debug, Clone)]
+struct PricedTrade {
+    trade_id: String,
+    counterparty_id: String,
+    netting_set_id: String,
+    currency: Currency,
+    notional: f64,
+    /// Fixed rate of the swap
+    fixed_rate: f64,
+    /// 1 paying fixed, -1 receiving fixed
+    direction: f64,
+    maturity_years: f64,
+    /// Present value in trade currency
+    pv: f64,
+    /// PV change for a 1bp parallel rise in the discount curve
+    rho: f64,
+}
+
+/// Result store to persist report tables to
+#[derive(Debug, Clone, Copy)]
+pub struct StoreTarget<'a> {
+    /// Root directory of the Parquet result store
+    pub root: &'a str,
+    /// Run ID the tables are stored under
+    pub run_id: &'a str,
+}
+
 /// Run the report command
-pub fn run(report_type: &str, portfolio: &str, output_dir: &str) -> Result<()> {
+pub fn run(
+    report_type: &str,
+    portfolio: &str,
+    output_dir: &str,
+    as_of: Option<&str>,
+    formats: &[ReportFormat],
+    store: Option<StoreTarget<'_>>,
+) -> Result<Report> {
     info!("Generating report...");
     info!("  Report type: {}", report_type);
     info!("  Portfolio: {}", portfolio);
     info!("  Output directory: {}", output_dir);
 
-    // Validate portfolio file exists
-    if !std::path::Path::new(portfolio).exists() {
-        return Err(CliError::FileNotFound(portfolio.to_string()));
+    let registry = ReportRegistry::standard();
+    let definition = registry.get(report_type).ok_or_else(|| {
+        let known: Vec<&str> = registry
+            .definitions()
+            .iter()
+            .map(|d| d.id.as_str())
+            .collect();
+        CliError::InvalidArgument(format!(
+            "Unknown report type: {}. Supported: {}",
+            report_type,
+            known.join(", ")
+        ))
+    })?;
+    let formats = if formats.is_empty() {
+        definition.formats.clone()
+    } else {
+        formats.to_vec()
+    };
+
+    let snapshot = load_snapshot(portfolio)?;
+    let as_of = match as_of {
+        Some(date) => parse_date(date)?,
+        None => latest_trade_date(&snapshot)?,
+    };
+    let (trades, skipped) = price_snapshot(&snapshot, as_of)?;
+    if !skipped.is_empty() {
+        warn!(
+            "Skipped {} trades that cannot be priced: {}",
+            skipped.len(),
+            skipped.join(", ")
+        );
+    }
+
+    let as_of_text = as_of.format("%Y-%m-%d").to_string();
+    let data = build_report_data(report_type, &trades, &as_of_text)?
+        .with_metadata("portfolio", portfolio)
+        .with_metadata("trades_priced", trades.len().to_string())
+        .with_metadata("trades_skipped", skipped.len().to_string());
+
+    let mut generated = ReportTable::new(
+        "generated",
+        "Generated Reports",
+        &["report_type", "format", "path", "bytes"],
+    );
+    for format in formats {
+        let rendered = registry
+            .render(report_type, &data, format)
+            .map_err(|e| CliError::InvalidArgument(e.to_string()))?;
+        let path = rendered.write_to(output_dir)?;
+        info!("Wrote {}", path.display());
+        generated.push(vec![
+            report_type.into(),
+            format.to_string().into(),
+            path.display().to_string().into(),
+            Cell::Integer(rendered.bytes.len() as i64),
+        ]);
+    }
+
+    let mut report = Report::new().with_table(generated);
+    if let Some(target) = store {
+        report = report.with_table(store_report_data(report_type, &data, as_of, target)?);
     }
 
-    // Create output directory if it doesn't exist
-    std::fs::create_dir_all(output_dir)?;
+    info!("Report generation complete");
+    Ok(report)
+}
+
+/// Persist every table of the report data and tabulate the stored keys.
+fn store_report_data(
+    report_type: &str,
+    data: &ReportData,
+    as_of: NaiveDate,
+    target: StoreTarget<'_>,
+) -> Result<ReportTable> {
+    let kind = match report_type {
+        "xva" => ResultKind::Xva,
+        "exposure" => ResultKind::ExposureProfile,
+        "greeks" => ResultKind::PricingRun,
+        _ => ResultKind::Report,
+    };
+    let store = ParquetResultStore::local(target.root);
+
+    let mut stored = ReportTable::new(
+        "stored",
+        "Stored Results",
+        &["kind", "as_of", "run_id", "name", "rows"],
+    );
+    for table in &data.tables {
+        let key = ResultKey::new(kind, as_of, target.run_id, &table.name)?;
+        let mut frame = result_frame(table)?
+            .with_metadata("report_type", report_type)
+            .with_metadata("title", &table.title);
+        for (k, v) in &data.metadata {
+            frame = frame.with_metadata(k, v);
+        }
+        store.put(&key, &frame)?;
+        info!("Stored {}", key);
+        stored.push(vec![
+            kind.as_str().into(),
+            as_of.to_string().into(),
+            target.run_id.into(),
+            table.name.as_str().into(),
+            Cell::Integer(table.len() as i64),
+        ]);
+    }
+    Ok(stored)
+}
 
-    // TODO: Load portfolio
-    // TODO: Generate report using pricer_risk
+/// Convert a report table to a typed result frame.
+///
+/// Columns holding only numbers become float columns, only integers
+/// integer columns, and anything else text with the report formatting.
+fn result_frame(table: &DataTable) -> Result<ResultFrame> {
+    let mut frame = ResultFrame::new();
+    for (index, column) in table.columns.iter().enumerate() {
+        let cells = || table.rows.iter().map(move |row| &row[index]);
+        let is = |f: fn(&CellValue) -> bool| {
+            cells().all(|cell| f(cell) || matches!(cell, CellValue::Empty))
+        };
+        let data = if is(|c| matches!(c, CellValue::Integer(_))) {
+            ColumnData::Integer(
+                cells()
+                    .map(|cell| match cell {
+                        CellValue::Integer(value) => Some(*value),
+                        _ => None,
+                    })
+                    .collect(),
+            )
+        } else if is(|c| matches!(c, CellValue::Number(_) | CellValue::Integer(_))) {
+            ColumnData::Float(cells().map(CellValue::as_f64).collect())
+        } else {
+            ColumnData::Text(
+                (0..table.len())
+                    .map(|row| match table.rows[row][index] {
+                        CellValue::Empty => None,
+                        _ => Some(table.formatted(row, index)),
+                    })
+                    .collect(),
+            )
+        };
+        frame = frame.with_column(&column.name, data)?;
+    }
+    Ok(frame)
+}
 
+/// Build the report data for a standard report type.
+fn build_report_data(report_type: &str, trades: &[PricedTrade], as_of: &str) -> Result<ReportData> {
     match report_type {
-        "xva" => {
-            info!("Generating XVA report...");
-            // Use pricer_risk::xva
+        "xva" => Ok(xva_report_data(&portfolio_xva(trades), as_of)),
+        "exposure" => Ok(exposure_report_data(&exposure_profiles(trades), as_of)),
+        "greeks" => Ok(greeks_report_data(&trade_greeks(trades), as_of)
+            .with_table(sensitivities_table(&structured_greeks(trades)))),
+        "cashflows" => Ok(cashflow_report_data(
+            &cashflow_projection(trades, as_of)?,
+            STANDARD_BUCKETS,
+        )),
+        other => Err(CliError::InvalidArgument(format!(
+            "Report type {} has no data source",
+            other
+        ))),
+    }
+}
+
+/// Price the live swaps of a snapshot, returning priced trades and the IDs
+/// of skipped trades.
+fn price_snapshot(
+    snapshot: &PortfolioSnapshot,
+    as_of: NaiveDate,
+) -> Result<(Vec<PricedTrade>, Vec<String>)> {
+    let mut skipped = Vec::new();
+    let mut candidates: Vec<(&SnapshotTrade, DemoTrade, f64, f64)> = Vec::new();
+    for trade in snapshot.trades() {
+        let maturity = parse_date(&trade.maturity_date)?;
+        let maturity_years = (maturity - as_of).num_days() as f64 / 365.0;
+        match to_demo_trade(trade) {
+            Some((demo, direction)) if maturity_years > 0.0 => {
+                candidates.push((trade, demo, direction, maturity_years))
+            }
+            _ => skipped.push(trade.trade_id.clone()),
+        }
+    }
+
+    let demo_trades: Vec<DemoTrade> = candidates.iter().map(|(_, d, _, _)| d.clone()).collect();
+    let base = MarketProvider::new();
+    let bumped = MarketProvider::new();
+    for demo in &demo_trades {
+        let CurveEnum::Flat(curve) = *base.get_curve(demo.ccy);
+        bumped.set_curve(
+            demo.ccy,
+            CurveEnum::Flat(FlatCurve {
+                rate: curve.rate + RATE_BUMP,
+            }),
+        );
+    }
+    let base_pvs = run_portfolio_pricing(&demo_trades, &base);
+    let bumped_pvs = run_portfolio_pricing(&demo_trades, &bumped);
+
+    let priced = candidates
+        .into_iter()
+        .zip(base_pvs.iter().zip(&bumped_pvs))
+        .map(|((trade, demo, direction, maturity_years), (pv, up))| {
+            let scale = direction * trade.notional;
+            PricedTrade {
+                trade_id: trade.trade_id.clone(),
+                counterparty_id: trade.counterparty_id.clone(),
+                netting_set_id: trade.netting_set_id.clone(),
+                currency: demo.ccy,
+                notional: trade.notional,
+                fixed_rate: demo.instrument.fixed_rate(),
+                direction,
+                maturity_years,
+                pv: scale * pv.pv,
+                rho: scale * (up.pv - pv.pv),
+            }
+        })
+        .collect();
+    Ok((priced, skipped))
+}
+
+/// Closed-form exposure profile per netting set, in netting set order.
+fn exposure_profiles(trades: &[PricedTrade]) -> Vec<ExposureSeries> {
+    let mut netting_sets: BTreeMap<&str, Vec<&PricedTrade>> = BTreeMap::new();
+    for trade in trades {
+        netting_sets
+            .entry(&trade.netting_set_id)
+            .or_default()
+            .push(trade);
+    }
+
+    netting_sets
+        .into_iter()
+        .map(|(id, trades)| {
+            let pv: f64 = trades.iter().map(|t| t.pv).sum();
+            let gross: f64 = trades.iter().map(|t| t.notional.abs()).sum();
+            let horizon = trades
+                .iter()
+                .map(|t| t.maturity_years)
+                .fold(GRID_STEP, f64::max);
+            let time_grid = TimeGridBuilder::new(GRID_STEP)
+                .with_event_dates(trades.iter().map(|t| t.maturity_years).filter(|&m| m > 0.0))
+                .with_horizon(horizon)
+                .build()
+                .expect("grid step and horizon are positive");
+
+            let (ee, pfe) = time_grid
+                .iter()
+                .map(|&t| {
+                    let mean = pv * (horizon - t) / horizon;
+                    let std = NORMAL_RATE_VOL * t.sqrt() * (horizon - t) * gross;
+                    let ee = if std > 0.0 {
+                        let d = mean / std;
+                        mean * norm_cdf(d) + std * norm_pdf(d)
+                    } else {
+                        mean.max(0.0)
+                    };
+                    // 95% quantile of the positive part
+                    (ee, (mean + 1.644_853_626_951_472 * std).max(0.0))
+                })
+                .unzip();
+            ExposureSeries::new(id, time_grid, ee, pfe)
+        })
+        .collect()
+}
+
+/// CVA, DVA and funding adjustments per netting set, grouped by counterparty.
+fn portfolio_xva(trades: &[PricedTrade]) -> PortfolioXva {
+    let credit = CreditParams::new(CP_HAZARD_RATE, CP_LGD).expect("valid counterparty credit");
+    let own_credit = OwnCreditParams::new(OWN_HAZARD_RATE, OWN_LGD).expect("valid own credit");
+    let funding = FundingParams::symmetric(FUNDING_SPREAD);
+    let market = MarketProvider::new();
+
+    let mut by_counterparty: BTreeMap<&str, Vec<NettingSetXva>> = BTreeMap::new();
+    for profile in exposure_profiles(trades) {
+        let trade = trades
+            .iter()
+            .find(|t| t.netting_set_id == profile.id)
+            .expect("profiles are built from these trades");
+        let CurveEnum::Flat(curve) = *market.get_curve(trade.currency);
+        let dfs: Vec<f64> = profile
+            .time_grid
+            .iter()
+            .map(|t| (-curve.rate * t).exp())
+            .collect();
+        let pv: f64 = trades
+            .iter()
+            .filter(|t| t.netting_set_id == profile.id)
+            .map(|t| t.pv)
+            .sum();
+        let horizon = profile.time_grid.last().copied().unwrap_or(GRID_STEP);
+        let ene: Vec<f64> = profile
+            .time_grid
+            .iter()
+            .zip(&profile.ee)
+            .map(|(t, ee)| ee - pv * (horizon - t) / horizon)
+            .collect();
+
+        let (fca, fba, _) =
+            compute_fva(&profile.ee, &ene, &profile.time_grid, &funding, &dfs, false);
+        by_counterparty
+            .entry(&trade.counterparty_id)
+            .or_default()
+            .push(NettingSetXva::new(
+                NettingSetId::new(profile.id.as_str()),
+                CounterpartyId::new(trade.counterparty_id.as_str()),
+                compute_cva(&profile.ee, &profile.time_grid, &credit),
+                compute_dva(&ene, &profile.time_grid, &own_credit),
+                fca,
+                fba,
+            ));
+    }
+
+    PortfolioXva::from_counterparties(
+        by_counterparty
+            .into_iter()
+            .map(|(cp, sets)| CounterpartyXva::from_netting_sets(CounterpartyId::new(cp), sets))
+            .collect(),
+    )
+}
+
+/// Future swap coupons, floating legs at the flat curve rate.
+fn cashflow_projection(trades: &[PricedTrade], as_of: &str) -> Result<CashflowProjection> {
+    let as_of = Date::parse(as_of).map_err(|e| CliError::Parse(e.to_string()))?;
+    let credit = CreditParams::new(CP_HAZARD_RATE, CP_LGD).expect("valid counterparty credit");
+    let market = MarketProvider::new();
+    let mut projector = CashflowProjector::new(as_of);
+    let mut builder = PortfolioBuilder::new();
+    let mut counterparties: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+
+    for trade in trades {
+        let netting_sets = counterparties.entry(&trade.counterparty_id).or_default();
+        if !netting_sets.contains(&trade.netting_set_id.as_str()) {
+            netting_sets.push(&trade.netting_set_id);
         }
-        "exposure" => {
-            info!("Generating exposure report...");
-            // Use pricer_risk::exposure
+        let CurveEnum::Flat(curve) = *market.get_curve(trade.currency);
+        projector = projector
+            .with_floating_rate(trade.currency, curve.rate)
+            .map_err(|e| CliError::Pricing(e.to_string()))?;
+
+        // Annual payments counted back from maturity
+        let mut payment_dates: Vec<f64> = (0..)
+            .map(|k| trade.maturity_years - k as f64)
+            .take_while(|&t| t > 0.0)
+            .collect();
+        payment_dates.reverse();
+        let swap = Swap::new(
+            trade.notional,
+            trade.fixed_rate,
+            payment_dates,
+            PaymentFrequency::Annual,
+            trade.currency,
+        )
+        .map_err(|e| CliError::Validation(vec![format!("{}: {}", trade.trade_id, e)]))?;
+        builder = builder.add_trade(Trade::new(
+            TradeId::new(trade.trade_id.as_str()),
+            Instrument::Swap(swap),
+            trade.currency,
+            CounterpartyId::new(trade.counterparty_id.as_str()),
+            NettingSetId::new(trade.netting_set_id.as_str()),
+            trade.direction * trade.notional,
+        ));
+    }
+    for (cp, netting_sets) in counterparties {
+        builder =
+            builder.add_counterparty(Counterparty::new(CounterpartyId::new(cp), credit.clone()));
+        for ns in netting_sets {
+            builder = builder.add_netting_set(NettingSet::new(
+                NettingSetId::new(ns),
+                CounterpartyId::new(cp),
+            ));
         }
-        "greeks" => {
-            info!("Generating Greeks report...");
-            // Use pricer_pricing::greeks
+    }
+    let portfolio = builder
+        .build()
+        .map_err(|e| CliError::Validation(vec![e.to_string()]))?;
+    Ok(projector.project(&portfolio))
+}
+
+/// Per-trade Greeks: swaps carry rate sensitivity only.
+fn trade_greeks(trades: &[PricedTrade]) -> Vec<(String, PortfolioGreeks<f64>)> {
+    trades
+        .iter()
+        .map(|trade| {
+            let mut greeks = PortfolioGreeks::with_values(0.0, 0.0, 0.0, 0.0, trade.rho);
+            greeks.add_factor_greek(format!("{}.rate", trade.currency), trade.rho);
+            (trade.trade_id.clone(), greeks)
+        })
+        .collect()
+}
+
+/// Per-trade Greeks with units, currency and bump: rho per 1bp parallel
+/// shift of the trade-currency curve.
+fn structured_greeks(trades: &[PricedTrade]) -> Vec<(String, StructuredGreeks)> {
+    trades
+        .iter()
+        .map(|trade| {
+            let rho = Sensitivity::new(
+                Greek::Rho,
+                format!("{}.rate", trade.currency),
+                trade.rho,
+                BumpSpec::Absolute { size: RATE_BUMP },
+            );
+            let greeks = StructuredGreeks::new(trade.pv, 0.0, trade.currency.to_string())
+                .with_sensitivity(rho);
+            (trade.trade_id.clone(), greeks)
+        })
+        .collect()
+}
+
+/// Latest trade date in the snapshot, the default valuation date.
+fn latest_trade_date(snapshot: &PortfolioSnapshot) -> Result<NaiveDate> {
+    snapshot
+        .trades()
+        .iter()
+        .map(|t| parse_date(&t.trade_date))
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .max()
+        .ok_or_else(|| CliError::InvalidArgument("Portfolio snapshot has no trades".to_string()))
+}
+
+fn parse_date(date: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|e| CliError::Parse(format!("Invalid date '{}': {}", date, e)))
+}
+
+/// Standard normal CDF (Abramowitz-Stegun 7.1.26 via erf)
+fn norm_cdf(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.327_591_1 * x.abs() / std::f64::consts::SQRT_2);
+    let poly = t
+        * (0.254_829_592
+            + t * (-0.284_496_736
+                + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    let erf = 1.0 - poly * (-(x * x) / 2.0).exp();
+    if x >= 0.0 {
+        0.5 * (1.0 + erf)
+    } else {
+        0.5 * (1.0 - erf)
+    }
+}
+
+/// Standard normal PDF
+fn norm_pdf(x: f64) -> f64 {
+    (-(x * x) / 2.0).exp() / (2.0 * std::f64::consts::PI).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    const SNAPSHOT: &str = "\
+trade_id,instrument_type,counterparty_id,netting_set_id,notional,currency,trade_date,maturity_date,fixed_rate,pay_fixed
+IRS-1,InterestRateSwap,CP001,NS001,100000000,USD,2026-01-10,2031-01-10,0.0425,true
+IRS-2,InterestRateSwap,CP001,NS001,50000000,USD,2026-02-10,2029-02-10,0.0380,false
+IRS-3,InterestRateSwap,CP002,NS002,75000000,EUR,2026-03-10,2036-03-10,0.0310,true
+FXF-1,FxForward,CP002,NS002,10000000,EUR,2026-03-10,2027-03-10,,
+";
+
+    fn write_snapshot(dir: &tempfile::TempDir) -> String {
+        let path = dir.path().join("portfolio.csv");
+        std::fs::File::create(&path)
+            .unwrap()
+            .write_all(SNAPSHOT.as_bytes())
+            .unwrap();
+        path.display().to_string()
+    }
+
+    #[test]
+    fn test_generates_all_formats() {
+        let dir = tempfile::tempdir().unwrap();
+        let portfolio = write_snapshot(&dir);
+        let out = dir.path().join("reports");
+
+        let report = run("xva", &portfolio, out.to_str().unwrap(), None, &[], None).unwrap();
+        let generated = report.table("generated").unwrap();
+        assert_eq!(generated.rows.len(), 4);
+        for ext in ["csv", "json", "xlsx", "pdf"] {
+            // Valuation date defaults to the latest trade date
+            assert!(out.join(format!("xva_2026-03-10.{}", ext)).exists());
         }
-        other => {
-            return Err(CliError::InvalidArgument(format!(
-                "Unknown report type: {}. Supported: xva, exposure, greeks",
-                other
-            )));
+
+        let csv = std::fs::read_to_string(out.join("xva_2026-03-10.csv")).unwrap();
+        assert!(csv.contains("NS001,CP001"));
+        assert!(csv.contains("NS002,CP002"));
+    }
+
+    #[test]
+    fn test_greeks_and_exposure_reports() {
+        let dir = tempfile::tempdir().unwrap();
+        let portfolio = write_snapshot(&dir);
+        let out = dir.path().join("reports");
+        let out = out.to_str().unwrap();
+
+        run(
+            "greeks",
+            &portfolio,
+            out,
+            Some("2026-10-15"),
+            &[ReportFormat::Json],
+            None,
+        )
+        .unwrap();
+        let json = std::fs::read_to_string(format!("{}/greeks_2026-10-15.json", out)).unwrap();
+        assert!(json.contains("\"trade\": \"IRS-3\""));
+        assert!(json.contains("\"factor\": \"EUR.rate\""));
+        assert!(json.contains("\"unit\": \"per 1bp\""));
+        assert!(json.contains("\"bump\": \"absolute 0.0001\""));
+        assert!(json.contains("\"trades_skipped\": \"1\""));
+
+        run(
+            "exposure",
+            &portfolio,
+            out,
+            Some("2026-10-15"),
+            &[ReportFormat::Csv],
+            None,
+        )
+        .unwrap();
+        let csv = std::fs::read_to_string(format!("{}/exposure_2026-10-15.csv", out)).unwrap();
+        assert!(csv.starts_with("Exposure Summary\nid,epe,effective_epe,peak_ee,peak_pfe\n"));
+    }
+
+    #[test]
+    fn test_cashflow_report() {
+        let dir = tempfile::tempdir().unwrap();
+        let portfolio = write_snapshot(&dir);
+        let out = dir.path().join("reports");
+        let out = out.to_str().unwrap();
+
+        run(
+            "cashflows",
+            &portfolio,
+            out,
+            Some("2026-10-15"),
+            &[ReportFormat::Csv],
+            None,
+        )
+        .unwrap();
+        let csv = std::fs::read_to_string(format!("{}/cashflows_2026-10-15.csv", out)).unwrap();
+        assert!(
+            csv.starts_with("Liquidity Ladder\ncurrency,bucket,inflows,outflows,net,cumulative\n")
+        );
+        // IRS-1 pays 4.25% on 100m annually; IRS-2 receives 3.80% on 50m
+        assert!(csv.contains("IRS-1,CP001,fixed_coupon,USD,-4250000.00"));
+        assert!(csv.contains("IRS-2,CP001,fixed_coupon,USD,1900000.00"));
+        assert!(csv.contains("IRS-3,CP002,floating_coupon,EUR,"));
+        assert!(!csv.contains("FXF-1"));
+    }
+
+    #[test]
+    fn test_stores_report_tables() {
+        let dir = tempfile::tempdir().unwrap();
+        let portfolio = write_snapshot(&dir);
+        let out = dir.path().join("reports");
+        let root = dir.path().join("results");
+        let target = StoreTarget {
+            root: root.to_str().unwrap(),
+            run_id: "eod",
+        };
+
+        let report = run(
+            "xva",
+            &portfolio,
+            out.to_str().unwrap(),
+            Some("2026-10-15"),
+            &[ReportFormat::Csv],
+            Some(target),
+        )
+        .unwrap();
+        let stored = report.table("stored").unwrap();
+        assert_eq!(stored.rows.len(), 3);
+
+        let store = ParquetResultStore::local(&root);
+        let key = ResultKey::new(
+            ResultKind::Xva,
+            parse_date("2026-10-15").unwrap(),
+            "eod",
+            "netting_sets",
+        )
+        .unwrap();
+        let frame = store.get(&key).unwrap().unwrap();
+        assert_eq!(frame.num_rows(), 2);
+        assert!(matches!(
+            frame.column("netting_set"),
+            Some(ColumnData::Text(_))
+        ));
+        let Some(ColumnData::Float(cva)) = frame.column("cva") else {
+            panic!("cva is not a float column");
+        };
+        assert!(cva.iter().all(|v| v.is_some_and(|v| v > 0.0)));
+        assert_eq!(frame.metadata["trades_skipped"], "1");
+        assert_eq!(frame.metadata["report_type"], "xva");
+    }
+
+    #[test]
+    fn test_prices_live_swaps_with_rate_sensitivity() {
+        let snapshot = PortfolioSnapshot::load(write_snapshot(&tempfile::tempdir().unwrap()));
+        let (trades, skipped) =
+            price_snapshot(&snapshot.unwrap(), parse_date("2026-10-15").unwrap()).unwrap();
+        assert_eq!(skipped, vec!["FXF-1".to_string()]);
+        assert_eq!(trades.len(), 3);
+
+        // Paying and receiving fixed move in opposite directions, and a 1bp
+        // bump moves PV by a small fraction of its level
+        let trade = |id: &str| trades.iter().find(|t| t.trade_id == id).unwrap();
+        assert!(trade("IRS-1").rho * trade("IRS-2").rho < 0.0);
+        for t in &trades {
+            assert!(t.rho != 0.0);
+            assert!(t.rho.abs() < 0.01 * t.pv.abs());
         }
     }
 
-    info!("Report generation complete");
-    Ok(())
+    #[test]
+    fn test_unknown_report_type() {
+        let dir = tempfile::tempdir().unwrap();
+        let portfolio = write_snapshot(&dir);
+        let err = run("pnl", &portfolio, "unused", None, &[], None).unwrap_err();
+        assert!(matches!(err, CliError::InvalidArgument(_)));
+        assert!(err.to_string().contains("xva, exposure, greeks, cashflows"));
+    }
+
+    #[test]
+    fn test_missing_portfolio() {
+        let err = run("xva", "does/not/exist.csv", "unused", None, &[], None).unwrap_err();
+        assert!(matches!(err, CliError::FileNotFound(_)));
+    }
 }
@@ -0,0 +1,121 @@
+//! Validate command implementation
+//!
+//! Checks a portfolio file against the canonical JSON trade schema
+//! (`adapter_loader::TradeDocument`). Every invalid field and dangling
+//! reference is reported together, keyed by JSON path, so a whole file can
+//! be fixed in one pass. A valid file produces a summary of its contents.
+
+use std::collections::BTreeMap;
+
+use adapter_loader::{LoaderError, ProductRecord, TradeDocument};
+use tracing::info;
+
+use crate::report::{Report, ReportTable};
+use crate::{CliError, Result};
+
+/// Run the validate command
+pub fn run(portfolio: &str) -> Result<Report> {
+    info!("Validating portfolio: {}", portfolio);
+
+    let document = TradeDocument::load(portfolio).map_err(|e| match e {
+        LoaderError::FileNotFound(path) => CliError::FileNotFound(path),
+        LoaderError::IoError(io) => CliError::Io(io),
+        other => CliError::Parse(other.to_string()),
+    })?;
+
+    validate(&document)
+}
+
+/// Validate a parsed document, summarising it when it is clean.
+pub fn validate(document: &TradeDocument) -> Result<Report> {
+    let errors = document.validate();
+    if !errors.is_empty() {
+        return Err(CliError::Validation(
+            errors.iter().map(ToString::to_string).collect(),
+        ));
+    }
+
+    let mut products: BTreeMap<&str, usize> = BTreeMap::new();
+    for trade in &document.trades {
+        let product = match trade.product {
+            ProductRecord::VanillaOption { .. } => "vanilla_option",
+            ProductRecord::Forward { .. } => "forward",
+            ProductRecord::Swap { .. } => "swap",
+        };
+        *products.entry(product).or_default() += 1;
+    }
+
+    let mut summary = ReportTable::new("validation", "Portfolio Validation", &["item", "value"]);
+    summary.push(vec![
+        "schema_version".into(),
+        (document.schema_version as usize).into(),
+    ]);
+    summary.push(vec![
+        "counterparties".into(),
+        document.counterparties.len().into(),
+    ]);
+    summary.push(vec![
+        "netting_sets".into(),
+        document.netting_sets.len().into(),
+    ]);
+    summary.push(vec!["trades".into(), document.trades.len().into()]);
+    for (product, count) in products {
+        summary.push(vec![format!("trades.{}", product).into(), count.into()]);
+    }
+    summary.push(vec!["status".into(), "valid".into()]);
+
+    Ok(Report::new().with_table(summary))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PORTFOLIO: &str = r#"{
+        "schema_version": 1,
+        "counterparties": [{ "id": "CP001", "hazard_rate": 0.02, "lgd": 0.6 }],
+        "netting_sets": [{ "id": "NS001", "counterparty_id": "CP001" }],
+        "trades": [{
+            "trade_id": "T001",
+            "counterparty_id": "CP001",
+            "netting_set_id": "NS001",
+            "currency": "USD",
+            "notional": 1000000.0,
+            "trade_date": "2026-01-15",
+            "maturity_date": "2027-01-15",
+            "product": { "type": "forward", "strike": 100.0, "direction": "long" }
+        }]
+    }"#;
+
+    #[test]
+    fn test_valid_portfolio_summary() {
+        let document = TradeDocument::from_json(PORTFOLIO).unwrap();
+        assert!(validate(&document).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_portfolio_lists_fields() {
+        let mut document = TradeDocument::from_json(PORTFOLIO).unwrap();
+        document.trades[0].notional = -5.0;
+        document.trades[0].trade_date = "15/01/2026".to_string();
+
+        let err = validate(&document).unwrap_err();
+        assert_eq!(err.exit_code(), 9);
+        match err {
+            CliError::Validation(errors) => {
+                assert_eq!(errors.len(), 2);
+                assert_eq!(errors[0], "trades[0].notional: must be positive, got -5");
+                assert!(errors[1].starts_with("trades[0].trade_date: invalid date"));
+            }
+            other => panic!("expected validation error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_missing_file() {
+        assert!(matches!(
+            run("/nonexistent/portfolio.json"),
+            Err(CliError::FileNotFound(_))
+        ));
+    }
+}
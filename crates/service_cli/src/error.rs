@@ -35,4 +35,85 @@ pub enum CliError {
     /// Parse error
     #[error("Parse error: {0}")]
     Parse(String),
+
+    /// Input failed field-level validation
+    #[error("Validation failed with {} error(s):\n  {}", .0.len(), .0.join("\n  "))]
+    Validation(Vec<String>),
+
+    /// Result store error
+    #[error("Store error: {0}")]
+    Store(#[from] infra_store::StoreError),
+}
+
+impl CliError {
+    /// Process exit code for this error class.
+    ///
+    /// Codes are stable so scripts can branch on the failure class:
+    ///
+    /// | Code | Class              |
+    /// |------|--------------------|
+    /// | 2    | Invalid argument   |
+    /// | 3    | File not found     |
+    /// | 4    | Parse error        |
+    /// | 5    | Configuration error|
+    /// | 6    | I/O error          |
+    /// | 7    | Pricing error      |
+    /// | 8    | Calibration error  |
+    /// | 9    | Validation error   |
+    /// | 10   | Store error        |
+    ///
+    /// Exit code 2 matches clap's own usage-error code.
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Self::InvalidArgument(_) => 2,
+            Self::FileNotFound(_) => 3,
+            Self::Parse(_) => 4,
+            Self::Config(_) => 5,
+            Self::Io(_) => 6,
+            Self::Pricing(_) => 7,
+            Self::Calibration(_) => 8,
+            Self::Validation(_) => 9,
+            Self::Store(_) => 10,
+        }
+    }
+
+    /// Short machine-readable name of the error class.
+    pub fn class(&self) -> &'static str {
+        match self {
+            Self::InvalidArgument(_) => "invalid_argument",
+            Self::FileNotFound(_) => "file_not_found",
+            Self::Parse(_) => "parse",
+            Self::Config(_) => "config",
+            Self::Io(_) => "io",
+            Self::Pricing(_) => "pricing",
+            Self::Calibration(_) => "calibration",
+            Self::Validation(_) => "validation",
+            Self::Store(_) => "store",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_codes_are_distinct() {
+        let errors = [
+            CliError::InvalidArgument(String::new()),
+            CliError::FileNotFound(String::new()),
+            CliError::Parse(String::new()),
+            CliError::Config(config::ConfigError::Frozen),
+            CliError::Io(std::io::Error::other("io")),
+            CliError::Pricing(String::new()),
+            CliError::Calibration(String::new()),
+            CliError::Validation(Vec::new()),
+            CliError::Store(infra_store::StoreError::NotFound(String::new())),
+        ];
+        let mut codes: Vec<u8> = errors.iter().map(CliError::exit_code).collect();
+        codes.sort();
+        codes.dedup();
+        assert_eq!(codes.len(), errors.len());
+        assert!(!codes.contains(&0) && !codes.contains(&1));
+    }
 }
@@ -4,24 +4,41 @@
 //!
 //! # Commands
 //!
+//! - `neutryx bootstrap --quotes <csv> --curve-config <toml>` - Bootstrap a curve set
 //! - `neutryx calibrate` - Calibrate model parameters from market data
 //! - `neutryx price --portfolio <file>` - Price a portfolio of trades
-//! - `neutryx report` - Generate risk reports
+//! - `neutryx report --portfolio <snapshot>` - Generate XVA, exposure, Greeks or cashflow
+//!   reports
+//! - `neutryx diff --left <snapshot> --right <snapshot>` - Reconcile portfolio snapshots
+//! - `neutryx validate --portfolio <json>` - Validate a portfolio against the trade schema
+//!
+//! # Output
+//!
+//! Every command produces a [`report::Report`], rendered with the global
+//! `--format` (`table`, `csv`, `json`, `ndjson`) and written to stdout or
+//! the global `--output` file. Logs go to stderr so stdout stays parseable.
+//! Failures exit with a per-class code (see [`CliError::exit_code`]).
 //!
 //! # Architecture
 //!
 //! As part of the **S**ervice layer in the A-I-P-S architecture, this crate
 //! orchestrates all other layers to provide a unified command-line interface.
 
+use std::path::PathBuf;
+use std::process::ExitCode;
+
 use clap::{Parser, Subcommand};
+use pricer_risk::reporting::ReportFormat;
 use tracing::info;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 mod commands;
 mod config;
 mod error;
+mod report;
 
 pub use error::{CliError, Result};
+use report::{OutputFormat, Report};
 
 /// Neutryx XVA Pricing Library CLI
 #[derive(Parser)]
@@ -36,12 +53,39 @@ struct Cli {
     #[arg(short, long, global = true, default_value = "neutryx.toml")]
     config: String,
 
+    /// Output format
+    #[arg(short, long, global = true, value_enum, default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
+
+    /// Write the report to this file instead of stdout
+    #[arg(short, long, global = true)]
+    output: Option<PathBuf>,
+
     #[command(subcommand)]
     command: Commands,
 }
 
 #[derive(Subcommand)]
 enum Commands {
+    /// Bootstrap a yield curve set from market quotes
+    Bootstrap {
+        /// Path to market quotes file (CSV)
+        #[arg(short, long)]
+        quotes: String,
+
+        /// Path to curve configuration file (TOML)
+        #[arg(long)]
+        curve_config: String,
+
+        /// Also write the bootstrapped curve set as nested JSON to this file
+        #[arg(long)]
+        curve_output: Option<String>,
+
+        /// Maximum acceptable repricing error in basis points
+        #[arg(long, default_value = "0.01")]
+        tolerance_bp: f64,
+    },
+
     /// Calibrate model parameters from market data
     Calibrate {
         /// Path to market data file
@@ -51,10 +95,6 @@ enum Commands {
         /// Model type to calibrate (e.g., hull-white, cir)
         #[arg(short = 't', long, default_value = "hull-white")]
         model_type: String,
-
-        /// Output file for calibrated parameters
-        #[arg(short, long)]
-        output: Option<String>,
     },
 
     /// Price a portfolio of trades
@@ -70,15 +110,11 @@ enum Commands {
         /// Number of Monte Carlo paths
         #[arg(short, long, default_value = "10000")]
         num_paths: usize,
-
-        /// Output format (json, csv, table)
-        #[arg(short, long, default_value = "table")]
-        format: String,
     },
 
     /// Generate risk reports
     Report {
-        /// Report type (xva, exposure, greeks)
+        /// Report type (xva, exposure, greeks, cashflows)
         #[arg(short = 't', long, default_value = "xva")]
         report_type: String,
 
@@ -87,8 +123,46 @@ enum Commands {
         portfolio: String,
 
         /// Output directory
-        #[arg(short, long, default_value = "./reports")]
+        #[arg(short = 'd', long, default_value = "./reports")]
         output_dir: String,
+
+        /// Valuation date (YYYY-MM-DD); defaults to the latest trade date
+        #[arg(long)]
+        as_of: Option<String>,
+
+        /// Comma-separated file formats (csv, json, xlsx, pdf); defaults to all
+        #[arg(long, value_delimiter = ',')]
+        formats: Vec<ReportFormat>,
+
+        /// Result store directory to also persist the report tables to (Parquet)
+        #[arg(long)]
+        store: Option<String>,
+
+        /// Run ID the stored tables are keyed by
+        #[arg(long, default_value = "eod", requires = "store")]
+        run_id: String,
+    },
+
+    /// Reconcile two portfolio snapshots
+    Diff {
+        /// Baseline snapshot (CSV, or Parquet with the `parquet` feature)
+        #[arg(short, long)]
+        left: String,
+
+        /// Comparison snapshot (CSV, or Parquet with the `parquet` feature)
+        #[arg(short, long)]
+        right: String,
+
+        /// Revalue the added, removed and amended trades
+        #[arg(long)]
+        revalue: bool,
+    },
+
+    /// Validate a portfolio file against the canonical JSON trade schema
+    Validate {
+        /// Portfolio file (JSON, trade schema version 1)
+        #[arg(short, long)]
+        portfolio: String,
     },
 
     /// Check system configuration and dependencies
@@ -98,10 +172,10 @@ enum Commands {
     Demo,
 }
 
-fn main() -> Result<()> {
-    // Initialise tracing
+fn main() -> ExitCode {
+    // Initialise tracing (stderr, so stdout carries only the report)
     tracing_subscriber::registry()
-        .with(tracing_subscriber::fmt::layer())
+        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
         .with(tracing_subscriber::EnvFilter::from_default_env())
         .init();
 
@@ -111,23 +185,72 @@ fn main() -> Result<()> {
         info!("Verbose mode enabled");
     }
 
-    match cli.command {
+    let format = cli.format;
+    let output = cli.output.clone();
+
+    match run(cli.command).and_then(|report| report.write(format, output.as_deref())) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            if format.is_structured() {
+                let payload = serde_json::json!({
+                    "error": { "class": err.class(), "message": err.to_string() }
+                });
+                eprintln!("{}", payload);
+            } else {
+                eprintln!("Error: {}", err);
+            }
+            ExitCode::from(err.exit_code())
+        }
+    }
+}
+
+fn run(command: Commands) -> Result<Report> {
+    match command {
+        Commands::Bootstrap {
+            quotes,
+            curve_config,
+            curve_output,
+            tolerance_bp,
+        } => commands::bootstrap::run(
+            &quotes,
+            &curve_config,
+            curve_output.as_deref(),
+            tolerance_bp,
+        ),
         Commands::Calibrate {
             market_data,
             model_type,
-            output,
-        } => commands::calibrate::run(&market_data, &model_type, output.as_deref()),
+        } => commands::calibrate::run(&market_data, &model_type),
         Commands::Price {
             portfolio,
             date,
             num_paths,
-            format,
-        } => commands::price::run(&portfolio, date.as_deref(), num_paths, &format),
+        } => commands::price::run(&portfolio, date.as_deref(), num_paths),
         Commands::Report {
             report_type,
             portfolio,
             output_dir,
-        } => commands::report::run(&report_type, &portfolio, &output_dir),
+            as_of,
+            formats,
+            store,
+            run_id,
+        } => commands::report::run(
+            &report_type,
+            &portfolio,
+            &output_dir,
+            as_of.as_deref(),
+            &formats,
+            store.as_deref().map(|root| commands::report::StoreTarget {
+                root,
+                run_id: &run_id,
+            }),
+        ),
+        Commands::Diff {
+            left,
+            right,
+            revalue,
+        } => commands::diff::run(&left, &right, revalue),
+        Commands::Validate { portfolio } => commands::validate::run(&portfolio),
         Commands::Check => commands::check::run(),
         Commands::Demo => commands::demo::run(),
     }
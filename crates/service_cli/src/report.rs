@@ -0,0 +1,441 @@
+//! Structured command output
+//!
+//! Every command returns a [`Report`]: a list of named tables that can be
+//! rendered as an aligned text table, CSV, JSON or NDJSON and written to
+//! stdout or a file. This keeps human-readable output and pipeline output
+//! in a single code path.
+//!
+//! # Formats
+//!
+//! - `table`: aligned columns with a title per table
+//! - `csv`: one header + rows block per table, blocks separated by a blank line,
+//!   numbers at full precision
+//! - `json`: a single object keyed by table name, each an array of row objects
+//! - `ndjson`: one JSON object per row with a `table` field
+
+use std::io::Write;
+use std::path::Path;
+
+use clap::ValueEnum;
+use serde_json::{Map, Value};
+
+use crate::{CliError, Result};
+
+/// Output format for command reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// Aligned text table
+    #[default]
+    Table,
+    /// Comma-separated values
+    Csv,
+    /// JSON document
+    Json,
+    /// Newline-delimited JSON (one row per line)
+    Ndjson,
+}
+
+impl OutputFormat {
+    /// Whether the format is intended for machine consumption.
+    pub fn is_structured(&self) -> bool {
+        matches!(self, Self::Json | Self::Ndjson)
+    }
+}
+
+/// A single report cell.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Cell {
+    /// Free text
+    Text(String),
+    /// Integer value
+    Integer(i64),
+    /// Floating-point value, rendered in tables with the given decimals
+    Number(f64, usize),
+    /// Boolean flag
+    Bool(bool),
+    /// Missing value
+    Empty,
+}
+
+impl Cell {
+    /// Floating-point cell with an explicit table precision.
+    pub fn number(value: f64, decimals: usize) -> Self {
+        Self::Number(value, decimals)
+    }
+
+    fn to_text(&self) -> String {
+        match self {
+            Self::Text(s) => s.clone(),
+            Self::Integer(i) => i.to_string(),
+            Self::Number(v, decimals) => format!("{:.*}", decimals, v),
+            Self::Bool(b) => b.to_string(),
+            Self::Empty => String::new(),
+        }
+    }
+
+    fn to_raw_text(&self) -> String {
+        match self {
+            Self::Number(v, _) => v.to_string(),
+            other => other.to_text(),
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        match self {
+            Self::Text(s) => Value::String(s.clone()),
+            Self::Integer(i) => Value::from(*i),
+            Self::Number(v, _) => serde_json::Number::from_f64(*v)
+                .map(Value::Number)
+                .unwrap_or(Value::Null),
+            Self::Bool(b) => Value::Bool(*b),
+            Self::Empty => Value::Null,
+        }
+    }
+
+    fn is_numeric(&self) -> bool {
+        matches!(self, Self::Integer(_) | Self::Number(..))
+    }
+}
+
+impl From<&str> for Cell {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl From<String> for Cell {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<f64> for Cell {
+    fn from(value: f64) -> Self {
+        Self::Number(value, 6)
+    }
+}
+
+impl From<usize> for Cell {
+    fn from(value: usize) -> Self {
+        Self::Integer(value as i64)
+    }
+}
+
+impl From<i64> for Cell {
+    fn from(value: i64) -> Self {
+        Self::Integer(value)
+    }
+}
+
+impl From<bool> for Cell {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl<T: Into<Cell>> From<Option<T>> for Cell {
+    fn from(value: Option<T>) -> Self {
+        value.map(Into::into).unwrap_or(Self::Empty)
+    }
+}
+
+/// A named table of rows.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportTable {
+    /// Machine-readable table name (used as JSON key)
+    pub name: String,
+    /// Human-readable title (used in table output)
+    pub title: String,
+    /// Column names
+    pub columns: Vec<String>,
+    /// Rows, each with one cell per column
+    pub rows: Vec<Vec<Cell>>,
+}
+
+impl ReportTable {
+    /// Create an empty table.
+    pub fn new(name: impl Into<String>, title: impl Into<String>, columns: &[&str]) -> Self {
+        Self {
+            name: name.into(),
+            title: title.into(),
+            columns: columns.iter().map(|c| c.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Append a row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the row length does not match the number of columns.
+    pub fn push(&mut self, row: Vec<Cell>) {
+        assert_eq!(
+            row.len(),
+            self.columns.len(),
+            "row length must match columns of table '{}'",
+            self.name
+        );
+        self.rows.push(row);
+    }
+
+    fn row_object(&self, row: &[Cell]) -> Map<String, Value> {
+        self.columns
+            .iter()
+            .zip(row)
+            .map(|(c, v)| (c.clone(), v.to_json()))
+            .collect()
+    }
+
+    fn render_table(&self, out: &mut String) {
+        let texts: Vec<Vec<String>> = self
+            .rows
+            .iter()
+            .map(|row| row.iter().map(Cell::to_text).collect())
+            .collect();
+        let widths: Vec<usize> = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                texts
+                    .iter()
+                    .map(|r| r[i].chars().count())
+                    .max()
+                    .unwrap_or(0)
+                    .max(c.chars().count())
+            })
+            .collect();
+        let total = widths.iter().sum::<usize>() + 2 * widths.len().saturating_sub(1);
+
+        out.push_str(&self.title);
+        out.push('\n');
+        out.push_str(&"=".repeat(self.title.chars().count()));
+        out.push('\n');
+
+        let header: Vec<String> = self
+            .columns
+            .iter()
+            .zip(&widths)
+            .map(|(c, w)| format!("{:<w$}", c, w = w))
+            .collect();
+        out.push_str(header.join("  ").trim_end());
+        out.push('\n');
+        out.push_str(&"-".repeat(total));
+        out.push('\n');
+
+        for (row, text) in self.rows.iter().zip(&texts) {
+            let line: Vec<String> = row
+                .iter()
+                .zip(text)
+                .zip(&widths)
+                .map(|((cell, t), w)| {
+                    if cell.is_numeric() {
+                        format!("{:>w$}", t, w = w)
+                    } else {
+                        format!("{:<w$}", t, w = w)
+                    }
+                })
+                .collect();
+            out.push_str(line.join("  ").trim_end());
+            out.push('\n');
+        }
+        if self.rows.is_empty() {
+            out.push_str("(no data)\n");
+        }
+    }
+
+    fn render_csv(&self, out: &mut String) {
+        let header: Vec<String> = self.columns.iter().map(|c| csv_escape(c)).collect();
+        out.push_str(&header.join(","));
+        out.push('\n');
+        for row in &self.rows {
+            let line: Vec<String> = row.iter().map(|c| csv_escape(&c.to_raw_text())).collect();
+            out.push_str(&line.join(","));
+            out.push('\n');
+        }
+    }
+}
+
+fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Structured output of a CLI command.
+///
+/// A report may carry a failure alongside its tables (e.g. diagnostics that
+/// breach a tolerance): the tables are still written, then the process exits
+/// with the failure's exit code.
+#[derive(Debug, Default)]
+pub struct Report {
+    /// Tables in display order
+    pub tables: Vec<ReportTable>,
+    /// Failure to report after the tables have been written
+    pub failure: Option<CliError>,
+}
+
+impl Report {
+    /// Create an empty report.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a table (builder style).
+    pub fn with_table(mut self, table: ReportTable) -> Self {
+        self.tables.push(table);
+        self
+    }
+
+    /// Mark the report as failed (builder style).
+    pub fn with_failure(mut self, failure: CliError) -> Self {
+        self.failure = Some(failure);
+        self
+    }
+
+    /// Find a table by name.
+    #[allow(dead_code)]
+    pub fn table(&self, name: &str) -> Option<&ReportTable> {
+        self.tables.iter().find(|t| t.name == name)
+    }
+
+    /// Render the report in the given format.
+    pub fn render(&self, format: OutputFormat) -> Result<String> {
+        let mut out = String::new();
+        match format {
+            OutputFormat::Table => {
+                for (i, table) in self.tables.iter().enumerate() {
+                    if i > 0 {
+                        out.push('\n');
+                    }
+                    table.render_table(&mut out);
+                }
+            }
+            OutputFormat::Csv => {
+                for (i, table) in self.tables.iter().enumerate() {
+                    if i > 0 {
+                        out.push('\n');
+                    }
+                    table.render_csv(&mut out);
+                }
+            }
+            OutputFormat::Json => {
+                let doc: Map<String, Value> = self
+                    .tables
+                    .iter()
+                    .map(|t| {
+                        let rows = t
+                            .rows
+                            .iter()
+                            .map(|r| Value::Object(t.row_object(r)))
+                            .collect();
+                        (t.name.clone(), Value::Array(rows))
+                    })
+                    .collect();
+                out = serde_json::to_string_pretty(&Value::Object(doc))
+                    .map_err(|e| CliError::Parse(format!("Failed to serialise report: {}", e)))?;
+                out.push('\n');
+            }
+            OutputFormat::Ndjson => {
+                for table in &self.tables {
+                    for row in &table.rows {
+                        let mut obj = Map::new();
+                        obj.insert("table".to_string(), Value::String(table.name.clone()));
+                        obj.extend(table.row_object(row));
+                        let line = serde_json::to_string(&Value::Object(obj)).map_err(|e| {
+                            CliError::Parse(format!("Failed to serialise report: {}", e))
+                        })?;
+                        out.push_str(&line);
+                        out.push('\n');
+                    }
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    /// Render the report and write it to `output`, or stdout if `None`.
+    ///
+    /// Returns the report's failure, if any, once the output is written.
+    pub fn write(self, format: OutputFormat, output: Option<&Path>) -> Result<()> {
+        let rendered = self.render(format)?;
+        match output {
+            Some(path) => std::fs::write(path, rendered)?,
+            None => {
+                let mut stdout = std::io::stdout().lock();
+                stdout.write_all(rendered.as_bytes())?;
+                stdout.flush()?;
+            }
+        }
+        match self.failure {
+            Some(failure) => Err(failure),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Report {
+        let mut table = ReportTable::new("trades", "Trades", &["trade_id", "pv", "live"]);
+        table.push(vec!["T1".into(), Cell::number(1.5, 2), true.into()]);
+        table.push(vec!["T,2".into(), Cell::Empty, false.into()]);
+        Report::new().with_table(table)
+    }
+
+    #[test]
+    fn test_render_table() {
+        let out = sample().render(OutputFormat::Table).unwrap();
+        assert!(out.starts_with("Trades\n======\n"));
+        assert!(out.contains("T1        1.50  true"));
+    }
+
+    #[test]
+    fn test_render_csv_escapes_fields() {
+        let out = sample().render(OutputFormat::Csv).unwrap();
+        assert_eq!(out, "trade_id,pv,live\nT1,1.5,true\n\"T,2\",,false\n");
+    }
+
+    #[test]
+    fn test_render_json() {
+        let out = sample().render(OutputFormat::Json).unwrap();
+        let value: Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(value["trades"][0]["pv"], 1.5);
+        assert_eq!(value["trades"][1]["pv"], Value::Null);
+    }
+
+    #[test]
+    fn test_render_ndjson() {
+        let out = sample().render(OutputFormat::Ndjson).unwrap();
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let first: Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(first["table"], "trades");
+        assert_eq!(first["trade_id"], "T1");
+    }
+
+    #[test]
+    fn test_write_to_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.csv");
+        sample().write(OutputFormat::Csv, Some(&path)).unwrap();
+        assert!(std::fs::read_to_string(path)
+            .unwrap()
+            .starts_with("trade_id"));
+    }
+
+    #[test]
+    fn test_write_returns_failure_after_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out.json");
+        let result = sample()
+            .with_failure(CliError::Calibration("tolerance".to_string()))
+            .write(OutputFormat::Json, Some(&path));
+        assert!(matches!(result, Err(CliError::Calibration(_))));
+        assert!(path.exists());
+    }
+}
@@ -1,6 +1,8 @@
 //! Server configuration
 
-use anyhow::Result;
+use std::collections::BTreeMap;
+
+use anyhow::{bail, Result};
 use serde::Deserialize;
 
 /// Server configuration
@@ -26,6 +28,82 @@ pub struct ServerConfig {
     /// Number of worker threads
     #[serde(default = "default_workers")]
     pub workers: usize,
+
+    /// API keys and their tenants; single-tenant without keys
+    #[serde(default)]
+    pub api_keys: BTreeMap<String, String>,
+
+    /// Seconds a response is replayed to requests with its idempotency key
+    #[serde(default = "default_idempotency_window")]
+    pub idempotency_window_secs: u64,
+
+    /// Idempotency keys held at most
+    #[serde(default = "default_idempotency_capacity")]
+    pub idempotency_capacity: usize,
+
+    /// Response encodings offered: `gzip`, `zstd`; none if empty
+    #[serde(default = "default_compression")]
+    pub compression: Vec<String>,
+
+    /// Smallest response body compressed, in bytes
+    #[serde(default = "default_compression_min_bytes")]
+    pub compression_min_bytes: u16,
+
+    /// Instrument types priced by both engines; dual-run off if empty
+    #[serde(default)]
+    pub verify_instruments: Vec<String>,
+
+    /// Monte Carlo paths of the verification engine
+    #[serde(default = "default_verify_paths")]
+    pub verify_paths: usize,
+
+    /// Price difference tolerated relative to the primary price
+    #[serde(default = "default_verify_tolerance")]
+    pub verify_tolerance: f64,
+
+    /// Price difference always tolerated
+    #[serde(default = "default_verify_absolute_tolerance")]
+    pub verify_absolute_tolerance: f64,
+
+    /// Calibration store file (JSON lines); in memory if unset
+    #[serde(default)]
+    pub calibration_store: Option<String>,
+
+    /// Relative parameter move between calibrations that raises an alert
+    #[serde(default = "default_drift_threshold")]
+    pub calibration_drift_threshold: f64,
+
+    /// Parquet result store directory; in memory if unset
+    #[serde(default)]
+    pub results_dir: Option<String>,
+
+    /// Serve the distributed worker service
+    #[serde(default)]
+    pub worker_enabled: bool,
+
+    /// Distributed worker address
+    #[serde(default = "default_worker_addr")]
+    pub worker_addr: String,
+
+    /// Worker endpoints this server coordinates
+    #[serde(default)]
+    pub distributed_workers: Vec<String>,
+
+    /// Netting sets per distributed shard
+    #[serde(default = "default_shard_size")]
+    pub distributed_shard_size: usize,
+
+    /// Dispatches of one shard before a distributed job fails
+    #[serde(default = "default_max_attempts")]
+    pub distributed_max_attempts: usize,
+
+    /// Deadline of one shard evaluation in seconds
+    #[serde(default = "default_shard_timeout")]
+    pub distributed_timeout_secs: u64,
+
+    /// Distributed jobs one tenant may run at a time
+    #[serde(default = "default_jobs_per_tenant")]
+    pub distributed_jobs_per_tenant: usize,
 }
 
 fn default_true() -> bool {
@@ -40,10 +118,62 @@ fn default_grpc_addr() -> String {
     "0.0.0.0:50051".to_string()
 }
 
+fn default_worker_addr() -> String {
+    "0.0.0.0:50052".to_string()
+}
+
+fn default_shard_size() -> usize {
+    64
+}
+
+fn default_max_attempts() -> usize {
+    3
+}
+
+fn default_shard_timeout() -> u64 {
+    300
+}
+
+fn default_jobs_per_tenant() -> usize {
+    4
+}
+
+fn default_idempotency_window() -> u64 {
+    24 * 60 * 60
+}
+
+fn default_idempotency_capacity() -> usize {
+    10_000
+}
+
+fn default_compression() -> Vec<String> {
+    vec!["gzip".to_string(), "zstd".to_string()]
+}
+
+fn default_compression_min_bytes() -> u16 {
+    1024
+}
+
+fn default_verify_paths() -> usize {
+    100_000
+}
+
+fn default_verify_tolerance() -> f64 {
+    0.01
+}
+
+fn default_verify_absolute_tolerance() -> f64 {
+    1e-4
+}
+
 fn default_workers() -> usize {
     num_cpus::get()
 }
 
+fn default_drift_threshold() -> f64 {
+    0.25
+}
+
 impl ServerConfig {
     /// Load configuration from environment variables
     pub fn from_env() -> Result<Self> {
@@ -63,16 +193,157 @@ impl ServerConfig {
             .map(|v| v.parse().unwrap_or_else(|_| default_workers()))
             .unwrap_or_else(|_| default_workers());
 
+        let api_keys = match std::env::var("NEUTRYX_API_KEYS") {
+            Ok(v) => parse_api_keys(&v)?,
+            Err(_) => BTreeMap::new(),
+        };
+
+        let idempotency_window_secs = std::env::var("NEUTRYX_IDEMPOTENCY_WINDOW_SECS")
+            .map(|v| v.parse().unwrap_or_else(|_| default_idempotency_window()))
+            .unwrap_or_else(|_| default_idempotency_window());
+
+        let idempotency_capacity = std::env::var("NEUTRYX_IDEMPOTENCY_CAPACITY")
+            .map(|v| v.parse().unwrap_or_else(|_| default_idempotency_capacity()))
+            .unwrap_or_else(|_| default_idempotency_capacity());
+
+        let compression = match std::env::var("NEUTRYX_COMPRESSION") {
+            Ok(v) => parse_compression(&v)?,
+            Err(_) => default_compression(),
+        };
+
+        let compression_min_bytes = std::env::var("NEUTRYX_COMPRESSION_MIN_BYTES")
+            .map(|v| {
+                v.parse()
+                    .unwrap_or_else(|_| default_compression_min_bytes())
+            })
+            .unwrap_or_else(|_| default_compression_min_bytes());
+
+        let verify_instruments = std::env::var("NEUTRYX_VERIFY_INSTRUMENTS")
+            .map(|v| {
+                v.split(',')
+                    .map(str::trim)
+                    .filter(|t| !t.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let verify_paths = std::env::var("NEUTRYX_VERIFY_PATHS")
+            .map(|v| v.parse().unwrap_or_else(|_| default_verify_paths()))
+            .unwrap_or_else(|_| default_verify_paths());
+
+        let verify_tolerance = std::env::var("NEUTRYX_VERIFY_TOLERANCE")
+            .map(|v| v.parse().unwrap_or_else(|_| default_verify_tolerance()))
+            .unwrap_or_else(|_| default_verify_tolerance());
+
+        let verify_absolute_tolerance = std::env::var("NEUTRYX_VERIFY_ABSOLUTE_TOLERANCE")
+            .map(|v| {
+                v.parse()
+                    .unwrap_or_else(|_| default_verify_absolute_tolerance())
+            })
+            .unwrap_or_else(|_| default_verify_absolute_tolerance());
+
+        let calibration_store = std::env::var("NEUTRYX_CALIBRATION_STORE").ok();
+
+        let calibration_drift_threshold = std::env::var("NEUTRYX_CALIBRATION_DRIFT_THRESHOLD")
+            .map(|v| v.parse().unwrap_or_else(|_| default_drift_threshold()))
+            .unwrap_or_else(|_| default_drift_threshold());
+
+        let results_dir = std::env::var("NEUTRYX_RESULTS_DIR").ok();
+
+        let worker_enabled = std::env::var("NEUTRYX_WORKER_ENABLED")
+            .map(|v| v.parse().unwrap_or(false))
+            .unwrap_or(false);
+
+        let worker_addr =
+            std::env::var("NEUTRYX_WORKER_ADDR").unwrap_or_else(|_| default_worker_addr());
+
+        let distributed_workers = std::env::var("NEUTRYX_DISTRIBUTED_WORKERS")
+            .map(|v| {
+                v.split(',')
+                    .map(str::trim)
+                    .filter(|w| !w.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let distributed_shard_size = std::env::var("NEUTRYX_DISTRIBUTED_SHARD_SIZE")
+            .map(|v| v.parse().unwrap_or_else(|_| default_shard_size()))
+            .unwrap_or_else(|_| default_shard_size());
+
+        let distributed_max_attempts = std::env::var("NEUTRYX_DISTRIBUTED_MAX_ATTEMPTS")
+            .map(|v| v.parse().unwrap_or_else(|_| default_max_attempts()))
+            .unwrap_or_else(|_| default_max_attempts());
+
+        let distributed_timeout_secs = std::env::var("NEUTRYX_DISTRIBUTED_TIMEOUT_SECS")
+            .map(|v| v.parse().unwrap_or_else(|_| default_shard_timeout()))
+            .unwrap_or_else(|_| default_shard_timeout());
+
+        let distributed_jobs_per_tenant = std::env::var("NEUTRYX_DISTRIBUTED_JOBS_PER_TENANT")
+            .map(|v| v.parse().unwrap_or_else(|_| default_jobs_per_tenant()))
+            .unwrap_or_else(|_| default_jobs_per_tenant());
+
         Ok(Self {
             rest_enabled,
             rest_addr,
             grpc_enabled,
             grpc_addr,
             workers,
+            api_keys,
+            idempotency_window_secs,
+            idempotency_capacity,
+            compression,
+            compression_min_bytes,
+            verify_instruments,
+            verify_paths,
+            verify_tolerance,
+            verify_absolute_tolerance,
+            calibration_store,
+            calibration_drift_threshold,
+            results_dir,
+            worker_enabled,
+            worker_addr,
+            distributed_workers,
+            distributed_shard_size,
+            distributed_max_attempts,
+            distributed_timeout_secs,
+            distributed_jobs_per_tenant,
         })
     }
 }
 
+/// Parse comma-separated response encodings; `none` disables compression
+fn parse_compression(spec: &str) -> Result<Vec<String>> {
+    let mut encodings = Vec::new();
+    for encoding in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        match encoding.to_ascii_lowercase().as_str() {
+            "none" => return Ok(Vec::new()),
+            e @ ("gzip" | "zstd") => encodings.push(e.to_string()),
+            other => bail!("NEUTRYX_COMPRESSION: unsupported encoding {}", other),
+        }
+    }
+    Ok(encodings)
+}
+
+/// Parse `key=tenant` pairs separated by commas
+fn parse_api_keys(spec: &str) -> Result<BTreeMap<String, String>> {
+    let mut keys = BTreeMap::new();
+    for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        let Some((key, tenant)) = entry.split_once('=') else {
+            bail!("NEUTRYX_API_KEYS entry without a tenant: expected key=tenant");
+        };
+        let (key, tenant) = (key.trim(), tenant.trim());
+        if key.is_empty() || tenant.is_empty() {
+            bail!("NEUTRYX_API_KEYS entry with an empty key or tenant");
+        }
+        if keys.insert(key.to_string(), tenant.to_string()).is_some() {
+            bail!("NEUTRYX_API_KEYS lists a key more than once");
+        }
+    }
+    Ok(keys)
+}
+
 impl Default for ServerConfig {
     fn default() -> Self {
         Self {
@@ -81,6 +352,52 @@ impl Default for ServerConfig {
             grpc_enabled: false,
             grpc_addr: default_grpc_addr(),
             workers: default_workers(),
+            api_keys: BTreeMap::new(),
+            idempotency_window_secs: default_idempotency_window(),
+            idempotency_capacity: default_idempotency_capacity(),
+            compression: default_compression(),
+            compression_min_bytes: default_compression_min_bytes(),
+            verify_instruments: Vec::new(),
+            verify_paths: default_verify_paths(),
+            verify_tolerance: default_verify_tolerance(),
+            verify_absolute_tolerance: default_verify_absolute_tolerance(),
+            calibration_store: None,
+            calibration_drift_threshold: default_drift_threshold(),
+            results_dir: None,
+            worker_enabled: false,
+            worker_addr: default_worker_addr(),
+            distributed_workers: Vec::new(),
+            distributed_shard_size: default_shard_size(),
+            distributed_max_attempts: default_max_attempts(),
+            distributed_timeout_secs: default_shard_timeout(),
+            distributed_jobs_per_tenant: default_jobs_per_tenant(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_api_keys() {
+        let keys = parse_api_keys(" k1=rates, k2=fx ,k3=rates,").unwrap();
+        assert_eq!(keys.len(), 3);
+        assert_eq!(keys["k2"], "fx");
+        assert_eq!(keys["k3"], "rates");
+
+        assert!(parse_api_keys("k1").is_err());
+        assert!(parse_api_keys("=rates").is_err());
+        assert!(parse_api_keys("k1=rates,k1=fx").is_err());
+    }
+
+    #[test]
+    fn test_parse_compression() {
+        assert_eq!(
+            parse_compression("ZSTD, gzip").unwrap(),
+            vec!["zstd", "gzip"]
+        );
+        assert!(parse_compression("none").unwrap().is_empty());
+        assert!(parse_compression("br").is_err());
+    }
+}
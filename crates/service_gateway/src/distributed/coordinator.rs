@@ -0,0 +1,597 @@
+//! Coordinator side: shards netting sets across workers and aggregates
+//!
+//! Shards sit in a shared queue that every healthy worker drains. When a
+//! worker fails (unreachable, timed out, crashed mid-shard) the shard goes
+//! back on the queue for the remaining workers and the failed worker is
+//! dropped for the rest of the job. A shard a worker rejects as invalid
+//! fails the job at once, since every worker would reject it.
+//!
+//! Every shard carries its tenant and job ID, which the worker echoes back;
+//! a response for any other job is treated as a worker failure, so results
+//! never cross tenants. Each tenant may run a limited number of jobs at a
+//! time so that one desk cannot take all the workers.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use pricer_risk::exposure::ExposureCalculator;
+use serde::{Deserialize, Serialize};
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::transport::{Channel, Endpoint};
+use tonic::{Code, Request, Status};
+use tracing::{info, warn};
+
+use super::messages::{
+    MarketSpec, NettingSetResult, NettingSetSpec, OwnCredit, ShardRequest, ShardResponse,
+};
+use super::worker::EVALUATE_SHARD_PATH;
+use super::DistributedError;
+use crate::tenant::TenantId;
+
+/// Default number of netting sets per shard
+pub const DEFAULT_SHARD_SIZE: usize = 64;
+
+/// Default number of jobs one tenant may run at a time
+pub const DEFAULT_JOBS_PER_TENANT: usize = 4;
+
+/// Exposure and XVA job over a portfolio of netting sets
+#[derive(Clone, Debug, Deserialize)]
+pub struct ExposureJob {
+    pub market: MarketSpec,
+    pub time_grid: Vec<f64>,
+    /// Monte Carlo paths (default 5,000)
+    pub num_paths: Option<u32>,
+    pub seed: Option<u64>,
+    /// PFE confidence level (default 0.95)
+    pub confidence: Option<f64>,
+    /// Own credit; DVA is zero without it
+    pub own_credit: Option<OwnCredit>,
+    pub netting_sets: Vec<NettingSetSpec>,
+}
+
+/// Aggregated results of a distributed job
+#[derive(Clone, Debug, Serialize)]
+pub struct DistributedExposure {
+    /// Results of each netting set, in job order
+    pub netting_sets: Vec<NettingSetResult>,
+    /// Sum of the netting set EE profiles
+    pub ee: Vec<f64>,
+    pub epe: f64,
+    pub cva: f64,
+    pub dva: f64,
+    pub shards: usize,
+    /// Shards re-dispatched after a worker failure
+    pub redispatched: usize,
+    /// Workers dropped during the job
+    pub failed_workers: Vec<String>,
+}
+
+/// Coordinator configuration
+#[derive(Clone, Debug)]
+pub struct CoordinatorConfig {
+    /// Worker endpoints, e.g. `http://10.0.0.5:50052`
+    pub workers: Vec<String>,
+    /// Netting sets per shard
+    pub shard_size: usize,
+    /// Dispatches of one shard before the job fails
+    pub max_attempts: usize,
+    /// Deadline of one shard evaluation
+    pub timeout: Duration,
+    /// Jobs one tenant may run at a time
+    pub jobs_per_tenant: usize,
+}
+
+impl CoordinatorConfig {
+    /// Default configuration over the given workers
+    pub fn new(workers: Vec<String>) -> Self {
+        Self {
+            workers,
+            shard_size: DEFAULT_SHARD_SIZE,
+            max_attempts: 3,
+            timeout: Duration::from_secs(300),
+            jobs_per_tenant: DEFAULT_JOBS_PER_TENANT,
+        }
+    }
+
+    /// Set the number of netting sets per shard
+    pub fn with_shard_size(mut self, shard_size: usize) -> Self {
+        self.shard_size = shard_size.max(1);
+        self
+    }
+
+    /// Set the number of dispatches of one shard before the job fails
+    pub fn with_max_attempts(mut self, max_attempts: usize) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Set the deadline of one shard evaluation
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set the number of jobs one tenant may run at a time
+    pub fn with_jobs_per_tenant(mut self, jobs_per_tenant: usize) -> Self {
+        self.jobs_per_tenant = jobs_per_tenant.max(1);
+        self
+    }
+}
+
+/// Shards jobs to workers over gRPC and aggregates their results
+pub struct Coordinator {
+    workers: Vec<(String, Endpoint)>,
+    config: CoordinatorConfig,
+    next_job: AtomicU64,
+    /// Running jobs by tenant
+    running: Mutex<HashMap<TenantId, usize>>,
+}
+
+/// A tenant's claim on one of its job slots, released on drop
+struct JobSlot<'a> {
+    running: &'a Mutex<HashMap<TenantId, usize>>,
+    tenant: &'a TenantId,
+}
+
+impl Drop for JobSlot<'_> {
+    fn drop(&mut self) {
+        let mut running = self.running.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(jobs) = running.get_mut(self.tenant) {
+            *jobs -= 1;
+            if *jobs == 0 {
+                running.remove(self.tenant);
+            }
+        }
+    }
+}
+
+/// Shard waiting for a worker
+struct Pending {
+    shard: ShardRequest,
+    attempts: usize,
+}
+
+/// How a worker finished draining the queue
+enum Drained {
+    Done,
+    Failed { worker: String, message: String },
+    Fatal(DistributedError),
+}
+
+impl Coordinator {
+    /// Create a coordinator over the configured workers
+    pub fn new(config: CoordinatorConfig) -> Result<Self, DistributedError> {
+        if config.workers.is_empty() {
+            return Err(DistributedError::InvalidJob(
+                "at least one worker is required".to_string(),
+            ));
+        }
+        let workers = config
+            .workers
+            .iter()
+            .map(|worker| {
+                let endpoint = Endpoint::from_shared(worker.clone())
+                    .map_err(|e| DistributedError::InvalidEndpoint {
+                        endpoint: worker.clone(),
+                        message: e.to_string(),
+                    })?
+                    .connect_timeout(config.timeout)
+                    .timeout(config.timeout);
+                Ok((worker.clone(), endpoint))
+            })
+            .collect::<Result<_, DistributedError>>()?;
+        Ok(Self {
+            workers,
+            config,
+            next_job: AtomicU64::new(1),
+            running: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Claim a job slot of the tenant
+    fn claim<'a>(&'a self, tenant: &'a TenantId) -> Result<JobSlot<'a>, DistributedError> {
+        let mut running = self.running.lock().unwrap_or_else(|e| e.into_inner());
+        let jobs = running.entry(tenant.clone()).or_insert(0);
+        if *jobs >= self.config.jobs_per_tenant {
+            return Err(DistributedError::TenantBusy {
+                tenant: tenant.to_string(),
+                running: *jobs,
+            });
+        }
+        *jobs += 1;
+        Ok(JobSlot {
+            running: &self.running,
+            tenant,
+        })
+    }
+
+    /// Run a tenant's job across the workers
+    pub async fn run(
+        &self,
+        tenant: &TenantId,
+        job: ExposureJob,
+    ) -> Result<DistributedExposure, DistributedError> {
+        if job.netting_sets.is_empty() {
+            return Err(DistributedError::InvalidJob(
+                "no netting sets to evaluate".to_string(),
+            ));
+        }
+        let _slot = self.claim(tenant)?;
+        let job_id = self.next_job.fetch_add(1, Ordering::Relaxed);
+        let queue: VecDeque<Pending> = job
+            .netting_sets
+            .chunks(self.config.shard_size)
+            .enumerate()
+            .map(|(i, netting_sets)| Pending {
+                shard: ShardRequest {
+                    tenant: tenant.to_string(),
+                    job_id,
+                    shard_id: i as u32,
+                    market: Some(job.market),
+                    time_grid: job.time_grid.clone(),
+                    num_paths: job.num_paths.unwrap_or(5_000),
+                    seed: job.seed.unwrap_or(42),
+                    confidence: job.confidence.unwrap_or(0.95),
+                    own_credit: job.own_credit,
+                    netting_sets: netting_sets.to_vec(),
+                },
+                attempts: 0,
+            })
+            .collect();
+        let shards = queue.len();
+        info!(
+            "Job {} of tenant {}: {} netting sets in {} shards over {} workers",
+            job_id,
+            tenant,
+            job.netting_sets.len(),
+            shards,
+            self.workers.len()
+        );
+
+        let queue = Arc::new(Mutex::new(queue));
+        let results = Arc::new(Mutex::new(Vec::with_capacity(shards)));
+        let mut healthy = self.workers.clone();
+        let mut failed_workers = Vec::new();
+        let mut redispatched = 0;
+
+        // A worker that fails requeues its shard; workers that already found
+        // the queue empty pick it up in the next round
+        loop {
+            let mut round = tokio::task::JoinSet::new();
+            for (worker, endpoint) in &healthy {
+                round.spawn(drain(
+                    worker.clone(),
+                    endpoint.connect_lazy(),
+                    Arc::clone(&queue),
+                    Arc::clone(&results),
+                    self.config.max_attempts,
+                ));
+            }
+            while let Some(drained) = round.join_next().await {
+                match drained.unwrap_or_else(|e| std::panic::resume_unwind(e.into_panic())) {
+                    Drained::Done => {}
+                    Drained::Failed { worker, message } => {
+                        warn!("Worker {} failed, re-dispatching: {}", worker, message);
+                        healthy.retain(|(w, _)| *w != worker);
+                        failed_workers.push(worker);
+                        redispatched += 1;
+                    }
+                    Drained::Fatal(error) => return Err(error),
+                }
+            }
+
+            let pending = queue.lock().unwrap_or_else(|e| e.into_inner()).len();
+            if pending == 0 {
+                break;
+            }
+            if healthy.is_empty() {
+                return Err(DistributedError::NoHealthyWorkers { pending });
+            }
+        }
+
+        let mut results = std::mem::take(&mut *results.lock().unwrap_or_else(|e| e.into_inner()));
+        results.sort_by_key(|r| r.shard_id);
+        Ok(aggregate(
+            &job.time_grid,
+            results,
+            shards,
+            redispatched,
+            failed_workers,
+        ))
+    }
+}
+
+/// Evaluate shards from the queue on one worker until it is empty or the
+/// worker fails
+async fn drain(
+    worker: String,
+    channel: Channel,
+    queue: Arc<Mutex<VecDeque<Pending>>>,
+    results: Arc<Mutex<Vec<ShardResponse>>>,
+    max_attempts: usize,
+) -> Drained {
+    loop {
+        let next = queue.lock().unwrap_or_else(|e| e.into_inner()).pop_front();
+        let Some(mut pending) = next else {
+            return Drained::Done;
+        };
+        let shard_id = pending.shard.shard_id;
+        let evaluated = evaluate(channel.clone(), pending.shard.clone())
+            .await
+            .and_then(|response| owned_by(&pending.shard, response));
+        match evaluated {
+            Ok(response) => results
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(response),
+            Err(status) if status.code() == Code::InvalidArgument => {
+                return Drained::Fatal(DistributedError::Rejected {
+                    worker,
+                    shard: shard_id,
+                    message: status.message().to_string(),
+                });
+            }
+            Err(status) => {
+                pending.attempts += 1;
+                if pending.attempts >= max_attempts {
+                    return Drained::Fatal(DistributedError::ShardFailed {
+                        shard: shard_id,
+                        attempts: pending.attempts,
+                        message: status.message().to_string(),
+                    });
+                }
+                queue
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .push_back(pending);
+                return Drained::Failed {
+                    worker,
+                    message: status.message().to_string(),
+                };
+            }
+        }
+    }
+}
+
+async fn evaluate(channel: Channel, shard: ShardRequest) -> Result<ShardResponse, Status> {
+    let mut grpc = tonic::client::Grpc::new(channel);
+    grpc.ready()
+        .await
+        .map_err(|e| Status::unavailable(e.to_string()))?;
+    grpc.unary(
+        Request::new(shard),
+        PathAndQuery::from_static(EVALUATE_SHARD_PATH),
+        tonic::codec::ProstCodec::default(),
+    )
+    .await
+    .map(tonic::Response::into_inner)
+}
+
+/// Accept only the response to the shard's own tenant and job
+fn owned_by(shard: &ShardRequest, response: ShardResponse) -> Result<ShardResponse, Status> {
+    if response.tenant != shard.tenant
+        || response.job_id != shard.job_id
+        || response.shard_id != shard.shard_id
+    {
+        return Err(Status::internal(format!(
+            "response for job {} shard {} of another tenant or job",
+            response.job_id, response.shard_id
+        )));
+    }
+    Ok(response)
+}
+
+/// Sum the netting set results into portfolio totals
+fn aggregate(
+    time_grid: &[f64],
+    results: Vec<ShardResponse>,
+    shards: usize,
+    redispatched: usize,
+    failed_workers: Vec<String>,
+) -> DistributedExposure {
+    let netting_sets: Vec<NettingSetResult> =
+        results.into_iter().flat_map(|r| r.netting_sets).collect();
+    let mut ee = vec![0.0; time_grid.len()];
+    for ns in &netting_sets {
+        for (total, value) in ee.iter_mut().zip(&ns.ee) {
+            *total += value;
+        }
+    }
+    DistributedExposure {
+        epe: ExposureCalculator::expected_positive_exposure(&ee, time_grid),
+        cva: netting_sets.iter().map(|ns| ns.cva).sum(),
+        dva: netting_sets.iter().map(|ns| ns.dva).sum(),
+        ee,
+        netting_sets,
+        shards,
+        redispatched,
+        failed_workers,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distributed::messages::TradeSpec;
+    use crate::distributed::worker::{evaluate_shard, WorkerServer};
+    use tonic::transport::server::TcpIncoming;
+    use tonic::transport::Server;
+
+    async fn spawn_worker() -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let incoming = TcpIncoming::from_listener(listener, true, None).unwrap();
+        tokio::spawn(
+            Server::builder()
+                .add_service(WorkerServer)
+                .serve_with_incoming(incoming),
+        );
+        format!("http://{}", addr)
+    }
+
+    /// Endpoint nothing listens on
+    async fn dead_worker() -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        format!("http://{}", listener.local_addr().unwrap())
+    }
+
+    fn tenant(id: &str) -> TenantId {
+        TenantId::new(id).unwrap()
+    }
+
+    fn job(netting_sets: usize) -> ExposureJob {
+        ExposureJob {
+            market: MarketSpec {
+                spot: 100.0,
+                volatility: 0.2,
+                rate: 0.03,
+            },
+            time_grid: vec![0.25, 0.5, 0.75, 1.0],
+            num_paths: Some(500),
+            seed: Some(7),
+            confidence: None,
+            own_credit: Some(OwnCredit {
+                hazard_rate: 0.01,
+                lgd: 0.6,
+            }),
+            netting_sets: (0..netting_sets)
+                .map(|n| NettingSetSpec {
+                    id: format!("NS{}", n),
+                    hazard_rate: 0.02,
+                    lgd: 0.6,
+                    trades: vec![
+                        TradeSpec {
+                            id: format!("T{}-1", n),
+                            instrument_type: "european_option".to_string(),
+                            strike: 90.0 + n as f64 * 5.0,
+                            expiry: 1.0,
+                            is_call: true,
+                            quantity: 10.0,
+                        },
+                        TradeSpec {
+                            id: format!("T{}-2", n),
+                            instrument_type: "forward".to_string(),
+                            strike: 100.0,
+                            expiry: 0.8,
+                            is_call: true,
+                            quantity: -5.0,
+                        },
+                    ],
+                })
+                .collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_redispatches_shards_of_failed_worker() {
+        let workers = vec![
+            dead_worker().await,
+            spawn_worker().await,
+            spawn_worker().await,
+        ];
+        let coordinator = Coordinator::new(
+            CoordinatorConfig::new(workers.clone())
+                .with_shard_size(2)
+                .with_timeout(Duration::from_secs(10)),
+        )
+        .unwrap();
+
+        let result = coordinator.run(&tenant("rates"), job(5)).await.unwrap();
+        assert_eq!(result.shards, 3);
+        assert_eq!(result.redispatched, 1);
+        assert_eq!(result.failed_workers, vec![workers[0].clone()]);
+
+        // Same netting set results as evaluating the whole job in one shard
+        let local = evaluate_shard(&ShardRequest {
+            market: Some(job(5).market),
+            time_grid: job(5).time_grid,
+            num_paths: 500,
+            seed: 7,
+            confidence: 0.95,
+            own_credit: job(5).own_credit,
+            netting_sets: job(5).netting_sets,
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(result.netting_sets, local.netting_sets);
+        let cva: f64 = local.netting_sets.iter().map(|ns| ns.cva).sum();
+        assert!((result.cva - cva).abs() < 1e-12);
+        assert!(result.cva > 0.0 && result.dva > 0.0);
+        assert!(
+            (result.ee[0] - local.netting_sets.iter().map(|ns| ns.ee[0]).sum::<f64>()).abs() < 1e-9
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invalid_shard_fails_without_retry() {
+        let coordinator =
+            Coordinator::new(CoordinatorConfig::new(vec![spawn_worker().await])).unwrap();
+        let mut job = job(1);
+        job.netting_sets[0].trades[0].instrument_type = "swaption".to_string();
+
+        let error = coordinator.run(&tenant("rates"), job).await.unwrap_err();
+        assert!(matches!(error, DistributedError::Rejected { shard: 0, .. }));
+    }
+
+    #[tokio::test]
+    async fn test_fails_when_no_worker_is_left() {
+        let coordinator =
+            Coordinator::new(CoordinatorConfig::new(vec![dead_worker().await]).with_shard_size(1))
+                .unwrap();
+
+        let error = coordinator.run(&tenant("rates"), job(3)).await.unwrap_err();
+        assert!(matches!(
+            error,
+            DistributedError::NoHealthyWorkers { pending: 3 }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_jobs_are_limited_per_tenant() {
+        let coordinator = Coordinator::new(
+            CoordinatorConfig::new(vec![spawn_worker().await]).with_jobs_per_tenant(1),
+        )
+        .unwrap();
+        let rates = tenant("rates");
+
+        let slot = coordinator.claim(&rates).unwrap();
+        let error = coordinator.run(&rates, job(1)).await.unwrap_err();
+        assert!(matches!(
+            error,
+            DistributedError::TenantBusy { running: 1, .. }
+        ));
+        // Other tenants are unaffected
+        let result = coordinator.run(&tenant("fx"), job(1)).await.unwrap();
+        assert_eq!(result.netting_sets.len(), 1);
+
+        drop(slot);
+        assert!(coordinator.run(&rates, job(1)).await.is_ok());
+    }
+
+    #[test]
+    fn test_rejects_response_of_another_tenant() {
+        let shard = ShardRequest {
+            tenant: "rates".to_string(),
+            job_id: 3,
+            shard_id: 1,
+            ..Default::default()
+        };
+        let response = ShardResponse {
+            tenant: "rates".to_string(),
+            job_id: 3,
+            shard_id: 1,
+            netting_sets: Vec::new(),
+        };
+        assert!(owned_by(&shard, response.clone()).is_ok());
+        let foreign = ShardResponse {
+            tenant: "fx".to_string(),
+            ..response
+        };
+        assert_eq!(
+            owned_by(&shard, foreign).unwrap_err().code(),
+            Code::Internal
+        );
+    }
+}
@@ -0,0 +1,125 @@
+//! Wire messages of the `neutryx.distributed.v1.Worker` service
+//!
+//! Defined directly with prost derives rather than generated from a
+//! `.proto` file; the field tags below are the wire contract between
+//! coordinator and workers. The same types deserialise from the JSON body of
+//! the coordinator's REST endpoint.
+
+use serde::{Deserialize, Serialize};
+
+/// Market state shared by every netting set of a job
+#[derive(Clone, Copy, PartialEq, prost::Message, Serialize, Deserialize)]
+pub struct MarketSpec {
+    #[prost(double, tag = "1")]
+    pub spot: f64,
+    #[prost(double, tag = "2")]
+    pub volatility: f64,
+    #[prost(double, tag = "3")]
+    pub rate: f64,
+}
+
+/// Own credit for DVA
+#[derive(Clone, Copy, PartialEq, prost::Message, Serialize, Deserialize)]
+pub struct OwnCredit {
+    #[prost(double, tag = "1")]
+    pub hazard_rate: f64,
+    #[prost(double, tag = "2")]
+    pub lgd: f64,
+}
+
+/// Trade in a netting set
+#[derive(Clone, PartialEq, prost::Message, Serialize, Deserialize)]
+pub struct TradeSpec {
+    #[prost(string, tag = "1")]
+    pub id: String,
+    /// "european_option", "vanilla_option" or "forward"
+    #[prost(string, tag = "2")]
+    pub instrument_type: String,
+    #[prost(double, tag = "3")]
+    pub strike: f64,
+    #[prost(double, tag = "4")]
+    pub expiry: f64,
+    #[prost(bool, tag = "5")]
+    #[serde(default = "default_true")]
+    pub is_call: bool,
+    /// Units of the underlying, negative when sold
+    #[prost(double, tag = "6")]
+    pub quantity: f64,
+}
+
+/// Netting set with its counterparty credit
+#[derive(Clone, PartialEq, prost::Message, Serialize, Deserialize)]
+pub struct NettingSetSpec {
+    #[prost(string, tag = "1")]
+    pub id: String,
+    #[prost(double, tag = "2")]
+    pub hazard_rate: f64,
+    #[prost(double, tag = "3")]
+    pub lgd: f64,
+    #[prost(message, repeated, tag = "4")]
+    pub trades: Vec<TradeSpec>,
+}
+
+/// Netting sets dispatched to one worker
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ShardRequest {
+    #[prost(uint64, tag = "1")]
+    pub job_id: u64,
+    #[prost(uint32, tag = "2")]
+    pub shard_id: u32,
+    #[prost(message, optional, tag = "3")]
+    pub market: Option<MarketSpec>,
+    #[prost(double, repeated, tag = "4")]
+    pub time_grid: Vec<f64>,
+    #[prost(uint32, tag = "5")]
+    pub num_paths: u32,
+    #[prost(uint64, tag = "6")]
+    pub seed: u64,
+    /// PFE confidence level
+    #[prost(double, tag = "7")]
+    pub confidence: f64,
+    #[prost(message, optional, tag = "8")]
+    pub own_credit: Option<OwnCredit>,
+    #[prost(message, repeated, tag = "9")]
+    pub netting_sets: Vec<NettingSetSpec>,
+    /// Tenant that submitted the job
+    #[prost(string, tag = "10")]
+    pub tenant: String,
+}
+
+/// Exposure profile and XVA of one netting set
+#[derive(Clone, PartialEq, prost::Message, Serialize, Deserialize)]
+pub struct NettingSetResult {
+    #[prost(string, tag = "1")]
+    pub id: String,
+    #[prost(double, repeated, tag = "2")]
+    pub ee: Vec<f64>,
+    #[prost(double, repeated, tag = "3")]
+    pub ene: Vec<f64>,
+    #[prost(double, repeated, tag = "4")]
+    pub pfe: Vec<f64>,
+    #[prost(double, tag = "5")]
+    pub epe: f64,
+    #[prost(double, tag = "6")]
+    pub cva: f64,
+    #[prost(double, tag = "7")]
+    pub dva: f64,
+}
+
+/// Results of a shard
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ShardResponse {
+    #[prost(uint64, tag = "1")]
+    pub job_id: u64,
+    #[prost(uint32, tag = "2")]
+    pub shard_id: u32,
+    #[prost(message, repeated, tag = "3")]
+    pub netting_sets: Vec<NettingSetResult>,
+    /// Tenant of the shard, echoed back
+    #[prost(string, tag = "4")]
+    pub tenant: String,
+}
+
+fn default_true() -> bool {
+    true
+}
@@ -0,0 +1,103 @@
+//! Distributed exposure and XVA across worker nodes
+//!
+//! A [`Coordinator`] splits a portfolio's netting sets into shards and
+//! dispatches them over gRPC to worker processes serving
+//! `neutryx.distributed.v1.Worker` ([`WorkerServer`]). Each worker returns
+//! the EE/ENE/PFE profiles, EPE, CVA and DVA of its netting sets, and the
+//! coordinator aggregates them into portfolio totals. Shards of a worker
+//! that fails are re-dispatched to the remaining workers.
+//!
+//! Netting sets are independent given the market paths, and every worker
+//! simulates the same paths from the job's seed, so results do not depend
+//! on the number of workers or on how the portfolio was sharded.
+//!
+//! # Deployment
+//!
+//! - Workers: `NEUTRYX_WORKER_ENABLED=true`, listening on
+//!   `NEUTRYX_WORKER_ADDR` (default `0.0.0.0:50052`)
+//! - Coordinator: `NEUTRYX_DISTRIBUTED_WORKERS=http://host1:50052,http://host2:50052`,
+//!   exposing `POST /api/v1/exposure/distributed`; shard size, dispatch
+//!   attempts and shard timeout from `NEUTRYX_DISTRIBUTED_SHARD_SIZE` (64),
+//!   `NEUTRYX_DISTRIBUTED_MAX_ATTEMPTS` (3) and
+//!   `NEUTRYX_DISTRIBUTED_TIMEOUT_SECS` (300); each tenant may run
+//!   `NEUTRYX_DISTRIBUTED_JOBS_PER_TENANT` (4) jobs at a time
+
+use thiserror::Error;
+
+use crate::error::ServerError;
+use crate::tenant::TenantId;
+
+mod coordinator;
+mod messages;
+mod worker;
+
+pub use coordinator::{Coordinator, CoordinatorConfig, DistributedExposure, ExposureJob};
+pub use worker::WorkerServer;
+
+/// Distributed job errors
+#[derive(Error, Debug)]
+pub enum DistributedError {
+    /// Job or coordinator configuration is invalid
+    #[error("Invalid job: {0}")]
+    InvalidJob(String),
+
+    /// Worker endpoint is not a valid URI
+    #[error("Invalid worker endpoint {endpoint}: {message}")]
+    InvalidEndpoint { endpoint: String, message: String },
+
+    /// Worker rejected a shard as invalid
+    #[error("Worker {worker} rejected shard {shard}: {message}")]
+    Rejected {
+        worker: String,
+        shard: u32,
+        message: String,
+    },
+
+    /// Shard failed on every dispatch
+    #[error("Shard {shard} failed after {attempts} attempts: {message}")]
+    ShardFailed {
+        shard: u32,
+        attempts: usize,
+        message: String,
+    },
+
+    /// Every worker failed with shards still pending
+    #[error("No healthy workers left with {pending} shards pending")]
+    NoHealthyWorkers { pending: usize },
+
+    /// Tenant already runs as many jobs as it may
+    #[error("Tenant {tenant} already runs {running} distributed jobs")]
+    TenantBusy { tenant: String, running: usize },
+}
+
+impl From<DistributedError> for ServerError {
+    fn from(error: DistributedError) -> Self {
+        match error {
+            DistributedError::InvalidJob(_) | DistributedError::Rejected { .. } => {
+                ServerError::InvalidRequest(error.to_string())
+            }
+            DistributedError::InvalidEndpoint { .. } => ServerError::Internal(error.to_string()),
+            DistributedError::ShardFailed { .. }
+            | DistributedError::NoHealthyWorkers { .. }
+            | DistributedError::TenantBusy { .. } => ServerError::Unavailable(error.to_string()),
+        }
+    }
+}
+
+/// Run the tenant's exposure job across the configured workers
+pub async fn distributed_exposure(
+    axum::Extension(tenant): axum::Extension<TenantId>,
+    coordinator: Option<axum::Extension<std::sync::Arc<Coordinator>>>,
+    axum::Json(job): axum::Json<ExposureJob>,
+) -> Result<axum::Json<DistributedExposure>, ServerError> {
+    let Some(axum::Extension(coordinator)) = coordinator else {
+        return Err(ServerError::Unavailable(
+            "no distributed workers configured".to_string(),
+        ));
+    };
+    coordinator
+        .run(&tenant, job)
+        .await
+        .map(axum::Json)
+        .map_err(Into::into)
+}
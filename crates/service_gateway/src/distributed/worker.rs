@@ -0,0 +1,223 @@
+//! Worker side: evaluates shards of netting sets
+//!
+//! Every worker simulates the same spot paths from the job's seed, so a
+//! netting set's results do not depend on which worker evaluated it or how
+//! the portfolio was sharded.
+
+use pricer_models::analytical::BlackScholes;
+use pricer_pricing::rng::PricerRng;
+use pricer_risk::exposure::ExposureCalculator;
+use pricer_risk::portfolio::CreditParams;
+use pricer_risk::xva::{compute_cva, compute_dva, OwnCreditParams};
+use tonic::codegen::{http, Body, BoxFuture, Context, Poll, Service, StdError};
+use tonic::{Request, Response, Status};
+
+use super::messages::{
+    MarketSpec, NettingSetResult, NettingSetSpec, ShardRequest, ShardResponse, TradeSpec,
+};
+
+/// Fully qualified name of the worker service
+pub const SERVICE_NAME: &str = "neutryx.distributed.v1.Worker";
+
+/// Path of the shard evaluation method
+pub const EVALUATE_SHARD_PATH: &str = "/neutryx.distributed.v1.Worker/EvaluateShard";
+
+/// Evaluate the exposure profile and XVA of every netting set in a shard
+pub fn evaluate_shard(shard: &ShardRequest) -> Result<ShardResponse, Status> {
+    let market = validate(shard)?;
+    let grid = &shard.time_grid;
+    let own_credit = shard
+        .own_credit
+        .map(|c| OwnCreditParams::new(c.hazard_rate, c.lgd))
+        .transpose()
+        .map_err(|e| Status::invalid_argument(e.to_string()))?;
+
+    let paths = simulate_spots(&market, grid, shard.num_paths as usize, shard.seed);
+    let netting_sets = shard
+        .netting_sets
+        .iter()
+        .map(|ns| {
+            let credit = CreditParams::new(ns.hazard_rate, ns.lgd)
+                .map_err(|e| Status::invalid_argument(format!("netting set {}: {}", ns.id, e)))?;
+            let values = netted_values(ns, &market, grid, &paths)?;
+            let ee = ExposureCalculator::expected_exposure(&values);
+            let ene = ExposureCalculator::expected_negative_exposure(&values);
+            Ok(NettingSetResult {
+                id: ns.id.clone(),
+                pfe: ExposureCalculator::potential_future_exposure(&values, shard.confidence),
+                epe: ExposureCalculator::expected_positive_exposure(&ee, grid),
+                cva: compute_cva(&ee, grid, &credit),
+                dva: own_credit
+                    .as_ref()
+                    .map_or(0.0, |own| compute_dva(&ene, grid, own)),
+                ee,
+                ene,
+            })
+        })
+        .collect::<Result<_, Status>>()?;
+
+    Ok(ShardResponse {
+        job_id: shard.job_id,
+        shard_id: shard.shard_id,
+        netting_sets,
+        tenant: shard.tenant.clone(),
+    })
+}
+
+fn validate(shard: &ShardRequest) -> Result<MarketSpec, Status> {
+    let invalid = |message: &str| Err(Status::invalid_argument(message));
+    let Some(market) = shard.market else {
+        return invalid("market is required");
+    };
+    if !(market.spot > 0.0 && market.volatility > 0.0) {
+        return invalid("spot and volatility must be positive");
+    }
+    let grid = &shard.time_grid;
+    if grid.len() < 2 || grid.windows(2).any(|w| w[1] <= w[0]) || grid[0] < 0.0 {
+        return invalid("time_grid must have at least two increasing points from zero");
+    }
+    if shard.num_paths == 0 {
+        return invalid("num_paths must be positive");
+    }
+    if !(shard.confidence > 0.0 && shard.confidence < 1.0) {
+        return invalid("confidence must lie in (0, 1)");
+    }
+    for trade in shard.netting_sets.iter().flat_map(|ns| &ns.trades) {
+        if !matches!(
+            trade.instrument_type.as_str(),
+            "vanilla_option" | "european_option" | "forward"
+        ) {
+            return Err(Status::invalid_argument(format!(
+                "trade {}: unknown instrument type {}",
+                trade.id, trade.instrument_type
+            )));
+        }
+        if !(trade.strike > 0.0 && trade.expiry > 0.0) {
+            return Err(Status::invalid_argument(format!(
+                "trade {}: strike and expiry must be positive",
+                trade.id
+            )));
+        }
+    }
+    Ok(market)
+}
+
+/// Geometric Brownian motion spot at each grid time, `[scenario][time]`
+fn simulate_spots(market: &MarketSpec, grid: &[f64], num_paths: usize, seed: u64) -> Vec<Vec<f64>> {
+    let sigma = market.volatility;
+    let drift = market.rate - 0.5 * sigma * sigma;
+    (0..num_paths)
+        .map(|p| {
+            let mut rng = PricerRng::from_seed(seed.wrapping_add(p as u64));
+            let mut spot = market.spot;
+            let mut previous = 0.0;
+            grid.iter()
+                .map(|&t| {
+                    let dt = t - previous;
+                    spot *= (drift * dt + sigma * dt.sqrt() * rng.gen_normal()).exp();
+                    previous = t;
+                    spot
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Netted value of a netting set on every path, `[scenario][time]`
+fn netted_values(
+    ns: &NettingSetSpec,
+    market: &MarketSpec,
+    grid: &[f64],
+    paths: &[Vec<f64>],
+) -> Result<Vec<Vec<f64>>, Status> {
+    paths
+        .iter()
+        .map(|path| {
+            path.iter()
+                .zip(grid)
+                .map(|(&spot, &t)| {
+                    let bs = BlackScholes::new(spot, market.rate, market.volatility)
+                        .map_err(|e| Status::internal(e.to_string()))?;
+                    Ok(ns
+                        .trades
+                        .iter()
+                        .map(|trade| trade_value(trade, &bs, market, spot, t))
+                        .sum())
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Black-Scholes value of a trade at time `t`, zero once it has expired
+fn trade_value(
+    trade: &TradeSpec,
+    bs: &BlackScholes<f64>,
+    market: &MarketSpec,
+    spot: f64,
+    t: f64,
+) -> f64 {
+    let tau = trade.expiry - t;
+    if tau <= 0.0 {
+        return 0.0;
+    }
+    let unit = match trade.instrument_type.as_str() {
+        "forward" => spot - trade.strike * (-market.rate * tau).exp(),
+        _ if trade.is_call => bs.price_call(trade.strike, tau),
+        _ => bs.price_put(trade.strike, tau),
+    };
+    trade.quantity * unit
+}
+
+/// gRPC server of the worker service
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WorkerServer;
+
+impl tonic::server::NamedService for WorkerServer {
+    const NAME: &'static str = SERVICE_NAME;
+}
+
+impl<B> Service<http::Request<B>> for WorkerServer
+where
+    B: Body + Send + 'static,
+    B::Error: Into<StdError> + Send + 'static,
+{
+    type Response = http::Response<tonic::body::BoxBody>;
+    type Error = std::convert::Infallible;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<B>) -> Self::Future {
+        match request.uri().path() {
+            EVALUATE_SHARD_PATH => Box::pin(async move {
+                let mut grpc = tonic::server::Grpc::new(tonic::codec::ProstCodec::default());
+                Ok(grpc.unary(EvaluateShard, request).await)
+            }),
+            path => {
+                let status = Status::unimplemented(format!("unknown method {}", path));
+                Box::pin(async move { Ok(status.into_http()) })
+            }
+        }
+    }
+}
+
+struct EvaluateShard;
+
+impl tonic::server::UnaryService<ShardRequest> for EvaluateShard {
+    type Response = ShardResponse;
+    type Future = BoxFuture<Response<ShardResponse>, Status>;
+
+    fn call(&mut self, request: Request<ShardRequest>) -> Self::Future {
+        let shard = request.into_inner();
+        Box::pin(async move {
+            // Simulation is CPU bound; keep it off the async workers
+            tokio::task::spawn_blocking(move || evaluate_shard(&shard))
+                .await
+                .map_err(|e| Status::internal(e.to_string()))?
+                .map(Response::new)
+        })
+    }
+}
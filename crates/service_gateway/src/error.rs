@@ -23,6 +23,14 @@ pub enum ServerError {
     #[error("Invalid request: {0}")]
     InvalidRequest(String),
 
+    /// Missing or unknown API key
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    /// Request conflicts with an earlier one
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
     /// Not found
     #[error("Not found: {0}")]
     NotFound(String),
@@ -30,6 +38,10 @@ pub enum ServerError {
     /// Internal error
     #[error("Internal error: {0}")]
     Internal(String),
+
+    /// Service unavailable
+    #[error("Service unavailable: {0}")]
+    Unavailable(String),
 }
 
 impl IntoResponse for ServerError {
@@ -38,8 +50,11 @@ impl IntoResponse for ServerError {
             ServerError::Pricing(msg) => (StatusCode::UNPROCESSABLE_ENTITY, msg.clone()),
             ServerError::Calibration(msg) => (StatusCode::UNPROCESSABLE_ENTITY, msg.clone()),
             ServerError::InvalidRequest(msg) => (StatusCode::BAD_REQUEST, msg.clone()),
+            ServerError::Unauthorized(msg) => (StatusCode::UNAUTHORIZED, msg.clone()),
+            ServerError::Conflict(msg) => (StatusCode::CONFLICT, msg.clone()),
             ServerError::NotFound(msg) => (StatusCode::NOT_FOUND, msg.clone()),
             ServerError::Internal(msg) => (StatusCode::INTERNAL_SERVER_ERROR, msg.clone()),
+            ServerError::Unavailable(msg) => (StatusCode::SERVICE_UNAVAILABLE, msg.clone()),
         };
 
         let body = Json(json!({
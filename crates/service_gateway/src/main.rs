@@ -13,12 +13,46 @@
 //! - `POST /api/v1/price` - Price a single instrument
 //! - `POST /api/v1/price/batch` - Price a portfolio
 //! - `POST /api/v1/calibrate` - Calibrate model parameters
+//! - `GET /api/v1/calibrate/history/{model}` - Stored calibrations of a model
+//! - `GET /api/v1/calibrate/alerts/{model}` - Parameter drift of the latest
+//!   stored calibration of a model
+//! - `POST /api/v1/predeal` - What-if incremental XVA, PFE, limit headroom and
+//!   SA-CCR EAD of a candidate trade
+//! - `GET /api/v1/results` - Stored EOD results, filtered by `kind`,
+//!   `run_id` and valuation dates `from`/`to`
+//! - `GET /api/v1/results/{kind}/{as_of}/{run_id}/{name}` - One stored
+//!   result table
+//! - `GET /api/v1/verification` - Dual-run price verification counts
+//! - `POST /api/v1/exposure/distributed` - Exposure and XVA sharded across
+//!   worker nodes (`distributed` feature)
 //! - `GET /api/v1/health` - Health check
 //!
+//! With API keys configured (`NEUTRYX_API_KEYS=key=tenant,...`), API v1
+//! requests must carry an `X-Api-Key` header and see only their tenant's
+//! calibrations, results and distributed jobs.
+//!
+//! POST pricing and XVA requests carrying an `Idempotency-Key` header are
+//! run once; retries within `NEUTRYX_IDEMPOTENCY_WINDOW_SECS` (24 hours)
+//! replay the stored response.
+//!
+//! In dual-run mode (`NEUTRYX_VERIFY_INSTRUMENTS=european_option,...`)
+//! prices of the listed instrument types are checked against a Monte Carlo
+//! engine; differences beyond tolerance are returned as `warnings`.
+//!
+//! Responses are compressed with gzip or zstd as the client's
+//! `Accept-Encoding` allows (`NEUTRYX_COMPRESSION`, default `gzip,zstd`).
+//! Stored result listings and tables page with `limit`, `offset` and
+//! `cursor`, and result tables select columns with `fields`.
+//!
+//! With the `arrow` feature, `price/batch` and `exposure` return an Arrow
+//! IPC stream when requested with `Accept: application/vnd.apache.arrow.stream`.
+//!
 //! ## gRPC (Tonic)
 //! - `PricingService.PriceInstrument` - Price a single instrument
 //! - `PricingService.PricePortfolio` - Price a portfolio (streaming)
 //! - `CalibrationService.Calibrate` - Calibrate model parameters
+//! - `neutryx.distributed.v1.Worker/EvaluateShard` - Evaluate a shard of
+//!   netting sets for a coordinator (`distributed` feature)
 
 use std::net::SocketAddr;
 
@@ -27,8 +61,11 @@ use tracing::info;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 mod config;
+#[cfg(feature = "distributed")]
+mod distributed;
 mod error;
 mod rest;
+mod tenant;
 
 pub use error::ServerError;
 
@@ -49,13 +86,117 @@ async fn main() -> Result<()> {
     info!("  REST enabled: {}", config.rest_enabled);
     info!("  gRPC enabled: {}", config.grpc_enabled);
 
+    // Start distributed worker service
+    #[cfg(feature = "distributed")]
+    let worker = if config.worker_enabled {
+        let addr: SocketAddr = config.worker_addr.parse()?;
+        info!("Starting distributed worker on {}", addr);
+        Some(tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(distributed::WorkerServer)
+                .serve(addr),
+        ))
+    } else {
+        None
+    };
+
     // Start REST server
     #[cfg(feature = "rest")]
     if config.rest_enabled {
         let addr: SocketAddr = config.rest_addr.parse()?;
         info!("Starting REST server on {}", addr);
 
-        let app = rest::create_router();
+        let keys = std::sync::Arc::new(tenant::ApiKeys::new(&config.api_keys)?);
+        let scoped = keys.is_multi_tenant();
+        if scoped {
+            info!("Serving {} tenants", keys.tenant_count());
+        }
+
+        // Tenants' stores sit beside the single-tenant ones: a file per
+        // tenant for calibrations, a prefix per tenant for results
+        let calibration_store = config.calibration_store.clone();
+        let drift = infra_store::DriftConfig::new(infra_store::DriftThreshold::relative(
+            config.calibration_drift_threshold,
+        ));
+        let calibration = std::sync::Arc::new(tenant::TenantScoped::new(move |tenant| {
+            let store = match &calibration_store {
+                Some(path) if scoped => {
+                    infra_store::CalibrationStore::open(tenant.scope_file(path.as_ref()))?
+                }
+                Some(path) => infra_store::CalibrationStore::open(path)?,
+                None => infra_store::CalibrationStore::in_memory(),
+            };
+            Ok(std::sync::Arc::new(rest::CalibrationState {
+                store,
+                drift: drift.clone(),
+            }))
+        }));
+        let results_dir = config.results_dir.clone();
+        let results = std::sync::Arc::new(rest::ResultStores::new(move |tenant| {
+            let store: std::sync::Arc<dyn infra_store::ResultStore> = match &results_dir {
+                Some(dir) if scoped => std::sync::Arc::new(
+                    infra_store::storage::ParquetResultStore::local(dir)
+                        .with_prefix(tenant.as_str()),
+                ),
+                Some(dir) => {
+                    std::sync::Arc::new(infra_store::storage::ParquetResultStore::local(dir))
+                }
+                None => std::sync::Arc::new(infra_store::storage::MemoryResultStore::new()),
+            };
+            Ok(store)
+        }));
+        if !scoped {
+            // Fail at startup rather than on the first request
+            let tenant = tenant::TenantId::default_tenant();
+            calibration.get(&tenant)?;
+            results.get(&tenant)?;
+        }
+        let idempotency = std::sync::Arc::new(rest::IdempotencyCache::new(
+            std::time::Duration::from_secs(config.idempotency_window_secs),
+            config.idempotency_capacity,
+        ));
+        let app = rest::create_router(keys, calibration, results, idempotency);
+        let app = if config.verify_instruments.is_empty() {
+            app
+        } else {
+            info!(
+                "Verifying prices of {} against Monte Carlo",
+                config.verify_instruments.join(", ")
+            );
+            let verifier = rest::PriceVerifier::new(
+                rest::VerificationConfig::new(config.verify_instruments.clone())
+                    .with_paths(config.verify_paths)
+                    .with_tolerance(config.verify_tolerance, config.verify_absolute_tolerance),
+            );
+            app.layer(axum::Extension(std::sync::Arc::new(verifier)))
+        };
+        let gzip = config.compression.iter().any(|e| e == "gzip");
+        let zstd = config.compression.iter().any(|e| e == "zstd");
+        let app = if gzip || zstd {
+            app.layer(rest::compression(gzip, zstd, config.compression_min_bytes))
+        } else {
+            app
+        };
+
+        #[cfg(feature = "distributed")]
+        let app = if config.distributed_workers.is_empty() {
+            app
+        } else {
+            info!(
+                "Coordinating {} distributed workers",
+                config.distributed_workers.len()
+            );
+            let coordinator = distributed::Coordinator::new(
+                distributed::CoordinatorConfig::new(config.distributed_workers.clone())
+                    .with_shard_size(config.distributed_shard_size)
+                    .with_max_attempts(config.distributed_max_attempts)
+                    .with_jobs_per_tenant(config.distributed_jobs_per_tenant)
+                    .with_timeout(std::time::Duration::from_secs(
+                        config.distributed_timeout_secs,
+                    )),
+            )?;
+            app.layer(axum::Extension(std::sync::Arc::new(coordinator)))
+        };
 
         let listener = tokio::net::TcpListener::bind(addr).await?;
         axum::serve(listener, app).await?;
@@ -67,5 +208,10 @@ async fn main() -> Result<()> {
         info!("gRPC server not yet implemented");
     }
 
+    #[cfg(feature = "distributed")]
+    if let Some(worker) = worker {
+        worker.await??;
+    }
+
     Ok(())
 }
@@ -0,0 +1,201 @@
+//! Swap analytics
+//!
+//! Par rate, annuity, PV01, accrued interest and carry of a vanilla
+//! interest rate swap off flat discount and forward curves, with the same
+//! fixed and floating schedule on both legs.
+
+use axum::Json;
+use chrono::{Datelike, NaiveDate};
+use pricer_core::market_data::curves::{CurveEnum, CurveName, CurveSet};
+use pricer_core::types::time::{Date, DayCountConvention};
+use pricer_models::instruments::rates::{
+    price_irs, FixedLeg, FloatingLeg, InterestRateSwap, RateIndex, SwapDirection,
+};
+use pricer_models::schedules::{Frequency, ScheduleBuilder};
+use serde::{Deserialize, Serialize};
+
+use crate::error::ServerError;
+
+/// Swap analytics request
+#[derive(Deserialize)]
+pub struct SwapAnalyticsRequest {
+    pub notional: f64,
+    pub fixed_rate: f64,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    /// e.g. "annual", "semi-annual", "quarterly" (default "semi-annual")
+    pub frequency: Option<String>,
+    /// e.g. "SOFR", "EURIBOR3M"
+    pub index: String,
+    /// Floating leg spread (default 0)
+    pub spread: Option<f64>,
+    /// "pay_fixed" or "receive_fixed"
+    pub direction: String,
+    /// Fixed leg day count (default "30/360"); the floating leg uses the
+    /// index convention
+    pub fixed_day_count: Option<String>,
+    pub valuation_date: NaiveDate,
+    /// Carry horizon (default: no carry)
+    pub horizon_date: Option<NaiveDate>,
+    pub discount_rate: f64,
+    /// Flat forward rate of the index (default: the discount rate)
+    pub forward_rate: Option<f64>,
+}
+
+/// Swap analytics response
+#[derive(Debug, Serialize)]
+pub struct SwapAnalyticsResponse {
+    pub npv: f64,
+    pub par_rate: f64,
+    /// Annuity per unit notional
+    pub annuity: f64,
+    pub pv01: f64,
+    pub accrued_interest: f64,
+    pub carry: Option<f64>,
+}
+
+/// Compute swap analytics
+pub async fn swap_analytics(
+    Json(request): Json<SwapAnalyticsRequest>,
+) -> Result<Json<SwapAnalyticsResponse>, ServerError> {
+    run(&request).map(Json)
+}
+
+fn run(request: &SwapAnalyticsRequest) -> Result<SwapAnalyticsResponse, ServerError> {
+    let invalid = |message: String| ServerError::InvalidRequest(message);
+    if !request.notional.is_finite() || request.notional <= 0.0 {
+        return Err(invalid("notional must be positive".to_string()));
+    }
+    let index: RateIndex = request.index.parse().map_err(invalid)?;
+    let frequency: Frequency = request
+        .frequency
+        .as_deref()
+        .unwrap_or("semi-annual")
+        .parse()
+        .map_err(invalid)?;
+    let fixed_day_count: DayCountConvention = request
+        .fixed_day_count
+        .as_deref()
+        .unwrap_or("30/360")
+        .parse()
+        .map_err(invalid)?;
+    let direction = match request.direction.as_str() {
+        "pay_fixed" => SwapDirection::PayFixed,
+        "receive_fixed" => SwapDirection::ReceiveFixed,
+        other => return Err(invalid(format!("Unknown swap direction: {}", other))),
+    };
+
+    let schedule = ScheduleBuilder::new()
+        .start(to_date(request.start_date)?)
+        .end(to_date(request.end_date)?)
+        .frequency(frequency)
+        .day_count(fixed_day_count)
+        .build()
+        .map_err(|e| invalid(e.to_string()))?;
+    let swap = InterestRateSwap::new(
+        request.notional,
+        FixedLeg::new(schedule.clone(), request.fixed_rate, fixed_day_count),
+        FloatingLeg::new(
+            schedule,
+            request.spread.unwrap_or(0.0),
+            index,
+            index.default_day_count(),
+        ),
+        index.currency(),
+        direction,
+    );
+
+    let mut curves = CurveSet::new();
+    curves.insert(CurveName::Discount, CurveEnum::flat(request.discount_rate));
+    curves.insert(
+        index.curve_name(),
+        CurveEnum::flat(request.forward_rate.unwrap_or(request.discount_rate)),
+    );
+    curves.set_discount_curve(CurveName::Discount);
+
+    let valuation_date = to_date(request.valuation_date)?;
+    let carry = match request.horizon_date {
+        Some(horizon) if horizon < request.valuation_date => {
+            return Err(invalid(
+                "horizon_date must not be before valuation_date".to_string(),
+            ))
+        }
+        Some(horizon) => Some(swap.carry(&curves, valuation_date, to_date(horizon)?)),
+        None => None,
+    };
+
+    Ok(SwapAnalyticsResponse {
+        npv: price_irs(&swap, &curves, valuation_date),
+        par_rate: swap.par_rate(&curves, valuation_date),
+        annuity: swap.annuity(&curves, valuation_date),
+        pv01: swap.pv01(&curves, valuation_date),
+        accrued_interest: swap.accrued_interest(&curves, valuation_date),
+        carry,
+    })
+}
+
+fn to_date(date: NaiveDate) -> Result<Date, ServerError> {
+    Date::from_ymd(date.year(), date.month(), date.day())
+        .map_err(|e| ServerError::InvalidRequest(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(direction: &str) -> SwapAnalyticsRequest {
+        SwapAnalyticsRequest {
+            notional: 10_000_000.0,
+            fixed_rate: 0.03,
+            start_date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+            end_date: NaiveDate::from_ymd_opt(2029, 1, 15).unwrap(),
+            frequency: None,
+            index: "SOFR".to_string(),
+            spread: None,
+            direction: direction.to_string(),
+            fixed_day_count: None,
+            valuation_date: NaiveDate::from_ymd_opt(2024, 3, 15).unwrap(),
+            horizon_date: NaiveDate::from_ymd_opt(2024, 6, 15),
+            discount_rate: 0.03,
+            forward_rate: Some(0.035),
+        }
+    }
+
+    #[test]
+    fn test_payer_and_receiver_analytics() {
+        let payer = run(&request("pay_fixed")).unwrap();
+        let receiver = run(&request("receive_fixed")).unwrap();
+
+        assert!(payer.par_rate > 0.03 && payer.par_rate < 0.04);
+        assert!(payer.annuity > 4.0 && payer.annuity < 5.0);
+        assert!((payer.pv01 - 10_000_000.0 * payer.annuity * 1e-4).abs() < 1e-6);
+        assert!(payer.npv > 0.0);
+        assert!((payer.npv + receiver.npv).abs() < 1e-6);
+        assert!(payer.accrued_interest > 0.0);
+        assert!((payer.accrued_interest + receiver.accrued_interest).abs() < 1e-6);
+        assert!(payer.carry.unwrap() > 0.0);
+        assert_eq!(payer.pv01, receiver.pv01);
+    }
+
+    #[test]
+    fn test_invalid_requests_are_rejected() {
+        let mut bad_index = request("pay_fixed");
+        bad_index.index = "LIBOR".to_string();
+        assert!(matches!(
+            run(&bad_index),
+            Err(ServerError::InvalidRequest(_))
+        ));
+
+        assert!(matches!(
+            run(&request("buy")),
+            Err(ServerError::InvalidRequest(_))
+        ));
+
+        let mut bad_horizon = request("pay_fixed");
+        bad_horizon.horizon_date = NaiveDate::from_ymd_opt(2024, 1, 1);
+        assert!(matches!(
+            run(&bad_horizon),
+            Err(ServerError::InvalidRequest(_))
+        ));
+    }
+}
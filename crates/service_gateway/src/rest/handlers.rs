@@ -1,9 +1,23 @@
 //! REST API handlers
+//!
+//! Batch endpoints answer with JSON by default, or with an Arrow IPC stream
+//! when the request carries `Accept: application/vnd.apache.arrow.stream`.
 
-use axum::Json;
+use std::sync::Arc;
+
+use axum::extract::Path;
+use axum::http::HeaderMap;
+use axum::response::{IntoResponse, Response};
+use axum::{Extension, Json};
+use infra_store::{CalibrationRecord, CalibrationStore, DriftAlert, DriftConfig};
+use pricer_models::analytical::BlackScholes;
+use pricer_pricing::greeks::{BumpSpec, Sensitivity, StructuredGreeks};
+use pricer_pricing::mc::Greek;
 use serde::{Deserialize, Serialize};
 
+use super::verification::{PriceVerifier, Verification};
 use crate::error::ServerError;
+use crate::tenant::{TenantId, TenantScoped};
 
 // ============================================================================
 // Request/Response Types
@@ -26,21 +40,35 @@ pub struct PriceRequest {
     pub spot: f64,
     pub volatility: f64,
     pub rate: f64,
+    /// ISO 4217 currency of measure (default: USD)
+    pub currency: Option<String>,
 }
 
-/// Pricing response
-#[derive(Serialize)]
+/// Pricing response: price and Greeks quoted per risk factor with units,
+/// currency of measure and bump specification
+#[derive(Debug, Serialize)]
 pub struct PriceResponse {
-    pub price: f64,
-    pub delta: Option<f64>,
-    pub gamma: Option<f64>,
-    pub vega: Option<f64>,
-    pub theta: Option<f64>,
+    #[serde(flatten)]
+    pub greeks: StructuredGreeks,
+    /// Secondary engine price check, in dual-run mode
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verification: Option<Verification>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+impl From<StructuredGreeks> for PriceResponse {
+    fn from(greeks: StructuredGreeks) -> Self {
+        Self {
+            greeks,
+            verification: None,
+            warnings: Vec::new(),
+        }
+    }
 }
 
 /// Portfolio pricing request
 #[derive(Deserialize)]
-#[allow(dead_code)]
 pub struct PortfolioRequest {
     pub instruments: Vec<PriceRequest>,
     pub compute_greeks: Option<bool>,
@@ -69,6 +97,13 @@ pub struct CalibrateResponse {
     pub error: f64,
 }
 
+/// Stored calibration results of one tenant, served by the calibration
+/// history and drift alert endpoints
+pub struct CalibrationState {
+    pub store: CalibrationStore,
+    pub drift: DriftConfig,
+}
+
 /// Exposure request
 #[derive(Deserialize)]
 #[allow(dead_code)]
@@ -99,8 +134,10 @@ pub async fn health() -> Json<HealthResponse> {
     })
 }
 
-/// Price a single instrument
+/// Price a single instrument, checking the price against the secondary
+/// engine in dual-run mode
 pub async fn price_instrument(
+    verifier: Option<Extension<Arc<PriceVerifier>>>,
     Json(request): Json<PriceRequest>,
 ) -> Result<Json<PriceResponse>, ServerError> {
     // TODO: Use pricer_pricing for actual pricing
@@ -134,35 +171,62 @@ pub async fn price_instrument(
         }
     };
 
-    Ok(Json(PriceResponse {
-        price,
-        delta: None,
-        gamma: None,
-        vega: None,
-        theta: None,
-    }))
+    let currency = request.currency.as_deref().unwrap_or(DEFAULT_CURRENCY);
+    let greeks = greeks(&request)?.into_iter().fold(
+        StructuredGreeks::new(price, 0.0, currency),
+        |response, s| response.with_sensitivity(s),
+    );
+
+    let mut response = PriceResponse::from(greeks);
+    if let Some(Extension(verifier)) = verifier {
+        response.verification = verifier.verify(&request, price)?;
+        response.warnings.extend(
+            response
+                .verification
+                .as_ref()
+                .and_then(Verification::warning),
+        );
+    }
+    Ok(Json(response))
 }
 
 /// Price a portfolio of instruments
+#[cfg_attr(not(feature = "arrow"), allow(unused_variables))]
 pub async fn price_portfolio(
+    verifier: Option<Extension<Arc<PriceVerifier>>>,
+    headers: HeaderMap,
     Json(request): Json<PortfolioRequest>,
-) -> Result<Json<PortfolioResponse>, ServerError> {
+) -> Result<Response, ServerError> {
     let mut results = Vec::with_capacity(request.instruments.len());
     let mut total_value = 0.0;
 
+    let compute_greeks = request.compute_greeks.unwrap_or(true);
     for instrument in request.instruments {
-        let response = price_instrument(Json(instrument)).await?;
-        total_value += response.price;
-        results.push(response.0);
+        let Json(mut response) = price_instrument(verifier.clone(), Json(instrument)).await?;
+        if !compute_greeks {
+            response.greeks.sensitivities.clear();
+        }
+        total_value += response.greeks.price;
+        results.push(response);
+    }
+
+    #[cfg(feature = "arrow")]
+    if super::ipc::accepts_arrow(&headers) {
+        let batch = super::ipc::price_results(&results, total_value)?;
+        return super::ipc::stream_response(&batch);
     }
 
     Ok(Json(PortfolioResponse {
         results,
         total_value,
-    }))
+    })
+    .into_response())
 }
 
 /// Calibrate model parameters
+///
+/// Results are not stored: the calibration store only holds real
+/// calibrations, and this endpoint does not run one yet.
 pub async fn calibrate(
     Json(request): Json<CalibrateRequest>,
 ) -> Result<Json<CalibrateResponse>, ServerError> {
@@ -193,28 +257,107 @@ pub async fn calibrate(
     }
 }
 
+/// The tenant's stored calibrations of a model, oldest first
+pub async fn calibration_history(
+    Extension(tenant): Extension<TenantId>,
+    Extension(calibrations): Extension<Arc<TenantScoped<CalibrationState>>>,
+    Path(model): Path<String>,
+) -> Result<Json<Vec<CalibrationRecord>>, ServerError> {
+    let records = calibrations.get(&tenant)?.store.records(&model);
+    if records.is_empty() {
+        return Err(ServerError::NotFound(format!(
+            "No calibrations of model {}",
+            model
+        )));
+    }
+    Ok(Json(records))
+}
+
+/// Parameters of the tenant's latest stored calibration of a model that
+/// moved beyond the drift threshold since the calibration before it
+pub async fn calibration_alerts(
+    Extension(tenant): Extension<TenantId>,
+    Extension(calibrations): Extension<Arc<TenantScoped<CalibrationState>>>,
+    Path(model): Path<String>,
+) -> Result<Json<Vec<DriftAlert>>, ServerError> {
+    let calibration = calibrations.get(&tenant)?;
+    let latest = calibration
+        .store
+        .latest(&model)
+        .ok_or_else(|| ServerError::NotFound(format!("No calibrations of model {}", model)))?;
+    Ok(Json(calibration.store.drift(&latest, &calibration.drift)))
+}
+
 /// Calculate exposure metrics
+#[cfg_attr(not(feature = "arrow"), allow(unused_variables))]
 pub async fn calculate_exposure(
+    headers: HeaderMap,
     Json(request): Json<ExposureRequest>,
-) -> Result<Json<ExposureResponse>, ServerError> {
+) -> Result<Response, ServerError> {
     // TODO: Use pricer_risk for actual exposure calculation
 
     let num_times = request.time_grid.len();
 
-    Ok(Json(ExposureResponse {
+    let response = ExposureResponse {
         ee: vec![0.0; num_times],
         epe: 0.0,
         ene: 0.0,
         pfe_95: vec![0.0; num_times],
-    }))
+    };
+
+    #[cfg(feature = "arrow")]
+    if super::ipc::accepts_arrow(&headers) {
+        let batch = super::ipc::exposure_profile(&request.time_grid, &response)?;
+        return super::ipc::stream_response(&batch);
+    }
+
+    Ok(Json(response).into_response())
 }
 
 // ============================================================================
 // Helper Functions
 // ============================================================================
 
+/// Currency of measure when the request does not name one
+const DEFAULT_CURRENCY: &str = "USD";
+
+/// Closed-form Greeks of a priced instrument, quoted in market units
+fn greeks(request: &PriceRequest) -> Result<Vec<Sensitivity>, ServerError> {
+    let (strike, expiry) = (request.strike, request.expiry);
+    let raw = match request.instrument_type.as_str() {
+        "vanilla_option" | "european_option" => {
+            let bs = BlackScholes::new(request.spot, request.rate, request.volatility)
+                .map_err(|e| ServerError::InvalidRequest(e.to_string()))?;
+            let is_call = request.is_call.unwrap_or(true);
+            vec![
+                (Greek::Delta, bs.delta(strike, expiry, is_call)),
+                (Greek::Gamma, bs.gamma(strike, expiry)),
+                (Greek::Vega, bs.vega(strike, expiry)),
+                (Greek::Theta, bs.theta(strike, expiry, is_call)),
+                (Greek::Rho, bs.rho(strike, expiry, is_call)),
+            ]
+        }
+        "forward" => {
+            let growth = (request.rate * expiry).exp();
+            vec![
+                (Greek::Delta, growth),
+                (Greek::Theta, -request.rate * request.spot * growth),
+                (Greek::Rho, request.spot * expiry * growth),
+            ]
+        }
+        _ => Vec::new(),
+    };
+
+    Ok(raw
+        .into_iter()
+        .map(|(greek, value)| {
+            Sensitivity::from_raw(greek, greek.risk_factor(), value, BumpSpec::Analytic)
+        })
+        .collect())
+}
+
 /// Standard normal CDF approximation
-fn normal_cdf(x: f64) -> f64 {
+pub(super) fn normal_cdf(x: f64) -> f64 {
     let a1 = 0.254829592;
     let a2 = -0.284496736;
     let a3 = 1.421413741;
@@ -229,3 +372,183 @@ fn normal_cdf(x: f64) -> f64 {
 
     0.5 * (1.0 + sign * y)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rest::verification::VerificationConfig;
+
+    fn request(instrument_type: &str) -> PriceRequest {
+        PriceRequest {
+            instrument_type: instrument_type.to_string(),
+            strike: 100.0,
+            expiry: 1.0,
+            is_call: Some(true),
+            spot: 100.0,
+            volatility: 0.2,
+            rate: 0.05,
+            currency: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_option_greeks_are_quoted_in_market_units() {
+        let Json(response) = price_instrument(None, Json(request("european_option")))
+            .await
+            .unwrap();
+        assert!(response.verification.is_none());
+        let response = response.greeks;
+
+        assert_eq!(response.currency, "USD");
+        assert!((response.price - 10.4506).abs() < 1e-3);
+        // ATM one-year call: ∂V/∂σ ≈ 37.5, ∂V/∂r ≈ 53.2
+        assert!((response.value(Greek::Vega).unwrap() - 0.375).abs() < 1e-3);
+        assert!((response.value(Greek::Rho).unwrap() - 0.00532).abs() < 1e-5);
+        assert!(response.value(Greek::Theta).unwrap() < 0.0);
+
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["sensitivities"][4]["unit"], "per_basis_point");
+        assert_eq!(json["sensitivities"][4]["bump"]["method"], "analytic");
+    }
+
+    #[tokio::test]
+    async fn test_calibration_history_and_alerts_per_tenant() {
+        use chrono::NaiveDate;
+        use infra_store::{FitQuality, Save};
+
+        let calibrations = Arc::new(TenantScoped::new(|_: &TenantId| {
+            Ok(Arc::new(CalibrationState {
+                store: CalibrationStore::in_memory(),
+                drift: DriftConfig::default(),
+            }))
+        }));
+        let rates = TenantId::new("rates").unwrap();
+        let fx = TenantId::new("fx").unwrap();
+        let record = |day: u32, sigma: f64| {
+            CalibrationRecord::new(
+                "hull-white",
+                NaiveDate::from_ymd_opt(2024, 3, day).unwrap(),
+                FitQuality {
+                    rmse: 1e-4,
+                    max_error: 1e-4,
+                    converged: true,
+                },
+            )
+            .with_parameter("alpha", 0.05)
+            .with_parameter("sigma", sigma)
+        };
+        let store = &calibrations.get(&rates).unwrap().store;
+        store.save(&record(1, 0.02)).unwrap();
+        store.save(&record(4, 0.01)).unwrap();
+
+        let Json(history) = calibration_history(
+            Extension(rates.clone()),
+            Extension(calibrations.clone()),
+            Path("hull-white".to_string()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(history.len(), 2);
+        let Json(alerts) = calibration_alerts(
+            Extension(rates.clone()),
+            Extension(calibrations.clone()),
+            Path("hull-white".to_string()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].parameter, "sigma");
+
+        // Another tenant sees none of them
+        assert!(calibration_history(
+            Extension(fx.clone()),
+            Extension(calibrations.clone()),
+            Path("hull-white".to_string())
+        )
+        .await
+        .is_err());
+        assert!(calibration_alerts(
+            Extension(fx),
+            Extension(calibrations.clone()),
+            Path("hull-white".to_string())
+        )
+        .await
+        .is_err());
+        assert!(calibration_history(
+            Extension(rates),
+            Extension(calibrations),
+            Path("cir".to_string())
+        )
+        .await
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_calibrate_does_not_store() {
+        let Json(response) = calibrate(Json(CalibrateRequest {
+            model_type: "hull-white".to_string(),
+            market_data: serde_json::Value::Null,
+        }))
+        .await
+        .unwrap();
+        assert_eq!(response.model_type, "hull-white");
+    }
+
+    #[tokio::test]
+    async fn test_portfolio_without_greeks() {
+        let portfolio = PortfolioRequest {
+            instruments: vec![request("forward")],
+            compute_greeks: Some(false),
+        };
+        let response = price_portfolio(None, HeaderMap::new(), Json(portfolio))
+            .await
+            .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(json["results"][0]["sensitivities"], serde_json::json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_dual_run_verifies_configured_types() {
+        let verifier = Arc::new(PriceVerifier::new(
+            VerificationConfig::new(["european_option".to_string()])
+                .with_paths(20_000)
+                .with_tolerance(0.0, 0.0),
+        ));
+        let Json(response) = price_instrument(
+            Some(Extension(verifier.clone())),
+            Json(request("european_option")),
+        )
+        .await
+        .unwrap();
+        let verification = response.verification.as_ref().unwrap();
+        assert_eq!(verification.engine, "monte_carlo");
+        // Agrees within Monte Carlo noise alone
+        assert!(verification.within_tolerance);
+        assert!(response.warnings.is_empty());
+
+        // A forward is not verified here
+        let Json(response) =
+            price_instrument(Some(Extension(verifier.clone())), Json(request("forward")))
+                .await
+                .unwrap();
+        assert!(response.verification.is_none());
+
+        let json = serde_json::to_value(
+            price_instrument(
+                Some(Extension(verifier.clone())),
+                Json(request("european_option")),
+            )
+            .await
+            .unwrap()
+            .0,
+        )
+        .unwrap();
+        assert_eq!(json["verification"]["engine"], "monte_carlo");
+        assert!(json["price"].is_number());
+        assert_eq!(verifier.metrics().counts["european_option"].checked, 2);
+    }
+}
@@ -0,0 +1,424 @@
+//! Idempotency keys for POST endpoints
+//!
+//! A client that retries a pricing or XVA request after a timeout or a
+//! dropped connection sends the same `Idempotency-Key` header on every
+//! attempt. The first request runs; retries within the replay window
+//! receive the stored response, marked `Idempotent-Replayed: true`, instead
+//! of running the calculation again.
+//!
+//! - Keys are scoped by tenant, so two tenants may use the same key
+//! - A key reused with a different method, path, `Accept` header or body,
+//!   or retried while its first request is still running, is answered with
+//!   409 Conflict
+//! - Server errors (5xx) are not stored, so the request can be retried
+//! - Requests without the header are not deduplicated
+
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use axum::body::{Body, Bytes};
+use axum::extract::{Request, State};
+use axum::http::header::ACCEPT;
+use axum::http::{HeaderMap, HeaderValue, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+use crate::error::ServerError;
+use crate::tenant::TenantId;
+
+/// Header carrying the client's idempotency key
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Header marking a replayed response
+pub const REPLAYED_HEADER: &str = "idempotent-replayed";
+
+/// Longest accepted idempotency key
+const MAX_KEY_LENGTH: usize = 255;
+
+/// Largest request body buffered for fingerprinting
+const MAX_BODY_BYTES: usize = 64 * 1024 * 1024;
+
+/// Stored response of a completed request
+#[derive(Clone)]
+struct StoredResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+}
+
+impl StoredResponse {
+    fn replay(&self) -> Response {
+        let mut response = (self.status, self.body.clone()).into_response();
+        *response.headers_mut() = self.headers.clone();
+        response
+            .headers_mut()
+            .insert(REPLAYED_HEADER, HeaderValue::from_static("true"));
+        response
+    }
+}
+
+/// State of a request under one key
+enum Entry {
+    Running {
+        fingerprint: u64,
+        started: Instant,
+    },
+    Done {
+        fingerprint: u64,
+        stored: Instant,
+        response: StoredResponse,
+    },
+}
+
+impl Entry {
+    fn fingerprint(&self) -> u64 {
+        match self {
+            Entry::Running { fingerprint, .. } | Entry::Done { fingerprint, .. } => *fingerprint,
+        }
+    }
+
+    fn since(&self) -> Instant {
+        match self {
+            Entry::Running { started, .. } => *started,
+            Entry::Done { stored, .. } => *stored,
+        }
+    }
+}
+
+type EntryKey = (TenantId, String);
+
+/// Responses of idempotent requests, kept for a replay window
+pub struct IdempotencyCache {
+    window: Duration,
+    capacity: usize,
+    entries: Mutex<HashMap<EntryKey, Entry>>,
+}
+
+/// What to do with a keyed request
+enum Claim {
+    Run,
+    Replay(StoredResponse),
+}
+
+impl IdempotencyCache {
+    /// Cache replaying responses for `window`, holding at most `capacity`
+    /// keys
+    pub fn new(window: Duration, capacity: usize) -> Self {
+        Self {
+            window,
+            capacity: capacity.max(1),
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Number of keys held, including expired ones not yet evicted
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<EntryKey, Entry>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Replay the stored response of the key, or claim it to run the request
+    fn claim(&self, key: &EntryKey, fingerprint: u64) -> Result<Claim, ServerError> {
+        let now = Instant::now();
+        let mut entries = self.lock();
+        match entries.get(key) {
+            Some(entry) if now.duration_since(entry.since()) < self.window => {
+                if entry.fingerprint() != fingerprint {
+                    return Err(ServerError::Conflict(format!(
+                        "Idempotency key {} was used for a different request",
+                        key.1
+                    )));
+                }
+                return match entry {
+                    Entry::Running { .. } => Err(ServerError::Conflict(format!(
+                        "Request with idempotency key {} is still running",
+                        key.1
+                    ))),
+                    Entry::Done { response, .. } => Ok(Claim::Replay(response.clone())),
+                };
+            }
+            _ => {}
+        }
+
+        if entries.len() >= self.capacity {
+            entries.retain(|_, entry| now.duration_since(entry.since()) < self.window);
+        }
+        if entries.len() >= self.capacity {
+            // Evict the oldest completed request
+            let oldest = entries
+                .iter()
+                .filter(|(_, entry)| matches!(entry, Entry::Done { .. }))
+                .min_by_key(|(_, entry)| entry.since())
+                .map(|(key, _)| key.clone());
+            match oldest {
+                Some(oldest) => {
+                    entries.remove(&oldest);
+                }
+                None => {
+                    return Err(ServerError::Unavailable(
+                        "too many idempotent requests running".to_string(),
+                    ))
+                }
+            }
+        }
+        entries.insert(
+            key.clone(),
+            Entry::Running {
+                fingerprint,
+                started: now,
+            },
+        );
+        Ok(Claim::Run)
+    }
+}
+
+/// A claimed key, released unless its response is stored
+struct Running<'a> {
+    cache: &'a IdempotencyCache,
+    key: EntryKey,
+    fingerprint: u64,
+    done: bool,
+}
+
+impl Running<'_> {
+    fn store(mut self, response: StoredResponse) {
+        self.cache.lock().insert(
+            self.key.clone(),
+            Entry::Done {
+                fingerprint: self.fingerprint,
+                stored: Instant::now(),
+                response,
+            },
+        );
+        self.done = true;
+    }
+}
+
+impl Drop for Running<'_> {
+    fn drop(&mut self) {
+        if !self.done {
+            self.cache.lock().remove(&self.key);
+        }
+    }
+}
+
+/// Deduplicate requests carrying an `Idempotency-Key` header
+pub async fn idempotent(
+    State(cache): State<Arc<IdempotencyCache>>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ServerError> {
+    let Some(key) = request.headers().get(IDEMPOTENCY_KEY_HEADER) else {
+        return Ok(next.run(request).await);
+    };
+    let key = key
+        .to_str()
+        .ok()
+        .map(str::trim)
+        .filter(|k| !k.is_empty() && k.len() <= MAX_KEY_LENGTH)
+        .ok_or_else(|| {
+            ServerError::InvalidRequest(format!(
+                "Idempotency key must be 1 to {} visible ASCII characters",
+                MAX_KEY_LENGTH
+            ))
+        })?
+        .to_string();
+    let tenant = request
+        .extensions()
+        .get::<TenantId>()
+        .cloned()
+        .unwrap_or_else(TenantId::default_tenant);
+
+    let (parts, body) = request.into_parts();
+    let body = axum::body::to_bytes(body, MAX_BODY_BYTES)
+        .await
+        .map_err(|e| ServerError::InvalidRequest(e.to_string()))?;
+    let mut hasher = DefaultHasher::new();
+    parts.method.hash(&mut hasher);
+    parts.uri.hash(&mut hasher);
+    parts
+        .headers
+        .get(ACCEPT)
+        .map(HeaderValue::as_bytes)
+        .hash(&mut hasher);
+    body.hash(&mut hasher);
+    let fingerprint = hasher.finish();
+
+    let key = (tenant, key);
+    let running = match cache.claim(&key, fingerprint)? {
+        Claim::Replay(response) => return Ok(response.replay()),
+        Claim::Run => Running {
+            cache: &cache,
+            key,
+            fingerprint,
+            done: false,
+        },
+    };
+
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+    let (parts, body) = response.into_parts();
+    let body = axum::body::to_bytes(body, usize::MAX)
+        .await
+        .map_err(|e| ServerError::Internal(e.to_string()))?;
+    if !parts.status.is_server_error() {
+        running.store(StoredResponse {
+            status: parts.status,
+            headers: parts.headers.clone(),
+            body: body.clone(),
+        });
+    }
+    Ok(Response::from_parts(parts, Body::from(body)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use axum::routing::post;
+    use axum::Router;
+    use tower::ServiceExt;
+
+    /// Router echoing the request body and counting the calls that ran
+    fn router(cache: Arc<IdempotencyCache>, calls: Arc<AtomicUsize>) -> Router {
+        Router::new()
+            .route(
+                "/price",
+                post(move |body: String| async move {
+                    let n = calls.fetch_add(1, Ordering::SeqCst) + 1;
+                    if body == "fail" {
+                        return (StatusCode::SERVICE_UNAVAILABLE, String::new());
+                    }
+                    (StatusCode::OK, format!("{}:{}", body, n))
+                }),
+            )
+            .route_layer(axum::middleware::from_fn_with_state(cache, idempotent))
+    }
+
+    fn request(key: Option<&str>, tenant: &str, body: &'static str) -> Request {
+        let mut request = Request::post("/price");
+        if let Some(key) = key {
+            request = request.header(IDEMPOTENCY_KEY_HEADER, key);
+        }
+        let mut request = request.body(Body::from(body)).unwrap();
+        request
+            .extensions_mut()
+            .insert(TenantId::new(tenant).unwrap());
+        request
+    }
+
+    async fn send(app: &Router, request: Request) -> (u16, bool, String) {
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status().as_u16();
+        let replayed = response.headers().contains_key(REPLAYED_HEADER);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, replayed, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_retry_replays_stored_response() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let cache = Arc::new(IdempotencyCache::new(Duration::from_secs(60), 100));
+        let app = router(cache.clone(), calls.clone());
+
+        assert_eq!(
+            send(&app, request(Some("k1"), "rates", "a")).await,
+            (200, false, "a:1".to_string())
+        );
+        assert_eq!(
+            send(&app, request(Some("k1"), "rates", "a")).await,
+            (200, true, "a:1".to_string())
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Different request under the same key
+        let (status, _, _) = send(&app, request(Some("k1"), "rates", "b")).await;
+        assert_eq!(status, 409);
+
+        // Same key of another tenant, and requests without a key, run
+        assert_eq!(
+            send(&app, request(Some("k1"), "fx", "a")).await,
+            (200, false, "a:2".to_string())
+        );
+        assert_eq!(
+            send(&app, request(None, "rates", "a")).await,
+            (200, false, "a:3".to_string())
+        );
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_server_errors_and_expired_keys_run_again() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = router(
+            Arc::new(IdempotencyCache::new(Duration::from_secs(60), 100)),
+            calls.clone(),
+        );
+        send(&app, request(Some("k1"), "rates", "fail")).await;
+        let (status, replayed, _) = send(&app, request(Some("k1"), "rates", "fail")).await;
+        assert_eq!((status, replayed), (503, false));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let expired = router(
+            Arc::new(IdempotencyCache::new(Duration::ZERO, 100)),
+            calls.clone(),
+        );
+        send(&expired, request(Some("k2"), "rates", "a")).await;
+        let (_, replayed, body) = send(&expired, request(Some("k2"), "rates", "a")).await;
+        assert!(!replayed);
+        assert_eq!(body, "a:4");
+    }
+
+    #[test]
+    fn test_running_key_conflicts_and_capacity_evicts_oldest() {
+        let cache = IdempotencyCache::new(Duration::from_secs(60), 2);
+        let key = |k: &str| (TenantId::default_tenant(), k.to_string());
+        let stored = StoredResponse {
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+            body: Bytes::new(),
+        };
+
+        assert!(matches!(cache.claim(&key("a"), 1), Ok(Claim::Run)));
+        assert!(matches!(
+            cache.claim(&key("a"), 1),
+            Err(ServerError::Conflict(_))
+        ));
+        Running {
+            cache: &cache,
+            key: key("a"),
+            fingerprint: 1,
+            done: false,
+        }
+        .store(stored.clone());
+        assert!(matches!(cache.claim(&key("a"), 1), Ok(Claim::Replay(_))));
+
+        // A dropped claim releases its key
+        assert!(matches!(cache.claim(&key("b"), 2), Ok(Claim::Run)));
+        drop(Running {
+            cache: &cache,
+            key: key("b"),
+            fingerprint: 2,
+            done: false,
+        });
+        assert_eq!(cache.len(), 1);
+
+        assert!(matches!(cache.claim(&key("b"), 2), Ok(Claim::Run)));
+        // The stored response of "a" is evicted for "c"; with every key
+        // running there is nothing left to evict
+        assert!(matches!(cache.claim(&key("c"), 3), Ok(Claim::Run)));
+        assert_eq!(cache.len(), 2);
+        assert!(matches!(
+            cache.claim(&key("a"), 1),
+            Err(ServerError::Unavailable(_))
+        ));
+    }
+}
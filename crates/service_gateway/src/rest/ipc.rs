@@ -0,0 +1,235 @@
+//! Arrow IPC encoding of batch results
+//!
+//! Clients that send `Accept: application/vnd.apache.arrow.stream` receive
+//! batch pricing and exposure results as an Arrow IPC stream instead of
+//! JSON, which pyarrow, pandas and Polars map into columns without parsing.
+//! Scalar aggregates travel as schema metadata, and each Greek column
+//! carries its quoting unit as field metadata.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use arrow::array::{ArrayRef, Float64Array, UInt64Array};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::ipc::writer::StreamWriter;
+use arrow::record_batch::RecordBatch;
+use axum::http::header::{ACCEPT, CONTENT_TYPE};
+use axum::http::HeaderMap;
+use axum::response::{IntoResponse, Response};
+
+use pricer_pricing::mc::Greek;
+
+use super::handlers::{ExposureResponse, PriceResponse};
+use crate::error::ServerError;
+
+/// Media type of an Arrow IPC stream
+pub const ARROW_STREAM: &str = "application/vnd.apache.arrow.stream";
+
+/// Maximum rows per record batch in an encoded stream
+const BATCH_ROWS: usize = 65_536;
+
+/// Whether the client asked for an Arrow IPC stream
+pub fn accepts_arrow(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|range| {
+            range
+                .split(';')
+                .next()
+                .is_some_and(|media| media.trim().eq_ignore_ascii_case(ARROW_STREAM))
+        })
+}
+
+/// One row per priced instrument, in request order
+pub fn price_results(
+    results: &[PriceResponse],
+    total_value: f64,
+) -> Result<RecordBatch, ServerError> {
+    const GREEKS: [Greek; 5] = [
+        Greek::Delta,
+        Greek::Gamma,
+        Greek::Vega,
+        Greek::Theta,
+        Greek::Rho,
+    ];
+
+    let mut fields = vec![
+        Field::new("index", DataType::UInt64, false),
+        Field::new("price", DataType::Float64, false),
+    ];
+    fields.extend(GREEKS.iter().map(|greek| {
+        Field::new(greek.name(), DataType::Float64, true).with_metadata(HashMap::from([(
+            "unit".to_string(),
+            greek.unit().to_string(),
+        )]))
+    }));
+    let schema = Schema::new(fields).with_metadata(HashMap::from([(
+        "total_value".to_string(),
+        total_value.to_string(),
+    )]));
+
+    let mut columns: Vec<ArrayRef> = vec![
+        Arc::new(UInt64Array::from_iter_values(0..results.len() as u64)),
+        Arc::new(Float64Array::from_iter_values(
+            results.iter().map(|r| r.greeks.price),
+        )),
+    ];
+    columns.extend(GREEKS.iter().map(|&greek| -> ArrayRef {
+        Arc::new(
+            results
+                .iter()
+                .map(|r| r.greeks.value(greek))
+                .collect::<Float64Array>(),
+        )
+    }));
+
+    RecordBatch::try_new(Arc::new(schema), columns)
+        .map_err(|e| ServerError::Internal(e.to_string()))
+}
+
+/// One row per time grid point
+pub fn exposure_profile(
+    time_grid: &[f64],
+    exposure: &ExposureResponse,
+) -> Result<RecordBatch, ServerError> {
+    if exposure.ee.len() != time_grid.len() || exposure.pfe_95.len() != time_grid.len() {
+        return Err(ServerError::Internal(format!(
+            "Exposure profile length does not match time grid of {} points",
+            time_grid.len()
+        )));
+    }
+
+    let schema = Schema::new(vec![
+        Field::new("time", DataType::Float64, false),
+        Field::new("ee", DataType::Float64, false),
+        Field::new("pfe_95", DataType::Float64, false),
+    ])
+    .with_metadata(HashMap::from([
+        ("epe".to_string(), exposure.epe.to_string()),
+        ("ene".to_string(), exposure.ene.to_string()),
+    ]));
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(Float64Array::from(time_grid.to_vec())),
+        Arc::new(Float64Array::from(exposure.ee.clone())),
+        Arc::new(Float64Array::from(exposure.pfe_95.clone())),
+    ];
+
+    RecordBatch::try_new(Arc::new(schema), columns)
+        .map_err(|e| ServerError::Internal(e.to_string()))
+}
+
+/// Encode a batch as an IPC stream of bounded record batches
+pub fn encode_stream(batch: &RecordBatch) -> Result<Vec<u8>, ServerError> {
+    let encode = || {
+        let mut writer = StreamWriter::try_new(Vec::new(), batch.schema_ref())?;
+        let mut offset = 0;
+        while offset < batch.num_rows() {
+            let len = BATCH_ROWS.min(batch.num_rows() - offset);
+            writer.write(&batch.slice(offset, len))?;
+            offset += len;
+        }
+        writer.finish()?;
+        writer.into_inner()
+    };
+    encode().map_err(|e: arrow::error::ArrowError| ServerError::Internal(e.to_string()))
+}
+
+/// HTTP response carrying a batch as an Arrow IPC stream
+pub fn stream_response(batch: &RecordBatch) -> Result<Response, ServerError> {
+    let body = encode_stream(batch)?;
+    Ok(([(CONTENT_TYPE, ARROW_STREAM)], body).into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::Array;
+    use arrow::ipc::reader::StreamReader;
+    use axum::http::HeaderValue;
+    use pricer_pricing::greeks::{BumpSpec, Sensitivity, StructuredGreeks};
+
+    fn decode(bytes: Vec<u8>) -> Vec<RecordBatch> {
+        StreamReader::try_new(std::io::Cursor::new(bytes), None)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_accepts_arrow() {
+        let mut headers = HeaderMap::new();
+        assert!(!accepts_arrow(&headers));
+
+        headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
+        assert!(!accepts_arrow(&headers));
+
+        headers.insert(
+            ACCEPT,
+            HeaderValue::from_static("application/json;q=0.5, application/vnd.apache.arrow.stream"),
+        );
+        assert!(accepts_arrow(&headers));
+    }
+
+    #[test]
+    fn test_price_results_roundtrip() {
+        let results = vec![
+            PriceResponse::from(StructuredGreeks::new(10.45, 0.0, "USD").with_sensitivity(
+                Sensitivity::new(Greek::Delta, "spot", 0.64, BumpSpec::Analytic),
+            )),
+            PriceResponse::from(StructuredGreeks::new(5.57, 0.0, "USD")),
+        ];
+        let batch = price_results(&results, 16.02).unwrap();
+        let decoded = decode(encode_stream(&batch).unwrap());
+
+        assert_eq!(decoded.len(), 1);
+        let batch = &decoded[0];
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.schema().metadata()["total_value"], "16.02");
+
+        let price = batch.column_by_name("price").unwrap();
+        let price = price.as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(price.values(), &[10.45, 5.57]);
+
+        let delta = batch.column_by_name("delta").unwrap();
+        assert_eq!(delta.null_count(), 1);
+
+        let schema = batch.schema();
+        let rho = schema.field_with_name("rho").unwrap();
+        assert_eq!(rho.metadata()["unit"], "per 1bp");
+    }
+
+    #[test]
+    fn test_large_results_are_chunked() {
+        let results: Vec<PriceResponse> = (0..BATCH_ROWS + 10)
+            .map(|i| PriceResponse::from(StructuredGreeks::new(i as f64, 0.0, "USD")))
+            .collect();
+        let batch = price_results(&results, 0.0).unwrap();
+        let decoded = decode(encode_stream(&batch).unwrap());
+
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(decoded[0].num_rows(), BATCH_ROWS);
+        assert_eq!(decoded[1].num_rows(), 10);
+    }
+
+    #[test]
+    fn test_exposure_profile() {
+        let exposure = ExposureResponse {
+            ee: vec![0.0, 1.0, 2.0],
+            epe: 1.0,
+            ene: -0.5,
+            pfe_95: vec![0.0, 3.0, 4.0],
+        };
+        let batch = exposure_profile(&[0.0, 0.5, 1.0], &exposure).unwrap();
+        assert_eq!(batch.num_rows(), 3);
+        assert_eq!(batch.schema().metadata()["epe"], "1");
+
+        assert!(matches!(
+            exposure_profile(&[0.0, 1.0], &exposure),
+            Err(ServerError::Internal(_))
+        ));
+    }
+}
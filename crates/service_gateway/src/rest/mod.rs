@@ -1,25 +1,136 @@
 //! REST API routes (Axum)
 
+use std::sync::Arc;
+
 use axum::{
+    middleware,
     routing::{get, post},
-    Router,
+    Extension, Router,
 };
+use tower_http::compression::predicate::{NotForContentType, Predicate, SizeAbove};
+use tower_http::compression::CompressionLayer;
+
+use crate::tenant::{authenticate, ApiKeys, TenantScoped};
 
+mod analytics;
 mod handlers;
+mod idempotency;
+#[cfg(feature = "arrow")]
+mod ipc;
+mod pagination;
+mod predeal;
+mod results;
+mod verification;
+
+pub use handlers::CalibrationState;
+pub use idempotency::IdempotencyCache;
+pub use results::ResultStores;
+pub use verification::{PriceVerifier, VerificationConfig};
 
 /// Create the REST API router
-pub fn create_router() -> Router {
+///
+/// API v1 routes resolve the tenant of the request's API key; calibrations
+/// and results are kept per tenant. Pricing and XVA requests carrying an
+/// `Idempotency-Key` are deduplicated through `idempotency`.
+pub fn create_router(
+    keys: Arc<ApiKeys>,
+    calibration: Arc<TenantScoped<CalibrationState>>,
+    results: Arc<ResultStores>,
+    idempotency: Arc<IdempotencyCache>,
+) -> Router {
     Router::new()
         // Health check
         .route("/health", get(handlers::health))
         // API v1 routes
-        .nest("/api/v1", api_v1_routes())
+        .nest(
+            "/api/v1",
+            api_v1_routes(idempotency).layer(middleware::from_fn_with_state(keys, authenticate)),
+        )
+        .layer(Extension(calibration))
+        .layer(Extension(results))
 }
 
-fn api_v1_routes() -> Router {
-    Router::new()
+/// Compress responses of at least `min_size` bytes with gzip and/or zstd,
+/// as the client's `Accept-Encoding` allows
+pub fn compression(gzip: bool, zstd: bool, min_size: u16) -> CompressionLayer<impl Predicate> {
+    CompressionLayer::new().gzip(gzip).zstd(zstd).compress_when(
+        SizeAbove::new(min_size)
+            .and(NotForContentType::GRPC)
+            .and(NotForContentType::IMAGES),
+    )
+}
+
+fn api_v1_routes(idempotency: Arc<IdempotencyCache>) -> Router {
+    // Pricing and XVA calculations, replayed to retried requests
+    let calculations = Router::new()
         .route("/price", post(handlers::price_instrument))
         .route("/price/batch", post(handlers::price_portfolio))
-        .route("/calibrate", post(handlers::calibrate))
         .route("/exposure", post(handlers::calculate_exposure))
+        .route("/predeal", post(predeal::predeal_check))
+        .route("/analytics/swap", post(analytics::swap_analytics));
+
+    #[cfg(feature = "distributed")]
+    let calculations = calculations.route(
+        "/exposure/distributed",
+        post(crate::distributed::distributed_exposure),
+    );
+
+    Router::new()
+        .route("/calibrate", post(handlers::calibrate))
+        .route(
+            "/calibrate/history/:model",
+            get(handlers::calibration_history),
+        )
+        .route(
+            "/calibrate/alerts/:model",
+            get(handlers::calibration_alerts),
+        )
+        .route("/verification", get(verification::verification_metrics))
+        .route("/results", get(results::list_results))
+        .route(
+            "/results/:kind/:as_of/:run_id/:name",
+            get(results::get_result),
+        )
+        .merge(calculations.route_layer(middleware::from_fn_with_state(
+            idempotency,
+            idempotency::idempotent,
+        )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::header::{ACCEPT_ENCODING, CONTENT_ENCODING};
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_responses_are_compressed_as_accepted() {
+        let app = Router::new()
+            .route("/health", get(handlers::health))
+            .layer(compression(false, true, 0));
+        let encoding = |accept: &'static str| {
+            let app = app.clone();
+            async move {
+                let response = app
+                    .oneshot(
+                        Request::get("/health")
+                            .header(ACCEPT_ENCODING, accept)
+                            .body(Body::empty())
+                            .unwrap(),
+                    )
+                    .await
+                    .unwrap();
+                response
+                    .headers()
+                    .get(CONTENT_ENCODING)
+                    .map(|e| e.to_str().unwrap().to_string())
+            }
+        };
+
+        assert_eq!(encoding("gzip, zstd").await.as_deref(), Some("zstd"));
+        // gzip is not offered by this server
+        assert_eq!(encoding("gzip").await, None);
+    }
 }
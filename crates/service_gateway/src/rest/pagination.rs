@@ -0,0 +1,137 @@
+//! Pagination and field selection of large results
+//!
+//! Listings take `limit` with either `offset` or the opaque `cursor` of the
+//! previous page. Pages are answered with the listing's total size in
+//! `X-Total-Count` and, while more remain, the cursor of the next page in
+//! `X-Next-Cursor`; the body keeps the shape of an unpaged response, so
+//! clients that do not page are unaffected.
+
+use std::ops::Range;
+
+use axum::http::HeaderValue;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use crate::error::ServerError;
+
+/// Header carrying the number of items in the whole listing
+pub const TOTAL_COUNT_HEADER: &str = "x-total-count";
+
+/// Header carrying the cursor of the next page
+pub const NEXT_CURSOR_HEADER: &str = "x-next-cursor";
+
+/// Page of a listing
+#[derive(Debug, Default, Deserialize)]
+pub struct PageParams {
+    /// Items per page (default: all)
+    pub limit: Option<usize>,
+    /// Items to skip
+    pub offset: Option<usize>,
+    /// Cursor of the previous page's `X-Next-Cursor`
+    pub cursor: Option<String>,
+}
+
+impl PageParams {
+    /// Items of a listing of `total` on this page; `resume` maps a cursor to
+    /// the index the page starts at
+    pub fn window(
+        &self,
+        total: usize,
+        resume: impl FnOnce(&str) -> Result<usize, ServerError>,
+    ) -> Result<Range<usize>, ServerError> {
+        if self.limit == Some(0) {
+            return Err(ServerError::InvalidRequest(
+                "limit must be positive".to_string(),
+            ));
+        }
+        let start = match (&self.cursor, self.offset) {
+            (Some(_), Some(_)) => {
+                return Err(ServerError::InvalidRequest(
+                    "offset and cursor are mutually exclusive".to_string(),
+                ))
+            }
+            (Some(cursor), None) => resume(cursor)?,
+            (None, offset) => offset.unwrap_or(0),
+        };
+        let start = start.min(total);
+        let end = self
+            .limit
+            .map_or(total, |limit| start.saturating_add(limit).min(total));
+        Ok(start..end)
+    }
+}
+
+/// Columns or fields to return
+#[derive(Debug, Default, Deserialize)]
+pub struct FieldParams {
+    /// Comma-separated names (default: all)
+    pub fields: Option<String>,
+}
+
+impl FieldParams {
+    /// Selected names, or `None` for all
+    pub fn names(&self) -> Option<Vec<&str>> {
+        self.fields.as_deref().map(|fields| {
+            fields
+                .split(',')
+                .map(str::trim)
+                .filter(|f| !f.is_empty())
+                .collect()
+        })
+    }
+}
+
+/// JSON page with its paging headers
+pub fn paged<T: Serialize>(body: T, total: usize, next_cursor: Option<String>) -> Response {
+    let mut response = Json(body).into_response();
+    let headers = response.headers_mut();
+    headers.insert(TOTAL_COUNT_HEADER, HeaderValue::from(total));
+    if let Some(cursor) = next_cursor.and_then(|c| HeaderValue::from_str(&c).ok()) {
+        headers.insert(NEXT_CURSOR_HEADER, cursor);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(limit: Option<usize>, offset: Option<usize>, cursor: Option<&str>) -> PageParams {
+        PageParams {
+            limit,
+            offset,
+            cursor: cursor.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_window() {
+        let resume = |c: &str| Ok(c.parse::<usize>().unwrap());
+        assert_eq!(page(None, None, None).window(10, resume).unwrap(), 0..10);
+        assert_eq!(
+            page(Some(4), Some(8), None).window(10, resume).unwrap(),
+            8..10
+        );
+        assert_eq!(
+            page(Some(4), None, Some("3")).window(10, resume).unwrap(),
+            3..7
+        );
+        assert_eq!(
+            page(None, Some(20), None).window(10, resume).unwrap(),
+            10..10
+        );
+
+        assert!(page(Some(0), None, None).window(10, resume).is_err());
+        assert!(page(None, Some(1), Some("1")).window(10, resume).is_err());
+    }
+
+    #[test]
+    fn test_field_names() {
+        let fields = FieldParams {
+            fields: Some("pv, cva,,".to_string()),
+        };
+        assert_eq!(fields.names(), Some(vec!["pv", "cva"]));
+        assert_eq!(FieldParams::default().names(), None);
+    }
+}
@@ -0,0 +1,428 @@
+//! What-if pre-deal check
+//!
+//! Values the counterparty's existing equity book with and without the
+//! candidate trade on the same simulated spot paths, so the difference in
+//! each metric is free of Monte Carlo noise between the two runs:
+//!
+//! - incremental CVA and EE/PFE from the netted exposure profiles
+//! - PFE limit headroom before and after the trade
+//! - SA-CCR EAD of the netting set before and after (unmargined, one
+//!   single-name equity hedging set)
+
+use axum::Json;
+use pricer_pricing::rng::PricerRng;
+use pricer_risk::exposure::ExposureCalculator;
+use pricer_risk::limits::{Limit, LimitChecker, LimitError, LimitInputs};
+use pricer_risk::portfolio::{CounterpartyId, CreditParams, PortfolioBuilder};
+use pricer_risk::xva::compute_cva;
+use serde::{Deserialize, Serialize};
+
+use super::handlers::normal_cdf;
+use crate::error::ServerError;
+
+/// SA-CCR alpha multiplier
+const SA_CCR_ALPHA: f64 = 1.4;
+/// SA-CCR supervisory factor for single-name equity
+const EQUITY_SUPERVISORY_FACTOR: f64 = 0.32;
+/// SA-CCR supervisory option volatility for single-name equity
+const EQUITY_SUPERVISORY_VOLATILITY: f64 = 1.2;
+/// SA-CCR floor of the PFE multiplier
+const MULTIPLIER_FLOOR: f64 = 0.05;
+
+/// Trade in a pre-deal check
+#[derive(Clone, Debug, Deserialize)]
+pub struct PreDealTrade {
+    /// "european_option" or "forward"
+    pub instrument_type: String,
+    pub strike: f64,
+    pub expiry: f64,
+    pub is_call: Option<bool>,
+    /// Units of the underlying, negative when sold
+    pub quantity: f64,
+}
+
+/// Counterparty of a pre-deal check
+#[derive(Deserialize)]
+pub struct PreDealCounterparty {
+    pub id: String,
+    pub hazard_rate: f64,
+    pub lgd: f64,
+    /// PFE limit over the whole time grid
+    pub pfe_limit: Option<f64>,
+}
+
+/// Pre-deal check request
+#[derive(Deserialize)]
+pub struct PreDealRequest {
+    pub counterparty: PreDealCounterparty,
+    /// Trades already in the netting set with the counterparty
+    #[serde(default)]
+    pub existing_trades: Vec<PreDealTrade>,
+    pub candidate: PreDealTrade,
+    pub spot: f64,
+    pub volatility: f64,
+    pub rate: f64,
+    pub time_grid: Vec<f64>,
+    pub num_paths: Option<usize>,
+    pub seed: Option<u64>,
+    /// PFE confidence level (default 0.95)
+    pub confidence: Option<f64>,
+}
+
+/// Metric before and after the candidate trade
+#[derive(Debug, Serialize)]
+pub struct Incremental {
+    pub before: f64,
+    pub after: f64,
+    pub delta: f64,
+}
+
+impl Incremental {
+    fn new(before: f64, after: f64) -> Self {
+        Self {
+            before,
+            after,
+            delta: after - before,
+        }
+    }
+}
+
+/// PFE limit headroom before and after the candidate trade
+#[derive(Debug, Serialize)]
+pub struct LimitImpact {
+    pub limit: f64,
+    pub headroom_before: f64,
+    pub headroom_after: f64,
+    /// "within", "warning" or "breach" after the trade
+    pub status_after: String,
+}
+
+/// Pre-deal check response
+#[derive(Debug, Serialize)]
+pub struct PreDealResponse {
+    pub counterparty_id: String,
+    pub candidate_value: f64,
+    pub cva: Incremental,
+    pub epe: Incremental,
+    pub peak_pfe: Incremental,
+    /// Change in EE at each time point
+    pub ee_delta: Vec<f64>,
+    /// Change in PFE at each time point
+    pub pfe_delta: Vec<f64>,
+    pub limit: Option<LimitImpact>,
+    pub sa_ccr_ead: Incremental,
+}
+
+/// Run a what-if pre-deal check
+pub async fn predeal_check(
+    Json(request): Json<PreDealRequest>,
+) -> Result<Json<PreDealResponse>, ServerError> {
+    run(&request).map(Json)
+}
+
+fn run(request: &PreDealRequest) -> Result<PreDealResponse, ServerError> {
+    validate(request)?;
+    let credit = CreditParams::new(request.counterparty.hazard_rate, request.counterparty.lgd)
+        .map_err(|e| ServerError::InvalidRequest(e.to_string()))?;
+    let confidence = request.confidence.unwrap_or(0.95);
+    let grid = &request.time_grid;
+
+    // [scenario][time] netted values without and with the candidate
+    let paths = simulate_spots(request);
+    let value = |trades: &[&PreDealTrade], spot: f64, t: f64| -> f64 {
+        trades
+            .iter()
+            .map(|trade| trade_value(trade, spot, t, request))
+            .sum()
+    };
+    let existing: Vec<&PreDealTrade> = request.existing_trades.iter().collect();
+    let mut combined = existing.clone();
+    combined.push(&request.candidate);
+    let netted = |trades: &[&PreDealTrade]| -> Vec<Vec<f64>> {
+        paths
+            .iter()
+            .map(|path| {
+                path.iter()
+                    .zip(grid)
+                    .map(|(&s, &t)| value(trades, s, t))
+                    .collect()
+            })
+            .collect()
+    };
+    let before = netted(&existing);
+    let after = netted(&combined);
+
+    let ee_before = ExposureCalculator::expected_exposure(&before);
+    let ee_after = ExposureCalculator::expected_exposure(&after);
+    let pfe_before = ExposureCalculator::potential_future_exposure(&before, confidence);
+    let pfe_after = ExposureCalculator::potential_future_exposure(&after, confidence);
+
+    let limit = match request.counterparty.pfe_limit {
+        Some(amount) => Some(limit_impact(request, amount, &pfe_before, &pfe_after)?),
+        None => None,
+    };
+
+    let difference = |a: &[f64], b: &[f64]| a.iter().zip(b).map(|(x, y)| y - x).collect();
+    Ok(PreDealResponse {
+        counterparty_id: request.counterparty.id.clone(),
+        candidate_value: trade_value(&request.candidate, request.spot, 0.0, request),
+        cva: Incremental::new(
+            compute_cva(&ee_before, grid, &credit),
+            compute_cva(&ee_after, grid, &credit),
+        ),
+        epe: Incremental::new(
+            ExposureCalculator::expected_positive_exposure(&ee_before, grid),
+            ExposureCalculator::expected_positive_exposure(&ee_after, grid),
+        ),
+        peak_pfe: Incremental::new(
+            ExposureCalculator::peak_pfe(&pfe_before),
+            ExposureCalculator::peak_pfe(&pfe_after),
+        ),
+        ee_delta: difference(&ee_before, &ee_after),
+        pfe_delta: difference(&pfe_before, &pfe_after),
+        limit,
+        sa_ccr_ead: Incremental::new(
+            sa_ccr_ead(&existing, request),
+            sa_ccr_ead(&combined, request),
+        ),
+    })
+}
+
+fn validate(request: &PreDealRequest) -> Result<(), ServerError> {
+    let invalid = |message: &str| Err(ServerError::InvalidRequest(message.to_string()));
+    if request.time_grid.len() < 2 || request.time_grid.windows(2).any(|w| w[1] <= w[0]) {
+        return invalid("time_grid must have at least two increasing points");
+    }
+    if request.time_grid[0] < 0.0 {
+        return invalid("time_grid must start at or after zero");
+    }
+    if !(request.spot > 0.0 && request.volatility > 0.0) {
+        return invalid("spot and volatility must be positive");
+    }
+    if request.num_paths == Some(0) {
+        return invalid("num_paths must be positive");
+    }
+    for trade in request.existing_trades.iter().chain([&request.candidate]) {
+        if !matches!(
+            trade.instrument_type.as_str(),
+            "vanilla_option" | "european_option" | "forward"
+        ) {
+            return Err(ServerError::InvalidRequest(format!(
+                "Unknown instrument type: {}",
+                trade.instrument_type
+            )));
+        }
+        if !(trade.strike > 0.0 && trade.expiry > 0.0) {
+            return invalid("strike and expiry must be positive");
+        }
+    }
+    Ok(())
+}
+
+/// Geometric Brownian motion spot at each grid time, `[scenario][time]`
+fn simulate_spots(request: &PreDealRequest) -> Vec<Vec<f64>> {
+    let seed = request.seed.unwrap_or(42);
+    let sigma = request.volatility;
+    let drift = request.rate - 0.5 * sigma * sigma;
+    (0..request.num_paths.unwrap_or(5_000))
+        .map(|p| {
+            let mut rng = PricerRng::from_seed(seed.wrapping_add(p as u64));
+            let mut spot = request.spot;
+            let mut previous = 0.0;
+            request
+                .time_grid
+                .iter()
+                .map(|&t| {
+                    let dt = t - previous;
+                    spot *= (drift * dt + sigma * dt.sqrt() * rng.gen_normal()).exp();
+                    previous = t;
+                    spot
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Black-Scholes value of a trade at time `t`, zero once it has expired
+fn trade_value(trade: &PreDealTrade, spot: f64, t: f64, request: &PreDealRequest) -> f64 {
+    let tau = trade.expiry - t;
+    if tau <= 0.0 {
+        return 0.0;
+    }
+    let discount = (-request.rate * tau).exp();
+    let unit = match trade.instrument_type.as_str() {
+        "forward" => spot - trade.strike * discount,
+        _ => {
+            let sigma_sqrt = request.volatility * tau.sqrt();
+            let d1 = ((spot / trade.strike).ln()
+                + (request.rate + 0.5 * request.volatility.powi(2)) * tau)
+                / sigma_sqrt;
+            let d2 = d1 - sigma_sqrt;
+            if trade.is_call.unwrap_or(true) {
+                spot * normal_cdf(d1) - trade.strike * discount * normal_cdf(d2)
+            } else {
+                trade.strike * discount * normal_cdf(-d2) - spot * normal_cdf(-d1)
+            }
+        }
+    };
+    trade.quantity * unit
+}
+
+fn limit_impact(
+    request: &PreDealRequest,
+    amount: f64,
+    pfe_before: &[f64],
+    pfe_after: &[f64],
+) -> Result<LimitImpact, ServerError> {
+    let counterparty_id = CounterpartyId::new(request.counterparty.id.as_str());
+    let tenor = *request.time_grid.last().unwrap_or(&0.0);
+    let to_request_error = |e: LimitError| ServerError::InvalidRequest(e.to_string());
+    let checker = LimitChecker::new()
+        .with_limit(Limit::pfe(counterparty_id.clone(), tenor, amount).map_err(to_request_error)?)
+        .map_err(to_request_error)?;
+    let portfolio = PortfolioBuilder::new()
+        .build()
+        .map_err(|e| ServerError::Internal(e.to_string()))?;
+    let check = |pfe: &[f64]| {
+        let inputs = LimitInputs::new(request.time_grid.clone())
+            .with_pfe(counterparty_id.clone(), pfe.to_vec());
+        checker
+            .check(&portfolio, &inputs)
+            .map(|report| report.checks[0].clone())
+            .map_err(to_request_error)
+    };
+    let before = check(pfe_before)?;
+    let after = check(pfe_after)?;
+    Ok(LimitImpact {
+        limit: amount,
+        headroom_before: amount - before.usage,
+        headroom_after: amount - after.usage,
+        status_after: after.status.to_string(),
+    })
+}
+
+/// SA-CCR EAD of an unmargined netting set holding one single-name equity
+/// hedging set (MAR52)
+fn sa_ccr_ead(trades: &[&PreDealTrade], request: &PreDealRequest) -> f64 {
+    let value: f64 = trades
+        .iter()
+        .map(|trade| trade_value(trade, request.spot, 0.0, request))
+        .sum();
+    let replacement_cost = value.max(0.0);
+
+    // A single reference entity aggregates to its own effective notional
+    let effective_notional: f64 = trades
+        .iter()
+        .map(|trade| {
+            let maturity_factor = trade.expiry.min(1.0).sqrt();
+            supervisory_delta(trade, request.spot) * trade.quantity * request.spot * maturity_factor
+        })
+        .sum();
+    let add_on = EQUITY_SUPERVISORY_FACTOR * effective_notional.abs();
+
+    let multiplier = if add_on > 0.0 {
+        (MULTIPLIER_FLOOR
+            + (1.0 - MULTIPLIER_FLOOR) * (value / (2.0 * (1.0 - MULTIPLIER_FLOOR) * add_on)).exp())
+        .min(1.0)
+    } else {
+        1.0
+    };
+    SA_CCR_ALPHA * (replacement_cost + multiplier * add_on)
+}
+
+/// Supervisory delta of a long position (MAR52.40)
+fn supervisory_delta(trade: &PreDealTrade, spot: f64) -> f64 {
+    if trade.instrument_type == "forward" {
+        return 1.0;
+    }
+    let sigma_sqrt = EQUITY_SUPERVISORY_VOLATILITY * trade.expiry.sqrt();
+    let d1 = ((spot / trade.strike).ln() + 0.5 * sigma_sqrt * sigma_sqrt) / sigma_sqrt;
+    if trade.is_call.unwrap_or(true) {
+        normal_cdf(d1)
+    } else {
+        -normal_cdf(-d1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(instrument_type: &str, strike: f64, is_call: bool, quantity: f64) -> PreDealTrade {
+        PreDealTrade {
+            instrument_type: instrument_type.to_string(),
+            strike,
+            expiry: 2.0,
+            is_call: Some(is_call),
+            quantity,
+        }
+    }
+
+    fn request(existing: Vec<PreDealTrade>, candidate: PreDealTrade) -> PreDealRequest {
+        PreDealRequest {
+            counterparty: PreDealCounterparty {
+                id: "CP001".to_string(),
+                hazard_rate: 0.02,
+                lgd: 0.6,
+                pfe_limit: Some(5_000.0),
+            },
+            existing_trades: existing,
+            candidate,
+            spot: 100.0,
+            volatility: 0.2,
+            rate: 0.03,
+            time_grid: vec![0.0, 0.5, 1.0, 1.5, 2.0],
+            num_paths: Some(2_000),
+            seed: Some(7),
+            confidence: None,
+        }
+    }
+
+    #[test]
+    fn test_bought_option_adds_exposure() {
+        let existing = vec![trade("forward", 100.0, true, 50.0)];
+        let response = run(&request(
+            existing,
+            trade("european_option", 100.0, true, 20.0),
+        ))
+        .unwrap();
+
+        assert!(response.candidate_value > 0.0);
+        assert!(response.cva.delta > 0.0);
+        assert!(response.peak_pfe.delta > 0.0);
+        assert!(response.ee_delta.iter().all(|&d| d >= 0.0));
+        assert!(response.sa_ccr_ead.delta > 0.0);
+        let limit = response.limit.unwrap();
+        assert!(limit.headroom_after < limit.headroom_before);
+    }
+
+    #[test]
+    fn test_offsetting_trade_reduces_exposure() {
+        let existing = vec![trade("forward", 100.0, true, 50.0)];
+        let response = run(&request(existing, trade("forward", 100.0, true, -40.0))).unwrap();
+
+        assert!(response.cva.delta < 0.0);
+        assert!(response.epe.delta < 0.0);
+        assert!(response.sa_ccr_ead.delta < 0.0);
+        assert_eq!(response.limit.unwrap().status_after, "within");
+    }
+
+    #[test]
+    fn test_sa_ccr_ead_of_forward() {
+        // At-the-money-forward: V = 0, multiplier 1, EAD = 1.4 · 32% · N · MF
+        let forward = trade("forward", 100.0 * (0.03_f64 * 2.0).exp(), true, 10.0);
+        let req = request(Vec::new(), forward.clone());
+        let ead = sa_ccr_ead(&[&forward], &req);
+        assert!((ead - 1.4 * 0.32 * 1_000.0).abs() < 1e-9);
+        assert_eq!(sa_ccr_ead(&[], &req), 0.0);
+    }
+
+    #[test]
+    fn test_rejects_invalid_request() {
+        let mut bad = request(Vec::new(), trade("swaption", 100.0, true, 1.0));
+        assert!(matches!(run(&bad), Err(ServerError::InvalidRequest(_))));
+        bad.candidate = trade("forward", 100.0, true, 1.0);
+        bad.time_grid = vec![1.0];
+        assert!(matches!(run(&bad), Err(ServerError::InvalidRequest(_))));
+    }
+}
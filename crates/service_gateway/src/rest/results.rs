@@ -0,0 +1,359 @@
+//! Stored result queries
+//!
+//! Lists and reads the pricing runs, exposure profiles, XVA results and
+//! report tables that EOD batches persist to the result store
+//! (`NEUTRYX_RESULTS_DIR`, in memory if unset). Each tenant sees only its
+//! own store, under its prefix of the directory on a multi-tenant server.
+//!
+//! Both endpoints page with `limit`, `offset` and `cursor` (see
+//! `pagination`); a result table also takes `fields` to return only some of
+//! its columns.
+
+use std::sync::Arc;
+
+use axum::extract::{Path, Query};
+use axum::response::Response;
+use axum::Extension;
+use chrono::NaiveDate;
+use infra_store::{ResultKey, ResultKind, ResultQuery, ResultStore, StoreError};
+use serde::Deserialize;
+
+use super::pagination::{paged, FieldParams, PageParams};
+use crate::error::ServerError;
+use crate::tenant::{TenantId, TenantScoped};
+
+/// Result stores by tenant
+pub type ResultStores = TenantScoped<dyn ResultStore>;
+
+/// Filters of a result listing
+#[derive(Debug, Default, Deserialize)]
+pub struct ResultsParams {
+    /// pricing_run, exposure_profile, xva or report
+    pub kind: Option<String>,
+    pub run_id: Option<String>,
+    /// Earliest valuation date (YYYY-MM-DD)
+    pub from: Option<NaiveDate>,
+    /// Latest valuation date (YYYY-MM-DD)
+    pub to: Option<NaiveDate>,
+}
+
+impl From<StoreError> for ServerError {
+    fn from(error: StoreError) -> Self {
+        match error {
+            StoreError::InvalidKey(message) => ServerError::InvalidRequest(message),
+            StoreError::NotFound(message) => ServerError::NotFound(message),
+            other => ServerError::Internal(other.to_string()),
+        }
+    }
+}
+
+/// Keys of the tenant's stored results matching the filters, by valuation
+/// date
+///
+/// The cursor of a page is the path of its last key.
+pub async fn list_results(
+    Extension(tenant): Extension<TenantId>,
+    Extension(stores): Extension<Arc<ResultStores>>,
+    Query(params): Query<ResultsParams>,
+    Query(page): Query<PageParams>,
+) -> Result<Response, ServerError> {
+    let query = ResultQuery {
+        kind: params
+            .kind
+            .as_deref()
+            .map(str::parse::<ResultKind>)
+            .transpose()?,
+        run_id: params.run_id,
+        from: params.from,
+        to: params.to,
+    };
+    let mut keys = stores.get(&tenant)?.list(&query)?;
+    let total = keys.len();
+    let window = page.window(total, |cursor| {
+        let after = parse_key(cursor)?;
+        Ok(keys.partition_point(|key| *key <= after))
+    })?;
+    let next_cursor =
+        (window.end < total && !window.is_empty()).then(|| keys[window.end - 1].to_string());
+    keys.truncate(window.end);
+    keys.drain(..window.start);
+    Ok(paged(keys, total, next_cursor))
+}
+
+/// Rows of one of the tenant's stored result frames
+///
+/// The cursor of a page is the index of the row after it.
+pub async fn get_result(
+    Extension(tenant): Extension<TenantId>,
+    Extension(stores): Extension<Arc<ResultStores>>,
+    Path((kind, as_of, run_id, name)): Path<(String, NaiveDate, String, String)>,
+    Query(page): Query<PageParams>,
+    Query(fields): Query<FieldParams>,
+) -> Result<Response, ServerError> {
+    let key = ResultKey::new(kind.parse()?, as_of, run_id, name)?;
+    let frame = stores
+        .get(&tenant)?
+        .get(&key)?
+        .ok_or_else(|| ServerError::NotFound(format!("No stored result {}", key)))?;
+    let frame = match fields.names() {
+        Some(names) => frame.select(&names).map_err(|e| match e {
+            StoreError::NotFound(column) => {
+                ServerError::InvalidRequest(format!("Unknown field: {}", column))
+            }
+            other => other.into(),
+        })?,
+        None => frame,
+    };
+
+    let total = frame.num_rows();
+    let window = page.window(total, |cursor| {
+        cursor
+            .parse()
+            .map_err(|_| ServerError::InvalidRequest(format!("Invalid cursor {}", cursor)))
+    })?;
+    let next_cursor = (window.end < total).then(|| window.end.to_string());
+    let frame = if window.len() == total {
+        frame
+    } else {
+        frame.rows(window.start, window.len())
+    };
+    Ok(paged(frame, total, next_cursor))
+}
+
+/// Key from its path, `kind/as_of/run_id/name`
+fn parse_key(path: &str) -> Result<ResultKey, ServerError> {
+    let invalid = || ServerError::InvalidRequest(format!("Invalid cursor {}", path));
+    let mut segments = path.splitn(4, '/');
+    let (Some(kind), Some(as_of), Some(run_id), Some(name)) = (
+        segments.next(),
+        segments.next(),
+        segments.next(),
+        segments.next(),
+    ) else {
+        return Err(invalid());
+    };
+    let as_of = as_of.parse().map_err(|_| invalid())?;
+    Ok(ResultKey::new(kind.parse()?, as_of, run_id, name)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rest::pagination::{NEXT_CURSOR_HEADER, TOTAL_COUNT_HEADER};
+    use axum::http::HeaderMap;
+    use infra_store::storage::{ColumnData, MemoryResultStore};
+    use infra_store::ResultFrame;
+    use serde::de::DeserializeOwned;
+
+    fn stores() -> Arc<ResultStores> {
+        Arc::new(TenantScoped::new(|_: &TenantId| {
+            Ok(Arc::new(MemoryResultStore::new()) as Arc<dyn ResultStore>)
+        }))
+    }
+
+    async fn json<T: DeserializeOwned>(response: Response) -> (T, HeaderMap) {
+        let headers = response.headers().clone();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (serde_json::from_slice(&body).unwrap(), headers)
+    }
+
+    fn page(limit: Option<usize>, cursor: Option<&str>) -> Query<PageParams> {
+        Query(PageParams {
+            limit,
+            cursor: cursor.map(String::from),
+            ..Default::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_list_and_get_results() {
+        let stores = stores();
+        let tenant = TenantId::new("rates").unwrap();
+        let other = TenantId::new("fx").unwrap();
+        let as_of = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let key = ResultKey::new(ResultKind::Xva, as_of, "eod", "netting_sets").unwrap();
+        let frame = ResultFrame::new()
+            .with_column("cva", ColumnData::Float(vec![Some(1.25)]))
+            .unwrap();
+        stores.get(&tenant).unwrap().put(&key, &frame).unwrap();
+
+        let (keys, _) = json::<Vec<ResultKey>>(
+            list_results(
+                Extension(tenant.clone()),
+                Extension(stores.clone()),
+                Query(ResultsParams {
+                    kind: Some("xva".to_string()),
+                    from: Some(as_of),
+                    ..Default::default()
+                }),
+                page(None, None),
+            )
+            .await
+            .unwrap(),
+        )
+        .await;
+        assert_eq!(keys, vec![key]);
+
+        let path = |name: &str| {
+            Path((
+                "xva".to_string(),
+                as_of,
+                "eod".to_string(),
+                name.to_string(),
+            ))
+        };
+        let get = |tenant: &TenantId, name: &str| {
+            get_result(
+                Extension(tenant.clone()),
+                Extension(stores.clone()),
+                path(name),
+                page(None, None),
+                Query(FieldParams::default()),
+            )
+        };
+        let (loaded, _) = json::<ResultFrame>(get(&tenant, "netting_sets").await.unwrap()).await;
+        assert_eq!(loaded, frame);
+
+        let missing = get(&tenant, "ee").await;
+        assert!(matches!(missing, Err(ServerError::NotFound(_))));
+
+        // Other tenants see none of the results
+        let hidden = get(&other, "netting_sets").await;
+        assert!(matches!(hidden, Err(ServerError::NotFound(_))));
+        let (keys, _) = json::<Vec<ResultKey>>(
+            list_results(
+                Extension(other),
+                Extension(stores.clone()),
+                Query(ResultsParams::default()),
+                page(None, None),
+            )
+            .await
+            .unwrap(),
+        )
+        .await;
+        assert!(keys.is_empty());
+
+        let invalid = list_results(
+            Extension(tenant),
+            Extension(stores),
+            Query(ResultsParams {
+                kind: Some("pnl".to_string()),
+                ..Default::default()
+            }),
+            page(None, None),
+        )
+        .await;
+        assert!(matches!(invalid, Err(ServerError::InvalidRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_pages_of_keys_follow_cursor() {
+        let stores = stores();
+        let tenant = TenantId::default_tenant();
+        let store = stores.get(&tenant).unwrap();
+        for day in 1..=5 {
+            let as_of = NaiveDate::from_ymd_opt(2024, 3, day).unwrap();
+            let key = ResultKey::new(ResultKind::PricingRun, as_of, "eod", "trades").unwrap();
+            store.put(&key, &ResultFrame::new()).unwrap();
+        }
+
+        let mut cursor: Option<String> = None;
+        let mut pages = Vec::new();
+        loop {
+            let response = list_results(
+                Extension(tenant.clone()),
+                Extension(stores.clone()),
+                Query(ResultsParams::default()),
+                page(Some(2), cursor.as_deref()),
+            )
+            .await
+            .unwrap();
+            let (keys, headers) = json::<Vec<ResultKey>>(response).await;
+            assert_eq!(headers[TOTAL_COUNT_HEADER], "5");
+            pages.push(keys.len());
+            cursor = headers
+                .get(NEXT_CURSOR_HEADER)
+                .map(|c| c.to_str().unwrap().to_string());
+            if cursor.is_none() {
+                break;
+            }
+        }
+        assert_eq!(pages, vec![2, 2, 1]);
+
+        let bad = list_results(
+            Extension(tenant),
+            Extension(stores),
+            Query(ResultsParams::default()),
+            page(Some(2), Some("pricing_run/2024-03-01")),
+        )
+        .await;
+        assert!(matches!(bad, Err(ServerError::InvalidRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn test_rows_and_fields_of_a_result() {
+        let stores = stores();
+        let tenant = TenantId::default_tenant();
+        let as_of = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        let key = ResultKey::new(ResultKind::PricingRun, as_of, "eod", "trades").unwrap();
+        let frame = ResultFrame::new()
+            .with_column(
+                "trade_id",
+                ColumnData::Text((1..=5).map(|i| Some(format!("T{}", i))).collect()),
+            )
+            .unwrap()
+            .with_column(
+                "pv",
+                ColumnData::Float((1..=5).map(|i| Some(i as f64)).collect()),
+            )
+            .unwrap();
+        stores.get(&tenant).unwrap().put(&key, &frame).unwrap();
+
+        let get = |page: Query<PageParams>, fields: Option<&str>| {
+            get_result(
+                Extension(tenant.clone()),
+                Extension(stores.clone()),
+                Path((
+                    "pricing_run".to_string(),
+                    as_of,
+                    "eod".to_string(),
+                    "trades".to_string(),
+                )),
+                page,
+                Query(FieldParams {
+                    fields: fields.map(String::from),
+                }),
+            )
+        };
+
+        let (rows, headers) =
+            json::<ResultFrame>(get(page(Some(2), Some("2")), Some("pv")).await.unwrap()).await;
+        assert_eq!(rows.columns.len(), 1);
+        assert_eq!(
+            rows.column("pv"),
+            Some(&ColumnData::Float(vec![Some(3.0), Some(4.0)]))
+        );
+        assert_eq!(headers[TOTAL_COUNT_HEADER], "5");
+        assert_eq!(headers[NEXT_CURSOR_HEADER], "4");
+
+        let (rows, headers) = json::<ResultFrame>(
+            get(
+                Query(PageParams {
+                    offset: Some(4),
+                    ..Default::default()
+                }),
+                None,
+            )
+            .await
+            .unwrap(),
+        )
+        .await;
+        assert_eq!(rows.num_rows(), 1);
+        assert!(!headers.contains_key(NEXT_CURSOR_HEADER));
+
+        let unknown = get(page(None, None), Some("cva")).await;
+        assert!(matches!(unknown, Err(ServerError::InvalidRequest(_))));
+    }
+}
@@ -0,0 +1,346 @@
+//! Independent price verification
+//!
+//! In dual-run mode, instruments of the configured types
+//! (`NEUTRYX_VERIFY_INSTRUMENTS`) are priced a second time by Monte Carlo
+//! simulation and compared with the closed-form price returned to the
+//! client. A difference beyond
+//!
+//! ```text
+//! max(absolute_tolerance, relative_tolerance × |price|) + 3 × MC standard error
+//! ```
+//!
+//! is a breach: it is logged, added to the response's `warnings` and
+//! counted in the metrics served at `GET /api/v1/verification`.
+//!
+//! Options and forwards are simulated directly; a forward is the mean of
+//! `S_T − K` over one run, so its standard error needs no assumption about
+//! how separate call and put estimates co-vary.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::{Arc, Mutex};
+
+use axum::{Extension, Json};
+use pricer_pricing::mc::{
+    generate_gbm_paths, GbmParams, MonteCarloConfig, MonteCarloPricer, PathWorkspace, PayoffParams,
+};
+use pricer_pricing::rng::PricerRng;
+use serde::Serialize;
+use tracing::warn;
+
+use super::handlers::PriceRequest;
+use crate::error::ServerError;
+
+/// Name of the secondary engine in responses
+const ENGINE: &str = "monte_carlo";
+
+/// Standard errors of Monte Carlo noise allowed on top of the tolerance
+const STD_ERROR_MULTIPLE: f64 = 3.0;
+
+/// Dual-run configuration
+#[derive(Clone, Debug)]
+pub struct VerificationConfig {
+    /// Instrument types priced by both engines
+    pub instrument_types: BTreeSet<String>,
+    /// Monte Carlo paths of the secondary engine
+    pub n_paths: usize,
+    /// Seed of the secondary engine, so verification is reproducible
+    pub seed: u64,
+    /// Difference allowed relative to the primary price
+    pub relative_tolerance: f64,
+    /// Difference always allowed, for prices near zero
+    pub absolute_tolerance: f64,
+}
+
+impl VerificationConfig {
+    /// Verify the given instrument types with default settings
+    pub fn new(instrument_types: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            instrument_types: instrument_types.into_iter().collect(),
+            n_paths: 100_000,
+            seed: 42,
+            relative_tolerance: 0.01,
+            absolute_tolerance: 1e-4,
+        }
+    }
+
+    /// Set the Monte Carlo paths of the secondary engine
+    pub fn with_paths(mut self, n_paths: usize) -> Self {
+        self.n_paths = n_paths.max(2);
+        self
+    }
+
+    /// Set the relative and absolute tolerances
+    pub fn with_tolerance(mut self, relative: f64, absolute: f64) -> Self {
+        self.relative_tolerance = relative.abs();
+        self.absolute_tolerance = absolute.abs();
+        self
+    }
+}
+
+/// Comparison of the primary price with the secondary engine's
+#[derive(Clone, Debug, Serialize)]
+pub struct Verification {
+    pub engine: &'static str,
+    pub price: f64,
+    pub std_error: f64,
+    /// Primary minus secondary price
+    pub difference: f64,
+    /// Largest difference accepted
+    pub tolerance: f64,
+    pub within_tolerance: bool,
+}
+
+impl Verification {
+    /// Warning of a breach
+    pub fn warning(&self) -> Option<String> {
+        (!self.within_tolerance).then(|| {
+            format!(
+                "Price differs from {} price {:.6} by {:.6} (tolerance {:.6})",
+                self.engine, self.price, self.difference, self.tolerance
+            )
+        })
+    }
+}
+
+/// Verification counts of one instrument type
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct VerificationCounts {
+    pub checked: u64,
+    pub breaches: u64,
+}
+
+/// Verification metrics response
+#[derive(Debug, Serialize)]
+pub struct VerificationMetrics {
+    pub enabled: bool,
+    pub instrument_types: Vec<String>,
+    /// Counts by instrument type
+    pub counts: BTreeMap<String, VerificationCounts>,
+}
+
+/// Prices instruments with the secondary engine and records the outcome
+pub struct PriceVerifier {
+    config: VerificationConfig,
+    counts: Mutex<BTreeMap<String, VerificationCounts>>,
+}
+
+impl PriceVerifier {
+    pub fn new(config: VerificationConfig) -> Self {
+        Self {
+            config,
+            counts: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Check the primary price of a request, if its type is verified
+    pub fn verify(
+        &self,
+        request: &PriceRequest,
+        primary: f64,
+    ) -> Result<Option<Verification>, ServerError> {
+        if !self
+            .config
+            .instrument_types
+            .contains(&request.instrument_type)
+        {
+            return Ok(None);
+        }
+        let Some((price, std_error)) = self.secondary_price(request)? else {
+            return Ok(None);
+        };
+
+        let difference = primary - price;
+        let tolerance = (self.config.relative_tolerance * primary.abs())
+            .max(self.config.absolute_tolerance)
+            + STD_ERROR_MULTIPLE * std_error;
+        let verification = Verification {
+            engine: ENGINE,
+            price,
+            std_error,
+            difference,
+            tolerance,
+            within_tolerance: difference.abs() <= tolerance,
+        };
+
+        let mut counts = self.counts.lock().unwrap_or_else(|e| e.into_inner());
+        let counts = counts.entry(request.instrument_type.clone()).or_default();
+        counts.checked += 1;
+        if let Some(warning) = verification.warning() {
+            counts.breaches += 1;
+            warn!(
+                "{} verification breach: {}",
+                request.instrument_type, warning
+            );
+        }
+        Ok(Some(verification))
+    }
+
+    /// Counts so far
+    pub fn metrics(&self) -> VerificationMetrics {
+        VerificationMetrics {
+            enabled: true,
+            instrument_types: self.config.instrument_types.iter().cloned().collect(),
+            counts: self
+                .counts
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .clone(),
+        }
+    }
+
+    /// Monte Carlo price and standard error, for the types it can price
+    fn secondary_price(&self, request: &PriceRequest) -> Result<Option<(f64, f64)>, ServerError> {
+        let config = MonteCarloConfig::builder()
+            .n_paths(self.config.n_paths)
+            .n_steps(1)
+            .seed(self.config.seed)
+            .build()
+            .map_err(|e| ServerError::Internal(e.to_string()))?;
+        let mut pricer =
+            MonteCarloPricer::new(config).map_err(|e| ServerError::Internal(e.to_string()))?;
+        let gbm = GbmParams::new(
+            request.spot,
+            request.rate,
+            request.volatility,
+            request.expiry,
+        );
+        let discount = (-request.rate * request.expiry).exp();
+
+        let priced = match request.instrument_type.as_str() {
+            "vanilla_option" | "european_option" => {
+                let payoff = if request.is_call.unwrap_or(true) {
+                    PayoffParams::call(request.strike)
+                } else {
+                    PayoffParams::put(request.strike)
+                };
+                let result = pricer.price_european(gbm, payoff, discount);
+                Some((result.price, result.std_error))
+            }
+            "forward" => Some(self.simulate_forward(gbm, request.strike)),
+            _ => None,
+        };
+        Ok(priced)
+    }
+
+    /// Mean of `S_T − K` and its standard error over one seeded run
+    fn simulate_forward(&self, gbm: GbmParams, strike: f64) -> (f64, f64) {
+        let n_paths = self.config.n_paths;
+        let mut workspace = PathWorkspace::new(n_paths, 1);
+        PricerRng::from_seed(self.config.seed).fill_normal(workspace.randoms_mut());
+        generate_gbm_paths(&mut workspace, gbm, n_paths, 1);
+
+        let (sum, sum_sq) = workspace.paths()[..2 * n_paths]
+            .chunks_exact(2)
+            .map(|path| path[1] - strike)
+            .fold((0.0, 0.0), |(sum, sum_sq), x| (sum + x, sum_sq + x * x));
+        let n = n_paths as f64;
+        let mean = sum / n;
+        let variance = ((sum_sq - n * mean * mean) / (n - 1.0)).max(0.0);
+        (mean, (variance / n).sqrt())
+    }
+}
+
+/// Verification metrics
+pub async fn verification_metrics(
+    verifier: Option<Extension<Arc<PriceVerifier>>>,
+) -> Json<VerificationMetrics> {
+    Json(match verifier {
+        Some(Extension(verifier)) => verifier.metrics(),
+        None => VerificationMetrics {
+            enabled: false,
+            instrument_types: Vec::new(),
+            counts: BTreeMap::new(),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(instrument_type: &str) -> PriceRequest {
+        PriceRequest {
+            instrument_type: instrument_type.to_string(),
+            strike: 100.0,
+            expiry: 1.0,
+            is_call: Some(true),
+            spot: 100.0,
+            volatility: 0.2,
+            rate: 0.05,
+            currency: None,
+        }
+    }
+
+    fn verifier() -> PriceVerifier {
+        PriceVerifier::new(
+            VerificationConfig::new(["european_option".to_string(), "forward".to_string()])
+                .with_paths(50_000),
+        )
+    }
+
+    #[test]
+    fn test_agreeing_prices_pass() {
+        let verifier = verifier();
+        // Black-Scholes ATM call
+        let option = verifier
+            .verify(&request("european_option"), 10.4506)
+            .unwrap()
+            .unwrap();
+        assert!(option.within_tolerance, "{:?}", option);
+        assert!(option.std_error > 0.0);
+
+        let forward = 100.0 * 0.05_f64.exp() - 100.0;
+        let forward = verifier
+            .verify(&request("forward"), forward)
+            .unwrap()
+            .unwrap();
+        assert!(forward.within_tolerance, "{:?}", forward);
+
+        assert!(verifier
+            .verify(&request("vanilla_option"), 10.4506)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_breach_is_warned_and_counted() {
+        let verifier = verifier();
+        let verification = verifier
+            .verify(&request("european_option"), 11.0)
+            .unwrap()
+            .unwrap();
+        assert!(!verification.within_tolerance);
+        assert!(verification.warning().unwrap().contains("monte_carlo"));
+
+        verifier
+            .verify(&request("european_option"), 10.4506)
+            .unwrap();
+        let metrics = verifier.metrics();
+        assert_eq!(
+            metrics.counts["european_option"],
+            VerificationCounts {
+                checked: 2,
+                breaches: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_forward_std_error_is_that_of_the_payoff() {
+        // sd(S_T) = F √(exp(σ²T) − 1) for lognormal S_T with forward F
+        let verifier = verifier();
+        let forward = 100.0 * 0.05_f64.exp();
+        let expected = forward * (0.04_f64.exp() - 1.0).sqrt() / 50_000_f64.sqrt();
+
+        let verification = verifier
+            .verify(&request("forward"), forward - 100.0)
+            .unwrap()
+            .unwrap();
+        assert!(
+            (verification.std_error / expected - 1.0).abs() < 0.05,
+            "{} vs {}",
+            verification.std_error,
+            expected
+        );
+    }
+}
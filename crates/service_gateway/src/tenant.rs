@@ -0,0 +1,304 @@
+//! Tenant scoping
+//!
+//! Each API key configured in `NEUTRYX_API_KEYS` (`key=tenant,...`) maps to
+//! a tenant ID, so several desks or clients can share one deployment. API
+//! requests authenticate with an `X-Api-Key` (or `Authorization: Bearer`)
+//! header and are resolved to their tenant before reaching a handler; the
+//! calibration store, result store and distributed jobs are then scoped by
+//! that tenant, so no tenant sees another's calibrations, results or jobs.
+//!
+//! Without configured keys the server runs single-tenant: requests need no
+//! key and all belong to the [`DEFAULT_TENANT`], whose stores are the
+//! unscoped ones of earlier releases.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use axum::extract::{Request, State};
+use axum::http::header::AUTHORIZATION;
+use axum::middleware::Next;
+use axum::response::Response;
+use serde::Serialize;
+
+use crate::error::ServerError;
+
+/// Header carrying the API key
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Tenant of every request on a single-tenant server
+pub const DEFAULT_TENANT: &str = "default";
+
+/// Tenant identifier
+///
+/// ASCII letters, digits, `-` and `_` only, as it names tenant files and
+/// object store prefixes.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+pub struct TenantId(String);
+
+impl TenantId {
+    /// Validate a tenant identifier
+    pub fn new(id: impl Into<String>) -> Result<Self, ServerError> {
+        let id = id.into();
+        let valid = !id.is_empty()
+            && id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(ServerError::InvalidRequest(format!(
+                "Invalid tenant ID: {:?}",
+                id
+            )));
+        }
+        Ok(Self(id))
+    }
+
+    /// Tenant of a single-tenant server
+    pub fn default_tenant() -> Self {
+        Self(DEFAULT_TENANT.to_string())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Tenant's copy of a store file: `calibrations.jsonl` becomes
+    /// `calibrations.<tenant>.jsonl` in the same directory
+    pub fn scope_file(&self, path: &Path) -> PathBuf {
+        let stem = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let name = match path.extension() {
+            Some(ext) => format!("{}.{}.{}", stem, self.0, ext.to_string_lossy()),
+            None => format!("{}.{}", stem, self.0),
+        };
+        path.with_file_name(name)
+    }
+}
+
+impl fmt::Display for TenantId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// API keys and the tenants they belong to
+#[derive(Debug, Default)]
+pub struct ApiKeys {
+    tenants: HashMap<String, TenantId>,
+}
+
+impl ApiKeys {
+    /// Keys of the given tenants; single-tenant if `keys` is empty
+    pub fn new(keys: &BTreeMap<String, String>) -> Result<Self, ServerError> {
+        let tenants = keys
+            .iter()
+            .map(|(key, tenant)| Ok((key.clone(), TenantId::new(tenant.as_str())?)))
+            .collect::<Result<_, ServerError>>()?;
+        Ok(Self { tenants })
+    }
+
+    /// Whether requests are scoped by the tenant of their key
+    pub fn is_multi_tenant(&self) -> bool {
+        !self.tenants.is_empty()
+    }
+
+    /// Number of distinct tenants with a key
+    pub fn tenant_count(&self) -> usize {
+        let mut tenants: Vec<&TenantId> = self.tenants.values().collect();
+        tenants.sort();
+        tenants.dedup();
+        tenants.len()
+    }
+
+    /// Tenant of a request presenting `key`
+    pub fn resolve(&self, key: Option<&str>) -> Result<TenantId, ServerError> {
+        if !self.is_multi_tenant() {
+            return Ok(TenantId::default_tenant());
+        }
+        let key = key.ok_or_else(|| ServerError::Unauthorized("API key required".to_string()))?;
+        self.tenants
+            .get(key)
+            .cloned()
+            .ok_or_else(|| ServerError::Unauthorized("Unknown API key".to_string()))
+    }
+}
+
+/// Resolve the request's tenant from its API key, making it available to
+/// handlers as `Extension<TenantId>`
+pub async fn authenticate(
+    State(keys): State<Arc<ApiKeys>>,
+    mut request: Request,
+    next: Next,
+) -> Result<Response, ServerError> {
+    let headers = request.headers();
+    let key = headers
+        .get(API_KEY_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .or_else(|| {
+            headers
+                .get(AUTHORIZATION)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.strip_prefix("Bearer "))
+        })
+        .map(str::trim);
+    let tenant = keys.resolve(key)?;
+    request.extensions_mut().insert(tenant);
+    Ok(next.run(request).await)
+}
+
+/// Opens a tenant's instance of a scoped resource
+type Open<T> = Box<dyn Fn(&TenantId) -> Result<Arc<T>, ServerError> + Send + Sync>;
+
+/// One instance of a store or cache per tenant, opened on first use
+pub struct TenantScoped<T: ?Sized> {
+    open: Open<T>,
+    scopes: RwLock<HashMap<TenantId, Arc<T>>>,
+}
+
+impl<T: ?Sized> TenantScoped<T> {
+    /// Scope opening each tenant's instance with `open`
+    pub fn new(
+        open: impl Fn(&TenantId) -> Result<Arc<T>, ServerError> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            open: Box::new(open),
+            scopes: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// The tenant's instance, opening it if this is its first use
+    pub fn get(&self, tenant: &TenantId) -> Result<Arc<T>, ServerError> {
+        if let Some(scope) = self
+            .scopes
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(tenant)
+        {
+            return Ok(Arc::clone(scope));
+        }
+        let mut scopes = self.scopes.write().unwrap_or_else(|e| e.into_inner());
+        if let Some(scope) = scopes.get(tenant) {
+            return Ok(Arc::clone(scope));
+        }
+        let scope = (self.open)(tenant)?;
+        scopes.insert(tenant.clone(), Arc::clone(&scope));
+        Ok(scope)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::routing::get;
+    use axum::{Extension, Router};
+    use tower::ServiceExt;
+
+    fn keys() -> ApiKeys {
+        ApiKeys::new(&BTreeMap::from([
+            ("k-rates".to_string(), "rates".to_string()),
+            ("k-rates-2".to_string(), "rates".to_string()),
+            ("k-fx".to_string(), "fx".to_string()),
+        ]))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_tenant_ids_are_validated() {
+        assert!(TenantId::new("desk_A-1").is_ok());
+        assert!(TenantId::new("").is_err());
+        assert!(TenantId::new("../fx").is_err());
+        assert!(ApiKeys::new(&BTreeMap::from([("k".to_string(), "a b".to_string())])).is_err());
+    }
+
+    #[test]
+    fn test_keys_resolve_to_tenants() {
+        let keys = keys();
+        assert!(keys.is_multi_tenant());
+        assert_eq!(keys.tenant_count(), 2);
+        assert_eq!(keys.resolve(Some("k-rates-2")).unwrap().as_str(), "rates");
+        assert!(matches!(
+            keys.resolve(Some("k-eq")),
+            Err(ServerError::Unauthorized(_))
+        ));
+        assert!(matches!(
+            keys.resolve(None),
+            Err(ServerError::Unauthorized(_))
+        ));
+
+        let single = ApiKeys::default();
+        assert_eq!(single.resolve(None).unwrap(), TenantId::default_tenant());
+    }
+
+    #[test]
+    fn test_scope_file() {
+        let tenant = TenantId::new("fx").unwrap();
+        assert_eq!(
+            tenant.scope_file(Path::new("/data/calibrations.jsonl")),
+            PathBuf::from("/data/calibrations.fx.jsonl")
+        );
+        assert_eq!(
+            tenant.scope_file(Path::new("calibrations")),
+            PathBuf::from("calibrations.fx")
+        );
+    }
+
+    #[test]
+    fn test_scoped_instances_are_per_tenant() {
+        let scoped = TenantScoped::new(|tenant: &TenantId| Ok(Arc::new(tenant.to_string())));
+        let rates = TenantId::new("rates").unwrap();
+        let fx = TenantId::new("fx").unwrap();
+
+        let first = scoped.get(&rates).unwrap();
+        assert!(Arc::ptr_eq(&first, &scoped.get(&rates).unwrap()));
+        assert_eq!(*scoped.get(&fx).unwrap(), "fx");
+    }
+
+    #[tokio::test]
+    async fn test_authenticate_middleware() {
+        let app = Router::new()
+            .route(
+                "/whoami",
+                get(|Extension(tenant): Extension<TenantId>| async move { tenant.to_string() }),
+            )
+            .layer(axum::middleware::from_fn_with_state(
+                Arc::new(keys()),
+                authenticate,
+            ));
+        let call = |request: Request| {
+            let app = app.clone();
+            async move {
+                let response = app.oneshot(request).await.unwrap();
+                let status = response.status();
+                let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                    .await
+                    .unwrap();
+                (status, String::from_utf8(body.to_vec()).unwrap())
+            }
+        };
+
+        let (status, body) = call(
+            Request::get("/whoami")
+                .header(API_KEY_HEADER, "k-fx")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!((status.as_u16(), body.as_str()), (200, "fx"));
+
+        let (status, body) = call(
+            Request::get("/whoami")
+                .header(AUTHORIZATION, "Bearer k-rates")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!((status.as_u16(), body.as_str()), (200, "rates"));
+
+        let (status, _) = call(Request::get("/whoami").body(Body::empty()).unwrap()).await;
+        assert_eq!(status.as_u16(), 401);
+    }
+}
@@ -0,0 +1,40 @@
+//! Array conversion helpers
+//!
+//! Inputs accept anything exposing a one-dimensional float64 buffer (such
+//! as a numpy array) without going through Python floats one by one, and
+//! fall back to any sequence of numbers. Outputs are returned as lists,
+//! which `numpy.asarray` converts without copying element by element.
+
+use pyo3::buffer::PyBuffer;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// Extract a one-dimensional array of floats
+pub(crate) fn to_vec(obj: &Bound<'_, PyAny>) -> PyResult<Vec<f64>> {
+    if let Ok(buffer) = PyBuffer::<f64>::get_bound(obj) {
+        if buffer.dimensions() > 1 {
+            return Err(PyValueError::new_err(format!(
+                "expected a one-dimensional array, got {} dimensions",
+                buffer.dimensions()
+            )));
+        }
+        if let Ok(values) = buffer.to_vec(obj.py()) {
+            return Ok(values);
+        }
+    }
+    obj.extract()
+}
+
+/// Check that an array has the expected length
+pub(crate) fn check_len(name: &str, values: &[f64], expected: usize) -> PyResult<()> {
+    if values.len() == expected {
+        Ok(())
+    } else {
+        Err(PyValueError::new_err(format!(
+            "{} has {} points, expected {}",
+            name,
+            values.len(),
+            expected
+        )))
+    }
+}
@@ -9,7 +9,7 @@ use pyo3::prelude::*;
 // ============================================================================
 
 /// A vanilla European option
-#[pyclass]
+#[pyclass(name = "VanillaOption")]
 #[derive(Clone)]
 pub struct PyVanillaOption {
     /// Strike price
@@ -47,7 +47,7 @@ impl PyVanillaOption {
 }
 
 /// A forward contract
-#[pyclass]
+#[pyclass(name = "Forward")]
 #[derive(Clone)]
 pub struct PyForward {
     /// Strike price (delivery price)
@@ -80,7 +80,7 @@ impl PyForward {
 // ============================================================================
 
 /// Hull-White short rate model
-#[pyclass]
+#[pyclass(name = "HullWhite")]
 #[derive(Clone)]
 pub struct PyHullWhite {
     /// Mean reversion speed (alpha)
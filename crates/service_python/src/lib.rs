@@ -23,11 +23,36 @@
 //! # Price using Black-Scholes
 //! price = neutryx.price_black_scholes(option, spot=100.0, vol=0.2, rate=0.05)
 //! print(f"Option price: {price}")
+//!
+//! # Monte Carlo price and Greeks
+//! pricer = neutryx.MonteCarloPricer(n_paths=50_000, seed=42)
+//! greeks = pricer.price_with_greeks(option, spot=100.0, vol=0.2, rate=0.05)
+//!
+//! # Portfolio XVA from exposure profiles (numpy arrays or lists)
+//! portfolio = (
+//!     neutryx.PortfolioBuilder()
+//!     .add_counterparty("CP001", hazard_rate=0.02, lgd=0.6)
+//!     .add_netting_set("NS001", "CP001")
+//!     .add_trade("T001", option, "CP001", "NS001", notional=1_000_000.0)
+//!     .build()
+//! )
+//! xva = neutryx.XvaCalculator().with_funding(50.0).compute_portfolio(
+//!     portfolio, ee={"NS001": ee}, ene={"NS001": ene}, time_grid=times
+//! )
+//! print(xva.cva, xva.fva, xva.total)
 //! ```
 
+// PyO3 0.22's generated wrappers convert `PyResult` errors into `PyErr`,
+// which clippy reports against the method signature
+#![allow(clippy::useless_conversion)]
+
 use pyo3::prelude::*;
 
+mod arrays;
 mod bindings;
+mod portfolio;
+mod pricing;
+mod xva;
 
 /// Neutryx XVA Pricing Library for Python
 ///
@@ -45,6 +70,14 @@ fn neutryx(m: &Bound<'_, PyModule>) -> PyResult<()> {
     // Register pricing functions
     m.add_function(wrap_pyfunction!(bindings::price_black_scholes, m)?)?;
     m.add_function(wrap_pyfunction!(bindings::price_garman_kohlhagen, m)?)?;
+    m.add_class::<pricing::PyMonteCarloPricer>()?;
+    m.add_class::<pricing::PyGreeks>()?;
+
+    // Register portfolio and XVA types
+    m.add_class::<portfolio::PyPortfolioBuilder>()?;
+    m.add_class::<portfolio::PyPortfolio>()?;
+    m.add_class::<xva::PyXvaCalculator>()?;
+    m.add_class::<xva::PyXvaResult>()?;
 
     // Register utility functions
     m.add_function(wrap_pyfunction!(version, m)?)?;
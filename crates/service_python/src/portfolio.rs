@@ -0,0 +1,236 @@
+//! Portfolio construction bindings
+//!
+//! Builds a `pricer_risk::portfolio::Portfolio` from Python. Trades are
+//! attached to their netting sets automatically, and the usual reference
+//! checks run when the portfolio is built.
+
+use std::sync::Arc;
+
+use pricer_core::types::Currency;
+use pricer_models::instruments::{
+    Direction, ExerciseStyle, Forward, Instrument, InstrumentParams, PayoffType, VanillaOption,
+};
+use pricer_risk::portfolio::{
+    Counterparty, CounterpartyId, CreditParams, NettingSet, NettingSetId, Portfolio,
+    PortfolioBuilder, Trade, TradeId,
+};
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+
+use crate::bindings::{PyForward, PyVanillaOption};
+
+/// Smoothing width for option payoffs held in a portfolio
+const PAYOFF_SMOOTHING: f64 = 1e-6;
+
+// ============================================================================
+// Portfolio Builder
+// ============================================================================
+
+/// Builder for a portfolio of trades, counterparties and netting sets
+///
+/// Methods return the builder so calls can be chained.
+#[pyclass(name = "PortfolioBuilder")]
+#[derive(Default)]
+pub struct PyPortfolioBuilder {
+    counterparties: Vec<Counterparty>,
+    netting_sets: Vec<NettingSet>,
+    trades: Vec<Trade>,
+}
+
+#[pymethods]
+impl PyPortfolioBuilder {
+    /// Create an empty builder
+    #[new]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a counterparty with a flat hazard rate and loss given default
+    #[pyo3(signature = (counterparty_id, hazard_rate, lgd=0.6))]
+    pub fn add_counterparty<'py>(
+        mut slf: PyRefMut<'py, Self>,
+        counterparty_id: &str,
+        hazard_rate: f64,
+        lgd: f64,
+    ) -> PyResult<PyRefMut<'py, Self>> {
+        let credit = CreditParams::new(hazard_rate, lgd)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        slf.counterparties.push(Counterparty::new(
+            CounterpartyId::new(counterparty_id),
+            credit,
+        ));
+        Ok(slf)
+    }
+
+    /// Add an uncollateralised netting set with a counterparty
+    pub fn add_netting_set<'py>(
+        mut slf: PyRefMut<'py, Self>,
+        netting_set_id: &str,
+        counterparty_id: &str,
+    ) -> PyRefMut<'py, Self> {
+        slf.netting_sets.push(NettingSet::new(
+            NettingSetId::new(netting_set_id),
+            CounterpartyId::new(counterparty_id),
+        ));
+        slf
+    }
+
+    /// Add a trade on a VanillaOption or Forward
+    #[pyo3(signature = (trade_id, instrument, counterparty_id, netting_set_id, notional, currency="USD"))]
+    pub fn add_trade<'py>(
+        mut slf: PyRefMut<'py, Self>,
+        trade_id: &str,
+        instrument: &Bound<'py, PyAny>,
+        counterparty_id: &str,
+        netting_set_id: &str,
+        notional: f64,
+        currency: &str,
+    ) -> PyResult<PyRefMut<'py, Self>> {
+        let currency: Currency = currency
+            .parse()
+            .map_err(|e: pricer_core::types::CurrencyError| PyValueError::new_err(e.to_string()))?;
+        slf.trades.push(Trade::new(
+            TradeId::new(trade_id),
+            to_instrument(instrument)?,
+            currency,
+            CounterpartyId::new(counterparty_id),
+            NettingSetId::new(netting_set_id),
+            notional,
+        ));
+        Ok(slf)
+    }
+
+    /// Validate references and build the portfolio
+    pub fn build(&self) -> PyResult<PyPortfolio> {
+        let mut netting_sets = self.netting_sets.clone();
+        for netting_set in &mut netting_sets {
+            let trade_ids: Vec<TradeId> = self
+                .trades
+                .iter()
+                .filter(|trade| trade.netting_set_id() == netting_set.id())
+                .map(|trade| trade.id().clone())
+                .collect();
+            netting_set.add_trades(trade_ids);
+        }
+
+        let portfolio = PortfolioBuilder::new()
+            .add_counterparties(self.counterparties.iter().cloned())
+            .add_netting_sets(netting_sets)
+            .add_trades(self.trades.iter().cloned())
+            .build()
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(PyPortfolio {
+            inner: Arc::new(portfolio),
+        })
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PortfolioBuilder(counterparties={}, netting_sets={}, trades={})",
+            self.counterparties.len(),
+            self.netting_sets.len(),
+            self.trades.len()
+        )
+    }
+}
+
+/// Convert a Python instrument into a portfolio instrument
+fn to_instrument(instrument: &Bound<'_, PyAny>) -> PyResult<Instrument<f64>> {
+    if let Ok(option) = instrument.extract::<PyVanillaOption>() {
+        let params = InstrumentParams::new(option.strike, option.expiry, 1.0)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let payoff = if option.is_call {
+            PayoffType::Call
+        } else {
+            PayoffType::Put
+        };
+        return Ok(Instrument::Vanilla(VanillaOption::new(
+            params,
+            payoff,
+            ExerciseStyle::European,
+            PAYOFF_SMOOTHING,
+        )));
+    }
+    if let Ok(forward) = instrument.extract::<PyForward>() {
+        let forward = Forward::new(forward.strike, forward.maturity, 1.0, Direction::Long)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        return Ok(Instrument::Forward(forward));
+    }
+    Err(PyTypeError::new_err(
+        "instrument must be a VanillaOption or Forward",
+    ))
+}
+
+// ============================================================================
+// Portfolio
+// ============================================================================
+
+/// A validated portfolio
+#[pyclass(name = "Portfolio", frozen)]
+#[derive(Clone)]
+pub struct PyPortfolio {
+    pub(crate) inner: Arc<Portfolio>,
+}
+
+#[pymethods]
+impl PyPortfolio {
+    /// Number of trades
+    #[getter]
+    pub fn trade_count(&self) -> usize {
+        self.inner.trade_count()
+    }
+
+    /// Number of counterparties
+    #[getter]
+    pub fn counterparty_count(&self) -> usize {
+        self.inner.counterparty_count()
+    }
+
+    /// Number of netting sets
+    #[getter]
+    pub fn netting_set_count(&self) -> usize {
+        self.inner.netting_set_count()
+    }
+
+    /// Sum of trade notionals
+    #[getter]
+    pub fn total_notional(&self) -> f64 {
+        self.inner.total_notional()
+    }
+
+    /// Netting set identifiers, sorted
+    pub fn netting_set_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self
+            .inner
+            .netting_set_ids()
+            .map(|id| id.as_str().to_string())
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    /// Trade identifiers in a netting set, sorted
+    pub fn trades_in_netting_set(&self, netting_set_id: &str) -> Vec<String> {
+        let mut ids: Vec<String> = self
+            .inner
+            .trades_in_netting_set(&NettingSetId::new(netting_set_id))
+            .into_iter()
+            .map(|trade| trade.id().as_str().to_string())
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    fn __len__(&self) -> usize {
+        self.inner.trade_count()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Portfolio(counterparties={}, netting_sets={}, trades={})",
+            self.counterparty_count(),
+            self.netting_set_count(),
+            self.trade_count()
+        )
+    }
+}
@@ -0,0 +1,261 @@
+//! Monte Carlo pricing bindings
+//!
+//! Exposes `pricer_pricing::mc::MonteCarloPricer` for European options,
+//! with Greeks selected by name and batch pricing over arrays of market
+//! inputs.
+
+use pricer_pricing::mc::{
+    GbmParams, Greek, MonteCarloConfig, MonteCarloPricer, PayoffParams, PricingResult,
+};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::arrays::{check_len, to_vec};
+use crate::bindings::PyVanillaOption;
+
+// ============================================================================
+// Greeks
+// ============================================================================
+
+/// Price, standard error and any Greeks requested from a Monte Carlo run
+///
+/// Greeks that were not requested are None.
+#[pyclass(name = "Greeks")]
+#[derive(Clone)]
+pub struct PyGreeks {
+    /// Option price
+    #[pyo3(get)]
+    pub price: f64,
+
+    /// Monte Carlo standard error of the price
+    #[pyo3(get)]
+    pub std_error: f64,
+
+    /// dV/dS
+    #[pyo3(get)]
+    pub delta: Option<f64>,
+
+    /// d2V/dS2
+    #[pyo3(get)]
+    pub gamma: Option<f64>,
+
+    /// dV/dsigma
+    #[pyo3(get)]
+    pub vega: Option<f64>,
+
+    /// dV/dt
+    #[pyo3(get)]
+    pub theta: Option<f64>,
+
+    /// dV/dr
+    #[pyo3(get)]
+    pub rho: Option<f64>,
+
+    /// d2V/dS dsigma
+    #[pyo3(get)]
+    pub vanna: Option<f64>,
+
+    /// d2V/dsigma2
+    #[pyo3(get)]
+    pub volga: Option<f64>,
+}
+
+impl From<PricingResult> for PyGreeks {
+    fn from(result: PricingResult) -> Self {
+        Self {
+            price: result.price,
+            std_error: result.std_error,
+            delta: result.delta,
+            gamma: result.gamma,
+            vega: result.vega,
+            theta: result.theta,
+            rho: result.rho,
+            vanna: result.vanna,
+            volga: result.volga,
+        }
+    }
+}
+
+#[pymethods]
+impl PyGreeks {
+    fn __repr__(&self) -> String {
+        let mut fields = vec![
+            format!("price={}", self.price),
+            format!("std_error={}", self.std_error),
+        ];
+        for (name, value) in self.named() {
+            if let Some(value) = value {
+                fields.push(format!("{}={}", name, value));
+            }
+        }
+        format!("Greeks({})", fields.join(", "))
+    }
+}
+
+impl PyGreeks {
+    fn named(&self) -> [(&'static str, Option<f64>); 7] {
+        [
+            ("delta", self.delta),
+            ("gamma", self.gamma),
+            ("vega", self.vega),
+            ("theta", self.theta),
+            ("rho", self.rho),
+            ("vanna", self.vanna),
+            ("volga", self.volga),
+        ]
+    }
+}
+
+/// Parse a Greek name as used from Python
+fn parse_greek(name: &str) -> PyResult<Greek> {
+    match name.to_ascii_lowercase().as_str() {
+        "delta" => Ok(Greek::Delta),
+        "gamma" => Ok(Greek::Gamma),
+        "vega" => Ok(Greek::Vega),
+        "theta" => Ok(Greek::Theta),
+        "rho" => Ok(Greek::Rho),
+        "vanna" => Ok(Greek::Vanna),
+        "volga" => Ok(Greek::Volga),
+        _ => Err(PyValueError::new_err(format!("Unknown Greek: {}", name))),
+    }
+}
+
+// ============================================================================
+// Monte Carlo Pricer
+// ============================================================================
+
+/// Monte Carlo pricer for European options under GBM
+///
+/// The pricer keeps its random stream between calls, so repeated calls on
+/// the same instance draw fresh paths; create a new pricer with the same
+/// seed to reproduce a result.
+#[pyclass(name = "MonteCarloPricer")]
+pub struct PyMonteCarloPricer {
+    pricer: MonteCarloPricer,
+}
+
+#[pymethods]
+impl PyMonteCarloPricer {
+    /// Create a pricer simulating `n_paths` paths of `n_steps` steps
+    #[new]
+    #[pyo3(signature = (n_paths=10_000, n_steps=1, seed=None))]
+    pub fn new(n_paths: usize, n_steps: usize, seed: Option<u64>) -> PyResult<Self> {
+        let mut builder = MonteCarloConfig::builder()
+            .n_paths(n_paths)
+            .n_steps(n_steps);
+        if let Some(seed) = seed {
+            builder = builder.seed(seed);
+        }
+        let pricer = builder
+            .build()
+            .and_then(MonteCarloPricer::new)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(Self { pricer })
+    }
+
+    /// Number of simulated paths
+    #[getter]
+    pub fn n_paths(&self) -> usize {
+        self.pricer.config().n_paths()
+    }
+
+    /// Number of time steps per path
+    #[getter]
+    pub fn n_steps(&self) -> usize {
+        self.pricer.config().n_steps()
+    }
+
+    /// Price a vanilla option
+    ///
+    /// # Arguments
+    /// * `option` - The vanilla option to price
+    /// * `spot` - Current spot price
+    /// * `vol` - Volatility (annualised)
+    /// * `rate` - Risk-free rate (annualised)
+    ///
+    /// # Returns
+    /// The discounted option price
+    pub fn price(&mut self, option: &PyVanillaOption, spot: f64, vol: f64, rate: f64) -> f64 {
+        let (gbm, payoff, df) = inputs(option, spot, vol, rate);
+        self.pricer.price_european(gbm, payoff, df).price
+    }
+
+    /// Price a vanilla option together with the requested Greeks
+    ///
+    /// `greeks` names any of delta, gamma, vega, theta, rho, vanna and
+    /// volga; by default delta, gamma, vega, theta and rho are computed.
+    #[pyo3(signature = (option, spot, vol, rate, greeks=None))]
+    pub fn price_with_greeks(
+        &mut self,
+        option: &PyVanillaOption,
+        spot: f64,
+        vol: f64,
+        rate: f64,
+        greeks: Option<Vec<String>>,
+    ) -> PyResult<PyGreeks> {
+        let greeks = match greeks {
+            Some(names) => names
+                .iter()
+                .map(|name| parse_greek(name))
+                .collect::<PyResult<Vec<_>>>()?,
+            None => vec![
+                Greek::Delta,
+                Greek::Gamma,
+                Greek::Vega,
+                Greek::Theta,
+                Greek::Rho,
+            ],
+        };
+        let (gbm, payoff, df) = inputs(option, spot, vol, rate);
+        Ok(self
+            .pricer
+            .price_with_greeks(gbm, payoff, df, &greeks)
+            .into())
+    }
+
+    /// Price a vanilla option across arrays of spots and volatilities
+    ///
+    /// `spots` and `vols` are numpy arrays or sequences of equal length;
+    /// returns one price per element.
+    pub fn price_batch(
+        &mut self,
+        option: &PyVanillaOption,
+        spots: &Bound<'_, PyAny>,
+        vols: &Bound<'_, PyAny>,
+        rate: f64,
+    ) -> PyResult<Vec<f64>> {
+        let spots = to_vec(spots)?;
+        let vols = to_vec(vols)?;
+        check_len("vols", &vols, spots.len())?;
+
+        Ok(spots
+            .iter()
+            .zip(&vols)
+            .map(|(&spot, &vol)| self.price(option, spot, vol, rate))
+            .collect())
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "MonteCarloPricer(n_paths={}, n_steps={})",
+            self.n_paths(),
+            self.n_steps()
+        )
+    }
+}
+
+/// Model, payoff and discount factor for a vanilla option
+fn inputs(
+    option: &PyVanillaOption,
+    spot: f64,
+    vol: f64,
+    rate: f64,
+) -> (GbmParams, PayoffParams, f64) {
+    let gbm = GbmParams::new(spot, rate, vol, option.expiry);
+    let payoff = if option.is_call {
+        PayoffParams::call(option.strike)
+    } else {
+        PayoffParams::put(option.strike)
+    };
+    (gbm, payoff, (-rate * option.expiry).exp())
+}
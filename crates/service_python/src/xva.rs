@@ -0,0 +1,281 @@
+//! XVA calculation bindings
+//!
+//! Exposes `pricer_risk::xva::XvaCalculator`. Exposure profiles, time grids
+//! and discount factors are numpy arrays or sequences of equal length;
+//! discount factors default to 1.0 at every point when omitted.
+
+use std::collections::HashMap;
+
+use pricer_risk::portfolio::{CounterpartyId, CreditParams, NettingSetId};
+use pricer_risk::xva::{
+    CounterpartyXva, FundingParams, NettingSetXva, OwnCreditParams, PortfolioXva, XvaCalculator,
+};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::arrays::{check_len, to_vec};
+use crate::portfolio::PyPortfolio;
+
+// ============================================================================
+// XVA Result
+// ============================================================================
+
+/// Valuation adjustments for a netting set, counterparty or portfolio
+#[pyclass(name = "XvaResult")]
+#[derive(Clone)]
+pub struct PyXvaResult {
+    /// Credit Valuation Adjustment
+    #[pyo3(get)]
+    pub cva: f64,
+
+    /// Debit Valuation Adjustment
+    #[pyo3(get)]
+    pub dva: f64,
+
+    /// Funding Cost Adjustment
+    #[pyo3(get)]
+    pub fca: f64,
+
+    /// Funding Benefit Adjustment
+    #[pyo3(get)]
+    pub fba: f64,
+
+    /// Results by counterparty (portfolio results only)
+    by_counterparty: Vec<(String, PyXvaResult)>,
+}
+
+impl From<&NettingSetXva> for PyXvaResult {
+    fn from(xva: &NettingSetXva) -> Self {
+        Self::new(xva.cva, xva.dva, xva.fca, xva.fba)
+    }
+}
+
+impl From<&CounterpartyXva> for PyXvaResult {
+    fn from(xva: &CounterpartyXva) -> Self {
+        Self::new(xva.cva, xva.dva, xva.fca, xva.fba)
+    }
+}
+
+impl From<&PortfolioXva> for PyXvaResult {
+    fn from(xva: &PortfolioXva) -> Self {
+        let mut result = Self::new(xva.cva, xva.dva, xva.fca, xva.fba);
+        result.by_counterparty = xva
+            .by_counterparty
+            .iter()
+            .map(|cp| (cp.counterparty_id.as_str().to_string(), cp.into()))
+            .collect();
+        result
+    }
+}
+
+impl PyXvaResult {
+    fn new(cva: f64, dva: f64, fca: f64, fba: f64) -> Self {
+        Self {
+            cva,
+            dva,
+            fca,
+            fba,
+            by_counterparty: Vec::new(),
+        }
+    }
+}
+
+#[pymethods]
+impl PyXvaResult {
+    /// Funding Valuation Adjustment (FCA - FBA)
+    #[getter]
+    pub fn fva(&self) -> f64 {
+        self.fca - self.fba
+    }
+
+    /// Total adjustment (CVA - DVA + FVA)
+    #[getter]
+    pub fn total(&self) -> f64 {
+        self.cva - self.dva + self.fva()
+    }
+
+    /// Results by counterparty identifier (empty below portfolio level)
+    #[getter]
+    pub fn by_counterparty<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new_bound(py);
+        for (id, result) in &self.by_counterparty {
+            dict.set_item(id, result.clone().into_py(py))?;
+        }
+        Ok(dict)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "XvaResult(cva={}, dva={}, fca={}, fba={}, total={})",
+            self.cva,
+            self.dva,
+            self.fca,
+            self.fba,
+            self.total()
+        )
+    }
+}
+
+// ============================================================================
+// XVA Calculator
+// ============================================================================
+
+/// XVA calculator
+///
+/// Configure with `with_own_credit`, `with_funding` and `bilateral`, each
+/// of which returns the calculator.
+#[pyclass(name = "XvaCalculator")]
+#[derive(Clone, Default)]
+pub struct PyXvaCalculator {
+    calculator: XvaCalculator,
+}
+
+#[pymethods]
+impl PyXvaCalculator {
+    /// Create a calculator with default configuration
+    #[new]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set own credit for DVA
+    pub fn with_own_credit(
+        mut slf: PyRefMut<'_, Self>,
+        hazard_rate: f64,
+        lgd: f64,
+    ) -> PyResult<PyRefMut<'_, Self>> {
+        let own = OwnCreditParams::new(hazard_rate, lgd)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        slf.calculator = slf.calculator.clone().with_own_credit(own);
+        Ok(slf)
+    }
+
+    /// Set funding spreads in basis points for FVA
+    ///
+    /// `lend_bps` defaults to `borrow_bps`.
+    #[pyo3(signature = (borrow_bps, lend_bps=None))]
+    pub fn with_funding(
+        mut slf: PyRefMut<'_, Self>,
+        borrow_bps: f64,
+        lend_bps: Option<f64>,
+    ) -> PyResult<PyRefMut<'_, Self>> {
+        let funding = FundingParams::from_bps(borrow_bps, lend_bps.unwrap_or(borrow_bps));
+        funding
+            .validate()
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        slf.calculator = slf.calculator.clone().with_funding(funding);
+        Ok(slf)
+    }
+
+    /// Enable bilateral CVA/DVA
+    pub fn bilateral(mut slf: PyRefMut<'_, Self>) -> PyRefMut<'_, Self> {
+        slf.calculator = slf.calculator.clone().bilateral();
+        slf
+    }
+
+    /// Compute XVA for a single netting set from its exposure profiles
+    ///
+    /// # Arguments
+    /// * `ee` - Expected Exposure profile
+    /// * `ene` - Expected Negative Exposure profile
+    /// * `time_grid` - Time points in years
+    /// * `hazard_rate` - Counterparty hazard rate
+    /// * `lgd` - Counterparty loss given default
+    /// * `discount_factors` - Risk-free discount factors (optional)
+    #[pyo3(signature = (ee, ene, time_grid, hazard_rate, lgd=0.6, discount_factors=None))]
+    pub fn compute_netting_set(
+        &self,
+        ee: &Bound<'_, PyAny>,
+        ene: &Bound<'_, PyAny>,
+        time_grid: &Bound<'_, PyAny>,
+        hazard_rate: f64,
+        lgd: f64,
+        discount_factors: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<PyXvaResult> {
+        let time_grid = to_vec(time_grid)?;
+        let ee = profile("ee", ee, &time_grid)?;
+        let ene = profile("ene", ene, &time_grid)?;
+        let dfs = discount_factors_or_flat(discount_factors, &time_grid)?;
+        let credit = CreditParams::new(hazard_rate, lgd)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+
+        let xva = self.calculator.compute_netting_set_xva(
+            NettingSetId::new("NS"),
+            CounterpartyId::new("CP"),
+            &ee,
+            &ene,
+            &time_grid,
+            &credit,
+            &dfs,
+        );
+        Ok((&xva).into())
+    }
+
+    /// Compute XVA for a portfolio
+    ///
+    /// `ee` and `ene` map each netting set identifier to its profile;
+    /// counterparty credit comes from the portfolio.
+    #[pyo3(signature = (portfolio, ee, ene, time_grid, discount_factors=None))]
+    pub fn compute_portfolio(
+        &self,
+        portfolio: &PyPortfolio,
+        ee: HashMap<String, Bound<'_, PyAny>>,
+        ene: HashMap<String, Bound<'_, PyAny>>,
+        time_grid: &Bound<'_, PyAny>,
+        discount_factors: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<PyXvaResult> {
+        let time_grid = to_vec(time_grid)?;
+        let ee = profiles("ee", ee, &time_grid)?;
+        let ene = profiles("ene", ene, &time_grid)?;
+        let dfs = discount_factors_or_flat(discount_factors, &time_grid)?;
+
+        let xva = self
+            .calculator
+            .compute_portfolio_xva(&portfolio.inner, &ee, &ene, &time_grid, &dfs)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok((&xva).into())
+    }
+
+    fn __repr__(&self) -> String {
+        let config = self.calculator.config();
+        format!(
+            "XvaCalculator(own_credit={}, bilateral={})",
+            config.own_credit.is_some(),
+            config.bilateral
+        )
+    }
+}
+
+/// Extract a profile on the time grid
+fn profile(name: &str, values: &Bound<'_, PyAny>, time_grid: &[f64]) -> PyResult<Vec<f64>> {
+    let values = to_vec(values)?;
+    check_len(name, &values, time_grid.len())?;
+    Ok(values)
+}
+
+/// Extract profiles keyed by netting set identifier
+fn profiles(
+    name: &str,
+    values: HashMap<String, Bound<'_, PyAny>>,
+    time_grid: &[f64],
+) -> PyResult<HashMap<NettingSetId, Vec<f64>>> {
+    values
+        .into_iter()
+        .map(|(id, values)| {
+            let values = profile(&format!("{}[{}]", name, id), &values, time_grid)?;
+            Ok((NettingSetId::new(id), values))
+        })
+        .collect()
+}
+
+/// Extract discount factors, or 1.0 at every point
+fn discount_factors_or_flat(
+    values: Option<&Bound<'_, PyAny>>,
+    time_grid: &[f64],
+) -> PyResult<Vec<f64>> {
+    match values {
+        Some(values) => profile("discount_factors", values, time_grid),
+        None => Ok(vec![1.0; time_grid.len()]),
+    }
+}
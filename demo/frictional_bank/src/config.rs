@@ -3,6 +3,7 @@
 //! Handles loading and management of demo configuration from TOML files
 //! with environment variable override support.
 
+use crate::scheduler::SchedulerConfig;
 use serde::Deserialize;
 use std::path::{Path, PathBuf};
 
@@ -16,6 +17,8 @@ pub enum DemoMode {
     Quick,
     /// Custom configuration
     Custom,
+    /// Play back a recorded tick file instead of live market data
+    Replay,
 }
 
 impl Default for DemoMode {
@@ -45,6 +48,45 @@ pub struct DemoConfig {
     /// Service gateway URL
     #[serde(default = "default_gateway_url")]
     pub gateway_url: String,
+
+    /// Workflow scheduler settings
+    #[serde(default)]
+    pub scheduler: SchedulerConfig,
+
+    /// Market data replay settings (Replay mode)
+    #[serde(default)]
+    pub replay: ReplayConfig,
+}
+
+/// Market data replay settings
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReplayConfig {
+    /// Tick file or capture directory written by `adapter_feeds::capture`
+    pub tick_file: Option<PathBuf>,
+
+    /// Playback speed relative to the recording (2.0 plays twice as fast)
+    #[serde(default = "default_replay_speed")]
+    pub speed: f64,
+
+    /// Directory to capture live intraday market data into for later replay
+    pub record_dir: Option<PathBuf>,
+}
+
+fn default_replay_speed() -> f64 {
+    1.0
+}
+
+/// Fastest supported playback speed
+pub const MAX_REPLAY_SPEED: f64 = 1_000.0;
+
+impl Default for ReplayConfig {
+    fn default() -> Self {
+        Self {
+            tick_file: None,
+            speed: default_replay_speed(),
+            record_dir: None,
+        }
+    }
 }
 
 fn default_data_dir() -> PathBuf {
@@ -67,6 +109,8 @@ impl Default for DemoConfig {
             max_trades: Some(10),
             log_level: default_log_level(),
             gateway_url: default_gateway_url(),
+            scheduler: SchedulerConfig::default(),
+            replay: ReplayConfig::default(),
         }
     }
 }
@@ -92,6 +136,7 @@ impl DemoConfig {
                 "full" => DemoMode::Full,
                 "quick" => DemoMode::Quick,
                 "custom" => DemoMode::Custom,
+                "replay" => DemoMode::Replay,
                 _ => self.mode,
             };
         }
@@ -112,6 +157,20 @@ impl DemoConfig {
             self.gateway_url = gateway_url;
         }
 
+        if let Ok(tick_file) = std::env::var("DEMO_REPLAY_FILE") {
+            self.replay.tick_file = Some(PathBuf::from(tick_file));
+        }
+
+        if let Ok(record_dir) = std::env::var("DEMO_RECORD_DIR") {
+            self.replay.record_dir = Some(PathBuf::from(record_dir));
+        }
+
+        if let Ok(speed) = std::env::var("DEMO_REPLAY_SPEED") {
+            if let Ok(speed) = speed.parse() {
+                self.replay.speed = speed;
+            }
+        }
+
         self
     }
 
@@ -154,11 +213,25 @@ impl DemoConfig {
             errors.push("data_dir cannot be empty".to_string());
         }
 
+        // Validate scheduled jobs
+        errors.extend(self.scheduler.validation_errors());
+
         // Mode-specific validation
         if self.mode == DemoMode::Quick && self.max_trades.is_none() {
             errors.push("Quick mode requires max_trades to be set".to_string());
         }
 
+        if self.mode == DemoMode::Replay && self.replay.tick_file.is_none() {
+            errors.push("Replay mode requires replay.tick_file to be set".to_string());
+        }
+
+        if !(self.replay.speed > 0.0 && self.replay.speed <= MAX_REPLAY_SPEED) {
+            errors.push(format!(
+                "replay.speed {} must be greater than 0 and at most {}",
+                self.replay.speed, MAX_REPLAY_SPEED
+            ));
+        }
+
         if errors.is_empty() {
             Ok(())
         } else {
@@ -365,6 +438,80 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_scheduler_section_parses_and_validates() {
+        let config: DemoConfig = toml::from_str(
+            r#"
+            max_trades = 10
+
+            [scheduler]
+            enabled = true
+
+            [[scheduler.jobs]]
+            name = "ny_eod"
+            workflow = "eod"
+            cron = "30 17 * * mon-fri"
+            market = "new_york"
+
+            [[scheduler.jobs]]
+            name = "tokyo_intraday"
+            workflow = "intraday"
+            cron = "*/10 9-15 * * *"
+            market = "tokyo"
+            business_days_only = false
+            "#,
+        )
+        .unwrap();
+        assert!(config.scheduler.enabled);
+        assert_eq!(config.scheduler.jobs.len(), 2);
+        assert!(config.scheduler.jobs[0].business_days_only);
+        assert!(!config.scheduler.jobs[1].business_days_only);
+        assert!(config.validate().is_ok());
+
+        let mut invalid = config.clone();
+        invalid.scheduler.jobs[1].cron = "every minute".to_string();
+        if let Err(ConfigError::Validation(errors)) = invalid.validate() {
+            assert!(errors.iter().any(|e| e.contains("tokyo_intraday")));
+        } else {
+            panic!("Expected validation error");
+        }
+    }
+
+    #[test]
+    fn test_replay_section_parses_and_validates() {
+        let config: DemoConfig = toml::from_str(
+            r#"
+            mode = "replay"
+
+            [replay]
+            tick_file = "demo/data/ticks/usd_session.ticks"
+            speed = 10.0
+            record_dir = "demo/data/output/ticks"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.mode, DemoMode::Replay);
+        assert_eq!(config.replay.speed, 10.0);
+        assert!(config.replay.record_dir.is_some());
+        assert!(config.validate().is_ok());
+
+        let mut missing_file = config.clone();
+        missing_file.replay.tick_file = None;
+        if let Err(ConfigError::Validation(errors)) = missing_file.validate() {
+            assert!(errors.iter().any(|e| e.contains("Replay mode")));
+        } else {
+            panic!("Expected validation error");
+        }
+
+        let mut bad_speed = config;
+        bad_speed.replay.speed = 0.0;
+        if let Err(ConfigError::Validation(errors)) = bad_speed.validate() {
+            assert!(errors.iter().any(|e| e.contains("replay.speed")));
+        } else {
+            panic!("Expected validation error");
+        }
+    }
+
     #[test]
     fn test_config_error_display() {
         let error = ConfigError::Validation(vec!["Error 1".to_string(), "Error 2".to_string()]);
@@ -7,9 +7,11 @@
 //! ## Features
 //!
 //! - **EOD Batch Processing**: End-of-day batch workflow for pricing and risk calculation
-//! - **Intraday Processing**: Real-time portfolio re-evaluation on market data updates
+//! - **Intraday Processing**: Event-driven repricing of trades affected by market data updates
 //! - **Stress Testing**: Scenario-based stress testing with preset shocks
 //! - **IRS AAD Demo**: IRS pricing with AAD vs Bump-and-Revalue performance comparison
+//! - **Scheduling**: Cron- and calendar-driven triggering of EOD and intraday workflows
+//! - **Replay**: Reproducible intraday runs driven by a recorded tick file
 //!
 //! ## Architecture Compliance
 //!
@@ -20,15 +22,19 @@
 
 pub mod config;
 pub mod error;
+pub mod scheduler;
 pub mod workflow;
 
 /// Prelude module for convenient imports
 pub mod prelude {
-    pub use crate::config::{DemoConfig, DemoMode};
+    pub use crate::config::{DemoConfig, DemoMode, ReplayConfig};
     pub use crate::error::DemoError;
+    pub use crate::scheduler::{
+        JobStatus, MarketCentre, ScheduleJobConfig, ScheduledWorkflow, Scheduler, SchedulerConfig,
+    };
     pub use crate::workflow::{
         DemoWorkflow, EodBatchWorkflow, IntradayWorkflow, IrsAadWorkflow, IrsParams,
-        ProgressCallback, StressTestWorkflow, WorkflowResult, WorkflowStep,
+        ProgressCallback, ReplayFeed, StressTestWorkflow, WorkflowResult, WorkflowStep,
     };
     #[cfg(feature = "l1l2-integration")]
     pub use crate::workflow::{
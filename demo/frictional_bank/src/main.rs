@@ -6,7 +6,7 @@ use std::net::SocketAddr;
 
 use anyhow::Result;
 use axum::{
-    extract::State,
+    extract::{Path, State},
     http::StatusCode,
     response::IntoResponse,
     routing::{get, post},
@@ -21,6 +21,7 @@ use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 #[derive(Clone)]
 struct AppState {
     config: DemoConfig,
+    scheduler: Scheduler,
 }
 
 #[tokio::main]
@@ -45,8 +46,14 @@ async fn main() -> Result<()> {
 
     let addr = SocketAddr::from(([0, 0, 0, 0], port));
 
+    // Create scheduler; the loop only runs when enabled in the config
+    let scheduler = Scheduler::new(config.clone())?;
+    if config.scheduler.enabled {
+        scheduler.start();
+    }
+
     // Create app state
-    let state = AppState { config };
+    let state = AppState { config, scheduler };
 
     // Build router
     let app = Router::new()
@@ -56,6 +63,16 @@ async fn main() -> Result<()> {
         .route("/api/v1/workflow/eod", post(eod_workflow_handler))
         .route("/api/v1/workflow/intraday", post(intraday_workflow_handler))
         .route("/api/v1/workflow/stress", post(stress_workflow_handler))
+        .route("/api/v1/schedule", get(schedule_handler))
+        .route("/api/v1/schedule/:name/pause", post(schedule_pause_handler))
+        .route(
+            "/api/v1/schedule/:name/resume",
+            post(schedule_resume_handler),
+        )
+        .route(
+            "/api/v1/schedule/:name/trigger",
+            post(schedule_trigger_handler),
+        )
         .layer(TraceLayer::new_for_http())
         .with_state(state);
 
@@ -200,3 +217,82 @@ async fn stress_workflow_handler(State(state): State<AppState>) -> impl IntoResp
         }),
     }
 }
+
+/// Scheduler status response
+#[derive(Serialize)]
+struct ScheduleResponse {
+    active: bool,
+    jobs: Vec<JobStatus>,
+}
+
+/// Scheduled jobs listing
+async fn schedule_handler(State(state): State<AppState>) -> impl IntoResponse {
+    Json(ScheduleResponse {
+        active: state.scheduler.is_active(),
+        jobs: state.scheduler.status(),
+    })
+}
+
+/// Map a scheduler lookup to a JSON response (404 for unknown jobs)
+fn job_response(result: Result<JobStatus, DemoError>) -> axum::response::Response {
+    match result {
+        Ok(status) => Json(status).into_response(),
+        Err(e) => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({ "error": e.to_string() })),
+        )
+            .into_response(),
+    }
+}
+
+/// Pause a scheduled job
+async fn schedule_pause_handler(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> impl IntoResponse {
+    tracing::info!("Pausing scheduled job '{}'", name);
+    job_response(state.scheduler.pause(&name))
+}
+
+/// Resume a paused job
+async fn schedule_resume_handler(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> impl IntoResponse {
+    tracing::info!("Resuming scheduled job '{}'", name);
+    job_response(state.scheduler.resume(&name))
+}
+
+/// Run a scheduled job immediately
+async fn schedule_trigger_handler(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> impl IntoResponse {
+    tracing::info!("Triggering scheduled job '{}'", name);
+
+    if state.scheduler.job_status(&name).is_none() {
+        return job_response(Err(DemoError::validation(format!(
+            "Unknown scheduled job '{}'",
+            name
+        ))));
+    }
+
+    match state.scheduler.trigger(&name).await {
+        Ok(result) => Json(WorkflowResponse {
+            success: result.success,
+            workflow: name,
+            message: format!("Processed {} trades", result.trades_processed),
+            duration_ms: result.duration_ms,
+            trades_processed: result.trades_processed,
+        })
+        .into_response(),
+        Err(e) => Json(WorkflowResponse {
+            success: false,
+            workflow: name,
+            message: format!("Error: {}", e),
+            duration_ms: 0,
+            trades_processed: 0,
+        })
+        .into_response(),
+    }
+}
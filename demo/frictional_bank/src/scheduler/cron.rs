@@ -0,0 +1,264 @@
+//! Five-field cron expressions.
+//!
+//! Supports the standard `minute hour day-of-month month day-of-week`
+//! layout with `*`, single values, ranges (`1-5`), steps (`*/15`, `0-30/10`)
+//! and comma-separated lists. Day-of-week accepts 0-7 (0 and 7 are Sunday)
+//! and three-letter names; months accept three-letter names. As in classic
+//! cron, when both day fields are restricted a date matches if either does.
+
+use crate::error::DemoError;
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use std::fmt;
+use std::str::FromStr;
+
+const MONTH_NAMES: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const DAY_NAMES: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// Parsed cron expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronExpr {
+    /// Original expression text
+    source: String,
+    /// Bit i set if minute i matches
+    minutes: u64,
+    /// Bit i set if hour i matches
+    hours: u32,
+    /// Bit i set if day-of-month i matches (1-31)
+    days_of_month: u32,
+    /// Bit i set if month i matches (1-12)
+    months: u16,
+    /// Bit i set if weekday i matches (0 = Sunday)
+    days_of_week: u8,
+    /// Whether the day-of-month field was `*`
+    dom_any: bool,
+    /// Whether the day-of-week field was `*`
+    dow_any: bool,
+}
+
+impl CronExpr {
+    /// Parse a five-field cron expression
+    pub fn parse(expr: &str) -> Result<Self, DemoError> {
+        let fields: Vec<&str> = expr.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(DemoError::validation(format!(
+                "Cron expression '{}' must have 5 fields, found {}",
+                expr,
+                fields.len()
+            )));
+        }
+
+        let minutes = parse_field(fields[0], 0, 59, &[])?;
+        let hours = parse_field(fields[1], 0, 23, &[])?;
+        let days_of_month = parse_field(fields[2], 1, 31, &[])?;
+        let months = parse_field(fields[3], 1, 12, &MONTH_NAMES)?;
+        let mut days_of_week = parse_field(fields[4], 0, 7, &DAY_NAMES)?;
+        // Fold 7 (Sunday) onto 0
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week |= 1;
+        }
+
+        Ok(Self {
+            source: fields.join(" "),
+            minutes,
+            hours: hours as u32,
+            days_of_month: days_of_month as u32,
+            months: months as u16,
+            days_of_week: (days_of_week & 0x7f) as u8,
+            dom_any: fields[2] == "*",
+            dow_any: fields[4] == "*",
+        })
+    }
+
+    /// Expression text
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Whether the expression fires on a date (ignoring the time fields)
+    pub fn matches_date(&self, date: NaiveDate) -> bool {
+        if self.months & (1 << date.month()) == 0 {
+            return false;
+        }
+        let dom = self.days_of_month & (1 << date.day()) != 0;
+        let dow = self.days_of_week & (1 << date.weekday().num_days_from_sunday()) != 0;
+        match (self.dom_any, self.dow_any) {
+            (true, true) => true,
+            (false, true) => dom,
+            (true, false) => dow,
+            (false, false) => dom || dow,
+        }
+    }
+
+    /// Whether the expression fires at a local date-time (to the minute)
+    pub fn matches(&self, at: NaiveDateTime) -> bool {
+        self.matches_date(at.date())
+            && self.hours & (1 << at.hour()) != 0
+            && self.minutes & (1 << at.minute()) != 0
+    }
+
+    /// Firing times on a date, in ascending order
+    pub fn times_on(&self, date: NaiveDate) -> impl Iterator<Item = NaiveDateTime> + '_ {
+        let active = self.matches_date(date);
+        (0..24u32)
+            .filter(move |h| active && self.hours & (1 << h) != 0)
+            .flat_map(move |h| {
+                (0..60u32)
+                    .filter(move |m| self.minutes & (1 << m) != 0)
+                    .filter_map(move |m| NaiveTime::from_hms_opt(h, m, 0))
+                    .map(move |t| date.and_time(t))
+            })
+    }
+}
+
+impl FromStr for CronExpr {
+    type Err = DemoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for CronExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+/// Parse one cron field into a bitset over `min..=max`
+fn parse_field(field: &str, min: u32, max: u32, names: &[&str]) -> Result<u64, DemoError> {
+    let invalid =
+        |reason: &str| DemoError::validation(format!("Invalid cron field '{}': {}", field, reason));
+    let value = |s: &str| -> Result<u32, DemoError> {
+        let lower = s.to_ascii_lowercase();
+        if let Some(i) = names.iter().position(|n| *n == lower) {
+            // Month names are 1-based, day names 0-based
+            return Ok(i as u32 + min);
+        }
+        let v: u32 = s.parse().map_err(|_| invalid("not a number"))?;
+        if v < min || v > max {
+            return Err(invalid(&format!("{} outside {}-{}", v, min, max)));
+        }
+        Ok(v)
+    };
+
+    let mut bits = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((r, s)) => {
+                let step: u32 = s.parse().map_err(|_| invalid("bad step"))?;
+                if step == 0 {
+                    return Err(invalid("step must be positive"));
+                }
+                (r, step)
+            }
+            None => (part, 1),
+        };
+
+        let (lo, hi) = if range == "*" {
+            (min, max)
+        } else if let Some((a, b)) = range.split_once('-') {
+            let (a, b) = (value(a)?, value(b)?);
+            if a > b {
+                return Err(invalid("descending range"));
+            }
+            (a, b)
+        } else {
+            let v = value(range)?;
+            // `5/15` means from 5 to the end of the range in steps of 15
+            if step > 1 {
+                (v, max)
+            } else {
+                (v, v)
+            }
+        };
+
+        for v in (lo..=hi).step_by(step as usize) {
+            bits |= 1 << v;
+        }
+    }
+    Ok(bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(y: i32, m: u32, d: u32, h: u32, min: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_opt(h, min, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_fields() {
+        let cron = CronExpr::parse("*/15 9-17 * * mon-fri").unwrap();
+        // Thursday 2026-10-15
+        assert!(cron.matches(at(2026, 10, 15, 9, 45)));
+        assert!(!cron.matches(at(2026, 10, 15, 9, 50)));
+        assert!(!cron.matches(at(2026, 10, 15, 18, 0)));
+        // Saturday
+        assert!(!cron.matches(at(2026, 10, 17, 10, 0)));
+        assert_eq!(cron.to_string(), "*/15 9-17 * * mon-fri");
+    }
+
+    #[test]
+    fn test_sunday_aliases_and_lists() {
+        let a = CronExpr::parse("0 0 * * 0").unwrap();
+        let b = CronExpr::parse("0 0 * * 7").unwrap();
+        let c = CronExpr::parse("0 0 * * SUN").unwrap();
+        assert_eq!(a.days_of_week, b.days_of_week);
+        assert_eq!(a.days_of_week, c.days_of_week);
+
+        let cron = CronExpr::parse("0,30 6 1,15 jan,jul *").unwrap();
+        assert!(cron.matches(at(2026, 7, 15, 6, 30)));
+        assert!(!cron.matches(at(2026, 8, 15, 6, 30)));
+    }
+
+    #[test]
+    fn test_day_fields_are_ored_when_both_restricted() {
+        // The 1st of the month or any Monday
+        let cron = CronExpr::parse("0 12 1 * mon").unwrap();
+        assert!(cron.matches_date(NaiveDate::from_ymd_opt(2026, 10, 1).unwrap()));
+        assert!(cron.matches_date(NaiveDate::from_ymd_opt(2026, 10, 12).unwrap()));
+        assert!(!cron.matches_date(NaiveDate::from_ymd_opt(2026, 10, 13).unwrap()));
+    }
+
+    #[test]
+    fn test_times_on_date() {
+        let cron = CronExpr::parse("0/20 8 * * *").unwrap();
+        let times: Vec<_> = cron
+            .times_on(NaiveDate::from_ymd_opt(2026, 10, 15).unwrap())
+            .collect();
+        assert_eq!(
+            times,
+            vec![
+                at(2026, 10, 15, 8, 0),
+                at(2026, 10, 15, 8, 20),
+                at(2026, 10, 15, 8, 40)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_expressions() {
+        for expr in [
+            "* * * *",
+            "60 * * * *",
+            "* 24 * * *",
+            "* * 0 * *",
+            "* * * 13 *",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "x * * * *",
+        ] {
+            assert!(
+                CronExpr::parse(expr).is_err(),
+                "'{}' should be rejected",
+                expr
+            );
+        }
+    }
+}
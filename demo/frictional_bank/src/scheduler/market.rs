@@ -0,0 +1,201 @@
+//! Market centres with their holiday calendar and local time zone.
+//!
+//! Schedules are expressed in the local time of a market so that, e.g., an
+//! EOD run at the New York close follows US daylight saving time. The
+//! time-zone rules are the current US and EU DST rules, which is all the
+//! demo needs; no tz database is involved.
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, TimeZone, Utc, Weekday};
+use infra_master::{Calendar, CalendarId};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Market centre a schedule is anchored to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MarketCentre {
+    /// New York (US Eastern time, NY calendar)
+    #[default]
+    NewYork,
+    /// London (UK time, London calendar)
+    London,
+    /// Frankfurt (Central European time, TARGET calendar)
+    Target,
+    /// Tokyo (Japan Standard Time, Tokyo calendar)
+    Tokyo,
+}
+
+impl MarketCentre {
+    /// Holiday calendar of the market
+    pub fn calendar(&self) -> Calendar {
+        Calendar::get(match self {
+            Self::NewYork => CalendarId::NewYork,
+            Self::London => CalendarId::London,
+            Self::Target => CalendarId::Target,
+            Self::Tokyo => CalendarId::Tokyo,
+        })
+    }
+
+    /// IANA name of the market's time zone (informational)
+    pub fn timezone_name(&self) -> &'static str {
+        match self {
+            Self::NewYork => "America/New_York",
+            Self::London => "Europe/London",
+            Self::Target => "Europe/Berlin",
+            Self::Tokyo => "Asia/Tokyo",
+        }
+    }
+
+    /// Whether a date is a business day in this market
+    pub fn is_business_day(&self, date: NaiveDate) -> bool {
+        self.calendar().is_business_day(date)
+    }
+
+    /// Standard (winter) UTC offset in hours
+    fn standard_offset_hours(&self) -> i64 {
+        match self {
+            Self::NewYork => -5,
+            Self::London => 0,
+            Self::Target => 1,
+            Self::Tokyo => 9,
+        }
+    }
+
+    /// UTC instants at which daylight saving starts and ends in a year
+    fn dst_bounds(&self, year: i32) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        match self {
+            // 02:00 local on the second Sunday of March / first Sunday of November
+            Self::NewYork => {
+                let start = nth_sunday(year, 3, 2).and_hms_opt(7, 0, 0)?;
+                let end = nth_sunday(year, 11, 1).and_hms_opt(6, 0, 0)?;
+                Some((Utc.from_utc_datetime(&start), Utc.from_utc_datetime(&end)))
+            }
+            // 01:00 UTC on the last Sunday of March / October
+            Self::London | Self::Target => {
+                let start = last_sunday(year, 3).and_hms_opt(1, 0, 0)?;
+                let end = last_sunday(year, 10).and_hms_opt(1, 0, 0)?;
+                Some((Utc.from_utc_datetime(&start), Utc.from_utc_datetime(&end)))
+            }
+            Self::Tokyo => None,
+        }
+    }
+
+    /// UTC offset in effect at an instant
+    pub fn utc_offset(&self, at: DateTime<Utc>) -> Duration {
+        let standard = Duration::hours(self.standard_offset_hours());
+        match self.dst_bounds(at.year()) {
+            Some((start, end)) if at >= start && at < end => standard + Duration::hours(1),
+            _ => standard,
+        }
+    }
+
+    /// Local wall-clock time at an instant
+    pub fn to_local(&self, at: DateTime<Utc>) -> NaiveDateTime {
+        at.naive_utc() + self.utc_offset(at)
+    }
+
+    /// Instant of a local wall-clock time
+    ///
+    /// Ambiguous times (autumn fall-back) resolve to the earlier instant.
+    /// Non-existent times (spring gap) are shifted forward by the gap, as
+    /// most schedulers do.
+    pub fn to_utc(&self, local: NaiveDateTime) -> DateTime<Utc> {
+        let standard = Duration::hours(self.standard_offset_hours());
+        let summer = standard + Duration::hours(1);
+        [summer, standard]
+            .into_iter()
+            .map(|offset| Utc.from_utc_datetime(&(local - offset)))
+            .find(|&candidate| self.to_local(candidate) == local)
+            .unwrap_or_else(|| Utc.from_utc_datetime(&(local - standard)))
+    }
+}
+
+impl fmt::Display for MarketCentre {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::NewYork => "New York",
+            Self::London => "London",
+            Self::Target => "TARGET",
+            Self::Tokyo => "Tokyo",
+        };
+        f.write_str(name)
+    }
+}
+
+/// The `n`-th Sunday of a month
+fn nth_sunday(year: i32, month: u32, n: u8) -> NaiveDate {
+    NaiveDate::from_weekday_of_month_opt(year, month, Weekday::Sun, n)
+        .expect("every month has at least four Sundays")
+}
+
+/// The last Sunday of a month
+fn last_sunday(year: i32, month: u32) -> NaiveDate {
+    NaiveDate::from_weekday_of_month_opt(year, month, Weekday::Sun, 5)
+        .unwrap_or_else(|| nth_sunday(year, month, 4))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local(y: i32, m: u32, d: u32, h: u32, min: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_opt(h, min, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_new_york_close_follows_dst() {
+        let ny = MarketCentre::NewYork;
+        // 17:00 EDT = 21:00 UTC, 17:00 EST = 22:00 UTC
+        assert_eq!(
+            ny.to_utc(local(2026, 7, 15, 17, 0)).naive_utc(),
+            local(2026, 7, 15, 21, 0)
+        );
+        assert_eq!(
+            ny.to_utc(local(2026, 1, 15, 17, 0)).naive_utc(),
+            local(2026, 1, 15, 22, 0)
+        );
+        // DST 2026 starts 8 March and ends 1 November
+        assert_eq!(
+            ny.to_utc(local(2026, 3, 9, 17, 0)).naive_utc(),
+            local(2026, 3, 9, 21, 0)
+        );
+        assert_eq!(
+            ny.to_utc(local(2026, 11, 2, 17, 0)).naive_utc(),
+            local(2026, 11, 2, 22, 0)
+        );
+    }
+
+    #[test]
+    fn test_european_and_tokyo_offsets() {
+        let summer = Utc.with_ymd_and_hms(2026, 6, 1, 12, 0, 0).unwrap();
+        let winter = Utc.with_ymd_and_hms(2026, 12, 1, 12, 0, 0).unwrap();
+        assert_eq!(MarketCentre::London.utc_offset(summer), Duration::hours(1));
+        assert_eq!(MarketCentre::London.utc_offset(winter), Duration::hours(0));
+        assert_eq!(MarketCentre::Target.utc_offset(summer), Duration::hours(2));
+        assert_eq!(MarketCentre::Tokyo.utc_offset(summer), Duration::hours(9));
+        assert_eq!(MarketCentre::Tokyo.utc_offset(winter), Duration::hours(9));
+    }
+
+    #[test]
+    fn test_dst_gap_and_overlap() {
+        let ny = MarketCentre::NewYork;
+        // 02:30 on 8 March 2026 does not exist; shifted to 03:30 EDT
+        let gap = ny.to_utc(local(2026, 3, 8, 2, 30));
+        assert_eq!(ny.to_local(gap), local(2026, 3, 8, 3, 30));
+        // 01:30 on 1 November 2026 occurs twice; the EDT instant is used
+        let overlap = ny.to_utc(local(2026, 11, 1, 1, 30));
+        assert_eq!(overlap.naive_utc(), local(2026, 11, 1, 5, 30));
+    }
+
+    #[test]
+    fn test_business_days() {
+        let christmas = NaiveDate::from_ymd_opt(2026, 12, 25).unwrap();
+        assert!(!MarketCentre::NewYork.is_business_day(christmas));
+        let independence_day = NaiveDate::from_ymd_opt(2025, 7, 4).unwrap();
+        assert!(!MarketCentre::NewYork.is_business_day(independence_day));
+        assert!(MarketCentre::London.is_business_day(independence_day));
+    }
+}
@@ -0,0 +1,651 @@
+//! Workflow scheduler with calendar-aware triggers.
+//!
+//! Triggers EOD and intraday workflows from cron expressions evaluated in
+//! the local time of a market centre. Fire dates that are holidays in the
+//! market's calendar are skipped, so an EOD job at the New York close never
+//! runs on Thanksgiving and follows US daylight saving time.
+//!
+//! Jobs are configured in the `[scheduler]` section of [`DemoConfig`]:
+//!
+//! ```toml
+//! [scheduler]
+//! enabled = true
+//!
+//! [[scheduler.jobs]]
+//! name = "ny_eod"
+//! workflow = "eod"
+//! cron = "30 17 * * mon-fri"
+//! market = "new_york"
+//! ```
+//!
+//! Jobs can be listed, paused, resumed and triggered on demand through
+//! [`Scheduler`], which the HTTP server exposes under `/api/v1/schedule`.
+
+mod cron;
+mod market;
+
+pub use cron::CronExpr;
+pub use market::MarketCentre;
+
+use crate::config::DemoConfig;
+use crate::error::DemoError;
+use crate::workflow::{DemoWorkflow, EodBatchWorkflow, IntradayWorkflow, WorkflowResult};
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+
+/// How far ahead to search for the next fire time
+const MAX_LOOKAHEAD_DAYS: i64 = 366 * 2;
+/// Upper bound on a single scheduler sleep, so clock changes are picked up
+const MAX_SLEEP_SECS: i64 = 60;
+
+/// Workflow a job triggers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduledWorkflow {
+    /// EOD batch workflow
+    Eod,
+    /// Intraday repricing workflow
+    Intraday,
+}
+
+impl ScheduledWorkflow {
+    /// Instantiate the workflow
+    fn build(&self) -> Box<dyn DemoWorkflow> {
+        match self {
+            Self::Eod => Box::new(EodBatchWorkflow::new()),
+            Self::Intraday => Box::new(IntradayWorkflow::new()),
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Configuration of a scheduled job
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScheduleJobConfig {
+    /// Unique job name
+    pub name: String,
+    /// Workflow to trigger
+    pub workflow: ScheduledWorkflow,
+    /// Five-field cron expression in the market's local time
+    pub cron: String,
+    /// Market whose time zone and calendar apply
+    #[serde(default)]
+    pub market: MarketCentre,
+    /// Skip dates that are holidays in the market's calendar
+    #[serde(default = "default_true")]
+    pub business_days_only: bool,
+    /// Whether the job starts active (false = paused)
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+impl ScheduleJobConfig {
+    /// Create an enabled, business-day job
+    pub fn new(
+        name: impl Into<String>,
+        workflow: ScheduledWorkflow,
+        cron: impl Into<String>,
+        market: MarketCentre,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            workflow,
+            cron: cron.into(),
+            market,
+            business_days_only: true,
+            enabled: true,
+        }
+    }
+}
+
+/// Scheduler section of the demo configuration
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SchedulerConfig {
+    /// Start the scheduler loop with the server
+    #[serde(default)]
+    pub enabled: bool,
+    /// Scheduled jobs
+    #[serde(default)]
+    pub jobs: Vec<ScheduleJobConfig>,
+}
+
+impl SchedulerConfig {
+    /// Validation messages for the job definitions
+    pub fn validation_errors(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        let mut names = HashSet::new();
+        for job in &self.jobs {
+            if job.name.is_empty() {
+                errors.push("Scheduled job name cannot be empty".to_string());
+            } else if !names.insert(job.name.as_str()) {
+                errors.push(format!("Duplicate scheduled job '{}'", job.name));
+            }
+            if let Err(e) = CronExpr::parse(&job.cron) {
+                errors.push(format!("Scheduled job '{}': {}", job.name, e));
+            }
+        }
+        errors
+    }
+}
+
+/// Cron expression anchored to a market
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobSchedule {
+    /// Firing pattern in local time
+    cron: CronExpr,
+    /// Market providing time zone and calendar
+    market: MarketCentre,
+    /// Skip market holidays
+    business_days_only: bool,
+}
+
+impl JobSchedule {
+    /// Create a schedule
+    pub fn new(cron: CronExpr, market: MarketCentre, business_days_only: bool) -> Self {
+        Self {
+            cron,
+            market,
+            business_days_only,
+        }
+    }
+
+    /// First fire time strictly after `after`
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let local_after = self.market.to_local(after);
+        let mut date = local_after.date();
+        for _ in 0..MAX_LOOKAHEAD_DAYS {
+            if !self.business_days_only || self.market.is_business_day(date) {
+                let fire = self
+                    .cron
+                    .times_on(date)
+                    .map(|local| self.market.to_utc(local))
+                    .find(|&at| at > after);
+                if fire.is_some() {
+                    return fire;
+                }
+            }
+            date = date.succ_opt()?;
+        }
+        None
+    }
+
+    /// Local wall-clock time of an instant in the schedule's market
+    pub fn local_time(&self, at: DateTime<Utc>) -> NaiveDateTime {
+        self.market.to_local(at)
+    }
+}
+
+/// How a job run was started
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TriggerKind {
+    /// Fired by the schedule
+    Scheduled,
+    /// Triggered on demand
+    Manual,
+}
+
+/// Outcome of a job run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobRun {
+    /// How the run was started
+    pub trigger: TriggerKind,
+    /// Start time
+    pub started_at: DateTime<Utc>,
+    /// Whether the workflow succeeded
+    pub success: bool,
+    /// Execution duration in milliseconds
+    pub duration_ms: u64,
+    /// Trades processed
+    pub trades_processed: usize,
+    /// Workflow errors
+    pub errors: Vec<String>,
+}
+
+/// Externally visible job state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobStatus {
+    /// Job name
+    pub name: String,
+    /// Workflow triggered
+    pub workflow: ScheduledWorkflow,
+    /// Cron expression
+    pub cron: String,
+    /// Market centre
+    pub market: MarketCentre,
+    /// Market time zone
+    pub timezone: String,
+    /// Whether the job is paused
+    pub paused: bool,
+    /// Whether a run is in progress
+    pub running: bool,
+    /// Next fire time (UTC)
+    pub next_fire: Option<DateTime<Utc>>,
+    /// Next fire time in the market's local time
+    pub next_fire_local: Option<NaiveDateTime>,
+    /// Most recent run
+    pub last_run: Option<JobRun>,
+}
+
+/// Mutable state of one job
+struct JobState {
+    config: ScheduleJobConfig,
+    schedule: JobSchedule,
+    paused: bool,
+    running: bool,
+    next_fire: Option<DateTime<Utc>>,
+    last_run: Option<JobRun>,
+}
+
+impl JobState {
+    fn status(&self) -> JobStatus {
+        JobStatus {
+            name: self.config.name.clone(),
+            workflow: self.config.workflow,
+            cron: self.schedule.cron.to_string(),
+            market: self.config.market,
+            timezone: self.config.market.timezone_name().to_string(),
+            paused: self.paused,
+            running: self.running,
+            next_fire: self.next_fire,
+            next_fire_local: self.next_fire.map(|t| self.schedule.local_time(t)),
+            last_run: self.last_run.clone(),
+        }
+    }
+}
+
+struct SchedulerInner {
+    config: DemoConfig,
+    jobs: Mutex<Vec<JobState>>,
+    active: AtomicBool,
+    wake: Notify,
+}
+
+/// Calendar-aware workflow scheduler
+///
+/// Cheap to clone; clones share the same jobs.
+#[derive(Clone)]
+pub struct Scheduler {
+    inner: Arc<SchedulerInner>,
+}
+
+impl Scheduler {
+    /// Create a scheduler for the jobs in `config.scheduler`
+    pub fn new(config: DemoConfig) -> Result<Self, DemoError> {
+        let errors = config.scheduler.validation_errors();
+        if !errors.is_empty() {
+            return Err(DemoError::validation(errors.join("; ")));
+        }
+
+        let now = Utc::now();
+        let jobs = config
+            .scheduler
+            .jobs
+            .iter()
+            .map(|job| {
+                let schedule = JobSchedule::new(
+                    CronExpr::parse(&job.cron)?,
+                    job.market,
+                    job.business_days_only,
+                );
+                Ok(JobState {
+                    config: job.clone(),
+                    next_fire: schedule.next_after(now),
+                    schedule,
+                    paused: !job.enabled,
+                    running: false,
+                    last_run: None,
+                })
+            })
+            .collect::<Result<Vec<_>, DemoError>>()?;
+
+        Ok(Self {
+            inner: Arc::new(SchedulerInner {
+                config,
+                jobs: Mutex::new(jobs),
+                active: AtomicBool::new(false),
+                wake: Notify::new(),
+            }),
+        })
+    }
+
+    /// Status of all jobs
+    pub fn status(&self) -> Vec<JobStatus> {
+        self.inner
+            .jobs
+            .lock()
+            .unwrap()
+            .iter()
+            .map(JobState::status)
+            .collect()
+    }
+
+    /// Status of one job
+    pub fn job_status(&self, name: &str) -> Option<JobStatus> {
+        self.with_job(name, |job| job.status()).ok()
+    }
+
+    /// Stop a job from firing on schedule
+    pub fn pause(&self, name: &str) -> Result<JobStatus, DemoError> {
+        self.with_job(name, |job| {
+            job.paused = true;
+            job.status()
+        })
+    }
+
+    /// Re-enable a paused job from the next fire time after now
+    pub fn resume(&self, name: &str) -> Result<JobStatus, DemoError> {
+        let status = self.with_job(name, |job| {
+            job.paused = false;
+            job.next_fire = job.schedule.next_after(Utc::now());
+            job.status()
+        })?;
+        self.inner.wake.notify_one();
+        Ok(status)
+    }
+
+    /// Run a job immediately, regardless of its schedule or pause state
+    pub async fn trigger(&self, name: &str) -> Result<WorkflowResult, DemoError> {
+        self.run_job(name, TriggerKind::Manual).await
+    }
+
+    /// Names of jobs due at `now`, advancing their next fire time
+    ///
+    /// Paused jobs and jobs that are still running are not returned; a
+    /// running job whose fire time passes skips that occurrence.
+    pub fn take_due(&self, now: DateTime<Utc>) -> Vec<String> {
+        let mut jobs = self.inner.jobs.lock().unwrap();
+        let mut due = Vec::new();
+        for job in jobs.iter_mut() {
+            if job.next_fire.is_some_and(|t| t <= now) {
+                job.next_fire = job.schedule.next_after(now);
+                if job.paused {
+                    continue;
+                }
+                if job.running {
+                    tracing::warn!(
+                        "Scheduled job '{}' still running; skipping",
+                        job.config.name
+                    );
+                    continue;
+                }
+                due.push(job.config.name.clone());
+            }
+        }
+        due
+    }
+
+    /// Earliest upcoming fire time across active jobs
+    pub fn next_wakeup(&self) -> Option<DateTime<Utc>> {
+        self.inner
+            .jobs
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|job| !job.paused)
+            .filter_map(|job| job.next_fire)
+            .min()
+    }
+
+    /// Start the scheduling loop on the Tokio runtime
+    pub fn start(&self) -> JoinHandle<()> {
+        self.inner.active.store(true, Ordering::SeqCst);
+        let scheduler = self.clone();
+        tokio::spawn(async move {
+            tracing::info!("Scheduler started with {} job(s)", scheduler.status().len());
+            while scheduler.inner.active.load(Ordering::SeqCst) {
+                for name in scheduler.take_due(Utc::now()) {
+                    let runner = scheduler.clone();
+                    tokio::spawn(async move {
+                        if let Err(e) = runner.run_job(&name, TriggerKind::Scheduled).await {
+                            tracing::error!("Scheduled job '{}' failed to start: {}", name, e);
+                        }
+                    });
+                }
+
+                let max_sleep = Duration::seconds(MAX_SLEEP_SECS);
+                let sleep = scheduler
+                    .next_wakeup()
+                    .map(|t| (t - Utc::now()).clamp(Duration::zero(), max_sleep))
+                    .unwrap_or(max_sleep)
+                    .to_std()
+                    .unwrap_or_default();
+                tokio::select! {
+                    _ = tokio::time::sleep(sleep) => {}
+                    _ = scheduler.inner.wake.notified() => {}
+                }
+            }
+            tracing::info!("Scheduler stopped");
+        })
+    }
+
+    /// Stop the scheduling loop (running jobs complete)
+    pub fn stop(&self) {
+        self.inner.active.store(false, Ordering::SeqCst);
+        self.inner.wake.notify_one();
+    }
+
+    /// Whether the scheduling loop is active
+    pub fn is_active(&self) -> bool {
+        self.inner.active.load(Ordering::SeqCst)
+    }
+
+    /// Execute a job's workflow and record the run
+    async fn run_job(&self, name: &str, trigger: TriggerKind) -> Result<WorkflowResult, DemoError> {
+        let workflow = self.with_job(name, |job| {
+            if job.running {
+                return Err(DemoError::workflow(format!(
+                    "Job '{}' is already running",
+                    name
+                )));
+            }
+            job.running = true;
+            Ok(job.config.workflow)
+        })??;
+
+        tracing::info!("Running scheduled job '{}' ({:?})", name, trigger);
+        let started_at = Utc::now();
+        let outcome = workflow.build().run(&self.inner.config, None).await;
+
+        let run = match &outcome {
+            Ok(result) => JobRun {
+                trigger,
+                started_at,
+                success: result.success,
+                duration_ms: result.duration_ms,
+                trades_processed: result.trades_processed,
+                errors: result.errors.clone(),
+            },
+            Err(e) => JobRun {
+                trigger,
+                started_at,
+                success: false,
+                duration_ms: (Utc::now() - started_at).num_milliseconds().max(0) as u64,
+                trades_processed: 0,
+                errors: vec![e.to_string()],
+            },
+        };
+        self.with_job(name, |job| {
+            job.running = false;
+            job.last_run = Some(run);
+        })?;
+        outcome
+    }
+
+    /// Apply `f` to a job by name
+    fn with_job<T>(&self, name: &str, f: impl FnOnce(&mut JobState) -> T) -> Result<T, DemoError> {
+        let mut jobs = self.inner.jobs.lock().unwrap();
+        jobs.iter_mut()
+            .find(|job| job.config.name == name)
+            .map(f)
+            .ok_or_else(|| DemoError::validation(format!("Unknown scheduled job '{}'", name)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    fn schedule(cron: &str, market: MarketCentre) -> JobSchedule {
+        JobSchedule::new(CronExpr::parse(cron).unwrap(), market, true)
+    }
+
+    #[test]
+    fn test_next_fire_in_market_time() {
+        let ny_close = schedule("30 17 * * mon-fri", MarketCentre::NewYork);
+        // Thursday 15 Oct 2026, 12:00 UTC -> 17:30 EDT = 21:30 UTC same day
+        assert_eq!(
+            ny_close.next_after(utc(2026, 10, 15, 12, 0)),
+            Some(utc(2026, 10, 15, 21, 30))
+        );
+        // After DST ends the same local time is 22:30 UTC
+        assert_eq!(
+            ny_close.next_after(utc(2026, 11, 3, 12, 0)),
+            Some(utc(2026, 11, 3, 22, 30))
+        );
+
+        let tokyo_close = schedule("0 15 * * mon-fri", MarketCentre::Tokyo);
+        // Friday 16 Oct 2026 07:00 UTC is after the 15:00 JST close -> Monday
+        assert_eq!(
+            tokyo_close.next_after(utc(2026, 10, 16, 7, 0)),
+            Some(utc(2026, 10, 19, 6, 0))
+        );
+    }
+
+    #[test]
+    fn test_holidays_are_skipped() {
+        let ny_close = schedule("0 17 * * mon-fri", MarketCentre::NewYork);
+        // Christmas 2026 is a Friday -> next run is Monday 28 Dec
+        assert_eq!(
+            ny_close.next_after(utc(2026, 12, 24, 23, 0)),
+            Some(utc(2026, 12, 28, 22, 0))
+        );
+
+        let every_day = JobSchedule::new(
+            CronExpr::parse("0 17 * * mon-fri").unwrap(),
+            MarketCentre::NewYork,
+            false,
+        );
+        assert_eq!(
+            every_day.next_after(utc(2026, 12, 24, 23, 0)),
+            Some(utc(2026, 12, 25, 22, 0))
+        );
+    }
+
+    fn test_config(dir: &std::path::Path) -> DemoConfig {
+        DemoConfig {
+            data_dir: dir.to_path_buf(),
+            max_trades: Some(5),
+            scheduler: SchedulerConfig {
+                enabled: true,
+                jobs: vec![
+                    ScheduleJobConfig::new(
+                        "ny_eod",
+                        ScheduledWorkflow::Eod,
+                        "30 17 * * mon-fri",
+                        MarketCentre::NewYork,
+                    ),
+                    ScheduleJobConfig::new(
+                        "ldn_intraday",
+                        ScheduledWorkflow::Intraday,
+                        "*/5 8-16 * * mon-fri",
+                        MarketCentre::London,
+                    ),
+                ],
+            },
+            ..DemoConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_invalid_job_config_rejected() {
+        let mut config = DemoConfig::default();
+        config.scheduler.jobs = vec![
+            ScheduleJobConfig::new("a", ScheduledWorkflow::Eod, "bad", MarketCentre::London),
+            ScheduleJobConfig::new(
+                "b",
+                ScheduledWorkflow::Eod,
+                "0 18 * * *",
+                MarketCentre::London,
+            ),
+            ScheduleJobConfig::new(
+                "b",
+                ScheduledWorkflow::Eod,
+                "0 18 * * *",
+                MarketCentre::London,
+            ),
+        ];
+        assert_eq!(config.scheduler.validation_errors().len(), 2);
+        assert!(Scheduler::new(config).is_err());
+    }
+
+    #[test]
+    fn test_take_due_advances_and_respects_pause() {
+        let dir = tempfile::tempdir().unwrap();
+        let scheduler = Scheduler::new(test_config(dir.path())).unwrap();
+        assert_eq!(scheduler.status().len(), 2);
+
+        let next = scheduler.job_status("ny_eod").unwrap().next_fire.unwrap();
+        let ny_eod = "ny_eod".to_string();
+        assert!(!scheduler
+            .take_due(next - Duration::minutes(1))
+            .contains(&ny_eod));
+        let due = scheduler.take_due(next);
+        assert!(due.contains(&ny_eod));
+        assert!(scheduler.job_status("ny_eod").unwrap().next_fire.unwrap() > next);
+
+        scheduler.pause("ny_eod").unwrap();
+        let next = scheduler.job_status("ny_eod").unwrap().next_fire.unwrap();
+        assert!(!scheduler.take_due(next).contains(&ny_eod));
+
+        let status = scheduler.resume("ny_eod").unwrap();
+        assert!(!status.paused);
+        assert!(status.next_fire.is_some());
+        assert!(scheduler.pause("missing").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_manual_trigger_records_run() {
+        let dir = tempfile::tempdir().unwrap();
+        let scheduler = Scheduler::new(test_config(dir.path())).unwrap();
+        scheduler.pause("ny_eod").unwrap();
+
+        let result = scheduler.trigger("ny_eod").await.unwrap();
+        assert!(result.success);
+
+        let status = scheduler.job_status("ny_eod").unwrap();
+        assert!(!status.running);
+        let run = status.last_run.unwrap();
+        assert_eq!(run.trigger, TriggerKind::Manual);
+        assert!(run.success);
+        assert_eq!(run.trades_processed, 5);
+        assert!(scheduler.trigger("missing").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_start_and_stop() {
+        let dir = tempfile::tempdir().unwrap();
+        let scheduler = Scheduler::new(test_config(dir.path())).unwrap();
+        let handle = scheduler.start();
+        assert!(scheduler.is_active());
+        scheduler.stop();
+        tokio::time::timeout(std::time::Duration::from_secs(5), handle)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!scheduler.is_active());
+    }
+}
@@ -0,0 +1,150 @@
+//! Resumable checkpoints for batch runs.
+//!
+//! After every successful step the batch state and the set of completed
+//! steps are written to `<dir>/<run_id>/checkpoint.json`. Re-running with
+//! the same run ID restores the state and skips the completed steps.
+
+use super::manifest::RunManifest;
+use crate::error::DemoError;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Persisted batch progress
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Checkpoint<S> {
+    /// Names of completed steps
+    pub completed_steps: Vec<String>,
+    /// Batch state after the last completed step
+    pub state: S,
+}
+
+/// File-based checkpoint and manifest store
+#[derive(Debug, Clone)]
+pub struct CheckpointStore {
+    /// Root directory; one sub-directory per run
+    dir: PathBuf,
+}
+
+impl CheckpointStore {
+    /// Create a store rooted at `dir`
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Directory holding the files of one run
+    pub fn run_dir(&self, run_id: &str) -> PathBuf {
+        self.dir.join(run_id)
+    }
+
+    /// Path of the checkpoint file of a run
+    pub fn checkpoint_path(&self, run_id: &str) -> PathBuf {
+        self.run_dir(run_id).join("checkpoint.json")
+    }
+
+    /// Path of the manifest file of a run
+    pub fn manifest_path(&self, run_id: &str) -> PathBuf {
+        self.run_dir(run_id).join("manifest.json")
+    }
+
+    /// Load the checkpoint of a run, if one exists
+    pub fn load<S: DeserializeOwned>(
+        &self,
+        run_id: &str,
+    ) -> Result<Option<Checkpoint<S>>, DemoError> {
+        let path = self.checkpoint_path(run_id);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path)?;
+        serde_json::from_str(&content).map(Some).map_err(|e| {
+            DemoError::data_load(format!("Corrupt checkpoint {}: {}", path.display(), e))
+        })
+    }
+
+    /// Persist a checkpoint
+    pub fn save<S: Serialize>(
+        &self,
+        run_id: &str,
+        checkpoint: &Checkpoint<S>,
+    ) -> Result<(), DemoError> {
+        let json = serde_json::to_string(checkpoint)
+            .map_err(|e| DemoError::workflow(format!("Cannot serialise checkpoint: {}", e)))?;
+        self.write_atomic(&self.checkpoint_path(run_id), &json)
+    }
+
+    /// Persist a run manifest
+    pub fn save_manifest(&self, manifest: &RunManifest) -> Result<(), DemoError> {
+        self.write_atomic(&self.manifest_path(&manifest.run_id), &manifest.to_json())
+    }
+
+    /// Load the manifest of a run, if one exists
+    pub fn load_manifest(&self, run_id: &str) -> Result<Option<RunManifest>, DemoError> {
+        let path = self.manifest_path(run_id);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path)?;
+        serde_json::from_str(&content).map(Some).map_err(|e| {
+            DemoError::data_load(format!("Corrupt manifest {}: {}", path.display(), e))
+        })
+    }
+
+    /// Delete the checkpoint of a run, keeping its manifest
+    pub fn clear(&self, run_id: &str) -> Result<(), DemoError> {
+        let path = self.checkpoint_path(run_id);
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    /// Write via a temporary file so readers never see a partial file
+    fn write_atomic(&self, path: &Path, content: &str) -> Result<(), DemoError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, content)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkpoint_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = CheckpointStore::new(dir.path());
+        assert!(store.load::<Vec<f64>>("RUN1").unwrap().is_none());
+
+        let checkpoint = Checkpoint {
+            completed_steps: vec!["load".to_string()],
+            state: vec![1.0, 2.0],
+        };
+        store.save("RUN1", &checkpoint).unwrap();
+
+        let loaded: Checkpoint<Vec<f64>> = store.load("RUN1").unwrap().unwrap();
+        assert_eq!(loaded.completed_steps, vec!["load"]);
+        assert_eq!(loaded.state, vec![1.0, 2.0]);
+
+        store.clear("RUN1").unwrap();
+        assert!(store.load::<Vec<f64>>("RUN1").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_corrupt_checkpoint_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = CheckpointStore::new(dir.path());
+        fs::create_dir_all(store.run_dir("RUN1")).unwrap();
+        fs::write(store.checkpoint_path("RUN1"), "{not json").unwrap();
+
+        assert!(store.load::<Vec<f64>>("RUN1").is_err());
+    }
+}
@@ -0,0 +1,162 @@
+//! Machine-readable run manifest for batch runs.
+//!
+//! One manifest is written per run and rewritten after every step, so an
+//! interrupted run still leaves a record of what completed.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Outcome of a single step
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StepStatus {
+    /// Not yet executed
+    Pending,
+    /// Executed successfully in this run
+    Succeeded,
+    /// Completed in an earlier attempt and restored from checkpoint
+    Restored,
+    /// Failed after exhausting its retries
+    Failed,
+    /// Not executed because a dependency failed or the run was cancelled
+    Skipped,
+}
+
+/// Overall outcome of a run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatus {
+    /// Steps are still executing
+    Running,
+    /// All steps succeeded or were restored
+    Succeeded,
+    /// At least one step failed
+    Failed,
+    /// The run was cancelled
+    Cancelled,
+}
+
+/// Manifest entry for a single step
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StepRecord {
+    /// Step name
+    pub name: String,
+    /// Names of the steps this step depends on
+    pub depends_on: Vec<String>,
+    /// Step outcome
+    pub status: StepStatus,
+    /// Number of attempts made in this run
+    pub attempts: u32,
+    /// Wall-clock time spent in this run, including retry backoff
+    pub duration_ms: u64,
+    /// Last error message, if any attempt failed
+    pub error: Option<String>,
+}
+
+/// Manifest describing a batch run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunManifest {
+    /// Run identifier (also the checkpoint key)
+    pub run_id: String,
+    /// Workflow name
+    pub workflow: String,
+    /// Overall outcome
+    pub status: RunStatus,
+    /// Whether completed steps were restored from a checkpoint
+    pub resumed: bool,
+    /// Run start time
+    pub started_at: DateTime<Utc>,
+    /// Run end time (None while running)
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Steps in execution order
+    pub steps: Vec<StepRecord>,
+}
+
+impl RunManifest {
+    /// Create a manifest with all steps pending
+    pub fn new(
+        run_id: impl Into<String>,
+        workflow: impl Into<String>,
+        steps: impl IntoIterator<Item = (String, Vec<String>)>,
+    ) -> Self {
+        Self {
+            run_id: run_id.into(),
+            workflow: workflow.into(),
+            status: RunStatus::Running,
+            resumed: false,
+            started_at: Utc::now(),
+            finished_at: None,
+            steps: steps
+                .into_iter()
+                .map(|(name, depends_on)| StepRecord {
+                    name,
+                    depends_on,
+                    status: StepStatus::Pending,
+                    attempts: 0,
+                    duration_ms: 0,
+                    error: None,
+                })
+                .collect(),
+        }
+    }
+
+    /// Look up a step record by name
+    pub fn step(&self, name: &str) -> Option<&StepRecord> {
+        self.steps.iter().find(|s| s.name == name)
+    }
+
+    /// Mutable step record by name
+    pub(crate) fn step_mut(&mut self, name: &str) -> Option<&mut StepRecord> {
+        self.steps.iter_mut().find(|s| s.name == name)
+    }
+
+    /// Error messages of failed steps
+    pub fn errors(&self) -> Vec<String> {
+        self.steps
+            .iter()
+            .filter(|s| s.status == StepStatus::Failed)
+            .map(|s| {
+                format!(
+                    "Step '{}' failed after {} attempt(s): {}",
+                    s.name,
+                    s.attempts,
+                    s.error.as_deref().unwrap_or("unknown error")
+                )
+            })
+            .collect()
+    }
+
+    /// Serialise the manifest as pretty-printed JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_round_trip() {
+        let mut manifest = RunManifest::new(
+            "RUN1",
+            "EOD Batch",
+            vec![
+                ("load".to_string(), vec![]),
+                ("price".to_string(), vec!["load".to_string()]),
+            ],
+        );
+        let step = manifest.step_mut("price").unwrap();
+        step.status = StepStatus::Failed;
+        step.attempts = 3;
+        step.error = Some("timeout".to_string());
+
+        let json = manifest.to_json();
+        assert!(json.contains("\"status\": \"failed\""));
+
+        let parsed: RunManifest = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.step("load").unwrap().status, StepStatus::Pending);
+        assert_eq!(parsed.errors().len(), 1);
+        assert!(parsed.errors()[0].contains("timeout"));
+    }
+}
@@ -0,0 +1,949 @@
+//! EOD Batch Workflow implementation.
+//!
+//! Executes end-of-day batch processing following A-I-P-S data flow as a
+//! DAG of named steps:
+//! 1. `load_portfolio` - load trades from demo_inputs
+//! 2. `build_curves` - snapshot curves and vols via pricer_optimiser
+//! 3. `simulate_exposures` - price and project EE/ENE profiles per netting set
+//! 4. `compute_xva` - CVA/DVA/FVA using pricer_risk
+//! 5. `write_reports` - write reports to demo_outputs
+//!
+//! Every step has a retry policy, and the batch state is checkpointed after
+//! each step under `<data_dir>/eod_runs/<run_id>/` together with a JSON run
+//! manifest. Re-running with the same run ID resumes from the first
+//! incomplete step.
+//!
+//! Every run, successful or not, is appended to the audit journal
+//! `<data_dir>/audit.jsonl` as a run record with fingerprints of the
+//! portfolio and market inputs, the batch configuration and the XVA results.
+
+mod checkpoint;
+mod manifest;
+mod pipeline;
+
+pub use checkpoint::{Checkpoint, CheckpointStore};
+pub use manifest::{RunManifest, RunStatus, StepRecord, StepStatus};
+pub use pipeline::{BatchPipeline, BatchStep, RetryPolicy};
+
+use super::{DemoWorkflow, ProgressCallback, WorkflowResult, WorkflowStep};
+use crate::config::DemoConfig;
+use crate::error::DemoError;
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+use demo_inputs::prelude::{FrontOffice, TradeSource};
+use demo_inputs::trade_source::{InstrumentType, TradeParams, TradeRecord};
+use demo_outputs::prelude::FileWriter;
+use demo_outputs::regulatory::{AuditStore, RunRecord};
+use demo_outputs::report_sink::{Report, ReportFormat, ReportSink};
+use pricer_core::types::provenance::RunMetadata;
+use pricer_core::types::Currency;
+use pricer_models::analytical::distributions::{norm_cdf, norm_pdf};
+use pricer_models::demo::{
+    BlackScholes, CurveEnum, FlatCurve, InstrumentEnum, ModelEnum, SabrVolSurface, VanillaSwap,
+    VolSurfaceEnum,
+};
+use pricer_optimiser::provider::MarketProvider;
+use pricer_risk::demo::{run_portfolio_pricing, DemoTrade};
+use pricer_risk::portfolio::CreditParams;
+use pricer_risk::xva::{compute_cva, compute_dva, compute_fva, FundingParams, OwnCreditParams};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// Step names, in DAG order
+pub const STEP_LOAD_PORTFOLIO: &str = "load_portfolio";
+/// Curve and vol surface snapshot step
+pub const STEP_BUILD_CURVES: &str = "build_curves";
+/// Exposure projection step
+pub const STEP_SIMULATE_EXPOSURES: &str = "simulate_exposures";
+/// XVA aggregation step
+pub const STEP_COMPUTE_XVA: &str = "compute_xva";
+/// Report output step
+pub const STEP_WRITE_REPORTS: &str = "write_reports";
+
+/// Exposure time grid spacing in years (quarterly)
+const GRID_STEP: f64 = 0.25;
+/// Normal rate volatility driving the exposure diffusion
+const NORMAL_RATE_VOL: f64 = 0.01;
+/// Counterparty hazard rate and LGD used for CVA
+const CP_HAZARD_RATE: f64 = 0.02;
+const CP_LGD: f64 = 0.6;
+/// Own hazard rate and LGD used for DVA
+const OWN_HAZARD_RATE: f64 = 0.01;
+const OWN_LGD: f64 = 0.6;
+/// Symmetric funding spread used for FVA
+const FUNDING_SPREAD: f64 = 0.005;
+
+/// Sequence number disambiguating run IDs generated within the same instant
+static RUN_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// Trade as carried through the batch state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EodTrade {
+    /// Trade identifier
+    pub trade_id: String,
+    /// Instrument type label
+    pub instrument_type: String,
+    /// Counterparty identifier
+    pub counterparty_id: String,
+    /// Netting set identifier
+    pub netting_set_id: String,
+    /// Currency code
+    pub currency: String,
+    /// Notional amount
+    pub notional: f64,
+    /// Fixed rate used for the VanillaSwap proxy
+    pub fixed_rate: f64,
+    /// Years to maturity
+    pub maturity_years: f64,
+    /// Present value (set by `simulate_exposures`)
+    pub pv: Option<f64>,
+}
+
+/// Curve and vol surface parameters for one currency
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MarketSnapshot {
+    /// Currency code
+    pub currency: String,
+    /// Flat curve rate
+    pub rate: f64,
+    /// SABR alpha
+    pub vol: f64,
+}
+
+/// Expected exposure profile of one netting set
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExposureProfile {
+    /// Netting set identifier
+    pub netting_set_id: String,
+    /// Counterparty identifier
+    pub counterparty_id: String,
+    /// Currency of the discount curve
+    pub currency: String,
+    /// Netted present value
+    pub pv: f64,
+    /// Time grid in years
+    pub time_grid: Vec<f64>,
+    /// Expected positive exposure
+    pub ee: Vec<f64>,
+    /// Expected negative exposure (positive magnitude)
+    pub ene: Vec<f64>,
+}
+
+/// XVA of one netting set
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NettingSetXvaSummary {
+    /// Netting set identifier
+    pub netting_set_id: String,
+    /// Counterparty identifier
+    pub counterparty_id: String,
+    /// Credit valuation adjustment
+    pub cva: f64,
+    /// Debit valuation adjustment
+    pub dva: f64,
+    /// Funding valuation adjustment
+    pub fva: f64,
+}
+
+/// Batch state checkpointed between steps
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EodState {
+    /// Portfolio loaded by `load_portfolio`
+    pub trades: Vec<EodTrade>,
+    /// Market snapshot built by `build_curves`
+    pub market: Vec<MarketSnapshot>,
+    /// Exposure profiles from `simulate_exposures`
+    pub exposures: Vec<ExposureProfile>,
+    /// Netting set XVA from `compute_xva`
+    pub xva: Vec<NettingSetXvaSummary>,
+    /// Report IDs written by `write_reports`
+    pub reports: Vec<String>,
+}
+
+impl EodState {
+    /// Total PV of the priced portfolio
+    pub fn total_pv(&self) -> f64 {
+        self.trades.iter().filter_map(|t| t.pv).sum()
+    }
+
+    /// Snapshot for a currency
+    fn market_for(&self, currency: &str) -> Result<&MarketSnapshot, DemoError> {
+        self.market
+            .iter()
+            .find(|m| m.currency == currency)
+            .ok_or_else(|| DemoError::computation(format!("No market snapshot for {}", currency)))
+    }
+}
+
+/// EOD Batch Workflow
+pub struct EodBatchWorkflow {
+    /// Cancellation flag
+    cancelled: Arc<AtomicBool>,
+    /// Fixed run ID (resumes an earlier run); generated per run if None
+    run_id: Option<String>,
+    /// Retry policy for steps without an override
+    default_retry: RetryPolicy,
+    /// Per-step retry overrides
+    step_retries: HashMap<String, RetryPolicy>,
+    /// Manifest of the most recent run
+    last_manifest: Mutex<Option<RunManifest>>,
+}
+
+impl EodBatchWorkflow {
+    /// Create a new EOD batch workflow
+    pub fn new() -> Self {
+        Self {
+            cancelled: Arc::new(AtomicBool::new(false)),
+            run_id: None,
+            default_retry: RetryPolicy::none(),
+            step_retries: HashMap::new(),
+            last_manifest: Mutex::new(None),
+        }
+    }
+
+    /// Use a fixed run ID, resuming its checkpoint if one exists
+    pub fn with_run_id(mut self, run_id: impl Into<String>) -> Self {
+        self.run_id = Some(run_id.into());
+        self
+    }
+
+    /// Set the retry policy for steps without an override
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.default_retry = policy;
+        self
+    }
+
+    /// Override the retry policy of a named step
+    pub fn with_step_retry(mut self, step: impl Into<String>, policy: RetryPolicy) -> Self {
+        self.step_retries.insert(step.into(), policy);
+        self
+    }
+
+    /// Manifest of the most recent run
+    pub fn last_manifest(&self) -> Option<RunManifest> {
+        self.last_manifest.lock().unwrap().clone()
+    }
+
+    /// Checkpoint store for a configuration
+    pub fn checkpoint_store(config: &DemoConfig) -> CheckpointStore {
+        CheckpointStore::new(config.data_dir.join("eod_runs"))
+    }
+
+    /// Audit journal of batch runs for a configuration
+    pub fn audit_store(config: &DemoConfig) -> std::io::Result<AuditStore> {
+        std::fs::create_dir_all(&config.data_dir)?;
+        AuditStore::open(&config.data_dir.join("audit.jsonl"))
+    }
+
+    /// Append a finished run to the audit journal
+    fn record_audit(
+        config: &DemoConfig,
+        manifest: &RunManifest,
+        state: &EodState,
+    ) -> Result<(), DemoError> {
+        let inputs: Vec<_> = state
+            .trades
+            .iter()
+            .map(|t| (&t.trade_id, t.notional, t.fixed_rate, t.maturity_years))
+            .collect();
+        let batch_config = serde_json::json!({
+            "steps": manifest.steps.iter().map(|s| &s.name).collect::<Vec<_>>(),
+            "grid_step": GRID_STEP,
+            "normal_rate_vol": NORMAL_RATE_VOL,
+            "counterparty_credit": [CP_HAZARD_RATE, CP_LGD],
+            "own_credit": [OWN_HAZARD_RATE, OWN_LGD],
+            "funding_spread": FUNDING_SPREAD,
+        });
+        let mut record = RunRecord::start(
+            &manifest.run_id,
+            "eod_batch",
+            manifest.workflow.as_str(),
+            &(&inputs, &state.market),
+            &batch_config,
+        )
+        .with_provenance(&RunMetadata::current());
+        record.started_at = manifest.started_at;
+        let record = match manifest.status {
+            RunStatus::Succeeded => record.succeed(&state.xva),
+            RunStatus::Cancelled => record.fail("cancelled"),
+            _ => record.fail(manifest.errors().join("; ")),
+        };
+        Self::audit_store(config)?.record_run(&record)?;
+        Ok(())
+    }
+
+    /// Generate a unique run ID
+    fn generate_run_id() -> String {
+        format!(
+            "EOD_{}_{}",
+            Utc::now().format("%Y%m%dT%H%M%S%3f"),
+            RUN_SEQ.fetch_add(1, Ordering::Relaxed)
+        )
+    }
+
+    /// Report progress if callback is provided
+    fn report_progress(progress: &Option<ProgressCallback>, step: WorkflowStep, pct: f64) {
+        if let Some(cb) = progress {
+            cb(step, pct);
+        }
+    }
+
+    /// Workflow step reported for a batch step
+    fn workflow_step(name: &str) -> WorkflowStep {
+        match name {
+            STEP_LOAD_PORTFOLIO => WorkflowStep::LoadingTrades,
+            STEP_BUILD_CURVES => WorkflowStep::Calibrating,
+            STEP_SIMULATE_EXPOSURES => WorkflowStep::Pricing,
+            STEP_COMPUTE_XVA => WorkflowStep::CalculatingXva,
+            _ => WorkflowStep::SendingOutputs,
+        }
+    }
+
+    /// Build the step DAG for a configuration
+    fn build_pipeline(
+        &self,
+        config: &DemoConfig,
+        run_id: &str,
+    ) -> Result<BatchPipeline<EodState>, DemoError> {
+        let trade_count = config.max_trades.unwrap_or(100);
+        let output_dir = config.data_dir.join("output");
+        let report_suffix = run_id.to_string();
+
+        let mut pipeline = BatchPipeline::new()
+            .with_default_retry(self.default_retry)
+            .add_step(BatchStep::new(
+                STEP_LOAD_PORTFOLIO,
+                move |state: &mut EodState| Self::load_portfolio(state, trade_count),
+            ))
+            .add_step(
+                BatchStep::new(STEP_BUILD_CURVES, Self::build_curves)
+                    .with_dependencies(&[STEP_LOAD_PORTFOLIO]),
+            )
+            .add_step(
+                BatchStep::new(STEP_SIMULATE_EXPOSURES, Self::simulate_exposures)
+                    .with_dependencies(&[STEP_LOAD_PORTFOLIO, STEP_BUILD_CURVES]),
+            )
+            .add_step(
+                BatchStep::new(STEP_COMPUTE_XVA, Self::compute_xva)
+                    .with_dependencies(&[STEP_SIMULATE_EXPOSURES]),
+            )
+            .add_step(
+                BatchStep::new(STEP_WRITE_REPORTS, move |state: &mut EodState| {
+                    Self::write_reports(state, &output_dir, &report_suffix)
+                })
+                .with_dependencies(&[STEP_SIMULATE_EXPOSURES, STEP_COMPUTE_XVA])
+                .with_retry(RetryPolicy::new(3)),
+            );
+
+        for (step, policy) in &self.step_retries {
+            pipeline.set_retry(step, *policy)?;
+        }
+        Ok(pipeline)
+    }
+
+    /// Step: load trades from FrontOffice
+    fn load_portfolio(state: &mut EodState, count: usize) -> Result<(), DemoError> {
+        let front_office = FrontOffice::new();
+        let today = Utc::now().date_naive();
+        state.trades = front_office
+            .generate_trades(count)
+            .iter()
+            .map(|record| Self::to_eod_trade(record, today))
+            .collect();
+        tracing::info!("Loaded {} trades from FrontOffice", state.trades.len());
+        Ok(())
+    }
+
+    /// Step: snapshot the curve and vol surface of every portfolio currency
+    fn build_curves(state: &mut EodState) -> Result<(), DemoError> {
+        let market = MarketProvider::new();
+        let currencies: BTreeSet<&str> = state.trades.iter().map(|t| t.currency.as_str()).collect();
+
+        state.market = currencies
+            .into_iter()
+            .map(|code| {
+                let ccy = Self::parse_currency(code);
+                let CurveEnum::Flat(curve) = *market.get_curve(ccy);
+                let VolSurfaceEnum::Sabr(vol) = *market.get_vol(ccy);
+                MarketSnapshot {
+                    currency: code.to_string(),
+                    rate: curve.rate,
+                    vol: vol.alpha,
+                }
+            })
+            .collect();
+        tracing::info!(
+            "Built market snapshot for {} currencies",
+            state.market.len()
+        );
+        Ok(())
+    }
+
+    /// Step: price trades and project exposure profiles per netting set
+    ///
+    /// Each netting set's MtM is modelled as Gaussian with mean amortising
+    /// linearly from today's PV to zero at maturity and standard deviation
+    /// `σ √t (T - t) N` (swap-like annuity decay), giving closed-form EE/ENE.
+    fn simulate_exposures(state: &mut EodState) -> Result<(), DemoError> {
+        // Rebuild the market from the checkpointed snapshot
+        let market = MarketProvider::new();
+        for snapshot in &state.market {
+            let ccy = Self::parse_currency(&snapshot.currency);
+            market.set_curve(
+                ccy,
+                CurveEnum::Flat(FlatCurve {
+                    rate: snapshot.rate,
+                }),
+            );
+            market.set_vol(
+                ccy,
+                VolSurfaceEnum::Sabr(SabrVolSurface {
+                    alpha: snapshot.vol,
+                }),
+            );
+        }
+
+        let demo_trades: Vec<DemoTrade> = state.trades.iter().map(Self::to_demo_trade).collect();
+        let results = run_portfolio_pricing(&demo_trades, &market);
+        for (trade, result) in state.trades.iter_mut().zip(&results) {
+            trade.pv = Some(result.pv * trade.notional);
+        }
+
+        let mut netting_sets: BTreeMap<&str, Vec<&EodTrade>> = BTreeMap::new();
+        for trade in &state.trades {
+            netting_sets
+                .entry(&trade.netting_set_id)
+                .or_default()
+                .push(trade);
+        }
+
+        state.exposures = netting_sets
+            .into_iter()
+            .map(|(id, trades)| {
+                let pv: f64 = trades.iter().filter_map(|t| t.pv).sum();
+                let gross: f64 = trades.iter().map(|t| t.notional.abs()).sum();
+                let horizon = trades
+                    .iter()
+                    .map(|t| t.maturity_years)
+                    .fold(GRID_STEP, f64::max);
+                let steps = (horizon / GRID_STEP).ceil() as usize;
+                let time_grid: Vec<f64> = (0..=steps)
+                    .map(|i| (i as f64 * GRID_STEP).min(horizon))
+                    .collect();
+
+                let (ee, ene) = time_grid
+                    .iter()
+                    .map(|&t| {
+                        let remaining = (horizon - t) / horizon;
+                        let mean = pv * remaining;
+                        let std = NORMAL_RATE_VOL * t.sqrt() * (horizon - t) * gross;
+                        let ee = if std > 0.0 {
+                            let d = mean / std;
+                            mean * norm_cdf(d) + std * norm_pdf(d)
+                        } else {
+                            mean.max(0.0)
+                        };
+                        (ee, ee - mean)
+                    })
+                    .unzip();
+
+                ExposureProfile {
+                    netting_set_id: id.to_string(),
+                    counterparty_id: trades[0].counterparty_id.clone(),
+                    currency: trades[0].currency.clone(),
+                    pv,
+                    time_grid,
+                    ee,
+                    ene,
+                }
+            })
+            .collect();
+
+        tracing::info!(
+            "Priced {} trades into {} netting sets, total PV: {:.2}",
+            state.trades.len(),
+            state.exposures.len(),
+            state.total_pv()
+        );
+        Ok(())
+    }
+
+    /// Step: compute CVA/DVA/FVA per netting set
+    fn compute_xva(state: &mut EodState) -> Result<(), DemoError> {
+        let credit = CreditParams::new(CP_HAZARD_RATE, CP_LGD)
+            .map_err(|e| DemoError::computation(e.to_string()))?;
+        let own_credit = OwnCreditParams::new(OWN_HAZARD_RATE, OWN_LGD)
+            .map_err(|e| DemoError::computation(e.to_string()))?;
+        let funding = FundingParams::symmetric(FUNDING_SPREAD);
+
+        let xva = state
+            .exposures
+            .iter()
+            .map(|profile| {
+                let rate = state.market_for(&profile.currency)?.rate;
+                let dfs: Vec<f64> = profile
+                    .time_grid
+                    .iter()
+                    .map(|t| (-rate * t).exp())
+                    .collect();
+                let (_, _, fva) = compute_fva(
+                    &profile.ee,
+                    &profile.ene,
+                    &profile.time_grid,
+                    &funding,
+                    &dfs,
+                    false,
+                );
+                Ok(NettingSetXvaSummary {
+                    netting_set_id: profile.netting_set_id.clone(),
+                    counterparty_id: profile.counterparty_id.clone(),
+                    cva: compute_cva(&profile.ee, &profile.time_grid, &credit),
+                    dva: compute_dva(&profile.ene, &profile.time_grid, &own_credit),
+                    fva,
+                })
+            })
+            .collect::<Result<Vec<_>, DemoError>>()?;
+        state.xva = xva;
+
+        let cva: f64 = state.xva.iter().map(|x| x.cva).sum();
+        let dva: f64 = state.xva.iter().map(|x| x.dva).sum();
+        let fva: f64 = state.xva.iter().map(|x| x.fva).sum();
+        tracing::info!(
+            "XVA calculated - CVA: {:.2}, DVA: {:.2}, FVA: {:.2}",
+            cva,
+            dva,
+            fva
+        );
+        Ok(())
+    }
+
+    /// Step: write pricing and XVA reports
+    fn write_reports(
+        state: &mut EodState,
+        output_dir: &PathBuf,
+        run_id: &str,
+    ) -> Result<(), DemoError> {
+        let file_writer = FileWriter::new(output_dir);
+        let generated_at = Utc::now().to_rfc3339();
+        let reports = [
+            Report {
+                report_id: format!("EOD_PRICING_{}", run_id),
+                title: "EOD Pricing Report".to_string(),
+                report_type: ReportFormat::Csv,
+                content: Self::generate_pricing_report(&state.trades),
+                generated_at: generated_at.clone(),
+                recipients: vec![],
+            },
+            Report {
+                report_id: format!("EOD_XVA_{}", run_id),
+                title: "EOD XVA Summary".to_string(),
+                report_type: ReportFormat::Json,
+                content: Self::generate_xva_report(state),
+                generated_at,
+                recipients: vec![],
+            },
+        ];
+
+        state.reports.clear();
+        for report in &reports {
+            file_writer.send(report).map_err(|e| {
+                DemoError::workflow(format!("Failed to write {}: {}", report.report_id, e))
+            })?;
+            state.reports.push(report.report_id.clone());
+        }
+        tracing::info!("Reports written to {}", output_dir.display());
+        Ok(())
+    }
+
+    /// Convert TradeRecord from demo_inputs to the checkpointable batch trade
+    fn to_eod_trade(record: &TradeRecord, today: NaiveDate) -> EodTrade {
+        let maturity_years = NaiveDate::parse_from_str(&record.maturity_date, "%Y-%m-%d")
+            .map(|d| (d - today).num_days() as f64 / 365.25)
+            .unwrap_or(5.0)
+            .clamp(GRID_STEP, 30.0);
+
+        EodTrade {
+            trade_id: record.trade_id.clone(),
+            instrument_type: Self::instrument_label(&record.instrument_type).to_string(),
+            counterparty_id: record.counterparty_id.clone(),
+            netting_set_id: record.netting_set_id.clone(),
+            currency: record.currency.clone(),
+            notional: record.notional,
+            fixed_rate: Self::extract_fixed_rate(record),
+            maturity_years,
+            pv: None,
+        }
+    }
+
+    /// Convert a batch trade to DemoTrade for pricer_risk
+    fn to_demo_trade(trade: &EodTrade) -> DemoTrade {
+        DemoTrade::new(
+            trade.trade_id.clone(),
+            Self::parse_currency(&trade.currency),
+            ModelEnum::BlackScholes(BlackScholes { vol: 0.2 }),
+            InstrumentEnum::VanillaSwap(VanillaSwap {
+                fixed_rate: trade.fixed_rate,
+            }),
+        )
+    }
+
+    /// Parse currency string to Currency enum
+    fn parse_currency(ccy_str: &str) -> Currency {
+        match ccy_str {
+            "USD" => Currency::USD,
+            "EUR" => Currency::EUR,
+            "GBP" => Currency::GBP,
+            "JPY" => Currency::JPY,
+            "CHF" => Currency::CHF,
+            // Other currencies default to USD for demo purposes
+            _ => Currency::USD,
+        }
+    }
+
+    /// Extract a fixed rate from trade params (for conversion to VanillaSwap)
+    fn extract_fixed_rate(record: &TradeRecord) -> f64 {
+        match &record.params {
+            TradeParams::InterestRateSwap { fixed_rate, .. } => *fixed_rate,
+            TradeParams::EquityOption { strike, .. } => strike / 100.0 * 0.01,
+            TradeParams::Forward { forward_price, .. } => forward_price / 100.0 * 0.01,
+            TradeParams::FxForward { rate, .. } => rate * 0.01,
+            TradeParams::FxOption { strike, .. } => strike * 0.01,
+            TradeParams::CreditDefaultSwap { spread_bps, .. } => spread_bps / 10000.0,
+        }
+    }
+
+    /// Report label of an instrument type
+    fn instrument_label(instrument_type: &InstrumentType) -> &'static str {
+        match instrument_type {
+            InstrumentType::EquityOption => "EquityOption",
+            InstrumentType::EquityForward => "EquityForward",
+            InstrumentType::InterestRateSwap => "IRS",
+            InstrumentType::FxForward => "FxForward",
+            InstrumentType::FxOption => "FxOption",
+            InstrumentType::CreditDefaultSwap => "CDS",
+        }
+    }
+
+    /// Generate pricing report content
+    fn generate_pricing_report(trades: &[EodTrade]) -> String {
+        let mut content =
+            String::from("trade_id,instrument_type,counterparty,currency,notional,pv\n");
+
+        for trade in trades {
+            content.push_str(&format!(
+                "{},{},{},{},{:.2},{:.2}\n",
+                trade.trade_id,
+                trade.instrument_type,
+                trade.counterparty_id,
+                trade.currency,
+                trade.notional,
+                trade.pv.unwrap_or(0.0)
+            ));
+        }
+
+        content
+    }
+
+    /// Generate XVA summary report content
+    fn generate_xva_report(state: &EodState) -> String {
+        let cva: f64 = state.xva.iter().map(|x| x.cva).sum();
+        let dva: f64 = state.xva.iter().map(|x| x.dva).sum();
+        let fva: f64 = state.xva.iter().map(|x| x.fva).sum();
+
+        let report = serde_json::json!({
+            "report_type": "XVA Summary",
+            "generated_at": Utc::now().to_rfc3339(),
+            "metrics": {
+                "total_pv": state.total_pv(),
+                "cva": cva,
+                "dva": dva,
+                "fva": fva,
+                "total_xva": -cva + dva - fva,
+            },
+            "netting_sets": state.xva,
+            "trade_count": state.trades.len(),
+        });
+        serde_json::to_string_pretty(&report).unwrap_or_default()
+    }
+}
+
+impl Default for EodBatchWorkflow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl DemoWorkflow for EodBatchWorkflow {
+    fn name(&self) -> &str {
+        "EOD Batch"
+    }
+
+    async fn run(
+        &self,
+        config: &DemoConfig,
+        progress: Option<ProgressCallback>,
+    ) -> Result<WorkflowResult, DemoError> {
+        let start = Instant::now();
+        self.cancelled.store(false, Ordering::SeqCst);
+
+        let run_id = self.run_id.clone().unwrap_or_else(Self::generate_run_id);
+        tracing::info!("Starting EOD Batch workflow (run {})", run_id);
+
+        let pipeline = self.build_pipeline(config, &run_id)?;
+        let store = Self::checkpoint_store(config);
+        let on_step = |name: &str, pct: f64| {
+            Self::report_progress(&progress, Self::workflow_step(name), pct);
+        };
+
+        let (state, manifest) = pipeline
+            .run(
+                &run_id,
+                self.name(),
+                EodState::default(),
+                &store,
+                &self.cancelled,
+                &on_step,
+            )
+            .await?;
+        *self.last_manifest.lock().unwrap() = Some(manifest.clone());
+        Self::record_audit(config, &manifest, &state)?;
+
+        let duration_ms = start.elapsed().as_millis() as u64;
+        match manifest.status {
+            RunStatus::Succeeded => {
+                Self::report_progress(&progress, WorkflowStep::Completed, 1.0);
+                tracing::info!("EOD Batch completed in {}ms", duration_ms);
+                Ok(WorkflowResult::success(duration_ms, state.trades.len()))
+            }
+            RunStatus::Cancelled => Ok(WorkflowResult::failure(
+                duration_ms,
+                vec!["Workflow cancelled".to_string()],
+            )),
+            _ => {
+                let mut result = WorkflowResult::failure(duration_ms, manifest.errors());
+                result.trades_processed = state.trades.len();
+                Ok(result)
+            }
+        }
+    }
+
+    async fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        tracing::info!("EOD Batch workflow cancelled");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config() -> DemoConfig {
+        let mut config = DemoConfig::default();
+        config.data_dir = std::env::temp_dir().join("frictional_bank_test");
+        config
+    }
+
+    #[tokio::test]
+    async fn test_eod_batch_workflow() {
+        let workflow = EodBatchWorkflow::new();
+        let mut config = test_config();
+        config.max_trades = Some(10); // Small number for testing
+
+        let result = workflow.run(&config, None).await.unwrap();
+        assert!(result.success);
+        assert!(result.trades_processed > 0);
+    }
+
+    #[tokio::test]
+    async fn test_eod_batch_with_progress() {
+        let workflow = EodBatchWorkflow::new();
+        let mut config = test_config();
+        config.max_trades = Some(5);
+
+        let steps_received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let steps_clone = steps_received.clone();
+
+        let progress: ProgressCallback = Arc::new(move |step, pct| {
+            steps_clone.lock().unwrap().push((step, pct));
+        });
+
+        let result = workflow.run(&config, Some(progress)).await.unwrap();
+        assert!(result.success);
+
+        let steps = steps_received.lock().unwrap();
+        assert!(!steps.is_empty());
+        // Should include LoadingTrades step
+        assert!(steps.iter().any(|(s, _)| *s == WorkflowStep::LoadingTrades));
+        // Should end with Completed
+        assert!(steps.iter().any(|(s, _)| *s == WorkflowStep::Completed));
+    }
+
+    #[test]
+    fn test_convert_trade_record() {
+        let record = TradeRecord {
+            trade_id: "TEST001".to_string(),
+            instrument_type: InstrumentType::InterestRateSwap,
+            counterparty_id: "CP001".to_string(),
+            netting_set_id: "NS001".to_string(),
+            notional: 1_000_000.0,
+            currency: "USD".to_string(),
+            trade_date: "2026-01-10".to_string(),
+            maturity_date: "2031-01-10".to_string(),
+            params: TradeParams::InterestRateSwap {
+                fixed_rate: 0.0425,
+                float_index: "SOFR".to_string(),
+                pay_fixed: true,
+            },
+        };
+
+        let today = NaiveDate::from_ymd_opt(2026, 1, 10).unwrap();
+        let trade = EodBatchWorkflow::to_eod_trade(&record, today);
+        assert_eq!(trade.instrument_type, "IRS");
+        assert_eq!(trade.fixed_rate, 0.0425);
+        assert!((trade.maturity_years - 5.0).abs() < 0.01);
+
+        let demo_trade = EodBatchWorkflow::to_demo_trade(&trade);
+        assert_eq!(demo_trade.id, "TEST001");
+        assert_eq!(demo_trade.ccy, Currency::USD);
+    }
+
+    #[test]
+    fn test_parse_currency() {
+        assert_eq!(EodBatchWorkflow::parse_currency("USD"), Currency::USD);
+        assert_eq!(EodBatchWorkflow::parse_currency("EUR"), Currency::EUR);
+        assert_eq!(EodBatchWorkflow::parse_currency("JPY"), Currency::JPY);
+        assert_eq!(EodBatchWorkflow::parse_currency("UNKNOWN"), Currency::USD);
+    }
+
+    #[tokio::test]
+    async fn test_eod_batch_cancellation() {
+        use std::sync::Arc as StdArc;
+
+        let workflow = StdArc::new(EodBatchWorkflow::new());
+        let workflow_clone = workflow.clone();
+
+        // Spawn a task that cancels the workflow after a short delay
+        tokio::spawn(async move {
+            tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+            workflow_clone.cancel().await;
+        });
+
+        let mut config = test_config();
+        config.max_trades = Some(1000); // Large enough to allow cancellation
+        let result = workflow.run(&config, None).await.unwrap();
+
+        // Result may succeed if it completes before cancellation
+        // This test verifies the cancellation mechanism works
+        assert!(result.success || result.errors.iter().any(|e| e.contains("cancelled")));
+    }
+
+    fn isolated_config(dir: &std::path::Path) -> DemoConfig {
+        DemoConfig {
+            data_dir: dir.to_path_buf(),
+            max_trades: Some(8),
+            ..DemoConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_eod_batch_writes_manifest_and_resumes() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = isolated_config(dir.path());
+
+        let workflow = EodBatchWorkflow::new().with_run_id("EOD_TEST");
+        let result = workflow.run(&config, None).await.unwrap();
+        assert!(result.success);
+        assert_eq!(result.trades_processed, 8);
+
+        let store = EodBatchWorkflow::checkpoint_store(&config);
+        let manifest = store.load_manifest("EOD_TEST").unwrap().unwrap();
+        assert_eq!(manifest.status, RunStatus::Succeeded);
+        assert_eq!(manifest.steps.len(), 5);
+        assert_eq!(manifest.steps[0].name, STEP_LOAD_PORTFOLIO);
+        assert_eq!(manifest.steps[4].name, STEP_WRITE_REPORTS);
+
+        let checkpoint: Checkpoint<EodState> = store.load("EOD_TEST").unwrap().unwrap();
+        assert_eq!(checkpoint.state.exposures.len(), checkpoint.state.xva.len());
+        assert!(checkpoint
+            .state
+            .xva
+            .iter()
+            .all(|x| x.cva >= 0.0 && x.dva >= 0.0));
+        assert_eq!(checkpoint.state.reports.len(), 2);
+
+        // Same run ID: everything is restored, nothing is recomputed
+        let resumed = EodBatchWorkflow::new().with_run_id("EOD_TEST");
+        let result = resumed.run(&config, None).await.unwrap();
+        assert!(result.success);
+        assert_eq!(result.trades_processed, 8);
+        let manifest = resumed.last_manifest().unwrap();
+        assert!(manifest.resumed);
+        assert!(manifest
+            .steps
+            .iter()
+            .all(|s| s.status == StepStatus::Restored));
+
+        // Both runs are in the audit journal, on the same inputs and results
+        let audit = EodBatchWorkflow::audit_store(&config).unwrap();
+        audit.verify().unwrap();
+        let records = audit.get_events_for_entity("Run", "EOD_TEST");
+        assert_eq!(records.len(), 2);
+        let run = audit.run("EOD_TEST").unwrap();
+        assert_eq!(run.status, demo_outputs::regulatory::RunStatus::Succeeded);
+        let first: RunRecord = serde_json::from_value(records[0].details.clone()).unwrap();
+        assert!(first.same_inputs(&run));
+        assert_eq!(first.results_hash, run.results_hash);
+    }
+
+    #[tokio::test]
+    async fn test_eod_batch_report_failure_is_retried() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = isolated_config(dir.path());
+        // A file where the output directory should be makes every write fail
+        std::fs::write(dir.path().join("output"), "blocked").unwrap();
+
+        let workflow = EodBatchWorkflow::new()
+            .with_run_id("EOD_FAIL")
+            .with_step_retry(STEP_WRITE_REPORTS, RetryPolicy::new(2).with_backoff(1, 1.0));
+        let result = workflow.run(&config, None).await.unwrap();
+        assert!(!result.success);
+        assert_eq!(result.trades_processed, 8);
+        assert!(result.errors[0].contains(STEP_WRITE_REPORTS));
+
+        let manifest = workflow.last_manifest().unwrap();
+        assert_eq!(manifest.status, RunStatus::Failed);
+        let step = manifest.step(STEP_WRITE_REPORTS).unwrap();
+        assert_eq!(step.status, StepStatus::Failed);
+        assert_eq!(step.attempts, 2);
+        assert_eq!(
+            manifest.step(STEP_COMPUTE_XVA).unwrap().status,
+            StepStatus::Succeeded
+        );
+
+        // Unblock the output directory and resume from the failed step
+        std::fs::remove_file(dir.path().join("output")).unwrap();
+        let result = workflow.run(&config, None).await.unwrap();
+        assert!(result.success);
+        let manifest = workflow.last_manifest().unwrap();
+        assert_eq!(
+            manifest.step(STEP_COMPUTE_XVA).unwrap().status,
+            StepStatus::Restored
+        );
+        assert_eq!(
+            manifest.step(STEP_WRITE_REPORTS).unwrap().status,
+            StepStatus::Succeeded
+        );
+    }
+
+    #[test]
+    fn test_unknown_step_retry_rejected() {
+        let workflow = EodBatchWorkflow::new().with_step_retry("no_such_step", RetryPolicy::new(2));
+        assert!(workflow
+            .build_pipeline(&DemoConfig::default(), "RUN")
+            .is_err());
+    }
+}
@@ -0,0 +1,550 @@
+//! Step DAG executor for batch workflows.
+//!
+//! A [`BatchPipeline`] is a set of named steps with explicit dependencies.
+//! Steps run in topological order against a shared, serialisable state.
+//! Each step has a [`RetryPolicy`]; after every successful step the state is
+//! checkpointed so that a re-run with the same run ID resumes from the first
+//! incomplete step instead of starting from scratch.
+
+use super::checkpoint::{Checkpoint, CheckpointStore};
+use super::manifest::{RunManifest, RunStatus, StepStatus};
+use crate::error::DemoError;
+use chrono::Utc;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// Retry policy for a batch step
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first
+    pub max_attempts: u32,
+    /// Delay before the first retry in milliseconds
+    pub backoff_ms: u64,
+    /// Factor applied to the delay after each retry
+    pub backoff_multiplier: f64,
+}
+
+impl RetryPolicy {
+    /// Single attempt, no retries
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            backoff_ms: 0,
+            backoff_multiplier: 1.0,
+        }
+    }
+
+    /// Up to `max_attempts` attempts with a 100ms doubling backoff
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            backoff_ms: 100,
+            backoff_multiplier: 2.0,
+        }
+    }
+
+    /// Set the initial backoff and its growth factor
+    pub fn with_backoff(mut self, backoff_ms: u64, multiplier: f64) -> Self {
+        self.backoff_ms = backoff_ms;
+        self.backoff_multiplier = multiplier.max(1.0);
+        self
+    }
+
+    /// Delay before retry number `retry` (1-based)
+    pub fn delay_for(&self, retry: u32) -> Duration {
+        let factor = self.backoff_multiplier.powi(retry.saturating_sub(1) as i32);
+        Duration::from_millis((self.backoff_ms as f64 * factor) as u64)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+/// Step body operating on the batch state
+pub type StepFn<S> = Box<dyn Fn(&mut S) -> Result<(), DemoError> + Send + Sync>;
+
+/// A named step in a batch pipeline
+pub struct BatchStep<S> {
+    /// Unique step name
+    name: String,
+    /// Names of steps that must complete first
+    depends_on: Vec<String>,
+    /// Step-specific retry policy (falls back to the pipeline default)
+    retry: Option<RetryPolicy>,
+    /// Step body
+    run: StepFn<S>,
+}
+
+impl<S> BatchStep<S> {
+    /// Create a step without dependencies
+    pub fn new<F>(name: impl Into<String>, run: F) -> Self
+    where
+        F: Fn(&mut S) -> Result<(), DemoError> + Send + Sync + 'static,
+    {
+        Self {
+            name: name.into(),
+            depends_on: Vec::new(),
+            retry: None,
+            run: Box::new(run),
+        }
+    }
+
+    /// Declare dependencies on other steps
+    pub fn with_dependencies(mut self, deps: &[&str]) -> Self {
+        self.depends_on = deps.iter().map(|d| d.to_string()).collect();
+        self
+    }
+
+    /// Set the retry policy of this step
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
+
+    /// Step name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Step dependencies
+    pub fn depends_on(&self) -> &[String] {
+        &self.depends_on
+    }
+}
+
+/// DAG of batch steps with retry and checkpoint support
+pub struct BatchPipeline<S> {
+    /// Steps in insertion order
+    steps: Vec<BatchStep<S>>,
+    /// Retry policy for steps without their own
+    default_retry: RetryPolicy,
+}
+
+impl<S> BatchPipeline<S>
+where
+    S: Clone + Serialize + DeserializeOwned + Send,
+{
+    /// Create an empty pipeline
+    pub fn new() -> Self {
+        Self {
+            steps: Vec::new(),
+            default_retry: RetryPolicy::none(),
+        }
+    }
+
+    /// Set the retry policy for steps without their own
+    pub fn with_default_retry(mut self, policy: RetryPolicy) -> Self {
+        self.default_retry = policy;
+        self
+    }
+
+    /// Append a step
+    pub fn add_step(mut self, step: BatchStep<S>) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    /// Override the retry policy of a named step
+    pub fn set_retry(&mut self, name: &str, policy: RetryPolicy) -> Result<(), DemoError> {
+        let step = self
+            .steps
+            .iter_mut()
+            .find(|s| s.name == name)
+            .ok_or_else(|| DemoError::workflow(format!("Unknown batch step '{}'", name)))?;
+        step.retry = Some(policy);
+        Ok(())
+    }
+
+    /// Effective retry policy of a step
+    pub fn retry_policy(&self, name: &str) -> Option<RetryPolicy> {
+        self.steps
+            .iter()
+            .find(|s| s.name == name)
+            .map(|s| s.retry.unwrap_or(self.default_retry))
+    }
+
+    /// Execution order of the steps
+    ///
+    /// Ties are broken by insertion order. Fails on duplicate names,
+    /// unknown dependencies and cycles.
+    pub fn topological_order(&self) -> Result<Vec<usize>, DemoError> {
+        let mut index: HashMap<&str, usize> = HashMap::new();
+        for (i, step) in self.steps.iter().enumerate() {
+            if index.insert(step.name.as_str(), i).is_some() {
+                return Err(DemoError::workflow(format!(
+                    "Duplicate batch step '{}'",
+                    step.name
+                )));
+            }
+        }
+
+        let mut in_degree = vec![0usize; self.steps.len()];
+        let mut dependents: Vec<Vec<usize>> = vec![Vec::new(); self.steps.len()];
+        for (i, step) in self.steps.iter().enumerate() {
+            for dep in &step.depends_on {
+                let &j = index.get(dep.as_str()).ok_or_else(|| {
+                    DemoError::workflow(format!(
+                        "Batch step '{}' depends on unknown step '{}'",
+                        step.name, dep
+                    ))
+                })?;
+                in_degree[i] += 1;
+                dependents[j].push(i);
+            }
+        }
+
+        let mut order = Vec::with_capacity(self.steps.len());
+        let mut done = vec![false; self.steps.len()];
+        while order.len() < self.steps.len() {
+            let next = (0..self.steps.len()).find(|&i| !done[i] && in_degree[i] == 0);
+            let Some(i) = next else {
+                let cyclic: Vec<&str> = (0..self.steps.len())
+                    .filter(|&i| !done[i])
+                    .map(|i| self.steps[i].name.as_str())
+                    .collect();
+                return Err(DemoError::workflow(format!(
+                    "Batch steps form a cycle: {}",
+                    cyclic.join(", ")
+                )));
+            };
+            done[i] = true;
+            order.push(i);
+            for &d in &dependents[i] {
+                in_degree[d] -= 1;
+            }
+        }
+        Ok(order)
+    }
+
+    /// Execute the pipeline
+    ///
+    /// Restores `run_id`'s checkpoint from `store` if present, then runs the
+    /// remaining steps. `on_step` is called with the step name and 0.0 before
+    /// and 1.0 after each executed step. Step failures are recorded in the
+    /// returned manifest rather than returned as errors; `Err` is reserved
+    /// for an invalid DAG or an unusable checkpoint store.
+    pub async fn run(
+        &self,
+        run_id: &str,
+        workflow: &str,
+        initial: S,
+        store: &CheckpointStore,
+        cancelled: &AtomicBool,
+        on_step: &(dyn Fn(&str, f64) + Send + Sync),
+    ) -> Result<(S, RunManifest), DemoError> {
+        let order = self.topological_order()?;
+        let mut manifest = RunManifest::new(
+            run_id,
+            workflow,
+            order.iter().map(|&i| {
+                let step = &self.steps[i];
+                (step.name.clone(), step.depends_on.clone())
+            }),
+        );
+
+        let mut state = initial;
+        let mut completed: Vec<String> = Vec::new();
+        if let Some(checkpoint) = store.load::<S>(run_id)? {
+            tracing::info!(
+                "Resuming batch run {} ({} step(s) restored)",
+                run_id,
+                checkpoint.completed_steps.len()
+            );
+            state = checkpoint.state;
+            for name in checkpoint.completed_steps {
+                if let Some(record) = manifest.step_mut(&name) {
+                    record.status = StepStatus::Restored;
+                    completed.push(name);
+                }
+            }
+            manifest.resumed = true;
+        }
+        store.save_manifest(&manifest)?;
+
+        for &i in &order {
+            let step = &self.steps[i];
+            if completed.contains(&step.name) {
+                continue;
+            }
+
+            if cancelled.load(Ordering::SeqCst) {
+                manifest.status = RunStatus::Cancelled;
+                break;
+            }
+
+            let blocked = step.depends_on.iter().find(|dep| {
+                manifest.step(dep).is_some_and(|r| {
+                    !matches!(r.status, StepStatus::Succeeded | StepStatus::Restored)
+                })
+            });
+            if let Some(dep) = blocked {
+                let record = manifest.step_mut(&step.name).expect("step in manifest");
+                record.status = StepStatus::Skipped;
+                record.error = Some(format!("dependency '{}' did not complete", dep));
+                store.save_manifest(&manifest)?;
+                continue;
+            }
+
+            on_step(&step.name, 0.0);
+            let policy = step.retry.unwrap_or(self.default_retry);
+            let started = Instant::now();
+            let mut attempts = 0;
+            let mut last_error = None;
+            let mut outcome = None;
+
+            while attempts < policy.max_attempts {
+                if attempts > 0 {
+                    let delay = policy.delay_for(attempts);
+                    tracing::warn!(
+                        "Retrying batch step '{}' in {}ms (attempt {}/{})",
+                        step.name,
+                        delay.as_millis(),
+                        attempts + 1,
+                        policy.max_attempts
+                    );
+                    tokio::time::sleep(delay).await;
+                    if cancelled.load(Ordering::SeqCst) {
+                        break;
+                    }
+                }
+                attempts += 1;
+
+                // Run against a copy so a failed attempt leaves no partial
+                // updates behind for the next attempt
+                let mut candidate = state.clone();
+                match (step.run)(&mut candidate) {
+                    Ok(()) => {
+                        outcome = Some(candidate);
+                        break;
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            "Batch step '{}' attempt {} failed: {}",
+                            step.name,
+                            attempts,
+                            e
+                        );
+                        last_error = Some(e.to_string());
+                    }
+                }
+            }
+
+            let record = manifest.step_mut(&step.name).expect("step in manifest");
+            record.attempts = attempts;
+            record.duration_ms = started.elapsed().as_millis() as u64;
+            record.error = last_error;
+
+            match outcome {
+                Some(next) => {
+                    record.status = StepStatus::Succeeded;
+                    state = next;
+                    completed.push(step.name.clone());
+                    store.save(
+                        run_id,
+                        &Checkpoint {
+                            completed_steps: completed.clone(),
+                            state: &state,
+                        },
+                    )?;
+                    on_step(&step.name, 1.0);
+                }
+                None => record.status = StepStatus::Failed,
+            }
+            store.save_manifest(&manifest)?;
+        }
+
+        for record in &mut manifest.steps {
+            if record.status == StepStatus::Pending {
+                record.status = StepStatus::Skipped;
+            }
+        }
+        if manifest.status == RunStatus::Running {
+            let all_done = manifest
+                .steps
+                .iter()
+                .all(|r| matches!(r.status, StepStatus::Succeeded | StepStatus::Restored));
+            manifest.status = if all_done {
+                RunStatus::Succeeded
+            } else if cancelled.load(Ordering::SeqCst) {
+                RunStatus::Cancelled
+            } else {
+                RunStatus::Failed
+            };
+        }
+        manifest.finished_at = Some(Utc::now());
+        store.save_manifest(&manifest)?;
+
+        Ok((state, manifest))
+    }
+}
+
+impl<S> Default for BatchPipeline<S>
+where
+    S: Clone + Serialize + DeserializeOwned + Send,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicU32;
+    use std::sync::Arc;
+
+    fn no_progress(_: &str, _: f64) {}
+
+    #[test]
+    fn test_retry_policy_backoff() {
+        let policy = RetryPolicy::new(4).with_backoff(10, 3.0);
+        assert_eq!(policy.delay_for(1), Duration::from_millis(10));
+        assert_eq!(policy.delay_for(2), Duration::from_millis(30));
+        assert_eq!(policy.delay_for(3), Duration::from_millis(90));
+        assert_eq!(RetryPolicy::new(0).max_attempts, 1);
+    }
+
+    #[test]
+    fn test_topological_order() {
+        let pipeline = BatchPipeline::<Vec<String>>::new()
+            .add_step(BatchStep::new("report", |_| Ok(())).with_dependencies(&["price", "load"]))
+            .add_step(BatchStep::new("price", |_| Ok(())).with_dependencies(&["load"]))
+            .add_step(BatchStep::new("load", |_| Ok(())));
+
+        let order = pipeline.topological_order().unwrap();
+        assert_eq!(order, vec![2, 1, 0]);
+    }
+
+    #[test]
+    fn test_invalid_dags_rejected() {
+        let cyclic = BatchPipeline::<Vec<String>>::new()
+            .add_step(BatchStep::new("a", |_| Ok(())).with_dependencies(&["b"]))
+            .add_step(BatchStep::new("b", |_| Ok(())).with_dependencies(&["a"]));
+        assert!(cyclic
+            .topological_order()
+            .unwrap_err()
+            .to_string()
+            .contains("cycle"));
+
+        let unknown = BatchPipeline::<Vec<String>>::new()
+            .add_step(BatchStep::new("a", |_| Ok(())).with_dependencies(&["missing"]));
+        assert!(unknown.topological_order().is_err());
+
+        let duplicate = BatchPipeline::<Vec<String>>::new()
+            .add_step(BatchStep::new("a", |_| Ok(())))
+            .add_step(BatchStep::new("a", |_| Ok(())));
+        assert!(duplicate.topological_order().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_flaky_step_succeeds_on_retry() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = CheckpointStore::new(dir.path());
+        let calls = Arc::new(AtomicU32::new(0));
+        let calls_clone = calls.clone();
+
+        let pipeline = BatchPipeline::<Vec<String>>::new().add_step(
+            BatchStep::new("flaky", move |state: &mut Vec<String>| {
+                state.push("partial".to_string());
+                if calls_clone.fetch_add(1, Ordering::SeqCst) < 2 {
+                    return Err(DemoError::workflow("transient"));
+                }
+                Ok(())
+            })
+            .with_retry(RetryPolicy::new(3).with_backoff(1, 1.0)),
+        );
+
+        let cancelled = AtomicBool::new(false);
+        let (state, manifest) = pipeline
+            .run("RUN", "test", Vec::new(), &store, &cancelled, &no_progress)
+            .await
+            .unwrap();
+
+        assert_eq!(manifest.status, RunStatus::Succeeded);
+        assert_eq!(manifest.step("flaky").unwrap().attempts, 3);
+        // Failed attempts must not leak partial state
+        assert_eq!(state, vec!["partial".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_resume_after_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = CheckpointStore::new(dir.path());
+        let load_calls = Arc::new(AtomicU32::new(0));
+        let fail = Arc::new(AtomicBool::new(true));
+
+        let build = |load_calls: Arc<AtomicU32>, fail: Arc<AtomicBool>| {
+            BatchPipeline::<Vec<String>>::new()
+                .add_step(BatchStep::new("load", move |state: &mut Vec<String>| {
+                    load_calls.fetch_add(1, Ordering::SeqCst);
+                    state.push("loaded".to_string());
+                    Ok(())
+                }))
+                .add_step(
+                    BatchStep::new("report", move |state: &mut Vec<String>| {
+                        if fail.load(Ordering::SeqCst) {
+                            return Err(DemoError::workflow("disk full"));
+                        }
+                        state.push("reported".to_string());
+                        Ok(())
+                    })
+                    .with_dependencies(&["load"]),
+                )
+                .add_step(BatchStep::new("archive", |_| Ok(())).with_dependencies(&["report"]))
+        };
+
+        let cancelled = AtomicBool::new(false);
+        let pipeline = build(load_calls.clone(), fail.clone());
+        let (_, manifest) = pipeline
+            .run("RUN", "test", Vec::new(), &store, &cancelled, &no_progress)
+            .await
+            .unwrap();
+        assert_eq!(manifest.status, RunStatus::Failed);
+        assert_eq!(manifest.step("report").unwrap().status, StepStatus::Failed);
+        assert_eq!(
+            manifest.step("archive").unwrap().status,
+            StepStatus::Skipped
+        );
+        assert!(manifest.errors()[0].contains("disk full"));
+
+        fail.store(false, Ordering::SeqCst);
+        let (state, manifest) = pipeline
+            .run("RUN", "test", Vec::new(), &store, &cancelled, &no_progress)
+            .await
+            .unwrap();
+        assert_eq!(manifest.status, RunStatus::Succeeded);
+        assert!(manifest.resumed);
+        assert_eq!(manifest.step("load").unwrap().status, StepStatus::Restored);
+        assert_eq!(load_calls.load(Ordering::SeqCst), 1);
+        assert_eq!(state, vec!["loaded".to_string(), "reported".to_string()]);
+
+        let on_disk = store.load_manifest("RUN").unwrap().unwrap();
+        assert_eq!(on_disk.status, RunStatus::Succeeded);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_run_skips_remaining_steps() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = CheckpointStore::new(dir.path());
+        let pipeline = BatchPipeline::<Vec<String>>::new()
+            .add_step(BatchStep::new("a", |_| Ok(())))
+            .add_step(BatchStep::new("b", |_| Ok(())).with_dependencies(&["a"]));
+
+        let cancelled = AtomicBool::new(true);
+        let (_, manifest) = pipeline
+            .run("RUN", "test", Vec::new(), &store, &cancelled, &no_progress)
+            .await
+            .unwrap();
+        assert_eq!(manifest.status, RunStatus::Cancelled);
+        assert!(manifest
+            .steps
+            .iter()
+            .all(|s| s.status == StepStatus::Skipped));
+    }
+}
@@ -1,30 +1,49 @@
 //! Intraday Workflow implementation.
 //!
-//! Handles real-time portfolio re-evaluation on market data updates.
-//! Subscribes to MarketDataProvider stream and updates risk metrics.
+//! Event-driven portfolio re-evaluation on market data updates.
+//! Subscribes to a market data stream, marks trades affected by each
+//! update dirty, reprices only those trades and publishes the PV deltas
+//! over the WebSocket sink.
+//!
+//! In [`DemoMode::Replay`] the market data stream is a recorded tick file
+//! played back at the configured speed instead of the simulated feed.
+//! Otherwise, if `replay.record_dir` is set, every quote received is
+//! captured there for later replay.
 
+use super::replay::ReplayFeed;
+use super::repricing::{RepricingEngine, RiskFactor};
 use super::{DemoWorkflow, ProgressCallback, WorkflowResult, WorkflowStep};
-use crate::config::DemoConfig;
+use crate::config::{DemoConfig, DemoMode};
 use crate::error::DemoError;
+use adapter_feeds::capture::TickCapture;
+use adapter_feeds::MarketQuote;
+use async_channel::Receiver;
 use async_trait::async_trait;
-use demo_inputs::prelude::{FrontOffice, TradeSource};
+use demo_inputs::prelude::{FrontOffice, MeanReversionModel, StreamingPriceGenerator, TradeSource};
 use demo_inputs::trade_source::{TradeParams, TradeRecord};
 use demo_outputs::prelude::WebSocketSink;
-// MetricType and MetricUpdate are available for real-time dashboard updates
-#[allow(unused_imports)]
-use demo_outputs::risk_dashboard::{MetricType, MetricUpdate};
 use pricer_core::types::Currency;
-use pricer_models::demo::{BlackScholes, InstrumentEnum, ModelEnum, VanillaSwap};
+use pricer_models::demo::{BlackScholes, CmsSwap, InstrumentEnum, ModelEnum, VanillaSwap};
 use pricer_optimiser::provider::MarketProvider;
-use pricer_risk::demo::{run_portfolio_pricing_sequential, DemoTrade};
+use pricer_risk::demo::DemoTrade;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Instant;
+use tokio::time::Duration;
+
+/// How long to wait for a market event before re-checking the running flag
+const IDLE_POLL_MS: u64 = 200;
 
 /// Intraday Workflow
 pub struct IntradayWorkflow {
     /// Running flag
     running: Arc<AtomicBool>,
+    /// Sink receiving repricing deltas
+    sink: Arc<WebSocketSink>,
+    /// External market data stream (simulated if absent)
+    feed: Option<Receiver<MarketQuote>>,
+    /// Interval between simulated market data updates
+    update_interval_ms: u64,
 }
 
 impl IntradayWorkflow {
@@ -32,24 +51,62 @@ impl IntradayWorkflow {
     pub fn new() -> Self {
         Self {
             running: Arc::new(AtomicBool::new(false)),
+            sink: Arc::new(WebSocketSink::new()),
+            feed: None,
+            update_interval_ms: 50,
         }
     }
 
+    /// Publish repricing deltas to an existing WebSocket sink
+    pub fn with_sink(mut self, sink: Arc<WebSocketSink>) -> Self {
+        self.sink = sink;
+        self
+    }
+
+    /// Subscribe to an external market data stream
+    ///
+    /// Quote identifiers follow `<CCY>.CURVE` and `<CCY>.VOL`. The workflow
+    /// stops when the stream closes.
+    pub fn with_market_feed(mut self, feed: Receiver<MarketQuote>) -> Self {
+        self.feed = Some(feed);
+        self
+    }
+
+    /// Set the interval between simulated market data updates
+    pub fn with_update_interval(mut self, interval_ms: u64) -> Self {
+        self.update_interval_ms = interval_ms;
+        self
+    }
+
+    /// WebSocket sink receiving repricing deltas
+    pub fn sink(&self) -> Arc<WebSocketSink> {
+        Arc::clone(&self.sink)
+    }
+
     /// Check if the workflow is currently running
     pub fn is_running(&self) -> bool {
         self.running.load(Ordering::SeqCst)
     }
 
     /// Convert TradeRecord to DemoTrade for pricing
+    ///
+    /// Options are priced as CMS swaps so that they depend on the
+    /// volatility surface.
     fn convert_trade_record(record: &TradeRecord) -> DemoTrade {
         let ccy = Self::parse_currency(&record.currency);
         let fixed_rate = Self::extract_fixed_rate(record);
+        let instrument = match record.params {
+            TradeParams::EquityOption { .. } | TradeParams::FxOption { .. } => {
+                InstrumentEnum::CmsSwap(CmsSwap { fixed_rate })
+            }
+            _ => InstrumentEnum::VanillaSwap(VanillaSwap { fixed_rate }),
+        };
 
         DemoTrade::new(
             record.trade_id.clone(),
             ccy,
             ModelEnum::BlackScholes(BlackScholes { vol: 0.2 }),
-            InstrumentEnum::VanillaSwap(VanillaSwap { fixed_rate }),
+            instrument,
         )
     }
 
@@ -77,45 +134,69 @@ impl IntradayWorkflow {
         }
     }
 
-    /// Calculate risk metrics from pricing results
-    fn calculate_risk_metrics(
-        trade_records: &[TradeRecord],
-        pv_values: &[f64],
-        prev_pv: Option<f64>,
-    ) -> RiskMetrics {
-        let total_pv: f64 = pv_values
-            .iter()
-            .zip(trade_records.iter())
-            .map(|(pv, t)| pv * t.notional)
-            .sum();
-
-        // Mock Greeks calculation
-        let delta = total_pv * 0.05;
-        let gamma = total_pv * 0.001;
-        let vega = total_pv * 0.02;
-
-        // PnL if we have previous PV
-        let pnl = prev_pv.map(|prev| total_pv - prev).unwrap_or(0.0);
-
-        RiskMetrics {
-            total_pv,
-            delta,
-            gamma,
-            vega,
-            pnl,
+    /// Start capturing market data into `dir`, logging rather than failing
+    fn open_capture(dir: &std::path::Path) -> Option<TickCapture> {
+        match TickCapture::new(dir) {
+            Ok(capture) => {
+                tracing::info!("Capturing market data into {}", dir.display());
+                Some(capture)
+            }
+            Err(e) => {
+                tracing::warn!("Market data capture disabled: {}", e);
+                None
+            }
         }
     }
-}
 
-/// Risk metrics for a single update
-#[derive(Debug, Clone)]
-#[allow(dead_code)]
-struct RiskMetrics {
-    total_pv: f64,
-    delta: f64,
-    gamma: f64,
-    vega: f64,
-    pnl: f64,
+    /// Record a quote, abandoning the capture on the first write error
+    fn capture_quote(capture: &mut Option<TickCapture>, quote: &MarketQuote) {
+        if let Some(active) = capture.as_mut() {
+            if let Err(e) = active.record(quote) {
+                tracing::warn!("Market data capture stopped: {}", e);
+                *capture = None;
+            }
+        }
+    }
+
+    /// Simulate a market data stream over the portfolio's risk factors
+    ///
+    /// Each tick moves one risk factor, cycling through them, so that
+    /// every update only affects part of the portfolio.
+    fn spawn_simulated_feed(&self, engine: &RepricingEngine) -> Receiver<MarketQuote> {
+        let (tx, rx) = async_channel::bounded(1000);
+
+        let mut generators: Vec<StreamingPriceGenerator> = engine
+            .risk_factors()
+            .into_iter()
+            .map(|factor| {
+                let level = engine.level_of(factor).unwrap_or_default();
+                let model = match factor {
+                    RiskFactor::Curve(_) => MeanReversionModel::for_rates(level),
+                    RiskFactor::Vol(_) => MeanReversionModel::new(0.5, level, 0.05),
+                };
+                StreamingPriceGenerator::new(factor.identifier(), level, Box::new(model))
+                    .with_dt(1.0 / 252.0)
+                    .with_spread_bps(0.0)
+            })
+            .collect();
+        let running = Arc::clone(&self.running);
+        let interval_ms = self.update_interval_ms;
+
+        tokio::spawn(async move {
+            let n = generators.len();
+            let mut tick = 0;
+            while running.load(Ordering::SeqCst) && n > 0 {
+                tokio::time::sleep(Duration::from_millis(interval_ms)).await;
+                let quote = generators[tick % n].next_quote();
+                if tx.send(quote).await.is_err() {
+                    return; // Channel closed
+                }
+                tick += 1;
+            }
+        });
+
+        rx
+    }
 }
 
 impl Default for IntradayWorkflow {
@@ -159,57 +240,106 @@ impl DemoWorkflow for IntradayWorkflow {
             .iter()
             .map(Self::convert_trade_record)
             .collect();
+        let notionals: Vec<f64> = trade_records.iter().map(|t| t.notional).collect();
 
         if let Some(ref cb) = progress {
             cb(WorkflowStep::LoadingTrades, 1.0);
         }
 
-        // Create WebSocket sink for real-time updates
-        let _ws_sink = WebSocketSink::new();
+        // Initial full valuation and risk factor dependency index
+        let mut engine = RepricingEngine::new(
+            demo_trades,
+            notionals,
+            MarketProvider::new(),
+            Arc::clone(&self.sink),
+        );
 
-        // Market provider for pricing
-        let market = MarketProvider::new();
+        // Subscribe to market data
+        let mut replay_ticks = None;
+        let feed = match &self.feed {
+            Some(feed) => feed.clone(),
+            None if config.mode == DemoMode::Replay => {
+                let recording = ReplayFeed::from_config(&config.replay).inspect_err(|_| {
+                    self.running.store(false, Ordering::SeqCst);
+                })?;
+                replay_ticks = Some(recording.len());
+                recording.spawn(Arc::clone(&self.running))
+            }
+            None => self.spawn_simulated_feed(&engine),
+        };
+
+        let mut capture = match (&replay_ticks, &config.replay.record_dir) {
+            (None, Some(dir)) => Self::open_capture(dir),
+            _ => None,
+        };
 
         if let Some(ref cb) = progress {
             cb(WorkflowStep::LoadingMarketData, 1.0);
         }
 
         let mut updates_processed = 0;
-        let max_updates = config.max_trades.unwrap_or(10);
-        let mut prev_total_pv: Option<f64> = None;
+        let mut ticks_received = 0;
+        // A replay runs until the recording ends
+        let max_updates = match replay_ticks {
+            Some(_) => usize::MAX,
+            None => config.max_trades.unwrap_or(10),
+        };
 
-        // Intraday processing loop
+        // Event loop: wait for market data, reprice the affected trades
         while self.running.load(Ordering::SeqCst) && updates_processed < max_updates {
-            // Simulate market data update delay (100ms target for real-time)
-            tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
-
-            // Re-price portfolio
-            let pricing_results = run_portfolio_pricing_sequential(&demo_trades, &market);
-            let pv_values: Vec<f64> = pricing_results.iter().map(|r| r.pv).collect();
+            let quote = match tokio::time::timeout(Duration::from_millis(IDLE_POLL_MS), feed.recv())
+                .await
+            {
+                Ok(Ok(quote)) => quote,
+                Ok(Err(_)) => break, // Feed closed
+                Err(_) => continue,  // Idle, re-check running flag
+            };
+            Self::capture_quote(&mut capture, &quote);
+            engine.apply_quote(&quote);
+            ticks_received += 1;
+
+            // Coalesce updates already queued into the same dirty set
+            while let Ok(quote) = feed.try_recv() {
+                Self::capture_quote(&mut capture, &quote);
+                engine.apply_quote(&quote);
+                ticks_received += 1;
+            }
 
-            // Calculate risk metrics
-            let metrics = Self::calculate_risk_metrics(&trade_records, &pv_values, prev_total_pv);
-            prev_total_pv = Some(metrics.total_pv);
+            let Some(result) = engine.reprice() else {
+                continue;
+            };
 
-            // Log metrics
             tracing::debug!(
-                "Update {}: PV={:.2}, Delta={:.2}, PnL={:.2}",
+                "Update {}: repriced {} trades, PV={:.2}, PnL={:.2}",
                 updates_processed + 1,
-                metrics.total_pv,
-                metrics.delta,
-                metrics.pnl
+                result.deltas.len(),
+                result.total_pv,
+                result.pnl
             );
 
             // Report progress
             updates_processed += 1;
             if let Some(ref cb) = progress {
-                let pct = updates_processed as f64 / max_updates as f64;
+                let pct = match replay_ticks {
+                    Some(total) => ticks_received as f64 / total.max(1) as f64,
+                    None => updates_processed as f64 / max_updates as f64,
+                };
                 cb(WorkflowStep::Pricing, pct);
             }
         }
 
         self.running.store(false, Ordering::SeqCst);
 
+        if let Some(capture) = capture {
+            let recorded = capture.ticks_recorded();
+            match capture.finish() {
+                Ok(files) => {
+                    tracing::info!("Captured {} ticks into {} file(s)", recorded, files.len())
+                }
+                Err(e) => tracing::warn!("Failed to close tick capture: {}", e),
+            }
+        }
+
         if let Some(ref cb) = progress {
             cb(WorkflowStep::Completed, 1.0);
         }
@@ -222,10 +352,11 @@ impl DemoWorkflow for IntradayWorkflow {
         };
 
         tracing::info!(
-            "Intraday workflow completed: {} updates in {}ms (avg {:.0}ms/update)",
+            "Intraday workflow completed: {} updates in {}ms (avg {:.0}ms/update, {} trades repriced)",
             updates_processed,
             duration_ms,
-            avg_latency
+            avg_latency,
+            engine.trades_repriced()
         );
 
         Ok(WorkflowResult::success(duration_ms, updates_processed))
@@ -272,6 +403,112 @@ mod tests {
         // Should complete with 0 updates due to immediate cancel
     }
 
+    #[tokio::test]
+    async fn test_intraday_reprices_on_market_feed() {
+        let (tx, rx) = async_channel::unbounded();
+        let workflow = IntradayWorkflow::new().with_market_feed(rx);
+        let mut ws = workflow.sink().subscribe();
+        let config = DemoConfig {
+            max_trades: Some(10),
+            ..DemoConfig::default()
+        };
+
+        // Two effective updates; the unknown quote and repeated level are ignored
+        tx.send(MarketQuote::with_last("USD.CURVE", 0.051))
+            .await
+            .unwrap();
+        tx.send(MarketQuote::with_last("AAPL", 185.0))
+            .await
+            .unwrap();
+        tx.send(MarketQuote::with_last("USD.CURVE", 0.051))
+            .await
+            .unwrap();
+        tx.send(MarketQuote::with_last("EUR.CURVE", 0.029))
+            .await
+            .unwrap();
+        drop(tx);
+
+        let result = workflow.run(&config, None).await.unwrap();
+        assert!(result.success);
+        assert!(result.trades_processed >= 1);
+        assert!(ws.try_recv().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_intraday_replays_tick_file() {
+        use adapter_feeds::capture::TickRecorder;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.ticks");
+        let mut recorder = TickRecorder::create(&path).unwrap();
+        for i in 0..24 {
+            let quote =
+                MarketQuote::with_last("USD.CURVE", 0.05 + i as f64 * 1e-4).with_timestamp(i * 25);
+            recorder.record(&quote).unwrap();
+        }
+        recorder.finish().unwrap();
+
+        let workflow = IntradayWorkflow::new();
+        let mut ws = workflow.sink().subscribe();
+        let mut config = DemoConfig {
+            mode: DemoMode::Replay,
+            // Fewer than the recorded updates; a replay still plays to the end
+            max_trades: Some(20),
+            ..DemoConfig::default()
+        };
+        config.replay.tick_file = Some(path);
+
+        let result = workflow.run(&config, None).await.unwrap();
+        assert!(result.success);
+        assert!(result.trades_processed > 20);
+        assert!(ws.try_recv().is_ok());
+        assert!(!workflow.is_running());
+
+        config.replay.tick_file = Some(dir.path().join("missing.ticks"));
+        assert!(matches!(
+            workflow.run(&config, None).await,
+            Err(DemoError::DataLoad(_))
+        ));
+        assert!(!workflow.is_running());
+    }
+
+    #[tokio::test]
+    async fn test_intraday_captures_feed_for_replay() {
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, rx) = async_channel::unbounded();
+        let workflow = IntradayWorkflow::new().with_market_feed(rx);
+        let mut config = DemoConfig {
+            max_trades: Some(10),
+            ..DemoConfig::default()
+        };
+        config.replay.record_dir = Some(dir.path().join("capture"));
+
+        for (i, level) in [0.051, 0.052, 0.053].into_iter().enumerate() {
+            tx.send(MarketQuote::with_last("USD.CURVE", level).with_timestamp(1_000 + i as i64))
+                .await
+                .unwrap();
+        }
+        drop(tx);
+        workflow.run(&config, None).await.unwrap();
+
+        let replayed = adapter_feeds::capture::read_capture(dir.path().join("capture")).unwrap();
+        let levels: Vec<Option<f64>> = replayed.iter().map(|q| q.last).collect();
+        assert_eq!(levels, [Some(0.051), Some(0.052), Some(0.053)]);
+    }
+
+    #[test]
+    fn test_options_depend_on_vol() {
+        let front_office = FrontOffice::new();
+        for record in front_office.generate_trades(50) {
+            let trade = IntradayWorkflow::convert_trade_record(&record);
+            let is_option = matches!(
+                record.params,
+                TradeParams::EquityOption { .. } | TradeParams::FxOption { .. }
+            );
+            assert_eq!(trade.instrument.requires_vol(), is_option);
+        }
+    }
+
     #[test]
     fn test_parse_currency() {
         assert_eq!(IntradayWorkflow::parse_currency("USD"), Currency::USD);
@@ -9,11 +9,19 @@
 mod eod_batch;
 mod intraday;
 mod irs_aad;
+mod replay;
+mod repricing;
 mod stress_test;
 
-pub use eod_batch::EodBatchWorkflow;
+pub use eod_batch::{
+    BatchPipeline, BatchStep, Checkpoint, CheckpointStore, EodBatchWorkflow, EodState, EodTrade,
+    ExposureProfile, MarketSnapshot, NettingSetXvaSummary, RetryPolicy, RunManifest, RunStatus,
+    StepRecord, StepStatus,
+};
 pub use intraday::IntradayWorkflow;
 pub use irs_aad::{IrsAadConfig, IrsAadWorkflow, IrsComputeResult, IrsParams, XvaDemoResult};
+pub use replay::ReplayFeed;
+pub use repricing::{RepricingEngine, RepricingResult, RiskFactor, PORTFOLIO_ENTITY};
 pub use stress_test::{PresetScenarioType, ScenarioResult, StressTestResult, StressTestWorkflow};
 
 use crate::config::DemoConfig;
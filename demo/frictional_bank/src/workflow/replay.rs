@@ -0,0 +1,174 @@
+//! Recorded market data playback.
+//!
+//! Plays a capture written by `adapter_feeds::capture` (a single tick file
+//! or a capture directory) back onto a
+//! market data channel, preserving the recorded gaps between ticks scaled
+//! by the configured speed. Consumers see the same stream of quotes as
+//! they would from a live feed, so demos and tests are reproducible.
+
+use crate::config::ReplayConfig;
+use crate::error::DemoError;
+use adapter_feeds::capture::read_capture;
+use adapter_feeds::MarketQuote;
+use async_channel::Receiver;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::time::Duration;
+
+/// Recorded ticks ready for playback
+#[derive(Debug, Clone)]
+pub struct ReplayFeed {
+    /// Ticks in recorded order
+    ticks: Vec<MarketQuote>,
+    /// Playback speed relative to the recording
+    speed: f64,
+}
+
+impl ReplayFeed {
+    /// Create a feed from ticks already in memory
+    pub fn new(ticks: Vec<MarketQuote>) -> Self {
+        Self { ticks, speed: 1.0 }
+    }
+
+    /// Load the tick file or capture directory named in the replay settings
+    pub fn from_config(config: &ReplayConfig) -> Result<Self, DemoError> {
+        let path = config
+            .tick_file
+            .as_ref()
+            .ok_or_else(|| DemoError::validation("Replay mode requires replay.tick_file"))?;
+        let ticks = read_capture(path)
+            .map_err(|e| DemoError::data_load(format!("Tick file {}: {}", path.display(), e)))?;
+        tracing::info!("Loaded {} ticks from {}", ticks.len(), path.display());
+        Ok(Self::new(ticks).with_speed(config.speed))
+    }
+
+    /// Set the playback speed (2.0 plays twice as fast as recorded)
+    pub fn with_speed(mut self, speed: f64) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Recorded ticks
+    pub fn ticks(&self) -> &[MarketQuote] {
+        &self.ticks
+    }
+
+    /// Number of recorded ticks
+    pub fn len(&self) -> usize {
+        self.ticks.len()
+    }
+
+    /// Whether the recording is empty
+    pub fn is_empty(&self) -> bool {
+        self.ticks.is_empty()
+    }
+
+    /// Wall-clock time to wait before publishing tick `index`
+    pub fn delay_before(&self, index: usize) -> Duration {
+        if index == 0 || index >= self.ticks.len() || self.speed <= 0.0 {
+            return Duration::ZERO;
+        }
+        let gap_ms = (self.ticks[index].timestamp_ms - self.ticks[index - 1].timestamp_ms).max(0);
+        Duration::from_secs_f64(gap_ms as f64 / 1_000.0 / self.speed)
+    }
+
+    /// Total playback time at the configured speed
+    pub fn playback_duration(&self) -> Duration {
+        (1..self.ticks.len()).map(|i| self.delay_before(i)).sum()
+    }
+
+    /// Start playback onto a new market data channel.
+    ///
+    /// The channel closes after the last tick, or early once `running`
+    /// is cleared.
+    pub fn spawn(&self, running: Arc<AtomicBool>) -> Receiver<MarketQuote> {
+        let (tx, rx) = async_channel::bounded(1000);
+        let feed = self.clone();
+
+        tokio::spawn(async move {
+            for (i, quote) in feed.ticks.iter().enumerate() {
+                let delay = feed.delay_before(i);
+                if !delay.is_zero() {
+                    tokio::time::sleep(delay).await;
+                }
+                if !running.load(Ordering::SeqCst) || tx.send(quote.clone()).await.is_err() {
+                    return;
+                }
+            }
+        });
+
+        rx
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use adapter_feeds::capture::TickRecorder;
+
+    fn ticks() -> Vec<MarketQuote> {
+        vec![
+            MarketQuote::with_last("USD.CURVE", 0.051).with_timestamp(1_000),
+            MarketQuote::with_last("EUR.CURVE", 0.029).with_timestamp(1_400),
+            MarketQuote::with_last("USD.VOL", 0.21).with_timestamp(2_000),
+        ]
+    }
+
+    #[test]
+    fn test_delays_scale_with_speed() {
+        let feed = ReplayFeed::new(ticks()).with_speed(2.0);
+        assert_eq!(feed.delay_before(0), Duration::ZERO);
+        assert_eq!(feed.delay_before(1), Duration::from_millis(200));
+        assert_eq!(feed.delay_before(2), Duration::from_millis(300));
+        assert_eq!(feed.playback_duration(), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_from_config_reads_tick_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.ticks");
+        let mut recorder = TickRecorder::create(&path).unwrap();
+        for quote in ticks() {
+            recorder.record(&quote).unwrap();
+        }
+        recorder.flush().unwrap();
+
+        let config = ReplayConfig {
+            tick_file: Some(path),
+            speed: 4.0,
+            record_dir: None,
+        };
+        let feed = ReplayFeed::from_config(&config).unwrap();
+        assert_eq!(feed.len(), 3);
+        assert_eq!(feed.delay_before(1), Duration::from_millis(100));
+
+        let missing = ReplayConfig {
+            tick_file: Some(dir.path().join("missing.ticks")),
+            speed: 1.0,
+            record_dir: None,
+        };
+        assert!(matches!(
+            ReplayFeed::from_config(&missing),
+            Err(DemoError::DataLoad(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_playback_preserves_order_and_closes() {
+        let feed = ReplayFeed::new(ticks()).with_speed(100.0);
+        let rx = feed.spawn(Arc::new(AtomicBool::new(true)));
+
+        let mut received = Vec::new();
+        while let Ok(quote) = rx.recv().await {
+            received.push(quote.identifier);
+        }
+        assert_eq!(received, ["USD.CURVE", "EUR.CURVE", "USD.VOL"]);
+    }
+
+    #[tokio::test]
+    async fn test_playback_stops_when_not_running() {
+        let feed = ReplayFeed::new(ticks());
+        let rx = feed.spawn(Arc::new(AtomicBool::new(false)));
+        assert!(rx.recv().await.is_err());
+    }
+}
@@ -0,0 +1,378 @@
+//! Event-driven intraday repricing engine.
+//!
+//! Market data updates are mapped to risk factors. Each risk factor knows
+//! which trades depend on it, so an update only marks those trades dirty.
+//! A repricing pass prices the dirty set, and the PV changes are published
+//! to the WebSocket sink as a metric batch.
+//!
+//! Quote identifiers follow `<CCY>.CURVE` (flat curve rate) and
+//! `<CCY>.VOL` (SABR alpha), e.g. `USD.CURVE` or `EUR.VOL`.
+
+use adapter_feeds::MarketQuote;
+use chrono::Utc;
+use demo_outputs::risk_dashboard::{MetricType, MetricUpdate, WebSocketSink};
+use pricer_core::types::Currency;
+use pricer_models::demo::{CurveEnum, FlatCurve, SabrVolSurface, VolSurfaceEnum};
+use pricer_optimiser::provider::MarketProvider;
+use pricer_risk::demo::{run_portfolio_pricing_sequential, DemoTrade};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Arc;
+
+/// Entity id for portfolio-level metrics
+pub const PORTFOLIO_ENTITY: &str = "PORTFOLIO";
+
+/// Market risk factor a trade price depends on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RiskFactor {
+    /// Discount curve of a currency
+    Curve(Currency),
+    /// Volatility surface of a currency
+    Vol(Currency),
+}
+
+impl RiskFactor {
+    /// Parse a quote identifier such as `USD.CURVE` or `EUR.VOL`
+    pub fn from_identifier(identifier: &str) -> Option<Self> {
+        let (ccy, kind) = identifier.split_once('.')?;
+        let ccy = ccy.parse::<Currency>().ok()?;
+        match kind {
+            "CURVE" => Some(Self::Curve(ccy)),
+            "VOL" => Some(Self::Vol(ccy)),
+            _ => None,
+        }
+    }
+
+    /// Quote identifier for this risk factor
+    pub fn identifier(&self) -> String {
+        match self {
+            Self::Curve(ccy) => format!("{}.CURVE", ccy.code()),
+            Self::Vol(ccy) => format!("{}.VOL", ccy.code()),
+        }
+    }
+
+    /// Risk factors a trade depends on
+    pub fn dependencies(trade: &DemoTrade) -> Vec<Self> {
+        let mut factors = vec![Self::Curve(trade.ccy)];
+        if trade.instrument.requires_vol() {
+            factors.push(Self::Vol(trade.ccy));
+        }
+        factors
+    }
+}
+
+/// Result of one repricing pass
+#[derive(Debug, Clone)]
+pub struct RepricingResult {
+    /// PV change per repriced trade, in notional terms
+    pub deltas: Vec<(String, f64)>,
+    /// Portfolio PV after repricing
+    pub total_pv: f64,
+    /// Portfolio PV change
+    pub pnl: f64,
+}
+
+/// Event-driven repricing engine
+///
+/// Holds the current PV of every trade and a dirty set of trades whose
+/// risk factors moved since the last repricing pass.
+pub struct RepricingEngine {
+    /// Trades being monitored
+    trades: Vec<DemoTrade>,
+    /// Notional per trade
+    notionals: Vec<f64>,
+    /// Current PV per unit notional
+    pvs: Vec<f64>,
+    /// Trades depending on each risk factor
+    dependents: HashMap<RiskFactor, Vec<usize>>,
+    /// Last applied level per risk factor
+    levels: HashMap<RiskFactor, f64>,
+    /// Trades awaiting repricing
+    dirty: BTreeSet<usize>,
+    /// Market data cache used for pricing
+    market: MarketProvider,
+    /// Sink receiving PV deltas
+    sink: Arc<WebSocketSink>,
+    /// Total trades repriced since creation
+    trades_repriced: usize,
+}
+
+impl RepricingEngine {
+    /// Create an engine and price the full portfolio once
+    pub fn new(
+        trades: Vec<DemoTrade>,
+        notionals: Vec<f64>,
+        market: MarketProvider,
+        sink: Arc<WebSocketSink>,
+    ) -> Self {
+        let mut dependents: HashMap<RiskFactor, Vec<usize>> = HashMap::new();
+        for (idx, trade) in trades.iter().enumerate() {
+            for factor in RiskFactor::dependencies(trade) {
+                dependents.entry(factor).or_default().push(idx);
+            }
+        }
+
+        let pvs = run_portfolio_pricing_sequential(&trades, &market)
+            .into_iter()
+            .map(|r| r.pv)
+            .collect();
+
+        let levels = dependents
+            .keys()
+            .map(|&factor| (factor, Self::market_level(&market, factor)))
+            .collect();
+
+        Self {
+            trades,
+            notionals,
+            pvs,
+            dependents,
+            levels,
+            dirty: BTreeSet::new(),
+            market,
+            sink,
+            trades_repriced: 0,
+        }
+    }
+
+    /// Current level of a risk factor in the market cache
+    fn market_level(market: &MarketProvider, factor: RiskFactor) -> f64 {
+        match factor {
+            RiskFactor::Curve(ccy) => match market.get_curve(ccy).as_ref() {
+                CurveEnum::Flat(curve) => curve.rate,
+            },
+            RiskFactor::Vol(ccy) => match market.get_vol(ccy).as_ref() {
+                VolSurfaceEnum::Sabr(sabr) => sabr.alpha,
+            },
+        }
+    }
+
+    /// Risk factors the portfolio depends on
+    pub fn risk_factors(&self) -> Vec<RiskFactor> {
+        self.dependents.keys().copied().collect()
+    }
+
+    /// Last applied level of a risk factor the portfolio depends on
+    pub fn level_of(&self, factor: RiskFactor) -> Option<f64> {
+        self.levels.get(&factor).copied()
+    }
+
+    /// Apply a market data update, marking dependent trades dirty
+    ///
+    /// Returns the number of trades newly marked dirty. Quotes with an
+    /// unknown identifier or no price, or for factors no trade depends on,
+    /// are ignored, as are quotes that leave the level unchanged.
+    pub fn apply_quote(&mut self, quote: &MarketQuote) -> usize {
+        let (Some(factor), Some(level)) = (
+            RiskFactor::from_identifier(&quote.identifier),
+            quote.mid().or(quote.last),
+        ) else {
+            return 0;
+        };
+        let Some(trades) = self.dependents.get(&factor) else {
+            return 0;
+        };
+        if self.levels.get(&factor) == Some(&level) {
+            return 0;
+        }
+
+        match factor {
+            RiskFactor::Curve(ccy) => self
+                .market
+                .set_curve(ccy, CurveEnum::Flat(FlatCurve { rate: level })),
+            RiskFactor::Vol(ccy) => self
+                .market
+                .set_vol(ccy, VolSurfaceEnum::Sabr(SabrVolSurface { alpha: level })),
+        }
+        self.levels.insert(factor, level);
+
+        let before = self.dirty.len();
+        self.dirty.extend(trades.iter().copied());
+        self.dirty.len() - before
+    }
+
+    /// Number of trades awaiting repricing
+    pub fn dirty_count(&self) -> usize {
+        self.dirty.len()
+    }
+
+    /// Total trades repriced since creation
+    pub fn trades_repriced(&self) -> usize {
+        self.trades_repriced
+    }
+
+    /// Portfolio PV in notional terms
+    pub fn total_pv(&self) -> f64 {
+        self.pvs
+            .iter()
+            .zip(&self.notionals)
+            .map(|(pv, notional)| pv * notional)
+            .sum()
+    }
+
+    /// Reprice the dirty trades and publish the PV deltas
+    ///
+    /// Returns `None` if no trade was dirty.
+    pub fn reprice(&mut self) -> Option<RepricingResult> {
+        if self.dirty.is_empty() {
+            return None;
+        }
+
+        let indices: Vec<usize> = std::mem::take(&mut self.dirty).into_iter().collect();
+        let dirty_trades: Vec<DemoTrade> =
+            indices.iter().map(|&i| self.trades[i].clone()).collect();
+        let results = run_portfolio_pricing_sequential(&dirty_trades, &self.market);
+
+        let deltas: Vec<(String, f64)> = indices
+            .iter()
+            .zip(results)
+            .map(|(&i, result)| {
+                let delta = (result.pv - self.pvs[i]) * self.notionals[i];
+                self.pvs[i] = result.pv;
+                (result.trade_id, delta)
+            })
+            .collect();
+        self.trades_repriced += deltas.len();
+
+        let result = RepricingResult {
+            pnl: deltas.iter().map(|(_, delta)| delta).sum(),
+            total_pv: self.total_pv(),
+            deltas,
+        };
+        self.publish(&result);
+        Some(result)
+    }
+
+    /// Publish a repricing result as a metric batch
+    fn publish(&self, result: &RepricingResult) {
+        let timestamp = Utc::now();
+        let metric = |entity_id: &str, value: f64| MetricUpdate {
+            metric_type: MetricType::PnL,
+            entity_id: entity_id.to_string(),
+            value,
+            currency: "USD".to_string(),
+            confidence: None,
+            horizon_days: None,
+            timestamp,
+        };
+
+        let mut batch: Vec<MetricUpdate> = result
+            .deltas
+            .iter()
+            .map(|(trade_id, delta)| metric(trade_id, *delta))
+            .collect();
+        batch.push(metric(PORTFOLIO_ENTITY, result.pnl));
+
+        if let Err(e) = self.sink.send_batch(batch) {
+            tracing::warn!("Failed to publish repricing deltas: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use demo_outputs::risk_dashboard::WebSocketMessage;
+
+    fn engine(sink: Arc<WebSocketSink>) -> RepricingEngine {
+        let trades = vec![
+            DemoTrade::new_vanilla_swap("T001", Currency::USD, 0.02),
+            DemoTrade::new_cms_swap("T002", Currency::USD, 0.025),
+            DemoTrade::new_vanilla_swap("T003", Currency::EUR, 0.01),
+        ];
+        RepricingEngine::new(trades, vec![1e6, 2e6, 3e6], MarketProvider::new(), sink)
+    }
+
+    #[test]
+    fn test_risk_factor_identifiers() {
+        assert_eq!(
+            RiskFactor::from_identifier("USD.CURVE"),
+            Some(RiskFactor::Curve(Currency::USD))
+        );
+        assert_eq!(
+            RiskFactor::from_identifier("eur.VOL"),
+            Some(RiskFactor::Vol(Currency::EUR))
+        );
+        assert_eq!(RiskFactor::from_identifier("AAPL"), None);
+        assert_eq!(RiskFactor::from_identifier("USD.SPOT"), None);
+        assert_eq!(RiskFactor::Vol(Currency::JPY).identifier(), "JPY.VOL");
+    }
+
+    #[test]
+    fn test_only_dependent_trades_marked_dirty() {
+        let mut engine = engine(Arc::new(WebSocketSink::new()));
+        assert_eq!(engine.risk_factors().len(), 3);
+
+        // EUR curve moves only the EUR swap
+        assert_eq!(
+            engine.apply_quote(&MarketQuote::with_last("EUR.CURVE", 0.031)),
+            1
+        );
+        // USD vol moves only the CMS swap
+        assert_eq!(
+            engine.apply_quote(&MarketQuote::with_last("USD.VOL", 0.32)),
+            1
+        );
+        // No trade depends on JPY, unknown quotes are ignored
+        assert_eq!(
+            engine.apply_quote(&MarketQuote::with_last("JPY.CURVE", 0.02)),
+            0
+        );
+        assert_eq!(
+            engine.apply_quote(&MarketQuote::with_last("AAPL", 185.0)),
+            0
+        );
+        assert_eq!(engine.dirty_count(), 2);
+
+        let result = engine.reprice().unwrap();
+        let ids: Vec<&str> = result.deltas.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, vec!["T002", "T003"]);
+        assert!(result.deltas.iter().all(|(_, delta)| *delta != 0.0));
+        assert_eq!(engine.trades_repriced(), 2);
+        assert!(engine.reprice().is_none());
+
+        // Re-applying the same level is a no-op
+        assert_eq!(
+            engine.apply_quote(&MarketQuote::with_last("EUR.CURVE", 0.031)),
+            0
+        );
+    }
+
+    #[test]
+    fn test_incremental_matches_full_repricing() {
+        let mut engine = engine(Arc::new(WebSocketSink::new()));
+        let initial = engine.total_pv();
+
+        engine.apply_quote(&MarketQuote::with_last("USD.CURVE", 0.055));
+        let result = engine.reprice().unwrap();
+
+        let market = MarketProvider::new();
+        market.set_curve(Currency::USD, CurveEnum::Flat(FlatCurve { rate: 0.055 }));
+        let full: f64 = run_portfolio_pricing_sequential(&engine.trades, &market)
+            .iter()
+            .zip(&engine.notionals)
+            .map(|(r, n)| r.pv * n)
+            .sum();
+
+        assert!((result.total_pv - full).abs() < 1e-9);
+        assert!((result.pnl - (full - initial)).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_deltas_published_to_sink() {
+        let sink = Arc::new(WebSocketSink::new());
+        let mut rx = sink.subscribe();
+        let mut engine = engine(Arc::clone(&sink));
+
+        engine.apply_quote(&MarketQuote::with_last("USD.CURVE", 0.04));
+        let result = engine.reprice().unwrap();
+
+        match rx.recv().await.unwrap() {
+            WebSocketMessage::MetricBatch(batch) => {
+                assert_eq!(batch.len(), 3);
+                assert_eq!(batch[2].entity_id, PORTFOLIO_ENTITY);
+                assert!((batch[2].value - result.pnl).abs() < 1e-9);
+            }
+            other => panic!("Expected MetricBatch, got {:?}", other),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 //! TUI Application state and event handling.
 
 use crate::api_client::ApiClient;
+use crate::portfolio_view::PortfolioView;
 use crate::screens;
 use anyhow::Result;
 use crossterm::{
@@ -30,6 +31,10 @@ pub enum Screen {
     Chart,
     /// IRS AAD Demo screen (Task 6.2)
     IrsAadDemo,
+    /// Counterparty → netting set → trade drill-down
+    DrillDown,
+    /// Blotter with live Greeks and what-if bumps
+    LiveGreeks,
 }
 
 impl Screen {
@@ -42,6 +47,8 @@ impl Screen {
             Self::TradeBlotter => "Trade Blotter",
             Self::Chart => "Exposure Chart",
             Self::IrsAadDemo => "IRS AAD Demo",
+            Self::DrillDown => "Portfolio Drill-down",
+            Self::LiveGreeks => "Live Greeks",
         }
     }
 }
@@ -298,6 +305,8 @@ struct RenderState {
     exposure_series: ExposureTimeSeries,
     /// IRS AAD Demo state (Task 6.2)
     irs_aad_state: IrsAadDemoState,
+    /// Drill-down and live Greeks state
+    portfolio_view: PortfolioView,
 }
 
 /// TUI Application state
@@ -314,6 +323,8 @@ pub struct TuiApp {
     exposure_series: ExposureTimeSeries,
     /// IRS AAD Demo state (Task 6.2)
     irs_aad_state: IrsAadDemoState,
+    /// Drill-down and live Greeks state
+    portfolio_view: PortfolioView,
     /// Exit flag
     should_quit: bool,
     /// API client
@@ -340,6 +351,7 @@ impl TuiApp {
             risk_metrics: Self::sample_risk_metrics(),
             exposure_series: ExposureTimeSeries::default(),
             irs_aad_state: IrsAadDemoState::default(),
+            portfolio_view: PortfolioView::default(),
             should_quit: false,
             api_client: ApiClient::new("http://localhost:8080".to_string()),
             terminal,
@@ -401,6 +413,7 @@ impl TuiApp {
             risk_metrics: self.risk_metrics.clone(),
             exposure_series: self.exposure_series.clone(),
             irs_aad_state: self.irs_aad_state.clone(),
+            portfolio_view: self.portfolio_view.clone(),
         }
     }
 
@@ -434,6 +447,17 @@ impl TuiApp {
 
     /// Handle keyboard input
     fn handle_key(&mut self, key: KeyCode) {
+        // Drill-down and blotter keys take precedence, so the blotter
+        // filter can capture any character while it is being edited
+        let consumed = match self.current_screen {
+            Screen::DrillDown => self.portfolio_view.handle_drilldown_key(key),
+            Screen::LiveGreeks => self.portfolio_view.handle_blotter_key(key),
+            _ => false,
+        };
+        if consumed {
+            return;
+        }
+
         match key {
             KeyCode::Char('q') | KeyCode::Esc => self.should_quit = true,
             KeyCode::Char('1') => self.current_screen = Screen::Dashboard,
@@ -442,6 +466,8 @@ impl TuiApp {
             KeyCode::Char('4') => self.current_screen = Screen::TradeBlotter,
             KeyCode::Char('5') => self.current_screen = Screen::Chart,
             KeyCode::Char('6') => self.current_screen = Screen::IrsAadDemo,
+            KeyCode::Char('7') => self.current_screen = Screen::DrillDown,
+            KeyCode::Char('8') => self.current_screen = Screen::LiveGreeks,
             KeyCode::Up | KeyCode::Char('k') => {
                 if self.current_screen == Screen::IrsAadDemo {
                     // Navigate IRS AAD Demo fields (Task 6.2)
@@ -606,6 +632,10 @@ impl TuiApp {
             Screen::IrsAadDemo => {
                 screens::draw_irs_aad_demo(frame, chunks[1], &state.irs_aad_state)
             }
+            Screen::DrillDown => screens::draw_drilldown(frame, chunks[1], &state.portfolio_view),
+            Screen::LiveGreeks => {
+                screens::draw_live_greeks(frame, chunks[1], &state.portfolio_view)
+            }
         }
 
         // Draw footer
@@ -628,7 +658,7 @@ impl TuiApp {
     /// Draw footer with keybindings
     fn draw_footer(frame: &mut Frame, area: Rect) {
         let footer_text =
-            " [1]Dashboard [2]Portfolio [3]Risk [4]Blotter [5]Chart [6]IRS AAD | [Up/Down]Nav | [q]Quit ";
+            " [1]Dashboard [2]Portfolio [3]Risk [4]Blotter [5]Chart [6]IRS AAD [7]Drill-down [8]Greeks | [Up/Down]Nav | [q]Quit ";
         let footer = Paragraph::new(footer_text)
             .style(Style::default().fg(Color::DarkGray))
             .block(Block::default().borders(Borders::ALL));
@@ -668,6 +698,12 @@ mod tests {
         assert_eq!(Screen::Portfolio.title(), "Portfolio");
     }
 
+    #[test]
+    fn test_drilldown_screen_titles() {
+        assert_eq!(Screen::DrillDown.title(), "Portfolio Drill-down");
+        assert_eq!(Screen::LiveGreeks.title(), "Live Greeks");
+    }
+
     #[test]
     fn test_chart_screen_exists() {
         // Test that Chart screen has correct title
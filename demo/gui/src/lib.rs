@@ -11,6 +11,9 @@
 //! - **Risk**: CVA, DVA, FVA, Exposure display
 //! - **TradeBlotter**: Selected trade details
 //! - **Chart**: Exposure time series chart
+//! - **DrillDown**: Counterparty → netting set → trade hierarchy
+//! - **LiveGreeks**: Sortable, filterable blotter with live Greeks and
+//!   what-if bumps
 //!
 //! ## Web Mode (feature: `web`)
 //! Uses axum for REST API and WebSocket support.
@@ -24,6 +27,7 @@
 
 pub mod api_client;
 pub mod app;
+pub mod portfolio_view;
 pub mod screens;
 pub mod visualisation;
 
@@ -37,6 +41,7 @@ pub mod prelude {
         ExposureTimeSeries, IrsAadBenchmark, IrsAadDemoState, IrsAadParams, IrsAadResult, Screen,
         TuiApp,
     };
+    pub use crate::portfolio_view::{BumpFactor, PortfolioView, SortColumn};
     pub use crate::visualisation::{
         AccuracyVerificationData, AccuracyVisualiser, BenchmarkVisualiser, ComputationFlowDiagram,
         ScalabilityData, ScalabilityVisualiser, SpeedComparisonData,
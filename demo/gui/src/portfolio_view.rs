@@ -0,0 +1,1078 @@
+//! Portfolio drill-down and live Greeks state for the TUI.
+//!
+//! The demo book is held as a counterparty → netting set → trade hierarchy.
+//! Options and forwards are priced with the analytical Black-Scholes
+//! pricer from `pricer_models`, swaps on the flat rate. A what-if bump can be layered on the base market from
+//! the keyboard; every bump re-prices the whole book, so the drill-down
+//! and the blotter show base PV, bumped ("live") Greeks and the P&L of the
+//! bump side by side. The blotter's detail pane breaks the selected trade
+//! into its expected cashflows on the bumped market with
+//! `Instrument::cashflows`.
+//!
+//! Greek conventions, all scaled by trade notional (units of underlying
+//! for options and forwards, currency amount for swaps):
+//! - delta and gamma per unit move in spot
+//! - vega per volatility point
+//! - theta per calendar day
+//! - rho per basis point
+
+use crossterm::event::KeyCode;
+use pricer_core::market_data::curves::CurveEnum;
+use pricer_core::market_data::MarketSnapshot;
+use pricer_core::types::time::Date;
+use pricer_core::types::Currency;
+use pricer_models::analytical::BlackScholes;
+use pricer_models::instruments::{
+    Direction, ExerciseStyle, Forward, Instrument, InstrumentParams, PaymentFrequency, PayoffType,
+    Swap, ValuedCashflow, VanillaOption,
+};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+/// Relative spot move per bump step (1%)
+const SPOT_STEP: f64 = 0.01;
+/// Absolute volatility move per bump step (1 vol point)
+const VOL_STEP: f64 = 0.01;
+/// Absolute rate move per bump step (10bp)
+const RATE_STEP: f64 = 0.001;
+/// Lowest volatility a bump can reach
+const MIN_VOL: f64 = 0.001;
+/// Days per year for theta
+const DAYS_PER_YEAR: f64 = 365.0;
+
+/// Product terms of a trade
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Product {
+    /// European call
+    Call { strike: f64, expiry: f64 },
+    /// European put
+    Put { strike: f64, expiry: f64 },
+    /// Outright forward
+    Forward { strike: f64, expiry: f64 },
+    /// Pay-fixed swap with annual fixed payments
+    PayerSwap { fixed_rate: f64, tenor: u32 },
+    /// Receive-fixed swap with annual fixed payments
+    ReceiverSwap { fixed_rate: f64, tenor: u32 },
+}
+
+impl Product {
+    /// Short description for tables
+    pub fn label(&self) -> String {
+        match self {
+            Self::Call { strike, expiry } => format!("Call {} {}Y", strike, expiry),
+            Self::Put { strike, expiry } => format!("Put {} {}Y", strike, expiry),
+            Self::Forward { strike, expiry } => format!("Fwd {} {}Y", strike, expiry),
+            Self::PayerSwap { fixed_rate, tenor } => {
+                format!("{}Y IRS Pay {:.2}%", tenor, fixed_rate * 100.0)
+            }
+            Self::ReceiverSwap { fixed_rate, tenor } => {
+                format!("{}Y IRS Rec {:.2}%", tenor, fixed_rate * 100.0)
+            }
+        }
+    }
+}
+
+/// A trade in the demo book
+#[derive(Debug, Clone, PartialEq)]
+pub struct BookTrade {
+    /// Trade identifier
+    pub id: String,
+    /// Counterparty name
+    pub counterparty: String,
+    /// Netting set identifier
+    pub netting_set: String,
+    /// Underlying name; swaps use their currency
+    pub underlying: String,
+    /// Product terms
+    pub product: Product,
+    /// Units of underlying, or currency notional for swaps
+    pub notional: f64,
+}
+
+/// Market levels the book is priced on
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarketState {
+    /// Spot by underlying
+    pub spots: BTreeMap<String, f64>,
+    /// Flat continuously-compounded rate
+    pub rate: f64,
+    /// Flat volatility
+    pub vol: f64,
+}
+
+impl MarketState {
+    /// Market with a bump applied
+    pub fn bumped(&self, bump: &WhatIfBump) -> Self {
+        self.shocked(
+            bump.spot_steps as f64 * SPOT_STEP * 100.0,
+            bump.vol_steps as f64 * VOL_STEP * 100.0,
+            bump.rate_steps as f64 * RATE_STEP * 1e4,
+        )
+    }
+
+    /// Market with spots moved by `spot_pct` percent, volatility by
+    /// `vol_pts` points and the rate by `rate_bp` basis points
+    pub fn shocked(&self, spot_pct: f64, vol_pts: f64, rate_bp: f64) -> Self {
+        let spot_factor = 1.0 + spot_pct * 0.01;
+        Self {
+            spots: self
+                .spots
+                .iter()
+                .map(|(name, spot)| (name.clone(), (spot * spot_factor).max(f64::EPSILON)))
+                .collect(),
+            rate: self.rate + rate_bp * 1e-4,
+            vol: (self.vol + vol_pts * 0.01).max(MIN_VOL),
+        }
+    }
+}
+
+/// Risk factor a bump step moves
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BumpFactor {
+    /// All spots, relative
+    Spot,
+    /// Flat volatility
+    Vol,
+    /// Flat rate
+    Rate,
+}
+
+impl BumpFactor {
+    /// Next factor in the cycle
+    pub fn next(self) -> Self {
+        match self {
+            Self::Spot => Self::Vol,
+            Self::Vol => Self::Rate,
+            Self::Rate => Self::Spot,
+        }
+    }
+
+    /// Display name with step size
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Spot => "Spot (1%)",
+            Self::Vol => "Vol (1pt)",
+            Self::Rate => "Rate (10bp)",
+        }
+    }
+}
+
+/// Accumulated what-if bump, in steps per factor
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WhatIfBump {
+    /// Spot steps of 1%
+    pub spot_steps: i32,
+    /// Volatility steps of one point
+    pub vol_steps: i32,
+    /// Rate steps of 10bp
+    pub rate_steps: i32,
+}
+
+impl WhatIfBump {
+    /// Add steps to one factor
+    pub fn step(&mut self, factor: BumpFactor, steps: i32) {
+        match factor {
+            BumpFactor::Spot => self.spot_steps += steps,
+            BumpFactor::Vol => self.vol_steps += steps,
+            BumpFactor::Rate => self.rate_steps += steps,
+        }
+    }
+
+    /// Whether no factor is bumped
+    pub fn is_zero(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Human-readable bump, e.g. `Spot +2% Rate -10bp`
+    pub fn describe(&self) -> String {
+        if self.is_zero() {
+            return "none".to_string();
+        }
+        let mut parts = Vec::new();
+        if self.spot_steps != 0 {
+            parts.push(format!("Spot {:+}%", self.spot_steps));
+        }
+        if self.vol_steps != 0 {
+            parts.push(format!("Vol {:+}pt", self.vol_steps));
+        }
+        if self.rate_steps != 0 {
+            parts.push(format!("Rate {:+}bp", self.rate_steps * 10));
+        }
+        parts.join(" ")
+    }
+}
+
+/// PV and Greeks of a trade or an aggregate
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TradeGreeks {
+    pub pv: f64,
+    pub delta: f64,
+    pub gamma: f64,
+    pub vega: f64,
+    pub theta: f64,
+    pub rho: f64,
+}
+
+impl TradeGreeks {
+    /// Component-wise sum
+    pub fn add(&self, other: &Self) -> Self {
+        Self {
+            pv: self.pv + other.pv,
+            delta: self.delta + other.delta,
+            gamma: self.gamma + other.gamma,
+            vega: self.vega + other.vega,
+            theta: self.theta + other.theta,
+            rho: self.rho + other.rho,
+        }
+    }
+
+    fn scaled(&self, notional: f64) -> Self {
+        Self {
+            pv: self.pv * notional,
+            delta: self.delta * notional,
+            gamma: self.gamma * notional,
+            vega: self.vega * notional,
+            theta: self.theta * notional,
+            rho: self.rho * notional,
+        }
+    }
+}
+
+/// Price a trade on a market
+///
+/// Options use [`BlackScholes`] directly; forwards are priced as a long
+/// call and short put at the same strike. Trades whose underlying has no
+/// spot price at zero.
+pub fn price_trade(trade: &BookTrade, market: &MarketState) -> TradeGreeks {
+    let unit = match trade.product {
+        Product::Call { strike, expiry } => option_greeks(trade, market, strike, expiry, true),
+        Product::Put { strike, expiry } => option_greeks(trade, market, strike, expiry, false),
+        Product::Forward { strike, expiry } => {
+            let call = option_greeks(trade, market, strike, expiry, true);
+            let put = option_greeks(trade, market, strike, expiry, false);
+            TradeGreeks {
+                pv: call.pv - put.pv,
+                delta: call.delta - put.delta,
+                gamma: 0.0,
+                vega: 0.0,
+                theta: call.theta - put.theta,
+                rho: call.rho - put.rho,
+            }
+        }
+        Product::PayerSwap { fixed_rate, tenor } => swap_greeks(market.rate, fixed_rate, tenor),
+        Product::ReceiverSwap { fixed_rate, tenor } => {
+            swap_greeks(market.rate, fixed_rate, tenor).scaled(-1.0)
+        }
+    };
+    unit.scaled(trade.notional)
+}
+
+/// Expected cashflows of a trade on a market as of a date
+///
+/// Each trade is built as a unit-notional `pricer_models` instrument and
+/// its cashflows scaled by the trade notional, so their PVs sum to
+/// [`price_trade`]'s PV. Options and forwards pay in the underlying's
+/// currency when it names one, USD otherwise. Trades that cannot be
+/// valued, e.g. an option whose underlying has no spot, have none.
+pub fn trade_cashflows(
+    trade: &BookTrade,
+    market: &MarketState,
+    as_of: Date,
+) -> Vec<ValuedCashflow<f64>> {
+    let currency = trade.underlying.parse().unwrap_or(Currency::USD);
+    let mut snapshot = MarketSnapshot::new(as_of, currency, CurveEnum::flat(market.rate))
+        .with_volatility(market.vol);
+    if let Some(&spot) = market.spots.get(&trade.underlying) {
+        snapshot = snapshot.with_spot(spot);
+    }
+    let option = |strike, expiry, payoff| {
+        InstrumentParams::new(strike, expiry, 1.0).map(|params| {
+            Instrument::Vanilla(VanillaOption::new(
+                params,
+                payoff,
+                ExerciseStyle::European,
+                1e-6,
+            ))
+        })
+    };
+    let swap = |fixed_rate, tenor: u32| {
+        let dates = (1..=tenor).map(f64::from).collect();
+        Swap::new(1.0, fixed_rate, dates, PaymentFrequency::Annual, currency).map(Instrument::Swap)
+    };
+    let (instrument, sign) = match trade.product {
+        Product::Call { strike, expiry } => (option(strike, expiry, PayoffType::Call), 1.0),
+        Product::Put { strike, expiry } => (option(strike, expiry, PayoffType::Put), 1.0),
+        Product::Forward { strike, expiry } => (
+            Forward::new(strike, expiry, 1.0, Direction::Long).map(Instrument::Forward),
+            1.0,
+        ),
+        Product::PayerSwap { fixed_rate, tenor } => (swap(fixed_rate, tenor), 1.0),
+        Product::ReceiverSwap { fixed_rate, tenor } => (swap(fixed_rate, tenor), -1.0),
+    };
+    let scale = sign * trade.notional;
+    instrument
+        .and_then(|instrument| instrument.cashflows(&snapshot))
+        .map(|cashflows| {
+            cashflows
+                .into_iter()
+                .map(|cf| ValuedCashflow {
+                    amount: cf.amount * scale,
+                    present_value: cf.present_value * scale,
+                    ..cf
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Unit Black-Scholes Greeks in display conventions
+fn option_greeks(
+    trade: &BookTrade,
+    market: &MarketState,
+    strike: f64,
+    expiry: f64,
+    is_call: bool,
+) -> TradeGreeks {
+    let Some(&spot) = market.spots.get(&trade.underlying) else {
+        return TradeGreeks::default();
+    };
+    let Ok(bs) = BlackScholes::new(spot, market.rate, market.vol) else {
+        return TradeGreeks::default();
+    };
+    TradeGreeks {
+        pv: if is_call {
+            bs.price_call(strike, expiry)
+        } else {
+            bs.price_put(strike, expiry)
+        },
+        delta: bs.delta(strike, expiry, is_call),
+        gamma: bs.gamma(strike, expiry),
+        vega: bs.vega(strike, expiry) * 0.01,
+        theta: bs.theta(strike, expiry, is_call) / DAYS_PER_YEAR,
+        rho: bs.rho(strike, expiry, is_call) * 1e-4,
+    }
+}
+
+/// Unit payer swap PV and rho on a flat curve
+///
+/// Floating leg worth `1 - D(T)`; fixed leg `K` times the annual annuity.
+fn swap_greeks(rate: f64, fixed_rate: f64, tenor: u32) -> TradeGreeks {
+    let discount = |t: f64| (-rate * t).exp();
+    let times = (1..=tenor).map(f64::from);
+    let annuity: f64 = times.clone().map(discount).sum();
+    let annuity_duration: f64 = times.map(|t| t * discount(t)).sum();
+    let maturity = f64::from(tenor);
+
+    TradeGreeks {
+        pv: 1.0 - discount(maturity) - fixed_rate * annuity,
+        rho: (maturity * discount(maturity) + fixed_rate * annuity_duration) * 1e-4,
+        ..TradeGreeks::default()
+    }
+}
+
+/// Level of the drill-down hierarchy being shown
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DrillLevel {
+    /// All counterparties
+    Counterparties,
+    /// Netting sets of one counterparty
+    NettingSets { counterparty: String },
+    /// Trades of one netting set
+    Trades {
+        counterparty: String,
+        netting_set: String,
+    },
+}
+
+/// Aggregated row of the drill-down
+#[derive(Debug, Clone, PartialEq)]
+pub struct DrillNode {
+    /// Counterparty, netting set or trade identifier
+    pub name: String,
+    /// Number of trades below the node
+    pub trade_count: usize,
+    /// Greeks on the base market
+    pub base: TradeGreeks,
+    /// Greeks on the bumped market
+    pub live: TradeGreeks,
+}
+
+impl DrillNode {
+    /// P&L of the current bump
+    pub fn pnl(&self) -> f64 {
+        self.live.pv - self.base.pv
+    }
+}
+
+/// Blotter column the rows are sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortColumn {
+    Id,
+    Counterparty,
+    Pv,
+    Pnl,
+    Delta,
+    Gamma,
+    Vega,
+    Theta,
+    Rho,
+}
+
+impl SortColumn {
+    /// Next column in the cycle
+    pub fn next(self) -> Self {
+        match self {
+            Self::Id => Self::Counterparty,
+            Self::Counterparty => Self::Pv,
+            Self::Pv => Self::Pnl,
+            Self::Pnl => Self::Delta,
+            Self::Delta => Self::Gamma,
+            Self::Gamma => Self::Vega,
+            Self::Vega => Self::Theta,
+            Self::Theta => Self::Rho,
+            Self::Rho => Self::Id,
+        }
+    }
+
+    /// Column header
+    pub fn title(&self) -> &'static str {
+        match self {
+            Self::Id => "ID",
+            Self::Counterparty => "Counterparty",
+            Self::Pv => "PV",
+            Self::Pnl => "P&L",
+            Self::Delta => "Delta",
+            Self::Gamma => "Gamma",
+            Self::Vega => "Vega",
+            Self::Theta => "Theta",
+            Self::Rho => "Rho",
+        }
+    }
+
+    fn compare(&self, a: &BlotterRow, b: &BlotterRow) -> Ordering {
+        let number = |f: fn(&BlotterRow) -> f64| f(a).total_cmp(&f(b));
+        match self {
+            Self::Id => a.trade.id.cmp(&b.trade.id),
+            Self::Counterparty => a
+                .trade
+                .counterparty
+                .cmp(&b.trade.counterparty)
+                .then_with(|| a.trade.id.cmp(&b.trade.id)),
+            Self::Pv => number(|r| r.live.pv),
+            Self::Pnl => number(|r| r.pnl()),
+            Self::Delta => number(|r| r.live.delta),
+            Self::Gamma => number(|r| r.live.gamma),
+            Self::Vega => number(|r| r.live.vega),
+            Self::Theta => number(|r| r.live.theta),
+            Self::Rho => number(|r| r.live.rho),
+        }
+    }
+}
+
+/// Blotter row: a trade with base and live Greeks
+#[derive(Debug, Clone, Copy)]
+pub struct BlotterRow<'a> {
+    pub trade: &'a BookTrade,
+    pub base: TradeGreeks,
+    pub live: TradeGreeks,
+}
+
+impl BlotterRow<'_> {
+    /// P&L of the current bump
+    pub fn pnl(&self) -> f64 {
+        self.live.pv - self.base.pv
+    }
+}
+
+/// Drill-down, blotter and what-if state
+#[derive(Debug, Clone)]
+pub struct PortfolioView {
+    trades: Vec<BookTrade>,
+    market: MarketState,
+    bump: WhatIfBump,
+    base: Vec<TradeGreeks>,
+    live: Vec<TradeGreeks>,
+    /// Factor the bump keys move
+    pub bump_factor: BumpFactor,
+    /// Current drill-down level
+    pub level: DrillLevel,
+    /// Selected row at the current level
+    pub drill_selected: usize,
+    /// Blotter sort column
+    pub sort: SortColumn,
+    /// Sort descending
+    pub descending: bool,
+    /// Case-insensitive blotter filter
+    pub filter: String,
+    /// Whether keystrokes edit the filter
+    pub filter_editing: bool,
+    /// Selected blotter row
+    pub blotter_selected: usize,
+}
+
+impl Default for PortfolioView {
+    fn default() -> Self {
+        Self::new(Self::sample_trades(), Self::sample_market())
+    }
+}
+
+impl PortfolioView {
+    /// Create a view and price the book
+    pub fn new(trades: Vec<BookTrade>, market: MarketState) -> Self {
+        let base: Vec<TradeGreeks> = trades.iter().map(|t| price_trade(t, &market)).collect();
+        Self {
+            live: base.clone(),
+            base,
+            trades,
+            market,
+            bump: WhatIfBump::default(),
+            bump_factor: BumpFactor::Spot,
+            level: DrillLevel::Counterparties,
+            drill_selected: 0,
+            sort: SortColumn::Id,
+            descending: false,
+            filter: String::new(),
+            filter_editing: false,
+            blotter_selected: 0,
+        }
+    }
+
+    /// Sample book across three counterparties
+    fn sample_trades() -> Vec<BookTrade> {
+        let trade = |id: &str, cp: &str, ns: &str, und: &str, product, notional| BookTrade {
+            id: id.to_string(),
+            counterparty: cp.to_string(),
+            netting_set: ns.to_string(),
+            underlying: und.to_string(),
+            product,
+            notional,
+        };
+        vec![
+            trade(
+                "T001",
+                "Acme Corp",
+                "NS-ACME-EQ",
+                "AAPL",
+                Product::Call {
+                    strike: 200.0,
+                    expiry: 1.0,
+                },
+                10_000.0,
+            ),
+            trade(
+                "T002",
+                "Acme Corp",
+                "NS-ACME-EQ",
+                "AAPL",
+                Product::Put {
+                    strike: 170.0,
+                    expiry: 0.5,
+                },
+                5_000.0,
+            ),
+            trade(
+                "T003",
+                "Acme Corp",
+                "NS-ACME-IR",
+                "USD",
+                Product::PayerSwap {
+                    fixed_rate: 0.035,
+                    tenor: 5,
+                },
+                10_000_000.0,
+            ),
+            trade(
+                "T004",
+                "Globex Bank",
+                "NS-GLOBEX",
+                "EURUSD",
+                Product::Forward {
+                    strike: 1.10,
+                    expiry: 1.0,
+                },
+                5_000_000.0,
+            ),
+            trade(
+                "T005",
+                "Globex Bank",
+                "NS-GLOBEX",
+                "USD",
+                Product::ReceiverSwap {
+                    fixed_rate: 0.04,
+                    tenor: 10,
+                },
+                25_000_000.0,
+            ),
+            trade(
+                "T006",
+                "Initech Fund",
+                "NS-INITECH",
+                "SPX",
+                Product::Call {
+                    strike: 5_400.0,
+                    expiry: 0.75,
+                },
+                200.0,
+            ),
+            trade(
+                "T007",
+                "Initech Fund",
+                "NS-INITECH",
+                "SPX",
+                Product::Put {
+                    strike: 4_800.0,
+                    expiry: 0.75,
+                },
+                -150.0,
+            ),
+        ]
+    }
+
+    /// Sample market for the sample book
+    fn sample_market() -> MarketState {
+        MarketState {
+            spots: [("AAPL", 190.0), ("EURUSD", 1.08), ("SPX", 5_200.0)]
+                .into_iter()
+                .map(|(name, spot)| (name.to_string(), spot))
+                .collect(),
+            rate: 0.04,
+            vol: 0.22,
+        }
+    }
+
+    /// Trades in the book
+    pub fn trades(&self) -> &[BookTrade] {
+        &self.trades
+    }
+
+    /// Unbumped market
+    pub fn market(&self) -> &MarketState {
+        &self.market
+    }
+
+    /// Current what-if bump
+    pub fn bump(&self) -> WhatIfBump {
+        self.bump
+    }
+
+    /// Move the selected factor by a number of steps and re-price
+    pub fn apply_bump(&mut self, steps: i32) {
+        self.bump.step(self.bump_factor, steps);
+        self.reprice();
+    }
+
+    /// Remove the bump and re-price
+    pub fn clear_bump(&mut self) {
+        self.bump = WhatIfBump::default();
+        self.reprice();
+    }
+
+    /// Re-price every trade on the bumped market
+    fn reprice(&mut self) {
+        let market = self.market.bumped(&self.bump);
+        self.live = self
+            .trades
+            .iter()
+            .map(|t| price_trade(t, &market))
+            .collect();
+    }
+
+    /// Book totals on the base and bumped market
+    pub fn totals(&self) -> (TradeGreeks, TradeGreeks) {
+        let sum = |g: &[TradeGreeks]| g.iter().fold(TradeGreeks::default(), |a, b| a.add(b));
+        (sum(&self.base), sum(&self.live))
+    }
+
+    /// Rows of the current drill-down level, sorted by name
+    pub fn drill_nodes(&self) -> Vec<DrillNode> {
+        let mut nodes: BTreeMap<&str, DrillNode> = BTreeMap::new();
+        for (i, trade) in self.trades.iter().enumerate() {
+            let key = match &self.level {
+                DrillLevel::Counterparties => trade.counterparty.as_str(),
+                DrillLevel::NettingSets { counterparty } if trade.counterparty == *counterparty => {
+                    trade.netting_set.as_str()
+                }
+                DrillLevel::Trades {
+                    counterparty,
+                    netting_set,
+                } if trade.counterparty == *counterparty && trade.netting_set == *netting_set => {
+                    trade.id.as_str()
+                }
+                _ => continue,
+            };
+            let node = nodes.entry(key).or_insert_with(|| DrillNode {
+                name: key.to_string(),
+                trade_count: 0,
+                base: TradeGreeks::default(),
+                live: TradeGreeks::default(),
+            });
+            node.trade_count += 1;
+            node.base = node.base.add(&self.base[i]);
+            node.live = node.live.add(&self.live[i]);
+        }
+        nodes.into_values().collect()
+    }
+
+    /// Path of the current level, e.g. `Book > Acme Corp > NS-ACME-EQ`
+    pub fn breadcrumb(&self) -> String {
+        match &self.level {
+            DrillLevel::Counterparties => "Book".to_string(),
+            DrillLevel::NettingSets { counterparty } => format!("Book > {}", counterparty),
+            DrillLevel::Trades {
+                counterparty,
+                netting_set,
+            } => format!("Book > {} > {}", counterparty, netting_set),
+        }
+    }
+
+    /// Descend into the selected row; trades are the bottom level
+    pub fn drill_down(&mut self) {
+        let Some(node) = self.drill_nodes().into_iter().nth(self.drill_selected) else {
+            return;
+        };
+        self.level = match &self.level {
+            DrillLevel::Counterparties => DrillLevel::NettingSets {
+                counterparty: node.name,
+            },
+            DrillLevel::NettingSets { counterparty } => DrillLevel::Trades {
+                counterparty: counterparty.clone(),
+                netting_set: node.name,
+            },
+            DrillLevel::Trades { .. } => return,
+        };
+        self.drill_selected = 0;
+    }
+
+    /// Return to the parent level, selecting the node just left
+    pub fn drill_up(&mut self) {
+        let (parent, left) = match &self.level {
+            DrillLevel::Counterparties => return,
+            DrillLevel::NettingSets { counterparty } => {
+                (DrillLevel::Counterparties, counterparty.clone())
+            }
+            DrillLevel::Trades {
+                counterparty,
+                netting_set,
+            } => (
+                DrillLevel::NettingSets {
+                    counterparty: counterparty.clone(),
+                },
+                netting_set.clone(),
+            ),
+        };
+        self.level = parent;
+        self.drill_selected = self
+            .drill_nodes()
+            .iter()
+            .position(|n| n.name == left)
+            .unwrap_or(0);
+    }
+
+    /// Blotter rows matching the filter, in sort order
+    pub fn blotter_rows(&self) -> Vec<BlotterRow<'_>> {
+        let filter = self.filter.to_lowercase();
+        let mut rows: Vec<BlotterRow> = self
+            .trades
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| {
+                filter.is_empty()
+                    || [&t.id, &t.counterparty, &t.netting_set, &t.underlying]
+                        .iter()
+                        .any(|field| field.to_lowercase().contains(&filter))
+                    || t.product.label().to_lowercase().contains(&filter)
+            })
+            .map(|(i, trade)| BlotterRow {
+                trade,
+                base: self.base[i],
+                live: self.live[i],
+            })
+            .collect();
+        rows.sort_by(|a, b| {
+            let ordering = self.sort.compare(a, b);
+            if self.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+        rows
+    }
+
+    /// Expected cashflows of the selected blotter row on the bumped market,
+    /// as of today
+    pub fn selected_cashflows(&self) -> Vec<ValuedCashflow<f64>> {
+        let market = self.market.bumped(&self.bump);
+        self.blotter_rows()
+            .get(self.blotter_selected)
+            .map(|row| trade_cashflows(row.trade, &market, Date::today()))
+            .unwrap_or_default()
+    }
+
+    /// Handle a key on the drill-down screen; returns whether it was used
+    pub fn handle_drilldown_key(&mut self, key: KeyCode) -> bool {
+        match key {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.drill_selected = self.drill_selected.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let last = self.drill_nodes().len().saturating_sub(1);
+                self.drill_selected = (self.drill_selected + 1).min(last);
+            }
+            KeyCode::Enter | KeyCode::Right => self.drill_down(),
+            KeyCode::Backspace | KeyCode::Left => self.drill_up(),
+            _ => return self.handle_bump_key(key),
+        }
+        true
+    }
+
+    /// Handle a key on the blotter screen; returns whether it was used
+    ///
+    /// While the filter is being edited every key goes to the filter:
+    /// `Enter` keeps it, `Esc` clears it.
+    pub fn handle_blotter_key(&mut self, key: KeyCode) -> bool {
+        if self.filter_editing {
+            match key {
+                KeyCode::Char(c) => self.filter.push(c),
+                KeyCode::Backspace => {
+                    self.filter.pop();
+                }
+                KeyCode::Enter => self.filter_editing = false,
+                KeyCode::Esc => {
+                    self.filter.clear();
+                    self.filter_editing = false;
+                }
+                _ => {}
+            }
+            self.blotter_selected = 0;
+            return true;
+        }
+        match key {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.blotter_selected = self.blotter_selected.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let last = self.blotter_rows().len().saturating_sub(1);
+                self.blotter_selected = (self.blotter_selected + 1).min(last);
+            }
+            KeyCode::Char('/') => self.filter_editing = true,
+            KeyCode::Char('s') => self.sort = self.sort.next(),
+            KeyCode::Char('d') => self.descending = !self.descending,
+            _ => return self.handle_bump_key(key),
+        }
+        true
+    }
+
+    /// What-if keys shared by both screens
+    fn handle_bump_key(&mut self, key: KeyCode) -> bool {
+        match key {
+            KeyCode::Char('f') => self.bump_factor = self.bump_factor.next(),
+            KeyCode::Char('+') | KeyCode::Char('=') => self.apply_bump(1),
+            KeyCode::Char('-') => self.apply_bump(-1),
+            KeyCode::Char('c') => self.clear_bump(),
+            _ => return false,
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(notional: f64) -> BookTrade {
+        BookTrade {
+            id: "C".to_string(),
+            counterparty: "CP".to_string(),
+            netting_set: "NS".to_string(),
+            underlying: "X".to_string(),
+            product: Product::Call {
+                strike: 100.0,
+                expiry: 1.0,
+            },
+            notional,
+        }
+    }
+
+    fn market(spot: f64) -> MarketState {
+        MarketState {
+            spots: [("X".to_string(), spot)].into_iter().collect(),
+            rate: 0.05,
+            vol: 0.2,
+        }
+    }
+
+    #[test]
+    fn test_call_matches_black_scholes() {
+        let g = price_trade(&call(1.0), &market(100.0));
+        // Textbook ATM value for S=K=100, r=5%, sigma=20%, T=1
+        assert!((g.pv - 10.4506).abs() < 1e-3);
+        assert!((g.delta - 0.6368).abs() < 1e-3);
+        assert!(g.vega > 0.0 && g.theta < 0.0 && g.rho > 0.0);
+
+        let scaled = price_trade(&call(10.0), &market(100.0));
+        assert!((scaled.pv - 10.0 * g.pv).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_forward_is_linear() {
+        let mut fwd = call(1.0);
+        fwd.product = Product::Forward {
+            strike: 100.0,
+            expiry: 1.0,
+        };
+        let g = price_trade(&fwd, &market(105.0));
+        assert!((g.pv - (105.0 - 100.0 * (-0.05_f64).exp())).abs() < 1e-9);
+        assert!((g.delta - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_par_swap_has_zero_pv() {
+        let rate: f64 = 0.03;
+        let annuity: f64 = (1..=5).map(|t| (-rate * t as f64).exp()).sum();
+        let par = (1.0 - (-rate * 5.0).exp()) / annuity;
+        let g = swap_greeks(rate, par, 5);
+        assert!(g.pv.abs() < 1e-12);
+        assert!(g.rho > 0.0);
+    }
+
+    #[test]
+    fn test_cashflows_sum_to_pv() {
+        let as_of = Date::from_ymd(2026, 1, 15).unwrap();
+        let mut receiver = call(-2_000_000.0);
+        receiver.underlying = "EUR".to_string();
+        receiver.product = Product::ReceiverSwap {
+            fixed_rate: 0.04,
+            tenor: 3,
+        };
+        for trade in [call(10.0), receiver.clone()] {
+            let cashflows = trade_cashflows(&trade, &market(100.0), as_of);
+            let pv: f64 = cashflows.iter().map(|cf| cf.present_value).sum();
+            assert!(!cashflows.is_empty());
+            assert!((pv - price_trade(&trade, &market(100.0)).pv).abs() < 1e-6);
+        }
+
+        // Receiving fixed on a short notional pays the fixed coupons
+        let swap_flows = trade_cashflows(&receiver, &market(100.0), as_of);
+        assert_eq!(swap_flows.len(), 6);
+        assert_eq!(swap_flows[0].currency, Currency::EUR);
+        assert!((swap_flows[0].amount + 80_000.0).abs() < 1e-6);
+
+        // No spot for the underlying: nothing to show
+        let mut unknown = call(1.0);
+        unknown.underlying = "Y".to_string();
+        assert!(trade_cashflows(&unknown, &market(100.0), as_of).is_empty());
+    }
+
+    #[test]
+    fn test_drill_down_and_up() {
+        let mut view = PortfolioView::default();
+        let cps: Vec<String> = view.drill_nodes().into_iter().map(|n| n.name).collect();
+        assert_eq!(cps, vec!["Acme Corp", "Globex Bank", "Initech Fund"]);
+
+        view.drill_down();
+        assert_eq!(view.breadcrumb(), "Book > Acme Corp");
+        let sets = view.drill_nodes();
+        assert_eq!(sets.len(), 2);
+        assert_eq!(sets[0].name, "NS-ACME-EQ");
+        assert_eq!(sets[0].trade_count, 2);
+
+        view.handle_drilldown_key(KeyCode::Down);
+        view.handle_drilldown_key(KeyCode::Enter);
+        assert_eq!(view.breadcrumb(), "Book > Acme Corp > NS-ACME-IR");
+        assert_eq!(view.drill_nodes()[0].name, "T003");
+        // Trades are the bottom level
+        view.drill_down();
+        assert_eq!(view.breadcrumb(), "Book > Acme Corp > NS-ACME-IR");
+
+        view.drill_up();
+        assert_eq!(view.drill_selected, 1);
+        view.drill_up();
+        assert_eq!(view.level, DrillLevel::Counterparties);
+        assert_eq!(view.drill_selected, 0);
+    }
+
+    #[test]
+    fn test_drill_totals_match_book() {
+        let view = PortfolioView::default();
+        let (base, _) = view.totals();
+        let node_pv: f64 = view.drill_nodes().iter().map(|n| n.base.pv).sum();
+        assert!((node_pv - base.pv).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_blotter_sort_and_filter() {
+        let mut view = PortfolioView::default();
+        // Id -> Counterparty -> Pv, then descending
+        for key in ['s', 's', 'd'] {
+            view.handle_blotter_key(KeyCode::Char(key));
+        }
+        assert_eq!(view.sort, SortColumn::Pv);
+        let rows = view.blotter_rows();
+        assert!(rows.windows(2).all(|w| w[0].live.pv >= w[1].live.pv));
+
+        view.handle_blotter_key(KeyCode::Char('/'));
+        for c in "spx".chars() {
+            view.handle_blotter_key(KeyCode::Char(c));
+        }
+        view.handle_blotter_key(KeyCode::Enter);
+        assert!(!view.filter_editing);
+        let ids: Vec<&str> = view
+            .blotter_rows()
+            .iter()
+            .map(|r| r.trade.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["T006", "T007"]);
+
+        // Esc while editing clears the filter
+        view.handle_blotter_key(KeyCode::Char('/'));
+        view.handle_blotter_key(KeyCode::Esc);
+        assert_eq!(view.blotter_rows().len(), view.trades().len());
+    }
+
+    #[test]
+    fn test_filter_captures_bump_keys() {
+        let mut view = PortfolioView::default();
+        view.handle_blotter_key(KeyCode::Char('/'));
+        assert!(view.handle_blotter_key(KeyCode::Char('+')));
+        assert!(view.bump().is_zero());
+        assert_eq!(view.filter, "+");
+    }
+
+    #[test]
+    fn test_what_if_bump_reprices() {
+        let mut view = PortfolioView::default();
+        let (base, _) = view.totals();
+
+        view.handle_blotter_key(KeyCode::Char('+'));
+        assert_eq!(view.bump().describe(), "Spot +1%");
+        assert_ne!(view.totals().1.pv, base.pv);
+        // A 1% move in AAPL (190) earns about delta times 1.90
+        let rows = view.blotter_rows();
+        let t001 = rows.iter().find(|r| r.trade.id == "T001").unwrap();
+        let estimate = t001.base.delta * 1.9 + 0.5 * t001.base.gamma * 1.9 * 1.9;
+        assert!((t001.pnl() - estimate).abs() < 0.01 * estimate.abs());
+        // Swaps do not move with spot
+        let t003 = rows.iter().find(|r| r.trade.id == "T003").unwrap();
+        assert_eq!(t003.pnl(), 0.0);
+
+        view.handle_blotter_key(KeyCode::Char('f'));
+        view.handle_blotter_key(KeyCode::Char('f'));
+        view.handle_blotter_key(KeyCode::Char('-'));
+        assert_eq!(view.bump().describe(), "Spot +1% Rate -10bp");
+
+        view.handle_drilldown_key(KeyCode::Char('c'));
+        assert!(view.bump().is_zero());
+        assert_eq!(view.totals().1, base);
+    }
+
+    #[test]
+    fn test_vol_bump_is_floored() {
+        let mut view = PortfolioView::default();
+        view.handle_drilldown_key(KeyCode::Char('f'));
+        assert_eq!(view.bump_factor, BumpFactor::Vol);
+        view.apply_bump(-100);
+        let (_, live) = view.totals();
+        assert!(live.pv.is_finite());
+    }
+}
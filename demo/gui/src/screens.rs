@@ -1,11 +1,13 @@
 //! Screen rendering functions for the TUI.
 
 mod irs_aad;
+mod portfolio_drilldown;
 
 pub use irs_aad::{
     draw_irs_aad_screen, draw_irs_benchmark_chart, IrsAadScreenData, IrsDisplayParams,
     IrsDisplayResult,
 };
+pub use portfolio_drilldown::{draw_drilldown, draw_live_greeks};
 
 use crate::app::{ExposureTimeSeries, IrsAadDemoState, RiskMetrics, TradeRow};
 use ratatui::{
@@ -0,0 +1,347 @@
+//! Portfolio drill-down and live Greeks blotter screens.
+//!
+//! Both screens render a [`PortfolioView`]; the what-if bump shown in the
+//! status bar applies to both. The blotter shows the selected trade's
+//! expected cashflows in a detail pane below the table.
+
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+};
+
+use crate::portfolio_view::{PortfolioView, SortColumn, TradeGreeks};
+
+/// Green for gains, red for losses
+fn signed_style(value: f64) -> Style {
+    Style::default().fg(if value >= 0.0 {
+        Color::Green
+    } else {
+        Color::Red
+    })
+}
+
+fn header_row(titles: &[&str]) -> Row<'static> {
+    Row::new(
+        titles
+            .iter()
+            .map(|h| Cell::from(h.to_string()).style(Style::default().fg(Color::Yellow)))
+            .collect::<Vec<_>>(),
+    )
+    .height(1)
+}
+
+/// Status bar with the active bump and the bump keys
+fn draw_bump_bar(frame: &mut Frame, area: Rect, view: &PortfolioView, help: &str) {
+    let (base, live) = view.totals();
+    let text = vec![
+        Line::from(vec![
+            Span::raw("What-if: "),
+            Span::styled(
+                view.bump().describe(),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::raw("  Factor: "),
+            Span::styled(view.bump_factor.label(), Style::default().fg(Color::Yellow)),
+            Span::raw("  Book PV: "),
+            Span::raw(format!("{:.2}", live.pv)),
+            Span::raw("  P&L: "),
+            Span::styled(
+                format!("{:+.2}", live.pv - base.pv),
+                signed_style(live.pv - base.pv),
+            ),
+        ]),
+        Line::from(Span::styled(
+            format!("{} | [f]Factor [+/-]Bump [c]Clear", help),
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+    let bar = Paragraph::new(text).block(Block::default().borders(Borders::ALL));
+    frame.render_widget(bar, area);
+}
+
+/// Draw the counterparty → netting set → trade drill-down
+pub fn draw_drilldown(frame: &mut Frame, area: Rect, view: &PortfolioView) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(4)])
+        .split(area);
+
+    let nodes = view.drill_nodes();
+    let rows = nodes.iter().enumerate().map(|(idx, node)| {
+        let style = if idx == view.drill_selected {
+            Style::default().bg(Color::DarkGray)
+        } else {
+            Style::default()
+        };
+        Row::new(vec![
+            Cell::from(node.name.clone()),
+            Cell::from(node.trade_count.to_string()),
+            Cell::from(format!("{:.2}", node.base.pv)),
+            Cell::from(format!("{:.2}", node.live.pv)).style(signed_style(node.live.pv)),
+            Cell::from(format!("{:+.2}", node.pnl())).style(signed_style(node.pnl())),
+            Cell::from(format!("{:.2}", node.live.delta)),
+            Cell::from(format!("{:.2}", node.live.vega)),
+            Cell::from(format!("{:.2}", node.live.rho)),
+        ])
+        .style(style)
+    });
+    let widths = [
+        Constraint::Min(16),
+        Constraint::Length(7),
+        Constraint::Length(15),
+        Constraint::Length(15),
+        Constraint::Length(13),
+        Constraint::Length(12),
+        Constraint::Length(12),
+        Constraint::Length(12),
+    ];
+    let table = Table::new(rows, widths)
+        .header(header_row(&[
+            "Name", "Trades", "Base PV", "Live PV", "P&L", "Delta", "Vega", "Rho",
+        ]))
+        .block(
+            Block::default()
+                .title(format!(" {} ", view.breadcrumb()))
+                .borders(Borders::ALL),
+        );
+    frame.render_widget(table, chunks[0]);
+
+    draw_bump_bar(
+        frame,
+        chunks[1],
+        view,
+        "[Enter/Right]Drill down [Backspace/Left]Up",
+    );
+}
+
+/// Draw the sortable, filterable live Greeks blotter
+pub fn draw_live_greeks(frame: &mut Frame, area: Rect, view: &PortfolioView) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(0),
+            Constraint::Length(8),
+            Constraint::Length(4),
+        ])
+        .split(area);
+
+    // Filter and sort status
+    let filter = if view.filter_editing {
+        format!("{}_", view.filter)
+    } else if view.filter.is_empty() {
+        "(none)".to_string()
+    } else {
+        view.filter.clone()
+    };
+    let status = Paragraph::new(Line::from(vec![
+        Span::raw("Filter: "),
+        Span::styled(
+            filter,
+            Style::default().fg(if view.filter_editing {
+                Color::Yellow
+            } else {
+                Color::White
+            }),
+        ),
+        Span::raw(format!(
+            "  Sort: {} {}",
+            view.sort.title(),
+            if view.descending { "desc" } else { "asc" }
+        )),
+    ]))
+    .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(status, chunks[0]);
+
+    let blotter = view.blotter_rows();
+    let sort_marker = if view.descending { " v" } else { " ^" };
+    let column = |title: &'static str, col: Option<SortColumn>| -> String {
+        if col == Some(view.sort) {
+            format!("{}{}", title, sort_marker)
+        } else {
+            title.to_string()
+        }
+    };
+    let titles = [
+        column("ID", Some(SortColumn::Id)),
+        column("Counterparty", Some(SortColumn::Counterparty)),
+        column("Product", None),
+        column("PV", Some(SortColumn::Pv)),
+        column("P&L", Some(SortColumn::Pnl)),
+        column("Delta", Some(SortColumn::Delta)),
+        column("Gamma", Some(SortColumn::Gamma)),
+        column("Vega", Some(SortColumn::Vega)),
+        column("Theta", Some(SortColumn::Theta)),
+        column("Rho", Some(SortColumn::Rho)),
+    ];
+    let title_refs: Vec<&str> = titles.iter().map(String::as_str).collect();
+
+    let mut total = TradeGreeks::default();
+    let mut total_pnl = 0.0;
+    let mut rows: Vec<Row> = blotter
+        .iter()
+        .enumerate()
+        .map(|(idx, row)| {
+            total = total.add(&row.live);
+            total_pnl += row.pnl();
+            let style = if idx == view.blotter_selected {
+                Style::default().bg(Color::DarkGray)
+            } else {
+                Style::default()
+            };
+            greeks_row(
+                vec![
+                    Cell::from(row.trade.id.clone()),
+                    Cell::from(row.trade.counterparty.clone()),
+                    Cell::from(format!(
+                        "{} {}",
+                        row.trade.underlying,
+                        row.trade.product.label()
+                    )),
+                ],
+                &row.live,
+                row.pnl(),
+            )
+            .style(style)
+        })
+        .collect();
+    rows.push(
+        greeks_row(
+            vec![
+                Cell::from("Total"),
+                Cell::from(format!("{} trades", blotter.len())),
+                Cell::from(""),
+            ],
+            &total,
+            total_pnl,
+        )
+        .style(Style::default().add_modifier(Modifier::BOLD)),
+    );
+
+    let widths = [
+        Constraint::Length(6),
+        Constraint::Length(13),
+        Constraint::Min(18),
+        Constraint::Length(13),
+        Constraint::Length(11),
+        Constraint::Length(11),
+        Constraint::Length(9),
+        Constraint::Length(10),
+        Constraint::Length(9),
+        Constraint::Length(10),
+    ];
+    let table = Table::new(rows, widths)
+        .header(header_row(&title_refs))
+        .block(
+            Block::default()
+                .title(" Live Greeks Blotter ")
+                .borders(Borders::ALL),
+        );
+    frame.render_widget(table, chunks[1]);
+
+    draw_cashflows(frame, chunks[2], view);
+    draw_bump_bar(
+        frame,
+        chunks[3],
+        view,
+        "[/]Filter [s]Sort column [d]Direction",
+    );
+}
+
+/// Detail pane with the selected trade's expected cashflows
+fn draw_cashflows(frame: &mut Frame, area: Rect, view: &PortfolioView) {
+    let title = match view.blotter_rows().get(view.blotter_selected) {
+        Some(row) => format!(" Trade Cashflows: {} ", row.trade.id),
+        None => " Trade Cashflows ".to_string(),
+    };
+    let cashflows = view.selected_cashflows();
+    let rows = cashflows.iter().map(|cf| {
+        Row::new(vec![
+            Cell::from(cf.payment_date.to_string()),
+            Cell::from(cf.kind.as_str()),
+            Cell::from(cf.currency.code()),
+            Cell::from(format!("{:.2}", cf.amount)).style(signed_style(cf.amount)),
+            Cell::from(format!("{:.6}", cf.discount_factor)),
+            Cell::from(format!("{:.2}", cf.present_value)).style(signed_style(cf.present_value)),
+        ])
+    });
+    let widths = [
+        Constraint::Length(12),
+        Constraint::Length(20),
+        Constraint::Length(5),
+        Constraint::Length(16),
+        Constraint::Length(10),
+        Constraint::Length(16),
+    ];
+    let table = Table::new(rows, widths)
+        .header(header_row(&["Date", "Type", "Ccy", "Amount", "DF", "PV"]))
+        .block(Block::default().title(title).borders(Borders::ALL));
+    frame.render_widget(table, area);
+}
+
+fn greeks_row<'a>(mut cells: Vec<Cell<'a>>, g: &TradeGreeks, pnl: f64) -> Row<'a> {
+    cells.extend([
+        Cell::from(format!("{:.2}", g.pv)).style(signed_style(g.pv)),
+        Cell::from(format!("{:+.2}", pnl)).style(signed_style(pnl)),
+        Cell::from(format!("{:.2}", g.delta)),
+        Cell::from(format!("{:.4}", g.gamma)),
+        Cell::from(format!("{:.2}", g.vega)),
+        Cell::from(format!("{:.2}", g.theta)),
+        Cell::from(format!("{:.2}", g.rho)),
+    ]);
+    Row::new(cells)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn render(draw: impl FnOnce(&mut Frame, Rect)) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(140, 30)).unwrap();
+        terminal
+            .draw(|frame| {
+                let area = frame.size();
+                draw(frame, area);
+            })
+            .unwrap();
+        terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect()
+    }
+
+    #[test]
+    fn test_draw_drilldown() {
+        let mut view = PortfolioView::default();
+        let screen = render(|f, a| draw_drilldown(f, a, &view));
+        assert!(screen.contains("Book"));
+        assert!(screen.contains("Globex Bank"));
+
+        view.drill_down();
+        let screen = render(|f, a| draw_drilldown(f, a, &view));
+        assert!(screen.contains("Book > Acme Corp"));
+        assert!(screen.contains("NS-ACME-IR"));
+    }
+
+    #[test]
+    fn test_draw_live_greeks_with_bump_and_filter() {
+        let mut view = PortfolioView::default();
+        view.apply_bump(2);
+        view.filter = "acme".to_string();
+        view.sort = SortColumn::Delta;
+        let screen = render(|f, a| draw_live_greeks(f, a, &view));
+        assert!(screen.contains("Spot +2%"));
+        assert!(screen.contains("Delta ^"));
+        assert!(screen.contains("3 trades"));
+        assert!(!screen.contains("T006"));
+        assert!(screen.contains("Trade Cashflows: T002"));
+        assert!(screen.contains("option_payoff"));
+    }
+}
@@ -0,0 +1,685 @@
+//! Per-counterparty exposure profiles for the FrictionalBank WebApp.
+//!
+//! Simulates netting-set mark-to-market paths per counterparty and reduces
+//! them to EE, ENE and PFE 95%/99% time series with
+//! [`ExposureCalculator`]:
+//! - GET /api/exposure - Book summary plus paginated counterparty profiles
+//! - GET /api/exposure/chart?counterparty={id} - Chart-ready series
+//! - POST /api/exposure/resimulate - Intraday re-simulation
+//!
+//! A completed re-simulation replaces the cached profiles and pushes them
+//! to WebSocket clients as an `exposure_profiles` message.
+//!
+//! ENE is reported as a non-positive amount, matching the book-level
+//! `time_series`.
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Json,
+};
+use pricer_risk::exposure::ExposureCalculator;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rand_distr::{Distribution, StandardNormal};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use super::pricer_types::PricingErrorResponse;
+use super::websocket::broadcast_exposure_profiles;
+use super::AppState;
+
+/// Monte Carlo paths per counterparty
+pub const DEFAULT_NUM_PATHS: usize = 1000;
+/// Simulation time step in years
+const TIME_STEP: f64 = 0.25;
+/// Simulation horizon in years
+const HORIZON: f64 = 10.0;
+/// Default counterparties per page
+pub const DEFAULT_PAGE_SIZE: usize = 10;
+/// Largest accepted page size
+pub const MAX_PAGE_SIZE: usize = 100;
+
+/// Netting set driving one counterparty's exposure.
+///
+/// Mark-to-market follows `V(t) = (V0 + σ·W(t))·(T - t)/T` up to maturity
+/// `T`, the hump shape of an amortising swap book.
+struct CounterpartySpec {
+    id: &'static str,
+    name: &'static str,
+    /// Current mark-to-market
+    mtm: f64,
+    /// Absolute annual volatility of the mark-to-market
+    vol: f64,
+    /// Longest maturity in the netting set, in years
+    maturity: f64,
+}
+
+const COUNTERPARTIES: [CounterpartySpec; 12] = [
+    CounterpartySpec {
+        id: "CP001",
+        name: "Acme Corp",
+        mtm: 250_000.0,
+        vol: 400_000.0,
+        maturity: 5.0,
+    },
+    CounterpartySpec {
+        id: "CP002",
+        name: "Globex Bank",
+        mtm: -120_000.0,
+        vol: 650_000.0,
+        maturity: 10.0,
+    },
+    CounterpartySpec {
+        id: "CP003",
+        name: "Initech Fund",
+        mtm: 80_000.0,
+        vol: 300_000.0,
+        maturity: 3.0,
+    },
+    CounterpartySpec {
+        id: "CP004",
+        name: "Umbrella Insurance",
+        mtm: 400_000.0,
+        vol: 250_000.0,
+        maturity: 7.0,
+    },
+    CounterpartySpec {
+        id: "CP005",
+        name: "Stark Industries",
+        mtm: -50_000.0,
+        vol: 500_000.0,
+        maturity: 8.0,
+    },
+    CounterpartySpec {
+        id: "CP006",
+        name: "Wayne Enterprises",
+        mtm: 150_000.0,
+        vol: 350_000.0,
+        maturity: 4.0,
+    },
+    CounterpartySpec {
+        id: "CP007",
+        name: "Hooli Treasury",
+        mtm: 20_000.0,
+        vol: 200_000.0,
+        maturity: 2.0,
+    },
+    CounterpartySpec {
+        id: "CP008",
+        name: "Cyberdyne Capital",
+        mtm: -300_000.0,
+        vol: 450_000.0,
+        maturity: 6.0,
+    },
+    CounterpartySpec {
+        id: "CP009",
+        name: "Soylent Pension",
+        mtm: 600_000.0,
+        vol: 300_000.0,
+        maturity: 10.0,
+    },
+    CounterpartySpec {
+        id: "CP010",
+        name: "Tyrell Holdings",
+        mtm: 0.0,
+        vol: 550_000.0,
+        maturity: 5.0,
+    },
+    CounterpartySpec {
+        id: "CP011",
+        name: "Oceanic Airlines",
+        mtm: 90_000.0,
+        vol: 150_000.0,
+        maturity: 1.5,
+    },
+    CounterpartySpec {
+        id: "CP012",
+        name: "Vandelay Imports",
+        mtm: -20_000.0,
+        vol: 100_000.0,
+        maturity: 2.5,
+    },
+];
+
+// =============================================================================
+// Profile Types
+// =============================================================================
+
+/// Exposure percentiles at one time point.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfilePoint {
+    /// Time in years
+    pub time: f64,
+    /// Expected exposure
+    pub ee: f64,
+    /// Expected negative exposure (non-positive)
+    pub ene: f64,
+    /// 95th percentile potential future exposure
+    pub pfe95: f64,
+    /// 99th percentile potential future exposure
+    pub pfe99: f64,
+}
+
+/// Exposure profile of one counterparty.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CounterpartyExposureProfile {
+    /// Counterparty identifier
+    pub counterparty_id: String,
+    /// Counterparty name
+    pub name: String,
+    /// Time-averaged EE over the horizon
+    pub epe: f64,
+    /// Peak EE
+    pub peak_ee: f64,
+    /// Peak PFE 95%
+    pub peak_pfe95: f64,
+    /// Peak PFE 99%
+    pub peak_pfe99: f64,
+    /// Profile on the simulation grid
+    pub time_series: Vec<ProfilePoint>,
+}
+
+/// Profiles from one simulation run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExposureProfileSet {
+    /// Increments with each re-simulation
+    pub run_id: u64,
+    /// Completion time in milliseconds since the Unix epoch
+    pub as_of: i64,
+    /// Monte Carlo paths per counterparty
+    pub num_paths: usize,
+    /// Profiles in counterparty ID order
+    pub profiles: Vec<CounterpartyExposureProfile>,
+}
+
+impl ExposureProfileSet {
+    /// Simulate all counterparties.
+    ///
+    /// The seed is derived from `run_id`, so a run is reproducible.
+    pub fn simulate(run_id: u64, num_paths: usize) -> Self {
+        let mut rng = StdRng::seed_from_u64(0x5EED_0000 ^ run_id);
+        let steps = (HORIZON / TIME_STEP).round() as usize;
+        let time_grid: Vec<f64> = (0..=steps).map(|i| i as f64 * TIME_STEP).collect();
+
+        let profiles = COUNTERPARTIES
+            .iter()
+            .map(|spec| simulate_counterparty(spec, &time_grid, num_paths, &mut rng))
+            .collect();
+
+        Self {
+            run_id,
+            as_of: chrono::Utc::now().timestamp_millis(),
+            num_paths,
+            profiles,
+        }
+    }
+
+    /// Profile for one counterparty ID
+    pub fn profile(&self, counterparty_id: &str) -> Option<&CounterpartyExposureProfile> {
+        self.profiles
+            .iter()
+            .find(|p| p.counterparty_id == counterparty_id)
+    }
+}
+
+fn simulate_counterparty(
+    spec: &CounterpartySpec,
+    time_grid: &[f64],
+    num_paths: usize,
+    rng: &mut StdRng,
+) -> CounterpartyExposureProfile {
+    let paths: Vec<Vec<f64>> = (0..num_paths)
+        .map(|_| {
+            let mut w = 0.0;
+            let mut prev = 0.0;
+            time_grid
+                .iter()
+                .map(|&t| {
+                    let z: f64 = StandardNormal.sample(rng);
+                    w += (t - prev).sqrt() * z;
+                    prev = t;
+                    let remaining = ((spec.maturity - t) / spec.maturity).max(0.0);
+                    (spec.mtm + spec.vol * w) * remaining
+                })
+                .collect()
+        })
+        .collect();
+
+    let ee = ExposureCalculator::expected_exposure(&paths);
+    let ene = ExposureCalculator::expected_negative_exposure(&paths);
+    let pfe95 = ExposureCalculator::potential_future_exposure(&paths, 0.95);
+    let pfe99 = ExposureCalculator::potential_future_exposure(&paths, 0.99);
+    let epe = ExposureCalculator::expected_positive_exposure(&ee, time_grid);
+
+    let time_series = time_grid
+        .iter()
+        .enumerate()
+        .map(|(i, &time)| ProfilePoint {
+            time,
+            ee: ee[i],
+            ene: -ene[i],
+            pfe95: pfe95[i],
+            pfe99: pfe99[i],
+        })
+        .collect();
+
+    CounterpartyExposureProfile {
+        counterparty_id: spec.id.to_string(),
+        name: spec.name.to_string(),
+        epe,
+        peak_ee: ExposureCalculator::peak_pfe(&ee),
+        peak_pfe95: ExposureCalculator::peak_pfe(&pfe95),
+        peak_pfe99: ExposureCalculator::peak_pfe(&pfe99),
+        time_series,
+    }
+}
+
+/// Current profiles, simulating the first run on demand.
+pub async fn current_profiles(state: &AppState) -> Arc<ExposureProfileSet> {
+    if let Some(set) = state.exposure_profiles.read().await.as_ref() {
+        return Arc::clone(set);
+    }
+    let mut cached = state.exposure_profiles.write().await;
+    Arc::clone(
+        cached.get_or_insert_with(|| Arc::new(ExposureProfileSet::simulate(1, DEFAULT_NUM_PATHS))),
+    )
+}
+
+// =============================================================================
+// Pagination
+// =============================================================================
+
+/// Query parameters for GET /api/exposure.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ExposureQuery {
+    /// Restrict profiles to one counterparty ID
+    pub counterparty: Option<String>,
+    /// 1-based page number (default 1)
+    pub page: Option<usize>,
+    /// Profiles per page (default 10, at most 100)
+    pub page_size: Option<usize>,
+}
+
+/// Page position of the returned profiles.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Pagination {
+    /// 1-based page number
+    pub page: usize,
+    /// Profiles per page
+    pub page_size: usize,
+    /// Profiles matching the query
+    pub total_items: usize,
+    /// Pages at this page size
+    pub total_pages: usize,
+}
+
+/// Counterparty profiles selected by an [`ExposureQuery`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExposureProfilePage {
+    /// Simulation run the profiles come from
+    pub run_id: u64,
+    /// Completion time of the run in milliseconds since the Unix epoch
+    pub as_of: i64,
+    /// Profiles on this page
+    pub counterparties: Vec<CounterpartyExposureProfile>,
+    /// Page position
+    pub pagination: Pagination,
+}
+
+fn error_response(
+    status: StatusCode,
+    error_type: &str,
+    message: String,
+    field: Option<&str>,
+) -> (StatusCode, Json<PricingErrorResponse>) {
+    (
+        status,
+        Json(PricingErrorResponse {
+            error_type: error_type.to_string(),
+            message,
+            field: field.map(str::to_string),
+        }),
+    )
+}
+
+impl ExposureProfileSet {
+    /// Select one page of profiles.
+    ///
+    /// Fails with 400 for an out-of-range page or page size and 404 for an
+    /// unknown counterparty. A page past the end is empty.
+    pub fn page(
+        &self,
+        query: &ExposureQuery,
+    ) -> Result<ExposureProfilePage, (StatusCode, Json<PricingErrorResponse>)> {
+        let page = query.page.unwrap_or(1);
+        let page_size = query.page_size.unwrap_or(DEFAULT_PAGE_SIZE);
+        if page == 0 {
+            return Err(error_response(
+                StatusCode::BAD_REQUEST,
+                "ValidationError",
+                "page must be at least 1".to_string(),
+                Some("page"),
+            ));
+        }
+        if page_size == 0 || page_size > MAX_PAGE_SIZE {
+            return Err(error_response(
+                StatusCode::BAD_REQUEST,
+                "ValidationError",
+                format!("page_size must be between 1 and {}", MAX_PAGE_SIZE),
+                Some("page_size"),
+            ));
+        }
+
+        let matching: Vec<&CounterpartyExposureProfile> = match &query.counterparty {
+            Some(id) => vec![self.profile(id).ok_or_else(|| {
+                error_response(
+                    StatusCode::NOT_FOUND,
+                    "NotFound",
+                    format!("Unknown counterparty: {}", id),
+                    Some("counterparty"),
+                )
+            })?],
+            None => self.profiles.iter().collect(),
+        };
+
+        let total_items = matching.len();
+        Ok(ExposureProfilePage {
+            run_id: self.run_id,
+            as_of: self.as_of,
+            counterparties: matching
+                .into_iter()
+                .skip((page - 1) * page_size)
+                .take(page_size)
+                .cloned()
+                .collect(),
+            pagination: Pagination {
+                page,
+                page_size,
+                total_items,
+                total_pages: total_items.div_ceil(page_size),
+            },
+        })
+    }
+}
+
+// =============================================================================
+// Chart Endpoint
+// =============================================================================
+
+/// Query parameters for GET /api/exposure/chart.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExposureChartQuery {
+    /// Counterparty ID
+    pub counterparty: String,
+}
+
+/// One line on the exposure chart.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ChartSeries {
+    /// Legend label
+    pub label: String,
+    /// Values aligned with the chart labels
+    pub data: Vec<f64>,
+}
+
+/// Chart-ready exposure profile with percentile bands.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExposureChartResponse {
+    /// Counterparty identifier
+    pub counterparty_id: String,
+    /// Counterparty name
+    pub name: String,
+    /// Simulation run the profile comes from
+    pub run_id: u64,
+    /// Time axis in years
+    pub labels: Vec<f64>,
+    /// EE, ENE, PFE 95% and PFE 99% series
+    pub series: Vec<ChartSeries>,
+}
+
+impl From<(&CounterpartyExposureProfile, u64)> for ExposureChartResponse {
+    fn from((profile, run_id): (&CounterpartyExposureProfile, u64)) -> Self {
+        let column = |label: &str, f: fn(&ProfilePoint) -> f64| ChartSeries {
+            label: label.to_string(),
+            data: profile.time_series.iter().map(f).collect(),
+        };
+        Self {
+            counterparty_id: profile.counterparty_id.clone(),
+            name: profile.name.clone(),
+            run_id,
+            labels: profile.time_series.iter().map(|p| p.time).collect(),
+            series: vec![
+                column("EE", |p| p.ee),
+                column("ENE", |p| p.ene),
+                column("PFE 95%", |p| p.pfe95),
+                column("PFE 99%", |p| p.pfe99),
+            ],
+        }
+    }
+}
+
+/// Get a counterparty's exposure profile as chart series.
+///
+/// # Endpoint
+///
+/// `GET /api/exposure/chart?counterparty={id}`
+pub async fn get_exposure_chart(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ExposureChartQuery>,
+) -> Result<Json<ExposureChartResponse>, (StatusCode, Json<PricingErrorResponse>)> {
+    let set = current_profiles(&state).await;
+    let profile = set.profile(&query.counterparty).ok_or_else(|| {
+        error_response(
+            StatusCode::NOT_FOUND,
+            "NotFound",
+            format!("Unknown counterparty: {}", query.counterparty),
+            Some("counterparty"),
+        )
+    })?;
+    Ok(Json((profile, set.run_id).into()))
+}
+
+// =============================================================================
+// Intraday Re-simulation
+// =============================================================================
+
+/// Response for POST /api/exposure/resimulate.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResimulationResponse {
+    /// New simulation run
+    pub run_id: u64,
+    /// Completion time in milliseconds since the Unix epoch
+    pub as_of: i64,
+    /// Counterparties re-simulated
+    pub counterparty_count: usize,
+    /// Simulation time in milliseconds
+    pub processing_time_ms: f64,
+}
+
+/// Re-simulate all profiles, replace the cache and notify clients.
+pub async fn resimulate(state: &AppState) -> Arc<ExposureProfileSet> {
+    let run_id = current_profiles(state).await.run_id + 1;
+    let set = tokio::task::spawn_blocking(move || {
+        ExposureProfileSet::simulate(run_id, DEFAULT_NUM_PATHS)
+    })
+    .await
+    .map(Arc::new)
+    .expect("exposure simulation panicked");
+
+    {
+        let mut cached = state.exposure_profiles.write().await;
+        // A concurrent re-simulation may have finished first; keep the newer
+        if cached.as_ref().is_none_or(|c| c.run_id < set.run_id) {
+            *cached = Some(Arc::clone(&set));
+        }
+    }
+    broadcast_exposure_profiles(state, &set);
+    set
+}
+
+/// Run an intraday re-simulation of all counterparty profiles.
+///
+/// # Endpoint
+///
+/// `POST /api/exposure/resimulate`
+///
+/// On completion the new profiles are pushed to WebSocket clients as an
+/// `exposure_profiles` message.
+pub async fn resimulate_exposure(State(state): State<Arc<AppState>>) -> Json<ResimulationResponse> {
+    let start = std::time::Instant::now();
+    let set = resimulate(&state).await;
+    Json(ResimulationResponse {
+        run_id: set.run_id,
+        as_of: set.as_of,
+        counterparty_count: set.profiles.len(),
+        processing_time_ms: start.elapsed().as_secs_f64() * 1000.0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(counterparty: Option<&str>, page: usize, page_size: usize) -> ExposureQuery {
+        ExposureQuery {
+            counterparty: counterparty.map(str::to_string),
+            page: Some(page),
+            page_size: Some(page_size),
+        }
+    }
+
+    #[test]
+    fn test_profiles_have_ordered_percentile_bands() {
+        let set = ExposureProfileSet::simulate(1, 500);
+        assert_eq!(set.profiles.len(), COUNTERPARTIES.len());
+        for profile in &set.profiles {
+            assert_eq!(profile.time_series.len(), 41);
+            for p in &profile.time_series {
+                assert!(p.ee >= 0.0 && p.ene <= 0.0);
+                assert!(
+                    p.pfe99 >= p.pfe95,
+                    "{} at {}",
+                    profile.counterparty_id,
+                    p.time
+                );
+            }
+            assert!(profile.peak_pfe99 >= profile.peak_pfe95);
+            assert!(profile.peak_pfe95 >= profile.epe);
+        }
+    }
+
+    #[test]
+    fn test_exposure_vanishes_after_maturity() {
+        let set = ExposureProfileSet::simulate(1, 200);
+        let hooli = set.profile("CP007").unwrap();
+        let after = hooli.time_series.iter().find(|p| p.time > 2.0).unwrap();
+        assert_eq!(after.ee, 0.0);
+        assert_eq!(after.pfe99, 0.0);
+        // Today's exposure is the current MtM on every path
+        assert!((hooli.time_series[0].ee - 20_000.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_simulation_is_reproducible_per_run() {
+        let a = ExposureProfileSet::simulate(3, 100);
+        let b = ExposureProfileSet::simulate(3, 100);
+        let c = ExposureProfileSet::simulate(4, 100);
+        assert_eq!(a.profiles, b.profiles);
+        assert_ne!(a.profiles, c.profiles);
+    }
+
+    #[test]
+    fn test_pagination() {
+        let set = ExposureProfileSet::simulate(1, 50);
+        let first = set.page(&query(None, 1, 5)).unwrap();
+        assert_eq!(first.counterparties.len(), 5);
+        assert_eq!(first.counterparties[0].counterparty_id, "CP001");
+        assert_eq!(first.pagination.total_items, 12);
+        assert_eq!(first.pagination.total_pages, 3);
+
+        let last = set.page(&query(None, 3, 5)).unwrap();
+        assert_eq!(last.counterparties.len(), 2);
+        assert_eq!(last.counterparties[0].counterparty_id, "CP011");
+        assert!(set
+            .page(&query(None, 4, 5))
+            .unwrap()
+            .counterparties
+            .is_empty());
+
+        let defaults = set.page(&ExposureQuery::default()).unwrap();
+        assert_eq!(defaults.pagination.page_size, DEFAULT_PAGE_SIZE);
+    }
+
+    #[test]
+    fn test_pagination_errors_and_filter() {
+        let set = ExposureProfileSet::simulate(1, 50);
+        let one = set.page(&query(Some("CP002"), 1, 10)).unwrap();
+        assert_eq!(one.counterparties[0].name, "Globex Bank");
+        assert_eq!(one.pagination.total_items, 1);
+
+        let (status, body) = set.page(&query(Some("CP999"), 1, 10)).unwrap_err();
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body.field.as_deref(), Some("counterparty"));
+        assert_eq!(
+            set.page(&query(None, 0, 10)).unwrap_err().0,
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            set.page(&query(None, 1, MAX_PAGE_SIZE + 1)).unwrap_err().0,
+            StatusCode::BAD_REQUEST
+        );
+    }
+
+    #[tokio::test]
+    async fn test_chart_endpoint() {
+        let state = Arc::new(AppState::new());
+        let Json(chart) = get_exposure_chart(
+            State(Arc::clone(&state)),
+            Query(ExposureChartQuery {
+                counterparty: "CP004".to_string(),
+            }),
+        )
+        .await
+        .unwrap();
+        assert_eq!(chart.name, "Umbrella Insurance");
+        let labels: Vec<&str> = chart.series.iter().map(|s| s.label.as_str()).collect();
+        assert_eq!(labels, ["EE", "ENE", "PFE 95%", "PFE 99%"]);
+        assert!(chart
+            .series
+            .iter()
+            .all(|s| s.data.len() == chart.labels.len()));
+
+        let missing = get_exposure_chart(
+            State(state),
+            Query(ExposureChartQuery {
+                counterparty: "nope".to_string(),
+            }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(missing.0, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_resimulation_replaces_profiles_and_broadcasts() {
+        let state = Arc::new(AppState::new());
+        let first = current_profiles(&state).await;
+        assert_eq!(first.run_id, 1);
+        let mut rx = state.tx.subscribe();
+
+        let Json(response) = resimulate_exposure(State(Arc::clone(&state))).await;
+        assert_eq!(response.run_id, 2);
+        assert_eq!(response.counterparty_count, COUNTERPARTIES.len());
+        assert_eq!(current_profiles(&state).await.run_id, 2);
+
+        let msg: serde_json::Value = serde_json::from_str(&rx.try_recv().unwrap()).unwrap();
+        assert_eq!(msg["update_type"], "exposure_profiles");
+        assert_eq!(msg["data"]["run_id"], 2);
+        assert_eq!(
+            msg["data"]["counterparties"].as_array().unwrap().len(),
+            COUNTERPARTIES.len()
+        );
+        assert!(msg["data"]["counterparties"][0]["time_series"][1]["pfe99"].is_number());
+    }
+}
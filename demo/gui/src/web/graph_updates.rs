@@ -0,0 +1,414 @@
+//! Live computation graph diffs for subscribed trades.
+//!
+//! When a trade is re-priced its parameter and node values are loaded into a
+//! [`SimpleGraphExtractor`], and
+//! [`extract_affected_nodes`](GraphExtractable::extract_affected_nodes)
+//! yields the nodes whose values moved. Diffs are only produced for trades
+//! at least one WebSocket client has subscribed to.
+//!
+//! Diffs are coalesced per trade and sent at most once per
+//! [`GraphUpdateConfig::min_interval`]: repeated re-pricings inside the
+//! interval collapse to the latest value per node, with the deltas summed.
+//! Messages carry at most [`GraphUpdateConfig::max_nodes_per_message`]
+//! nodes. Whatever is still pending is flushed by [`spawn_flusher`].
+
+use pricer_pricing::graph::{GraphError, GraphExtractable, SimpleGraphExtractor};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+
+use super::handlers::get_trade_params;
+use super::websocket::{broadcast_graph_update, GraphNodeUpdate};
+use super::AppState;
+
+/// Batching and throttling settings for `graph_update` messages.
+#[derive(Debug, Clone)]
+pub struct GraphUpdateConfig {
+    /// Minimum time between messages for the same trade
+    pub min_interval: Duration,
+    /// Largest number of nodes in one message; larger diffs are split
+    pub max_nodes_per_message: usize,
+}
+
+impl Default for GraphUpdateConfig {
+    fn default() -> Self {
+        Self {
+            min_interval: Duration::from_millis(250),
+            max_nodes_per_message: 200,
+        }
+    }
+}
+
+/// Value of a graph parameter the re-pricing did not supply.
+///
+/// Matches the input node values of the sample graph served by
+/// `GET /api/graph`.
+fn default_param_value(index: usize) -> f64 {
+    100.0 + index as f64 * 10.0
+}
+
+/// Node values of a trade graph, following the node layout of
+/// [`SimpleGraphExtractor`]: pairwise `op` nodes (`a * b` or `exp(a)`),
+/// pairwise `combine` nodes (`a + b` or `sqrt(a)`) and the `price` output.
+fn computed_node_values(trade_id: &str, inputs: &[f64], price: f64) -> Vec<(String, f64)> {
+    let ops: Vec<f64> = inputs
+        .chunks(2)
+        .map(|c| {
+            if c.len() == 2 {
+                c[0] * c[1]
+            } else {
+                c[0].exp()
+            }
+        })
+        .collect();
+    let combines: Vec<f64> = ops
+        .chunks(2)
+        .map(|c| {
+            if c.len() == 2 {
+                c[0] + c[1]
+            } else {
+                c[0].sqrt()
+            }
+        })
+        .collect();
+
+    let mut values: Vec<(String, f64)> = ops
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| (format!("{}_op_{}", trade_id, i), v))
+        .collect();
+    values.extend(
+        combines
+            .iter()
+            .enumerate()
+            .map(|(i, &v)| (format!("{}_combine_{}", trade_id, i), v)),
+    );
+    values.push((format!("{}_price", trade_id), price));
+    values
+}
+
+/// Extracts, coalesces and throttles graph diffs per trade.
+#[derive(Debug)]
+pub struct GraphUpdatePublisher {
+    config: GraphUpdateConfig,
+    extractor: SimpleGraphExtractor,
+    /// Last value per parameter, by trade
+    params: HashMap<String, Vec<(String, f64)>>,
+    /// Coalesced updates awaiting the throttle, by trade then node
+    pending: HashMap<String, BTreeMap<String, GraphNodeUpdate>>,
+    /// When each trade's last message was sent
+    last_sent: HashMap<String, Instant>,
+}
+
+impl Default for GraphUpdatePublisher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GraphUpdatePublisher {
+    /// Create a publisher with default throttling
+    pub fn new() -> Self {
+        Self::with_config(GraphUpdateConfig::default())
+    }
+
+    /// Create a publisher with custom throttling
+    pub fn with_config(config: GraphUpdateConfig) -> Self {
+        Self {
+            config,
+            extractor: SimpleGraphExtractor::new(),
+            params: HashMap::new(),
+            pending: HashMap::new(),
+            last_sent: HashMap::new(),
+        }
+    }
+
+    /// Record a re-pricing of a trade and queue the nodes it changed.
+    ///
+    /// `market` supplies parameter values by name; parameters of the trade's
+    /// graph it does not mention keep their previous values. Returns the
+    /// number of nodes whose value moved.
+    pub fn record_repricing(
+        &mut self,
+        trade_id: &str,
+        market: &[(&str, f64)],
+        price: f64,
+    ) -> Result<usize, GraphError> {
+        let params = self.params.entry(trade_id.to_string()).or_insert_with(|| {
+            get_trade_params(trade_id)
+                .into_iter()
+                .enumerate()
+                .map(|(i, name)| (name, default_param_value(i)))
+                .collect()
+        });
+        for (name, value) in params.iter_mut() {
+            if let Some((_, v)) = market.iter().find(|(n, _)| n == name) {
+                *value = *v;
+            }
+        }
+
+        let first_pricing = !self.extractor.has_trade(trade_id);
+        if first_pricing {
+            self.extractor
+                .register_trade(trade_id, params.iter().map(|(n, _)| n.clone()).collect());
+        }
+        for (name, value) in params.iter() {
+            self.extractor.set_param_value(trade_id, name, *value)?;
+        }
+        let inputs: Vec<f64> = params.iter().map(|(_, v)| *v).collect();
+        let computed = computed_node_values(trade_id, &inputs, price);
+        for (node_id, value) in &computed {
+            self.extractor
+                .set_computed_value(trade_id, node_id, *value)?;
+        }
+
+        let mut affected = self.extractor.extract_affected_nodes(trade_id)?;
+        if first_pricing {
+            // The extractor only reports computed nodes once they have a
+            // previous value; send the initial values so clients can render
+            affected.extend(computed.into_iter().map(|(id, value)| {
+                pricer_pricing::graph::GraphNodeUpdate {
+                    id,
+                    value,
+                    delta: None,
+                }
+            }));
+        }
+        let count = affected.len();
+        let pending = self.pending.entry(trade_id.to_string()).or_default();
+        for node in affected {
+            let update = GraphNodeUpdate {
+                id: node.id,
+                value: node.value,
+                delta: node.delta,
+            };
+            match pending.get_mut(&update.id) {
+                Some(queued) => {
+                    queued.value = update.value;
+                    queued.delta = queued.delta.zip(update.delta).map(|(a, b)| a + b);
+                }
+                None => {
+                    pending.insert(update.id.clone(), update);
+                }
+            }
+        }
+        if pending.is_empty() {
+            self.pending.remove(trade_id);
+        }
+        Ok(count)
+    }
+
+    /// Take the batches whose throttle interval has elapsed.
+    ///
+    /// Returns `(trade_id, nodes)` pairs ready to broadcast, split so that
+    /// no batch exceeds the configured node limit.
+    pub fn drain_ready(&mut self, now: Instant) -> Vec<(String, Vec<GraphNodeUpdate>)> {
+        let ready: Vec<String> = self
+            .pending
+            .keys()
+            .filter(|trade_id| {
+                self.last_sent
+                    .get(*trade_id)
+                    .is_none_or(|sent| now.duration_since(*sent) >= self.config.min_interval)
+            })
+            .cloned()
+            .collect();
+
+        let mut batches = Vec::new();
+        for trade_id in ready {
+            let Some(nodes) = self.pending.remove(&trade_id) else {
+                continue;
+            };
+            let nodes: Vec<GraphNodeUpdate> = nodes.into_values().collect();
+            for chunk in nodes.chunks(self.config.max_nodes_per_message.max(1)) {
+                batches.push((trade_id.clone(), chunk.to_vec()));
+            }
+            self.last_sent.insert(trade_id, now);
+        }
+        batches.sort_by(|a, b| a.0.cmp(&b.0));
+        batches
+    }
+
+    /// Whether any updates are waiting for the throttle
+    pub fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+
+    /// Throttling settings
+    pub fn config(&self) -> &GraphUpdateConfig {
+        &self.config
+    }
+}
+
+/// Record a trade re-pricing and send any diffs that are due.
+///
+/// Does nothing unless a client is subscribed to the trade.
+pub async fn publish_repricing(
+    state: &AppState,
+    trade_id: &str,
+    market: &[(&str, f64)],
+    price: f64,
+) {
+    if !state.is_graph_subscribed(trade_id).await {
+        return;
+    }
+    let batches = {
+        let mut publisher = state.graph_publisher.lock().await;
+        if let Err(e) = publisher.record_repricing(trade_id, market, price) {
+            tracing::warn!("Graph diff extraction failed for {}: {}", trade_id, e);
+            return;
+        }
+        publisher.drain_ready(Instant::now())
+    };
+    for (trade_id, nodes) in batches {
+        broadcast_graph_update(state, &trade_id, nodes);
+    }
+}
+
+/// Send pending diffs whose throttle interval has elapsed.
+///
+/// Diffs for trades that lost all subscribers are dropped.
+pub async fn flush_pending(state: &AppState) {
+    let batches = state
+        .graph_publisher
+        .lock()
+        .await
+        .drain_ready(Instant::now());
+    for (trade_id, nodes) in batches {
+        if state.is_graph_subscribed(&trade_id).await {
+            broadcast_graph_update(state, &trade_id, nodes);
+        }
+    }
+}
+
+/// Periodically flush throttled diffs.
+pub fn spawn_flusher(state: Arc<AppState>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let period = state.graph_publisher.lock().await.config().min_interval;
+        let mut ticker = tokio::time::interval(period);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            flush_pending(&state).await;
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn publisher(min_interval_ms: u64, max_nodes: usize) -> GraphUpdatePublisher {
+        GraphUpdatePublisher::with_config(GraphUpdateConfig {
+            min_interval: Duration::from_millis(min_interval_ms),
+            max_nodes_per_message: max_nodes,
+        })
+    }
+
+    fn node<'a>(batch: &'a [GraphNodeUpdate], id: &str) -> &'a GraphNodeUpdate {
+        batch.iter().find(|n| n.id == id).unwrap()
+    }
+
+    #[test]
+    fn test_node_ids_match_extracted_graph() {
+        let values = computed_node_values("T001", &[1.0, 2.0, 3.0, 4.0, 5.0], 9.0);
+        let ids: Vec<&str> = values.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(
+            ids,
+            [
+                "T001_op_0",
+                "T001_op_1",
+                "T001_op_2",
+                "T001_combine_0",
+                "T001_combine_1",
+                "T001_price"
+            ]
+        );
+        assert_eq!(values[0].1, 2.0);
+        assert_eq!(values[3].1, 14.0);
+        assert_eq!(values[4].1, 5.0_f64.exp().sqrt());
+
+        let mut extractor = SimpleGraphExtractor::new();
+        extractor.register_trade("T001", vec!["a", "b", "c", "d", "e"]);
+        let graph = extractor.extract_graph(Some("T001")).unwrap();
+        for (id, _) in &values {
+            assert!(graph.find_node(id).is_some(), "{} missing", id);
+        }
+    }
+
+    #[test]
+    fn test_first_repricing_sends_values_then_only_changes() {
+        let mut p = publisher(0, 100);
+        p.record_repricing("T001", &[("spot", 100.0), ("vol", 0.2)], 10.0)
+            .unwrap();
+        let first = p.drain_ready(Instant::now());
+        assert_eq!(first.len(), 1);
+        // T001 graph: spot, vol, rate, time inputs
+        assert_eq!(node(&first[0].1, "T001_spot").delta, None);
+        assert_eq!(node(&first[0].1, "T001_rate").value, 120.0);
+
+        p.record_repricing("T001", &[("spot", 101.0), ("vol", 0.2)], 10.5)
+            .unwrap();
+        let second = p.drain_ready(Instant::now());
+        let ids: Vec<&str> = second[0].1.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            ["T001_combine_0", "T001_op_0", "T001_price", "T001_spot"]
+        );
+        assert!((node(&second[0].1, "T001_price").delta.unwrap() - 0.5).abs() < 1e-12);
+
+        // Unchanged re-pricing produces nothing
+        p.record_repricing("T001", &[("spot", 101.0)], 10.5)
+            .unwrap();
+        assert!(!p.has_pending());
+    }
+
+    #[test]
+    fn test_throttle_coalesces_repricings() {
+        let mut p = publisher(1_000, 100);
+        let t0 = Instant::now();
+        p.record_repricing("T002", &[], 5.0).unwrap();
+        assert_eq!(p.drain_ready(t0).len(), 1);
+
+        p.record_repricing("T002", &[], 6.0).unwrap();
+        p.record_repricing("T002", &[], 7.5).unwrap();
+        assert!(p.drain_ready(t0 + Duration::from_millis(500)).is_empty());
+        assert!(p.has_pending());
+
+        let batches = p.drain_ready(t0 + Duration::from_millis(1_000));
+        assert_eq!(batches.len(), 1);
+        let price = node(&batches[0].1, "T002_price");
+        assert_eq!(price.value, 7.5);
+        assert!((price.delta.unwrap() - 2.5).abs() < 1e-12);
+        assert!(!p.has_pending());
+    }
+
+    #[test]
+    fn test_large_diffs_are_split() {
+        let mut p = publisher(0, 3);
+        p.record_repricing("T003", &[], 1.0).unwrap();
+        let batches = p.drain_ready(Instant::now());
+        // 4 inputs, 2 ops, 1 combine, price
+        assert_eq!(batches.len(), 3);
+        assert!(batches.iter().all(|(t, n)| t == "T003" && n.len() <= 3));
+        assert_eq!(batches.iter().map(|(_, n)| n.len()).sum::<usize>(), 8);
+    }
+
+    #[tokio::test]
+    async fn test_publish_only_for_subscribed_trades() {
+        let state = AppState::new();
+        let mut rx = state.graph_tx.subscribe();
+
+        publish_repricing(&state, "T001", &[("spot", 99.0)], 1.0).await;
+        assert!(rx.try_recv().is_err());
+
+        state.add_graph_subscriber("T001").await;
+        publish_repricing(&state, "T001", &[("spot", 99.0)], 1.0).await;
+        let msg = rx.try_recv().unwrap();
+        assert_eq!(msg.trade_id, "T001");
+        let parsed: serde_json::Value = serde_json::from_str(&msg.message).unwrap();
+        assert_eq!(parsed["update_type"], "graph_update");
+        assert_eq!(parsed["data"]["trade_id"], "T001");
+    }
+}
@@ -15,6 +15,7 @@ use std::sync::Arc;
 use std::time::Instant;
 use uuid::Uuid;
 
+use super::exposure_profiles::{current_profiles, ExposureProfilePage, ExposureQuery};
 use super::jobs::JobResponse;
 use super::pricer_types::{
     parse_tenor_to_years, validate_bucket_dv01_request, validate_first_order_greeks_request,
@@ -239,7 +240,7 @@ pub struct PriceRequestItem {
 
 /// Price portfolio (POST)
 pub async fn price_portfolio(
-    State(_state): State<Arc<AppState>>,
+    State(state): State<Arc<AppState>>,
     Json(request): Json<PriceRequest>,
 ) -> impl IntoResponse {
     // In production, forward to service_gateway
@@ -258,6 +259,12 @@ pub async fn price_portfolio(
         })
         .collect();
 
+    // Push graph diffs for re-priced trades that clients are watching
+    for (item, trade) in request.instruments.iter().zip(&trades) {
+        let market = [("spot", item.spot), ("vol", item.vol), ("rate", item.rate)];
+        super::graph_updates::publish_repricing(&state, &trade.id, &market, trade.pv).await;
+    }
+
     if trades.is_empty() {
         trades = sample_trades();
     }
@@ -282,6 +289,9 @@ pub struct ExposureResponse {
     pub pfe: f64,
     pub eepe: f64,
     pub time_series: Vec<ExposurePoint>,
+    /// Per-counterparty profiles (`run_id`, `as_of`, `counterparties`, `pagination`)
+    #[serde(flatten)]
+    pub profiles: ExposureProfilePage,
 }
 
 /// Single exposure data point
@@ -295,8 +305,15 @@ pub struct ExposurePoint {
 }
 
 /// Get exposure metrics
-pub async fn get_exposure(State(state): State<Arc<AppState>>) -> Json<ExposureResponse> {
+///
+/// Query parameters `counterparty`, `page` and `page_size` select the
+/// per-counterparty EE/ENE/PFE profiles returned alongside the book summary.
+pub async fn get_exposure(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<ExposureQuery>,
+) -> Result<Json<ExposureResponse>, (StatusCode, Json<PricingErrorResponse>)> {
     let start = Instant::now();
+    let profiles = current_profiles(&state).await.page(&query)?;
 
     // Generate sample exposure profile
     let time_series: Vec<ExposurePoint> = (0..=40)
@@ -329,14 +346,15 @@ pub async fn get_exposure(State(state): State<Arc<AppState>>) -> Json<ExposureRe
         tracing::warn!("Exposure API response slow: {}ms", elapsed_us / 1000);
     }
 
-    Json(ExposureResponse {
+    Ok(Json(ExposureResponse {
         ee: peak.ee,
         epe: peak.epe,
         ene: peak.ene,
         pfe: peak.pfe,
         eepe: 350_000.0,
         time_series,
-    })
+        profiles,
+    }))
 }
 
 /// Risk metrics response
@@ -1159,7 +1177,7 @@ fn generate_sample_graph(trade_id: Option<&str>) -> GraphResponse {
 }
 
 /// Get parameters for a specific trade
-fn get_trade_params(trade_id: &str) -> Vec<String> {
+pub(crate) fn get_trade_params(trade_id: &str) -> Vec<String> {
     match trade_id {
         "T001" => vec![
             "spot".to_string(),
@@ -3648,9 +3666,24 @@ mod tests {
     #[tokio::test]
     async fn test_get_exposure() {
         let state = Arc::new(AppState::new());
-        let response = get_exposure(State(state)).await;
+        let response = get_exposure(State(state), Query(ExposureQuery::default()))
+            .await
+            .unwrap();
         assert!(!response.time_series.is_empty());
         assert!(response.ee > 0.0);
+        assert_eq!(response.profiles.counterparties.len(), 10);
+        assert_eq!(response.profiles.pagination.total_pages, 2);
+    }
+
+    #[tokio::test]
+    async fn test_get_exposure_rejects_bad_page() {
+        let state = Arc::new(AppState::new());
+        let query = ExposureQuery {
+            page: Some(0),
+            ..Default::default()
+        };
+        let (status, _) = get_exposure(State(state), Query(query)).await.unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
     }
 
     #[tokio::test]
@@ -10,13 +10,29 @@
 //! - REST API: `GET /api/graph` for computation graph data
 //! - WebSocket: `graph_update` messages for real-time node updates (Task 4.1)
 //! - Subscription: Clients can subscribe to specific trade graph updates (Task 4.3)
+//! - Live diffs: re-pricing a subscribed trade sends throttled `graph_update`
+//!   messages to the subscribed clients only (see [`graph_updates`])
+//!
+//! ## Exposure Profiles
+//!
+//! - REST API: `GET /api/exposure` (paginated per-counterparty EE/ENE/PFE),
+//!   `GET /api/exposure/chart` and `POST /api/exposure/resimulate`
+//! - WebSocket: `exposure_profiles` messages after each re-simulation
+//!
+//! ## Scenario Runner
+//!
+//! - REST API: `POST /api/scenarios/runs` and `GET /api/scenarios/runs/{id}`
+//! - WebSocket: `scenario_progress` and `scenario_complete` messages
 
+pub mod exposure_profiles;
+pub mod graph_updates;
 pub mod handlers;
 pub mod jobs;
 pub mod metrics;
 pub mod openapi;
 pub mod pricer_types;
 pub mod scenario_handlers;
+pub mod scenario_runner;
 pub mod websocket;
 
 use axum::{
@@ -24,20 +40,23 @@ use axum::{
     routing::{get, post},
     Router,
 };
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::net::SocketAddr;
 use std::sync::atomic::AtomicU32;
 use std::sync::Arc;
 use std::time::Instant;
-use tokio::sync::{broadcast, RwLock};
+use tokio::sync::{broadcast, Mutex, RwLock};
 use tower_http::cors::{AllowOrigin, Any, CorsLayer};
 use tower_http::services::ServeDir;
 use tower_http::set_header::SetResponseHeaderLayer;
 use tracing::info;
 
+use exposure_profiles::ExposureProfileSet;
+use graph_updates::GraphUpdatePublisher;
 use handlers::GraphCache;
 use jobs::JobManager;
 use pricer_types::BootstrapCurveCache;
+use websocket::GraphBroadcast;
 
 // =========================================================================
 // Task 6.1: PerformanceMetrics State (Requirement 9.5)
@@ -220,6 +239,12 @@ pub struct AppState {
     pub graph_cache: RwLock<GraphCache>,
     /// Set of trade IDs that clients have subscribed to for graph updates (Task 4.3)
     pub graph_subscriptions: RwLock<HashSet<String>>,
+    /// Number of WebSocket connections subscribed to each trade's graph
+    pub graph_subscriber_counts: RwLock<HashMap<String, usize>>,
+    /// Channel for `graph_update` messages, filtered per connection by trade
+    pub graph_tx: broadcast::Sender<GraphBroadcast>,
+    /// Extracts and throttles graph diffs for subscribed trades
+    pub graph_publisher: Mutex<GraphUpdatePublisher>,
     /// Performance metrics (Task 6.1)
     pub metrics: PerformanceMetrics,
     /// Debug configuration (Task 1.1)
@@ -228,20 +253,27 @@ pub struct AppState {
     pub curve_cache: BootstrapCurveCache,
     /// Async job manager (Task 7.1)
     pub job_manager: JobManager,
+    /// Latest per-counterparty exposure profiles, simulated on first use
+    pub exposure_profiles: RwLock<Option<Arc<ExposureProfileSet>>>,
 }
 
 impl AppState {
     /// Create new application state
     pub fn new() -> Self {
         let (tx, _) = broadcast::channel(100);
+        let (graph_tx, _) = broadcast::channel(100);
         Self {
             tx,
             graph_cache: RwLock::new(GraphCache::new()),
             graph_subscriptions: RwLock::new(HashSet::new()),
+            graph_subscriber_counts: RwLock::new(HashMap::new()),
+            graph_tx,
+            graph_publisher: Mutex::new(GraphUpdatePublisher::new()),
             metrics: PerformanceMetrics::new(),
             debug_config: DebugConfig::from_env(),
             curve_cache: BootstrapCurveCache::new(),
             job_manager: JobManager::new(),
+            exposure_profiles: RwLock::new(None),
         }
     }
 
@@ -297,6 +329,35 @@ impl AppState {
     pub async fn clear_graph_subscriptions(&self) {
         let mut subscriptions = self.graph_subscriptions.write().await;
         subscriptions.clear();
+        self.graph_subscriber_counts.write().await.clear();
+    }
+
+    /// Register one more connection subscribed to a trade's graph.
+    ///
+    /// Unlike [`subscribe_graph`](Self::subscribe_graph) this is counted,
+    /// so the trade stays subscribed until every connection has released it.
+    pub async fn add_graph_subscriber(&self, trade_id: &str) {
+        let mut counts = self.graph_subscriber_counts.write().await;
+        *counts.entry(trade_id.to_string()).or_insert(0) += 1;
+        self.subscribe_graph(trade_id).await;
+    }
+
+    /// Release one connection's subscription to a trade's graph.
+    ///
+    /// The trade is unsubscribed once no connection holds it.
+    pub async fn remove_graph_subscriber(&self, trade_id: &str) {
+        let mut counts = self.graph_subscriber_counts.write().await;
+        let remaining = match counts.get_mut(trade_id) {
+            Some(count) => {
+                *count = count.saturating_sub(1);
+                *count
+            }
+            None => 0,
+        };
+        if remaining == 0 {
+            counts.remove(trade_id);
+            self.unsubscribe_graph(trade_id).await;
+        }
     }
 }
 
@@ -338,7 +399,7 @@ fn build_cors() -> CorsLayer {
 
 fn build_csp_header() -> SetResponseHeaderLayer<HeaderValue> {
     const DEFAULT_CSP: &str = "default-src 'self'; \
-        script-src 'self'; \
+        script-src 'self' 'wasm-unsafe-eval'; \
         style-src 'self' 'unsafe-inline' https://fonts.googleapis.com https://cdnjs.cloudflare.com; \
         font-src 'self' https://fonts.gstatic.com https://cdnjs.cloudflare.com data:; \
         img-src 'self' data: blob:; \
@@ -361,6 +422,14 @@ pub fn build_router(state: Arc<AppState>) -> Router {
         .route("/portfolio", get(handlers::get_portfolio))
         .route("/portfolio", post(handlers::price_portfolio))
         .route("/exposure", get(handlers::get_exposure))
+        .route(
+            "/exposure/chart",
+            get(exposure_profiles::get_exposure_chart),
+        )
+        .route(
+            "/exposure/resimulate",
+            post(exposure_profiles::resimulate_exposure),
+        )
         .route("/risk", get(handlers::get_risk_metrics))
         // Task 3.2: Add /api/graph route for computation graph visualisation
         .route("/graph", get(handlers::get_graph))
@@ -407,6 +476,9 @@ pub fn build_router(state: Arc<AppState>) -> Router {
             "/scenarios/compare",
             post(scenario_handlers::compare_scenarios),
         )
+        // Shock scenario runs on the demo book (progress over /api/ws)
+        .route("/scenarios/runs", post(scenario_runner::start_shock_run))
+        .route("/scenarios/runs/:id", get(scenario_runner::get_shock_run))
         // Task 7.2: Add /api/v1/jobs endpoints for async job management
         .route("/v1/jobs", get(handlers::list_jobs))
         .route("/v1/jobs/:id", get(handlers::get_job_status))
@@ -419,7 +491,8 @@ pub fn build_router(state: Arc<AppState>) -> Router {
         ServeDir::new("demo/gui/static").not_found_service(handlers::serve_index_with_config());
 
     // CSP header: default policy for local static assets.
-    // - Script sources limited to self (vendor assets).
+    // - Script sources limited to self (vendor assets); 'wasm-unsafe-eval'
+    //   lets the client-side pricer in static/pkg compile.
     // - 'unsafe-inline' required for inline style attributes in the demo.
     // - Override via FB_CSP for stricter policies.
     let csp_header = build_csp_header();
@@ -444,6 +517,7 @@ pub fn build_router(state: Arc<AppState>) -> Router {
 /// Run the web server
 pub async fn run_server(addr: SocketAddr) -> anyhow::Result<()> {
     let state = Arc::new(AppState::new());
+    graph_updates::spawn_flusher(Arc::clone(&state));
     let app = build_router(state);
 
     info!("Starting web dashboard at http://{}", addr);
@@ -556,6 +630,23 @@ mod tests {
             let subscriptions = state.graph_subscriptions.read().await;
             assert!(subscriptions.is_empty());
         }
+
+        #[tokio::test]
+        async fn test_graph_subscribers_are_counted() {
+            let state = AppState::new();
+            state.add_graph_subscriber("T001").await;
+            state.add_graph_subscriber("T001").await;
+
+            state.remove_graph_subscriber("T001").await;
+            assert!(state.is_graph_subscribed("T001").await);
+
+            state.remove_graph_subscriber("T001").await;
+            assert!(!state.is_graph_subscribed("T001").await);
+
+            // Releasing an unknown trade is a no-op
+            state.remove_graph_subscriber("T002").await;
+            assert!(state.get_graph_subscriptions().await.is_empty());
+        }
     }
 
     // =========================================================================
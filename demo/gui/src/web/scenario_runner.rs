@@ -0,0 +1,611 @@
+//! Shock scenario runner for the FrictionalBank WebApp.
+//!
+//! Revalues the demo book under user-defined market shocks and reports
+//! per-trade P&L alongside per-counterparty CVA/DVA changes:
+//! - POST /api/scenarios/runs - Start a run; returns the run (job) ID
+//! - GET /api/scenarios/runs/{id} - Poll run status and results
+//!
+//! Runs execute as background jobs on the [`JobManager`](super::jobs::JobManager)
+//! and stream `scenario_progress` / `scenario_complete` messages over the
+//! WebSocket while trades are revalued.
+//!
+//! XVA uses a one-year, uncollateralised approximation per netting set:
+//! `CVA = -LGD * PD(cpty) * max(NS PV, 0)` and
+//! `DVA = LGD * PD(own) * max(-NS PV, 0)`, with `PD = 1 - exp(-s / LGD)`.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::handlers::JobErrorResponse;
+use super::jobs::JobResponse;
+use super::pricer_types::PricingErrorResponse;
+use super::websocket::{broadcast_scenario_complete, broadcast_scenario_progress};
+use super::AppState;
+use crate::portfolio_view::{price_trade, BookTrade, MarketState, PortfolioView};
+
+/// Largest accepted spot shock in percent
+const MAX_SPOT_PCT: f64 = 50.0;
+/// Largest accepted volatility shock in points
+const MAX_VOL_PTS: f64 = 50.0;
+/// Largest accepted rate shock in basis points
+const MAX_RATE_BP: f64 = 500.0;
+
+/// Recovery rate for counterparties and the bank
+const RECOVERY: f64 = 0.4;
+/// Bank's own credit spread in basis points (for DVA)
+const OWN_SPREAD_BP: f64 = 60.0;
+/// Credit spread for counterparties missing from the table, in basis points
+const DEFAULT_SPREAD_BP: f64 = 100.0;
+
+/// Counterparty credit spread in basis points
+fn counterparty_spread_bp(counterparty: &str) -> f64 {
+    match counterparty {
+        "Acme Corp" => 150.0,
+        "Globex Bank" => 90.0,
+        "Initech Fund" => 250.0,
+        _ => DEFAULT_SPREAD_BP,
+    }
+}
+
+/// One-year default probability implied by a spread
+fn default_probability(spread_bp: f64) -> f64 {
+    1.0 - (-spread_bp * 1e-4 / (1.0 - RECOVERY)).exp()
+}
+
+// =============================================================================
+// Request / Response Types
+// =============================================================================
+
+/// Market shocks applied to the whole book.
+///
+/// Omitted fields default to no shock.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ShockDefinition {
+    /// Relative spot move in percent (e.g. `-10.0` for spot down 10%)
+    pub spot_pct: f64,
+    /// Absolute volatility move in points (e.g. `5.0` for +5 vol)
+    pub vol_pts: f64,
+    /// Parallel rate move in basis points
+    pub rate_bp: f64,
+}
+
+impl ShockDefinition {
+    /// Check each shock is finite and within its limit.
+    ///
+    /// Returns the offending field and a message.
+    pub fn validate(&self) -> Result<(), (&'static str, String)> {
+        let limits = [
+            ("spotPct", self.spot_pct, MAX_SPOT_PCT, "%"),
+            ("volPts", self.vol_pts, MAX_VOL_PTS, "pts"),
+            ("rateBp", self.rate_bp, MAX_RATE_BP, "bp"),
+        ];
+        for (field, value, limit, unit) in limits {
+            if !value.is_finite() || value.abs() > limit {
+                return Err((
+                    field,
+                    format!("{} must be within ±{}{}, got {}", field, limit, unit, value),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Human-readable shocks, e.g. `Spot -10% Vol +5pts`
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if self.spot_pct != 0.0 {
+            parts.push(format!("Spot {:+}%", self.spot_pct));
+        }
+        if self.vol_pts != 0.0 {
+            parts.push(format!("Vol {:+}pts", self.vol_pts));
+        }
+        if self.rate_bp != 0.0 {
+            parts.push(format!("Rates {:+}bp", self.rate_bp));
+        }
+        if parts.is_empty() {
+            "No shock".to_string()
+        } else {
+            parts.join(" ")
+        }
+    }
+
+    /// Apply the shocks to a market
+    pub fn apply(&self, market: &MarketState) -> MarketState {
+        market.shocked(self.spot_pct, self.vol_pts, self.rate_bp)
+    }
+}
+
+/// Request for POST /api/scenarios/runs.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShockRunRequest {
+    /// Scenario name shown in the results (defaults to the shock description)
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Shocks to apply
+    #[serde(flatten)]
+    pub shocks: ShockDefinition,
+}
+
+/// Response for POST /api/scenarios/runs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShockRunCreatedResponse {
+    /// Run ID, also the job ID
+    pub run_id: String,
+    /// Trades to be revalued
+    pub trade_count: usize,
+    /// Where to poll for results
+    pub status_url: String,
+}
+
+/// Base and shocked PV of one trade.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeShockResult {
+    /// Trade identifier
+    pub trade_id: String,
+    /// Counterparty name
+    pub counterparty: String,
+    /// Netting set identifier
+    pub netting_set: String,
+    /// Product description
+    pub product: String,
+    /// PV before the shocks
+    pub base_pv: f64,
+    /// PV after the shocks
+    pub shocked_pv: f64,
+    /// Shocked minus base PV
+    pub pnl: f64,
+}
+
+/// CVA/DVA of one counterparty before and after the shocks.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CounterpartyXvaResult {
+    /// Counterparty name
+    pub counterparty: String,
+    /// Sum of trade P&L with this counterparty
+    pub pnl: f64,
+    /// CVA before the shocks (non-positive)
+    pub base_cva: f64,
+    /// CVA after the shocks
+    pub shocked_cva: f64,
+    /// Shocked minus base CVA
+    pub cva_delta: f64,
+    /// DVA before the shocks (non-negative)
+    pub base_dva: f64,
+    /// DVA after the shocks
+    pub shocked_dva: f64,
+    /// Shocked minus base DVA
+    pub dva_delta: f64,
+}
+
+/// Result of a completed shock scenario run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShockScenarioResult {
+    /// Scenario name
+    pub name: String,
+    /// Shocks applied
+    pub shocks: ShockDefinition,
+    /// Per-trade results in book order
+    pub trades: Vec<TradeShockResult>,
+    /// Per-counterparty XVA, sorted by name
+    pub counterparties: Vec<CounterpartyXvaResult>,
+    /// Book P&L
+    pub total_pnl: f64,
+    /// Change in book CVA
+    pub total_cva_delta: f64,
+    /// Change in book DVA
+    pub total_dva_delta: f64,
+    /// Change in CVA plus DVA
+    pub total_xva_delta: f64,
+}
+
+impl ShockScenarioResult {
+    /// Aggregate per-trade results into counterparty XVA and totals.
+    pub fn from_trades(
+        name: String,
+        shocks: ShockDefinition,
+        trades: Vec<TradeShockResult>,
+    ) -> Self {
+        // Netting set PVs keyed by (counterparty, netting set)
+        let mut netting_sets: BTreeMap<(&str, &str), (f64, f64)> = BTreeMap::new();
+        for t in &trades {
+            let entry = netting_sets
+                .entry((t.counterparty.as_str(), t.netting_set.as_str()))
+                .or_default();
+            entry.0 += t.base_pv;
+            entry.1 += t.shocked_pv;
+        }
+
+        let lgd = 1.0 - RECOVERY;
+        let own_pd = default_probability(OWN_SPREAD_BP);
+        let mut by_counterparty: BTreeMap<&str, CounterpartyXvaResult> = BTreeMap::new();
+        for ((counterparty, _), (base, shocked)) in netting_sets {
+            let pd = default_probability(counterparty_spread_bp(counterparty));
+            let cva = |pv: f64| -lgd * pd * pv.max(0.0);
+            let dva = |pv: f64| lgd * own_pd * (-pv).max(0.0);
+            let row =
+                by_counterparty
+                    .entry(counterparty)
+                    .or_insert_with(|| CounterpartyXvaResult {
+                        counterparty: counterparty.to_string(),
+                        pnl: 0.0,
+                        base_cva: 0.0,
+                        shocked_cva: 0.0,
+                        cva_delta: 0.0,
+                        base_dva: 0.0,
+                        shocked_dva: 0.0,
+                        dva_delta: 0.0,
+                    });
+            row.pnl += shocked - base;
+            row.base_cva += cva(base);
+            row.shocked_cva += cva(shocked);
+            row.base_dva += dva(base);
+            row.shocked_dva += dva(shocked);
+        }
+        let counterparties: Vec<CounterpartyXvaResult> = by_counterparty
+            .into_values()
+            .map(|mut row| {
+                row.cva_delta = row.shocked_cva - row.base_cva;
+                row.dva_delta = row.shocked_dva - row.base_dva;
+                row
+            })
+            .collect();
+
+        let total_pnl = trades.iter().map(|t| t.pnl).sum();
+        let total_cva_delta = counterparties.iter().map(|c| c.cva_delta).sum();
+        let total_dva_delta = counterparties.iter().map(|c| c.dva_delta).sum();
+        Self {
+            name,
+            shocks,
+            trades,
+            counterparties,
+            total_pnl,
+            total_cva_delta,
+            total_dva_delta,
+            total_xva_delta: total_cva_delta + total_dva_delta,
+        }
+    }
+}
+
+/// Revalue one trade on the base and shocked markets.
+pub fn revalue_trade(
+    trade: &BookTrade,
+    base: &MarketState,
+    shocked: &MarketState,
+) -> TradeShockResult {
+    let base_pv = price_trade(trade, base).pv;
+    let shocked_pv = price_trade(trade, shocked).pv;
+    TradeShockResult {
+        trade_id: trade.id.clone(),
+        counterparty: trade.counterparty.clone(),
+        netting_set: trade.netting_set.clone(),
+        product: format!("{} {}", trade.underlying, trade.product.label()),
+        base_pv,
+        shocked_pv,
+        pnl: shocked_pv - base_pv,
+    }
+}
+
+// =============================================================================
+// Run Execution
+// =============================================================================
+
+/// Revalue the demo book under the shocks, reporting progress per trade.
+///
+/// Updates the job's progress and broadcasts `scenario_progress` after each
+/// trade, then completes the job with the [`ShockScenarioResult`] and
+/// broadcasts `scenario_complete`.
+pub async fn execute_run(state: Arc<AppState>, run_id: Uuid, request: ShockRunRequest) {
+    let view = PortfolioView::default();
+    let base = view.market().clone();
+    let shocked = request.shocks.apply(&base);
+    let total = view.trades().len();
+    let run_key = run_id.to_string();
+
+    let mut trades = Vec::with_capacity(total);
+    for (idx, trade) in view.trades().iter().enumerate() {
+        trades.push(revalue_trade(trade, &base, &shocked));
+        let completed = idx + 1;
+        state
+            .job_manager
+            .update_progress(run_id, (completed * 100 / total.max(1)) as u8)
+            .await;
+        broadcast_scenario_progress(&state, &run_key, completed, total);
+        tokio::task::yield_now().await;
+    }
+
+    let name = request
+        .name
+        .filter(|n| !n.trim().is_empty())
+        .unwrap_or_else(|| request.shocks.describe());
+    let result = ShockScenarioResult::from_trades(name, request.shocks, trades);
+
+    match serde_json::to_value(&result) {
+        Ok(value) => {
+            state.job_manager.complete_job(run_id, value).await;
+            broadcast_scenario_complete(&state, &run_key, result.total_pnl, result.total_xva_delta);
+        }
+        Err(e) => {
+            state
+                .job_manager
+                .fail_job(run_id, format!("Failed to serialise result: {}", e))
+                .await;
+        }
+    }
+}
+
+// =============================================================================
+// Handlers
+// =============================================================================
+
+/// Start a shock scenario run on the demo book.
+///
+/// # Endpoint
+///
+/// `POST /api/scenarios/runs`
+///
+/// Returns `202 Accepted` with the run ID; progress arrives over the
+/// WebSocket and results via `GET /api/scenarios/runs/{id}`.
+pub async fn start_shock_run(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<ShockRunRequest>,
+) -> Result<(StatusCode, Json<ShockRunCreatedResponse>), (StatusCode, Json<PricingErrorResponse>)> {
+    if let Err((field, message)) = request.shocks.validate() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(PricingErrorResponse {
+                error_type: "ValidationError".to_string(),
+                message,
+                field: Some(field.to_string()),
+            }),
+        ));
+    }
+
+    let description = format!("Shock scenario: {}", request.shocks.describe());
+    let run_id = state.job_manager.create_job(Some(&description)).await;
+    let trade_count = PortfolioView::default().trades().len();
+    tokio::spawn(execute_run(Arc::clone(&state), run_id, request));
+
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(ShockRunCreatedResponse {
+            run_id: run_id.to_string(),
+            trade_count,
+            status_url: format!("/api/scenarios/runs/{}", run_id),
+        }),
+    ))
+}
+
+/// Get the status, and once complete the results, of a shock scenario run.
+///
+/// # Endpoint
+///
+/// `GET /api/scenarios/runs/{id}`
+pub async fn get_shock_run(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    let Ok(run_id) = Uuid::parse_str(&id) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(JobErrorResponse {
+                code: "INVALID_RUN_ID".to_string(),
+                message: format!("Invalid run ID format: {}", id),
+            }),
+        )
+            .into_response();
+    };
+
+    match state.job_manager.get_status(run_id).await {
+        Some(status) => (StatusCode::OK, Json(JobResponse::new(run_id, status))).into_response(),
+        None => (
+            StatusCode::NOT_FOUND,
+            Json(JobErrorResponse {
+                code: "RUN_NOT_FOUND".to_string(),
+                message: format!("Scenario run not found: {}", id),
+            }),
+        )
+            .into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::web::jobs::JobStatus;
+
+    fn request(spot_pct: f64, vol_pts: f64, rate_bp: f64) -> ShockRunRequest {
+        ShockRunRequest {
+            name: None,
+            shocks: ShockDefinition {
+                spot_pct,
+                vol_pts,
+                rate_bp,
+            },
+        }
+    }
+
+    #[test]
+    fn test_request_deserialises_flat_shocks() {
+        let req: ShockRunRequest =
+            serde_json::from_str(r#"{"name":"Crash","spotPct":-20,"volPts":10}"#).unwrap();
+        assert_eq!(req.name.as_deref(), Some("Crash"));
+        assert_eq!(req.shocks.spot_pct, -20.0);
+        assert_eq!(req.shocks.vol_pts, 10.0);
+        assert_eq!(req.shocks.rate_bp, 0.0);
+    }
+
+    #[test]
+    fn test_shock_validation() {
+        assert!(request(-10.0, 5.0, 25.0).shocks.validate().is_ok());
+        assert_eq!(
+            request(-60.0, 0.0, 0.0).shocks.validate().unwrap_err().0,
+            "spotPct"
+        );
+        assert_eq!(
+            request(0.0, f64::NAN, 0.0).shocks.validate().unwrap_err().0,
+            "volPts"
+        );
+        assert_eq!(
+            request(0.0, 0.0, -501.0).shocks.validate().unwrap_err().0,
+            "rateBp"
+        );
+    }
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(ShockDefinition::default().describe(), "No shock");
+        assert_eq!(
+            request(-10.0, 5.0, 25.0).shocks.describe(),
+            "Spot -10% Vol +5pts Rates +25bp"
+        );
+    }
+
+    #[test]
+    fn test_zero_shock_has_no_pnl_or_xva_delta() {
+        let view = PortfolioView::default();
+        let market = view.market();
+        let trades = view
+            .trades()
+            .iter()
+            .map(|t| revalue_trade(t, market, market))
+            .collect();
+        let result = ShockScenarioResult::from_trades("Flat".into(), Default::default(), trades);
+        assert_eq!(result.total_pnl, 0.0);
+        assert_eq!(result.total_xva_delta, 0.0);
+        assert_eq!(result.counterparties.len(), 3);
+        assert!(result.counterparties.iter().all(|c| c.base_cva <= 0.0));
+    }
+
+    #[test]
+    fn test_xva_follows_netting_set_exposure() {
+        let trade = |id: &str, ns: &str, base_pv: f64, shocked_pv: f64| TradeShockResult {
+            trade_id: id.to_string(),
+            counterparty: "Globex Bank".to_string(),
+            netting_set: ns.to_string(),
+            product: String::new(),
+            base_pv,
+            shocked_pv,
+            pnl: shocked_pv - base_pv,
+        };
+        // NS-A nets to +100 then +300; NS-B is a liability of 200 then 100
+        let result = ShockScenarioResult::from_trades(
+            "Test".into(),
+            Default::default(),
+            vec![
+                trade("T1", "NS-A", 300.0, 500.0),
+                trade("T2", "NS-A", -200.0, -200.0),
+                trade("T3", "NS-B", -200.0, -100.0),
+            ],
+        );
+        let lgd = 1.0 - RECOVERY;
+        let pd = default_probability(90.0);
+        let own_pd = default_probability(OWN_SPREAD_BP);
+        let row = &result.counterparties[0];
+        assert!((row.base_cva + lgd * pd * 100.0).abs() < 1e-9);
+        assert!((row.cva_delta + lgd * pd * 200.0).abs() < 1e-9);
+        assert!((row.dva_delta + lgd * own_pd * 100.0).abs() < 1e-9);
+        assert!((result.total_pnl - 300.0).abs() < 1e-9);
+        assert!(
+            (result.total_xva_delta - (result.total_cva_delta + result.total_dva_delta)).abs()
+                < 1e-12
+        );
+    }
+
+    #[tokio::test]
+    async fn test_execute_run_streams_progress_and_completes() {
+        let state = Arc::new(AppState::new());
+        let mut rx = state.tx.subscribe();
+        let run_id = state.job_manager.create_job(None).await;
+
+        execute_run(Arc::clone(&state), run_id, request(-10.0, 5.0, 0.0)).await;
+
+        let total = PortfolioView::default().trades().len();
+        let mut progress = Vec::new();
+        let mut complete = None;
+        while let Ok(msg) = rx.try_recv() {
+            let parsed: serde_json::Value = serde_json::from_str(&msg).unwrap();
+            assert_eq!(parsed["data"]["runId"], run_id.to_string());
+            match parsed["update_type"].as_str() {
+                Some("scenario_progress") => progress.push(parsed["data"]["percent"].clone()),
+                Some("scenario_complete") => complete = Some(parsed["data"].clone()),
+                other => panic!("unexpected message {:?}", other),
+            }
+        }
+        assert_eq!(progress.len(), total);
+        assert_eq!(progress.last().unwrap(), 100);
+
+        let Some(JobStatus::Completed { result }) = state.job_manager.get_status(run_id).await
+        else {
+            panic!("run did not complete");
+        };
+        let result: ShockScenarioResult = serde_json::from_value(result).unwrap();
+        assert_eq!(result.name, "Spot -10% Vol +5pts");
+        assert_eq!(result.trades.len(), total);
+        assert!(result.total_pnl != 0.0);
+        assert_eq!(complete.unwrap()["totalPnl"], result.total_pnl);
+    }
+
+    #[tokio::test]
+    async fn test_start_rejects_out_of_range_shock() {
+        let state = Arc::new(AppState::new());
+        let err = start_shock_run(State(state), Json(request(0.0, 0.0, 900.0)))
+            .await
+            .unwrap_err();
+        assert_eq!(err.0, StatusCode::BAD_REQUEST);
+        assert_eq!(err.1.field.as_deref(), Some("rateBp"));
+    }
+
+    #[tokio::test]
+    async fn test_start_and_poll_run() {
+        let state = Arc::new(AppState::new());
+        let (status, Json(created)) =
+            start_shock_run(State(Arc::clone(&state)), Json(request(5.0, 0.0, -25.0)))
+                .await
+                .unwrap();
+        assert_eq!(status, StatusCode::ACCEPTED);
+        assert_eq!(
+            created.status_url,
+            format!("/api/scenarios/runs/{}", created.run_id)
+        );
+
+        let run_id = Uuid::parse_str(&created.run_id).unwrap();
+        for _ in 0..100 {
+            if state
+                .job_manager
+                .get_status(run_id)
+                .await
+                .is_some_and(|s| s.is_terminal())
+            {
+                break;
+            }
+            tokio::task::yield_now().await;
+        }
+        let response = get_shock_run(State(Arc::clone(&state)), Path(created.run_id))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let missing = get_shock_run(State(Arc::clone(&state)), Path(Uuid::new_v4().to_string()))
+            .await
+            .into_response();
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+        let invalid = get_shock_run(State(state), Path("not-a-uuid".to_string()))
+            .await
+            .into_response();
+        assert_eq!(invalid.status(), StatusCode::BAD_REQUEST);
+    }
+}
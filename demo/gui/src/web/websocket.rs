@@ -7,6 +7,9 @@
 //!
 //! - `risk`: Risk metrics update (PV, CVA, DVA, FVA)
 //! - `exposure`: Exposure metrics update (EE, EPE, PFE)
+//! - `exposure_profiles`: Per-counterparty EE/ENE/PFE profiles after a re-simulation
+//! - `limit_events`: Counterparty limit warnings and breaches after a limit check
+//! - `scenario_progress` / `scenario_complete`: Shock scenario run progress
 //! - `graph_update`: Computation graph node updates (Task 4.1)
 //! - `irs_benchmark`: IRS AAD ベンチマーク結果の配信 (Task 6.3)
 //!
@@ -16,6 +19,9 @@
 //! - Send: `{"type":"subscribe_graph","trade_id":"T001"}`
 //! - Send: `{"type":"unsubscribe_graph","trade_id":"T001"}`
 //!
+//! `graph_update` messages are only forwarded to connections subscribed to
+//! the trade; subscriptions are released when the connection closes.
+//!
 //! ## IRS AAD Benchmark Updates (Task 6.3)
 //!
 //! ベンチマーク結果をリアルタイムで配信:
@@ -29,10 +35,14 @@ use axum::{
     response::IntoResponse,
 };
 use futures::{sink::SinkExt, stream::StreamExt};
+use pricer_risk::limits::LimitReport;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::sync::Arc;
+use tokio::sync::RwLock;
 use tracing::{info, warn};
 
+use super::exposure_profiles::ExposureProfileSet;
 use super::AppState;
 
 /// WebSocket upgrade handler
@@ -60,10 +70,28 @@ async fn handle_socket(socket: WebSocket, state: Arc<AppState>) {
         return;
     }
 
+    // Trades this connection has subscribed to; graph updates for other
+    // trades are not forwarded
+    let subscribed: Arc<RwLock<HashSet<String>>> = Arc::new(RwLock::new(HashSet::new()));
+    let mut graph_rx = state.graph_tx.subscribe();
+
     // Spawn task to forward broadcast messages to this client
+    let send_subscribed = Arc::clone(&subscribed);
     let mut send_task = tokio::spawn(async move {
         loop {
-            match rx.recv().await {
+            let received = tokio::select! {
+                msg = rx.recv() => msg,
+                graph = graph_rx.recv() => match graph {
+                    Ok(update) => {
+                        if !send_subscribed.read().await.contains(&update.trade_id) {
+                            continue;
+                        }
+                        Ok(update.message)
+                    }
+                    Err(e) => Err(e),
+                },
+            };
+            match received {
                 Ok(msg) => {
                     if sender.send(Message::Text(msg.into())).await.is_err() {
                         break;
@@ -85,6 +113,7 @@ async fn handle_socket(socket: WebSocket, state: Arc<AppState>) {
 
     // Handle incoming messages from client (Task 4.3: subscription support)
     let state_clone = Arc::clone(&state);
+    let recv_subscribed = Arc::clone(&subscribed);
     let mut recv_task = tokio::spawn(async move {
         while let Some(Ok(msg)) = receiver.next().await {
             if let Message::Text(text) = msg {
@@ -124,7 +153,13 @@ async fn handle_socket(socket: WebSocket, state: Arc<AppState>) {
                 if let Ok(request) = serde_json::from_str::<GraphSubscriptionRequest>(text_str) {
                     match request.request_type.as_str() {
                         "subscribe_graph" => {
-                            state_clone.subscribe_graph(&request.trade_id).await;
+                            if recv_subscribed
+                                .write()
+                                .await
+                                .insert(request.trade_id.clone())
+                            {
+                                state_clone.add_graph_subscriber(&request.trade_id).await;
+                            }
                             info!(
                                 "Client subscribed to graph updates for trade: {}",
                                 request.trade_id
@@ -139,7 +174,9 @@ async fn handle_socket(socket: WebSocket, state: Arc<AppState>) {
                             let _ = state_clone.tx.send(confirmation.to_string());
                         }
                         "unsubscribe_graph" => {
-                            state_clone.unsubscribe_graph(&request.trade_id).await;
+                            if recv_subscribed.write().await.remove(&request.trade_id) {
+                                state_clone.remove_graph_subscriber(&request.trade_id).await;
+                            }
                             info!(
                                 "Client unsubscribed from graph updates for trade: {}",
                                 request.trade_id
@@ -171,6 +208,11 @@ async fn handle_socket(socket: WebSocket, state: Arc<AppState>) {
         _ = &mut recv_task => send_task.abort(),
     }
 
+    // Release this connection's graph subscriptions
+    for trade_id in subscribed.read().await.iter() {
+        state.remove_graph_subscriber(trade_id).await;
+    }
+
     info!("WebSocket client disconnected");
 }
 
@@ -216,6 +258,72 @@ impl RealTimeUpdate {
     }
 }
 
+impl RealTimeUpdate {
+    /// Create an exposure profiles update from a completed simulation run.
+    ///
+    /// Carries every counterparty's EE/ENE/PFE time series so clients can
+    /// redraw charts without refetching.
+    pub fn exposure_profiles(set: &ExposureProfileSet) -> Self {
+        Self {
+            update_type: "exposure_profiles".to_string(),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            data: serde_json::json!({
+                "run_id": set.run_id,
+                "as_of": set.as_of,
+                "num_paths": set.num_paths,
+                "counterparties": set.profiles
+            }),
+        }
+    }
+}
+
+/// Broadcast re-simulated exposure profiles to all connected clients
+pub fn broadcast_exposure_profiles(state: &AppState, set: &ExposureProfileSet) {
+    let _ = state
+        .tx
+        .send(RealTimeUpdate::exposure_profiles(set).to_json());
+}
+
+impl RealTimeUpdate {
+    /// Create a limit events update from a limit check.
+    ///
+    /// Carries the warnings and breaches, breaches first; limits within
+    /// their warning level are omitted.
+    pub fn limit_events(report: &LimitReport) -> Self {
+        let events: Vec<serde_json::Value> = report
+            .events()
+            .iter()
+            .map(|event| {
+                serde_json::json!({
+                    "counterparty_id": event.counterparty_id.as_str(),
+                    "limit_type": event.limit_type.to_string(),
+                    "status": event.status.to_string(),
+                    "amount": event.amount,
+                    "usage": event.usage,
+                    "utilisation": event.utilisation,
+                    "peak_time": event.peak_time,
+                    "message": event.to_string()
+                })
+            })
+            .collect();
+        Self {
+            update_type: "limit_events".to_string(),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            data: serde_json::json!({
+                "has_breaches": report.has_breaches(),
+                "events": events
+            }),
+        }
+    }
+}
+
+/// Broadcast limit warnings and breaches to all connected clients
+pub fn broadcast_limit_events(state: &AppState, report: &LimitReport) {
+    let _ = state
+        .tx
+        .send(RealTimeUpdate::limit_events(report).to_json());
+}
+
 /// Broadcast an update to all connected clients
 pub fn broadcast_update(state: &AppState, update: RealTimeUpdate) {
     let _ = state.tx.send(update.to_json());
@@ -253,6 +361,18 @@ pub struct GraphNodeUpdate {
     pub delta: Option<f64>,
 }
 
+/// A serialised `graph_update` message tagged with its trade.
+///
+/// Sent on [`AppState::graph_tx`] so each connection can forward only the
+/// trades it has subscribed to.
+#[derive(Debug, Clone)]
+pub struct GraphBroadcast {
+    /// Trade the update belongs to
+    pub trade_id: String,
+    /// JSON-encoded [`RealTimeUpdate`]
+    pub message: String,
+}
+
 impl RealTimeUpdate {
     /// Create a graph update event.
     ///
@@ -483,14 +603,15 @@ impl RealTimeUpdate {
 // Task 4.2: Graph Update Broadcast
 // =============================================================================
 
-/// Broadcast a graph update to all connected clients.
+/// Broadcast a graph update to the clients subscribed to a trade.
 ///
-/// This function creates a `graph_update` message and broadcasts it
-/// through the existing broadcast channel.
+/// This function creates a `graph_update` message and sends it on the
+/// graph channel, from which each connection forwards only the trades it
+/// has subscribed to.
 ///
 /// # Arguments
 ///
-/// * `state` - Application state containing the broadcast channel
+/// * `state` - Application state containing the graph channel
 /// * `trade_id` - The trade ID whose graph has been updated
 /// * `updated_nodes` - Vector of node updates with new values and deltas
 ///
@@ -516,7 +637,10 @@ pub fn broadcast_graph_update(
     updated_nodes: Vec<GraphNodeUpdate>,
 ) {
     let update = RealTimeUpdate::graph_update(trade_id, updated_nodes);
-    let _ = state.tx.send(update.to_json());
+    let _ = state.graph_tx.send(GraphBroadcast {
+        trade_id: trade_id.to_string(),
+        message: update.to_json(),
+    });
 }
 
 // =============================================================================
@@ -872,6 +996,73 @@ pub fn broadcast_greeks_batch_update(
     }
 }
 
+// =============================================================================
+// Scenario Runner Progress Events
+// =============================================================================
+
+impl RealTimeUpdate {
+    /// Create a scenario run progress event.
+    ///
+    /// # Arguments
+    ///
+    /// * `run_id` - Job ID of the scenario run
+    /// * `completed` - Trades revalued so far
+    /// * `total` - Trades in the book
+    pub fn scenario_progress(run_id: &str, completed: usize, total: usize) -> Self {
+        let percent = if total == 0 {
+            100
+        } else {
+            completed * 100 / total
+        };
+        Self {
+            update_type: "scenario_progress".to_string(),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            data: serde_json::json!({
+                "runId": run_id,
+                "completed": completed,
+                "total": total,
+                "percent": percent
+            }),
+        }
+    }
+
+    /// Create a scenario run complete event.
+    ///
+    /// # Arguments
+    ///
+    /// * `run_id` - Job ID of the scenario run
+    /// * `total_pnl` - Book P&L under the shocks
+    /// * `total_xva_delta` - Change in CVA plus DVA under the shocks
+    pub fn scenario_complete(run_id: &str, total_pnl: f64, total_xva_delta: f64) -> Self {
+        Self {
+            update_type: "scenario_complete".to_string(),
+            timestamp: chrono::Utc::now().timestamp_millis(),
+            data: serde_json::json!({
+                "runId": run_id,
+                "totalPnl": total_pnl,
+                "totalXvaDelta": total_xva_delta
+            }),
+        }
+    }
+}
+
+/// Broadcast scenario run progress to all connected clients.
+pub fn broadcast_scenario_progress(state: &AppState, run_id: &str, completed: usize, total: usize) {
+    let update = RealTimeUpdate::scenario_progress(run_id, completed, total);
+    let _ = state.tx.send(update.to_json());
+}
+
+/// Broadcast scenario run completion to all connected clients.
+pub fn broadcast_scenario_complete(
+    state: &AppState,
+    run_id: &str,
+    total_pnl: f64,
+    total_xva_delta: f64,
+) {
+    let update = RealTimeUpdate::scenario_complete(run_id, total_pnl, total_xva_delta);
+    let _ = state.tx.send(update.to_json());
+}
+
 // =============================================================================
 // Task 4.3: Subscription Message Types
 // =============================================================================
@@ -910,6 +1101,36 @@ mod tests {
         assert!(json.contains("ee"));
     }
 
+    #[test]
+    fn test_limit_events_update() {
+        use pricer_risk::limits::{Limit, LimitChecker, LimitInputs};
+        use pricer_risk::portfolio::{CounterpartyId, PortfolioBuilder};
+
+        let cp = CounterpartyId::new("CP001");
+        let checker = LimitChecker::new()
+            .with_limits([
+                Limit::pfe(cp.clone(), 1.0, 100.0).unwrap(),
+                Limit::settlement(cp.clone(), 100.0).unwrap(),
+            ])
+            .unwrap();
+        let inputs = LimitInputs::new(vec![0.5, 1.0])
+            .with_pfe(cp.clone(), vec![60.0, 90.0])
+            .with_settlement(cp, 150.0);
+        let report = checker
+            .check(&PortfolioBuilder::new().build().unwrap(), &inputs)
+            .unwrap();
+
+        let update = RealTimeUpdate::limit_events(&report);
+        assert_eq!(update.update_type, "limit_events");
+        assert_eq!(update.data["has_breaches"], true);
+        let events = update.data["events"].as_array().unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0]["limit_type"], "Settlement");
+        assert_eq!(events[0]["status"], "breach");
+        assert_eq!(events[1]["status"], "warning");
+        assert_eq!(events[1]["peak_time"], 1.0);
+    }
+
     // =========================================================================
     // Task 4.1: graph_update Message Type Tests
     // =========================================================================
@@ -1036,7 +1257,7 @@ mod tests {
         #[tokio::test]
         async fn test_broadcast_graph_update() {
             let state = AppState::new();
-            let mut rx = state.tx.subscribe();
+            let mut rx = state.graph_tx.subscribe();
 
             let updated_nodes = vec![GraphNodeUpdate {
                 id: "N1".to_string(),
@@ -1050,7 +1271,9 @@ mod tests {
             let received = rx.try_recv();
             assert!(received.is_ok());
 
-            let msg = received.unwrap();
+            let broadcast = received.unwrap();
+            assert_eq!(broadcast.trade_id, "T001");
+            let msg = broadcast.message;
             assert!(msg.contains("graph_update"));
             assert!(msg.contains("T001"));
         }
@@ -1058,7 +1281,7 @@ mod tests {
         #[tokio::test]
         async fn test_broadcast_graph_update_multiple_nodes() {
             let state = AppState::new();
-            let mut rx = state.tx.subscribe();
+            let mut rx = state.graph_tx.subscribe();
 
             let updated_nodes = vec![
                 GraphNodeUpdate {
@@ -1080,7 +1303,7 @@ mod tests {
 
             broadcast_graph_update(&state, "T001", updated_nodes);
 
-            let received = rx.try_recv().unwrap();
+            let received = rx.try_recv().unwrap().message;
             assert!(received.contains("N1"));
             assert!(received.contains("N2"));
             assert!(received.contains("N3"));
@@ -325,6 +325,28 @@ async function ensureXlsxLoaded() {
     await loadScript(LIB_PATHS.xlsx);
 }
 
+// Analytical pricers compiled to WebAssembly from pricer_models (`wasm`
+// feature, built into static/pkg). Resolves to null when the bundle has
+// not been built, so callers fall back to the server.
+const WASM_PRICER_PATH = '/pkg/pricer_models.js';
+let wasmPricerLoader = null;
+
+function loadWasmPricer() {
+    if (!wasmPricerLoader) {
+        wasmPricerLoader = import(WASM_PRICER_PATH)
+            .then(async module => {
+                await module.default();
+                Logger.info('WasmPricer', 'Client-side pricer loaded');
+                return module;
+            })
+            .catch(error => {
+                Logger.debug('WasmPricer', 'Client-side pricer unavailable', { error: error.message });
+                return null;
+            });
+    }
+    return wasmPricerLoader;
+}
+
 function fetchJson(url, options = {}, errorMessage = 'Request failed') {
     return fetch(url, options).then(async response => {
         if (!response.ok) {
@@ -1023,6 +1045,7 @@ function navigateTo(viewName) {
         risk: 'Risk Analysis',
         exposure: 'Exposure Profile',
         scenarios: 'Scenario Analysis',
+        'scenario-runner': 'Shock Scenario Runner',
         analytics: '3D Analytics',
         graph: 'Computation Graph',
         pricer: 'Interactive Pricer',
@@ -2975,6 +2998,10 @@ function handleWsMessage(data) {
     } else if (messageType === 'graph_update') {
         // Task 5.1: Handle graph_update messages via GraphManager
         graphManager.handleGraphUpdate({ ...data, type: messageType });
+    } else if (messageType === 'scenario_progress' && typeof scenarioRunner !== 'undefined') {
+        scenarioRunner.handleProgress(data.data);
+    } else if (messageType === 'scenario_complete' && typeof scenarioRunner !== 'undefined') {
+        scenarioRunner.handleComplete(data.data);
     }
 }
 
@@ -4898,6 +4925,7 @@ const whatIfSimulator = {
         modal?.classList.add('active');
         if (dialog) openDialog(dialog, modal);
         this.initChart();
+        loadWasmPricer();
     },
     
     close() {
@@ -13798,3 +13826,174 @@ if (originalNavForScenarios) {
         }
     };
 }
+
+// ============================================
+// Shock Scenario Runner
+// ============================================
+
+/**
+ * Shock scenario runner: POST /api/scenarios/runs, follow progress over the
+ * WebSocket and load results from GET /api/scenarios/runs/{id}.
+ */
+const scenarioRunner = (() => {
+    const state = {
+        runId: null,
+        // Poll as a fallback when the WebSocket is down
+        pollTimer: null
+    };
+
+    const money = new Intl.NumberFormat('en-GB', { maximumFractionDigits: 0, signDisplay: 'auto' });
+    const signedMoney = new Intl.NumberFormat('en-GB', { maximumFractionDigits: 0, signDisplay: 'exceptZero' });
+
+    function readNumber(id) {
+        const value = parseFloat(document.getElementById(id)?.value);
+        return Number.isFinite(value) ? value : 0;
+    }
+
+    function setProgress(percent) {
+        const wrap = document.getElementById('runner-progress');
+        const fill = document.getElementById('runner-progress-fill');
+        const text = document.getElementById('runner-progress-text');
+        if (wrap) wrap.style.display = '';
+        if (fill) fill.style.width = `${percent}%`;
+        if (text) text.textContent = `${percent}%`;
+    }
+
+    function setRunning(running) {
+        const btn = document.getElementById('runner-run');
+        if (btn) btn.disabled = running;
+    }
+
+    function cell(text, signed = false, value = 0) {
+        const td = document.createElement('td');
+        td.textContent = text;
+        if (signed) td.className = value < 0 ? 'negative' : value > 0 ? 'positive' : '';
+        return td;
+    }
+
+    function fillRows(tbodyId, rows) {
+        const tbody = document.getElementById(tbodyId);
+        if (!tbody) return;
+        tbody.replaceChildren(...rows.map(cells => {
+            const tr = document.createElement('tr');
+            tr.append(...cells);
+            return tr;
+        }));
+    }
+
+    function setTotal(id, value) {
+        const el = document.getElementById(id);
+        if (!el) return;
+        el.textContent = signedMoney.format(value);
+        el.className = value < 0 ? 'negative' : value > 0 ? 'positive' : '';
+    }
+
+    function renderResult(result) {
+        const title = document.getElementById('runner-title');
+        if (title) title.textContent = `Results: ${result.name}`;
+        setTotal('runner-total-pnl', result.totalPnl);
+        setTotal('runner-total-cva', result.totalCvaDelta);
+        setTotal('runner-total-dva', result.totalDvaDelta);
+        setTotal('runner-total-xva', result.totalXvaDelta);
+
+        fillRows('runner-trades', result.trades.map(t => [
+            cell(t.tradeId),
+            cell(t.counterparty),
+            cell(t.product),
+            cell(money.format(t.basePv)),
+            cell(money.format(t.shockedPv)),
+            cell(signedMoney.format(t.pnl), true, t.pnl)
+        ]));
+        fillRows('runner-xva', result.counterparties.map(c => [
+            cell(c.counterparty),
+            cell(signedMoney.format(c.pnl), true, c.pnl),
+            cell(money.format(c.baseCva)),
+            cell(signedMoney.format(c.cvaDelta), true, c.cvaDelta),
+            cell(money.format(c.baseDva)),
+            cell(signedMoney.format(c.dvaDelta), true, c.dvaDelta)
+        ]));
+    }
+
+    function stopPolling() {
+        if (state.pollTimer) {
+            clearTimeout(state.pollTimer);
+            state.pollTimer = null;
+        }
+    }
+
+    async function loadResult(runId) {
+        stopPolling();
+        if (runId !== state.runId) return;
+        try {
+            const job = await fetchJson(`${API_BASE}/scenarios/runs/${runId}`, {}, 'Failed to load scenario run');
+            if (runId !== state.runId) return;
+            if (job.status === 'completed') {
+                state.runId = null;
+                setProgress(100);
+                setRunning(false);
+                renderResult(job.result);
+            } else if (job.status === 'failed') {
+                state.runId = null;
+                setRunning(false);
+                showToast(job.error || 'Scenario run failed', 'error');
+            } else {
+                if (job.progress_percent !== undefined) setProgress(job.progress_percent);
+                state.pollTimer = setTimeout(() => loadResult(runId), 1000);
+            }
+        } catch (e) {
+            state.runId = null;
+            setRunning(false);
+            Logger.error('ScenarioRunner', 'Failed to load result', { error: e.message });
+            showToast(e.message, 'error');
+        }
+    }
+
+    async function run(event) {
+        event.preventDefault();
+        if (state.runId) return;
+
+        const request = {
+            name: document.getElementById('runner-name')?.value.trim() || null,
+            spotPct: readNumber('runner-spot'),
+            volPts: readNumber('runner-vol'),
+            rateBp: readNumber('runner-rate')
+        };
+
+        setRunning(true);
+        setProgress(0);
+        try {
+            const created = await fetchJson(`${API_BASE}/scenarios/runs`, {
+                method: 'POST',
+                headers: { 'Content-Type': 'application/json' },
+                body: JSON.stringify(request)
+            }, 'Failed to start scenario run');
+            state.runId = created.runId;
+            Logger.debug('ScenarioRunner', 'Run started', { runId: created.runId, trades: created.tradeCount });
+            state.pollTimer = setTimeout(() => loadResult(created.runId), 1000);
+        } catch (e) {
+            setRunning(false);
+            Logger.error('ScenarioRunner', 'Failed to start run', { error: e.message });
+            showToast(e.message, 'error');
+        }
+    }
+
+    function handleProgress(data) {
+        if (data && data.runId === state.runId) setProgress(data.percent);
+    }
+
+    function handleComplete(data) {
+        if (data && data.runId === state.runId) loadResult(data.runId);
+    }
+
+    function init() {
+        document.getElementById('runner-form')?.addEventListener('submit', run);
+    }
+
+    return { init, handleProgress, handleComplete };
+})();
+
+document.addEventListener('DOMContentLoaded', () => {
+    if (document.getElementById('scenario-runner-view')) {
+        scenarioRunner.init();
+    }
+});
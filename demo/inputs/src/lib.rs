@@ -8,7 +8,7 @@
 //! ## Modules
 //!
 //! - [`market_data_provider`]: Simulates market data feeds (Reuters, Bloomberg style)
-//! - [`trade_source`]: Simulates front office trade booking
+//! - [`trade_source`]: Simulates front office trade booking and generates synthetic portfolios
 //! - [`file_source`]: Generates CSV/Parquet files for batch processing
 
 pub mod file_source;
@@ -24,5 +24,8 @@ pub mod prelude {
         BloombergSim, MarketDataProvider, MeanReversionModel, PriceEvolutionModel, RandomWalkModel,
         ReutersSim, StreamingPriceGenerator, SyntheticGenerator,
     };
-    pub use crate::trade_source::{FpmlGenerator, FrontOffice, TradeSource};
+    pub use crate::trade_source::{
+        FpmlGenerator, FrontOffice, NotionalDistribution, PortfolioGenerator, ProductMix,
+        TradeSource,
+    };
 }
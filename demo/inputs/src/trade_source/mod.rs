@@ -1,13 +1,16 @@
 //! Trade source simulations.
 //!
 //! This module provides mock implementations of trade booking systems
-//! that generate trade data for the Neutryx adapter layer.
+//! that generate trade data for the Neutryx adapter layer, and a seeded
+//! synthetic portfolio generator for demos and benchmarks.
 
 mod fpml_generator;
 mod front_office;
+mod portfolio_generator;
 
 pub use fpml_generator::FpmlGenerator;
 pub use front_office::FrontOffice;
+pub use portfolio_generator::{NotionalDistribution, PortfolioGenerator, ProductMix};
 
 /// Trait for trade sources
 pub trait TradeSource: Send + Sync {
@@ -16,7 +19,7 @@ pub trait TradeSource: Send + Sync {
 }
 
 /// A trade record from the booking system
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct TradeRecord {
     /// Trade ID
     pub trade_id: String,
@@ -56,7 +59,7 @@ pub enum InstrumentType {
 }
 
 /// Trade-specific parameters
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum TradeParams {
     /// Equity option parameters
     EquityOption {
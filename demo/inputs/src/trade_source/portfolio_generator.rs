@@ -0,0 +1,569 @@
+//! Synthetic portfolio generation.
+//!
+//! Generates portfolios of any size with a configurable product mix,
+//! maturity range, counterparty set, currencies and notional distribution.
+//! Generation is driven by a seeded RNG, so the same settings always yield
+//! the same portfolio; this makes it suitable for benchmarks as well as
+//! demos.
+
+use super::{InstrumentType, TradeParams, TradeRecord, TradeSource};
+use chrono::{Days, NaiveDate, Utc};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_distr::{Distribution, LogNormal};
+
+/// Default RNG seed
+pub const DEFAULT_SEED: u64 = 42;
+
+/// Share of each product family in a generated portfolio.
+///
+/// Weights are relative and need not sum to 100. FX trades are split
+/// between forwards and options.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProductMix {
+    /// Vanilla equity options
+    pub vanilla: f64,
+    /// Interest rate swaps
+    pub swaps: f64,
+    /// FX forwards and options
+    pub fx: f64,
+    /// Credit default swaps
+    pub cds: f64,
+}
+
+impl ProductMix {
+    /// Create a product mix from percentages (or any relative weights).
+    ///
+    /// Returns `None` if a weight is negative or not finite, or if all
+    /// weights are zero.
+    pub fn new(vanilla: f64, swaps: f64, fx: f64, cds: f64) -> Option<Self> {
+        let weights = [vanilla, swaps, fx, cds];
+        if weights.iter().any(|w| !w.is_finite() || *w < 0.0) || weights.iter().sum::<f64>() <= 0.0
+        {
+            return None;
+        }
+        Some(Self {
+            vanilla,
+            swaps,
+            fx,
+            cds,
+        })
+    }
+
+    /// Split `count` trades across vanilla, swaps, FX and CDS.
+    ///
+    /// Uses largest remainders so the counts always sum to `count`.
+    pub fn allocate(&self, count: usize) -> [usize; 4] {
+        let weights = [self.vanilla, self.swaps, self.fx, self.cds];
+        let total: f64 = weights.iter().sum();
+        let exact: Vec<f64> = weights.iter().map(|w| w / total * count as f64).collect();
+        let mut counts = [0usize; 4];
+        for (c, e) in counts.iter_mut().zip(&exact) {
+            *c = e.floor() as usize;
+        }
+
+        let mut by_remainder: Vec<usize> = (0..4).collect();
+        by_remainder.sort_by(|&a, &b| {
+            let ra = exact[a] - exact[a].floor();
+            let rb = exact[b] - exact[b].floor();
+            rb.total_cmp(&ra).then(a.cmp(&b))
+        });
+        let assigned: usize = counts.iter().sum();
+        for &i in by_remainder.iter().take(count - assigned) {
+            counts[i] += 1;
+        }
+        counts
+    }
+}
+
+impl Default for ProductMix {
+    /// 30% vanilla, 30% swaps, 25% FX, 15% CDS
+    fn default() -> Self {
+        Self {
+            vanilla: 30.0,
+            swaps: 30.0,
+            fx: 25.0,
+            cds: 15.0,
+        }
+    }
+}
+
+/// Distribution of trade notionals
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NotionalDistribution {
+    /// Every trade has the same notional
+    Fixed(f64),
+    /// Uniform between `min` and `max`
+    Uniform { min: f64, max: f64 },
+    /// Log-normal with the given median and log-space standard deviation
+    LogNormal { median: f64, sigma: f64 },
+}
+
+impl NotionalDistribution {
+    /// Draw a notional
+    fn sample<R: Rng>(&self, rng: &mut R) -> f64 {
+        match *self {
+            Self::Fixed(notional) => notional,
+            Self::Uniform { min, max } if max > min => rng.gen_range(min..max),
+            Self::Uniform { min, .. } => min,
+            Self::LogNormal { median, sigma } => LogNormal::new(median.ln(), sigma)
+                .map(|d| d.sample(rng))
+                .unwrap_or(median),
+        }
+    }
+}
+
+impl Default for NotionalDistribution {
+    /// Log-normal around 10mm, so most trades fall between 2mm and 50mm
+    fn default() -> Self {
+        Self::LogNormal {
+            median: 10_000_000.0,
+            sigma: 0.8,
+        }
+    }
+}
+
+/// Seeded synthetic portfolio generator.
+///
+/// # Example
+///
+/// ```rust
+/// use demo_inputs::trade_source::{PortfolioGenerator, ProductMix};
+///
+/// let generator = PortfolioGenerator::new()
+///     .with_seed(7)
+///     .with_mix(ProductMix::new(0.0, 80.0, 20.0, 0.0).unwrap())
+///     .with_currencies(&["USD", "EUR"]);
+///
+/// let portfolio = generator.generate(1_000);
+/// assert_eq!(portfolio.len(), 1_000);
+/// assert_eq!(portfolio, generator.generate(1_000));
+/// ```
+#[derive(Debug, Clone)]
+pub struct PortfolioGenerator {
+    seed: u64,
+    mix: ProductMix,
+    /// Maturity range in months (inclusive)
+    maturity_months: (u32, u32),
+    counterparties: Vec<String>,
+    netting_sets_per_counterparty: usize,
+    currencies: Vec<String>,
+    notional: NotionalDistribution,
+    trade_date: NaiveDate,
+}
+
+impl Default for PortfolioGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PortfolioGenerator {
+    /// Create a generator with the default mix, ten counterparties,
+    /// USD/EUR/GBP/JPY and maturities from one month to ten years
+    pub fn new() -> Self {
+        Self {
+            seed: DEFAULT_SEED,
+            mix: ProductMix::default(),
+            maturity_months: (1, 120),
+            counterparties: numbered_counterparties(10),
+            netting_sets_per_counterparty: 2,
+            currencies: ["USD", "EUR", "GBP", "JPY"]
+                .iter()
+                .map(|c| c.to_string())
+                .collect(),
+            notional: NotionalDistribution::default(),
+            trade_date: Utc::now().date_naive(),
+        }
+    }
+
+    /// Set the RNG seed
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Set the product mix
+    pub fn with_mix(mut self, mix: ProductMix) -> Self {
+        self.mix = mix;
+        self
+    }
+
+    /// Set the maturity range in months (inclusive, at least one month)
+    pub fn with_maturity_months(mut self, min: u32, max: u32) -> Self {
+        let min = min.max(1);
+        self.maturity_months = (min, max.max(min));
+        self
+    }
+
+    /// Use counterparties `CP001` to `CPnnn`
+    pub fn with_counterparty_count(mut self, count: usize) -> Self {
+        self.counterparties = numbered_counterparties(count.max(1));
+        self
+    }
+
+    /// Use the given counterparty IDs (ignored if empty)
+    pub fn with_counterparties(mut self, ids: &[&str]) -> Self {
+        if !ids.is_empty() {
+            self.counterparties = ids.iter().map(|id| id.to_string()).collect();
+        }
+        self
+    }
+
+    /// Set the number of netting sets per counterparty (at least one)
+    pub fn with_netting_sets_per_counterparty(mut self, count: usize) -> Self {
+        self.netting_sets_per_counterparty = count.max(1);
+        self
+    }
+
+    /// Set the trade currencies (ignored if empty)
+    pub fn with_currencies(mut self, currencies: &[&str]) -> Self {
+        if !currencies.is_empty() {
+            self.currencies = currencies.iter().map(|c| c.to_uppercase()).collect();
+        }
+        self
+    }
+
+    /// Set the notional distribution
+    pub fn with_notional(mut self, notional: NotionalDistribution) -> Self {
+        self.notional = notional;
+        self
+    }
+
+    /// Set the trade date (fix it for output that is identical across days)
+    pub fn with_trade_date(mut self, date: NaiveDate) -> Self {
+        self.trade_date = date;
+        self
+    }
+
+    /// Product mix in use
+    pub fn mix(&self) -> ProductMix {
+        self.mix
+    }
+
+    /// Generate `count` trades.
+    ///
+    /// Trade IDs are numbered in portfolio order, so the same settings
+    /// produce the same IDs.
+    pub fn generate(&self, count: usize) -> Vec<TradeRecord> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let [vanilla, swaps, fx, cds] = self.mix.allocate(count);
+
+        let mut kinds = Vec::with_capacity(count);
+        kinds.extend(std::iter::repeat_n(InstrumentType::EquityOption, vanilla));
+        kinds.extend(std::iter::repeat_n(InstrumentType::InterestRateSwap, swaps));
+        for _ in 0..fx {
+            kinds.push(if rng.gen_bool(0.5) {
+                InstrumentType::FxForward
+            } else {
+                InstrumentType::FxOption
+            });
+        }
+        kinds.extend(std::iter::repeat_n(InstrumentType::CreditDefaultSwap, cds));
+        kinds.shuffle(&mut rng);
+
+        kinds
+            .into_iter()
+            .enumerate()
+            .map(|(i, kind)| self.generate_trade(&mut rng, i + 1, kind))
+            .collect()
+    }
+
+    fn generate_trade(&self, rng: &mut StdRng, seq: usize, kind: InstrumentType) -> TradeRecord {
+        let cp = &self.counterparties[rng.gen_range(0..self.counterparties.len())];
+        let ns = rng.gen_range(1..=self.netting_sets_per_counterparty);
+        let currency = self.currencies[rng.gen_range(0..self.currencies.len())].clone();
+        let (min_m, max_m) = self.maturity_months;
+        let months = rng.gen_range(min_m..=max_m) as u64;
+        let maturity = self
+            .trade_date
+            .checked_add_days(Days::new(months * 365 / 12))
+            .unwrap_or(self.trade_date);
+        let notional = self.notional.sample(rng);
+
+        let (prefix, currency, params) = match kind {
+            InstrumentType::InterestRateSwap => (
+                "IRS",
+                currency.clone(),
+                TradeParams::InterestRateSwap {
+                    fixed_rate: rng.gen_range(0.005..0.06),
+                    float_index: float_index(&currency).to_string(),
+                    pay_fixed: rng.gen_bool(0.5),
+                },
+            ),
+            InstrumentType::FxForward => {
+                let (base, quote) = self.currency_pair(rng, &currency);
+                let spot = fx_spot(&base, &quote);
+                (
+                    "FX-FWD",
+                    base.clone(),
+                    TradeParams::FxForward {
+                        buy_currency: base,
+                        sell_currency: quote,
+                        rate: spot * (1.0 + rng.gen_range(-0.02..0.02)),
+                    },
+                )
+            }
+            InstrumentType::FxOption => {
+                let (base, quote) = self.currency_pair(rng, &currency);
+                let spot = fx_spot(&base, &quote);
+                (
+                    "FX-OPT",
+                    base.clone(),
+                    TradeParams::FxOption {
+                        currency_pair: format!("{}{}", base, quote),
+                        strike: spot * rng.gen_range(0.90..1.10),
+                        is_call: rng.gen_bool(0.5),
+                    },
+                )
+            }
+            InstrumentType::CreditDefaultSwap => {
+                let (entity, spread) =
+                    REFERENCE_ENTITIES[rng.gen_range(0..REFERENCE_ENTITIES.len())];
+                (
+                    "CDS",
+                    currency,
+                    TradeParams::CreditDefaultSwap {
+                        reference_entity: entity.to_string(),
+                        spread_bps: spread * rng.gen_range(0.8..1.2),
+                        is_protection_buyer: rng.gen_bool(0.5),
+                    },
+                )
+            }
+            // Vanilla options; forwards are not part of the mix
+            InstrumentType::EquityOption | InstrumentType::EquityForward => {
+                let (ticker, spot) = equity_underlying(&currency);
+                (
+                    "EQ-OPT",
+                    currency,
+                    TradeParams::EquityOption {
+                        underlying: ticker.to_string(),
+                        strike: spot * rng.gen_range(0.85..1.15),
+                        is_call: rng.gen_bool(0.5),
+                    },
+                )
+            }
+        };
+
+        TradeRecord {
+            trade_id: format!("{}-{:06}", prefix, seq),
+            instrument_type: kind,
+            counterparty_id: cp.clone(),
+            netting_set_id: format!("{}-NS{}", cp, ns),
+            notional,
+            currency,
+            trade_date: self.trade_date.to_string(),
+            maturity_date: maturity.to_string(),
+            params,
+        }
+    }
+
+    /// Pick an FX pair containing `currency`, quoted against another
+    /// configured currency (or USD/EUR if only one is configured)
+    fn currency_pair(&self, rng: &mut StdRng, currency: &str) -> (String, String) {
+        let others: Vec<&String> = self
+            .currencies
+            .iter()
+            .filter(|c| c.as_str() != currency)
+            .collect();
+        let other = match others.as_slice() {
+            [] if currency == "USD" => "EUR".to_string(),
+            [] => "USD".to_string(),
+            others => others[rng.gen_range(0..others.len())].clone(),
+        };
+        if rng.gen_bool(0.5) {
+            (currency.to_string(), other)
+        } else {
+            (other, currency.to_string())
+        }
+    }
+}
+
+impl TradeSource for PortfolioGenerator {
+    fn generate_trades(&self, count: usize) -> Vec<TradeRecord> {
+        self.generate(count)
+    }
+}
+
+/// CDS reference entities with indicative spreads in bps
+const REFERENCE_ENTITIES: [(&str, f64); 6] = [
+    ("FORD", 150.0),
+    ("GM", 120.0),
+    ("BOEING", 80.0),
+    ("ATT", 100.0),
+    ("VERIZON", 75.0),
+    ("VOLKSWAGEN", 110.0),
+];
+
+fn numbered_counterparties(count: usize) -> Vec<String> {
+    (1..=count).map(|i| format!("CP{:03}", i)).collect()
+}
+
+/// Overnight index for swaps in a currency
+fn float_index(currency: &str) -> &'static str {
+    match currency {
+        "EUR" => "EURIBOR",
+        "GBP" => "SONIA",
+        "JPY" => "TONAR",
+        "CHF" => "SARON",
+        _ => "SOFR",
+    }
+}
+
+/// Representative equity underlying and spot for a currency
+fn equity_underlying(currency: &str) -> (&'static str, f64) {
+    match currency {
+        "EUR" => ("SAP.DE", 180.0),
+        "GBP" => ("VOD.L", 0.72),
+        "JPY" => ("7203.T", 2800.0),
+        "CHF" => ("NESN.SW", 95.0),
+        _ => ("AAPL", 185.0),
+    }
+}
+
+/// Indicative spot for `base`/`quote`, via USD
+fn fx_spot(base: &str, quote: &str) -> f64 {
+    fn usd_per_unit(ccy: &str) -> f64 {
+        match ccy {
+            "EUR" => 1.085,
+            "GBP" => 1.265,
+            "JPY" => 1.0 / 150.25,
+            "CHF" => 1.0 / 0.882,
+            _ => 1.0,
+        }
+    }
+    usd_per_unit(base) / usd_per_unit(quote)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generator() -> PortfolioGenerator {
+        PortfolioGenerator::new().with_trade_date(NaiveDate::from_ymd_opt(2025, 1, 15).unwrap())
+    }
+
+    fn count(trades: &[TradeRecord], kinds: &[InstrumentType]) -> usize {
+        trades
+            .iter()
+            .filter(|t| kinds.contains(&t.instrument_type))
+            .count()
+    }
+
+    #[test]
+    fn test_same_seed_same_portfolio() {
+        let a = generator().with_seed(11).generate(200);
+        let b = generator().with_seed(11).generate(200);
+        let c = generator().with_seed(12).generate(200);
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn test_mix_is_respected_exactly() {
+        let mix = ProductMix::new(10.0, 50.0, 25.0, 15.0).unwrap();
+        let trades = generator().with_mix(mix).generate(1_000);
+
+        assert_eq!(trades.len(), 1_000);
+        assert_eq!(count(&trades, &[InstrumentType::EquityOption]), 100);
+        assert_eq!(count(&trades, &[InstrumentType::InterestRateSwap]), 500);
+        assert_eq!(
+            count(
+                &trades,
+                &[InstrumentType::FxForward, InstrumentType::FxOption]
+            ),
+            250
+        );
+        assert_eq!(count(&trades, &[InstrumentType::CreditDefaultSwap]), 150);
+    }
+
+    #[test]
+    fn test_allocation_sums_to_count() {
+        let mix = ProductMix::new(1.0, 1.0, 1.0, 0.0).unwrap();
+        assert_eq!(mix.allocate(10), [4, 3, 3, 0]);
+        for n in [0, 1, 7, 99] {
+            assert_eq!(ProductMix::default().allocate(n).iter().sum::<usize>(), n);
+        }
+    }
+
+    #[test]
+    fn test_invalid_mix_rejected() {
+        assert!(ProductMix::new(0.0, 0.0, 0.0, 0.0).is_none());
+        assert!(ProductMix::new(-10.0, 50.0, 50.0, 10.0).is_none());
+        assert!(ProductMix::new(f64::NAN, 50.0, 50.0, 10.0).is_none());
+    }
+
+    #[test]
+    fn test_settings_are_applied() {
+        let trade_date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let trades = generator()
+            .with_maturity_months(12, 24)
+            .with_counterparties(&["ACME", "GLOBEX"])
+            .with_netting_sets_per_counterparty(1)
+            .with_currencies(&["eur", "jpy"])
+            .with_notional(NotionalDistribution::Uniform {
+                min: 1_000_000.0,
+                max: 2_000_000.0,
+            })
+            .generate(300);
+
+        for trade in &trades {
+            assert!(["ACME", "GLOBEX"].contains(&trade.counterparty_id.as_str()));
+            assert_eq!(
+                trade.netting_set_id,
+                format!("{}-NS1", trade.counterparty_id)
+            );
+            assert!((1_000_000.0..2_000_000.0).contains(&trade.notional));
+
+            let maturity: NaiveDate = trade.maturity_date.parse().unwrap();
+            let days = (maturity - trade_date).num_days();
+            assert!((365..=730).contains(&days), "{} days", days);
+
+            match &trade.params {
+                TradeParams::FxForward {
+                    buy_currency,
+                    sell_currency,
+                    ..
+                } => {
+                    let mut pair = [buy_currency.as_str(), sell_currency.as_str()];
+                    pair.sort();
+                    assert_eq!(pair, ["EUR", "JPY"]);
+                }
+                TradeParams::FxOption { currency_pair, .. } => {
+                    assert!(currency_pair == "EURJPY" || currency_pair == "JPYEUR");
+                }
+                _ => assert!(["EUR", "JPY"].contains(&trade.currency.as_str())),
+            }
+        }
+    }
+
+    #[test]
+    fn test_trade_ids_are_unique() {
+        let trades = generator().generate(500);
+        let mut ids: Vec<&str> = trades.iter().map(|t| t.trade_id.as_str()).collect();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), 500);
+    }
+
+    #[test]
+    fn test_log_normal_notional_median() {
+        let mut notionals: Vec<f64> = generator()
+            .with_seed(3)
+            .generate(2_001)
+            .iter()
+            .map(|t| t.notional)
+            .collect();
+        notionals.sort_by(f64::total_cmp);
+        let median = notionals[1_000];
+        assert!((7_000_000.0..14_000_000.0).contains(&median), "{}", median);
+    }
+
+    #[test]
+    fn test_fx_spot_cross() {
+        assert!((fx_spot("USD", "JPY") - 150.25).abs() < 1e-9);
+        assert!((fx_spot("EUR", "USD") - 1.085).abs() < 1e-12);
+        assert!((fx_spot("EUR", "JPY") - 1.085 * 150.25).abs() < 1e-9);
+    }
+}
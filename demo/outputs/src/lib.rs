@@ -19,7 +19,7 @@ pub mod settlement;
 
 /// Prelude module for convenient imports
 pub mod prelude {
-    pub use crate::regulatory::{AuditStore, RegulatorApi};
+    pub use crate::regulatory::{AuditStore, RegulatorApi, TradeReporter};
     pub use crate::report_sink::{EmailSender, FileWriter, ReportSink};
     pub use crate::risk_dashboard::{MetricsStore, WebSocketSink};
     pub use crate::settlement::{NettingEngine, SwiftReceiver};
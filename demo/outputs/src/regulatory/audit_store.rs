@@ -1,11 +1,33 @@
-//! Audit trail storage for regulatory compliance.
+//! Append-only audit trail for regulatory compliance and model governance.
+//!
+//! Every event is chained to its predecessor: its `hash` is the SHA-256 of
+//! the event's content including the previous event's hash. Altering,
+//! removing or reordering a recorded event breaks the chain, which
+//! [`AuditStore::verify`] detects and which stops a tampered journal from
+//! being reopened.
+//!
+//! A store opened with [`AuditStore::open`] writes each event to a JSON
+//! lines journal before acknowledging it, and never rewrites the file. The
+//! in-memory window used for queries keeps the most recent `max_events`
+//! events; the journal keeps everything.
+//!
+//! Calculation runs are recorded as [`RunRecord`]s: who ran what, with
+//! fingerprints of inputs, configuration and results.
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::VecDeque;
-use std::io::{self, BufReader, BufWriter};
+use std::fmt::Write as _;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
 use std::path::Path;
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, RwLock};
+
+use super::run_record::{RunRecord, RunStatus};
+
+/// Previous hash of the first event in a chain
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
 
 /// Audit event types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -26,6 +48,8 @@ pub enum AuditEventType {
     ReportSubmitted,
     /// User action
     UserAction,
+    /// Calculation run record (details hold a [`RunRecord`])
+    ModelRun,
 }
 
 /// Audit event
@@ -49,6 +73,108 @@ pub struct AuditEvent {
     pub before: Option<serde_json::Value>,
     /// After state (for amendments)
     pub after: Option<serde_json::Value>,
+    /// Hash of the preceding event
+    #[serde(default)]
+    pub previous_hash: String,
+    /// SHA-256 of this event's content and `previous_hash`
+    #[serde(default)]
+    pub hash: String,
+}
+
+impl AuditEvent {
+    /// Hash of the event's content, excluding the `hash` field itself.
+    pub fn compute_hash(&self) -> String {
+        let mut content = serde_json::to_value(self).unwrap_or_default();
+        if let Some(fields) = content.as_object_mut() {
+            fields.remove("hash");
+        }
+        // serde_json maps are sorted, so the JSON text is canonical
+        Sha256::digest(content.to_string().as_bytes()).iter().fold(
+            String::with_capacity(64),
+            |mut out, b| {
+                let _ = write!(out, "{:02x}", b);
+                out
+            },
+        )
+    }
+}
+
+/// Filter over audit events; an empty query matches every event.
+#[derive(Debug, Clone, Default)]
+pub struct AuditQuery {
+    /// Only this event type
+    pub event_type: Option<AuditEventType>,
+    /// Only this actor
+    pub actor: Option<String>,
+    /// Only this entity type
+    pub entity_type: Option<String>,
+    /// Only this entity ID
+    pub entity_id: Option<String>,
+    /// Events at or after
+    pub from: Option<DateTime<Utc>>,
+    /// Events at or before
+    pub to: Option<DateTime<Utc>>,
+    /// Keep only the most recent matches
+    pub limit: Option<usize>,
+}
+
+impl AuditQuery {
+    /// Query matching every event
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Restrict to an event type
+    pub fn with_event_type(mut self, event_type: AuditEventType) -> Self {
+        self.event_type = Some(event_type);
+        self
+    }
+
+    /// Restrict to an actor
+    pub fn with_actor(mut self, actor: impl Into<String>) -> Self {
+        self.actor = Some(actor.into());
+        self
+    }
+
+    /// Restrict to one entity
+    pub fn with_entity(
+        mut self,
+        entity_type: impl Into<String>,
+        entity_id: impl Into<String>,
+    ) -> Self {
+        self.entity_type = Some(entity_type.into());
+        self.entity_id = Some(entity_id.into());
+        self
+    }
+
+    /// Restrict to timestamps in `[from, to]`
+    pub fn with_time_range(mut self, from: DateTime<Utc>, to: DateTime<Utc>) -> Self {
+        self.from = Some(from);
+        self.to = Some(to);
+        self
+    }
+
+    /// Keep only the `limit` most recent matches
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Whether the query selects an event (ignoring the limit)
+    pub fn matches(&self, event: &AuditEvent) -> bool {
+        self.event_type.is_none_or(|t| t == event.event_type)
+            && self.actor.as_ref().is_none_or(|a| *a == event.actor)
+            && self
+                .entity_type
+                .as_ref()
+                .is_none_or(|t| *t == event.entity_type)
+            && self
+                .entity_id
+                .as_ref()
+                .is_none_or(|id| *id == event.entity_id)
+            && self.from.is_none_or(|from| event.timestamp >= from)
+            && self.to.is_none_or(|to| event.timestamp <= to)
+    }
 }
 
 /// Audit trail store
@@ -59,24 +185,108 @@ pub struct AuditStore {
     max_events: usize,
     /// Event counter for ID generation
     counter: Arc<RwLock<u64>>,
+    /// Hash of the last recorded event
+    head: Arc<RwLock<String>>,
+    /// Append-only journal, when opened from a file
+    journal: Option<Arc<Mutex<File>>>,
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn event_counter(event: &AuditEvent) -> Option<u64> {
+    event
+        .event_id
+        .strip_prefix("AUD-")
+        .and_then(|s| s.parse::<u64>().ok())
+}
+
+/// Check each event's hash and its link to the preceding event.
+///
+/// `previous` is the expected `previous_hash` of the first event, or
+/// `None` when the events start mid-chain.
+fn verify_chain<'a>(
+    events: impl IntoIterator<Item = &'a AuditEvent>,
+    mut previous: Option<&'a str>,
+) -> io::Result<()> {
+    for event in events {
+        if let Some(expected) = previous {
+            if event.previous_hash != expected {
+                return Err(invalid_data(format!(
+                    "audit chain broken before event {}",
+                    event.event_id
+                )));
+            }
+        }
+        if event.hash != event.compute_hash() {
+            return Err(invalid_data(format!(
+                "audit event {} has been modified",
+                event.event_id
+            )));
+        }
+        previous = Some(&event.hash);
+    }
+    Ok(())
 }
 
 impl AuditStore {
-    /// Create a new audit store
+    /// Create a new in-memory audit store
     pub fn new() -> Self {
         Self {
             events: Arc::new(RwLock::new(VecDeque::with_capacity(10000))),
             max_events: 100000,
             counter: Arc::new(RwLock::new(0)),
+            head: Arc::new(RwLock::new(GENESIS_HASH.to_string())),
+            journal: None,
         }
     }
 
-    /// Set maximum events to retain
+    /// Open an append-only journal (JSON lines), creating it if missing.
+    ///
+    /// The whole chain is verified before the store accepts new events; a
+    /// modified, removed or reordered event is an `InvalidData` error.
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let mut store = Self::new();
+        let mut events = Vec::new();
+        if path.exists() {
+            for (number, line) in BufReader::new(File::open(path)?).lines().enumerate() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let event: AuditEvent = serde_json::from_str(&line)
+                    .map_err(|e| invalid_data(format!("journal line {}: {}", number + 1, e)))?;
+                events.push(event);
+            }
+        }
+        verify_chain(&events, Some(GENESIS_HASH))?;
+
+        store.restore(events);
+        store.journal = Some(Arc::new(Mutex::new(
+            OpenOptions::new().create(true).append(true).open(path)?,
+        )));
+        Ok(store)
+    }
+
+    /// Set maximum events to retain in memory
     pub fn with_max_events(mut self, max: usize) -> Self {
         self.max_events = max;
         self
     }
 
+    /// Take over loaded events as the current chain
+    fn restore(&mut self, events: Vec<AuditEvent>) {
+        let counter = events.iter().filter_map(event_counter).max().unwrap_or(0);
+        let head = events
+            .last()
+            .map_or_else(|| GENESIS_HASH.to_string(), |e| e.hash.clone());
+        let skip = events.len().saturating_sub(self.max_events);
+        self.events = Arc::new(RwLock::new(events.into_iter().skip(skip).collect()));
+        self.counter = Arc::new(RwLock::new(counter));
+        self.head = Arc::new(RwLock::new(head));
+    }
+
     /// Record an audit event
     pub fn record(
         &self,
@@ -85,15 +295,9 @@ impl AuditStore {
         entity_type: &str,
         entity_id: &str,
         details: serde_json::Value,
-    ) -> String {
-        let event_id = {
-            let mut counter = self.counter.write().unwrap();
-            *counter += 1;
-            format!("AUD-{:012}", *counter)
-        };
-
-        let event = AuditEvent {
-            event_id: event_id.clone(),
+    ) -> io::Result<String> {
+        self.store_event(AuditEvent {
+            event_id: String::new(),
             event_type,
             timestamp: Utc::now(),
             actor: actor.to_string(),
@@ -102,10 +306,9 @@ impl AuditStore {
             details,
             before: None,
             after: None,
-        };
-
-        self.store_event(event);
-        event_id
+            previous_hash: String::new(),
+            hash: String::new(),
+        })
     }
 
     /// Record an amendment with before/after state
@@ -116,15 +319,9 @@ impl AuditStore {
         entity_id: &str,
         before: serde_json::Value,
         after: serde_json::Value,
-    ) -> String {
-        let event_id = {
-            let mut counter = self.counter.write().unwrap();
-            *counter += 1;
-            format!("AUD-{:012}", *counter)
-        };
-
-        let event = AuditEvent {
-            event_id: event_id.clone(),
+    ) -> io::Result<String> {
+        self.store_event(AuditEvent {
+            event_id: String::new(),
             event_type: AuditEventType::TradeAmended,
             timestamp: Utc::now(),
             actor: actor.to_string(),
@@ -133,49 +330,128 @@ impl AuditStore {
             details: serde_json::json!({}),
             before: Some(before),
             after: Some(after),
-        };
+            previous_hash: String::new(),
+            hash: String::new(),
+        })
+    }
 
-        self.store_event(event);
-        event_id
+    /// Record a calculation run
+    ///
+    /// Recording the same run again (e.g. at start and on completion)
+    /// appends a new event; [`AuditStore::run`] returns the latest.
+    pub fn record_run(&self, run: &RunRecord) -> io::Result<String> {
+        let details = serde_json::to_value(run).map_err(io::Error::other)?;
+        self.record(
+            AuditEventType::ModelRun,
+            &run.actor,
+            "Run",
+            &run.run_id,
+            details,
+        )
     }
 
-    /// Store an event, evicting old events if necessary
-    fn store_event(&self, event: AuditEvent) {
+    /// Assign the next ID, chain the event and append it.
+    ///
+    /// With a journal the event is written and synced before it becomes
+    /// visible; on failure nothing is recorded.
+    fn store_event(&self, mut event: AuditEvent) -> io::Result<String> {
+        // The events lock serialises appends, so IDs and the chain follow
+        // journal order
         let mut events = self.events.write().unwrap();
+        let mut counter = self.counter.write().unwrap();
+        let mut head = self.head.write().unwrap();
+
+        event.event_id = format!("AUD-{:012}", *counter + 1);
+        event.previous_hash = head.clone();
+        event.hash = event.compute_hash();
+
+        if let Some(journal) = &self.journal {
+            let mut line = serde_json::to_string(&event).map_err(io::Error::other)?;
+            line.push('\n');
+            let mut file = journal.lock().unwrap();
+            file.write_all(line.as_bytes())?;
+            file.sync_data()?;
+        }
+
+        *counter += 1;
+        *head = event.hash.clone();
         if events.len() >= self.max_events {
             events.pop_front();
         }
+        let event_id = event.event_id.clone();
         events.push_back(event);
+        Ok(event_id)
     }
 
-    /// Get events for an entity
-    pub fn get_events_for_entity(&self, entity_type: &str, entity_id: &str) -> Vec<AuditEvent> {
+    /// Verify the hash chain of the events held in memory
+    pub fn verify(&self) -> io::Result<()> {
         let events = self.events.read().unwrap();
-        events
+        // Evicted events are only in the journal
+        let first = events.front().map(|e| e.previous_hash.as_str());
+        verify_chain(events.iter(), first)
+    }
+
+    /// Hash of the last recorded event, to anchor the chain externally
+    pub fn head_hash(&self) -> String {
+        self.head.read().unwrap().clone()
+    }
+
+    /// Events selected by a query, oldest first
+    pub fn query(&self, query: &AuditQuery) -> Vec<AuditEvent> {
+        let events = self.events.read().unwrap();
+        let mut matches: Vec<_> = events
             .iter()
-            .filter(|e| e.entity_type == entity_type && e.entity_id == entity_id)
+            .rev()
+            .filter(|e| query.matches(e))
+            .take(query.limit.unwrap_or(usize::MAX))
             .cloned()
-            .collect()
+            .collect();
+        matches.reverse();
+        matches
+    }
+
+    /// Latest record of each run selected by a query, by first recording
+    pub fn runs(&self, query: &AuditQuery) -> Vec<RunRecord> {
+        let events = AuditQuery {
+            event_type: Some(AuditEventType::ModelRun),
+            limit: None,
+            ..query.clone()
+        };
+        let mut runs: Vec<RunRecord> = Vec::new();
+        for event in self.query(&events) {
+            let Ok(run) = serde_json::from_value::<RunRecord>(event.details) else {
+                continue;
+            };
+            match runs.iter_mut().find(|r| r.run_id == run.run_id) {
+                Some(existing) => *existing = run,
+                None => runs.push(run),
+            }
+        }
+        if let Some(limit) = query.limit.filter(|&limit| limit < runs.len()) {
+            runs.drain(..runs.len() - limit);
+        }
+        runs
+    }
+
+    /// Latest record of a run
+    pub fn run(&self, run_id: &str) -> Option<RunRecord> {
+        self.runs(&AuditQuery::new().with_entity("Run", run_id))
+            .pop()
+    }
+
+    /// Get events for an entity
+    pub fn get_events_for_entity(&self, entity_type: &str, entity_id: &str) -> Vec<AuditEvent> {
+        self.query(&AuditQuery::new().with_entity(entity_type, entity_id))
     }
 
     /// Get events by type
     pub fn get_events_by_type(&self, event_type: AuditEventType) -> Vec<AuditEvent> {
-        let events = self.events.read().unwrap();
-        events
-            .iter()
-            .filter(|e| e.event_type == event_type)
-            .cloned()
-            .collect()
+        self.query(&AuditQuery::new().with_event_type(event_type))
     }
 
     /// Get events in a time range
     pub fn get_events_in_range(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<AuditEvent> {
-        let events = self.events.read().unwrap();
-        events
-            .iter()
-            .filter(|e| e.timestamp >= from && e.timestamp <= to)
-            .cloned()
-            .collect()
+        self.query(&AuditQuery::new().with_time_range(from, to))
     }
 
     /// Get recent events
@@ -197,6 +473,48 @@ impl AuditStore {
         serde_json::to_string_pretty(&events_vec).unwrap_or_default()
     }
 
+    /// Export the events selected by a query as JSON lines, with hashes,
+    /// so the extract can be verified independently
+    pub fn export_jsonl(&self, query: &AuditQuery) -> String {
+        self.query(query)
+            .iter()
+            .filter_map(|e| serde_json::to_string(e).ok())
+            .fold(String::new(), |mut out, line| {
+                out.push_str(&line);
+                out.push('\n');
+                out
+            })
+    }
+
+    /// Export the runs selected by a query as CSV
+    pub fn export_runs_csv(&self, query: &AuditQuery) -> String {
+        let mut out = String::from(
+            "run_id,run_type,actor,started_at,completed_at,status,inputs_hash,config_hash,results_hash\n",
+        );
+        for run in self.runs(query) {
+            let status = match &run.status {
+                RunStatus::Running => "running".to_string(),
+                RunStatus::Succeeded => "succeeded".to_string(),
+                RunStatus::Failed(message) => format!("failed: {}", message),
+            };
+            let fields = [
+                run.run_id,
+                run.run_type,
+                run.actor,
+                run.started_at.to_rfc3339(),
+                run.completed_at.map(|t| t.to_rfc3339()).unwrap_or_default(),
+                status,
+                run.inputs_hash,
+                run.config_hash,
+                run.results_hash.unwrap_or_default(),
+            ];
+            let row: Vec<_> = fields.iter().map(|f| csv_field(f)).collect();
+            out.push_str(&row.join(","));
+            out.push('\n');
+        }
+        out
+    }
+
     /// Save audit trail to a JSON file
     pub fn save_to_file(&self, path: &Path) -> io::Result<()> {
         let events = self.events.read().unwrap();
@@ -210,7 +528,7 @@ impl AuditStore {
         Ok(())
     }
 
-    /// Load audit trail from a JSON file
+    /// Load audit trail from a JSON file, verifying its hash chain
     pub fn load_from_file(path: &Path) -> io::Result<Self> {
         let file = std::fs::File::open(path)?;
         let reader = BufReader::new(file);
@@ -218,23 +536,12 @@ impl AuditStore {
         let events_vec: Vec<AuditEvent> = serde_json::from_reader(reader)
             .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
 
-        // Find the highest counter value from loaded events
-        let max_counter = events_vec
-            .iter()
-            .filter_map(|e| {
-                e.event_id
-                    .strip_prefix("AUD-")
-                    .and_then(|s| s.parse::<u64>().ok())
-            })
-            .max()
-            .unwrap_or(0);
-
-        let store = Self {
-            events: Arc::new(RwLock::new(VecDeque::from(events_vec))),
-            max_events: 100000,
-            counter: Arc::new(RwLock::new(max_counter)),
-        };
+        // A saved window may start mid-chain
+        let first = events_vec.first().map(|e| e.previous_hash.as_str());
+        verify_chain(&events_vec, first)?;
 
+        let mut store = Self::new();
+        store.restore(events_vec);
         Ok(store)
     }
 
@@ -290,6 +597,15 @@ impl AuditStore {
     }
 }
 
+/// Quote a CSV field when it contains a delimiter, quote or newline
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
 impl Default for AuditStore {
     fn default() -> Self {
         Self::new()
@@ -303,13 +619,15 @@ mod tests {
     #[test]
     fn test_audit_store_record() {
         let store = AuditStore::new();
-        let event_id = store.record(
-            AuditEventType::TradeBooked,
-            "SYSTEM",
-            "Trade",
-            "T001",
-            serde_json::json!({"notional": 1000000}),
-        );
+        let event_id = store
+            .record(
+                AuditEventType::TradeBooked,
+                "SYSTEM",
+                "Trade",
+                "T001",
+                serde_json::json!({"notional": 1000000}),
+            )
+            .unwrap();
         assert!(event_id.starts_with("AUD-"));
         assert_eq!(store.count(), 1);
     }
@@ -321,27 +639,33 @@ mod tests {
 
         // Create and populate store
         let store = AuditStore::new();
-        store.record(
-            AuditEventType::TradeBooked,
-            "USER1",
-            "Trade",
-            "T001",
-            serde_json::json!({"notional": 1000000}),
-        );
-        store.record(
-            AuditEventType::Valuation,
-            "SYSTEM",
-            "Trade",
-            "T001",
-            serde_json::json!({"pv": 50000}),
-        );
-        store.record(
-            AuditEventType::ReportSubmitted,
-            "USER2",
-            "Report",
-            "R001",
-            serde_json::json!({"type": "SaCcr"}),
-        );
+        store
+            .record(
+                AuditEventType::TradeBooked,
+                "USER1",
+                "Trade",
+                "T001",
+                serde_json::json!({"notional": 1000000}),
+            )
+            .unwrap();
+        store
+            .record(
+                AuditEventType::Valuation,
+                "SYSTEM",
+                "Trade",
+                "T001",
+                serde_json::json!({"pv": 50000}),
+            )
+            .unwrap();
+        store
+            .record(
+                AuditEventType::ReportSubmitted,
+                "USER2",
+                "Report",
+                "R001",
+                serde_json::json!({"type": "SaCcr"}),
+            )
+            .unwrap();
 
         // Save to file
         store.save_to_file(&file_path).expect("Failed to save");
@@ -351,6 +675,7 @@ mod tests {
 
         // Verify loaded data
         assert_eq!(loaded_store.count(), 3);
+        assert_eq!(loaded_store.head_hash(), store.head_hash());
 
         let trade_events = loaded_store.get_events_for_entity("Trade", "T001");
         assert_eq!(trade_events.len(), 2);
@@ -371,13 +696,15 @@ mod tests {
         // Create store and add events
         let store = AuditStore::new();
         for i in 0..5 {
-            store.record(
-                AuditEventType::TradeBooked,
-                "SYSTEM",
-                "Trade",
-                &format!("T{:03}", i),
-                serde_json::json!({}),
-            );
+            store
+                .record(
+                    AuditEventType::TradeBooked,
+                    "SYSTEM",
+                    "Trade",
+                    &format!("T{:03}", i),
+                    serde_json::json!({}),
+                )
+                .unwrap();
         }
 
         // Save and reload
@@ -385,13 +712,15 @@ mod tests {
         let loaded_store = AuditStore::load_from_file(&file_path).expect("Failed to load");
 
         // New event should continue from last counter
-        let new_event_id = loaded_store.record(
-            AuditEventType::TradeBooked,
-            "SYSTEM",
-            "Trade",
-            "T005",
-            serde_json::json!({}),
-        );
+        let new_event_id = loaded_store
+            .record(
+                AuditEventType::TradeBooked,
+                "SYSTEM",
+                "Trade",
+                "T005",
+                serde_json::json!({}),
+            )
+            .unwrap();
 
         // The new event should have ID "AUD-000000000006" (counter 6)
         assert!(new_event_id.starts_with("AUD-"));
@@ -400,6 +729,7 @@ mod tests {
         // Verify counter continuity
         let events = loaded_store.get_recent_events(1);
         assert_eq!(events[0].event_id, "AUD-000000000006");
+        loaded_store.verify().unwrap();
 
         // Cleanup
         let _ = std::fs::remove_file(&file_path);
@@ -415,32 +745,38 @@ mod tests {
 
         // Create store and add initial events
         let store = AuditStore::new();
-        let _id1 = store.record(
-            AuditEventType::TradeBooked,
-            "USER1",
-            "Trade",
-            "T001",
-            serde_json::json!({}),
-        );
-        let id2 = store.record(
-            AuditEventType::Valuation,
-            "SYSTEM",
-            "Trade",
-            "T001",
-            serde_json::json!({}),
-        );
+        let _id1 = store
+            .record(
+                AuditEventType::TradeBooked,
+                "USER1",
+                "Trade",
+                "T001",
+                serde_json::json!({}),
+            )
+            .unwrap();
+        let id2 = store
+            .record(
+                AuditEventType::Valuation,
+                "SYSTEM",
+                "Trade",
+                "T001",
+                serde_json::json!({}),
+            )
+            .unwrap();
 
         // Save initial events
         store.save_to_file(&file_path).expect("Failed to save");
 
         // Add more events
-        let _id3 = store.record(
-            AuditEventType::ReportGenerated,
-            "SYSTEM",
-            "Report",
-            "R001",
-            serde_json::json!({}),
-        );
+        let _id3 = store
+            .record(
+                AuditEventType::ReportGenerated,
+                "SYSTEM",
+                "Report",
+                "R001",
+                serde_json::json!({}),
+            )
+            .unwrap();
 
         // Append only new events (after id2)
         let appended = store
@@ -461,4 +797,135 @@ mod tests {
         let result = AuditStore::load_from_file(Path::new("/nonexistent/path/audit.json"));
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_journal_is_append_only_and_tamper_evident() {
+        let path =
+            std::env::temp_dir().join(format!("test_audit_journal_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let store = AuditStore::open(&path).unwrap();
+        store
+            .record(
+                AuditEventType::TradeBooked,
+                "USER1",
+                "Trade",
+                "T001",
+                serde_json::json!({"notional": 1000000}),
+            )
+            .unwrap();
+        store
+            .record_amendment(
+                "USER2",
+                "Trade",
+                "T001",
+                serde_json::json!({"notional": 1000000}),
+                serde_json::json!({"notional": 2000000}),
+            )
+            .unwrap();
+        drop(store);
+
+        // Reopening continues the chain
+        let store = AuditStore::open(&path).unwrap();
+        assert_eq!(store.count(), 2);
+        store
+            .record(
+                AuditEventType::Valuation,
+                "SYSTEM",
+                "Trade",
+                "T001",
+                serde_json::json!({"pv": 0.1 + 0.2, "dv01": 1e-7 / 3.0}),
+            )
+            .unwrap();
+        store.verify().unwrap();
+        drop(store);
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 3);
+
+        // Rewriting history is detected
+        let tampered = std::fs::read_to_string(&path)
+            .unwrap()
+            .replace("2000000", "3000000");
+        std::fs::write(&path, tampered).unwrap();
+        let err = AuditStore::open(&path).err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        // So is dropping an event
+        let store = AuditStore::new();
+        for i in 0..3 {
+            store
+                .record(
+                    AuditEventType::UserAction,
+                    "U",
+                    "Session",
+                    &i.to_string(),
+                    serde_json::json!({}),
+                )
+                .unwrap();
+        }
+        let lines: Vec<_> = store
+            .export_jsonl(&AuditQuery::new())
+            .lines()
+            .enumerate()
+            .filter(|(i, _)| *i != 1)
+            .map(|(_, line)| format!("{}\n", line))
+            .collect();
+        std::fs::write(&path, lines.concat()).unwrap();
+        assert!(AuditStore::open(&path).is_err());
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_run_records_query_and_export() {
+        let store = AuditStore::new();
+        let config = serde_json::json!({"paths": 10000});
+        let run = RunRecord::start("eod-1", "eod_xva", "batch", &[1.0, 2.0], &config);
+        store.record_run(&run).unwrap();
+        store
+            .record(
+                AuditEventType::Valuation,
+                "batch",
+                "Trade",
+                "T001",
+                serde_json::json!({}),
+            )
+            .unwrap();
+        store.record_run(&run.clone().succeed(&[12.5])).unwrap();
+        let other = RunRecord::start("adhoc-1", "pricing", "alice", "inputs", &config)
+            .fail("missing curve, EUR");
+        store.record_run(&other).unwrap();
+
+        let runs = store.runs(&AuditQuery::new());
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].run_id, "eod-1");
+        assert_eq!(runs[0].status, RunStatus::Succeeded);
+        assert_eq!(
+            store.run("eod-1").unwrap().results_hash,
+            runs[0].results_hash
+        );
+        assert!(store.run("eod-2").is_none());
+
+        let alice = store.runs(&AuditQuery::new().with_actor("alice"));
+        assert_eq!(alice.len(), 1);
+        assert_eq!(
+            store.runs(&AuditQuery::new().with_limit(1))[0].run_id,
+            "adhoc-1"
+        );
+        assert_eq!(
+            store
+                .query(
+                    &AuditQuery::new()
+                        .with_event_type(AuditEventType::ModelRun)
+                        .with_limit(2)
+                )
+                .len(),
+            2
+        );
+
+        let csv = store.export_runs_csv(&AuditQuery::new());
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("run_id,run_type,actor"));
+        assert!(lines[2].contains("\"failed: missing curve, EUR\""));
+    }
 }
@@ -1,13 +1,20 @@
 //! Regulatory reporting systems.
 //!
 //! This module provides mock implementations of regulatory reporting
-//! APIs and audit trail storage.
+//! APIs, an append-only, hash-chained audit trail ([`AuditStore`]) with
+//! immutable [`RunRecord`]s for model governance, and a
+//! [`trade_reporting`] formatter for EMIR Refit / CFTC-style trade and
+//! valuation reports.
 
 mod audit_store;
 mod regulator_api;
+mod run_record;
+pub mod trade_reporting;
 
-pub use audit_store::{AuditEvent, AuditEventType, AuditStore};
+pub use audit_store::{AuditEvent, AuditEventType, AuditQuery, AuditStore, GENESIS_HASH};
 pub use regulator_api::{RegulatorApi, SubmissionLog, SubmissionRequest, SubmissionResponse};
+pub use run_record::{RunRecord, RunStatus};
+pub use trade_reporting::{Regime, TradeReport, TradeReporter, ValuationResults};
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -27,6 +34,10 @@ pub enum ReportType {
     Lcr,
     /// Net Stable Funding Ratio
     Nsfr,
+    /// EMIR Refit trade and valuation report
+    EmirTrade,
+    /// CFTC swap data report
+    CftcSwap,
 }
 
 /// A regulatory report submission
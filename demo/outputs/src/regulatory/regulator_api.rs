@@ -167,6 +167,11 @@ impl RegulatorApi {
                     errors.push("Missing required field: cva_capital".to_string());
                 }
             }
+            ReportType::EmirTrade | ReportType::CftcSwap => {
+                if !request.data.get("records").is_some_and(|r| r.is_array()) {
+                    errors.push("Missing required field: records".to_string());
+                }
+            }
             _ => {}
         }
 
@@ -0,0 +1,157 @@
+//! Immutable records of calculation runs for model governance.
+//!
+//! A [`RunRecord`] captures who ran what: the run type and actor, a
+//! fingerprint of the inputs, the configuration itself and its
+//! fingerprint, a fingerprint of the results and the start and end
+//! timestamps. Fingerprints are [`fingerprint`] hashes rendered as 16 hex
+//! digits, so a rerun on the same inputs can be matched to the original.
+//! Once recorded in an [`AuditStore`](super::AuditStore) the record is
+//! covered by the store's hash chain.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use pricer_core::types::provenance::{fingerprint, RunMetadata};
+use serde::{Deserialize, Serialize};
+
+/// Outcome of a run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RunStatus {
+    /// Run started and has not completed
+    Running,
+    /// Run completed and produced results
+    Succeeded,
+    /// Run failed with an error message
+    Failed(String),
+}
+
+/// Record of one calculation run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunRecord {
+    /// Run ID, e.g. `eod-2024-03-01`
+    pub run_id: String,
+    /// Kind of run (`eod_xva`, `pricing`, `calibration`, ...)
+    pub run_type: String,
+    /// User or system that started the run
+    pub actor: String,
+    /// Start timestamp
+    pub started_at: DateTime<Utc>,
+    /// Completion timestamp
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Fingerprint of the inputs (trades, market data snapshot)
+    pub inputs_hash: String,
+    /// Calculation configuration
+    pub config: serde_json::Value,
+    /// Fingerprint of the configuration
+    pub config_hash: String,
+    /// Fingerprint of the results
+    pub results_hash: Option<String>,
+    /// Outcome
+    pub status: RunStatus,
+    /// Engine versions, seed and git commit from the run's [`RunMetadata`]
+    #[serde(default)]
+    pub provenance: BTreeMap<String, String>,
+}
+
+fn hex_fingerprint<T: fmt::Debug + ?Sized>(value: &T) -> String {
+    format!("{:016x}", fingerprint(value))
+}
+
+impl RunRecord {
+    /// Start a run now, fingerprinting its inputs and configuration.
+    ///
+    /// The configuration is fingerprinted through its JSON form, so the
+    /// hash does not depend on the Rust type it was held in.
+    pub fn start<I, C>(
+        run_id: impl Into<String>,
+        run_type: impl Into<String>,
+        actor: impl Into<String>,
+        inputs: &I,
+        config: &C,
+    ) -> Self
+    where
+        I: fmt::Debug + ?Sized,
+        C: Serialize + ?Sized,
+    {
+        let config = serde_json::to_value(config).unwrap_or(serde_json::Value::Null);
+        Self {
+            run_id: run_id.into(),
+            run_type: run_type.into(),
+            actor: actor.into(),
+            started_at: Utc::now(),
+            completed_at: None,
+            inputs_hash: hex_fingerprint(inputs),
+            config_hash: hex_fingerprint(&config.to_string()),
+            config,
+            results_hash: None,
+            status: RunStatus::Running,
+            provenance: BTreeMap::new(),
+        }
+    }
+
+    /// Attach the provenance of the run.
+    pub fn with_provenance(mut self, metadata: &RunMetadata) -> Self {
+        self.provenance = metadata.entries().into_iter().collect();
+        self
+    }
+
+    /// Complete the run now, fingerprinting its results.
+    pub fn succeed<R: fmt::Debug + ?Sized>(mut self, results: &R) -> Self {
+        self.completed_at = Some(Utc::now());
+        self.results_hash = Some(hex_fingerprint(results));
+        self.status = RunStatus::Succeeded;
+        self
+    }
+
+    /// Complete the run now as failed.
+    pub fn fail(mut self, message: impl Into<String>) -> Self {
+        self.completed_at = Some(Utc::now());
+        self.status = RunStatus::Failed(message.into());
+        self
+    }
+
+    /// Whether another run used the same inputs and configuration.
+    pub fn same_inputs(&self, other: &Self) -> bool {
+        self.inputs_hash == other.inputs_hash && self.config_hash == other.config_hash
+    }
+
+    /// Duration of a completed run.
+    pub fn duration(&self) -> Option<chrono::Duration> {
+        self.completed_at.map(|end| end - self.started_at)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_record_fingerprints() {
+        let trades = vec![("T001", 1_000_000.0), ("T002", -500_000.0)];
+        let config = serde_json::json!({"paths": 10_000, "seed": 42});
+
+        let run = RunRecord::start("eod-1", "eod_xva", "batch", &trades, &config)
+            .with_provenance(&RunMetadata::default().with_seed(42))
+            .succeed(&[12.5, 3.25]);
+        assert_eq!(run.status, RunStatus::Succeeded);
+        assert_eq!(run.inputs_hash.len(), 16);
+        assert_eq!(run.provenance["seed"], "42");
+        assert!(run.duration().unwrap() >= chrono::Duration::zero());
+
+        let rerun = RunRecord::start("eod-2", "eod_xva", "batch", &trades, &config);
+        assert!(run.same_inputs(&rerun));
+        assert_eq!(rerun.status, RunStatus::Running);
+
+        let changed = RunRecord::start(
+            "eod-3",
+            "eod_xva",
+            "batch",
+            &trades,
+            &serde_json::json!({"paths": 20_000, "seed": 42}),
+        )
+        .fail("market data missing");
+        assert!(!run.same_inputs(&changed));
+        assert!(matches!(changed.status, RunStatus::Failed(_)));
+    }
+}
@@ -0,0 +1,102 @@
+//! Flat CSV rendering, one row per record.
+//!
+//! Column headers are the field names of the regime's reporting tables.
+
+use super::{Regime, TradeReportRecord};
+
+const EMIR_HEADERS: [&str; 17] = [
+    "UTI",
+    "Action type",
+    "Event date",
+    "Counterparty 1 (Reporting counterparty)",
+    "Counterparty 2",
+    "Asset class",
+    "Contract type",
+    "Notional amount of leg 1",
+    "Notional currency 1",
+    "Effective date",
+    "Expiration date",
+    "Cleared",
+    "Collateralisation category",
+    "Valuation amount",
+    "Valuation currency",
+    "Valuation timestamp",
+    "Delta",
+];
+
+const CFTC_HEADERS: [&str; 17] = [
+    "Unique transaction identifier",
+    "Action type",
+    "Event timestamp",
+    "Counterparty 1",
+    "Counterparty 2",
+    "Asset class",
+    "Product type",
+    "Notional amount-Leg 1",
+    "Notional currency-Leg 1",
+    "Effective date",
+    "Expiration date",
+    "Cleared",
+    "Collateralisation category",
+    "Valuation amount",
+    "Valuation currency",
+    "Valuation timestamp",
+    "Delta",
+];
+
+/// Render records with a header row.
+pub(super) fn render(regime: Regime, records: &[&TradeReportRecord]) -> String {
+    let headers = match regime {
+        Regime::EmirRefit => EMIR_HEADERS,
+        Regime::Cftc => CFTC_HEADERS,
+    };
+    let mut out = headers.map(escape).join(",");
+    out.push('\n');
+
+    for r in records {
+        let valuation = r.valuation.as_ref();
+        let fields = [
+            r.uti.clone(),
+            r.action.code(regime).to_string(),
+            r.event_date.to_string(),
+            r.reporting_lei.clone(),
+            r.other_counterparty_lei.clone().unwrap_or_default(),
+            r.asset_class.code(regime).to_string(),
+            r.contract_type.code().to_string(),
+            format!("{:.2}", r.notional),
+            r.notional_currency.clone(),
+            r.effective_date.to_string(),
+            r.maturity_date.to_string(),
+            if r.cleared { "Y" } else { "N" }.to_string(),
+            r.collateralisation.code().to_string(),
+            valuation
+                .map(|v| format!("{:.2}", v.amount))
+                .unwrap_or_default(),
+            valuation.map(|v| v.currency.clone()).unwrap_or_default(),
+            valuation
+                .map(|v| v.timestamp.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+                .unwrap_or_default(),
+            valuation
+                .and_then(|v| v.delta)
+                .map(|d| format!("{:.5}", d))
+                .unwrap_or_default(),
+        ];
+        out.push_str(
+            &fields
+                .iter()
+                .map(|f| escape(f))
+                .collect::<Vec<_>>()
+                .join(","),
+        );
+        out.push('\n');
+    }
+    out
+}
+
+fn escape(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -0,0 +1,147 @@
+//! Legal entity and transaction identifiers.
+//!
+//! LEIs follow ISO 17442: 18 alphanumeric characters followed by two
+//! ISO 7064 MOD 97-10 check digits. UTIs follow ISO 23897: up to 52
+//! upper-case alphanumeric characters, starting with the LEI of the
+//! generating entity.
+
+/// Length of a Legal Entity Identifier.
+pub const LEI_LENGTH: usize = 20;
+
+/// Maximum length of a Unique Transaction Identifier.
+pub const UTI_MAX_LENGTH: usize = 52;
+
+/// Check an LEI's format and check digits.
+///
+/// # Errors
+///
+/// Returns a description of the first problem found.
+pub fn validate_lei(lei: &str) -> Result<(), String> {
+    if lei.len() != LEI_LENGTH {
+        return Err(format!(
+            "LEI must be {} characters, got {}",
+            LEI_LENGTH,
+            lei.len()
+        ));
+    }
+    if !is_upper_alphanumeric(lei) {
+        return Err("LEI must contain only A-Z and 0-9".to_string());
+    }
+    if !lei[LEI_LENGTH - 2..].bytes().all(|b| b.is_ascii_digit()) {
+        return Err("LEI check digits must be numeric".to_string());
+    }
+    if mod97(lei) != 1 {
+        return Err("LEI check digits do not match".to_string());
+    }
+    Ok(())
+}
+
+/// Complete an 18-character LEI prefix with its check digits.
+///
+/// Returns `None` if the prefix is not 18 upper-case alphanumeric
+/// characters.
+pub fn lei_from_prefix(prefix: &str) -> Option<String> {
+    if prefix.len() != LEI_LENGTH - 2 || !is_upper_alphanumeric(prefix) {
+        return None;
+    }
+    let check = 98 - mod97(&format!("{}00", prefix));
+    Some(format!("{}{:02}", prefix, check))
+}
+
+/// Build a UTI from the generating entity's LEI and a trade identifier.
+///
+/// Characters outside A-Z and 0-9 are dropped from the trade identifier,
+/// letters are upper-cased and the result is cut to
+/// [`UTI_MAX_LENGTH`] characters.
+pub fn generate_uti(generating_lei: &str, trade_id: &str) -> String {
+    let suffix: String = trade_id
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    let mut uti = format!("{}{}", generating_lei, suffix);
+    uti.truncate(UTI_MAX_LENGTH);
+    uti
+}
+
+/// Check a UTI's length, character set and LEI prefix.
+///
+/// # Errors
+///
+/// Returns a description of the first problem found.
+pub fn validate_uti(uti: &str) -> Result<(), String> {
+    if uti.len() <= LEI_LENGTH || uti.len() > UTI_MAX_LENGTH {
+        return Err(format!(
+            "UTI must be {} to {} characters, got {}",
+            LEI_LENGTH + 1,
+            UTI_MAX_LENGTH,
+            uti.len()
+        ));
+    }
+    if !is_upper_alphanumeric(uti) {
+        return Err("UTI must contain only A-Z and 0-9".to_string());
+    }
+    validate_lei(&uti[..LEI_LENGTH]).map_err(|e| format!("UTI prefix is not a valid LEI: {}", e))
+}
+
+fn is_upper_alphanumeric(s: &str) -> bool {
+    s.bytes()
+        .all(|b| b.is_ascii_digit() || b.is_ascii_uppercase())
+}
+
+/// ISO 7064 MOD 97-10 remainder, with letters expanded to 10..=35.
+///
+/// Callers check the character set first.
+fn mod97(s: &str) -> u32 {
+    s.chars().fold(0, |acc, c| {
+        let value = c.to_digit(36).unwrap_or(0);
+        if value >= 10 {
+            (acc * 100 + value) % 97
+        } else {
+            (acc * 10 + value) % 97
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_published_lei_is_valid() {
+        assert_eq!(validate_lei("7LTWFZYICNSX8D621K86"), Ok(()));
+        assert_eq!(validate_lei("5493001KJTIIGC8Y1R12"), Ok(()));
+    }
+
+    #[test]
+    fn test_lei_errors() {
+        assert!(validate_lei("7LTWFZYICNSX8D621K87")
+            .unwrap_err()
+            .contains("check digits"));
+        assert!(validate_lei("7LTWFZYICNSX8D621K8").is_err());
+        assert!(validate_lei("7ltwfzyicnsx8d621k86").is_err());
+    }
+
+    #[test]
+    fn test_lei_from_prefix_round_trips() {
+        assert_eq!(
+            lei_from_prefix("7LTWFZYICNSX8D621K").as_deref(),
+            Some("7LTWFZYICNSX8D621K86")
+        );
+        let lei = lei_from_prefix("NEUTRYXBANK0000001").unwrap();
+        assert_eq!(validate_lei(&lei), Ok(()));
+        assert_eq!(lei_from_prefix("SHORT"), None);
+    }
+
+    #[test]
+    fn test_generate_uti() {
+        let lei = "7LTWFZYICNSX8D621K86";
+        let uti = generate_uti(lei, "irs-usd/5y_001");
+        assert_eq!(uti, "7LTWFZYICNSX8D621K86IRSUSD5Y001");
+        assert_eq!(validate_uti(&uti), Ok(()));
+
+        let long = generate_uti(lei, &"X".repeat(60));
+        assert_eq!(long.len(), UTI_MAX_LENGTH);
+        assert!(validate_uti(lei).is_err());
+    }
+}
@@ -0,0 +1,494 @@
+//! EMIR Refit / CFTC-style trade and valuation reporting.
+//!
+//! [`TradeReporter`] turns a [`Portfolio`] and its latest
+//! [`ValuationResults`] into one [`TradeReportRecord`] per trade, checks
+//! every field against the regime's rules and renders the records that
+//! pass as ISO 20022 `auth.030` XML or CSV.
+//!
+//! The portfolio carries no trade dates, clearing flags or LEIs, so:
+//!
+//! - the effective date defaults to the valuation date and the maturity
+//!   date is derived from the instrument expiry;
+//! - a netting set counts as cleared when its collateral agreement uses
+//!   the cleared margin period of risk;
+//! - LEIs are supplied per counterparty with
+//!   [`TradeReporter::with_counterparty_lei`].
+//!
+//! # Example
+//!
+//! ```ignore
+//! let report = TradeReporter::new(Regime::EmirRefit, bank_lei, reporting_time)
+//!     .with_counterparty_lei("CP001", "5493001KJTIIGC8Y1R12")
+//!     .report(&portfolio, &valuations);
+//! for issue in &report.issues {
+//!     eprintln!("{}", issue);
+//! }
+//! std::fs::write("emir.xml", report.to_xml())?;
+//! ```
+
+mod csv;
+mod identifiers;
+mod validation;
+mod xml;
+
+pub use identifiers::{
+    generate_uti, lei_from_prefix, validate_lei, validate_uti, LEI_LENGTH, UTI_MAX_LENGTH,
+};
+pub use validation::{validate_record, IssueSeverity, ValidationIssue};
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use pricer_risk::portfolio::{CollateralAgreement, NettingSet, Portfolio, Trade, TradeId};
+use serde::{Deserialize, Serialize};
+
+use super::{ReportType, SubmissionRequest};
+
+/// Days per year used to turn instrument expiries into maturity dates.
+const DAYS_PER_YEAR: f64 = 365.25;
+
+/// Reporting regime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Regime {
+    /// EU EMIR Refit (ESMA RTS/ITS on reporting)
+    EmirRefit,
+    /// US CFTC Part 45 swap data reporting
+    Cftc,
+}
+
+impl Regime {
+    /// Regulatory report type used for submissions.
+    pub fn report_type(&self) -> ReportType {
+        match self {
+            Regime::EmirRefit => ReportType::EmirTrade,
+            Regime::Cftc => ReportType::CftcSwap,
+        }
+    }
+}
+
+/// Lifecycle event being reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ActionType {
+    /// New trade
+    New,
+    /// Modification of trade terms
+    Modify,
+    /// Daily valuation update
+    Valuation,
+    /// Early termination
+    Terminate,
+}
+
+impl ActionType {
+    /// Action code in the regime's reporting standard.
+    pub fn code(&self, regime: Regime) -> &'static str {
+        match (self, regime) {
+            (ActionType::New, _) => "NEWT",
+            (ActionType::Modify, _) => "MODI",
+            (ActionType::Valuation, _) => "VALU",
+            (ActionType::Terminate, Regime::EmirRefit) => "ETRM",
+            (ActionType::Terminate, Regime::Cftc) => "TERM",
+        }
+    }
+}
+
+/// Asset class of the contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AssetClass {
+    /// Interest rates
+    InterestRate,
+    /// Equity
+    Equity,
+    /// Foreign exchange
+    ForeignExchange,
+    /// Credit
+    Credit,
+    /// Commodity
+    Commodity,
+}
+
+impl AssetClass {
+    /// Asset class code in the regime's reporting standard.
+    pub fn code(&self, regime: Regime) -> &'static str {
+        match (self, regime) {
+            (AssetClass::InterestRate, Regime::EmirRefit) => "INTR",
+            (AssetClass::Equity, Regime::EmirRefit) => "EQUI",
+            (AssetClass::ForeignExchange, Regime::EmirRefit) => "CURR",
+            (AssetClass::Credit, Regime::EmirRefit) => "CRDT",
+            (AssetClass::Commodity, Regime::EmirRefit) => "COMM",
+            (AssetClass::InterestRate, Regime::Cftc) => "IR",
+            (AssetClass::Equity, Regime::Cftc) => "EQ",
+            (AssetClass::ForeignExchange, Regime::Cftc) => "FX",
+            (AssetClass::Credit, Regime::Cftc) => "CR",
+            (AssetClass::Commodity, Regime::Cftc) => "CO",
+        }
+    }
+}
+
+/// Contract type (ISO 20022 `FinancialInstrumentContractType2Code`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ContractType {
+    /// Swap
+    Swap,
+    /// Option
+    Option,
+    /// Forward
+    Forward,
+}
+
+impl ContractType {
+    /// Contract type code.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ContractType::Swap => "SWAP",
+            ContractType::Option => "OPTN",
+            ContractType::Forward => "FORW",
+        }
+    }
+}
+
+/// Collateralisation category of the netting set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Collateralisation {
+    /// No collateral agreement
+    Uncollateralised,
+    /// Variation margin only
+    Partial,
+    /// Variation margin and initial margin
+    Full,
+}
+
+impl Collateralisation {
+    /// Category code.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Collateralisation::Uncollateralised => "UNCL",
+            Collateralisation::Partial => "PRCL",
+            Collateralisation::Full => "FLCL",
+        }
+    }
+
+    fn of(collateral: Option<&CollateralAgreement>) -> Self {
+        match collateral {
+            None => Collateralisation::Uncollateralised,
+            Some(csa) if csa.independent_amount() > 0.0 => Collateralisation::Full,
+            Some(_) => Collateralisation::Partial,
+        }
+    }
+}
+
+/// Mark-to-market valuation of one trade.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Valuation {
+    /// Contract value from the reporting counterparty's perspective
+    pub amount: f64,
+    /// Valuation currency (ISO 4217)
+    pub currency: String,
+    /// Valuation timestamp
+    pub timestamp: DateTime<Utc>,
+    /// Option delta, where applicable
+    pub delta: Option<f64>,
+}
+
+/// Trade values from one valuation run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValuationResults {
+    /// Valuation timestamp
+    pub as_of: DateTime<Utc>,
+    /// Present value by trade
+    pub values: HashMap<TradeId, f64>,
+    /// Delta by trade, for options
+    pub deltas: HashMap<TradeId, f64>,
+}
+
+impl ValuationResults {
+    /// Create empty results.
+    pub fn new(as_of: DateTime<Utc>) -> Self {
+        Self {
+            as_of,
+            values: HashMap::new(),
+            deltas: HashMap::new(),
+        }
+    }
+
+    /// Wrap the output of [`Portfolio::price_all_trades`].
+    pub fn from_prices(as_of: DateTime<Utc>, values: HashMap<TradeId, f64>) -> Self {
+        Self {
+            as_of,
+            values,
+            deltas: HashMap::new(),
+        }
+    }
+
+    /// Add a trade value.
+    pub fn with_value(mut self, trade_id: impl Into<TradeId>, value: f64) -> Self {
+        self.values.insert(trade_id.into(), value);
+        self
+    }
+
+    /// Add a trade delta.
+    pub fn with_delta(mut self, trade_id: impl Into<TradeId>, delta: f64) -> Self {
+        self.deltas.insert(trade_id.into(), delta);
+        self
+    }
+
+    /// The most recent of several valuation runs.
+    pub fn latest<'a>(runs: impl IntoIterator<Item = &'a Self>) -> Option<&'a Self> {
+        runs.into_iter().max_by_key(|r| r.as_of)
+    }
+}
+
+/// One trade's entry in a trade report.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TradeReportRecord {
+    /// Unique transaction identifier
+    pub uti: String,
+    /// Internal trade identifier
+    pub trade_id: String,
+    /// Lifecycle event
+    pub action: ActionType,
+    /// Date of the reported event
+    pub event_date: NaiveDate,
+    /// LEI of the reporting counterparty
+    pub reporting_lei: String,
+    /// LEI of the other counterparty, if known
+    pub other_counterparty_lei: Option<String>,
+    /// Internal identifier of the other counterparty
+    pub other_counterparty_id: String,
+    /// Asset class
+    pub asset_class: AssetClass,
+    /// Contract type
+    pub contract_type: ContractType,
+    /// Notional amount
+    pub notional: f64,
+    /// Notional currency (ISO 4217)
+    pub notional_currency: String,
+    /// Effective date
+    pub effective_date: NaiveDate,
+    /// Maturity date
+    pub maturity_date: NaiveDate,
+    /// Whether the trade is centrally cleared
+    pub cleared: bool,
+    /// Collateralisation category
+    pub collateralisation: Collateralisation,
+    /// Netting set the trade belongs to
+    pub netting_set_id: String,
+    /// Latest valuation, if any
+    pub valuation: Option<Valuation>,
+}
+
+/// Builds trade reports for one reporting counterparty.
+#[derive(Debug, Clone)]
+pub struct TradeReporter {
+    regime: Regime,
+    reporting_lei: String,
+    reporting_timestamp: DateTime<Utc>,
+    action: ActionType,
+    effective_date: Option<NaiveDate>,
+    counterparty_leis: HashMap<String, String>,
+}
+
+impl TradeReporter {
+    /// Create a reporter for new-trade reports.
+    pub fn new(
+        regime: Regime,
+        reporting_lei: impl Into<String>,
+        reporting_timestamp: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            regime,
+            reporting_lei: reporting_lei.into(),
+            reporting_timestamp,
+            action: ActionType::New,
+            effective_date: None,
+            counterparty_leis: HashMap::new(),
+        }
+    }
+
+    /// Set the lifecycle event reported.
+    pub fn with_action(mut self, action: ActionType) -> Self {
+        self.action = action;
+        self
+    }
+
+    /// Set the effective date of all trades (default: valuation date).
+    pub fn with_effective_date(mut self, date: NaiveDate) -> Self {
+        self.effective_date = Some(date);
+        self
+    }
+
+    /// Register a counterparty's LEI.
+    pub fn with_counterparty_lei(
+        mut self,
+        counterparty_id: impl Into<String>,
+        lei: impl Into<String>,
+    ) -> Self {
+        self.counterparty_leis
+            .insert(counterparty_id.into(), lei.into());
+        self
+    }
+
+    /// Reporting regime.
+    pub fn regime(&self) -> Regime {
+        self.regime
+    }
+
+    /// Build records for every trade in the portfolio, sorted by trade id.
+    pub fn records(
+        &self,
+        portfolio: &Portfolio,
+        valuations: &ValuationResults,
+    ) -> Vec<TradeReportRecord> {
+        let mut records: Vec<_> = portfolio
+            .trades()
+            .map(|trade| {
+                let netting_set = portfolio.netting_set(trade.netting_set_id());
+                self.record(trade, netting_set, valuations)
+            })
+            .collect();
+        records.sort_by(|a, b| a.trade_id.cmp(&b.trade_id));
+        records
+    }
+
+    /// Build and validate the report.
+    pub fn report(&self, portfolio: &Portfolio, valuations: &ValuationResults) -> TradeReport {
+        let records = self.records(portfolio, valuations);
+        let issues = records
+            .iter()
+            .flat_map(|r| validate_record(r, self.regime, self.reporting_timestamp))
+            .collect();
+        TradeReport {
+            regime: self.regime,
+            reporting_lei: self.reporting_lei.clone(),
+            reporting_timestamp: self.reporting_timestamp,
+            records,
+            issues,
+        }
+    }
+
+    fn record(
+        &self,
+        trade: &Trade,
+        netting_set: Option<&NettingSet>,
+        valuations: &ValuationResults,
+    ) -> TradeReportRecord {
+        let (asset_class, contract_type) = classify(trade);
+        let event_date = valuations.as_of.date_naive();
+        let effective_date = self.effective_date.unwrap_or(event_date);
+        let days = (trade.expiry() * DAYS_PER_YEAR).round() as i64;
+        let collateral = netting_set.and_then(NettingSet::collateral);
+        let currency = trade.currency().code().to_string();
+
+        TradeReportRecord {
+            uti: generate_uti(&self.reporting_lei, trade.id().as_str()),
+            trade_id: trade.id().as_str().to_string(),
+            action: self.action,
+            event_date,
+            reporting_lei: self.reporting_lei.clone(),
+            other_counterparty_lei: self
+                .counterparty_leis
+                .get(trade.counterparty_id().as_str())
+                .cloned(),
+            other_counterparty_id: trade.counterparty_id().as_str().to_string(),
+            asset_class,
+            contract_type,
+            notional: trade.notional(),
+            notional_currency: currency.clone(),
+            effective_date,
+            maturity_date: effective_date + Duration::days(days),
+            cleared: collateral
+                .is_some_and(|csa| csa.mpor() == CollateralAgreement::cleared_mpor()),
+            collateralisation: Collateralisation::of(collateral),
+            netting_set_id: trade.netting_set_id().as_str().to_string(),
+            valuation: valuations.values.get(trade.id()).map(|&amount| Valuation {
+                amount,
+                currency,
+                timestamp: valuations.as_of,
+                delta: valuations.deltas.get(trade.id()).copied(),
+            }),
+        }
+    }
+}
+
+/// Map an instrument to its asset class and contract type.
+///
+/// Options and forwards are on a single spot underlying, reported as
+/// equity.
+fn classify(trade: &Trade) -> (AssetClass, ContractType) {
+    if trade.is_swap() {
+        (AssetClass::InterestRate, ContractType::Swap)
+    } else if trade.is_vanilla() {
+        (AssetClass::Equity, ContractType::Option)
+    } else {
+        (AssetClass::Equity, ContractType::Forward)
+    }
+}
+
+/// Validated trade report for one regime.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TradeReport {
+    /// Reporting regime
+    pub regime: Regime,
+    /// LEI of the reporting counterparty
+    pub reporting_lei: String,
+    /// Report creation timestamp
+    pub reporting_timestamp: DateTime<Utc>,
+    /// All records, including rejected ones
+    pub records: Vec<TradeReportRecord>,
+    /// Field-level validation findings
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl TradeReport {
+    /// Whether no record has an error.
+    pub fn is_valid(&self) -> bool {
+        self.issues.iter().all(|i| !i.is_error())
+    }
+
+    /// Records without errors; only these are rendered.
+    pub fn reportable_records(&self) -> Vec<&TradeReportRecord> {
+        self.records
+            .iter()
+            .filter(|r| !self.issues.iter().any(|i| i.is_error() && i.uti == r.uti))
+            .collect()
+    }
+
+    /// UTIs of records held back because of errors.
+    pub fn rejected_utis(&self) -> Vec<&str> {
+        let mut utis: Vec<&str> = self
+            .issues
+            .iter()
+            .filter(|i| i.is_error())
+            .map(|i| i.uti.as_str())
+            .collect();
+        utis.dedup();
+        utis
+    }
+
+    /// Render reportable records as ISO 20022 `auth.030` XML.
+    pub fn to_xml(&self) -> String {
+        xml::render(self, &self.reportable_records())
+    }
+
+    /// Render reportable records as CSV with the regime's field names.
+    pub fn to_csv(&self) -> String {
+        csv::render(self.regime, &self.reportable_records())
+    }
+
+    /// Submission request for [`super::RegulatorApi`].
+    pub fn to_submission(&self) -> SubmissionRequest {
+        let records = self.reportable_records();
+        SubmissionRequest {
+            report_type: self.regime.report_type(),
+            reporting_date: self.reporting_timestamp.date_naive().to_string(),
+            data: serde_json::json!({
+                "reporting_lei": self.reporting_lei,
+                "record_count": records.len(),
+                "records": records,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,250 @@
+//! Trade reporting tests on a small option and forward portfolio.
+
+use super::*;
+use crate::regulatory::RegulatorApi;
+use crate::regulatory::ReportStatus;
+use chrono::TimeZone;
+use pricer_core::types::Currency;
+use pricer_models::instruments::{
+    Direction, ExerciseStyle, Forward, Instrument, InstrumentParams, PayoffType, VanillaOption,
+};
+use pricer_risk::portfolio::{
+    Counterparty, CounterpartyId, CreditParams, NettingSetId, PortfolioBuilder,
+};
+
+const BANK_LEI: &str = "7LTWFZYICNSX8D621K86";
+const FUND_LEI: &str = "5493001KJTIIGC8Y1R12";
+
+fn valuation_time() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 10, 15, 18, 0, 0).unwrap()
+}
+
+fn reporting_time() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2026, 10, 15, 20, 30, 0).unwrap()
+}
+
+/// Option with a fund under a CSA with initial margin, and a forward
+/// with a corporate, uncollateralised.
+fn portfolio() -> Portfolio {
+    let credit = CreditParams::new(0.02, 0.4).unwrap();
+    let csa = CollateralAgreement::new(0.0, 0.0, 50_000.0, Currency::USD, 10.0 / 250.0).unwrap();
+    let mut fund_ns = NettingSet::with_collateral(
+        NettingSetId::new("NS-FUND"),
+        CounterpartyId::new("CP-FUND"),
+        csa,
+    );
+    fund_ns.add_trade(TradeId::new("EQ-CALL-1Y"));
+    let mut corp_ns = NettingSet::new(NettingSetId::new("NS-CORP"), CounterpartyId::new("CP-CORP"));
+    corp_ns.add_trade(TradeId::new("EQ-FWD-6M"));
+
+    let params = InstrumentParams::new(100.0, 1.0, 1.0).unwrap();
+    let call = VanillaOption::new(params, PayoffType::Call, ExerciseStyle::European, 1e-6);
+    let forward = Forward::new(100.0, 0.5, 1.0, Direction::Long).unwrap();
+
+    PortfolioBuilder::new()
+        .add_counterparty(Counterparty::new(
+            CounterpartyId::new("CP-FUND"),
+            credit.clone(),
+        ))
+        .add_counterparty(Counterparty::new(CounterpartyId::new("CP-CORP"), credit))
+        .add_netting_set(fund_ns)
+        .add_netting_set(corp_ns)
+        .add_trade(Trade::new(
+            TradeId::new("EQ-CALL-1Y"),
+            Instrument::Vanilla(call),
+            Currency::USD,
+            CounterpartyId::new("CP-FUND"),
+            NettingSetId::new("NS-FUND"),
+            1_000_000.0,
+        ))
+        .add_trade(Trade::new(
+            TradeId::new("EQ-FWD-6M"),
+            Instrument::Forward(forward),
+            Currency::EUR,
+            CounterpartyId::new("CP-CORP"),
+            NettingSetId::new("NS-CORP"),
+            250_000.0,
+        ))
+        .build()
+        .unwrap()
+}
+
+fn valuations() -> ValuationResults {
+    ValuationResults::new(valuation_time())
+        .with_value("EQ-CALL-1Y", 104_500.25)
+        .with_delta("EQ-CALL-1Y", 0.6368)
+        .with_value("EQ-FWD-6M", -3_120.5)
+}
+
+fn reporter(regime: Regime) -> TradeReporter {
+    TradeReporter::new(regime, BANK_LEI, reporting_time())
+        .with_counterparty_lei("CP-FUND", FUND_LEI)
+        .with_counterparty_lei("CP-CORP", lei_from_prefix("CORPLEI00000000001").unwrap())
+}
+
+#[test]
+fn test_records_from_portfolio() {
+    let records = reporter(Regime::EmirRefit).records(&portfolio(), &valuations());
+    assert_eq!(records.len(), 2);
+
+    let call = &records[0];
+    assert_eq!(call.uti, "7LTWFZYICNSX8D621K86EQCALL1Y");
+    assert_eq!(call.other_counterparty_lei.as_deref(), Some(FUND_LEI));
+    assert_eq!(call.asset_class, AssetClass::Equity);
+    assert_eq!(call.contract_type, ContractType::Option);
+    assert_eq!(
+        call.effective_date,
+        NaiveDate::from_ymd_opt(2026, 10, 15).unwrap()
+    );
+    assert_eq!(
+        call.maturity_date,
+        NaiveDate::from_ymd_opt(2027, 10, 15).unwrap()
+    );
+    assert_eq!(call.collateralisation, Collateralisation::Full);
+    assert!(!call.cleared);
+    assert_eq!(call.valuation.as_ref().unwrap().delta, Some(0.6368));
+
+    let forward = &records[1];
+    assert_eq!(forward.contract_type, ContractType::Forward);
+    assert_eq!(forward.notional_currency, "EUR");
+    assert_eq!(
+        forward.collateralisation,
+        Collateralisation::Uncollateralised
+    );
+    assert_eq!(forward.valuation.as_ref().unwrap().amount, -3_120.5);
+}
+
+#[test]
+fn test_valid_report_renders_all_records() {
+    let report = reporter(Regime::EmirRefit).report(&portfolio(), &valuations());
+    assert!(report.is_valid(), "{:?}", report.issues);
+    assert_eq!(report.reportable_records().len(), 2);
+
+    let xml = report.to_xml();
+    assert!(xml.contains("auth.030.001.03"));
+    assert!(xml.contains("<NbRcrds>2</NbRcrds>"));
+    assert!(xml.contains("<CmptntAuthrty>ESMA</CmptntAuthrty>"));
+    assert!(xml.contains("<UnqTxIdr>7LTWFZYICNSX8D621K86EQCALL1Y</UnqTxIdr>"));
+    assert!(xml.contains("<CtrctTp>OPTN</CtrctTp><AsstClss>EQUI</AsstClss>"));
+    assert!(xml.contains("<Amt Ccy=\"EUR\">3120.50</Amt><Sgn>false</Sgn>"));
+    assert!(xml.contains("<Dlta>0.63680</Dlta>"));
+    assert!(xml.contains("<Tp>NEWT</Tp>"));
+
+    let csv = report.to_csv();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].starts_with("UTI,Action type,Event date,"));
+    assert_eq!(
+        lines[1],
+        "7LTWFZYICNSX8D621K86EQCALL1Y,NEWT,2026-10-15,7LTWFZYICNSX8D621K86,\
+         5493001KJTIIGC8Y1R12,EQUI,OPTN,1000000.00,USD,2026-10-15,2027-10-15,N,FLCL,\
+         104500.25,USD,2026-10-15T18:00:00Z,0.63680"
+    );
+}
+
+#[test]
+fn test_cftc_codes() {
+    let report = reporter(Regime::Cftc)
+        .with_action(ActionType::Terminate)
+        .report(&portfolio(), &valuations());
+    let csv = report.to_csv();
+    assert!(csv.starts_with("Unique transaction identifier,"));
+    assert!(csv.contains(",TERM,"));
+    assert!(csv.contains(",EQ,FORW,"));
+    assert!(report
+        .to_xml()
+        .contains("<CmptntAuthrty>CFTC</CmptntAuthrty>"));
+}
+
+#[test]
+fn test_invalid_records_are_held_back() {
+    let report = TradeReporter::new(Regime::EmirRefit, BANK_LEI, reporting_time())
+        .with_counterparty_lei("CP-FUND", "5493001KJTIIGC8Y1R13")
+        .report(&portfolio(), &valuations());
+    assert!(!report.is_valid());
+    assert!(report.reportable_records().is_empty());
+    assert_eq!(report.rejected_utis().len(), 2);
+
+    let fields: Vec<&str> = report.issues.iter().map(|i| i.field.as_str()).collect();
+    assert_eq!(
+        fields,
+        vec!["other_counterparty_lei", "other_counterparty_lei"]
+    );
+    assert!(report.issues[0].message.contains("check digits"));
+    assert!(report.issues[1].message.contains("CP-CORP"));
+    assert!(!report.to_xml().contains("<Rpt>"));
+}
+
+#[test]
+fn test_field_validation() {
+    let reporter = reporter(Regime::EmirRefit);
+    let mut record = reporter.records(&portfolio(), &valuations()).remove(0);
+    assert!(validate_record(&record, Regime::EmirRefit, reporting_time()).is_empty());
+
+    record.notional = f64::NAN;
+    record.notional_currency = "usd".to_string();
+    record.maturity_date = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+    let valuation = record.valuation.as_mut().unwrap();
+    valuation.delta = None;
+    valuation.timestamp = reporting_time() + Duration::hours(1);
+
+    let fields: Vec<String> = validate_record(&record, Regime::EmirRefit, reporting_time())
+        .into_iter()
+        .map(|i| i.field)
+        .collect();
+    assert_eq!(
+        fields,
+        vec![
+            "notional",
+            "notional_currency",
+            "maturity_date",
+            "valuation.timestamp",
+            "valuation.delta",
+        ]
+    );
+    // CFTC only requires option deltas on valuation reports
+    assert!(!validate_record(&record, Regime::Cftc, reporting_time())
+        .iter()
+        .any(|i| i.field == "valuation.delta"));
+}
+
+#[test]
+fn test_missing_valuation() {
+    let partial = ValuationResults::new(valuation_time()).with_value("EQ-FWD-6M", 10.0);
+    let report = reporter(Regime::EmirRefit).report(&portfolio(), &partial);
+    assert!(report.is_valid());
+    assert_eq!(report.issues[0].severity, IssueSeverity::Warning);
+
+    let report = reporter(Regime::EmirRefit)
+        .with_action(ActionType::Valuation)
+        .report(&portfolio(), &partial);
+    assert_eq!(report.rejected_utis(), vec!["7LTWFZYICNSX8D621K86EQCALL1Y"]);
+    assert_eq!(report.reportable_records().len(), 1);
+}
+
+#[test]
+fn test_latest_valuation_run() {
+    let earlier = ValuationResults::new(valuation_time() - Duration::days(1));
+    let latest = valuations();
+    let runs = [latest.clone(), earlier];
+    assert_eq!(ValuationResults::latest(&runs), Some(&latest));
+}
+
+#[test]
+fn test_submission_to_regulator() {
+    let report = reporter(Regime::EmirRefit).report(&portfolio(), &valuations());
+    let request = report.to_submission();
+    assert_eq!(request.report_type, ReportType::EmirTrade);
+    assert_eq!(request.reporting_date, "2026-10-15");
+    assert_eq!(request.data["record_count"], 2);
+
+    let api = RegulatorApi::new();
+    assert_eq!(api.submit(request).status, ReportStatus::Acknowledged);
+
+    let empty = SubmissionRequest {
+        report_type: ReportType::CftcSwap,
+        reporting_date: "2026-10-15".to_string(),
+        data: serde_json::json!({}),
+    };
+    assert_eq!(api.submit(empty).status, ReportStatus::Rejected);
+}
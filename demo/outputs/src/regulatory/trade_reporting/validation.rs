@@ -0,0 +1,186 @@
+//! Field-level validation of trade report records.
+
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::identifiers::{validate_lei, validate_uti};
+use super::{ActionType, ContractType, Regime, TradeReportRecord};
+
+/// Largest notional the 25-digit amount fields can hold.
+const MAX_AMOUNT: f64 = 1e20;
+
+/// How serious a validation finding is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IssueSeverity {
+    /// The record would be rejected and is not reported
+    Error,
+    /// The record is reported but should be reviewed
+    Warning,
+}
+
+/// A problem with one field of one record.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidationIssue {
+    /// UTI of the record
+    pub uti: String,
+    /// Record field name
+    pub field: String,
+    /// Severity
+    pub severity: IssueSeverity,
+    /// Description
+    pub message: String,
+}
+
+impl ValidationIssue {
+    /// Whether the issue blocks reporting.
+    pub fn is_error(&self) -> bool {
+        self.severity == IssueSeverity::Error
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:?} {} {}: {}",
+            self.severity, self.uti, self.field, self.message
+        )
+    }
+}
+
+/// Check every field of a record.
+///
+/// Findings are returned in field order. The regimes share their rules
+/// except for option deltas: EMIR Refit requires one with every
+/// valuation of an option, CFTC only on valuation reports.
+pub fn validate_record(
+    record: &TradeReportRecord,
+    regime: Regime,
+    reporting_timestamp: DateTime<Utc>,
+) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    let mut report = |field: &str, severity: IssueSeverity, message: String| {
+        issues.push(ValidationIssue {
+            uti: record.uti.clone(),
+            field: field.to_string(),
+            severity,
+            message,
+        })
+    };
+    let mut error = |field: &str, message: String| report(field, IssueSeverity::Error, message);
+
+    if let Err(e) = validate_uti(&record.uti) {
+        error("uti", e);
+    }
+    if record.event_date > reporting_timestamp.date_naive() {
+        error(
+            "event_date",
+            format!("event date {} is after the report date", record.event_date),
+        );
+    }
+    if let Err(e) = validate_lei(&record.reporting_lei) {
+        error("reporting_lei", e);
+    }
+    match &record.other_counterparty_lei {
+        None => error(
+            "other_counterparty_lei",
+            format!("no LEI for counterparty {}", record.other_counterparty_id),
+        ),
+        Some(lei) if *lei == record.reporting_lei => error(
+            "other_counterparty_lei",
+            "other counterparty is the reporting counterparty".to_string(),
+        ),
+        Some(lei) => {
+            if let Err(e) = validate_lei(lei) {
+                error("other_counterparty_lei", e);
+            }
+        }
+    }
+    if !record.notional.is_finite() || record.notional <= 0.0 {
+        error(
+            "notional",
+            format!("notional must be positive, got {}", record.notional),
+        );
+    } else if record.notional >= MAX_AMOUNT {
+        error("notional", "notional exceeds 20 integer digits".to_string());
+    }
+    if let Err(e) = validate_currency(&record.notional_currency) {
+        error("notional_currency", e);
+    }
+    if record.maturity_date < record.effective_date {
+        error(
+            "maturity_date",
+            format!(
+                "maturity date {} is before effective date {}",
+                record.maturity_date, record.effective_date
+            ),
+        );
+    } else if record.action != ActionType::Terminate && record.maturity_date < record.event_date {
+        error(
+            "maturity_date",
+            format!("trade matured on {}", record.maturity_date),
+        );
+    }
+
+    let delta_required = match regime {
+        Regime::EmirRefit => true,
+        Regime::Cftc => record.action == ActionType::Valuation,
+    };
+    match &record.valuation {
+        Some(valuation) => {
+            if !valuation.amount.is_finite() || valuation.amount.abs() >= MAX_AMOUNT {
+                error(
+                    "valuation.amount",
+                    format!("invalid valuation amount {}", valuation.amount),
+                );
+            }
+            if let Err(e) = validate_currency(&valuation.currency) {
+                error("valuation.currency", e);
+            }
+            if valuation.timestamp > reporting_timestamp {
+                error(
+                    "valuation.timestamp",
+                    format!(
+                        "valuation at {} is after the report timestamp",
+                        valuation.timestamp.to_rfc3339()
+                    ),
+                );
+            }
+            if record.contract_type == ContractType::Option {
+                match valuation.delta {
+                    None if delta_required => {
+                        error("valuation.delta", "options require a delta".to_string())
+                    }
+                    Some(delta) if !(-1.0..=1.0).contains(&delta) => error(
+                        "valuation.delta",
+                        format!("delta {} outside [-1, 1]", delta),
+                    ),
+                    _ => {}
+                }
+            }
+        }
+        None if record.action == ActionType::Valuation => error(
+            "valuation",
+            "valuation reports require a valuation".to_string(),
+        ),
+        None if record.action != ActionType::Terminate => report(
+            "valuation",
+            IssueSeverity::Warning,
+            "no valuation available; report it separately".to_string(),
+        ),
+        None => {}
+    }
+
+    issues
+}
+
+/// Check an ISO 4217 alphabetic currency code.
+fn validate_currency(code: &str) -> Result<(), String> {
+    if code.len() == 3 && code.bytes().all(|b| b.is_ascii_uppercase()) {
+        Ok(())
+    } else {
+        Err(format!("'{}' is not an ISO 4217 currency code", code))
+    }
+}
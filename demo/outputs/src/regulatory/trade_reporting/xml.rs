@@ -0,0 +1,133 @@
+//! ISO 20022 `auth.030` (DerivativesTradeReport) rendering.
+//!
+//! Emits the subset of the message the records carry, with the element
+//! names and nesting of `auth.030.001.03`. Both regimes use the same
+//! message; they differ in the competent authority and the codes.
+
+use std::fmt::Write;
+
+use super::{ActionType, Regime, TradeReport, TradeReportRecord};
+
+const NAMESPACE: &str = "urn:iso:std:iso:20022:tech:xsd:auth.030.001.03";
+
+/// Render records as one `DerivsTradRpt` document.
+pub(super) fn render(report: &TradeReport, records: &[&TradeReportRecord]) -> String {
+    let authority = match report.regime {
+        Regime::EmirRefit => "ESMA",
+        Regime::Cftc => "CFTC",
+    };
+    let timestamp = report
+        .reporting_timestamp
+        .format("%Y-%m-%dT%H:%M:%SZ")
+        .to_string();
+
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(out, "<Document xmlns=\"{}\">", NAMESPACE);
+    out.push_str("  <DerivsTradRpt>\n");
+    out.push_str("    <RptHdr>\n");
+    let _ = writeln!(out, "      <NbRcrds>{}</NbRcrds>", records.len());
+    let _ = writeln!(out, "      <CmptntAuthrty>{}</CmptntAuthrty>", authority);
+    out.push_str("    </RptHdr>\n");
+    out.push_str("    <TradData>\n");
+    for record in records {
+        render_record(&mut out, report.regime, &timestamp, record);
+    }
+    out.push_str("    </TradData>\n");
+    out.push_str("  </DerivsTradRpt>\n");
+    out.push_str("</Document>\n");
+    out
+}
+
+fn render_record(out: &mut String, regime: Regime, timestamp: &str, r: &TradeReportRecord) {
+    let action = match r.action {
+        ActionType::New => "New",
+        ActionType::Modify => "Mod",
+        ActionType::Valuation => "ValtnUpd",
+        ActionType::Terminate => "Termntn",
+    };
+    let _ = writeln!(out, "      <Rpt>\n        <{}>", action);
+    let _ = writeln!(out, "          <RptgTmStmp>{}</RptgTmStmp>", timestamp);
+
+    out.push_str("          <CtrPtySpcfcData>\n            <CtrPty>\n");
+    let _ = writeln!(
+        out,
+        "              <RptgCtrPty><Id><Lgl><Id><LEI>{}</LEI></Id></Lgl></Id></RptgCtrPty>",
+        escape(&r.reporting_lei)
+    );
+    let _ = writeln!(
+        out,
+        "              <OthrCtrPty><IdTp><Lgl><Id><LEI>{}</LEI></Id></Lgl></IdTp></OthrCtrPty>",
+        escape(r.other_counterparty_lei.as_deref().unwrap_or_default())
+    );
+    out.push_str("            </CtrPty>\n");
+    if let Some(v) = &r.valuation {
+        out.push_str("            <Valtn>\n");
+        let _ = writeln!(
+            out,
+            "              <CtrctVal><Amt Ccy=\"{}\">{:.2}</Amt><Sgn>{}</Sgn></CtrctVal>",
+            escape(&v.currency),
+            v.amount.abs(),
+            v.amount >= 0.0
+        );
+        let _ = writeln!(
+            out,
+            "              <TmStmp>{}</TmStmp>",
+            v.timestamp.format("%Y-%m-%dT%H:%M:%SZ")
+        );
+        // Portfolio values come from pricing models
+        out.push_str("              <Tp>MTMO</Tp>\n");
+        if let Some(delta) = v.delta {
+            let _ = writeln!(out, "              <Dlta>{:.5}</Dlta>", delta);
+        }
+        out.push_str("            </Valtn>\n");
+    }
+    let _ = writeln!(
+        out,
+        "            <Coll><Collstn>{}</Collstn></Coll>",
+        r.collateralisation.code()
+    );
+    out.push_str("          </CtrPtySpcfcData>\n");
+
+    out.push_str("          <CmonTradData>\n");
+    let _ = writeln!(
+        out,
+        "            <CtrctData><CtrctTp>{}</CtrctTp><AsstClss>{}</AsstClss></CtrctData>",
+        r.contract_type.code(),
+        r.asset_class.code(regime)
+    );
+    out.push_str("            <TxData>\n");
+    let _ = writeln!(
+        out,
+        "              <TxId><UnqTxIdr>{}</UnqTxIdr></TxId>",
+        escape(&r.uti)
+    );
+    let _ = writeln!(
+        out,
+        "              <NtnlAmt><FrstLeg><Amt><Amt Ccy=\"{}\">{:.2}</Amt></Amt></FrstLeg></NtnlAmt>",
+        escape(&r.notional_currency),
+        r.notional
+    );
+    let _ = writeln!(out, "              <FctvDt>{}</FctvDt>", r.effective_date);
+    let _ = writeln!(out, "              <XprtnDt>{}</XprtnDt>", r.maturity_date);
+    let _ = writeln!(
+        out,
+        "              <DerivEvt><Tp>{}</Tp><TmStmp><Dt>{}</Dt></TmStmp></DerivEvt>",
+        r.action.code(regime),
+        r.event_date
+    );
+    let _ = writeln!(
+        out,
+        "              <TradClr><ClrSts>{}</ClrSts></TradClr>",
+        r.cleared
+    );
+    out.push_str("            </TxData>\n          </CmonTradData>\n");
+    let _ = writeln!(out, "        </{}>\n      </Rpt>", action);
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
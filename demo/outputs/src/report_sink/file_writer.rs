@@ -1,6 +1,7 @@
 //! File writer for report output.
 
 use super::{Report, ReportSink};
+use pricer_risk::reporting::RenderedReport;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
@@ -78,6 +79,38 @@ impl FileWriter {
         Ok(path)
     }
 
+    /// Write a report rendered by `pricer_risk::reporting`
+    ///
+    /// The rendered file name (report id and valuation date) is kept, so
+    /// re-running a report for the same date replaces the previous file.
+    pub fn write_rendered(&self, report: &RenderedReport) -> Result<PathBuf, String> {
+        let path = report
+            .write_to(&self.output_dir)
+            .map_err(|e| format!("Failed to write file: {}", e))?;
+
+        let written = WrittenFile {
+            path: path.clone(),
+            report_id: report.report_id.clone(),
+            size: report.bytes.len(),
+            written_at: chrono::Utc::now().to_rfc3339(),
+        };
+
+        {
+            let mut files = self.written_files.write().unwrap();
+            files.push(written);
+        }
+
+        info!(
+            path = %path.display(),
+            report_id = %report.report_id,
+            format = %report.format,
+            size = report.bytes.len(),
+            "Rendered report written to file"
+        );
+
+        Ok(path)
+    }
+
     /// Get list of written files
     pub fn get_written_files(&self) -> Vec<WrittenFile> {
         let files = self.written_files.read().unwrap();
@@ -145,4 +178,28 @@ mod tests {
         // Cleanup
         fs::remove_file(path).ok();
     }
+
+    #[test]
+    fn test_write_rendered_report() {
+        use pricer_risk::reporting::{DataTable, ReportData, ReportDefinition};
+
+        let temp_dir = env::temp_dir().join("neutryx_test_rendered");
+        let writer = FileWriter::new(&temp_dir);
+
+        let mut table = DataTable::new("pv", "PV").with_column("trade");
+        table.push_row(vec!["T1".into()]).unwrap();
+        let data = ReportData::new("PV Report", "2026-10-15").with_table(table);
+        let definition = ReportDefinition::new("pv", "PV Report", "{{table pv}}").unwrap();
+        let rendered = definition
+            .render(&data, pricer_risk::reporting::ReportFormat::Pdf)
+            .unwrap();
+
+        let path = writer.write_rendered(&rendered).unwrap();
+        assert_eq!(path.file_name().unwrap(), "pv_2026-10-15.pdf");
+        assert_eq!(fs::read(&path).unwrap(), rendered.bytes);
+        assert_eq!(writer.get_written_files()[0].size, rendered.bytes.len());
+        assert_eq!(ReportFormat::from(rendered.format), ReportFormat::Pdf);
+
+        fs::remove_dir_all(temp_dir).ok();
+    }
 }
@@ -1,7 +1,9 @@
 //! Report output destinations.
 //!
 //! This module provides mock implementations of report
-//! output destinations (files, email, etc.).
+//! output destinations (files, email, etc.). Reports rendered by
+//! `pricer_risk::reporting` (including binary XLSX and PDF output) are
+//! written with [`FileWriter::write_rendered`].
 
 mod email_sender;
 mod file_writer;
@@ -74,3 +76,15 @@ impl ReportFormat {
         }
     }
 }
+
+impl From<pricer_risk::reporting::ReportFormat> for ReportFormat {
+    fn from(format: pricer_risk::reporting::ReportFormat) -> Self {
+        use pricer_risk::reporting::ReportFormat as Rendered;
+        match format {
+            Rendered::Csv => ReportFormat::Csv,
+            Rendered::Json => ReportFormat::Json,
+            Rendered::Xlsx => ReportFormat::Excel,
+            Rendered::Pdf => ReportFormat::Pdf,
+        }
+    }
+}
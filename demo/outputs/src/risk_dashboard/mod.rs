@@ -7,9 +7,10 @@ mod metrics_store;
 mod websocket_sink;
 
 pub use metrics_store::MetricsStore;
-pub use websocket_sink::WebSocketSink;
+pub use websocket_sink::{WebSocketMessage, WebSocketSink};
 
 use chrono::{DateTime, Utc};
+use pricer_risk::limits::LimitEvent;
 use serde::{Deserialize, Serialize};
 
 /// Risk metric types
@@ -95,3 +96,36 @@ pub struct CounterpartyRisk {
     /// PFE (95%)
     pub pfe_95: f64,
 }
+
+/// Limit warning or breach alert
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LimitAlert {
+    /// Counterparty ID
+    pub counterparty_id: String,
+    /// Limit type (e.g. "PFE 5Y", "Gross notional", "Settlement")
+    pub limit_type: String,
+    /// "warning" or "breach"
+    pub status: String,
+    /// Limit amount
+    pub amount: f64,
+    /// Measured usage
+    pub usage: f64,
+    /// Usage as a fraction of the limit
+    pub utilisation: f64,
+    /// Timestamp
+    pub timestamp: DateTime<Utc>,
+}
+
+impl From<&LimitEvent> for LimitAlert {
+    fn from(event: &LimitEvent) -> Self {
+        Self {
+            counterparty_id: event.counterparty_id.to_string(),
+            limit_type: event.limit_type.to_string(),
+            status: event.status.to_string(),
+            amount: event.amount,
+            usage: event.usage,
+            utilisation: event.utilisation,
+            timestamp: Utc::now(),
+        }
+    }
+}
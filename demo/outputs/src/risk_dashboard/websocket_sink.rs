@@ -1,6 +1,7 @@
 //! WebSocket sink for real-time risk updates.
 
-use super::MetricUpdate;
+use super::{LimitAlert, MetricUpdate};
+use pricer_risk::limits::LimitReport;
 use serde::{Deserialize, Serialize};
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
@@ -27,6 +28,8 @@ pub enum WebSocketMessage {
     MetricUpdate(MetricUpdate),
     /// Batch of metric updates
     MetricBatch(Vec<MetricUpdate>),
+    /// Limit warning or breach
+    LimitAlert(LimitAlert),
     /// Heartbeat
     Heartbeat { timestamp: i64 },
     /// Error message
@@ -69,6 +72,14 @@ impl WebSocketSink {
         self.send(WebSocketMessage::MetricBatch(metrics))
     }
 
+    /// Send one alert per limit warning or breach in a limit report
+    pub fn send_limit_alerts(&self, report: &LimitReport) -> Result<(), &'static str> {
+        for event in report.events() {
+            self.send(WebSocketMessage::LimitAlert(LimitAlert::from(&event)))?;
+        }
+        Ok(())
+    }
+
     /// Send a message to all subscribers
     pub fn send(&self, message: WebSocketMessage) -> Result<(), &'static str> {
         if !self.running.load(Ordering::SeqCst) {
@@ -167,4 +178,38 @@ mod tests {
             _ => panic!("Expected MetricUpdate"),
         }
     }
+
+    #[tokio::test]
+    async fn test_limit_alerts() {
+        use pricer_risk::limits::{Limit, LimitChecker, LimitInputs};
+        use pricer_risk::portfolio::{CounterpartyId, PortfolioBuilder};
+
+        let cp = CounterpartyId::new("CP001");
+        let checker = LimitChecker::new()
+            .with_limits([
+                Limit::pfe(cp.clone(), 1.0, 100.0).unwrap(),
+                Limit::settlement(cp.clone(), 100.0).unwrap(),
+            ])
+            .unwrap();
+        let inputs = LimitInputs::new(vec![1.0])
+            .with_pfe(cp.clone(), vec![120.0])
+            .with_settlement(cp, 10.0);
+        let report = checker
+            .check(&PortfolioBuilder::new().build().unwrap(), &inputs)
+            .unwrap();
+
+        let sink = WebSocketSink::new();
+        let mut rx = sink.subscribe();
+        sink.send_limit_alerts(&report).unwrap();
+
+        assert_eq!(sink.message_count(), 1);
+        match rx.recv().await.unwrap() {
+            WebSocketMessage::LimitAlert(alert) => {
+                assert_eq!(alert.counterparty_id, "CP001");
+                assert_eq!(alert.limit_type, "PFE 1Y");
+                assert_eq!(alert.status, "breach");
+            }
+            _ => panic!("Expected LimitAlert"),
+        }
+    }
 }
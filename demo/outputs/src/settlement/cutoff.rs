@@ -0,0 +1,115 @@
+//! Settlement cutoffs and calendars per currency.
+
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use infra_master::{Calendar, CalendarId};
+use serde::{Deserialize, Serialize};
+
+/// Payment system cutoff of a currency
+///
+/// Payments in the currency settle on business days of `calendar`; a
+/// payment instructed after `cutoff` (UTC) settles on the next business
+/// day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SettlementCutoff {
+    /// Settlement holiday calendar
+    pub calendar: CalendarId,
+    /// Latest UTC time a payment can be instructed for same-day value
+    pub cutoff: NaiveTime,
+}
+
+impl SettlementCutoff {
+    /// Create a cutoff at `hour:minute` UTC on `calendar`
+    pub fn new(calendar: CalendarId, hour: u32, minute: u32) -> Self {
+        Self {
+            calendar,
+            cutoff: NaiveTime::from_hms_opt(hour, minute, 0).unwrap_or(NaiveTime::MIN),
+        }
+    }
+
+    /// Standard cutoff of a currency, if known
+    ///
+    /// Times are the winter-time cutoffs of the local RTGS systems
+    /// (Fedwire, TARGET2, CHAPS, BOJ-NET, SIC) converted to UTC.
+    pub fn standard(currency: &str) -> Option<Self> {
+        match currency {
+            "USD" => Some(Self::new(CalendarId::NewYork, 23, 0)),
+            "EUR" => Some(Self::new(CalendarId::Target, 16, 0)),
+            "GBP" => Some(Self::new(CalendarId::London, 16, 0)),
+            "JPY" => Some(Self::new(CalendarId::Tokyo, 6, 0)),
+            "CHF" => Some(Self::new(CalendarId::WeekendOnly, 15, 0)),
+            _ => None,
+        }
+    }
+
+    /// Settlement date of a payment due on `value_date`, instructed at `now`
+    ///
+    /// Value dates on holidays roll to the following business day. A
+    /// payment that can no longer meet its value date, because the date
+    /// is past or today's cutoff has passed, rolls to the first business
+    /// day it can still settle on.
+    pub fn settlement_date(&self, value_date: NaiveDate, now: DateTime<Utc>) -> NaiveDate {
+        let calendar = Calendar::get(self.calendar);
+        let today = now.date_naive();
+        let earliest = if now.time() <= self.cutoff {
+            today
+        } else {
+            today.succ_opt().unwrap_or(today)
+        };
+        calendar.next_business_day(value_date.max(earliest))
+    }
+}
+
+impl Default for SettlementCutoff {
+    /// Weekend-only calendar without an intraday cutoff
+    fn default() -> Self {
+        Self {
+            calendar: CalendarId::WeekendOnly,
+            cutoff: NaiveTime::from_hms_opt(23, 59, 59).unwrap_or(NaiveTime::MIN),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_settlement_date() {
+        let eur = SettlementCutoff::standard("EUR").unwrap();
+        let morning = Utc.with_ymd_and_hms(2026, 12, 24, 9, 0, 0).unwrap();
+        let evening = Utc.with_ymd_and_hms(2026, 12, 24, 17, 0, 0).unwrap();
+
+        // Before the cutoff a payment due today settles today
+        assert_eq!(
+            eur.settlement_date(date(2026, 12, 24), morning),
+            date(2026, 12, 24)
+        );
+        // After it, past Christmas, Boxing Day and the weekend
+        assert_eq!(
+            eur.settlement_date(date(2026, 12, 24), evening),
+            date(2026, 12, 28)
+        );
+        // Overdue payments settle as soon as possible
+        assert_eq!(
+            eur.settlement_date(date(2026, 12, 1), morning),
+            date(2026, 12, 24)
+        );
+        // Future value dates on holidays follow the calendar
+        assert_eq!(
+            eur.settlement_date(date(2027, 1, 1), morning),
+            date(2027, 1, 4)
+        );
+
+        assert!(SettlementCutoff::standard("XXX").is_none());
+        let weekend = SettlementCutoff::default();
+        assert_eq!(
+            weekend.settlement_date(date(2026, 12, 25), evening),
+            date(2026, 12, 25)
+        );
+    }
+}
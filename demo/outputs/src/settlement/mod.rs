@@ -1,12 +1,16 @@
 //! Settlement systems.
 //!
 //! This module provides mock implementations of settlement
-//! and payment processing systems.
+//! and payment processing systems, including a [`NettingEngine`] that
+//! nets projected cashflows into payment instructions under per-currency
+//! [`SettlementCutoff`]s.
 
+mod cutoff;
 mod netting_engine;
 mod swift_receiver;
 
-pub use netting_engine::NettingEngine;
+pub use cutoff::SettlementCutoff;
+pub use netting_engine::{NetPaymentInstruction, NettedPayment, NettingEngine, NettingStatistics};
 pub use swift_receiver::SwiftReceiver;
 
 use serde::{Deserialize, Serialize};
@@ -45,6 +49,8 @@ pub enum PaymentType {
     Fee,
     /// Premium payment
     Premium,
+    /// Net of payments of different types
+    Net,
 }
 
 /// Settlement status
@@ -1,15 +1,35 @@
 //! Netting engine for payment consolidation.
+//!
+//! Besides netting payments within a netting set, the engine turns the
+//! projected cashflows of a portfolio into payment instructions and nets
+//! them into one payment per counterparty pair, currency and settlement
+//! date, after applying the cutoff and holiday calendar of each currency.
 
-use super::PaymentInstruction;
-#[cfg(test)]
-use super::PaymentType;
+use super::{PaymentInstruction, PaymentType, SettlementCutoff};
+use chrono::{DateTime, NaiveDate, Utc};
+use pricer_risk::cashflows::{CashflowKind, CashflowProjection};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// Netting engine for consolidating payments
 pub struct NettingEngine {
     /// Payments by netting set
     payments: HashMap<String, Vec<PaymentInstruction>>,
+    /// Settlement cutoffs by currency code
+    cutoffs: HashMap<String, SettlementCutoff>,
+}
+
+/// Net payment instruction ready for release
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetPaymentInstruction {
+    /// Payment to release, value dated on the settlement date
+    pub payment: PaymentInstruction,
+    /// IDs of the payments netted into this one
+    pub netted_payment_ids: Vec<String>,
+    /// Sum of absolute amounts of the netted payments
+    pub gross_amount: f64,
+    /// Whether any netted payment was rolled past its original value date
+    pub rolled: bool,
 }
 
 /// Netted payment result
@@ -40,9 +60,34 @@ impl NettingEngine {
     pub fn new() -> Self {
         Self {
             payments: HashMap::new(),
+            cutoffs: HashMap::new(),
         }
     }
 
+    /// Use the standard cutoffs of USD, EUR, GBP, JPY and CHF
+    pub fn with_standard_cutoffs(mut self) -> Self {
+        for currency in ["USD", "EUR", "GBP", "JPY", "CHF"] {
+            if let Some(cutoff) = SettlementCutoff::standard(currency) {
+                self.cutoffs.insert(currency.to_string(), cutoff);
+            }
+        }
+        self
+    }
+
+    /// Set the settlement cutoff of a currency
+    ///
+    /// Currencies without a cutoff settle on weekdays with no intraday
+    /// cutoff.
+    pub fn with_cutoff(mut self, currency: &str, cutoff: SettlementCutoff) -> Self {
+        self.cutoffs.insert(currency.to_string(), cutoff);
+        self
+    }
+
+    /// Settlement cutoff applied to a currency
+    pub fn cutoff(&self, currency: &str) -> SettlementCutoff {
+        self.cutoffs.get(currency).copied().unwrap_or_default()
+    }
+
     /// Add a payment to a netting set
     pub fn add_payment(&mut self, netting_set_id: &str, payment: PaymentInstruction) {
         self.payments
@@ -58,6 +103,125 @@ impl NettingEngine {
         }
     }
 
+    /// Add the projected cashflows of a portfolio held by `entity`
+    ///
+    /// Each cashflow becomes a payment between `entity` and the trade
+    /// counterparty, filed under the counterparty ID since payments net
+    /// per counterparty rather than per close-out netting set. Received
+    /// amounts are paid by the counterparty. Returns the number of
+    /// payments added.
+    pub fn add_cashflows(&mut self, entity: &str, projection: &CashflowProjection) -> usize {
+        for cf in &projection.cashflows {
+            let counterparty = cf.counterparty_id.as_str();
+            let (payer, payee) = if cf.amount >= 0.0 {
+                (counterparty, entity)
+            } else {
+                (entity, counterparty)
+            };
+            let value_date = cf.date.to_string();
+            self.add_payment(
+                counterparty,
+                PaymentInstruction {
+                    payment_id: format!("{}-{}-{}", cf.trade_id, cf.kind, value_date),
+                    payer: payer.to_string(),
+                    payee: payee.to_string(),
+                    amount: cf.amount.abs(),
+                    currency: cf.currency.code().to_string(),
+                    value_date,
+                    payment_type: match cf.kind {
+                        CashflowKind::FixedCoupon
+                        | CashflowKind::FloatingCoupon
+                        | CashflowKind::RepoInterest => PaymentType::Interest,
+                        CashflowKind::NotionalExchange
+                        | CashflowKind::OptionExercise
+                        | CashflowKind::RepoPrincipal => PaymentType::Principal,
+                        CashflowKind::LendingFee => PaymentType::Fee,
+                    },
+                    reference: cf.trade_id.to_string(),
+                },
+            );
+        }
+        projection.cashflows.len()
+    }
+
+    /// Net all payments into instructions released at `now`
+    ///
+    /// Every payment is first moved to its settlement date under the
+    /// cutoff of its currency (see [`SettlementCutoff::settlement_date`]).
+    /// Payments are then netted across netting sets by counterparty pair,
+    /// currency and settlement date, and one instruction is emitted per
+    /// group with a non-zero net. Payments with unparseable value dates
+    /// are left out. Instructions are sorted by settlement date, currency,
+    /// payer and payee, and carry the payment type of their payments, or
+    /// [`PaymentType::Net`] when the types differ.
+    pub fn net_instructions(&self, now: DateTime<Utc>) -> Vec<NetPaymentInstruction> {
+        type Key = (NaiveDate, String, String, String);
+        let mut groups: BTreeMap<Key, Vec<(&PaymentInstruction, bool)>> = BTreeMap::new();
+        for payment in self.payments.values().flatten() {
+            let Ok(value_date) = NaiveDate::parse_from_str(&payment.value_date, "%Y-%m-%d") else {
+                continue;
+            };
+            let settle = self
+                .cutoff(&payment.currency)
+                .settlement_date(value_date, now);
+            let (party1, party2) = if payment.payer < payment.payee {
+                (payment.payer.clone(), payment.payee.clone())
+            } else {
+                (payment.payee.clone(), payment.payer.clone())
+            };
+            groups
+                .entry((settle, payment.currency.clone(), party1, party2))
+                .or_default()
+                .push((payment, settle != value_date));
+        }
+
+        let mut instructions = Vec::new();
+        for ((settle, currency, party1, party2), mut group) in groups {
+            group.sort_by(|a, b| a.0.payment_id.cmp(&b.0.payment_id));
+            let net: f64 = group
+                .iter()
+                .map(|(p, _)| {
+                    if p.payer == party1 {
+                        p.amount
+                    } else {
+                        -p.amount
+                    }
+                })
+                .sum();
+            if net.abs() <= 0.01 {
+                continue;
+            }
+            let (payer, payee) = if net > 0.0 {
+                (party1, party2)
+            } else {
+                (party2, party1)
+            };
+            let first_type = group[0].0.payment_type;
+            let payment_type = if group.iter().all(|(p, _)| p.payment_type == first_type) {
+                first_type
+            } else {
+                PaymentType::Net
+            };
+            let value_date = settle.format("%Y-%m-%d").to_string();
+            instructions.push(NetPaymentInstruction {
+                payment: PaymentInstruction {
+                    payment_id: format!("NET{:06}", instructions.len() + 1),
+                    reference: format!("Net of {} payments", group.len()),
+                    payer,
+                    payee,
+                    amount: net.abs(),
+                    currency,
+                    value_date,
+                    payment_type,
+                },
+                netted_payment_ids: group.iter().map(|(p, _)| p.payment_id.clone()).collect(),
+                gross_amount: group.iter().map(|(p, _)| p.amount.abs()).sum(),
+                rolled: group.iter().any(|&(_, rolled)| rolled),
+            });
+        }
+        instructions
+    }
+
     /// Calculate netted payments for a netting set
     pub fn calculate_net(&self, netting_set_id: &str) -> Option<Vec<NettedPayment>> {
         let payments = self.payments.get(netting_set_id)?;
@@ -196,6 +360,12 @@ pub struct NettingStatistics {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::TimeZone;
+    use infra_master::CalendarId;
+    use pricer_core::types::time::Date;
+    use pricer_core::types::Currency;
+    use pricer_risk::cashflows::Cashflow;
+    use pricer_risk::portfolio::{CounterpartyId, TradeId};
 
     #[test]
     fn test_netting_engine() {
@@ -233,4 +403,125 @@ mod tests {
         assert_eq!(nets.len(), 1);
         assert!((nets[0].net_amount - 400.0).abs() < 0.01);
     }
+
+    fn projection() -> CashflowProjection {
+        let as_of = Date::from_ymd(2026, 12, 21).unwrap();
+        let cf = |trade: &str, cp: &str, kind, date: Date, currency, amount| Cashflow {
+            trade_id: TradeId::new(trade),
+            counterparty_id: CounterpartyId::new(cp),
+            kind,
+            time: (date - as_of) as f64 / 365.0,
+            date,
+            currency,
+            amount,
+        };
+        let christmas = Date::from_ymd(2026, 12, 25).unwrap();
+        let boxing_day = Date::from_ymd(2026, 12, 28).unwrap();
+        CashflowProjection {
+            as_of,
+            cashflows: vec![
+                cf(
+                    "IRS-1",
+                    "CP001",
+                    CashflowKind::FixedCoupon,
+                    christmas,
+                    Currency::EUR,
+                    -4_000.0,
+                ),
+                cf(
+                    "IRS-1",
+                    "CP001",
+                    CashflowKind::FloatingCoupon,
+                    christmas,
+                    Currency::EUR,
+                    3_000.0,
+                ),
+                cf(
+                    "FWD-1",
+                    "CP001",
+                    CashflowKind::NotionalExchange,
+                    boxing_day,
+                    Currency::EUR,
+                    500.0,
+                ),
+                cf(
+                    "IRS-2",
+                    "CP002",
+                    CashflowKind::FixedCoupon,
+                    christmas,
+                    Currency::USD,
+                    2_000.0,
+                ),
+                cf(
+                    "IRS-3",
+                    "CP002",
+                    CashflowKind::FloatingCoupon,
+                    christmas,
+                    Currency::USD,
+                    -2_000.0,
+                ),
+            ],
+            unprojected: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_net_instructions_from_cashflows() {
+        let mut engine = NettingEngine::new().with_standard_cutoffs();
+        assert_eq!(engine.add_cashflows("BANK", &projection()), 5);
+
+        let now = Utc.with_ymd_and_hms(2026, 12, 21, 9, 0, 0).unwrap();
+        let instructions = engine.net_instructions(now);
+        // EUR coupons on Christmas roll into the forward payment on the 28th;
+        // the USD coupons offset and produce no instruction
+        assert_eq!(instructions.len(), 1);
+        let net = &instructions[0];
+        assert_eq!(net.payment.payer, "BANK");
+        assert_eq!(net.payment.payee, "CP001");
+        assert!((net.payment.amount - 500.0).abs() < 1e-9);
+        assert_eq!(net.payment.value_date, "2026-12-28");
+        assert_eq!(net.payment.payment_type, PaymentType::Net);
+        assert!((net.gross_amount - 7_500.0).abs() < 1e-9);
+        assert!(net.rolled);
+        assert_eq!(
+            net.netted_payment_ids,
+            vec![
+                "FWD-1-notional_exchange-2026-12-28",
+                "IRS-1-fixed_coupon-2026-12-25",
+                "IRS-1-floating_coupon-2026-12-25",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_net_instructions_after_cutoff() {
+        let mut engine = NettingEngine::new()
+            .with_cutoff("USD", SettlementCutoff::new(CalendarId::NewYork, 22, 0));
+        let payment = |id: &str, payer: &str, payee: &str, amount| PaymentInstruction {
+            payment_id: id.to_string(),
+            payer: payer.to_string(),
+            payee: payee.to_string(),
+            amount,
+            currency: "USD".to_string(),
+            value_date: "2026-07-02".to_string(),
+            payment_type: PaymentType::Interest,
+            reference: id.to_string(),
+        };
+        engine.add_payment("NS001", payment("P1", "A", "B", 1_000.0));
+        engine.add_payment("NS002", payment("P2", "B", "A", 400.0));
+
+        let before = Utc.with_ymd_and_hms(2026, 7, 2, 21, 0, 0).unwrap();
+        let nets = engine.net_instructions(before);
+        assert_eq!(nets.len(), 1);
+        assert_eq!(nets[0].payment.value_date, "2026-07-02");
+        assert_eq!(nets[0].payment.payment_type, PaymentType::Interest);
+        assert!((nets[0].payment.amount - 600.0).abs() < 1e-9);
+        assert!(!nets[0].rolled);
+
+        // Past the cutoff the payments settle on the next business day
+        let after = Utc.with_ymd_and_hms(2026, 7, 2, 23, 0, 0).unwrap();
+        let nets = engine.net_instructions(after);
+        assert_eq!(nets[0].payment.value_date, "2026-07-03");
+        assert!(nets[0].rolled);
+    }
 }
@@ -55,7 +55,7 @@ impl SwiftReceiver {
 
         let message_type = match payment.payment_type {
             PaymentType::Principal | PaymentType::Interest | PaymentType::Premium => "MT103",
-            PaymentType::Collateral => "MT202",
+            PaymentType::Collateral | PaymentType::Net => "MT202",
             PaymentType::Fee => "MT103",
         };
 